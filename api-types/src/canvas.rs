@@ -0,0 +1,74 @@
+//! Domain canvas and cross-domain reference types.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Response for canvas view (combined domain + imported tables)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CanvasResponse {
+    /// Tables owned by this domain
+    pub owned_tables: Vec<Value>,
+    /// Tables imported from other domains (read-only in this domain)
+    pub imported_tables: Vec<ImportedTableInfo>,
+    /// Relationships owned by this domain
+    pub owned_relationships: Vec<Value>,
+    /// Relationships imported from other domains (read-only)
+    pub imported_relationships: Vec<ImportedRelationshipInfo>,
+}
+
+/// Info about an imported table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ImportedTableInfo {
+    /// The table data
+    pub table: Value,
+    /// Domain that owns this table
+    pub source_domain: String,
+    /// Reference ID in this domain's cross-domain config
+    pub reference_id: String,
+    /// Optional display alias
+    pub display_alias: Option<String>,
+    /// Position override for this domain
+    pub position_override: Option<Value>,
+    /// Notes about why imported
+    pub notes: Option<String>,
+}
+
+/// Info about an imported relationship
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ImportedRelationshipInfo {
+    /// The relationship data
+    pub relationship: Value,
+    /// Domain that owns this relationship
+    pub source_domain: String,
+    /// Reference ID
+    pub reference_id: String,
+}
+
+/// Request body for adding a cross-domain table reference
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct AddCrossDomainTableRequest {
+    pub source_domain: String,
+    pub table_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_alias: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
+
+/// Request body for updating a cross-domain table reference
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct UpdateCrossDomainTableRequest {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_alias: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+}
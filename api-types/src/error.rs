@@ -0,0 +1,117 @@
+//! Typed API error codes.
+
+use serde::{Deserialize, Serialize};
+
+/// Machine-readable error code returned alongside the HTTP status.
+///
+/// New codes may be added in minor releases; match with a wildcard arm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[non_exhaustive]
+pub enum ErrorCode {
+    BadRequest,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    MethodNotAllowed,
+    NotAcceptable,
+    Conflict,
+    PayloadTooLarge,
+    ValidationFailed,
+    RateLimited,
+    Internal,
+    ServiceUnavailable,
+    /// A code this client version does not know about
+    #[serde(other)]
+    Unknown,
+}
+
+impl ErrorCode {
+    /// Map an HTTP status code to its error code.
+    pub fn from_status(status: u16) -> Self {
+        match status {
+            400 => Self::BadRequest,
+            401 => Self::Unauthorized,
+            403 => Self::Forbidden,
+            404 => Self::NotFound,
+            405 => Self::MethodNotAllowed,
+            406 => Self::NotAcceptable,
+            409 => Self::Conflict,
+            413 => Self::PayloadTooLarge,
+            422 => Self::ValidationFailed,
+            429 => Self::RateLimited,
+            503 => Self::ServiceUnavailable,
+            500..=599 => Self::Internal,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// The HTTP status code normally returned with this error code.
+    pub fn status(self) -> u16 {
+        match self {
+            Self::BadRequest => 400,
+            Self::Unauthorized => 401,
+            Self::Forbidden => 403,
+            Self::NotFound => 404,
+            Self::MethodNotAllowed => 405,
+            Self::NotAcceptable => 406,
+            Self::Conflict => 409,
+            Self::PayloadTooLarge => 413,
+            Self::ValidationFailed => 422,
+            Self::RateLimited => 429,
+            Self::ServiceUnavailable => 503,
+            Self::Internal | Self::Unknown => 500,
+        }
+    }
+}
+
+/// JSON error body: `{"error": "...", "status": 404, "code": "NOT_FOUND"}`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct ErrorResponse {
+    pub error: String,
+    pub status: u16,
+    /// Absent in responses from older servers; derive it from `status` then
+    #[serde(default = "unknown_code")]
+    pub code: ErrorCode,
+}
+
+fn unknown_code() -> ErrorCode {
+    ErrorCode::Unknown
+}
+
+impl ErrorResponse {
+    /// Build an error body for a status code.
+    pub fn new(status: u16, error: impl Into<String>) -> Self {
+        Self {
+            error: error.into(),
+            status,
+            code: ErrorCode::from_status(status),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes_round_trip() {
+        let body = ErrorResponse::new(404, "Table not found");
+        let json = serde_json::to_string(&body).unwrap();
+        assert!(json.contains("\"code\":\"NOT_FOUND\""));
+        assert_eq!(serde_json::from_str::<ErrorResponse>(&json).unwrap(), body);
+        assert_eq!(
+            ErrorCode::from_status(ErrorCode::RateLimited.status()),
+            ErrorCode::RateLimited
+        );
+
+        // Unknown codes from newer servers and bodies from older servers still parse
+        let newer: ErrorResponse =
+            serde_json::from_str(r#"{"error":"x","status":418,"code":"TEAPOT"}"#).unwrap();
+        assert_eq!(newer.code, ErrorCode::Unknown);
+        let older: ErrorResponse = serde_json::from_str(r#"{"error":"x","status":400}"#).unwrap();
+        assert_eq!(older.code, ErrorCode::Unknown);
+    }
+}
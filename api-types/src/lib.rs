@@ -0,0 +1,16 @@
+//! Request/response types for the Data Modelling API.
+//!
+//! These DTOs are the wire contract of the HTTP API. They depend only on serde so the
+//! data-modelling-sdk and other clients can share them instead of re-declaring copies.
+//! Enable the `utoipa` feature to derive OpenAPI schemas (used by the server).
+
+pub mod canvas;
+pub mod error;
+pub mod tables;
+
+pub use canvas::{
+    AddCrossDomainTableRequest, CanvasResponse, ImportedRelationshipInfo, ImportedTableInfo,
+    UpdateCrossDomainTableRequest,
+};
+pub use error::{ErrorCode, ErrorResponse};
+pub use tables::CreateTableRequest;
@@ -0,0 +1,39 @@
+//! Table request types.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Request body for creating a table
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa", derive(utoipa::ToSchema))]
+pub struct CreateTableRequest {
+    pub name: String,
+    pub columns: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub database_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub catalog_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_name: Option<String>,
+    #[serde(default)]
+    pub medallion_layers: Vec<String>,
+    /// Single medallion layer (backward compatibility; prefer `medallion_layers`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub medallion_layer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scd_pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_vault_classification: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modeling_level: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Previous or alternative physical names of the table
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub odcl_metadata: HashMap<String, Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<Value>,
+}
@@ -0,0 +1,58 @@
+//! SQL import parsing benchmark.
+//!
+//! Compares parsing a multi-statement DDL dump on a single thread with parsing it on the
+//! default rayon pool:
+//!
+//! ```sh
+//! cargo bench --bench sql_import
+//! ```
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use data_modelling_api::services::sql_parser::SQLParser;
+use std::hint::black_box;
+
+/// A DDL dump with `tables` CREATE TABLE statements, each referencing the previous table.
+fn ddl_dump(tables: usize) -> String {
+    (0..tables)
+        .map(|i| {
+            let parent = i.saturating_sub(1);
+            format!(
+                "CREATE TABLE sales.orders_{i} (\n\
+                 \x20 id BIGINT PRIMARY KEY,\n\
+                 \x20 customer_id BIGINT NOT NULL,\n\
+                 \x20 parent_id BIGINT REFERENCES sales.orders_{parent}(id),\n\
+                 \x20 status VARCHAR(32) DEFAULT 'new',\n\
+                 \x20 amount DECIMAL(12, 2),\n\
+                 \x20 notes TEXT,\n\
+                 \x20 created_at TIMESTAMP NOT NULL,\n\
+                 \x20 updated_at TIMESTAMP\n\
+                 );\n"
+            )
+        })
+        .collect()
+}
+
+fn bench_sql_import(c: &mut Criterion) {
+    let single_thread = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .expect("thread pool");
+    // The parser holds a `dyn Dialect` and is not `Sync`, so each run builds its own
+    let parse = |sql: &str| SQLParser::with_dialect_name("postgres").parse_with_relationships(sql);
+
+    let mut group = c.benchmark_group("sql_import");
+    group.sample_size(10);
+    for tables in [50, 500] {
+        let sql = ddl_dump(tables);
+        group.bench_with_input(BenchmarkId::new("single_thread", tables), &sql, |b, sql| {
+            b.iter(|| single_thread.install(|| parse(black_box(sql))))
+        });
+        group.bench_with_input(BenchmarkId::new("parallel", tables), &sql, |b, sql| {
+            b.iter(|| parse(black_box(sql)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_sql_import);
+criterion_main!(benches);
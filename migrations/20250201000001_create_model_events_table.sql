@@ -0,0 +1,16 @@
+-- Create model_events table: append-only log of model changes for external consumers
+CREATE TABLE IF NOT EXISTS model_events (
+    sequence BIGSERIAL PRIMARY KEY,
+    id UUID NOT NULL UNIQUE,
+    domain_id UUID NOT NULL REFERENCES domains(id) ON DELETE CASCADE,
+    domain_name VARCHAR(255) NOT NULL,
+    event_type VARCHAR(50) NOT NULL, -- table_created, table_updated, relationship_deleted, ...
+    entity_id UUID NOT NULL,
+    actor VARCHAR(255) NOT NULL,
+    before_data JSONB,
+    after_data JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Cursor reads: events of a domain after a sequence number
+CREATE INDEX IF NOT EXISTS idx_model_events_domain_sequence ON model_events(domain_id, sequence);
@@ -0,0 +1,10 @@
+-- Create revoked_refresh_tokens table: rotated and revoked refresh tokens, kept until they expire
+CREATE TABLE IF NOT EXISTS revoked_refresh_tokens (
+    token_id VARCHAR(64) PRIMARY KEY, -- jti claim, or SHA-256 of tokens issued without one
+    session_id VARCHAR(255) NOT NULL,
+    revoked_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+-- Cleanup of expired entries
+CREATE INDEX IF NOT EXISTS idx_revoked_refresh_tokens_expires_at ON revoked_refresh_tokens(expires_at);
@@ -0,0 +1,15 @@
+-- Create api_keys table: keys of machine-to-machine clients, acting as the user who created them
+CREATE TABLE IF NOT EXISTS api_keys (
+    id UUID PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    scope VARCHAR(20) NOT NULL, -- read_only, import_only, full
+    owner_id UUID NOT NULL,
+    owner_email VARCHAR(255) NOT NULL,
+    key_prefix VARCHAR(20) NOT NULL,
+    key_hash VARCHAR(64) NOT NULL UNIQUE, -- SHA-256 of the key
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ
+);
+
+-- Listing a user's keys
+CREATE INDEX IF NOT EXISTS idx_api_keys_owner_email ON api_keys(owner_email);
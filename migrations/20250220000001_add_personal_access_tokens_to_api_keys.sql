@@ -0,0 +1,4 @@
+-- Personal access tokens share the api_keys table: a kind column tells them apart, and the
+-- scope column holds a comma-separated list of scopes (read_only, import_only, export_only, full)
+ALTER TABLE api_keys ADD COLUMN IF NOT EXISTS kind VARCHAR(30) NOT NULL DEFAULT 'api_key';
+ALTER TABLE api_keys ALTER COLUMN scope TYPE VARCHAR(100);
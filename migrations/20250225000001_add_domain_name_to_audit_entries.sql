@@ -0,0 +1,11 @@
+-- Key audit entries by the domain's name so a domain's audit trail can be read by name and
+-- outlives the domain (compliance reviews need the record of a deleted domain)
+ALTER TABLE audit_entries ADD COLUMN IF NOT EXISTS domain_name VARCHAR(255);
+
+ALTER TABLE audit_entries DROP CONSTRAINT IF EXISTS audit_entries_domain_id_fkey;
+ALTER TABLE audit_entries
+    ADD CONSTRAINT audit_entries_domain_id_fkey
+    FOREIGN KEY (domain_id) REFERENCES domains(id) ON DELETE SET NULL;
+
+CREATE INDEX IF NOT EXISTS idx_audit_entries_domain_name
+    ON audit_entries(workspace_id, domain_name, created_at DESC);
@@ -0,0 +1,452 @@
+//! Command-line interface.
+//!
+//! Without a subcommand (or with `serve`) the binary runs the HTTP server; `desktop` runs it
+//! as a standalone single-user app (see `desktop.rs`). The other
+//! subcommands operate directly on a domain directory (`tables/*.yaml`, `relationships.yaml`,
+//! `environments.yaml`, `naming.yaml`, `work-items.yaml`, `relationship-rules.yaml`,
+//! `domain.yaml`, `profiles.yaml`) using the same services as the API, so CI can import, validate, export and
+//! diff models without running a server. `mcp` serves a domain directory to MCP clients over
+//! stdio.
+
+use anyhow::{Context, Result, anyhow, bail};
+use clap::{Parser, Subcommand};
+use serde::de::DeserializeOwned;
+use std::path::{Path, PathBuf};
+
+use crate::models::{DataModel, Table};
+use crate::routes::models::ExportTransforms;
+use crate::services::domain_service::{DOMAIN_METADATA_FILE, DomainMetadata};
+use crate::services::environment_service::EnvironmentConfig;
+use crate::services::export_service::ExportService;
+use crate::services::inheritance_service::InheritanceStrategy;
+use crate::services::mcp_service::{McpScope, McpServer};
+use crate::services::model_diff_service::diff_models;
+use crate::services::model_validation_service::{
+    Severity, validate_model, validate_profiles, validate_relationship_rules,
+};
+use crate::services::naming_service::NamingTemplates;
+use crate::services::relationship_rule_service::RelationshipRules;
+use crate::services::table_profile_service::{PROFILES_FILE, TableProfiles};
+use crate::services::table_readme_service::TableReadmes;
+use crate::services::work_item_service::WorkItemLinks;
+use crate::services::{
+    AvroParser, GitService, JSONSchemaParser, ModelService, ODCSParser, ProtobufParser, SQLParser,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "data-modelling-api",
+    version,
+    about = "Data Modelling API server and CLI"
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Run the HTTP API server (default)
+    Serve,
+    /// Run as a single-user desktop app: local mode, free port, bundled frontend, browser opened
+    Desktop {
+        /// Port to listen on (default: any free port)
+        #[arg(long)]
+        port: Option<u16>,
+        /// Directory for model files (default: LOCAL_DATA_DIR, WORKSPACE_DATA or ~/.data-modelling)
+        #[arg(long)]
+        data_dir: Option<PathBuf>,
+        /// Do not open the browser
+        #[arg(long)]
+        no_browser: bool,
+    },
+    /// Import a schema file into a domain directory
+    Import {
+        /// Domain directory (created if missing)
+        #[arg(long)]
+        dir: PathBuf,
+        /// Input format: sql, avro, json_schema, protobuf, odcs (default: from file extension)
+        #[arg(long)]
+        format: Option<String>,
+        /// SQL dialect (sql format only)
+        #[arg(long, default_value = "generic")]
+        dialect: String,
+        /// Schema file to import
+        file: PathBuf,
+    },
+    /// Export a domain directory to a format
+    Export {
+        #[arg(long)]
+        dir: PathBuf,
+        /// Export format: json_schema, avro, protobuf, sql, sql-queries, prisma, docs, dbt_sources, mermaid, odcl, png
+        #[arg(long)]
+        format: String,
+        /// Output file (default: stdout)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+        /// SQL dialect (sql and prisma formats)
+        #[arg(long)]
+        dialect: Option<String>,
+        /// ODCS variant for the odcl format
+        #[arg(long)]
+        odcl_format: Option<String>,
+        /// Environment profile from environments.yaml to apply
+        #[arg(long)]
+        environment: Option<String>,
+        /// How supertype/subtype hierarchies become tables (default: as modeled)
+        #[arg(long, value_enum)]
+        inheritance: Option<InheritanceStrategy>,
+    },
+    /// Validate a domain directory; exits with status 1 if errors are found
+    Validate {
+        #[arg(long)]
+        dir: PathBuf,
+        /// Print issues as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Rewrite a domain directory in the current on-disk format
+    Migrate {
+        #[arg(long)]
+        dir: PathBuf,
+    },
+    /// Serve a domain directory to MCP clients (LLM agents, IDE assistants) over stdio
+    Mcp {
+        #[arg(long)]
+        dir: PathBuf,
+        /// `read` exposes query tools only; `write` also lets `propose_table` add tables
+        #[arg(long, value_enum, default_value = "read")]
+        scope: McpScope,
+    },
+    /// Show differences between two domain directories
+    Diff {
+        before: PathBuf,
+        after: PathBuf,
+        /// Print the diff as JSON
+        #[arg(long)]
+        json: bool,
+        /// Exit with status 1 if the models differ
+        #[arg(long)]
+        exit_code: bool,
+    },
+}
+
+/// Run an offline subcommand.
+pub async fn run(command: Command) -> Result<()> {
+    match command {
+        Command::Serve | Command::Desktop { .. } => {
+            bail!("serve and desktop are handled by the server entry point")
+        }
+        Command::Import {
+            dir,
+            format,
+            dialect,
+            file,
+        } => import(&dir, format.as_deref(), &dialect, &file).await,
+        Command::Export {
+            dir,
+            format,
+            output,
+            dialect,
+            odcl_format,
+            environment,
+            inheritance,
+        } => export(
+            &dir,
+            &format,
+            output.as_deref(),
+            dialect.as_deref(),
+            odcl_format.as_deref(),
+            environment.as_deref(),
+            inheritance,
+        ),
+        Command::Validate { dir, json } => validate(&dir, json),
+        Command::Migrate { dir } => migrate(&dir),
+        Command::Mcp { dir, scope } => mcp(&dir, scope).await,
+        Command::Diff {
+            before,
+            after,
+            json,
+            exit_code,
+        } => diff(&before, &after, json, exit_code),
+    }
+}
+
+/// Load the model stored in a domain directory.
+fn load_model(dir: &Path) -> Result<(ModelService, DataModel)> {
+    if !dir.is_dir() {
+        bail!("Domain directory not found: {}", dir.display());
+    }
+    let name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "model".to_string());
+    let mut model_service = ModelService::new();
+    let model = model_service.load_or_create_model(name, dir.to_path_buf(), None)?;
+    Ok((model_service, model))
+}
+
+/// Read an optional YAML config file from the domain directory.
+fn load_yaml<T: DeserializeOwned + Default>(path: &Path) -> Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+    let content = std::fs::read_to_string(path)?;
+    serde_yaml::from_str(&content).with_context(|| format!("Invalid {}", path.display()))
+}
+
+async fn import(dir: &Path, format: Option<&str>, dialect: &str, file: &Path) -> Result<()> {
+    let extension = file
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let format = match format {
+        Some(format) => format.to_string(),
+        None => match extension.as_str() {
+            "sql" | "ddl" => "sql",
+            "avsc" | "avdl" => "avro",
+            "json" => "json_schema",
+            "proto" => "protobuf",
+            "yaml" | "yml" => "odcs",
+            _ => bail!("Cannot detect format of {}; pass --format", file.display()),
+        }
+        .to_string(),
+    };
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?
+        .replace('\x00', "");
+
+    let (tables, warnings): (Vec<Table>, Vec<String>) = match format.as_str() {
+        "sql" => {
+            let (tables, requiring_name) = SQLParser::with_dialect_name(dialect).parse(&content)?;
+            let warnings = requiring_name
+                .into_iter()
+                .map(|t| {
+                    format!(
+                        "Table name inferred as '{}' from {}",
+                        t.suggested_name, t.original_expression
+                    )
+                })
+                .collect();
+            (tables, warnings)
+        }
+        "avro" if extension == "avdl" => {
+            let result = AvroParser::new().parse_idl(&content)?;
+            (
+                result.tables,
+                result.errors.into_iter().map(|e| e.message).collect(),
+            )
+        }
+        "avro" => {
+            let (tables, errors) = AvroParser::new().parse(&content)?;
+            (tables, errors.into_iter().map(|e| e.message).collect())
+        }
+        "json_schema" => {
+            let (tables, errors) = JSONSchemaParser::new().parse(&content)?;
+            (tables, errors.into_iter().map(|e| e.message).collect())
+        }
+        "protobuf" => ProtobufParser::new()
+            .parse(&content)
+            .await
+            .map_err(|e| anyhow!("{}", e))?,
+        "odcs" => {
+            let (table, errors) = ODCSParser::new().parse(&content)?;
+            (vec![table], errors.into_iter().map(|e| e.message).collect())
+        }
+        other => bail!("Unsupported import format: {}", other),
+    };
+
+    std::fs::create_dir_all(dir)?;
+    let (mut model_service, _) = load_model(dir)?;
+    let mut imported = 0;
+    for table in tables {
+        let name = table.name.clone();
+        match model_service.import_table(table) {
+            Ok(_) => imported += 1,
+            Err(e) => eprintln!("skipped {}: {}", name, e),
+        }
+    }
+    for warning in &warnings {
+        eprintln!("warning: {}", warning);
+    }
+    println!("Imported {} table(s) into {}", imported, dir.display());
+    Ok(())
+}
+
+fn export(
+    dir: &Path,
+    format: &str,
+    output: Option<&Path>,
+    dialect: Option<&str>,
+    odcl_format: Option<&str>,
+    environment: Option<&str>,
+    inheritance: Option<InheritanceStrategy>,
+) -> Result<()> {
+    let (_, model) = load_model(dir)?;
+    let environments: EnvironmentConfig = load_yaml(&dir.join("environments.yaml"))?;
+    let naming: NamingTemplates = load_yaml(&dir.join("naming.yaml"))?;
+    let work_items: WorkItemLinks = load_yaml(&dir.join("work-items.yaml"))?;
+    let metadata: DomainMetadata = load_yaml(&dir.join(DOMAIN_METADATA_FILE))?;
+    let readmes = TableReadmes::load(dir);
+    let profiles: TableProfiles = load_yaml(&dir.join(PROFILES_FILE))?;
+    let transforms = ExportTransforms {
+        environment: environments
+            .export_profile(environment)
+            .map_err(|e| anyhow!(e))?,
+        naming: Some(naming).filter(|n| !n.is_empty()),
+        work_items: Some(work_items).filter(|w| !w.links.is_empty()),
+        domain: Some(metadata).filter(|m| !m.is_empty()),
+        readmes: Some(readmes).filter(|r| !r.is_empty()),
+        profiles: Some(profiles).filter(|p| !p.is_empty()),
+        inheritance,
+    };
+    if let Some(name) = environment
+        && transforms.environment.is_none()
+    {
+        bail!("Unknown environment profile: {}", name);
+    }
+    let transformed_model = transforms.apply(&model);
+    let model = transformed_model.as_ref().unwrap_or(&model);
+
+    let exported = ExportService::export_format(model, format, None, dialect, odcl_format)
+        .map_err(|e| anyhow!(e))?
+        .ok_or_else(|| anyhow!("Unsupported export format: {}", format))?;
+    match output {
+        Some(path) => {
+            std::fs::write(path, &exported.content)?;
+            eprintln!("Wrote {}", path.display());
+        }
+        None => {
+            use std::io::Write;
+            std::io::stdout().write_all(&exported.content)?;
+        }
+    }
+    Ok(())
+}
+
+fn validate(dir: &Path, json: bool) -> Result<()> {
+    let (_, model) = load_model(dir)?;
+    let rules: RelationshipRules = load_yaml(&dir.join("relationship-rules.yaml"))?;
+    let profiles: TableProfiles = load_yaml(&dir.join(PROFILES_FILE))?;
+    let mut issues = validate_model(&model);
+    issues.extend(validate_relationship_rules(&model, &rules));
+    issues.extend(validate_profiles(&model, &profiles));
+    let errors = issues
+        .iter()
+        .filter(|i| i.severity == Severity::Error)
+        .count();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&issues)?);
+    } else {
+        for issue in &issues {
+            let level = match issue.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            };
+            match &issue.table {
+                Some(table) => println!("{}: {}: {}", level, table, issue.message),
+                None => println!("{}: {}", level, issue.message),
+            }
+        }
+        println!(
+            "{} table(s), {} relationship(s): {} error(s), {} warning(s)",
+            model.tables.len(),
+            model.relationships.len(),
+            errors,
+            issues.len() - errors
+        );
+    }
+    if errors > 0 {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn migrate(dir: &Path) -> Result<()> {
+    // Loading converts a legacy diagram.drawio into canvas-layout.yaml
+    let (_, model) = load_model(dir)?;
+
+    let mut git_service = GitService::new();
+    git_service.set_git_directory_path(dir)?;
+    for table in &model.tables {
+        git_service.save_table_to_yaml(table)?;
+    }
+    git_service.save_relationships_to_yaml(&model.relationships, &model.tables)?;
+    println!(
+        "Migrated {} table(s) and {} relationship(s) in {}",
+        model.tables.len(),
+        model.relationships.len(),
+        dir.display()
+    );
+    Ok(())
+}
+
+/// Run an MCP server on stdin/stdout (newline-delimited JSON-RPC).
+async fn mcp(dir: &Path, scope: McpScope) -> Result<()> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let (model_service, _) = load_model(dir)?;
+    let mut server = McpServer::new(model_service, scope);
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    let mut stdout = tokio::io::stdout();
+    while let Some(line) = lines.next_line().await? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = server.handle_line(&line) {
+            stdout.write_all(response.as_bytes()).await?;
+            stdout.write_all(b"\n").await?;
+            stdout.flush().await?;
+        }
+    }
+    Ok(())
+}
+
+fn diff(before: &Path, after: &Path, json: bool, exit_code: bool) -> Result<()> {
+    let (_, old) = load_model(before)?;
+    let (_, new) = load_model(after)?;
+    let diff = diff_models(&old, &new);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&diff)?);
+    } else if diff.is_empty() {
+        println!("No differences");
+    } else {
+        for table in &diff.added_tables {
+            println!("+ table {}", table);
+        }
+        for table in &diff.removed_tables {
+            println!("- table {}", table);
+        }
+        for table in &diff.changed_tables {
+            println!("~ table {}", table.table);
+            for column in &table.added_columns {
+                println!("    + {}", column);
+            }
+            for column in &table.removed_columns {
+                println!("    - {}", column);
+            }
+            for change in &table.changed_columns {
+                println!(
+                    "    ~ {}: {} ({}: {} -> {})",
+                    change.column,
+                    change.kind.label(),
+                    change.field,
+                    change.before,
+                    change.after
+                );
+            }
+        }
+        for relationship in &diff.added_relationships {
+            println!("+ relationship {}", relationship);
+        }
+        for relationship in &diff.removed_relationships {
+            println!("- relationship {}", relationship);
+        }
+    }
+    if exit_code && !diff.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
@@ -0,0 +1,70 @@
+//! Desktop (embedded) mode.
+//!
+//! `data-modelling-api desktop` runs the server as a standalone app for one modeler: local
+//! mode without OAuth, a free loopback port, the frontend bundled next to the executable
+//! (`<exe dir>/frontend`) and the default browser opened on start.
+//!
+//! Models are stored in `--data-dir`, else `LOCAL_DATA_DIR` / `WORKSPACE_DATA`, else
+//! `~/.data-modelling`.
+
+use std::io;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use crate::services::local_mode::{LocalMode, enable_local_mode};
+
+/// Enable embedded local mode for this process.
+pub fn enable(data_dir: Option<PathBuf>) {
+    let env_dir_set = ["LOCAL_DATA_DIR", "WORKSPACE_DATA"]
+        .iter()
+        .any(|key| std::env::var(key).is_ok_and(|v| !v.trim().is_empty()));
+    let Some(mut mode) = LocalMode::from_lookup(|key| match key {
+        "LOCAL_MODE" => Some("true".to_string()),
+        _ => std::env::var(key).ok(),
+    }) else {
+        return;
+    };
+    if let Some(dir) = data_dir {
+        mode.data_dir = dir;
+    } else if !env_dir_set && let Some(home) = home_dir() {
+        mode.data_dir = home.join(".data-modelling");
+    }
+    mode.embedded = true;
+    enable_local_mode(mode);
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// Frontend bundled next to the executable, unless `FRONTEND_DIR` is set.
+pub fn bundled_frontend_dir() -> Option<PathBuf> {
+    if std::env::var("FRONTEND_DIR").is_ok() {
+        return None;
+    }
+    let dir = std::env::current_exe().ok()?.parent()?.join("frontend");
+    dir.join("index.html").exists().then_some(dir)
+}
+
+/// Open a URL in the default browser.
+pub fn open_browser(url: &str) -> io::Result<()> {
+    #[cfg(target_os = "macos")]
+    let mut command = Command::new("open");
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    };
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let mut command = Command::new("xdg-open");
+
+    command
+        .arg(url)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
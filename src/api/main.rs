@@ -2,23 +2,28 @@ use axum::{
     Router,
     body::Body,
     http::{HeaderValue, StatusCode, Uri, header},
-    response::{Json, Redirect, Response},
-    routing::get,
+    response::{Html, Json, Redirect, Response},
+    routing::{any, get},
 };
+use clap::Parser;
 use serde_json::{Value, json};
 use std::error::Error as StdError;
 use std::net::SocketAddr;
 use std::path::PathBuf;
+use std::sync::Arc;
 use tower::ServiceBuilder;
 use tower_http::{services::ServeDir, trace::TraceLayer};
 use tracing::{error, info, warn};
 
+mod cli;
+mod desktop;
 mod middleware;
 mod models;
 mod openapi;
 mod routes;
 mod services;
 mod storage;
+mod tls;
 
 // DrawIO and Export modules are at crate root - add them to binary's module tree
 #[path = "../drawio/mod.rs"]
@@ -27,6 +32,9 @@ mod drawio;
 #[path = "../export/mod.rs"]
 mod export;
 
+// Request/response DTOs shared with the SDK
+use data_modelling_api_types as api_types;
+
 // create_api_router is used via routes::create_api_router() call
 
 // Panic hook to catch and log panics
@@ -50,6 +58,49 @@ fn setup_panic_hook() {
 
 #[tokio::main(flavor = "multi_thread")]
 async fn main() -> Result<(), Box<dyn StdError + Send + Sync + 'static>> {
+    let cli = cli::Cli::parse();
+    match cli.command {
+        None | Some(cli::Command::Serve) => serve(ServeOptions::default()).await,
+        Some(cli::Command::Desktop {
+            port,
+            data_dir,
+            no_browser,
+        }) => {
+            desktop::enable(data_dir);
+            serve(ServeOptions {
+                port: Some(port.unwrap_or(0)),
+                frontend_dir: desktop::bundled_frontend_dir(),
+                open_browser: !no_browser,
+            })
+            .await
+        }
+        Some(command) => {
+            // Offline commands only log warnings unless RUST_LOG says otherwise
+            tracing_subscriber::fmt()
+                .with_env_filter(
+                    tracing_subscriber::EnvFilter::try_from_default_env()
+                        .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("warn")),
+                )
+                .with_writer(std::io::stderr)
+                .init();
+            cli::run(command).await.map_err(Into::into)
+        }
+    }
+}
+
+/// Overrides of the environment configuration, used by the `desktop` subcommand
+#[derive(Default)]
+struct ServeOptions {
+    /// Port to listen on; 0 picks a free port (default: PORT or 8081)
+    port: Option<u16>,
+    /// Frontend directory (default: FRONTEND_DIR)
+    frontend_dir: Option<PathBuf>,
+    /// Open the browser once the server is listening
+    open_browser: bool,
+}
+
+/// Run the HTTP API server.
+async fn serve(options: ServeOptions) -> Result<(), Box<dyn StdError + Send + Sync + 'static>> {
     // Setup panic hook first
     setup_panic_hook();
 
@@ -93,8 +144,12 @@ async fn main() -> Result<(), Box<dyn StdError + Send + Sync + 'static>> {
     // Determine frontend static files directory
     // In production: frontend-dioxus/dist (built Dioxus app) or frontend-react/dist (React app)
     // In development: can be empty (frontend served separately)
-    let frontend_dir =
-        std::env::var("FRONTEND_DIR").unwrap_or_else(|_| "frontend-dioxus/dist".to_string());
+    let frontend_dir = match options.frontend_dir {
+        Some(dir) => dir.to_string_lossy().to_string(),
+        None => {
+            std::env::var("FRONTEND_DIR").unwrap_or_else(|_| "frontend-dioxus/dist".to_string())
+        }
+    };
     let frontend_path = PathBuf::from(&frontend_dir);
 
     // Determine WASM files directory (relative to frontend dist or separate)
@@ -128,18 +183,34 @@ async fn main() -> Result<(), Box<dyn StdError + Send + Sync + 'static>> {
     // This ensures router becomes Router<()> before middleware is applied
     eprintln!("[8] Building main app router...");
 
+    let local_mode = services::local_mode::local_mode();
+    if let Some(local) = local_mode {
+        info!(
+            "Local mode: OAuth disabled, acting as {} with data in {:?}",
+            local.email, local.data_dir
+        );
+    }
+
+    // Mock mode answers from the OpenAPI spec and never touches storage
+    let mock_mode = middleware::mock_mode::MockModeConfig::from_env();
+
     // Create app state with storage initialization
     // This will use PostgreSQL or file-based storage based on STORAGE_BACKEND env var
-    info!("Initializing storage backend...");
-    let app_state = match routes::create_app_state_with_storage().await {
-        Ok(state) => {
-            info!("✓ Storage backend initialization completed");
-            state
-        }
-        Err(e) => {
-            error!("✗ Storage backend initialization failed: {}", e);
-            warn!("  Falling back to default file-based storage (no database)");
-            routes::create_app_state()
+    let app_state = if mock_mode.enabled {
+        info!("Mock mode: storage backend not initialized");
+        routes::create_app_state()
+    } else {
+        info!("Initializing storage backend...");
+        match routes::create_app_state_with_storage().await {
+            Ok(state) => {
+                info!("✓ Storage backend initialization completed");
+                state
+            }
+            Err(e) => {
+                error!("✗ Storage backend initialization failed: {}", e);
+                warn!("  Falling back to default file-based storage (no database)");
+                routes::create_app_state()
+            }
         }
     };
 
@@ -150,23 +221,80 @@ async fn main() -> Result<(), Box<dyn StdError + Send + Sync + 'static>> {
         tokio::spawn(async move {
             storage::session_store::start_session_cleanup_task(db_clone).await;
         });
+    } else {
+        // Sessions are kept in memory; prune the expired ones
+        tokio::spawn(routes::auth::start_session_prune_task(
+            app_state.session_store.clone(),
+        ));
+    }
+
+    if !mock_mode.enabled {
+        // Run scheduled export bundles in the background
+        tokio::spawn(routes::export_schedules::start_export_scheduler(
+            app_state.clone(),
+        ));
+
+        // Report opt-in usage telemetry in the background
+        tokio::spawn(services::telemetry_service::start_telemetry_reporter(
+            app_state.telemetry.clone(),
+        ));
     }
 
-    // Build the main router: health checks + API routes nested under /api/v1
+    // Versioned API routers: v1 is frozen and answers with deprecation headers, v2 is the
+    // rate-limited public read API. Unversioned /api/... requests are negotiated by Accept.
+    let api_versions = Arc::new(middleware::api_version::ApiVersionConfig::from_env());
+    let api_v1 =
+        routes::create_api_router(app_state.clone()).layer(axum::middleware::from_fn_with_state(
+            api_versions.clone(),
+            middleware::api_version::v1_deprecation_middleware,
+        ));
+    let api_v2 = routes::v2::v2_router(app_state.clone());
+    let versioned_api: Router = Router::new()
+        .nest("/api/v1", api_v1.clone())
+        .nest("/api/v2", api_v2.clone())
+        .with_state(app_state.clone());
+
+    // Build the main router: health checks + API routes nested under /api/v1 and /api/v2
     // Nest the API router (with AppState) first, then add other routes
+    // Redirect targets include the reverse proxy base path (BASE_PATH), if any
+    let openapi_json_url = middleware::base_path::prefixed("/api/v1/openapi.json");
+    let swagger_url = middleware::base_path::prefixed("/api/v1/swagger");
     let mut app = Router::new()
         .route("/health", get(health_check))
         .route("/api/v1/health", get(health_check))
+        .route("/ready", get(readiness_check))
+        .route("/api/v1/ready", get(readiness_check))
         // Redirect root-level openapi.json and swagger to the correct paths
         .route(
             "/openapi.json",
-            get(|| async { Redirect::permanent("/api/v1/openapi.json") }),
+            get(|| async move { Redirect::permanent(&openapi_json_url) }),
         )
         .route(
             "/swagger",
-            get(|| async { Redirect::permanent("/api/v1/swagger") }),
+            get(|| async move { Redirect::permanent(&swagger_url) }),
         )
-        .nest("/api/v1", routes::create_api_router(app_state.clone()));
+        .nest("/api/v1", api_v1)
+        .nest("/api/v2", api_v2)
+        .route(
+            "/api/versions",
+            get(move || routes::api_versions::list_api_versions(api_versions.clone())),
+        )
+        .route(
+            "/api/{*path}",
+            any(move |request| {
+                routes::api_versions::negotiate_api_version(versioned_api.clone(), request)
+            }),
+        );
+
+    // Below a base path, index.html is served with its root-relative asset links prefixed
+    let base_path = middleware::base_path::base_path();
+    let index_html = if base_path.is_empty() {
+        None
+    } else {
+        std::fs::read_to_string(frontend_path.join("index.html"))
+            .ok()
+            .map(|html| middleware::base_path::rewrite_root_links(&html, base_path))
+    };
 
     // Add static file serving for frontend and WASM if directories exist
     if frontend_path.exists() {
@@ -225,11 +353,16 @@ async fn main() -> Result<(), Box<dyn StdError + Send + Sync + 'static>> {
                 .precompressed_br(),
         );
 
+        if let Some(index_html) = index_html.clone() {
+            let index = get(move || async move { Html(index_html) });
+            app = app.route("/", index.clone()).route("/index.html", index);
+        }
+
         // Serve other static files from frontend dist root (favicon, etc.)
         // This fallback should come last to serve index.html for SPA routes
         app = app.fallback_service(
             ServeDir::new(&frontend_path)
-                .append_index_html_on_directories(true)
+                .append_index_html_on_directories(index_html.is_none())
                 .precompressed_gzip()
                 .precompressed_br(),
         );
@@ -243,8 +376,47 @@ async fn main() -> Result<(), Box<dyn StdError + Send + Sync + 'static>> {
         app = app.fallback(serve_spa_fallback);
     }
 
+    let request_audit = app_state.request_audit.clone();
+    let telemetry = app_state.telemetry.clone();
     let app = app.with_state(app_state);
 
+    // Mount everything below BASE_PATH when served behind a reverse proxy path prefix.
+    // /health stays available at the root for container health checks.
+    let app = if base_path.is_empty() {
+        app
+    } else {
+        info!("Serving application under base path: {}", base_path);
+        let mut root = Router::new()
+            .route("/health", get(health_check))
+            .route("/ready", get(readiness_check));
+        // The nested router only sees the base path itself, not its trailing-slash form
+        if let Some(index_html) = index_html {
+            root = root.route(
+                &format!("{}/", base_path),
+                get(move || async move { Html(index_html) }),
+            );
+        }
+        root.nest(base_path, app)
+    };
+
+    // In mock mode, documented API endpoints answer with canned responses
+    let app = if mock_mode.enabled {
+        let responder = mock_mode.responder();
+        if responder.is_empty() {
+            warn!("Mock mode: the API specification has no operations to mock");
+        }
+        info!(
+            "Mock mode: serving canned responses for {} operation(s)",
+            responder.len()
+        );
+        app.layer(axum::middleware::from_fn_with_state(
+            Arc::new(responder),
+            middleware::mock_mode::mock_mode_middleware,
+        ))
+    } else {
+        app
+    };
+
     // Apply middleware layers
     // Note: Rate limiting is configured via environment variables:
     // - RATE_LIMIT_ENABLED: true/false (default: true in production)
@@ -253,22 +425,56 @@ async fn main() -> Result<(), Box<dyn StdError + Send + Sync + 'static>> {
     let app = app.layer(
         ServiceBuilder::new()
             .layer(TraceLayer::new_for_http())
-            .layer(middleware::cors::create_cors_layer_from_env()),
+            .layer(middleware::cors::create_cors_layer_from_env())
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(middleware::security_headers::SecurityHeadersConfig::from_env()),
+                middleware::security_headers::security_headers_middleware,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(middleware::csrf::CsrfConfig::from_env()),
+                middleware::csrf::csrf_middleware,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                request_audit,
+                middleware::request_audit::request_audit_middleware,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                telemetry,
+                middleware::telemetry::telemetry_middleware,
+            ))
+            .layer(axum::middleware::from_fn_with_state(
+                Arc::new(middleware::request_timeout::RequestTimeoutConfig::from_env()),
+                middleware::request_timeout::request_timeout_middleware,
+            )),
     );
+    // Every request acts as the local user, so only answer ones addressed to this machine
+    let app = if local_mode.is_some() {
+        app.layer(axum::middleware::from_fn(
+            services::local_mode::local_host_middleware,
+        ))
+    } else {
+        app
+    };
     eprintln!("[9] App router built with state and middleware");
 
     // Run server on configurable port (default 8081 for API)
-    let port: u16 = std::env::var("PORT")
-        .ok()
-        .and_then(|p| p.parse().ok())
+    let port: u16 = options
+        .port
+        .or_else(|| std::env::var("PORT").ok().and_then(|p| p.parse().ok()))
         .unwrap_or(8081);
     eprintln!("[12] Setting up server address...");
-    let addr = SocketAddr::from(([0, 0, 0, 0], port));
+    // Local mode skips authentication, so it is only reachable from this machine
+    let addr = if local_mode.is_some() {
+        SocketAddr::from(([127, 0, 0, 1], port))
+    } else {
+        SocketAddr::from(([0, 0, 0, 0], port))
+    };
     info!("Server listening on {} (port {})", addr, port);
     info!("Health check available at http://{}/health", addr);
     info!(
-        "API health check available at http://{}/api/v1/health",
-        addr
+        "API health check available at http://{}{}",
+        addr,
+        middleware::base_path::prefixed("/api/v1/health")
     );
     if frontend_path.exists() {
         info!("Frontend available at http://{}", addr);
@@ -290,11 +496,33 @@ async fn main() -> Result<(), Box<dyn StdError + Send + Sync + 'static>> {
         }
     };
 
-    // Setup graceful shutdown
+    // The actual address differs from `addr` when a free port was requested
+    let addr = listener.local_addr().unwrap_or(addr);
+    info!("Server bound to {}", addr);
+    if local_mode.is_some() {
+        services::local_mode::set_local_port(addr.port());
+    }
+    if options.open_browser {
+        let scheme = if tls::TlsSettings::from_env().is_some() {
+            "https"
+        } else {
+            "http"
+        };
+        let url = format!(
+            "{}://{}{}/",
+            scheme,
+            addr,
+            middleware::base_path::base_path()
+        );
+        info!("Opening {} in the browser", url);
+        if let Err(e) = desktop::open_browser(&url) {
+            warn!("Failed to open the browser ({}); open {} manually", e, url);
+        }
+    }
+
     // Handle both SIGINT (Ctrl+C) and SIGTERM (Docker stop)
-    // Note: Graceful shutdown is currently disabled (commented out below)
     #[cfg(unix)]
-    let _shutdown_signal = async {
+    let shutdown_signal = async {
         use tokio::signal::unix::{SignalKind, signal};
         let mut sigterm =
             signal(SignalKind::terminate()).expect("Failed to install SIGTERM handler");
@@ -309,46 +537,79 @@ async fn main() -> Result<(), Box<dyn StdError + Send + Sync + 'static>> {
     };
 
     #[cfg(not(unix))]
-    let _shutdown_signal = async {
+    let shutdown_signal = async {
         tokio::signal::ctrl_c()
             .await
             .expect("Failed to install CTRL+C signal handler");
         info!("Shutdown signal received");
     };
 
-    // Run server with graceful shutdown
-    // In axum 0.8, Router with state implements IntoMakeService automatically
-    // axum::serve accepts Router directly and handles the conversion internally
-    eprintln!("[15] Starting axum server...");
-    info!("Starting server...");
-
     // In axum 0.8, Router with state can be used directly with axum::serve
     // The router automatically implements the required traits
     eprintln!("[10] Starting axum server...");
     info!("Server starting, listening on {}", addr);
     eprintln!("[11] About to call axum::serve...");
-    // Temporarily without graceful shutdown to debug
-    // axum::serve should block indefinitely, so if it returns, something went wrong
-    // axum::serve returns Result<(), std::io::Error>
-    // std::io::Error implements std::error::Error + Send + Sync + 'static
-    // Convert it to our error type
-    match axum::serve(listener, app).await {
-        Ok(()) => {
-            eprintln!("[12] axum::serve returned (unexpected - should block forever)");
+    let tls_config = match tls::TlsSettings::from_env() {
+        Some(settings) => match tls::load_rustls_config(&settings) {
+            Ok(config) => Some(config),
+            Err(e) => {
+                eprintln!("[11] ERROR: Failed to load TLS certificate: {}", e);
+                let err: Box<dyn StdError + Send + Sync + 'static> = Box::new(e);
+                return Err(err);
+            }
+        },
+        None => None,
+    };
+    let server = async {
+        match tls_config {
+            Some(config) => {
+                let std_listener = listener.into_std()?;
+                info!("Serving HTTPS on {}", addr);
+                axum_server::from_tcp_rustls(std_listener, config)
+                    .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+                    .await
+            }
+            None => {
+                axum::serve(
+                    listener,
+                    app.into_make_service_with_connect_info::<SocketAddr>(),
+                )
+                .await
+            }
         }
-        Err(e) => {
-            eprintln!("[12] ERROR: axum::serve returned error: {}", e);
-            // std::io::Error implements all required traits, convert to expected type
-            let err: Box<dyn StdError + Send + Sync + 'static> = Box::new(e);
-            return Err(err);
+    };
+
+    // On a shutdown signal the server keeps serving, reporting not ready, until the model
+    // file writes in flight finish, so a deploy doesn't leave a half-written YAML file. Open
+    // connections (collaboration WebSockets) are not waited for.
+    let shutdown = async {
+        shutdown_signal.await;
+        let timeout = std::time::Duration::from_secs(
+            std::env::var("SHUTDOWN_SAVE_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+        );
+        let saves = services::save_coordinator_service::save_coordinator();
+        info!("Waiting for {} model saves in flight", saves.in_flight());
+        if !saves.drain(timeout).await {
+            warn!(
+                "Shutting down with {} model saves still in flight after {}s",
+                saves.in_flight(),
+                timeout.as_secs()
+            );
         }
+    };
+    let serve_result = tokio::select! {
+        result = server => result,
+        _ = shutdown => Ok(()),
+    };
+    if let Err(e) = serve_result {
+        eprintln!("[12] ERROR: axum::serve returned error: {}", e);
+        // std::io::Error implements all required traits, convert to expected type
+        let err: Box<dyn StdError + Send + Sync + 'static> = Box::new(e);
+        return Err(err);
     }
-    eprintln!("[12] axum::serve returned (unexpected - should block forever)");
-
-    // With graceful shutdown (re-enable after debugging)
-    // axum::serve(listener, app)
-    //     .with_graceful_shutdown(shutdown_signal)
-    //     .await?;
 
     eprintln!("[18] Server shutdown complete");
     info!("Server shutdown complete");
@@ -367,6 +628,24 @@ async fn health_check() -> Json<Value> {
     }))
 }
 
+/// Readiness probe: not ready once shutdown has started, so load balancers stop sending
+/// requests while model saves in flight finish.
+async fn readiness_check() -> (StatusCode, Json<Value>) {
+    let saves = services::save_coordinator_service::save_coordinator();
+    let (status, state) = if saves.is_draining() {
+        (StatusCode::SERVICE_UNAVAILABLE, "shutting_down")
+    } else {
+        (StatusCode::OK, "ready")
+    };
+    (
+        status,
+        Json(json!({
+            "status": state,
+            "saves_in_flight": saves.in_flight()
+        })),
+    )
+}
+
 /// SPA fallback handler - serves index.html for non-API routes
 /// Used when frontend is served separately (e.g., Vite dev server)
 async fn serve_spa_fallback(uri: Uri) -> Result<Response<Body>, StatusCode> {
@@ -414,6 +693,9 @@ async fn serve_spa_fallback(uri: Uri) -> Result<Response<Body>, StatusCode> {
             header::CONTENT_TYPE,
             HeaderValue::from_static("text/html; charset=utf-8"),
         )
-        .body(Body::from(html))
+        .body(Body::from(middleware::base_path::rewrite_root_links(
+            html,
+            middleware::base_path::base_path(),
+        )))
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
 }
@@ -0,0 +1,198 @@
+//! Role-based access control on the workspace routes.
+//!
+//! Every workspace request is checked against the caller's role (see
+//! `access_control_service`): users without a role on the workspace or domain are turned
+//! away, and the role is added to the request's extensions. Routes guarded with
+//! [`writers_only`] refuse viewers anything but safe methods; endpoints that only check or
+//! preview are added after the guard where they are defined, so viewers may call them.
+//! Handlers that write on a GET check the role themselves ([`is_viewer`]).
+//! Requests act in the caller's own workspace unless
+//! they name another with the `X-Workspace` header (the email the workspace is keyed by);
+//! the handlers then resolve the workspace's files and storage through
+//! [`workspace_email`](crate::routes::workspace::workspace_email), which reads the choice
+//! made here.
+
+use axum::Router;
+use axum::extract::{Extension, Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Json, Response};
+use serde_json::json;
+use tracing::warn;
+
+use crate::routes::app_state::AppState;
+use crate::routes::members::access_role;
+use crate::routes::workspace::{get_user_context, home_workspace_email, validate_domain_name};
+use crate::services::domain_invite_service::DomainRole;
+
+/// Header naming the workspace a request acts in
+pub const WORKSPACE_HEADER: &str = "x-workspace";
+
+tokio::task_local! {
+    static ACTIVE_WORKSPACE: String;
+}
+
+/// Workspace the current request acts in, if it picked one other than the caller's own
+pub fn active_workspace() -> Option<String> {
+    ACTIVE_WORKSPACE
+        .try_with(|workspace| workspace.clone())
+        .ok()
+}
+
+/// Run `task` in `workspace` (from [`active_workspace`]), for work spawned by a request that
+/// outlives it.
+pub async fn in_workspace<F: Future>(workspace: Option<String>, task: F) -> F::Output {
+    match workspace {
+        Some(workspace) => ACTIVE_WORKSPACE.scope(workspace, task).await,
+        None => task.await,
+    }
+}
+
+/// Domain a workspace route (relative to `/workspace`) addresses, if any
+fn route_domain(path: &str) -> Option<&str> {
+    match path
+        .trim_matches('/')
+        .split('/')
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["domains", domain, ..] => Some(domain),
+        _ => None,
+    }
+}
+
+fn reject(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+/// Middleware checking workspace requests against the caller's role.
+pub async fn access_control_middleware(
+    State(state): State<AppState>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let requested = request
+        .headers()
+        .get(WORKSPACE_HEADER)
+        .and_then(|h| h.to_str().ok())
+        .map(str::trim)
+        .filter(|workspace| !workspace.is_empty())
+        .map(str::to_string);
+    let user = match get_user_context(&state, request.headers()).await {
+        Ok(user) => user,
+        // Handlers answer unauthenticated requests themselves
+        Err(_) if requested.is_none() => return next.run(request).await,
+        Err(status) => return status.into_response(),
+    };
+    let home = home_workspace_email(&user.email);
+    let workspace = requested.unwrap_or_else(|| home.clone());
+    // Invalid domain names are rejected by the handlers
+    let domain = route_domain(request.uri().path()).filter(|d| validate_domain_name(d).is_ok());
+
+    let role = match access_role(&user.email, &home, &workspace, domain) {
+        Ok(Some(role)) => role,
+        Ok(None) => {
+            warn!("{} has no role in workspace {}", user.email, workspace);
+            return reject(
+                StatusCode::FORBIDDEN,
+                "You have no access to this workspace",
+            );
+        }
+        Err(status) => return status.into_response(),
+    };
+    request.extensions_mut().insert(role);
+
+    if workspace.eq_ignore_ascii_case(&home) {
+        next.run(request).await
+    } else {
+        ACTIVE_WORKSPACE.scope(workspace, next.run(request)).await
+    }
+}
+
+/// Whether the caller of a request is a viewer, from the role [`access_control_middleware`]
+/// added to it
+pub fn is_viewer(role: Option<&Extension<DomainRole>>) -> bool {
+    role.is_some_and(|Extension(role)| *role == DomainRole::Viewer)
+}
+
+/// Middleware refusing requests the caller's role does not allow
+async fn require_writer(request: Request, next: Next) -> Response {
+    if let Some(role) = request.extensions().get::<DomainRole>()
+        && !role.allows(request.method())
+    {
+        return reject(
+            StatusCode::FORBIDDEN,
+            &format!("The {} role cannot make changes", role.as_str()),
+        );
+    }
+    next.run(request).await
+}
+
+/// Guard the routes of `router`: viewers may only call them with safe methods.
+///
+/// Routes added to the returned router are not guarded, so endpoints that only check or
+/// preview a request body are added after this call to let viewers POST to them.
+pub fn writers_only<S: Clone + Send + Sync + 'static>(router: Router<S>) -> Router<S> {
+    router.route_layer(axum::middleware::from_fn(require_writer))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_route_domain_and_active_workspace() {
+        assert_eq!(route_domain("/domains/sales/tables"), Some("sales"));
+        assert_eq!(route_domain("/domains/sales/members/a@b.c"), Some("sales"));
+        assert_eq!(route_domain("/domains/sales"), Some("sales"));
+        assert_eq!(route_domain("/domains"), None);
+        assert_eq!(route_domain("/members"), None);
+
+        assert_eq!(active_workspace(), None);
+        let active = ACTIVE_WORKSPACE
+            .scope("team@example.com".to_string(), async { active_workspace() })
+            .await;
+        assert_eq!(active.as_deref(), Some("team@example.com"));
+
+        // Spawned tasks keep the workspace they are given
+        let spawned = ACTIVE_WORKSPACE
+            .scope("team@example.com".to_string(), async {
+                tokio::spawn(in_workspace(active_workspace(), async {
+                    active_workspace()
+                }))
+                .await
+                .unwrap()
+            })
+            .await;
+        assert_eq!(spawned.as_deref(), Some("team@example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_writers_only_guard() {
+        use axum::routing::post;
+
+        let router = writers_only(Router::new().route("/tables", post(|| async {})))
+            .route("/preview", post(|| async {}))
+            .layer(axum::middleware::from_fn(
+                |mut request: Request, next: Next| async move {
+                    let role = match request.headers().get("role").map(|v| v.as_bytes()) {
+                        Some(b"viewer") => DomainRole::Viewer,
+                        _ => DomainRole::Editor,
+                    };
+                    request.extensions_mut().insert(role);
+                    next.run(request).await
+                },
+            ));
+        let server = axum_test::TestServer::new(router).unwrap();
+
+        let viewer = |path: &str| {
+            server.post(path).add_header(
+                axum::http::HeaderName::from_static("role"),
+                axum::http::HeaderValue::from_static("viewer"),
+            )
+        };
+        viewer("/tables").await.assert_status(StatusCode::FORBIDDEN);
+        viewer("/preview").await.assert_status_ok();
+        server.post("/tables").await.assert_status_ok();
+    }
+}
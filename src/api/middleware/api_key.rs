@@ -0,0 +1,163 @@
+//! API key authentication for machine-to-machine clients.
+//!
+//! Requests carrying an API key or personal access token (`X-API-Key` header, or
+//! `Authorization: Bearer dmk_...` / `dmp_...`) are checked here before any handler runs: the
+//! key must exist and be unexpired, the route must be a workspace route, and one of the key's
+//! scopes must allow the request. Any key may call `GET /auth/me` to see whom it acts as. Handlers then resolve the key's owner through the usual
+//! auth helpers ([`authenticate_api_key`]). Requests without a key pass through untouched.
+
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Json, Response};
+use chrono::Utc;
+use serde_json::json;
+use tracing::warn;
+
+use crate::routes::app_state::AppState;
+use crate::routes::workspace::get_workspace_data_dir;
+use crate::services::api_key_service::{
+    ApiKey, ApiKeyStore, FileApiKeyStore, PostgresApiKeyStore, hash_key, is_api_key,
+};
+use crate::services::jwt_service::JwtService;
+
+/// Header carrying an API key
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// API key the request carries, if any
+pub fn request_api_key(headers: &HeaderMap) -> Option<&str> {
+    if let Some(key) = headers.get(API_KEY_HEADER).and_then(|h| h.to_str().ok()) {
+        return Some(key);
+    }
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .and_then(JwtService::extract_bearer_token)
+        .filter(|token| is_api_key(token))
+}
+
+/// API keys in the storage backend
+pub fn api_key_store(state: &AppState) -> Result<Box<dyn ApiKeyStore>, StatusCode> {
+    if let Some(pool) = state.database() {
+        return Ok(Box::new(PostgresApiKeyStore::new(pool.clone())));
+    }
+    let dir = get_workspace_data_dir().map_err(|e| {
+        warn!("Failed to locate API keys: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(Box::new(FileApiKeyStore::new(&dir)))
+}
+
+/// The API key the request authenticates with: `Ok(None)` without a key, 401 for an unknown
+/// or expired one.
+pub async fn authenticate_api_key(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<Option<ApiKey>, StatusCode> {
+    let Some(secret) = request_api_key(headers) else {
+        return Ok(None);
+    };
+    let key = api_key_store(state)?
+        .find_by_hash(&hash_key(secret))
+        .await
+        .map_err(|e| {
+            warn!("Failed to look up API key: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+        .ok_or_else(|| {
+            warn!("Unknown API key");
+            StatusCode::UNAUTHORIZED
+        })?;
+    if key.is_expired(Utc::now()) {
+        warn!("API key {} has expired", key.id);
+        return Err(StatusCode::UNAUTHORIZED);
+    }
+    Ok(Some(key))
+}
+
+/// Whether API keys authenticate `path` (relative to `/api/v1`): the workspace routes
+fn is_workspace_route(path: &str) -> bool {
+    path == "/workspaces"
+        || path.starts_with("/workspaces/")
+        || path == "/workspace"
+        || path.starts_with("/workspace/")
+}
+
+fn reject(status: StatusCode, message: &str) -> Response {
+    (status, Json(json!({ "error": message }))).into_response()
+}
+
+/// Middleware checking requests that carry an API key against the key's scope.
+pub async fn api_key_middleware(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request_api_key(request.headers()).is_none() {
+        return next.run(request).await;
+    }
+    let key = match authenticate_api_key(&state, request.headers()).await {
+        Ok(Some(key)) => key,
+        Ok(None) => return next.run(request).await,
+        Err(StatusCode::UNAUTHORIZED) => {
+            return reject(StatusCode::UNAUTHORIZED, "Invalid or expired API key");
+        }
+        Err(status) => return status.into_response(),
+    };
+
+    let path = request.uri().path();
+    if path == "/auth/me" && request.method() == axum::http::Method::GET {
+        return next.run(request).await;
+    }
+    if !is_workspace_route(path) {
+        return reject(
+            StatusCode::FORBIDDEN,
+            "API keys only authenticate workspace routes",
+        );
+    }
+    if !key.allows(request.method(), path) {
+        warn!(
+            "API key {} ({}) may not {} {}",
+            key.id,
+            key.scopes_str(),
+            request.method(),
+            path
+        );
+        return reject(
+            StatusCode::FORBIDDEN,
+            &format!(
+                "API key scope {} does not allow this request",
+                key.scopes_str()
+            ),
+        );
+    }
+    next.run(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_request_api_key() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer eyJhbGciOiJIUzI1NiJ9.e30.sig"),
+        );
+        assert_eq!(request_api_key(&headers), None);
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer dmk_abc"),
+        );
+        assert_eq!(request_api_key(&headers), Some("dmk_abc"));
+        headers.insert(API_KEY_HEADER, HeaderValue::from_static("dmk_def"));
+        assert_eq!(request_api_key(&headers), Some("dmk_def"));
+
+        assert!(is_workspace_route("/workspace/domains/sales/tables"));
+        assert!(is_workspace_route("/workspaces"));
+        assert!(!is_workspace_route("/auth/api-keys"));
+        assert!(!is_workspace_route("/workspacex"));
+    }
+}
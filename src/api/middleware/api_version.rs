@@ -0,0 +1,247 @@
+//! API versioning.
+//!
+//! The API is served under versioned prefixes: `/api/v1` is frozen for the shipped SDK and
+//! `/api/v2` carries breaking changes (structured error bodies on every error, cursor
+//! pagination on list endpoints). Requests to the unversioned `/api/...` prefix pick a
+//! version from the `Accept` header (`application/vnd.data-modelling.v2+json`), defaulting
+//! to v1. v1 responses carry `Deprecation`, `Sunset` and `Link: rel="successor-version"`
+//! headers so clients can find out about the migration.
+//!
+//! Configuration (environment variables):
+//! - `API_V1_DEPRECATION_DATE` (RFC 3339): when v1 was deprecated; `Deprecation: true` if unset
+//! - `API_V1_SUNSET_DATE` (RFC 3339): when v1 will be removed; no `Sunset` header if unset
+
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, HeaderValue, header};
+use axum::middleware::Next;
+use axum::response::Response;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::warn;
+use utoipa::ToSchema;
+
+use super::base_path::prefixed;
+
+/// Vendor media type prefix, followed by the version and `+json`
+const MEDIA_TYPE_PREFIX: &str = "application/vnd.data-modelling.";
+
+/// Response header naming the version that served the request
+pub const API_VERSION_HEADER: &str = "api-version";
+
+/// A served API version
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApiVersion {
+    V1,
+    V2,
+}
+
+impl ApiVersion {
+    /// All served versions, oldest first
+    pub const ALL: [ApiVersion; 2] = [ApiVersion::V1, ApiVersion::V2];
+    /// Version used when a request does not ask for one
+    pub const DEFAULT: ApiVersion = ApiVersion::V1;
+    /// Newest version
+    pub const LATEST: ApiVersion = ApiVersion::V2;
+
+    /// Short name, e.g. `v2`
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::V1 => "v1",
+            Self::V2 => "v2",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|v| v.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Path prefix relative to the base path, e.g. `/api/v2`
+    pub fn path(self) -> String {
+        format!("/api/{}", self.name())
+    }
+
+    /// Vendor media type, e.g. `application/vnd.data-modelling.v2+json`
+    pub fn media_type(self) -> String {
+        format!("{}{}+json", MEDIA_TYPE_PREFIX, self.name())
+    }
+
+    /// Version requested by an `Accept` header. `Ok(None)` when it names no version;
+    /// `Err` with the media type when it only names versions this server does not serve.
+    pub fn negotiate(headers: &HeaderMap) -> Result<Option<Self>, String> {
+        let mut unknown = None;
+        let accepted = headers
+            .get_all(header::ACCEPT)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .filter_map(|range| range.split(';').next())
+            .map(str::trim)
+            .filter_map(|media_type| {
+                let version = media_type
+                    .get(..MEDIA_TYPE_PREFIX.len())
+                    .filter(|p| p.eq_ignore_ascii_case(MEDIA_TYPE_PREFIX))
+                    .map(|_| &media_type[MEDIA_TYPE_PREFIX.len()..])?;
+                let version = version.strip_suffix("+json").unwrap_or(version);
+                let found = Self::from_name(version);
+                if found.is_none() {
+                    unknown.get_or_insert_with(|| media_type.to_string());
+                }
+                found
+            })
+            .max();
+        match (accepted, unknown) {
+            (Some(version), _) => Ok(Some(version)),
+            (None, Some(media_type)) => Err(media_type),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+/// Deprecation settings of v1
+#[derive(Debug, Clone, Default)]
+pub struct ApiVersionConfig {
+    pub v1_deprecated_at: Option<DateTime<Utc>>,
+    pub v1_sunset_at: Option<DateTime<Utc>>,
+}
+
+impl ApiVersionConfig {
+    /// Read settings from environment variables.
+    pub fn from_env() -> Self {
+        let date = |key: &str| {
+            let value = std::env::var(key).ok()?;
+            match DateTime::parse_from_rfc3339(value.trim()) {
+                Ok(date) => Some(date.with_timezone(&Utc)),
+                Err(_) => {
+                    warn!("Ignoring invalid {} value (expected RFC 3339)", key);
+                    None
+                }
+            }
+        };
+        Self {
+            v1_deprecated_at: date("API_V1_DEPRECATION_DATE"),
+            v1_sunset_at: date("API_V1_SUNSET_DATE"),
+        }
+    }
+
+    /// Lifecycle of a version, as reported by the discovery endpoint
+    pub fn describe(&self, version: ApiVersion) -> ApiVersionInfo {
+        let (status, deprecated_at, sunset_at) = match version {
+            ApiVersion::V1 => ("deprecated", self.v1_deprecated_at, self.v1_sunset_at),
+            ApiVersion::V2 => ("current", None, None),
+        };
+        ApiVersionInfo {
+            version: version.name().to_string(),
+            status: status.to_string(),
+            path: prefixed(&version.path()),
+            media_type: version.media_type(),
+            deprecated_at,
+            sunset_at,
+        }
+    }
+
+    /// `Deprecation`, `Sunset` and `Link` headers for v1 responses
+    fn deprecation_headers(&self) -> Vec<(&'static str, String)> {
+        let deprecation = match self.v1_deprecated_at {
+            Some(date) => format!("@{}", date.timestamp()),
+            None => "true".to_string(),
+        };
+        let mut headers = vec![
+            ("deprecation", deprecation),
+            (
+                "link",
+                format!(
+                    "<{}>; rel=\"successor-version\"",
+                    prefixed(&ApiVersion::LATEST.path())
+                ),
+            ),
+        ];
+        if let Some(sunset) = self.v1_sunset_at {
+            headers.push((
+                "sunset",
+                sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+            ));
+        }
+        headers
+    }
+}
+
+/// A served API version
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ApiVersionInfo {
+    /// Version name, e.g. `v2`
+    pub version: String,
+    /// `current` or `deprecated`
+    pub status: String,
+    /// Path prefix the version is served under
+    pub path: String,
+    /// Media type selecting the version on the unversioned `/api` prefix
+    pub media_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sunset_at: Option<DateTime<Utc>>,
+}
+
+/// Middleware adding the deprecation headers to v1 responses.
+pub async fn v1_deprecation_middleware(
+    State(config): State<Arc<ApiVersionConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    for (name, value) in config.deprecation_headers() {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.append(name, value);
+        }
+    }
+    headers.insert(API_VERSION_HEADER, HeaderValue::from_static("v1"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn accept(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_accept_header_negotiation() {
+        assert_eq!(ApiVersion::negotiate(&HeaderMap::new()), Ok(None));
+        assert_eq!(ApiVersion::negotiate(&accept("application/json")), Ok(None));
+        assert_eq!(
+            ApiVersion::negotiate(&accept(
+                "application/json;q=0.5, application/vnd.data-modelling.v2+json"
+            )),
+            Ok(Some(ApiVersion::V2))
+        );
+        assert_eq!(
+            ApiVersion::negotiate(&accept(
+                "application/vnd.data-modelling.v1+json; q=0.9, application/vnd.data-modelling.V2+json"
+            )),
+            Ok(Some(ApiVersion::V2))
+        );
+        assert_eq!(
+            ApiVersion::negotiate(&accept("application/vnd.data-modelling.v9+json")),
+            Err("application/vnd.data-modelling.v9+json".to_string())
+        );
+
+        let config = ApiVersionConfig {
+            v1_deprecated_at: None,
+            v1_sunset_at: DateTime::parse_from_rfc3339("2027-06-30T00:00:00Z")
+                .map(|d| d.with_timezone(&Utc))
+                .ok(),
+        };
+        let headers = config.deprecation_headers();
+        assert!(headers.contains(&("deprecation", "true".to_string())));
+        assert!(headers.contains(&("sunset", "Wed, 30 Jun 2027 00:00:00 GMT".to_string())));
+        assert_eq!(config.describe(ApiVersion::V2).status, "current");
+    }
+}
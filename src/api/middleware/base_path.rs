@@ -0,0 +1,81 @@
+//! Reverse proxy base path configuration.
+//!
+//! When the server is mounted below a path prefix (e.g. `https://host/modelling/`), set
+//! `BASE_PATH=/modelling`. The router, static file serving, generated URLs and the default
+//! OAuth callback URL are all prefixed with it. Unset or `/` means the server is at the root.
+
+use std::sync::OnceLock;
+
+/// Normalize a configured base path to either "" or "/segment[/segment...]" without a trailing slash.
+pub fn normalize_base_path(raw: &str) -> String {
+    let segments: Vec<&str> = raw.trim().split('/').filter(|s| !s.is_empty()).collect();
+    if segments.is_empty() {
+        String::new()
+    } else {
+        format!("/{}", segments.join("/"))
+    }
+}
+
+static BASE_PATH: OnceLock<String> = OnceLock::new();
+
+/// The configured base path (from `BASE_PATH`), normalized. Read once at first use.
+pub fn base_path() -> &'static str {
+    BASE_PATH.get_or_init(|| normalize_base_path(&std::env::var("BASE_PATH").unwrap_or_default()))
+}
+
+/// Prefix an absolute server path (e.g. "/api/v1/health") with the base path.
+pub fn prefixed(path: &str) -> String {
+    format!("{}{}", base_path(), path)
+}
+
+/// Prefix the root-relative `href="/..."` and `src="/..."` links in an HTML page with the base
+/// path, so pages built for the root load their assets below it. Protocol-relative `//` links
+/// are left alone.
+pub fn rewrite_root_links(html: &str, base_path: &str) -> String {
+    if base_path.is_empty() {
+        return html.to_string();
+    }
+    let mut rewritten = String::with_capacity(html.len());
+    let mut rest = html;
+    while let Some(pos) = ["href=\"/", "src=\"/"]
+        .iter()
+        .filter_map(|attr| rest.find(attr).map(|i| i + attr.len() - 1))
+        .min()
+    {
+        rewritten.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+        if !rest.starts_with("//") {
+            rewritten.push_str(base_path);
+        }
+        rewritten.push('/');
+        rest = &rest[1..];
+    }
+    rewritten.push_str(rest);
+    rewritten
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_base_path() {
+        assert_eq!(normalize_base_path(""), "");
+        assert_eq!(normalize_base_path("/"), "");
+        assert_eq!(normalize_base_path("modelling/"), "/modelling");
+        assert_eq!(
+            normalize_base_path(" /tools//modelling/ "),
+            "/tools/modelling"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_root_links() {
+        let html = r#"<link href="/assets/app.css"><script src="/assets/app.js"></script><a href="//cdn.example.com/x.js">x</a><img src="logo.png">"#;
+        assert_eq!(
+            rewrite_root_links(html, "/modelling"),
+            r#"<link href="/modelling/assets/app.css"><script src="/modelling/assets/app.js"></script><a href="//cdn.example.com/x.js">x</a><img src="logo.png">"#
+        );
+        assert_eq!(rewrite_root_links(html, ""), html);
+    }
+}
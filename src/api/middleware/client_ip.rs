@@ -0,0 +1,86 @@
+//! Client address of a request.
+//!
+//! The address is the TCP peer, unless `TRUST_PROXY_HEADERS=true` says the server runs behind
+//! a reverse proxy, in which case the first `X-Forwarded-For` entry is used. Without that
+//! setting forwarded headers are ignored, since any client can send them. Used to key
+//! per-client throttling and to build the origin of generated links.
+
+use axum::extract::{ConnectInfo, FromRequestParts};
+use axum::http::{HeaderMap, request::Parts};
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::OnceLock;
+
+static TRUST_PROXY_HEADERS: OnceLock<bool> = OnceLock::new();
+
+/// Whether `X-Forwarded-*` headers come from a trusted reverse proxy (`TRUST_PROXY_HEADERS`).
+pub fn trust_proxy_headers() -> bool {
+    *TRUST_PROXY_HEADERS.get_or_init(|| {
+        std::env::var("TRUST_PROXY_HEADERS")
+            .is_ok_and(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+    })
+}
+
+/// A forwarded header set by the reverse proxy; None unless proxy headers are trusted.
+pub fn forwarded_header<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    if !trust_proxy_headers() {
+        return None;
+    }
+    headers.get(name).and_then(|v| v.to_str().ok())
+}
+
+fn resolve(headers: &HeaderMap, peer: Option<IpAddr>, trust_proxy: bool) -> Option<IpAddr> {
+    if trust_proxy {
+        let forwarded = headers
+            .get("x-forwarded-for")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.split(',').next())
+            .and_then(|ip| ip.trim().parse().ok());
+        if forwarded.is_some() {
+            return forwarded;
+        }
+    }
+    peer
+}
+
+/// Client address of a request; None when the server was not started with connect info.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClientIp(pub Option<IpAddr>);
+
+impl<S: Send + Sync> FromRequestParts<S> for ClientIp {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let peer = parts
+            .extensions
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(addr)| addr.ip());
+        Ok(Self(resolve(&parts.headers, peer, trust_proxy_headers())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_forwarded_for_only_behind_proxy() {
+        let peer: IpAddr = "10.0.0.5".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("203.0.113.7, 10.0.0.1"),
+        );
+
+        assert_eq!(resolve(&headers, Some(peer), false), Some(peer));
+        assert_eq!(
+            resolve(&headers, Some(peer), true),
+            Some("203.0.113.7".parse().unwrap())
+        );
+
+        headers.insert("x-forwarded-for", HeaderValue::from_static("garbage"));
+        assert_eq!(resolve(&headers, Some(peer), true), Some(peer));
+        assert_eq!(resolve(&HeaderMap::new(), None, true), None);
+    }
+}
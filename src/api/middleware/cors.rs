@@ -1,6 +1,8 @@
 //! CORS middleware configuration.
 
+use std::time::Duration;
 use tower_http::cors::CorsLayer;
+use tracing::warn;
 
 /// Create a CORS layer with permissive settings for development.
 ///
@@ -11,73 +13,122 @@ pub fn create_cors_layer() -> CorsLayer {
     CorsLayer::permissive()
 }
 
-/// Create a CORS layer based on environment configuration.
-///
-/// - If `CORS_PERMISSIVE=true` (or `APP_ENV=development`) => permissive.
-/// - Otherwise requires `CORS_ALLOWED_ORIGINS` (comma-separated list).
+/// CORS settings read from the environment.
 ///
-/// Optional:
-/// - `CORS_ALLOW_CREDENTIALS=true`
-#[allow(dead_code)]
-pub fn create_cors_layer_from_env() -> CorsLayer {
-    use axum::http::{HeaderValue, Method, header};
-    use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin};
+/// - `CORS_ALLOWED_ORIGINS`: comma-separated list of allowed origins (required; `*` is rejected)
+/// - `CORS_ALLOWED_METHODS`: comma-separated methods (default: GET, POST, PUT, PATCH, DELETE, OPTIONS)
+/// - `CORS_ALLOWED_HEADERS`: comma-separated headers (default: Authorization, Content-Type, Idempotency-Key)
+/// - `CORS_ALLOW_CREDENTIALS=true`: allow cookies/credentials
+/// - `CORS_MAX_AGE_SECS`: how long browsers may cache preflight responses
+/// - `CORS_PERMISSIVE=true`: allow any origin; dev-only, ignored when `APP_ENV` is production
+#[derive(Debug, Clone, PartialEq)]
+pub struct CorsConfig {
+    pub permissive: bool,
+    pub allowed_origins: Vec<String>,
+    pub allowed_methods: Vec<String>,
+    pub allowed_headers: Vec<String>,
+    pub allow_credentials: bool,
+    pub max_age: Option<Duration>,
+}
+
+impl CorsConfig {
+    /// Read CORS settings from environment variables.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Read CORS settings using the given variable lookup.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let flag = |key: &str| {
+            lookup(key)
+                .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+                .unwrap_or(false)
+        };
+        let list = |key: &str, default: &[&str]| -> Vec<String> {
+            match lookup(key) {
+                Some(value) => value
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+                None => default.iter().map(|s| s.to_string()).collect(),
+            }
+        };
 
-    // Fail-closed by default: treat missing APP_ENV as production.
-    let app_env = std::env::var("APP_ENV").unwrap_or_else(|_| "production".to_string());
-    let cors_permissive = std::env::var("CORS_PERMISSIVE")
-        .ok()
-        .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
-        .unwrap_or(false);
+        // Fail-closed by default: treat missing APP_ENV as production.
+        let app_env = lookup("APP_ENV").unwrap_or_else(|| "production".to_string());
+        let permissive = flag("CORS_PERMISSIVE");
+        if permissive && app_env.eq_ignore_ascii_case("production") {
+            warn!("CORS_PERMISSIVE is ignored in production; set APP_ENV=development to use it");
+        }
 
-    if cors_permissive || app_env.to_lowercase() == "development" {
-        return CorsLayer::permissive();
+        let mut allowed_origins = list("CORS_ALLOWED_ORIGINS", &[]);
+        if allowed_origins.iter().any(|o| o == "*") {
+            warn!("Wildcard CORS origin is not allowed; list origins explicitly");
+            allowed_origins.retain(|o| o != "*");
+        }
+
+        Self {
+            permissive: permissive && !app_env.eq_ignore_ascii_case("production"),
+            allowed_origins,
+            allowed_methods: list(
+                "CORS_ALLOWED_METHODS",
+                &["GET", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"],
+            ),
+            allowed_headers: list(
+                "CORS_ALLOWED_HEADERS",
+                &[
+                    "Authorization",
+                    "Content-Type",
+                    "Idempotency-Key",
+                    "X-Workspace",
+                ],
+            ),
+            allow_credentials: flag("CORS_ALLOW_CREDENTIALS"),
+            max_age: lookup("CORS_MAX_AGE_SECS")
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .map(Duration::from_secs),
+        }
     }
 
-    let allowed_origins = std::env::var("CORS_ALLOWED_ORIGINS").unwrap_or_default();
-    let origins: Vec<HeaderValue> = allowed_origins
-        .split(',')
-        .map(|s| s.trim())
-        .filter(|s| !s.is_empty())
-        .filter_map(|s| s.parse::<HeaderValue>().ok())
-        .collect();
-
-    // If misconfigured, fail closed (no origins).
-    let allow_origin = if origins.is_empty() {
-        AllowOrigin::list(Vec::<HeaderValue>::new())
-    } else {
-        AllowOrigin::list(origins)
-    };
-
-    let allow_credentials = std::env::var("CORS_ALLOW_CREDENTIALS")
-        .ok()
-        .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
-        .unwrap_or(false);
-
-    let mut layer = CorsLayer::new()
-        .allow_origin(allow_origin)
-        .allow_methods(AllowMethods::list([
-            Method::GET,
-            Method::POST,
-            Method::PUT,
-            Method::PATCH,
-            Method::DELETE,
-            Method::OPTIONS,
-        ]))
-        .allow_headers(AllowHeaders::list([
-            header::AUTHORIZATION,
-            header::CONTENT_TYPE,
-        ]));
-
-    if allow_credentials {
-        layer = layer.allow_credentials(true);
+    /// Build the CORS layer for these settings.
+    pub fn into_layer(self) -> CorsLayer {
+        if self.permissive {
+            warn!("CORS is permissive (any origin allowed) - development use only");
+            return CorsLayer::permissive();
+        }
+        if self.allowed_origins.is_empty() {
+            warn!("CORS_ALLOWED_ORIGINS is not set; cross-origin requests will be rejected");
+        }
+
+        let mut layer = create_custom_cors_layer(
+            self.allowed_origins,
+            self.allowed_methods,
+            self.allowed_headers,
+        );
+        if self.allow_credentials {
+            layer = layer.allow_credentials(true);
+        }
+        if let Some(max_age) = self.max_age {
+            layer = layer.max_age(max_age);
+        }
+        layer
     }
+}
 
-    layer
+/// Create a CORS layer based on environment configuration.
+///
+/// See [`CorsConfig`] for the supported variables. Without `CORS_ALLOWED_ORIGINS`
+/// no cross-origin requests are allowed (fail closed).
+#[allow(dead_code)]
+pub fn create_cors_layer_from_env() -> CorsLayer {
+    CorsConfig::from_env().into_layer()
 }
 
 /// Create a CORS layer with custom settings.
 ///
+/// Values that are not valid origins, methods or header names are skipped with a warning.
+///
 /// # Arguments
 ///
 /// * `allowed_origins` - List of allowed origins (e.g., ["http://localhost:3000"])
@@ -103,16 +154,67 @@ pub fn create_custom_cors_layer(
     allowed_methods: Vec<String>,
     allowed_headers: Vec<String>,
 ) -> CorsLayer {
+    use axum::http::{HeaderName, HeaderValue, Method};
     use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin};
 
+    fn parse_all<T: std::str::FromStr>(values: &[String], kind: &str) -> Vec<T> {
+        values
+            .iter()
+            .filter_map(|s| match s.parse::<T>() {
+                Ok(v) => Some(v),
+                Err(_) => {
+                    warn!("Ignoring invalid CORS {}: {}", kind, s);
+                    None
+                }
+            })
+            .collect()
+    }
+
     CorsLayer::new()
-        .allow_origin(AllowOrigin::list(
-            allowed_origins.iter().map(|s| s.parse().unwrap()),
-        ))
-        .allow_methods(AllowMethods::list(
-            allowed_methods.iter().map(|s| s.parse().unwrap()),
-        ))
-        .allow_headers(AllowHeaders::list(
-            allowed_headers.iter().map(|s| s.parse().unwrap()),
-        ))
+        .allow_origin(AllowOrigin::list(parse_all::<HeaderValue>(
+            &allowed_origins,
+            "origin",
+        )))
+        .allow_methods(AllowMethods::list(parse_all::<Method>(
+            &allowed_methods,
+            "method",
+        )))
+        .allow_headers(AllowHeaders::list(parse_all::<HeaderName>(
+            &allowed_headers,
+            "header",
+        )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(vars: &[(&str, &str)]) -> CorsConfig {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        CorsConfig::from_lookup(|key| vars.get(key).cloned())
+    }
+
+    #[test]
+    fn test_permissive_requires_non_production_env() {
+        assert!(!config(&[("CORS_PERMISSIVE", "true")]).permissive);
+        assert!(config(&[("CORS_PERMISSIVE", "true"), ("APP_ENV", "development")]).permissive);
+    }
+
+    #[test]
+    fn test_origins_credentials_and_max_age() {
+        let cfg = config(&[
+            ("CORS_ALLOWED_ORIGINS", "https://app.example.com, *"),
+            ("CORS_ALLOW_CREDENTIALS", "true"),
+            ("CORS_MAX_AGE_SECS", "600"),
+        ]);
+        assert_eq!(cfg.allowed_origins, vec!["https://app.example.com"]);
+        assert!(cfg.allow_credentials);
+        assert_eq!(cfg.max_age, Some(Duration::from_secs(600)));
+        // Building the layer must not panic for a credentialed, explicit origin list
+        let _ = cfg.into_layer();
+    }
 }
@@ -0,0 +1,167 @@
+//! Double-submit CSRF protection for cookie-based auth flows.
+//!
+//! Clients fetch a token from `GET /api/v1/auth/csrf-token`, which also sets it as a cookie,
+//! and echo it in the `X-CSRF-Token` header on state-changing requests. Requests using a
+//! bearer token (`Authorization` header) or carrying no cookies are not subject to the check,
+//! since browsers never attach those credentials automatically.
+//!
+//! Configuration (environment variables):
+//! - `CSRF_PROTECTION_ENABLED` (default false)
+//! - `CSRF_COOKIE_NAME` (default `csrf_token`)
+//! - `CSRF_COOKIE_SECURE` (default true): set the `Secure` attribute on the cookie
+
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, HeaderValue, Method, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Json, Response};
+use serde_json::json;
+use std::sync::Arc;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Header clients use to echo the CSRF token.
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+/// CSRF settings.
+#[derive(Debug, Clone)]
+pub struct CsrfConfig {
+    pub enabled: bool,
+    pub cookie_name: String,
+    pub cookie_secure: bool,
+}
+
+impl CsrfConfig {
+    /// Read settings from environment variables.
+    pub fn from_env() -> Self {
+        let flag = |key: &str, default: bool| {
+            std::env::var(key)
+                .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+                .unwrap_or(default)
+        };
+        Self {
+            enabled: flag("CSRF_PROTECTION_ENABLED", false),
+            cookie_name: std::env::var("CSRF_COOKIE_NAME")
+                .ok()
+                .filter(|n| !n.is_empty())
+                .unwrap_or_else(|| "csrf_token".to_string()),
+            cookie_secure: flag("CSRF_COOKIE_SECURE", true),
+        }
+    }
+}
+
+/// Read a cookie value from the request headers.
+fn cookie_value<'a>(headers: &'a HeaderMap, name: &str) -> Option<&'a str> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// Compare tokens without short-circuiting on the first differing byte.
+fn tokens_match(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+/// Check a request against the double-submit rule.
+pub fn is_request_allowed(config: &CsrfConfig, method: &Method, headers: &HeaderMap) -> bool {
+    if !config.enabled
+        || matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+        || headers.contains_key(header::AUTHORIZATION)
+        || !headers.contains_key(header::COOKIE)
+    {
+        return true;
+    }
+    let cookie = cookie_value(headers, &config.cookie_name);
+    let submitted = headers.get(CSRF_HEADER).and_then(|v| v.to_str().ok());
+    matches!((cookie, submitted), (Some(c), Some(s)) if !c.is_empty() && tokens_match(c, s))
+}
+
+/// Middleware rejecting cookie-authenticated state-changing requests without a valid token.
+pub async fn csrf_middleware(
+    State(config): State<Arc<CsrfConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if is_request_allowed(&config, request.method(), request.headers()) {
+        return next.run(request).await;
+    }
+    warn!(
+        "Rejected {} {} - missing or invalid CSRF token",
+        request.method(),
+        request.uri().path()
+    );
+    (
+        StatusCode::FORBIDDEN,
+        Json(json!({ "error": "Missing or invalid CSRF token" })),
+    )
+        .into_response()
+}
+
+/// GET /auth/csrf-token - Issue a CSRF token and set it as a cookie
+pub async fn issue_csrf_token() -> Result<Response, StatusCode> {
+    let config = CsrfConfig::from_env();
+    let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    let path = match super::base_path::base_path() {
+        "" => "/",
+        p => p,
+    };
+    let mut cookie = format!(
+        "{}={}; Path={}; SameSite=Strict",
+        config.cookie_name, token, path
+    );
+    if config.cookie_secure {
+        cookie.push_str("; Secure");
+    }
+
+    let mut response = Json(json!({ "csrf_token": token, "header": CSRF_HEADER })).into_response();
+    response.headers_mut().insert(
+        header::SET_COOKIE,
+        HeaderValue::from_str(&cookie).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+    );
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_double_submit_rule() {
+        let config = CsrfConfig {
+            enabled: true,
+            cookie_name: "csrf_token".to_string(),
+            cookie_secure: true,
+        };
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::COOKIE,
+            HeaderValue::from_static("session=abc; csrf_token=t0k3n"),
+        );
+        assert!(is_request_allowed(&config, &Method::GET, &headers));
+        assert!(!is_request_allowed(&config, &Method::POST, &headers));
+
+        headers.insert(CSRF_HEADER, HeaderValue::from_static("wrong"));
+        assert!(!is_request_allowed(&config, &Method::POST, &headers));
+
+        headers.insert(CSRF_HEADER, HeaderValue::from_static("t0k3n"));
+        assert!(is_request_allowed(&config, &Method::POST, &headers));
+
+        // Bearer-authenticated requests are not subject to the check
+        let mut bearer = HeaderMap::new();
+        bearer.insert(header::COOKIE, HeaderValue::from_static("csrf_token=x"));
+        bearer.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer abc"),
+        );
+        assert!(is_request_allowed(&config, &Method::DELETE, &bearer));
+    }
+}
@@ -0,0 +1,130 @@
+//! Idempotency-Key middleware.
+//!
+//! Requests carrying an `Idempotency-Key` header are deduplicated through an
+//! [`IdempotencyCache`]: a repeat of a successful request within the dedupe window gets the
+//! original response (marked with `Idempotent-Replayed: true`) instead of running again.
+//! Requests without the header pass through untouched.
+
+use axum::body::{Body, to_bytes};
+use axum::extract::{Request, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use std::sync::Arc;
+use tracing::{info, warn};
+
+use super::request_audit::request_user;
+use crate::services::idempotency_service::{
+    Claim, IdempotencyCache, StoredResponse, body_fingerprint, wait_for_completion,
+};
+
+/// Request header carrying the client's idempotency key
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+/// Response header set on replayed responses
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+/// Largest request body that can be deduplicated (axum's default body limit)
+const MAX_IDEMPOTENT_BODY_BYTES: usize = 2 * 1024 * 1024;
+/// Longest accepted idempotency key
+const MAX_KEY_LENGTH: usize = 255;
+
+fn replay(response: &StoredResponse) -> Response {
+    let mut builder = Response::builder()
+        .status(response.status)
+        .header(IDEMPOTENT_REPLAYED_HEADER, "true");
+    if let Some(content_type) = response
+        .content_type
+        .as_deref()
+        .and_then(|ct| HeaderValue::from_str(ct).ok())
+    {
+        builder = builder.header(header::CONTENT_TYPE, content_type);
+    }
+    builder
+        .body(Body::from(response.body.clone()))
+        .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
+}
+
+/// Middleware deduplicating requests that carry an `Idempotency-Key` header.
+pub async fn idempotency_middleware(
+    State(cache): State<Arc<IdempotencyCache>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(key) = request
+        .headers()
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|k| !k.is_empty())
+        .map(str::to_string)
+    else {
+        return next.run(request).await;
+    };
+    if key.len() > MAX_KEY_LENGTH {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
+
+    let user = request_user(request.headers()).unwrap_or_default();
+    let scoped_key = format!(
+        "{}|{}|{}|{}",
+        user,
+        request.method(),
+        request.uri().path(),
+        key
+    );
+
+    let (parts, raw) = request.into_parts();
+    let bytes = match to_bytes(raw, MAX_IDEMPOTENT_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(_) => return StatusCode::PAYLOAD_TOO_LARGE.into_response(),
+    };
+    let content_type = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok());
+    let fingerprint = body_fingerprint(content_type, &bytes);
+
+    let guard = match cache.claim(&scoped_key, &fingerprint) {
+        Claim::Run(guard) => guard,
+        Claim::Replay(response) => {
+            info!("Replaying response for idempotency key {}", key);
+            return replay(&response);
+        }
+        Claim::InProgress(completion) => {
+            return match wait_for_completion(completion).await {
+                Some(response) => replay(&response),
+                // The original request was abandoned; let the client retry
+                None => StatusCode::CONFLICT.into_response(),
+            };
+        }
+        Claim::Mismatch => {
+            warn!("Idempotency key {} reused with a different body", key);
+            return StatusCode::UNPROCESSABLE_ENTITY.into_response();
+        }
+    };
+
+    let response = next
+        .run(Request::from_parts(parts, Body::from(bytes)))
+        .await;
+    let (parts, body) = response.into_parts();
+    let body = match to_bytes(body, usize::MAX).await {
+        Ok(body) => body,
+        Err(e) => {
+            warn!(
+                "Failed to buffer response for idempotency key {}: {}",
+                key, e
+            );
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+    guard.complete(StoredResponse {
+        status: parts.status.as_u16(),
+        content_type: parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string),
+        body: body.to_vec(),
+    });
+    Response::from_parts(parts, Body::from(body))
+}
@@ -0,0 +1,161 @@
+//! Contract-first mock mode.
+//!
+//! With mock mode on, requests to documented API endpoints are answered with canned responses
+//! generated from the OpenAPI specification (see [`MockResponder`]) and never reach the
+//! handlers or storage. Responses carry `X-Mock-Operation` with the operation id, which names
+//! the fixture file overriding the body. Undocumented endpoints answer 404. The specification
+//! itself (`/api/v1/openapi.json`), the Swagger page and the health checks are served as
+//! usual.
+//!
+//! Mock responses skip authentication, so mock mode is ignored in production.
+//!
+//! Configuration (environment variables):
+//! - `MOCK_MODE` (default false): dev-only, ignored unless `APP_ENV=development`
+//! - `MOCK_FIXTURES_DIR`: directory of `<operation_id>.json` files replacing generated bodies
+
+use axum::body::Body;
+use axum::extract::{Request, State};
+use axum::http::{HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Json, Response};
+use serde_json::json;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::warn;
+use utoipa::OpenApi;
+
+pub use crate::services::mock_response_service::MockResponder;
+use crate::services::mock_response_service::load_fixtures;
+
+/// API root the specification's paths are relative to
+const API_PREFIX: &str = "/api/v1";
+
+/// Endpoints served by their handlers even in mock mode
+const PASSTHROUGH_PATHS: &[&str] = &["/openapi.json", "/swagger", "/health", "/ready"];
+
+/// Mock mode settings.
+#[derive(Debug, Clone, Default)]
+pub struct MockModeConfig {
+    pub enabled: bool,
+    pub fixtures_dir: Option<PathBuf>,
+}
+
+impl MockModeConfig {
+    /// Read settings from environment variables.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Read settings using the given variable lookup.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let requested = lookup("MOCK_MODE")
+            .is_some_and(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes" | "on"));
+        // Fail-closed by default: treat missing APP_ENV as production.
+        let app_env = lookup("APP_ENV").unwrap_or_else(|| "production".to_string());
+        let enabled = requested && app_env == "development";
+        if requested && !enabled {
+            warn!("MOCK_MODE is ignored in production; set APP_ENV=development to use it");
+        }
+        Self {
+            enabled,
+            fixtures_dir: lookup("MOCK_FIXTURES_DIR")
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from),
+        }
+    }
+
+    /// Canned responses of the API specification, with the configured fixtures.
+    pub fn responder(&self) -> MockResponder {
+        let fixtures = self
+            .fixtures_dir
+            .as_deref()
+            .map(load_fixtures)
+            .unwrap_or_default();
+        let spec =
+            serde_json::to_value(super::super::openapi::ApiDoc::openapi()).unwrap_or_default();
+        MockResponder::from_spec(&spec, &fixtures)
+    }
+}
+
+/// Middleware answering API requests with canned responses.
+pub async fn mock_mode_middleware(
+    State(responder): State<Arc<MockResponder>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let path = request.uri().path().to_string();
+    let Some(api_path) = path
+        .strip_prefix(crate::middleware::base_path::base_path())
+        .and_then(|p| p.strip_prefix(API_PREFIX))
+    else {
+        return next.run(request).await;
+    };
+    if PASSTHROUGH_PATHS.contains(&api_path) {
+        return next.run(request).await;
+    }
+
+    let method = request.method().as_str();
+    let (Some(mock), Some(operation_id)) = (
+        responder.respond(method, api_path),
+        responder.operation_id(method, api_path),
+    ) else {
+        return (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "error": format!("No documented endpoint for {} {}", method, api_path)
+            })),
+        )
+            .into_response();
+    };
+
+    let body = match (&mock.body, mock.content_type.as_deref()) {
+        (Some(serde_json::Value::String(text)), Some(content_type))
+            if !content_type.contains("json") =>
+        {
+            Body::from(text.clone())
+        }
+        (Some(value), Some(_)) => Body::from(value.to_string()),
+        _ => Body::empty(),
+    };
+    let mut response = Response::new(body);
+    *response.status_mut() = StatusCode::from_u16(mock.status).unwrap_or(StatusCode::OK);
+    let headers = response.headers_mut();
+    if let Some(content_type) = mock
+        .content_type
+        .as_deref()
+        .and_then(|c| HeaderValue::from_str(c).ok())
+    {
+        headers.insert(header::CONTENT_TYPE, content_type);
+    }
+    if let Ok(operation) = HeaderValue::from_str(operation_id) {
+        headers.insert("x-mock-operation", operation);
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_mock_mode_is_dev_only_and_covers_the_spec() {
+        let config = |vars: &[(&str, &str)]| {
+            let vars: HashMap<String, String> = vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            MockModeConfig::from_lookup(|key| vars.get(key).cloned())
+        };
+        assert!(!config(&[("MOCK_MODE", "true")]).enabled);
+        assert!(!config(&[("MOCK_MODE", "true"), ("APP_ENV", "production")]).enabled);
+        assert!(config(&[("MOCK_MODE", "true"), ("APP_ENV", "development")]).enabled);
+
+        // Every documented operation has a canned response
+        let responder = MockModeConfig::default().responder();
+        assert!(!responder.is_empty());
+        let workspaces = responder.respond("GET", "/workspace/domains").unwrap();
+        assert_eq!(workspaces.status, 200);
+    }
+}
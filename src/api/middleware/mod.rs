@@ -1,12 +1,26 @@
 // Middleware module - contains observability and other middleware
 
+pub mod access_control;
+pub mod api_key;
+pub mod api_version;
+pub mod base_path;
+pub mod client_ip;
 pub mod cors;
+pub mod csrf;
+pub mod idempotency;
+pub mod mock_mode;
 pub mod observability;
 pub mod rate_limit;
+pub mod request_audit;
+pub mod request_timeout;
+pub mod security_headers;
+pub mod telemetry;
 
 // Re-export for convenience
 #[allow(unused_imports)]
-pub use cors::{create_cors_layer, create_cors_layer_from_env, create_custom_cors_layer};
+pub use cors::{
+    CorsConfig, create_cors_layer, create_cors_layer_from_env, create_custom_cors_layer,
+};
 // Rate limit exports are kept for potential future use
 #[allow(unused_imports)]
 pub use rate_limit::{SharedRateLimiter, create_rate_limit_layer, rate_limit_middleware};
@@ -1,24 +1,51 @@
 //! Rate limiting middleware.
 //!
-//! Provides rate limiting for API endpoints using the governor crate.
+//! Provides rate limiting for API endpoints using the governor crate. Used by the public
+//! v2 read API (see `routes::v2`). Each client has its own quota: requests carrying an API
+//! key are counted against the key, others against the client address (see
+//! [`ClientIp`](super::client_ip::ClientIp)).
 
 use axum::extract::{Request, State};
-use axum::http::StatusCode;
+use axum::http::{HeaderValue, StatusCode, header};
 use axum::middleware::Next;
-use axum::response::Response;
+use axum::response::{IntoResponse, Response};
+use governor::clock::{Clock, DefaultClock};
+use governor::state::keyed::DefaultKeyedStateStore;
 use governor::{Quota, RateLimiter};
+use std::net::IpAddr;
 use std::num::NonZeroU32;
 use std::sync::Arc;
 use tower::Layer;
 
+use super::api_key::request_api_key;
+use super::client_ip::ClientIp;
+use crate::routes::error::ApiError;
+use crate::services::api_key_service::hash_key;
+
+/// Clients tracked before idle ones are dropped
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Who a request is counted against
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RateLimitKey {
+    /// Hash of the API key the request carries
+    ApiKey(String),
+    /// Client address; None when it is unknown
+    Client(Option<IpAddr>),
+}
+
+impl RateLimitKey {
+    fn of(request: &Request, ClientIp(client): ClientIp) -> Self {
+        match request_api_key(request.headers()) {
+            Some(key) => Self::ApiKey(hash_key(key)),
+            None => Self::Client(client),
+        }
+    }
+}
+
 /// Rate limiter state
-#[allow(dead_code)] // Reserved for future rate limiting implementation
 pub type RateLimiterState = Arc<
-    RateLimiter<
-        governor::state::direct::NotKeyed,
-        governor::state::InMemoryState,
-        governor::clock::DefaultClock,
-    >,
+    RateLimiter<RateLimitKey, DefaultKeyedStateStore<RateLimitKey>, governor::clock::DefaultClock>,
 >;
 
 /// Shared rate limiter (alias for compatibility)
@@ -29,30 +56,44 @@ pub type SharedRateLimiter = RateLimiterState;
 #[allow(dead_code)] // Reserved for future rate limiting implementation
 pub fn create_rate_limiter() -> RateLimiterState {
     let quota = Quota::per_minute(NonZeroU32::new(100).unwrap());
-    Arc::new(RateLimiter::direct(quota))
+    Arc::new(RateLimiter::keyed(quota))
 }
 
 /// Create a rate limiter with custom quota
-#[allow(dead_code)] // Reserved for future rate limiting implementation
 pub fn create_rate_limiter_with_quota(requests_per_minute: u32) -> RateLimiterState {
     let quota = Quota::per_minute(
         NonZeroU32::new(requests_per_minute).unwrap_or(NonZeroU32::new(100).unwrap()),
     );
-    Arc::new(RateLimiter::direct(quota))
+    Arc::new(RateLimiter::keyed(quota))
 }
 
-/// Rate limiting middleware
-#[allow(dead_code)] // Reserved for future rate limiting implementation
+/// Rate limiting middleware: 429 with a `Retry-After` header once the client's quota is used up
 pub async fn rate_limit_middleware(
     State(limiter): State<RateLimiterState>,
+    client: ClientIp,
     request: Request,
     next: Next,
-) -> Result<Response, StatusCode> {
-    match limiter.check() {
-        Ok(_) => Ok(next.run(request).await),
-        Err(_) => {
+) -> Response {
+    if limiter.len() > MAX_TRACKED_CLIENTS {
+        limiter.retain_recent();
+    }
+    match limiter.check_key(&RateLimitKey::of(&request, client)) {
+        Ok(_) => next.run(request).await,
+        Err(not_until) => {
             tracing::warn!("Rate limit exceeded for request: {}", request.uri());
-            Err(StatusCode::TOO_MANY_REQUESTS)
+            let retry_after = not_until
+                .wait_time_from(DefaultClock::default().now())
+                .as_secs()
+                .max(1);
+            let mut response = ApiError {
+                status: StatusCode::TOO_MANY_REQUESTS,
+                message: "Rate limit exceeded".to_string(),
+            }
+            .into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+            response
         }
     }
 }
@@ -65,3 +106,42 @@ pub fn create_rate_limit_layer() -> impl Layer<axum::Router> + Clone {
     // This is a placeholder for future implementation
     tower::layer::util::Identity::new()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, routing::get};
+    use axum_test::TestServer;
+
+    #[tokio::test]
+    async fn test_quota_per_client() {
+        let app = Router::new().route("/", get(|| async { "ok" })).layer(
+            axum::middleware::from_fn_with_state(
+                create_rate_limiter_with_quota(1),
+                rate_limit_middleware,
+            ),
+        );
+        let server = TestServer::new(app).unwrap();
+
+        server
+            .get("/")
+            .add_header("x-api-key", "dmk_first")
+            .await
+            .assert_status_ok();
+        let limited = server.get("/").add_header("x-api-key", "dmk_first").await;
+        limited.assert_status(StatusCode::TOO_MANY_REQUESTS);
+        assert!(limited.headers().contains_key(header::RETRY_AFTER));
+
+        // Other keys and keyless clients have their own quota
+        server
+            .get("/")
+            .add_header("x-api-key", "dmk_second")
+            .await
+            .assert_status_ok();
+        server.get("/").await.assert_status_ok();
+        server
+            .get("/")
+            .await
+            .assert_status(StatusCode::TOO_MANY_REQUESTS);
+    }
+}
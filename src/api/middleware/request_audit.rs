@@ -0,0 +1,131 @@
+//! Request audit middleware.
+//!
+//! Records mutating API calls in the [`RequestAuditLog`] sink. Only JSON bodies with a known
+//! length up to [`MAX_AUDIT_BODY_BYTES`] are buffered and recorded; other bodies are streamed
+//! through untouched and noted as omitted.
+
+use axum::body::{Body, to_bytes};
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, Method, header};
+use axum::middleware::Next;
+use axum::response::Response;
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::services::jwt_service::JwtService;
+use crate::services::local_mode::local_mode;
+use crate::services::request_audit_service::{RequestAuditEntry, RequestAuditLog, path_context};
+
+/// Largest request body recorded in the audit log.
+pub const MAX_AUDIT_BODY_BYTES: usize = 1024 * 1024;
+
+/// Email of the authenticated user, if the request carries a valid access token.
+pub(crate) fn request_user(headers: &HeaderMap) -> Option<String> {
+    if let Some(local) = local_mode() {
+        return Some(local.email.clone());
+    }
+    let token = match headers
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+    {
+        Some(auth_header) => JwtService::extract_bearer_token(auth_header),
+        None => headers.get("x-session-id").and_then(|h| h.to_str().ok()),
+    }?;
+    JwtService::try_from_env()
+        .ok()?
+        .validate_access_token(token)
+        .ok()
+        .map(|claims| claims.sub)
+        .filter(|sub| !sub.is_empty())
+}
+
+/// Middleware writing an audit entry for every POST/PUT/PATCH/DELETE request.
+pub async fn request_audit_middleware(
+    State(log): State<Arc<RequestAuditLog>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !log.is_enabled()
+        || !matches!(
+            *request.method(),
+            Method::POST | Method::PUT | Method::PATCH | Method::DELETE
+        )
+    {
+        return next.run(request).await;
+    }
+
+    let started = Instant::now();
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+    let user = request_user(request.headers()).map(|u| log.redaction.pseudonymize(&u));
+
+    let is_json = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("application/json"));
+    let content_length = request
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse::<usize>().ok());
+
+    let (mut body, mut body_omitted) = (None, None);
+    let request = match content_length {
+        Some(0) => request,
+        Some(len) if is_json && len <= MAX_AUDIT_BODY_BYTES => {
+            let (parts, raw) = request.into_parts();
+            let bytes = match to_bytes(raw, MAX_AUDIT_BODY_BYTES).await {
+                Ok(bytes) => bytes,
+                Err(e) => {
+                    warn!("Failed to buffer request body for audit: {}", e);
+                    Default::default()
+                }
+            };
+            match serde_json::from_slice::<serde_json::Value>(&bytes) {
+                Ok(mut value) => {
+                    log.redaction.redact(&mut value);
+                    body = Some(value);
+                }
+                Err(_) => body_omitted = Some("invalid JSON body".to_string()),
+            }
+            Request::from_parts(parts, Body::from(bytes))
+        }
+        Some(len) if is_json => {
+            body_omitted = Some(format!("body too large ({} bytes)", len));
+            request
+        }
+        _ => {
+            body_omitted = Some("non-JSON or streamed body".to_string());
+            request
+        }
+    };
+
+    let response = next.run(request).await;
+
+    let (domain, object_ids) = path_context(&path);
+    let entry = RequestAuditEntry {
+        id: Uuid::new_v4(),
+        timestamp: Utc::now(),
+        method,
+        path,
+        status: response.status().as_u16(),
+        duration_ms: started.elapsed().as_millis() as u64,
+        user,
+        domain,
+        object_ids,
+        body,
+        body_omitted,
+        event: None,
+    };
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = log.record(&entry) {
+            warn!("Failed to write request audit entry: {}", e);
+        }
+    });
+
+    response
+}
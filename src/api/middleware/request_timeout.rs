@@ -0,0 +1,160 @@
+//! Per-request time budgets and cancellation.
+//!
+//! Every request runs within a time budget chosen by its route, with longer budgets for
+//! imports and exports. A request that runs out of time is answered with 504 and its handler
+//! future dropped, which releases the model lock if it was held. Handlers can extract a
+//! [`Cancellation`] that is cancelled when the budget runs out or the client disconnects, and
+//! check it in work running on the blocking thread pool, which dropping cannot interrupt.
+//!
+//! The budget covers producing the response; streamed response bodies are not limited and
+//! stop when the client goes away.
+//!
+//! Configuration (environment variables):
+//! - `REQUEST_TIMEOUT_ENABLED` (default true)
+//! - `REQUEST_TIMEOUT_SECS` (default 120)
+//! - `IMPORT_TIMEOUT_SECS` (default 600): routes with an `import` path segment
+//! - `EXPORT_TIMEOUT_SECS` (default 600): routes with an `export` or `exports` path segment
+
+use axum::extract::{FromRequestParts, Request, State};
+use axum::http::StatusCode;
+use axum::http::request::Parts;
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Json, Response};
+use serde_json::json;
+use std::convert::Infallible;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+pub use crate::services::cancellation::Cancellation;
+
+/// Request time budgets.
+#[derive(Debug, Clone)]
+pub struct RequestTimeoutConfig {
+    pub enabled: bool,
+    pub default: Duration,
+    pub import: Duration,
+    pub export: Duration,
+}
+
+impl RequestTimeoutConfig {
+    /// Read settings from environment variables.
+    pub fn from_env() -> Self {
+        let seconds = |key: &str, default: u64| {
+            let seconds = std::env::var(key)
+                .ok()
+                .and_then(|v| v.trim().parse().ok())
+                .filter(|s| *s > 0)
+                .unwrap_or(default);
+            Duration::from_secs(seconds)
+        };
+        Self {
+            enabled: std::env::var("REQUEST_TIMEOUT_ENABLED")
+                .map(|v| !matches!(v.to_lowercase().as_str(), "0" | "false" | "no" | "off"))
+                .unwrap_or(true),
+            default: seconds("REQUEST_TIMEOUT_SECS", 120),
+            import: seconds("IMPORT_TIMEOUT_SECS", 600),
+            export: seconds("EXPORT_TIMEOUT_SECS", 600),
+        }
+    }
+
+    /// Time budget of a request to `path`
+    pub fn budget(&self, path: &str) -> Duration {
+        let has_segment = |names: &[&str]| path.split('/').any(|s| names.contains(&s));
+        if has_segment(&["import"]) {
+            self.import
+        } else if has_segment(&["export", "exports"]) {
+            self.export
+        } else {
+            self.default
+        }
+    }
+}
+
+/// Middleware running each request within its time budget.
+pub async fn request_timeout_middleware(
+    State(config): State<Arc<RequestTimeoutConfig>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    if !config.enabled {
+        return next.run(request).await;
+    }
+    let budget = config.budget(request.uri().path());
+    let path = request.uri().path().to_string();
+    let cancellation = Cancellation::new();
+    request.extensions_mut().insert(cancellation.clone());
+
+    // Dropped with this future if the client disconnects before the response is ready
+    let guard = cancellation.cancel_on_drop();
+    let result = tokio::time::timeout(budget, next.run(request)).await;
+    guard.disarm();
+
+    match result {
+        Ok(response) => response,
+        Err(_) => {
+            cancellation.cancel();
+            warn!(
+                "Request to {} exceeded its {}s budget",
+                path,
+                budget.as_secs()
+            );
+            (
+                StatusCode::GATEWAY_TIMEOUT,
+                Json(json!({
+                    "error": format!("Request exceeded its {}s time budget", budget.as_secs())
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// The request's cancellation; never cancelled if the middleware is not installed.
+impl<S: Send + Sync> FromRequestParts<S> for Cancellation {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<Cancellation>()
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_budget_by_route_and_cancel_on_drop() {
+        let config = RequestTimeoutConfig {
+            enabled: true,
+            default: Duration::from_secs(1),
+            import: Duration::from_secs(2),
+            export: Duration::from_secs(3),
+        };
+        assert_eq!(config.budget("/api/v1/import/sql"), config.import);
+        assert_eq!(
+            config.budget("/api/v1/workspace/domains/sales/export/all"),
+            config.export
+        );
+        assert_eq!(config.budget("/api/v1/workspace/exporters"), config.default);
+
+        // An abandoned request future cancels its work; a completed one doesn't
+        let cancellation = Cancellation::new();
+        let guard = cancellation.cancel_on_drop();
+        let abandoned = tokio::time::timeout(Duration::from_millis(10), async move {
+            let _guard = guard;
+            tokio::time::sleep(Duration::from_secs(5)).await;
+        });
+        assert!(abandoned.await.is_err());
+        assert!(cancellation.is_cancelled());
+        assert!(cancellation.check().is_err());
+
+        let completed = Cancellation::new();
+        completed.cancel_on_drop().disarm();
+        assert!(!completed.is_cancelled());
+    }
+}
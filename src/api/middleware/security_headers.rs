@@ -0,0 +1,107 @@
+//! Security response headers.
+//!
+//! Adds standard security headers to every response. The Content-Security-Policy is only
+//! set on HTML documents (the served frontend), where it takes effect.
+//!
+//! Configuration (environment variables):
+//! - `SECURITY_HEADERS_ENABLED` (default true)
+//! - `CONTENT_SECURITY_POLICY`: overrides the default policy
+//! - `REFERRER_POLICY` (default `strict-origin-when-cross-origin`)
+//! - `STRICT_TRANSPORT_SECURITY`: value for the HSTS header; unset disables it
+
+use axum::extract::{Request, State};
+use axum::http::{HeaderName, HeaderValue, header};
+use axum::middleware::Next;
+use axum::response::Response;
+use std::sync::Arc;
+use tracing::warn;
+
+/// Default CSP for the frontend: same-origin assets, WASM and websocket connections.
+const DEFAULT_CSP: &str = "default-src 'self'; script-src 'self' 'wasm-unsafe-eval'; \
+     style-src 'self' 'unsafe-inline'; img-src 'self' data: blob:; connect-src 'self' ws: wss:; \
+     object-src 'none'; base-uri 'self'; frame-ancestors 'none'";
+
+/// Security header settings.
+#[derive(Debug, Clone)]
+pub struct SecurityHeadersConfig {
+    pub enabled: bool,
+    pub content_security_policy: Option<HeaderValue>,
+    pub referrer_policy: Option<HeaderValue>,
+    pub strict_transport_security: Option<HeaderValue>,
+}
+
+impl SecurityHeadersConfig {
+    /// Read settings from environment variables.
+    pub fn from_env() -> Self {
+        let enabled = std::env::var("SECURITY_HEADERS_ENABLED")
+            .map(|v| !matches!(v.to_lowercase().as_str(), "0" | "false" | "no" | "off"))
+            .unwrap_or(true);
+        let header_value = |key: &str, default: Option<&str>| {
+            let value = std::env::var(key).ok().or(default.map(str::to_string))?;
+            match HeaderValue::from_str(value.trim()) {
+                Ok(v) if !v.is_empty() => Some(v),
+                Ok(_) => None,
+                Err(_) => {
+                    warn!("Ignoring invalid {} value", key);
+                    None
+                }
+            }
+        };
+        Self {
+            enabled,
+            content_security_policy: header_value("CONTENT_SECURITY_POLICY", Some(DEFAULT_CSP)),
+            referrer_policy: header_value(
+                "REFERRER_POLICY",
+                Some("strict-origin-when-cross-origin"),
+            ),
+            strict_transport_security: header_value("STRICT_TRANSPORT_SECURITY", None),
+        }
+    }
+}
+
+/// Middleware adding security headers; headers already set by a handler are kept.
+pub async fn security_headers_middleware(
+    State(config): State<Arc<SecurityHeadersConfig>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let mut response = next.run(request).await;
+    if !config.enabled {
+        return response;
+    }
+
+    let is_html = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/html"));
+
+    let headers = response.headers_mut();
+    let mut set = |name: HeaderName, value: Option<&HeaderValue>| {
+        if let Some(value) = value
+            && !headers.contains_key(&name)
+        {
+            headers.insert(name, value.clone());
+        }
+    };
+    set(
+        header::X_CONTENT_TYPE_OPTIONS,
+        Some(&HeaderValue::from_static("nosniff")),
+    );
+    set(
+        header::X_FRAME_OPTIONS,
+        Some(&HeaderValue::from_static("DENY")),
+    );
+    set(header::REFERRER_POLICY, config.referrer_policy.as_ref());
+    set(
+        header::STRICT_TRANSPORT_SECURITY,
+        config.strict_transport_security.as_ref(),
+    );
+    if is_html {
+        set(
+            header::CONTENT_SECURITY_POLICY,
+            config.content_security_policy.as_ref(),
+        );
+    }
+    response
+}
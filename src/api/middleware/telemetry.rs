@@ -0,0 +1,29 @@
+//! Usage telemetry middleware.
+//!
+//! Counts API requests by feature in the opt-in [`Telemetry`] collector. Only the request
+//! path is inspected; requests answered with 404 are not counted.
+
+use axum::extract::{Request, State};
+use axum::http::StatusCode;
+use axum::middleware::Next;
+use axum::response::Response;
+use std::sync::Arc;
+
+use crate::services::telemetry_service::Telemetry;
+
+/// Middleware recording the feature of every API request.
+pub async fn telemetry_middleware(
+    State(telemetry): State<Arc<Telemetry>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !telemetry.is_enabled() {
+        return next.run(request).await;
+    }
+    let path = request.uri().path().to_string();
+    let response = next.run(request).await;
+    if response.status() != StatusCode::NOT_FOUND {
+        telemetry.record_request(&path);
+    }
+    response
+}
@@ -5,6 +5,51 @@ use std::collections::HashMap;
 pub struct ForeignKey {
     pub table_id: String, // UUID as string
     pub column_name: String,
+    /// Name of the composite key this column belongs to; columns sharing a name form one key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constraint_name: Option<String>,
+    /// 1-based position of this column within a composite key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_position: Option<u32>,
+}
+
+/// How masked values are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum MaskType {
+    /// A fixed placeholder (`****`), or NULL for non-string columns
+    Redact,
+    /// Only the last four characters (string columns)
+    Partial,
+    /// SHA-256 hex digest of the value (string columns)
+    Hash,
+    /// NULL
+    Null,
+}
+
+impl MaskType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Redact => "redact",
+            Self::Partial => "partial",
+            Self::Hash => "hash",
+            Self::Null => "null",
+        }
+    }
+
+    /// Whether the mask can only be applied to string columns
+    pub fn requires_string(self) -> bool {
+        matches!(self, Self::Partial | Self::Hash)
+    }
+}
+
+/// Column masking policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct MaskingPolicy {
+    pub mask_type: MaskType,
+    /// Roles (Snowflake) or account groups (Databricks) that see unmasked values
+    #[serde(default)]
+    pub visibility_roles: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
@@ -31,6 +76,18 @@ pub struct Column {
     pub quality: Vec<HashMap<String, serde_json::Value>>,
     #[serde(default)]
     pub enum_values: Vec<String>,
+    /// Data classification, e.g. `pii` or `confidential`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub classification: Option<String>,
+    /// How the column is masked for users outside its visibility roles; required for `pii`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub masking_policy: Option<MaskingPolicy>,
+    /// Business glossary terms the column is linked to (term URLs or IDs)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub glossary_links: Vec<String>,
+    /// Values of the workspace's custom column fields, keyed by field name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub custom_properties: HashMap<String, serde_json::Value>,
     #[serde(default)]
     pub column_order: i32,
 }
@@ -54,9 +111,25 @@ impl Column {
             errors: Vec::new(),
             quality: Vec::new(),
             enum_values: Vec::new(),
+            classification: None,
+            masking_policy: None,
+            glossary_links: Vec::new(),
+            custom_properties: HashMap::new(),
             column_order: 0,
         }
     }
+
+    /// Whether the column holds character data
+    pub fn is_string(&self) -> bool {
+        super::relationship::type_family(&self.data_type) == "string"
+    }
+
+    /// Whether the column is classified as personal data (`pii`)
+    pub fn is_pii(&self) -> bool {
+        self.classification
+            .as_deref()
+            .is_some_and(|c| c.trim().eq_ignore_ascii_case("pii"))
+    }
 }
 
 fn normalize_data_type(data_type: &str) -> String {
@@ -59,6 +59,14 @@ impl DataModel {
         self.tables.iter().find(|t| t.name == name)
     }
 
+    /// The table with alias `name`, unless a table is actually called `name`.
+    pub fn get_table_by_alias_mut(&mut self, name: &str) -> Option<&mut Table> {
+        if self.tables.iter().any(|t| t.name == name) {
+            return None;
+        }
+        self.tables.iter_mut().find(|t| t.has_alias(name))
+    }
+
     pub fn get_table_by_unique_key(
         &self,
         database_type: Option<&str>,
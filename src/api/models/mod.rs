@@ -8,11 +8,14 @@ pub mod enums;
 pub mod relationship;
 pub mod table;
 
-pub use column::Column;
+pub use column::{Column, MaskType, MaskingPolicy};
 pub use data_flow_diagram::DataFlowDiagram;
 pub use data_model::DataModel;
 // Enums are re-exported individually where needed
 // pub use enums::*;
 #[allow(unused_imports)]
 pub use relationship::{ConnectionPoint, Relationship, VisualMetadata};
-pub use table::{Position, Table};
+pub use table::{
+    Position, RowAccessPolicy, RowPolicyCommand, Subtype, SubtypeHierarchy, Table, TemporalKind,
+    TemporalSettings,
+};
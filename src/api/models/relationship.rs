@@ -1,12 +1,190 @@
 use super::enums::{Cardinality, RelationshipType};
+use super::table::Table;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ForeignKeyDetails {
+    /// First (or only) source column of the key
     pub source_column: String,
+    /// First (or only) target column of the key
     pub target_column: String,
+    /// Ordered column pairs of a composite key; empty for single-column keys
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub column_pairs: Vec<ForeignKeyColumnPair>,
+}
+
+/// One source → target column mapping of a (composite) foreign key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForeignKeyColumnPair {
+    pub source_column: String,
+    pub target_column: String,
+}
+
+impl ForeignKeyDetails {
+    /// Single-column foreign key.
+    pub fn new(source_column: impl Into<String>, target_column: impl Into<String>) -> Self {
+        Self {
+            source_column: source_column.into(),
+            target_column: target_column.into(),
+            column_pairs: Vec::new(),
+        }
+    }
+
+    /// Foreign key from ordered source/target column lists.
+    ///
+    /// Fails when the lists are empty or differ in length.
+    pub fn from_columns(
+        source_columns: &[String],
+        target_columns: &[String],
+    ) -> Result<Self, String> {
+        if source_columns.is_empty() || source_columns.len() != target_columns.len() {
+            return Err(format!(
+                "Foreign key column count mismatch: {} source column(s), {} target column(s)",
+                source_columns.len(),
+                target_columns.len()
+            ));
+        }
+        let mut details = Self::new(&source_columns[0], &target_columns[0]);
+        if source_columns.len() > 1 {
+            details.column_pairs = source_columns
+                .iter()
+                .zip(target_columns)
+                .map(|(source, target)| ForeignKeyColumnPair {
+                    source_column: source.clone(),
+                    target_column: target.clone(),
+                })
+                .collect();
+        }
+        Ok(details)
+    }
+
+    /// Whether the key spans more than one column.
+    pub fn is_composite(&self) -> bool {
+        self.column_pairs.len() > 1
+    }
+
+    /// All column pairs in key order (a single pair for single-column keys).
+    pub fn pairs(&self) -> Vec<(&str, &str)> {
+        if self.column_pairs.is_empty() {
+            vec![(self.source_column.as_str(), self.target_column.as_str())]
+        } else {
+            self.column_pairs
+                .iter()
+                .map(|p| (p.source_column.as_str(), p.target_column.as_str()))
+                .collect()
+        }
+    }
+
+    /// Ordered source columns.
+    pub fn source_columns(&self) -> Vec<&str> {
+        self.pairs().into_iter().map(|(s, _)| s).collect()
+    }
+
+    /// Ordered target columns.
+    pub fn target_columns(&self) -> Vec<&str> {
+        self.pairs().into_iter().map(|(_, t)| t).collect()
+    }
+
+    /// Check that the column pairs line up and that paired columns have compatible types.
+    ///
+    /// Columns missing from either table are not reported, since relationships may be
+    /// recorded before all columns are modelled; type alignment is checked for every
+    /// pair whose columns both exist.
+    pub fn validate(&self, source: &Table, target: &Table) -> Result<(), String> {
+        if let Some(first) = self.column_pairs.first()
+            && (first.source_column != self.source_column
+                || first.target_column != self.target_column)
+        {
+            return Err(
+                "Foreign key source_column/target_column must match the first column pair"
+                    .to_string(),
+            );
+        }
+        let pairs = self.pairs();
+        if pairs
+            .iter()
+            .any(|(s, t)| s.trim().is_empty() || t.trim().is_empty())
+        {
+            return Err(
+                "Foreign key column count mismatch: every source column needs a target column"
+                    .to_string(),
+            );
+        }
+        for (idx, (source_column, _)) in pairs.iter().enumerate() {
+            if pairs[..idx]
+                .iter()
+                .any(|(s, _)| s.eq_ignore_ascii_case(source_column))
+            {
+                return Err(format!(
+                    "Foreign key lists source column '{}' more than once",
+                    source_column
+                ));
+            }
+        }
+
+        let find = |table: &'_ Table, name: &str| {
+            table
+                .columns
+                .iter()
+                .find(|c| c.name.eq_ignore_ascii_case(name))
+                .map(|c| c.data_type.clone())
+        };
+        for (source_column, target_column) in pairs {
+            if let (Some(source_type), Some(target_type)) =
+                (find(source, source_column), find(target, target_column))
+                && type_family(&source_type) != type_family(&target_type)
+            {
+                return Err(format!(
+                    "Foreign key type mismatch: {}.{} ({}) -> {}.{} ({})",
+                    source.name,
+                    source_column,
+                    source_type,
+                    target.name,
+                    target_column,
+                    target_type
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Human-readable label, e.g. `(order_id, line_no) -> (id, line_no)`.
+    pub fn label(&self) -> String {
+        if self.is_composite() {
+            format!(
+                "({}) -> ({})",
+                self.source_columns().join(", "),
+                self.target_columns().join(", ")
+            )
+        } else {
+            format!("{} -> {}", self.source_column, self.target_column)
+        }
+    }
+}
+
+/// Coarse type family used to compare foreign key column types across dialects.
+pub(crate) fn type_family(data_type: &str) -> String {
+    let base = data_type
+        .split(['(', '<', ' '])
+        .next()
+        .unwrap_or("")
+        .to_uppercase();
+    let family = match base.as_str() {
+        "INT" | "INTEGER" | "INT2" | "INT4" | "INT8" | "SMALLINT" | "BIGINT" | "TINYINT"
+        | "MEDIUMINT" | "SERIAL" | "BIGSERIAL" | "SMALLSERIAL" | "LONG" | "SHORT" => "integer",
+        "DECIMAL" | "NUMERIC" | "NUMBER" | "DEC" => "decimal",
+        "FLOAT" | "FLOAT4" | "FLOAT8" | "REAL" | "DOUBLE" => "float",
+        "CHAR" | "VARCHAR" | "NCHAR" | "NVARCHAR" | "TEXT" | "STRING" | "CHARACTER"
+        | "VARCHAR2" | "NVARCHAR2" | "CLOB" => "string",
+        "UUID" | "UNIQUEIDENTIFIER" => "uuid",
+        "BOOL" | "BOOLEAN" | "BIT" => "boolean",
+        "TIMESTAMP" | "TIMESTAMPTZ" | "DATETIME" | "DATETIME2" | "TIMESTAMP_NTZ"
+        | "TIMESTAMP_LTZ" => "timestamp",
+        other => other,
+    };
+    family.to_string()
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,7 +202,7 @@ pub struct ConnectionPoint {
     pub y: f64,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct VisualMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source_connection_point: Option<String>, // "north", "south", "east", "west"
@@ -34,6 +212,74 @@ pub struct VisualMetadata {
     pub routing_waypoints: Vec<ConnectionPoint>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub label_position: Option<ConnectionPoint>,
+    /// Where the relationship attaches to the source table box
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_anchor: Option<RelationshipAnchor>,
+    /// Where the relationship attaches to the target table box
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_anchor: Option<RelationshipAnchor>,
+}
+
+/// Side of a table box
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum TableSide {
+    North,
+    South,
+    East,
+    West,
+}
+
+/// Stable attachment point of a relationship on a table box: a side, and either the row of
+/// a column (east and west sides) or a fraction along the side. Without either, the
+/// relationship attaches at the middle of the side.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RelationshipAnchor {
+    pub side: TableSide,
+    /// Index of the column whose row the relationship attaches at
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column_index: Option<usize>,
+    /// Position along the side, from 0 (top or left) to 1 (bottom or right)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fraction: Option<f64>,
+}
+
+impl RelationshipAnchor {
+    /// Check the anchor against the table it attaches to.
+    pub fn validate(&self, column_count: usize) -> Result<(), String> {
+        match (self.column_index, self.fraction) {
+            (Some(_), Some(_)) => {
+                Err("An anchor takes a column_index or a fraction, not both".into())
+            }
+            (Some(_), None) if matches!(self.side, TableSide::North | TableSide::South) => {
+                Err("A column_index anchor must be on the east or west side".into())
+            }
+            (Some(index), None) if index >= column_count => Err(format!(
+                "column_index {} is out of range for a table with {} columns",
+                index, column_count
+            )),
+            (None, Some(fraction)) if !(0.0..=1.0).contains(&fraction) => {
+                Err("fraction must be between 0 and 1".into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Point of the anchor relative to the table box, `(0, 0)` top left to `(1, 1)` bottom
+    /// right. `row_position` gives the vertical position of a column's row, if it is shown.
+    pub fn relative_point(&self, row_position: impl Fn(usize) -> Option<f64>) -> (f64, f64) {
+        let along = match (self.column_index, self.fraction) {
+            (_, Some(fraction)) => fraction.clamp(0.0, 1.0),
+            (Some(index), None) => row_position(index).unwrap_or(0.5),
+            (None, None) => 0.5,
+        };
+        match self.side {
+            TableSide::North => (along, 0.0),
+            TableSide::South => (along, 1.0),
+            TableSide::East => (1.0, along),
+            TableSide::West => (0.0, along),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
@@ -49,6 +295,12 @@ pub struct Relationship {
     pub source_optional: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub target_optional: Option<bool>,
+    /// Role the source table plays in the relationship (e.g. "report")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_role: Option<String>,
+    /// Role the target table plays in the relationship (e.g. "manager")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_role: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub foreign_key_details: Option<ForeignKeyDetails>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -76,6 +328,8 @@ impl Relationship {
             cardinality: None,
             source_optional: None,
             target_optional: None,
+            source_role: None,
+            target_role: None,
             foreign_key_details: None,
             etl_job_metadata: None,
             relationship_type: None,
@@ -86,4 +340,33 @@ impl Relationship {
             updated_at: now,
         }
     }
+
+    /// Whether both ends are the same table (e.g. `employee.manager_id -> employee.id`).
+    pub fn is_self_reference(&self) -> bool {
+        self.source_table_id == self.target_table_id
+    }
+
+    /// Whether this relationship connects the same tables in the same roles. Role names
+    /// distinguish several relationships between one pair of tables.
+    pub fn same_endpoints(
+        &self,
+        source_table_id: Uuid,
+        target_table_id: Uuid,
+        source_role: Option<&str>,
+        target_role: Option<&str>,
+    ) -> bool {
+        self.source_table_id == source_table_id
+            && self.target_table_id == target_table_id
+            && self.source_role.as_deref() == source_role
+            && self.target_role.as_deref() == target_role
+    }
+
+    /// Edge label built from the role names, e.g. `report / manager`.
+    pub fn role_label(&self) -> Option<String> {
+        match (self.source_role.as_deref(), self.target_role.as_deref()) {
+            (Some(source), Some(target)) => Some(format!("{} / {}", source, target)),
+            (Some(role), None) | (None, Some(role)) => Some(role.to_string()),
+            (None, None) => None,
+        }
+    }
 }
@@ -13,6 +13,141 @@ pub struct Position {
     pub y: f64,
 }
 
+/// Key of `odcl_metadata` holding a table's row access policies
+pub const ROW_ACCESS_POLICIES_KEY: &str = "rowAccessPolicies";
+
+/// Statements a row access policy applies to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum RowPolicyCommand {
+    #[default]
+    All,
+    Select,
+    Insert,
+    Update,
+    Delete,
+}
+
+impl RowPolicyCommand {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::Select => "select",
+            Self::Insert => "insert",
+            Self::Update => "update",
+            Self::Delete => "delete",
+        }
+    }
+}
+
+/// Row-level security policy: members of `roles` only see the rows matching `predicate`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct RowAccessPolicy {
+    pub name: String,
+    /// SQL boolean expression over the table's columns, e.g. `region = 'EMEA'`
+    pub predicate: String,
+    /// Roles the policy applies to; everyone if empty
+    #[serde(default)]
+    pub roles: Vec<String>,
+    #[serde(default)]
+    pub command: RowPolicyCommand,
+}
+
+/// Key of `odcl_metadata` holding a table's temporal settings
+pub const TEMPORAL_KEY: &str = "temporal";
+
+/// Which time dimensions a temporal table tracks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TemporalKind {
+    /// System time: the database keeps every row version in a history table
+    SystemVersioned,
+    /// Application (valid) time: rows carry the period they are true in the real world
+    ApplicationTime,
+    /// Both system and application time
+    Bitemporal,
+}
+
+impl TemporalKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SystemVersioned => "system_versioned",
+            Self::ApplicationTime => "application_time",
+            Self::Bitemporal => "bitemporal",
+        }
+    }
+
+    pub fn has_system_time(self) -> bool {
+        matches!(self, Self::SystemVersioned | Self::Bitemporal)
+    }
+
+    pub fn has_application_time(self) -> bool {
+        matches!(self, Self::ApplicationTime | Self::Bitemporal)
+    }
+}
+
+fn default_system_start() -> String {
+    "sys_start".to_string()
+}
+
+fn default_system_end() -> String {
+    "sys_end".to_string()
+}
+
+fn default_valid_from() -> String {
+    "valid_from".to_string()
+}
+
+fn default_valid_to() -> String {
+    "valid_to".to_string()
+}
+
+fn default_period_data_type() -> String {
+    "TIMESTAMP".to_string()
+}
+
+/// Temporal settings of a table and the names of its period columns
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct TemporalSettings {
+    pub kind: TemporalKind,
+    #[serde(default = "default_system_start")]
+    pub system_start_column: String,
+    #[serde(default = "default_system_end")]
+    pub system_end_column: String,
+    #[serde(default = "default_valid_from")]
+    pub valid_from_column: String,
+    #[serde(default = "default_valid_to")]
+    pub valid_to_column: String,
+    /// Data type of generated period columns
+    #[serde(default = "default_period_data_type")]
+    pub period_data_type: String,
+    /// History table of system-versioned tables; `<table>_history` if omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_table: Option<String>,
+}
+
+/// Key of `odcl_metadata` holding the subtypes of a supertype table
+pub const SUBTYPES_KEY: &str = "subtypes";
+
+/// A subtype table of a supertype
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct Subtype {
+    pub table_id: Uuid,
+    /// Value of the supertype's discriminator column for rows of this subtype
+    pub discriminator_value: String,
+}
+
+/// Supertype/subtype hierarchy, stored on the supertype table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SubtypeHierarchy {
+    /// Supertype column telling which subtype a row belongs to
+    pub discriminator_column: String,
+    pub subtypes: Vec<Subtype>,
+    /// Whether every supertype row belongs to one of the subtypes (the supertype is abstract)
+    #[serde(default)]
+    pub complete: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
 pub struct Table {
     pub id: Uuid,
@@ -34,6 +169,9 @@ pub struct Table {
     pub modeling_level: Option<ModelingLevel>,
     #[serde(default)]
     pub tags: Vec<String>,
+    /// Previous or alternative physical names; imports and search match these too
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
     #[serde(default)]
     pub odcl_metadata: HashMap<String, serde_json::Value>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -66,6 +204,7 @@ impl Table {
             data_vault_classification: None,
             modeling_level: None,
             tags: Vec::new(),
+            aliases: Vec::new(),
             odcl_metadata: HashMap::new(),
             position: None,
             yaml_file_path: None,
@@ -86,6 +225,58 @@ impl Table {
         )
     }
 
+    /// True if `name` is one of the table's aliases (case-insensitive).
+    pub fn has_alias(&self, name: &str) -> bool {
+        self.aliases.iter().any(|a| a.eq_ignore_ascii_case(name))
+    }
+
+    /// Rename the table, keeping the old name as an alias.
+    pub fn rename(&mut self, new_name: &str) {
+        let old_name = std::mem::replace(&mut self.name, new_name.to_string());
+        self.aliases.retain(|a| !a.eq_ignore_ascii_case(new_name));
+        if !old_name.eq_ignore_ascii_case(new_name) && !self.has_alias(&old_name) {
+            self.aliases.push(old_name);
+        }
+    }
+
+    /// Row access policies stored in `odcl_metadata`; an error if they are malformed.
+    pub fn row_access_policies(&self) -> Result<Vec<RowAccessPolicy>, serde_json::Error> {
+        match self.odcl_metadata.get(ROW_ACCESS_POLICIES_KEY) {
+            Some(policies) if !policies.is_null() => serde_json::from_value(policies.clone()),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Replace the row access policies; an empty list removes them.
+    pub fn set_row_access_policies(&mut self, policies: &[RowAccessPolicy]) {
+        if policies.is_empty() {
+            self.odcl_metadata.remove(ROW_ACCESS_POLICIES_KEY);
+        } else {
+            self.odcl_metadata.insert(
+                ROW_ACCESS_POLICIES_KEY.to_string(),
+                serde_json::json!(policies),
+            );
+        }
+    }
+
+    /// Temporal settings stored in `odcl_metadata`, if the table is temporal; an error if
+    /// they are malformed.
+    pub fn temporal(&self) -> Result<Option<TemporalSettings>, serde_json::Error> {
+        match self.odcl_metadata.get(TEMPORAL_KEY) {
+            Some(settings) if !settings.is_null() => serde_json::from_value(settings.clone()),
+            _ => Ok(None),
+        }
+    }
+
+    /// Subtypes of the table stored in `odcl_metadata`, if it is a supertype; an error if
+    /// they are malformed.
+    pub fn subtypes(&self) -> Result<Option<SubtypeHierarchy>, serde_json::Error> {
+        match self.odcl_metadata.get(SUBTYPES_KEY) {
+            Some(hierarchy) if !hierarchy.is_null() => serde_json::from_value(hierarchy.clone()),
+            _ => Ok(None),
+        }
+    }
+
     #[allow(dead_code)]
     pub fn validate_pattern_exclusivity(&self) -> Result<(), String> {
         if self.scd_pattern.is_some() && self.data_vault_classification.is_some() {
@@ -7,21 +7,121 @@ use utoipa::{Modify, OpenApi};
 #[openapi(
     paths(
         // Authentication
-        crate::routes::auth::initiate_github_login,
-        crate::routes::auth::initiate_desktop_github_login,
-        crate::routes::auth::handle_github_callback,
+        crate::routes::auth::list_oauth_providers,
+        crate::routes::auth::initiate_oauth_login,
+        crate::routes::auth::initiate_desktop_oauth_login,
+        crate::routes::auth::handle_oauth_callback,
         crate::routes::auth::poll_auth_status,
         crate::routes::auth::exchange_auth_code,
         crate::routes::auth::refresh_token,
         crate::routes::auth::get_auth_status,
         crate::routes::auth::select_email,
         crate::routes::auth::logout,
+        crate::routes::auth::revoke_refresh_token,
+        crate::routes::auth::list_sessions,
+        crate::routes::auth::revoke_user_session,
+        crate::routes::api_keys::list_api_keys,
+        crate::routes::api_keys::create_api_key,
+        crate::routes::api_keys::delete_api_key,
+        crate::routes::api_keys::list_personal_access_tokens,
+        crate::routes::api_keys::create_personal_access_token,
+        crate::routes::api_keys::revoke_personal_access_token,
         // Workspace
         crate::routes::workspace::create_workspace,
         crate::routes::workspace::get_workspace_info,
         crate::routes::workspace::list_profiles,
+        crate::routes::usage::get_workspace_usage,
         crate::routes::workspace::list_domains,
         crate::routes::workspace::create_domain,
+        crate::routes::domain_provisioning::bulk_create_domains,
+        crate::routes::domain_metadata::get_domain_metadata,
+        crate::routes::domain_metadata::update_domain_metadata,
+        crate::routes::data_product::get_data_product,
+        crate::routes::data_product::update_data_product,
+        crate::routes::data_product::export_data_product_descriptor,
+        crate::routes::table_readme::get_table_readme,
+        crate::routes::table_readme::update_table_readme,
+        crate::routes::column_edges::get_column_edges,
+        crate::routes::relationship_anchors::update_relationship_anchors,
+        crate::routes::column_references::get_column_references,
+        crate::routes::rename::rename_table,
+        crate::routes::rename::rename_column,
+        crate::routes::evolution_policy::get_evolution_policy,
+        crate::routes::evolution_policy::update_evolution_policy,
+        crate::routes::evolution_policy::check_table_compatibility,
+        crate::routes::retention::get_retention_policy,
+        crate::routes::retention::update_retention_policy,
+        crate::routes::retention::export_purge_plan,
+        crate::routes::row_policies::get_row_access_policies,
+        crate::routes::row_policies::update_row_access_policies,
+        crate::routes::temporal::get_temporal_settings,
+        crate::routes::temporal::update_temporal_settings,
+        crate::routes::temporal::delete_temporal_settings,
+        crate::routes::subtypes::get_subtypes,
+        crate::routes::subtypes::update_subtypes,
+        crate::routes::subtypes::delete_subtypes,
+        crate::routes::size_estimates::get_size_annotation,
+        crate::routes::size_estimates::update_size_annotation,
+        crate::routes::size_estimates::delete_size_annotation,
+        crate::routes::size_estimates::get_storage_estimate,
+        crate::routes::quality_score::get_quality_score,
+        crate::routes::history::diff_with_past,
+        crate::routes::contract_tests::export_contract_tests,
+        crate::routes::diagram_shares::share_diagram,
+        crate::routes::diagram_shares::get_shared_diagram,
+        crate::routes::domain_invites::list_domain_invites,
+        crate::routes::domain_invites::create_domain_invite,
+        crate::routes::domain_invites::revoke_domain_invite,
+        crate::routes::domain_invites::redeem_domain_invite,
+        crate::routes::members::list_workspace_members,
+        crate::routes::members::add_workspace_member,
+        crate::routes::members::update_workspace_member,
+        crate::routes::members::remove_workspace_member,
+        crate::routes::members::list_domain_members,
+        crate::routes::members::add_domain_member,
+        crate::routes::members::update_domain_member,
+        crate::routes::members::remove_domain_member,
+        crate::routes::domain_shares::list_domain_shares,
+        crate::routes::domain_shares::create_domain_share,
+        crate::routes::domain_shares::delete_domain_share,
+        crate::routes::domain_shares::get_shared_domain,
+        crate::routes::domain_shares::list_shared_tables,
+        crate::routes::domain_shares::get_shared_table,
+        crate::routes::domain_shares::get_sharing_settings,
+        crate::routes::domain_shares::update_sharing_settings,
+        crate::routes::metadata_schema::get_metadata_schema,
+        crate::routes::metadata_schema::update_metadata_schema,
+        crate::routes::standard_columns::get_standard_columns,
+        crate::routes::standard_columns::update_standard_columns,
+        crate::routes::medallion_layers::get_medallion_layers,
+        crate::routes::medallion_layers::update_medallion_layers,
+        crate::routes::workspace_provisioning::get_provisioning_rules,
+        crate::routes::workspace_provisioning::update_provisioning_rules,
+        crate::routes::script_hooks::get_script_hooks,
+        crate::routes::script_hooks::update_script_hooks,
+        crate::routes::script_hooks::test_script_hooks,
+        crate::routes::payload_validation::validate_payload,
+        crate::routes::table_profiles::upload_table_profile,
+        crate::routes::table_profiles::get_table_profiles,
+        crate::routes::metadata_propagation::propagate_domain_metadata,
+        crate::routes::duplicate_tables::find_duplicate_tables,
+        crate::routes::domain_graph::get_domain_graph,
+        crate::routes::models::export_enterprise,
+        crate::routes::saved_filters::list_saved_filters,
+        crate::routes::saved_filters::create_saved_filter,
+        crate::routes::saved_filters::get_saved_filter,
+        crate::routes::saved_filters::update_saved_filter,
+        crate::routes::saved_filters::delete_saved_filter,
+        crate::routes::commands::get_command_index,
+        crate::routes::commands::execute_command,
+        crate::routes::events::list_domain_events,
+        crate::routes::events::get_event_schema,
+        crate::routes::attachments::list_domain_attachments,
+        crate::routes::attachments::upload_domain_attachment,
+        crate::routes::attachments::list_table_attachments,
+        crate::routes::attachments::upload_table_attachment,
+        crate::routes::attachments::download_attachment,
+        crate::routes::attachments::delete_attachment,
         crate::routes::workspace::get_domain,
         crate::routes::workspace::update_domain,
         crate::routes::workspace::delete_domain,
@@ -38,6 +138,16 @@ use utoipa::{Modify, OpenApi};
         crate::routes::workspace::get_domain_relationship,
         crate::routes::workspace::update_domain_relationship,
         crate::routes::workspace::delete_domain_relationship,
+        crate::routes::relationships_file::get_relationships_schema,
+        crate::routes::relationships_file::download_relationships_file,
+        crate::routes::relationships_file::validate_relationships_upload,
+        crate::routes::relationships_file::upload_relationships_file,
+        // Drafts
+        crate::routes::drafts::open_draft,
+        crate::routes::drafts::get_draft,
+        crate::routes::drafts::save_draft,
+        crate::routes::drafts::discard_draft,
+        crate::routes::drafts::get_save_status,
         // Cross-domain
         crate::routes::workspace::get_cross_domain_config,
         crate::routes::workspace::list_cross_domain_tables,
@@ -57,9 +167,52 @@ use utoipa::{Modify, OpenApi};
         crate::routes::import::import_avro,
         crate::routes::import::import_json_schema,
         crate::routes::import::import_protobuf,
+        crate::routes::import::import_erwin,
+        crate::routes::import::import_orm_text,
+        crate::routes::import::import_dbml,
+        crate::routes::import::import_lakehouse,
+        crate::routes::import::domain_import_google_sheets,
+        crate::routes::import::import_relationships_csv,
+        crate::routes::import::get_google_sheets_mapping,
+        crate::routes::import::put_google_sheets_mapping,
+        crate::routes::import::import_event_storming,
         // Export
         crate::routes::models::export_format,
         crate::routes::models::export_all,
+        // Work items
+        crate::routes::work_items::list_table_links,
+        crate::routes::work_items::add_table_link,
+        crate::routes::work_items::delete_table_link,
+        crate::routes::work_items::list_proposal_links,
+        crate::routes::work_items::add_proposal_link,
+        crate::routes::work_items::delete_proposal_link,
+        crate::routes::work_items::sync_domain_links,
+        // Export schedules
+        crate::routes::export_schedules::list_export_schedules,
+        crate::routes::export_schedules::create_export_schedule,
+        crate::routes::export_schedules::get_export_schedule,
+        crate::routes::export_schedules::update_export_schedule,
+        crate::routes::export_schedules::delete_export_schedule,
+        crate::routes::export_schedules::run_export_schedule,
+        crate::routes::export_schedules::list_export_runs,
+        crate::routes::exports::list_exports,
+        crate::routes::exports::download_export,
+        // Environments
+        crate::routes::environments::list_environments,
+        crate::routes::environments::get_environment,
+        crate::routes::environments::upsert_environment,
+        crate::routes::environments::delete_environment,
+        crate::routes::environments::get_domain_variables,
+        crate::routes::environments::update_domain_variables,
+        // Naming
+        crate::routes::naming::get_naming_templates,
+        crate::routes::naming::update_naming_templates,
+        crate::routes::naming::preview_naming_templates,
+        crate::routes::naming::preview_candidate_naming_templates,
+        crate::routes::relationship_rules::get_relationship_rules,
+        crate::routes::relationship_rules::update_relationship_rules,
+        crate::routes::relationship_rules::list_rule_violations,
+        crate::routes::relationship_rules::check_relationship_rules,
         // Git Sync
         crate::routes::git_sync::get_sync_config,
         crate::routes::git_sync::update_sync_config,
@@ -86,10 +239,28 @@ use utoipa::{Modify, OpenApi};
         crate::routes::collaboration_sessions::get_presence,
         // Audit
         crate::routes::audit::get_domain_history,
+        crate::routes::audit::list_domain_audit,
         crate::routes::audit::get_table_history,
         crate::routes::audit::get_relationship_history,
         crate::routes::audit::get_workspace_history,
         crate::routes::audit::get_audit_entry,
+        crate::routes::audit::get_request_audit_log,
+        // Notifications
+        crate::routes::notifications::get_notification_preferences,
+        crate::routes::notifications::update_notification_preferences,
+        crate::routes::notifications::list_notification_channels,
+        crate::routes::notifications::send_test_notification,
+        // Desktop
+        crate::routes::desktop::list_files,
+        crate::routes::desktop::open_archive,
+        crate::routes::desktop::save_archive,
+        // Development
+        crate::routes::dev_fixtures::provision_fixtures,
+        // Telemetry
+        crate::routes::telemetry::get_telemetry_status,
+        // GraphQL
+        crate::routes::graphql::graphql_handler,
+        crate::routes::graphql::graphql_sdl,
         // AI
         crate::routes::ai::resolve_errors,
         // OpenAPI
@@ -99,20 +270,44 @@ use utoipa::{Modify, OpenApi};
         // Import types from models
         crate::models::Table,
         crate::models::Column,
+        crate::models::MaskingPolicy,
+        crate::models::MaskType,
+        crate::models::RowAccessPolicy,
+        crate::models::RowPolicyCommand,
+        crate::models::TemporalKind,
+        crate::models::TemporalSettings,
+        crate::models::Subtype,
+        crate::models::SubtypeHierarchy,
+        crate::services::inheritance_service::InheritanceStrategy,
         crate::models::Relationship,
         crate::models::DataModel,
     )),
     modifiers(&SecurityAddon),
     tags(
-        (name = "Authentication", description = "GitHub OAuth authentication endpoints"),
+        (name = "Authentication", description = "OAuth (GitHub, Google, Microsoft) authentication endpoints"),
         (name = "Workspace", description = "Workspace and domain management"),
         (name = "Tables", description = "Table CRUD operations"),
         (name = "Relationships", description = "Relationship CRUD operations"),
         (name = "Import", description = "Multi-format import endpoints"),
         (name = "Export", description = "Multi-format export endpoints"),
+        (name = "Environments", description = "Per-domain environment profiles (dev/test/prod)"),
+        (name = "Naming", description = "Physical table naming templates applied at export"),
+        (name = "Relationship Rules", description = "Allowed medallion layer transitions for relationships"),
+        (name = "Events", description = "Append-only model change log with cursor reads"),
+        (name = "Attachments", description = "Files attached to domains and tables"),
+        (name = "Sharing", description = "Read-only share links for external partners"),
+        (name = "Work Items", description = "Jira / Azure DevOps work item links on tables and proposals"),
+        (name = "Export Schedules", description = "Recurring export bundles published to git, S3 or webhooks"),
+        (name = "Saved Filters", description = "Named table filters applied to table lists and the canvas"),
+        (name = "Script Hooks", description = "Rhai validation rules and import transforms run on every import"),
         (name = "Git Sync", description = "Git synchronization operations"),
         (name = "Collaboration", description = "Real-time collaboration sessions"),
         (name = "Audit", description = "Audit trail queries"),
+        (name = "Notifications", description = "Model event notification preferences"),
+        (name = "Desktop", description = "Local file dialogs for the desktop app"),
+        (name = "Development", description = "Sample workspaces for integration tests (development only)"),
+        (name = "Telemetry", description = "Opt-in anonymous usage telemetry"),
+        (name = "GraphQL", description = "GraphQL queries over tables, relationships, lineage and search"),
         (name = "AI", description = "AI-powered error resolution"),
         (name = "OpenAPI", description = "OpenAPI specification"),
     ),
@@ -143,6 +338,18 @@ impl Modify for SecurityAddon {
         // Update version to match Cargo.toml version
         openapi.info.version = env!("CARGO_PKG_VERSION").to_string();
 
+        // Include the reverse proxy base path (BASE_PATH) in server URLs
+        let base_path = crate::middleware::base_path::base_path();
+        if !base_path.is_empty()
+            && let Some(servers) = openapi.servers.as_mut()
+        {
+            for server in servers {
+                server.url = server
+                    .url
+                    .replacen("/api/v1", &format!("{}/api/v1", base_path), 1);
+            }
+        }
+
         // Initialize components if they don't exist
         if openapi.components.is_none() {
             openapi.components = Some(utoipa::openapi::Components::new());
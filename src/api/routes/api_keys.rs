@@ -0,0 +1,581 @@
+//! API key and personal access token management routes.
+//!
+//! Signed-in users create, list and delete the API keys their CI pipelines use and the
+//! personal access tokens their own scripts use (see `api_key_service`). Nested under
+//! `/auth/api-keys` and `/auth/tokens`; requests authenticated with a key are rejected, so a
+//! key can't create or extend other keys.
+
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+    routing::{delete, get},
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::error::ApiError;
+use super::workspace::get_user_context;
+use crate::middleware::api_key::{api_key_store, request_api_key};
+use crate::services::api_key_service::{ApiKey, ApiKeyKind, ApiKeyScope, ApiKeyStore};
+use crate::storage::traits::UserContext;
+
+/// Days until a personal access token expires unless the request says otherwise
+const DEFAULT_TOKEN_LIFETIME_DAYS: i64 = 90;
+/// Longest lifetime of a personal access token
+const MAX_TOKEN_LIFETIME_DAYS: i64 = 365;
+/// Longest expiry of an API key (keys created without one never expire)
+const MAX_API_KEY_LIFETIME_DAYS: i64 = 3650;
+
+/// Create the API key router (nested under `/auth/api-keys`)
+pub fn api_keys_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_api_keys).post(create_api_key))
+        .route("/{key_id}", delete(delete_api_key))
+}
+
+/// Create the personal access token router (nested under `/auth/tokens`)
+pub fn personal_access_tokens_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/",
+            get(list_personal_access_tokens).post(create_personal_access_token),
+        )
+        .route("/{token_id}", delete(revoke_personal_access_token))
+}
+
+/// Request body for creating an API key
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    /// What the key is for (e.g. a pipeline name)
+    pub name: String,
+    pub scope: ApiKeyScope,
+    /// Days until the key expires (at most 3650); never if omitted
+    pub expires_in_days: Option<i64>,
+}
+
+/// An API key, without its secret
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiKeyResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+    /// First characters of the key
+    pub prefix: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub expired: bool,
+}
+
+impl From<&ApiKey> for ApiKeyResponse {
+    fn from(key: &ApiKey) -> Self {
+        Self {
+            id: key.id,
+            name: key.name.clone(),
+            scopes: key.scopes.clone(),
+            prefix: key.prefix.clone(),
+            created_at: key.created_at,
+            expires_at: key.expires_at,
+            expired: key.is_expired(Utc::now()),
+        }
+    }
+}
+
+/// A newly created API key
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedApiKeyResponse {
+    pub api_key: ApiKeyResponse,
+    /// The key, sent in the `X-API-Key` header. It is not shown again.
+    pub key: String,
+}
+
+/// Path parameters of an API key
+#[derive(Deserialize)]
+pub struct ApiKeyPath {
+    pub key_id: Uuid,
+}
+
+/// Request body for creating a personal access token
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreatePersonalAccessTokenRequest {
+    /// What the token is for (e.g. a script name)
+    pub name: String,
+    /// What the token may do; a request is allowed if any scope allows it
+    pub scopes: Vec<ApiKeyScope>,
+    /// Days until the token expires (default: 90, max: 365)
+    pub expires_in_days: Option<i64>,
+}
+
+/// A personal access token, without its secret
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PersonalAccessTokenResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub scopes: Vec<ApiKeyScope>,
+    /// First characters of the token
+    pub prefix: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub expired: bool,
+}
+
+impl From<&ApiKey> for PersonalAccessTokenResponse {
+    fn from(key: &ApiKey) -> Self {
+        Self {
+            id: key.id,
+            name: key.name.clone(),
+            scopes: key.scopes.clone(),
+            prefix: key.prefix.clone(),
+            created_at: key.created_at,
+            expires_at: key.expires_at,
+            expired: key.is_expired(Utc::now()),
+        }
+    }
+}
+
+/// A newly created personal access token
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedPersonalAccessTokenResponse {
+    pub personal_access_token: PersonalAccessTokenResponse,
+    /// The token, sent in the `X-API-Key` header or as a bearer token. It is not shown again.
+    pub token: String,
+}
+
+/// Path parameters of a personal access token
+#[derive(Deserialize)]
+pub struct PersonalAccessTokenPath {
+    pub token_id: Uuid,
+}
+
+/// The signed-in user managing keys; API keys can't manage keys
+async fn key_manager(state: &AppState, headers: &HeaderMap) -> Result<UserContext, StatusCode> {
+    if request_api_key(headers).is_some() {
+        warn!("Rejected API key management with an API key");
+        return Err(StatusCode::FORBIDDEN);
+    }
+    get_user_context(state, headers).await
+}
+
+/// The user's keys of one kind
+async fn user_keys(
+    store: &dyn ApiKeyStore,
+    user: &UserContext,
+    kind: ApiKeyKind,
+) -> Result<Vec<ApiKey>, StatusCode> {
+    let keys = store.list(&user.email).await.map_err(|e| {
+        warn!("Failed to list API keys: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(keys.into_iter().filter(|k| k.kind == kind).collect())
+}
+
+/// Delete a key of the user; 404 unless the user has a key of `kind` with that id
+async fn delete_user_key(
+    store: &dyn ApiKeyStore,
+    user: &UserContext,
+    kind: ApiKeyKind,
+    id: Uuid,
+) -> Result<(), StatusCode> {
+    if !user_keys(store, user, kind)
+        .await?
+        .iter()
+        .any(|k| k.id == id)
+    {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let deleted = store.delete(&user.email, id).await.map_err(|e| {
+        warn!("Failed to delete API key: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if !deleted {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(())
+}
+
+/// GET /auth/api-keys - List the user's API keys
+#[utoipa::path(
+    get,
+    path = "/auth/api-keys",
+    tag = "Authentication",
+    responses(
+        (status = 200, description = "API keys of the user", body = Vec<ApiKeyResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Authenticated with an API key"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_api_keys(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<ApiKeyResponse>>, StatusCode> {
+    let user = key_manager(&state, &headers).await?;
+    let keys = user_keys(api_key_store(&state)?.as_ref(), &user, ApiKeyKind::ApiKey).await?;
+    Ok(Json(keys.iter().map(Into::into).collect()))
+}
+
+/// POST /auth/api-keys - Create an API key acting as the user
+#[utoipa::path(
+    post,
+    path = "/auth/api-keys",
+    tag = "Authentication",
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 200, description = "API key created; the key is only returned here", body = CreatedApiKeyResponse),
+        (status = 400, description = "Empty name or invalid expiry", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Authenticated with an API key"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<CreateApiKeyRequest>,
+) -> Result<Json<CreatedApiKeyResponse>, ApiError> {
+    let user = key_manager(&state, &headers).await?;
+    let store = api_key_store(&state)?;
+    Ok(Json(new_api_key(store.as_ref(), &user, request).await?))
+}
+
+/// Create an API key of `user` in `store`
+async fn new_api_key(
+    store: &dyn ApiKeyStore,
+    user: &UserContext,
+    request: CreateApiKeyRequest,
+) -> Result<CreatedApiKeyResponse, ApiError> {
+    let bad_request = |message: &str| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message: message.to_string(),
+    };
+    let name = request.name.trim();
+    if name.is_empty() {
+        return Err(bad_request("name must not be empty"));
+    }
+    if request
+        .expires_in_days
+        .is_some_and(|days| !(1..=MAX_API_KEY_LIFETIME_DAYS).contains(&days))
+    {
+        return Err(bad_request(&format!(
+            "expires_in_days must be between 1 and {}",
+            MAX_API_KEY_LIFETIME_DAYS
+        )));
+    }
+
+    let expires_at = request
+        .expires_in_days
+        .map(|days| Utc::now() + Duration::days(days));
+    let (key, secret) = ApiKey::generate(
+        ApiKeyKind::ApiKey,
+        name.to_string(),
+        vec![request.scope],
+        user,
+        expires_at,
+    );
+    store.create(&key).await.map_err(|e| {
+        warn!("Failed to create API key: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    info!(
+        "{} created API key {} ({})",
+        user.email,
+        key.id,
+        key.scopes_str()
+    );
+
+    Ok(CreatedApiKeyResponse {
+        api_key: (&key).into(),
+        key: secret,
+    })
+}
+
+/// DELETE /auth/api-keys/{key_id} - Delete an API key
+#[utoipa::path(
+    delete,
+    path = "/auth/api-keys/{key_id}",
+    tag = "Authentication",
+    params(
+        ("key_id" = Uuid, Path, description = "API key ID")
+    ),
+    responses(
+        (status = 204, description = "API key deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Authenticated with an API key"),
+        (status = 404, description = "API key not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_api_key(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<ApiKeyPath>,
+) -> Result<StatusCode, StatusCode> {
+    let user = key_manager(&state, &headers).await?;
+    let store = api_key_store(&state)?;
+    delete_user_key(store.as_ref(), &user, ApiKeyKind::ApiKey, path.key_id).await?;
+    info!("{} deleted API key {}", user.email, path.key_id);
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /auth/tokens - List the user's personal access tokens
+#[utoipa::path(
+    get,
+    path = "/auth/tokens",
+    tag = "Authentication",
+    responses(
+        (status = 200, description = "Personal access tokens of the user", body = Vec<PersonalAccessTokenResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Authenticated with an API key or token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_personal_access_tokens(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<PersonalAccessTokenResponse>>, StatusCode> {
+    let user = key_manager(&state, &headers).await?;
+    let store = api_key_store(&state)?;
+    let tokens = user_keys(store.as_ref(), &user, ApiKeyKind::PersonalAccessToken).await?;
+    Ok(Json(tokens.iter().map(Into::into).collect()))
+}
+
+/// POST /auth/tokens - Create a personal access token acting as the user
+#[utoipa::path(
+    post,
+    path = "/auth/tokens",
+    tag = "Authentication",
+    request_body = CreatePersonalAccessTokenRequest,
+    responses(
+        (status = 200, description = "Token created; the token is only returned here", body = CreatedPersonalAccessTokenResponse),
+        (status = 400, description = "Empty name, no scopes or invalid expiry", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Authenticated with an API key or token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_personal_access_token(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<CreatePersonalAccessTokenRequest>,
+) -> Result<Json<CreatedPersonalAccessTokenResponse>, ApiError> {
+    let user = key_manager(&state, &headers).await?;
+    let store = api_key_store(&state)?;
+    Ok(Json(
+        new_personal_access_token(store.as_ref(), &user, request).await?,
+    ))
+}
+
+/// Create a personal access token of `user` in `store`
+async fn new_personal_access_token(
+    store: &dyn ApiKeyStore,
+    user: &UserContext,
+    request: CreatePersonalAccessTokenRequest,
+) -> Result<CreatedPersonalAccessTokenResponse, ApiError> {
+    let bad_request = |message: String| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message,
+    };
+    let name = request.name.trim();
+    if name.is_empty() {
+        return Err(bad_request("name must not be empty".to_string()));
+    }
+    let mut scopes = request.scopes;
+    scopes.sort_by_key(|scope| scope.as_str());
+    scopes.dedup();
+    if scopes.is_empty() {
+        return Err(bad_request("scopes must not be empty".to_string()));
+    }
+    let days = request
+        .expires_in_days
+        .unwrap_or(DEFAULT_TOKEN_LIFETIME_DAYS);
+    if !(1..=MAX_TOKEN_LIFETIME_DAYS).contains(&days) {
+        return Err(bad_request(format!(
+            "expires_in_days must be between 1 and {}",
+            MAX_TOKEN_LIFETIME_DAYS
+        )));
+    }
+
+    let (token, secret) = ApiKey::generate(
+        ApiKeyKind::PersonalAccessToken,
+        name.to_string(),
+        scopes,
+        user,
+        Some(Utc::now() + Duration::days(days)),
+    );
+    store.create(&token).await.map_err(|e| {
+        warn!("Failed to create personal access token: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    info!(
+        "{} created personal access token {} ({})",
+        user.email,
+        token.id,
+        token.scopes_str()
+    );
+
+    Ok(CreatedPersonalAccessTokenResponse {
+        personal_access_token: (&token).into(),
+        token: secret,
+    })
+}
+
+/// DELETE /auth/tokens/{token_id} - Revoke a personal access token
+#[utoipa::path(
+    delete,
+    path = "/auth/tokens/{token_id}",
+    tag = "Authentication",
+    params(
+        ("token_id" = Uuid, Path, description = "Personal access token ID")
+    ),
+    responses(
+        (status = 204, description = "Token revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Authenticated with an API key or token"),
+        (status = 404, description = "Token not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn revoke_personal_access_token(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<PersonalAccessTokenPath>,
+) -> Result<StatusCode, StatusCode> {
+    let user = key_manager(&state, &headers).await?;
+    delete_user_key(
+        api_key_store(&state)?.as_ref(),
+        &user,
+        ApiKeyKind::PersonalAccessToken,
+        path.token_id,
+    )
+    .await?;
+    info!(
+        "{} revoked personal access token {}",
+        user.email, path.token_id
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api_key_service::FileApiKeyStore;
+
+    fn user() -> UserContext {
+        UserContext {
+            user_id: Uuid::new_v4(),
+            email: "ada@example.com".to_string(),
+        }
+    }
+
+    fn token_request(
+        scopes: Vec<ApiKeyScope>,
+        days: Option<i64>,
+    ) -> CreatePersonalAccessTokenRequest {
+        CreatePersonalAccessTokenRequest {
+            name: "nightly-export".to_string(),
+            scopes,
+            expires_in_days: days,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_lifetime_and_scopes_are_validated() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileApiKeyStore::new(dir.path());
+        let user = user();
+        let export = vec![ApiKeyScope::ExportOnly];
+
+        for days in [Some(0), Some(MAX_TOKEN_LIFETIME_DAYS + 1)] {
+            let err = new_personal_access_token(&store, &user, token_request(export.clone(), days))
+                .await
+                .unwrap_err();
+            assert_eq!(err.status, StatusCode::BAD_REQUEST);
+        }
+        let err = new_personal_access_token(&store, &user, token_request(Vec::new(), None))
+            .await
+            .unwrap_err();
+        assert_eq!(err.message, "scopes must not be empty");
+
+        let created = new_personal_access_token(
+            &store,
+            &user,
+            token_request(vec![ApiKeyScope::ReadOnly, ApiKeyScope::ExportOnly], None),
+        )
+        .await
+        .unwrap();
+        let token = created.personal_access_token;
+        assert_eq!(
+            token.expires_at.unwrap().date_naive(),
+            (Utc::now() + Duration::days(DEFAULT_TOKEN_LIFETIME_DAYS)).date_naive()
+        );
+        assert_eq!(token.scopes.len(), 2);
+
+        let err = new_api_key(
+            &store,
+            &user,
+            CreateApiKeyRequest {
+                name: "ci".to_string(),
+                scope: ApiKeyScope::Full,
+                expires_in_days: Some(MAX_API_KEY_LIFETIME_DAYS + 1),
+            },
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_keys_and_tokens_are_deleted_by_their_own_routes() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileApiKeyStore::new(dir.path());
+        let user = user();
+        let key = new_api_key(
+            &store,
+            &user,
+            CreateApiKeyRequest {
+                name: "ci".to_string(),
+                scope: ApiKeyScope::ImportOnly,
+                expires_in_days: None,
+            },
+        )
+        .await
+        .unwrap()
+        .api_key;
+        assert_eq!(key.scopes, vec![ApiKeyScope::ImportOnly]);
+        let token = new_personal_access_token(
+            &store,
+            &user,
+            token_request(vec![ApiKeyScope::Full], Some(30)),
+        )
+        .await
+        .unwrap()
+        .personal_access_token;
+
+        // A token id is unknown to the API key routes, and the other way around
+        let wrong_kind = delete_user_key(&store, &user, ApiKeyKind::ApiKey, token.id).await;
+        assert_eq!(wrong_kind, Err(StatusCode::NOT_FOUND));
+        let wrong_kind =
+            delete_user_key(&store, &user, ApiKeyKind::PersonalAccessToken, key.id).await;
+        assert_eq!(wrong_kind, Err(StatusCode::NOT_FOUND));
+        assert_eq!(store.list(&user.email).await.unwrap().len(), 2);
+
+        delete_user_key(&store, &user, ApiKeyKind::ApiKey, key.id)
+            .await
+            .unwrap();
+        delete_user_key(&store, &user, ApiKeyKind::PersonalAccessToken, token.id)
+            .await
+            .unwrap();
+        assert!(store.list(&user.email).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,103 @@
+//! API version discovery and content negotiation.
+//!
+//! `GET /api/versions` lists the served versions with their lifecycle. Requests to the
+//! unversioned `/api/...` prefix are forwarded to the version named by their `Accept`
+//! header (see [`ApiVersion::negotiate`]), or to the default version.
+
+use axum::{
+    Router,
+    extract::Request,
+    http::{HeaderValue, StatusCode, Uri, header},
+    response::{IntoResponse, Json, Response},
+};
+use serde::Serialize;
+use std::sync::Arc;
+use tower::Service;
+use utoipa::ToSchema;
+
+use super::error::ApiError;
+use crate::middleware::api_version::{ApiVersion, ApiVersionConfig, ApiVersionInfo};
+
+/// Served API versions
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiVersionsResponse {
+    pub versions: Vec<ApiVersionInfo>,
+    /// Version served on the unversioned `/api` prefix without an `Accept` header
+    pub default: String,
+    /// Newest version
+    pub latest: String,
+}
+
+/// GET /api/versions - List the served API versions
+pub async fn list_api_versions(config: Arc<ApiVersionConfig>) -> Json<ApiVersionsResponse> {
+    Json(ApiVersionsResponse {
+        versions: ApiVersion::ALL
+            .into_iter()
+            .map(|version| config.describe(version))
+            .collect(),
+        default: ApiVersion::DEFAULT.name().to_string(),
+        latest: ApiVersion::LATEST.name().to_string(),
+    })
+}
+
+/// `/api/rest?query` rewritten to `/api/{version}/rest?query`
+fn versioned_uri(uri: &Uri, version: ApiVersion) -> Option<Uri> {
+    let path_and_query = uri.path_and_query()?.as_str();
+    let rest = path_and_query.strip_prefix("/api")?;
+    format!("{}{}", version.path(), rest).parse().ok()
+}
+
+/// Forward an unversioned `/api/...` request to the negotiated version of `versioned`, a
+/// router serving every version under its `/api/{version}` prefix.
+pub async fn negotiate_api_version(mut versioned: Router, mut request: Request) -> Response {
+    let version = match ApiVersion::negotiate(request.headers()) {
+        Ok(version) => version.unwrap_or(ApiVersion::DEFAULT),
+        Err(media_type) => {
+            return ApiError {
+                status: StatusCode::NOT_ACCEPTABLE,
+                message: format!(
+                    "API version {} is not served; see /api/versions",
+                    media_type
+                ),
+            }
+            .into_response();
+        }
+    };
+    // Explicitly versioned paths that reach here are unknown or not served in that version
+    let explicit = request
+        .uri()
+        .path()
+        .trim_start_matches("/api/")
+        .split('/')
+        .next()
+        .and_then(|segment| segment.strip_prefix('v'))
+        .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()));
+    let uri = match versioned_uri(request.uri(), version) {
+        Some(uri) if !explicit => uri,
+        _ => return ApiError::from(StatusCode::NOT_FOUND).into_response(),
+    };
+    *request.uri_mut() = uri;
+
+    // Routers are always ready to accept a request
+    let mut response = versioned.call(request).await.into_response();
+    response
+        .headers_mut()
+        .append(header::VARY, HeaderValue::from_static("accept"));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versioned_uri() {
+        let uri: Uri = "/api/workspace/domains?limit=5".parse().unwrap();
+        assert_eq!(
+            versioned_uri(&uri, ApiVersion::V2).unwrap(),
+            "/api/v2/workspace/domains?limit=5"
+        );
+        let other: Uri = "/assets/app.js".parse().unwrap();
+        assert_eq!(versioned_uri(&other, ApiVersion::V1), None);
+    }
+}
@@ -3,15 +3,25 @@
 //! Defines the AppState struct that holds all shared application state including
 //! model service, session store, storage backends, and database connections.
 
-use crate::routes::collaboration::CollaborationMessage;
+use crate::routes::collaboration_batch::{CollaborationBatching, CollaborationChannel};
+use crate::services::attachment_service::AttachmentService;
+use crate::services::ddl_lint_service::DdlLintConfig;
+use crate::services::diagram_share_service::DiagramImageCache;
+use crate::services::draft_service::{AutosaveSettings, DraftStore};
+use crate::services::event_bus_service::EventBus;
+use crate::services::model_limits_service::ModelLimits;
 use crate::services::model_service::ModelService;
+use crate::services::notification_service::NotificationService;
+use crate::services::request_audit_service::RequestAuditLog;
+use crate::services::script_hook_service::ScriptLimits;
+use crate::services::telemetry_service::Telemetry;
 use crate::storage::session_store::DbSessionStore;
 use crate::storage::{StorageBackend, StorageError};
 use axum::extract::FromRef;
 use sqlx::PgPool;
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::{Mutex, broadcast};
+use tokio::sync::Mutex;
 
 /// Application state shared across all route handlers.
 #[derive(Clone)]
@@ -27,8 +37,31 @@ pub struct AppState {
     /// PostgreSQL database connection pool (optional)
     pub database: Option<PgPool>,
     /// Collaboration broadcast channels (model_id -> channel)
-    pub collaboration_channels:
-        Arc<Mutex<HashMap<String, broadcast::Sender<CollaborationMessage>>>>,
+    pub collaboration_channels: Arc<Mutex<HashMap<String, CollaborationChannel>>>,
+    /// Batch window for collaboration connections
+    pub collaboration_batching: CollaborationBatching,
+    /// Notification service for model events (Slack/email)
+    pub notification_service: Arc<NotificationService>,
+    /// Audit log sink for mutating API calls
+    pub request_audit: Arc<RequestAuditLog>,
+    /// Attachment storage (local files or S3) with quotas and virus scanning
+    pub attachments: Arc<AttachmentService>,
+    /// Optional Kafka/NATS publisher for model change events
+    pub event_bus: Arc<EventBus>,
+    /// Model size guardrails (tables per domain, columns per table, import size)
+    pub model_limits: ModelLimits,
+    /// Severities of the DDL lint rules run on SQL imports
+    pub ddl_lint: Arc<DdlLintConfig>,
+    /// Resource limits for workspace script hooks
+    pub script_limits: ScriptLimits,
+    /// Rendered images served through diagram share links
+    pub diagram_images: Arc<DiagramImageCache>,
+    /// Opt-in anonymous usage telemetry
+    pub telemetry: Arc<Telemetry>,
+    /// Open drafts (unsaved table and relationship edits) by session and domain
+    pub drafts: Arc<DraftStore>,
+    /// Autosave settings of drafts opened without their own
+    pub draft_autosave: AutosaveSettings,
 }
 
 impl AppState {
@@ -41,6 +74,18 @@ impl AppState {
             storage: None,
             database: None,
             collaboration_channels: Arc::new(Mutex::new(HashMap::new())),
+            collaboration_batching: CollaborationBatching::from_env(),
+            notification_service: Arc::new(NotificationService::from_env()),
+            request_audit: Arc::new(RequestAuditLog::from_env()),
+            attachments: Arc::new(AttachmentService::from_env()),
+            event_bus: Arc::new(EventBus::from_env()),
+            model_limits: ModelLimits::from_env(),
+            ddl_lint: Arc::new(DdlLintConfig::from_env()),
+            script_limits: ScriptLimits::from_env(),
+            diagram_images: Arc::new(DiagramImageCache::new()),
+            telemetry: Arc::new(Telemetry::from_env()),
+            drafts: Arc::new(DraftStore::new()),
+            draft_autosave: AutosaveSettings::from_env(),
         }
     }
 
@@ -49,6 +94,14 @@ impl AppState {
     /// This will attempt to connect to PostgreSQL if DATABASE_URL is set,
     /// otherwise falls back to file-based storage.
     pub async fn init_storage(&mut self) -> Result<(), StorageError> {
+        // Local mode always stores models as files
+        if crate::services::local_mode::local_mode().is_some() {
+            if std::env::var("DATABASE_URL").is_ok() {
+                tracing::warn!("DATABASE_URL is ignored in local mode; using file-based storage");
+            }
+            return Ok(());
+        }
+
         // Check if DATABASE_URL is set
         if let Ok(database_url) = std::env::var("DATABASE_URL") {
             // Initialize PostgreSQL storage
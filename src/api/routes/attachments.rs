@@ -0,0 +1,405 @@
+//! Attachment routes.
+//!
+//! Upload, list, download and delete small files attached to a domain or one of its tables
+//! (sample payloads, mapping spreadsheets, screenshots). The index lives in the domain's
+//! `attachments.yaml`; contents are kept in the configured attachment store, subject to the
+//! per-file size limit, the per-domain quota and the optional virus-scan hook.
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{DefaultBodyLimit, Multipart, Path, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{Json, Response},
+    routing::get,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::workspace::{
+    DomainContext, DomainPath, DomainTablePath, get_workspace_data_dir, workspace_dir_name,
+};
+use crate::services::attachment_service::{
+    Attachment, AttachmentIndex, AttachmentTarget, AttachmentUpload, UploadError,
+    max_file_bytes_from_env,
+};
+
+/// Room for multipart boundaries and headers on top of the file itself
+const MULTIPART_OVERHEAD_BYTES: usize = 64 * 1024;
+
+/// Attachments of a domain or table, with the domain's storage usage
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AttachmentListResponse {
+    pub attachments: Vec<Attachment>,
+    /// Bytes used by all attachments of the domain
+    pub used_bytes: u64,
+    pub quota_bytes: u64,
+    pub max_file_bytes: usize,
+}
+
+/// Path parameters for a single attachment
+#[derive(Deserialize)]
+pub struct AttachmentPath {
+    pub domain: String,
+    pub attachment_id: Uuid,
+}
+
+/// Create the attachment router (workspace-relative paths)
+pub fn attachments_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/domains/{domain}/attachments",
+            get(list_domain_attachments).post(upload_domain_attachment),
+        )
+        .route(
+            "/domains/{domain}/attachments/{attachment_id}",
+            get(download_attachment).delete(delete_attachment),
+        )
+        .route(
+            "/domains/{domain}/tables/{table_id}/attachments",
+            get(list_table_attachments).post(upload_table_attachment),
+        )
+        .layer(DefaultBodyLimit::max(
+            max_file_bytes_from_env() + MULTIPART_OVERHEAD_BYTES,
+        ))
+}
+
+/// Get path to a domain's attachments.yaml
+fn get_index_path(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain)
+        .join("attachments.yaml"))
+}
+
+/// Load a domain's attachment index, returning an empty index if none is stored.
+pub fn load_attachment_index(email: &str, domain: &str) -> AttachmentIndex {
+    if let Ok(path) = get_index_path(email, domain)
+        && path.exists()
+        && let Ok(content) = std::fs::read_to_string(&path)
+        && let Ok(index) = serde_yaml::from_str(&content)
+    {
+        return index;
+    }
+    AttachmentIndex::default()
+}
+
+/// Save a domain's attachment index
+fn save_attachment_index(
+    email: &str,
+    domain: &str,
+    index: &AttachmentIndex,
+) -> Result<(), StatusCode> {
+    let path = get_index_path(email, domain)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+    let yaml = serde_yaml::to_string(index).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    std::fs::write(path, yaml).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(())
+}
+
+fn list_response(
+    state: &AppState,
+    index: &AttachmentIndex,
+    target: AttachmentTarget,
+    target_id: Option<&str>,
+) -> Json<AttachmentListResponse> {
+    Json(AttachmentListResponse {
+        attachments: index.for_target(target, target_id),
+        used_bytes: index.total_size(),
+        quota_bytes: state.attachments.max_domain_bytes,
+        max_file_bytes: state.attachments.max_file_bytes,
+    })
+}
+
+/// Read the `file` field of a multipart upload and store it.
+async fn upload(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+    target: AttachmentTarget,
+    target_id: Option<&str>,
+    mut multipart: Multipart,
+) -> Result<Json<Attachment>, StatusCode> {
+    let mut file = None;
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|_| StatusCode::BAD_REQUEST)?
+    {
+        if field.name() == Some("file") {
+            let filename = field.file_name().unwrap_or("attachment").to_string();
+            let content_type = field.content_type().map(str::to_string);
+            let content = field
+                .bytes()
+                .await
+                .map_err(|_| StatusCode::PAYLOAD_TOO_LARGE)?;
+            file = Some((filename, content_type, content.to_vec()));
+        }
+    }
+    let (filename, content_type, content) = file.ok_or(StatusCode::BAD_REQUEST)?;
+
+    let email = &ctx.user_context.email;
+    let mut index = load_attachment_index(email, domain);
+    let key_prefix = format!("{}/{}", workspace_dir_name(email), domain);
+    let attachment = state
+        .attachments
+        .upload(
+            &index,
+            &key_prefix,
+            AttachmentUpload {
+                target,
+                target_id,
+                filename: &filename,
+                content_type: content_type.as_deref(),
+                content,
+                uploaded_by: email,
+            },
+        )
+        .await
+        .map_err(|e| match e {
+            UploadError::Empty => StatusCode::BAD_REQUEST,
+            UploadError::FileTooLarge { .. } | UploadError::QuotaExceeded { .. } => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
+            UploadError::Infected(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            UploadError::Storage(e) => {
+                warn!("Failed to store attachment {}: {}", filename, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        })?;
+    index.attachments.push(attachment.clone());
+    save_attachment_index(email, domain, &index)?;
+    Ok(Json(attachment))
+}
+
+/// GET /workspace/domains/{domain}/attachments - List files attached to the domain
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/attachments",
+    tag = "Attachments",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Domain attachments and storage usage", body = AttachmentListResponse),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_domain_attachments(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+) -> Result<Json<AttachmentListResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let index = load_attachment_index(&ctx.user_context.email, &path.domain);
+    Ok(list_response(
+        &state,
+        &index,
+        AttachmentTarget::Domain,
+        None,
+    ))
+}
+
+/// POST /workspace/domains/{domain}/attachments - Attach a file to the domain
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/attachments",
+    tag = "Attachments",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body(content = Multipart, description = "File in the `file` field"),
+    responses(
+        (status = 200, description = "File attached", body = Attachment),
+        (status = 400, description = "Missing or empty file"),
+        (status = 401, description = "Unauthorized"),
+        (status = 413, description = "File too large or domain quota exceeded"),
+        (status = 422, description = "Rejected by the virus scan"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn upload_domain_attachment(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    multipart: Multipart,
+) -> Result<Json<Attachment>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    upload(
+        &state,
+        &ctx,
+        &path.domain,
+        AttachmentTarget::Domain,
+        None,
+        multipart,
+    )
+    .await
+}
+
+/// GET /workspace/domains/{domain}/tables/{table_id}/attachments - List files attached to a table
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/tables/{table_id}/attachments",
+    tag = "Attachments",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    responses(
+        (status = 200, description = "Table attachments and domain storage usage", body = AttachmentListResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_table_attachments(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+) -> Result<Json<AttachmentListResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    super::work_items::ensure_table_exists(&state, &ctx, &path.table_id).await?;
+    let index = load_attachment_index(&ctx.user_context.email, &path.domain);
+    Ok(list_response(
+        &state,
+        &index,
+        AttachmentTarget::Table,
+        Some(&path.table_id),
+    ))
+}
+
+/// POST /workspace/domains/{domain}/tables/{table_id}/attachments - Attach a file to a table
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/tables/{table_id}/attachments",
+    tag = "Attachments",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    request_body(content = Multipart, description = "File in the `file` field"),
+    responses(
+        (status = 200, description = "File attached", body = Attachment),
+        (status = 400, description = "Missing or empty file"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 413, description = "File too large or domain quota exceeded"),
+        (status = 422, description = "Rejected by the virus scan"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn upload_table_attachment(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+    multipart: Multipart,
+) -> Result<Json<Attachment>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    super::work_items::ensure_table_exists(&state, &ctx, &path.table_id).await?;
+    upload(
+        &state,
+        &ctx,
+        &path.domain,
+        AttachmentTarget::Table,
+        Some(&path.table_id),
+        multipart,
+    )
+    .await
+}
+
+/// GET /workspace/domains/{domain}/attachments/{attachment_id} - Download an attachment
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/attachments/{attachment_id}",
+    tag = "Attachments",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("attachment_id" = Uuid, Path, description = "Attachment id")
+    ),
+    responses(
+        (status = 200, description = "File content", content_type = "application/octet-stream"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Attachment not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn download_attachment(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<AttachmentPath>,
+) -> Result<Response, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let index = load_attachment_index(&ctx.user_context.email, &path.domain);
+    let attachment = index.get(path.attachment_id).ok_or(StatusCode::NOT_FOUND)?;
+    let content = state.attachments.download(attachment).await.map_err(|e| {
+        warn!("Failed to read attachment {}: {}", attachment.id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(
+            header::CONTENT_TYPE,
+            HeaderValue::from_str(&attachment.content_type)
+                .unwrap_or(HeaderValue::from_static("application/octet-stream")),
+        )
+        .header(
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_str(&format!(
+                "attachment; filename=\"{}\"",
+                attachment.filename.replace('"', "")
+            ))
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        )
+        .header(header::X_CONTENT_TYPE_OPTIONS, "nosniff")
+        .body(Body::from(content))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// DELETE /workspace/domains/{domain}/attachments/{attachment_id} - Delete an attachment
+#[utoipa::path(
+    delete,
+    path = "/workspace/domains/{domain}/attachments/{attachment_id}",
+    tag = "Attachments",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("attachment_id" = Uuid, Path, description = "Attachment id")
+    ),
+    responses(
+        (status = 204, description = "Attachment deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Attachment not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_attachment(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<AttachmentPath>,
+) -> Result<StatusCode, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let email = &ctx.user_context.email;
+    let mut index = load_attachment_index(email, &path.domain);
+    let attachment = index
+        .remove(path.attachment_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    state.attachments.delete(&attachment).await.map_err(|e| {
+        warn!("Failed to delete attachment {}: {}", attachment.id, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    save_attachment_index(email, &path.domain, &index)?;
+    Ok(StatusCode::NO_CONTENT)
+}
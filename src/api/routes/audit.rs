@@ -1,6 +1,8 @@
 //! Audit trail routes.
 //!
 //! Provides endpoints for querying audit history of changes to domains, tables, and relationships.
+//! Handlers record changes through [`record_audit`] (or [`record_domain_audit`] for changes
+//! within a loaded domain), and `GET /workspace/domains/{domain}/audit` reads a domain's trail.
 
 use axum::{
     Router,
@@ -9,13 +11,25 @@ use axum::{
     response::Json,
     routing::get,
 };
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use super::app_state::AppState;
+use super::workspace::{DomainContext, DomainPath};
 use crate::routes::workspace;
+use crate::services::audit_log_service::{
+    AuditAction, AuditEntityType, AuditFilter, AuditLog, AuditRecord, JsonlAuditLog,
+    PostgresAuditLog,
+};
+use crate::services::local_mode::local_mode;
+use crate::services::request_audit_service::{RequestAuditEntry, RequestAuditFilter};
+use crate::storage::traits::UserContext;
+
+const DEFAULT_AUDIT_LIMIT: usize = 100;
+const MAX_AUDIT_LIMIT: usize = 1000;
 
 /// Create the audit router
 pub fn audit_router() -> Router<AppState> {
@@ -31,6 +45,7 @@ pub fn audit_router() -> Router<AppState> {
             get(get_workspace_history),
         )
         .route("/entries/{entry_id}", get(get_audit_entry))
+        .route("/requests", get(get_request_audit_log))
 }
 
 /// Query parameters for audit history
@@ -511,3 +526,248 @@ pub async fn get_audit_entry(
         Err(StatusCode::NOT_FOUND)
     }
 }
+
+/// Query parameters for the request audit log
+#[derive(Deserialize, IntoParams)]
+pub struct RequestAuditQueryParams {
+    /// Day to read (YYYY-MM-DD, default: today UTC)
+    date: Option<NaiveDate>,
+    /// Filter by user email
+    user: Option<String>,
+    /// Filter by domain name
+    domain: Option<String>,
+    /// Filter by HTTP method
+    method: Option<String>,
+    /// Limit number of results (default: 100)
+    #[param(default = 100)]
+    limit: Option<usize>,
+}
+
+/// Check whether an email is listed in `ADMIN_EMAILS` (comma-separated).
+///
+/// In local mode the single local user administers their own server.
+pub fn is_admin(email: &str) -> bool {
+    if local_mode().is_some_and(|local| local.email.eq_ignore_ascii_case(email)) {
+        return true;
+    }
+    std::env::var("ADMIN_EMAILS")
+        .map(|admins| {
+            admins
+                .split(',')
+                .any(|admin| admin.trim().eq_ignore_ascii_case(email))
+        })
+        .unwrap_or(false)
+}
+
+/// GET /audit/requests - Query the request audit log (admins only)
+#[utoipa::path(
+    get,
+    path = "/audit/requests",
+    tag = "Audit",
+    params(RequestAuditQueryParams),
+    responses(
+        (status = 200, description = "Audit entries retrieved successfully, newest first", body = Vec<RequestAuditEntry>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an administrator"),
+        (status = 404, description = "Request audit log is not enabled")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_request_audit_log(
+    State(state): State<AppState>,
+    Query(params): Query<RequestAuditQueryParams>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<RequestAuditEntry>>, StatusCode> {
+    let user_context = workspace::get_user_context(&state, &headers).await?;
+    if !is_admin(&user_context.email) {
+        tracing::warn!(
+            "Non-admin {} denied access to request audit log",
+            user_context.email
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+    if !state.request_audit.is_enabled() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+
+    let filter = RequestAuditFilter {
+        date: params.date,
+        user: params.user,
+        domain: params.domain,
+        method: params.method,
+        limit: params.limit.unwrap_or(100).min(1000),
+    };
+    let log = state.request_audit.clone();
+    let entries = tokio::task::spawn_blocking(move || log.query(&filter))
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .map_err(|e| {
+            tracing::error!("Failed to read request audit log: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(Json(entries))
+}
+
+/// Audit log of a workspace for the current storage mode
+fn audit_log(
+    state: &AppState,
+    user_context: &UserContext,
+    workspace_id: Uuid,
+    domain_id: Option<Uuid>,
+) -> Result<Box<dyn AuditLog>, StatusCode> {
+    if let Some(pool) = state.database() {
+        return Ok(Box::new(PostgresAuditLog::new(
+            pool.clone(),
+            workspace_id,
+            domain_id,
+        )));
+    }
+    Ok(Box::new(JsonlAuditLog::new(
+        workspace::get_user_workspace_path(&user_context.email)?,
+    )))
+}
+
+/// Record a change in the workspace's audit log.
+///
+/// `domain_id` is `None` for changes recorded after the domain is gone. The change has
+/// already been applied, so failures are logged rather than returned.
+#[allow(clippy::too_many_arguments)]
+pub async fn record_audit(
+    state: &AppState,
+    user_context: &UserContext,
+    workspace_id: Uuid,
+    domain: &str,
+    domain_id: Option<Uuid>,
+    entity: (AuditEntityType, Uuid),
+    action: AuditAction,
+    before: Option<Value>,
+    after: Option<Value>,
+) {
+    let (entity_type, entity_id) = entity;
+    let record = AuditRecord::new(
+        domain,
+        entity_type,
+        entity_id,
+        action,
+        (&user_context.email, user_context.user_id),
+        before,
+        after,
+    );
+    let result = match audit_log(state, user_context, workspace_id, domain_id) {
+        Ok(log) => log.append(&record).await,
+        Err(_) => return,
+    };
+    if let Err(e) = result {
+        tracing::warn!(
+            "Failed to record {} {} audit entry for {} in domain {}: {}",
+            entity_type.as_str(),
+            action.as_str(),
+            entity_id,
+            domain,
+            e
+        );
+    }
+}
+
+/// Record a change to an entity of a loaded domain in the audit log.
+pub async fn record_domain_audit(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+    entity: (AuditEntityType, Uuid),
+    action: AuditAction,
+    before: Option<Value>,
+    after: Option<Value>,
+) {
+    record_audit(
+        state,
+        &ctx.user_context,
+        ctx.workspace.id,
+        domain,
+        Some(ctx.domain_info.id),
+        entity,
+        action,
+        before,
+        after,
+    )
+    .await;
+}
+
+/// Query parameters for a domain's audit trail
+#[derive(Deserialize, IntoParams)]
+pub struct DomainAuditQueryParams {
+    /// Only changes made by this user (email)
+    actor: Option<String>,
+    /// Only changes to this kind of entity
+    entity_type: Option<AuditEntityType>,
+    /// Maximum number of entries (default: 100, max: 1000)
+    limit: Option<usize>,
+    /// Number of entries to skip (default: 0)
+    offset: Option<usize>,
+}
+
+/// A page of a domain's audit trail
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DomainAuditResponse {
+    /// Matching entries, newest first
+    pub entries: Vec<AuditRecord>,
+    /// Number of matching entries
+    pub total: usize,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+/// GET /workspace/domains/{domain}/audit - Read the domain's audit trail
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/audit",
+    tag = "Audit",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        DomainAuditQueryParams
+    ),
+    responses(
+        (status = 200, description = "Audit entries, newest first", body = DomainAuditResponse),
+        (status = 400, description = "Invalid entity_type"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_domain_audit(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    Query(params): Query<DomainAuditQueryParams>,
+) -> Result<Json<DomainAuditResponse>, StatusCode> {
+    let ctx = workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let filter = AuditFilter {
+        actor: params.actor.filter(|a| !a.trim().is_empty()),
+        entity_type: params.entity_type,
+        limit: params
+            .limit
+            .unwrap_or(DEFAULT_AUDIT_LIMIT)
+            .clamp(1, MAX_AUDIT_LIMIT),
+        offset: params.offset.unwrap_or_default(),
+    };
+
+    let (entries, total) = audit_log(
+        &state,
+        &ctx.user_context,
+        ctx.workspace.id,
+        Some(ctx.domain_info.id),
+    )?
+    .read(&path.domain, &filter)
+    .await
+    .map_err(|e| {
+        tracing::warn!("Failed to read audit log of domain {}: {}", path.domain, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(DomainAuditResponse {
+        entries,
+        total,
+        limit: filter.limit,
+        offset: filter.offset,
+    }))
+}
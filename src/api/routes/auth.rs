@@ -1,4 +1,7 @@
-//! Authentication routes for GitHub OAuth with JWT tokens.
+//! Authentication routes for OAuth sign-in (GitHub, Google, Microsoft) with JWT tokens.
+//!
+//! The provider is selected by the `{provider}` segment of the login and callback routes;
+//! `GET /auth/providers` lists the configured ones.
 //!
 //! Supports both web and desktop authentication flows:
 //! - Web: Direct OAuth redirect flow
@@ -19,7 +22,7 @@ use axum::{
     extract::{Path, Query, State},
     http::StatusCode,
     response::{Json, Redirect},
-    routing::{get, post},
+    routing::{delete, get, post},
 };
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
@@ -31,8 +34,17 @@ use uuid::Uuid;
 
 use super::app_state::AppState;
 use super::workspace;
+use super::workspace_provisioning::{load_provisioning_rules, provision_on_login};
+use crate::middleware::api_key::authenticate_api_key;
+use crate::services::api_key_service::{ApiKey, ApiKeyScope};
 use crate::services::jwt_service::{Claims, JwtService, SharedJwtService, TokenPair};
-use crate::services::oauth_service::{GitHubEmail, OAuthService};
+use crate::services::local_mode::{LocalMode, local_mode};
+use crate::services::oauth_service::{GitHubEmail, OAuthProvider, OAuthProviders};
+use crate::services::session_expiry_service::SessionExpiry;
+use crate::services::token_revocation_service::{
+    JsonlRevocationList, PostgresRevocationList, RevocationList, RevokedRefreshToken,
+};
+use crate::storage::traits::UserContext;
 use url::Url;
 
 /// OAuth session storage - keeps track of active sessions for revocation
@@ -51,11 +63,16 @@ pub type OAuthStateStore = Arc<Mutex<HashMap<String, OAuthStateEntry>>>;
 /// One-time auth code exchange store: code -> token payload (short-lived).
 pub type TokenExchangeStore = Arc<Mutex<HashMap<String, TokenExchangeEntry>>>;
 
+/// Minutes after it was issued that an exchange code stays valid once the user is asked to
+/// select an email
+const EMAIL_SELECTION_MINUTES: i64 = 10;
+
 #[derive(Clone, Debug)]
 pub struct TokenExchangeEntry {
     pub tokens: TokenPair,
     pub emails: Vec<GitHubEmail>,
     pub select_email: bool,
+    pub issued_at: chrono::DateTime<chrono::Utc>,
     pub expires_at: chrono::DateTime<chrono::Utc>,
     pub github_id: u64,
     pub github_username: String,
@@ -71,6 +88,8 @@ pub enum OAuthSource {
 #[derive(Clone, Debug)]
 pub struct OAuthStateEntry {
     pub source: OAuthSource,
+    /// Provider the login was started with; the callback must come from the same one
+    pub provider: &'static str,
     #[allow(dead_code)]
     pub created_at: chrono::DateTime<chrono::Utc>,
     /// Custom redirect URI provided by the client (optional)
@@ -117,6 +136,56 @@ pub fn new_token_exchange_store() -> TokenExchangeStore {
     Arc::new(Mutex::new(HashMap::new()))
 }
 
+/// Record activity on an in-memory session, sliding its expiry. None if the session is
+/// unknown or has expired; expired sessions are removed.
+pub async fn touch_session(store: &SessionStore, session_id: &str) -> Option<SessionMetadata> {
+    let mut sessions = store.lock().await;
+    let session = sessions.get_mut(session_id)?;
+    if SessionExpiry::from_env().touch(session, chrono::Utc::now()) {
+        return Some(session.clone());
+    }
+    sessions.remove(session_id);
+    info!("In-memory session {} expired", session_id);
+    None
+}
+
+/// Verified emails of a session, from the database or the in-memory store (none if the
+/// session is unknown).
+pub(crate) async fn session_verified_emails(app_state: &AppState, session_id: &str) -> Vec<String> {
+    if let (Some(store), Ok(session_uuid)) =
+        (app_state.db_session_store(), Uuid::parse_str(session_id))
+        && let Ok(Some(session)) = store.get_session(session_uuid).await
+    {
+        return (session.emails.into_iter())
+            .filter(|e| e.verified)
+            .map(|e| e.email)
+            .collect();
+    }
+    let sessions = app_state.session_store.lock().await;
+    sessions
+        .get(session_id)
+        .map(|session| {
+            (session.emails.iter())
+                .filter(|e| e.verified)
+                .map(|e| e.email.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Periodically remove expired sessions from the in-memory store
+pub async fn start_session_prune_task(store: SessionStore) {
+    let expiry = SessionExpiry::from_env();
+    let mut interval = tokio::time::interval(expiry.prune_interval);
+    loop {
+        interval.tick().await;
+        let pruned = expiry.prune(&mut *store.lock().await, chrono::Utc::now());
+        if pruned > 0 {
+            info!("Pruned {} expired in-memory session(s)", pruned);
+        }
+    }
+}
+
 /// Pending authentication for desktop apps
 #[derive(Clone, Debug)]
 pub struct PendingAuth {
@@ -138,15 +207,22 @@ pub struct OAuthCallbackQuery {
     state: Option<String>,
 }
 
-/// Query parameters for GitHub OAuth login initiation
+/// Query parameters for OAuth login initiation
 #[derive(Deserialize, ToSchema)]
-pub struct GitHubLoginQuery {
+pub struct OAuthLoginQuery {
     /// Optional redirect URI to use after OAuth callback completion
     /// If not provided, uses FRONTEND_URL environment variable or default
     #[serde(default)]
     redirect_uri: Option<String>,
 }
 
+/// Response for GET /auth/providers
+#[derive(Serialize, ToSchema)]
+pub struct OAuthProvidersResponse {
+    /// Names of the configured providers, as used in `/auth/{provider}/login`
+    providers: Vec<String>,
+}
+
 /// Response for desktop auth initiation
 #[derive(Serialize, ToSchema)]
 pub struct DesktopAuthInitResponse {
@@ -185,8 +261,9 @@ pub struct AuthStatusResponse {
 pub struct SelectEmailRequest {
     email: String,
     /// Optional exchange code - allows selecting email without Bearer token
-    /// This is used when the initial exchange returned empty tokens with select_email=true
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// This is used when the initial exchange returned empty tokens with select_email=true;
+    /// also accepted as `session_code`, as returned by the exchange
+    #[serde(alias = "session_code", skip_serializing_if = "Option::is_none")]
     code: Option<String>,
 }
 
@@ -212,6 +289,9 @@ pub struct RefreshTokenRequest {
 #[derive(Deserialize, ToSchema)]
 pub struct ExchangeAuthCodeRequest {
     code: String,
+    /// Email to issue the tokens for, when the account has several (same as `email`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    selected_email: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     email: Option<String>,
 }
@@ -225,6 +305,10 @@ pub struct ExchangeAuthCodeResponse {
     token_type: String,
     emails: Vec<GitHubEmail>,
     select_email: bool,
+    /// Code completing the email selection, sent as `code` to `/auth/select-email` or back
+    /// to `/auth/exchange` with `selected_email`; only set when `select_email` is true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_code: Option<String>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -236,10 +320,38 @@ pub struct RefreshTokenResponse {
     token_type: String,
 }
 
+/// An active session of the signed-in user
+#[derive(Serialize, ToSchema)]
+pub struct SessionSummary {
+    session_id: String,
+    github_username: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    selected_email: Option<String>,
+    created_at: chrono::DateTime<chrono::Utc>,
+    last_activity: chrono::DateTime<chrono::Utc>,
+    expires_at: chrono::DateTime<chrono::Utc>,
+    /// Whether the request was made with this session
+    current: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SessionsResponse {
+    sessions: Vec<SessionSummary>,
+}
+
 /// Response for GET /api/v1/auth/me endpoint
 #[derive(Serialize, ToSchema)]
 pub struct UserInfoResponse {
     user: UserInfo,
+    /// Verified email addresses of the user's account
+    verified_emails: Vec<String>,
+    /// Session the request's token belongs to (absent in local mode)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_id: Option<String>,
+    /// Access granted to the request, using the API key scope names; sessions have `full`
+    scopes: Vec<String>,
+    /// Workspaces the user can access
+    workspaces: Vec<workspace::WorkspaceResponse>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -257,7 +369,7 @@ pub struct AuthState {
     pub pending_auth_store: PendingAuthStore,
     pub oauth_state_store: OAuthStateStore,
     pub token_exchange_store: TokenExchangeStore,
-    pub oauth_service: Arc<OAuthService>,
+    pub oauth_providers: OAuthProviders,
     pub jwt_service: SharedJwtService,
     pub app_state: AppState,
 }
@@ -271,7 +383,7 @@ impl FromRef<AuthState> for AppState {
 /// Create the auth router
 pub fn auth_router(
     session_store: SessionStore,
-    oauth_service: Arc<OAuthService>,
+    oauth_providers: OAuthProviders,
     app_state: AppState,
 ) -> Router<AppState> {
     let jwt_service = Arc::new(JwtService::from_env());
@@ -282,49 +394,99 @@ pub fn auth_router(
         pending_auth_store: new_pending_auth_store(),
         oauth_state_store: new_oauth_state_store(),
         token_exchange_store: new_token_exchange_store(),
-        oauth_service,
+        oauth_providers,
         jwt_service,
         app_state: app_state.clone(),
     };
 
     Router::new()
+        .route("/providers", get(list_oauth_providers))
         // Web OAuth flow
-        .route("/github/login", get(initiate_github_login))
-        .route("/github/callback", get(handle_github_callback))
+        .route("/{provider}/login", get(initiate_oauth_login))
+        .route("/{provider}/callback", get(handle_oauth_callback))
         // Desktop OAuth flow
-        .route("/github/login/desktop", get(initiate_desktop_github_login))
+        .route(
+            "/{provider}/login/desktop",
+            get(initiate_desktop_oauth_login),
+        )
         .route("/poll/{state_id}", get(poll_auth_status))
         // Web auth code exchange (avoid tokens-in-URL)
         .route("/exchange", post(exchange_auth_code))
         // Token management
         .route("/refresh", post(refresh_token))
+        .route("/revoke", post(revoke_refresh_token))
         // Common endpoints
         .route("/status", get(get_auth_status))
         .route("/select-email", post(select_email))
         .route("/logout", post(logout))
+        // Active sessions of the signed-in user
+        .route("/sessions", get(list_sessions))
+        .route("/sessions/{session_id}", delete(revoke_user_session))
         // New /api/v1/auth/me endpoint
         .route("/me", get(get_current_user))
+        // Double-submit CSRF token for cookie-based flows
+        .route(
+            "/csrf-token",
+            get(crate::middleware::csrf::issue_csrf_token),
+        )
         .with_state(auth_state)
 }
 
-/// GET /auth/github/login - Initiate GitHub OAuth flow (web - direct redirect)
+/// Look up a configured OAuth provider; 404 for unknown or unconfigured ones.
+fn oauth_provider(
+    auth_state: &AuthState,
+    name: &str,
+) -> Result<Arc<dyn OAuthProvider>, StatusCode> {
+    auth_state.oauth_providers.get(name).ok_or_else(|| {
+        warn!("OAuth provider not configured: {}", name);
+        StatusCode::NOT_FOUND
+    })
+}
+
+/// GET /auth/providers - List the configured OAuth providers
+#[utoipa::path(
+    get,
+    path = "/auth/providers",
+    tag = "Authentication",
+    responses(
+        (status = 200, description = "Configured OAuth providers", body = OAuthProvidersResponse)
+    )
+)]
+pub async fn list_oauth_providers(
+    State(auth_state): State<AuthState>,
+) -> Json<OAuthProvidersResponse> {
+    Json(OAuthProvidersResponse {
+        providers: auth_state
+            .oauth_providers
+            .names()
+            .into_iter()
+            .map(String::from)
+            .collect(),
+    })
+}
+
+/// GET /auth/{provider}/login - Initiate OAuth flow (web - direct redirect)
 #[utoipa::path(
     get,
-    path = "/auth/github/login",
+    path = "/auth/{provider}/login",
     tag = "Authentication",
     params(
+        ("provider" = String, Path, description = "OAuth provider: github, google, microsoft or oidc"),
         ("redirect_uri" = Option<String>, Query, description = "Optional redirect URI after OAuth completion")
     ),
     responses(
-        (status = 302, description = "Redirect to GitHub OAuth authorization page"),
+        (status = 302, description = "Redirect to the provider's OAuth authorization page"),
         (status = 400, description = "Bad request - invalid redirect_uri"),
+        (status = 404, description = "Provider not configured"),
         (status = 500, description = "Internal server error")
     )
 )]
-pub async fn initiate_github_login(
+pub async fn initiate_oauth_login(
     State(auth_state): State<AuthState>,
-    Query(params): Query<GitHubLoginQuery>,
+    Path(provider): Path<String>,
+    Query(params): Query<OAuthLoginQuery>,
 ) -> Result<Redirect, StatusCode> {
+    let provider = oauth_provider(&auth_state, &provider)?;
     // Validate redirect_uri if provided
     let redirect_uri = if let Some(ref uri) = params.redirect_uri {
         if !validate_redirect_uri(uri) {
@@ -341,39 +503,46 @@ pub async fn initiate_github_login(
         csrf_state.clone(),
         OAuthStateEntry {
             source: OAuthSource::Web,
+            provider: provider.name(),
             created_at: chrono::Utc::now(),
             redirect_uri,
         },
     );
 
-    match auth_state
-        .oauth_service
-        .get_authorize_url_with_state(&csrf_state)
+    match provider
+        .authorize_url(&csrf_state, load_provisioning_rules().uses_groups())
+        .await
     {
         Ok(url) => {
-            info!("Initiating GitHub OAuth flow (web)");
+            info!("Initiating {} OAuth flow (web)", provider.name());
             Ok(Redirect::temporary(&url))
         }
         Err(e) => {
-            warn!("Failed to generate GitHub OAuth URL: {}", e);
+            warn!("Failed to generate {} OAuth URL: {}", provider.name(), e);
             Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
 }
 
-/// GET /auth/github/login/desktop - Initiate GitHub OAuth flow for desktop apps
+/// GET /auth/{provider}/login/desktop - Initiate OAuth flow for desktop apps
 #[utoipa::path(
     get,
-    path = "/auth/github/login/desktop",
+    path = "/auth/{provider}/login/desktop",
     tag = "Authentication",
+    params(
+        ("provider" = String, Path, description = "OAuth provider: github, google, microsoft or oidc")
+    ),
     responses(
         (status = 200, description = "Desktop OAuth flow initiated successfully", body = DesktopAuthInitResponse),
+        (status = 404, description = "Provider not configured"),
         (status = 500, description = "Internal server error")
     )
 )]
-pub async fn initiate_desktop_github_login(
+pub async fn initiate_desktop_oauth_login(
     State(auth_state): State<AuthState>,
+    Path(provider): Path<String>,
 ) -> Result<Json<DesktopAuthInitResponse>, StatusCode> {
+    let provider = oauth_provider(&auth_state, &provider)?;
     let state_id = uuid::Uuid::new_v4().to_string();
     let csrf_state = Uuid::new_v4().to_string();
 
@@ -383,14 +552,15 @@ pub async fn initiate_desktop_github_login(
             source: OAuthSource::Desktop {
                 state_id: state_id.clone(),
             },
+            provider: provider.name(),
             created_at: chrono::Utc::now(),
             redirect_uri: None, // Desktop flow doesn't use redirect_uri
         },
     );
 
-    match auth_state
-        .oauth_service
-        .get_authorize_url_with_state(&csrf_state)
+    match provider
+        .authorize_url(&csrf_state, load_provisioning_rules().uses_groups())
+        .await
     {
         Ok(auth_url) => {
             let pending = PendingAuth {
@@ -410,14 +580,19 @@ pub async fn initiate_desktop_github_login(
                 .insert(state_id.clone(), pending);
 
             info!(
-                "Initiating GitHub OAuth flow (desktop), state_id: {}",
+                "Initiating {} OAuth flow (desktop), state_id: {}",
+                provider.name(),
                 state_id
             );
 
             Ok(Json(DesktopAuthInitResponse { state_id, auth_url }))
         }
         Err(e) => {
-            warn!("Failed to generate GitHub OAuth URL for desktop: {}", e);
+            warn!(
+                "Failed to generate {} OAuth URL for desktop: {}",
+                provider.name(),
+                e
+            );
             Err(StatusCode::INTERNAL_SERVER_ERROR)
         }
     }
@@ -499,8 +674,8 @@ pub async fn exchange_auth_code(
 
     // Get entry without removing it yet - we'll remove it only after successful token generation
     let mut store = auth_state.token_exchange_store.lock().await;
-    let entry = match store.get(&request.code) {
-        Some(e) => e.clone(),
+    let entry = match store.get_mut(&request.code) {
+        Some(e) => e,
         None => return Err(StatusCode::BAD_REQUEST),
     };
 
@@ -509,26 +684,34 @@ pub async fn exchange_auth_code(
     }
 
     // Handle email selection when select_email=true
+    let requested_email = request.selected_email.or(request.email);
+    if entry.select_email && entry.emails.len() > 1 && requested_email.is_none() {
+        // Return emails for selection without tokens. Don't remove the code yet - it
+        // completes the selection, so give the user time to choose. The deadline is fixed from
+        // when the code was issued, so repeated calls don't keep the code alive.
+        entry.expires_at = entry.issued_at + chrono::Duration::minutes(EMAIL_SELECTION_MINUTES);
+        if chrono::Utc::now() > entry.expires_at {
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        let emails = entry.emails.clone();
+        drop(store);
+        return Ok(Json(ExchangeAuthCodeResponse {
+            access_token: String::new(),
+            refresh_token: String::new(),
+            access_token_expires_at: 0,
+            refresh_token_expires_at: 0,
+            token_type: "Bearer".to_string(),
+            emails,
+            select_email: true,
+            session_code: Some(request.code),
+        }));
+    }
+    let entry = entry.clone();
+
     let (tokens, selected_email, should_remove_code) =
         if entry.select_email && entry.emails.len() > 1 {
             // Email selection required
-            let email = match request.email {
-                Some(e) => e.trim().to_string(),
-                None => {
-                    // Return emails for selection without tokens
-                    // Don't remove the code yet - allow re-exchange with email
-                    drop(store);
-                    return Ok(Json(ExchangeAuthCodeResponse {
-                        access_token: String::new(),
-                        refresh_token: String::new(),
-                        access_token_expires_at: 0,
-                        refresh_token_expires_at: 0,
-                        token_type: "Bearer".to_string(),
-                        emails: entry.emails,
-                        select_email: true,
-                    }));
-                }
-            };
+            let email = requested_email.unwrap_or_default().trim().to_string();
 
             // Validate email is in verified emails list
             let email_valid = entry.emails.iter().any(|e| e.email == email && e.verified);
@@ -552,21 +735,7 @@ pub async fn exchange_auth_code(
                     StatusCode::INTERNAL_SERVER_ERROR
                 })?;
 
-            // Update session selected_email in memory store
-            let mut sessions = auth_state.session_store.lock().await;
-            if let Some(session) = sessions.get_mut(&entry.session_id) {
-                session.selected_email = Some(email.clone());
-            }
-            drop(sessions);
-
-            // Also update database session if available
-            if let Some(db_session_store) = auth_state.app_state.db_session_store()
-                && let Ok(session_uuid) = Uuid::parse_str(&entry.session_id)
-            {
-                let _ = db_session_store
-                    .update_selected_email(session_uuid, &email)
-                    .await;
-            }
+            record_selected_email(&auth_state, &entry.session_id, &email).await;
 
             (new_tokens, Some(email), true) // Remove code after successful token generation
         } else {
@@ -606,9 +775,28 @@ pub async fn exchange_auth_code(
             entry.emails
         },
         select_email: selected_email.is_none() && entry.select_email,
+        session_code: None,
     }))
 }
 
+/// Record the email a session selected, in memory and in the database if there is one
+async fn record_selected_email(auth_state: &AuthState, session_id: &str, email: &str) {
+    let mut sessions = auth_state.session_store.lock().await;
+    if let Some(session) = sessions.get_mut(session_id) {
+        session.selected_email = Some(email.to_string());
+    }
+    drop(sessions);
+
+    if let Some(db_session_store) = auth_state.app_state.db_session_store()
+        && let Ok(session_uuid) = Uuid::parse_str(session_id)
+        && let Err(e) = db_session_store
+            .update_selected_email(session_uuid, email)
+            .await
+    {
+        warn!("Failed to update selected email in database: {}", e);
+    }
+}
+
 fn legacy_token_redirect_enabled() -> bool {
     match std::env::var("AUTH_LEGACY_TOKEN_REDIRECT") {
         Ok(v) => matches!(v.to_lowercase().as_str(), "1" | "true" | "yes" | "on"),
@@ -674,22 +862,28 @@ fn validate_redirect_uri(uri: &str) -> bool {
     false
 }
 
-/// GET /auth/github/callback - Handle GitHub OAuth callback
+/// GET /auth/{provider}/callback - Handle OAuth callback
 ///
 /// This handler supports both in-memory (file storage) and database-backed (PostgreSQL) sessions.
 #[utoipa::path(
     get,
-    path = "/auth/github/callback",
+    path = "/auth/{provider}/callback",
     tag = "Authentication",
+    params(
+        ("provider" = String, Path, description = "OAuth provider: github, google, microsoft or oidc")
+    ),
     responses(
         (status = 302, description = "Redirect to frontend with auth code or error"),
-        (status = 400, description = "Bad request - invalid callback parameters")
+        (status = 400, description = "Bad request - invalid callback parameters"),
+        (status = 404, description = "Provider not configured")
     )
 )]
-pub async fn handle_github_callback(
+pub async fn handle_oauth_callback(
     State(auth_state): State<AuthState>,
+    Path(provider): Path<String>,
     Query(params): Query<OAuthCallbackQuery>,
 ) -> Result<Redirect, StatusCode> {
+    let provider = oauth_provider(&auth_state, &provider)?;
     let code = match params.code.as_ref() {
         Some(c) if !c.is_empty() => c.as_str(),
         _ => return Err(StatusCode::BAD_REQUEST),
@@ -710,10 +904,21 @@ pub async fn handle_github_callback(
             return Err(StatusCode::BAD_REQUEST);
         }
     };
-    info!("Received GitHub OAuth callback (validated state)");
+    if entry.provider != provider.name() {
+        warn!(
+            "OAuth callback from {} for a login started with {}",
+            provider.name(),
+            entry.provider
+        );
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    info!(
+        "Received {} OAuth callback (validated state)",
+        provider.name()
+    );
 
-    // Exchange code for GitHub access token
-    let github_access_token = match auth_state.oauth_service.exchange_code(code).await {
+    // Exchange code for the provider's access token
+    let github_access_token = match provider.exchange_code(code, state).await {
         Ok(token) => token,
         Err(e) => {
             warn!("Failed to exchange OAuth code: {}", e);
@@ -721,19 +926,48 @@ pub async fn handle_github_callback(
         }
     };
 
-    // Fetch user info from GitHub
-    let (github_id, username, emails) = match auth_state
-        .oauth_service
-        .fetch_user_info(&github_access_token)
-        .await
-    {
-        Ok(info) => info,
+    // Fetch user info from the provider
+    let (github_id, username, emails) = match provider.fetch_user(&github_access_token).await {
+        Ok(user) => (user.id, user.username, user.emails),
         Err(e) => {
-            warn!("Failed to fetch user info from GitHub: {}", e);
+            warn!("Failed to fetch user info from {}: {}", provider.name(), e);
             return Err(StatusCode::INTERNAL_SERVER_ERROR);
         }
     };
 
+    // Place first-time users in team workspaces according to the provisioning rules
+    let provisioning_rules = load_provisioning_rules();
+    if !provisioning_rules.rules.is_empty() {
+        let groups = if provisioning_rules.uses_groups() {
+            provider
+                .fetch_groups(&github_access_token)
+                .await
+                .unwrap_or_else(|e| {
+                    warn!(
+                        "Failed to fetch {} groups for {}: {}",
+                        provider.name(),
+                        username,
+                        e
+                    );
+                    Vec::new()
+                })
+        } else {
+            Vec::new()
+        };
+        let verified: Vec<String> = emails
+            .iter()
+            .filter(|e| e.verified)
+            .map(|e| e.email.clone())
+            .collect();
+        provision_on_login(
+            &auth_state.app_state,
+            &provisioning_rules,
+            &verified,
+            &groups,
+        )
+        .await;
+    }
+
     // Generate session ID
     let session_uuid = Uuid::new_v4();
     let session_id = session_uuid.to_string();
@@ -744,7 +978,7 @@ pub async fn handle_github_callback(
         .find(|e| e.primary && e.verified)
         .or_else(|| emails.iter().find(|e| e.verified))
         .map(|e| e.email.clone())
-        .unwrap_or_else(|| format!("{}@github", username));
+        .unwrap_or_else(|| format!("{}@{}", username, provider.name()));
 
     let selected_email = if emails.len() == 1 {
         Some(primary_email.clone())
@@ -795,15 +1029,18 @@ pub async fn handle_github_callback(
             })?;
 
         info!(
-            "Created database session for GitHub user: {} (session: {}, user_id: {})",
-            username, session_id, user_id
+            "Created database session for {} user: {} (session: {}, user_id: {})",
+            provider.name(),
+            username,
+            session_id,
+            user_id
         );
     } else {
         // In-memory storage mode (legacy)
         let user_id = crate::routes::workspace::get_or_create_file_user_id(&primary_email)
             .unwrap_or_else(|_| Uuid::new_v4());
         let now = chrono::Utc::now();
-        let expires_at = now + chrono::Duration::days(7); // 7 days expiry
+        let expires_at = SessionExpiry::from_env().expires_at(now, now);
         let session = SessionMetadata {
             user_id,
             github_id,
@@ -822,8 +1059,10 @@ pub async fn handle_github_callback(
             .await
             .insert(session_id.clone(), session);
         info!(
-            "Created in-memory session for GitHub user: {} (session: {})",
-            username, session_id
+            "Created in-memory session for {} user: {} (session: {})",
+            provider.name(),
+            username,
+            session_id
         );
     }
 
@@ -870,13 +1109,15 @@ pub async fn handle_github_callback(
 
         // Secure mode: store tokens server-side and redirect with short-lived one-time code.
         let exchange_code = Uuid::new_v4().to_string();
+        let issued_at = chrono::Utc::now();
         auth_state.token_exchange_store.lock().await.insert(
             exchange_code.clone(),
             TokenExchangeEntry {
                 tokens: tokens.clone(),
                 emails: emails.clone(),
                 select_email: emails.len() > 1,
-                expires_at: chrono::Utc::now() + chrono::Duration::minutes(2),
+                issued_at,
+                expires_at: issued_at + chrono::Duration::minutes(2),
                 github_id,
                 github_username: username.clone(),
                 session_id: session_id.clone(),
@@ -890,9 +1131,49 @@ pub async fn handle_github_callback(
     }
 }
 
+/// Revocation list of refresh tokens in the storage backend
+fn refresh_token_revocations(
+    auth_state: &AuthState,
+) -> Result<Box<dyn RevocationList>, StatusCode> {
+    if let Some(pool) = auth_state.app_state.database() {
+        return Ok(Box::new(PostgresRevocationList::new(pool.clone())));
+    }
+    let dir = workspace::get_workspace_data_dir().map_err(|e| {
+        warn!("Failed to locate refresh token revocation list: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(Box::new(JsonlRevocationList::new(&dir)))
+}
+
+/// Revoke a session, so none of its tokens can be refreshed.
+///
+/// Supports both in-memory and database-backed sessions.
+async fn revoke_session(auth_state: &AuthState, session_id: &str) {
+    if let Some(db_session_store) = auth_state.app_state.db_session_store() {
+        // Database-backed session revocation
+        if let Ok(uuid) = Uuid::parse_str(session_id) {
+            if let Err(e) = db_session_store.revoke_session(uuid).await {
+                warn!("Failed to revoke session in database: {}", e);
+            } else {
+                info!("Revoked session in database: {}", session_id);
+            }
+        }
+    } else {
+        // In-memory session revocation (legacy)
+        auth_state
+            .revoked_tokens
+            .lock()
+            .await
+            .insert(session_id.to_string());
+        auth_state.session_store.lock().await.remove(session_id);
+        info!("Revoked in-memory session: {}", session_id);
+    }
+}
+
 /// POST /auth/refresh - Refresh access token using refresh token
 ///
-/// Supports both in-memory and database-backed session validation.
+/// Supports both in-memory and database-backed session validation. The refresh token is
+/// rotated: it goes on the revocation list, and presenting it again revokes the session.
 #[utoipa::path(
     post,
     path = "/auth/refresh",
@@ -900,12 +1181,22 @@ pub async fn handle_github_callback(
     request_body = RefreshTokenRequest,
     responses(
         (status = 200, description = "Token refreshed successfully", body = RefreshTokenResponse),
-        (status = 401, description = "Unauthorized - invalid or expired refresh token")
+        (status = 401, description = "Unauthorized - invalid, expired, revoked or reused refresh token")
     )
 )]
 async fn refresh_token(
     State(auth_state): State<AuthState>,
     Json(request): Json<RefreshTokenRequest>,
+) -> Result<Json<RefreshTokenResponse>, StatusCode> {
+    let revocations = refresh_token_revocations(&auth_state)?;
+    rotate_refresh_token(&auth_state, revocations.as_ref(), request).await
+}
+
+/// Issue a new token pair for a refresh token, putting the presented one on `revocations`.
+async fn rotate_refresh_token(
+    auth_state: &AuthState,
+    revocations: &dyn RevocationList,
+    request: RefreshTokenRequest,
 ) -> Result<Json<RefreshTokenResponse>, StatusCode> {
     // Validate and decode refresh token
     let claims = auth_state
@@ -957,18 +1248,34 @@ async fn refresh_token(
         }
         drop(revoked);
 
-        let sessions = auth_state.session_store.lock().await;
-        if !sessions.contains_key(&claims.session_id) {
-            warn!("Session not found for refresh: {}", claims.session_id);
+        // Refreshing is activity, so it renews the session
+        if touch_session(&auth_state.session_store, &claims.session_id)
+            .await
+            .is_none()
+        {
+            warn!(
+                "Session not found or expired for refresh: {}",
+                claims.session_id
+            );
             return Err(StatusCode::UNAUTHORIZED);
         }
-        drop(sessions);
+    }
 
-        // Update last activity in memory
-        let mut sessions = auth_state.session_store.lock().await;
-        if let Some(session) = sessions.get_mut(&claims.session_id) {
-            session.last_activity = chrono::Utc::now();
-        }
+    // Rotate: the presented refresh token can't be used again
+    let rotated = revocations
+        .revoke(RevokedRefreshToken::new(&request.refresh_token, &claims))
+        .await
+        .map_err(|e| {
+            warn!("Failed to revoke rotated refresh token: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if !rotated {
+        warn!(
+            "Rotated refresh token reused, revoking session: {}",
+            claims.session_id
+        );
+        revoke_session(auth_state, &claims.session_id).await;
+        return Err(StatusCode::UNAUTHORIZED);
     }
 
     // Generate new token pair
@@ -1007,6 +1314,21 @@ pub async fn get_auth_status(
     State(auth_state): State<AuthState>,
     headers: axum::http::HeaderMap,
 ) -> Result<Json<AuthStatusResponse>, StatusCode> {
+    // Local mode is always signed in as the local user
+    if let Some(local) = local_mode() {
+        return Ok(Json(AuthStatusResponse {
+            authenticated: true,
+            github_username: Some(local.name.clone()),
+            emails: vec![GitHubEmail {
+                email: local.email.clone(),
+                verified: true,
+                primary: true,
+            }],
+            selected_email: Some(local.email.clone()),
+            token_expires_at: None,
+        }));
+    }
+
     // Try to get token from Authorization header (preferred) or x-session-id (legacy)
     let claims = extract_and_validate_token(&auth_state, &headers).await;
 
@@ -1075,8 +1397,9 @@ pub async fn get_auth_status(
             }
             drop(revoked);
 
-            let sessions = auth_state.session_store.lock().await;
-            if let Some(session) = sessions.get(&claims.session_id) {
+            if let Some(session) =
+                touch_session(&auth_state.session_store, &claims.session_id).await
+            {
                 return Ok(Json(AuthStatusResponse {
                     authenticated: true,
                     github_username: Some(session.github_username.clone()),
@@ -1097,7 +1420,8 @@ pub async fn get_auth_status(
     }))
 }
 
-/// GET /api/v1/auth/me - Get current authenticated user information
+/// GET /api/v1/auth/me - Get the authenticated user with their verified emails, session,
+/// granted scopes and workspaces
 #[utoipa::path(
     get,
     path = "/auth/me",
@@ -1112,10 +1436,69 @@ pub async fn get_current_user(
     State(auth_state): State<AuthState>,
     headers: axum::http::HeaderMap,
 ) -> Result<Json<UserInfoResponse>, StatusCode> {
-    // Extract and validate token
-    let claims = extract_and_validate_token(&auth_state, &headers)
-        .await
-        .ok_or(StatusCode::UNAUTHORIZED)?;
+    let authenticated = if let Some(local) = local_mode() {
+        Authenticated::Local(local.clone())
+    } else if let Some(key) = authenticate_api_key(&auth_state.app_state, &headers).await? {
+        Authenticated::ApiKey(key)
+    } else {
+        // Extract and validate token
+        let claims = extract_and_validate_token(&auth_state, &headers)
+            .await
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+        Authenticated::Token(claims)
+    };
+    Ok(Json(user_info(&auth_state, authenticated).await?))
+}
+
+/// What a `/auth/me` request authenticates with
+enum Authenticated {
+    Local(LocalMode),
+    /// API key or personal access token
+    ApiKey(ApiKey),
+    Token(Claims),
+}
+
+/// The user context `/auth/me` returns for an authenticated request
+async fn user_info(
+    auth_state: &AuthState,
+    authenticated: Authenticated,
+) -> Result<UserInfoResponse, StatusCode> {
+    let session_scopes = vec![ApiKeyScope::Full.as_str().to_string()];
+    let claims = match authenticated {
+        Authenticated::Local(local) => {
+            let user_context = local.user_context();
+            return Ok(UserInfoResponse {
+                user: UserInfo {
+                    id: user_context.user_id.to_string(),
+                    name: local.name.clone(),
+                    email: local.email.clone(),
+                },
+                verified_emails: vec![local.email.clone()],
+                session_id: None,
+                scopes: session_scopes,
+                workspaces: workspace::accessible_workspaces(&auth_state.app_state, &user_context)
+                    .await?,
+            });
+        }
+        // Keys act as their owner with the key's own scopes
+        Authenticated::ApiKey(key) => {
+            let user_context = key.user_context();
+            return Ok(UserInfoResponse {
+                user: UserInfo {
+                    id: key.owner_id.to_string(),
+                    name: key.owner_email.clone(),
+                    email: key.owner_email.clone(),
+                },
+                // The owner's login email, picked from their verified emails
+                verified_emails: vec![key.owner_email.clone()],
+                session_id: None,
+                scopes: key.scopes.iter().map(|s| s.as_str().to_string()).collect(),
+                workspaces: workspace::accessible_workspaces(&auth_state.app_state, &user_context)
+                    .await?,
+            });
+        }
+        Authenticated::Token(claims) => claims,
+    };
 
     // Check if session is revoked
     let revoked = auth_state.revoked_tokens.lock().await;
@@ -1131,7 +1514,8 @@ pub async fn get_current_user(
     };
 
     // Try database session first, then in-memory
-    let (github_username, email) =
+    let mut db_user_id = None;
+    let (github_username, email, emails) =
         if let Some(db_session_store) = auth_state.app_state.db_session_store() {
             match db_session_store.get_session(session_uuid).await {
                 Ok(Some(session)) => {
@@ -1139,12 +1523,17 @@ pub async fn get_current_user(
                     if session.revoked_at.is_some() || session.expires_at < chrono::Utc::now() {
                         return Err(StatusCode::UNAUTHORIZED);
                     }
+                    db_user_id = Some(session.user_id);
                     let email = session.selected_email.unwrap_or_else(|| claims.sub.clone());
-                    (session.github_username, email)
+                    (session.github_username, email, session.emails)
                 }
                 _ => {
                     // Fall back to JWT claims
-                    (claims.github_username.clone(), claims.sub.clone())
+                    (
+                        claims.github_username.clone(),
+                        claims.sub.clone(),
+                        Vec::new(),
+                    )
                 }
             }
         } else {
@@ -1158,23 +1547,53 @@ pub async fn get_current_user(
                     .selected_email
                     .clone()
                     .unwrap_or_else(|| claims.sub.clone());
-                (session.github_username.clone(), email)
+                (
+                    session.github_username.clone(),
+                    email,
+                    session.emails.clone(),
+                )
             } else {
                 // Fall back to JWT claims
-                (claims.github_username.clone(), claims.sub.clone())
+                (
+                    claims.github_username.clone(),
+                    claims.sub.clone(),
+                    Vec::new(),
+                )
             }
         };
 
+    let mut verified_emails: Vec<String> = emails
+        .into_iter()
+        .filter(|e| e.verified)
+        .map(|e| e.email)
+        .collect();
+    // Without a stored session, the token's subject is the email known to be verified: it was
+    // picked from the verified emails at login
+    if verified_emails.is_empty() {
+        verified_emails.push(claims.sub.clone());
+    }
+
     // Generate user ID from email (consistent with existing pattern)
     let user_id = uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_DNS, email.as_bytes());
+    // Same user as the workspace routes resolve for the token
+    let user_context = UserContext {
+        user_id: db_user_id.unwrap_or_else(|| {
+            uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_DNS, claims.sub.as_bytes())
+        }),
+        email: claims.sub.clone(),
+    };
 
-    Ok(Json(UserInfoResponse {
+    Ok(UserInfoResponse {
         user: UserInfo {
             id: user_id.to_string(),
             name: github_username,
             email,
         },
-    }))
+        verified_emails,
+        session_id: Some(claims.session_id),
+        scopes: session_scopes,
+        workspaces: workspace::accessible_workspaces(&auth_state.app_state, &user_context).await?,
+    })
 }
 
 /// POST /auth/select-email - Select email for workspace creation
@@ -1231,6 +1650,7 @@ async fn select_email(
         // Remove the code after use
         store.remove(code);
         drop(store);
+        record_selected_email(&auth_state, &session_id, &request.email).await;
 
         (session_id, github_id, github_username, emails)
     } else {
@@ -1368,7 +1788,7 @@ async fn select_email(
     match workspace::create_workspace_for_email(&mut model_service, &request.email).await {
         Ok(workspace_path) => {
             info!(
-                "Created workspace for GitHub user {} with email {}",
+                "Created workspace for user {} with email {}",
                 github_username, request.email
             );
             Ok(Json(SelectEmailResponse {
@@ -1404,35 +1824,8 @@ async fn logout(
     headers: axum::http::HeaderMap,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     if let Some(claims) = extract_and_validate_token(&auth_state, &headers).await {
-        let session_uuid = Uuid::parse_str(&claims.session_id).ok();
-
         // Revoke session - use database if available, otherwise in-memory
-        if let Some(db_session_store) = auth_state.app_state.db_session_store() {
-            // Database-backed session revocation
-            if let Some(uuid) = session_uuid {
-                if let Err(e) = db_session_store.revoke_session(uuid).await {
-                    warn!("Failed to revoke session in database: {}", e);
-                } else {
-                    info!("Revoked session in database: {}", claims.session_id);
-                }
-            }
-        } else {
-            // In-memory session revocation (legacy)
-            auth_state
-                .revoked_tokens
-                .lock()
-                .await
-                .insert(claims.session_id.clone());
-            auth_state
-                .session_store
-                .lock()
-                .await
-                .remove(&claims.session_id);
-            info!(
-                "Logged out and revoked in-memory session: {}",
-                claims.session_id
-            );
-        }
+        revoke_session(&auth_state, &claims.session_id).await;
     }
 
     Ok(Json(
@@ -1440,6 +1833,164 @@ async fn logout(
     ))
 }
 
+/// Active sessions of the user signed in with the request's session, and that session's id
+async fn user_sessions(
+    auth_state: &AuthState,
+    headers: &axum::http::HeaderMap,
+) -> Result<(String, Vec<(String, SessionMetadata)>), StatusCode> {
+    let claims = extract_and_validate_token(auth_state, headers)
+        .await
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    if let Some(db_session_store) = auth_state.app_state.db_session_store() {
+        let session_uuid =
+            Uuid::parse_str(&claims.session_id).map_err(|_| StatusCode::UNAUTHORIZED)?;
+        let current = db_session_store
+            .get_session(session_uuid)
+            .await
+            .map_err(|e| {
+                warn!("Failed to get session from database: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+        let sessions = db_session_store
+            .list_user_sessions(current.user_id)
+            .await
+            .map_err(|e| {
+                warn!("Failed to list sessions from database: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        return Ok((
+            claims.session_id,
+            sessions
+                .into_iter()
+                .map(|(id, session)| (id.to_string(), session))
+                .collect(),
+        ));
+    }
+
+    let current = touch_session(&auth_state.session_store, &claims.session_id)
+        .await
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+    let now = chrono::Utc::now();
+    let mut sessions: Vec<(String, SessionMetadata)> = auth_state
+        .session_store
+        .lock()
+        .await
+        .iter()
+        .filter(|(_, s)| s.user_id == current.user_id && s.expires_at > now)
+        .map(|(id, s)| (id.clone(), s.clone()))
+        .collect();
+    sessions.sort_by_key(|(_, s)| std::cmp::Reverse(s.last_activity));
+    Ok((claims.session_id, sessions))
+}
+
+/// GET /auth/sessions - List the signed-in user's active sessions
+///
+/// Sessions expire after `SESSION_TTL_HOURS` without activity, and after
+/// `SESSION_MAX_LIFETIME_HOURS` regardless.
+#[utoipa::path(
+    get,
+    path = "/auth/sessions",
+    tag = "Authentication",
+    responses(
+        (status = 200, description = "Active sessions, most recently active first", body = SessionsResponse),
+        (status = 401, description = "Unauthorized - invalid, expired or revoked token")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_sessions(
+    State(auth_state): State<AuthState>,
+    headers: axum::http::HeaderMap,
+) -> Result<Json<SessionsResponse>, StatusCode> {
+    let (current, sessions) = user_sessions(&auth_state, &headers).await?;
+    Ok(Json(SessionsResponse {
+        sessions: sessions
+            .into_iter()
+            .map(|(session_id, session)| SessionSummary {
+                current: session_id == current,
+                session_id,
+                github_username: session.github_username,
+                selected_email: session.selected_email,
+                created_at: session.created_at,
+                last_activity: session.last_activity,
+                expires_at: session.expires_at,
+            })
+            .collect(),
+    }))
+}
+
+/// DELETE /auth/sessions/{session_id} - Revoke one of the signed-in user's sessions
+///
+/// Tokens of the revoked session stop working and can't be refreshed; revoking the current
+/// session signs the user out.
+#[utoipa::path(
+    delete,
+    path = "/auth/sessions/{session_id}",
+    tag = "Authentication",
+    params(
+        ("session_id" = String, Path, description = "Session id")
+    ),
+    responses(
+        (status = 204, description = "Session revoked"),
+        (status = 401, description = "Unauthorized - invalid, expired or revoked token"),
+        (status = 404, description = "No active session of the user with this id")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn revoke_user_session(
+    State(auth_state): State<AuthState>,
+    headers: axum::http::HeaderMap,
+    Path(session_id): Path<String>,
+) -> Result<StatusCode, StatusCode> {
+    let (_, sessions) = user_sessions(&auth_state, &headers).await?;
+    if !sessions.iter().any(|(id, _)| *id == session_id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    revoke_session(&auth_state, &session_id).await;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /auth/revoke - Revoke a refresh token and its session
+///
+/// Cuts off a stolen refresh token: the token goes on the revocation list and its session is
+/// revoked, so neither it nor tokens rotated from it can be refreshed.
+#[utoipa::path(
+    post,
+    path = "/auth/revoke",
+    tag = "Authentication",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "Refresh token and session revoked", body = Object),
+        (status = 401, description = "Unauthorized - invalid or expired refresh token")
+    )
+)]
+async fn revoke_refresh_token(
+    State(auth_state): State<AuthState>,
+    Json(request): Json<RefreshTokenRequest>,
+) -> Result<Json<serde_json::Value>, StatusCode> {
+    let claims = auth_state
+        .jwt_service
+        .validate_refresh_token(&request.refresh_token)
+        .map_err(|e| {
+            warn!("Invalid refresh token: {}", e);
+            StatusCode::UNAUTHORIZED
+        })?;
+
+    refresh_token_revocations(&auth_state)?
+        .revoke(RevokedRefreshToken::new(&request.refresh_token, &claims))
+        .await
+        .map_err(|e| {
+            warn!("Failed to revoke refresh token: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    revoke_session(&auth_state, &claims.session_id).await;
+
+    Ok(Json(
+        serde_json::json!({ "message": "Refresh token revoked" }),
+    ))
+}
+
 /// Extract and validate JWT token from request headers
 /// Supports both Authorization: Bearer `token` and x-session-id (legacy)
 async fn extract_and_validate_token(
@@ -1572,3 +2123,320 @@ pub async fn validate_request_token_with_db(
 
     Ok(claims)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::api_key_service::ApiKeyKind;
+    use crate::services::jwt_service::TokenType;
+
+    fn auth_state() -> AuthState {
+        AuthState {
+            session_store: new_session_store(),
+            revoked_tokens: new_revoked_tokens(),
+            pending_auth_store: new_pending_auth_store(),
+            oauth_state_store: new_oauth_state_store(),
+            token_exchange_store: new_token_exchange_store(),
+            oauth_providers: OAuthProviders::default(),
+            jwt_service: Arc::new(JwtService::new("test-secret-test-secret-test-secret")),
+            app_state: AppState::new(),
+        }
+    }
+
+    fn claims(email: &str, session_id: &str) -> Claims {
+        let now = chrono::Utc::now().timestamp();
+        Claims {
+            sub: email.to_string(),
+            github_id: 1,
+            github_username: "ada".to_string(),
+            exp: now + 900,
+            iat: now,
+            token_type: TokenType::Access,
+            session_id: session_id.to_string(),
+            jti: None,
+        }
+    }
+
+    fn email(address: &str, verified: bool) -> GitHubEmail {
+        GitHubEmail {
+            email: address.to_string(),
+            verified,
+            primary: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_me_with_session_token() {
+        let state = auth_state();
+        let session_id = Uuid::new_v4().to_string();
+        let now = chrono::Utc::now();
+        state.session_store.lock().await.insert(
+            session_id.clone(),
+            SessionMetadata {
+                user_id: Uuid::new_v4(),
+                github_id: 1,
+                github_username: "ada".to_string(),
+                github_access_token: String::new(),
+                emails: vec![
+                    email("ada@example.com", true),
+                    email("ada@old.example.com", false),
+                ],
+                selected_email: Some("ada@example.com".to_string()),
+                created_at: now,
+                last_activity: now,
+                revoked_at: None,
+                expires_at: now + chrono::Duration::hours(1),
+            },
+        );
+
+        let info = user_info(
+            &state,
+            Authenticated::Token(claims("ada@example.com", &session_id)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(info.user.email, "ada@example.com");
+        assert_eq!(info.user.name, "ada");
+        assert_eq!(info.verified_emails, vec!["ada@example.com"]);
+        assert_eq!(info.session_id.as_deref(), Some(session_id.as_str()));
+        assert_eq!(info.scopes, vec!["full"]);
+
+        // Without a stored session the token's subject is reported as verified
+        let unknown = Uuid::new_v4().to_string();
+        let info = user_info(
+            &state,
+            Authenticated::Token(claims("ada@example.com", &unknown)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(info.verified_emails, vec!["ada@example.com"]);
+
+        state.revoked_tokens.lock().await.insert(session_id.clone());
+        let revoked = user_info(
+            &state,
+            Authenticated::Token(claims("ada@example.com", &session_id)),
+        )
+        .await;
+        assert_eq!(revoked.err(), Some(StatusCode::UNAUTHORIZED));
+    }
+
+    #[tokio::test]
+    async fn test_reused_refresh_token_revokes_session() {
+        let state = auth_state();
+        let session_id = Uuid::new_v4().to_string();
+        let now = chrono::Utc::now();
+        state.session_store.lock().await.insert(
+            session_id.clone(),
+            SessionMetadata {
+                user_id: Uuid::new_v4(),
+                github_id: 1,
+                github_username: "ada".to_string(),
+                github_access_token: String::new(),
+                emails: vec![email("ada@example.com", true)],
+                selected_email: Some("ada@example.com".to_string()),
+                created_at: now,
+                last_activity: now,
+                revoked_at: None,
+                expires_at: now + chrono::Duration::hours(1),
+            },
+        );
+        let pair = state
+            .jwt_service
+            .generate_token_pair("ada@example.com", 1, "ada", &session_id)
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let revocations = JsonlRevocationList::new(dir.path());
+        let refresh = |token: &str| RefreshTokenRequest {
+            refresh_token: token.to_string(),
+        };
+
+        let rotated = rotate_refresh_token(&state, &revocations, refresh(&pair.refresh_token))
+            .await
+            .unwrap();
+        assert_ne!(rotated.refresh_token, pair.refresh_token);
+
+        // Presenting the rotated token again revokes the session, cutting off the new token too
+        let reused = rotate_refresh_token(&state, &revocations, refresh(&pair.refresh_token)).await;
+        assert_eq!(reused.err(), Some(StatusCode::UNAUTHORIZED));
+        assert!(state.revoked_tokens.lock().await.contains(&session_id));
+        let renewed =
+            rotate_refresh_token(&state, &revocations, refresh(&rotated.refresh_token)).await;
+        assert_eq!(renewed.err(), Some(StatusCode::UNAUTHORIZED));
+    }
+
+    #[tokio::test]
+    async fn test_me_with_api_key() {
+        let owner = UserContext {
+            user_id: Uuid::new_v4(),
+            email: "ci@example.com".to_string(),
+        };
+        let (key, _) = ApiKey::generate(
+            ApiKeyKind::PersonalAccessToken,
+            "ci".to_string(),
+            vec![ApiKeyScope::ReadOnly, ApiKeyScope::ExportOnly],
+            &owner,
+            None,
+        );
+
+        let info = user_info(&auth_state(), Authenticated::ApiKey(key))
+            .await
+            .unwrap();
+        assert_eq!(info.user.id, owner.user_id.to_string());
+        assert_eq!(info.user.email, "ci@example.com");
+        assert_eq!(info.verified_emails, vec!["ci@example.com"]);
+        assert_eq!(info.session_id, None);
+        assert_eq!(info.scopes, vec!["read_only", "export_only"]);
+    }
+
+    #[tokio::test]
+    async fn test_me_in_local_mode() {
+        let local = LocalMode::from_lookup(|key| match key {
+            "LOCAL_MODE" => Some("true".to_string()),
+            "LOCAL_USER_NAME" => Some("Ada".to_string()),
+            _ => None,
+        })
+        .unwrap();
+
+        let info = user_info(&auth_state(), Authenticated::Local(local.clone()))
+            .await
+            .unwrap();
+        assert_eq!(info.user.id, local.user_context().user_id.to_string());
+        assert_eq!(info.user.name, "Ada");
+        assert_eq!(info.verified_emails, vec![local.email]);
+        assert_eq!(info.session_id, None);
+        assert_eq!(info.scopes, vec!["full"]);
+    }
+
+    /// A pending exchange code for an account with two verified emails and one unverified
+    async fn exchange_code(
+        state: &AuthState,
+        issued_at: chrono::DateTime<chrono::Utc>,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> String {
+        let session_id = Uuid::new_v4().to_string();
+        let tokens = state
+            .jwt_service
+            .generate_token_pair("ada@example.com", 1, "ada", &session_id)
+            .unwrap();
+        let code = Uuid::new_v4().to_string();
+        state.token_exchange_store.lock().await.insert(
+            code.clone(),
+            TokenExchangeEntry {
+                tokens,
+                emails: vec![
+                    email("ada@example.com", true),
+                    email("ada@work.example.com", true),
+                    email("ada@old.example.com", false),
+                ],
+                select_email: true,
+                issued_at,
+                expires_at,
+                github_id: 1,
+                github_username: "ada".to_string(),
+                session_id,
+            },
+        );
+        code
+    }
+
+    async fn exchange(
+        state: &AuthState,
+        code: &str,
+        selected_email: Option<&str>,
+    ) -> Result<ExchangeAuthCodeResponse, StatusCode> {
+        let request = ExchangeAuthCodeRequest {
+            code: code.to_string(),
+            selected_email: selected_email.map(str::to_string),
+            email: None,
+        };
+        exchange_auth_code(State(state.clone()), Json(request))
+            .await
+            .map(|Json(response)| response)
+    }
+
+    #[tokio::test]
+    async fn test_exchange_with_email_selection() {
+        let state = auth_state();
+        let now = chrono::Utc::now();
+        let code = exchange_code(&state, now, now + chrono::Duration::minutes(2)).await;
+
+        // Without an email the account's emails are returned for selection
+        let listed = exchange(&state, &code, None).await.unwrap();
+        assert!(listed.select_email);
+        assert!(listed.access_token.is_empty());
+        assert_eq!(listed.emails.len(), 3);
+        assert_eq!(listed.session_code.as_deref(), Some(code.as_str()));
+
+        let selected = exchange(&state, &code, Some("ada@work.example.com"))
+            .await
+            .unwrap();
+        assert!(!selected.select_email);
+        let claims = state
+            .jwt_service
+            .validate_access_token(&selected.access_token)
+            .unwrap();
+        assert_eq!(claims.sub, "ada@work.example.com");
+        // Codes are single-use
+        assert_eq!(
+            exchange(&state, &code, Some("ada@work.example.com"))
+                .await
+                .err(),
+            Some(StatusCode::BAD_REQUEST)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exchange_rejects_unverified_or_unknown_email() {
+        let state = auth_state();
+        let now = chrono::Utc::now();
+        let code = exchange_code(&state, now, now + chrono::Duration::minutes(2)).await;
+
+        for email in ["ada@old.example.com", "mallory@example.com"] {
+            assert_eq!(
+                exchange(&state, &code, Some(email)).await.err(),
+                Some(StatusCode::BAD_REQUEST),
+                "{}",
+                email
+            );
+        }
+        // The code still completes with a verified email
+        assert!(
+            exchange(&state, &code, Some("ada@example.com"))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exchange_selection_deadline_is_fixed() {
+        let state = auth_state();
+        let now = chrono::Utc::now();
+        let expired = exchange_code(&state, now, now - chrono::Duration::seconds(1)).await;
+        assert_eq!(
+            exchange(&state, &expired, None).await.err(),
+            Some(StatusCode::BAD_REQUEST)
+        );
+
+        // Listing the emails extends the code to 10 minutes after it was issued, not after
+        // the latest call
+        let issued_at = now - chrono::Duration::minutes(9);
+        let code = exchange_code(&state, issued_at, now + chrono::Duration::minutes(1)).await;
+        let deadline = issued_at + chrono::Duration::minutes(EMAIL_SELECTION_MINUTES);
+        for _ in 0..2 {
+            exchange(&state, &code, None).await.unwrap();
+            let store = state.token_exchange_store.lock().await;
+            assert_eq!(store[&code].expires_at, deadline);
+        }
+
+        let stale = exchange_code(
+            &state,
+            now - chrono::Duration::minutes(11),
+            now + chrono::Duration::minutes(1),
+        )
+        .await;
+        assert_eq!(
+            exchange(&state, &stale, None).await.err(),
+            Some(StatusCode::BAD_REQUEST)
+        );
+    }
+}
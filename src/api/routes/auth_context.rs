@@ -3,7 +3,9 @@
 //! Provides helper functions and extractors for authentication context.
 
 use super::app_state::AppState;
+use crate::middleware::api_key::authenticate_api_key;
 use crate::services::jwt_service::JwtService;
+use crate::services::local_mode::local_mode;
 use crate::storage::traits::UserContext;
 use axum::extract::FromRequestParts;
 use axum::http::{StatusCode, request::Parts};
@@ -19,7 +21,6 @@ pub struct AuthContext {
 
 impl AuthContext {
     /// Create from user context
-    #[allow(dead_code)] // Reserved for future use
     pub fn from_user_context(user_context: UserContext, session_id: Option<String>) -> Self {
         Self {
             email: user_context.email.clone(),
@@ -36,10 +37,20 @@ impl FromRequestParts<AppState> for AuthContext {
         parts: &mut Parts,
         state: &AppState,
     ) -> Result<Self, Self::Rejection> {
+        // Local mode has no sessions; every request acts as the local user
+        if let Some(local) = local_mode() {
+            return Ok(AuthContext::from_user_context(local.user_context(), None));
+        }
+
         // Use AppState directly from router state
         let app_state = state;
 
         let headers = parts.headers.clone();
+
+        // Machine-to-machine clients authenticate with an API key
+        if let Some(key) = authenticate_api_key(app_state, &headers).await? {
+            return Ok(AuthContext::from_user_context(key.user_context(), None));
+        }
         let jwt_service = JwtService::from_env();
 
         // Try Authorization header first (preferred)
@@ -70,12 +81,13 @@ impl FromRequestParts<AppState> for AuthContext {
             Ok(uuid) => uuid,
             Err(_) => {
                 // Invalid UUID format, try in-memory fallback
-                let sessions = app_state.session_store.lock().await;
-                if !sessions.contains_key(&claims.session_id) {
+                if crate::routes::auth::touch_session(&app_state.session_store, &claims.session_id)
+                    .await
+                    .is_none()
+                {
                     tracing::warn!("Session {} not found in store", claims.session_id);
                     return Err(StatusCode::UNAUTHORIZED);
                 }
-                drop(sessions);
 
                 // For file-based mode, we use a deterministic UUID based on email
                 let user_id = uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_DNS, claims.sub.as_bytes());
@@ -122,12 +134,14 @@ impl FromRequestParts<AppState> for AuthContext {
         }
 
         // Fall back to in-memory session store
-        let sessions = app_state.session_store.lock().await;
-        if !sessions.contains_key(&claims.session_id) {
+        // Requests are activity, so they renew the session
+        if crate::routes::auth::touch_session(&app_state.session_store, &claims.session_id)
+            .await
+            .is_none()
+        {
             tracing::warn!("Session {} not found in store", claims.session_id);
             return Err(StatusCode::UNAUTHORIZED);
         }
-        drop(sessions);
 
         // For file-based mode, we use a deterministic UUID based on email
         let user_id = uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_DNS, claims.sub.as_bytes());
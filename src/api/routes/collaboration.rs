@@ -8,6 +8,7 @@
 //! - Cursor and selection sharing
 //! - User presence tracking
 //! - Optimistic locking with version conflicts
+//! - Sequenced, batched and optionally compressed delivery (see [`super::collaboration_batch`])
 
 use axum::{
     Router,
@@ -16,15 +17,15 @@ use axum::{
     response::Response,
     routing::get,
 };
-use futures_util::{SinkExt, StreamExt};
+use futures_util::StreamExt;
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use std::time::{Duration, Instant};
-use tokio::sync::broadcast;
 use tracing::{info, warn};
 use uuid::Uuid;
 
 use super::app_state::AppState;
+use super::collaboration_batch::{CollaborationChannel, forward_messages};
 
 /// WebSocket connection query parameters
 #[derive(Deserialize)]
@@ -35,6 +36,14 @@ struct WebSocketQuery {
     shared_session_id: Option<String>,
     /// User ID for presence tracking
     user_id: Option<String>,
+    /// `deflate` to receive compressed binary frames
+    compress: Option<String>,
+}
+
+impl WebSocketQuery {
+    fn compress(&self) -> bool {
+        self.compress.as_deref() == Some("deflate")
+    }
 }
 
 /// WebSocket message types
@@ -174,9 +183,10 @@ async fn handle_shared_session_websocket(
         .session_id
         .clone()
         .unwrap_or_else(|| "Anonymous".to_string());
+    let compress = query.compress();
 
     ws.on_upgrade(move |socket| {
-        handle_shared_session_socket(socket, session_id, user_id, username, state)
+        handle_shared_session_socket(socket, session_id, user_id, username, compress, state)
     })
 }
 
@@ -186,6 +196,7 @@ async fn handle_shared_session_socket(
     session_id: String,
     user_id: Option<Uuid>,
     username: String,
+    compress: bool,
     state: AppState,
 ) {
     let user_id_str = user_id
@@ -197,11 +208,11 @@ async fn handle_shared_session_socket(
         session_id, user_id_str
     );
 
-    let (mut sender, mut receiver) = socket.split();
+    let (sender, mut receiver) = socket.split();
 
     // Get or create broadcast channel for this session
     let tx = get_or_create_broadcast_tx(&state, &format!("session:{}", session_id)).await;
-    let mut rx = tx.subscribe();
+    let rx = tx.subscribe();
 
     // Broadcast user joined
     let join_msg = CollaborationMessage::UserJoined {
@@ -223,36 +234,15 @@ async fn handle_shared_session_socket(
 
     // Spawn task to send messages from broadcast channel to this client
     let user_id_for_send = user_id_str.clone();
+    let batching = state.collaboration_batching;
     let mut send_task = tokio::spawn(async move {
-        while let Ok(msg) = rx.recv().await {
-            // Don't send cursor updates back to the originating user
-            let should_skip = match &msg {
-                CollaborationMessage::CursorUpdate { user_id, .. }
-                    if *user_id == user_id_for_send =>
-                {
-                    true
-                }
-                CollaborationMessage::SelectionUpdate { user_id, .. }
-                    if *user_id == user_id_for_send =>
-                {
-                    true
-                }
-                _ => false,
-            };
-
-            if should_skip {
-                continue;
-            }
-
-            if let Ok(json) = serde_json::to_string(&msg)
-                && sender
-                    .send(axum::extract::ws::Message::Text(json.into()))
-                    .await
-                    .is_err()
-            {
-                break;
-            }
-        }
+        // Don't send cursor and selection updates back to the originating user
+        forward_messages(rx, sender, batching, compress, |msg| match msg {
+            CollaborationMessage::CursorUpdate { user_id, .. }
+            | CollaborationMessage::SelectionUpdate { user_id, .. } => *user_id == user_id_for_send,
+            _ => false,
+        })
+        .await;
     });
 
     // Spawn task to receive messages from this client
@@ -321,7 +311,7 @@ async fn handle_shared_session_message(
     user_id: &str,
     username: &str,
     state: &AppState,
-    tx: &broadcast::Sender<CollaborationMessage>,
+    tx: &CollaborationChannel,
     cursor_limiter: &mut CursorRateLimiter,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let msg: CollaborationMessage = serde_json::from_str(text)?;
@@ -519,6 +509,8 @@ async fn handle_websocket(
         model_id
     );
 
+    let compress = query.compress();
+
     // Ensure workspace is loaded before upgrading WebSocket
     // Use session_id from query parameter (WebSocket connections can't send custom headers)
     if let Err(e) =
@@ -531,35 +523,30 @@ async fn handle_websocket(
         // Continue anyway - the sync request handler will also try to ensure workspace
     }
 
-    ws.on_upgrade(move |socket| handle_socket(socket, model_id, state))
+    ws.on_upgrade(move |socket| handle_socket(socket, model_id, compress, state))
 }
 
 /// Handle WebSocket connection
-async fn handle_socket(socket: axum::extract::ws::WebSocket, model_id: String, state: AppState) {
+async fn handle_socket(
+    socket: axum::extract::ws::WebSocket,
+    model_id: String,
+    compress: bool,
+    state: AppState,
+) {
     info!(
         "[Collaboration] WebSocket connected for model: {}",
         model_id
     );
 
-    let (mut sender, mut receiver) = socket.split();
+    let (sender, mut receiver) = socket.split();
 
     // Get or create broadcast channel for this model
     let tx = get_or_create_broadcast_tx(&state, &model_id).await;
-    let mut rx = tx.subscribe();
+    let rx = tx.subscribe();
 
     // Spawn task to send messages from broadcast channel to this client
-    let mut send_task = tokio::spawn(async move {
-        while let Ok(msg) = rx.recv().await {
-            if let Ok(json) = serde_json::to_string(&msg)
-                && sender
-                    .send(axum::extract::ws::Message::Text(json.into()))
-                    .await
-                    .is_err()
-            {
-                break;
-            }
-        }
-    });
+    let batching = state.collaboration_batching;
+    let mut send_task = tokio::spawn(forward_messages(rx, sender, batching, compress, |_| false));
 
     // Spawn task to receive messages from this client
     let model_id_for_recv = model_id.clone();
@@ -599,7 +586,7 @@ async fn handle_client_message(
     text: &str,
     model_id: &str,
     state: &AppState,
-    tx: &broadcast::Sender<CollaborationMessage>,
+    tx: &CollaborationChannel,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
     let msg: CollaborationMessage = serde_json::from_str(text)?;
 
@@ -656,16 +643,13 @@ async fn handle_client_message(
 }
 
 /// Get or create broadcast channel for a model
-async fn get_or_create_broadcast_tx(
-    state: &AppState,
-    model_id: &str,
-) -> broadcast::Sender<CollaborationMessage> {
+async fn get_or_create_broadcast_tx(state: &AppState, model_id: &str) -> CollaborationChannel {
     let mut channels = state.collaboration_channels.lock().await;
 
     if let Some(tx) = channels.get(model_id) {
         tx.clone()
     } else {
-        let (tx, _rx) = broadcast::channel::<CollaborationMessage>(1000);
+        let tx = CollaborationChannel::default();
         channels.insert(model_id.to_string(), tx.clone());
         info!(
             "[Collaboration] Created broadcast channel for model: {}",
@@ -0,0 +1,417 @@
+//! Sequencing, batching and compression of collaboration messages.
+//!
+//! Every message broadcast on a collaboration channel gets the channel's next sequence number.
+//! Each connection collects the messages that arrive within the batch window and sends them as
+//! one frame, coalescing cursor moves per user and position-only table updates per table so a
+//! drag or bulk layout change reaches clients as a handful of frames instead of hundreds.
+//!
+//! Frames on the wire:
+//! - a single message is sent as-is with its `seq` added;
+//! - several messages are sent as `{"type": "BATCH", "first_seq", "last_seq", "messages"}`,
+//!   where the sequence range also covers messages that were coalesced away or not forwarded
+//!   to this client.
+//!
+//! The next frame always continues at `last_seq + 1`; a jump means the client fell too far
+//! behind and messages were dropped, and it should send a `SYNC_REQUEST`.
+//!
+//! Compression: the WebSocket stack does not negotiate RFC 7692 permessage-deflate, so clients
+//! opt in with `?compress=deflate` and then receive every frame as a binary message holding the
+//! raw-deflated JSON text.
+//!
+//! Configuration (environment variables):
+//! - `COLLAB_BATCH_WINDOW_MS` (default 50): how long a connection collects messages before
+//!   sending them; 0 sends every message on its own
+
+use axum::extract::ws::Message;
+use flate2::Compression;
+use flate2::write::DeflateEncoder;
+use futures_util::{Sink, SinkExt};
+use serde::Serialize;
+use std::io::Write;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use super::collaboration::CollaborationMessage;
+
+const DEFAULT_BATCH_WINDOW: Duration = Duration::from_millis(50);
+
+/// Messages sent in one frame at most
+const MAX_BATCH_MESSAGES: usize = 500;
+
+/// Messages buffered per channel before slow connections start missing messages
+const CHANNEL_CAPACITY: usize = 1000;
+
+/// A broadcast message with its position in the channel
+#[derive(Debug, Clone, Serialize)]
+pub struct SequencedMessage {
+    pub seq: u64,
+    #[serde(flatten)]
+    pub message: CollaborationMessage,
+}
+
+/// Broadcast channel of a model or shared session that numbers its messages.
+#[derive(Clone)]
+pub struct CollaborationChannel {
+    sender: broadcast::Sender<SequencedMessage>,
+    last_seq: Arc<Mutex<u64>>,
+}
+
+impl Default for CollaborationChannel {
+    fn default() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            sender,
+            last_seq: Arc::new(Mutex::new(0)),
+        }
+    }
+}
+
+impl CollaborationChannel {
+    /// Broadcast a message with the next sequence number.
+    pub fn send(
+        &self,
+        message: CollaborationMessage,
+    ) -> Result<u64, broadcast::error::SendError<SequencedMessage>> {
+        // Numbering and sending under one lock keeps sequence numbers in send order
+        let mut last_seq = self.last_seq.lock().unwrap_or_else(PoisonError::into_inner);
+        *last_seq += 1;
+        let seq = *last_seq;
+        self.sender
+            .send(SequencedMessage { seq, message })
+            .map(|_| seq)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<SequencedMessage> {
+        self.sender.subscribe()
+    }
+}
+
+/// Batch window for collaboration connections.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollaborationBatching {
+    pub window: Duration,
+}
+
+impl Default for CollaborationBatching {
+    fn default() -> Self {
+        Self {
+            window: DEFAULT_BATCH_WINDOW,
+        }
+    }
+}
+
+impl CollaborationBatching {
+    /// Read the batch window from environment variables.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Read the batch window using the given variable lookup.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        lookup("COLLAB_BATCH_WINDOW_MS")
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(|ms| Self {
+                window: Duration::from_millis(ms),
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Messages that only matter in their latest version
+#[derive(PartialEq)]
+enum CoalesceKey {
+    Cursor(String),
+    TablePosition(String),
+}
+
+fn coalesce_key(message: &CollaborationMessage) -> Option<CoalesceKey> {
+    match message {
+        CollaborationMessage::CursorUpdate { user_id, .. } => {
+            Some(CoalesceKey::Cursor(user_id.clone()))
+        }
+        CollaborationMessage::TableUpdate { payload } => {
+            let fields = payload.as_object()?;
+            let position_only = fields.contains_key("position")
+                && fields.keys().all(|k| k == "id" || k == "position");
+            position_only
+                .then(|| table_id(message).map(|id| CoalesceKey::TablePosition(id.to_string())))
+                .flatten()
+        }
+        _ => None,
+    }
+}
+
+fn table_id(message: &CollaborationMessage) -> Option<&str> {
+    match message {
+        CollaborationMessage::TableCreate { payload }
+        | CollaborationMessage::TableUpdate { payload }
+        | CollaborationMessage::TableDelete { payload } => payload.get("id")?.as_str(),
+        _ => None,
+    }
+}
+
+/// Body of a frame carrying several messages
+#[derive(Serialize)]
+#[serde(tag = "type", rename = "BATCH")]
+struct BatchFrame<'a> {
+    first_seq: u64,
+    last_seq: u64,
+    messages: &'a [SequencedMessage],
+}
+
+/// Messages waiting to be sent to one connection.
+#[derive(Default)]
+pub struct MessageBatch {
+    first_seq: Option<u64>,
+    last_seq: u64,
+    messages: Vec<SequencedMessage>,
+}
+
+impl MessageBatch {
+    fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Account for a message that is not sent to this connection.
+    pub fn skip(&mut self, seq: u64) {
+        self.first_seq.get_or_insert(seq);
+        self.last_seq = seq;
+    }
+
+    /// Add a message, replacing an older pending version of it.
+    pub fn push(&mut self, message: SequencedMessage) {
+        self.skip(message.seq);
+        if let Some(key) = coalesce_key(&message.message) {
+            for pending in self.messages.iter_mut().rev() {
+                if coalesce_key(&pending.message).as_ref() == Some(&key) {
+                    *pending = message;
+                    return;
+                }
+                // Never move a position update across another change to the same table
+                if let CoalesceKey::TablePosition(id) = &key
+                    && table_id(&pending.message) == Some(id.as_str())
+                {
+                    break;
+                }
+            }
+        }
+        self.messages.push(message);
+    }
+
+    /// JSON text of the pending messages, or `None` while nothing is to be sent.
+    fn take_json(&mut self) -> Option<String> {
+        if self.messages.is_empty() {
+            return None;
+        }
+        let batch = std::mem::take(self);
+        let first_seq = batch.first_seq.unwrap_or(batch.last_seq);
+        let json = if batch.messages.len() == 1 && first_seq == batch.last_seq {
+            serde_json::to_string(&batch.messages[0])
+        } else {
+            serde_json::to_string(&BatchFrame {
+                first_seq,
+                last_seq: batch.last_seq,
+                messages: &batch.messages,
+            })
+        };
+        json.map_err(|e| warn!("[Collaboration] Failed to serialize messages: {}", e))
+            .ok()
+    }
+
+    /// WebSocket frame of the pending messages; the batch is empty afterwards.
+    pub fn take_frame(&mut self, compress: bool) -> Option<Message> {
+        let json = self.take_json()?;
+        if !compress {
+            return Some(Message::Text(json.into()));
+        }
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::fast());
+        match encoder
+            .write_all(json.as_bytes())
+            .and_then(|_| encoder.finish())
+        {
+            Ok(bytes) => Some(Message::Binary(bytes.into())),
+            Err(e) => {
+                warn!("[Collaboration] Failed to compress messages: {}", e);
+                Some(Message::Text(json.into()))
+            }
+        }
+    }
+}
+
+/// Next message for a connection; messages lost by falling behind show up as a sequence gap.
+async fn next_message(rx: &mut broadcast::Receiver<SequencedMessage>) -> Option<SequencedMessage> {
+    loop {
+        match rx.recv().await {
+            Ok(message) => return Some(message),
+            Err(broadcast::error::RecvError::Lagged(missed)) => {
+                warn!(
+                    "[Collaboration] Connection fell behind, {} messages dropped",
+                    missed
+                );
+            }
+            Err(broadcast::error::RecvError::Closed) => return None,
+        }
+    }
+}
+
+/// Forward channel messages to a client in batches until the channel or the client goes away.
+///
+/// Messages for which `skip` returns true are not sent to this client.
+pub async fn forward_messages<S>(
+    mut rx: broadcast::Receiver<SequencedMessage>,
+    mut sink: S,
+    batching: CollaborationBatching,
+    compress: bool,
+    skip: impl Fn(&CollaborationMessage) -> bool,
+) where
+    S: Sink<Message> + Unpin,
+{
+    let mut batch = MessageBatch::default();
+    let add = |batch: &mut MessageBatch, message: SequencedMessage| {
+        if skip(&message.message) {
+            batch.skip(message.seq);
+        } else {
+            batch.push(message);
+        }
+    };
+
+    while let Some(message) = next_message(&mut rx).await {
+        add(&mut batch, message);
+        if !batching.window.is_zero() {
+            let deadline = tokio::time::Instant::now() + batching.window;
+            while batch.len() < MAX_BATCH_MESSAGES {
+                match tokio::time::timeout_at(deadline, next_message(&mut rx)).await {
+                    Ok(Some(message)) => add(&mut batch, message),
+                    Ok(None) | Err(_) => break,
+                }
+            }
+        }
+        if let Some(frame) = batch.take_frame(compress)
+            && sink.send(frame).await.is_err()
+        {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::DeflateDecoder;
+    use serde_json::{Value, json};
+    use std::io::Read;
+
+    fn cursor(seq: u64, user: &str, x: f64) -> SequencedMessage {
+        SequencedMessage {
+            seq,
+            message: CollaborationMessage::CursorUpdate {
+                user_id: user.to_string(),
+                username: user.to_string(),
+                x,
+                y: 0.0,
+            },
+        }
+    }
+
+    fn table_update(seq: u64, payload: Value) -> SequencedMessage {
+        SequencedMessage {
+            seq,
+            message: CollaborationMessage::TableUpdate { payload },
+        }
+    }
+
+    fn frame_json(frame: Message) -> Value {
+        match frame {
+            Message::Text(text) => serde_json::from_str(text.as_str()).unwrap(),
+            Message::Binary(bytes) => {
+                let mut text = String::new();
+                DeflateDecoder::new(&bytes[..])
+                    .read_to_string(&mut text)
+                    .unwrap();
+                serde_json::from_str(&text).unwrap()
+            }
+            other => panic!("unexpected frame {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_channel_numbers_messages_in_order() {
+        let channel = CollaborationChannel::default();
+        let mut rx = channel.subscribe();
+        assert_eq!(channel.send(CollaborationMessage::Heartbeat).unwrap(), 1);
+        assert_eq!(channel.send(CollaborationMessage::HeartbeatAck).unwrap(), 2);
+        assert_eq!(rx.try_recv().unwrap().seq, 1);
+        assert_eq!(rx.try_recv().unwrap().seq, 2);
+    }
+
+    #[test]
+    fn test_batch_coalesces_cursors_and_positions() {
+        let mut batch = MessageBatch::default();
+        batch.push(cursor(1, "alice", 1.0));
+        batch.push(table_update(
+            2,
+            json!({"id": "t1", "position": {"x": 1, "y": 1}}),
+        ));
+        batch.push(cursor(3, "bob", 5.0));
+        batch.push(cursor(4, "alice", 2.0));
+        batch.push(table_update(
+            5,
+            json!({"id": "t1", "position": {"x": 2, "y": 2}}),
+        ));
+        batch.push(table_update(6, json!({"id": "t1", "name": "orders"})));
+        batch.push(table_update(
+            7,
+            json!({"id": "t1", "position": {"x": 3, "y": 3}}),
+        ));
+        assert_eq!(batch.len(), 5);
+
+        let frame = frame_json(batch.take_frame(false).unwrap());
+        assert_eq!(batch.len(), 0);
+        assert_eq!(frame["type"], "BATCH");
+        assert_eq!(frame["first_seq"], 1);
+        assert_eq!(frame["last_seq"], 7);
+        let seqs: Vec<u64> = frame["messages"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["seq"].as_u64().unwrap())
+            .collect();
+        // The position update may not jump over the rename of the same table
+        assert_eq!(seqs, vec![4, 5, 3, 6, 7]);
+        assert_eq!(frame["messages"][0]["x"], 2.0);
+        assert_eq!(frame["messages"][0]["type"], "CURSOR_UPDATE");
+    }
+
+    #[test]
+    fn test_single_message_frame_and_compression() {
+        let mut batch = MessageBatch::default();
+        batch.push(table_update(9, json!({"id": "t1", "name": "orders"})));
+        let frame = batch.take_frame(true).unwrap();
+        assert!(matches!(frame, Message::Binary(_)));
+        let json = frame_json(frame);
+        assert_eq!(json["type"], "TABLE_UPDATE");
+        assert_eq!(json["seq"], 9);
+        assert_eq!(json["payload"]["name"], "orders");
+
+        // Skipped messages keep the sequence range contiguous
+        batch.skip(10);
+        assert!(batch.take_frame(false).is_none());
+        batch.push(cursor(11, "alice", 1.0));
+        let json = frame_json(batch.take_frame(false).unwrap());
+        assert_eq!(json["type"], "BATCH");
+        assert_eq!(json["first_seq"], 10);
+        assert_eq!(json["last_seq"], 11);
+    }
+
+    #[test]
+    fn test_batch_window_from_lookup() {
+        let batching = CollaborationBatching::from_lookup(|_| Some("0".to_string()));
+        assert!(batching.window.is_zero());
+        assert_eq!(
+            CollaborationBatching::from_lookup(|_| None),
+            CollaborationBatching::default()
+        );
+    }
+}
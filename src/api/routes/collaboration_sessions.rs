@@ -202,7 +202,7 @@ pub async fn list_sessions(
         // Get user's workspace_id from storage - requires workspace lookup by email
         let storage = state.storage().ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
         let workspace = storage
-            .get_workspace_by_email(&auth.email)
+            .get_workspace_by_email(&super::workspace::workspace_email(&auth.email))
             .await
             .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
             .ok_or(StatusCode::NOT_FOUND)?;
@@ -0,0 +1,62 @@
+//! Canvas column edge routes.
+//!
+//! Returns the column-to-column lines of the domain's lineage relationships, so the canvas
+//! can draw detailed mapping lines when a relationship is expanded.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::column_references::load_domain_model;
+use super::workspace::DomainPath;
+use crate::services::column_edge_service::{ColumnEdge, column_edges};
+
+/// Query parameters for the column edges
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct ColumnEdgesQuery {
+    /// Only the edges of this relationship (the one being expanded)
+    pub relationship_id: Option<Uuid>,
+}
+
+/// GET /workspace/domains/{domain}/canvas/column-edges - Column-level lineage lines
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/canvas/column-edges",
+    tag = "Workspace",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ColumnEdgesQuery
+    ),
+    responses(
+        (status = 200, description = "Source and target column anchors of each mapped column pair of the lineage relationships", body = Vec<ColumnEdge>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Domain or relationship not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_column_edges(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    Query(query): Query<ColumnEdgesQuery>,
+) -> Result<Json<Vec<ColumnEdge>>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let (tables, relationships) = load_domain_model(&state, &ctx).await?;
+    if let Some(id) = query.relationship_id
+        && !relationships.iter().any(|r| r.id == id)
+    {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(Json(column_edges(
+        &tables,
+        &relationships,
+        query.relationship_id,
+    )))
+}
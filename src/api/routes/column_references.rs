@@ -0,0 +1,184 @@
+//! Column reference routes.
+//!
+//! Lists every relationship, foreign key, lineage mapping and cross-domain reference that
+//! touches a column, so it can be renamed or dropped safely.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::workspace::{
+    DomainContext, get_workspace_data_dir, load_cross_domain_config, workspace_dir_name,
+};
+use crate::models::{Relationship, Table};
+use crate::services::column_reference_service::{
+    ColumnReference, DomainImports, find_cross_domain_references, find_model_references,
+};
+
+/// Path parameters for column routes
+#[derive(Deserialize)]
+pub struct DomainColumnPath {
+    pub domain: String,
+    pub table_id: String,
+    pub column: String,
+}
+
+/// Everything that references a column
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ColumnReferencesResponse {
+    pub table_id: Uuid,
+    pub table_name: String,
+    pub column: String,
+    /// Number of references; zero means the column can be renamed or dropped freely
+    pub total: usize,
+    pub references: Vec<ColumnReference>,
+}
+
+/// Tables and relationships of the loaded domain
+pub(crate) async fn load_domain_model(
+    state: &AppState,
+    ctx: &DomainContext,
+) -> Result<(Vec<Table>, Vec<Relationship>), StatusCode> {
+    if let Some(storage) = state.storage.as_ref() {
+        let tables = storage
+            .get_tables(ctx.domain_info.id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let relationships = storage
+            .get_relationships(ctx.domain_info.id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        return Ok((tables, relationships));
+    }
+    let model_service = state.model_service.lock().await;
+    Ok(model_service
+        .get_current_model()
+        .map(|m| (m.tables.clone(), m.relationships.clone()))
+        .unwrap_or_default())
+}
+
+/// What the user's other domains import from `domain`
+pub(crate) async fn load_domain_imports(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+) -> Result<Vec<DomainImports>, StatusCode> {
+    let mut imports = Vec::new();
+
+    if let Some(storage) = state.storage.as_ref() {
+        let domains = storage
+            .get_domains(ctx.workspace.id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        for other in domains.into_iter().filter(|d| d.id != ctx.domain_info.id) {
+            let refs = match storage.get_cross_domain_refs(other.id).await {
+                Ok(refs) => refs,
+                Err(e) => {
+                    warn!("Failed to read cross-domain refs of {}: {}", other.name, e);
+                    continue;
+                }
+            };
+            imports.push(DomainImports {
+                domain: other.name,
+                table_ids: refs
+                    .iter()
+                    .filter(|r| r.source_domain_id == ctx.domain_info.id)
+                    .map(|r| r.table_id)
+                    .collect(),
+                relationship_ids: Vec::new(),
+            });
+        }
+        return Ok(imports);
+    }
+
+    let user_workspace = get_workspace_data_dir()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .join(workspace_dir_name(&ctx.user_context.email));
+    if let Ok(entries) = std::fs::read_dir(&user_workspace) {
+        for entry in entries.flatten() {
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            let config_path = entry.path().join("cross_domain.yaml");
+            if name == domain || name.starts_with('.') || !config_path.exists() {
+                continue;
+            }
+            let config = load_cross_domain_config(&config_path);
+            imports.push(DomainImports {
+                domain: name,
+                table_ids: config
+                    .imported_tables
+                    .iter()
+                    .filter(|r| r.source_domain == domain)
+                    .map(|r| r.table_id)
+                    .collect(),
+                relationship_ids: config
+                    .imported_relationships
+                    .iter()
+                    .filter(|r| r.source_domain == domain)
+                    .map(|r| r.relationship_id)
+                    .collect(),
+            });
+        }
+    }
+    Ok(imports)
+}
+
+/// GET /workspace/domains/{domain}/columns/{table_id}/{column}/references - Find references to a column
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/columns/{table_id}/{column}/references",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID"),
+        ("column" = String, Path, description = "Column name")
+    ),
+    responses(
+        (status = 200, description = "Relationships, foreign keys, lineage mappings and cross-domain references touching the column", body = ColumnReferencesResponse),
+        (status = 400, description = "Invalid table ID"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table or column not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_column_references(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainColumnPath>,
+) -> Result<Json<ColumnReferencesResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    let (tables, relationships) = load_domain_model(&state, &ctx).await?;
+    let table = tables
+        .iter()
+        .find(|t| t.id == table_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let column = table
+        .columns
+        .iter()
+        .find(|c| c.name.eq_ignore_ascii_case(&path.column))
+        .map(|c| c.name.clone())
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let mut references = find_model_references(table, &column, &tables, &relationships);
+    let imports = load_domain_imports(&state, &ctx, &path.domain).await?;
+    references.extend(find_cross_domain_references(table, &references, &imports));
+
+    Ok(Json(ColumnReferencesResponse {
+        table_id,
+        table_name: table.name.clone(),
+        column,
+        total: references.len(),
+        references,
+    }))
+}
@@ -0,0 +1,241 @@
+//! Command palette routes.
+//!
+//! `GET /workspace/command-index` lists searchable commands for the user's domains, each with
+//! a stable action id; `POST /workspace/commands/execute` runs one by id. Navigation
+//! commands (open a table, download an export, apply a saved filter) return the API path to
+//! follow; the others run the same handler as their dedicated endpoint.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use utoipa::{IntoParams, ToSchema};
+
+use super::app_state::AppState;
+use super::error::ApiError;
+use super::export_schedules::{
+    RunScheduleQuery, SchedulePath, load_export_schedules, run_export_schedule,
+};
+use super::saved_filters::{SavedFilterQuery, load_saved_filters, resolve_saved_filter};
+use super::workspace::{
+    CreateRelationshipRequest, DomainPath, DomainTablePath, create_domain_relationship,
+    get_domain_table, get_user_context, load_workspace_models, validate_domain_name,
+};
+use crate::services::command_palette_service::{
+    Command, CommandAction, CommandKind, DomainObjects, domain_commands, search_commands,
+};
+
+/// Most commands returned when no limit is given
+const DEFAULT_LIMIT: usize = 50;
+
+/// Query parameters for the command index
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct CommandIndexQuery {
+    /// Search terms matched against command titles and keywords
+    pub q: Option<String>,
+    /// Only list commands of this domain
+    pub domain: Option<String>,
+    /// Most commands returned (default 50)
+    pub limit: Option<usize>,
+}
+
+/// Searchable commands of the workspace
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CommandIndexResponse {
+    pub commands: Vec<Command>,
+    /// Commands matching the query before the limit was applied
+    pub total: usize,
+}
+
+/// Request body for executing a command
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ExecuteCommandRequest {
+    /// Action id from the command index
+    pub action_id: String,
+}
+
+/// Outcome of an executed command
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ExecuteCommandResponse {
+    pub action_id: String,
+    pub kind: CommandKind,
+    /// API path the client should open or download next
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub navigate_to: Option<String>,
+    /// Response of the underlying operation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub result: Option<Value>,
+}
+
+/// GET /workspace/command-index - List searchable commands
+#[utoipa::path(
+    get,
+    path = "/workspace/command-index",
+    tag = "Workspace",
+    params(CommandIndexQuery),
+    responses(
+        (status = 200, description = "Matching commands, best first", body = CommandIndexResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_command_index(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<CommandIndexQuery>,
+) -> Result<Json<CommandIndexResponse>, StatusCode> {
+    let user_context = get_user_context(&state, &headers).await?;
+    let email = &user_context.email;
+
+    let mut commands = Vec::new();
+    for model in load_workspace_models(&state, &user_context).await? {
+        if query.domain.as_ref().is_some_and(|d| *d != model.name) {
+            continue;
+        }
+        let objects = DomainObjects {
+            export_schedules: load_export_schedules(email, &model.name)
+                .schedules
+                .into_iter()
+                .map(|s| (s.id, s.name))
+                .collect(),
+            saved_filters: load_saved_filters(email, &model.name)
+                .filters
+                .into_iter()
+                .map(|f| (f.id, f.name))
+                .collect(),
+        };
+        commands.extend(domain_commands(&model, &objects));
+    }
+
+    let mut commands = search_commands(commands, query.q.as_deref().unwrap_or_default());
+    let total = commands.len();
+    commands.truncate(query.limit.unwrap_or(DEFAULT_LIMIT));
+    Ok(Json(CommandIndexResponse { commands, total }))
+}
+
+/// POST /workspace/commands/execute - Execute a command by action id
+#[utoipa::path(
+    post,
+    path = "/workspace/commands/execute",
+    tag = "Workspace",
+    request_body = ExecuteCommandRequest,
+    responses(
+        (status = 200, description = "Command executed", body = ExecuteCommandResponse),
+        (status = 400, description = "Unknown or malformed action id", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 404, description = "Domain or object of the command not found"),
+        (status = 409, description = "Relationship already exists")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn execute_command(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<ExecuteCommandRequest>,
+) -> Result<Json<ExecuteCommandResponse>, ApiError> {
+    let action = CommandAction::parse(request.action_id.trim()).ok_or_else(|| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message: format!("Unknown action id '{}'", request.action_id),
+    })?;
+    let domain = action.domain().to_string();
+    validate_domain_name(&domain)?;
+    let user_context = get_user_context(&state, &headers).await?;
+
+    let (navigate_to, result) = match &action {
+        CommandAction::OpenDomain { .. } => {
+            super::workspace::ensure_domain_loaded(&state, &headers, &domain).await?;
+            (Some(format!("/workspace/domains/{}/canvas", domain)), None)
+        }
+        CommandAction::OpenTable { table_id, .. } => {
+            let Json(table) = get_domain_table(
+                State(state.clone()),
+                headers.clone(),
+                Path(DomainTablePath {
+                    domain: domain.clone(),
+                    table_id: table_id.to_string(),
+                }),
+            )
+            .await?;
+            (
+                Some(format!("/workspace/domains/{}/tables/{}", domain, table_id)),
+                Some(table),
+            )
+        }
+        CommandAction::RunExport { format, .. } => {
+            super::workspace::ensure_domain_loaded(&state, &headers, &domain).await?;
+            (
+                Some(format!("/workspace/domains/{}/export/{}", domain, format)),
+                None,
+            )
+        }
+        CommandAction::RunExportSchedule { schedule_id, .. } => {
+            let Json(run) = run_export_schedule(
+                State(state.clone()),
+                headers.clone(),
+                Path(SchedulePath {
+                    domain: domain.clone(),
+                    schedule_id: *schedule_id,
+                }),
+                Query(RunScheduleQuery::default()),
+            )
+            .await?;
+            (None, Some(json!(run)))
+        }
+        CommandAction::CreateRelationship {
+            source_table_id,
+            target_table_id,
+            source_column,
+            target_column,
+            ..
+        } => {
+            let Json(relationship) = create_domain_relationship(
+                State(state.clone()),
+                headers.clone(),
+                Path(DomainPath {
+                    domain: domain.clone(),
+                }),
+                Json(CreateRelationshipRequest {
+                    source_table_id: source_table_id.to_string(),
+                    target_table_id: target_table_id.to_string(),
+                    cardinality: Some("ManyToOne".to_string()),
+                    foreign_key_details: Some(json!({
+                        "source_column": source_column,
+                        "target_column": target_column,
+                    })),
+                    etl_job_metadata: None,
+                    relationship_type: Some("ForeignKey".to_string()),
+                    source_role: None,
+                    target_role: None,
+                    bypass_rules: false,
+                }),
+            )
+            .await?;
+            (None, Some(relationship))
+        }
+        CommandAction::ApplySavedFilter { filter_id, .. } => {
+            let query = SavedFilterQuery {
+                filter: Some(*filter_id),
+            };
+            resolve_saved_filter(&user_context.email, &domain, &query)?;
+            (
+                Some(format!(
+                    "/workspace/domains/{}/canvas?filter={}",
+                    domain, filter_id
+                )),
+                None,
+            )
+        }
+    };
+
+    Ok(Json(ExecuteCommandResponse {
+        action_id: action.to_string(),
+        kind: action.kind(),
+        navigate_to,
+        result,
+    }))
+}
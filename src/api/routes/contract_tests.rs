@@ -0,0 +1,98 @@
+//! Contract test generation routes.
+//!
+//! Download a consumer-driven contract test skeleton for a table, so data consumer teams can
+//! codify their dependency on a contract version.
+
+use axum::{
+    body::Body,
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::Response,
+};
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::workspace::DomainTablePath;
+use crate::export::contract_tests::{ContractTestExporter, ContractTestFramework};
+
+/// Query parameters for contract test generation
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ContractTestQuery {
+    /// Test framework: pytest (default) or rust
+    pub framework: Option<String>,
+    /// Contract version to pin; defaults to the table's ODCS version
+    pub version: Option<String>,
+}
+
+/// GET /workspace/domains/{domain}/tables/{table_id}/contract-tests - Download a contract test skeleton
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/tables/{table_id}/contract-tests",
+    tag = "Export",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID"),
+        ContractTestQuery
+    ),
+    responses(
+        (status = 200, description = "Test module asserting the table's column names, types and nullability", content_type = "text/plain"),
+        (status = 400, description = "Invalid table ID or unknown framework"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn export_contract_tests(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+    Query(query): Query<ContractTestQuery>,
+) -> Result<Response<Body>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let framework = match query.framework.as_deref() {
+        Some(name) => ContractTestFramework::parse(name).ok_or(StatusCode::BAD_REQUEST)?,
+        None => ContractTestFramework::Pytest,
+    };
+
+    let table = match state.storage.as_ref() {
+        Some(storage) => storage
+            .get_table(ctx.domain_info.id, table_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        None => state
+            .model_service
+            .lock()
+            .await
+            .get_table(table_id)
+            .cloned(),
+    }
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    let version = query
+        .version
+        .as_deref()
+        .or_else(|| ContractTestExporter::contract_version(&table))
+        .unwrap_or("unversioned");
+    let content = ContractTestExporter::export_table(&table, framework, version);
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(framework.content_type()),
+        )
+        .header(
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_str(&format!(
+                "attachment; filename=\"{}\"",
+                framework.filename(&table)
+            ))
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        )
+        .body(Body::from(content))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
@@ -0,0 +1,177 @@
+//! Data product routes.
+//!
+//! Provides domain-scoped endpoints to read and replace the domain's data product
+//! definition (owner, SLA, output and input ports) and to export it as a Data Product
+//! Specification descriptor. The definition is stored as `data-product.yaml` in the domain
+//! directory.
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{Json, Response},
+    routing::get,
+};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::error::ApiError;
+use super::workspace::{
+    DomainPath, ensure_domain_loaded, get_workspace_data_dir, load_workspace_models,
+    workspace_dir_name,
+};
+use crate::services::data_product_service::DataProduct;
+
+/// Create the data product router
+pub fn data_product_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_data_product).put(update_data_product))
+        .route("/descriptor", get(export_data_product_descriptor))
+}
+
+/// Get path to a user's domain directory
+fn get_domain_dir(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain))
+}
+
+/// Load a domain's data product definition (empty if none is stored)
+pub(crate) fn load_data_product(email: &str, domain: &str) -> Result<DataProduct, StatusCode> {
+    Ok(DataProduct::load(&get_domain_dir(email, domain)?))
+}
+
+/// GET /workspace/domains/{domain}/data-product - Get the domain's data product definition
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/data-product",
+    tag = "Workspace",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Data product definition (empty if none is stored)", body = DataProduct),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_data_product(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+) -> Result<Json<DataProduct>, StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    Ok(Json(load_data_product(
+        &ctx.user_context.email,
+        &path.domain,
+    )?))
+}
+
+/// PUT /workspace/domains/{domain}/data-product - Replace the domain's data product definition
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/data-product",
+    tag = "Workspace",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body = DataProduct,
+    responses(
+        (status = 200, description = "Data product definition saved", body = DataProduct),
+        (status = 400, description = "Invalid owner or port name, or a port references an unknown domain or table", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_data_product(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    Json(product): Json<DataProduct>,
+) -> Result<Json<DataProduct>, ApiError> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let models = load_workspace_models(&state, &ctx.user_context).await?;
+    let tables = models
+        .iter()
+        .find(|m| m.name == path.domain)
+        .map(|m| m.tables.as_slice())
+        .unwrap_or_default();
+    let source_tables = |domain: &str| {
+        models
+            .iter()
+            .find(|m| m.name == domain)
+            .map(|m| m.tables.iter().map(|t| t.id).collect())
+    };
+    if let Err(e) = product.validate(&path.domain, tables, source_tables) {
+        warn!("Invalid data product for domain {}: {}", path.domain, e);
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: e,
+        });
+    }
+    let dir = get_domain_dir(&ctx.user_context.email, &path.domain)?;
+    product
+        .save(&dir)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(product))
+}
+
+/// GET /workspace/domains/{domain}/data-product/descriptor - Export the data product descriptor
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/data-product/descriptor",
+    tag = "Export",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Data Product Specification descriptor", content_type = "application/x-yaml"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "The domain has no data product definition"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn export_data_product_descriptor(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+) -> Result<Response<Body>, StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let product = load_data_product(&ctx.user_context.email, &path.domain)?;
+    if product == DataProduct::default() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let table_names: BTreeMap<Uuid, String> = load_workspace_models(&state, &ctx.user_context)
+        .await?
+        .into_iter()
+        .flat_map(|m| m.tables.into_iter().map(|t| (t.id, t.name)))
+        .collect();
+    let yaml = product
+        .descriptor(&path.domain, &table_names)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/x-yaml"),
+        )
+        .header(
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_str(&format!(
+                "attachment; filename=\"{}.dataproduct.yaml\"",
+                path.domain
+            ))
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        )
+        .body(Body::from(yaml))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
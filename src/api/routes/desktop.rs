@@ -0,0 +1,351 @@
+//! Desktop file routes.
+//!
+//! Available only when the binary runs as a desktop app (`desktop` subcommand), where the
+//! server listens on loopback for a single local user. The frontend uses these endpoints as
+//! its file dialogs: browse the local disk, open a model archive into a domain, and save a
+//! domain as a model archive. Every endpoint returns 404 in other modes, and 403 unless the
+//! request is addressed to `127.0.0.1:<port>` or `localhost:<port>`. Archives are only saved
+//! under the user's home directory or the data directory.
+
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+    routing::{get, post},
+};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+use utoipa::{IntoParams, ToSchema};
+
+use super::app_state::AppState;
+use super::error::ApiError;
+use super::workspace::{
+    ensure_domain_loaded_with_reload, get_user_context, get_workspace_data_dir,
+    validate_domain_name, workspace_dir_name,
+};
+use crate::services::local_mode::{embedded_mode, is_local_host, local_mode};
+use crate::services::model_archive_service::{ARCHIVE_EXTENSION, ModelArchive};
+
+/// Create the desktop router
+pub fn desktop_router() -> Router<AppState> {
+    Router::new()
+        .route("/files", get(list_files))
+        .route("/files/open", post(open_archive))
+        .route("/files/save", post(save_archive))
+}
+
+/// Query parameters for browsing the local disk
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct ListFilesQuery {
+    /// Directory to list (default: the user's home directory)
+    pub path: Option<String>,
+}
+
+/// A directory or model archive on the local disk
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DesktopFileEntry {
+    pub name: String,
+    pub path: String,
+    pub is_dir: bool,
+    /// Size in bytes (files only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+}
+
+/// Contents of a local directory
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DesktopDirectory {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent: Option<String>,
+    /// Subdirectories first, then model archives, each sorted by name
+    pub entries: Vec<DesktopFileEntry>,
+}
+
+/// Request body for opening or saving a model archive
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DesktopArchiveRequest {
+    /// Archive path on the local disk
+    pub path: String,
+    /// Domain to open the archive into, or to save
+    pub domain: String,
+    /// Replace an existing domain (open) or file (save)
+    #[serde(default)]
+    pub overwrite: bool,
+}
+
+/// Result of opening or saving a model archive
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DesktopArchiveResponse {
+    pub domain: String,
+    pub path: String,
+    /// Domain files contained in the archive
+    pub files: Vec<String>,
+}
+
+fn require_embedded(headers: &HeaderMap) -> Result<(), StatusCode> {
+    if !embedded_mode() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let host = headers
+        .get(axum::http::header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or_default();
+    if !is_local_host(host) {
+        warn!("[Desktop] Rejected request for host '{}'", host);
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(())
+}
+
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// Reject saving to a path outside the user's home directory and the data directory.
+fn check_save_path(path: &Path) -> Result<(), ApiError> {
+    let roots: Vec<PathBuf> = [home_dir(), local_mode().map(|local| local.data_dir.clone())]
+        .into_iter()
+        .flatten()
+        .filter_map(|root| root.canonicalize().ok())
+        .collect();
+    // Resolve symlinks, including an existing file that would be overwritten
+    let resolved = if path.exists() {
+        path.canonicalize()
+    } else {
+        (path.parent().filter(|p| !p.as_os_str().is_empty()))
+            .unwrap_or(Path::new("."))
+            .canonicalize()
+    }
+    .map_err(|e| bad_request(format!("Cannot save to '{}': {}", path.display(), e)))?;
+    if roots.iter().any(|root| resolved.starts_with(root)) {
+        return Ok(());
+    }
+    warn!(
+        "[Desktop] Refused to save outside home and data directories: {}",
+        path.display()
+    );
+    Err(ApiError {
+        status: StatusCode::FORBIDDEN,
+        message: "Archives can only be saved under the home or data directory".to_string(),
+    })
+}
+
+fn bad_request(message: String) -> ApiError {
+    ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message,
+    }
+}
+
+/// Directory of a domain in the local workspace
+fn domain_dir(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain))
+}
+
+/// GET /desktop/files - List directories and model archives in a local directory
+#[utoipa::path(
+    get,
+    path = "/desktop/files",
+    tag = "Desktop",
+    params(ListFilesQuery),
+    responses(
+        (status = 200, description = "Directory contents", body = DesktopDirectory),
+        (status = 400, description = "Not a readable directory", body = crate::api_types::ErrorResponse),
+        (status = 403, description = "Not addressed to the desktop app's loopback host"),
+        (status = 404, description = "Not running as a desktop app")
+    )
+)]
+pub async fn list_files(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<ListFilesQuery>,
+) -> Result<Json<DesktopDirectory>, ApiError> {
+    require_embedded(&headers)?;
+    get_user_context(&state, &headers).await?;
+
+    let dir = match query
+        .path
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+    {
+        Some(path) => PathBuf::from(path),
+        None => home_dir()
+            .or_else(|| local_mode().map(|local| local.data_dir.clone()))
+            .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?,
+    };
+    let dir = dir
+        .canonicalize()
+        .map_err(|e| bad_request(format!("Cannot open '{}': {}", dir.display(), e)))?;
+    let read_dir = std::fs::read_dir(&dir)
+        .map_err(|e| bad_request(format!("Cannot read '{}': {}", dir.display(), e)))?;
+
+    let mut entries: Vec<DesktopFileEntry> = read_dir
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let metadata = entry.metadata().ok()?;
+            let is_archive = Path::new(&name)
+                .extension()
+                .is_some_and(|e| e.eq_ignore_ascii_case(ARCHIVE_EXTENSION));
+            if name.starts_with('.') || !(metadata.is_dir() || is_archive) {
+                return None;
+            }
+            Some(DesktopFileEntry {
+                path: entry.path().to_string_lossy().to_string(),
+                is_dir: metadata.is_dir(),
+                size: (!metadata.is_dir()).then_some(metadata.len()),
+                name,
+            })
+        })
+        .collect();
+    entries.sort_by(|a, b| {
+        b.is_dir
+            .cmp(&a.is_dir)
+            .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+    });
+
+    Ok(Json(DesktopDirectory {
+        path: dir.to_string_lossy().to_string(),
+        parent: dir.parent().map(|p| p.to_string_lossy().to_string()),
+        entries,
+    }))
+}
+
+/// POST /desktop/files/open - Open a model archive from the local disk into a domain
+#[utoipa::path(
+    post,
+    path = "/desktop/files/open",
+    tag = "Desktop",
+    request_body = DesktopArchiveRequest,
+    responses(
+        (status = 200, description = "Archive opened into the domain", body = DesktopArchiveResponse),
+        (status = 400, description = "Invalid domain name or not a model archive", body = crate::api_types::ErrorResponse),
+        (status = 403, description = "Not addressed to the desktop app's loopback host"),
+        (status = 404, description = "Archive not found, or not running as a desktop app"),
+        (status = 409, description = "Domain already has tables; set overwrite to replace it", body = crate::api_types::ErrorResponse)
+    )
+)]
+pub async fn open_archive(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<DesktopArchiveRequest>,
+) -> Result<Json<DesktopArchiveResponse>, ApiError> {
+    require_embedded(&headers)?;
+    validate_domain_name(&request.domain)?;
+    let user_context = get_user_context(&state, &headers).await?;
+
+    let path = PathBuf::from(request.path.trim());
+    let bytes = std::fs::read(&path).map_err(|_| StatusCode::NOT_FOUND)?;
+    let archive = ModelArchive::read(&bytes).map_err(|e| bad_request(e.to_string()))?;
+
+    let dir = domain_dir(&user_context.email, &request.domain)?;
+    let has_tables = std::fs::read_dir(dir.join("tables"))
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false);
+    if has_tables && !request.overwrite {
+        return Err(ApiError {
+            status: StatusCode::CONFLICT,
+            message: format!(
+                "Domain '{}' already has tables; set overwrite to replace it",
+                request.domain
+            ),
+        });
+    }
+    archive
+        .extract(&dir, true)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    ensure_domain_loaded_with_reload(&state, &headers, &request.domain, true).await?;
+
+    info!(
+        "[Desktop] Opened {} into domain {}",
+        path.display(),
+        request.domain
+    );
+    Ok(Json(DesktopArchiveResponse {
+        domain: request.domain,
+        path: path.to_string_lossy().to_string(),
+        files: archive.paths(),
+    }))
+}
+
+/// POST /desktop/files/save - Save a domain as a model archive on the local disk
+#[utoipa::path(
+    post,
+    path = "/desktop/files/save",
+    tag = "Desktop",
+    request_body = DesktopArchiveRequest,
+    responses(
+        (status = 200, description = "Domain saved", body = DesktopArchiveResponse),
+        (status = 400, description = "Invalid domain name or target directory", body = crate::api_types::ErrorResponse),
+        (status = 403, description = "Not addressed to the desktop app's loopback host, or target outside the home and data directories", body = crate::api_types::ErrorResponse),
+        (status = 404, description = "Domain not found, or not running as a desktop app"),
+        (status = 409, description = "File exists; set overwrite to replace it", body = crate::api_types::ErrorResponse)
+    )
+)]
+pub async fn save_archive(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<DesktopArchiveRequest>,
+) -> Result<Json<DesktopArchiveResponse>, ApiError> {
+    require_embedded(&headers)?;
+    validate_domain_name(&request.domain)?;
+    let user_context = get_user_context(&state, &headers).await?;
+
+    let dir = domain_dir(&user_context.email, &request.domain)?;
+    if !dir.is_dir() {
+        return Err(StatusCode::NOT_FOUND.into());
+    }
+
+    let mut path = PathBuf::from(request.path.trim());
+    if !path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case(ARCHIVE_EXTENSION))
+    {
+        path.as_mut_os_string()
+            .push(format!(".{}", ARCHIVE_EXTENSION));
+    }
+    if !path
+        .parent()
+        .is_some_and(|p| p.as_os_str().is_empty() || p.is_dir())
+    {
+        return Err(bad_request(format!(
+            "Directory of '{}' does not exist",
+            path.display()
+        )));
+    }
+    check_save_path(&path)?;
+    if path.exists() && !request.overwrite {
+        return Err(ApiError {
+            status: StatusCode::CONFLICT,
+            message: format!("'{}' exists; set overwrite to replace it", path.display()),
+        });
+    }
+
+    let archive = ModelArchive::from_domain(&dir).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let zip_data = archive
+        .to_zip()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    std::fs::write(&path, zip_data)
+        .map_err(|e| bad_request(format!("Cannot write '{}': {}", path.display(), e)))?;
+
+    info!(
+        "[Desktop] Saved domain {} to {}",
+        request.domain,
+        path.display()
+    );
+    Ok(Json(DesktopArchiveResponse {
+        domain: request.domain,
+        path: path.to_string_lossy().to_string(),
+        files: archive.paths(),
+    }))
+}
@@ -0,0 +1,271 @@
+//! Test fixture routes.
+//!
+//! `GET /dev/fixtures?scenario=` provisions a deterministic sample workspace into the caller's
+//! workspace: one large domain, several domains with cross-domain references, or domains
+//! with conflicting table definitions. Frontend and SDK integration tests use it instead of
+//! hand-crafted fixtures. Fixture domains are named `fixture-*`; existing ones are left as
+//! they are unless `reset=true`.
+//!
+//! Only available in development (`APP_ENV=development`); every endpoint returns 404
+//! otherwise.
+
+use axum::{
+    Router,
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+    routing::get,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::domain_provisioning::{DomainManifestEntry, create_domain_with_model, domain_exists};
+use super::workspace::{
+    get_cross_domain_config_path, get_or_create_workspace, get_user_context,
+    get_workspace_data_dir, load_cross_domain_config, save_cross_domain_config, workspace_dir_name,
+};
+use crate::services::fixture_service::{FixtureDomain, FixtureScenario, generate};
+use crate::storage::traits::{UserContext, WorkspaceInfo as StorageWorkspaceInfo};
+use data_modelling_sdk::models::CrossDomainTableRef;
+
+/// Create the dev router
+pub fn dev_router() -> Router<AppState> {
+    Router::new().route("/fixtures", get(provision_fixtures))
+}
+
+/// Query parameters for provisioning fixtures
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct FixturesQuery {
+    /// large-domain, multi-domain or conflicts
+    pub scenario: FixtureScenario,
+    /// Number of tables of the large-domain scenario (default 200, at most 2000)
+    pub tables: Option<usize>,
+    /// Delete the scenario's domains first and provision them again
+    #[serde(default)]
+    pub reset: bool,
+}
+
+/// A provisioned fixture table
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FixtureTableSummary {
+    pub id: Uuid,
+    pub name: String,
+}
+
+/// Outcome for one fixture domain
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FixtureDomainResult {
+    pub domain: String,
+    /// created, exists or failed
+    pub status: String,
+    pub tables: Vec<FixtureTableSummary>,
+    pub relationships: usize,
+    /// Domains whose tables this domain imports
+    pub imports_from: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response for fixture provisioning
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FixturesResponse {
+    pub scenario: FixtureScenario,
+    pub domains: Vec<FixtureDomainResult>,
+}
+
+fn require_development() -> Result<(), StatusCode> {
+    // Fail-closed: a missing APP_ENV is production
+    let app_env = std::env::var("APP_ENV").unwrap_or_else(|_| "production".to_string());
+    if app_env == "development" {
+        Ok(())
+    } else {
+        Err(StatusCode::NOT_FOUND)
+    }
+}
+
+/// GET /dev/fixtures - Provision a deterministic sample workspace
+#[utoipa::path(
+    get,
+    path = "/dev/fixtures",
+    tag = "Development",
+    params(FixturesQuery),
+    responses(
+        (status = 200, description = "Per-domain provisioning results", body = FixturesResponse),
+        (status = 400, description = "Unknown scenario"),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 404, description = "Not running in development"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn provision_fixtures(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<FixturesQuery>,
+) -> Result<Json<FixturesResponse>, StatusCode> {
+    require_development()?;
+    let user_context = get_user_context(&state, &headers).await?;
+    let workspace = get_or_create_workspace(&state, &user_context).await?;
+    let user_dir = get_workspace_data_dir()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .join(workspace_dir_name(&user_context.email));
+
+    let domains = generate(query.scenario, &workspace.email, query.tables);
+    let mut results = Vec::with_capacity(domains.len());
+    for domain in &domains {
+        let mut result = FixtureDomainResult {
+            domain: domain.name.clone(),
+            status: "created".to_string(),
+            tables: domain
+                .tables
+                .iter()
+                .map(|t| FixtureTableSummary {
+                    id: t.id,
+                    name: t.name.clone(),
+                })
+                .collect(),
+            relationships: domain.relationships.len(),
+            imports_from: domain
+                .imports
+                .iter()
+                .map(|i| i.source_domain.clone())
+                .collect(),
+            error: None,
+        };
+        if domain_exists(&state, &workspace, &user_dir, &domain.name).await? {
+            if !query.reset {
+                result.status = "exists".to_string();
+                results.push(result);
+                continue;
+            }
+            delete_fixture_domain(&state, &user_context, &workspace, &user_dir, &domain.name)
+                .await?;
+        }
+        let entry = DomainManifestEntry {
+            name: domain.name.clone(),
+            description: Some(domain.description.clone()),
+            owners: Vec::new(),
+            template: None,
+        };
+        if let Err(e) = create_domain_with_model(
+            &state,
+            &user_context,
+            &workspace,
+            &user_dir,
+            &entry,
+            &domain.tables,
+            &domain.relationships,
+        )
+        .await
+        {
+            warn!("Failed to provision fixture domain {}: {}", domain.name, e);
+            result.status = "failed".to_string();
+            result.error = Some(e);
+        }
+        results.push(result);
+    }
+
+    // Cross-domain references need both domains, so they are added once all exist
+    for (domain, result) in domains.iter().zip(results.iter_mut()) {
+        if result.status != "created" {
+            continue;
+        }
+        if let Err(e) = add_imports(&state, &user_context, &workspace, domain).await {
+            warn!("Failed to import tables into {}: {}", domain.name, e);
+            result.status = "failed".to_string();
+            result.error = Some(e);
+        }
+    }
+
+    info!(
+        "Provisioned {:?} fixtures ({} domain(s)) for user {}",
+        query.scenario,
+        results.len(),
+        user_context.email
+    );
+    Ok(Json(FixturesResponse {
+        scenario: query.scenario,
+        domains: results,
+    }))
+}
+
+async fn delete_fixture_domain(
+    state: &AppState,
+    user_context: &UserContext,
+    workspace: &StorageWorkspaceInfo,
+    user_dir: &std::path::Path,
+    name: &str,
+) -> Result<(), StatusCode> {
+    if state.is_postgres()
+        && let Some(storage) = state.storage.as_ref()
+    {
+        if let Ok(Some(domain)) = storage.get_domain_by_name(workspace.id, name).await {
+            storage
+                .delete_domain(domain.id, user_context)
+                .await
+                .map_err(|e| {
+                    warn!("Failed to delete fixture domain {}: {}", name, e);
+                    StatusCode::INTERNAL_SERVER_ERROR
+                })?;
+        }
+        return Ok(());
+    }
+    std::fs::remove_dir_all(user_dir.join(name)).map_err(|e| {
+        warn!("Failed to delete fixture domain {}: {}", name, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+/// Record the tables `domain` imports from the other fixture domains.
+async fn add_imports(
+    state: &AppState,
+    user_context: &UserContext,
+    workspace: &StorageWorkspaceInfo,
+    domain: &FixtureDomain,
+) -> Result<(), String> {
+    if domain.imports.is_empty() {
+        return Ok(());
+    }
+    if state.is_postgres()
+        && let Some(storage) = state.storage.as_ref()
+    {
+        let target = storage
+            .get_domain_by_name(workspace.id, &domain.name)
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| format!("Domain {} not found", domain.name))?;
+        for import in &domain.imports {
+            let source = storage
+                .get_domain_by_name(workspace.id, &import.source_domain)
+                .await
+                .map_err(|e| e.to_string())?
+                .ok_or_else(|| format!("Domain {} not found", import.source_domain))?;
+            storage
+                .add_cross_domain_ref(target.id, source.id, import.table_id, None, None, None)
+                .await
+                .map_err(|e| e.to_string())?;
+        }
+        return Ok(());
+    }
+
+    let config_path = get_cross_domain_config_path(&user_context.email, &domain.name)
+        .map_err(|_| "Cannot locate the workspace directory".to_string())?;
+    let mut config = load_cross_domain_config(&config_path);
+    for import in &domain.imports {
+        if !config
+            .imported_tables
+            .iter()
+            .any(|t| t.table_id == import.table_id)
+        {
+            config.imported_tables.push(CrossDomainTableRef::new(
+                import.source_domain.clone(),
+                import.table_id,
+            ));
+        }
+    }
+    save_cross_domain_config(&config_path, &config)
+        .map_err(|_| "Failed to save the cross-domain configuration".to_string())
+}
@@ -0,0 +1,260 @@
+//! Diagram share link routes.
+//!
+//! A signed, expiring URL to a domain's rendered PNG diagram, for embedding in Confluence or
+//! Notion pages without workspace credentials. The token carries the workspace, domain
+//! and tables; the image is rendered from the current model on each request (cached until
+//! the model changes), so embeds stay live. Rotating `JWT_SECRET` revokes all links.
+//!
+//! Configuration (environment variables):
+//! - `SHARED_DIAGRAM_RATE_LIMIT_PER_MINUTE` (default 120): image requests per minute of each
+//!   client address
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{Json, Response},
+    routing::get,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::domain_shares::sharing_enabled;
+use super::export_schedules::load_domain_model;
+use super::workspace::{DomainPath, workspace_email};
+use crate::middleware::base_path::prefixed;
+use crate::middleware::client_ip::forwarded_header;
+use crate::middleware::rate_limit::{create_rate_limiter_with_quota, rate_limit_middleware};
+use crate::services::diagram_share_service::{DiagramImageCache, model_fingerprint};
+use crate::services::export_service::ExportService;
+use crate::services::jwt_service::JwtService;
+
+/// Lifetime of a share link when none is requested
+const DEFAULT_EXPIRY_DAYS: i64 = 30;
+/// Longest lifetime of a share link
+const MAX_EXPIRY_DAYS: i64 = 365;
+const DEFAULT_RATE_LIMIT_PER_MINUTE: u32 = 120;
+
+/// Create the public image router (nested under `/shared/diagrams`)
+pub fn shared_diagram_router() -> Router<AppState> {
+    let requests_per_minute = std::env::var("SHARED_DIAGRAM_RATE_LIMIT_PER_MINUTE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_RATE_LIMIT_PER_MINUTE);
+    Router::new()
+        .route("/{token}", get(get_shared_diagram))
+        .layer(axum::middleware::from_fn_with_state(
+            create_rate_limiter_with_quota(requests_per_minute),
+            rate_limit_middleware,
+        ))
+}
+
+/// Request body for creating a diagram share link
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct ShareDiagramRequest {
+    /// Tables to draw; the whole domain if omitted
+    #[serde(default)]
+    pub table_ids: Vec<Uuid>,
+    /// Days until the link expires (default 30, at most 365)
+    pub expires_in_days: Option<i64>,
+}
+
+/// A diagram share link
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ShareDiagramResponse {
+    /// Public image URL for embedding
+    pub url: String,
+    pub token: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// Path parameters of a shared diagram
+#[derive(Deserialize)]
+pub struct SharedDiagramPath {
+    pub token: String,
+}
+
+/// Origin the share URL is built on: `PUBLIC_API_URL`, else the request's host. Forwarded
+/// headers are only used behind a trusted proxy (`TRUST_PROXY_HEADERS`).
+pub(crate) fn public_origin(headers: &HeaderMap) -> String {
+    if let Ok(url) = std::env::var("PUBLIC_API_URL") {
+        return url.trim_end_matches('/').to_string();
+    }
+    let host = forwarded_header(headers, "x-forwarded-host")
+        .or_else(|| headers.get(header::HOST).and_then(|v| v.to_str().ok()));
+    match host {
+        Some(host) => format!(
+            "{}://{}",
+            forwarded_header(headers, "x-forwarded-proto").unwrap_or("http"),
+            host
+        ),
+        None => String::new(),
+    }
+}
+
+/// Sign the token of a diagram link. Like domain share links, it names the workspace holding
+/// the domain, since no workspace is active when the link is opened.
+pub(super) fn sign_diagram_token(
+    jwt: &JwtService,
+    email: &str,
+    domain: &str,
+    table_ids: Vec<Uuid>,
+    expires_in: Duration,
+) -> Result<(String, i64), String> {
+    jwt.sign_diagram_share(&workspace_email(email), domain, table_ids, expires_in)
+}
+
+/// POST /workspace/domains/{domain}/export/png/share - Create a signed image URL for embedding
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/export/png/share",
+    tag = "Export",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body = ShareDiagramRequest,
+    responses(
+        (status = 200, description = "Share link created", body = ShareDiagramResponse),
+        (status = 400, description = "Unknown table or invalid expiry"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Sharing is disabled on this server"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn share_diagram(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    Json(request): Json<ShareDiagramRequest>,
+) -> Result<Json<ShareDiagramResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    if !sharing_enabled() {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let days = request.expires_in_days.unwrap_or(DEFAULT_EXPIRY_DAYS);
+    if !(1..=MAX_EXPIRY_DAYS).contains(&days) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let model = load_domain_model(&state, &ctx.user_context.email, &path.domain)
+        .await
+        .map_err(|e| {
+            warn!("Failed to load domain {}: {}", path.domain, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    if request
+        .table_ids
+        .iter()
+        .any(|id| !model.tables.iter().any(|t| t.id == *id))
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let (token, expires_at) = sign_diagram_token(
+        &JwtService::from_env(),
+        &ctx.user_context.email,
+        &path.domain,
+        request.table_ids,
+        Duration::days(days),
+    )
+    .map_err(|e| {
+        warn!("Failed to sign diagram share token: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(ShareDiagramResponse {
+        url: format!(
+            "{}{}",
+            public_origin(&headers),
+            prefixed(&format!("/api/v1/shared/diagrams/{}", token))
+        ),
+        token,
+        expires_at: DateTime::from_timestamp(expires_at, 0).unwrap_or_default(),
+    }))
+}
+
+/// GET /shared/diagrams/{token} - Render a shared diagram (no authentication)
+#[utoipa::path(
+    get,
+    path = "/shared/diagrams/{token}",
+    tag = "Export",
+    params(
+        ("token" = String, Path, description = "Share token from the share link")
+    ),
+    responses(
+        (status = 200, description = "Rendered diagram", content_type = "image/png"),
+        (status = 304, description = "Diagram unchanged since the ETag sent in If-None-Match"),
+        (status = 401, description = "Invalid or expired share token"),
+        (status = 404, description = "Domain no longer exists, or sharing is disabled"),
+        (status = 429, description = "Rate limit exceeded", body = crate::api_types::ErrorResponse),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_shared_diagram(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<SharedDiagramPath>,
+) -> Result<Response<Body>, StatusCode> {
+    if !sharing_enabled() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let claims = JwtService::from_env()
+        .validate_diagram_share(&path.token)
+        .map_err(|_| StatusCode::UNAUTHORIZED)?;
+    let model = load_domain_model(&state, &claims.sub, &claims.domain)
+        .await
+        .map_err(|e| {
+            warn!("Failed to load shared domain {}: {}", claims.domain, e);
+            StatusCode::NOT_FOUND
+        })?;
+
+    let fingerprint = model_fingerprint(&model, &claims.table_ids);
+    let etag = format!("\"{}\"", fingerprint);
+    let response = Response::builder()
+        .header(header::ETAG, &etag)
+        // Embeds revalidate on every view so model changes show up immediately
+        .header(header::CACHE_CONTROL, "public, no-cache");
+    if headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.split(',').any(|tag| tag.trim() == etag))
+    {
+        return response
+            .status(StatusCode::NOT_MODIFIED)
+            .body(Body::empty())
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    let key = DiagramImageCache::key(&claims.sub, &claims.domain, &claims.table_ids);
+    let png = match state.diagram_images.get(&key, &fingerprint) {
+        Some(png) => png,
+        None => {
+            let table_ids = claims.table_ids.clone();
+            // Rendering is CPU-bound; keep it off the async workers
+            let png = tokio::task::spawn_blocking(move || {
+                let table_ids = Some(table_ids.as_slice()).filter(|ids| !ids.is_empty());
+                ExportService::export_png(&model, 1920, 1080, table_ids).map_err(|e| e.to_string())
+            })
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .map_err(|e| {
+                warn!("Failed to render shared diagram: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+            state.diagram_images.insert(key, fingerprint, png)
+        }
+    };
+
+    response
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, HeaderValue::from_static("image/png"))
+        .body(Body::from(png.as_ref().clone()))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
@@ -0,0 +1,117 @@
+//! Domain dependency graph routes.
+//!
+//! Serves the workspace's domain-level dependency graph (which domain consumes which) for
+//! the "context map" view.
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use std::collections::HashMap;
+use tracing::warn;
+
+use super::app_state::AppState;
+use super::workspace::{
+    get_or_create_workspace, get_user_context, get_user_workspace_path, load_cross_domain_config,
+    load_workspace_models,
+};
+use crate::services::domain_graph_service::{DomainGraph, DomainImport, build_domain_graph};
+use crate::storage::traits::UserContext;
+
+/// Cross-domain imports of every domain of the user's workspace
+async fn load_workspace_imports(
+    state: &AppState,
+    user_context: &UserContext,
+) -> Result<Vec<DomainImport>, StatusCode> {
+    let mut imports = Vec::new();
+
+    if let Some(storage) = state.storage.as_ref() {
+        let workspace = get_or_create_workspace(state, user_context).await?;
+        let domains = storage.get_domains(workspace.id).await.map_err(|e| {
+            warn!("Failed to list domains: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        let names: HashMap<_, _> = domains.iter().map(|d| (d.id, d.name.clone())).collect();
+        for domain in &domains {
+            let refs = match storage.get_cross_domain_refs(domain.id).await {
+                Ok(refs) => refs,
+                Err(e) => {
+                    warn!("Failed to read cross-domain refs of {}: {}", domain.name, e);
+                    continue;
+                }
+            };
+            let mut tables: HashMap<&str, usize> = HashMap::new();
+            for reference in &refs {
+                if let Some(provider) = names.get(&reference.source_domain_id) {
+                    *tables.entry(provider).or_default() += 1;
+                }
+            }
+            imports.extend(tables.into_iter().map(|(provider, tables)| DomainImport {
+                consumer: domain.name.clone(),
+                provider: provider.to_string(),
+                tables,
+                relationships: 0,
+            }));
+        }
+        return Ok(imports);
+    }
+
+    if let Ok(entries) = std::fs::read_dir(get_user_workspace_path(&user_context.email)?) {
+        for entry in entries.flatten() {
+            let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            let config_path = entry.path().join("cross_domain.yaml");
+            if name.starts_with('.') || !config_path.exists() {
+                continue;
+            }
+            let config = load_cross_domain_config(&config_path);
+            let mut by_provider: HashMap<&str, DomainImport> = HashMap::new();
+            for reference in &config.imported_tables {
+                by_provider
+                    .entry(&reference.source_domain)
+                    .or_default()
+                    .tables += 1;
+            }
+            for reference in &config.imported_relationships {
+                by_provider
+                    .entry(&reference.source_domain)
+                    .or_default()
+                    .relationships += 1;
+            }
+            imports.extend(
+                by_provider
+                    .into_iter()
+                    .map(|(provider, import)| DomainImport {
+                        consumer: name.clone(),
+                        provider: provider.to_string(),
+                        ..import
+                    }),
+            );
+        }
+    }
+    Ok(imports)
+}
+
+/// GET /workspace/domains/graph - Domain dependency graph of the workspace
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/graph",
+    tag = "Workspace",
+    responses(
+        (status = 200, description = "Domains, which domain consumes which, and dependency cycles", body = DomainGraph),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_domain_graph(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<DomainGraph>, StatusCode> {
+    let user_context = get_user_context(&state, &headers).await?;
+    let models = load_workspace_models(&state, &user_context).await?;
+    let imports = load_workspace_imports(&state, &user_context).await?;
+    Ok(Json(build_domain_graph(&models, &imports)))
+}
@@ -0,0 +1,335 @@
+//! Domain invite routes.
+//!
+//! Domain owners invite other users to a domain with a role, list the pending invites and
+//! current members, and revoke invites they no longer want redeemed. The invited user
+//! redeems the token with `POST /invites/{token}/redeem` while signed in; an invite made
+//! for an email can only be redeemed by that user. Members with the owner role count as
+//! domain owners.
+
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+    routing::{delete, get},
+};
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::diagram_shares::public_origin;
+use super::domain_metadata::is_domain_owner;
+use super::error::ApiError;
+use super::workspace::{
+    DomainPath, get_user_context, get_workspace_data_dir, workspace_dir_name, workspace_email,
+};
+use crate::middleware::base_path::prefixed;
+use crate::services::domain_invite_service::{
+    DomainInvite, DomainMember, DomainMembers, DomainRole, RedeemError,
+};
+use crate::services::jwt_service::JwtService;
+
+/// Days an invite can be redeemed for unless set otherwise
+const DEFAULT_INVITE_DAYS: i64 = 7;
+
+/// Create the invite management router (nested under `/workspace/domains/{domain}/invites`)
+pub fn domain_invites_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_domain_invites).post(create_domain_invite))
+        .route("/{invite_id}", delete(revoke_domain_invite))
+}
+
+/// Request body for inviting a user to a domain
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateDomainInviteRequest {
+    pub role: DomainRole,
+    /// Only this user may redeem the invite; anyone with the token if omitted
+    pub email: Option<String>,
+    /// Days until the invite expires (default 7)
+    pub expires_in_days: Option<i64>,
+}
+
+/// A newly created invite
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedDomainInviteResponse {
+    pub invite: DomainInvite,
+    /// URL the invited user redeems the invite at (`POST`)
+    pub url: String,
+    pub token: String,
+}
+
+/// Pending invites and members of a domain
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DomainInvitesResponse {
+    pub invites: Vec<DomainInvite>,
+    pub members: Vec<DomainMember>,
+}
+
+/// A redeemed invite
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RedeemedDomainInviteResponse {
+    /// Email of the workspace holding the domain
+    pub workspace: String,
+    pub domain: String,
+    pub member: DomainMember,
+}
+
+/// Path parameters of an invite
+#[derive(Deserialize)]
+pub struct DomainInvitePath {
+    pub domain: String,
+    pub invite_id: Uuid,
+}
+
+/// Path parameters of an invite token
+#[derive(Deserialize)]
+pub struct InviteTokenPath {
+    pub token: String,
+}
+
+fn get_domain_dir(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain))
+}
+
+/// GET /workspace/domains/{domain}/invites - List pending invites and members of the domain
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/invites",
+    tag = "Sharing",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Pending invites and members", body = DomainInvitesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a domain owner"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_domain_invites(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+) -> Result<Json<DomainInvitesResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    if !is_domain_owner(&ctx, &path.domain) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let members = DomainMembers::load(&get_domain_dir(&ctx.user_context.email, &path.domain)?);
+    Ok(Json(DomainInvitesResponse {
+        invites: members.pending(Utc::now()),
+        members: members.members,
+    }))
+}
+
+/// POST /workspace/domains/{domain}/invites - Invite a user to the domain
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/invites",
+    tag = "Sharing",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body = CreateDomainInviteRequest,
+    responses(
+        (status = 200, description = "Invite created", body = CreatedDomainInviteResponse),
+        (status = 400, description = "Invalid expiry or email", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a domain owner", body = crate::api_types::ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_domain_invite(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    Json(request): Json<CreateDomainInviteRequest>,
+) -> Result<Json<CreatedDomainInviteResponse>, ApiError> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let bad_request = |message: &str| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message: message.to_string(),
+    };
+    if !is_domain_owner(&ctx, &path.domain) {
+        return Err(ApiError {
+            status: StatusCode::FORBIDDEN,
+            message: "Only domain owners can invite users to a domain".to_string(),
+        });
+    }
+    let days = request.expires_in_days.unwrap_or(DEFAULT_INVITE_DAYS);
+    if days < 1 {
+        return Err(bad_request("expires_in_days must be at least 1"));
+    }
+    if request.email.as_deref().is_some_and(|e| !e.contains('@')) {
+        return Err(bad_request("email must be an email address"));
+    }
+
+    let now = Utc::now();
+    let invite = DomainInvite {
+        id: Uuid::new_v4(),
+        role: request.role,
+        email: request.email,
+        created_by: ctx.user_context.email.clone(),
+        created_at: now,
+        expires_at: now + Duration::days(days),
+    };
+    let token = JwtService::from_env()
+        .sign_domain_invite(
+            // The token names the workspace holding the domain
+            &workspace_email(&ctx.user_context.email),
+            &path.domain,
+            invite.id,
+            invite.expires_at,
+        )
+        .map_err(|e| {
+            warn!("Failed to sign domain invite token: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+
+    let dir = get_domain_dir(&ctx.user_context.email, &path.domain)?;
+    let _lock = DomainMembers::lock(&dir).await;
+    let mut members = DomainMembers::load(&dir);
+    members.invites.push(invite.clone());
+    members.save(&dir).map_err(|e| {
+        warn!("Failed to save domain invites: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    info!(
+        "{} invited {} to domain {} as {:?} (invite {})",
+        ctx.user_context.email,
+        invite.email.as_deref().unwrap_or("anyone with the link"),
+        path.domain,
+        invite.role,
+        invite.id
+    );
+
+    Ok(Json(CreatedDomainInviteResponse {
+        invite,
+        url: format!(
+            "{}{}",
+            public_origin(&headers),
+            prefixed(&format!("/api/v1/invites/{}/redeem", token))
+        ),
+        token,
+    }))
+}
+
+/// DELETE /workspace/domains/{domain}/invites/{invite_id} - Revoke a pending invite
+#[utoipa::path(
+    delete,
+    path = "/workspace/domains/{domain}/invites/{invite_id}",
+    tag = "Sharing",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("invite_id" = Uuid, Path, description = "Invite ID")
+    ),
+    responses(
+        (status = 204, description = "Invite revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a domain owner"),
+        (status = 404, description = "Invite not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn revoke_domain_invite(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainInvitePath>,
+) -> Result<StatusCode, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    if !is_domain_owner(&ctx, &path.domain) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let dir = get_domain_dir(&ctx.user_context.email, &path.domain)?;
+    let _lock = DomainMembers::lock(&dir).await;
+    let mut members = DomainMembers::load(&dir);
+    if !members.revoke(path.invite_id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    members.save(&dir).map_err(|e| {
+        warn!("Failed to save domain invites: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /invites/{token}/redeem - Redeem a domain invite as the signed-in user
+#[utoipa::path(
+    post,
+    path = "/invites/{token}/redeem",
+    tag = "Sharing",
+    params(
+        ("token" = String, Path, description = "Invite token")
+    ),
+    responses(
+        (status = 200, description = "Invite redeemed; the user is a member of the domain", body = RedeemedDomainInviteResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "The invite is for another user", body = crate::api_types::ErrorResponse),
+        (status = 404, description = "Invalid, expired, revoked or already redeemed invite", body = crate::api_types::ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn redeem_domain_invite(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<InviteTokenPath>,
+) -> Result<Json<RedeemedDomainInviteResponse>, ApiError> {
+    let user_context = get_user_context(&state, &headers).await?;
+    let not_found = |message: &str| ApiError {
+        status: StatusCode::NOT_FOUND,
+        message: message.to_string(),
+    };
+    let claims = JwtService::from_env()
+        .validate_domain_invite(&path.token)
+        .map_err(|_| not_found("Invalid or expired invite"))?;
+    if super::workspace::validate_domain_name(&claims.domain).is_err() {
+        return Err(not_found("Invalid or expired invite"));
+    }
+
+    let dir = get_domain_dir(&claims.sub, &claims.domain)?;
+    let _lock = DomainMembers::lock(&dir).await;
+    let mut members = DomainMembers::load(&dir);
+    let member = match members.redeem(claims.invite_id, &user_context.email, Utc::now()) {
+        Ok(member) => member,
+        Err(RedeemError::WrongRecipient) => {
+            return Err(ApiError {
+                status: StatusCode::FORBIDDEN,
+                message: "This invite is for another user".to_string(),
+            });
+        }
+        Err(RedeemError::Expired) => {
+            // Drop the expired invite
+            let _ = members.save(&dir);
+            return Err(not_found("Invite has expired"));
+        }
+        Err(RedeemError::NotFound) => {
+            return Err(not_found("Invite was revoked or already redeemed"));
+        }
+    };
+    members.save(&dir).map_err(|e| {
+        warn!("Failed to save domain members: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    info!(
+        "{} joined domain {} of {} as {:?}",
+        user_context.email, claims.domain, claims.sub, member.role
+    );
+
+    Ok(Json(RedeemedDomainInviteResponse {
+        workspace: claims.sub,
+        domain: claims.domain,
+        member,
+    }))
+}
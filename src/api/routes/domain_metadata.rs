@@ -0,0 +1,118 @@
+//! Domain metadata routes.
+//!
+//! Provides domain-scoped endpoints to read and replace a domain's description, color, icon
+//! and owners. Metadata is stored as `domain.yaml` in the domain directory, returned by the
+//! domain list/get endpoints and rendered in the docs export.
+
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+    routing::get,
+};
+use std::path::PathBuf;
+use tracing::warn;
+
+use super::app_state::AppState;
+use super::workspace::{
+    DomainContext, DomainPath, get_workspace_data_dir, home_workspace_email, workspace_dir_name,
+    workspace_email,
+};
+use crate::services::domain_invite_service::DomainRole;
+use crate::services::domain_service::DomainMetadata;
+
+/// Create the domain metadata router
+pub fn domain_metadata_router() -> Router<AppState> {
+    Router::new().route("/", get(get_domain_metadata).put(update_domain_metadata))
+}
+
+/// Get path to a user's domain directory
+fn get_domain_dir(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain))
+}
+
+/// Load a domain's metadata, returning empty metadata if none is stored.
+pub fn load_domain_metadata(email: &str, domain: &str) -> DomainMetadata {
+    get_domain_dir(email, domain)
+        .map(|dir| DomainMetadata::load(&dir))
+        .unwrap_or_default()
+}
+
+/// Whether the user owns the domain: listed in its owners, granted the owner role (see
+/// `access_control_service`), or a user of the workspace while no owners are declared.
+pub(crate) fn is_domain_owner(ctx: &DomainContext, domain: &str) -> bool {
+    let email = &ctx.user_context.email;
+    super::members::access_role(
+        email,
+        &home_workspace_email(email),
+        &workspace_email(email),
+        Some(domain),
+    )
+    .is_ok_and(|role| role == Some(DomainRole::Owner))
+}
+
+/// GET /workspace/domains/{domain}/metadata - Get the domain's metadata
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/metadata",
+    tag = "Workspace",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Domain metadata retrieved successfully", body = DomainMetadata),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_domain_metadata(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+) -> Result<Json<DomainMetadata>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    Ok(Json(load_domain_metadata(
+        &ctx.user_context.email,
+        &domain_path.domain,
+    )))
+}
+
+/// PUT /workspace/domains/{domain}/metadata - Replace the domain's metadata
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/metadata",
+    tag = "Workspace",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body = DomainMetadata,
+    responses(
+        (status = 200, description = "Domain metadata saved successfully", body = DomainMetadata),
+        (status = 400, description = "Bad request - invalid color, icon or owner email"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_domain_metadata(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+    Json(metadata): Json<DomainMetadata>,
+) -> Result<Json<DomainMetadata>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    if let Err(e) = metadata.validate() {
+        warn!("Invalid metadata for domain {}: {}", domain_path.domain, e);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let dir = get_domain_dir(&ctx.user_context.email, &domain_path.domain)?;
+    metadata
+        .save(&dir)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(metadata))
+}
@@ -0,0 +1,322 @@
+//! Bulk domain provisioning routes.
+//!
+//! `POST /workspace/domains:bulk` creates many domains from a manifest in one call, e.g. to
+//! onboard a business unit with its standard domain taxonomy. Each entry may name a template
+//! domain whose tables, relationships and domain configuration are cloned into the new
+//! domain; descriptions and owners are stored in the domain's `domain.yaml`.
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use super::app_state::AppState;
+use super::export_schedules::load_domain_model;
+use super::workspace::{
+    create_workspace_for_email_and_domain, get_or_create_workspace, get_user_context,
+    get_workspace_data_dir, validate_domain_name, workspace_dir_name,
+};
+use crate::models::{DataModel, Relationship, Table};
+use crate::services::GitService;
+use crate::services::domain_service::{
+    DomainMetadata, TEMPLATE_CONFIG_FILES, clone_model_contents,
+};
+use crate::storage::traits::{UserContext, WorkspaceInfo as StorageWorkspaceInfo};
+
+/// Maximum number of domains in one manifest
+const MAX_BULK_DOMAINS: usize = 200;
+
+/// A domain to create
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct DomainManifestEntry {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Owner emails
+    #[serde(default)]
+    pub owners: Vec<String>,
+    /// Domain to clone tables, relationships and configuration from (overrides the manifest default)
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
+/// Manifest of domains to create
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct BulkDomainsRequest {
+    pub domains: Vec<DomainManifestEntry>,
+    /// Default template domain for entries that don't name one
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
+/// Outcome for one manifest entry
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkDomainResult {
+    pub domain: String,
+    /// created, exists or failed
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    pub tables_cloned: usize,
+    pub relationships_cloned: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Response for bulk domain provisioning
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BulkDomainsResponse {
+    pub created: usize,
+    pub results: Vec<BulkDomainResult>,
+}
+
+/// POST /workspace/domains:bulk - Create many domains from a manifest
+#[utoipa::path(
+    post,
+    path = "/workspace/domains:bulk",
+    tag = "Workspace",
+    request_body = BulkDomainsRequest,
+    responses(
+        (status = 200, description = "Per-domain provisioning results", body = BulkDomainsResponse),
+        (status = 400, description = "Bad request - invalid or duplicate domain name, unknown template or too many domains"),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn bulk_create_domains(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<BulkDomainsRequest>,
+) -> Result<Json<BulkDomainsResponse>, StatusCode> {
+    let user_context = get_user_context(&state, &headers).await?;
+    if request.domains.is_empty() || request.domains.len() > MAX_BULK_DOMAINS {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    // Validate the whole manifest before creating anything
+    let mut names = HashSet::new();
+    for entry in &request.domains {
+        validate_domain_name(entry.name.trim())?;
+        if !names.insert(entry.name.trim().to_string()) {
+            warn!("Duplicate domain in manifest: {}", entry.name);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+    let workspace = get_or_create_workspace(&state, &user_context).await?;
+    let user_dir = get_workspace_data_dir()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .join(workspace_dir_name(&user_context.email));
+
+    let mut templates: HashMap<String, DataModel> = HashMap::new();
+    for entry in &request.domains {
+        let Some(template) = entry.template.as_ref().or(request.template.as_ref()) else {
+            continue;
+        };
+        if templates.contains_key(template) {
+            continue;
+        }
+        validate_domain_name(template)?;
+        if !domain_exists(&state, &workspace, &user_dir, template).await? {
+            warn!("Unknown template domain: {}", template);
+            return Err(StatusCode::BAD_REQUEST);
+        }
+        let model = load_domain_model(&state, &user_context.email, template)
+            .await
+            .map_err(|e| {
+                warn!("Failed to load template domain {}: {}", template, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        templates.insert(template.clone(), model);
+    }
+
+    let mut results = Vec::with_capacity(request.domains.len());
+    for entry in &request.domains {
+        let name = entry.name.trim();
+        let template = entry.template.clone().or_else(|| request.template.clone());
+        let mut result = BulkDomainResult {
+            domain: name.to_string(),
+            status: "created".to_string(),
+            template: template.clone(),
+            tables_cloned: 0,
+            relationships_cloned: 0,
+            error: None,
+        };
+        if domain_exists(&state, &workspace, &user_dir, name).await? {
+            result.status = "exists".to_string();
+            results.push(result);
+            continue;
+        }
+        let template_dir = template.as_ref().map(|t| user_dir.join(t));
+        let template_model = template.as_ref().and_then(|t| templates.get(t));
+        match provision_domain(
+            &state,
+            &user_context,
+            &workspace,
+            &user_dir,
+            entry,
+            template_dir.as_deref().zip(template_model),
+        )
+        .await
+        {
+            Ok((tables, relationships)) => {
+                result.tables_cloned = tables;
+                result.relationships_cloned = relationships;
+            }
+            Err(e) => {
+                warn!("Failed to provision domain {}: {}", name, e);
+                result.status = "failed".to_string();
+                result.error = Some(e);
+            }
+        }
+        results.push(result);
+    }
+
+    let created = results.iter().filter(|r| r.status == "created").count();
+    info!(
+        "Provisioned {} of {} domain(s) for user {}",
+        created,
+        results.len(),
+        user_context.email
+    );
+    Ok(Json(BulkDomainsResponse { created, results }))
+}
+
+pub(crate) async fn domain_exists(
+    state: &AppState,
+    workspace: &StorageWorkspaceInfo,
+    user_dir: &Path,
+    name: &str,
+) -> Result<bool, StatusCode> {
+    if state.is_postgres()
+        && let Some(storage) = state.storage.as_ref()
+    {
+        return storage
+            .get_domain_by_name(workspace.id, name)
+            .await
+            .map(|domain| domain.is_some())
+            .map_err(|e| {
+                warn!("Failed to look up domain {}: {}", name, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            });
+    }
+    Ok(user_dir.join(name).is_dir())
+}
+
+/// Create one domain, cloning the template (its directory and model) if given. Returns the
+/// number of cloned tables and relationships.
+pub(crate) async fn provision_domain(
+    state: &AppState,
+    user_context: &UserContext,
+    workspace: &StorageWorkspaceInfo,
+    user_dir: &Path,
+    entry: &DomainManifestEntry,
+    template: Option<(&Path, &DataModel)>,
+) -> Result<(usize, usize), String> {
+    let (tables, relationships) = template
+        .map(|(_, model)| clone_model_contents(model))
+        .unwrap_or_default();
+    create_domain_with_model(
+        state,
+        user_context,
+        workspace,
+        user_dir,
+        entry,
+        &tables,
+        &relationships,
+    )
+    .await?;
+    if let Some((template_dir, _)) = template {
+        copy_template_config(template_dir, &user_dir.join(entry.name.trim()))?;
+    }
+    Ok((tables.len(), relationships.len()))
+}
+
+/// Create one domain holding the given tables and relationships, keeping their ids.
+pub(crate) async fn create_domain_with_model(
+    state: &AppState,
+    user_context: &UserContext,
+    workspace: &StorageWorkspaceInfo,
+    user_dir: &Path,
+    entry: &DomainManifestEntry,
+    tables: &[Table],
+    relationships: &[Relationship],
+) -> Result<(), String> {
+    let name = entry.name.trim();
+    let domain_dir = user_dir.join(name);
+
+    if state.is_postgres()
+        && let Some(storage) = state.storage.as_ref()
+    {
+        let domain = storage
+            .create_domain(
+                workspace.id,
+                name.to_string(),
+                entry.description.clone(),
+                user_context,
+            )
+            .await
+            .map_err(|e| e.to_string())?;
+        for table in tables {
+            storage
+                .create_table(domain.id, table.clone(), user_context)
+                .await
+                .map_err(|e| format!("Failed to clone table {}: {}", table.name, e))?;
+        }
+        for relationship in relationships {
+            storage
+                .create_relationship(domain.id, relationship.clone(), user_context)
+                .await
+                .map_err(|e| format!("Failed to clone relationship: {}", e))?;
+        }
+    } else {
+        {
+            let mut model_service = state.model_service.lock().await;
+            create_workspace_for_email_and_domain(&mut model_service, &user_context.email, name)
+                .await?;
+        }
+        if !tables.is_empty() {
+            let mut git_service = GitService::new();
+            git_service
+                .set_git_directory_path(&domain_dir)
+                .map_err(|e| e.to_string())?;
+            for table in tables {
+                git_service
+                    .save_table_to_yaml(table)
+                    .map_err(|e| e.to_string())?;
+            }
+            git_service
+                .save_relationships_to_yaml(relationships, tables)
+                .map_err(|e| e.to_string())?;
+        }
+    }
+
+    let metadata = DomainMetadata {
+        description: entry.description.clone(),
+        owners: entry.owners.clone(),
+        ..Default::default()
+    };
+    if !metadata.is_empty() {
+        metadata.save(&domain_dir).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Copy the template's domain configuration files into the new domain.
+fn copy_template_config(template_dir: &Path, domain_dir: &Path) -> Result<(), String> {
+    for file in TEMPLATE_CONFIG_FILES {
+        let source = template_dir.join(file);
+        if source.exists() {
+            std::fs::create_dir_all(domain_dir).map_err(|e| e.to_string())?;
+            std::fs::copy(&source, domain_dir.join(file))
+                .map_err(|e| format!("Failed to copy {}: {}", file, e))?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,606 @@
+//! Read-only domain sharing routes.
+//!
+//! Domain owners create share links (optionally password-protected and expiring) that let
+//! external partners browse the domain's canvas and tables without an account. The public
+//! API under `/shared/domains/{token}` only reads; deleting a share revokes its link, and
+//! administrators can turn sharing off server-wide with `PUT /admin/sharing`. A password is
+//! sent in the `X-Share-Password` header.
+
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+    routing::{delete, get},
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Instant;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::audit::is_admin;
+use super::diagram_shares::public_origin;
+use super::domain_metadata::{is_domain_owner, load_domain_metadata};
+use super::error::ApiError;
+use super::export_schedules::load_domain_model;
+use super::workspace::{DomainPath, get_workspace_data_dir, workspace_dir_name, workspace_email};
+use crate::middleware::base_path::prefixed;
+use crate::middleware::client_ip::ClientIp;
+use crate::models::{DataModel, Relationship, Table};
+use crate::services::domain_share_service::{
+    DomainShare, DomainShares, SharingSettings, client_password_failures, hash_password,
+    share_password_failures,
+};
+use crate::services::jwt_service::{DomainShareClaims, JwtService};
+
+/// Header carrying the password of a protected link
+const PASSWORD_HEADER: &str = "x-share-password";
+
+/// Create the share management router (nested under `/workspace/domains/{domain}/shares`)
+pub fn domain_shares_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_domain_shares).post(create_domain_share))
+        .route("/{share_id}", delete(delete_domain_share))
+}
+
+/// Create the public read-only router (nested under `/shared/domains/{token}`)
+pub fn shared_domain_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(get_shared_domain))
+        .route("/tables", get(list_shared_tables))
+        .route("/tables/{table_id}", get(get_shared_table))
+}
+
+/// Request body for creating a share link
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct CreateDomainShareRequest {
+    /// Who the link is for (e.g. a partner name)
+    pub label: Option<String>,
+    /// Password partners must send in `X-Share-Password`
+    pub password: Option<String>,
+    /// Days until the link expires; never if omitted
+    pub expires_in_days: Option<i64>,
+}
+
+/// A share link, without its secrets
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DomainShareResponse {
+    pub id: Uuid,
+    pub label: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub password_protected: bool,
+    pub expired: bool,
+}
+
+impl From<&DomainShare> for DomainShareResponse {
+    fn from(share: &DomainShare) -> Self {
+        Self {
+            id: share.id,
+            label: share.label.clone(),
+            created_by: share.created_by.clone(),
+            created_at: share.created_at,
+            expires_at: share.expires_at,
+            password_protected: share.password_hash.is_some(),
+            expired: share.is_expired(Utc::now()),
+        }
+    }
+}
+
+/// A newly created share link
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CreatedDomainShareResponse {
+    pub share: DomainShareResponse,
+    /// Public URL of the shared domain
+    pub url: String,
+    pub token: String,
+}
+
+/// A shared domain's canvas
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SharedDomainResponse {
+    pub domain: String,
+    pub description: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub tables: Vec<Table>,
+    pub relationships: Vec<Relationship>,
+}
+
+/// Path parameters of a share
+#[derive(Deserialize)]
+pub struct DomainSharePath {
+    pub domain: String,
+    pub share_id: Uuid,
+}
+
+/// Path parameters of a shared domain
+#[derive(Deserialize)]
+pub struct SharedDomainPath {
+    pub token: String,
+}
+
+/// Path parameters of a table of a shared domain
+#[derive(Deserialize)]
+pub struct SharedTablePath {
+    pub token: String,
+    pub table_id: Uuid,
+}
+
+fn get_domain_dir(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain))
+}
+
+/// Sign the token of a share link. It names the workspace holding the domain rather than the
+/// user, since no workspace is active when the link is opened.
+fn sign_share_token(
+    jwt: &JwtService,
+    email: &str,
+    domain: &str,
+    share: &DomainShare,
+) -> Result<String, String> {
+    jwt.sign_domain_share(&workspace_email(email), domain, share.id, share.expires_at)
+}
+
+/// Whether share links work on this server.
+pub fn sharing_enabled() -> bool {
+    get_workspace_data_dir()
+        .map(|dir| SharingSettings::load(&dir))
+        .unwrap_or_default()
+        .enabled
+}
+
+/// GET /workspace/domains/{domain}/shares - List the domain's share links
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/shares",
+    tag = "Sharing",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Share links of the domain", body = Vec<DomainShareResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_domain_shares(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+) -> Result<Json<Vec<DomainShareResponse>>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let shares = DomainShares::load(&get_domain_dir(&ctx.user_context.email, &path.domain)?);
+    Ok(Json(shares.shares.iter().map(Into::into).collect()))
+}
+
+/// POST /workspace/domains/{domain}/shares - Create a read-only share link
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/shares",
+    tag = "Sharing",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body = CreateDomainShareRequest,
+    responses(
+        (status = 200, description = "Share link created", body = CreatedDomainShareResponse),
+        (status = 400, description = "Invalid expiry or empty password", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a domain owner, or sharing is disabled", body = crate::api_types::ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_domain_share(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    Json(request): Json<CreateDomainShareRequest>,
+) -> Result<Json<CreatedDomainShareResponse>, ApiError> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let forbidden = |message: &str| ApiError {
+        status: StatusCode::FORBIDDEN,
+        message: message.to_string(),
+    };
+    let bad_request = |message: &str| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message: message.to_string(),
+    };
+    if !sharing_enabled() {
+        return Err(forbidden("Sharing is disabled on this server"));
+    }
+    if !is_domain_owner(&ctx, &path.domain) {
+        return Err(forbidden("Only domain owners can share a domain"));
+    }
+    if request.expires_in_days.is_some_and(|days| days < 1) {
+        return Err(bad_request("expires_in_days must be at least 1"));
+    }
+    if request.password.as_deref().is_some_and(str::is_empty) {
+        return Err(bad_request("password must not be empty"));
+    }
+
+    let now = Utc::now();
+    let share = DomainShare {
+        id: Uuid::new_v4(),
+        label: request.label,
+        created_by: ctx.user_context.email.clone(),
+        created_at: now,
+        expires_at: request
+            .expires_in_days
+            .map(|days| now + Duration::days(days)),
+        password_hash: request.password.as_deref().map(hash_password),
+    };
+    let token = sign_share_token(
+        &JwtService::from_env(),
+        &ctx.user_context.email,
+        &path.domain,
+        &share,
+    )
+    .map_err(|e| {
+        warn!("Failed to sign domain share token: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let dir = get_domain_dir(&ctx.user_context.email, &path.domain)?;
+    let mut shares = DomainShares::load(&dir);
+    shares.shares.push(share.clone());
+    shares.save(&dir).map_err(|e| {
+        warn!("Failed to save domain shares: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    info!(
+        "{} shared domain {} (share {})",
+        ctx.user_context.email, path.domain, share.id
+    );
+
+    Ok(Json(CreatedDomainShareResponse {
+        share: (&share).into(),
+        url: format!(
+            "{}{}",
+            public_origin(&headers),
+            prefixed(&format!("/api/v1/shared/domains/{}", token))
+        ),
+        token,
+    }))
+}
+
+/// DELETE /workspace/domains/{domain}/shares/{share_id} - Revoke a share link
+#[utoipa::path(
+    delete,
+    path = "/workspace/domains/{domain}/shares/{share_id}",
+    tag = "Sharing",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("share_id" = Uuid, Path, description = "Share ID")
+    ),
+    responses(
+        (status = 204, description = "Share link revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a domain owner"),
+        (status = 404, description = "Share not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_domain_share(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainSharePath>,
+) -> Result<StatusCode, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    if !is_domain_owner(&ctx, &path.domain) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let dir = get_domain_dir(&ctx.user_context.email, &path.domain)?;
+    let mut shares = DomainShares::load(&dir);
+    if !shares.remove(path.share_id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    shares.save(&dir).map_err(|e| {
+        warn!("Failed to save domain shares: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Check the password of a protected link, throttling wrong ones per link and per client.
+async fn check_share_password(
+    share: &DomainShare,
+    headers: &HeaderMap,
+    ClientIp(client): ClientIp,
+) -> Result<(), ApiError> {
+    let now = Instant::now();
+    let locked_for = share_password_failures()
+        .locked_for(&share.id, now)
+        .max(client.and_then(|ip| client_password_failures().locked_for(&ip, now)));
+    if let Some(wait) = locked_for {
+        return Err(ApiError {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            message: format!(
+                "Too many wrong passwords; try again in {} seconds",
+                wait.as_secs().max(1)
+            ),
+        });
+    }
+
+    let password = headers
+        .get(PASSWORD_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    let attempted = password.is_some();
+    let checked = share.clone();
+    // PBKDF2 takes tens of milliseconds; keep it off the async workers
+    let valid = tokio::task::spawn_blocking(move || checked.check_password(password.as_deref()))
+        .await
+        .map_err(|_| ApiError::from(StatusCode::INTERNAL_SERVER_ERROR))?;
+    if !valid {
+        if attempted {
+            share_password_failures().record_failure(share.id, now);
+            if let Some(ip) = client {
+                client_password_failures().record_failure(ip, now);
+            }
+        }
+        return Err(ApiError {
+            status: StatusCode::UNAUTHORIZED,
+            message: "Share link requires a valid X-Share-Password header".to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Check a share token (and password) and load the shared domain.
+async fn open_share(
+    state: &AppState,
+    headers: &HeaderMap,
+    client: ClientIp,
+    token: &str,
+) -> Result<(DomainShareClaims, DomainShare, DataModel), ApiError> {
+    // Disabled sharing looks like an unknown link
+    if !sharing_enabled() {
+        return Err(StatusCode::NOT_FOUND.into());
+    }
+    let claims = JwtService::from_env()
+        .validate_domain_share(token)
+        .map_err(|_| ApiError::from(StatusCode::UNAUTHORIZED))?;
+    let shares = DomainShares::load(&get_domain_dir(&claims.sub, &claims.domain)?);
+    let share = shares
+        .get(claims.share_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+    if share.is_expired(Utc::now()) {
+        return Err(ApiError {
+            status: StatusCode::GONE,
+            message: "Share link has expired".to_string(),
+        });
+    }
+    if share.password_hash.is_some() {
+        check_share_password(&share, headers, client).await?;
+    }
+    let model = load_domain_model(state, &claims.sub, &claims.domain)
+        .await
+        .map_err(|e| {
+            warn!("Failed to load shared domain {}: {}", claims.domain, e);
+            ApiError::from(StatusCode::NOT_FOUND)
+        })?;
+    Ok((claims, share, model))
+}
+
+/// GET /shared/domains/{token} - Browse a shared domain's canvas (no account needed)
+#[utoipa::path(
+    get,
+    path = "/shared/domains/{token}",
+    tag = "Sharing",
+    params(
+        ("token" = String, Path, description = "Share token from the share link"),
+        ("X-Share-Password" = Option<String>, Header, description = "Password of a protected link")
+    ),
+    responses(
+        (status = 200, description = "Tables (with canvas positions) and relationships of the domain", body = SharedDomainResponse),
+        (status = 401, description = "Invalid token or password", body = crate::api_types::ErrorResponse),
+        (status = 404, description = "Link revoked or sharing disabled"),
+        (status = 410, description = "Link expired", body = crate::api_types::ErrorResponse),
+        (status = 429, description = "Too many wrong passwords", body = crate::api_types::ErrorResponse)
+    )
+)]
+pub async fn get_shared_domain(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    client: ClientIp,
+    Path(path): Path<SharedDomainPath>,
+) -> Result<Json<SharedDomainResponse>, ApiError> {
+    let (claims, share, model) = open_share(&state, &headers, client, &path.token).await?;
+    Ok(Json(SharedDomainResponse {
+        description: load_domain_metadata(&claims.sub, &claims.domain).description,
+        domain: claims.domain,
+        expires_at: share.expires_at,
+        tables: model.tables,
+        relationships: model.relationships,
+    }))
+}
+
+/// GET /shared/domains/{token}/tables - List the tables of a shared domain
+#[utoipa::path(
+    get,
+    path = "/shared/domains/{token}/tables",
+    tag = "Sharing",
+    params(
+        ("token" = String, Path, description = "Share token from the share link"),
+        ("X-Share-Password" = Option<String>, Header, description = "Password of a protected link")
+    ),
+    responses(
+        (status = 200, description = "Tables of the domain", body = Vec<Table>),
+        (status = 401, description = "Invalid token or password", body = crate::api_types::ErrorResponse),
+        (status = 404, description = "Link revoked or sharing disabled"),
+        (status = 410, description = "Link expired", body = crate::api_types::ErrorResponse),
+        (status = 429, description = "Too many wrong passwords", body = crate::api_types::ErrorResponse)
+    )
+)]
+pub async fn list_shared_tables(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    client: ClientIp,
+    Path(path): Path<SharedDomainPath>,
+) -> Result<Json<Vec<Table>>, ApiError> {
+    let (_, _, model) = open_share(&state, &headers, client, &path.token).await?;
+    Ok(Json(model.tables))
+}
+
+/// GET /shared/domains/{token}/tables/{table_id} - Get a table of a shared domain
+#[utoipa::path(
+    get,
+    path = "/shared/domains/{token}/tables/{table_id}",
+    tag = "Sharing",
+    params(
+        ("token" = String, Path, description = "Share token from the share link"),
+        ("table_id" = Uuid, Path, description = "Table UUID"),
+        ("X-Share-Password" = Option<String>, Header, description = "Password of a protected link")
+    ),
+    responses(
+        (status = 200, description = "The table", body = Table),
+        (status = 401, description = "Invalid token or password", body = crate::api_types::ErrorResponse),
+        (status = 404, description = "Table not found, link revoked or sharing disabled"),
+        (status = 410, description = "Link expired", body = crate::api_types::ErrorResponse),
+        (status = 429, description = "Too many wrong passwords", body = crate::api_types::ErrorResponse)
+    )
+)]
+pub async fn get_shared_table(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    client: ClientIp,
+    Path(path): Path<SharedTablePath>,
+) -> Result<Json<Table>, ApiError> {
+    let (_, _, model) = open_share(&state, &headers, client, &path.token).await?;
+    model
+        .tables
+        .into_iter()
+        .find(|t| t.id == path.table_id)
+        .map(Json)
+        .ok_or_else(|| StatusCode::NOT_FOUND.into())
+}
+
+/// GET /admin/sharing - Get the server-wide sharing setting
+#[utoipa::path(
+    get,
+    path = "/admin/sharing",
+    tag = "Sharing",
+    responses(
+        (status = 200, description = "Whether share links work on this server", body = SharingSettings),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an administrator")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_sharing_settings(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<SharingSettings>, StatusCode> {
+    let user_context = super::workspace::get_user_context(&state, &headers).await?;
+    if !is_admin(&user_context.email) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let dir = get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(SharingSettings::load(&dir)))
+}
+
+/// PUT /admin/sharing - Enable or disable sharing server-wide
+#[utoipa::path(
+    put,
+    path = "/admin/sharing",
+    tag = "Sharing",
+    request_body = SharingSettings,
+    responses(
+        (status = 200, description = "Setting saved; disabling stops all existing domain and diagram links", body = SharingSettings),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an administrator"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_sharing_settings(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(settings): Json<SharingSettings>,
+) -> Result<Json<SharingSettings>, StatusCode> {
+    let user_context = super::workspace::get_user_context(&state, &headers).await?;
+    if !is_admin(&user_context.email) {
+        warn!(
+            "Non-admin {} denied changing the sharing setting",
+            user_context.email
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let dir = get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    settings.save(&dir).map_err(|e| {
+        warn!("Failed to save sharing setting: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    info!(
+        "{} {} sharing",
+        user_context.email,
+        if settings.enabled {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+    Ok(Json(settings))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::middleware::access_control::in_workspace;
+
+    #[tokio::test]
+    async fn test_share_tokens_name_the_active_workspace() {
+        let jwt = JwtService::new("test-secret-at-least-32-characters-long");
+        let share = DomainShare {
+            id: Uuid::new_v4(),
+            label: None,
+            created_by: "alice@example.com".to_string(),
+            created_at: Utc::now(),
+            expires_at: None,
+            password_hash: None,
+        };
+        let team = Some("team@example.com".to_string());
+
+        // Created under X-Workspace, opened without an active workspace
+        let token = in_workspace(team.clone(), async {
+            sign_share_token(&jwt, "alice@example.com", "sales", &share)
+        })
+        .await
+        .unwrap();
+        let claims = jwt.validate_domain_share(&token).unwrap();
+        assert_eq!(claims.sub, "team@example.com");
+        assert_eq!(workspace_dir_name(&claims.sub), "team_at_example_com");
+
+        let (token, _) = in_workspace(team, async {
+            super::super::diagram_shares::sign_diagram_token(
+                &jwt,
+                "alice@example.com",
+                "sales",
+                Vec::new(),
+                Duration::days(1),
+            )
+        })
+        .await
+        .unwrap();
+        let claims = jwt.validate_diagram_share(&token).unwrap();
+        assert_eq!(workspace_dir_name(&claims.sub), "team_at_example_com");
+
+        // In the home workspace the token names the user
+        let token = sign_share_token(&jwt, "alice@example.com", "sales", &share).unwrap();
+        let claims = jwt.validate_domain_share(&token).unwrap();
+        assert_eq!(workspace_dir_name(&claims.sub), "alice_at_example_com");
+    }
+}
@@ -0,0 +1,585 @@
+//! Draft mode routes.
+//!
+//! `POST /workspace/domains/{domain}/draft` opens a draft for the calling session. Until it is
+//! saved or discarded, that session's table and relationship edits (including canvas moves)
+//! are kept in the draft and nothing is written to the domain; other sessions keep seeing the
+//! saved model. `POST .../draft/save` writes all changes at once, `POST .../draft/discard`
+//! drops them.
+//!
+//! A draft can autosave in the background (`DRAFT_AUTOSAVE_SECS`, or per draft in the body of
+//! `POST .../draft`). `GET /workspace/domains/{domain}/save-status` lists the tables and
+//! relationships with unsaved changes and when the domain was last written, for "unsaved
+//! changes" indicators.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{info, warn};
+use utoipa::{IntoParams, ToSchema};
+
+use super::app_state::AppState;
+use super::error::ApiError;
+use super::events;
+use super::workspace::{
+    DomainContext, DomainPath, ensure_domain_loaded, ensure_domain_loaded_with_reload,
+    load_domain_model, serialize_table_with_database_type,
+};
+use crate::middleware::access_control::{active_workspace, in_workspace};
+use crate::models::{DataModel, Relationship, Table};
+use crate::services::draft_service::{
+    AutosaveScope, AutosaveSettings, DirtyObject, Draft, DraftChanges, DraftStore, DraftSummary,
+};
+use crate::services::event_log_service::ChangeEventType;
+use crate::services::jwt_service::JwtService;
+use crate::services::local_mode::local_mode;
+
+/// An open draft
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DraftStatus {
+    pub domain: String,
+    pub opened_at: DateTime<Utc>,
+    /// Unsaved changes in the draft (for save and discard: the changes saved or dropped)
+    pub changes: DraftSummary,
+    pub autosave: AutosaveSettings,
+}
+
+impl DraftStatus {
+    async fn new(domain: &str, draft: &Draft) -> Self {
+        Self {
+            domain: domain.to_string(),
+            opened_at: draft.opened_at,
+            changes: draft.changes().await.summary(),
+            autosave: draft.autosave(),
+        }
+    }
+}
+
+/// Body of `POST /draft`
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct OpenDraftRequest {
+    /// Autosave settings for this draft (default: `DRAFT_AUTOSAVE_SECS`, all changes). Sent
+    /// for an already open draft, they replace its settings.
+    #[serde(default)]
+    pub autosave: Option<AutosaveSettings>,
+}
+
+/// Autosave state of an open draft
+#[derive(Debug, Serialize, ToSchema)]
+pub struct AutosaveStatus {
+    /// Seconds between autosaves; 0 when autosave is off
+    pub interval_secs: u64,
+    pub scope: AutosaveScope,
+    pub last_autosave_at: Option<DateTime<Utc>>,
+    /// Why the last autosave failed, until one succeeds
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// Unsaved changes of a domain for the calling session
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SaveStatus {
+    pub domain: String,
+    /// Whether the session has a draft open; without one every edit is saved as it is made
+    pub draft_open: bool,
+    /// Tables and relationships with unsaved changes
+    pub dirty: Vec<DirtyObject>,
+    /// When the domain's tables and relationships were last written
+    pub last_persisted_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autosave: Option<AutosaveStatus>,
+}
+
+/// Query parameters of `POST /draft/save`
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct SaveDraftQuery {
+    /// Overwrite tables and relationships that were changed by someone else meanwhile
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Session a draft belongs to: the JWT session id, or a fixed id in local mode
+pub(crate) fn session_id(headers: &HeaderMap) -> Option<String> {
+    if local_mode().is_some() {
+        return Some("local".to_string());
+    }
+    let token = match headers.get("authorization").and_then(|h| h.to_str().ok()) {
+        Some(auth_header) => JwtService::extract_bearer_token(auth_header),
+        None => headers.get("x-session-id").and_then(|h| h.to_str().ok()),
+    }?;
+    JwtService::from_env()
+        .validate_access_token(token)
+        .ok()
+        .map(|claims| claims.session_id)
+}
+
+fn draft_key(headers: &HeaderMap, ctx: &DomainContext, domain: &str) -> Result<String, StatusCode> {
+    let session_id = session_id(headers).ok_or(StatusCode::UNAUTHORIZED)?;
+    Ok(DraftStore::key(
+        &session_id,
+        &ctx.user_context.email,
+        domain,
+    ))
+}
+
+/// The saved tables and relationships of the domain
+async fn saved_model(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+) -> Result<DataModel, StatusCode> {
+    if let Some(storage) = state.storage.as_ref() {
+        let domain_id = ctx.domain_info.id;
+        let mut model = DataModel::new(domain.to_string(), String::new(), String::new());
+        model.tables = storage
+            .get_tables(domain_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        model.relationships = storage
+            .get_relationships(domain_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        return Ok(model);
+    }
+    state
+        .model_service
+        .lock()
+        .await
+        .get_current_model()
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Latest write of the model's tables and relationships: file modification times in a
+/// domain directory, `updated_at` otherwise
+fn last_persisted_at(model: &DataModel) -> Option<DateTime<Utc>> {
+    if model.git_directory_path.is_empty() {
+        let tables = model.tables.iter().map(|t| t.updated_at);
+        let relationships = model.relationships.iter().map(|r| r.updated_at);
+        return tables.chain(relationships).max();
+    }
+    let dir = std::path::Path::new(&model.git_directory_path);
+    let table_files = std::fs::read_dir(dir.join("tables"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path());
+    table_files
+        .chain(std::iter::once(dir.join("relationships.yaml")))
+        .filter_map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+        .max()
+        .map(DateTime::<Utc>::from)
+}
+
+fn conflict_error(conflicts: &[uuid::Uuid]) -> ApiError {
+    let ids: Vec<String> = conflicts.iter().map(|id| id.to_string()).collect();
+    ApiError {
+        status: StatusCode::CONFLICT,
+        message: format!(
+            "Modified by someone else since the draft was opened: {}. Save with force=true to overwrite them",
+            ids.join(", ")
+        ),
+    }
+}
+
+/// Write the draft's changes through the storage backend
+async fn apply_to_storage(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+    changes: &DraftChanges,
+    force: bool,
+) -> Result<(), ApiError> {
+    let Some(storage) = state.storage.as_ref() else {
+        return Ok(());
+    };
+    let saved = saved_model(state, ctx, domain).await?;
+    let conflicts = changes.conflicts(&saved.tables, &saved.relationships);
+    if !conflicts.is_empty() && !force {
+        return Err(conflict_error(&conflicts));
+    }
+
+    let failed = |e| {
+        warn!("Failed to save draft: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    };
+    let (domain_id, user) = (ctx.domain_info.id, &ctx.user_context);
+    for relationship in &changes.deleted_relationships {
+        storage
+            .delete_relationship(domain_id, relationship.id, user)
+            .await
+            .map_err(failed)?;
+    }
+    for table in &changes.deleted_tables {
+        storage
+            .delete_table(domain_id, table.id, user)
+            .await
+            .map_err(failed)?;
+    }
+    for table in &changes.created_tables {
+        storage
+            .create_table(domain_id, table.clone(), user)
+            .await
+            .map_err(failed)?;
+    }
+    for (_, table) in &changes.updated_tables {
+        storage
+            .update_table(table.clone(), None, user)
+            .await
+            .map_err(failed)?;
+    }
+    for relationship in &changes.created_relationships {
+        storage
+            .create_relationship(domain_id, relationship.clone(), user)
+            .await
+            .map_err(failed)?;
+    }
+    for (_, relationship) in &changes.updated_relationships {
+        storage
+            .update_relationship(relationship.clone(), None, user)
+            .await
+            .map_err(failed)?;
+    }
+    Ok(())
+}
+
+/// Write the draft's changes to the domain directory, reloading it first so conflicts are
+/// checked against the files as they are now
+async fn apply_to_files(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+    changes: &DraftChanges,
+    force: bool,
+) -> Result<(), ApiError> {
+    let mut model_service = state.model_service.lock().await;
+    let saved = load_domain_model(&mut model_service, &ctx.user_context.email, domain, true)?;
+    let conflicts = changes.conflicts(&saved.tables, &saved.relationships);
+    if !conflicts.is_empty() && !force {
+        return Err(conflict_error(&conflicts));
+    }
+    model_service.apply_draft(changes).map_err(|e| {
+        warn!("Failed to save draft: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR.into()
+    })
+}
+
+/// Write the draft's unsaved changes (with `autosave`, only those in the autosave scope) and
+/// fold them into its snapshot. `ctx` must not carry the draft.
+async fn persist(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+    draft: &Draft,
+    autosave: Option<AutosaveScope>,
+    force: bool,
+) -> Result<DraftChanges, ApiError> {
+    let _saving = draft.lock_saves().await;
+    let mut changes = draft.changes().await;
+    if autosave == Some(AutosaveScope::Layout) {
+        changes = changes.layout_only();
+    }
+    if changes.is_empty() {
+        return Ok(changes);
+    }
+    if state.storage.is_some() {
+        apply_to_storage(state, ctx, domain, &changes, force).await?;
+    } else {
+        apply_to_files(state, ctx, domain, &changes, force).await?;
+    }
+    // Events are held back while a draft is open and recorded once the changes are written
+    record_changes(state, ctx, domain, &changes).await;
+    draft.mark_saved(&changes, autosave.is_some());
+    Ok(changes)
+}
+
+/// Autosave `draft` until it is closed or its autosave is turned off
+fn spawn_autosave(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+    key: String,
+    draft: Arc<Draft>,
+) {
+    if !draft.start_autosave() {
+        return;
+    }
+    let (state, domain) = (state.clone(), domain.to_string());
+    let ctx = DomainContext {
+        draft: None,
+        ..ctx.clone()
+    };
+    // Saves go to the workspace the draft was opened in, after the request is gone
+    let workspace = active_workspace();
+    tokio::spawn(in_workspace(workspace, async move {
+        while let Some(settings) = draft.next_autosave() {
+            tokio::time::sleep(Duration::from_secs(settings.interval_secs)).await;
+            if !state.drafts.is_open(&key, &draft) {
+                break;
+            }
+            // Conflicts are not overwritten; they stay unsaved until an explicit save
+            match persist(&state, &ctx, &domain, &draft, Some(settings.scope), false).await {
+                Ok(changes) if !changes.is_empty() => info!(
+                    "Autosaved draft of domain {} for {}: {:?}",
+                    domain,
+                    ctx.user_context.email,
+                    changes.summary()
+                ),
+                Ok(_) => {}
+                Err(e) => {
+                    warn!("Autosave of domain {} failed: {}", domain, e.message);
+                    draft.record_autosave_error(e.message);
+                }
+            }
+        }
+    }));
+}
+
+/// Record the change events that were held back while the draft was open
+async fn record_changes(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+    changes: &DraftChanges,
+) {
+    let table_json = |t: &Table| Some(serialize_table_with_database_type(t));
+    let relationship_json = |r: &Relationship| serde_json::to_value(r).ok();
+    let mut records: Vec<(ChangeEventType, uuid::Uuid, Option<Value>, Option<Value>)> = Vec::new();
+    records.extend(
+        (changes.created_tables.iter())
+            .map(|t| (ChangeEventType::TableCreated, t.id, None, table_json(t))),
+    );
+    records.extend(changes.updated_tables.iter().map(|(before, after)| {
+        (
+            ChangeEventType::TableUpdated,
+            after.id,
+            table_json(before),
+            table_json(after),
+        )
+    }));
+    records.extend(
+        (changes.deleted_tables.iter())
+            .map(|t| (ChangeEventType::TableDeleted, t.id, table_json(t), None)),
+    );
+    records.extend(changes.created_relationships.iter().map(|r| {
+        (
+            ChangeEventType::RelationshipCreated,
+            r.id,
+            None,
+            relationship_json(r),
+        )
+    }));
+    records.extend(changes.updated_relationships.iter().map(|(before, after)| {
+        (
+            ChangeEventType::RelationshipUpdated,
+            after.id,
+            relationship_json(before),
+            relationship_json(after),
+        )
+    }));
+    records.extend(changes.deleted_relationships.iter().map(|r| {
+        (
+            ChangeEventType::RelationshipDeleted,
+            r.id,
+            relationship_json(r),
+            None,
+        )
+    }));
+    for (event_type, id, before, after) in records {
+        events::record_model_change(state, ctx, domain, event_type, id, before, after).await;
+    }
+}
+
+/// POST /workspace/domains/{domain}/draft - Open a draft of the domain for this session
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/draft",
+    tag = "Workspace",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body(content = Option<OpenDraftRequest>, description = "Autosave settings", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Draft opened (or the already open draft)", body = DraftStatus),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Domain not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn open_draft(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    request: Option<Json<OpenDraftRequest>>,
+) -> Result<Json<DraftStatus>, StatusCode> {
+    let ctx = ensure_domain_loaded_with_reload(&state, &headers, &path.domain, true).await?;
+    let autosave = request.and_then(|Json(request)| request.autosave);
+    let key = draft_key(&headers, &ctx, &path.domain)?;
+    let draft = match &ctx.draft {
+        Some(draft) => {
+            if let Some(settings) = autosave {
+                draft.set_autosave(settings);
+            }
+            draft.clone()
+        }
+        None => {
+            let base = saved_model(&state, &ctx, &path.domain).await?;
+            let settings = autosave.unwrap_or(state.draft_autosave);
+            info!(
+                "{} opened a draft of domain {}",
+                ctx.user_context.email, path.domain
+            );
+            state.drafts.open(key.clone(), base, settings)
+        }
+    };
+    spawn_autosave(&state, &ctx, &path.domain, key, draft.clone());
+    Ok(Json(DraftStatus::new(&path.domain, &draft).await))
+}
+
+/// GET /workspace/domains/{domain}/draft - The session's open draft of the domain
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/draft",
+    tag = "Workspace",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Open draft and its pending changes", body = DraftStatus),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No draft is open")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_draft(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+) -> Result<Json<DraftStatus>, StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let draft = ctx.draft.as_ref().ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(DraftStatus::new(&path.domain, draft).await))
+}
+
+/// POST /workspace/domains/{domain}/draft/save - Write the draft's changes and close it
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/draft/save",
+    tag = "Workspace",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        SaveDraftQuery
+    ),
+    responses(
+        (status = 200, description = "Changes saved; the draft is closed", body = DraftStatus),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No draft is open"),
+        (status = 409, description = "Tables or relationships changed by the draft were modified by someone else; nothing was saved", body = crate::api_types::ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn save_draft(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    Query(query): Query<SaveDraftQuery>,
+) -> Result<Json<DraftStatus>, ApiError> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let draft = ctx.draft.clone().ok_or(StatusCode::NOT_FOUND)?;
+    let mut status = DraftStatus::new(&path.domain, &draft).await;
+    let ctx = DomainContext { draft: None, ..ctx };
+    status.changes = persist(&state, &ctx, &path.domain, &draft, None, query.force)
+        .await?
+        .summary();
+
+    state
+        .drafts
+        .remove(&draft_key(&headers, &ctx, &path.domain)?);
+    info!(
+        "{} saved a draft of domain {}: {:?}",
+        ctx.user_context.email, path.domain, status.changes
+    );
+    Ok(Json(status))
+}
+
+/// POST /workspace/domains/{domain}/draft/discard - Drop the draft's changes and close it
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/draft/discard",
+    tag = "Workspace",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Draft discarded", body = DraftStatus),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No draft is open")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn discard_draft(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+) -> Result<Json<DraftStatus>, StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let draft = state
+        .drafts
+        .remove(&draft_key(&headers, &ctx, &path.domain)?)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    info!(
+        "{} discarded a draft of domain {}",
+        ctx.user_context.email, path.domain
+    );
+    Ok(Json(DraftStatus::new(&path.domain, &draft).await))
+}
+
+/// GET /workspace/domains/{domain}/save-status - Unsaved changes of the domain for this session
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/save-status",
+    tag = "Workspace",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Unsaved tables and relationships and last save times", body = SaveStatus),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Domain not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_save_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+) -> Result<Json<SaveStatus>, StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let saved = saved_model(&state, &ctx, &path.domain).await?;
+    let mut status = SaveStatus {
+        domain: path.domain,
+        draft_open: ctx.draft.is_some(),
+        dirty: Vec::new(),
+        last_persisted_at: last_persisted_at(&saved),
+        autosave: None,
+    };
+    if let Some(draft) = &ctx.draft {
+        status.dirty = draft.changes().await.dirty_objects();
+        status.last_persisted_at = status.last_persisted_at.max(draft.last_saved_at());
+        let settings = draft.autosave();
+        status.autosave = Some(AutosaveStatus {
+            interval_secs: settings.interval_secs,
+            scope: settings.scope,
+            last_autosave_at: draft.last_autosave_at(),
+            last_error: draft.autosave_error(),
+        });
+    }
+    Ok(Json(status))
+}
@@ -0,0 +1,89 @@
+//! Duplicate table analysis routes.
+//!
+//! Finds near-duplicate tables across all of the user's domains and suggests consolidating
+//! them or referencing one copy across domains instead.
+
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use super::app_state::AppState;
+use super::workspace::{get_user_context, load_workspace_models};
+use crate::models::Table;
+use crate::services::duplicate_table_service::{
+    DEFAULT_THRESHOLD, DuplicateTablePair, find_duplicates,
+};
+
+/// Most pairs returned when no limit is given
+const DEFAULT_LIMIT: usize = 100;
+
+/// Query parameters for duplicate detection
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct DuplicateTablesQuery {
+    /// Lowest score reported, 0-1 (default 0.7)
+    pub threshold: Option<f64>,
+    /// Most pairs returned (default 100)
+    pub limit: Option<usize>,
+}
+
+/// Near-duplicate tables of the workspace
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DuplicateTablesResponse {
+    pub threshold: f64,
+    /// Tables compared
+    pub tables: usize,
+    /// Scored pairs, best first
+    pub pairs: Vec<DuplicateTablePair>,
+    /// Whether pairs beyond the limit were dropped
+    pub truncated: bool,
+}
+
+/// GET /workspace/duplicate-tables - Detect near-duplicate tables across domains
+#[utoipa::path(
+    get,
+    path = "/workspace/duplicate-tables",
+    tag = "Workspace",
+    params(DuplicateTablesQuery),
+    responses(
+        (status = 200, description = "Scored near-duplicate pairs with a consolidation or referencing suggestion", body = DuplicateTablesResponse),
+        (status = 400, description = "Threshold outside 0-1"),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn find_duplicate_tables(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<DuplicateTablesQuery>,
+) -> Result<Json<DuplicateTablesResponse>, StatusCode> {
+    let user_context = get_user_context(&state, &headers).await?;
+    let threshold = query.threshold.unwrap_or(DEFAULT_THRESHOLD);
+    if !(0.0..=1.0).contains(&threshold) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let tables: Vec<(String, Table)> = load_workspace_models(&state, &user_context)
+        .await?
+        .into_iter()
+        .flat_map(|model| {
+            let domain = model.name;
+            model.tables.into_iter().map(move |t| (domain.clone(), t))
+        })
+        .collect();
+
+    let mut pairs = find_duplicates(&tables, threshold);
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT);
+    let truncated = pairs.len() > limit;
+    pairs.truncate(limit);
+    Ok(Json(DuplicateTablesResponse {
+        threshold,
+        tables: tables.len(),
+        pairs,
+        truncated,
+    }))
+}
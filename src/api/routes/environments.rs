@@ -0,0 +1,283 @@
+//! Environment profile routes.
+//!
+//! Provides domain-scoped CRUD endpoints for environment profiles (dev/test/prod).
+//! Profiles are stored as `environments.yaml` in the domain directory and are applied
+//! at export time via the `environment` query parameter. Domain-wide `${variable}`
+//! values live in the same file and are managed via `/variables`.
+
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+    routing::get,
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tracing::info;
+
+use super::app_state::AppState;
+use super::workspace::{DomainPath, get_workspace_data_dir, workspace_dir_name};
+use crate::services::environment_service::{
+    EnvironmentConfig, EnvironmentProfile, is_valid_environment_name,
+};
+
+/// Create the environment profile router
+pub fn environments_router() -> Router<AppState> {
+    Router::new().route("/", get(list_environments)).route(
+        "/{name}",
+        get(get_environment)
+            .put(upsert_environment)
+            .delete(delete_environment),
+    )
+}
+
+/// Path parameters for a single environment profile
+#[derive(Deserialize)]
+pub struct EnvironmentPath {
+    pub domain: String,
+    pub name: String,
+}
+
+/// Get path to a domain's environments.yaml
+fn get_environments_path(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain)
+        .join("environments.yaml"))
+}
+
+/// Load a domain's environment profiles, returning an empty config if none are stored.
+pub fn load_environment_config(email: &str, domain: &str) -> EnvironmentConfig {
+    if let Ok(path) = get_environments_path(email, domain)
+        && path.exists()
+        && let Ok(content) = std::fs::read_to_string(&path)
+        && let Ok(config) = serde_yaml::from_str(&content)
+    {
+        return config;
+    }
+    EnvironmentConfig::default()
+}
+
+/// Save a domain's environment profiles
+fn save_environment_config(
+    email: &str,
+    domain: &str,
+    config: &EnvironmentConfig,
+) -> Result<(), StatusCode> {
+    let path = get_environments_path(email, domain)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+    let yaml = serde_yaml::to_string(config).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    std::fs::write(path, yaml).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(())
+}
+
+/// Resolve the profile applied at export time for an optional environment name.
+///
+/// Returns NOT_FOUND if the domain has no profile with that name.
+pub fn resolve_export_profile(
+    email: &str,
+    domain: &str,
+    environment: Option<&str>,
+) -> Result<Option<EnvironmentProfile>, StatusCode> {
+    load_environment_config(email, domain)
+        .export_profile(environment)
+        .map_err(|_| StatusCode::NOT_FOUND)
+}
+
+/// GET /workspace/domains/{domain}/environments - List environment profiles for a domain
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/environments",
+    tag = "Environments",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Environment profiles retrieved successfully", body = EnvironmentConfig),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_environments(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+) -> Result<Json<EnvironmentConfig>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    Ok(Json(load_environment_config(
+        &ctx.user_context.email,
+        &domain_path.domain,
+    )))
+}
+
+/// GET /workspace/domains/{domain}/environments/{name} - Get a single environment profile
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/environments/{name}",
+    tag = "Environments",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("name" = String, Path, description = "Environment name")
+    ),
+    responses(
+        (status = 200, description = "Environment profile retrieved successfully", body = EnvironmentProfile),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Environment not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_environment(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<EnvironmentPath>,
+) -> Result<Json<EnvironmentProfile>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    load_environment_config(&ctx.user_context.email, &path.domain)
+        .get(&path.name)
+        .cloned()
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// PUT /workspace/domains/{domain}/environments/{name} - Create or replace an environment profile
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/environments/{name}",
+    tag = "Environments",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("name" = String, Path, description = "Environment name")
+    ),
+    request_body = EnvironmentProfile,
+    responses(
+        (status = 200, description = "Environment profile saved successfully", body = EnvironmentProfile),
+        (status = 400, description = "Bad request - invalid environment name"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn upsert_environment(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<EnvironmentPath>,
+    Json(mut profile): Json<EnvironmentProfile>,
+) -> Result<Json<EnvironmentProfile>, StatusCode> {
+    if !is_valid_environment_name(&path.name) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+
+    // The path is authoritative for the profile name
+    profile.name = path.name.clone();
+
+    let email = &ctx.user_context.email;
+    let mut config = load_environment_config(email, &path.domain);
+    config.upsert(profile.clone());
+    save_environment_config(email, &path.domain, &config)?;
+
+    info!(
+        "Saved environment profile '{}' for domain '{}'",
+        path.name, path.domain
+    );
+    Ok(Json(profile))
+}
+
+/// DELETE /workspace/domains/{domain}/environments/{name} - Delete an environment profile
+#[utoipa::path(
+    delete,
+    path = "/workspace/domains/{domain}/environments/{name}",
+    tag = "Environments",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("name" = String, Path, description = "Environment name")
+    ),
+    responses(
+        (status = 204, description = "Environment profile deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Environment not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_environment(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<EnvironmentPath>,
+) -> Result<StatusCode, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+
+    let email = &ctx.user_context.email;
+    let mut config = load_environment_config(email, &path.domain);
+    if !config.remove(&path.name) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    save_environment_config(email, &path.domain, &config)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /workspace/domains/{domain}/variables - Get the domain's placeholder variables
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/variables",
+    tag = "Environments",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Variables retrieved successfully", body = BTreeMap<String, String>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_domain_variables(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+) -> Result<Json<BTreeMap<String, String>>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    let config = load_environment_config(&ctx.user_context.email, &domain_path.domain);
+    Ok(Json(config.variables))
+}
+
+/// PUT /workspace/domains/{domain}/variables - Replace the domain's placeholder variables
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/variables",
+    tag = "Environments",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body = BTreeMap<String, String>,
+    responses(
+        (status = 200, description = "Variables saved successfully", body = BTreeMap<String, String>),
+        (status = 400, description = "Bad request - invalid variable name"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_domain_variables(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+    Json(variables): Json<BTreeMap<String, String>>,
+) -> Result<Json<BTreeMap<String, String>>, StatusCode> {
+    if !variables.keys().all(|k| is_valid_environment_name(k)) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+
+    let email = &ctx.user_context.email;
+    let mut config = load_environment_config(email, &domain_path.domain);
+    config.variables = variables;
+    save_environment_config(email, &domain_path.domain, &config)?;
+    Ok(Json(config.variables))
+}
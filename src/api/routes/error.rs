@@ -4,10 +4,12 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
 };
-use serde_json::json;
+
+use crate::api_types::ErrorResponse;
+use crate::services::model_limits_service::LimitExceeded;
 
 /// API error response
-#[allow(dead_code)] // Reserved for future error handling improvements
+#[derive(Debug)]
 pub struct ApiError {
     pub status: StatusCode,
     pub message: String,
@@ -15,11 +17,28 @@ pub struct ApiError {
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
-        let body = json!({
-            "error": self.message,
-            "status": self.status.as_u16(),
-        });
+        let body = ErrorResponse::new(self.status.as_u16(), self.message);
 
         (self.status, axum::Json(body)).into_response()
     }
 }
+
+/// Bare status codes from existing helpers get the canonical reason as message
+impl From<StatusCode> for ApiError {
+    fn from(status: StatusCode) -> Self {
+        Self {
+            status,
+            message: status.canonical_reason().unwrap_or_default().to_string(),
+        }
+    }
+}
+
+/// Model size limit violations are reported as 422 with the violated limit
+impl From<LimitExceeded> for ApiError {
+    fn from(err: LimitExceeded) -> Self {
+        Self {
+            status: StatusCode::UNPROCESSABLE_ENTITY,
+            message: err.to_string(),
+        }
+    }
+}
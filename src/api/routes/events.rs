@@ -0,0 +1,192 @@
+//! Model change event routes.
+//!
+//! Exposes a domain's append-only change log for external consumers, read with a
+//! `since` cursor. Table and relationship handlers record events through
+//! [`record_model_change`], which also records them in the audit log, forwards them to the
+//! Kafka/NATS event bus when one is configured and records the domain's new quality score.
+//! Table updates that break compatibility also notify the domain's owners.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::audit;
+use super::evolution_policy::notify_breaking_change;
+use super::quality_score::record_quality_score;
+use super::workspace::{DomainContext, DomainPath, get_workspace_data_dir, workspace_dir_name};
+use crate::services::audit_log_service::audit_kind;
+use crate::services::event_bus_service::event_avro_schema;
+use crate::services::event_log_service::{
+    ChangeEventType, EventLog, JsonlEventLog, ModelChangeEvent, PostgresEventLog,
+};
+
+const DEFAULT_EVENT_LIMIT: usize = 100;
+const MAX_EVENT_LIMIT: usize = 1000;
+
+/// Query parameters for reading events
+#[derive(Deserialize, IntoParams)]
+pub struct EventsQueryParams {
+    /// Return events after this sequence number (default: 0, from the start)
+    since: Option<i64>,
+    /// Maximum number of events (default: 100, max: 1000)
+    limit: Option<usize>,
+}
+
+/// A page of a domain's change events
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ModelEventsResponse {
+    pub events: Vec<ModelChangeEvent>,
+    /// Pass as `since` to read the next page
+    pub next_cursor: i64,
+    /// True if more events are available after this page
+    pub has_more: bool,
+}
+
+/// Event log of a domain for the current storage mode
+pub(crate) fn event_log(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+) -> Result<Box<dyn EventLog>, StatusCode> {
+    if let Some(pool) = state.database.as_ref() {
+        return Ok(Box::new(PostgresEventLog::new(
+            pool.clone(),
+            ctx.domain_info.id,
+        )));
+    }
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Box::new(JsonlEventLog::new(
+        workspace_data_dir
+            .join(workspace_dir_name(&ctx.user_context.email))
+            .join(domain),
+    )))
+}
+
+/// Record a table or relationship change in the domain's event log.
+///
+/// The change has already been applied, so failures are logged rather than returned.
+pub async fn record_model_change(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+    event_type: ChangeEventType,
+    entity_id: Uuid,
+    before: Option<Value>,
+    after: Option<Value>,
+) {
+    // Changes made in a draft are recorded when the draft is saved
+    if ctx.draft.is_some() {
+        return;
+    }
+    let (entity_type, action) = audit_kind(event_type);
+    audit::record_domain_audit(
+        state,
+        ctx,
+        domain,
+        (entity_type, entity_id),
+        action,
+        before.clone(),
+        after.clone(),
+    )
+    .await;
+    if event_type == ChangeEventType::TableUpdated
+        && let (Some(before), Some(after)) = (&before, &after)
+    {
+        notify_breaking_change(state, ctx, domain, entity_id, before, after);
+    }
+    let event = ModelChangeEvent::new(
+        domain,
+        event_type,
+        entity_id,
+        &ctx.user_context.email,
+        before,
+        after,
+    );
+    let result = match event_log(state, ctx, domain) {
+        Ok(log) => log.append(event).await,
+        Err(_) => return,
+    };
+    match result {
+        Ok(event) => {
+            state.event_bus.publish_in_background(event);
+            record_quality_score(state, ctx, domain).await;
+        }
+        Err(e) => warn!(
+            "Failed to record {} event for {} in domain {}: {}",
+            event_type.as_str(),
+            entity_id,
+            domain,
+            e
+        ),
+    }
+}
+
+/// GET /workspace/events/schema - Avro schema of the event bus messages
+#[utoipa::path(
+    get,
+    path = "/workspace/events/schema",
+    tag = "Events",
+    responses(
+        (status = 200, description = "Avro schema (JSON) of the messages published to Kafka/NATS", body = Object)
+    )
+)]
+pub async fn get_event_schema() -> Json<Value> {
+    Json(event_avro_schema())
+}
+
+/// GET /workspace/domains/{domain}/events - Read the domain's change events
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/events",
+    tag = "Events",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        EventsQueryParams
+    ),
+    responses(
+        (status = 200, description = "Change events after the cursor, oldest first", body = ModelEventsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_domain_events(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    Query(params): Query<EventsQueryParams>,
+) -> Result<Json<ModelEventsResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let since = params.since.unwrap_or_default().max(0);
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_EVENT_LIMIT)
+        .clamp(1, MAX_EVENT_LIMIT);
+
+    // Read one extra event to tell whether another page follows
+    let mut events = event_log(&state, &ctx, &path.domain)?
+        .read(since, limit + 1)
+        .await
+        .map_err(|e| {
+            warn!("Failed to read events for domain {}: {}", path.domain, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    let has_more = events.len() > limit;
+    events.truncate(limit);
+    let next_cursor = events.last().map(|e| e.sequence).unwrap_or(since);
+
+    Ok(Json(ModelEventsResponse {
+        events,
+        next_cursor,
+        has_more,
+    }))
+}
@@ -0,0 +1,299 @@
+//! Schema evolution policy routes.
+//!
+//! Get and declare a table's evolution policy, and check a proposed set of columns against
+//! it. Table updates that violate the policy are rejected unless a domain owner overrides
+//! the check with `?override_policy=true`. Table updates that break compatibility anyway
+//! (overridden, or on tables without a policy) notify the domain's owners.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::warn;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::domain_metadata::is_domain_owner;
+use super::error::ApiError;
+use super::notifications::notify_domain_owners;
+use super::workspace::{
+    DomainContext, DomainTablePath, get_workspace_data_dir, workspace_dir_name,
+};
+use crate::models::Column;
+use crate::services::evolution_policy_service::{
+    CompatibilityViolation, EvolutionPolicies, EvolutionPolicy, check_compatibility,
+};
+use crate::services::notification_service::ModelEvent;
+
+/// A table's evolution policy
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EvolutionPolicyResponse {
+    pub table_id: Uuid,
+    pub policy: EvolutionPolicy,
+}
+
+/// Request body for declaring a table's evolution policy
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateEvolutionPolicyRequest {
+    pub policy: EvolutionPolicy,
+}
+
+/// Request body for a compatibility check
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CompatibilityCheckRequest {
+    /// The proposed columns of the table
+    pub columns: Vec<Column>,
+}
+
+/// Result of a compatibility check
+#[derive(Debug, Serialize, ToSchema)]
+pub struct CompatibilityCheckResponse {
+    pub table_id: Uuid,
+    pub policy: EvolutionPolicy,
+    pub compatible: bool,
+    pub violations: Vec<CompatibilityViolation>,
+}
+
+/// Query parameters of table updates
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct PolicyOverrideQuery {
+    /// Apply the update even if it violates the table's evolution policy (domain owners only)
+    #[serde(default)]
+    pub override_policy: bool,
+}
+
+fn get_domain_dir(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain))
+}
+
+/// Load the evolution policy of a table.
+pub fn load_table_policy(email: &str, domain: &str, table_id: Uuid) -> EvolutionPolicy {
+    get_domain_dir(email, domain)
+        .map(|dir| EvolutionPolicies::load(&dir).get(table_id))
+        .unwrap_or_default()
+}
+
+/// Reject a column change that violates the table's evolution policy.
+///
+/// Domain owners can apply it anyway with `override_policy`; other users get 403.
+pub(crate) fn enforce_evolution_policy(
+    ctx: &DomainContext,
+    domain: &str,
+    table_id: Uuid,
+    before: &[Column],
+    after: &[Column],
+    override_policy: bool,
+) -> Result<(), ApiError> {
+    let policy = load_table_policy(&ctx.user_context.email, domain, table_id);
+    let violations = check_compatibility(policy, before, after);
+    if violations.is_empty() {
+        return Ok(());
+    }
+    if override_policy {
+        if is_domain_owner(ctx, domain) {
+            warn!(
+                "{} overrode the {:?} evolution policy of table {}",
+                ctx.user_context.email, policy, table_id
+            );
+            return Ok(());
+        }
+        return Err(ApiError {
+            status: StatusCode::FORBIDDEN,
+            message: "Only domain owners can override an evolution policy".to_string(),
+        });
+    }
+    let messages: Vec<&str> = violations.iter().map(|v| v.message.as_str()).collect();
+    Err(ApiError {
+        status: StatusCode::CONFLICT,
+        message: format!(
+            "Change violates the table's {:?} evolution policy: {}",
+            policy,
+            messages.join("; ")
+        ),
+    })
+}
+
+/// Notify the domain's owners of a table update that breaks compatibility: with the table's
+/// policy, or backward compatibility (existing readers) for tables without one.
+pub(crate) fn notify_breaking_change(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+    table_id: Uuid,
+    before: &serde_json::Value,
+    after: &serde_json::Value,
+) {
+    let columns = |table: &serde_json::Value| {
+        (table.get("columns").cloned())
+            .and_then(|columns| serde_json::from_value::<Vec<Column>>(columns).ok())
+    };
+    let (Some(before_columns), Some(after_columns)) = (columns(before), columns(after)) else {
+        return;
+    };
+    let policy = match load_table_policy(&ctx.user_context.email, domain, table_id) {
+        EvolutionPolicy::None => EvolutionPolicy::Backward,
+        policy => policy,
+    };
+    let violations = check_compatibility(policy, &before_columns, &after_columns);
+    if violations.is_empty() {
+        return;
+    }
+    let table_name = (after.get("name").and_then(|name| name.as_str()))
+        .map(str::to_string)
+        .unwrap_or_else(|| table_id.to_string());
+    notify_domain_owners(
+        state,
+        ctx,
+        domain,
+        ModelEvent::breaking_change(domain, &ctx.user_context.email, &table_name, &violations),
+    );
+}
+
+/// Columns of a table in the loaded domain
+async fn table_columns(
+    state: &AppState,
+    ctx: &DomainContext,
+    table_id: Uuid,
+) -> Result<Vec<Column>, StatusCode> {
+    let columns = match state.storage.as_ref() {
+        Some(storage) => storage
+            .get_table(ctx.domain_info.id, table_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .map(|t| t.columns),
+        None => state
+            .model_service
+            .lock()
+            .await
+            .get_table(table_id)
+            .map(|t| t.columns.clone()),
+    };
+    columns.ok_or(StatusCode::NOT_FOUND)
+}
+
+/// GET /workspace/domains/{domain}/tables/{table_id}/evolution-policy - Get a table's evolution policy
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/tables/{table_id}/evolution-policy",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    responses(
+        (status = 200, description = "The table's evolution policy (none if not declared)", body = EvolutionPolicyResponse),
+        (status = 400, description = "Invalid table ID"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_evolution_policy(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+) -> Result<Json<EvolutionPolicyResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    table_columns(&state, &ctx, table_id).await?;
+
+    Ok(Json(EvolutionPolicyResponse {
+        table_id,
+        policy: load_table_policy(&ctx.user_context.email, &path.domain, table_id),
+    }))
+}
+
+/// PUT /workspace/domains/{domain}/tables/{table_id}/evolution-policy - Declare a table's evolution policy
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/tables/{table_id}/evolution-policy",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    request_body = UpdateEvolutionPolicyRequest,
+    responses(
+        (status = 200, description = "Policy declared", body = EvolutionPolicyResponse),
+        (status = 400, description = "Invalid table ID"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Only domain owners can change the policy"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_evolution_policy(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+    Json(request): Json<UpdateEvolutionPolicyRequest>,
+) -> Result<Json<EvolutionPolicyResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    table_columns(&state, &ctx, table_id).await?;
+    if !is_domain_owner(&ctx, &path.domain) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let dir = get_domain_dir(&ctx.user_context.email, &path.domain)?;
+    let mut policies = EvolutionPolicies::load(&dir);
+    policies.set(table_id, request.policy);
+    policies.save(&dir).map_err(|e| {
+        warn!("Failed to save evolution policies: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    Ok(Json(EvolutionPolicyResponse {
+        table_id,
+        policy: request.policy,
+    }))
+}
+
+/// POST /workspace/domains/{domain}/tables/{table_id}/compatibility - Check proposed columns against the policy
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/tables/{table_id}/compatibility",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    request_body = CompatibilityCheckRequest,
+    responses(
+        (status = 200, description = "Whether the proposed columns satisfy the table's evolution policy", body = CompatibilityCheckResponse),
+        (status = 400, description = "Invalid table ID"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn check_table_compatibility(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+    Json(request): Json<CompatibilityCheckRequest>,
+) -> Result<Json<CompatibilityCheckResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let columns = table_columns(&state, &ctx, table_id).await?;
+
+    let policy = load_table_policy(&ctx.user_context.email, &path.domain, table_id);
+    let violations = check_compatibility(policy, &columns, &request.columns);
+    Ok(Json(CompatibilityCheckResponse {
+        table_id,
+        policy,
+        compatible: violations.is_empty(),
+        violations,
+    }))
+}
@@ -0,0 +1,636 @@
+//! Scheduled export routes.
+//!
+//! Domain-scoped CRUD for recurring export jobs, a run-now endpoint and run history.
+//! Schedules are stored in the domain's `export-schedules.yaml`; a background task started
+//! with the server runs due schedules and notifies the owner when a run fails. Set
+//! `EXPORT_SCHEDULER_ENABLED=false` on replicas that should not run schedules.
+
+use anyhow::anyhow;
+use axum::{
+    Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+    routing::{get, post},
+};
+use chrono::Utc;
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::notifications::notify_user;
+use super::workspace::{DomainPath, get_workspace_data_dir, workspace_dir_name};
+use crate::models::DataModel;
+use crate::services::ModelService;
+use crate::services::export_scheduler_service::{
+    BundlePlan, ExportRun, ExportSchedule, ExportSchedules, ExportTarget, RunStatus, RunTrigger,
+    TargetCredentials, plan_bundle, publish,
+};
+use crate::services::notification_service::{ModelEvent, NotificationEvent};
+
+const SCHEDULES_FILE: &str = "export-schedules.yaml";
+
+/// How often the background task looks for due schedules
+const SCHEDULER_TICK: Duration = Duration::from_secs(30);
+
+/// Request body for creating or replacing a schedule
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct ExportScheduleRequest {
+    pub name: String,
+    /// Cron expression in UTC, e.g. `0 2 * * *` for 02:00 daily
+    pub cron: String,
+    /// Export formats in the bundle (default: odcl, dbt_sources)
+    #[serde(default)]
+    pub formats: Option<Vec<String>>,
+    /// Environment profile applied before exporting
+    #[serde(default)]
+    pub environment: Option<String>,
+    pub target: ExportTarget,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    /// Credentials for targets not allowlisted for the server's own; omit on update to keep
+    /// the stored ones (unless the target changes)
+    #[serde(default)]
+    pub credentials: Option<TargetCredentials>,
+}
+
+/// Query parameters for running a schedule
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct RunScheduleQuery {
+    /// Republish every table, even if unchanged since the last publish
+    #[serde(default)]
+    pub force: bool,
+}
+
+/// Path parameters for a single schedule
+#[derive(Deserialize)]
+pub struct SchedulePath {
+    pub domain: String,
+    pub schedule_id: Uuid,
+}
+
+/// Create the export schedule router
+pub fn export_schedules_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_export_schedules).post(create_export_schedule))
+        .route(
+            "/{schedule_id}",
+            get(get_export_schedule)
+                .put(update_export_schedule)
+                .delete(delete_export_schedule),
+        )
+        .route("/{schedule_id}/run", post(run_export_schedule))
+        .route("/{schedule_id}/runs", get(list_export_runs))
+}
+
+/// Get path to a domain's export-schedules.yaml
+fn get_schedules_path(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain)
+        .join(SCHEDULES_FILE))
+}
+
+/// Load a domain's export schedules
+pub(crate) fn load_export_schedules(email: &str, domain: &str) -> ExportSchedules {
+    if let Ok(path) = get_schedules_path(email, domain)
+        && path.exists()
+        && let Ok(content) = std::fs::read_to_string(&path)
+        && let Ok(schedules) = serde_yaml::from_str(&content)
+    {
+        return schedules;
+    }
+    ExportSchedules::default()
+}
+
+/// Save a domain's export schedules
+fn save_export_schedules(
+    email: &str,
+    domain: &str,
+    schedules: &ExportSchedules,
+) -> Result<(), StatusCode> {
+    let path = get_schedules_path(email, domain)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+    let yaml = serde_yaml::to_string(schedules).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    std::fs::write(path, yaml).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Validate a request into a schedule owned by `email`, falling back to `stored_credentials`
+/// if the request carries none.
+fn build_schedule(
+    email: &str,
+    domain: &str,
+    id: Uuid,
+    request: ExportScheduleRequest,
+    stored_credentials: Option<TargetCredentials>,
+) -> Result<ExportSchedule, StatusCode> {
+    // Reject unknown environment profiles up front rather than on every run
+    super::environments::resolve_export_profile(email, domain, request.environment.as_deref())?;
+    let now = Utc::now();
+    let mut schedule = ExportSchedule {
+        id,
+        name: request.name,
+        cron: request.cron,
+        formats: request
+            .formats
+            .unwrap_or_else(|| vec!["odcl".to_string(), "dbt_sources".to_string()]),
+        environment: request.environment,
+        target: request.target,
+        enabled: request.enabled.unwrap_or(true),
+        created_by: email.to_string(),
+        created_at: now,
+        last_run_at: None,
+        next_run_at: None,
+        published_tables: Default::default(),
+        credentials: request.credentials.or(stored_credentials),
+    };
+    schedule.prepare(now).map_err(|e| {
+        warn!("[ExportSchedules] Invalid schedule: {}", e);
+        StatusCode::BAD_REQUEST
+    })?;
+    Ok(schedule)
+}
+
+/// Load a domain model outside of a request (storage backend or domain directory).
+pub(crate) async fn load_domain_model(
+    state: &AppState,
+    email: &str,
+    domain: &str,
+) -> anyhow::Result<DataModel> {
+    if state.is_postgres()
+        && let Some(storage) = state.storage.as_ref()
+    {
+        let workspace = storage
+            .get_workspace_by_email(&super::workspace::workspace_email(email))
+            .await?
+            .ok_or_else(|| anyhow!("Workspace not found"))?;
+        let domain_info = storage
+            .get_domain_by_name(workspace.id, domain)
+            .await?
+            .ok_or_else(|| anyhow!("Domain not found"))?;
+        let mut model = DataModel::new(domain.to_string(), String::new(), String::new());
+        model.tables = storage.get_tables(domain_info.id).await?;
+        model.relationships = storage.get_relationships(domain_info.id).await?;
+        return Ok(model);
+    }
+    let domain_path = get_workspace_data_dir()
+        .map_err(|e| anyhow!(e))?
+        .join(workspace_dir_name(email))
+        .join(domain);
+    ModelService::new().load_or_create_model(domain.to_string(), domain_path, None)
+}
+
+/// Result of a successful run
+struct PublishOutcome {
+    plan: BundlePlan,
+    files: Vec<String>,
+    /// Target detail, or None when nothing changed and publishing was skipped
+    detail: Option<String>,
+}
+
+/// Plan and publish a schedule's bundle. Unchanged tables are skipped unless `force` is set.
+async fn execute_schedule(
+    state: &AppState,
+    schedule: &ExportSchedule,
+    domain: &str,
+    force: bool,
+) -> anyhow::Result<PublishOutcome> {
+    let email = &schedule.created_by;
+    let model = load_domain_model(state, email, domain).await?;
+    let transforms =
+        super::models::load_export_transforms(email, domain, schedule.environment.as_deref())
+            .map_err(|_| anyhow!("Environment profile not found"))?;
+    let transformed_model = transforms.apply(&model);
+    let model = transformed_model.as_ref().unwrap_or(&model);
+
+    let mut plan = plan_bundle(model, &schedule.formats, &schedule.published_tables, force)?;
+    if plan.is_empty() {
+        return Ok(PublishOutcome {
+            plan,
+            files: Vec::new(),
+            detail: None,
+        });
+    }
+    let files = std::mem::take(&mut plan.files);
+    let paths = files.iter().map(|f| f.path.clone()).collect();
+    let work_dir = get_workspace_data_dir()
+        .map_err(|e| anyhow!(e))?
+        .join(workspace_dir_name(email))
+        .join(".export-schedules")
+        .join(schedule.id.to_string());
+    let detail = publish(schedule, domain, files, plan.removed.clone(), work_dir).await?;
+    Ok(PublishOutcome {
+        plan,
+        files: paths,
+        detail: Some(detail),
+    })
+}
+
+/// Run a schedule now, record the run and notify the owner on failure.
+async fn run_schedule(
+    state: &AppState,
+    email: &str,
+    domain: &str,
+    schedule_id: Uuid,
+    trigger: RunTrigger,
+    force: bool,
+) -> Result<ExportRun, StatusCode> {
+    let schedule = load_export_schedules(email, domain)
+        .get(schedule_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    let started_at = Utc::now();
+    let result = execute_schedule(state, &schedule, domain, force).await;
+    let mut run = ExportRun {
+        id: Uuid::new_v4(),
+        schedule_id,
+        trigger,
+        status: RunStatus::Succeeded,
+        started_at,
+        finished_at: Utc::now(),
+        files: Vec::new(),
+        removed_files: Vec::new(),
+        changed_tables: Vec::new(),
+        detail: None,
+        error: None,
+    };
+    let mut published = None;
+    match result {
+        Ok(outcome) => {
+            match &outcome.detail {
+                Some(detail) => info!(
+                    "[ExportSchedules] '{}' in domain {} published {} changed table(s): {}",
+                    schedule.name,
+                    domain,
+                    outcome.plan.changed_tables.len(),
+                    detail
+                ),
+                None => {
+                    info!(
+                        "[ExportSchedules] '{}' in domain {} skipped: no table changes",
+                        schedule.name, domain
+                    );
+                    run.status = RunStatus::Skipped;
+                }
+            }
+            run.files = outcome.files;
+            run.removed_files = outcome.plan.removed;
+            run.changed_tables = outcome.plan.changed_tables;
+            run.detail = outcome.detail;
+            published = Some(outcome.plan.published);
+        }
+        Err(e) => {
+            let error = format!("{:#}", e);
+            warn!(
+                "[ExportSchedules] '{}' in domain {} failed: {}",
+                schedule.name, domain, error
+            );
+            notify_user(
+                state,
+                &schedule.created_by,
+                ModelEvent {
+                    event: NotificationEvent::ScheduledExportFailed,
+                    domain: domain.to_string(),
+                    actor_email: schedule.created_by.clone(),
+                    summary: format!("Scheduled export '{}' failed: {}", schedule.name, error),
+                    table_names: Vec::new(),
+                },
+            );
+            run.status = RunStatus::Failed;
+            run.error = Some(error);
+        }
+    }
+
+    // Reload so edits made while the export ran are kept
+    let mut schedules = load_export_schedules(email, domain);
+    schedules.record_run(run.clone(), published);
+    save_export_schedules(email, domain, &schedules)?;
+    Ok(run)
+}
+
+/// Whether this instance runs due schedules (`EXPORT_SCHEDULER_ENABLED`, default on)
+fn scheduler_enabled() -> bool {
+    std::env::var("EXPORT_SCHEDULER_ENABLED")
+        .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+        .unwrap_or(true)
+}
+
+/// Run due schedules of every workspace until the process exits.
+pub async fn start_export_scheduler(state: AppState) {
+    if !scheduler_enabled() {
+        info!("[ExportSchedules] Scheduler disabled by EXPORT_SCHEDULER_ENABLED");
+        return;
+    }
+    let mut interval = tokio::time::interval(SCHEDULER_TICK);
+    loop {
+        interval.tick().await;
+        for (email, domain, schedule_id) in due_schedules() {
+            let _ = run_schedule(
+                &state,
+                &email,
+                &domain,
+                schedule_id,
+                RunTrigger::Scheduled,
+                false,
+            )
+            .await;
+        }
+    }
+}
+
+/// Find due schedules in `WORKSPACE_DATA/<user>/<domain>/export-schedules.yaml`.
+fn due_schedules() -> Vec<(String, String, Uuid)> {
+    let Ok(workspace_data_dir) = get_workspace_data_dir() else {
+        return Vec::new();
+    };
+    let now = Utc::now();
+    let mut due = Vec::new();
+    let user_dirs = std::fs::read_dir(workspace_data_dir)
+        .into_iter()
+        .flatten()
+        .flatten();
+    for user_dir in user_dirs {
+        let domain_dirs = std::fs::read_dir(user_dir.path())
+            .into_iter()
+            .flatten()
+            .flatten();
+        for domain_dir in domain_dirs {
+            let path = domain_dir.path().join(SCHEDULES_FILE);
+            let Some(domain) = domain_dir.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            let Ok(content) = std::fs::read_to_string(&path) else {
+                continue;
+            };
+            let Ok(schedules) = serde_yaml::from_str::<ExportSchedules>(&content) else {
+                warn!("[ExportSchedules] Ignoring invalid {}", path.display());
+                continue;
+            };
+            for schedule in schedules.schedules.iter().filter(|s| s.is_due(now)) {
+                // Only run schedules owned by the workspace they are stored in
+                if workspace_dir_name(&schedule.created_by)
+                    == user_dir.file_name().to_string_lossy()
+                {
+                    due.push((schedule.created_by.clone(), domain.clone(), schedule.id));
+                }
+            }
+        }
+    }
+    due
+}
+
+/// GET /workspace/domains/{domain}/export-schedules - List export schedules
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/export-schedules",
+    tag = "Export Schedules",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Export schedules", body = Vec<ExportSchedule>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_export_schedules(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+) -> Result<Json<Vec<ExportSchedule>>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    Ok(Json(
+        load_export_schedules(&ctx.user_context.email, &domain_path.domain)
+            .schedules
+            .into_iter()
+            .map(ExportSchedule::redacted)
+            .collect(),
+    ))
+}
+
+/// POST /workspace/domains/{domain}/export-schedules - Create an export schedule
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/export-schedules",
+    tag = "Export Schedules",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body = ExportScheduleRequest,
+    responses(
+        (status = 200, description = "Export schedule created", body = ExportSchedule),
+        (status = 400, description = "Invalid cron expression, format or target"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Environment profile not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_export_schedule(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+    Json(request): Json<ExportScheduleRequest>,
+) -> Result<Json<ExportSchedule>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    let email = &ctx.user_context.email;
+    let schedule = build_schedule(email, &domain_path.domain, Uuid::new_v4(), request, None)?;
+
+    let mut schedules = load_export_schedules(email, &domain_path.domain);
+    schedules.schedules.push(schedule.clone());
+    save_export_schedules(email, &domain_path.domain, &schedules)?;
+    info!(
+        "[ExportSchedules] Created '{}' ({}) in domain {}",
+        schedule.name, schedule.cron, domain_path.domain
+    );
+    Ok(Json(schedule.redacted()))
+}
+
+/// GET /workspace/domains/{domain}/export-schedules/{schedule_id} - Get an export schedule
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/export-schedules/{schedule_id}",
+    tag = "Export Schedules",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("schedule_id" = Uuid, Path, description = "Schedule ID")
+    ),
+    responses(
+        (status = 200, description = "Export schedule", body = ExportSchedule),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Schedule not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_export_schedule(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<SchedulePath>,
+) -> Result<Json<ExportSchedule>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    load_export_schedules(&ctx.user_context.email, &path.domain)
+        .get(path.schedule_id)
+        .cloned()
+        .map(|schedule| Json(schedule.redacted()))
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// PUT /workspace/domains/{domain}/export-schedules/{schedule_id} - Replace an export schedule
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/export-schedules/{schedule_id}",
+    tag = "Export Schedules",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("schedule_id" = Uuid, Path, description = "Schedule ID")
+    ),
+    request_body = ExportScheduleRequest,
+    responses(
+        (status = 200, description = "Export schedule updated", body = ExportSchedule),
+        (status = 400, description = "Invalid cron expression, format or target"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Schedule not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_export_schedule(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<SchedulePath>,
+    Json(request): Json<ExportScheduleRequest>,
+) -> Result<Json<ExportSchedule>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let email = &ctx.user_context.email;
+    let mut schedules = load_export_schedules(email, &path.domain);
+    let existing = schedules
+        .schedules
+        .iter_mut()
+        .find(|s| s.id == path.schedule_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    // Stored secrets are never returned, so an update without credentials keeps them
+    let stored_credentials = if request.target == existing.target {
+        existing.credentials.clone()
+    } else {
+        None
+    };
+    let mut schedule = build_schedule(
+        email,
+        &path.domain,
+        path.schedule_id,
+        request,
+        stored_credentials,
+    )?;
+    schedule.created_at = existing.created_at;
+    schedule.last_run_at = existing.last_run_at;
+    // Table hashes describe what the target holds; a new target or format set starts over
+    if schedule.target == existing.target && schedule.formats == existing.formats {
+        schedule.published_tables = std::mem::take(&mut existing.published_tables);
+    }
+    *existing = schedule.clone();
+    save_export_schedules(email, &path.domain, &schedules)?;
+    Ok(Json(schedule.redacted()))
+}
+
+/// DELETE /workspace/domains/{domain}/export-schedules/{schedule_id} - Delete an export schedule
+#[utoipa::path(
+    delete,
+    path = "/workspace/domains/{domain}/export-schedules/{schedule_id}",
+    tag = "Export Schedules",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("schedule_id" = Uuid, Path, description = "Schedule ID")
+    ),
+    responses(
+        (status = 204, description = "Export schedule and its run history deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Schedule not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_export_schedule(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<SchedulePath>,
+) -> Result<StatusCode, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let email = &ctx.user_context.email;
+    let mut schedules = load_export_schedules(email, &path.domain);
+    if !schedules.remove(path.schedule_id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    save_export_schedules(email, &path.domain, &schedules)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /workspace/domains/{domain}/export-schedules/{schedule_id}/run - Run a schedule now
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/export-schedules/{schedule_id}/run",
+    tag = "Export Schedules",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("schedule_id" = Uuid, Path, description = "Schedule ID"),
+        RunScheduleQuery
+    ),
+    responses(
+        (status = 200, description = "Run finished; check `status` for the outcome", body = ExportRun),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Schedule not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn run_export_schedule(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<SchedulePath>,
+    Query(query): Query<RunScheduleQuery>,
+) -> Result<Json<ExportRun>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    run_schedule(
+        &state,
+        &ctx.user_context.email,
+        &path.domain,
+        path.schedule_id,
+        RunTrigger::Manual,
+        query.force,
+    )
+    .await
+    .map(Json)
+}
+
+/// GET /workspace/domains/{domain}/export-schedules/{schedule_id}/runs - Run history, newest first
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/export-schedules/{schedule_id}/runs",
+    tag = "Export Schedules",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("schedule_id" = Uuid, Path, description = "Schedule ID")
+    ),
+    responses(
+        (status = 200, description = "Run history", body = Vec<ExportRun>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Schedule not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_export_runs(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<SchedulePath>,
+) -> Result<Json<Vec<ExportRun>>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let schedules = load_export_schedules(&ctx.user_context.email, &path.domain);
+    if schedules.get(path.schedule_id).is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(Json(schedules.runs_for(path.schedule_id)))
+}
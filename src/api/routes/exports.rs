@@ -0,0 +1,239 @@
+//! Export history routes.
+//!
+//! Domain exports are recorded in the domain's export registry (`exports.yaml`) as they are
+//! downloaded. These endpoints list the recorded exports, optionally for one table or
+//! format, and download a previous export again exactly as it was delivered.
+
+use axum::{
+    Extension, Router,
+    body::Body,
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{Json, Response},
+    routing::get,
+};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tracing::warn;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::models::ExportQuery;
+use super::workspace::{
+    DomainContext, DomainPath, ensure_domain_loaded, get_workspace_data_dir, workspace_dir_name,
+};
+use crate::middleware::access_control::is_viewer;
+use crate::services::domain_invite_service::DomainRole;
+use crate::services::export_registry_service::{
+    ExportArtifact, ExportRegistry, ExportRequest, artifact_path, record_export,
+};
+use crate::services::export_stream_service::stream_body;
+
+/// Response header carrying the id an export is recorded under
+pub const EXPORT_ID_HEADER: &str = "x-export-id";
+
+/// Query parameters for the export history
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct ExportHistoryQuery {
+    /// Only exports containing this table
+    pub table_id: Option<Uuid>,
+    /// Only exports of this format
+    pub format: Option<String>,
+}
+
+/// Path parameters for a single recorded export
+#[derive(Deserialize)]
+pub struct ExportArtifactPath {
+    pub domain: String,
+    pub export_id: Uuid,
+}
+
+/// Create the export history router
+pub fn exports_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_exports))
+        .route("/{export_id}", get(download_export))
+}
+
+/// Get path to a user's domain directory
+fn get_domain_dir(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain))
+}
+
+/// Describe a domain export before it is generated: its format, the tables of the loaded
+/// domain model it covers and the options that affect its content.
+pub(crate) async fn export_request(
+    state: &AppState,
+    ctx: &DomainContext,
+    format: &str,
+    query: &ExportQuery,
+) -> ExportRequest {
+    let selection: Option<Vec<Uuid>> = query.table_ids.as_ref().map(|ids| {
+        ids.iter()
+            .filter_map(|id| Uuid::parse_str(id).ok())
+            .collect()
+    });
+    let table_ids = state
+        .model_service
+        .lock()
+        .await
+        .get_current_model()
+        .map(|model| {
+            model
+                .tables
+                .iter()
+                .map(|t| t.id)
+                .filter(|id| selection.as_ref().is_none_or(|ids| ids.contains(id)))
+                .collect()
+        })
+        .unwrap_or_default();
+    let options = [
+        ("dialect", &query.dialect),
+        ("format", &query.format),
+        ("schema_type", &query.schema_type),
+        ("environment", &query.environment),
+    ]
+    .into_iter()
+    .filter_map(|(key, value)| value.clone().map(|v| (key.to_string(), v)))
+    .collect::<BTreeMap<_, _>>();
+
+    ExportRequest {
+        format: format.to_string(),
+        table_ids,
+        options,
+        filename: String::new(),
+        content_type: String::new(),
+        exported_by: ctx.user_context.email.clone(),
+    }
+}
+
+/// Record a domain export response in the domain's export registry.
+///
+/// The body is recorded as it is sent and the response gets an `X-Export-Id` header. If the
+/// domain directory cannot be resolved or the artifact cannot be created, the export is
+/// served without being recorded. Exports by viewers, who may not write to the domain, are
+/// not recorded either.
+pub(crate) fn record_domain_export(
+    email: &str,
+    domain: &str,
+    role: Option<&Extension<DomainRole>>,
+    mut request: ExportRequest,
+    response: Response<Body>,
+) -> Response<Body> {
+    if is_viewer(role) {
+        return response;
+    }
+    let Ok(domain_dir) = get_domain_dir(email, domain) else {
+        return response;
+    };
+    let (mut parts, body) = response.into_parts();
+    request.content_type = parts
+        .headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("application/octet-stream")
+        .to_string();
+    request.filename = parts
+        .headers
+        .get(header::CONTENT_DISPOSITION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split("filename=\"").nth(1))
+        .and_then(|v| v.split('"').next())
+        .unwrap_or(domain)
+        .to_string();
+
+    let (id, body) = record_export(&domain_dir, request, body);
+    if let Some(value) = id.and_then(|id| HeaderValue::from_str(&id.to_string()).ok()) {
+        parts.headers.insert(EXPORT_ID_HEADER, value);
+    }
+    Response::from_parts(parts, body)
+}
+
+/// GET /workspace/domains/{domain}/exports - List recorded exports of the domain
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/exports",
+    tag = "Export",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ExportHistoryQuery
+    ),
+    responses(
+        (status = 200, description = "Recorded exports, newest first", body = Vec<ExportArtifact>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_exports(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    Query(query): Query<ExportHistoryQuery>,
+) -> Result<Json<Vec<ExportArtifact>>, StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let registry = ExportRegistry::load(&get_domain_dir(&ctx.user_context.email, &path.domain)?);
+    Ok(Json(
+        registry.history(query.table_id, query.format.as_deref()),
+    ))
+}
+
+/// GET /workspace/domains/{domain}/exports/{export_id} - Download a recorded export again
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/exports/{export_id}",
+    tag = "Export",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("export_id" = Uuid, Path, description = "Export id")
+    ),
+    responses(
+        (status = 200, description = "The export as originally delivered; the ETag is its SHA-256", content_type = "application/octet-stream"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Export not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn download_export(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<ExportArtifactPath>,
+) -> Result<Response<Body>, StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let domain_dir = get_domain_dir(&ctx.user_context.email, &path.domain)?;
+    let registry = ExportRegistry::load(&domain_dir);
+    let artifact = registry.get(path.export_id).ok_or(StatusCode::NOT_FOUND)?;
+    let file = std::fs::File::open(artifact_path(&domain_dir, artifact.id)).map_err(|e| {
+        warn!("Failed to open export {}: {}", artifact.id, e);
+        StatusCode::NOT_FOUND
+    })?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(
+            header::CONTENT_TYPE,
+            HeaderValue::from_str(&artifact.content_type)
+                .unwrap_or(HeaderValue::from_static("application/octet-stream")),
+        )
+        .header(
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_str(&format!(
+                "attachment; filename=\"{}\"",
+                artifact.filename.replace('"', "")
+            ))
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        )
+        .header(header::ETAG, format!("\"{}\"", artifact.sha256))
+        .header(EXPORT_ID_HEADER, artifact.id.to_string())
+        .body(stream_body(move |out| {
+            let mut file = file;
+            std::io::copy(&mut file, out).map(|_| ())
+        }))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
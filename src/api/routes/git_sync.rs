@@ -19,8 +19,9 @@ use utoipa::ToSchema;
 use super::app_state::AppState;
 use super::auth_context::AuthContext;
 use super::workspace::{
-    DomainPath, get_workspace_data_dir, sanitize_email_for_path, validate_domain_name,
+    DomainPath, get_workspace_data_dir, validate_domain_name, workspace_dir_name,
 };
+use crate::services::notification_service::{ModelEvent, NotificationEvent};
 use data_modelling_sdk::git::GitService as SdkGitService;
 
 /// Create the domain-scoped git sync router
@@ -583,7 +584,7 @@ fn get_domain_workspace_path(email: &str, domain: &str) -> Result<PathBuf, Statu
     validate_domain_name(domain)?;
     let workspace_data_dir =
         get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let sanitized_email = sanitize_email_for_path(email);
+    let sanitized_email = workspace_dir_name(email);
     Ok(workspace_data_dir.join(&sanitized_email).join(domain))
 }
 
@@ -860,7 +861,7 @@ pub async fn commit_changes(
     security(("bearer_auth" = []))
 )]
 pub async fn push_changes(
-    State(_state): State<AppState>,
+    State(state): State<AppState>,
     auth: AuthContext,
     Query(params): Query<DomainPath>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
@@ -871,6 +872,17 @@ pub async fn push_changes(
         Ok(_) => match git_service.push("origin", "main") {
             Ok(_) => {
                 info!("Pushed changes for domain {}", params.domain);
+                super::notifications::notify_user(
+                    &state,
+                    &auth.email,
+                    ModelEvent {
+                        event: NotificationEvent::TablePublished,
+                        domain: params.domain.clone(),
+                        actor_email: auth.email.clone(),
+                        summary: "Domain changes pushed to git remote".to_string(),
+                        table_names: Vec::new(),
+                    },
+                );
                 Ok(Json(serde_json::json!({
                     "message": format!("Changes pushed for domain {}", params.domain)
                 })))
@@ -0,0 +1,661 @@
+//! GraphQL API for flexible model querying.
+//!
+//! `POST /api/v1/graphql` executes queries over a domain's tables, columns and relationships,
+//! including lineage traversal and search; `GET /api/v1/graphql` returns the schema as SDL.
+//! Table and relationship lookups go through dataloaders, so nested fields across many
+//! tables are resolved with one storage call per domain instead of one per table.
+
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Enum, ID, Object, Result, Schema, SimpleObject,
+};
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::metadata_schema;
+use super::table_readme;
+use super::workspace;
+use crate::models::{Column, Relationship, Table};
+
+/// Maximum lineage traversal depth
+const MAX_LINEAGE_DEPTH: i32 = 10;
+
+pub type ModelSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// Build (once) the GraphQL schema.
+pub fn schema() -> &'static ModelSchema {
+    static SCHEMA: OnceLock<ModelSchema> = OnceLock::new();
+    SCHEMA.get_or_init(|| {
+        Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .limit_depth(16)
+            .limit_complexity(2000)
+            .finish()
+    })
+}
+
+/// POST /graphql - Execute a GraphQL query
+#[utoipa::path(
+    post,
+    path = "/graphql",
+    tag = "GraphQL",
+    request_body(content = serde_json::Value, description = "GraphQL request: {\"query\": ..., \"variables\": {...}}"),
+    responses(
+        (status = 200, description = "GraphQL response (errors are reported in the `errors` field)", body = serde_json::Value),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn graphql_handler(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<async_graphql::Request>,
+) -> Result<Json<async_graphql::Response>, StatusCode> {
+    workspace::get_user_context(&state, &headers).await?;
+
+    let loader = DataLoader::new(
+        ModelLoader {
+            state: state.clone(),
+        },
+        tokio::spawn,
+    );
+    let request = request.data(RequestContext { state, headers }).data(loader);
+    Ok(Json(schema().execute(request).await))
+}
+
+/// GET /graphql - GraphQL schema definition (SDL)
+#[utoipa::path(
+    get,
+    path = "/graphql",
+    tag = "GraphQL",
+    responses(
+        (status = 200, description = "GraphQL schema in SDL format", content_type = "text/plain")
+    )
+)]
+pub async fn graphql_sdl() -> String {
+    schema().sdl()
+}
+
+/// Per-request data for resolvers.
+struct RequestContext {
+    state: AppState,
+    headers: HeaderMap,
+}
+
+/// Load a domain for the caller.
+async fn domain_context(ctx: &Context<'_>, domain: &str) -> Result<workspace::DomainContext> {
+    let request = ctx.data::<RequestContext>()?;
+    Ok(
+        workspace::ensure_domain_loaded(&request.state, &request.headers, domain)
+            .await
+            .map_err(|status| match status {
+                StatusCode::NOT_FOUND => format!("Domain not found: {}", domain),
+                status => format!("Failed to load domain {}: {}", domain, status),
+            })?,
+    )
+}
+
+/// Resolve a domain name to its id, loading it for the caller.
+async fn domain_id(ctx: &Context<'_>, domain: &str) -> Result<Uuid> {
+    Ok(domain_context(ctx, domain).await?.domain_info.id)
+}
+
+// ============================================================================
+// Dataloaders
+// ============================================================================
+
+/// Table lookup key
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct TableKey {
+    domain_id: Uuid,
+    table_id: Uuid,
+}
+
+/// Relationships touching a table (as source or target)
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+struct TableRelationshipsKey {
+    domain_id: Uuid,
+    table_id: Uuid,
+}
+
+/// Batches table and relationship lookups per domain over the storage backend.
+struct ModelLoader {
+    state: AppState,
+}
+
+impl ModelLoader {
+    /// Load all tables and relationships of a domain from storage (or the loaded file model).
+    async fn domain_model(
+        &self,
+        domain_id: Uuid,
+    ) -> std::result::Result<(Vec<Table>, Vec<Relationship>), String> {
+        if let Some(storage) = self.state.storage.as_ref() {
+            let tables = storage
+                .get_tables(domain_id)
+                .await
+                .map_err(|e| e.to_string())?;
+            let relationships = storage
+                .get_relationships(domain_id)
+                .await
+                .map_err(|e| e.to_string())?;
+            return Ok((tables, relationships));
+        }
+        let model_service = self.state.model_service.lock().await;
+        Ok(model_service
+            .get_current_model()
+            .map(|m| (m.tables.clone(), m.relationships.clone()))
+            .unwrap_or_default())
+    }
+}
+
+fn domain_ids<'a>(keys: impl Iterator<Item = &'a Uuid>) -> HashSet<Uuid> {
+    keys.copied().collect()
+}
+
+impl Loader<TableKey> for ModelLoader {
+    type Value = Table;
+    type Error = String;
+
+    async fn load(
+        &self,
+        keys: &[TableKey],
+    ) -> std::result::Result<HashMap<TableKey, Table>, String> {
+        let mut found = HashMap::new();
+        for domain_id in domain_ids(keys.iter().map(|k| &k.domain_id)) {
+            let (tables, _) = self.domain_model(domain_id).await?;
+            for table in tables {
+                let key = TableKey {
+                    domain_id,
+                    table_id: table.id,
+                };
+                if keys.contains(&key) {
+                    found.insert(key, table);
+                }
+            }
+        }
+        Ok(found)
+    }
+}
+
+impl Loader<TableRelationshipsKey> for ModelLoader {
+    type Value = Vec<Relationship>;
+    type Error = String;
+
+    async fn load(
+        &self,
+        keys: &[TableRelationshipsKey],
+    ) -> std::result::Result<HashMap<TableRelationshipsKey, Vec<Relationship>>, String> {
+        let mut found: HashMap<TableRelationshipsKey, Vec<Relationship>> =
+            keys.iter().map(|key| (*key, Vec::new())).collect();
+        for domain_id in domain_ids(keys.iter().map(|k| &k.domain_id)) {
+            let (_, relationships) = self.domain_model(domain_id).await?;
+            for relationship in relationships {
+                for table_id in [relationship.source_table_id, relationship.target_table_id] {
+                    if let Some(list) = found.get_mut(&TableRelationshipsKey {
+                        domain_id,
+                        table_id,
+                    }) && !list.iter().any(|r| r.id == relationship.id)
+                    {
+                        list.push(relationship.clone());
+                    }
+                }
+            }
+        }
+        Ok(found)
+    }
+}
+
+fn loader<'a>(ctx: &Context<'a>) -> Result<&'a DataLoader<ModelLoader>> {
+    ctx.data::<DataLoader<ModelLoader>>()
+}
+
+/// Serialize an enum value to its wire name (e.g. `Bronze` -> "bronze").
+fn enum_name<T: serde::Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+// ============================================================================
+// Schema types
+// ============================================================================
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Tables of a domain, optionally filtered by tag or medallion layer
+    async fn tables(
+        &self,
+        ctx: &Context<'_>,
+        domain: String,
+        tag: Option<String>,
+        medallion_layer: Option<String>,
+    ) -> Result<Vec<TableNode>> {
+        let domain_id = domain_id(ctx, &domain).await?;
+        let loader = loader(ctx)?;
+        let (tables, _) = loader.loader().domain_model(domain_id).await?;
+        loader
+            .feed_many(tables.iter().map(|t| {
+                (
+                    TableKey {
+                        domain_id,
+                        table_id: t.id,
+                    },
+                    t.clone(),
+                )
+            }))
+            .await;
+
+        Ok(tables
+            .into_iter()
+            .filter(|t| tag.as_ref().is_none_or(|tag| t.tags.contains(tag)))
+            .filter(|t| {
+                medallion_layer.as_ref().is_none_or(|layer| {
+                    t.medallion_layers
+                        .iter()
+                        .any(|l| enum_name(l).eq_ignore_ascii_case(layer))
+                })
+            })
+            .map(|table| TableNode { domain_id, table })
+            .collect())
+    }
+
+    /// A single table by id
+    async fn table(&self, ctx: &Context<'_>, domain: String, id: ID) -> Result<Option<TableNode>> {
+        let domain_id = domain_id(ctx, &domain).await?;
+        let table_id = Uuid::parse_str(&id)?;
+        Ok(loader(ctx)?
+            .load_one(TableKey {
+                domain_id,
+                table_id,
+            })
+            .await?
+            .map(|table| TableNode { domain_id, table }))
+    }
+
+    /// Relationships of a domain
+    async fn relationships(
+        &self,
+        ctx: &Context<'_>,
+        domain: String,
+    ) -> Result<Vec<RelationshipNode>> {
+        let domain_id = domain_id(ctx, &domain).await?;
+        let (_, relationships) = loader(ctx)?.loader().domain_model(domain_id).await?;
+        Ok(relationships
+            .into_iter()
+            .map(|relationship| RelationshipNode {
+                domain_id,
+                relationship,
+            })
+            .collect())
+    }
+
+    /// Tables reachable from a table by following relationships
+    async fn lineage(
+        &self,
+        ctx: &Context<'_>,
+        domain: String,
+        table_id: ID,
+        #[graphql(default_with = "LineageDirection::Both")] direction: LineageDirection,
+        #[graphql(default = 3)] depth: i32,
+    ) -> Result<Vec<LineageNode>> {
+        let domain_id = domain_id(ctx, &domain).await?;
+        traverse(
+            loader(ctx)?,
+            domain_id,
+            Uuid::parse_str(&table_id)?,
+            direction,
+            depth,
+        )
+        .await
+    }
+
+    /// Case-insensitive search over table names and aliases, tags, column names, descriptions,
+    /// READMEs and custom field values
+    async fn search(
+        &self,
+        ctx: &Context<'_>,
+        domain: String,
+        query: String,
+        #[graphql(default = 50)] limit: i32,
+    ) -> Result<Vec<SearchHit>> {
+        let domain_ctx = domain_context(ctx, &domain).await?;
+        let domain_id = domain_ctx.domain_info.id;
+        let (tables, _) = loader(ctx)?.loader().domain_model(domain_id).await?;
+        let readmes = table_readme::load_table_readmes(&domain_ctx.user_context.email, &domain);
+        let metadata_schema = metadata_schema::load_metadata_schema();
+        let needle = query.to_lowercase();
+        let matches = |s: &str| s.to_lowercase().contains(&needle);
+
+        let mut hits = Vec::new();
+        for table in tables {
+            let node = || TableNode {
+                domain_id,
+                table: table.clone(),
+            };
+            if matches(&table.name) {
+                hits.push(SearchHit::new(node(), None, "name"));
+            } else if table.aliases.iter().any(|a| matches(a)) {
+                hits.push(SearchHit::new(node(), None, "alias"));
+            }
+            if table.tags.iter().any(|t| matches(t)) {
+                hits.push(SearchHit::new(node(), None, "tag"));
+            }
+            if readmes.matches(&table.name, &needle) {
+                hits.push(SearchHit::new(node(), None, "readme"));
+            }
+            if metadata_schema.table_matches(&table, &needle) {
+                hits.push(SearchHit::new(node(), None, "custom_field"));
+            }
+            for column in &table.columns {
+                if matches(&column.name) {
+                    hits.push(SearchHit::new(node(), Some(&column.name), "column"));
+                } else if matches(&column.description) {
+                    hits.push(SearchHit::new(node(), Some(&column.name), "description"));
+                } else if metadata_schema.column_matches(column, &needle) {
+                    hits.push(SearchHit::new(node(), Some(&column.name), "custom_field"));
+                }
+            }
+        }
+        hits.truncate(limit.clamp(1, 500) as usize);
+        Ok(hits)
+    }
+}
+
+/// A table in a domain
+pub struct TableNode {
+    domain_id: Uuid,
+    table: Table,
+}
+
+#[Object]
+impl TableNode {
+    async fn id(&self) -> ID {
+        ID(self.table.id.to_string())
+    }
+
+    async fn name(&self) -> &str {
+        &self.table.name
+    }
+
+    async fn catalog_name(&self) -> Option<&str> {
+        self.table.catalog_name.as_deref()
+    }
+
+    async fn schema_name(&self) -> Option<&str> {
+        self.table.schema_name.as_deref()
+    }
+
+    async fn database_type(&self) -> Option<String> {
+        self.table.database_type.as_ref().map(enum_name)
+    }
+
+    async fn modeling_level(&self) -> Option<String> {
+        self.table.modeling_level.as_ref().map(enum_name)
+    }
+
+    async fn medallion_layers(&self) -> Vec<String> {
+        self.table.medallion_layers.iter().map(enum_name).collect()
+    }
+
+    async fn tags(&self) -> &[String] {
+        &self.table.tags
+    }
+
+    /// Previous or alternative physical names
+    async fn aliases(&self) -> &[String] {
+        &self.table.aliases
+    }
+
+    async fn columns(&self) -> Vec<ColumnNode> {
+        self.table.columns.iter().map(ColumnNode::from).collect()
+    }
+
+    /// Relationships where this table is the source or target
+    async fn relationships(&self, ctx: &Context<'_>) -> Result<Vec<RelationshipNode>> {
+        let relationships = loader(ctx)?
+            .load_one(TableRelationshipsKey {
+                domain_id: self.domain_id,
+                table_id: self.table.id,
+            })
+            .await?
+            .unwrap_or_default();
+        Ok(relationships
+            .into_iter()
+            .map(|relationship| RelationshipNode {
+                domain_id: self.domain_id,
+                relationship,
+            })
+            .collect())
+    }
+
+    /// Tables reachable from this table by following relationships
+    async fn lineage(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default_with = "LineageDirection::Both")] direction: LineageDirection,
+        #[graphql(default = 3)] depth: i32,
+    ) -> Result<Vec<LineageNode>> {
+        traverse(
+            loader(ctx)?,
+            self.domain_id,
+            self.table.id,
+            direction,
+            depth,
+        )
+        .await
+    }
+}
+
+/// A table column
+#[derive(SimpleObject)]
+pub struct ColumnNode {
+    name: String,
+    data_type: String,
+    nullable: bool,
+    primary_key: bool,
+    description: String,
+    /// Referenced table id when the column is a foreign key
+    foreign_key_table_id: Option<ID>,
+    foreign_key_column: Option<String>,
+}
+
+impl From<&Column> for ColumnNode {
+    fn from(column: &Column) -> Self {
+        Self {
+            name: column.name.clone(),
+            data_type: column.data_type.clone(),
+            nullable: column.nullable,
+            primary_key: column.primary_key,
+            description: column.description.clone(),
+            foreign_key_table_id: column
+                .foreign_key
+                .as_ref()
+                .map(|fk| ID(fk.table_id.clone())),
+            foreign_key_column: column.foreign_key.as_ref().map(|fk| fk.column_name.clone()),
+        }
+    }
+}
+
+/// A relationship between two tables
+pub struct RelationshipNode {
+    domain_id: Uuid,
+    relationship: Relationship,
+}
+
+#[Object]
+impl RelationshipNode {
+    async fn id(&self) -> ID {
+        ID(self.relationship.id.to_string())
+    }
+
+    async fn source_table_id(&self) -> ID {
+        ID(self.relationship.source_table_id.to_string())
+    }
+
+    async fn target_table_id(&self) -> ID {
+        ID(self.relationship.target_table_id.to_string())
+    }
+
+    async fn cardinality(&self) -> Option<String> {
+        self.relationship.cardinality.as_ref().map(enum_name)
+    }
+
+    async fn source(&self, ctx: &Context<'_>) -> Result<Option<TableNode>> {
+        self.table(ctx, self.relationship.source_table_id).await
+    }
+
+    async fn target(&self, ctx: &Context<'_>) -> Result<Option<TableNode>> {
+        self.table(ctx, self.relationship.target_table_id).await
+    }
+}
+
+impl RelationshipNode {
+    async fn table(&self, ctx: &Context<'_>, table_id: Uuid) -> Result<Option<TableNode>> {
+        Ok(loader(ctx)?
+            .load_one(TableKey {
+                domain_id: self.domain_id,
+                table_id,
+            })
+            .await?
+            .map(|table| TableNode {
+                domain_id: self.domain_id,
+                table,
+            }))
+    }
+}
+
+/// Direction of a lineage traversal; relationships point from source to target
+#[derive(Enum, Clone, Copy, PartialEq, Eq)]
+pub enum LineageDirection {
+    Upstream,
+    Downstream,
+    Both,
+}
+
+/// A table reached during lineage traversal
+#[derive(SimpleObject)]
+pub struct LineageNode {
+    table: TableNode,
+    /// Number of relationship hops from the starting table
+    depth: i32,
+    /// Relationship followed to reach this table
+    relationship_id: ID,
+    direction: LineageDirection,
+}
+
+/// A search match
+#[derive(SimpleObject)]
+pub struct SearchHit {
+    table: TableNode,
+    column: Option<String>,
+    /// What matched: name, alias, tag, readme, column, description or custom_field
+    matched: String,
+}
+
+impl SearchHit {
+    fn new(table: TableNode, column: Option<&str>, matched: &str) -> Self {
+        Self {
+            table,
+            column: column.map(str::to_string),
+            matched: matched.to_string(),
+        }
+    }
+}
+
+/// Breadth-first traversal over relationships, one batched load per level.
+async fn traverse(
+    loader: &DataLoader<ModelLoader>,
+    domain_id: Uuid,
+    start: Uuid,
+    direction: LineageDirection,
+    max_depth: i32,
+) -> Result<Vec<LineageNode>> {
+    let mut visited = HashSet::from([start]);
+    let mut frontier = vec![start];
+    let mut nodes = Vec::new();
+
+    for depth in 1..=max_depth.clamp(1, MAX_LINEAGE_DEPTH) {
+        let relationships = loader
+            .load_many(frontier.iter().map(|&table_id| TableRelationshipsKey {
+                domain_id,
+                table_id,
+            }))
+            .await?;
+
+        let mut next = Vec::new();
+        for (key, relationships) in relationships {
+            for r in relationships {
+                let step = if r.source_table_id == key.table_id
+                    && direction != LineageDirection::Upstream
+                {
+                    Some((r.target_table_id, LineageDirection::Downstream))
+                } else if r.target_table_id == key.table_id
+                    && direction != LineageDirection::Downstream
+                {
+                    Some((r.source_table_id, LineageDirection::Upstream))
+                } else {
+                    None
+                };
+                if let Some((table_id, step_direction)) = step
+                    && visited.insert(table_id)
+                {
+                    next.push((table_id, r.id, step_direction));
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+
+        let mut tables = loader
+            .load_many(next.iter().map(|&(table_id, _, _)| TableKey {
+                domain_id,
+                table_id,
+            }))
+            .await?;
+        for &(table_id, relationship_id, step_direction) in &next {
+            if let Some(table) = tables.remove(&TableKey {
+                domain_id,
+                table_id,
+            }) {
+                nodes.push(LineageNode {
+                    table: TableNode { domain_id, table },
+                    depth,
+                    relationship_id: ID(relationship_id.to_string()),
+                    direction: step_direction,
+                });
+            }
+        }
+        frontier = next.into_iter().map(|(table_id, _, _)| table_id).collect();
+    }
+    Ok(nodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_schema_exposes_model_queries() {
+        let sdl = schema().sdl();
+        for field in ["tables(", "lineage(", "search(", "relationships("] {
+            assert!(sdl.contains(field), "missing {}", field);
+        }
+
+        // Resolvers require the per-request context
+        let response = schema()
+            .execute("{ tables(domain: \"sales\") { name } }")
+            .await;
+        assert!(!response.errors.is_empty());
+    }
+}
@@ -0,0 +1,153 @@
+//! Time-travel routes.
+//!
+//! Reconstruct a domain's model at a past time by undoing the changes its event log recorded
+//! since, for read-only inspection (`GET /tables?as_of=`) and for diffing against today.
+//! Changes made before the event log existed were not recorded, so tables and relationships
+//! last changed then appear as they are now.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::warn;
+use utoipa::IntoParams;
+
+use super::app_state::AppState;
+use super::column_references::load_domain_model;
+use super::events::event_log;
+use super::table_view::table_from_view;
+use super::workspace::{
+    DomainContext, DomainPath, ensure_domain_loaded, serialize_table_with_database_type,
+};
+use crate::models::DataModel;
+use crate::services::event_log_service::{ModelChangeEvent, ModelState};
+use crate::services::model_diff_service::diff_models;
+
+/// Events read from the log per request while collecting a domain's history
+const EVENT_PAGE_SIZE: usize = 1000;
+
+/// Query parameter reading a domain as it was at a past time
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct AsOfQuery {
+    /// Point in time to reconstruct (RFC 3339, e.g. `2024-06-01T00:00:00Z`)
+    pub as_of: Option<DateTime<Utc>>,
+}
+
+/// Query parameter of the history diff
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct HistoryDiffQuery {
+    /// Point in time to compare today's model with (RFC 3339)
+    pub as_of: DateTime<Utc>,
+}
+
+/// The domain's current tables and relationships
+async fn current_model(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+) -> Result<DataModel, StatusCode> {
+    let (tables, relationships) = load_domain_model(state, ctx).await?;
+    let mut model = DataModel::new(domain.to_string(), String::new(), String::new());
+    model.tables = tables;
+    model.relationships = relationships;
+    Ok(model)
+}
+
+/// Rewind `today` to the domain's state at `as_of` using its event log.
+async fn model_as_of(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+    today: &DataModel,
+    as_of: DateTime<Utc>,
+) -> Result<DataModel, StatusCode> {
+    let log = event_log(state, ctx, domain)?;
+    let mut events: Vec<ModelChangeEvent> = Vec::new();
+    loop {
+        let since = events.last().map_or(0, |e| e.sequence);
+        let page = log.read(since, EVENT_PAGE_SIZE).await.map_err(|e| {
+            warn!("Failed to read events of domain {}: {}", domain, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        let done = page.len() < EVENT_PAGE_SIZE;
+        events.extend(page);
+        if done {
+            break;
+        }
+    }
+
+    let mut past = ModelState {
+        tables: today
+            .tables
+            .iter()
+            .map(serialize_table_with_database_type)
+            .collect(),
+        relationships: today
+            .relationships
+            .iter()
+            .filter_map(|r| serde_json::to_value(r).ok())
+            .collect(),
+    };
+    past.rewind(&events, as_of);
+
+    let mut model = DataModel::new(domain.to_string(), String::new(), String::new());
+    model.tables = past
+        .tables
+        .into_iter()
+        .filter_map(table_from_view)
+        .collect();
+    model.relationships = past
+        .relationships
+        .into_iter()
+        .filter_map(|r| serde_json::from_value(r).ok())
+        .collect();
+    Ok(model)
+}
+
+/// The domain's tables as they were at `as_of`
+pub(crate) async fn tables_as_of(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+    as_of: DateTime<Utc>,
+) -> Result<Vec<crate::models::Table>, StatusCode> {
+    let today = current_model(state, ctx, domain).await?;
+    Ok(model_as_of(state, ctx, domain, &today, as_of).await?.tables)
+}
+
+/// GET /workspace/domains/{domain}/history/diff - Compare the domain at a past time with today
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/history/diff",
+    tag = "Workspace",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        HistoryDiffQuery
+    ),
+    responses(
+        (status = 200, description = "Tables and relationships added, removed or changed since `as_of`", body = Object),
+        (status = 400, description = "Missing or invalid as_of timestamp"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn diff_with_past(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    Query(query): Query<HistoryDiffQuery>,
+) -> Result<Json<Value>, StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let today = current_model(&state, &ctx, &path.domain).await?;
+    let past = model_as_of(&state, &ctx, &path.domain, &today, query.as_of).await?;
+
+    Ok(Json(json!({
+        "as_of": query.as_of,
+        "diff": diff_models(&past, &today),
+    })))
+}
@@ -8,7 +8,7 @@
 
 use axum::{
     Router,
-    extract::{Multipart, Path, State},
+    extract::{DefaultBodyLimit, Multipart, Path, State},
     http::{HeaderMap, StatusCode},
     response::Json,
     routing::post,
@@ -16,13 +16,39 @@ use axum::{
 use serde::Deserialize;
 use serde_json::{Value, json};
 use std::collections::HashMap;
+use std::sync::Arc;
 use tracing::{error, info, warn};
 use utoipa::ToSchema;
 
 use super::app_state::AppState;
 use super::auth_context::AuthContext;
-use crate::models::Table;
-use crate::services::{AvroParser, JSONSchemaParser, ODCSParser, ProtobufParser, SQLParser};
+use super::domain_provisioning::{DomainManifestEntry, create_domain_with_model, domain_exists};
+use super::error::ApiError;
+use super::medallion_layers::infer_medallion_layers;
+use super::standard_columns::apply_standard_columns;
+use crate::middleware::idempotency::idempotency_middleware;
+use crate::models::{Relationship, Table};
+use crate::services::dbml_parser::DbmlParser;
+use crate::services::event_storming_parser::{EventStormingFormat, EventStormingParser};
+use crate::services::google_sheets_parser::{
+    GoogleSheetsParser, SheetReference, SheetsColumnMapping,
+};
+use crate::services::idempotency_service::IdempotencyCache;
+use crate::services::lakehouse_parser::LakehouseParser;
+use crate::services::model_limits_service::{LimitExceeded, ModelLimits};
+use crate::services::model_service::ModelService;
+use crate::services::orm_parser::{OrmFormat, OrmParser};
+use crate::services::outbound_http_service::{public_client, read_limited};
+use crate::services::relationship_csv_parser::RelationshipCsvParser;
+use crate::services::relationship_service::ImportedRelationship;
+use crate::services::script_hook_service::ScriptHooks;
+use crate::services::{
+    AvroParser, ErwinParser, GitService, JSONSchemaParser, ODCSParser, ProtobufParser,
+    RelationshipService, SQLParser,
+};
+
+/// Room for multipart boundaries and headers on top of the imported file itself
+const MULTIPART_OVERHEAD_BYTES: usize = 64 * 1024;
 
 /// Validation errors from import validation.
 #[derive(Debug, Clone)]
@@ -76,6 +102,39 @@ fn validate_imported_tables(tables: &[Table]) -> Vec<ImportValidationError> {
     errors
 }
 
+/// Infer medallion layers of imported tables, run the workspace's script hooks over them,
+/// then validate them for security.
+///
+/// Layers are inferred first so hooks see (and may override) them. Import transform hooks
+/// modify the tables in place; tables rejected by a validation hook are reported like any
+/// other validation error, so the import is refused.
+async fn check_imported_tables(
+    state: &AppState,
+    tables: &mut [Table],
+) -> Vec<ImportValidationError> {
+    infer_medallion_layers(tables);
+    let hooks = super::workspace::get_workspace_data_dir()
+        .map(|dir| ScriptHooks::load(&dir))
+        .unwrap_or_default();
+    let failures = if hooks.is_empty() {
+        Vec::new()
+    } else {
+        let (transformed, failures) = state
+            .script_limits
+            .run_import_hooks_blocking(hooks, tables.to_vec())
+            .await;
+        tables.clone_from_slice(&transformed);
+        failures
+    };
+    let mut errors = validate_imported_tables(tables);
+    errors.extend(failures.into_iter().map(|failure| ImportValidationError {
+        table_name: failure.table,
+        field: format!("script:{}", failure.hook),
+        message: failure.message,
+    }));
+    errors
+}
+
 /// Validate an identifier (table or column name) for security.
 fn validate_identifier(name: &str, identifier_type: &str) -> Result<(), String> {
     // Check empty
@@ -153,6 +212,55 @@ pub struct SQLTextImportRequest {
     pub dialect: Option<String>, // SQL dialect name (e.g., "postgres", "mysql", "databricks", "duckdb")
 }
 
+/// Request for ORM model text import (SQLAlchemy, Django, Prisma)
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ORMTextImportRequest {
+    pub content: String,
+    /// `sqlalchemy`, `django` or `prisma`; detected from filename/content when omitted
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub filename: Option<String>,
+}
+
+/// Request for a DBML (dbdiagram.io) import
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct DBMLImportRequest {
+    /// DBML source
+    pub content: String,
+}
+
+/// Request for a Google Sheets data dictionary import
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct GoogleSheetsImportRequest {
+    /// Spreadsheet URL (`https://docs.google.com/spreadsheets/d/<id>/edit#gid=<gid>`)
+    pub url: String,
+    /// Google OAuth access token with the `spreadsheets.readonly` scope; used for this request only
+    pub access_token: String,
+    /// Layout override; defaults to the workspace's saved mapping
+    #[serde(default)]
+    pub mapping: Option<SheetsColumnMapping>,
+    /// Save `mapping` as the workspace default
+    #[serde(default)]
+    pub save_mapping: bool,
+}
+
+/// Request for an event storming board import
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct EventStormingImportRequest {
+    /// Domain to create for the candidate model
+    pub domain: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Board exported as JSON or CSV
+    pub content: String,
+    /// `json` or `csv`; detected from filename/content when omitted
+    #[serde(default)]
+    pub format: Option<String>,
+    #[serde(default)]
+    pub filename: Option<String>,
+}
+
 /// Request for ODCS/ODCL text import
 ///
 /// Supports ODCS v3.1.0 (primary) and legacy ODCL formats (deprecated, support ends 31/12/26)
@@ -167,10 +275,30 @@ pub struct ODCLTextImportRequest {
     pub filename: Option<String>,
 }
 
+/// Reject an import that would push the domain past the model size limits.
+fn check_model_limits(
+    limits: &ModelLimits,
+    model_service: &ModelService,
+    tables: &[Table],
+) -> Result<(), LimitExceeded> {
+    for table in tables {
+        limits.check_columns(&table.name, table.columns.len())?;
+    }
+    let existing = model_service
+        .get_current_model()
+        .map(|m| m.tables.len())
+        .unwrap_or_default();
+    limits.check_table_count(existing, tables.len())
+}
+
 /// Create the domain-scoped import router
 ///
 /// All routes require JWT authentication and domain path parameter.
 /// Routes are nested under `/workspace/domains/{domain}/import`
+///
+/// Requests with an `Idempotency-Key` header are deduplicated, so a retried upload returns
+/// the original result instead of importing the tables twice. Request bodies may be as large
+/// as the `IMPORT_MAX_FILE_BYTES` limit; larger imports are rejected.
 pub fn domain_import_router() -> Router<AppState> {
     Router::new()
         // ODCS v3.1.0 (primary) and legacy ODCL (deprecated, support ends 31/12/26)
@@ -181,6 +309,19 @@ pub fn domain_import_router() -> Router<AppState> {
         .route("/avro", post(domain_import_avro))
         .route("/json-schema", post(domain_import_json_schema))
         .route("/protobuf", post(domain_import_protobuf))
+        .route("/erwin", post(domain_import_erwin))
+        .route("/orm/text", post(domain_import_orm_text))
+        .route("/dbml", post(domain_import_dbml))
+        .route("/lakehouse", post(domain_import_lakehouse))
+        .route("/google-sheets", post(domain_import_google_sheets))
+        .route("/relationships/csv", post(domain_import_relationships_csv))
+        .route_layer(axum::middleware::from_fn_with_state(
+            Arc::new(IdempotencyCache::from_env()),
+            idempotency_middleware,
+        ))
+        .layer(DefaultBodyLimit::max(
+            ModelLimits::from_env().max_import_file_bytes + MULTIPART_OVERHEAD_BYTES,
+        ))
 }
 
 /// POST /import/odcl - Import tables from ODCS/ODCL file
@@ -207,7 +348,7 @@ async fn import_odcl(
     State(state): State<AppState>,
     auth: AuthContext,
     mut multipart: Multipart,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     info!(
         "[Import] ODCS/ODCL import by user {} (ODCS v3.1.0 is primary, ODCL is legacy)",
         auth.email
@@ -225,13 +366,11 @@ async fn import_odcl(
                 && !filename.ends_with(".yaml")
                 && !filename.ends_with(".yml")
             {
-                return Err(StatusCode::BAD_REQUEST);
+                return Err(StatusCode::BAD_REQUEST.into());
             }
 
             if let Ok(content) = field.bytes().await {
-                if content.len() > 10 * 1024 * 1024 {
-                    return Err(StatusCode::BAD_REQUEST);
-                }
+                state.model_limits.check_import_size(content.len())?;
                 yaml_content = String::from_utf8_lossy(&content).to_string();
             }
         } else if name == "use_ai" {
@@ -241,23 +380,23 @@ async fn import_odcl(
     }
 
     if yaml_content.is_empty() {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into());
     }
 
     // Basic sanitization
     yaml_content = yaml_content.replace('\x00', "");
 
     let mut parser = ODCSParser::new();
-    let (table, parse_errors) = match parser.parse(&yaml_content) {
+    let (mut table, parse_errors) = match parser.parse(&yaml_content) {
         Ok(result) => result,
         Err(e) => {
             error!("ODCS/ODCL parsing error: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(StatusCode::BAD_REQUEST.into());
         }
     };
 
     // Validate imported tables for security
-    let validation_errors = validate_imported_tables(std::slice::from_ref(&table));
+    let validation_errors = check_imported_tables(&state, std::slice::from_mut(&mut table)).await;
     if !validation_errors.is_empty() {
         let errors_json: Vec<Value> = validation_errors
             .iter()
@@ -314,12 +453,19 @@ async fn import_odcl(
         })));
     }
 
+    apply_standard_columns(std::slice::from_mut(&mut table));
+    check_model_limits(
+        &state.model_limits,
+        &model_service,
+        std::slice::from_ref(&table),
+    )?;
+
     // Add table to model
-    let added_table = match model_service.add_table(table.clone()) {
+    let added_table = match model_service.import_table(table.clone()) {
         Ok(t) => t,
         Err(e) => {
             error!("Failed to add table: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(StatusCode::BAD_REQUEST.into());
         }
     };
 
@@ -365,28 +511,26 @@ pub async fn import_odcl_text(
     State(state): State<AppState>,
     auth: AuthContext,
     Json(request): Json<ODCLTextImportRequest>,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     info!(
         "[Import] ODCS/ODCL text import by user {} (ODCS v3.1.0 is primary, ODCL is legacy)",
         auth.email
     );
     // Basic sanitization
     let yaml_content = request.content.replace('\x00', "");
-    if yaml_content.len() > 10 * 1024 * 1024 {
-        return Err(StatusCode::BAD_REQUEST);
-    }
+    state.model_limits.check_import_size(yaml_content.len())?;
 
     let mut parser = ODCSParser::new();
-    let (table, parse_errors) = match parser.parse(&yaml_content) {
+    let (mut table, parse_errors) = match parser.parse(&yaml_content) {
         Ok(result) => result,
         Err(e) => {
             error!("ODCS/ODCL parsing error: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(StatusCode::BAD_REQUEST.into());
         }
     };
 
     // Validate imported tables for security
-    let validation_errors = validate_imported_tables(std::slice::from_ref(&table));
+    let validation_errors = check_imported_tables(&state, std::slice::from_mut(&mut table)).await;
     if !validation_errors.is_empty() {
         let errors_json: Vec<Value> = validation_errors
             .iter()
@@ -443,6 +587,13 @@ pub async fn import_odcl_text(
         })));
     }
 
+    apply_standard_columns(std::slice::from_mut(&mut table));
+    check_model_limits(
+        &state.model_limits,
+        &model_service,
+        std::slice::from_ref(&table),
+    )?;
+
     // Add parse errors to table.errors
     let mut table_with_errors = table.clone();
     for parse_error in &parse_errors {
@@ -463,7 +614,7 @@ pub async fn import_odcl_text(
     }
 
     // Add table to model - save even if it has errors
-    let added_table = match model_service.add_table(table_with_errors.clone()) {
+    let added_table = match model_service.import_table(table_with_errors.clone()) {
         Ok(t) => t,
         Err(e) => {
             warn!("Failed to add table normally, saving with errors: {}", e);
@@ -489,7 +640,7 @@ pub async fn import_odcl_text(
                 Ok(t) => t,
                 Err(e2) => {
                     error!("Failed to save table even with errors: {}", e2);
-                    return Err(StatusCode::BAD_REQUEST);
+                    return Err(StatusCode::BAD_REQUEST.into());
                 }
             }
         }
@@ -525,6 +676,9 @@ pub async fn import_odcl_text(
 
 /// POST /import/sql - Import tables from SQL file
 ///
+/// The DDL is linted (see `ddl_lint_service`); findings are attached to each table's
+/// `errors` and listed under `lint` in the response.
+///
 /// Requires JWT authentication.
 #[utoipa::path(
     post,
@@ -543,7 +697,7 @@ pub async fn import_sql(
     State(state): State<AppState>,
     auth: AuthContext,
     mut multipart: Multipart,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     info!("[Import] SQL import by user {}", auth.email);
     let mut sql_content = String::new();
     let mut dialect = "generic".to_string(); // Default dialect
@@ -569,21 +723,19 @@ pub async fn import_sql(
     }
 
     if sql_content.is_empty() {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into());
     }
 
     // Sanitize content
     sql_content = sql_content.replace('\x00', "");
-    if sql_content.len() > 10 * 1024 * 1024 {
-        return Err(StatusCode::BAD_REQUEST);
-    }
+    state.model_limits.check_import_size(sql_content.len())?;
 
     // Parse SQL before any await points to avoid Send issues
     // SQLParser contains a Box<dyn Dialect> which is not Send
     info!("[Import] Starting SQL import with dialect: '{}'", dialect);
-    let (tables, tables_requiring_name) = {
+    let (mut tables, tables_requiring_name, relationships) = {
         let parser = SQLParser::with_dialect_name(&dialect);
-        match parser.parse(&sql_content) {
+        match parser.parse_with_relationships(&sql_content) {
             Ok(result) => {
                 info!(
                     "[Import] Parsed {} tables from SQL with dialect '{}'",
@@ -611,7 +763,7 @@ pub async fn import_sql(
                     "[Import] SQL parsing error with dialect '{}': {}",
                     dialect, e
                 );
-                return Err(StatusCode::BAD_REQUEST);
+                return Err(StatusCode::BAD_REQUEST.into());
             }
         }
     };
@@ -644,7 +796,7 @@ pub async fn import_sql(
     }
 
     // Validate imported tables for security
-    let validation_errors = validate_imported_tables(&tables);
+    let validation_errors = check_imported_tables(&state, &mut tables).await;
     if !validation_errors.is_empty() {
         let errors_json: Vec<Value> = validation_errors
             .iter()
@@ -667,6 +819,8 @@ pub async fn import_sql(
         })));
     }
 
+    let lint_findings = state.ddl_lint.lint_tables(&mut tables);
+
     let mut model_service = state.model_service.lock().await;
 
     // Check for naming conflicts
@@ -695,6 +849,9 @@ pub async fn import_sql(
         })));
     }
 
+    apply_standard_columns(&mut tables);
+    check_model_limits(&state.model_limits, &model_service, &tables)?;
+
     // Add tables to model - save even if they have errors
     let mut added_tables = Vec::new();
     let mut import_errors = Vec::new();
@@ -707,7 +864,7 @@ pub async fn import_sql(
         );
 
         // Try normal add first
-        match model_service.add_table(table.clone()) {
+        match model_service.import_table(table.clone()) {
             Ok(added_table) => {
                 let db_type_after = added_table.database_type.map(|dt| format!("{:?}", dt));
                 info!(
@@ -805,19 +962,34 @@ pub async fn import_sql(
         })
         .collect();
 
+    let (relationships, skipped) = link_imported_relationships(&mut model_service, &relationships);
+    import_errors.extend(skipped.into_iter().map(|message| {
+        json!({
+            "type": "relationship_error",
+            "field": null,
+            "message": message
+        })
+    }));
+
     info!(
-        "[Import] Returning {} tables in response ({} with errors)",
+        "[Import] Returning {} tables and {} relationships in response ({} tables with errors)",
         tables_json.len(),
+        relationships.len(),
         added_tables.iter().filter(|t| !t.errors.is_empty()).count()
     );
     Ok(Json(json!({
         "tables": tables_json,
-        "errors": import_errors
+        "relationships": relationships,
+        "errors": import_errors,
+        "lint": lint_findings
     })))
 }
 
 /// POST /import/sql/text - Import tables from SQL text
 ///
+/// The DDL is linted (see `ddl_lint_service`); findings are attached to each table's
+/// `errors` and listed under `lint` in the response.
+///
 /// Requires JWT authentication.
 #[utoipa::path(
     post,
@@ -836,25 +1008,23 @@ pub async fn import_sql_text(
     State(state): State<AppState>,
     auth: AuthContext,
     Json(request): Json<SQLTextImportRequest>,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     info!("[Import] SQL text import by user {}", auth.email);
 
     // Basic sanitization
     let sql_content = request.content.replace('\x00', "");
-    if sql_content.len() > 10 * 1024 * 1024 {
-        return Err(StatusCode::BAD_REQUEST);
-    }
+    state.model_limits.check_import_size(sql_content.len())?;
 
     // Parse SQL before any await points to avoid Send issues
     // SQLParser contains a Box<dyn Dialect> which is not Send
     let dialect = request.dialect.as_deref().unwrap_or("generic");
-    let (mut tables, tables_requiring_name) = {
+    let (mut tables, tables_requiring_name, mut relationships) = {
         let parser = SQLParser::with_dialect_name(dialect);
-        match parser.parse(&sql_content) {
+        match parser.parse_with_relationships(&sql_content) {
             Ok(result) => result,
             Err(e) => {
                 error!("SQL parsing error: {}", e);
-                return Err(StatusCode::BAD_REQUEST);
+                return Err(StatusCode::BAD_REQUEST.into());
             }
         }
     };
@@ -870,6 +1040,14 @@ pub async fn import_sql_text(
                         "[Import] Updating table name from '{}' to '{}' (index: {})",
                         table.name, new_name, name_input.table_index
                     );
+                    for relationship in relationships.iter_mut() {
+                        if relationship.source_table == table.name {
+                            relationship.source_table = new_name.clone();
+                        }
+                        if relationship.target_table == table.name {
+                            relationship.target_table = new_name.clone();
+                        }
+                    }
                     table.name = new_name.clone();
                 } else {
                     warn!(
@@ -919,7 +1097,7 @@ pub async fn import_sql_text(
     }
 
     // Validate imported tables for security
-    let validation_errors = validate_imported_tables(&tables);
+    let validation_errors = check_imported_tables(&state, &mut tables).await;
     if !validation_errors.is_empty() {
         let errors_json: Vec<Value> = validation_errors
             .iter()
@@ -942,6 +1120,8 @@ pub async fn import_sql_text(
         })));
     }
 
+    let lint_findings = state.ddl_lint.lint_tables(&mut tables);
+
     let mut model_service = state.model_service.lock().await;
 
     // Check for naming conflicts
@@ -970,12 +1150,15 @@ pub async fn import_sql_text(
         })));
     }
 
+    apply_standard_columns(&mut tables);
+    check_model_limits(&state.model_limits, &model_service, &tables)?;
+
     // Add tables to model - save even if they have errors
     let mut added_tables = Vec::new();
     let mut import_errors = Vec::new();
 
     for mut table in tables {
-        match model_service.add_table(table.clone()) {
+        match model_service.import_table(table.clone()) {
             Ok(added_table) => {
                 added_tables.push(added_table);
             }
@@ -1050,6 +1233,15 @@ pub async fn import_sql_text(
         }
     }
 
+    let (relationships, skipped) = link_imported_relationships(&mut model_service, &relationships);
+    import_errors.extend(skipped.into_iter().map(|message| {
+        json!({
+            "type": "relationship_error",
+            "field": null,
+            "message": message
+        })
+    }));
+
     let tables_json: Vec<Value> = added_tables
         .iter()
         .map(|t| serde_json::to_value(t).unwrap_or(json!({})))
@@ -1057,19 +1249,25 @@ pub async fn import_sql_text(
 
     Ok(Json(json!({
         "tables": tables_json,
+        "relationships": relationships,
         "ai_suggestions": json!([]),
-        "errors": import_errors
+        "errors": import_errors,
+        "lint": lint_findings
     })))
 }
 
 /// POST /import/avro - Import tables from AVRO schema file
 ///
+/// Accepts a single `.avsc` schema, an Avro IDL (`.avdl`) file, or a zip of `.avsc`
+/// files. IDL and zip imports create one table per record and relationships from
+/// record-typed fields, resolving references by fullname across files.
+///
 /// Requires JWT authentication.
 #[utoipa::path(
     post,
     path = "/import/avro",
     tag = "Import",
-    request_body(content = Multipart, description = "AVRO schema file"),
+    request_body(content = Multipart, description = "AVRO schema (.avsc), IDL (.avdl) or zip of .avsc files"),
     responses(
         (status = 200, description = "AVRO schema imported successfully", body = Object),
         (status = 400, description = "Bad request - invalid AVRO schema"),
@@ -1082,9 +1280,11 @@ async fn import_avro(
     State(state): State<AppState>,
     auth: AuthContext,
     mut multipart: Multipart,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     info!("[Import] Avro import by user {}", auth.email);
     let mut avro_content = String::new();
+    let mut avro_bytes = Vec::new();
+    let mut file_name = String::new();
     let _use_ai = false;
 
     // Parse multipart form data
@@ -1092,11 +1292,11 @@ async fn import_avro(
         let name = field.name().unwrap_or("");
 
         if name == "file" {
+            file_name = field.file_name().unwrap_or_default().to_lowercase();
             if let Ok(content) = field.bytes().await {
-                if content.len() > 10 * 1024 * 1024 {
-                    return Err(StatusCode::BAD_REQUEST);
-                }
+                state.model_limits.check_import_size(content.len())?;
                 avro_content = String::from_utf8_lossy(&content).to_string();
+                avro_bytes = content.to_vec();
             }
         } else if name == "use_ai" {
             let _ = field.text().await;
@@ -1104,7 +1304,50 @@ async fn import_avro(
     }
 
     if avro_content.is_empty() {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    // Avro IDL and zipped .avsc sets resolve cross-references and create relationships
+    let is_zip = file_name.ends_with(".zip") || avro_bytes.starts_with(b"PK\x03\x04");
+    if is_zip || file_name.ends_with(".avdl") {
+        let parser = AvroParser::new();
+        let parsed = if is_zip {
+            parser.parse_archive(&avro_bytes)
+        } else {
+            parser.parse_idl(&avro_content.replace('\x00', ""))
+        };
+        let parsed = match parsed {
+            Ok(result) => result,
+            Err(e) => {
+                error!("AVRO schema set parsing error: {}", e);
+                return Err(StatusCode::BAD_REQUEST.into());
+            }
+        };
+        if parsed.tables.is_empty() {
+            return Err(StatusCode::BAD_REQUEST.into());
+        }
+
+        let errors_json: Vec<Value> = parsed
+            .errors
+            .iter()
+            .map(|e| {
+                json!({
+                    "type": e.error_type,
+                    "field": e.field,
+                    "message": e.message
+                })
+            })
+            .collect();
+        return Ok(Json(
+            store_imported_model(
+                &state,
+                parsed.tables,
+                &parsed.relationships,
+                errors_json,
+                "Avro",
+            )
+            .await?,
+        ));
     }
 
     // Sanitize content
@@ -1112,20 +1355,20 @@ async fn import_avro(
 
     // Parse AVRO
     let parser = AvroParser::new();
-    let (tables, parse_errors) = match parser.parse(&avro_content) {
+    let (mut tables, parse_errors) = match parser.parse(&avro_content) {
         Ok(result) => result,
         Err(e) => {
             error!("AVRO parsing error: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(StatusCode::BAD_REQUEST.into());
         }
     };
 
     if tables.is_empty() {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into());
     }
 
     // Validate imported tables for security
-    let validation_errors = validate_imported_tables(&tables);
+    let validation_errors = check_imported_tables(&state, &mut tables).await;
     if !validation_errors.is_empty() {
         let errors_json: Vec<Value> = validation_errors
             .iter()
@@ -1212,12 +1455,15 @@ async fn import_avro(
         }
     }
 
+    apply_standard_columns(&mut tables_with_errors);
+    check_model_limits(&state.model_limits, &model_service, &tables_with_errors)?;
+
     // Add tables to model - save even if they have errors
     let mut added_tables = Vec::new();
     let mut import_errors = Vec::new();
 
     for mut table in tables_with_errors {
-        match model_service.add_table(table.clone()) {
+        match model_service.import_table(table.clone()) {
             Ok(added_table) => {
                 added_tables.push(added_table);
             }
@@ -1316,7 +1562,7 @@ pub async fn import_json_schema(
     State(state): State<AppState>,
     auth: AuthContext,
     mut multipart: Multipart,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     info!("[Import] JSON Schema import by user {}", auth.email);
     let mut json_content = String::new();
     let _use_ai = false;
@@ -1327,9 +1573,7 @@ pub async fn import_json_schema(
 
         if name == "file" {
             if let Ok(content) = field.bytes().await {
-                if content.len() > 10 * 1024 * 1024 {
-                    return Err(StatusCode::BAD_REQUEST);
-                }
+                state.model_limits.check_import_size(content.len())?;
                 json_content = String::from_utf8_lossy(&content).to_string();
             }
         } else if name == "use_ai" {
@@ -1338,7 +1582,7 @@ pub async fn import_json_schema(
     }
 
     if json_content.is_empty() {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into());
     }
 
     // Sanitize content
@@ -1346,20 +1590,20 @@ pub async fn import_json_schema(
 
     // Parse JSON Schema
     let parser = JSONSchemaParser::new();
-    let (tables, parse_errors) = match parser.parse(&json_content) {
+    let (mut tables, parse_errors) = match parser.parse(&json_content) {
         Ok(result) => result,
         Err(e) => {
             error!("JSON Schema parsing error: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(StatusCode::BAD_REQUEST.into());
         }
     };
 
     if tables.is_empty() {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into());
     }
 
     // Validate imported tables for security
-    let validation_errors = validate_imported_tables(&tables);
+    let validation_errors = check_imported_tables(&state, &mut tables).await;
     if !validation_errors.is_empty() {
         let errors_json: Vec<Value> = validation_errors
             .iter()
@@ -1446,12 +1690,15 @@ pub async fn import_json_schema(
         }
     }
 
+    apply_standard_columns(&mut tables_with_errors);
+    check_model_limits(&state.model_limits, &model_service, &tables_with_errors)?;
+
     // Add tables to model - save even if they have errors
     let mut added_tables = Vec::new();
     let mut import_errors = Vec::new();
 
     for mut table in tables_with_errors {
-        match model_service.add_table(table.clone()) {
+        match model_service.import_table(table.clone()) {
             Ok(added_table) => {
                 added_tables.push(added_table);
             }
@@ -1550,7 +1797,7 @@ async fn import_protobuf(
     State(state): State<AppState>,
     auth: AuthContext,
     mut multipart: Multipart,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     info!("[Import] Protobuf import by user {}", auth.email);
     let mut proto_content = String::new();
     let _use_ai = false;
@@ -1561,9 +1808,7 @@ async fn import_protobuf(
 
         if name == "file" {
             if let Ok(content) = field.bytes().await {
-                if content.len() > 10 * 1024 * 1024 {
-                    return Err(StatusCode::BAD_REQUEST);
-                }
+                state.model_limits.check_import_size(content.len())?;
                 proto_content = String::from_utf8_lossy(&content).to_string();
             }
         } else if name == "use_ai" {
@@ -1572,7 +1817,7 @@ async fn import_protobuf(
     }
 
     if proto_content.is_empty() {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into());
     }
 
     // Sanitize content
@@ -1580,11 +1825,11 @@ async fn import_protobuf(
 
     // Parse Protobuf
     let parser = ProtobufParser::new();
-    let (tables, parse_error_strings) = match parser.parse(&proto_content).await {
+    let (mut tables, parse_error_strings) = match parser.parse(&proto_content).await {
         Ok(result) => result,
         Err(e) => {
             error!("Protobuf parsing error: {}", e);
-            return Err(StatusCode::BAD_REQUEST);
+            return Err(StatusCode::BAD_REQUEST.into());
         }
     };
 
@@ -1599,11 +1844,11 @@ async fn import_protobuf(
         .collect();
 
     if tables.is_empty() {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into());
     }
 
     // Validate imported tables for security
-    let validation_errors = validate_imported_tables(&tables);
+    let validation_errors = check_imported_tables(&state, &mut tables).await;
     if !validation_errors.is_empty() {
         let errors_json: Vec<Value> = validation_errors
             .iter()
@@ -1665,10 +1910,13 @@ async fn import_protobuf(
         })));
     }
 
+    apply_standard_columns(&mut tables);
+    check_model_limits(&state.model_limits, &model_service, &tables)?;
+
     // Add tables to model
     let mut added_tables = Vec::new();
     for table in tables {
-        match model_service.add_table(table.clone()) {
+        match model_service.import_table(table.clone()) {
             Ok(added_table) => added_tables.push(added_table),
             Err(e) => {
                 warn!("Failed to add table {}: {}", table.name, e);
@@ -1699,96 +1947,980 @@ async fn import_protobuf(
     })))
 }
 
-// Domain-scoped import handlers - use ensure_domain_loaded() to load domain before importing
+/// Link relationships discovered by an importer into the current model.
+///
+/// Resolves table names against the model, adds the relationships, and persists
+/// `relationships.yaml` when the model has a git directory. Returns the created
+/// relationships and messages for any that were skipped.
+pub(crate) fn link_imported_relationships(
+    model_service: &mut crate::services::ModelService,
+    imported: &[ImportedRelationship],
+) -> (Vec<Relationship>, Vec<String>) {
+    if imported.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+    let Some(model) = model_service.get_current_model().cloned() else {
+        return (Vec::new(), vec!["No model loaded".to_string()]);
+    };
 
-/// POST /workspace/domains/{domain}/import/odcl - Import tables from ODCS/ODCL file (domain-scoped)
-#[utoipa::path(
-    post,
-    path = "/workspace/domains/{domain}/import/odcl",
-    tag = "Import",
-    params(
-        ("domain" = String, Path, description = "Domain name")
-    ),
-    request_body(content = Multipart, description = "ODCS/ODCL YAML file"),
-    responses(
-        (status = 200, description = "ODCS/ODCL file imported successfully", body = Object),
-        (status = 400, description = "Bad request - invalid file or format"),
-        (status = 401, description = "Unauthorized - invalid or missing token"),
-        (status = 500, description = "Internal server error")
-    ),
-    security(("bearer_auth" = []))
-)]
-async fn domain_import_odcl(
-    State(state): State<AppState>,
-    Path(path): Path<super::workspace::DomainPath>,
-    headers: HeaderMap,
-    auth: AuthContext,
-    #[allow(unused_mut)] mut multipart: Multipart,
-) -> Result<Json<Value>, StatusCode> {
-    // Ensure domain is loaded before importing
-    let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let mut rel_service = RelationshipService::new(Some(model));
+    let (created, skipped) = match rel_service.link_imported_relationships(imported) {
+        Ok(result) => result,
+        Err(e) => return (Vec::new(), vec![e.to_string()]),
+    };
+    if created.is_empty() {
+        return (created, skipped);
+    }
 
-    // Delegate to the existing import handler logic
-    import_odcl(State(state), auth, multipart).await
+    if let (Some(updated), Some(model)) = (
+        rel_service.get_model_mut(),
+        model_service.get_current_model_mut(),
+    ) {
+        model.relationships = updated.relationships.clone();
+
+        if !model.git_directory_path.is_empty() {
+            let mut git_service = GitService::new();
+            if let Err(e) =
+                git_service.set_git_directory_path(std::path::Path::new(&model.git_directory_path))
+            {
+                warn!("Failed to set git directory for relationship save: {}", e);
+            } else if let Err(e) =
+                git_service.save_relationships_to_yaml(&model.relationships, &model.tables)
+            {
+                warn!("Failed to save imported relationships to YAML: {}", e);
+            }
+        }
+    }
+
+    (created, skipped)
 }
 
-/// POST /workspace/domains/{domain}/import/odcl/text - Import tables from ODCS/ODCL text (domain-scoped)
+/// Validate, conflict-check and store imported tables, then link their relationships.
+///
+/// Shared by importers that produce relationships alongside tables. Returns the
+/// JSON response body in the same shape as the other import endpoints.
+async fn store_imported_model(
+    state: &AppState,
+    mut tables: Vec<Table>,
+    relationships: &[ImportedRelationship],
+    mut errors_json: Vec<Value>,
+    source: &str,
+) -> Result<Value, LimitExceeded> {
+    // Validate imported tables for security
+    let validation_errors = check_imported_tables(state, &mut tables).await;
+    if !validation_errors.is_empty() {
+        let errors_json: Vec<Value> = validation_errors
+            .iter()
+            .map(|e| {
+                json!({
+                    "type": "validation_error",
+                    "table": e.table_name,
+                    "field": e.field,
+                    "message": e.message
+                })
+            })
+            .collect();
+        warn!(
+            "[Import] Validation failed for {} import: {:?}",
+            source, validation_errors
+        );
+        return Ok(json!({
+            "tables": [],
+            "errors": errors_json
+        }));
+    }
+
+    let mut model_service = state.model_service.lock().await;
+
+    // Check for naming conflicts
+    let conflicts = model_service.detect_naming_conflicts(&tables);
+    if !conflicts.is_empty() {
+        let conflict_info: Vec<Value> = conflicts
+            .iter()
+            .map(|(t1, t2)| {
+                json!({
+                    "new_table": t1.name,
+                    "existing_table": t2.name,
+                    "message": format!("Table '{}' conflicts with existing table", t1.name)
+                })
+            })
+            .collect();
+
+        return Ok(json!({
+            "tables": tables,
+            "conflicts": conflict_info,
+            "errors": errors_json
+        }));
+    }
+
+    apply_standard_columns(&mut tables);
+    check_model_limits(&state.model_limits, &model_service, &tables)?;
+
+    // Add tables to model
+    let mut added_tables = Vec::new();
+    for table in tables {
+        match model_service.import_table(table.clone()) {
+            Ok(added_table) => added_tables.push(added_table),
+            Err(e) => {
+                warn!("Failed to add table {}: {}", table.name, e);
+            }
+        }
+    }
+
+    let (relationships, skipped) = link_imported_relationships(&mut model_service, relationships);
+    errors_json.extend(skipped.into_iter().map(|message| {
+        json!({
+            "type": "relationship_error",
+            "field": null,
+            "message": message
+        })
+    }));
+
+    Ok(json!({
+        "tables": added_tables,
+        "relationships": relationships,
+        "ai_suggestions": json!([]),
+        "errors": errors_json
+    }))
+}
+
+/// POST /import/erwin - Import tables and relationships from an Erwin XML export
+///
+/// Entities become tables, attributes become columns, relationships become
+/// foreign key relationships and subject areas become `subject_area:<name>` tags.
+///
+/// Requires JWT authentication.
 #[utoipa::path(
     post,
-    path = "/workspace/domains/{domain}/import/odcl/text",
+    path = "/import/erwin",
     tag = "Import",
-    params(
-        ("domain" = String, Path, description = "Domain name")
-    ),
-    request_body(content = ODCLTextImportRequest, description = "ODCS/ODCL YAML text"),
+    request_body(content = Multipart, description = "Erwin XML export file"),
     responses(
-        (status = 200, description = "ODCS/ODCL text imported successfully", body = Object),
-        (status = 400, description = "Bad request - invalid text or format"),
+        (status = 200, description = "Erwin model imported successfully", body = Object),
+        (status = 400, description = "Bad request - invalid Erwin XML"),
         (status = 401, description = "Unauthorized - invalid or missing token"),
         (status = 500, description = "Internal server error")
     ),
     security(("bearer_auth" = []))
 )]
-async fn domain_import_odcl_text(
+async fn import_erwin(
     State(state): State<AppState>,
-    Path(path): Path<super::workspace::DomainPath>,
-    headers: HeaderMap,
     auth: AuthContext,
-    Json(request): Json<ODCLTextImportRequest>,
-) -> Result<Json<Value>, StatusCode> {
-    // Ensure domain is loaded before importing
-    let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    mut multipart: Multipart,
+) -> Result<Json<Value>, ApiError> {
+    info!("[Import] Erwin XML import by user {}", auth.email);
+    let mut xml_content = String::new();
 
-    // Delegate to the existing import handler logic
-    import_odcl_text(State(state), auth, Json(request)).await
+    // Parse multipart form data
+    while let Ok(Some(field)) = multipart.next_field().await {
+        if field.name().unwrap_or("") == "file"
+            && let Ok(content) = field.bytes().await
+        {
+            state.model_limits.check_import_size(content.len())?;
+            xml_content = String::from_utf8_lossy(&content).to_string();
+        }
+    }
+
+    if xml_content.is_empty() {
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    // Sanitize content
+    xml_content = xml_content.replace('\x00', "");
+
+    let parsed = match ErwinParser::new().parse(&xml_content) {
+        Ok(result) => result,
+        Err(e) => {
+            error!("Erwin XML parsing error: {}", e);
+            return Err(StatusCode::BAD_REQUEST.into());
+        }
+    };
+
+    if parsed.tables.is_empty() {
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    let errors_json: Vec<Value> = parsed
+        .errors
+        .iter()
+        .map(|e| {
+            json!({
+                "type": e.error_type,
+                "field": e.field,
+                "message": e.message
+            })
+        })
+        .collect();
+
+    let mut response = store_imported_model(
+        &state,
+        parsed.tables,
+        &parsed.relationships,
+        errors_json,
+        "Erwin",
+    )
+    .await?;
+    response["subject_areas"] = json!(parsed.subject_areas);
+    Ok(Json(response))
 }
 
-/// POST /workspace/domains/{domain}/import/sql - Import tables from SQL file (domain-scoped)
+/// POST /import/orm/text - Import tables and relationships from ORM model source
+///
+/// Supports SQLAlchemy (`Table()` and declarative classes), Django `models.py`
+/// and Prisma `schema.prisma`. The format is detected from the filename or
+/// content when not given explicitly.
+///
+/// Requires JWT authentication.
 #[utoipa::path(
     post,
-    path = "/workspace/domains/{domain}/import/sql",
+    path = "/import/orm/text",
     tag = "Import",
-    params(
-        ("domain" = String, Path, description = "Domain name")
-    ),
-    request_body(content = Multipart, description = "SQL file"),
+    request_body = ORMTextImportRequest,
     responses(
-        (status = 200, description = "SQL file imported successfully", body = Object),
-        (status = 400, description = "Bad request - invalid file or format"),
+        (status = 200, description = "ORM models imported successfully", body = Object),
+        (status = 400, description = "Bad request - unknown format or no models found"),
         (status = 401, description = "Unauthorized - invalid or missing token"),
         (status = 500, description = "Internal server error")
     ),
     security(("bearer_auth" = []))
 )]
-async fn domain_import_sql(
+pub async fn import_orm_text(
     State(state): State<AppState>,
-    Path(path): Path<super::workspace::DomainPath>,
-    headers: HeaderMap,
     auth: AuthContext,
-    #[allow(unused_mut)] mut multipart: Multipart,
-) -> Result<Json<Value>, StatusCode> {
-    // Ensure domain is loaded before importing
-    let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    Json(request): Json<ORMTextImportRequest>,
+) -> Result<Json<Value>, ApiError> {
+    info!("[Import] ORM text import by user {}", auth.email);
+
+    if request.content.is_empty() {
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+    state
+        .model_limits
+        .check_import_size(request.content.len())?;
+
+    // Sanitize content
+    let content = request.content.replace('\x00', "");
+
+    let format = match request.format.as_deref() {
+        Some(name) => OrmFormat::from_name(name),
+        None => OrmFormat::detect(request.filename.as_deref(), &content),
+    }
+    .ok_or(StatusCode::BAD_REQUEST)?;
+
+    let parsed = match OrmParser::new().parse(&content, format) {
+        Ok(result) => result,
+        Err(e) => {
+            error!("ORM model parsing error: {}", e);
+            return Err(StatusCode::BAD_REQUEST.into());
+        }
+    };
+
+    if parsed.tables.is_empty() {
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    let errors_json: Vec<Value> = parsed
+        .errors
+        .iter()
+        .map(|e| {
+            json!({
+                "type": e.error_type,
+                "field": e.field,
+                "message": e.message
+            })
+        })
+        .collect();
+
+    Ok(Json(
+        store_imported_model(
+            &state,
+            parsed.tables,
+            &parsed.relationships,
+            errors_json,
+            "ORM",
+        )
+        .await?,
+    ))
+}
+
+/// POST /import/dbml - Import tables and relationships from DBML (dbdiagram.io)
+///
+/// Tables, refs, enums and table groups are imported: enum-typed columns get the enum
+/// values and table groups are applied as `table_group:<name>` table tags.
+///
+/// Requires JWT authentication.
+#[utoipa::path(
+    post,
+    path = "/import/dbml",
+    tag = "Import",
+    request_body = DBMLImportRequest,
+    responses(
+        (status = 200, description = "DBML imported successfully", body = Object),
+        (status = 400, description = "Bad request - no tables found"),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn import_dbml(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Json(request): Json<DBMLImportRequest>,
+) -> Result<Json<Value>, ApiError> {
+    info!("[Import] DBML import by user {}", auth.email);
+
+    if request.content.is_empty() {
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+    state
+        .model_limits
+        .check_import_size(request.content.len())?;
+
+    // Sanitize content
+    let content = request.content.replace('\x00', "");
+
+    let parsed = match DbmlParser::new().parse(&content) {
+        Ok(result) => result,
+        Err(e) => {
+            error!("DBML parsing error: {}", e);
+            return Err(StatusCode::BAD_REQUEST.into());
+        }
+    };
+
+    let errors_json: Vec<Value> = parsed
+        .errors
+        .iter()
+        .map(|e| {
+            json!({
+                "type": e.error_type,
+                "field": e.field,
+                "message": e.message
+            })
+        })
+        .collect();
+
+    let mut response = store_imported_model(
+        &state,
+        parsed.tables,
+        &parsed.relationships,
+        errors_json,
+        "DBML",
+    )
+    .await?;
+    response["table_groups"] = json!(parsed.table_groups);
+    Ok(Json(response))
+}
+
+/// Resolve a lakehouse metadata path against `LAKEHOUSE_IMPORT_ROOT`.
+///
+/// Local paths are only readable beneath the configured root (e.g. a mounted object store).
+fn resolve_lakehouse_path(path: &str) -> Result<std::path::PathBuf, StatusCode> {
+    let root = std::env::var("LAKEHOUSE_IMPORT_ROOT").map_err(|_| {
+        warn!("[Import] Lakehouse path import requested but LAKEHOUSE_IMPORT_ROOT is not set");
+        StatusCode::BAD_REQUEST
+    })?;
+    let root = std::fs::canonicalize(root).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let relative = path.trim_start_matches("file://").trim_start_matches('/');
+    let resolved = std::fs::canonicalize(root.join(relative)).map_err(|_| StatusCode::NOT_FOUND)?;
+    if !resolved.starts_with(&root) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(resolved)
+}
+
+/// POST /import/lakehouse - Import tables from Delta Lake or Iceberg metadata
+///
+/// Multipart fields:
+/// - `file`: a Delta commit JSON, an Iceberg `metadata.json`, or a zip of `_delta_log` /
+///   `metadata` directories
+/// - `path`: alternatively, an `https://` URL (e.g. a presigned object-store URL) on a public
+///   address, fetched without following redirects, or a path beneath `LAKEHOUSE_IMPORT_ROOT`
+/// - `table_name`: optional table name when the metadata does not carry one
+///
+/// Requires JWT authentication.
+#[utoipa::path(
+    post,
+    path = "/import/lakehouse",
+    tag = "Import",
+    request_body(content = Multipart, description = "Delta log / Iceberg metadata file, zip, or path"),
+    responses(
+        (status = 200, description = "Lakehouse metadata imported successfully", body = Object),
+        (status = 400, description = "Bad request - invalid metadata"),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 403, description = "Forbidden - path outside the import root"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn import_lakehouse(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    mut multipart: Multipart,
+) -> Result<Json<Value>, ApiError> {
+    info!("[Import] Lakehouse metadata import by user {}", auth.email);
+    let mut file_bytes = Vec::new();
+    let mut source_path = None;
+    let mut table_name = None;
+
+    // Parse multipart form data
+    while let Ok(Some(field)) = multipart.next_field().await {
+        match field.name().unwrap_or("") {
+            "file" => {
+                if let Ok(content) = field.bytes().await {
+                    state.model_limits.check_import_size(content.len())?;
+                    file_bytes = content.to_vec();
+                }
+            }
+            "path" => source_path = field.text().await.ok().filter(|p| !p.trim().is_empty()),
+            "table_name" => table_name = field.text().await.ok().filter(|n| !n.trim().is_empty()),
+            _ => {}
+        }
+    }
+
+    let parser = LakehouseParser::new();
+    let parsed = match (file_bytes.is_empty(), source_path) {
+        (true, Some(path)) if path.starts_with("https://") => {
+            // Public addresses only, no redirects, and never more than the import limit
+            let client = public_client(&path).await.map_err(|e| {
+                warn!("[Import] Refusing to fetch lakehouse metadata: {}", e);
+                StatusCode::BAD_REQUEST
+            })?;
+            let response = client.get(&path).send().await.map_err(|e| {
+                warn!("[Import] Failed to fetch lakehouse metadata: {}", e);
+                StatusCode::BAD_REQUEST
+            })?;
+            if !response.status().is_success() {
+                return Err(StatusCode::BAD_REQUEST.into());
+            }
+            if let Some(length) = response.content_length() {
+                state
+                    .model_limits
+                    .check_import_size(usize::try_from(length).unwrap_or(usize::MAX))?;
+            }
+            let bytes = read_limited(response, state.model_limits.max_import_file_bytes)
+                .await
+                .map_err(|_| StatusCode::BAD_REQUEST)?;
+            state.model_limits.check_import_size(bytes.len())?;
+            if bytes.starts_with(b"PK\x03\x04") {
+                parser.parse_archive(&bytes)
+            } else {
+                parser.parse(
+                    &String::from_utf8_lossy(&bytes).replace('\x00', ""),
+                    table_name.as_deref(),
+                )
+            }
+        }
+        (true, Some(path)) => {
+            let resolved = resolve_lakehouse_path(&path)?;
+            if resolved.is_dir() {
+                parser.parse_directory(&resolved)
+            } else {
+                let content =
+                    std::fs::read_to_string(&resolved).map_err(|_| StatusCode::BAD_REQUEST)?;
+                parser.parse(&content, table_name.as_deref())
+            }
+        }
+        (true, None) => return Err(StatusCode::BAD_REQUEST.into()),
+        (false, _) if file_bytes.starts_with(b"PK\x03\x04") => parser.parse_archive(&file_bytes),
+        (false, _) => parser.parse(
+            &String::from_utf8_lossy(&file_bytes).replace('\x00', ""),
+            table_name.as_deref(),
+        ),
+    };
+
+    let (tables, parse_errors) = match parsed {
+        Ok(result) => result,
+        Err(e) => {
+            error!("Lakehouse metadata parsing error: {}", e);
+            return Err(StatusCode::BAD_REQUEST.into());
+        }
+    };
+
+    if tables.is_empty() {
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    let errors_json: Vec<Value> = parse_errors
+        .iter()
+        .map(|e| {
+            json!({
+                "type": e.error_type,
+                "field": e.field,
+                "message": e.message
+            })
+        })
+        .collect();
+
+    Ok(Json(
+        store_imported_model(&state, tables, &[], errors_json, "Lakehouse").await?,
+    ))
+}
+
+/// Get path to a workspace's saved Google Sheets column mapping
+fn get_sheets_mapping_path(email: &str) -> Result<std::path::PathBuf, StatusCode> {
+    let workspace_data_dir = super::workspace::get_workspace_data_dir()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(super::workspace::workspace_dir_name(email))
+        .join("google-sheets-mapping.yaml"))
+}
+
+/// Load the workspace's Google Sheets column mapping, falling back to the default layout.
+fn load_sheets_mapping(email: &str) -> SheetsColumnMapping {
+    if let Ok(path) = get_sheets_mapping_path(email)
+        && path.exists()
+        && let Ok(content) = std::fs::read_to_string(&path)
+        && let Ok(mapping) = serde_yaml::from_str(&content)
+    {
+        return mapping;
+    }
+    SheetsColumnMapping::default()
+}
+
+/// Save the workspace's Google Sheets column mapping
+fn save_sheets_mapping(email: &str, mapping: &SheetsColumnMapping) -> Result<(), StatusCode> {
+    let path = get_sheets_mapping_path(email)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+    let yaml = serde_yaml::to_string(mapping).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    std::fs::write(path, yaml).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// GET /workspace/import/google-sheets/mapping - Get the workspace's dictionary column mapping
+#[utoipa::path(
+    get,
+    path = "/workspace/import/google-sheets/mapping",
+    tag = "Import",
+    responses(
+        (status = 200, description = "Saved mapping, or the default layout", body = SheetsColumnMapping),
+        (status = 401, description = "Unauthorized - invalid or missing token")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_google_sheets_mapping(auth: AuthContext) -> Json<SheetsColumnMapping> {
+    Json(load_sheets_mapping(&auth.email))
+}
+
+/// PUT /workspace/import/google-sheets/mapping - Save the workspace's dictionary column mapping
+#[utoipa::path(
+    put,
+    path = "/workspace/import/google-sheets/mapping",
+    tag = "Import",
+    request_body = SheetsColumnMapping,
+    responses(
+        (status = 200, description = "Mapping saved", body = SheetsColumnMapping),
+        (status = 400, description = "Bad request - empty required header names"),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn put_google_sheets_mapping(
+    auth: AuthContext,
+    Json(mapping): Json<SheetsColumnMapping>,
+) -> Result<Json<SheetsColumnMapping>, StatusCode> {
+    if [&mapping.table, &mapping.column, &mapping.data_type]
+        .iter()
+        .any(|h| h.trim().is_empty())
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    save_sheets_mapping(&auth.email, &mapping)?;
+    info!("[Import] Saved Google Sheets mapping for {}", auth.email);
+    Ok(Json(mapping))
+}
+
+/// Import tables and columns from a Google Sheets data dictionary
+async fn import_google_sheets(
+    state: &AppState,
+    email: &str,
+    request: GoogleSheetsImportRequest,
+) -> Result<Json<Value>, ApiError> {
+    info!("[Import] Google Sheets import by user {}", email);
+    let sheet = SheetReference::from_url(request.url.trim()).ok_or(StatusCode::BAD_REQUEST)?;
+    if request.access_token.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    let mapping = match request.mapping {
+        Some(mapping) => {
+            if request.save_mapping {
+                save_sheets_mapping(email, &mapping)?;
+            }
+            mapping
+        }
+        None => load_sheets_mapping(email),
+    };
+
+    let parser = GoogleSheetsParser::new();
+    let rows = parser
+        .fetch_rows(&sheet, request.access_token.trim(), &mapping)
+        .await
+        .map_err(|e| {
+            warn!("[Import] Failed to read Google Sheet: {:#}", e);
+            StatusCode::BAD_GATEWAY
+        })?;
+
+    let (tables, parse_errors) = match parser.parse(&rows, &mapping) {
+        Ok(result) => result,
+        Err(e) => {
+            error!("Google Sheets dictionary parsing error: {}", e);
+            return Err(StatusCode::BAD_REQUEST.into());
+        }
+    };
+
+    if tables.is_empty() {
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    let errors_json: Vec<Value> = parse_errors
+        .iter()
+        .map(|e| {
+            json!({
+                "type": e.error_type,
+                "field": e.field,
+                "message": e.message
+            })
+        })
+        .collect();
+
+    Ok(Json(
+        store_imported_model(state, tables, &[], errors_json, "Google Sheets").await?,
+    ))
+}
+
+/// POST /import/relationships/csv - Create relationships in bulk from a CSV mapping file
+///
+/// Each row maps one column pair: `source_table,source_column,target_table,target_column`
+/// and an optional `cardinality` (`ManyToOne`, `1:N`, ...). Rows mapping the same table pair
+/// form one composite foreign key. Rows that reference unknown tables or columns are
+/// reported in `errors` with their row number; the remaining rows are still imported.
+///
+/// Requires JWT authentication.
+#[utoipa::path(
+    post,
+    path = "/import/relationships/csv",
+    tag = "Import",
+    request_body(content = Multipart, description = "CSV file with source_table, source_column, target_table, target_column and cardinality columns"),
+    responses(
+        (status = 200, description = "Relationships created, with row-level errors", body = Object),
+        (status = 400, description = "Bad request - missing file or required CSV column", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 404, description = "No model loaded")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn import_relationships_csv(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    mut multipart: Multipart,
+) -> Result<Json<Value>, ApiError> {
+    info!("[Import] Relationship CSV import by user {}", auth.email);
+    let mut csv_content = String::new();
+
+    // Parse multipart form data
+    while let Ok(Some(field)) = multipart.next_field().await {
+        if field.name().unwrap_or("") != "file" {
+            continue;
+        }
+        if let Some(filename) = field.file_name()
+            && !filename.to_lowercase().ends_with(".csv")
+        {
+            return Err(StatusCode::BAD_REQUEST.into());
+        }
+        if let Ok(content) = field.bytes().await {
+            state.model_limits.check_import_size(content.len())?;
+            csv_content = String::from_utf8_lossy(&content).replace('\x00', "");
+        }
+    }
+
+    if csv_content.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+
+    let mut model_service = state.model_service.lock().await;
+    let model = model_service
+        .get_current_model()
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let (imported, parse_errors) = RelationshipCsvParser::new()
+        .parse(&csv_content, model)
+        .map_err(|e| ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: e.to_string(),
+        })?;
+
+    let mut errors_json: Vec<Value> = parse_errors
+        .iter()
+        .map(|e| {
+            json!({
+                "type": e.error_type,
+                "field": e.field,
+                "message": e.message
+            })
+        })
+        .collect();
+    let (relationships, skipped) = link_imported_relationships(&mut model_service, &imported);
+    errors_json.extend(skipped.into_iter().map(|message| {
+        json!({
+            "type": "relationship_error",
+            "field": null,
+            "message": message
+        })
+    }));
+
+    Ok(Json(json!({
+        "relationships": relationships,
+        "errors": errors_json
+    })))
+}
+
+/// POST /workspace/import/event-storming - Create a domain from an event storming board
+///
+/// Aggregates and domain events become conceptual-level tables tagged `event_storming`,
+/// events reference the aggregate that emits them and aggregates the aggregates they refer
+/// to; commands are recorded on the aggregate that handles them. The domain must not exist.
+#[utoipa::path(
+    post,
+    path = "/workspace/import/event-storming",
+    tag = "Import",
+    request_body = EventStormingImportRequest,
+    responses(
+        (status = 200, description = "Domain created; tables and relationships with note-level errors, or validation errors if nothing was created", body = Object),
+        (status = 400, description = "Bad request - invalid domain name, unknown format or unreadable board", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 409, description = "Domain already exists"),
+        (status = 422, description = "Model size limit exceeded", body = crate::api_types::ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn import_event_storming(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<EventStormingImportRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let user_context = super::workspace::get_user_context(&state, &headers).await?;
+    info!(
+        "[Import] Event storming import by user {}",
+        user_context.email
+    );
+    let domain = request.domain.trim();
+    super::workspace::validate_domain_name(domain)?;
+    if request.content.trim().is_empty() {
+        return Err(StatusCode::BAD_REQUEST.into());
+    }
+    state
+        .model_limits
+        .check_import_size(request.content.len())?;
+
+    // Sanitize content
+    let content = request.content.replace('\x00', "");
+
+    let format = match request.format.as_deref() {
+        Some(name) => EventStormingFormat::from_name(name).ok_or(StatusCode::BAD_REQUEST)?,
+        None => EventStormingFormat::detect(request.filename.as_deref(), &content),
+    };
+    let parsed = EventStormingParser::new()
+        .parse(&content, format)
+        .map_err(|e| ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: e.to_string(),
+        })?;
+    if parsed.tables.is_empty() {
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: "The board has no aggregates or events".to_string(),
+        });
+    }
+
+    let mut tables = parsed.tables;
+    let validation_errors = check_imported_tables(&state, &mut tables).await;
+    if !validation_errors.is_empty() {
+        warn!(
+            "[Import] Validation failed for event storming import: {:?}",
+            validation_errors
+        );
+        let errors_json: Vec<Value> = validation_errors
+            .iter()
+            .map(|e| {
+                json!({
+                    "type": "validation_error",
+                    "table": e.table_name,
+                    "field": e.field,
+                    "message": e.message
+                })
+            })
+            .collect();
+        return Ok(Json(json!({
+            "domain": domain,
+            "tables": [],
+            "errors": errors_json
+        })));
+    }
+    for table in &tables {
+        state
+            .model_limits
+            .check_columns(&table.name, table.columns.len())?;
+    }
+    state.model_limits.check_table_count(0, tables.len())?;
+
+    let workspace = super::workspace::get_or_create_workspace(&state, &user_context).await?;
+    let user_dir = super::workspace::get_workspace_data_dir()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .join(super::workspace::workspace_dir_name(&user_context.email));
+    if domain_exists(&state, &workspace, &user_dir, domain).await? {
+        return Err(ApiError {
+            status: StatusCode::CONFLICT,
+            message: format!("Domain '{}' already exists", domain),
+        });
+    }
+    let entry = DomainManifestEntry {
+        name: domain.to_string(),
+        description: request.description.clone().or_else(|| {
+            Some("Candidate conceptual model from an event storming workshop".to_string())
+        }),
+        owners: Vec::new(),
+        template: None,
+    };
+    create_domain_with_model(
+        &state,
+        &user_context,
+        &workspace,
+        &user_dir,
+        &entry,
+        &tables,
+        &parsed.relationships,
+    )
+    .await
+    .map_err(|e| {
+        error!(
+            "Failed to create domain {} from event storming board: {}",
+            domain, e
+        );
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    // Creating the domain loaded it before its tables were written
+    super::workspace::ensure_domain_loaded_with_reload(&state, &headers, domain, true).await?;
+
+    let errors_json: Vec<Value> = parsed
+        .errors
+        .iter()
+        .map(|e| {
+            json!({
+                "type": e.error_type,
+                "field": e.field,
+                "message": e.message
+            })
+        })
+        .collect();
+    info!(
+        "[Import] Created domain {} with {} candidate table(s) from an event storming board",
+        domain,
+        tables.len()
+    );
+    Ok(Json(json!({
+        "domain": domain,
+        "tables": tables,
+        "relationships": parsed.relationships,
+        "errors": errors_json
+    })))
+}
+
+// Domain-scoped import handlers - use ensure_domain_loaded() to load domain before importing
+
+/// POST /workspace/domains/{domain}/import/odcl - Import tables from ODCS/ODCL file (domain-scoped)
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/import/odcl",
+    tag = "Import",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body(content = Multipart, description = "ODCS/ODCL YAML file"),
+    responses(
+        (status = 200, description = "ODCS/ODCL file imported successfully", body = Object),
+        (status = 400, description = "Bad request - invalid file or format"),
+        (status = 422, description = "Model size limit exceeded", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn domain_import_odcl(
+    State(state): State<AppState>,
+    Path(path): Path<super::workspace::DomainPath>,
+    headers: HeaderMap,
+    auth: AuthContext,
+    #[allow(unused_mut)] mut multipart: Multipart,
+) -> Result<Json<Value>, ApiError> {
+    // Ensure domain is loaded before importing
+    let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+
+    // Delegate to the existing import handler logic
+    import_odcl(State(state), auth, multipart).await
+}
+
+/// POST /workspace/domains/{domain}/import/odcl/text - Import tables from ODCS/ODCL text (domain-scoped)
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/import/odcl/text",
+    tag = "Import",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body(content = ODCLTextImportRequest, description = "ODCS/ODCL YAML text"),
+    responses(
+        (status = 200, description = "ODCS/ODCL text imported successfully", body = Object),
+        (status = 400, description = "Bad request - invalid text or format"),
+        (status = 422, description = "Model size limit exceeded", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn domain_import_odcl_text(
+    State(state): State<AppState>,
+    Path(path): Path<super::workspace::DomainPath>,
+    headers: HeaderMap,
+    auth: AuthContext,
+    Json(request): Json<ODCLTextImportRequest>,
+) -> Result<Json<Value>, ApiError> {
+    // Ensure domain is loaded before importing
+    let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+
+    // Delegate to the existing import handler logic
+    import_odcl_text(State(state), auth, Json(request)).await
+}
+
+/// POST /workspace/domains/{domain}/import/sql - Import tables from SQL file (domain-scoped)
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/import/sql",
+    tag = "Import",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body(content = Multipart, description = "SQL file"),
+    responses(
+        (status = 200, description = "SQL file imported successfully", body = Object),
+        (status = 400, description = "Bad request - invalid file or format"),
+        (status = 422, description = "Model size limit exceeded", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn domain_import_sql(
+    State(state): State<AppState>,
+    Path(path): Path<super::workspace::DomainPath>,
+    headers: HeaderMap,
+    auth: AuthContext,
+    #[allow(unused_mut)] mut multipart: Multipart,
+) -> Result<Json<Value>, ApiError> {
+    // Ensure domain is loaded before importing
+    let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
 
     // Delegate to the existing import handler logic
     import_sql(State(state), auth, multipart).await
@@ -1806,6 +2938,7 @@ async fn domain_import_sql(
     responses(
         (status = 200, description = "SQL text imported successfully", body = Object),
         (status = 400, description = "Bad request - invalid text or format"),
+        (status = 422, description = "Model size limit exceeded", body = crate::api_types::ErrorResponse),
         (status = 401, description = "Unauthorized - invalid or missing token"),
         (status = 500, description = "Internal server error")
     ),
@@ -1817,7 +2950,7 @@ async fn domain_import_sql_text(
     headers: HeaderMap,
     auth: AuthContext,
     Json(request): Json<SQLTextImportRequest>,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     // Ensure domain is loaded before importing
     let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
 
@@ -1833,10 +2966,11 @@ async fn domain_import_sql_text(
     params(
         ("domain" = String, Path, description = "Domain name")
     ),
-    request_body(content = Multipart, description = "Avro schema file"),
+    request_body(content = Multipart, description = "AVRO schema (.avsc), IDL (.avdl) or zip of .avsc files"),
     responses(
         (status = 200, description = "Avro schema imported successfully", body = Object),
         (status = 400, description = "Bad request - invalid file or format"),
+        (status = 422, description = "Model size limit exceeded", body = crate::api_types::ErrorResponse),
         (status = 401, description = "Unauthorized - invalid or missing token"),
         (status = 500, description = "Internal server error")
     ),
@@ -1848,7 +2982,7 @@ async fn domain_import_avro(
     headers: HeaderMap,
     auth: AuthContext,
     #[allow(unused_mut)] mut multipart: Multipart,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     // Ensure domain is loaded before importing
     let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
 
@@ -1868,6 +3002,7 @@ async fn domain_import_avro(
     responses(
         (status = 200, description = "JSON Schema imported successfully", body = Object),
         (status = 400, description = "Bad request - invalid file or format"),
+        (status = 422, description = "Model size limit exceeded", body = crate::api_types::ErrorResponse),
         (status = 401, description = "Unauthorized - invalid or missing token"),
         (status = 500, description = "Internal server error")
     ),
@@ -1879,7 +3014,7 @@ async fn domain_import_json_schema(
     headers: HeaderMap,
     auth: AuthContext,
     #[allow(unused_mut)] mut multipart: Multipart,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     // Ensure domain is loaded before importing
     let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
 
@@ -1899,6 +3034,7 @@ async fn domain_import_json_schema(
     responses(
         (status = 200, description = "Protobuf schema imported successfully", body = Object),
         (status = 400, description = "Bad request - invalid file or format"),
+        (status = 422, description = "Model size limit exceeded", body = crate::api_types::ErrorResponse),
         (status = 401, description = "Unauthorized - invalid or missing token"),
         (status = 500, description = "Internal server error")
     ),
@@ -1910,10 +3046,202 @@ async fn domain_import_protobuf(
     headers: HeaderMap,
     auth: AuthContext,
     #[allow(unused_mut)] mut multipart: Multipart,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     // Ensure domain is loaded before importing
     let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
 
     // Delegate to the existing import handler logic
     import_protobuf(State(state), auth, multipart).await
 }
+
+/// POST /workspace/domains/{domain}/import/erwin - Import tables from an Erwin XML export (domain-scoped)
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/import/erwin",
+    tag = "Import",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body(content = Multipart, description = "Erwin XML export file"),
+    responses(
+        (status = 200, description = "Erwin model imported successfully", body = Object),
+        (status = 400, description = "Bad request - invalid file or format"),
+        (status = 422, description = "Model size limit exceeded", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn domain_import_erwin(
+    State(state): State<AppState>,
+    Path(path): Path<super::workspace::DomainPath>,
+    headers: HeaderMap,
+    auth: AuthContext,
+    #[allow(unused_mut)] mut multipart: Multipart,
+) -> Result<Json<Value>, ApiError> {
+    // Ensure domain is loaded before importing
+    let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+
+    // Delegate to the existing import handler logic
+    import_erwin(State(state), auth, multipart).await
+}
+
+/// POST /workspace/domains/{domain}/import/orm/text - Import tables from ORM model source (domain-scoped)
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/import/orm/text",
+    tag = "Import",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body(content = ORMTextImportRequest, description = "SQLAlchemy, Django or Prisma model source"),
+    responses(
+        (status = 200, description = "ORM models imported successfully", body = Object),
+        (status = 400, description = "Bad request - unknown format or no models found"),
+        (status = 422, description = "Model size limit exceeded", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn domain_import_orm_text(
+    State(state): State<AppState>,
+    Path(path): Path<super::workspace::DomainPath>,
+    headers: HeaderMap,
+    auth: AuthContext,
+    Json(request): Json<ORMTextImportRequest>,
+) -> Result<Json<Value>, ApiError> {
+    // Ensure domain is loaded before importing
+    let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+
+    // Delegate to the existing import handler logic
+    import_orm_text(State(state), auth, Json(request)).await
+}
+
+/// POST /workspace/domains/{domain}/import/dbml - Import tables from DBML (domain-scoped)
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/import/dbml",
+    tag = "Import",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body(content = DBMLImportRequest, description = "DBML source, e.g. a dbdiagram.io export"),
+    responses(
+        (status = 200, description = "DBML imported successfully", body = Object),
+        (status = 400, description = "Bad request - no tables found"),
+        (status = 422, description = "Model size limit exceeded", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn domain_import_dbml(
+    State(state): State<AppState>,
+    Path(path): Path<super::workspace::DomainPath>,
+    headers: HeaderMap,
+    auth: AuthContext,
+    Json(request): Json<DBMLImportRequest>,
+) -> Result<Json<Value>, ApiError> {
+    // Ensure domain is loaded before importing
+    let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+
+    // Delegate to the existing import handler logic
+    import_dbml(State(state), auth, Json(request)).await
+}
+
+/// POST /workspace/domains/{domain}/import/lakehouse - Import tables from Delta Lake or Iceberg metadata (domain-scoped)
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/import/lakehouse",
+    tag = "Import",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body(content = Multipart, description = "Delta log / Iceberg metadata file, zip, or path"),
+    responses(
+        (status = 200, description = "Lakehouse metadata imported successfully", body = Object),
+        (status = 400, description = "Bad request - invalid file or format"),
+        (status = 422, description = "Model size limit exceeded", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 403, description = "Forbidden - path outside the import root"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn domain_import_lakehouse(
+    State(state): State<AppState>,
+    Path(path): Path<super::workspace::DomainPath>,
+    headers: HeaderMap,
+    auth: AuthContext,
+    #[allow(unused_mut)] mut multipart: Multipart,
+) -> Result<Json<Value>, ApiError> {
+    // Ensure domain is loaded before importing
+    let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+
+    // Delegate to the existing import handler logic
+    import_lakehouse(State(state), auth, multipart).await
+}
+
+/// POST /workspace/domains/{domain}/import/google-sheets - Import tables from a Google Sheets data dictionary (domain-scoped)
+///
+/// Each sheet row describes one column. The layout comes from the request's `mapping` or the
+/// workspace's saved mapping; the OAuth access token is only used to read the sheet.
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/import/google-sheets",
+    tag = "Import",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body = GoogleSheetsImportRequest,
+    responses(
+        (status = 200, description = "Data dictionary imported successfully", body = Object),
+        (status = 400, description = "Bad request - invalid URL, token or sheet layout"),
+        (status = 422, description = "Model size limit exceeded", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 502, description = "Google Sheets API request failed")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn domain_import_google_sheets(
+    State(state): State<AppState>,
+    Path(path): Path<super::workspace::DomainPath>,
+    headers: HeaderMap,
+    Json(request): Json<GoogleSheetsImportRequest>,
+) -> Result<Json<Value>, ApiError> {
+    // Ensure domain is loaded before importing
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+
+    import_google_sheets(&state, &ctx.user_context.email, request).await
+}
+
+/// POST /workspace/domains/{domain}/import/relationships/csv - Create relationships from a CSV mapping file (domain-scoped)
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/import/relationships/csv",
+    tag = "Import",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body(content = Multipart, description = "CSV file with source_table, source_column, target_table, target_column and cardinality columns"),
+    responses(
+        (status = 200, description = "Relationships created, with row-level errors", body = Object),
+        (status = 400, description = "Bad request - missing file or required CSV column", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 404, description = "Domain not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+async fn domain_import_relationships_csv(
+    State(state): State<AppState>,
+    Path(path): Path<super::workspace::DomainPath>,
+    headers: HeaderMap,
+    auth: AuthContext,
+    multipart: Multipart,
+) -> Result<Json<Value>, ApiError> {
+    // Ensure domain is loaded before importing
+    let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+
+    import_relationships_csv(State(state), auth, multipart).await
+}
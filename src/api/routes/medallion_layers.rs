@@ -0,0 +1,108 @@
+//! Medallion layer mapping routes.
+//!
+//! `GET /workspace/medallion-layers` returns the naming conventions imports use to infer the
+//! medallion layer of tables that don't declare one; administrators replace them with
+//! `PUT /workspace/medallion-layers`.
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use tracing::{info, warn};
+
+use super::app_state::AppState;
+use super::audit::is_admin;
+use super::error::ApiError;
+use super::workspace::{get_user_context, get_workspace_data_dir};
+use crate::models::Table;
+use crate::services::medallion_inference_service::MedallionLayerMapping;
+
+/// Load the workspace's medallion layer mapping (the default conventions if none is configured).
+pub fn load_medallion_layer_mapping() -> MedallionLayerMapping {
+    get_workspace_data_dir()
+        .map(|dir| MedallionLayerMapping::load(&dir))
+        .unwrap_or_default()
+}
+
+/// Infer the medallion layer of imported tables that don't declare one.
+pub(crate) fn infer_medallion_layers(tables: &mut [Table]) {
+    let mapping = load_medallion_layer_mapping();
+    for table in tables {
+        if let Some(layer) = mapping.infer(table) {
+            info!(
+                "Inferred medallion layer {:?} for table '{}'",
+                layer, table.name
+            );
+        }
+    }
+}
+
+/// GET /workspace/medallion-layers - Get the workspace's medallion layer mapping
+#[utoipa::path(
+    get,
+    path = "/workspace/medallion-layers",
+    tag = "Workspace",
+    responses(
+        (status = 200, description = "Medallion layer mapping", body = MedallionLayerMapping),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_medallion_layers(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<MedallionLayerMapping>, StatusCode> {
+    get_user_context(&state, &headers).await?;
+    Ok(Json(load_medallion_layer_mapping()))
+}
+
+/// PUT /workspace/medallion-layers - Replace the workspace's medallion layer mapping
+#[utoipa::path(
+    put,
+    path = "/workspace/medallion-layers",
+    tag = "Workspace",
+    request_body = MedallionLayerMapping,
+    responses(
+        (status = 200, description = "Mapping saved; following imports infer layers with it", body = MedallionLayerMapping),
+        (status = 400, description = "Rule without a table prefix or schema name, or with an empty one", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an administrator"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_medallion_layers(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(mapping): Json<MedallionLayerMapping>,
+) -> Result<Json<MedallionLayerMapping>, ApiError> {
+    let user_context = get_user_context(&state, &headers).await?;
+    if !is_admin(&user_context.email) {
+        warn!(
+            "Non-admin {} denied changing the medallion layer mapping",
+            user_context.email
+        );
+        return Err(StatusCode::FORBIDDEN.into());
+    }
+    mapping.check_rules().map_err(|message| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message,
+    })?;
+    let dir = get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    mapping.save(&dir).map_err(|e| {
+        warn!("Failed to save medallion layer mapping: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    info!(
+        "{} {} medallion layer inference ({} rules)",
+        user_context.email,
+        if mapping.enabled {
+            "enabled"
+        } else {
+            "disabled"
+        },
+        mapping.rules.len()
+    );
+    Ok(Json(mapping))
+}
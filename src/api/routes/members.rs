@@ -0,0 +1,459 @@
+//! Workspace and domain member routes.
+//!
+//! Owners grant other users a role (owner, editor or viewer) by email, on the whole
+//! workspace (`/workspace/members`) or on one domain (`/workspace/domains/{domain}/members`).
+//! Grants are stored in the workspace's or the domain's `members.yaml` and enforced by
+//! `middleware::access_control`; users from outside the workspace reach it by sending the
+//! workspace's email in the `X-Workspace` header.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use super::app_state::AppState;
+use super::domain_metadata::is_domain_owner;
+use super::error::ApiError;
+use super::workspace::{
+    DomainPath, get_user_context, get_workspace_data_dir, home_workspace_email,
+    sanitize_email_for_path, workspace_email,
+};
+use crate::services::access_control_service::{AccessGrants, effective_role};
+use crate::services::domain_invite_service::{DomainMember, DomainMembers, DomainRole};
+use crate::services::domain_service::DomainMetadata;
+
+/// Request body for granting a user a role
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct GrantMemberRequest {
+    pub email: String,
+    pub role: DomainRole,
+}
+
+/// Request body for changing a member's role
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateMemberRequest {
+    pub role: DomainRole,
+}
+
+/// Members of a workspace or domain
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MembersResponse {
+    /// Email the workspace is keyed by
+    pub workspace: String,
+    /// None for workspace-wide members
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    pub members: Vec<DomainMember>,
+}
+
+/// Path parameters of a workspace member
+#[derive(Deserialize)]
+pub struct MemberPath {
+    pub email: String,
+}
+
+/// Path parameters of a domain member
+#[derive(Deserialize)]
+pub struct DomainMemberPath {
+    pub domain: String,
+    pub email: String,
+}
+
+/// Directory of the workspace keyed by `workspace`
+fn workspace_dir(workspace: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir.join(sanitize_email_for_path(workspace)))
+}
+
+/// Role of `email` in `workspace`, or on one of its domains; `home` is the workspace the user
+/// works in by default. None if the user has no access.
+pub(crate) fn access_role(
+    email: &str,
+    home: &str,
+    workspace: &str,
+    domain: Option<&str>,
+) -> Result<Option<DomainRole>, StatusCode> {
+    let dir = workspace_dir(workspace)?;
+    let workspace_members = DomainMembers::load(&dir);
+    let domain_grants = domain.map(|domain| {
+        let domain_dir = dir.join(domain);
+        (
+            DomainMembers::load(&domain_dir),
+            DomainMetadata::load(&domain_dir).owners,
+        )
+    });
+    Ok(effective_role(
+        email,
+        home,
+        &AccessGrants {
+            workspace,
+            workspace_members: &workspace_members,
+            domain: domain_grants
+                .as_ref()
+                .map(|(members, owners)| (members, owners.as_slice())),
+        },
+    ))
+}
+
+/// Whether `email` was granted a role in `workspace`, on the workspace or on one of its
+/// domains.
+pub(crate) fn is_member(email: &str, workspace: &str) -> bool {
+    let Ok(dir) = workspace_dir(workspace) else {
+        return false;
+    };
+    if DomainMembers::load(&dir).role_of(email).is_some() {
+        return true;
+    }
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return false;
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|domain_dir| domain_dir.is_dir())
+        .any(|domain_dir| {
+            DomainMembers::load(&domain_dir).role_of(email).is_some()
+                || (DomainMetadata::load(&domain_dir).owners.iter())
+                    .any(|owner| owner.eq_ignore_ascii_case(email))
+        })
+}
+
+/// The caller and the workspace the request acts in, if the caller owns the workspace
+async fn workspace_owner(
+    state: &AppState,
+    headers: &HeaderMap,
+) -> Result<(String, String), ApiError> {
+    let user_context = get_user_context(state, headers).await?;
+    let email = user_context.email;
+    let workspace = workspace_email(&email);
+    let role = access_role(&email, &home_workspace_email(&email), &workspace, None)?;
+    if role != Some(DomainRole::Owner) {
+        return Err(ApiError {
+            status: StatusCode::FORBIDDEN,
+            message: "Only workspace owners can manage workspace members".to_string(),
+        });
+    }
+    Ok((email, workspace))
+}
+
+/// The caller and the domain's directory, if the caller owns the domain
+async fn domain_owner(
+    state: &AppState,
+    headers: &HeaderMap,
+    domain: &str,
+) -> Result<(String, PathBuf), ApiError> {
+    let ctx = super::workspace::ensure_domain_loaded(state, headers, domain).await?;
+    if !is_domain_owner(&ctx, domain) {
+        return Err(ApiError {
+            status: StatusCode::FORBIDDEN,
+            message: "Only domain owners can manage domain members".to_string(),
+        });
+    }
+    let email = ctx.user_context.email;
+    let dir = workspace_dir(&workspace_email(&email))?.join(domain);
+    Ok((email, dir))
+}
+
+fn save(members: &DomainMembers, dir: &std::path::Path) -> Result<(), StatusCode> {
+    members.save(dir).map_err(|e| {
+        warn!("Failed to save members: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+/// Grant a new member a role
+async fn add_member(
+    dir: &std::path::Path,
+    granted_by: &str,
+    request: GrantMemberRequest,
+) -> Result<DomainMember, ApiError> {
+    let email = request.email.trim();
+    if !email.contains('@') {
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: "email must be an email address".to_string(),
+        });
+    }
+    let _lock = DomainMembers::lock(dir).await;
+    let mut members = DomainMembers::load(dir);
+    if members.role_of(email).is_some() {
+        return Err(ApiError {
+            status: StatusCode::CONFLICT,
+            message: format!("{} is already a member; change their role instead", email),
+        });
+    }
+    let member = members.grant(email, request.role, granted_by, Utc::now());
+    save(&members, dir)?;
+    info!(
+        "{} granted {} the {} role ({})",
+        granted_by,
+        email,
+        member.role.as_str(),
+        dir.display()
+    );
+    Ok(member)
+}
+
+/// Change a member's role
+async fn change_member(
+    dir: &std::path::Path,
+    granted_by: &str,
+    email: &str,
+    role: DomainRole,
+) -> Result<DomainMember, StatusCode> {
+    let _lock = DomainMembers::lock(dir).await;
+    let mut members = DomainMembers::load(dir);
+    if members.role_of(email).is_none() {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    let member = members.grant(email, role, granted_by, Utc::now());
+    save(&members, dir)?;
+    Ok(member)
+}
+
+/// Revoke a member's role
+async fn remove_member(dir: &std::path::Path, email: &str) -> Result<StatusCode, StatusCode> {
+    let _lock = DomainMembers::lock(dir).await;
+    let mut members = DomainMembers::load(dir);
+    if !members.remove_member(email) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    save(&members, dir)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /workspace/members - List the workspace's members
+#[utoipa::path(
+    get,
+    path = "/workspace/members",
+    tag = "Sharing",
+    responses(
+        (status = 200, description = "Users granted a role on the whole workspace", body = MembersResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "No access to the workspace"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_workspace_members(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<MembersResponse>, StatusCode> {
+    let user_context = get_user_context(&state, &headers).await?;
+    let workspace = workspace_email(&user_context.email);
+    let members = DomainMembers::load(&workspace_dir(&workspace)?).members;
+    Ok(Json(MembersResponse {
+        workspace,
+        domain: None,
+        members,
+    }))
+}
+
+/// POST /workspace/members - Grant a user a role on the whole workspace
+#[utoipa::path(
+    post,
+    path = "/workspace/members",
+    tag = "Sharing",
+    request_body = GrantMemberRequest,
+    responses(
+        (status = 200, description = "Role granted", body = DomainMember),
+        (status = 400, description = "Invalid email", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a workspace owner", body = crate::api_types::ErrorResponse),
+        (status = 409, description = "Already a member", body = crate::api_types::ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn add_workspace_member(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<GrantMemberRequest>,
+) -> Result<Json<DomainMember>, ApiError> {
+    let (email, workspace) = workspace_owner(&state, &headers).await?;
+    Ok(Json(
+        add_member(&workspace_dir(&workspace)?, &email, request).await?,
+    ))
+}
+
+/// PUT /workspace/members/{email} - Change a workspace member's role
+#[utoipa::path(
+    put,
+    path = "/workspace/members/{email}",
+    tag = "Sharing",
+    params(
+        ("email" = String, Path, description = "Member email")
+    ),
+    request_body = UpdateMemberRequest,
+    responses(
+        (status = 200, description = "Role changed", body = DomainMember),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a workspace owner", body = crate::api_types::ErrorResponse),
+        (status = 404, description = "Not a member"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_workspace_member(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<MemberPath>,
+    Json(request): Json<UpdateMemberRequest>,
+) -> Result<Json<DomainMember>, ApiError> {
+    let (email, workspace) = workspace_owner(&state, &headers).await?;
+    let dir = workspace_dir(&workspace)?;
+    Ok(Json(
+        change_member(&dir, &email, &path.email, request.role).await?,
+    ))
+}
+
+/// DELETE /workspace/members/{email} - Revoke a workspace member's role
+#[utoipa::path(
+    delete,
+    path = "/workspace/members/{email}",
+    tag = "Sharing",
+    params(
+        ("email" = String, Path, description = "Member email")
+    ),
+    responses(
+        (status = 204, description = "Role revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a workspace owner", body = crate::api_types::ErrorResponse),
+        (status = 404, description = "Not a member"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn remove_workspace_member(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<MemberPath>,
+) -> Result<StatusCode, ApiError> {
+    let (_, workspace) = workspace_owner(&state, &headers).await?;
+    Ok(remove_member(&workspace_dir(&workspace)?, &path.email).await?)
+}
+
+/// GET /workspace/domains/{domain}/members - List the domain's members
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/members",
+    tag = "Sharing",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Users granted a role on the domain", body = MembersResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "No access to the domain"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_domain_members(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+) -> Result<Json<MembersResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let workspace = workspace_email(&ctx.user_context.email);
+    let members = DomainMembers::load(&workspace_dir(&workspace)?.join(&path.domain)).members;
+    Ok(Json(MembersResponse {
+        workspace,
+        domain: Some(path.domain),
+        members,
+    }))
+}
+
+/// POST /workspace/domains/{domain}/members - Grant a user a role on the domain
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/members",
+    tag = "Sharing",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body = GrantMemberRequest,
+    responses(
+        (status = 200, description = "Role granted", body = DomainMember),
+        (status = 400, description = "Invalid email", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a domain owner", body = crate::api_types::ErrorResponse),
+        (status = 409, description = "Already a member", body = crate::api_types::ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn add_domain_member(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    Json(request): Json<GrantMemberRequest>,
+) -> Result<Json<DomainMember>, ApiError> {
+    let (email, dir) = domain_owner(&state, &headers, &path.domain).await?;
+    Ok(Json(add_member(&dir, &email, request).await?))
+}
+
+/// PUT /workspace/domains/{domain}/members/{email} - Change a domain member's role
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/members/{email}",
+    tag = "Sharing",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("email" = String, Path, description = "Member email")
+    ),
+    request_body = UpdateMemberRequest,
+    responses(
+        (status = 200, description = "Role changed", body = DomainMember),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a domain owner", body = crate::api_types::ErrorResponse),
+        (status = 404, description = "Not a member"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_domain_member(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainMemberPath>,
+    Json(request): Json<UpdateMemberRequest>,
+) -> Result<Json<DomainMember>, ApiError> {
+    let (email, dir) = domain_owner(&state, &headers, &path.domain).await?;
+    Ok(Json(
+        change_member(&dir, &email, &path.email, request.role).await?,
+    ))
+}
+
+/// DELETE /workspace/domains/{domain}/members/{email} - Revoke a domain member's role
+#[utoipa::path(
+    delete,
+    path = "/workspace/domains/{domain}/members/{email}",
+    tag = "Sharing",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("email" = String, Path, description = "Member email")
+    ),
+    responses(
+        (status = 204, description = "Role revoked"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a domain owner", body = crate::api_types::ErrorResponse),
+        (status = 404, description = "Not a member"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn remove_domain_member(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainMemberPath>,
+) -> Result<StatusCode, ApiError> {
+    let (_, dir) = domain_owner(&state, &headers, &path.domain).await?;
+    Ok(remove_member(&dir, &path.email).await?)
+}
@@ -0,0 +1,167 @@
+//! Metadata propagation routes.
+//!
+//! Copy descriptions, classifications and glossary links down column-level lineage into
+//! blank downstream columns. `dry_run` reports what would be filled without saving, so
+//! silver and gold tables can inherit most of their documentation after a review.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::events;
+use super::workspace::{
+    DomainPath, get_workspace_data_dir, serialize_table_with_database_type, workspace_dir_name,
+};
+use crate::models::Table;
+use crate::services::event_log_service::ChangeEventType;
+use crate::services::git_service::GitService;
+use crate::services::metadata_propagation_service::{
+    PropagatedField, PropagatedValue, propagate_metadata,
+};
+
+/// Request body for propagating metadata
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct PropagateMetadataRequest {
+    /// Report the values that would be filled without saving them
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Fields to propagate; all of them if omitted
+    pub fields: Option<Vec<PropagatedField>>,
+}
+
+/// Result of a metadata propagation
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PropagateMetadataResponse {
+    pub dry_run: bool,
+    /// Values copied to downstream columns
+    pub propagated: Vec<PropagatedValue>,
+    /// Tables that were (or would be) updated
+    pub updated_tables: Vec<Uuid>,
+}
+
+/// IDs of the tables a propagation touched, in first-touched order
+fn touched_tables(propagated: &[PropagatedValue]) -> Vec<Uuid> {
+    let mut ids = Vec::new();
+    for value in propagated {
+        if !ids.contains(&value.table_id) {
+            ids.push(value.table_id);
+        }
+    }
+    ids
+}
+
+/// POST /workspace/domains/{domain}/propagate-metadata - Fill blank column metadata from lineage
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/propagate-metadata",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body = PropagateMetadataRequest,
+    responses(
+        (status = 200, description = "Values propagated (or, for a dry run, that would be)", body = PropagateMetadataResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Domain not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn propagate_domain_metadata(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    Json(request): Json<PropagateMetadataRequest>,
+) -> Result<Json<PropagateMetadataResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let fields = request
+        .fields
+        .unwrap_or_else(|| PropagatedField::ALL.to_vec());
+
+    let (before, after, propagated): (Vec<Table>, Vec<Table>, _) =
+        if let Some(storage) = state.storage.as_ref() {
+            let mut tables = storage
+                .get_tables(ctx.domain_info.id)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            let relationships = storage
+                .get_relationships(ctx.domain_info.id)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            let before = tables.clone();
+            let propagated = propagate_metadata(&mut tables, &relationships, &fields);
+            if !request.dry_run {
+                let updated = touched_tables(&propagated);
+                for table in tables.iter().filter(|t| updated.contains(&t.id)) {
+                    storage
+                        .update_table(table.clone(), None, &ctx.user_context)
+                        .await
+                        .map_err(|e| {
+                            warn!("Failed to update table {}: {}", table.name, e);
+                            StatusCode::INTERNAL_SERVER_ERROR
+                        })?;
+                }
+            }
+            (before, tables, propagated)
+        } else {
+            let mut model_service = state.model_service.lock().await;
+            let model = model_service
+                .get_current_model_mut()
+                .ok_or(StatusCode::NOT_FOUND)?;
+            let before = model.tables.clone();
+            let mut tables = model.tables.clone();
+            let propagated = propagate_metadata(&mut tables, &model.relationships, &fields);
+            if !request.dry_run && !propagated.is_empty() {
+                let dir: PathBuf = get_workspace_data_dir()
+                    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+                    .join(workspace_dir_name(&ctx.user_context.email))
+                    .join(&path.domain);
+                let mut git_service = GitService::new();
+                git_service
+                    .set_git_directory_path(&dir)
+                    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                let updated = touched_tables(&propagated);
+                for table in tables.iter().filter(|t| updated.contains(&t.id)) {
+                    git_service.save_table_to_yaml(table).map_err(|e| {
+                        warn!("Failed to save table {}: {}", table.name, e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })?;
+                }
+                model.tables = tables.clone();
+            }
+            (before, tables, propagated)
+        };
+
+    let updated_tables = touched_tables(&propagated);
+    if !request.dry_run {
+        for table in after.iter().filter(|t| updated_tables.contains(&t.id)) {
+            events::record_model_change(
+                &state,
+                &ctx,
+                &path.domain,
+                ChangeEventType::TableUpdated,
+                table.id,
+                before
+                    .iter()
+                    .find(|t| t.id == table.id)
+                    .map(serialize_table_with_database_type),
+                Some(serialize_table_with_database_type(table)),
+            )
+            .await;
+        }
+    }
+
+    Ok(Json(PropagateMetadataResponse {
+        dry_run: request.dry_run,
+        propagated,
+        updated_tables,
+    }))
+}
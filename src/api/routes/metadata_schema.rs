@@ -0,0 +1,144 @@
+//! Custom metadata schema routes.
+//!
+//! `GET /workspace/metadata-schema` returns the workspace's typed custom fields for tables
+//! and columns; administrators replace them with `PUT /workspace/metadata-schema`. Table
+//! creates and updates are checked against the declared fields.
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use std::collections::HashMap;
+use tracing::{info, warn};
+
+use super::app_state::AppState;
+use super::audit::is_admin;
+use super::error::ApiError;
+use super::workspace::{get_user_context, get_workspace_data_dir};
+use crate::models::Column;
+use crate::services::metadata_schema_service::{CustomFieldViolation, MetadataSchema};
+
+/// Load the workspace's metadata schema (no fields if none is declared).
+pub fn load_metadata_schema() -> MetadataSchema {
+    get_workspace_data_dir()
+        .map(|dir| MetadataSchema::load(&dir))
+        .unwrap_or_default()
+}
+
+fn reject(violations: Vec<CustomFieldViolation>) -> Result<(), ApiError> {
+    if violations.is_empty() {
+        return Ok(());
+    }
+    let messages: Vec<String> = violations
+        .iter()
+        .map(|v| match &v.column {
+            Some(column) => format!("column '{}' field '{}' {}", column, v.field, v.message),
+            None => format!("field '{}' {}", v.field, v.message),
+        })
+        .collect();
+    Err(ApiError {
+        status: StatusCode::UNPROCESSABLE_ENTITY,
+        message: format!("Invalid custom metadata: {}", messages.join("; ")),
+    })
+}
+
+/// A table's `odcl_metadata` with the keys of an update merged in (null removes a key), if
+/// the update sets any.
+pub(crate) fn merged_metadata(
+    existing: &HashMap<String, serde_json::Value>,
+    updates: &serde_json::Value,
+) -> Option<HashMap<String, serde_json::Value>> {
+    let update = updates.get("odcl_metadata")?.as_object()?;
+    let mut merged = existing.clone();
+    for (key, value) in update {
+        if value.is_null() {
+            merged.remove(key);
+        } else {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+    Some(merged)
+}
+
+/// Reject table metadata and columns whose custom field values do not match the schema.
+///
+/// Either part may be omitted for partial updates, so untouched values are not rechecked.
+pub(crate) fn enforce_metadata_schema(
+    odcl_metadata: Option<&HashMap<String, serde_json::Value>>,
+    columns: Option<&[Column]>,
+) -> Result<(), ApiError> {
+    let schema = load_metadata_schema();
+    let mut violations = Vec::new();
+    if let Some(odcl_metadata) = odcl_metadata {
+        violations.extend(schema.validate_table_metadata(odcl_metadata));
+    }
+    if let Some(columns) = columns {
+        violations.extend(schema.validate_columns(columns));
+    }
+    reject(violations)
+}
+
+/// GET /workspace/metadata-schema - Get the workspace's custom metadata fields
+#[utoipa::path(
+    get,
+    path = "/workspace/metadata-schema",
+    tag = "Workspace",
+    responses(
+        (status = 200, description = "Declared custom fields", body = MetadataSchema),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_metadata_schema(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<MetadataSchema>, StatusCode> {
+    get_user_context(&state, &headers).await?;
+    Ok(Json(load_metadata_schema()))
+}
+
+/// PUT /workspace/metadata-schema - Replace the workspace's custom metadata fields
+#[utoipa::path(
+    put,
+    path = "/workspace/metadata-schema",
+    tag = "Workspace",
+    request_body = MetadataSchema,
+    responses(
+        (status = 200, description = "Schema saved; following table writes are validated against it", body = MetadataSchema),
+        (status = 400, description = "Missing or duplicate field name, or allowed values on a field that is not a string or list", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an administrator"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_metadata_schema(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(schema): Json<MetadataSchema>,
+) -> Result<Json<MetadataSchema>, ApiError> {
+    let user_context = get_user_context(&state, &headers).await?;
+    if !is_admin(&user_context.email) {
+        warn!(
+            "Non-admin {} denied changing the metadata schema",
+            user_context.email
+        );
+        return Err(StatusCode::FORBIDDEN.into());
+    }
+    schema.check_definitions().map_err(|message| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message,
+    })?;
+    let dir = get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    schema.save(&dir).map_err(|e| {
+        warn!("Failed to save metadata schema: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    info!(
+        "{} saved {} custom metadata fields",
+        user_context.email,
+        schema.fields.len()
+    );
+    Ok(Json(schema))
+}
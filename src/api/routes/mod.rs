@@ -3,23 +3,77 @@
 //! All table and relationship operations are now domain-scoped under /workspace/domains/{domain}/
 
 pub mod ai;
+pub mod api_keys;
+pub mod api_versions;
 pub mod app_state;
+pub mod attachments;
 pub mod audit;
 pub mod auth;
 pub mod auth_context;
+pub mod column_edges;
+pub mod column_references;
+pub mod commands;
+pub mod contract_tests;
 pub mod data_flow;
+pub mod data_product;
+pub mod desktop;
+pub mod dev_fixtures;
+pub mod diagram_shares;
+pub mod domain_graph;
+pub mod domain_invites;
+pub mod domain_metadata;
+pub mod domain_provisioning;
+pub mod domain_shares;
+pub mod drafts;
+pub mod duplicate_tables;
 pub mod error;
 // DrawIO routes - uses crate::drawio from lib.rs
 pub mod collaboration;
+pub mod collaboration_batch;
 pub mod collaboration_sessions;
+pub mod environments;
+pub mod events;
+pub mod evolution_policy;
+pub mod export_schedules;
+pub mod exports;
 pub mod git_sync;
+pub mod graphql;
+pub mod history;
 pub mod import;
+pub mod medallion_layers;
+pub mod members;
+pub mod metadata_propagation;
+pub mod metadata_schema;
 pub mod models;
+pub mod naming;
+pub mod notifications;
 pub mod openapi;
+pub mod payload_validation;
+pub mod quality_score;
 // Legacy routes kept for AppState definition but not mounted
+pub mod relationship_anchors;
+pub mod relationship_rules;
 pub mod relationships;
+pub mod relationships_file;
+pub mod rename;
+pub mod retention;
+pub mod row_policies;
+pub mod saved_filters;
+pub mod script_hooks;
+pub mod size_estimates;
+pub mod standard_columns;
+pub mod subtypes;
+pub mod table_profiles;
+pub mod table_readme;
+pub mod table_view;
 pub mod tables;
+pub mod telemetry;
+pub mod temporal;
+pub mod usage;
+pub mod v2;
+pub mod work_items;
 pub mod workspace;
+pub mod workspace_provisioning;
 
 use axum::{
     Router,
@@ -38,27 +92,21 @@ pub use tables::AppState as LegacyAppState;
 /// - /workspace/domains/{domain}/tables
 /// - /workspace/domains/{domain}/relationships
 pub fn create_api_router(app_state: AppState) -> Router<AppState> {
-    use crate::services::oauth_service::OAuthService;
-    use std::env;
-    use std::sync::Arc;
+    use crate::services::oauth_service::OAuthProviders;
 
-    // Initialize OAuth service
-    let github_client_id = env::var("GITHUB_CLIENT_ID").unwrap_or_else(|_| "".to_string());
-    let github_client_secret = env::var("GITHUB_CLIENT_SECRET").unwrap_or_else(|_| "".to_string());
-    // GitHub callback MUST point to the API server, not the web client
-    // The API processes the callback and then redirects to the web client
-    let github_redirect_uri = env::var("GITHUB_REDIRECT_URI")
-        .unwrap_or_else(|_| "http://localhost:8081/api/v1/auth/github/callback".to_string());
-
-    let oauth_service = Arc::new(OAuthService::new(
-        github_client_id,
-        github_client_secret,
-        github_redirect_uri,
-    ));
+    // Initialize OAuth providers
+    let oauth_providers = OAuthProviders::from_env();
 
     Router::new()
         // All table/relationship operations are now under /workspace/domains/{domain}/
-        .nest("/workspace", workspace::workspace_router())
+        // Roles on the workspace and its domains are checked before any workspace handler runs
+        .nest(
+            "/workspace",
+            workspace::workspace_router().layer(axum::middleware::from_fn_with_state(
+                app_state.clone(),
+                crate::middleware::access_control::access_control_middleware,
+            )),
+        )
         // New /api/v1/workspaces endpoints (not nested under /workspace)
         .route("/workspaces", get(workspace::list_workspaces))
         .route("/workspaces", post(workspace::create_workspace_v1))
@@ -67,19 +115,64 @@ pub fn create_api_router(app_state: AppState) -> Router<AppState> {
             "/auth",
             auth::auth_router(
                 app_state.session_store.clone(),
-                oauth_service,
+                oauth_providers,
                 app_state.clone(),
             ),
         )
+        // API keys for machine-to-machine clients
+        .nest("/auth/api-keys", api_keys::api_keys_router())
+        // Personal access tokens for the user's own scripts
+        .nest("/auth/tokens", api_keys::personal_access_tokens_router())
         .nest("/ai", ai::ai_router())
         .nest(
             "/collaboration",
             collaboration_sessions::collaboration_sessions_router(),
         )
         .nest("/audit", audit::audit_router())
+        .nest("/notifications", notifications::notifications_router())
+        // Local file dialogs for the desktop app (404 unless running via `desktop`)
+        .nest("/desktop", desktop::desktop_router())
+        // Deterministic sample workspaces for integration tests (404 unless APP_ENV=development)
+        .nest("/dev", dev_fixtures::dev_router())
+        // What opt-in usage telemetry reports
+        .route("/telemetry", get(telemetry::get_telemetry_status))
+        // Signed diagram image links; the token in the path is the only credential
+        .nest("/shared/diagrams", diagram_shares::shared_diagram_router())
+        // Read-only domain browsing for share link holders (no account)
+        .nest(
+            "/shared/domains/{token}",
+            domain_shares::shared_domain_router(),
+        )
+        // Signed-in users redeem domain invites; the token names the domain
+        .route(
+            "/invites/{token}/redeem",
+            post(domain_invites::redeem_domain_invite),
+        )
+        .route(
+            "/admin/sharing",
+            get(domain_shares::get_sharing_settings).put(domain_shares::update_sharing_settings),
+        )
+        // Workspace scripting hooks run on every import
+        .route(
+            "/admin/script-hooks",
+            get(script_hooks::get_script_hooks).put(script_hooks::update_script_hooks),
+        )
+        .route(
+            "/admin/script-hooks/test",
+            post(script_hooks::test_script_hooks),
+        )
+        .route(
+            "/graphql",
+            post(graphql::graphql_handler).get(graphql::graphql_sdl),
+        )
         .merge(collaboration::collaboration_router())
         // OpenAPI documentation endpoints
         .merge(openapi::openapi_router())
+        // Requests carrying an API key must stay within its scope
+        .layer(axum::middleware::from_fn_with_state(
+            app_state,
+            crate::middleware::api_key::api_key_middleware,
+        ))
     // Note: State is applied by callers who need it (e.g., TestServer)
     // For production use, call .with_state(app_state) after creating the router
 }
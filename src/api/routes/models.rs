@@ -1,27 +1,55 @@
 //! Model export routes.
 
 use axum::{
+    Extension,
     body::Body,
     extract::{Path, Query, State},
     http::{HeaderMap, HeaderValue, StatusCode, header},
-    response::Response,
+    response::{IntoResponse, Json, Response},
 };
 use serde::Deserialize;
-use serde_json::json;
-use utoipa::ToSchema;
+use tracing::info;
+use utoipa::{IntoParams, ToSchema};
 use uuid::Uuid;
 
 use super::app_state::AppState;
+use crate::export::odps::OdpsExporter;
+use crate::models::{DataModel, Table};
+use crate::services::cancellation::Cancellation;
+use crate::services::domain_invite_service::DomainRole;
+use crate::services::domain_service::DomainMetadata;
 use crate::services::drawio_service::DrawIOService;
+use crate::services::enterprise_model_service::merge_domains;
+use crate::services::environment_service::EnvironmentProfile;
 use crate::services::export_service::ExportService;
+use crate::services::export_stream_service::{ZIP_SPOOL_LIMIT, stream_body};
+use crate::services::inheritance_service::{InheritanceStrategy, apply_inheritance};
+use crate::services::naming_service::NamingTemplates;
+use crate::services::table_profile_service::TableProfiles;
+use crate::services::table_readme_service::TableReadmes;
+use crate::services::work_item_service::WorkItemLinks;
+use crate::storage::traits::UserContext;
+use std::collections::HashMap;
+use std::io::{Seek, SeekFrom};
 use std::path::Path as StdPath;
 
 #[derive(Deserialize, ToSchema)]
 pub struct ExportQuery {
     pub table_ids: Option<Vec<String>>,
-    pub dialect: Option<String>,     // For SQL export
+    pub dialect: Option<String>,                  // For SQL export
     pub format: Option<String>, // For ODCS export (odcs_v3_1_0, odcl_v3_legacy, datacontract, simple)
     pub schema_type: Option<String>, // For schema export: json_schema, avro, protobuf
+    pub environment: Option<String>, // Environment profile to apply (e.g. dev, test, prod)
+    pub inheritance: Option<InheritanceStrategy>, // How supertype/subtype hierarchies become tables
+}
+
+/// Query parameters of the enterprise export
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct EnterpriseExportQuery {
+    /// odcs (default), drawio, mermaid, or summary (what would be merged, as JSON)
+    pub format: Option<String>,
+    /// ODCS variant (odcs_v3_1_0, odcl_v3_legacy, datacontract, simple)
+    pub odcs_format: Option<String>,
 }
 
 // Legacy routers removed - all export routes are now domain-scoped
@@ -36,7 +64,9 @@ pub struct ExportQuery {
     tag = "Export",
     params(
         ("domain" = String, Path, description = "Domain name"),
-        ("format" = String, Path, description = "Export format: json_schema, avro, protobuf, sql, odcl, png")
+        ("format" = String, Path, description = "Export format: json_schema, avro, protobuf, sql, sql-queries, prisma, docs, dbt_sources, mermaid, dbml, odcl, odps, png"),
+        ("environment" = Option<String>, Query, description = "Environment profile whose catalog/schema names are substituted"),
+        ("inheritance" = Option<InheritanceStrategy>, Query, description = "How supertype/subtype hierarchies become tables: single_table, class_table or concrete_table; kept as modeled if omitted")
     ),
     responses(
         (status = 200, description = "Model exported successfully", content_type = "application/octet-stream"),
@@ -54,12 +84,51 @@ pub async fn domain_export_format(
     headers: HeaderMap,
     Path(path): Path<super::workspace::DomainExportPath>,
     Query(query): Query<ExportQuery>,
+    role: Option<Extension<DomainRole>>,
 ) -> Result<Response<Body>, StatusCode> {
     // Ensure domain is loaded before exporting
-    let _ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let request = super::exports::export_request(&state, &ctx, &path.format, &query).await;
+    let response = if path.format == "odps" {
+        // ODPS combines the domain's data product definition with its tables
+        export_odps(&state, &ctx.user_context, &path.domain).await?
+    } else {
+        let transforms = resolve_export_transforms(&ctx, &path.domain, &query)?;
+        // Delegate to the existing export handler logic
+        export_format(State(state), Path(path.format), Query(query), transforms).await?
+    };
+    Ok(super::exports::record_domain_export(
+        &ctx.user_context.email,
+        &path.domain,
+        role.as_ref(),
+        request,
+        response,
+    ))
+}
 
-    // Delegate to the existing export handler logic
-    export_format(State(state), Path(path.format), Query(query)).await
+/// Export a domain as an ODPS data product document
+async fn export_odps(
+    state: &AppState,
+    user_context: &UserContext,
+    domain: &str,
+) -> Result<Response<Body>, StatusCode> {
+    let models = super::workspace::load_workspace_models(state, user_context).await?;
+    let model = models
+        .iter()
+        .find(|m| m.name == domain)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    let workspace_tables: HashMap<Uuid, &Table> = models
+        .iter()
+        .flat_map(|m| m.tables.iter().map(|t| (t.id, t)))
+        .collect();
+    let product = super::data_product::load_data_product(&user_context.email, domain)?;
+    let yaml = OdpsExporter::export_product(model, &product, &workspace_tables)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    streamed_response(
+        "application/x-yaml",
+        &format!("{}.odps.yaml", domain),
+        Body::from(yaml),
+    )
 }
 
 /// GET /workspace/domains/{domain}/export/all - Export domain model to all formats as ZIP (domain-scoped)
@@ -85,13 +154,191 @@ pub async fn domain_export_all(
     headers: HeaderMap,
     Path(domain_path): Path<super::workspace::DomainPath>,
     Query(query): Query<ExportQuery>,
+    role: Option<Extension<DomainRole>>,
+    cancellation: Cancellation,
 ) -> Result<Response<Body>, StatusCode> {
     // Ensure domain is loaded before exporting
-    let _ctx =
-        super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    let request = super::exports::export_request(&state, &ctx, "all", &query).await;
+    let transforms = resolve_export_transforms(&ctx, &domain_path.domain, &query)?;
 
     // Delegate to the existing export handler logic
-    export_all(State(state), Query(query)).await
+    let response = export_all(State(state), Query(query), transforms, cancellation).await?;
+    Ok(super::exports::record_domain_export(
+        &ctx.user_context.email,
+        &domain_path.domain,
+        role.as_ref(),
+        request,
+        response,
+    ))
+}
+
+/// GET /workspace/export/enterprise - Export all domains merged into one model
+#[utoipa::path(
+    get,
+    path = "/workspace/export/enterprise",
+    tag = "Export",
+    params(EnterpriseExportQuery),
+    responses(
+        (status = 200, description = "Enterprise model as ODCS (JSON object of YAML documents by table), DrawIO XML or a Mermaid ER diagram; the summary format returns what was merged", body = crate::services::enterprise_model_service::EnterpriseModelSummary),
+        (status = 400, description = "Unknown format"),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn export_enterprise(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<EnterpriseExportQuery>,
+) -> Result<Response<Body>, StatusCode> {
+    let user_context = super::workspace::get_user_context(&state, &headers).await?;
+    let models = super::workspace::load_workspace_models(&state, &user_context).await?;
+    let (model, summary) = merge_domains("enterprise", &models);
+    info!(
+        "Enterprise export for {}: {} domains, {} tables, {} cross-domain and {} unresolved relationships",
+        user_context.email,
+        summary.domains.len(),
+        summary.tables,
+        summary.cross_domain_relationships,
+        summary.unresolved_relationships
+    );
+
+    match query.format.as_deref().unwrap_or("odcs") {
+        "odcs" | "odcl" => {
+            let odcs_format = query
+                .odcs_format
+                .unwrap_or_else(|| "odcs_v3_1_0".to_string());
+            streamed_response(
+                "application/json",
+                "enterprise.odcl.json",
+                stream_body(move |out| {
+                    ExportService::write_odcl_json(&model, None, &odcs_format, out)
+                }),
+            )
+        }
+        "drawio" => {
+            let xml = DrawIOService::new(StdPath::new(""))
+                .export_to_drawio(&model)
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            streamed_response("application/xml", "enterprise.drawio", Body::from(xml))
+        }
+        "mermaid" => streamed_response(
+            "text/plain",
+            "enterprise.mmd",
+            Body::from(crate::export::mermaid::MermaidExporter::export_model(
+                &model, None,
+            )),
+        ),
+        "summary" => Ok(Json(summary).into_response()),
+        _ => Err(StatusCode::BAD_REQUEST),
+    }
+}
+
+/// Domain-level substitutions applied to the model before it is exported.
+#[derive(Default)]
+pub struct ExportTransforms {
+    /// Environment profile and ${variable} values for catalog/schema names
+    pub environment: Option<EnvironmentProfile>,
+    /// Physical naming templates for table names
+    pub naming: Option<NamingTemplates>,
+    /// Work item links shown alongside tables (e.g. in the docs export)
+    pub work_items: Option<WorkItemLinks>,
+    /// Domain description and owners shown in the docs export
+    pub domain: Option<DomainMetadata>,
+    /// Table READMEs rendered in the docs export
+    pub readmes: Option<TableReadmes>,
+    /// Latest observed table profiles rendered in the docs export
+    pub profiles: Option<TableProfiles>,
+    /// How supertype/subtype hierarchies are mapped to tables
+    pub inheritance: Option<InheritanceStrategy>,
+}
+
+impl ExportTransforms {
+    /// Return the transformed model, or None if nothing needs to change.
+    pub fn apply(&self, model: &DataModel) -> Option<DataModel> {
+        if self.environment.is_none()
+            && self.naming.is_none()
+            && self.work_items.is_none()
+            && self.domain.is_none()
+            && self.readmes.is_none()
+            && self.profiles.is_none()
+            && self.inheritance.is_none()
+        {
+            return None;
+        }
+        let mut model = match &self.environment {
+            Some(environment) => environment.apply_to_model(model),
+            None => model.clone(),
+        };
+        // Hierarchies reference tables by ID, so map them before anything renames tables
+        if let Some(strategy) = self.inheritance {
+            model = apply_inheritance(&model, strategy);
+        }
+        // READMEs are keyed by logical table name, so attach them before naming templates
+        if let Some(readmes) = &self.readmes {
+            model = readmes.apply_to_model(&model);
+        }
+        if let Some(naming) = &self.naming {
+            model = naming.apply_to_model(&model);
+        }
+        if let Some(work_items) = &self.work_items {
+            model = work_items.apply_to_model(&model);
+        }
+        if let Some(domain) = &self.domain {
+            model = domain.apply_to_model(&model);
+        }
+        if let Some(profiles) = &self.profiles {
+            model = profiles.apply_to_model(&model);
+        }
+        Some(model)
+    }
+}
+
+/// Resolve the environment profile, domain variables, naming templates, work item links,
+/// domain metadata, table READMEs, table profiles and inheritance strategy for this export.
+fn resolve_export_transforms(
+    ctx: &super::workspace::DomainContext,
+    domain: &str,
+    query: &ExportQuery,
+) -> Result<ExportTransforms, StatusCode> {
+    let transforms = load_export_transforms(
+        &ctx.user_context.email,
+        domain,
+        query.environment.as_deref(),
+    )?;
+    Ok(ExportTransforms {
+        inheritance: query.inheritance,
+        ..transforms
+    })
+}
+
+/// Load the export transforms configured for a user's domain.
+pub fn load_export_transforms(
+    email: &str,
+    domain: &str,
+    environment: Option<&str>,
+) -> Result<ExportTransforms, StatusCode> {
+    let environment = super::environments::resolve_export_profile(email, domain, environment)?;
+    let naming =
+        Some(super::naming::load_naming_templates(email, domain)).filter(|n| !n.is_empty());
+    let work_items = Some(super::work_items::load_work_item_links(email, domain))
+        .filter(|w| !w.links.is_empty());
+    let metadata =
+        Some(super::domain_metadata::load_domain_metadata(email, domain)).filter(|m| !m.is_empty());
+    let readmes =
+        Some(super::table_readme::load_table_readmes(email, domain)).filter(|r| !r.is_empty());
+    let profiles =
+        Some(super::table_profiles::load_table_profiles(email, domain)).filter(|p| !p.is_empty());
+    Ok(ExportTransforms {
+        environment,
+        naming,
+        work_items,
+        domain: metadata,
+        readmes,
+        profiles,
+        inheritance: None,
+    })
 }
 
 /// GET /export/:format - Export model to specified format
@@ -100,7 +347,8 @@ pub async fn domain_export_all(
     path = "/models/export/{format}",
     tag = "Export",
     params(
-        ("format" = String, Path, description = "Export format: json_schema, avro, protobuf, sql, odcl, png")
+        ("format" = String, Path, description = "Export format: json_schema, avro, protobuf, sql, sql-queries, prisma, docs, dbt_sources, mermaid, dbml, odcl, png"),
+        ("environment" = Option<String>, Query, description = "Environment profile whose catalog/schema names are substituted")
     ),
     responses(
         (status = 200, description = "Model exported successfully", content_type = "application/octet-stream"),
@@ -114,6 +362,7 @@ async fn export_format(
     State(state): State<AppState>,
     Path(format): Path<String>,
     Query(query): Query<ExportQuery>,
+    transforms: ExportTransforms,
 ) -> Result<Response<Body>, StatusCode> {
     let model_service = state.model_service.lock().await;
 
@@ -122,6 +371,11 @@ async fn export_format(
         None => return Err(StatusCode::NOT_FOUND),
     };
 
+    // Substitute environment-specific catalog/schema names, ${variable} placeholders
+    // and physical table names
+    let transformed_model = transforms.apply(model);
+    let model = transformed_model.as_ref().unwrap_or(model);
+
     // Parse table IDs if provided
     let table_ids: Option<Vec<Uuid>> = query.table_ids.as_ref().map(|ids| {
         ids.iter()
@@ -131,85 +385,68 @@ async fn export_format(
 
     let table_ids_slice = table_ids.as_deref();
 
-    // Export based on format
-    let (content, content_type, filename) = match format.as_str() {
-        "json_schema" => {
-            let json = ExportService::export_json_schema(model, table_ids_slice);
-            let content = serde_json::to_string_pretty(&json)
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            (content, "application/json", format!("{}.json", model.name))
-        }
-        "avro" => {
-            let json = ExportService::export_avro(model, table_ids_slice);
-            let content = serde_json::to_string_pretty(&json)
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            (content, "application/json", format!("{}.avsc", model.name))
-        }
-        "protobuf" => {
-            let content = ExportService::export_protobuf(model, table_ids_slice);
-            (
-                content,
-                "application/x-protobuf",
-                format!("{}.proto", model.name),
-            )
-        }
+    // SQL and multi-table ODCS exports are written table by table into a streamed body
+    let odcl_format = query.format.as_deref().unwrap_or("odcs_v3_1_0").to_string();
+    let exported_tables = model
+        .tables
+        .iter()
+        .filter(|t| table_ids_slice.is_none_or(|ids| ids.contains(&t.id)))
+        .count();
+    match format.as_str() {
         "sql" => {
-            let content =
-                ExportService::export_sql(model, table_ids_slice, query.dialect.as_deref());
-            (content, "text/plain", format!("{}.sql", model.name))
+            let model = model.clone();
+            let dialect = query.dialect.clone();
+            return streamed_response(
+                "text/plain",
+                &format!("{}.sql", model.name),
+                stream_body(move |out| {
+                    ExportService::write_sql(&model, table_ids.as_deref(), dialect.as_deref(), out)
+                }),
+            );
         }
-        "odcl" => {
-            let format_type = query.format.as_deref().unwrap_or("odcs_v3_1_0");
-            let exports = ExportService::export_odcl(model, table_ids_slice, format_type);
-
-            // For single table, return YAML directly; for multiple, return JSON with all YAMLs
-            if exports.len() == 1 {
-                let (_, yaml) = exports.iter().next().unwrap();
-                (
-                    yaml.clone(),
-                    "application/x-yaml",
-                    format!("{}.yaml", model.name),
-                )
-            } else {
-                let json = json!(exports);
-                let content = serde_json::to_string_pretty(&json)
-                    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-                (
-                    content,
-                    "application/json",
-                    format!("{}.odcl.json", model.name),
-                )
-            }
-        }
-        "png" => {
-            let png_data = ExportService::export_png(model, 1920, 1080, table_ids_slice)
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            return Response::builder()
-                .status(StatusCode::OK)
-                .header(header::CONTENT_TYPE, HeaderValue::from_static("image/png"))
-                .header(
-                    header::CONTENT_DISPOSITION,
-                    HeaderValue::from_str(&format!("attachment; filename=\"{}.png\"", model.name))
-                        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
-                )
-                .body(Body::from(png_data))
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR);
+        "odcl" if exported_tables > 1 => {
+            let model = model.clone();
+            return streamed_response(
+                "application/json",
+                &format!("{}.odcl.json", model.name),
+                stream_body(move |out| {
+                    ExportService::write_odcl_json(&model, table_ids.as_deref(), &odcl_format, out)
+                }),
+            );
         }
-        _ => return Err(StatusCode::BAD_REQUEST),
-    };
+        _ => {}
+    }
+
+    // Render on the blocking pool without holding the model lock, so an abandoned request
+    // doesn't keep other requests waiting
+    let model = model.clone();
+    drop(model_service);
+    let exported = tokio::task::spawn_blocking(move || {
+        ExportService::export_format(
+            &model,
+            &format,
+            table_ids.as_deref(),
+            query.dialect.as_deref(),
+            query.format.as_deref(),
+        )
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+    .ok_or(StatusCode::BAD_REQUEST)?;
 
     Response::builder()
         .status(StatusCode::OK)
         .header(
             header::CONTENT_TYPE,
-            HeaderValue::from_str(content_type).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+            HeaderValue::from_static(exported.content_type),
         )
         .header(
             header::CONTENT_DISPOSITION,
-            HeaderValue::from_str(&format!("attachment; filename=\"{}\"", filename))
+            HeaderValue::from_str(&format!("attachment; filename=\"{}\"", exported.filename))
                 .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
         )
-        .body(Body::from(content))
+        .body(Body::from(exported.content))
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
 }
 
@@ -228,6 +465,8 @@ async fn export_format(
 async fn export_all(
     State(state): State<AppState>,
     Query(query): Query<ExportQuery>,
+    transforms: ExportTransforms,
+    cancellation: Cancellation,
 ) -> Result<Response<Body>, StatusCode> {
     let model_service = state.model_service.lock().await;
 
@@ -236,159 +475,220 @@ async fn export_all(
         None => return Err(StatusCode::NOT_FOUND),
     };
 
-    // Generate all export formats
-    let mut zip_data = Vec::new();
-    {
-        use std::io::Write;
-        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(&mut zip_data));
-        let options =
-            zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
-
-        // Export schemas (JSON Schema, AVRO, Protobuf) - individual files per table if schema_type is specified
-        if let Some(ref schema_type) = query.schema_type {
-            match schema_type.as_str() {
-                "json_schema" => {
-                    // Export individual JSON Schema files per table
-                    use crate::export::json_schema::JSONSchemaExporter;
-                    for table in &model.tables {
-                        let table_schema = JSONSchemaExporter::export_table(table);
-                        let schema_str = serde_json::to_string_pretty(&table_schema)
-                            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-                        zip.start_file(format!("schemas/{}.json", table.name), options)
-                            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-                        zip.write_all(schema_str.as_bytes())
-                            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-                    }
+    // Substitute environment-specific catalog/schema names, ${variable} placeholders
+    // and physical table names
+    let transformed_model = transforms.apply(model);
+    let model = transformed_model.as_ref().unwrap_or(model);
+
+    // Build the archive on the blocking pool, spooling large ones to disk, then stream it
+    let model = model.clone();
+    drop(model_service);
+    let schema_type = query.schema_type.clone();
+    let filename = format!("{}.zip", model.name);
+    let mut spool = tokio::task::spawn_blocking(move || {
+        let mut spool = tempfile::spooled_tempfile(ZIP_SPOOL_LIMIT);
+        // Stops part way if the request is abandoned
+        write_export_zip(
+            &model,
+            schema_type.as_deref(),
+            cancellation.writer(&mut spool),
+        )?;
+        spool
+            .seek(SeekFrom::Start(0))
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        Ok::<_, StatusCode>(spool)
+    })
+    .await
+    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)??;
+
+    streamed_response(
+        "application/zip",
+        &filename,
+        stream_body(move |out| std::io::copy(&mut spool, out).map(|_| ())),
+    )
+}
+
+/// Write every export format of the model into a zip archive.
+fn write_export_zip<W: std::io::Write + Seek>(
+    model: &DataModel,
+    schema_type: Option<&str>,
+    writer: W,
+) -> Result<(), StatusCode> {
+    use std::io::Write;
+    let mut zip = zip::ZipWriter::new(writer);
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    // Export schemas (JSON Schema, AVRO, Protobuf) - individual files per table if schema_type is specified
+    if let Some(schema_type) = schema_type {
+        match schema_type {
+            "json_schema" => {
+                // Export individual JSON Schema files per table
+                use crate::export::json_schema::JSONSchemaExporter;
+                for table in &model.tables {
+                    let table_schema = JSONSchemaExporter::export_table(table);
+                    let schema_str = serde_json::to_string_pretty(&table_schema)
+                        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                    zip.start_file(format!("schemas/{}.json", table.name), options)
+                        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                    zip.write_all(schema_str.as_bytes())
+                        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
                 }
-                "avro" => {
-                    // Export individual AVRO schema files per table
-                    use crate::export::avro::AvroExporter;
-                    for table in &model.tables {
-                        let table_schema = AvroExporter::export_table(table);
-                        let schema_str = serde_json::to_string_pretty(&table_schema)
-                            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-                        zip.start_file(format!("schemas/{}.avsc", table.name), options)
-                            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-                        zip.write_all(schema_str.as_bytes())
-                            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-                    }
+            }
+            "avro" => {
+                // Export individual AVRO schema files per table
+                use crate::export::avro::AvroExporter;
+                for table in &model.tables {
+                    let table_schema = AvroExporter::export_table(table);
+                    let schema_str = serde_json::to_string_pretty(&table_schema)
+                        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                    zip.start_file(format!("schemas/{}.avsc", table.name), options)
+                        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                    zip.write_all(schema_str.as_bytes())
+                        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
                 }
-                "protobuf" => {
-                    // Export individual Protobuf files per table
-                    for table in &model.tables {
-                        let mut proto = String::new();
-                        proto.push_str("syntax = \"proto3\";\n\n");
-                        proto.push_str("package com.datamodel;\n\n");
-                        // Use ExportService helper for protobuf export
-                        let mut table_proto = String::new();
-                        table_proto.push_str(&format!("message {} {{\n", table.name));
-                        for (idx, col) in table.columns.iter().enumerate() {
-                            table_proto.push_str(&format!(
-                                "  {} {} = {};\n",
-                                ExportService::map_to_protobuf_type(&col.data_type),
-                                col.name,
-                                idx + 1
-                            ));
-                        }
-                        table_proto.push_str("}\n");
-                        proto.push_str(&table_proto);
-                        zip.start_file(format!("schemas/{}.proto", table.name), options)
-                            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-                        zip.write_all(proto.as_bytes())
-                            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            }
+            "protobuf" => {
+                // Export individual Protobuf files per table
+                for table in &model.tables {
+                    let mut proto = String::new();
+                    proto.push_str("syntax = \"proto3\";\n\n");
+                    proto.push_str("package com.datamodel;\n\n");
+                    // Use ExportService helper for protobuf export
+                    let mut table_proto = String::new();
+                    table_proto.push_str(&format!("message {} {{\n", table.name));
+                    for (idx, col) in table.columns.iter().enumerate() {
+                        table_proto.push_str(&format!(
+                            "  {} {} = {};\n",
+                            ExportService::map_to_protobuf_type(&col.data_type),
+                            col.name,
+                            idx + 1
+                        ));
                     }
-                }
-                _ => {
-                    // Unknown schema type - skip schema export
+                    table_proto.push_str("}\n");
+                    proto.push_str(&table_proto);
+                    zip.start_file(format!("schemas/{}.proto", table.name), options)
+                        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+                    zip.write_all(proto.as_bytes())
+                        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
                 }
             }
-        } else {
-            // Default: export all schema formats as combined files (backward compatibility)
-            let json_schema = ExportService::export_json_schema(model, None);
-            let json_schema_str = serde_json::to_string_pretty(&json_schema)
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            zip.start_file("model.json_schema.json", options)
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            zip.write_all(json_schema_str.as_bytes())
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-            let avro = ExportService::export_avro(model, None);
-            let avro_str = serde_json::to_string_pretty(&avro)
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            zip.start_file("model.avsc", options)
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            zip.write_all(avro_str.as_bytes())
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-
-            let protobuf = ExportService::export_protobuf(model, None);
-            zip.start_file("model.proto", options)
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            zip.write_all(protobuf.as_bytes())
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            _ => {
+                // Unknown schema type - skip schema export
+            }
         }
+    } else {
+        // Default: export all schema formats as combined files (backward compatibility)
+        let json_schema = ExportService::export_json_schema(model, None);
+        let json_schema_str = serde_json::to_string_pretty(&json_schema)
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        zip.start_file("model.json_schema.json", options)
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        zip.write_all(json_schema_str.as_bytes())
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        // Export SQL
-        let sql = ExportService::export_sql(model, None, None);
-        zip.start_file("model.sql", options)
+        let avro = ExportService::export_avro(model, None);
+        let avro_str =
+            serde_json::to_string_pretty(&avro).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        zip.start_file("model.avsc", options)
             .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        zip.write_all(sql.as_bytes())
+        zip.write_all(avro_str.as_bytes())
             .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        // Export ODCL
-        let odcl_exports = ExportService::export_odcl(model, None, "odcs_v3_1_0");
-        for (table_name, yaml) in odcl_exports {
-            zip.start_file(format!("tables/{}.yaml", table_name), options)
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            zip.write_all(yaml.as_bytes())
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        }
+        let protobuf = ExportService::export_protobuf(model, None);
+        zip.start_file("model.proto", options)
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        zip.write_all(protobuf.as_bytes())
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
 
-        // Export DrawIO XML - three versions (conceptual, logical, physical)
-        let drawio_service = DrawIOService::new(StdPath::new(&model.git_directory_path));
-
-        use crate::models::enums::ModelingLevel;
-        for level in [
-            ModelingLevel::Conceptual,
-            ModelingLevel::Logical,
-            ModelingLevel::Physical,
-        ] {
-            let drawio_xml = drawio_service
-                .export_to_drawio_with_level(model, Some(level))
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            let level_name = match level {
-                ModelingLevel::Conceptual => "conceptual",
-                ModelingLevel::Logical => "logical",
-                ModelingLevel::Physical => "physical",
-            };
-            zip.start_file(format!("diagrams/diagram_{}.drawio", level_name), options)
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-            zip.write_all(drawio_xml.as_bytes())
-                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        }
+    // Export SQL
+    let sql = ExportService::export_sql(model, None, None);
+    zip.start_file("model.sql", options)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    zip.write_all(sql.as_bytes())
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
 
-        // Export PNG
-        let png_data = ExportService::export_png(model, 1920, 1080, None)
-            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        zip.start_file("diagram.png", options)
+    // Export Prisma schema
+    let prisma = crate::export::prisma::PrismaExporter::export_model(model, None, None);
+    zip.start_file("schema.prisma", options)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    zip.write_all(prisma.as_bytes())
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Export Markdown documentation
+    let docs = crate::export::markdown::MarkdownExporter::export_model(model, None);
+    zip.start_file("docs.md", options)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    zip.write_all(docs.as_bytes())
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Export dbt sources
+    let sources = crate::export::dbt::DbtExporter::export_sources(model, None);
+    zip.start_file("dbt/sources.yml", options)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    zip.write_all(sources.as_bytes())
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    // Export ODCL, one table at a time
+    for table in &model.tables {
+        let yaml = ExportService::export_odcl_table(model, table, "odcs_v3_1_0");
+        zip.start_file(format!("tables/{}.yaml", table.name), options)
             .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        zip.write_all(&png_data)
+        zip.write_all(yaml.as_bytes())
             .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
 
-        zip.finish()
+    // Export DrawIO XML - three versions (conceptual, logical, physical)
+    let drawio_service = DrawIOService::new(StdPath::new(&model.git_directory_path));
+
+    use crate::models::enums::ModelingLevel;
+    for level in [
+        ModelingLevel::Conceptual,
+        ModelingLevel::Logical,
+        ModelingLevel::Physical,
+    ] {
+        let drawio_xml = drawio_service
+            .export_to_drawio_with_level(model, Some(level))
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let level_name = match level {
+            ModelingLevel::Conceptual => "conceptual",
+            ModelingLevel::Logical => "logical",
+            ModelingLevel::Physical => "physical",
+        };
+        zip.start_file(format!("diagrams/diagram_{}.drawio", level_name), options)
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        zip.write_all(drawio_xml.as_bytes())
             .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
     }
 
+    // Export PNG
+    let png_data = ExportService::export_png(model, 1920, 1080, None)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    zip.start_file("diagram.png", options)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    zip.write_all(&png_data)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    zip.finish()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(())
+}
+
+/// Attachment response with a streamed body.
+fn streamed_response(
+    content_type: &'static str,
+    filename: &str,
+    body: Body,
+) -> Result<Response<Body>, StatusCode> {
     Response::builder()
         .status(StatusCode::OK)
-        .header(
-            header::CONTENT_TYPE,
-            HeaderValue::from_static("application/zip"),
-        )
+        .header(header::CONTENT_TYPE, HeaderValue::from_static(content_type))
         .header(
             header::CONTENT_DISPOSITION,
-            HeaderValue::from_str(&format!("attachment; filename=\"{}.zip\"", model.name))
+            HeaderValue::from_str(&format!("attachment; filename=\"{}\"", filename))
                 .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
         )
-        .body(Body::from(zip_data))
+        .body(body)
         .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
 }
@@ -0,0 +1,181 @@
+//! Naming template routes.
+//!
+//! Provides domain-scoped endpoints to configure physical naming templates and preview
+//! the resulting table names. Templates are stored as `naming.yaml` in the domain directory
+//! and applied to every domain export.
+
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+    routing::{get, post},
+};
+use std::path::PathBuf;
+
+use super::app_state::AppState;
+use super::workspace::{DomainPath, get_workspace_data_dir, workspace_dir_name};
+use crate::middleware::access_control::writers_only;
+use crate::services::naming_service::{NamePreview, NamingTemplates};
+
+/// Create the naming template router
+pub fn naming_router() -> Router<AppState> {
+    let router = Router::new()
+        .route("/", get(get_naming_templates).put(update_naming_templates))
+        .route("/preview", get(preview_naming_templates));
+    // Previewing unsaved templates changes nothing, so viewers may do it
+    writers_only(router).route("/preview", post(preview_candidate_naming_templates))
+}
+
+/// Get path to a domain's naming.yaml
+fn get_naming_path(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain)
+        .join("naming.yaml"))
+}
+
+/// Load a domain's naming templates, returning empty templates if none are stored.
+pub fn load_naming_templates(email: &str, domain: &str) -> NamingTemplates {
+    if let Ok(path) = get_naming_path(email, domain)
+        && path.exists()
+        && let Ok(content) = std::fs::read_to_string(&path)
+        && let Ok(templates) = serde_yaml::from_str(&content)
+    {
+        return templates;
+    }
+    NamingTemplates::default()
+}
+
+/// Save a domain's naming templates
+fn save_naming_templates(
+    email: &str,
+    domain: &str,
+    templates: &NamingTemplates,
+) -> Result<(), StatusCode> {
+    let path = get_naming_path(email, domain)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+    let yaml = serde_yaml::to_string(templates).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    std::fs::write(path, yaml).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(())
+}
+
+/// Preview templates against the currently loaded domain model
+async fn preview_with(
+    state: &AppState,
+    templates: &NamingTemplates,
+) -> Result<Json<Vec<NamePreview>>, StatusCode> {
+    let model_service = state.model_service.lock().await;
+    let model = model_service
+        .get_current_model()
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok(Json(templates.preview(model)))
+}
+
+/// GET /workspace/domains/{domain}/naming - Get the domain's naming templates
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/naming",
+    tag = "Naming",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Naming templates retrieved successfully", body = NamingTemplates),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_naming_templates(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+) -> Result<Json<NamingTemplates>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    Ok(Json(load_naming_templates(
+        &ctx.user_context.email,
+        &domain_path.domain,
+    )))
+}
+
+/// PUT /workspace/domains/{domain}/naming - Replace the domain's naming templates
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/naming",
+    tag = "Naming",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body = NamingTemplates,
+    responses(
+        (status = 200, description = "Naming templates saved successfully", body = NamingTemplates),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_naming_templates(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+    Json(templates): Json<NamingTemplates>,
+) -> Result<Json<NamingTemplates>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    save_naming_templates(&ctx.user_context.email, &domain_path.domain, &templates)?;
+    Ok(Json(templates))
+}
+
+/// GET /workspace/domains/{domain}/naming/preview - Preview physical names using the saved templates
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/naming/preview",
+    tag = "Naming",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Logical and physical table names", body = Vec<NamePreview>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Model not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn preview_naming_templates(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+) -> Result<Json<Vec<NamePreview>>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    let templates = load_naming_templates(&ctx.user_context.email, &domain_path.domain);
+    preview_with(&state, &templates).await
+}
+
+/// POST /workspace/domains/{domain}/naming/preview - Preview physical names for unsaved templates
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/naming/preview",
+    tag = "Naming",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body = NamingTemplates,
+    responses(
+        (status = 200, description = "Logical and physical table names", body = Vec<NamePreview>),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Model not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn preview_candidate_naming_templates(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+    Json(templates): Json<NamingTemplates>,
+) -> Result<Json<Vec<NamePreview>>, StatusCode> {
+    super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    preview_with(&state, &templates).await
+}
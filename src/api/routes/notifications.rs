@@ -0,0 +1,245 @@
+//! Notification preference routes.
+//!
+//! Lets users configure which model events they are notified about and through
+//! which channels (Slack webhook, email). Preferences are stored per user as
+//! `notifications.yaml` in the user's workspace directory. Slack webhooks must be on
+//! `hooks.slack.com` (or a host in `NOTIFICATION_WEBHOOK_HOSTS`), and email goes to the login
+//! email or another of the user's verified emails.
+
+use axum::{
+    Router,
+    extract::State,
+    http::StatusCode,
+    response::Json,
+    routing::{get, post},
+};
+use serde::Serialize;
+use std::path::PathBuf;
+use tracing::warn;
+use utoipa::ToSchema;
+
+use super::app_state::AppState;
+use super::auth::session_verified_emails;
+use super::auth_context::AuthContext;
+use super::domain_metadata::load_domain_metadata;
+use super::workspace::{
+    DomainContext, get_workspace_data_dir, sanitize_email_for_path, workspace_email,
+};
+use crate::services::notification_service::{
+    ModelEvent, NotificationEvent, NotificationPreferences, check_slack_webhook_url,
+};
+
+/// Create the notifications router
+pub fn notifications_router() -> Router<AppState> {
+    Router::new()
+        .route(
+            "/preferences",
+            get(get_notification_preferences).put(update_notification_preferences),
+        )
+        .route("/channels", get(list_notification_channels))
+        .route("/test", post(send_test_notification))
+}
+
+/// Response listing notification channels available on this server
+#[derive(Serialize, ToSchema)]
+pub struct NotificationChannelsResponse {
+    channels: Vec<String>,
+}
+
+/// Response for a test notification
+#[derive(Serialize, ToSchema)]
+pub struct TestNotificationResponse {
+    delivered: Vec<String>,
+}
+
+/// Get path to a user's notification preferences file
+fn get_preferences_path(email: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(sanitize_email_for_path(email))
+        .join("notifications.yaml"))
+}
+
+/// Load a user's notification preferences, returning defaults if none are stored.
+pub fn load_notification_preferences(email: &str) -> NotificationPreferences {
+    if let Ok(path) = get_preferences_path(email)
+        && path.exists()
+        && let Ok(content) = std::fs::read_to_string(&path)
+        && let Ok(preferences) = serde_yaml::from_str(&content)
+    {
+        return preferences;
+    }
+    NotificationPreferences::default()
+}
+
+/// Save a user's notification preferences
+fn save_notification_preferences(
+    email: &str,
+    preferences: &NotificationPreferences,
+) -> Result<(), StatusCode> {
+    let path = get_preferences_path(email)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+    let yaml = serde_yaml::to_string(preferences).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    std::fs::write(path, yaml).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(())
+}
+
+/// Notify a user of a model event according to their stored preferences.
+///
+/// Delivery happens in the background; failures are logged only.
+pub fn notify_user(state: &AppState, email: &str, event: ModelEvent) {
+    let preferences = load_notification_preferences(email);
+    if !preferences.is_subscribed(event.event) {
+        return;
+    }
+    state
+        .notification_service
+        .dispatch_in_background(email.to_string(), preferences, event);
+}
+
+/// Notify a domain's owners of a model event (the workspace owner if it declares none).
+pub fn notify_domain_owners(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+    event: ModelEvent,
+) {
+    let email = &ctx.user_context.email;
+    let mut owners = load_domain_metadata(email, domain).owners;
+    if owners.is_empty() {
+        owners.push(workspace_email(email));
+    }
+    owners.retain(|owner| owner.contains('@'));
+    owners.sort();
+    owners.dedup();
+    for owner in owners {
+        notify_user(state, &owner, event.clone());
+    }
+}
+
+/// GET /notifications/preferences - Get notification preferences for the current user
+#[utoipa::path(
+    get,
+    path = "/notifications/preferences",
+    tag = "Notifications",
+    responses(
+        (status = 200, description = "Notification preferences retrieved successfully", body = NotificationPreferences),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_notification_preferences(
+    auth: AuthContext,
+) -> Result<Json<NotificationPreferences>, StatusCode> {
+    Ok(Json(load_notification_preferences(&auth.email)))
+}
+
+/// PUT /notifications/preferences - Replace notification preferences for the current user
+#[utoipa::path(
+    put,
+    path = "/notifications/preferences",
+    tag = "Notifications",
+    request_body = NotificationPreferences,
+    responses(
+        (status = 200, description = "Notification preferences updated successfully", body = NotificationPreferences),
+        (status = 400, description = "Bad request - webhook URL not on an allowed host, or email not one of the user's verified emails"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_notification_preferences(
+    State(state): State<AppState>,
+    auth: AuthContext,
+    Json(preferences): Json<NotificationPreferences>,
+) -> Result<Json<NotificationPreferences>, StatusCode> {
+    if let Some(url) = preferences.slack_webhook_url.as_deref()
+        && let Err(e) = check_slack_webhook_url(url)
+    {
+        warn!("Rejected Slack webhook URL for {}: {}", auth.email, e);
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    if let Some(address) = preferences.email_address.as_deref()
+        && !address.eq_ignore_ascii_case(&auth.email)
+    {
+        let verified = match auth.session_id.as_deref() {
+            Some(session_id) => session_verified_emails(&state, session_id).await,
+            None => Vec::new(),
+        };
+        if !verified
+            .iter()
+            .any(|email| email.eq_ignore_ascii_case(address))
+        {
+            warn!(
+                "Rejected notification email {} for {}: not a verified email",
+                address, auth.email
+            );
+            return Err(StatusCode::BAD_REQUEST);
+        }
+    }
+
+    save_notification_preferences(&auth.email, &preferences)?;
+    Ok(Json(preferences))
+}
+
+/// GET /notifications/channels - List notification channels configured on this server
+#[utoipa::path(
+    get,
+    path = "/notifications/channels",
+    tag = "Notifications",
+    responses(
+        (status = 200, description = "Available channels", body = NotificationChannelsResponse),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_notification_channels(
+    State(state): State<AppState>,
+    _auth: AuthContext,
+) -> Json<NotificationChannelsResponse> {
+    Json(NotificationChannelsResponse {
+        channels: state
+            .notification_service
+            .available_channels()
+            .into_iter()
+            .map(String::from)
+            .collect(),
+    })
+}
+
+/// POST /notifications/test - Send a test notification using the current preferences
+#[utoipa::path(
+    post,
+    path = "/notifications/test",
+    tag = "Notifications",
+    responses(
+        (status = 200, description = "Test notification sent", body = TestNotificationResponse),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn send_test_notification(
+    State(state): State<AppState>,
+    auth: AuthContext,
+) -> Json<TestNotificationResponse> {
+    let mut preferences = load_notification_preferences(&auth.email);
+    let event = ModelEvent {
+        event: NotificationEvent::TablePublished,
+        domain: "test".to_string(),
+        actor_email: auth.email.clone(),
+        summary: "This is a test notification".to_string(),
+        table_names: Vec::new(),
+    };
+    // Force subscription so the test reaches every enabled channel
+    preferences.events = vec![event.event];
+    let delivered = state
+        .notification_service
+        .dispatch(&auth.email, &preferences, &event)
+        .await;
+    Json(TestNotificationResponse {
+        delivered: delivered.into_iter().map(String::from).collect(),
+    })
+}
@@ -33,7 +33,7 @@ pub async fn serve_openapi_json() -> Json<utoipa::openapi::OpenApi> {
 }
 
 /// GET /swagger - Serve a simple HTML page with link to OpenAPI spec
-pub async fn serve_swagger_html() -> Html<&'static str> {
+pub async fn serve_swagger_html() -> Html<String> {
     Html(
         r#"
 <!DOCTYPE html>
@@ -80,6 +80,10 @@ pub async fn serve_swagger_html() -> Html<&'static str> {
     </div>
 </body>
 </html>
-"#,
+"#
+        .replace(
+            "/api/v1/openapi.json",
+            &crate::middleware::base_path::prefixed("/api/v1/openapi.json"),
+        ),
     )
 }
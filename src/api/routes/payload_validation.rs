@@ -0,0 +1,129 @@
+//! Sample payload validation routes.
+//!
+//! Lets producers test events against a table's modeled contract before go-live: the
+//! payload is validated against the JSON Schema the JSON Schema export generates for the
+//! table, with errors reported per row and field.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::Serialize;
+use serde_json::Value;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::error::ApiError;
+use super::workspace::DomainTablePath;
+use crate::models::DataModel;
+use crate::services::export_service::ExportService;
+use crate::services::payload_validation_service::{
+    MAX_REPORTED_ERRORS, PayloadError, parse_payload, validate_document,
+};
+
+/// Result of validating a payload
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ValidatePayloadResponse {
+    pub table_id: Uuid,
+    pub valid: bool,
+    /// Documents in the payload
+    pub rows: usize,
+    pub valid_rows: usize,
+    /// Row and field level errors (at most 1000)
+    pub errors: Vec<PayloadError>,
+    /// Whether errors beyond the reported ones were dropped
+    pub truncated: bool,
+    /// The JSON Schema the payload was validated against
+    #[schema(value_type = Object)]
+    pub schema: Value,
+}
+
+/// POST /workspace/domains/{domain}/tables/{table_id}/validate-payload - Validate sample payloads
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/tables/{table_id}/validate-payload",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    request_body(content = String, description = "A JSON document, a JSON array of documents, or NDJSON (one document per line)", content_type = "application/json"),
+    responses(
+        (status = 200, description = "Validation result (invalid payloads are reported, not rejected)", body = ValidatePayloadResponse),
+        (status = 400, description = "Invalid table ID or empty payload"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 422, description = "Payload exceeds the import size limit", body = crate::api_types::ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn validate_payload(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+    body: String,
+) -> Result<Json<ValidatePayloadResponse>, ApiError> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    state.model_limits.check_import_size(body.len())?;
+
+    let table = match state.storage.as_ref() {
+        Some(storage) => storage
+            .get_table(ctx.domain_info.id, table_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        None => state
+            .model_service
+            .lock()
+            .await
+            .get_table(table_id)
+            .cloned(),
+    }
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    let table_name = table.name.clone();
+    let mut model = DataModel::new(path.domain.clone(), String::new(), String::new());
+    model.tables.push(table);
+    let schema = ExportService::export_json_schema(&model, Some(&[table_id]))["definitions"]
+        [&table_name]
+        .clone();
+
+    let documents = parse_payload(&body);
+    if documents.is_empty() {
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: "Payload contains no documents".to_string(),
+        });
+    }
+    let mut errors = Vec::new();
+    let mut valid_rows = 0;
+    for (row, document) in &documents {
+        let row_errors = match document {
+            Ok(document) => validate_document(&schema, *row, document),
+            Err(message) => vec![PayloadError {
+                row: *row,
+                field: None,
+                message: message.clone(),
+            }],
+        };
+        if row_errors.is_empty() {
+            valid_rows += 1;
+        }
+        errors.extend(row_errors);
+    }
+    let truncated = errors.len() > MAX_REPORTED_ERRORS;
+    errors.truncate(MAX_REPORTED_ERRORS);
+
+    Ok(Json(ValidatePayloadResponse {
+        table_id,
+        valid: valid_rows == documents.len(),
+        rows: documents.len(),
+        valid_rows,
+        errors,
+        truncated,
+        schema,
+    }))
+}
@@ -0,0 +1,159 @@
+//! Quality score routes.
+//!
+//! `GET /workspace/domains/{domain}/score` returns the domain's current quality score and its
+//! recorded history. Table and relationship changes record a new score through
+//! [`record_quality_score`].
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::warn;
+use utoipa::{IntoParams, ToSchema};
+
+use super::app_state::AppState;
+use super::column_references::load_domain_model;
+use super::error::ApiError;
+use super::relationship_rules::load_relationship_rules;
+use super::table_profiles::load_table_profiles;
+use super::workspace::{
+    DomainContext, DomainPath, ensure_domain_loaded, get_workspace_data_dir, workspace_dir_name,
+};
+use crate::models::DataModel;
+use crate::services::model_validation_service::{
+    validate_model, validate_profiles, validate_relationship_rules,
+};
+use crate::services::quality_score_service::{
+    QualityHistory, QualityScore, QualityScorePoint, compute_score, parse_window,
+};
+
+const DEFAULT_HISTORY_WINDOW: &str = "90d";
+
+/// Query parameters of the quality score
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct QualityScoreQuery {
+    /// How far back to return the history, e.g. `90d` or `12w` (default 90d)
+    pub history: Option<String>,
+}
+
+/// A domain's quality score and its history
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QualityScoreResponse {
+    pub domain: String,
+    pub current: QualityScore,
+    /// Scores recorded within the window, oldest first
+    pub history: Vec<QualityScorePoint>,
+    /// Change of the score since the first point of the window
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trend: Option<f64>,
+}
+
+fn get_domain_dir(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain))
+}
+
+/// Score the domain as it is now.
+async fn current_score(
+    state: &AppState,
+    ctx: &DomainContext,
+    domain: &str,
+) -> Result<QualityScore, StatusCode> {
+    let (tables, relationships) = load_domain_model(state, ctx).await?;
+    let mut model = DataModel::new(domain.to_string(), String::new(), String::new());
+    model.tables = tables;
+    model.relationships = relationships;
+
+    let email = &ctx.user_context.email;
+    let mut issues = validate_model(&model);
+    issues.extend(validate_relationship_rules(
+        &model,
+        &load_relationship_rules(email, domain),
+    ));
+    issues.extend(validate_profiles(
+        &model,
+        &load_table_profiles(email, domain),
+    ));
+    Ok(compute_score(&model.tables, &model.relationships, &issues))
+}
+
+/// Record the domain's score after a change.
+///
+/// The change has already been applied, so failures are logged rather than returned.
+pub async fn record_quality_score(state: &AppState, ctx: &DomainContext, domain: &str) {
+    let Ok(score) = current_score(state, ctx, domain).await else {
+        return;
+    };
+    let Ok(dir) = get_domain_dir(&ctx.user_context.email, domain) else {
+        return;
+    };
+    if let Err(e) = QualityHistory::new(&dir).record(&score) {
+        warn!("Failed to record quality score of domain {}: {}", domain, e);
+    }
+}
+
+/// GET /workspace/domains/{domain}/score - Quality score of a domain and its history
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/score",
+    tag = "Workspace",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        QualityScoreQuery
+    ),
+    responses(
+        (status = 200, description = "Current score with its components, and the scores recorded within the window", body = QualityScoreResponse),
+        (status = 400, description = "Invalid history window", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_quality_score(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    Query(query): Query<QualityScoreQuery>,
+) -> Result<Json<QualityScoreResponse>, ApiError> {
+    let window = parse_window(query.history.as_deref().unwrap_or(DEFAULT_HISTORY_WINDOW)).map_err(
+        |message| ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message,
+        },
+    )?;
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let current = current_score(&state, &ctx, &path.domain).await?;
+    let history = QualityHistory::new(&get_domain_dir(&ctx.user_context.email, &path.domain)?);
+
+    // Domains not changed since scoring was introduced start their history now
+    let recorded = history.read().map_err(|e| {
+        warn!("Failed to read quality scores: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if recorded.is_empty()
+        && let Err(e) = history.record(&current)
+    {
+        warn!("Failed to record quality score: {}", e);
+    }
+
+    let points = history.since(Utc::now() - window).map_err(|e| {
+        warn!("Failed to read quality scores: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let trend = points
+        .first()
+        .map(|first| ((current.score - first.score.score) * 10.0).round() / 10.0);
+    Ok(Json(QualityScoreResponse {
+        domain: path.domain,
+        current,
+        history: points,
+        trend,
+    }))
+}
@@ -0,0 +1,148 @@
+//! Relationship anchor routes.
+//!
+//! Pin where a relationship attaches to its tables: a side of the table box plus a column
+//! row or a fraction along the side. Anchors are part of the relationship's visual metadata,
+//! so the canvas and the DrawIO export draw the relationship at the same points.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::column_references::load_domain_model;
+use super::error::ApiError;
+use super::workspace::{DomainRelationshipPath, ensure_domain_loaded};
+use crate::models::relationship::{RelationshipAnchor, VisualMetadata};
+use crate::services::canvas_layout_service::CanvasLayoutService;
+
+/// Request body for setting a relationship's anchors
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateRelationshipAnchorsRequest {
+    /// Where the relationship leaves the source table; floats freely if omitted
+    #[serde(default)]
+    pub source_anchor: Option<RelationshipAnchor>,
+    /// Where the relationship enters the target table; floats freely if omitted
+    #[serde(default)]
+    pub target_anchor: Option<RelationshipAnchor>,
+}
+
+fn bad_request(message: String) -> ApiError {
+    ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message,
+    }
+}
+
+/// PUT /workspace/domains/{domain}/relationships/{relationship_id}/anchors - Set anchors
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/relationships/{relationship_id}/anchors",
+    tag = "Relationships",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("relationship_id" = String, Path, description = "Relationship UUID")
+    ),
+    request_body = UpdateRelationshipAnchorsRequest,
+    responses(
+        (status = 200, description = "Relationship with its new anchors", body = Object),
+        (status = 400, description = "Invalid relationship ID or anchor"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Relationship not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_relationship_anchors(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainRelationshipPath>,
+    Json(request): Json<UpdateRelationshipAnchorsRequest>,
+) -> Result<Json<Value>, ApiError> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let relationship_id =
+        Uuid::parse_str(&path.relationship_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+
+    let (tables, relationships) = load_domain_model(&state, &ctx).await?;
+    let mut relationship = relationships
+        .into_iter()
+        .find(|r| r.id == relationship_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+
+    // Column rows are checked against the tables the anchors sit on
+    let column_count = |table_id: Uuid| {
+        tables
+            .iter()
+            .find(|t| t.id == table_id)
+            .map_or(0, |t| t.columns.len())
+    };
+    for (end, anchor, table_id) in [
+        (
+            "source_anchor",
+            &request.source_anchor,
+            relationship.source_table_id,
+        ),
+        (
+            "target_anchor",
+            &request.target_anchor,
+            relationship.target_table_id,
+        ),
+    ] {
+        if let Some(anchor) = anchor {
+            anchor
+                .validate(column_count(table_id))
+                .map_err(|e| bad_request(format!("{}: {}", end, e)))?;
+        }
+    }
+
+    let mut visual = relationship
+        .visual_metadata
+        .take()
+        .unwrap_or_else(VisualMetadata::default);
+    visual.source_anchor = request.source_anchor;
+    visual.target_anchor = request.target_anchor;
+    relationship.visual_metadata = Some(visual.clone());
+    relationship.updated_at = chrono::Utc::now();
+
+    if let Some(storage) = ctx.storage(&state) {
+        let updated = storage
+            .update_relationship(relationship, None, &ctx.user_context)
+            .await
+            .map_err(|e| {
+                warn!("Failed to save anchors of {}: {}", relationship_id, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        return Ok(Json(serde_json::to_value(updated).unwrap_or(json!({}))));
+    }
+
+    // File mode keeps visual metadata in canvas-layout.yaml
+    let mut model_service = ctx.model_service(&state).await;
+    let model = model_service
+        .get_current_model_mut()
+        .ok_or(StatusCode::BAD_REQUEST)?;
+    if let Some(existing) = model
+        .relationships
+        .iter_mut()
+        .find(|r| r.id == relationship_id)
+    {
+        *existing = relationship.clone();
+    }
+    if !model.git_directory_path.is_empty() {
+        CanvasLayoutService::new(std::path::Path::new(&model.git_directory_path))
+            .update_relationship_routing(model, relationship_id, visual)
+            .map_err(|e| {
+                warn!("Failed to save anchors of {}: {}", relationship_id, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+    }
+
+    Ok(Json(
+        serde_json::to_value(relationship).unwrap_or(json!({})),
+    ))
+}
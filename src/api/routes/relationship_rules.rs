@@ -0,0 +1,342 @@
+//! Relationship rule routes.
+//!
+//! Domain-scoped endpoints to configure layer transition rules (e.g. "gold tables may only
+//! read from silver"), list existing relationships that break them and check a proposed
+//! relationship. Rules are stored as `relationship-rules.yaml` in the domain directory and
+//! enforced when domain relationships are created or their type changes; a request may set
+//! `bypass_rules` to proceed anyway, which is recorded in the request audit log.
+
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+    routing::{get, post},
+};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::json;
+use std::path::PathBuf;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::export_schedules::load_domain_model;
+use super::workspace::{DomainPath, get_workspace_data_dir, workspace_dir_name};
+use crate::middleware::access_control::writers_only;
+use crate::models::enums::RelationshipType;
+use crate::services::RequestAuditEntry;
+use crate::services::relationship_rule_service::{RelationshipRules, RuleViolation};
+
+/// Audit event written when a request bypasses relationship rules
+const BYPASS_EVENT: &str = "relationship_rule_bypass";
+
+/// Create the relationship rules router
+pub fn relationship_rules_router() -> Router<AppState> {
+    let router = Router::new()
+        .route(
+            "/",
+            get(get_relationship_rules).put(update_relationship_rules),
+        )
+        .route("/violations", get(list_rule_violations));
+    // Checking a proposed relationship changes nothing, so viewers may do it
+    writers_only(router).route("/check", post(check_relationship_rules))
+}
+
+/// Request body for checking a proposed relationship
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct RuleCheckRequest {
+    pub source_table_id: Uuid,
+    pub target_table_id: Uuid,
+    /// DataFlow, Dependency, ForeignKey or EtlTransformation
+    #[serde(default)]
+    pub relationship_type: Option<String>,
+}
+
+/// A relationship change to check against the rules.
+pub(crate) enum RelationshipChange {
+    Create {
+        source_table_id: Uuid,
+        target_table_id: Uuid,
+        relationship_type: Option<RelationshipType>,
+    },
+    /// Type change of an existing relationship
+    Update {
+        relationship_id: Uuid,
+        relationship_type: RelationshipType,
+    },
+}
+
+/// Get path to a domain's relationship-rules.yaml
+fn get_rules_path(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain)
+        .join("relationship-rules.yaml"))
+}
+
+/// Load a domain's relationship rules, returning no rules if none are stored.
+pub fn load_relationship_rules(email: &str, domain: &str) -> RelationshipRules {
+    if let Ok(path) = get_rules_path(email, domain)
+        && path.exists()
+        && let Ok(content) = std::fs::read_to_string(&path)
+        && let Ok(rules) = serde_yaml::from_str(&content)
+    {
+        return rules;
+    }
+    RelationshipRules::default()
+}
+
+/// Save a domain's relationship rules
+fn save_relationship_rules(
+    email: &str,
+    domain: &str,
+    rules: &RelationshipRules,
+) -> Result<(), StatusCode> {
+    let path = get_rules_path(email, domain)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+    let yaml = serde_yaml::to_string(rules).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    std::fs::write(path, yaml).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(())
+}
+
+/// Parse a relationship type name as used by the relationship endpoints.
+fn parse_relationship_type(value: &str) -> Option<RelationshipType> {
+    match value {
+        "DataFlow" => Some(RelationshipType::DataFlow),
+        "Dependency" => Some(RelationshipType::Dependency),
+        "ForeignKey" => Some(RelationshipType::ForeignKey),
+        "EtlTransformation" => Some(RelationshipType::EtlTransformation),
+        _ => None,
+    }
+}
+
+/// Check a relationship change against the domain's rules.
+///
+/// Violations are rejected with 422 unless `bypass` is set; bypasses are logged and
+/// recorded in the request audit log.
+pub(crate) async fn enforce_relationship_rules(
+    state: &AppState,
+    email: &str,
+    domain: &str,
+    change: RelationshipChange,
+    bypass: bool,
+) -> Result<(), StatusCode> {
+    let rules = load_relationship_rules(email, domain);
+    if rules.rules.is_empty() {
+        return Ok(());
+    }
+    let model = load_domain_model(state, email, domain).await.map_err(|e| {
+        warn!("Failed to load domain {} for rule check: {}", domain, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let (relationship_id, violations) = match change {
+        RelationshipChange::Create {
+            source_table_id,
+            target_table_id,
+            relationship_type,
+        } => (
+            None,
+            rules.check(&model, source_table_id, target_table_id, relationship_type),
+        ),
+        RelationshipChange::Update {
+            relationship_id,
+            relationship_type,
+        } => {
+            let Some(existing) = model.relationships.iter().find(|r| r.id == relationship_id)
+            else {
+                return Ok(());
+            };
+            (
+                Some(relationship_id),
+                rules.check(
+                    &model,
+                    existing.source_table_id,
+                    existing.target_table_id,
+                    Some(relationship_type),
+                ),
+            )
+        }
+    };
+    if violations.is_empty() {
+        return Ok(());
+    }
+    if !bypass {
+        return Err(StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    warn!(
+        "Relationship rules bypassed in domain {}: {}",
+        domain,
+        violations
+            .iter()
+            .map(|v| v.message.as_str())
+            .collect::<Vec<_>>()
+            .join("; ")
+    );
+    let log = state.request_audit.clone();
+    let entry = RequestAuditEntry {
+        id: Uuid::new_v4(),
+        timestamp: Utc::now(),
+        method: if relationship_id.is_some() {
+            "PUT"
+        } else {
+            "POST"
+        }
+        .to_string(),
+        path: format!("/workspace/domains/{}/relationships", domain),
+        status: 200,
+        duration_ms: 0,
+        user: Some(log.redaction.pseudonymize(email)),
+        domain: Some(domain.to_string()),
+        object_ids: relationship_id
+            .map(|id| id.to_string())
+            .into_iter()
+            .collect(),
+        body: Some(json!({ "violations": violations })),
+        body_omitted: None,
+        event: Some(BYPASS_EVENT.to_string()),
+    };
+    tokio::task::spawn_blocking(move || {
+        if let Err(e) = log.record(&entry) {
+            warn!("Failed to record relationship rule bypass: {}", e);
+        }
+    });
+    Ok(())
+}
+
+/// GET /workspace/domains/{domain}/relationship-rules - Get the domain's relationship rules
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/relationship-rules",
+    tag = "Relationship Rules",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Relationship rules retrieved successfully", body = RelationshipRules),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_relationship_rules(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+) -> Result<Json<RelationshipRules>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    Ok(Json(load_relationship_rules(
+        &ctx.user_context.email,
+        &domain_path.domain,
+    )))
+}
+
+/// PUT /workspace/domains/{domain}/relationship-rules - Replace the domain's relationship rules
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/relationship-rules",
+    tag = "Relationship Rules",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body = RelationshipRules,
+    responses(
+        (status = 200, description = "Relationship rules saved successfully", body = RelationshipRules),
+        (status = 400, description = "Bad request - rule without a name or unknown layer"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_relationship_rules(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+    Json(rules): Json<RelationshipRules>,
+) -> Result<Json<RelationshipRules>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    const LAYERS: [&str; 4] = ["bronze", "silver", "gold", "operational"];
+    let known = |layer: &String| LAYERS.iter().any(|l| l.eq_ignore_ascii_case(layer));
+    if rules.rules.iter().any(|rule| {
+        rule.name.trim().is_empty() || !known(&rule.layer) || !rule.may_read_from.iter().all(known)
+    }) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    save_relationship_rules(&ctx.user_context.email, &domain_path.domain, &rules)?;
+    Ok(Json(rules))
+}
+
+/// GET /workspace/domains/{domain}/relationship-rules/violations - Relationships breaking the rules
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/relationship-rules/violations",
+    tag = "Relationship Rules",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Existing relationships that break a rule", body = Vec<RuleViolation>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_rule_violations(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+) -> Result<Json<Vec<RuleViolation>>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    let email = &ctx.user_context.email;
+    let rules = load_relationship_rules(email, &domain_path.domain);
+    let model = load_domain_model(&state, email, &domain_path.domain)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(rules.check_model(&model)))
+}
+
+/// POST /workspace/domains/{domain}/relationship-rules/check - Check a proposed relationship
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/relationship-rules/check",
+    tag = "Relationship Rules",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body = RuleCheckRequest,
+    responses(
+        (status = 200, description = "Rules the relationship would break (empty if allowed)", body = Vec<RuleViolation>),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn check_relationship_rules(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+    Json(request): Json<RuleCheckRequest>,
+) -> Result<Json<Vec<RuleViolation>>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    let email = &ctx.user_context.email;
+    let rules = load_relationship_rules(email, &domain_path.domain);
+    let model = load_domain_model(&state, email, &domain_path.domain)
+        .await
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(
+        rules.check(
+            &model,
+            request.source_table_id,
+            request.target_table_id,
+            request
+                .relationship_type
+                .as_deref()
+                .and_then(parse_relationship_type),
+        ),
+    ))
+}
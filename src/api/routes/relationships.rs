@@ -52,6 +52,12 @@ pub struct CreateRelationshipRequest {
     pub etl_job_metadata: Option<Value>,
     #[serde(default)]
     pub relationship_type: Option<String>,
+    /// Role of the source table, e.g. "report" for `employee.manager_id -> employee.id`
+    #[serde(default)]
+    pub source_role: Option<String>,
+    /// Role of the target table, e.g. "manager"
+    #[serde(default)]
+    pub target_role: Option<String>,
 }
 
 /// Request to update a relationship
@@ -72,6 +78,12 @@ pub struct UpdateRelationshipRequest {
     pub relationship_type: Option<String>,
     #[serde(default)]
     pub notes: Option<String>,
+    /// New source role; an empty string clears it
+    #[serde(default)]
+    pub source_role: Option<String>,
+    /// New target role; an empty string clears it
+    #[serde(default)]
+    pub target_role: Option<String>,
 }
 
 /// Request to check for circular dependency
@@ -145,10 +157,14 @@ async fn create_relationship(
     };
 
     // Check for duplicate relationship BEFORE creating
-    let existing = model
-        .relationships
-        .iter()
-        .find(|r| r.source_table_id == source_table_id && r.target_table_id == target_table_id);
+    let existing = model.relationships.iter().find(|r| {
+        r.same_endpoints(
+            source_table_id,
+            target_table_id,
+            request.source_role.as_deref(),
+            request.target_role.as_deref(),
+        )
+    });
 
     if existing.is_some() {
         return Err(StatusCode::BAD_REQUEST); // Relationship already exists
@@ -207,7 +223,9 @@ async fn create_relationship(
         etl_job_metadata,
         relationship_type,
     ) {
-        Ok(relationship) => {
+        Ok(mut relationship) => {
+            relationship.source_role = request.source_role.clone();
+            relationship.target_role = request.target_role.clone();
             // Add relationship to the original model (service works on a clone)
             model.relationships.push(relationship.clone());
 
@@ -272,9 +290,8 @@ async fn update_relationship(
     // Parse optional fields
     // Handle cardinality: if field is provided, parse it (empty string = None to clear)
     // Note: We need to distinguish between "not provided" (None) and "provided as empty" (Some(None))
-    let cardinality: Option<Option<Cardinality>> = if request.cardinality.is_some() {
+    let cardinality: Option<Option<Cardinality>> = if let Some(card_value) = &request.cardinality {
         // Field was provided (even if empty string)
-        let card_value = request.cardinality.as_ref().unwrap();
         if card_value.is_empty() {
             warn!("Cardinality field provided as empty string - clearing cardinality");
             Some(None) // Empty string means clear cardinality
@@ -333,6 +350,8 @@ async fn update_relationship(
         cardinality, // Pass Option<Option<Cardinality>> directly
         source_optional,
         target_optional,
+        request.source_role.clone(),
+        request.target_role.clone(),
         foreign_key_details,
         etl_job_metadata,
         relationship_type,
@@ -617,11 +636,12 @@ async fn update_relationship_routing(
     };
 
     // Create visual metadata first (before moving payload fields)
-    let visual_metadata_to_save = VisualMetadata {
+    let mut visual_metadata_to_save = VisualMetadata {
         routing_waypoints: payload.routing_waypoints.clone(),
         label_position: payload.label_position.clone(),
         source_connection_point: payload.source_connection_point.clone(),
         target_connection_point: payload.target_connection_point.clone(),
+        ..Default::default()
     };
 
     let relationship_result = if let Some(relationship) = model
@@ -629,6 +649,11 @@ async fn update_relationship_routing(
         .iter_mut()
         .find(|r| r.id == relationship_uuid)
     {
+        // Routing updates keep the anchors
+        if let Some(existing) = relationship.visual_metadata.as_ref() {
+            visual_metadata_to_save.source_anchor = existing.source_anchor.clone();
+            visual_metadata_to_save.target_anchor = existing.target_anchor.clone();
+        }
         relationship.visual_metadata = Some(visual_metadata_to_save.clone());
         Ok(serde_json::to_value(relationship).unwrap_or(json!({})))
     } else {
@@ -0,0 +1,233 @@
+//! Raw relationships file routes.
+//!
+//! Download a domain's relationships as a `relationships.yaml` document, validate a hand
+//! edited file against the published JSON Schema (`GET /workspace/relationships-schema`)
+//! and upload it to replace the domain's relationships. Uploads that do not validate are
+//! rejected with every problem and its location.
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{Json, Response},
+};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashSet;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use super::app_state::AppState;
+use super::workspace::{
+    DomainContext, DomainPath, ensure_domain_loaded, ensure_domain_loaded_with_reload,
+};
+use crate::models::{Relationship, Table};
+use crate::services::git_service::GitService;
+use crate::services::relationships_file_service::{
+    RELATIONSHIPS_FILE, RelationshipsFileError, count_relationships, relationships_schema,
+    validate_relationships_file,
+};
+
+/// Result of validating a relationships file
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RelationshipsFileReport {
+    pub valid: bool,
+    /// Number of relationship entries in the file
+    pub relationships: usize,
+    pub errors: Vec<RelationshipsFileError>,
+}
+
+impl RelationshipsFileReport {
+    fn new(content: &str, tables: &[Table]) -> Self {
+        let table_ids: HashSet<_> = tables.iter().map(|t| t.id).collect();
+        let errors = validate_relationships_file(content, Some(&table_ids));
+        Self {
+            valid: errors.is_empty(),
+            relationships: count_relationships(content),
+            errors,
+        }
+    }
+}
+
+/// Tables and relationships of the loaded domain, and its directory in file mode
+async fn domain_model(
+    state: &AppState,
+    ctx: &DomainContext,
+) -> Result<(Vec<Table>, Vec<Relationship>, Option<std::path::PathBuf>), StatusCode> {
+    if let Some(storage) = state.storage.as_ref() {
+        let domain_id = ctx.domain_info.id;
+        let tables = storage
+            .get_tables(domain_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        let relationships = storage
+            .get_relationships(domain_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        return Ok((tables, relationships, None));
+    }
+    let model_service = state.model_service.lock().await;
+    let model = model_service
+        .get_current_model()
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok((
+        model.tables.clone(),
+        model.relationships.clone(),
+        Some(std::path::PathBuf::from(&model.git_directory_path)),
+    ))
+}
+
+/// GET /workspace/relationships-schema - JSON Schema of relationships.yaml
+#[utoipa::path(
+    get,
+    path = "/workspace/relationships-schema",
+    tag = "Relationships",
+    responses(
+        (status = 200, description = "JSON Schema (draft 2020-12) of relationships.yaml", body = Object)
+    )
+)]
+pub async fn get_relationships_schema() -> Json<Value> {
+    Json(relationships_schema())
+}
+
+/// GET /workspace/domains/{domain}/relationships-file - Download the domain's relationships.yaml
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/relationships-file",
+    tag = "Relationships",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "relationships.yaml of the domain", content_type = "application/yaml", body = String),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Domain not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn download_relationships_file(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+) -> Result<Response, StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let (tables, relationships, domain_dir) = domain_model(&state, &ctx).await?;
+
+    // In file mode the file on disk is served as is, comments and all
+    let content = match domain_dir
+        .and_then(|dir| std::fs::read_to_string(dir.join(RELATIONSHIPS_FILE)).ok())
+    {
+        Some(content) => content,
+        None => GitService::relationships_to_yaml(&relationships, &tables).map_err(|e| {
+            warn!("Failed to render relationships of {}: {}", path.domain, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?,
+    };
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/yaml"),
+        )
+        .header(
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_static("attachment; filename=\"relationships.yaml\""),
+        )
+        .body(Body::from(content))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// POST /workspace/domains/{domain}/relationships-file/validate - Validate a relationships.yaml
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/relationships-file/validate",
+    tag = "Relationships",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body(content = String, content_type = "application/yaml", description = "relationships.yaml content"),
+    responses(
+        (status = 200, description = "Validation report; table ids are checked against the domain", body = RelationshipsFileReport),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Domain not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn validate_relationships_upload(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    content: String,
+) -> Result<Json<RelationshipsFileReport>, StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let (tables, _, _) = domain_model(&state, &ctx).await?;
+    Ok(Json(RelationshipsFileReport::new(&content, &tables)))
+}
+
+/// PUT /workspace/domains/{domain}/relationships-file - Replace the domain's relationships
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/relationships-file",
+    tag = "Relationships",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body(content = String, content_type = "application/yaml", description = "relationships.yaml content"),
+    responses(
+        (status = 200, description = "Relationships replaced", body = RelationshipsFileReport),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Domain not found"),
+        (status = 422, description = "The file does not validate; nothing was changed", body = RelationshipsFileReport),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn upload_relationships_file(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    content: String,
+) -> Result<(StatusCode, Json<RelationshipsFileReport>), StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let (tables, existing, domain_dir) = domain_model(&state, &ctx).await?;
+    let report = RelationshipsFileReport::new(&content, &tables);
+    if !report.valid {
+        return Ok((StatusCode::UNPROCESSABLE_ENTITY, Json(report)));
+    }
+    let relationships = GitService::new()
+        .parse_relationships(&content)
+        .map_err(|_| StatusCode::UNPROCESSABLE_ENTITY)?;
+
+    match (state.storage.as_ref(), domain_dir) {
+        (Some(storage), _) => {
+            let domain_id = ctx.domain_info.id;
+            for relationship in existing {
+                storage
+                    .delete_relationship(domain_id, relationship.id, &ctx.user_context)
+                    .await
+                    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            }
+            for relationship in relationships {
+                storage
+                    .create_relationship(domain_id, relationship, &ctx.user_context)
+                    .await
+                    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            }
+        }
+        (None, Some(dir)) => {
+            std::fs::write(dir.join(RELATIONSHIPS_FILE), &content).map_err(|e| {
+                warn!("Failed to write relationships of {}: {}", path.domain, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+            ensure_domain_loaded_with_reload(&state, &headers, &path.domain, true).await?;
+        }
+        (None, None) => return Err(StatusCode::NOT_FOUND),
+    }
+    info!(
+        "{} replaced the relationships of {} ({} relationships)",
+        ctx.user_context.email, path.domain, report.relationships
+    );
+    Ok((StatusCode::OK, Json(report)))
+}
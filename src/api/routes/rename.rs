@@ -0,0 +1,506 @@
+//! Rename refactoring routes.
+//!
+//! Rename a table or a column and rewrite every reference to it: relationship foreign key
+//! details (including lineage relationships), column-level foreign keys, and references in
+//! other domains that import the table. Old names are kept as aliases (`Table::aliases` for
+//! tables, the domain's `aliases.yaml` for columns).
+//!
+//! In file mode the whole rename happens under the model lock, so no other request sees a
+//! half-renamed domain.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::path::PathBuf;
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::column_references::{DomainColumnPath, load_domain_imports};
+use super::events;
+use super::workspace::{
+    DomainContext, DomainTablePath, get_workspace_data_dir, serialize_table_with_database_type,
+    workspace_dir_name,
+};
+use crate::models::{Relationship, Table};
+use crate::services::event_log_service::ChangeEventType;
+use crate::services::git_service::GitService;
+use crate::services::rename_service::{AliasIndex, apply_column_rename, move_table_files};
+
+/// Request body for a rename
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct RenameRequest {
+    pub new_name: String,
+}
+
+/// Result of a rename
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RenameResponse {
+    pub old_name: String,
+    pub new_name: String,
+    /// The renamed table after the change
+    pub table: Value,
+    /// Tables whose columns or foreign keys were rewritten
+    pub updated_tables: Vec<Uuid>,
+    /// Relationships whose foreign key details were rewritten
+    pub updated_relationships: Vec<Uuid>,
+    /// Other domains in which references were rewritten
+    pub updated_domains: Vec<String>,
+}
+
+/// Trimmed new name, rejecting names that can't be used as a file name
+fn validate_new_name(name: &str) -> Result<String, StatusCode> {
+    let name = name.trim();
+    if name.is_empty()
+        || name.len() > 255
+        || name.contains(['/', '\\'])
+        || name.contains("..")
+        || name.chars().any(char::is_control)
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    Ok(name.to_string())
+}
+
+fn domain_dir(ctx: &DomainContext, domain: &str) -> Result<PathBuf, StatusCode> {
+    Ok(get_workspace_data_dir()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .join(workspace_dir_name(&ctx.user_context.email))
+        .join(domain))
+}
+
+fn record_aliases(dir: &std::path::Path, update: impl FnOnce(&mut AliasIndex)) {
+    let mut aliases = AliasIndex::load(dir);
+    update(&mut aliases);
+    if let Err(e) = aliases.save(dir) {
+        warn!("Failed to save aliases in {}: {}", dir.display(), e);
+    }
+}
+
+/// Write changed tables and relationships of a file-mode domain back to YAML
+fn save_file_domain(
+    git_directory: &std::path::Path,
+    tables: &[Table],
+    relationships: &[Relationship],
+    changed_tables: &[Uuid],
+    save_relationships: bool,
+) -> Result<(), StatusCode> {
+    let mut git_service = GitService::new();
+    git_service
+        .set_git_directory_path(git_directory)
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    for table in tables.iter().filter(|t| changed_tables.contains(&t.id)) {
+        git_service.save_table_to_yaml(table).map_err(|e| {
+            warn!("Failed to save table {}: {}", table.name, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    }
+    if save_relationships {
+        git_service
+            .save_relationships_to_yaml(relationships, tables)
+            .map_err(|e| {
+                warn!("Failed to save relationships: {}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+    }
+    Ok(())
+}
+
+/// POST /workspace/domains/{domain}/tables/{table_id}/rename - Rename a table
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/tables/{table_id}/rename",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    request_body = RenameRequest,
+    responses(
+        (status = 200, description = "Table renamed; the old name is kept as an alias", body = RenameResponse),
+        (status = 400, description = "Invalid table ID or name"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 409, description = "Another table already has that name"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn rename_table(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+    Json(request): Json<RenameRequest>,
+) -> Result<Json<RenameResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let new_name = validate_new_name(&request.new_name)?;
+    let dir = domain_dir(&ctx, &path.domain)?;
+
+    let name_taken = |tables: &[Table]| {
+        tables
+            .iter()
+            .any(|t| t.id != table_id && t.name.eq_ignore_ascii_case(&new_name))
+    };
+
+    let (old_name, before, renamed) = if let Some(storage) = state.storage.as_ref() {
+        let tables = storage
+            .get_tables(ctx.domain_info.id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        if name_taken(&tables) {
+            return Err(StatusCode::CONFLICT);
+        }
+        let mut table = tables
+            .into_iter()
+            .find(|t| t.id == table_id)
+            .ok_or(StatusCode::NOT_FOUND)?;
+        let before = serialize_table_with_database_type(&table);
+        let old_name = table.name.clone();
+        table.rename(&new_name);
+        let renamed = storage
+            .update_table(table, None, &ctx.user_context)
+            .await
+            .map_err(|e| {
+                warn!("Failed to rename table {}: {}", table_id, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        (old_name, before, renamed)
+    } else {
+        let mut model_service = state.model_service.lock().await;
+        let model = model_service
+            .get_current_model()
+            .ok_or(StatusCode::NOT_FOUND)?;
+        if name_taken(&model.tables) {
+            return Err(StatusCode::CONFLICT);
+        }
+        let table = model
+            .get_table_by_id(table_id)
+            .ok_or(StatusCode::NOT_FOUND)?;
+        let old_name = table.name.clone();
+        let before = serialize_table_with_database_type(table);
+        let mut updated = table.clone();
+        updated.rename(&new_name);
+        let renamed = model_service
+            .update_table(
+                table_id,
+                &json!({ "name": new_name, "aliases": updated.aliases }),
+            )
+            .map_err(|e| {
+                warn!("Failed to rename table {}: {}", table_id, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?
+            .ok_or(StatusCode::NOT_FOUND)?;
+        if old_name != new_name {
+            move_table_files(&dir, &old_name, &new_name).map_err(|e| {
+                warn!("Failed to move files of table {}: {}", old_name, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+        }
+        // relationships.yaml carries table names for readability
+        if let Some(model) = model_service.get_current_model() {
+            save_file_domain(&dir, &model.tables, &model.relationships, &[], true)?;
+        }
+        (old_name, before, renamed)
+    };
+
+    let table = serialize_table_with_database_type(&renamed);
+    events::record_model_change(
+        &state,
+        &ctx,
+        &path.domain,
+        ChangeEventType::TableUpdated,
+        table_id,
+        Some(before),
+        Some(table.clone()),
+    )
+    .await;
+
+    Ok(Json(RenameResponse {
+        old_name,
+        new_name,
+        table,
+        updated_tables: vec![table_id],
+        updated_relationships: Vec::new(),
+        updated_domains: Vec::new(),
+    }))
+}
+
+/// POST /workspace/domains/{domain}/columns/{table_id}/{column}/rename - Rename a column
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/columns/{table_id}/{column}/rename",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID"),
+        ("column" = String, Path, description = "Current column name")
+    ),
+    request_body = RenameRequest,
+    responses(
+        (status = 200, description = "Column renamed and references rewritten; the old name is kept as an alias", body = RenameResponse),
+        (status = 400, description = "Invalid table ID or name"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table or column not found"),
+        (status = 409, description = "The table already has a column with that name"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn rename_column(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainColumnPath>,
+    Json(request): Json<RenameRequest>,
+) -> Result<Json<RenameResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let new_name = validate_new_name(&request.new_name)?;
+    let dir = domain_dir(&ctx, &path.domain)?;
+
+    // Resolve the column and check the new name is free
+    let check = |tables: &[Table]| -> Result<String, StatusCode> {
+        let table = tables
+            .iter()
+            .find(|t| t.id == table_id)
+            .ok_or(StatusCode::NOT_FOUND)?;
+        let old_name = table
+            .columns
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(&path.column))
+            .map(|c| c.name.clone())
+            .ok_or(StatusCode::NOT_FOUND)?;
+        let taken = table.columns.iter().any(|c| {
+            !c.name.eq_ignore_ascii_case(&old_name) && c.name.eq_ignore_ascii_case(&new_name)
+        });
+        if taken {
+            return Err(StatusCode::CONFLICT);
+        }
+        Ok(old_name)
+    };
+
+    // Domains importing the table may hold relationships and foreign keys to its columns
+    let importing_domains: Vec<String> = load_domain_imports(&state, &ctx, &path.domain)
+        .await?
+        .into_iter()
+        .filter(|d| d.table_ids.contains(&table_id) || !d.relationship_ids.is_empty())
+        .map(|d| d.domain)
+        .collect();
+    let mut updated_domains = Vec::new();
+
+    let (old_name, before_tables, before_relationships, tables, relationships, changes) =
+        if let Some(storage) = state.storage.as_ref() {
+            let mut tables = storage
+                .get_tables(ctx.domain_info.id)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            let mut relationships = storage
+                .get_relationships(ctx.domain_info.id)
+                .await
+                .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            let old_name = check(&tables)?;
+            let (before_tables, before_relationships) = (tables.clone(), relationships.clone());
+            let changes = apply_column_rename(
+                &mut tables,
+                &mut relationships,
+                table_id,
+                &old_name,
+                &new_name,
+            );
+            for table in tables.iter().filter(|t| changes.tables.contains(&t.id)) {
+                storage
+                    .update_table(table.clone(), None, &ctx.user_context)
+                    .await
+                    .map_err(|e| {
+                        warn!("Failed to update table {}: {}", table.name, e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })?;
+            }
+            for relationship in relationships
+                .iter()
+                .filter(|r| changes.relationships.contains(&r.id))
+            {
+                storage
+                    .update_relationship(relationship.clone(), None, &ctx.user_context)
+                    .await
+                    .map_err(|e| {
+                        warn!("Failed to update relationship {}: {}", relationship.id, e);
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    })?;
+            }
+
+            for domain in importing_domains {
+                let Ok(Some(other)) = storage.get_domain_by_name(ctx.workspace.id, &domain).await
+                else {
+                    continue;
+                };
+                let (Ok(mut other_tables), Ok(mut other_relationships)) = (
+                    storage.get_tables(other.id).await,
+                    storage.get_relationships(other.id).await,
+                ) else {
+                    warn!("Failed to load domain {} for column rename", domain);
+                    continue;
+                };
+                let other_changes = apply_column_rename(
+                    &mut other_tables,
+                    &mut other_relationships,
+                    table_id,
+                    &old_name,
+                    &new_name,
+                );
+                if other_changes.is_empty() {
+                    continue;
+                }
+                for table in other_tables
+                    .iter()
+                    .filter(|t| other_changes.tables.contains(&t.id))
+                {
+                    if let Err(e) = storage
+                        .update_table(table.clone(), None, &ctx.user_context)
+                        .await
+                    {
+                        warn!("Failed to update table {} in {}: {}", table.name, domain, e);
+                    }
+                }
+                for relationship in other_relationships
+                    .iter()
+                    .filter(|r| other_changes.relationships.contains(&r.id))
+                {
+                    if let Err(e) = storage
+                        .update_relationship(relationship.clone(), None, &ctx.user_context)
+                        .await
+                    {
+                        warn!(
+                            "Failed to update relationship {} in {}: {}",
+                            relationship.id, domain, e
+                        );
+                    }
+                }
+                updated_domains.push(domain);
+            }
+            (
+                old_name,
+                before_tables,
+                before_relationships,
+                tables,
+                relationships,
+                changes,
+            )
+        } else {
+            let mut model_service = state.model_service.lock().await;
+            let model = model_service
+                .get_current_model_mut()
+                .ok_or(StatusCode::NOT_FOUND)?;
+            let old_name = check(&model.tables)?;
+            let (before_tables, before_relationships) =
+                (model.tables.clone(), model.relationships.clone());
+            let changes = apply_column_rename(
+                &mut model.tables,
+                &mut model.relationships,
+                table_id,
+                &old_name,
+                &new_name,
+            );
+            save_file_domain(
+                &dir,
+                &model.tables,
+                &model.relationships,
+                &changes.tables,
+                !changes.relationships.is_empty(),
+            )?;
+
+            let user_workspace = dir.parent().map(PathBuf::from).unwrap_or_default();
+            for domain in importing_domains {
+                let other_dir = user_workspace.join(&domain);
+                let Ok((mut other, _)) = GitService::new().map_git_directory(&other_dir) else {
+                    warn!("Failed to load domain {} for column rename", domain);
+                    continue;
+                };
+                let other_changes = apply_column_rename(
+                    &mut other.tables,
+                    &mut other.relationships,
+                    table_id,
+                    &old_name,
+                    &new_name,
+                );
+                if other_changes.is_empty() {
+                    continue;
+                }
+                save_file_domain(
+                    &other_dir,
+                    &other.tables,
+                    &other.relationships,
+                    &other_changes.tables,
+                    !other_changes.relationships.is_empty(),
+                )?;
+                updated_domains.push(domain);
+            }
+            (
+                old_name,
+                before_tables,
+                before_relationships,
+                model.tables.clone(),
+                model.relationships.clone(),
+                changes,
+            )
+        };
+
+    record_aliases(&dir, |aliases| {
+        aliases.record_column_rename(table_id, &old_name, &new_name)
+    });
+
+    for table in tables.iter().filter(|t| changes.tables.contains(&t.id)) {
+        let before = before_tables
+            .iter()
+            .find(|t| t.id == table.id)
+            .map(serialize_table_with_database_type);
+        events::record_model_change(
+            &state,
+            &ctx,
+            &path.domain,
+            ChangeEventType::TableUpdated,
+            table.id,
+            before,
+            Some(serialize_table_with_database_type(table)),
+        )
+        .await;
+    }
+    for relationship in relationships
+        .iter()
+        .filter(|r| changes.relationships.contains(&r.id))
+    {
+        let before = before_relationships
+            .iter()
+            .find(|r| r.id == relationship.id)
+            .and_then(|r| serde_json::to_value(r).ok());
+        events::record_model_change(
+            &state,
+            &ctx,
+            &path.domain,
+            ChangeEventType::RelationshipUpdated,
+            relationship.id,
+            before,
+            serde_json::to_value(relationship).ok(),
+        )
+        .await;
+    }
+
+    let table = tables
+        .iter()
+        .find(|t| t.id == table_id)
+        .map(serialize_table_with_database_type)
+        .unwrap_or(Value::Null);
+    Ok(Json(RenameResponse {
+        old_name,
+        new_name,
+        table,
+        updated_tables: changes.tables,
+        updated_relationships: changes.relationships,
+        updated_domains,
+    }))
+}
@@ -0,0 +1,189 @@
+//! Retention and archival routes.
+//!
+//! Get and declare a table's retention policy (period, retention class, archival target,
+//! legal hold), and export the domain's purge and archival plan, grouping its tables by
+//! retention class.
+
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{Json, Response},
+};
+use serde::Serialize;
+use std::path::PathBuf;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::column_references::load_domain_model;
+use super::domain_metadata::is_domain_owner;
+use super::error::ApiError;
+use super::workspace::{
+    DomainContext, DomainPath, DomainTablePath, ensure_domain_loaded, get_workspace_data_dir,
+    workspace_dir_name,
+};
+use crate::services::retention_service::{RetentionPolicies, RetentionPolicy, purge_plan};
+
+/// A table's retention policy
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RetentionPolicyResponse {
+    pub table_id: Uuid,
+    pub policy: RetentionPolicy,
+}
+
+fn get_domain_dir(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain))
+}
+
+/// Check the table exists in the loaded domain
+async fn ensure_table(
+    state: &AppState,
+    ctx: &DomainContext,
+    table_id: &str,
+) -> Result<Uuid, StatusCode> {
+    let table_id = Uuid::parse_str(table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let (tables, _) = load_domain_model(state, ctx).await?;
+    if !tables.iter().any(|t| t.id == table_id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(table_id)
+}
+
+/// GET /workspace/domains/{domain}/tables/{table_id}/retention - Get a table's retention policy
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/tables/{table_id}/retention",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    responses(
+        (status = 200, description = "The table's retention policy (empty if not declared)", body = RetentionPolicyResponse),
+        (status = 400, description = "Invalid table ID"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_retention_policy(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+) -> Result<Json<RetentionPolicyResponse>, StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = ensure_table(&state, &ctx, &path.table_id).await?;
+    let dir = get_domain_dir(&ctx.user_context.email, &path.domain)?;
+
+    Ok(Json(RetentionPolicyResponse {
+        table_id,
+        policy: RetentionPolicies::load(&dir).get(table_id),
+    }))
+}
+
+/// PUT /workspace/domains/{domain}/tables/{table_id}/retention - Declare a table's retention policy
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/tables/{table_id}/retention",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    request_body = RetentionPolicy,
+    responses(
+        (status = 200, description = "Policy declared; an empty policy removes it", body = RetentionPolicyResponse),
+        (status = 400, description = "Invalid table ID, zero-day period or empty class or archival target", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Only domain owners can change the policy"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_retention_policy(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+    Json(policy): Json<RetentionPolicy>,
+) -> Result<Json<RetentionPolicyResponse>, ApiError> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = ensure_table(&state, &ctx, &path.table_id).await?;
+    if !is_domain_owner(&ctx, &path.domain) {
+        return Err(StatusCode::FORBIDDEN.into());
+    }
+    policy.validate().map_err(|message| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message,
+    })?;
+
+    let dir = get_domain_dir(&ctx.user_context.email, &path.domain)?;
+    let mut policies = RetentionPolicies::load(&dir);
+    policies.set(table_id, policy.clone());
+    policies.save(&dir).map_err(|e| {
+        warn!("Failed to save retention policies: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    info!(
+        "{} set the retention policy of table {} (legal hold: {})",
+        ctx.user_context.email, table_id, policy.legal_hold
+    );
+
+    Ok(Json(RetentionPolicyResponse { table_id, policy }))
+}
+
+/// GET /workspace/domains/{domain}/retention/purge-plan - Export the purge and archival plan
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/retention/purge-plan",
+    tag = "Export",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "The domain's tables grouped by retention class, with the archive, purge, hold or retain action of each", content_type = "application/x-yaml"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn export_purge_plan(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+) -> Result<Response<Body>, StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let (tables, _) = load_domain_model(&state, &ctx).await?;
+    let dir = get_domain_dir(&ctx.user_context.email, &path.domain)?;
+    let plan = purge_plan(
+        &path.domain,
+        &tables,
+        &RetentionPolicies::load(&dir),
+        chrono::Utc::now(),
+    );
+    let yaml = serde_yaml::to_string(&plan).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/x-yaml"),
+        )
+        .header(
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_str(&format!(
+                "attachment; filename=\"{}.purge-plan.yaml\"",
+                path.domain
+            ))
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        )
+        .body(Body::from(yaml))
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
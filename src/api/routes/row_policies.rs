@@ -0,0 +1,160 @@
+//! Row-level security policy routes.
+//!
+//! Get and replace a table's row access policies. They are stored on the table (as
+//! `odcl_metadata.rowAccessPolicies`), so they are versioned, exported and synced with it,
+//! and the SQL export renders them as PostgreSQL policies or Snowflake row access policies.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::error::ApiError;
+use super::evolution_policy::PolicyOverrideQuery;
+use super::workspace::{DomainContext, DomainTablePath};
+use crate::models::table::ROW_ACCESS_POLICIES_KEY;
+use crate::models::{RowAccessPolicy, Table};
+use crate::services::model_validation_service::row_access_policy_problems;
+
+/// A table's row access policies
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RowAccessPoliciesResponse {
+    pub table_id: Uuid,
+    pub table_name: String,
+    pub policies: Vec<RowAccessPolicy>,
+}
+
+/// Request body for replacing a table's row access policies
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateRowAccessPoliciesRequest {
+    /// The policies; an empty list removes them
+    pub policies: Vec<RowAccessPolicy>,
+}
+
+/// Find a table in the domain
+async fn find_table(
+    state: &AppState,
+    ctx: &DomainContext,
+    table_id: Uuid,
+) -> Result<Table, StatusCode> {
+    let table = match ctx.storage(state) {
+        Some(storage) => storage
+            .get_table(ctx.domain_info.id, table_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        None => ctx.model_service(state).await.get_table(table_id).cloned(),
+    };
+    table.ok_or(StatusCode::NOT_FOUND)
+}
+
+/// GET /workspace/domains/{domain}/tables/{table_id}/row-policies - Get a table's row access policies
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/tables/{table_id}/row-policies",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    responses(
+        (status = 200, description = "Row access policies of the table", body = RowAccessPoliciesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Malformed policies stored on the table")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_row_access_policies(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+) -> Result<Json<RowAccessPoliciesResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let table = find_table(&state, &ctx, table_id).await?;
+    let policies = table.row_access_policies().map_err(|e| {
+        warn!(
+            "Malformed row access policies on table {}: {}",
+            table.name, e
+        );
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(Json(RowAccessPoliciesResponse {
+        table_id,
+        table_name: table.name,
+        policies,
+    }))
+}
+
+/// PUT /workspace/domains/{domain}/tables/{table_id}/row-policies - Replace a table's row access policies
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/tables/{table_id}/row-policies",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    request_body = UpdateRowAccessPoliciesRequest,
+    responses(
+        (status = 200, description = "Row access policies saved", body = RowAccessPoliciesResponse),
+        (status = 400, description = "Empty or duplicate policy name, empty predicate or empty role", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_row_access_policies(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+    Json(request): Json<UpdateRowAccessPoliciesRequest>,
+) -> Result<Json<RowAccessPoliciesResponse>, ApiError> {
+    if let Some(problem) = row_access_policy_problems(&request.policies)
+        .into_iter()
+        .next()
+    {
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: problem,
+        });
+    }
+
+    // Save through the table update, so the change is versioned and recorded like any other
+    let policies = if request.policies.is_empty() {
+        serde_json::Value::Null
+    } else {
+        json!(request.policies)
+    };
+    let updates = json!({ "odcl_metadata": { ROW_ACCESS_POLICIES_KEY: policies } });
+    let Json(table) = super::workspace::update_domain_table(
+        State(state),
+        headers,
+        Path(path),
+        Query(PolicyOverrideQuery::default()),
+        Json(updates),
+    )
+    .await?;
+
+    Ok(Json(RowAccessPoliciesResponse {
+        table_id: table
+            .get("id")
+            .and_then(|id| id.as_str())
+            .and_then(|id| Uuid::parse_str(id).ok())
+            .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?,
+        table_name: table
+            .get("name")
+            .and_then(|name| name.as_str())
+            .unwrap_or_default()
+            .to_string(),
+        policies: request.policies,
+    }))
+}
@@ -0,0 +1,314 @@
+//! Saved filter routes.
+//!
+//! Domain-scoped CRUD for named table filters. Filters are stored in the domain's
+//! `saved-filters.yaml` and applied to `GET /tables` and `GET /canvas` of the domain with the
+//! `filter` query parameter.
+
+use axum::{
+    Router,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+    routing::get,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use std::path::PathBuf;
+use tracing::info;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::error::ApiError;
+use super::workspace::{DomainPath, get_workspace_data_dir, workspace_dir_name};
+use crate::services::saved_filter_service::{
+    FilterCriteria, SAVED_FILTERS_FILE, SavedFilter, SavedFilters,
+};
+
+/// Request body for creating or replacing a saved filter
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SavedFilterRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub criteria: FilterCriteria,
+}
+
+/// Query parameter applying a saved filter to a table list or canvas
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct SavedFilterQuery {
+    /// ID of a saved filter of the domain
+    pub filter: Option<Uuid>,
+}
+
+/// Path parameters for a single saved filter
+#[derive(Deserialize)]
+pub struct SavedFilterPath {
+    pub domain: String,
+    pub filter_id: Uuid,
+}
+
+/// Create the saved filter router
+pub fn saved_filters_router() -> Router<AppState> {
+    Router::new()
+        .route("/", get(list_saved_filters).post(create_saved_filter))
+        .route(
+            "/{filter_id}",
+            get(get_saved_filter)
+                .put(update_saved_filter)
+                .delete(delete_saved_filter),
+        )
+}
+
+/// Get path to a domain's saved-filters.yaml
+fn get_saved_filters_path(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain)
+        .join(SAVED_FILTERS_FILE))
+}
+
+/// Load a domain's saved filters, returning none if none are stored.
+pub(crate) fn load_saved_filters(email: &str, domain: &str) -> SavedFilters {
+    if let Ok(path) = get_saved_filters_path(email, domain)
+        && let Ok(content) = std::fs::read_to_string(&path)
+        && let Ok(filters) = serde_yaml::from_str(&content)
+    {
+        return filters;
+    }
+    SavedFilters::default()
+}
+
+/// Save a domain's saved filters
+fn save_saved_filters(email: &str, domain: &str, filters: &SavedFilters) -> Result<(), StatusCode> {
+    let path = get_saved_filters_path(email, domain)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+    let yaml = serde_yaml::to_string(filters).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    std::fs::write(path, yaml).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Criteria of the saved filter requested by `query`, if any.
+///
+/// Returns NOT_FOUND if the domain has no filter with that id.
+pub(crate) fn resolve_saved_filter(
+    email: &str,
+    domain: &str,
+    query: &SavedFilterQuery,
+) -> Result<Option<FilterCriteria>, StatusCode> {
+    let Some(id) = query.filter else {
+        return Ok(None);
+    };
+    load_saved_filters(email, domain)
+        .get(id)
+        .map(|f| Some(f.criteria.clone()))
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Check a request's name and criteria against the domain's other filters.
+fn validate_request(
+    filters: &SavedFilters,
+    request: &SavedFilterRequest,
+    id: Option<Uuid>,
+) -> Result<(), ApiError> {
+    if request.name.trim().is_empty() {
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: "Filter name is required".to_string(),
+        });
+    }
+    request.criteria.validate().map_err(|e| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message: e.to_string(),
+    })?;
+    if filters.name_taken(&request.name, id) {
+        return Err(ApiError {
+            status: StatusCode::CONFLICT,
+            message: format!("A filter named '{}' already exists", request.name.trim()),
+        });
+    }
+    Ok(())
+}
+
+/// GET /workspace/domains/{domain}/saved-filters - List saved filters
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/saved-filters",
+    tag = "Saved Filters",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "Saved filters", body = Vec<SavedFilter>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_saved_filters(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+) -> Result<Json<Vec<SavedFilter>>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    Ok(Json(
+        load_saved_filters(&ctx.user_context.email, &domain_path.domain).filters,
+    ))
+}
+
+/// POST /workspace/domains/{domain}/saved-filters - Save a filter
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/saved-filters",
+    tag = "Saved Filters",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    request_body = SavedFilterRequest,
+    responses(
+        (status = 200, description = "Saved filter created", body = SavedFilter),
+        (status = 400, description = "Missing name or unknown layer or modeling level", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 409, description = "A filter with this name already exists", body = crate::api_types::ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn create_saved_filter(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(domain_path): Path<DomainPath>,
+    Json(request): Json<SavedFilterRequest>,
+) -> Result<Json<SavedFilter>, ApiError> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &domain_path.domain).await?;
+    let email = &ctx.user_context.email;
+    let mut filters = load_saved_filters(email, &domain_path.domain);
+    validate_request(&filters, &request, None)?;
+
+    let now = Utc::now();
+    let filter = SavedFilter {
+        id: Uuid::new_v4(),
+        name: request.name.trim().to_string(),
+        description: request.description,
+        criteria: request.criteria,
+        created_by: email.clone(),
+        created_at: now,
+        updated_at: now,
+    };
+    filters.filters.push(filter.clone());
+    save_saved_filters(email, &domain_path.domain, &filters)?;
+    info!(
+        "[SavedFilters] Created '{}' in domain {}",
+        filter.name, domain_path.domain
+    );
+    Ok(Json(filter))
+}
+
+/// GET /workspace/domains/{domain}/saved-filters/{filter_id} - Get a saved filter
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/saved-filters/{filter_id}",
+    tag = "Saved Filters",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("filter_id" = Uuid, Path, description = "Saved filter ID")
+    ),
+    responses(
+        (status = 200, description = "Saved filter", body = SavedFilter),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Saved filter not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_saved_filter(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<SavedFilterPath>,
+) -> Result<Json<SavedFilter>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    load_saved_filters(&ctx.user_context.email, &path.domain)
+        .get(path.filter_id)
+        .cloned()
+        .map(Json)
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
+/// PUT /workspace/domains/{domain}/saved-filters/{filter_id} - Replace a saved filter
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/saved-filters/{filter_id}",
+    tag = "Saved Filters",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("filter_id" = Uuid, Path, description = "Saved filter ID")
+    ),
+    request_body = SavedFilterRequest,
+    responses(
+        (status = 200, description = "Saved filter updated", body = SavedFilter),
+        (status = 400, description = "Missing name or unknown layer or modeling level", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Saved filter not found"),
+        (status = 409, description = "A filter with this name already exists", body = crate::api_types::ErrorResponse)
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_saved_filter(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<SavedFilterPath>,
+    Json(request): Json<SavedFilterRequest>,
+) -> Result<Json<SavedFilter>, ApiError> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let email = &ctx.user_context.email;
+    let mut filters = load_saved_filters(email, &path.domain);
+    if filters.get(path.filter_id).is_none() {
+        return Err(StatusCode::NOT_FOUND.into());
+    }
+    validate_request(&filters, &request, Some(path.filter_id))?;
+
+    let filter = filters
+        .filters
+        .iter_mut()
+        .find(|f| f.id == path.filter_id)
+        .ok_or(StatusCode::NOT_FOUND)?;
+    filter.name = request.name.trim().to_string();
+    filter.description = request.description;
+    filter.criteria = request.criteria;
+    filter.updated_at = Utc::now();
+    let filter = filter.clone();
+    save_saved_filters(email, &path.domain, &filters)?;
+    Ok(Json(filter))
+}
+
+/// DELETE /workspace/domains/{domain}/saved-filters/{filter_id} - Delete a saved filter
+#[utoipa::path(
+    delete,
+    path = "/workspace/domains/{domain}/saved-filters/{filter_id}",
+    tag = "Saved Filters",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("filter_id" = Uuid, Path, description = "Saved filter ID")
+    ),
+    responses(
+        (status = 204, description = "Saved filter deleted"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Saved filter not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_saved_filter(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<SavedFilterPath>,
+) -> Result<StatusCode, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let email = &ctx.user_context.email;
+    let mut filters = load_saved_filters(email, &path.domain);
+    if !filters.remove(path.filter_id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    save_saved_filters(email, &path.domain, &filters)?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,166 @@
+//! Script hook routes.
+//!
+//! Admin-only endpoints to manage the workspace's Rhai scripting hooks (custom validation
+//! rules and import transforms, stored as `script-hooks.yaml` in the workspace data
+//! directory) and to try hooks against sample tables before saving them. Saved hooks run on
+//! every import.
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use super::app_state::AppState;
+use super::audit::is_admin;
+use super::error::ApiError;
+use super::workspace::{get_user_context, get_workspace_data_dir};
+use crate::models::Table;
+use crate::services::script_hook_service::{HookFailure, ScriptHook, ScriptHooks};
+
+/// Request body for trying hooks against sample tables
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ScriptHookTestRequest {
+    /// Hooks to run (default: the saved hooks)
+    #[serde(default)]
+    pub hooks: Option<Vec<ScriptHook>>,
+    pub tables: Vec<Table>,
+}
+
+/// Tables after the import transforms and the failures the hooks reported
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ScriptHookTestResponse {
+    pub tables: Vec<Table>,
+    pub failures: Vec<HookFailure>,
+}
+
+/// Reject callers that are not administrators
+async fn require_admin(state: &AppState, headers: &HeaderMap) -> Result<String, StatusCode> {
+    let user_context = get_user_context(state, headers).await?;
+    if !is_admin(&user_context.email) {
+        warn!(
+            "Non-admin {} denied access to script hooks",
+            user_context.email
+        );
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(user_context.email)
+}
+
+/// Check hook names are set and unique and that every script compiles
+fn validate_hooks(state: &AppState, hooks: &[ScriptHook]) -> Result<(), ApiError> {
+    let bad_request = |message: String| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message,
+    };
+    let mut names = HashSet::new();
+    for hook in hooks {
+        if hook.name.trim().is_empty() {
+            return Err(bad_request("Script hook name is required".to_string()));
+        }
+        if !names.insert(hook.name.as_str()) {
+            return Err(bad_request(format!(
+                "Duplicate script hook name '{}'",
+                hook.name
+            )));
+        }
+        state
+            .script_limits
+            .compile(&hook.script)
+            .map_err(|e| bad_request(format!("Script hook '{}': {}", hook.name, e)))?;
+    }
+    Ok(())
+}
+
+/// GET /admin/script-hooks - Get the workspace's script hooks
+#[utoipa::path(
+    get,
+    path = "/admin/script-hooks",
+    tag = "Script Hooks",
+    responses(
+        (status = 200, description = "Configured script hooks", body = ScriptHooks),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an administrator")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_script_hooks(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ScriptHooks>, StatusCode> {
+    require_admin(&state, &headers).await?;
+    let dir = get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(Json(ScriptHooks::load(&dir)))
+}
+
+/// PUT /admin/script-hooks - Replace the workspace's script hooks
+#[utoipa::path(
+    put,
+    path = "/admin/script-hooks",
+    tag = "Script Hooks",
+    request_body = ScriptHooks,
+    responses(
+        (status = 200, description = "Script hooks saved; they run on every following import", body = ScriptHooks),
+        (status = 400, description = "Missing or duplicate hook name, or a script does not compile", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an administrator"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_script_hooks(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(hooks): Json<ScriptHooks>,
+) -> Result<Json<ScriptHooks>, ApiError> {
+    let email = require_admin(&state, &headers).await?;
+    validate_hooks(&state, &hooks.hooks)?;
+    let dir = get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    hooks.save(&dir).map_err(|e| {
+        warn!("Failed to save script hooks: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    info!("{} saved {} script hooks", email, hooks.hooks.len());
+    Ok(Json(hooks))
+}
+
+/// POST /admin/script-hooks/test - Run hooks against sample tables without importing them
+#[utoipa::path(
+    post,
+    path = "/admin/script-hooks/test",
+    tag = "Script Hooks",
+    request_body = ScriptHookTestRequest,
+    responses(
+        (status = 200, description = "Transformed tables and hook failures", body = ScriptHookTestResponse),
+        (status = 400, description = "Missing or duplicate hook name, or a script does not compile", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an administrator")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn test_script_hooks(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<ScriptHookTestRequest>,
+) -> Result<Json<ScriptHookTestResponse>, ApiError> {
+    require_admin(&state, &headers).await?;
+    let hooks = match request.hooks {
+        Some(hooks) => {
+            validate_hooks(&state, &hooks)?;
+            ScriptHooks { hooks }
+        }
+        None => {
+            let dir = get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+            ScriptHooks::load(&dir)
+        }
+    };
+    let (tables, failures) = state
+        .script_limits
+        .run_import_hooks_blocking(hooks, request.tables)
+        .await;
+    Ok(Json(ScriptHookTestResponse { tables, failures }))
+}
@@ -0,0 +1,218 @@
+//! Size estimate routes.
+//!
+//! Record a table's estimated row count and monthly growth rate, and estimate the domain's
+//! storage footprint in a SQL dialect from those annotations and the column types.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::warn;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::column_references::load_domain_model;
+use super::error::ApiError;
+use super::workspace::{
+    DomainContext, DomainPath, DomainTablePath, ensure_domain_loaded, get_workspace_data_dir,
+    workspace_dir_name,
+};
+use crate::services::size_estimate_service::{
+    SizeAnnotation, SizeAnnotations, StorageDialect, StorageEstimate, estimate_storage,
+};
+
+/// A table's size annotation
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SizeAnnotationResponse {
+    pub table_id: Uuid,
+    /// None if the table has not been annotated
+    pub annotation: Option<SizeAnnotation>,
+}
+
+/// Query parameters of the storage estimate
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct StorageEstimateQuery {
+    /// SQL dialect whose storage layout to assume (postgres, mysql, sqlserver, databricks;
+    /// generic heuristics otherwise)
+    pub dialect: Option<String>,
+}
+
+fn get_domain_dir(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain))
+}
+
+/// Check the table exists in the loaded domain
+async fn ensure_table(
+    state: &AppState,
+    ctx: &DomainContext,
+    table_id: &str,
+) -> Result<Uuid, StatusCode> {
+    let table_id = Uuid::parse_str(table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let (tables, _) = load_domain_model(state, ctx).await?;
+    if !tables.iter().any(|t| t.id == table_id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    Ok(table_id)
+}
+
+fn save_annotation(
+    ctx: &DomainContext,
+    domain: &str,
+    table_id: Uuid,
+    annotation: Option<SizeAnnotation>,
+) -> Result<(), StatusCode> {
+    let dir = get_domain_dir(&ctx.user_context.email, domain)?;
+    let mut annotations = SizeAnnotations::load(&dir);
+    annotations.set(table_id, annotation);
+    annotations.save(&dir).map_err(|e| {
+        warn!("Failed to save size estimates: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+/// GET /workspace/domains/{domain}/tables/{table_id}/size-estimate - Get a table's size annotation
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/tables/{table_id}/size-estimate",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    responses(
+        (status = 200, description = "The table's estimated row count and growth rate", body = SizeAnnotationResponse),
+        (status = 400, description = "Invalid table ID"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_size_annotation(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+) -> Result<Json<SizeAnnotationResponse>, StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = ensure_table(&state, &ctx, &path.table_id).await?;
+    let dir = get_domain_dir(&ctx.user_context.email, &path.domain)?;
+
+    Ok(Json(SizeAnnotationResponse {
+        table_id,
+        annotation: SizeAnnotations::load(&dir).tables.get(&table_id).cloned(),
+    }))
+}
+
+/// PUT /workspace/domains/{domain}/tables/{table_id}/size-estimate - Record a table's size annotation
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/tables/{table_id}/size-estimate",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    request_body = SizeAnnotation,
+    responses(
+        (status = 200, description = "Annotation recorded", body = SizeAnnotationResponse),
+        (status = 400, description = "Invalid table ID or growth rate", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_size_annotation(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+    Json(annotation): Json<SizeAnnotation>,
+) -> Result<Json<SizeAnnotationResponse>, ApiError> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = ensure_table(&state, &ctx, &path.table_id).await?;
+    annotation.validate().map_err(|message| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message,
+    })?;
+    save_annotation(&ctx, &path.domain, table_id, Some(annotation.clone()))?;
+
+    Ok(Json(SizeAnnotationResponse {
+        table_id,
+        annotation: Some(annotation),
+    }))
+}
+
+/// DELETE /workspace/domains/{domain}/tables/{table_id}/size-estimate - Remove a table's size annotation
+#[utoipa::path(
+    delete,
+    path = "/workspace/domains/{domain}/tables/{table_id}/size-estimate",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    responses(
+        (status = 204, description = "Annotation removed"),
+        (status = 400, description = "Invalid table ID"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_size_annotation(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+) -> Result<StatusCode, StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = ensure_table(&state, &ctx, &path.table_id).await?;
+    save_annotation(&ctx, &path.domain, table_id, None)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /workspace/domains/{domain}/storage-estimate - Estimate the domain's storage footprint
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/storage-estimate",
+    tag = "Workspace",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        StorageEstimateQuery
+    ),
+    responses(
+        (status = 200, description = "Estimated row width of every table, and size now and after twelve months of growth of the annotated tables", body = StorageEstimate),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_storage_estimate(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+    Query(query): Query<StorageEstimateQuery>,
+) -> Result<Json<StorageEstimate>, StatusCode> {
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let (tables, _) = load_domain_model(&state, &ctx).await?;
+    let dir = get_domain_dir(&ctx.user_context.email, &path.domain)?;
+    let dialect = query
+        .dialect
+        .as_deref()
+        .map(StorageDialect::from_name)
+        .unwrap_or_default();
+
+    Ok(Json(estimate_storage(
+        &tables,
+        &SizeAnnotations::load(&dir),
+        dialect,
+    )))
+}
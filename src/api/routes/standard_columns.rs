@@ -0,0 +1,113 @@
+//! Standard columns policy routes.
+//!
+//! `GET /workspace/standard-columns` returns the workspace's surrogate key and audit column
+//! conventions; administrators replace them with `PUT /workspace/standard-columns`. While the
+//! policy is enabled, tables created through the API or by an import get the columns they
+//! are missing.
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use tracing::{info, warn};
+
+use super::app_state::AppState;
+use super::audit::is_admin;
+use super::error::ApiError;
+use super::workspace::{get_user_context, get_workspace_data_dir};
+use crate::models::Table;
+use crate::services::standard_columns_service::StandardColumnsPolicy;
+
+/// Load the workspace's standard columns policy (disabled if none is configured).
+pub fn load_standard_columns_policy() -> StandardColumnsPolicy {
+    get_workspace_data_dir()
+        .map(|dir| StandardColumnsPolicy::load(&dir))
+        .unwrap_or_default()
+}
+
+/// Add the standard columns new tables are missing.
+pub(crate) fn apply_standard_columns(tables: &mut [Table]) {
+    let policy = load_standard_columns_policy();
+    if !policy.enabled {
+        return;
+    }
+    for table in tables {
+        let added = policy.apply(table);
+        if !added.is_empty() {
+            info!(
+                "Added standard columns {} to table '{}'",
+                added.join(", "),
+                table.name
+            );
+        }
+    }
+}
+
+/// GET /workspace/standard-columns - Get the workspace's standard columns policy
+#[utoipa::path(
+    get,
+    path = "/workspace/standard-columns",
+    tag = "Workspace",
+    responses(
+        (status = 200, description = "Standard columns policy", body = StandardColumnsPolicy),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_standard_columns(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<StandardColumnsPolicy>, StatusCode> {
+    get_user_context(&state, &headers).await?;
+    Ok(Json(load_standard_columns_policy()))
+}
+
+/// PUT /workspace/standard-columns - Replace the workspace's standard columns policy
+#[utoipa::path(
+    put,
+    path = "/workspace/standard-columns",
+    tag = "Workspace",
+    request_body = StandardColumnsPolicy,
+    responses(
+        (status = 200, description = "Policy saved; following table creates and imports get the standard columns", body = StandardColumnsPolicy),
+        (status = 400, description = "Missing or duplicate column name, or missing data type", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an administrator"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_standard_columns(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(policy): Json<StandardColumnsPolicy>,
+) -> Result<Json<StandardColumnsPolicy>, ApiError> {
+    let user_context = get_user_context(&state, &headers).await?;
+    if !is_admin(&user_context.email) {
+        warn!(
+            "Non-admin {} denied changing the standard columns policy",
+            user_context.email
+        );
+        return Err(StatusCode::FORBIDDEN.into());
+    }
+    policy.check_definitions().map_err(|message| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message,
+    })?;
+    let dir = get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    policy.save(&dir).map_err(|e| {
+        warn!("Failed to save standard columns policy: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    info!(
+        "{} {} the standard columns policy",
+        user_context.email,
+        if policy.enabled {
+            "enabled"
+        } else {
+            "disabled"
+        }
+    );
+    Ok(Json(policy))
+}
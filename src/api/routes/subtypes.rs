@@ -0,0 +1,224 @@
+//! Supertype/subtype routes.
+//!
+//! Make a table the supertype of other tables. The hierarchy is stored on the supertype (as
+//! `odcl_metadata.subtypes`) and setting it adds the discriminator column if it is missing;
+//! see `inheritance_service` for how diagrams draw it and exports map it to tables.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::Serialize;
+use serde_json::json;
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::error::ApiError;
+use super::evolution_policy::PolicyOverrideQuery;
+use super::workspace::DomainTablePath;
+use crate::models::table::SUBTYPES_KEY;
+use crate::models::{Column, SubtypeHierarchy, Table};
+use crate::services::inheritance_service::{hierarchies, hierarchy_problems};
+
+/// A table's place in a supertype/subtype hierarchy
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SubtypesResponse {
+    pub table_id: Uuid,
+    pub table_name: String,
+    /// The table's subtypes; None if it is not a supertype
+    pub hierarchy: Option<SubtypeHierarchy>,
+    /// Supertype the table is a subtype of
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub supertype_id: Option<Uuid>,
+    /// Discriminator column added to the table by this request
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub added_column: Option<String>,
+}
+
+impl SubtypesResponse {
+    fn new(
+        table: &Table,
+        tables: &[Table],
+        added_column: Option<String>,
+    ) -> Result<Self, StatusCode> {
+        let hierarchy = table.subtypes().map_err(|e| {
+            warn!("Malformed subtypes on table {}: {}", table.name, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        let supertype_id = hierarchies(tables)
+            .into_iter()
+            .find(|(_, h)| h.subtypes.iter().any(|s| s.table_id == table.id))
+            .map(|(supertype, _)| supertype.id);
+        Ok(Self {
+            table_id: table.id,
+            table_name: table.name.clone(),
+            hierarchy,
+            supertype_id,
+            added_column,
+        })
+    }
+}
+
+/// Load the domain's tables and find one of them
+async fn load_tables(
+    state: &AppState,
+    headers: &HeaderMap,
+    path: &DomainTablePath,
+) -> Result<(Vec<Table>, Table), StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(state, headers, &path.domain).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let (tables, _) = super::column_references::load_domain_model(state, &ctx).await?;
+    let table = tables
+        .iter()
+        .find(|t| t.id == table_id)
+        .cloned()
+        .ok_or(StatusCode::NOT_FOUND)?;
+    Ok((tables, table))
+}
+
+/// Save table updates through the table update, so the change is versioned and recorded
+/// like any other, and return the updated table
+async fn update_table(
+    state: AppState,
+    headers: HeaderMap,
+    path: DomainTablePath,
+    updates: serde_json::Value,
+) -> Result<Table, ApiError> {
+    let Json(table) = super::workspace::update_domain_table(
+        State(state),
+        headers,
+        Path(path),
+        Query(PolicyOverrideQuery::default()),
+        Json(updates),
+    )
+    .await?;
+    serde_json::from_value(table).map_err(|e| {
+        warn!("Failed to read updated table: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR.into()
+    })
+}
+
+/// GET /workspace/domains/{domain}/tables/{table_id}/subtypes - Get a table's subtypes
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/tables/{table_id}/subtypes",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    responses(
+        (status = 200, description = "Subtypes of the table and the supertype it belongs to", body = SubtypesResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Malformed subtypes stored on the table")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_subtypes(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+) -> Result<Json<SubtypesResponse>, StatusCode> {
+    let (tables, table) = load_tables(&state, &headers, &path).await?;
+    Ok(Json(SubtypesResponse::new(&table, &tables, None)?))
+}
+
+/// PUT /workspace/domains/{domain}/tables/{table_id}/subtypes - Make a table a supertype
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/tables/{table_id}/subtypes",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Supertype table UUID")
+    ),
+    request_body = SubtypeHierarchy,
+    responses(
+        (status = 200, description = "Subtypes saved; a missing discriminator column was added", body = SubtypesResponse),
+        (status = 400, description = "Unknown or repeated subtype, empty or repeated discriminator value, or a subtype of another supertype", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 409, description = "Version conflict, or the new column violates the table's evolution policy", body = crate::api_types::ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_subtypes(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+    Json(hierarchy): Json<SubtypeHierarchy>,
+) -> Result<Json<SubtypesResponse>, ApiError> {
+    let (tables, mut table) = load_tables(&state, &headers, &path).await?;
+
+    let added_column = if hierarchy.discriminator_column.trim().is_empty()
+        || table
+            .columns
+            .iter()
+            .any(|c| c.name.eq_ignore_ascii_case(&hierarchy.discriminator_column))
+    {
+        None
+    } else {
+        let mut column = Column::new(
+            hierarchy.discriminator_column.clone(),
+            "VARCHAR(64)".to_string(),
+        );
+        column.nullable = false;
+        column.description = "Subtype of the row".to_string();
+        column.enum_values = hierarchy
+            .subtypes
+            .iter()
+            .map(|s| s.discriminator_value.clone())
+            .collect();
+        column.column_order = table.columns.len() as i32;
+        table.columns.push(column);
+        Some(hierarchy.discriminator_column.clone())
+    };
+    if let Some(problem) = hierarchy_problems(&tables, &table, &hierarchy)
+        .into_iter()
+        .next()
+    {
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: problem,
+        });
+    }
+
+    let mut updates = json!({ "odcl_metadata": { SUBTYPES_KEY: hierarchy } });
+    if added_column.is_some() {
+        updates["columns"] = json!(table.columns);
+    }
+    let table = update_table(state, headers, path, updates).await?;
+    Ok(Json(SubtypesResponse::new(&table, &tables, added_column)?))
+}
+
+/// DELETE /workspace/domains/{domain}/tables/{table_id}/subtypes - Remove a table's subtypes
+#[utoipa::path(
+    delete,
+    path = "/workspace/domains/{domain}/tables/{table_id}/subtypes",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Supertype table UUID")
+    ),
+    responses(
+        (status = 204, description = "Table is no longer a supertype; its discriminator column is kept"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_subtypes(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+) -> Result<StatusCode, ApiError> {
+    let updates = json!({ "odcl_metadata": { SUBTYPES_KEY: null } });
+    update_table(state, headers, path, updates).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
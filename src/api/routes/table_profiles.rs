@@ -0,0 +1,211 @@
+//! Table profile routes.
+//!
+//! Profiling jobs upload observed statistics of a table (row count, null %, distinct counts,
+//! min/max). Uploads are stored with their timestamps in the domain's `profiles.yaml`; the
+//! latest one is included in table payloads and the docs export, and compared with the
+//! table's declared constraints.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::error::ApiError;
+use super::workspace::{DomainTablePath, get_workspace_data_dir, workspace_dir_name};
+use crate::models::DataModel;
+use crate::services::model_validation_service::{ValidationIssue, validate_profiles};
+use crate::services::table_profile_service::{
+    ColumnProfile, PROFILES_FILE, TableProfile, TableProfiles,
+};
+
+/// Request body for uploading a table profile
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UploadProfileRequest {
+    /// When the data was profiled; defaults to now
+    pub profiled_at: Option<DateTime<Utc>>,
+    pub row_count: Option<u64>,
+    /// Statistics keyed by column name
+    #[serde(default)]
+    pub columns: BTreeMap<String, ColumnProfile>,
+}
+
+/// A stored profile and how it compares with the table's declared constraints
+#[derive(Debug, Serialize, ToSchema)]
+pub struct UploadProfileResponse {
+    pub table_id: Uuid,
+    pub profile: TableProfile,
+    /// Declared constraints contradicted by the profile
+    #[schema(value_type = Vec<Object>)]
+    pub issues: Vec<ValidationIssue>,
+}
+
+/// Profile history of a table, oldest first
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TableProfileHistory {
+    pub table_id: Uuid,
+    pub profiles: Vec<TableProfile>,
+}
+
+/// Get path to a domain's profiles.yaml
+fn get_profiles_path(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain)
+        .join(PROFILES_FILE))
+}
+
+/// Load a domain's table profiles, returning none if none are stored.
+pub fn load_table_profiles(email: &str, domain: &str) -> TableProfiles {
+    if let Ok(path) = get_profiles_path(email, domain)
+        && let Ok(content) = std::fs::read_to_string(&path)
+        && let Ok(profiles) = serde_yaml::from_str(&content)
+    {
+        return profiles;
+    }
+    TableProfiles::default()
+}
+
+/// Save a domain's table profiles
+fn save_table_profiles(
+    email: &str,
+    domain: &str,
+    profiles: &TableProfiles,
+) -> Result<(), StatusCode> {
+    let path = get_profiles_path(email, domain)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+    let yaml = serde_yaml::to_string(profiles).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    std::fs::write(path, yaml).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Add each table's latest profile as `profile` to serialized table payloads.
+pub(crate) fn attach_latest_profiles(email: &str, domain: &str, tables: &mut [Value]) {
+    let profiles = load_table_profiles(email, domain);
+    if profiles.is_empty() {
+        return;
+    }
+    for table in tables {
+        if let Some(profile) = table["id"]
+            .as_str()
+            .and_then(|id| Uuid::parse_str(id).ok())
+            .and_then(|id| profiles.latest(id))
+        {
+            table["profile"] = json!(profile);
+        }
+    }
+}
+
+/// POST /workspace/domains/{domain}/tables/{table_id}/profile - Upload observed statistics
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/tables/{table_id}/profile",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    request_body = UploadProfileRequest,
+    responses(
+        (status = 200, description = "Profile stored", body = UploadProfileResponse),
+        (status = 400, description = "Invalid table ID or statistics out of range", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn upload_table_profile(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+    Json(request): Json<UploadProfileRequest>,
+) -> Result<Json<UploadProfileResponse>, ApiError> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let table = match state.storage.as_ref() {
+        Some(storage) => storage
+            .get_table(ctx.domain_info.id, table_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        None => state
+            .model_service
+            .lock()
+            .await
+            .get_table(table_id)
+            .cloned(),
+    }
+    .ok_or(StatusCode::NOT_FOUND)?;
+
+    let email = &ctx.user_context.email;
+    let profile = TableProfile {
+        profiled_at: request.profiled_at.unwrap_or_else(Utc::now),
+        uploaded_by: email.clone(),
+        row_count: request.row_count,
+        columns: request.columns,
+    };
+    profile.validate().map_err(|message| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message,
+    })?;
+
+    let mut profiles = load_table_profiles(email, &path.domain);
+    profiles.record(table_id, profile.clone());
+    save_table_profiles(email, &path.domain, &profiles)?;
+
+    // Compare against the table's constraints using the profile just uploaded
+    let mut model = DataModel::new(path.domain.clone(), String::new(), String::new());
+    model.tables.push(table);
+    let mut uploaded = TableProfiles::default();
+    uploaded.record(table_id, profile.clone());
+    let issues = validate_profiles(&model, &uploaded);
+
+    Ok(Json(UploadProfileResponse {
+        table_id,
+        profile,
+        issues,
+    }))
+}
+
+/// GET /workspace/domains/{domain}/tables/{table_id}/profile - List a table's profiles
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/tables/{table_id}/profile",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    responses(
+        (status = 200, description = "Stored profiles, oldest first", body = TableProfileHistory),
+        (status = 400, description = "Invalid table ID"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_table_profiles(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+) -> Result<Json<TableProfileHistory>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    super::work_items::ensure_table_exists(&state, &ctx, &path.table_id).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let profiles = load_table_profiles(&ctx.user_context.email, &path.domain);
+    Ok(Json(TableProfileHistory {
+        table_id,
+        profiles: profiles.history(table_id).to_vec(),
+    }))
+}
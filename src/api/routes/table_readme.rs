@@ -0,0 +1,155 @@
+//! Table README routes.
+//!
+//! Get and replace a table's long-form Markdown documentation, stored beside its YAML as
+//! `tables/<table name>.md` in the domain directory. READMEs are rendered in the docs
+//! export and searched by the GraphQL `search` query and the MCP `search_tables` tool.
+
+use axum::{
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::workspace::{
+    DomainContext, DomainTablePath, get_workspace_data_dir, workspace_dir_name,
+};
+use crate::services::table_readme_service::{
+    MAX_README_BYTES, TableReadmes, load_readme, save_readme,
+};
+
+/// A table's README
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TableReadmeResponse {
+    pub table_id: Uuid,
+    pub table_name: String,
+    /// Markdown content; empty if the table has no README
+    pub content: String,
+}
+
+/// Request body for replacing a table's README
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdateTableReadmeRequest {
+    /// Markdown content; an empty string removes the README
+    pub content: String,
+}
+
+/// Get path to a user's domain directory
+fn get_domain_dir(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain))
+}
+
+/// Load all table READMEs of a user's domain.
+pub fn load_table_readmes(email: &str, domain: &str) -> TableReadmes {
+    get_domain_dir(email, domain)
+        .map(|dir| TableReadmes::load(&dir))
+        .unwrap_or_default()
+}
+
+/// Find a table's name in the domain
+async fn find_table_name(
+    state: &AppState,
+    ctx: &DomainContext,
+    table_id: Uuid,
+) -> Result<String, StatusCode> {
+    let name = match state.storage.as_ref() {
+        Some(storage) => storage
+            .get_tables(ctx.domain_info.id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .into_iter()
+            .find(|t| t.id == table_id)
+            .map(|t| t.name),
+        None => state
+            .model_service
+            .lock()
+            .await
+            .get_table(table_id)
+            .map(|t| t.name.clone()),
+    };
+    name.ok_or(StatusCode::NOT_FOUND)
+}
+
+/// GET /workspace/domains/{domain}/tables/{table_id}/readme - Get a table's README
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/tables/{table_id}/readme",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    responses(
+        (status = 200, description = "Table README", body = TableReadmeResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_table_readme(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+) -> Result<Json<TableReadmeResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let table_name = find_table_name(&state, &ctx, table_id).await?;
+    let dir = get_domain_dir(&ctx.user_context.email, &path.domain)?;
+    Ok(Json(TableReadmeResponse {
+        table_id,
+        content: load_readme(&dir, &table_name).unwrap_or_default(),
+        table_name,
+    }))
+}
+
+/// PUT /workspace/domains/{domain}/tables/{table_id}/readme - Replace a table's README
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/tables/{table_id}/readme",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    request_body = UpdateTableReadmeRequest,
+    responses(
+        (status = 200, description = "Table README saved", body = TableReadmeResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 413, description = "README too large"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_table_readme(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+    Json(request): Json<UpdateTableReadmeRequest>,
+) -> Result<Json<TableReadmeResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    if request.content.len() > MAX_README_BYTES {
+        return Err(StatusCode::PAYLOAD_TOO_LARGE);
+    }
+    let table_name = find_table_name(&state, &ctx, table_id).await?;
+    let dir = get_domain_dir(&ctx.user_context.email, &path.domain)?;
+    save_readme(&dir, &table_name, &request.content).map_err(|e| {
+        warn!("Failed to save README for table {}: {}", table_name, e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    Ok(Json(TableReadmeResponse {
+        table_id,
+        table_name,
+        content: request.content,
+    }))
+}
@@ -0,0 +1,218 @@
+//! Borrowed table payloads.
+//!
+//! Table responses show `database_type` and `medallion_layers` as display names. Building them
+//! by serializing a table to a `serde_json::Value` and patching it allocates a map node and a
+//! key string for every field of every column; list and canvas responses for large domains
+//! spent most of their time there. [`TableView`] borrows the table and serializes straight to
+//! the response body instead.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::models::Column;
+use crate::models::enums::{
+    DataVaultClassification, DatabaseType, MedallionLayer, ModelingLevel, SCDPattern,
+};
+use crate::models::table::{Position, Table};
+use crate::services::table_profile_service::TableProfile;
+
+/// Display name of a database type
+pub fn database_type_label(database_type: DatabaseType) -> &'static str {
+    match database_type {
+        DatabaseType::Postgres => "PostgreSQL",
+        DatabaseType::Mysql => "MySQL",
+        DatabaseType::SqlServer => "SQL Server",
+        DatabaseType::DatabricksDelta => "Databricks Delta",
+        DatabaseType::DatabricksIceberg => "Databricks Iceberg",
+        DatabaseType::AwsGlue => "AWS Glue",
+        DatabaseType::DatabricksLakebase => "Databricks Lakebase",
+        DatabaseType::Dynamodb => "DynamoDB",
+        DatabaseType::Cassandra => "Cassandra",
+        DatabaseType::Kafka => "Kafka",
+        DatabaseType::Pulsar => "Pulsar",
+    }
+}
+
+/// Display name of a medallion layer
+pub fn medallion_layer_label(layer: &MedallionLayer) -> &'static str {
+    match layer {
+        MedallionLayer::Bronze => "Bronze",
+        MedallionLayer::Silver => "Silver",
+        MedallionLayer::Gold => "Gold",
+        MedallionLayer::Operational => "Operational",
+    }
+}
+
+const DATABASE_TYPES: [DatabaseType; 11] = [
+    DatabaseType::Postgres,
+    DatabaseType::Mysql,
+    DatabaseType::SqlServer,
+    DatabaseType::DatabricksDelta,
+    DatabaseType::DatabricksIceberg,
+    DatabaseType::AwsGlue,
+    DatabaseType::DatabricksLakebase,
+    DatabaseType::Dynamodb,
+    DatabaseType::Cassandra,
+    DatabaseType::Kafka,
+    DatabaseType::Pulsar,
+];
+
+const MEDALLION_LAYERS: [MedallionLayer; 4] = [
+    MedallionLayer::Bronze,
+    MedallionLayer::Silver,
+    MedallionLayer::Gold,
+    MedallionLayer::Operational,
+];
+
+/// Parse a table payload (as recorded in the event log) back into a [`Table`], mapping the
+/// display names back to enum values.
+pub fn table_from_view(mut value: Value) -> Option<Table> {
+    let object = value.as_object_mut()?;
+    object.remove("profile");
+    if let Some(label) = object.get("database_type").and_then(Value::as_str)
+        && let Some(database_type) = DATABASE_TYPES
+            .into_iter()
+            .find(|t| database_type_label(*t) == label)
+    {
+        object.insert(
+            "database_type".to_string(),
+            serde_json::to_value(database_type).ok()?,
+        );
+    }
+    if let Some(Value::Array(layers)) = object.get_mut("medallion_layers") {
+        for layer in layers.iter_mut() {
+            let label = layer.as_str().unwrap_or_default();
+            if let Some(found) = MEDALLION_LAYERS
+                .iter()
+                .find(|l| medallion_layer_label(l) == label)
+            {
+                *layer = serde_json::to_value(found).ok()?;
+            }
+        }
+    }
+    serde_json::from_value(value).ok()
+}
+
+/// A table as returned by the table endpoints, borrowing from the loaded model.
+///
+/// Serializes to the same fields as [`Table`], with display names for `database_type` and
+/// `medallion_layers` and the latest profile (if attached) as `profile`.
+#[derive(Serialize)]
+pub struct TableView<'a> {
+    id: Uuid,
+    name: &'a str,
+    columns: &'a [Column],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    database_type: Option<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    catalog_name: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema_name: Option<&'a str>,
+    medallion_layers: Vec<&'static str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scd_pattern: Option<&'a SCDPattern>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data_vault_classification: Option<&'a DataVaultClassification>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    modeling_level: Option<&'a ModelingLevel>,
+    tags: &'a [String],
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    aliases: &'a [String],
+    odcl_metadata: &'a HashMap<String, Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    position: Option<&'a Position>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    yaml_file_path: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    drawio_cell_id: Option<&'a str>,
+    quality: &'a [HashMap<String, Value>],
+    errors: &'a [HashMap<String, Value>],
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profile: Option<&'a TableProfile>,
+}
+
+impl<'a> TableView<'a> {
+    pub fn new(table: &'a Table) -> Self {
+        Self {
+            id: table.id,
+            name: &table.name,
+            columns: &table.columns,
+            database_type: table.database_type.map(database_type_label),
+            catalog_name: table.catalog_name.as_deref(),
+            schema_name: table.schema_name.as_deref(),
+            medallion_layers: table
+                .medallion_layers
+                .iter()
+                .map(medallion_layer_label)
+                .collect(),
+            scd_pattern: table.scd_pattern.as_ref(),
+            data_vault_classification: table.data_vault_classification.as_ref(),
+            modeling_level: table.modeling_level.as_ref(),
+            tags: &table.tags,
+            aliases: &table.aliases,
+            odcl_metadata: &table.odcl_metadata,
+            position: table.position.as_ref(),
+            yaml_file_path: table.yaml_file_path.as_deref(),
+            drawio_cell_id: table.drawio_cell_id.as_deref(),
+            quality: &table.quality,
+            errors: &table.errors,
+            created_at: table.created_at,
+            updated_at: table.updated_at,
+            profile: None,
+        }
+    }
+
+    /// Include the table's latest profile.
+    pub fn with_profile(mut self, profile: Option<&'a TableProfile>) -> Self {
+        self.profile = profile;
+        self
+    }
+}
+
+/// Body of the table list endpoint
+#[derive(Serialize)]
+pub struct TablesView<'a> {
+    pub tables: Vec<TableView<'a>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_has_the_fields_of_table() {
+        let mut table = Table::new(
+            "orders".to_string(),
+            vec![Column::new("id".to_string(), "bigint".to_string())],
+        );
+        table.database_type = Some(DatabaseType::Postgres);
+        table.catalog_name = Some("main".to_string());
+        table.schema_name = Some("sales".to_string());
+        table.medallion_layers = vec![MedallionLayer::Gold];
+        table.scd_pattern = Some(SCDPattern::Type2);
+        table.data_vault_classification = Some(DataVaultClassification::Hub);
+        table.modeling_level = Some(ModelingLevel::Physical);
+        table.tags = vec!["finance".to_string()];
+        table.aliases = vec!["order".to_string()];
+        table.position = Some(Position { x: 1.0, y: 2.0 });
+        table.yaml_file_path = Some("tables/orders.yaml".to_string());
+        table.drawio_cell_id = Some("cell-1".to_string());
+
+        let plain = serde_json::to_value(&table).unwrap();
+        let view = serde_json::to_value(TableView::new(&table)).unwrap();
+        let keys = |v: &Value| v.as_object().unwrap().keys().cloned().collect::<Vec<_>>();
+        assert_eq!(keys(&plain), keys(&view));
+        assert_eq!(view["database_type"], "PostgreSQL");
+        assert_eq!(view["medallion_layers"], serde_json::json!(["Gold"]));
+        assert_eq!(view["columns"], plain["columns"]);
+        assert_eq!(view["created_at"], plain["created_at"]);
+
+        let parsed = table_from_view(view).unwrap();
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), plain);
+    }
+}
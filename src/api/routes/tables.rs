@@ -10,10 +10,10 @@ use axum::{
 };
 use serde::Deserialize;
 use serde_json::{Value, json};
-use std::collections::HashMap;
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
+use crate::api_types::CreateTableRequest;
 use crate::models::enums::{
     DataVaultClassification, DatabaseType, MedallionLayer, ModelingLevel, SCDPattern,
 };
@@ -35,36 +35,6 @@ pub struct GetTablesQuery {
     table_ids: Vec<String>,
 }
 
-/// Request body for creating a table
-#[allow(dead_code)]
-#[derive(Debug, Deserialize)]
-pub struct CreateTableRequest {
-    pub name: String,
-    pub columns: Vec<Value>,
-    #[serde(default)]
-    pub database_type: Option<String>,
-    #[serde(default)]
-    pub catalog_name: Option<String>,
-    #[serde(default)]
-    pub schema_name: Option<String>,
-    #[serde(default)]
-    pub medallion_layers: Vec<String>,
-    #[serde(default)]
-    pub medallion_layer: Option<String>, // Backward compatibility
-    #[serde(default)]
-    pub scd_pattern: Option<String>,
-    #[serde(default)]
-    pub data_vault_classification: Option<String>,
-    #[serde(default)]
-    pub modeling_level: Option<String>,
-    #[serde(default)]
-    pub tags: Vec<String>,
-    #[serde(default)]
-    pub odcl_metadata: HashMap<String, Value>,
-    #[serde(default)]
-    pub position: Option<Value>,
-}
-
 /// Request body for filtering tables
 #[allow(dead_code)]
 #[derive(Debug, Deserialize)]
@@ -382,6 +352,7 @@ async fn create_table(
         data_vault_classification,
         modeling_level,
         tags: request.tags,
+        aliases: request.aliases,
         odcl_metadata: request.odcl_metadata,
         position,
         yaml_file_path: None,
@@ -0,0 +1,64 @@
+//! Telemetry routes.
+//!
+//! Shows whether opt-in usage telemetry is enabled and exactly what it reports: the counts
+//! of the current period and the reports buffered locally for the endpoint.
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+use super::app_state::AppState;
+use super::workspace::get_user_context;
+use crate::services::telemetry_service::UsageReport;
+
+/// Telemetry settings and the data it reports
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TelemetryStatus {
+    /// Whether `TELEMETRY_ENABLED` is set (and `DO_NOT_TRACK` is not)
+    pub enabled: bool,
+    /// URL reports are sent to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub endpoint: Option<String>,
+    /// Length of a reporting period
+    pub interval_secs: u64,
+    /// Counts of the current period, reported when it ends
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current: Option<UsageReport>,
+    /// Reports waiting to be sent
+    pub pending: Vec<UsageReport>,
+}
+
+/// GET /telemetry - Show telemetry settings and pending usage reports
+#[utoipa::path(
+    get,
+    path = "/telemetry",
+    tag = "Telemetry",
+    responses(
+        (status = 200, description = "Telemetry settings and reports", body = TelemetryStatus),
+        (status = 401, description = "Unauthorized - invalid or missing token")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_telemetry_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<TelemetryStatus>, StatusCode> {
+    get_user_context(&state, &headers).await?;
+    let telemetry = &state.telemetry;
+    let enabled = telemetry.is_enabled();
+    Ok(Json(TelemetryStatus {
+        enabled,
+        endpoint: telemetry.config.endpoint.clone(),
+        interval_secs: telemetry.config.interval.as_secs(),
+        current: enabled.then(|| telemetry.current_report()),
+        pending: if enabled {
+            telemetry.pending_reports()
+        } else {
+            Vec::new()
+        },
+    }))
+}
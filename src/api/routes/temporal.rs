@@ -0,0 +1,193 @@
+//! Temporal table routes.
+//!
+//! Mark a table as system-versioned, application-time or bitemporal. The settings are stored
+//! on the table (as `odcl_metadata.temporal`) and marking it appends the period columns it
+//! is missing; see `temporal_service` for the generated columns, history table and DDL.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::Serialize;
+use serde_json::json;
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::error::ApiError;
+use super::evolution_policy::PolicyOverrideQuery;
+use super::workspace::{DomainContext, DomainTablePath};
+use crate::models::table::TEMPORAL_KEY;
+use crate::models::{Table, TemporalSettings};
+use crate::services::temporal_service::{add_period_columns, history_table, temporal_problems};
+
+/// A table's temporal settings
+#[derive(Debug, Serialize, ToSchema)]
+pub struct TemporalTableResponse {
+    pub table_id: Uuid,
+    pub table_name: String,
+    /// None if the table is not temporal
+    pub settings: Option<TemporalSettings>,
+    /// Definition of the history table of system-versioned tables
+    pub history_table: Option<Table>,
+    /// Period columns added to the table by this request
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub added_columns: Vec<String>,
+}
+
+impl TemporalTableResponse {
+    fn new(table: &Table, added_columns: Vec<String>) -> Result<Self, StatusCode> {
+        let settings = table.temporal().map_err(|e| {
+            warn!("Malformed temporal settings on table {}: {}", table.name, e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        Ok(Self {
+            table_id: table.id,
+            table_name: table.name.clone(),
+            history_table: settings.as_ref().and_then(|s| history_table(table, s)),
+            settings,
+            added_columns,
+        })
+    }
+}
+
+/// Find a table in the domain
+async fn find_table(
+    state: &AppState,
+    ctx: &DomainContext,
+    table_id: Uuid,
+) -> Result<Table, StatusCode> {
+    let table = match ctx.storage(state) {
+        Some(storage) => storage
+            .get_table(ctx.domain_info.id, table_id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?,
+        None => ctx.model_service(state).await.get_table(table_id).cloned(),
+    };
+    table.ok_or(StatusCode::NOT_FOUND)
+}
+
+/// Save table updates through the table update, so the change is versioned and recorded
+/// like any other, and return the updated table
+async fn update_table(
+    state: AppState,
+    headers: HeaderMap,
+    path: DomainTablePath,
+    updates: serde_json::Value,
+) -> Result<Table, ApiError> {
+    let Json(table) = super::workspace::update_domain_table(
+        State(state),
+        headers,
+        Path(path),
+        Query(PolicyOverrideQuery::default()),
+        Json(updates),
+    )
+    .await?;
+    serde_json::from_value(table).map_err(|e| {
+        warn!("Failed to read updated table: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR.into()
+    })
+}
+
+/// GET /workspace/domains/{domain}/tables/{table_id}/temporal - Get a table's temporal settings
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/tables/{table_id}/temporal",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    responses(
+        (status = 200, description = "Temporal settings and history table of the table", body = TemporalTableResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Malformed settings stored on the table")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_temporal_settings(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+) -> Result<Json<TemporalTableResponse>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let table = find_table(&state, &ctx, table_id).await?;
+    Ok(Json(TemporalTableResponse::new(&table, Vec::new())?))
+}
+
+/// PUT /workspace/domains/{domain}/tables/{table_id}/temporal - Mark a table as temporal
+#[utoipa::path(
+    put,
+    path = "/workspace/domains/{domain}/tables/{table_id}/temporal",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    request_body = TemporalSettings,
+    responses(
+        (status = 200, description = "Table marked temporal; missing period columns were added", body = TemporalTableResponse),
+        (status = 400, description = "Empty or shared period column names", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 409, description = "Version conflict, or the new columns violate the table's evolution policy", body = crate::api_types::ErrorResponse),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_temporal_settings(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+    Json(settings): Json<TemporalSettings>,
+) -> Result<Json<TemporalTableResponse>, ApiError> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let table_id = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let mut table = find_table(&state, &ctx, table_id).await?;
+
+    let added_columns = add_period_columns(&mut table, &settings);
+    if let Some(problem) = temporal_problems(&table, &settings).into_iter().next() {
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: problem,
+        });
+    }
+
+    let mut updates = json!({ "odcl_metadata": { TEMPORAL_KEY: settings } });
+    if !added_columns.is_empty() {
+        updates["columns"] = json!(table.columns);
+    }
+    let table = update_table(state, headers, path, updates).await?;
+    Ok(Json(TemporalTableResponse::new(&table, added_columns)?))
+}
+
+/// DELETE /workspace/domains/{domain}/tables/{table_id}/temporal - Unmark a temporal table
+#[utoipa::path(
+    delete,
+    path = "/workspace/domains/{domain}/tables/{table_id}/temporal",
+    tag = "Tables",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    responses(
+        (status = 204, description = "Table is no longer temporal; its period columns are kept"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_temporal_settings(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+) -> Result<StatusCode, ApiError> {
+    let updates = json!({ "odcl_metadata": { TEMPORAL_KEY: null } });
+    update_table(state, headers, path, updates).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,178 @@
+//! Workspace usage routes.
+//!
+//! Reports each domain's size (tables, widest table) against the configured model size
+//! limits, so users can see how close a domain is to the guardrails before an import fails.
+//! Domains with size annotations also report their estimated storage footprint.
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::Serialize;
+use std::path::Path;
+use tracing::warn;
+use utoipa::ToSchema;
+
+use super::app_state::AppState;
+use super::workspace::{
+    DomainContext, get_or_create_workspace, get_user_context, get_workspace_data_dir,
+    workspace_dir_name,
+};
+use crate::models::Table;
+use crate::services::model_limits_service::ModelLimits;
+use crate::services::odcs_parser::ODCSParser;
+use crate::services::size_estimate_service::{SizeAnnotations, StorageDialect, estimate_storage};
+
+/// Size of one domain
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DomainUsage {
+    pub domain: String,
+    pub tables: usize,
+    /// Column count of the domain's widest table
+    pub max_columns: usize,
+    /// True if the domain is over any limit (e.g. created before the limits were lowered)
+    pub over_limit: bool,
+    /// Estimated storage of the tables with size annotations (generic row size heuristics)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_bytes: Option<u64>,
+}
+
+/// Current consumption versus the configured limits
+#[derive(Debug, Serialize, ToSchema)]
+pub struct WorkspaceUsageResponse {
+    pub limits: ModelLimits,
+    pub total_tables: usize,
+    pub domains: Vec<DomainUsage>,
+}
+
+/// Number of tables in the loaded domain, from storage when available.
+pub(crate) async fn domain_table_count(state: &AppState, ctx: &DomainContext) -> usize {
+    if let Some(storage) = ctx.storage(state)
+        && let Ok(tables) = storage.get_tables(ctx.domain_info.id).await
+    {
+        return tables.len();
+    }
+    ctx.model_service(state)
+        .await
+        .get_current_model()
+        .map(|m| m.tables.len())
+        .unwrap_or_default()
+}
+
+fn domain_usage(
+    limits: &ModelLimits,
+    domain: String,
+    tables: &[Table],
+    domain_dir: &Path,
+) -> DomainUsage {
+    let max_columns = tables
+        .iter()
+        .map(|t| t.columns.len())
+        .max()
+        .unwrap_or_default();
+    let annotations = SizeAnnotations::load(domain_dir);
+    let estimated_bytes = (!annotations.tables.is_empty())
+        .then(|| estimate_storage(tables, &annotations, StorageDialect::Generic).total_bytes);
+    DomainUsage {
+        domain,
+        tables: tables.len(),
+        max_columns,
+        over_limit: tables.len() > limits.max_tables_per_domain
+            || max_columns > limits.max_columns_per_table,
+        estimated_bytes,
+    }
+}
+
+/// Tables of the table files in a file-mode domain directory
+fn file_domain_tables(domain_dir: &Path) -> Vec<Table> {
+    let Ok(entries) = std::fs::read_dir(domain_dir.join("tables")) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext == "yaml" || ext == "yml")
+        })
+        .filter_map(|path| {
+            std::fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| ODCSParser::new().parse(&content).ok())
+                .map(|(table, _)| table)
+        })
+        .collect()
+}
+
+/// GET /workspace/usage - Model size per domain versus the configured limits
+#[utoipa::path(
+    get,
+    path = "/workspace/usage",
+    tag = "Workspace",
+    responses(
+        (status = 200, description = "Usage per domain and configured limits", body = WorkspaceUsageResponse),
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_workspace_usage(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<WorkspaceUsageResponse>, StatusCode> {
+    let user_context = get_user_context(&state, &headers).await?;
+    let limits = state.model_limits;
+    let mut domains = Vec::new();
+    let user_workspace = get_workspace_data_dir()
+        .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+        .join(workspace_dir_name(&user_context.email));
+
+    if let Some(storage) = state.storage.as_ref() {
+        let workspace = get_or_create_workspace(&state, &user_context).await?;
+        let domain_infos = storage.get_domains(workspace.id).await.map_err(|e| {
+            warn!("Failed to list domains for usage: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        for domain_info in domain_infos {
+            let tables = storage.get_tables(domain_info.id).await.map_err(|e| {
+                warn!(
+                    "Failed to list tables of {} for usage: {}",
+                    domain_info.name, e
+                );
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+            let domain_dir = user_workspace.join(&domain_info.name);
+            domains.push(domain_usage(
+                &limits,
+                domain_info.name,
+                &tables,
+                &domain_dir,
+            ));
+        }
+    } else if let Ok(entries) = std::fs::read_dir(&user_workspace) {
+        for entry in entries.flatten() {
+            if let Ok(file_type) = entry.file_type()
+                && file_type.is_dir()
+                && let Some(name) = entry.file_name().to_str()
+                && !name.starts_with('.')
+            {
+                let tables = file_domain_tables(&entry.path());
+                domains.push(domain_usage(
+                    &limits,
+                    name.to_string(),
+                    &tables,
+                    &entry.path(),
+                ));
+            }
+        }
+    }
+
+    domains.sort_by(|a, b| a.domain.cmp(&b.domain));
+    Ok(Json(WorkspaceUsageResponse {
+        limits,
+        total_tables: domains.iter().map(|d| d.tables).sum(),
+        domains,
+    }))
+}
@@ -0,0 +1,257 @@
+//! Version 2 of the API: the public, rate-limited read API.
+//!
+//! Serves the workspace read endpoints under `/api/v2` with the v2 conventions: every error,
+//! including rejected paths and query strings and unknown routes, is a structured
+//! [`ErrorResponse`](crate::api_types::ErrorResponse) body, and lists are returned a page at a
+//! time as `{"items": [...], "next_cursor": "...", "has_more": true}`. Pass `next_cursor` back
+//! as `cursor` to read the next page. The handlers reuse the v1 implementations, so v2 sees
+//! the same data, access control and API key scopes.
+//!
+//! Configuration (environment variables):
+//! - `API_V2_RATE_LIMIT_PER_MINUTE` (default 600): requests per minute of each API key or
+//!   client address
+
+use axum::{
+    Router,
+    body::to_bytes,
+    extract::{
+        Path, Query, State,
+        rejection::{PathRejection, QueryRejection},
+    },
+    http::{HeaderMap, HeaderValue, StatusCode},
+    response::{Json, Response},
+    routing::get,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::{IntoParams, ToSchema};
+
+use super::app_state::AppState;
+use super::error::ApiError;
+use super::history::AsOfQuery;
+use super::saved_filters::SavedFilterQuery;
+use super::workspace::{self, DomainPath, DomainTablePath};
+use crate::middleware::api_version::API_VERSION_HEADER;
+use crate::middleware::rate_limit::{create_rate_limiter_with_quota, rate_limit_middleware};
+
+const DEFAULT_RATE_LIMIT_PER_MINUTE: u32 = 600;
+const DEFAULT_PAGE_LIMIT: usize = 100;
+const MAX_PAGE_LIMIT: usize = 1000;
+
+/// Query parameters of a paginated list
+#[derive(Debug, Default, Deserialize, IntoParams)]
+pub struct PageQuery {
+    /// `next_cursor` of the previous page (default: start from the first item)
+    pub cursor: Option<String>,
+    /// Maximum number of items (default: 100, max: 1000)
+    pub limit: Option<usize>,
+}
+
+/// A page of a list
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PageResponse {
+    pub items: Vec<Value>,
+    /// Pass as `cursor` to read the next page; absent on the last page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+    /// True if more items are available after this page
+    pub has_more: bool,
+}
+
+/// One page of `items`, ordered by their `key` field and starting after `cursor`
+fn paginate(mut items: Vec<Value>, key: &str, query: &PageQuery) -> Result<PageResponse, ApiError> {
+    let limit = query.limit.unwrap_or(DEFAULT_PAGE_LIMIT);
+    if limit == 0 || limit > MAX_PAGE_LIMIT {
+        return Err(ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message: format!("limit must be between 1 and {}", MAX_PAGE_LIMIT),
+        });
+    }
+    let key_of = |item: &Value| item.get(key).and_then(Value::as_str).map(str::to_string);
+    items.sort_by_cached_key(key_of);
+    if let Some(cursor) = query.cursor.as_deref() {
+        items.retain(|item| key_of(item).is_some_and(|k| k.as_str() > cursor));
+    }
+    let has_more = items.len() > limit;
+    items.truncate(limit);
+    Ok(PageResponse {
+        next_cursor: has_more.then(|| items.last().and_then(key_of)).flatten(),
+        items,
+        has_more,
+    })
+}
+
+/// JSON body of a v1 response, or its status as a structured error
+async fn v1_body(response: Response) -> Result<Value, ApiError> {
+    if !response.status().is_success() {
+        return Err(response.status().into());
+    }
+    let bytes = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .map_err(|_| ApiError::from(StatusCode::INTERNAL_SERVER_ERROR))?;
+    serde_json::from_slice(&bytes).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR.into())
+}
+
+/// The array under `field` of a v1 response body (the body itself if `None`)
+fn items_of(body: Value, field: Option<&str>) -> Vec<Value> {
+    let list = match field {
+        Some(field) => body.get(field).cloned().unwrap_or_default(),
+        None => body,
+    };
+    match list {
+        Value::Array(items) => items,
+        _ => Vec::new(),
+    }
+}
+
+fn rejected(rejection: impl std::fmt::Display) -> ApiError {
+    ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message: rejection.to_string(),
+    }
+}
+
+/// GET /api/v2/workspace/domains - Page of the domains in the workspace
+pub async fn list_domains(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    page: Result<Query<PageQuery>, QueryRejection>,
+) -> Result<Json<PageResponse>, ApiError> {
+    let Query(page) = page.map_err(rejected)?;
+    let response = workspace::list_domains(State(state), headers).await?;
+    let body = serde_json::to_value(response.0)
+        .map_err(|_| ApiError::from(StatusCode::INTERNAL_SERVER_ERROR))?;
+    Ok(Json(paginate(
+        items_of(body, Some("details")),
+        "name",
+        &page,
+    )?))
+}
+
+/// GET /api/v2/workspace/domains/{domain}/tables - Page of the tables in a domain
+pub async fn list_domain_tables(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    path: Result<Path<DomainPath>, PathRejection>,
+    page: Result<Query<PageQuery>, QueryRejection>,
+    filter: Result<Query<SavedFilterQuery>, QueryRejection>,
+    as_of: Result<Query<AsOfQuery>, QueryRejection>,
+) -> Result<Json<PageResponse>, ApiError> {
+    let Query(page) = page.map_err(rejected)?;
+    let response = workspace::get_domain_tables(
+        State(state),
+        headers,
+        path.map_err(rejected)?,
+        filter.map_err(rejected)?,
+        as_of.map_err(rejected)?,
+    )
+    .await?;
+    let body = v1_body(response).await?;
+    Ok(Json(paginate(items_of(body, Some("tables")), "id", &page)?))
+}
+
+/// GET /api/v2/workspace/domains/{domain}/tables/{table_id} - A single table
+pub async fn get_domain_table(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    path: Result<Path<DomainTablePath>, PathRejection>,
+) -> Result<Json<Value>, ApiError> {
+    Ok(workspace::get_domain_table(State(state), headers, path.map_err(rejected)?).await?)
+}
+
+/// GET /api/v2/workspace/domains/{domain}/relationships - Page of the relationships in a domain
+pub async fn list_domain_relationships(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    path: Result<Path<DomainPath>, PathRejection>,
+    page: Result<Query<PageQuery>, QueryRejection>,
+) -> Result<Json<PageResponse>, ApiError> {
+    let Query(page) = page.map_err(rejected)?;
+    let response =
+        workspace::get_domain_relationships(State(state), headers, path.map_err(rejected)?).await?;
+    Ok(Json(paginate(items_of(response.0, None), "id", &page)?))
+}
+
+async fn not_found() -> ApiError {
+    StatusCode::NOT_FOUND.into()
+}
+
+async fn method_not_allowed() -> ApiError {
+    ApiError {
+        status: StatusCode::METHOD_NOT_ALLOWED,
+        message: "The v2 API is read-only".to_string(),
+    }
+}
+
+/// Create the v2 router (mounted at `/api/v2`)
+pub fn v2_router(app_state: AppState) -> Router<AppState> {
+    let requests_per_minute = std::env::var("API_V2_RATE_LIMIT_PER_MINUTE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_RATE_LIMIT_PER_MINUTE);
+
+    let workspace_routes = Router::new()
+        .route("/domains", get(list_domains))
+        .route("/domains/{domain}/tables", get(list_domain_tables))
+        .route("/domains/{domain}/tables/{table_id}", get(get_domain_table))
+        .route(
+            "/domains/{domain}/relationships",
+            get(list_domain_relationships),
+        );
+    let workspace_routes = crate::middleware::access_control::writers_only(workspace_routes).layer(
+        axum::middleware::from_fn_with_state(
+            app_state.clone(),
+            crate::middleware::access_control::access_control_middleware,
+        ),
+    );
+
+    Router::new()
+        .nest("/workspace", workspace_routes)
+        .fallback(not_found)
+        .method_not_allowed_fallback(method_not_allowed)
+        .layer(axum::middleware::from_fn_with_state(
+            app_state,
+            crate::middleware::api_key::api_key_middleware,
+        ))
+        .layer(axum::middleware::from_fn_with_state(
+            create_rate_limiter_with_quota(requests_per_minute),
+            rate_limit_middleware,
+        ))
+        .layer(axum::middleware::map_response(
+            |mut response: Response| async {
+                response
+                    .headers_mut()
+                    .insert(API_VERSION_HEADER, HeaderValue::from_static("v2"));
+                response
+            },
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_cursor_pagination() {
+        let items = vec![json!({"id": "c"}), json!({"id": "a"}), json!({"id": "b"})];
+        let query = |cursor: Option<&str>, limit| PageQuery {
+            cursor: cursor.map(str::to_string),
+            limit: Some(limit),
+        };
+
+        let first = paginate(items.clone(), "id", &query(None, 2)).unwrap();
+        assert_eq!(first.items, vec![json!({"id": "a"}), json!({"id": "b"})]);
+        assert!(first.has_more);
+        assert_eq!(first.next_cursor.as_deref(), Some("b"));
+
+        let last = paginate(items.clone(), "id", &query(Some("b"), 2)).unwrap();
+        assert_eq!(last.items, vec![json!({"id": "c"})]);
+        assert!(!last.has_more);
+        assert_eq!(last.next_cursor, None);
+
+        let err = paginate(items, "id", &query(None, 0)).unwrap_err();
+        assert_eq!(err.status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,450 @@
+//! Work item link routes.
+//!
+//! Attach Jira issues and Azure DevOps work items to tables and change proposals so schema
+//! changes are traceable to tickets. Links are stored in the domain's `work-items.yaml` and
+//! shown in the docs export; `?sync=true` or `POST .../links/sync` refreshes their status.
+//! Proposals live in the trackers, so the first link to a proposal is when the server learns
+//! of it: the domain's owners are then notified that the proposal was opened.
+
+use axum::{
+    Extension,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use serde::Deserialize;
+use std::path::PathBuf;
+use tracing::info;
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::notifications::notify_domain_owners;
+use super::workspace::{
+    DomainContext, DomainPath, DomainTablePath, get_workspace_data_dir, workspace_dir_name,
+};
+use crate::middleware::access_control::is_viewer;
+use crate::services::domain_invite_service::DomainRole;
+use crate::services::notification_service::ModelEvent;
+use crate::services::work_item_service::{LinkTarget, WorkItemLink, WorkItemLinks, sync_links};
+
+/// Request body for attaching a work item
+#[derive(Debug, Deserialize, utoipa::ToSchema)]
+pub struct AddWorkItemLinkRequest {
+    /// Jira issue URL (`https://<host>/browse/PROJ-123`) or Azure DevOps work item URL
+    pub url: String,
+}
+
+/// Query parameters for listing links
+#[derive(Debug, Default, Deserialize, utoipa::IntoParams)]
+pub struct ListLinksQuery {
+    /// Refresh title and status from the tracker before returning (not for viewers, as the
+    /// refreshed links are saved)
+    #[serde(default)]
+    pub sync: bool,
+}
+
+/// Path parameters for a single table link
+#[derive(Deserialize)]
+pub struct TableLinkPath {
+    pub domain: String,
+    pub table_id: String,
+    pub link_id: Uuid,
+}
+
+/// Path parameters for proposal links
+#[derive(Deserialize)]
+pub struct ProposalPath {
+    pub domain: String,
+    pub proposal_id: String,
+}
+
+/// Path parameters for a single proposal link
+#[derive(Deserialize)]
+pub struct ProposalLinkPath {
+    pub domain: String,
+    pub proposal_id: String,
+    pub link_id: Uuid,
+}
+
+/// Get path to a domain's work-items.yaml
+fn get_work_items_path(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    Ok(workspace_data_dir
+        .join(workspace_dir_name(email))
+        .join(domain)
+        .join("work-items.yaml"))
+}
+
+/// Load a domain's work item links, returning an empty set if none are stored.
+pub fn load_work_item_links(email: &str, domain: &str) -> WorkItemLinks {
+    if let Ok(path) = get_work_items_path(email, domain)
+        && path.exists()
+        && let Ok(content) = std::fs::read_to_string(&path)
+        && let Ok(links) = serde_yaml::from_str(&content)
+    {
+        return links;
+    }
+    WorkItemLinks::default()
+}
+
+/// Save a domain's work item links
+fn save_work_item_links(
+    email: &str,
+    domain: &str,
+    links: &WorkItemLinks,
+) -> Result<(), StatusCode> {
+    let path = get_work_items_path(email, domain)?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    }
+    let yaml = serde_yaml::to_string(links).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    std::fs::write(path, yaml).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
+/// Verify that a table exists in the domain
+pub(crate) async fn ensure_table_exists(
+    state: &AppState,
+    ctx: &DomainContext,
+    table_id: &str,
+) -> Result<(), StatusCode> {
+    let table_uuid = Uuid::parse_str(table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let exists = match state.storage.as_ref() {
+        Some(storage) => storage
+            .get_tables(ctx.domain_info.id)
+            .await
+            .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?
+            .iter()
+            .any(|t| t.id == table_uuid),
+        None => state
+            .model_service
+            .lock()
+            .await
+            .get_table(table_uuid)
+            .is_some(),
+    };
+    if exists {
+        Ok(())
+    } else {
+        Err(StatusCode::NOT_FOUND)
+    }
+}
+
+async fn list_links(
+    ctx: &DomainContext,
+    domain: &str,
+    target: LinkTarget,
+    target_id: &str,
+    sync: bool,
+    role: Option<&Extension<DomainRole>>,
+) -> Result<Json<Vec<WorkItemLink>>, StatusCode> {
+    if sync && is_viewer(role) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    let email = &ctx.user_context.email;
+    let mut links = load_work_item_links(email, domain);
+    if sync {
+        sync_target(&mut links, Some((target, target_id))).await;
+        save_work_item_links(email, domain, &links)?;
+    }
+    Ok(Json(links.for_target(target, target_id)))
+}
+
+/// Sync all links, or only those of one target.
+async fn sync_target(links: &mut WorkItemLinks, target: Option<(LinkTarget, &str)>) {
+    let mut selected: Vec<WorkItemLink> = match target {
+        Some((kind, id)) => links.for_target(kind, id),
+        None => links.links.clone(),
+    };
+    sync_links(&mut selected).await;
+    for synced in selected {
+        if let Some(link) = links.links.iter_mut().find(|l| l.id == synced.id) {
+            *link = synced;
+        }
+    }
+}
+
+fn add_link(
+    ctx: &DomainContext,
+    domain: &str,
+    target: LinkTarget,
+    target_id: &str,
+    url: &str,
+) -> Result<Json<WorkItemLink>, StatusCode> {
+    let email = &ctx.user_context.email;
+    let mut links = load_work_item_links(email, domain);
+    let link = links
+        .add(target, target_id, url, email)
+        .map_err(|_| StatusCode::BAD_REQUEST)?;
+    save_work_item_links(email, domain, &links)?;
+    info!(
+        "[WorkItems] Linked {} to {:?} {} in domain {}",
+        link.key, target, target_id, domain
+    );
+    Ok(Json(link))
+}
+
+fn delete_link(
+    ctx: &DomainContext,
+    domain: &str,
+    target: LinkTarget,
+    target_id: &str,
+    link_id: Uuid,
+) -> Result<StatusCode, StatusCode> {
+    let email = &ctx.user_context.email;
+    let mut links = load_work_item_links(email, domain);
+    if !links.remove(target, target_id, link_id) {
+        return Err(StatusCode::NOT_FOUND);
+    }
+    save_work_item_links(email, domain, &links)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /workspace/domains/{domain}/tables/{table_id}/links - List work items linked to a table
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/tables/{table_id}/links",
+    tag = "Work Items",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID"),
+        ListLinksQuery
+    ),
+    responses(
+        (status = 200, description = "Linked work items", body = Vec<WorkItemLink>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Viewers cannot sync links"),
+        (status = 404, description = "Table not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_table_links(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+    Query(query): Query<ListLinksQuery>,
+    role: Option<Extension<DomainRole>>,
+) -> Result<Json<Vec<WorkItemLink>>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    ensure_table_exists(&state, &ctx, &path.table_id).await?;
+    list_links(
+        &ctx,
+        &path.domain,
+        LinkTarget::Table,
+        &path.table_id,
+        query.sync,
+        role.as_ref(),
+    )
+    .await
+}
+
+/// POST /workspace/domains/{domain}/tables/{table_id}/links - Link a work item to a table
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/tables/{table_id}/links",
+    tag = "Work Items",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID")
+    ),
+    request_body = AddWorkItemLinkRequest,
+    responses(
+        (status = 200, description = "Work item linked (or already linked)", body = WorkItemLink),
+        (status = 400, description = "Not a Jira or Azure DevOps work item URL"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Table not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn add_table_link(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainTablePath>,
+    Json(request): Json<AddWorkItemLinkRequest>,
+) -> Result<Json<WorkItemLink>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    ensure_table_exists(&state, &ctx, &path.table_id).await?;
+    add_link(
+        &ctx,
+        &path.domain,
+        LinkTarget::Table,
+        &path.table_id,
+        &request.url,
+    )
+}
+
+/// DELETE /workspace/domains/{domain}/tables/{table_id}/links/{link_id} - Remove a table's work item link
+#[utoipa::path(
+    delete,
+    path = "/workspace/domains/{domain}/tables/{table_id}/links/{link_id}",
+    tag = "Work Items",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("table_id" = String, Path, description = "Table UUID"),
+        ("link_id" = Uuid, Path, description = "Link UUID")
+    ),
+    responses(
+        (status = 204, description = "Link removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Link not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_table_link(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<TableLinkPath>,
+) -> Result<StatusCode, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    delete_link(
+        &ctx,
+        &path.domain,
+        LinkTarget::Table,
+        &path.table_id,
+        path.link_id,
+    )
+}
+
+/// GET /workspace/domains/{domain}/proposals/{proposal_id}/links - List work items linked to a proposal
+#[utoipa::path(
+    get,
+    path = "/workspace/domains/{domain}/proposals/{proposal_id}/links",
+    tag = "Work Items",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("proposal_id" = String, Path, description = "Proposal identifier"),
+        ListLinksQuery
+    ),
+    responses(
+        (status = 200, description = "Linked work items", body = Vec<WorkItemLink>),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Viewers cannot sync links")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn list_proposal_links(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<ProposalPath>,
+    Query(query): Query<ListLinksQuery>,
+    role: Option<Extension<DomainRole>>,
+) -> Result<Json<Vec<WorkItemLink>>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    list_links(
+        &ctx,
+        &path.domain,
+        LinkTarget::Proposal,
+        &path.proposal_id,
+        query.sync,
+        role.as_ref(),
+    )
+    .await
+}
+
+/// POST /workspace/domains/{domain}/proposals/{proposal_id}/links - Link a work item to a proposal
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/proposals/{proposal_id}/links",
+    tag = "Work Items",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("proposal_id" = String, Path, description = "Proposal identifier")
+    ),
+    request_body = AddWorkItemLinkRequest,
+    responses(
+        (status = 200, description = "Work item linked (or already linked)", body = WorkItemLink),
+        (status = 400, description = "Not a Jira or Azure DevOps work item URL"),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn add_proposal_link(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<ProposalPath>,
+    Json(request): Json<AddWorkItemLinkRequest>,
+) -> Result<Json<WorkItemLink>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    if path.proposal_id.trim().is_empty() || path.proposal_id.len() > 128 {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    let email = &ctx.user_context.email;
+    let opened = load_work_item_links(email, &path.domain)
+        .for_target(LinkTarget::Proposal, &path.proposal_id)
+        .is_empty();
+    let link = add_link(
+        &ctx,
+        &path.domain,
+        LinkTarget::Proposal,
+        &path.proposal_id,
+        &request.url,
+    )?;
+    if opened {
+        notify_domain_owners(
+            &state,
+            &ctx,
+            &path.domain,
+            ModelEvent::proposal_opened(&path.domain, email, &path.proposal_id, &request.url),
+        );
+    }
+    Ok(link)
+}
+
+/// DELETE /workspace/domains/{domain}/proposals/{proposal_id}/links/{link_id} - Remove a proposal's work item link
+#[utoipa::path(
+    delete,
+    path = "/workspace/domains/{domain}/proposals/{proposal_id}/links/{link_id}",
+    tag = "Work Items",
+    params(
+        ("domain" = String, Path, description = "Domain name"),
+        ("proposal_id" = String, Path, description = "Proposal identifier"),
+        ("link_id" = Uuid, Path, description = "Link UUID")
+    ),
+    responses(
+        (status = 204, description = "Link removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Link not found")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn delete_proposal_link(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<ProposalLinkPath>,
+) -> Result<StatusCode, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    delete_link(
+        &ctx,
+        &path.domain,
+        LinkTarget::Proposal,
+        &path.proposal_id,
+        path.link_id,
+    )
+}
+
+/// POST /workspace/domains/{domain}/links/sync - Refresh status of all work item links in a domain
+#[utoipa::path(
+    post,
+    path = "/workspace/domains/{domain}/links/sync",
+    tag = "Work Items",
+    params(
+        ("domain" = String, Path, description = "Domain name")
+    ),
+    responses(
+        (status = 200, description = "All links with refreshed status; failures are reported in `sync_error`", body = Vec<WorkItemLink>),
+        (status = 401, description = "Unauthorized")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn sync_domain_links(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(path): Path<DomainPath>,
+) -> Result<Json<Vec<WorkItemLink>>, StatusCode> {
+    let ctx = super::workspace::ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let email = &ctx.user_context.email;
+    let mut links = load_work_item_links(email, &path.domain);
+    sync_target(&mut links, None).await;
+    save_work_item_links(email, &path.domain, &links)?;
+    Ok(Json(links.links))
+}
@@ -11,26 +11,85 @@ use axum::{
     Router,
     extract::State,
     http::StatusCode,
-    response::Json,
+    response::{IntoResponse, Json, Response},
     routing::{get, post},
 };
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::{info, warn};
 use utoipa::ToSchema;
 
 use super::app_state::AppState;
+use super::attachments;
+use super::audit;
+use super::auth::touch_session;
+use super::column_edges;
+use super::column_references;
+use super::commands;
+use super::contract_tests;
 use super::data_flow;
+use super::data_product;
+use super::diagram_shares;
+use super::domain_graph;
+use super::domain_invites;
+use super::domain_metadata;
+use super::domain_provisioning;
+use super::domain_shares;
+use super::drafts;
+use super::duplicate_tables;
+use super::environments;
+use super::error::ApiError;
+use super::events;
+use super::evolution_policy;
+use super::export_schedules;
+use super::exports;
 use super::git_sync;
+use super::history;
 use super::import;
+use super::medallion_layers;
+use super::members;
+use super::metadata_propagation;
+use super::metadata_schema;
 use super::models;
+use super::naming;
+use super::payload_validation;
+use super::quality_score;
+use super::relationship_anchors;
+use super::relationship_rules;
+use super::relationships_file;
+use super::rename;
+use super::retention;
+use super::row_policies;
+use super::saved_filters;
+use super::size_estimates;
+use super::standard_columns;
+use super::subtypes;
+use super::table_profiles;
+use super::table_readme;
+use super::table_view::{TableView, TablesView};
+use super::temporal;
+use super::usage;
+use super::work_items;
+use super::workspace_provisioning;
+use crate::middleware::access_control::{active_workspace, writers_only};
+use crate::middleware::api_key::authenticate_api_key;
+use crate::services::audit_log_service::{AuditAction, AuditEntityType};
+use crate::services::domain_index_service::DomainIndex;
+use crate::services::domain_service::DomainMetadata;
+use crate::services::draft_service::{Draft, DraftStore};
+use crate::services::event_log_service::ChangeEventType;
 use crate::services::jwt_service::JwtService;
+use crate::services::local_mode::local_mode;
+use crate::services::model_service::ModelService;
+use crate::services::workspace_provisioning_service::WorkspaceAssignments;
 use crate::storage::{
-    StorageError,
+    StorageBackend, StorageError,
     traits::{DomainInfo, PositionExport, UserContext, WorkspaceInfo as StorageWorkspaceInfo},
 };
 use axum::http::HeaderMap;
 use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::OwnedMutexGuard;
 use uuid::Uuid;
 
 #[derive(Deserialize, ToSchema)]
@@ -107,21 +166,122 @@ pub struct DomainResponse {
 #[derive(Serialize, ToSchema)]
 pub struct DomainsListResponse {
     domains: Vec<String>,
+    /// Domains with their `domain.yaml` metadata, in the same order as `domains`
+    details: Vec<DomainSummary>,
+}
+
+/// A domain and its metadata
+#[derive(Serialize, ToSchema)]
+pub struct DomainSummary {
+    name: String,
+    #[serde(flatten)]
+    metadata: DomainMetadata,
+    /// Number of tables (file-backed workspaces, read from the domain index)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    table_count: Option<usize>,
+    /// Number of relationships (file-backed workspaces, read from the domain index)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    relationship_count: Option<usize>,
+}
+
+impl DomainsListResponse {
+    fn new(email: &str, domains: Vec<String>, descriptions: &HashMap<String, String>) -> Self {
+        let details = domains
+            .iter()
+            .map(|name| {
+                let mut metadata = domain_metadata::load_domain_metadata(email, name);
+                if metadata.description.is_none() {
+                    metadata.description = descriptions.get(name).cloned();
+                }
+                DomainSummary {
+                    name: name.clone(),
+                    metadata,
+                    table_count: None,
+                    relationship_count: None,
+                }
+            })
+            .collect();
+        Self { domains, details }
+    }
+
+    /// Add table and relationship counts from each domain's index.
+    fn with_counts(mut self, user_workspace: &std::path::Path) -> Self {
+        for summary in &mut self.details {
+            let index = DomainIndex::get(&user_workspace.join(&summary.name));
+            summary.table_count = Some(index.table_count());
+            summary.relationship_count = Some(index.relationship_count);
+        }
+        self
+    }
 }
 
 /// Create the workspace router
 pub fn workspace_router() -> Router<AppState> {
-    Router::new()
+    let router = Router::new()
         .route("/create", post(create_workspace))
         .route("/info", get(get_workspace_info))
         .route("/profiles", get(list_profiles))
+        .route("/usage", get(usage::get_workspace_usage))
+        // Typed custom fields for tables and columns
+        .route(
+            "/metadata-schema",
+            get(metadata_schema::get_metadata_schema).put(metadata_schema::update_metadata_schema),
+        )
+        // Surrogate key and audit columns added to new tables
+        .route(
+            "/standard-columns",
+            get(standard_columns::get_standard_columns)
+                .put(standard_columns::update_standard_columns),
+        )
+        // Naming conventions imports infer medallion layers from
+        .route(
+            "/medallion-layers",
+            get(medallion_layers::get_medallion_layers)
+                .put(medallion_layers::update_medallion_layers),
+        )
+        // Team workspace auto-provisioning on first login
+        .route(
+            "/provisioning-rules",
+            get(workspace_provisioning::get_provisioning_rules)
+                .put(workspace_provisioning::update_provisioning_rules),
+        )
+        // Near-duplicate tables across domains
+        .route(
+            "/duplicate-tables",
+            get(duplicate_tables::find_duplicate_tables),
+        )
+        // All domains merged into one model
+        .route("/export/enterprise", get(models::export_enterprise))
+        // Command palette: searchable actions and a generic executor
+        .route("/command-index", get(commands::get_command_index))
+        .route("/commands/execute", post(commands::execute_command))
         // Domain CRUD endpoints
         .route("/domains", get(list_domains))
+        // Domain dependency graph (context map)
+        .route("/domains/graph", get(domain_graph::get_domain_graph))
         .route("/domains", post(create_domain))
+        .route(
+            "/domains:bulk",
+            post(domain_provisioning::bulk_create_domains),
+        )
         .route("/domains/{domain}", get(get_domain))
         .route("/domains/{domain}", axum::routing::put(update_domain))
         .route("/domains/{domain}", axum::routing::delete(delete_domain))
         .route("/load-domain", post(load_domain))
+        // Session-scoped drafts: edits are kept in memory until saved or discarded
+        .route(
+            "/domains/{domain}/draft",
+            get(drafts::get_draft).post(drafts::open_draft),
+        )
+        .route("/domains/{domain}/draft/save", post(drafts::save_draft))
+        .route(
+            "/domains/{domain}/draft/discard",
+            post(drafts::discard_draft),
+        )
+        .route(
+            "/domains/{domain}/save-status",
+            get(drafts::get_save_status),
+        )
         // Domain-scoped table CRUD endpoints
         .route("/domains/{domain}/tables", get(get_domain_tables))
         .route("/domains/{domain}/tables", post(create_domain_table))
@@ -134,6 +294,11 @@ pub fn workspace_router() -> Router<AppState> {
             "/domains/{domain}/tables/{table_id}",
             axum::routing::delete(delete_domain_table),
         )
+        // JSON Schema of relationships.yaml
+        .route(
+            "/relationships-schema",
+            get(relationships_file::get_relationships_schema),
+        )
         // Domain-scoped relationship CRUD endpoints
         .route(
             "/domains/{domain}/relationships",
@@ -155,6 +320,17 @@ pub fn workspace_router() -> Router<AppState> {
             "/domains/{domain}/relationships/{relationship_id}",
             axum::routing::delete(delete_domain_relationship),
         )
+        // Stable attachment points of a relationship on its tables
+        .route(
+            "/domains/{domain}/relationships/{relationship_id}/anchors",
+            axum::routing::put(relationship_anchors::update_relationship_anchors),
+        )
+        // Raw relationships.yaml download, validation and upload
+        .route(
+            "/domains/{domain}/relationships-file",
+            get(relationships_file::download_relationships_file)
+                .put(relationships_file::upload_relationships_file),
+        )
         // Cross-domain reference endpoints
         .route(
             "/domains/{domain}/cross-domain",
@@ -190,8 +366,31 @@ pub fn workspace_router() -> Router<AppState> {
         )
         // Combined view endpoint (domain tables + imported tables with ownership info)
         .route("/domains/{domain}/canvas", get(get_domain_canvas))
+        // Column-to-column lines of lineage relationships, for expanded relationships
+        .route(
+            "/domains/{domain}/canvas/column-edges",
+            get(column_edges::get_column_edges),
+        )
+        // Named table filters applied to the table list and canvas with `?filter=`
+        .nest(
+            "/domains/{domain}/saved-filters",
+            saved_filters::saved_filters_router(),
+        )
+        // Append-only change log for external consumers
+        .route("/domains/{domain}/events", get(events::list_domain_events))
+        .route("/domains/{domain}/audit", get(audit::list_domain_audit))
+        .route("/events/schema", get(events::get_event_schema))
         // Domain-scoped import endpoints
         .nest("/domains/{domain}/import", import::domain_import_router())
+        .route(
+            "/import/google-sheets/mapping",
+            get(import::get_google_sheets_mapping).put(import::put_google_sheets_mapping),
+        )
+        // Creates a new domain, so it isn't domain-scoped
+        .route(
+            "/import/event-storming",
+            post(import::import_event_storming),
+        )
         // Domain-scoped export endpoints (added directly to ensure domain path parameter is available)
         .route(
             "/domains/{domain}/export/{format}",
@@ -201,21 +400,217 @@ pub fn workspace_router() -> Router<AppState> {
             "/domains/{domain}/export/all",
             get(models::domain_export_all),
         )
+        // Signed, expiring image URLs for embedding diagrams in wikis
+        .route(
+            "/domains/{domain}/export/png/share",
+            post(diagram_shares::share_diagram),
+        )
+        // Recurring export bundles published to git, S3 or webhooks
+        .nest(
+            "/domains/{domain}/export-schedules",
+            export_schedules::export_schedules_router(),
+        )
+        // Export history: every domain export, re-downloadable as delivered
+        .nest("/domains/{domain}/exports", exports::exports_router())
+        // Domain-scoped environment profiles (dev/test/prod)
+        .nest(
+            "/domains/{domain}/environments",
+            environments::environments_router(),
+        )
+        .route(
+            "/domains/{domain}/variables",
+            get(environments::get_domain_variables).put(environments::update_domain_variables),
+        )
+        .nest(
+            "/domains/{domain}/metadata",
+            domain_metadata::domain_metadata_router(),
+        )
+        // Data mesh: the domain as a data product (ports, owner, SLA)
+        .nest(
+            "/domains/{domain}/data-product",
+            data_product::data_product_router(),
+        )
+        // Roles granted on the whole workspace and on single domains
+        .route(
+            "/members",
+            get(members::list_workspace_members).post(members::add_workspace_member),
+        )
+        .route(
+            "/members/{email}",
+            axum::routing::put(members::update_workspace_member)
+                .delete(members::remove_workspace_member),
+        )
+        .route(
+            "/domains/{domain}/members",
+            get(members::list_domain_members).post(members::add_domain_member),
+        )
+        .route(
+            "/domains/{domain}/members/{email}",
+            axum::routing::put(members::update_domain_member).delete(members::remove_domain_member),
+        )
+        // Invites that give other users a role on the domain
+        .nest(
+            "/domains/{domain}/invites",
+            domain_invites::domain_invites_router(),
+        )
+        // Read-only share links for external partners
+        .nest(
+            "/domains/{domain}/shares",
+            domain_shares::domain_shares_router(),
+        )
+        // Long-form Markdown documentation stored beside the table YAML
+        .route(
+            "/domains/{domain}/tables/{table_id}/readme",
+            get(table_readme::get_table_readme).put(table_readme::update_table_readme),
+        )
+        // Everything depending on a column, checked before renaming or dropping it
+        .route(
+            "/domains/{domain}/columns/{table_id}/{column}/references",
+            get(column_references::get_column_references),
+        )
+        // Consumer contract test skeletons (pytest / Rust) per table
+        .route(
+            "/domains/{domain}/tables/{table_id}/contract-tests",
+            get(contract_tests::export_contract_tests),
+        )
+        // Observed data profiles (row counts, null %, distinct counts, min/max)
+        .route(
+            "/domains/{domain}/tables/{table_id}/profile",
+            get(table_profiles::get_table_profiles).post(table_profiles::upload_table_profile),
+        )
+        // Schema evolution policy, enforced when table columns are updated
+        .route(
+            "/domains/{domain}/tables/{table_id}/evolution-policy",
+            get(evolution_policy::get_evolution_policy)
+                .put(evolution_policy::update_evolution_policy),
+        )
+        // Retention period, archival target and legal hold, and the domain's purge plan
+        .route(
+            "/domains/{domain}/tables/{table_id}/retention",
+            get(retention::get_retention_policy).put(retention::update_retention_policy),
+        )
+        .route(
+            "/domains/{domain}/retention/purge-plan",
+            get(retention::export_purge_plan),
+        )
+        // Row-level security policies, exported as PostgreSQL / Snowflake DDL
+        .route(
+            "/domains/{domain}/tables/{table_id}/row-policies",
+            get(row_policies::get_row_access_policies)
+                .put(row_policies::update_row_access_policies),
+        )
+        // System-versioned / application-time / bitemporal tables and their history tables
+        .route(
+            "/domains/{domain}/tables/{table_id}/temporal",
+            get(temporal::get_temporal_settings)
+                .put(temporal::update_temporal_settings)
+                .delete(temporal::delete_temporal_settings),
+        )
+        // Supertype/subtype hierarchies, exported with the inheritance strategy chosen then
+        .route(
+            "/domains/{domain}/tables/{table_id}/subtypes",
+            get(subtypes::get_subtypes)
+                .put(subtypes::update_subtypes)
+                .delete(subtypes::delete_subtypes),
+        )
+        // Estimated row counts and growth, and the storage footprint they imply
+        .route(
+            "/domains/{domain}/tables/{table_id}/size-estimate",
+            get(size_estimates::get_size_annotation)
+                .put(size_estimates::update_size_annotation)
+                .delete(size_estimates::delete_size_annotation),
+        )
+        .route(
+            "/domains/{domain}/storage-estimate",
+            get(size_estimates::get_storage_estimate),
+        )
+        // Composite quality score, recorded on every change
+        .route(
+            "/domains/{domain}/score",
+            get(quality_score::get_quality_score),
+        )
+        // Changes since a past time, reconstructed from the event log
+        .route(
+            "/domains/{domain}/history/diff",
+            get(history::diff_with_past),
+        )
+        // Renames rewrite relationships, foreign keys and cross-domain references
+        .route(
+            "/domains/{domain}/tables/{table_id}/rename",
+            post(rename::rename_table),
+        )
+        .route(
+            "/domains/{domain}/columns/{table_id}/{column}/rename",
+            post(rename::rename_column),
+        )
+        // Copy descriptions, classifications and glossary links down column-level lineage
+        .route(
+            "/domains/{domain}/propagate-metadata",
+            post(metadata_propagation::propagate_domain_metadata),
+        )
+        // Files attached to domains and tables
+        .merge(attachments::attachments_router())
+        // Work item links (Jira / Azure DevOps) on tables and proposals
+        .route(
+            "/domains/{domain}/tables/{table_id}/links",
+            get(work_items::list_table_links).post(work_items::add_table_link),
+        )
+        .route(
+            "/domains/{domain}/tables/{table_id}/links/{link_id}",
+            axum::routing::delete(work_items::delete_table_link),
+        )
+        .route(
+            "/domains/{domain}/proposals/{proposal_id}/links",
+            get(work_items::list_proposal_links).post(work_items::add_proposal_link),
+        )
+        .route(
+            "/domains/{domain}/proposals/{proposal_id}/links/{link_id}",
+            axum::routing::delete(work_items::delete_proposal_link),
+        )
+        .route(
+            "/domains/{domain}/links/sync",
+            post(work_items::sync_domain_links),
+        )
         // Domain-scoped git sync endpoints
         .nest("/domains/{domain}/git", git_sync::domain_git_router())
         // Domain-scoped data-flow diagram endpoints
         .nest(
             "/domains/{domain}/data-flow-diagrams",
             data_flow::data_flow_router(),
+        );
+
+    // Endpoints that only check or preview the request body, which viewers may call
+    writers_only(router)
+        .route(
+            "/domains/{domain}/relationships-file/validate",
+            post(relationships_file::validate_relationships_upload),
+        )
+        // Validate sample payloads (JSON / NDJSON) against a table's JSON Schema
+        .route(
+            "/domains/{domain}/tables/{table_id}/validate-payload",
+            post(payload_validation::validate_payload),
+        )
+        .route(
+            "/domains/{domain}/tables/{table_id}/compatibility",
+            post(evolution_policy::check_table_compatibility),
+        )
+        // Domain-scoped physical naming templates and relationship rules; their routers guard
+        // their own writes
+        .nest("/domains/{domain}/naming", naming::naming_router())
+        .nest(
+            "/domains/{domain}/relationship-rules",
+            relationship_rules::relationship_rules_router(),
         )
 }
 
 /// Get the workspace data directory from environment variable
 pub fn get_workspace_data_dir() -> Result<PathBuf, String> {
-    let workspace_data = std::env::var("WORKSPACE_DATA")
-        .map_err(|_| "WORKSPACE_DATA environment variable not set".to_string())?;
-
-    let path = PathBuf::from(workspace_data);
+    let path = match local_mode() {
+        Some(local) => local.data_dir.clone(),
+        None => std::env::var("WORKSPACE_DATA")
+            .map(PathBuf::from)
+            .map_err(|_| "WORKSPACE_DATA environment variable not set".to_string())?,
+    };
     if !path.exists() {
         std::fs::create_dir_all(&path)
             .map_err(|e| format!("Failed to create workspace data directory: {}", e))?;
@@ -232,6 +627,30 @@ pub fn sanitize_email_for_path(email: &str) -> String {
         .replace(['.', '/', '\\', ':', '*', '?', '"', '<', '>', '|'], "_")
 }
 
+/// Email of the workspace the request acts in: the workspace picked with the `X-Workspace`
+/// header (see `middleware::access_control`), or the user's home workspace.
+pub fn workspace_email(email: &str) -> String {
+    active_workspace().unwrap_or_else(|| home_workspace_email(email))
+}
+
+/// Email the user's own workspace is keyed by: the team workspace the user was provisioned
+/// into on first login, or the user's own email.
+pub fn home_workspace_email(email: &str) -> String {
+    get_workspace_data_dir()
+        .ok()
+        .and_then(|dir| {
+            WorkspaceAssignments::cached(&dir)
+                .workspace_for(email)
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| email.to_string())
+}
+
+/// Directory name of the user's workspace in the workspace data directory
+pub fn workspace_dir_name(email: &str) -> String {
+    sanitize_email_for_path(&workspace_email(email))
+}
+
 /// Validate domain name for use in URL paths and file system.
 ///
 /// Prevents path traversal attacks and ensures domain names are safe.
@@ -341,7 +760,7 @@ pub async fn create_workspace_for_email_and_domain(
 
     // Create user workspace directory with domain subdirectory
     // Structure: {WORKSPACE_DATA}/{email}/{domain}/
-    let sanitized_email = sanitize_email_for_path(&email);
+    let sanitized_email = workspace_dir_name(&email);
     let user_workspace = workspace_data_dir.join(&sanitized_email).join(domain);
 
     // Create workspace directory structure
@@ -577,7 +996,16 @@ pub async fn list_workspaces(
     // Get user context from JWT token
     let user_context = get_user_context(&state, &headers).await?;
 
-    // Get workspaces for this user
+    Ok(Json(WorkspacesListResponse {
+        workspaces: user_workspaces(&state, &user_context).await?,
+    }))
+}
+
+/// Workspaces owned by a user
+pub async fn user_workspaces(
+    state: &AppState,
+    user_context: &UserContext,
+) -> Result<Vec<WorkspaceResponse>, StatusCode> {
     let workspaces: Vec<StorageWorkspaceInfo> = if let Some(storage) = state.storage.as_ref() {
         storage
             .get_workspaces_by_owner(user_context.user_id)
@@ -590,46 +1018,11 @@ pub async fn list_workspaces(
         // File-based mode - read from .workspaces.json file
         match get_workspace_data_dir() {
             Ok(workspace_data_dir) => {
-                let sanitized_email = sanitize_email_for_path(&user_context.email);
-                let user_workspace_base = workspace_data_dir.join(&sanitized_email);
-                let workspaces_file = user_workspace_base.join(".workspaces.json");
-
-                if workspaces_file.exists() {
-                    match std::fs::read_to_string(&workspaces_file) {
-                        Ok(content) => {
-                            let workspaces_map: HashMap<String, serde_json::Value> =
-                                serde_json::from_str(&content).unwrap_or_default();
-
-                            workspaces_map
-                                .values()
-                                .filter_map(|v| {
-                                    Some(StorageWorkspaceInfo {
-                                        id: uuid::Uuid::parse_str(v.get("id")?.as_str()?).ok()?,
-                                        owner_id: user_context.user_id,
-                                        email: v.get("email")?.as_str()?.to_string(),
-                                        name: v.get("name")?.as_str().map(|s| s.to_string()),
-                                        workspace_type: v
-                                            .get("type")?
-                                            .as_str()
-                                            .map(|s| s.to_string()),
-                                        created_at: chrono::DateTime::parse_from_rfc3339(
-                                            v.get("created_at")?.as_str()?,
-                                        )
-                                        .ok()?
-                                        .with_timezone(&chrono::Utc),
-                                        updated_at: chrono::Utc::now(), // Use current time as fallback
-                                    })
-                                })
-                                .collect()
-                        }
-                        Err(e) => {
-                            warn!("Failed to read workspaces file: {}", e);
-                            Vec::new()
-                        }
-                    }
-                } else {
-                    Vec::new()
-                }
+                let sanitized_email = workspace_dir_name(&user_context.email);
+                read_workspaces_file(
+                    &workspace_data_dir.join(&sanitized_email),
+                    user_context.user_id,
+                )
             }
             Err(_) => {
                 // WORKSPACE_DATA not set - return empty list
@@ -639,20 +1032,88 @@ pub async fn list_workspaces(
     };
 
     // Convert to response format
-    let workspace_responses: Vec<WorkspaceResponse> = workspaces
-        .into_iter()
-        .map(|w| WorkspaceResponse {
-            id: w.id,
-            name: w.name.unwrap_or_else(|| "Unnamed Workspace".to_string()),
-            workspace_type: w.workspace_type.unwrap_or_else(|| "personal".to_string()),
-            email: w.email,
-            created_at: w.created_at,
-        })
-        .collect();
+    Ok(workspaces.into_iter().map(workspace_response).collect())
+}
 
-    Ok(Json(WorkspacesListResponse {
-        workspaces: workspace_responses,
-    }))
+/// Workspaces a user can access: their own, and those where another user granted them a role
+/// on the workspace or on one of its domains (see `members`)
+pub async fn accessible_workspaces(
+    state: &AppState,
+    user_context: &UserContext,
+) -> Result<Vec<WorkspaceResponse>, StatusCode> {
+    let mut workspaces = user_workspaces(state, user_context).await?;
+    let all: Vec<StorageWorkspaceInfo> = if let Some(storage) = state.storage.as_ref() {
+        storage.get_workspaces().await.map_err(|e| {
+            warn!("Failed to get workspaces: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?
+    } else {
+        match get_workspace_data_dir()
+            .and_then(|dir| std::fs::read_dir(dir).map_err(|e| e.to_string()))
+        {
+            // Other users' ids are not known in file-based mode, and not returned
+            Ok(entries) => entries
+                .flatten()
+                .flat_map(|entry| read_workspaces_file(&entry.path(), Uuid::nil()))
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    };
+    for workspace in all {
+        if !workspaces.iter().any(|w| w.id == workspace.id)
+            && super::members::is_member(&user_context.email, &workspace.email)
+        {
+            workspaces.push(workspace_response(workspace));
+        }
+    }
+    Ok(workspaces)
+}
+
+/// Workspaces listed in a user's `.workspaces.json`
+fn read_workspaces_file(user_workspace_base: &Path, owner_id: Uuid) -> Vec<StorageWorkspaceInfo> {
+    let workspaces_file = user_workspace_base.join(".workspaces.json");
+    if !workspaces_file.exists() {
+        return Vec::new();
+    }
+    match std::fs::read_to_string(&workspaces_file) {
+        Ok(content) => {
+            let workspaces_map: HashMap<String, serde_json::Value> =
+                serde_json::from_str(&content).unwrap_or_default();
+
+            workspaces_map
+                .values()
+                .filter_map(|v| {
+                    Some(StorageWorkspaceInfo {
+                        id: uuid::Uuid::parse_str(v.get("id")?.as_str()?).ok()?,
+                        owner_id,
+                        email: v.get("email")?.as_str()?.to_string(),
+                        name: v.get("name")?.as_str().map(|s| s.to_string()),
+                        workspace_type: v.get("type")?.as_str().map(|s| s.to_string()),
+                        created_at: chrono::DateTime::parse_from_rfc3339(
+                            v.get("created_at")?.as_str()?,
+                        )
+                        .ok()?
+                        .with_timezone(&chrono::Utc),
+                        updated_at: chrono::Utc::now(), // Use current time as fallback
+                    })
+                })
+                .collect()
+        }
+        Err(e) => {
+            warn!("Failed to read workspaces file: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+fn workspace_response(w: StorageWorkspaceInfo) -> WorkspaceResponse {
+    WorkspaceResponse {
+        id: w.id,
+        name: w.name.unwrap_or_else(|| "Unnamed Workspace".to_string()),
+        workspace_type: w.workspace_type.unwrap_or_else(|| "personal".to_string()),
+        email: w.email,
+        created_at: w.created_at,
+    }
 }
 
 /// POST /api/v1/workspaces - Create a new workspace for the authenticated user
@@ -737,7 +1198,7 @@ pub async fn create_workspace_v1(
             }
         };
 
-        let sanitized_email = sanitize_email_for_path(&email);
+        let sanitized_email = workspace_dir_name(&email);
         let user_workspace_base = workspace_data_dir.join(&sanitized_email);
 
         // Ensure user workspace base directory exists
@@ -855,6 +1316,15 @@ pub async fn list_profiles(
     State(state): State<AppState>,
     headers: HeaderMap,
 ) -> Result<Json<ProfilesResponse>, StatusCode> {
+    // Local mode has a single profile: the local user's file workspace
+    if let Some(local) = local_mode() {
+        let workspace_data_dir =
+            get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+        return Ok(Json(ProfilesResponse {
+            profiles: vec![file_profile(&workspace_data_dir, &local.email)],
+        }));
+    }
+
     // Initialize JWT service and validate token
     let jwt_service = JwtService::from_env();
 
@@ -959,32 +1429,7 @@ pub async fn list_profiles(
             continue;
         }
 
-        let email = &email_info.email;
-        let sanitized_email = sanitize_email_for_path(email);
-        let user_workspace = workspace_data_dir.join(&sanitized_email);
-
-        // List domains (subdirectories) in the user's workspace
-        let mut domains = Vec::new();
-        if user_workspace.exists()
-            && let Ok(entries) = std::fs::read_dir(&user_workspace)
-        {
-            for entry in entries.flatten() {
-                if let Ok(file_type) = entry.file_type()
-                    && file_type.is_dir()
-                    && let Some(name) = entry.file_name().to_str()
-                {
-                    // Skip hidden directories and special directories
-                    if !name.starts_with('.') && name != "tables" {
-                        domains.push(name.to_string());
-                    }
-                }
-            }
-        }
-
-        profiles.push(ProfileInfo {
-            email: email.clone(),
-            domains,
-        });
+        profiles.push(file_profile(&workspace_data_dir, &email_info.email));
     }
 
     info!(
@@ -996,11 +1441,47 @@ pub async fn list_profiles(
     Ok(Json(ProfilesResponse { profiles }))
 }
 
+/// Profile of a file-based workspace, listing the domains (subdirectories) stored for `email`
+fn file_profile(workspace_data_dir: &std::path::Path, email: &str) -> ProfileInfo {
+    let user_workspace = workspace_data_dir.join(workspace_dir_name(email));
+
+    let mut domains = Vec::new();
+    if user_workspace.exists()
+        && let Ok(entries) = std::fs::read_dir(&user_workspace)
+    {
+        for entry in entries.flatten() {
+            if let Ok(file_type) = entry.file_type()
+                && file_type.is_dir()
+                && let Some(name) = entry.file_name().to_str()
+            {
+                // Skip hidden directories and special directories
+                if !name.starts_with('.') && name != "tables" {
+                    domains.push(name.to_string());
+                }
+            }
+        }
+    }
+
+    ProfileInfo {
+        email: email.to_string(),
+        domains,
+    }
+}
+
 /// Helper to get session email from JWT token in headers
 ///
 /// Validates the JWT token and returns the email (subject claim).
-/// Supports both Authorization: Bearer `token` and x-session-id header.
+/// Supports both Authorization: Bearer `token` and x-session-id header, and API keys.
 async fn get_session_email(state: &AppState, headers: &HeaderMap) -> Result<String, StatusCode> {
+    if let Some(local) = local_mode() {
+        return Ok(local.email.clone());
+    }
+
+    // Machine-to-machine clients authenticate with an API key
+    if let Some(key) = authenticate_api_key(state, headers).await? {
+        return Ok(key.owner_email);
+    }
+
     // Initialize JWT service
     let jwt_service = JwtService::from_env();
 
@@ -1036,8 +1517,10 @@ async fn get_session_email(state: &AppState, headers: &HeaderMap) -> Result<Stri
         Ok(uuid) => uuid,
         Err(_) => {
             // Invalid UUID format, try in-memory fallback
-            let sessions = state.session_store.lock().await;
-            if !sessions.contains_key(&claims.session_id) {
+            if touch_session(&state.session_store, &claims.session_id)
+                .await
+                .is_none()
+            {
                 warn!("Session {} not found in store", claims.session_id);
                 return Err(StatusCode::UNAUTHORIZED);
             }
@@ -1068,8 +1551,10 @@ async fn get_session_email(state: &AppState, headers: &HeaderMap) -> Result<Stri
     }
 
     // Fall back to in-memory session store
-    let sessions = state.session_store.lock().await;
-    if !sessions.contains_key(&claims.session_id) {
+    if touch_session(&state.session_store, &claims.session_id)
+        .await
+        .is_none()
+    {
         warn!("Session {} not found in store", claims.session_id);
         return Err(StatusCode::UNAUTHORIZED);
     }
@@ -1085,6 +1570,15 @@ pub async fn get_user_context(
     state: &AppState,
     headers: &HeaderMap,
 ) -> Result<UserContext, StatusCode> {
+    if let Some(local) = local_mode() {
+        return Ok(local.user_context());
+    }
+
+    // Machine-to-machine clients authenticate with an API key
+    if let Some(key) = authenticate_api_key(state, headers).await? {
+        return Ok(key.user_context());
+    }
+
     let jwt_service = JwtService::from_env();
 
     // Try Authorization header first (preferred)
@@ -1121,9 +1615,11 @@ pub async fn get_user_context(
         });
     }
 
-    // For file-based mode, verify session exists in memory store
-    let sessions = state.session_store.lock().await;
-    if !sessions.contains_key(&claims.session_id) {
+    // For file-based mode, verify session exists in memory store; requests renew it
+    if touch_session(&state.session_store, &claims.session_id)
+        .await
+        .is_none()
+    {
         warn!("Session {} not found in store", claims.session_id);
         return Err(StatusCode::UNAUTHORIZED);
     }
@@ -1141,20 +1637,33 @@ pub async fn get_user_context(
 /// Helper to get workspace for a user, creating it if it doesn't exist.
 ///
 /// This uses the storage backend for PostgreSQL mode, or file-based operations otherwise.
-async fn get_or_create_workspace(
+/// Users provisioned into a team workspace get the team's (organization) workspace.
+pub(crate) async fn get_or_create_workspace(
     state: &AppState,
     user_context: &UserContext,
 ) -> Result<StorageWorkspaceInfo, StatusCode> {
+    let email = workspace_email(&user_context.email);
+    let personal = email.eq_ignore_ascii_case(&user_context.email);
     if let Some(storage) = state.storage.as_ref() {
         // Try to get existing workspace
-        match storage.get_workspace_by_email(&user_context.email).await {
+        match storage.get_workspace_by_email(&email).await {
             Ok(Some(workspace)) => return Ok(workspace),
             Ok(None) => {
                 // Create new workspace
-                match storage
-                    .create_workspace(user_context.email.clone(), user_context)
-                    .await
-                {
+                let created = if personal {
+                    storage.create_workspace(email, user_context).await
+                } else {
+                    let name = team_workspace_name(&email);
+                    storage
+                        .create_workspace_with_details(
+                            email,
+                            user_context,
+                            name,
+                            "organization".to_string(),
+                        )
+                        .await
+                };
+                match created {
                     Ok(workspace) => return Ok(workspace),
                     Err(e) => {
                         warn!("Failed to create workspace: {}", e);
@@ -1170,23 +1679,29 @@ async fn get_or_create_workspace(
     }
 
     // File-based fallback: create a synthetic workspace info
-    let sanitized_email = sanitize_email_for_path(&user_context.email);
+    let sanitized_email = sanitize_email_for_path(&email);
     let workspace_id = uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_DNS, sanitized_email.as_bytes());
 
     Ok(StorageWorkspaceInfo {
         id: workspace_id,
         owner_id: user_context.user_id,
-        email: user_context.email.clone(),
-        name: Some(format!(
-            "Workspace {}",
-            user_context.email.split('@').next().unwrap_or("default")
-        )),
-        workspace_type: Some("personal".to_string()),
+        name: Some(if personal {
+            format!("Workspace {}", email.split('@').next().unwrap_or("default"))
+        } else {
+            team_workspace_name(&email)
+        }),
+        email,
+        workspace_type: Some(if personal { "personal" } else { "organization" }.to_string()),
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
     })
 }
 
+/// Default name of a team workspace keyed by `email`
+pub(crate) fn team_workspace_name(email: &str) -> String {
+    format!("Team {}", email.split('@').next().unwrap_or("default"))
+}
+
 /// Helper to get or create a domain in a workspace.
 async fn get_or_create_domain(
     state: &AppState,
@@ -1232,13 +1747,62 @@ async fn get_or_create_domain(
 }
 
 /// Helper to get user workspace path
-fn get_user_workspace_path(email: &str) -> Result<PathBuf, StatusCode> {
+pub(crate) fn get_user_workspace_path(email: &str) -> Result<PathBuf, StatusCode> {
     let workspace_data_dir =
         get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let sanitized_email = sanitize_email_for_path(email);
+    let sanitized_email = workspace_dir_name(email);
     Ok(workspace_data_dir.join(&sanitized_email))
 }
 
+/// Load every domain model of the user's workspace, named after its domain.
+///
+/// Uses the storage backend when configured, otherwise the domain directories. Domains that
+/// fail to load from disk are skipped with a warning.
+pub(crate) async fn load_workspace_models(
+    state: &AppState,
+    user_context: &UserContext,
+) -> Result<Vec<crate::models::DataModel>, StatusCode> {
+    let mut models = Vec::new();
+    if let Some(storage) = state.storage.as_ref() {
+        let workspace = get_or_create_workspace(state, user_context).await?;
+        let domains = storage.get_domains(workspace.id).await.map_err(|e| {
+            warn!("Failed to list domains: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+        for domain in domains {
+            let mut model =
+                crate::models::DataModel::new(domain.name.clone(), String::new(), String::new());
+            model.tables = storage.get_tables(domain.id).await.map_err(|e| {
+                warn!("Failed to list tables of {}: {}", domain.name, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+            model.relationships = storage.get_relationships(domain.id).await.map_err(|e| {
+                warn!("Failed to list relationships of {}: {}", domain.name, e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?;
+            models.push(model);
+        }
+    } else if let Ok(entries) = std::fs::read_dir(get_user_workspace_path(&user_context.email)?) {
+        for entry in entries.flatten() {
+            if let Ok(file_type) = entry.file_type()
+                && file_type.is_dir()
+                && let Some(name) = entry.file_name().to_str()
+                && !name.starts_with('.')
+            {
+                match crate::services::GitService::new().map_git_directory(&entry.path()) {
+                    Ok((mut model, _)) => {
+                        model.name = name.to_string();
+                        models.push(model);
+                    }
+                    Err(e) => warn!("Failed to load domain {}: {}", name, e),
+                }
+            }
+        }
+    }
+    models.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(models)
+}
+
 /// GET /workspace/domains - List all domains for the authenticated user
 #[utoipa::path(
     get,
@@ -1266,12 +1830,20 @@ pub async fn list_domains(
                 let mut domains: Vec<String> =
                     domain_infos.iter().map(|d| d.name.clone()).collect();
                 domains.sort();
+                let descriptions: HashMap<String, String> = domain_infos
+                    .into_iter()
+                    .filter_map(|d| Some((d.name, d.description?)))
+                    .collect();
                 info!(
                     "Listed {} domains for user {} from storage",
                     domains.len(),
                     user_context.email
                 );
-                return Ok(Json(DomainsListResponse { domains }));
+                return Ok(Json(DomainsListResponse::new(
+                    &user_context.email,
+                    domains,
+                    &descriptions,
+                )));
             }
             Err(e) => {
                 warn!("Storage backend failed, falling back to file system: {}", e);
@@ -1306,7 +1878,10 @@ pub async fn list_domains(
         user_context.email
     );
 
-    Ok(Json(DomainsListResponse { domains }))
+    Ok(Json(
+        DomainsListResponse::new(&user_context.email, domains, &HashMap::new())
+            .with_counts(&user_workspace),
+    ))
 }
 
 /// POST /workspace/domains - Create a new domain for the authenticated user
@@ -1365,6 +1940,16 @@ pub async fn create_domain(
                     "Created domain {} for user {} in storage",
                     domain_name, user_context.email
                 );
+                audit_domain_change(
+                    &state,
+                    &user_context,
+                    domain_name,
+                    Some(domain_info.id),
+                    AuditAction::Create,
+                    None,
+                    Some(json!({"name": domain_name})),
+                )
+                .await;
                 return Ok(Json(DomainResponse {
                     domain: domain_name.to_string(),
                     workspace_path,
@@ -1393,6 +1978,17 @@ pub async fn create_domain(
                 "Created domain {} for user {} at {}",
                 domain_name, user_context.email, workspace_path
             );
+            drop(model_service);
+            audit_domain_change(
+                &state,
+                &user_context,
+                domain_name,
+                None,
+                AuditAction::Create,
+                None,
+                Some(json!({"name": domain_name})),
+            )
+            .await;
             Ok(Json(DomainResponse {
                 domain: domain_name.to_string(),
                 workspace_path,
@@ -1406,6 +2002,37 @@ pub async fn create_domain(
     }
 }
 
+/// Record a create, rename or delete of a domain in the workspace's audit log.
+///
+/// File-backed domains have no stored id, so `domain_id` is `None` and the synthetic id of
+/// [`get_or_create_domain`] is recorded.
+async fn audit_domain_change(
+    state: &AppState,
+    user_context: &UserContext,
+    domain: &str,
+    domain_id: Option<Uuid>,
+    action: AuditAction,
+    before: Option<Value>,
+    after: Option<Value>,
+) {
+    let Ok(workspace) = get_or_create_workspace(state, user_context).await else {
+        return;
+    };
+    let entity_id = domain_id.unwrap_or_else(|| Uuid::new_v5(&workspace.id, domain.as_bytes()));
+    audit::record_audit(
+        state,
+        user_context,
+        workspace.id,
+        domain,
+        domain_id.filter(|_| action != AuditAction::Delete),
+        (AuditEntityType::Domain, entity_id),
+        action,
+        before,
+        after,
+    )
+    .await;
+}
+
 /// Domain info response with metadata (for API responses)
 #[derive(Serialize)]
 pub struct DomainInfoResponse {
@@ -1416,6 +2043,9 @@ pub struct DomainInfoResponse {
     imported_table_count: usize,
     created_at: Option<String>,
     modified_at: Option<String>,
+    /// Description, color, icon and owners from `domain.yaml`
+    #[serde(flatten)]
+    metadata: DomainMetadata,
 }
 
 /// Request to update a domain
@@ -1456,6 +2086,11 @@ async fn get_domain(
         let workspace = get_or_create_workspace(&state, &user_context).await?;
 
         if let Ok(Some(domain_info)) = storage.get_domain_by_name(workspace.id, domain_name).await {
+            let mut metadata =
+                domain_metadata::load_domain_metadata(&user_context.email, domain_name);
+            if metadata.description.is_none() {
+                metadata.description = domain_info.description.clone();
+            }
             // Get counts from storage
             let table_count = storage
                 .get_tables(domain_info.id)
@@ -1484,6 +2119,7 @@ async fn get_domain(
                 imported_table_count,
                 created_at: Some(domain_info.created_at.to_rfc3339()),
                 modified_at: Some(domain_info.updated_at.to_rfc3339()),
+                metadata,
             }));
         }
     }
@@ -1496,50 +2132,8 @@ async fn get_domain(
         return Err(StatusCode::NOT_FOUND);
     }
 
-    // Count tables
-    let tables_dir = domain_path.join("tables");
-    let table_count = if tables_dir.exists() {
-        std::fs::read_dir(&tables_dir)
-            .map(|entries| {
-                entries
-                    .filter_map(|e| e.ok())
-                    .filter(|e| {
-                        e.path()
-                            .extension()
-                            .map(|ext| ext == "yaml" || ext == "yml")
-                            .unwrap_or(false)
-                    })
-                    .count()
-            })
-            .unwrap_or(0)
-    } else {
-        0
-    };
-
-    // Count relationships
-    let relationships_file = domain_path.join("relationships.yaml");
-    let relationship_count = if relationships_file.exists() {
-        if let Ok(content) = std::fs::read_to_string(&relationships_file) {
-            // Simple count: number of "- id:" lines
-            content
-                .lines()
-                .filter(|l| l.trim().starts_with("- id:"))
-                .count()
-        } else {
-            0
-        }
-    } else {
-        0
-    };
-
-    // Count imported tables from cross-domain config
-    let cross_domain_path = domain_path.join("cross_domain.yaml");
-    let imported_table_count = if cross_domain_path.exists() {
-        let config = load_cross_domain_config(&cross_domain_path);
-        config.imported_tables.len()
-    } else {
-        0
-    };
+    // Counts come from the domain index, rebuilt only when the domain's files changed
+    let index = DomainIndex::get(&domain_path);
 
     // Get timestamps
     let metadata = std::fs::metadata(&domain_path).ok();
@@ -1554,11 +2148,12 @@ async fn get_domain(
     Ok(Json(DomainInfoResponse {
         name: domain_name.to_string(),
         workspace_path: domain_path.to_string_lossy().to_string(),
-        table_count,
-        relationship_count,
-        imported_table_count,
+        table_count: index.table_count(),
+        relationship_count: index.relationship_count,
+        imported_table_count: index.imported_table_count,
         created_at,
         modified_at,
+        metadata: DomainMetadata::load(&domain_path),
     }))
 }
 
@@ -1635,6 +2230,16 @@ pub async fn update_domain(
                             "Renamed domain {} to {} for user {}",
                             domain_name, new_name, user_context.email
                         );
+                        audit_domain_change(
+                            &state,
+                            &user_context,
+                            new_name,
+                            Some(updated_domain.id),
+                            AuditAction::Update,
+                            Some(json!({"name": domain_name})),
+                            Some(json!({"name": new_name})),
+                        )
+                        .await;
                         return Ok(Json(DomainResponse {
                             domain: new_name.to_string(),
                             workspace_path,
@@ -1706,6 +2311,16 @@ pub async fn update_domain(
             "Renamed domain {} to {} for user {}",
             domain_name, new_name, user_context.email
         );
+        audit_domain_change(
+            &state,
+            &user_context,
+            new_name,
+            None,
+            AuditAction::Update,
+            Some(json!({"name": domain_name})),
+            Some(json!({"name": new_name})),
+        )
+        .await;
 
         return Ok(Json(DomainResponse {
             domain: new_name.to_string(),
@@ -1760,6 +2375,16 @@ pub async fn delete_domain(
                         "Deleted domain {} for user {} from storage",
                         domain_name, user_context.email
                     );
+                    audit_domain_change(
+                        &state,
+                        &user_context,
+                        domain_name,
+                        Some(domain_info.id),
+                        AuditAction::Delete,
+                        Some(json!({"name": domain_name})),
+                        None,
+                    )
+                    .await;
                     return Ok(Json(DomainResponse {
                         domain: domain_name.to_string(),
                         workspace_path,
@@ -1799,6 +2424,16 @@ pub async fn delete_domain(
         "Deleted domain {} for user {}",
         domain_name, user_context.email
     );
+    audit_domain_change(
+        &state,
+        &user_context,
+        domain_name,
+        None,
+        AuditAction::Delete,
+        Some(json!({"name": domain_name})),
+        None,
+    )
+    .await;
 
     Ok(Json(DomainResponse {
         domain: domain_name.to_string(),
@@ -1837,7 +2472,7 @@ pub async fn load_domain(
     // Get workspace path
     let workspace_data_dir =
         get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let sanitized_email = sanitize_email_for_path(&email);
+    let sanitized_email = workspace_dir_name(&email);
     let workspace_path = workspace_data_dir.join(&sanitized_email).join(domain);
 
     // Create tables directory if needed
@@ -1876,47 +2511,22 @@ pub async fn load_domain(
 // Domain-scoped Table CRUD handlers
 // ============================================================================
 
+use crate::api_types::{
+    AddCrossDomainTableRequest, CreateTableRequest, ImportedRelationshipInfo, ImportedTableInfo,
+    UpdateCrossDomainTableRequest,
+};
 use crate::models::enums::{
     DataVaultClassification, DatabaseType, MedallionLayer, ModelingLevel, SCDPattern,
 };
 use crate::models::{Column, Position, Table};
-use serde_json::{Value, json};
-
-/// Request body for creating a table
-#[derive(Debug, Deserialize, ToSchema)]
-pub struct CreateTableRequest {
-    pub name: String,
-    pub columns: Vec<Value>,
-    #[serde(default)]
-    pub database_type: Option<String>,
-    #[serde(default)]
-    pub catalog_name: Option<String>,
-    #[serde(default)]
-    pub schema_name: Option<String>,
-    #[serde(default)]
-    pub medallion_layers: Vec<String>,
-    #[allow(dead_code)]
-    #[serde(default)]
-    pub medallion_layer: Option<String>,
-    #[serde(default)]
-    pub scd_pattern: Option<String>,
-    #[serde(default)]
-    pub data_vault_classification: Option<String>,
-    #[serde(default)]
-    pub modeling_level: Option<String>,
-    #[serde(default)]
-    pub tags: Vec<String>,
-    #[serde(default)]
-    pub odcl_metadata: std::collections::HashMap<String, Value>,
-    #[serde(default)]
-    pub position: Option<Value>,
-}
+use serde_json::{Value, json, value::RawValue};
 
 /// Result of ensuring a domain is loaded, with context for storage operations.
 #[allow(dead_code)]
 /// Context for domain operations
 ///
 /// This struct is public to allow domain-scoped handlers to access domain context.
+#[derive(Clone)]
 pub struct DomainContext {
     /// The domain info (for storage operations).
     pub domain_info: DomainInfo,
@@ -1924,6 +2534,27 @@ pub struct DomainContext {
     pub user_context: UserContext,
     /// The workspace info.
     pub workspace: StorageWorkspaceInfo,
+    /// The session's open draft of the domain, if any
+    pub draft: Option<Arc<Draft>>,
+}
+
+impl DomainContext {
+    /// Storage backend for table and relationship edits; none while a draft is open, so the
+    /// edits go to the draft's working copy.
+    pub fn storage<'a>(&self, state: &'a AppState) -> Option<&'a Arc<dyn StorageBackend>> {
+        match self.draft {
+            Some(_) => None,
+            None => state.storage.as_ref(),
+        }
+    }
+
+    /// Model service holding the domain: the draft's working copy while a draft is open.
+    pub async fn model_service(&self, state: &AppState) -> OwnedMutexGuard<ModelService> {
+        match &self.draft {
+            Some(draft) => draft.working.clone().lock_owned().await,
+            None => state.model_service.clone().lock_owned().await,
+        }
+    }
 }
 
 /// Helper to ensure domain is loaded for the current session.
@@ -1939,9 +2570,44 @@ pub async fn ensure_domain_loaded(
     ensure_domain_loaded_with_reload(state, headers, domain, false).await
 }
 
+/// Load a file-backed domain into the model service, optionally forcing a reload from disk.
+pub(crate) fn load_domain_model<'a>(
+    model_service: &'a mut ModelService,
+    email: &str,
+    domain: &str,
+    force_reload: bool,
+) -> Result<&'a crate::models::DataModel, StatusCode> {
+    let workspace_data_dir =
+        get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    let sanitized_email = workspace_dir_name(email);
+    let workspace_path = workspace_data_dir.join(&sanitized_email).join(domain);
+
+    // Create tables directory if needed
+    let tables_dir = workspace_path.join("tables");
+    if let Err(e) = std::fs::create_dir_all(&tables_dir) {
+        warn!("Failed to create workspace directory: {}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+    }
+
+    model_service
+        .load_or_create_model_with_reload(
+            format!("Workspace for {} - {}", email, domain),
+            workspace_path,
+            Some(format!("User workspace for {} in domain {}", email, domain)),
+            force_reload,
+        )
+        .map_err(|e| {
+            warn!("Failed to load domain {}: {}", domain, e);
+            StatusCode::NOT_FOUND
+        })?;
+    model_service
+        .get_current_model()
+        .ok_or(StatusCode::NOT_FOUND)
+}
+
 /// Helper to ensure domain is loaded with option to force reload from disk.
 /// Returns the domain context for storage operations.
-async fn ensure_domain_loaded_with_reload(
+pub(crate) async fn ensure_domain_loaded_with_reload(
     state: &AppState,
     headers: &HeaderMap,
     domain: &str,
@@ -1957,39 +2623,28 @@ async fn ensure_domain_loaded_with_reload(
     // For file-based storage, also load the model service
     if state.storage.is_none() || !state.is_postgres() {
         let mut model_service = state.model_service.lock().await;
-        let workspace_data_dir =
-            get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-        let sanitized_email = sanitize_email_for_path(&user_context.email);
-        let workspace_path = workspace_data_dir.join(&sanitized_email).join(domain);
-
-        // Create tables directory if needed
-        let tables_dir = workspace_path.join("tables");
-        if let Err(e) = std::fs::create_dir_all(&tables_dir) {
-            warn!("Failed to create workspace directory: {}", e);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
-
-        // Load model, optionally forcing reload from disk
-        let _ = model_service
-            .load_or_create_model_with_reload(
-                format!("Workspace for {} - {}", user_context.email, domain),
-                workspace_path,
-                Some(format!(
-                    "User workspace for {} in domain {}",
-                    user_context.email, domain
-                )),
-                force_reload, // Force reload if requested (e.g., for relationship operations to get latest tables)
-            )
-            .map_err(|e| {
-                warn!("Failed to load domain {}: {}", domain, e);
-                StatusCode::NOT_FOUND
-            })?;
+        let model = load_domain_model(
+            &mut model_service,
+            &user_context.email,
+            domain,
+            force_reload, // Force reload if requested (e.g., for relationship operations to get latest tables)
+        )?;
+        state
+            .telemetry
+            .record_model_size(domain_info.id, model.tables.len());
     }
 
+    let draft = drafts::session_id(headers).and_then(|session_id| {
+        state
+            .drafts
+            .get(&DraftStore::key(&session_id, &user_context.email, domain))
+    });
+
     Ok(DomainContext {
         domain_info,
         user_context,
         workspace,
+        draft,
     })
 }
 
@@ -2022,61 +2677,26 @@ pub struct DomainExportPath {
 
 /// Helper function to serialize table with database_type as "PostgreSQL" instead of "POSTGRES"
 /// and medallion_layers with proper capitalization
-fn serialize_table_with_database_type(table: &crate::models::table::Table) -> Value {
-    let mut table_json = serde_json::to_value(table).unwrap_or(json!({}));
-
-    // Convert database_type from enum to display string
-    if let Some(db_type) = table.database_type {
-        let db_type_str = match db_type {
-            DatabaseType::Postgres => "PostgreSQL",
-            DatabaseType::Mysql => "MySQL",
-            DatabaseType::SqlServer => "SQL Server",
-            DatabaseType::DatabricksDelta => "Databricks Delta",
-            DatabaseType::DatabricksIceberg => "Databricks Iceberg",
-            DatabaseType::AwsGlue => "AWS Glue",
-            DatabaseType::DatabricksLakebase => "Databricks Lakebase",
-            DatabaseType::Dynamodb => "DynamoDB",
-            DatabaseType::Cassandra => "Cassandra",
-            DatabaseType::Kafka => "Kafka",
-            DatabaseType::Pulsar => "Pulsar",
-        };
-        table_json["database_type"] = json!(db_type_str);
-    }
-
-    // Convert medallion_layers to capitalized strings
-    if let Some(layers) = table_json
-        .get("medallion_layers")
-        .and_then(|v| v.as_array())
-    {
-        let capitalized_layers: Vec<Value> = layers
-            .iter()
-            .filter_map(|v| v.as_str())
-            .map(|s| match s.to_lowercase().as_str() {
-                "bronze" => "Bronze",
-                "silver" => "Silver",
-                "gold" => "Gold",
-                "operational" => "Operational",
-                _ => s,
-            })
-            .map(|s| json!(s))
-            .collect();
-        table_json["medallion_layers"] = json!(capitalized_layers);
-    }
-
-    table_json
+pub(crate) fn serialize_table_with_database_type(table: &crate::models::table::Table) -> Value {
+    serde_json::to_value(TableView::new(table)).unwrap_or(json!({}))
 }
 
 /// GET /workspace/domains/{domain}/tables - Get all tables in a domain
+///
+/// With `filter`, only tables matching that saved filter are returned. With `as_of`, the
+/// tables are read-only reconstructions of the domain at that time from its event log.
 #[utoipa::path(
     get,
     path = "/workspace/domains/{domain}/tables",
     tag = "Tables",
     params(
-        ("domain" = String, Path, description = "Domain name")
+        ("domain" = String, Path, description = "Domain name"),
+        saved_filters::SavedFilterQuery,
+        history::AsOfQuery
     ),
     responses(
         (status = 200, description = "List of tables retrieved successfully", body = Object),
-        (status = 404, description = "Domain not found"),
+        (status = 404, description = "Domain or saved filter not found"),
         (status = 401, description = "Unauthorized - invalid or missing token")
     ),
     security(("bearer_auth" = []))
@@ -2085,19 +2705,39 @@ pub async fn get_domain_tables(
     State(state): State<AppState>,
     headers: HeaderMap,
     axum::extract::Path(path): axum::extract::Path<DomainPath>,
-) -> Result<Json<Value>, StatusCode> {
+    axum::extract::Query(query): axum::extract::Query<saved_filters::SavedFilterQuery>,
+    axum::extract::Query(history): axum::extract::Query<history::AsOfQuery>,
+) -> Result<Response, StatusCode> {
     let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let criteria =
+        saved_filters::resolve_saved_filter(&ctx.user_context.email, &path.domain, &query)?;
+    let selected = |table: &&Table| criteria.as_ref().is_none_or(|c| c.matches(table));
+
+    // Past tables have no profiles to show
+    if let Some(as_of) = history.as_of {
+        let tables = history::tables_as_of(&state, &ctx, &path.domain, as_of).await?;
+        return Ok(Json(TablesView {
+            tables: tables.iter().filter(selected).map(TableView::new).collect(),
+        })
+        .into_response());
+    }
+    let profiles = table_profiles::load_table_profiles(&ctx.user_context.email, &path.domain);
+    // Tables are serialized straight from the model into the response body
+    let respond = |tables: &[Table]| {
+        Json(TablesView {
+            tables: tables
+                .iter()
+                .filter(selected)
+                .map(|t| TableView::new(t).with_profile(profiles.latest(t.id)))
+                .collect(),
+        })
+        .into_response()
+    };
 
     // Try storage backend first (PostgreSQL)
-    if let Some(storage) = state.storage.as_ref() {
+    if let Some(storage) = ctx.storage(&state) {
         match storage.get_tables(ctx.domain_info.id).await {
-            Ok(tables) => {
-                let tables_json: Vec<Value> = tables
-                    .iter()
-                    .map(serialize_table_with_database_type)
-                    .collect();
-                return Ok(Json(json!({"tables": tables_json})));
-            }
+            Ok(tables) => return Ok(respond(&tables)),
             Err(e) => {
                 warn!("Storage backend failed, falling back to file system: {}", e);
             }
@@ -2105,19 +2745,11 @@ pub async fn get_domain_tables(
     }
 
     // File-based fallback
-    let model_service = state.model_service.lock().await;
-    let model = match model_service.get_current_model() {
-        Some(m) => m,
-        None => return Ok(Json(json!({"tables": []}))),
-    };
-
-    let tables_json: Vec<Value> = model
-        .tables
-        .iter()
-        .map(serialize_table_with_database_type)
-        .collect();
-
-    Ok(Json(json!({"tables": tables_json})))
+    let model_service = ctx.model_service(&state).await;
+    Ok(match model_service.get_current_model() {
+        Some(model) => respond(&model.tables),
+        None => respond(&[]),
+    })
 }
 
 /// POST /workspace/domains/{domain}/tables - Create a new table in a domain
@@ -2133,6 +2765,7 @@ pub async fn get_domain_tables(
         (status = 200, description = "Table created successfully", body = Object),
         (status = 400, description = "Bad request - invalid table data"),
         (status = 404, description = "Domain not found"),
+        (status = 422, description = "Model size limit exceeded or invalid custom metadata", body = crate::api_types::ErrorResponse),
         (status = 401, description = "Unauthorized - invalid or missing token"),
         (status = 500, description = "Internal server error")
     ),
@@ -2143,14 +2776,14 @@ pub async fn create_domain_table(
     headers: HeaderMap,
     axum::extract::Path(path): axum::extract::Path<DomainPath>,
     request: Result<Json<CreateTableRequest>, axum::extract::rejection::JsonRejection>,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     let request = request.map_err(|_| StatusCode::BAD_REQUEST)?;
     let request = request.0;
     let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
 
     // Validate required fields
     if request.name.trim().is_empty() || request.columns.is_empty() {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into());
     }
 
     // Parse columns
@@ -2183,8 +2816,14 @@ pub async fn create_domain_table(
     }
 
     if columns.is_empty() {
-        return Err(StatusCode::BAD_REQUEST);
+        return Err(StatusCode::BAD_REQUEST.into());
     }
+    state
+        .model_limits
+        .check_columns(request.name.trim(), columns.len())?;
+    state
+        .model_limits
+        .check_table_count(usage::domain_table_count(&state, &ctx).await, 1)?;
 
     // Parse medallion layers
     let medallion_layers = if !request.medallion_layers.is_empty() {
@@ -2263,7 +2902,7 @@ pub async fn create_domain_table(
         }
     });
 
-    let table = Table {
+    let mut table = Table {
         id: Uuid::new_v4(),
         name: request.name.trim().to_string(),
         columns,
@@ -2275,6 +2914,7 @@ pub async fn create_domain_table(
         data_vault_classification,
         modeling_level,
         tags: request.tags,
+        aliases: request.aliases,
         odcl_metadata: request.odcl_metadata,
         position,
         yaml_file_path: None,
@@ -2284,15 +2924,28 @@ pub async fn create_domain_table(
         created_at: chrono::Utc::now(),
         updated_at: chrono::Utc::now(),
     };
+    standard_columns::apply_standard_columns(std::slice::from_mut(&mut table));
+    metadata_schema::enforce_metadata_schema(Some(&table.odcl_metadata), Some(&table.columns))?;
 
     // Try storage backend first (PostgreSQL)
-    if let Some(storage) = state.storage.as_ref() {
+    if let Some(storage) = ctx.storage(&state) {
         match storage
             .create_table(ctx.domain_info.id, table.clone(), &ctx.user_context)
             .await
         {
             Ok(created_table) => {
-                return Ok(Json(serialize_table_with_database_type(&created_table)));
+                let created = serialize_table_with_database_type(&created_table);
+                events::record_model_change(
+                    &state,
+                    &ctx,
+                    &path.domain,
+                    ChangeEventType::TableCreated,
+                    created_table.id,
+                    None,
+                    Some(created.clone()),
+                )
+                .await;
+                return Ok(Json(created));
             }
             Err(e) => {
                 warn!("Storage backend failed, falling back to file system: {}", e);
@@ -2301,12 +2954,26 @@ pub async fn create_domain_table(
     }
 
     // File-based fallback
-    let mut model_service = state.model_service.lock().await;
+    let mut model_service = ctx.model_service(&state).await;
     match model_service.add_table(table.clone()) {
-        Ok(added_table) => Ok(Json(serialize_table_with_database_type(&added_table))),
+        Ok(added_table) => {
+            drop(model_service);
+            let created = serialize_table_with_database_type(&added_table);
+            events::record_model_change(
+                &state,
+                &ctx,
+                &path.domain,
+                ChangeEventType::TableCreated,
+                added_table.id,
+                None,
+                Some(created.clone()),
+            )
+            .await;
+            Ok(Json(created))
+        }
         Err(e) => {
             warn!("Failed to add table: {}", e);
-            Err(StatusCode::INTERNAL_SERVER_ERROR)
+            Err(StatusCode::INTERNAL_SERVER_ERROR.into())
         }
     }
 }
@@ -2328,16 +2995,26 @@ pub async fn create_domain_table(
     ),
     security(("bearer_auth" = []))
 )]
-async fn get_domain_table(
+pub async fn get_domain_table(
     State(state): State<AppState>,
     headers: HeaderMap,
     axum::extract::Path(path): axum::extract::Path<DomainTablePath>,
 ) -> Result<Json<Value>, StatusCode> {
     let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
     let table_uuid = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    let respond = |table: &crate::models::table::Table| {
+        let mut table_json = [serialize_table_with_database_type(table)];
+        table_profiles::attach_latest_profiles(
+            &ctx.user_context.email,
+            &path.domain,
+            &mut table_json,
+        );
+        let [table_json] = table_json;
+        Json(table_json)
+    };
 
     // Try storage backend first (PostgreSQL)
-    if let Some(storage) = state.storage.as_ref() {
+    if let Some(storage) = ctx.storage(&state) {
         match storage.get_table(ctx.domain_info.id, table_uuid).await {
             Ok(Some(table)) => {
                 // Verify table belongs to this domain
@@ -2345,14 +3022,14 @@ async fn get_domain_table(
                 match storage.get_tables(ctx.domain_info.id).await {
                     Ok(tables) => {
                         if tables.iter().any(|t| t.id == table_uuid) {
-                            return Ok(Json(serialize_table_with_database_type(&table)));
+                            return Ok(respond(&table));
                         } else {
                             return Err(StatusCode::NOT_FOUND);
                         }
                     }
                     Err(_) => {
                         // If we can't verify, return the table anyway (it was found by ID)
-                        return Ok(Json(serialize_table_with_database_type(&table)));
+                        return Ok(respond(&table));
                     }
                 }
             }
@@ -2365,11 +3042,11 @@ async fn get_domain_table(
 
     // File-based fallback
     // ctx already ensures domain is loaded, so model_service should have the model
-    let model_service = state.model_service.lock().await;
+    let model_service = ctx.model_service(&state).await;
     let table = model_service
         .get_table(table_uuid)
         .ok_or(StatusCode::NOT_FOUND)?;
-    Ok(Json(serialize_table_with_database_type(table)))
+    Ok(respond(table))
 }
 
 /// PUT /workspace/domains/{domain}/tables/{table_id} - Update a table
@@ -2379,14 +3056,18 @@ async fn get_domain_table(
     tag = "Tables",
     params(
         ("domain" = String, Path, description = "Domain name"),
-        ("table_id" = String, Path, description = "Table UUID")
+        ("table_id" = String, Path, description = "Table UUID"),
+        evolution_policy::PolicyOverrideQuery
     ),
     request_body(content = Object, description = "Table update fields"),
     responses(
         (status = 200, description = "Table updated successfully", body = Object),
         (status = 404, description = "Table not found"),
         (status = 400, description = "Bad request - invalid table ID or update data"),
-        (status = 401, description = "Unauthorized - invalid or missing token")
+        (status = 401, description = "Unauthorized - invalid or missing token"),
+        (status = 403, description = "Only domain owners can override an evolution policy", body = crate::api_types::ErrorResponse),
+        (status = 409, description = "Version conflict, or the change violates the table's evolution policy", body = crate::api_types::ErrorResponse),
+        (status = 422, description = "Model size limit exceeded or invalid custom metadata", body = crate::api_types::ErrorResponse)
     ),
     security(("bearer_auth" = []))
 )]
@@ -2394,20 +3075,55 @@ pub async fn update_domain_table(
     State(state): State<AppState>,
     headers: HeaderMap,
     axum::extract::Path(path): axum::extract::Path<DomainTablePath>,
+    axum::extract::Query(query): axum::extract::Query<evolution_policy::PolicyOverrideQuery>,
     Json(updates): Json<Value>,
-) -> Result<Json<Value>, StatusCode> {
+) -> Result<Json<Value>, ApiError> {
     let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
     let table_uuid = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
+    if let Some(columns) = updates.get("columns").and_then(Value::as_array) {
+        let name = updates
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or(&path.table_id);
+        state.model_limits.check_columns(name, columns.len())?;
+    }
+    let new_columns = updates
+        .get("columns")
+        .and_then(|columns| serde_json::from_value::<Vec<Column>>(columns.clone()).ok());
 
     // Try storage backend first (PostgreSQL)
-    if let Some(storage) = state.storage.as_ref() {
+    if let Some(storage) = ctx.storage(&state) {
         // Get existing table
         match storage.get_table(ctx.domain_info.id, table_uuid).await {
             Ok(Some(mut table)) => {
+                let before = serialize_table_with_database_type(&table);
+                if let Some(columns) = new_columns.as_deref() {
+                    evolution_policy::enforce_evolution_policy(
+                        &ctx,
+                        &path.domain,
+                        table_uuid,
+                        &table.columns,
+                        columns,
+                        query.override_policy,
+                    )?;
+                }
+                let metadata = metadata_schema::merged_metadata(&table.odcl_metadata, &updates);
+                metadata_schema::enforce_metadata_schema(
+                    metadata.as_ref(),
+                    new_columns.as_deref(),
+                )?;
+                if let Some(metadata) = metadata {
+                    table.odcl_metadata = metadata;
+                }
                 // Apply updates to the table
                 if let Some(name) = updates.get("name").and_then(|v| v.as_str()) {
                     table.name = name.to_string();
                 }
+                if let Some(aliases) = updates.get("aliases")
+                    && let Ok(aliases) = serde_json::from_value::<Vec<String>>(aliases.clone())
+                {
+                    table.aliases = aliases;
+                }
                 if let Some(columns) = updates.get("columns")
                     && let Ok(parsed_columns) =
                         serde_json::from_value::<Vec<Column>>(columns.clone())
@@ -2435,18 +3151,29 @@ pub async fn update_domain_table(
                     .await
                 {
                     Ok(updated_table) => {
-                        return Ok(Json(serialize_table_with_database_type(&updated_table)));
+                        let updated = serialize_table_with_database_type(&updated_table);
+                        events::record_model_change(
+                            &state,
+                            &ctx,
+                            &path.domain,
+                            ChangeEventType::TableUpdated,
+                            table_uuid,
+                            Some(before),
+                            Some(updated.clone()),
+                        )
+                        .await;
+                        return Ok(Json(updated));
                     }
                     Err(StorageError::VersionConflict { .. }) => {
-                        return Err(StatusCode::CONFLICT);
+                        return Err(StatusCode::CONFLICT.into());
                     }
                     Err(e) => {
                         warn!("Storage backend failed: {}", e);
-                        return Err(StatusCode::INTERNAL_SERVER_ERROR);
+                        return Err(StatusCode::INTERNAL_SERVER_ERROR.into());
                     }
                 }
             }
-            Ok(None) => return Err(StatusCode::NOT_FOUND),
+            Ok(None) => return Err(StatusCode::NOT_FOUND.into()),
             Err(e) => {
                 warn!("Storage backend failed, falling back to file system: {}", e);
             }
@@ -2454,13 +3181,44 @@ pub async fn update_domain_table(
     }
 
     // File-based fallback
-    let mut model_service = state.model_service.lock().await;
+    let mut model_service = ctx.model_service(&state).await;
+    if let (Some(table), Some(columns)) = (model_service.get_table(table_uuid), &new_columns) {
+        evolution_policy::enforce_evolution_policy(
+            &ctx,
+            &path.domain,
+            table_uuid,
+            &table.columns,
+            columns,
+            query.override_policy,
+        )?;
+    }
+    if let Some(table) = model_service.get_table(table_uuid) {
+        let metadata = metadata_schema::merged_metadata(&table.odcl_metadata, &updates);
+        metadata_schema::enforce_metadata_schema(metadata.as_ref(), new_columns.as_deref())?;
+    }
+    let before = model_service
+        .get_table(table_uuid)
+        .map(serialize_table_with_database_type);
     match model_service.update_table(table_uuid, &updates) {
-        Ok(Some(table)) => Ok(Json(serialize_table_with_database_type(&table))),
-        Ok(None) => Err(StatusCode::NOT_FOUND),
+        Ok(Some(table)) => {
+            drop(model_service);
+            let updated = serialize_table_with_database_type(&table);
+            events::record_model_change(
+                &state,
+                &ctx,
+                &path.domain,
+                ChangeEventType::TableUpdated,
+                table_uuid,
+                before,
+                Some(updated.clone()),
+            )
+            .await;
+            Ok(Json(updated))
+        }
+        Ok(None) => Err(StatusCode::NOT_FOUND.into()),
         Err(e) => {
             warn!("Failed to update table: {}", e);
-            Err(StatusCode::BAD_REQUEST)
+            Err(StatusCode::BAD_REQUEST.into())
         }
     }
 }
@@ -2491,14 +3249,14 @@ pub async fn delete_domain_table(
     let table_uuid = Uuid::parse_str(&path.table_id).map_err(|_| StatusCode::BAD_REQUEST)?;
 
     // Try storage backend first (PostgreSQL)
-    if let Some(storage) = state.storage.as_ref() {
+    if let Some(storage) = ctx.storage(&state) {
         // Verify table exists and belongs to this domain before deleting
+        let mut before = None;
         match storage.get_tables(ctx.domain_info.id).await {
-            Ok(tables) => {
-                if !tables.iter().any(|t| t.id == table_uuid) {
-                    return Err(StatusCode::NOT_FOUND);
-                }
-            }
+            Ok(tables) => match tables.iter().find(|t| t.id == table_uuid) {
+                Some(table) => before = Some(serialize_table_with_database_type(table)),
+                None => return Err(StatusCode::NOT_FOUND),
+            },
             Err(_) => {
                 // If we can't verify, continue with delete attempt
             }
@@ -2509,6 +3267,16 @@ pub async fn delete_domain_table(
             .await
         {
             Ok(()) => {
+                events::record_model_change(
+                    &state,
+                    &ctx,
+                    &path.domain,
+                    ChangeEventType::TableDeleted,
+                    table_uuid,
+                    before,
+                    None,
+                )
+                .await;
                 return Ok(Json(json!({"message": "Table deleted successfully"})));
             }
             Err(StorageError::NotFound { .. }) => {
@@ -2522,13 +3290,29 @@ pub async fn delete_domain_table(
 
     // File-based fallback
     // ctx already ensures domain is loaded, so model_service should have the model
-    let mut model_service = state.model_service.lock().await;
+    let mut model_service = ctx.model_service(&state).await;
     // Check if table exists first
-    if model_service.get_table(table_uuid).is_none() {
+    let Some(before) = model_service
+        .get_table(table_uuid)
+        .map(serialize_table_with_database_type)
+    else {
         return Err(StatusCode::NOT_FOUND);
-    }
+    };
     match model_service.delete_table(table_uuid) {
-        Ok(true) => Ok(Json(json!({"message": "Table deleted successfully"}))),
+        Ok(true) => {
+            drop(model_service);
+            events::record_model_change(
+                &state,
+                &ctx,
+                &path.domain,
+                ChangeEventType::TableDeleted,
+                table_uuid,
+                Some(before),
+                None,
+            )
+            .await;
+            Ok(Json(json!({"message": "Table deleted successfully"})))
+        }
         Ok(false) => Err(StatusCode::NOT_FOUND),
         Err(_) => Err(StatusCode::BAD_REQUEST),
     }
@@ -2555,6 +3339,15 @@ pub struct CreateRelationshipRequest {
     pub etl_job_metadata: Option<Value>,
     #[serde(default)]
     pub relationship_type: Option<String>,
+    /// Role of the source table, e.g. "report" for `employee.manager_id -> employee.id`
+    #[serde(default)]
+    pub source_role: Option<String>,
+    /// Role of the target table, e.g. "manager"
+    #[serde(default)]
+    pub target_role: Option<String>,
+    /// Create the relationship even if it breaks the domain's relationship rules (audited)
+    #[serde(default)]
+    pub bypass_rules: bool,
 }
 
 /// Request to update a relationship
@@ -2574,6 +3367,15 @@ pub struct UpdateRelationshipRequest {
     pub relationship_type: Option<String>,
     #[serde(default)]
     pub notes: Option<String>,
+    /// New source role; an empty string clears it
+    #[serde(default)]
+    pub source_role: Option<String>,
+    /// New target role; an empty string clears it
+    #[serde(default)]
+    pub target_role: Option<String>,
+    /// Apply a type change even if it breaks the domain's relationship rules (audited)
+    #[serde(default)]
+    pub bypass_rules: bool,
 }
 
 /// GET /workspace/domains/{domain}/relationships - Get all relationships in a domain
@@ -2599,7 +3401,7 @@ pub async fn get_domain_relationships(
     let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
 
     // Try storage backend first (PostgreSQL)
-    if let Some(storage) = state.storage.as_ref() {
+    if let Some(storage) = ctx.storage(&state) {
         match storage.get_relationships(ctx.domain_info.id).await {
             Ok(relationships) => {
                 let relationships_json: Vec<Value> = relationships
@@ -2615,7 +3417,7 @@ pub async fn get_domain_relationships(
     }
 
     // File-based fallback
-    let model_service = state.model_service.lock().await;
+    let model_service = ctx.model_service(&state).await;
     let model = match model_service.get_current_model() {
         Some(m) => m,
         None => return Ok(Json(json!([]))),
@@ -2685,6 +3487,19 @@ pub async fn create_domain_relationship(
             _ => None,
         });
 
+    relationship_rules::enforce_relationship_rules(
+        &state,
+        &ctx.user_context.email,
+        &path.domain,
+        relationship_rules::RelationshipChange::Create {
+            source_table_id,
+            target_table_id,
+            relationship_type,
+        },
+        request.bypass_rules,
+    )
+    .await?;
+
     let foreign_key_details = request
         .foreign_key_details
         .as_ref()
@@ -2696,12 +3511,17 @@ pub async fn create_domain_relationship(
         .and_then(|v| serde_json::from_value::<ETLJobMetadata>(v.clone()).ok());
 
     // Try storage backend first (PostgreSQL)
-    if let Some(storage) = state.storage.as_ref() {
+    if let Some(storage) = ctx.storage(&state) {
         // Check for duplicate
         match storage.get_relationships(ctx.domain_info.id).await {
             Ok(relationships) => {
                 if relationships.iter().any(|r| {
-                    r.source_table_id == source_table_id && r.target_table_id == target_table_id
+                    r.same_endpoints(
+                        source_table_id,
+                        target_table_id,
+                        request.source_role.as_deref(),
+                        request.target_role.as_deref(),
+                    )
                 }) {
                     return Err(StatusCode::CONFLICT);
                 }
@@ -2719,6 +3539,8 @@ pub async fn create_domain_relationship(
             cardinality,
             source_optional: Some(false),
             target_optional: Some(false),
+            source_role: request.source_role.clone(),
+            target_role: request.target_role.clone(),
             foreign_key_details: foreign_key_details.clone(),
             etl_job_metadata: etl_job_metadata.clone(),
             relationship_type,
@@ -2734,9 +3556,18 @@ pub async fn create_domain_relationship(
             .await
         {
             Ok(created_relationship) => {
-                return Ok(Json(
-                    serde_json::to_value(created_relationship).unwrap_or(json!({})),
-                ));
+                let created = serde_json::to_value(&created_relationship).unwrap_or(json!({}));
+                events::record_model_change(
+                    &state,
+                    &ctx,
+                    &path.domain,
+                    ChangeEventType::RelationshipCreated,
+                    created_relationship.id,
+                    None,
+                    Some(created.clone()),
+                )
+                .await;
+                return Ok(Json(created));
             }
             Err(e) => {
                 warn!("Storage backend failed, falling back to file system: {}", e);
@@ -2745,7 +3576,7 @@ pub async fn create_domain_relationship(
     }
 
     // File-based fallback
-    let mut model_service = state.model_service.lock().await;
+    let mut model_service = ctx.model_service(&state).await;
     let model = model_service
         .get_current_model_mut()
         .ok_or(StatusCode::BAD_REQUEST)?;
@@ -2760,12 +3591,15 @@ pub async fn create_domain_relationship(
         return Err(StatusCode::BAD_REQUEST);
     }
 
-    // Check for duplicate
-    if model
-        .relationships
-        .iter()
-        .any(|r| r.source_table_id == source_table_id && r.target_table_id == target_table_id)
-    {
+    // Check for duplicate (role names distinguish relationships between the same tables)
+    if model.relationships.iter().any(|r| {
+        r.same_endpoints(
+            source_table_id,
+            target_table_id,
+            request.source_role.as_deref(),
+            request.target_role.as_deref(),
+        )
+    }) {
         return Err(StatusCode::BAD_REQUEST);
     }
 
@@ -2789,7 +3623,9 @@ pub async fn create_domain_relationship(
         etl_job_metadata,
         relationship_type,
     ) {
-        Ok(relationship) => {
+        Ok(mut relationship) => {
+            relationship.source_role = request.source_role.clone();
+            relationship.target_role = request.target_role.clone();
             model.relationships.push(relationship.clone());
 
             // Auto-save relationships to YAML file (similar to how tables are auto-saved)
@@ -2815,10 +3651,20 @@ pub async fn create_domain_relationship(
                     }
                 }
             }
-
-            Ok(Json(
-                serde_json::to_value(relationship).unwrap_or(json!({})),
-            ))
+            drop(model_service);
+
+            let created = serde_json::to_value(&relationship).unwrap_or(json!({}));
+            events::record_model_change(
+                &state,
+                &ctx,
+                &path.domain,
+                ChangeEventType::RelationshipCreated,
+                relationship.id,
+                None,
+                Some(created.clone()),
+            )
+            .await;
+            Ok(Json(created))
         }
         Err(_) => Err(StatusCode::BAD_REQUEST),
     }
@@ -2851,7 +3697,7 @@ async fn get_domain_relationship(
         Uuid::parse_str(&path.relationship_id).map_err(|_| StatusCode::BAD_REQUEST)?;
 
     // Try storage backend first (PostgreSQL)
-    if let Some(storage) = state.storage.as_ref() {
+    if let Some(storage) = ctx.storage(&state) {
         match storage
             .get_relationship(ctx.domain_info.id, relationship_uuid)
             .await
@@ -2869,7 +3715,7 @@ async fn get_domain_relationship(
     }
 
     // File-based fallback
-    let model_service = state.model_service.lock().await;
+    let model_service = ctx.model_service(&state).await;
     let model = model_service
         .get_current_model()
         .ok_or(StatusCode::BAD_REQUEST)?;
@@ -2938,6 +3784,20 @@ pub async fn update_domain_relationship(
             _ => None,
         });
 
+    if let Some(relationship_type) = relationship_type {
+        relationship_rules::enforce_relationship_rules(
+            &state,
+            &ctx.user_context.email,
+            &path.domain,
+            relationship_rules::RelationshipChange::Update {
+                relationship_id: relationship_uuid,
+                relationship_type,
+            },
+            request.bypass_rules,
+        )
+        .await?;
+    }
+
     let foreign_key_details = request
         .foreign_key_details
         .as_ref()
@@ -2949,12 +3809,13 @@ pub async fn update_domain_relationship(
         .and_then(|v| serde_json::from_value::<ETLJobMetadata>(v.clone()).ok());
 
     // Try storage backend first (PostgreSQL)
-    if let Some(storage) = state.storage.as_ref() {
+    if let Some(storage) = ctx.storage(&state) {
         match storage
             .get_relationship(ctx.domain_info.id, relationship_uuid)
             .await
         {
             Ok(Some(mut relationship)) => {
+                let before = serde_json::to_value(&relationship).ok();
                 // Apply updates
                 if request.cardinality.is_some() {
                     relationship.cardinality = cardinality;
@@ -2981,6 +3842,12 @@ pub async fn update_domain_relationship(
                         Some(notes.clone())
                     };
                 }
+                if let Some(ref role) = request.source_role {
+                    relationship.source_role = (!role.is_empty()).then(|| role.clone());
+                }
+                if let Some(ref role) = request.target_role {
+                    relationship.target_role = (!role.is_empty()).then(|| role.clone());
+                }
                 relationship.updated_at = chrono::Utc::now();
 
                 match storage
@@ -2988,9 +3855,19 @@ pub async fn update_domain_relationship(
                     .await
                 {
                     Ok(updated_relationship) => {
-                        return Ok(Json(
-                            serde_json::to_value(updated_relationship).unwrap_or(json!({})),
-                        ));
+                        let updated =
+                            serde_json::to_value(&updated_relationship).unwrap_or(json!({}));
+                        events::record_model_change(
+                            &state,
+                            &ctx,
+                            &path.domain,
+                            ChangeEventType::RelationshipUpdated,
+                            relationship_uuid,
+                            before,
+                            Some(updated.clone()),
+                        )
+                        .await;
+                        return Ok(Json(updated));
                     }
                     Err(e) => {
                         warn!("Storage backend failed: {}", e);
@@ -3006,30 +3883,35 @@ pub async fn update_domain_relationship(
     }
 
     // File-based fallback
-    let mut model_service = state.model_service.lock().await;
+    let mut model_service = ctx.model_service(&state).await;
     let model = model_service
         .get_current_model_mut()
         .ok_or(StatusCode::BAD_REQUEST)?;
 
+    let before = model
+        .relationships
+        .iter()
+        .find(|r| r.id == relationship_uuid)
+        .and_then(|r| serde_json::to_value(r).ok());
     let mut rel_service = RelationshipService::new(Some(model.clone()));
 
     // Parse cardinality with Option<Option<Cardinality>> semantics for file-based fallback
-    let cardinality_option: Option<Option<Cardinality>> = if request.cardinality.is_some() {
-        let card_value = request.cardinality.as_ref().unwrap();
-        if card_value.is_empty() {
-            Some(None)
+    let cardinality_option: Option<Option<Cardinality>> =
+        if let Some(card_value) = &request.cardinality {
+            if card_value.is_empty() {
+                Some(None)
+            } else {
+                Some(match card_value.as_str() {
+                    "OneToOne" => Some(Cardinality::OneToOne),
+                    "OneToMany" => Some(Cardinality::OneToMany),
+                    "ManyToOne" => Some(Cardinality::ManyToOne),
+                    "ManyToMany" => Some(Cardinality::ManyToMany),
+                    _ => None,
+                })
+            }
         } else {
-            Some(match card_value.as_str() {
-                "OneToOne" => Some(Cardinality::OneToOne),
-                "OneToMany" => Some(Cardinality::OneToMany),
-                "ManyToOne" => Some(Cardinality::ManyToOne),
-                "ManyToMany" => Some(Cardinality::ManyToMany),
-                _ => None,
-            })
-        }
-    } else {
-        None
-    };
+            None
+        };
 
     rel_service.set_model(model.clone());
 
@@ -3038,6 +3920,8 @@ pub async fn update_domain_relationship(
         cardinality_option,
         request.source_optional,
         request.target_optional,
+        request.source_role.clone(),
+        request.target_role.clone(),
         foreign_key_details,
         etl_job_metadata,
         relationship_type,
@@ -3076,10 +3960,20 @@ pub async fn update_domain_relationship(
                     }
                 }
             }
-
-            Ok(Json(
-                serde_json::to_value(relationship).unwrap_or(json!({})),
-            ))
+            drop(model_service);
+
+            let updated = serde_json::to_value(&relationship).unwrap_or(json!({}));
+            events::record_model_change(
+                &state,
+                &ctx,
+                &path.domain,
+                ChangeEventType::RelationshipUpdated,
+                relationship_uuid,
+                before,
+                Some(updated.clone()),
+            )
+            .await;
+            Ok(Json(updated))
         }
         Ok(None) => Err(StatusCode::NOT_FOUND),
         Err(_) => Err(StatusCode::BAD_REQUEST),
@@ -3113,12 +4007,28 @@ pub async fn delete_domain_relationship(
         Uuid::parse_str(&path.relationship_id).map_err(|_| StatusCode::BAD_REQUEST)?;
 
     // Try storage backend first (PostgreSQL)
-    if let Some(storage) = state.storage.as_ref() {
+    if let Some(storage) = ctx.storage(&state) {
+        let before = storage
+            .get_relationship(ctx.domain_info.id, relationship_uuid)
+            .await
+            .ok()
+            .flatten()
+            .and_then(|r| serde_json::to_value(r).ok());
         match storage
             .delete_relationship(ctx.domain_info.id, relationship_uuid, &ctx.user_context)
             .await
         {
             Ok(()) => {
+                events::record_model_change(
+                    &state,
+                    &ctx,
+                    &path.domain,
+                    ChangeEventType::RelationshipDeleted,
+                    relationship_uuid,
+                    before,
+                    None,
+                )
+                .await;
                 return Ok(Json(json!({"message": "Relationship deleted"})));
             }
             Err(StorageError::NotFound { .. }) => {
@@ -3131,11 +4041,16 @@ pub async fn delete_domain_relationship(
     }
 
     // File-based fallback
-    let mut model_service = state.model_service.lock().await;
+    let mut model_service = ctx.model_service(&state).await;
     let model = model_service
         .get_current_model_mut()
         .ok_or(StatusCode::BAD_REQUEST)?;
 
+    let before = model
+        .relationships
+        .iter()
+        .find(|r| r.id == relationship_uuid)
+        .and_then(|r| serde_json::to_value(r).ok());
     let mut rel_service = RelationshipService::new(Some(model.clone()));
     rel_service.set_model(model.clone());
 
@@ -3166,7 +4081,18 @@ pub async fn delete_domain_relationship(
                     }
                 }
             }
-
+            drop(model_service);
+
+            events::record_model_change(
+                &state,
+                &ctx,
+                &path.domain,
+                ChangeEventType::RelationshipDeleted,
+                relationship_uuid,
+                before,
+                None,
+            )
+            .await;
             Ok(Json(json!({"message": "Relationship deleted"})))
         }
         Ok(false) => Err(StatusCode::NOT_FOUND),
@@ -3180,76 +4106,14 @@ pub async fn delete_domain_relationship(
 
 use data_modelling_sdk::models::{CrossDomainConfig, CrossDomainTableRef, Position as SdkPosition};
 
-/// Request to add a cross-domain table reference
-#[derive(Debug, Deserialize, utoipa::ToSchema)]
-pub struct AddCrossDomainTableRequest {
-    source_domain: String,
-    table_id: String,
-    #[serde(default)]
-    display_alias: Option<String>,
-    #[serde(default)]
-    position: Option<Value>,
-    #[serde(default)]
-    notes: Option<String>,
-}
-
-/// Request to update a cross-domain table reference
-#[derive(Debug, Deserialize, utoipa::ToSchema)]
-pub struct UpdateCrossDomainTableRequest {
-    #[serde(default)]
-    display_alias: Option<String>,
-    #[serde(default)]
-    position: Option<Value>,
-    #[serde(default)]
-    notes: Option<String>,
-}
-
-/// Response for canvas view (combined domain + imported tables)
-#[derive(Serialize, utoipa::ToSchema)]
-pub struct CanvasResponse {
-    /// Tables owned by this domain
-    owned_tables: Vec<Value>,
-    /// Tables imported from other domains (read-only in this domain)
-    imported_tables: Vec<ImportedTableInfo>,
-    /// Relationships owned by this domain
-    owned_relationships: Vec<Value>,
-    /// Relationships imported from other domains (read-only)
-    imported_relationships: Vec<ImportedRelationshipInfo>,
-}
-
-/// Info about an imported table
-#[derive(Serialize, utoipa::ToSchema)]
-pub struct ImportedTableInfo {
-    /// The table data
-    table: Value,
-    /// Domain that owns this table
-    source_domain: String,
-    /// Reference ID in this domain's cross-domain config
-    reference_id: String,
-    /// Optional display alias
-    display_alias: Option<String>,
-    /// Position override for this domain
-    position_override: Option<Value>,
-    /// Notes about why imported
-    notes: Option<String>,
-}
-
-/// Info about an imported relationship
-#[derive(Serialize, utoipa::ToSchema)]
-pub struct ImportedRelationshipInfo {
-    /// The relationship data
-    relationship: Value,
-    /// Domain that owns this relationship
-    source_domain: String,
-    /// Reference ID
-    reference_id: String,
-}
-
 /// Get path to cross-domain config file
-fn get_cross_domain_config_path(email: &str, domain: &str) -> Result<PathBuf, StatusCode> {
+pub(crate) fn get_cross_domain_config_path(
+    email: &str,
+    domain: &str,
+) -> Result<PathBuf, StatusCode> {
     let workspace_data_dir =
         get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
-    let sanitized_email = sanitize_email_for_path(email);
+    let sanitized_email = workspace_dir_name(email);
     Ok(workspace_data_dir
         .join(&sanitized_email)
         .join(domain)
@@ -3257,7 +4121,7 @@ fn get_cross_domain_config_path(email: &str, domain: &str) -> Result<PathBuf, St
 }
 
 /// Load cross-domain config from file
-fn load_cross_domain_config(path: &PathBuf) -> CrossDomainConfig {
+pub(crate) fn load_cross_domain_config(path: &PathBuf) -> CrossDomainConfig {
     if path.exists()
         && let Ok(content) = std::fs::read_to_string(path)
         && let Ok(config) = serde_yaml::from_str(&content)
@@ -3268,7 +4132,10 @@ fn load_cross_domain_config(path: &PathBuf) -> CrossDomainConfig {
 }
 
 /// Save cross-domain config to file
-fn save_cross_domain_config(path: &PathBuf, config: &CrossDomainConfig) -> Result<(), StatusCode> {
+pub(crate) fn save_cross_domain_config(
+    path: &PathBuf,
+    config: &CrossDomainConfig,
+) -> Result<(), StatusCode> {
     // Ensure parent directory exists
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
@@ -3479,6 +4346,16 @@ pub async fn add_cross_domain_table(
                         "Added cross-domain table reference: {} from {} to {}",
                         table_uuid, request.source_domain, path.domain
                     );
+                    audit::record_domain_audit(
+                        &state,
+                        &ctx,
+                        &path.domain,
+                        (AuditEntityType::CrossDomainRef, table_ref.id),
+                        AuditAction::Create,
+                        None,
+                        serde_json::to_value(&table_ref).ok(),
+                    )
+                    .await;
                     return Ok(Json(table_ref));
                 }
                 Err(e) => {
@@ -3533,6 +4410,16 @@ pub async fn add_cross_domain_table(
         "Added cross-domain table reference: {} from {} to {}",
         table_uuid, source_domain, path.domain
     );
+    audit::record_domain_audit(
+        &state,
+        &ctx,
+        &path.domain,
+        (AuditEntityType::CrossDomainRef, table_ref.id),
+        AuditAction::Create,
+        None,
+        serde_json::to_value(&table_ref).ok(),
+    )
+    .await;
 
     Ok(Json(table_ref))
 }
@@ -3599,6 +4486,7 @@ pub async fn update_cross_domain_table_ref(
                     .await
                 {
                     Ok(updated_info) => {
+                        let after = serde_json::to_value(&updated_info).ok();
                         let mut table_ref = CrossDomainTableRef::new(
                             format!("domain-{}", updated_info.source_domain_id),
                             updated_info.table_id,
@@ -3609,6 +4497,16 @@ pub async fn update_cross_domain_table_ref(
                         if let Some(ref pos) = updated_info.position {
                             table_ref.position = Some(SdkPosition { x: pos.x, y: pos.y });
                         }
+                        audit::record_domain_audit(
+                            &state,
+                            &ctx,
+                            &path.domain,
+                            (AuditEntityType::CrossDomainRef, updated_info.id),
+                            AuditAction::Update,
+                            serde_json::to_value(ref_info).ok(),
+                            after,
+                        )
+                        .await;
                         return Ok(Json(table_ref));
                     }
                     Err(e) => {
@@ -3632,6 +4530,7 @@ pub async fn update_cross_domain_table_ref(
         .iter_mut()
         .find(|t| t.table_id == table_uuid)
         .ok_or(StatusCode::NOT_FOUND)?;
+    let before = serde_json::to_value(&*table_ref).ok();
 
     if let Some(alias) = request.display_alias {
         table_ref.display_alias = if alias.is_empty() { None } else { Some(alias) };
@@ -3645,6 +4544,16 @@ pub async fn update_cross_domain_table_ref(
 
     let updated_ref = table_ref.clone();
     save_cross_domain_config(&config_path, &config)?;
+    audit::record_domain_audit(
+        &state,
+        &ctx,
+        &path.domain,
+        (AuditEntityType::CrossDomainRef, updated_ref.id),
+        AuditAction::Update,
+        before,
+        serde_json::to_value(&updated_ref).ok(),
+    )
+    .await;
 
     Ok(Json(updated_ref))
 }
@@ -3686,6 +4595,16 @@ pub async fn remove_cross_domain_table(
                             "Removed cross-domain table reference: {} from {}",
                             table_uuid, path.domain
                         );
+                        audit::record_domain_audit(
+                            &state,
+                            &ctx,
+                            &path.domain,
+                            (AuditEntityType::CrossDomainRef, ref_info.id),
+                            AuditAction::Delete,
+                            serde_json::to_value(ref_info).ok(),
+                            None,
+                        )
+                        .await;
                         return Ok(Json(json!({"message": "Table reference removed"})));
                     }
                     Err(e) => {
@@ -3702,6 +4621,11 @@ pub async fn remove_cross_domain_table(
     // File-based fallback
     let config_path = get_cross_domain_config_path(&ctx.user_context.email, &path.domain)?;
     let mut config = load_cross_domain_config(&config_path);
+    let removed = config
+        .imported_tables
+        .iter()
+        .find(|t| t.table_id == table_uuid)
+        .cloned();
 
     if !config.remove_table_ref(table_uuid) {
         return Err(StatusCode::NOT_FOUND);
@@ -3713,6 +4637,18 @@ pub async fn remove_cross_domain_table(
         "Removed cross-domain table reference: {} from {}",
         table_uuid, path.domain
     );
+    if let Some(removed) = removed {
+        audit::record_domain_audit(
+            &state,
+            &ctx,
+            &path.domain,
+            (AuditEntityType::CrossDomainRef, removed.id),
+            AuditAction::Delete,
+            serde_json::to_value(&removed).ok(),
+            None,
+        )
+        .await;
+    }
 
     Ok(Json(json!({"message": "Table reference removed"})))
 }
@@ -3786,6 +4722,11 @@ pub async fn remove_cross_domain_relationship(
 
     let config_path = get_cross_domain_config_path(&ctx.user_context.email, &path.domain)?;
     let mut config = load_cross_domain_config(&config_path);
+    let removed = config
+        .imported_relationships
+        .iter()
+        .find(|r| r.relationship_id == relationship_uuid)
+        .cloned();
 
     if !config.remove_relationship_ref(relationship_uuid) {
         return Err(StatusCode::NOT_FOUND);
@@ -3797,6 +4738,18 @@ pub async fn remove_cross_domain_relationship(
         "Removed cross-domain relationship reference: {} from {}",
         relationship_uuid, path.domain
     );
+    if let Some(removed) = removed {
+        audit::record_domain_audit(
+            &state,
+            &ctx,
+            &path.domain,
+            (AuditEntityType::CrossDomainRef, removed.id),
+            AuditAction::Delete,
+            serde_json::to_value(&removed).ok(),
+            None,
+        )
+        .await;
+    }
 
     Ok(Json(json!({"message": "Relationship reference removed"})))
 }
@@ -3893,6 +4846,23 @@ pub async fn sync_cross_domain_relationships(
     })))
 }
 
+/// Body of the canvas endpoint, serialized like [`CanvasResponse`](crate::api_types::CanvasResponse).
+///
+/// Owned tables and relationships are written out as JSON text while the model lock is held,
+/// rather than cloned or converted to `Value` trees, since other domains are loaded into the
+/// model service afterwards.
+#[derive(Serialize)]
+pub struct CanvasBody {
+    owned_tables: Vec<Box<RawValue>>,
+    imported_tables: Vec<ImportedTableInfo>,
+    owned_relationships: Vec<Box<RawValue>>,
+    imported_relationships: Vec<ImportedRelationshipInfo>,
+}
+
+fn to_raw_json<T: Serialize>(value: &T) -> Result<Box<RawValue>, StatusCode> {
+    serde_json::value::to_raw_value(value).map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)
+}
+
 /// GET /workspace/domains/{domain}/canvas - Get combined canvas view
 ///
 /// Returns all tables and relationships for the domain canvas, including:
@@ -3900,17 +4870,21 @@ pub async fn sync_cross_domain_relationships(
 /// - Imported tables from other domains (read-only)
 /// - Owned relationships (editable)
 /// - Imported relationships (read-only, between imported tables from same source domain)
+///
+/// With `filter`, only tables matching that saved filter are shown, along with the
+/// relationships between them.
 #[utoipa::path(
     get,
     path = "/workspace/domains/{domain}/canvas",
     tag = "Workspace",
     params(
-        ("domain" = String, Path, description = "Domain name")
+        ("domain" = String, Path, description = "Domain name"),
+        saved_filters::SavedFilterQuery
     ),
     responses(
-        (status = 200, description = "Canvas view retrieved successfully", body = CanvasResponse),
+        (status = 200, description = "Canvas view retrieved successfully", body = crate::api_types::CanvasResponse),
         (status = 403, description = "Forbidden - domain access denied"),
-        (status = 404, description = "Domain not found"),
+        (status = 404, description = "Domain or saved filter not found"),
         (status = 503, description = "Service unavailable - database not available")
     ),
     security(("bearer_auth" = []))
@@ -3919,39 +4893,54 @@ pub async fn get_domain_canvas(
     State(state): State<AppState>,
     headers: HeaderMap,
     axum::extract::Path(path): axum::extract::Path<DomainPath>,
-) -> Result<Json<CanvasResponse>, StatusCode> {
+    axum::extract::Query(query): axum::extract::Query<saved_filters::SavedFilterQuery>,
+) -> Result<Json<CanvasBody>, StatusCode> {
     let email = get_session_email(&state, &headers).await?;
+    let criteria = saved_filters::resolve_saved_filter(&email, &path.domain, &query)?;
+    let selected = |table: &Table| criteria.as_ref().is_none_or(|c| c.matches(table));
 
     // Load cross-domain config
     let config_path = get_cross_domain_config_path(&email, &path.domain)?;
     let config = load_cross_domain_config(&config_path);
 
     // Load this domain's model
-    ensure_domain_loaded(&state, &headers, &path.domain).await?;
+    let ctx = ensure_domain_loaded(&state, &headers, &path.domain).await?;
 
-    let model_service = state.model_service.lock().await;
+    let model_service = ctx.model_service(&state).await;
     let model = model_service
         .get_current_model()
         .ok_or(StatusCode::NOT_FOUND)?;
 
     // Owned tables and relationships
-    let owned_tables: Vec<Value> = model
+    let visible: std::collections::HashSet<Uuid> = model
         .tables
         .iter()
-        .map(|t| serde_json::to_value(t).unwrap_or(json!({})))
+        .filter(|t| selected(t))
+        .map(|t| t.id)
         .collect();
+    let owned_tables = model
+        .tables
+        .iter()
+        .filter(|t| visible.contains(&t.id))
+        .map(to_raw_json)
+        .collect::<Result<Vec<_>, _>>()?;
 
-    let owned_relationships: Vec<Value> = model
+    let owned_relationships = model
         .relationships
         .iter()
-        .map(|r| serde_json::to_value(r).unwrap_or(json!({})))
-        .collect();
+        .filter(|r| {
+            criteria.is_none()
+                || (visible.contains(&r.source_table_id) && visible.contains(&r.target_table_id))
+        })
+        .map(to_raw_json)
+        .collect::<Result<Vec<_>, _>>()?;
 
     drop(model_service);
 
     // Load imported tables from their source domains
     let mut imported_tables: Vec<ImportedTableInfo> = Vec::new();
     let mut imported_relationships: Vec<ImportedRelationshipInfo> = Vec::new();
+    let mut visible_imported = std::collections::HashSet::new();
 
     // Group by source domain for efficient loading
     let mut tables_by_domain: std::collections::HashMap<String, Vec<&CrossDomainTableRef>> =
@@ -3975,6 +4964,7 @@ pub async fn get_domain_canvas(
                     .tables
                     .iter()
                     .find(|t| t.id == table_ref.table_id)
+                    .filter(|t| selected(t))
                 {
                     let mut table_json = serde_json::to_value(table).unwrap_or(json!({}));
 
@@ -3985,6 +4975,7 @@ pub async fn get_domain_canvas(
                         obj.insert("position".to_string(), json!({"x": pos.x, "y": pos.y}));
                     }
 
+                    visible_imported.insert(table.id);
                     imported_tables.push(ImportedTableInfo {
                         table: table_json,
                         source_domain: source_domain.clone(),
@@ -4016,6 +5007,9 @@ pub async fn get_domain_canvas(
                 .relationships
                 .iter()
                 .find(|r| r.id == rel_ref.relationship_id)
+            && (criteria.is_none()
+                || (visible_imported.contains(&relationship.source_table_id)
+                    && visible_imported.contains(&relationship.target_table_id)))
         {
             imported_relationships.push(ImportedRelationshipInfo {
                 relationship: serde_json::to_value(relationship).unwrap_or(json!({})),
@@ -4030,7 +5024,7 @@ pub async fn get_domain_canvas(
     let mut model_service = state.model_service.lock().await;
     let _ = create_workspace_for_email_and_domain(&mut model_service, &email, &path.domain).await;
 
-    Ok(Json(CanvasResponse {
+    Ok(Json(CanvasBody {
         owned_tables,
         imported_tables,
         owned_relationships,
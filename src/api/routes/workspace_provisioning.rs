@@ -0,0 +1,252 @@
+//! Workspace auto-provisioning routes.
+//!
+//! Administrators manage the rules that place new users in shared team workspaces with
+//! `GET`/`PUT /workspace/provisioning-rules`. The rules are applied on first login: each
+//! verified email of the user that has no workspace yet is matched by email domain or GitHub
+//! organization, and a match assigns it to the rule's team workspace. A new team workspace is
+//! created as an organization workspace and gets the rule's template domain cloned into it.
+
+use anyhow::anyhow;
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    response::Json,
+};
+use std::path::Path;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use super::app_state::AppState;
+use super::audit::is_admin;
+use super::domain_provisioning::{DomainManifestEntry, domain_exists, provision_domain};
+use super::error::ApiError;
+use super::export_schedules::load_domain_model;
+use super::workspace::{
+    get_or_create_workspace, get_user_context, get_workspace_data_dir, sanitize_email_for_path,
+    team_workspace_name, workspace_dir_name,
+};
+use crate::services::workspace_provisioning_service::{
+    ProvisioningRule, ProvisioningRules, WorkspaceAssignments,
+};
+use crate::storage::traits::UserContext;
+
+/// Serializes first-login provisioning so concurrent logins don't lose assignments
+static PROVISIONING_LOCK: Mutex<()> = Mutex::const_new(());
+
+/// Load the provisioning rules (none if none are configured).
+pub fn load_provisioning_rules() -> ProvisioningRules {
+    get_workspace_data_dir()
+        .map(|dir| ProvisioningRules::load(&dir))
+        .unwrap_or_default()
+}
+
+/// Provision a user's emails into team workspaces on first login.
+///
+/// `groups` are the user's group memberships (GitHub organization logins). Failures are
+/// logged and leave the email with a personal workspace.
+pub(crate) async fn provision_on_login(
+    state: &AppState,
+    rules: &ProvisioningRules,
+    emails: &[String],
+    groups: &[String],
+) {
+    if rules.rules.is_empty() {
+        return;
+    }
+    let Ok(data_dir) = get_workspace_data_dir() else {
+        return;
+    };
+    for email in emails {
+        match provision_user(state, &data_dir, rules, email, groups).await {
+            Ok(Some(rule)) => info!(
+                "Provisioned {} into team workspace {} (rule {})",
+                email, rule.workspace, rule.name
+            ),
+            Ok(None) => {}
+            Err(e) => warn!("Failed to provision workspace for {}: {}", email, e),
+        }
+    }
+}
+
+/// Assign an email without a workspace to the first matching rule's team workspace.
+async fn provision_user<'a>(
+    state: &AppState,
+    data_dir: &Path,
+    rules: &'a ProvisioningRules,
+    email: &str,
+    groups: &[String],
+) -> anyhow::Result<Option<&'a ProvisioningRule>> {
+    let _guard = PROVISIONING_LOCK.lock().await;
+    let mut assignments = WorkspaceAssignments::load(data_dir);
+    if assignments.workspace_for(email).is_some() || has_workspace(state, data_dir, email).await? {
+        return Ok(None);
+    }
+    let Some(rule) = rules.first_match(email, groups) else {
+        return Ok(None);
+    };
+    assignments.assign(email, rule);
+    assignments.save(data_dir)?;
+    provision_team_workspace(state, data_dir, rule, email).await?;
+    Ok(Some(rule))
+}
+
+/// Whether an email already has its own workspace
+async fn has_workspace(state: &AppState, data_dir: &Path, email: &str) -> anyhow::Result<bool> {
+    if let Some(storage) = state.storage.as_ref() {
+        return storage
+            .get_workspace_by_email(email)
+            .await
+            .map(|workspace| workspace.is_some())
+            .map_err(|e| anyhow!("Failed to look up workspace: {}", e));
+    }
+    Ok(data_dir.join(sanitize_email_for_path(email)).is_dir())
+}
+
+/// Create the rule's team workspace if needed and clone its template domain into it.
+async fn provision_team_workspace(
+    state: &AppState,
+    data_dir: &Path,
+    rule: &ProvisioningRule,
+    member: &str,
+) -> anyhow::Result<()> {
+    let user_id = Uuid::new_v5(&Uuid::NAMESPACE_DNS, member.as_bytes());
+    let member_context = UserContext {
+        user_id,
+        email: member.to_string(),
+    };
+    let team_email = rule.workspace.to_lowercase();
+    if let Some(storage) = state.storage.as_ref()
+        && storage
+            .get_workspace_by_email(&team_email)
+            .await
+            .map_err(|e| anyhow!("Failed to look up team workspace: {}", e))?
+            .is_none()
+    {
+        let name = rule
+            .workspace_name
+            .clone()
+            .unwrap_or_else(|| team_workspace_name(&team_email));
+        storage
+            .create_workspace_with_details(
+                team_email.clone(),
+                &member_context,
+                name,
+                "organization".to_string(),
+            )
+            .await
+            .map_err(|e| anyhow!("Failed to create team workspace: {}", e))?;
+    }
+    // Resolves to the team workspace through the assignment
+    let workspace = get_or_create_workspace(state, &member_context)
+        .await
+        .map_err(|status| anyhow!("Failed to get team workspace: {}", status))?;
+
+    let Some(template) = &rule.template else {
+        return Ok(());
+    };
+    let team_dir = data_dir.join(sanitize_email_for_path(&team_email));
+    if domain_exists(state, &workspace, &team_dir, &template.domain)
+        .await
+        .map_err(|status| anyhow!("Failed to look up template domain: {}", status))?
+    {
+        return Ok(());
+    }
+    let model = load_domain_model(state, &template.workspace, &template.domain).await?;
+    let template_dir = data_dir
+        .join(workspace_dir_name(&template.workspace))
+        .join(&template.domain);
+    let team_context = UserContext {
+        user_id,
+        email: team_email,
+    };
+    let entry = DomainManifestEntry {
+        name: template.domain.clone(),
+        description: None,
+        owners: Vec::new(),
+        template: None,
+    };
+    let (tables, relationships) = provision_domain(
+        state,
+        &team_context,
+        &workspace,
+        &team_dir,
+        &entry,
+        Some((&template_dir, &model)),
+    )
+    .await
+    .map_err(|e| anyhow!(e))?;
+    info!(
+        "Cloned template domain {} into team workspace {} ({} tables, {} relationships)",
+        template.domain, rule.workspace, tables, relationships
+    );
+    Ok(())
+}
+
+/// GET /workspace/provisioning-rules - Get the workspace auto-provisioning rules (admins only)
+#[utoipa::path(
+    get,
+    path = "/workspace/provisioning-rules",
+    tag = "Workspace",
+    responses(
+        (status = 200, description = "Provisioning rules, applied in order", body = ProvisioningRules),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an administrator")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn get_provisioning_rules(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<ProvisioningRules>, StatusCode> {
+    let user_context = get_user_context(&state, &headers).await?;
+    if !is_admin(&user_context.email) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(Json(load_provisioning_rules()))
+}
+
+/// PUT /workspace/provisioning-rules - Replace the workspace auto-provisioning rules (admins only)
+#[utoipa::path(
+    put,
+    path = "/workspace/provisioning-rules",
+    tag = "Workspace",
+    request_body = ProvisioningRules,
+    responses(
+        (status = 200, description = "Rules saved; they apply to following first logins", body = ProvisioningRules),
+        (status = 400, description = "Empty or duplicate rule name, a rule without email domains or groups, or an invalid workspace email", body = crate::api_types::ErrorResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Caller is not an administrator"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(("bearer_auth" = []))
+)]
+pub async fn update_provisioning_rules(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(rules): Json<ProvisioningRules>,
+) -> Result<Json<ProvisioningRules>, ApiError> {
+    let user_context = get_user_context(&state, &headers).await?;
+    if !is_admin(&user_context.email) {
+        warn!(
+            "Non-admin {} denied changing the provisioning rules",
+            user_context.email
+        );
+        return Err(StatusCode::FORBIDDEN.into());
+    }
+    rules.validate().map_err(|message| ApiError {
+        status: StatusCode::BAD_REQUEST,
+        message,
+    })?;
+    let dir = get_workspace_data_dir().map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    rules.save(&dir).map_err(|e| {
+        warn!("Failed to save provisioning rules: {}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    info!(
+        "{} saved {} workspace provisioning rule(s)",
+        user_context.email,
+        rules.rules.len()
+    );
+    Ok(Json(rules))
+}
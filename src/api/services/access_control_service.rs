@@ -0,0 +1,141 @@
+//! Role-based access control for workspaces and domains.
+//!
+//! Users hold a role (owner, editor or viewer) on a workspace, granted in the workspace's
+//! `members.yaml`, and on a domain, granted in the domain's `members.yaml` (which redeemed
+//! invites also add to). A domain grant overrides the workspace grant. Users who work in a
+//! workspace without a grant keep the access they always had: owner until owners are named
+//! (domain owners in the domain metadata, or owner grants on the workspace), editor after.
+//! Other users only get what they were granted.
+
+use crate::services::domain_invite_service::{DomainMembers, DomainRole};
+
+/// Grants that decide a user's role in a workspace
+pub struct AccessGrants<'a> {
+    /// Email the workspace is keyed by
+    pub workspace: &'a str,
+    /// Workspace-wide grants
+    pub workspace_members: &'a DomainMembers,
+    /// Grants and declared owners of the domain, for requests to a domain
+    pub domain: Option<(&'a DomainMembers, &'a [String])>,
+}
+
+/// Role of `email` under `grants`; `home_workspace` is the workspace the user works in by
+/// default. None if the user has no access.
+pub fn effective_role(
+    email: &str,
+    home_workspace: &str,
+    grants: &AccessGrants,
+) -> Option<DomainRole> {
+    // The workspace's own account always owns it
+    if email.eq_ignore_ascii_case(grants.workspace) {
+        return Some(DomainRole::Owner);
+    }
+    if let Some((members, owners)) = grants.domain {
+        if owners.iter().any(|o| o.eq_ignore_ascii_case(email)) {
+            return Some(DomainRole::Owner);
+        }
+        if let Some(role) = members.role_of(email) {
+            return Some(role);
+        }
+    }
+    if let Some(role) = grants.workspace_members.role_of(email) {
+        return Some(role);
+    }
+    if !home_workspace.eq_ignore_ascii_case(grants.workspace) {
+        return None;
+    }
+    let owners_named = match grants.domain {
+        Some((_, owners)) => !owners.is_empty(),
+        None => grants
+            .workspace_members
+            .members
+            .iter()
+            .any(|m| m.role == DomainRole::Owner),
+    };
+    Some(if owners_named {
+        DomainRole::Editor
+    } else {
+        DomainRole::Owner
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::domain_invite_service::DomainMember;
+    use chrono::Utc;
+
+    fn members(grants: &[(&str, DomainRole)]) -> DomainMembers {
+        DomainMembers {
+            invites: Vec::new(),
+            members: grants
+                .iter()
+                .map(|(email, role)| DomainMember {
+                    email: email.to_string(),
+                    role: *role,
+                    invited_by: "team@example.com".to_string(),
+                    joined_at: Utc::now(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_domain_grants_override_workspace_grants() {
+        let team = "team@example.com";
+        let workspace_members = members(&[
+            ("lead@example.com", DomainRole::Owner),
+            ("analyst@example.com", DomainRole::Viewer),
+        ]);
+        let domain_members = members(&[("analyst@example.com", DomainRole::Editor)]);
+        let owners = vec!["Lead@example.com".to_string()];
+        let workspace = AccessGrants {
+            workspace: team,
+            workspace_members: &workspace_members,
+            domain: None,
+        };
+        let domain = AccessGrants {
+            domain: Some((&domain_members, &owners)),
+            ..workspace
+        };
+        let role =
+            |email: &str, home: &str, grants: &AccessGrants| effective_role(email, home, grants);
+
+        assert_eq!(role(team, team, &domain), Some(DomainRole::Owner));
+        assert_eq!(
+            role("analyst@example.com", "analyst@example.com", &domain),
+            Some(DomainRole::Editor)
+        );
+        assert_eq!(
+            role("analyst@example.com", "analyst@example.com", &workspace),
+            Some(DomainRole::Viewer)
+        );
+        // Workspace users without a grant are editors once owners are named
+        assert_eq!(
+            role("dev@example.com", team, &domain),
+            Some(DomainRole::Editor)
+        );
+        assert_eq!(
+            role("dev@example.com", team, &workspace),
+            Some(DomainRole::Editor)
+        );
+        let no_owners = AccessGrants {
+            domain: Some((&domain_members, &[])),
+            ..workspace
+        };
+        assert_eq!(
+            role("dev@example.com", team, &no_owners),
+            Some(DomainRole::Owner)
+        );
+        // Anyone else needs a grant
+        assert_eq!(
+            role("guest@example.com", "guest@example.com", &domain),
+            None
+        );
+
+        assert!(DomainRole::Viewer.allows(&axum::http::Method::GET));
+        assert!(!DomainRole::Viewer.allows(&axum::http::Method::PUT));
+        assert!(!DomainRole::Viewer.allows(&axum::http::Method::POST));
+        assert!(DomainRole::Editor.allows(&axum::http::Method::POST));
+    }
+}
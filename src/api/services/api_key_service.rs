@@ -0,0 +1,512 @@
+//! API keys for machine-to-machine clients.
+//!
+//! CI pipelines and other clients that can't go through OAuth authenticate with an API key
+//! sent in the `X-API-Key` header (or as a bearer token). A key acts as the user who created
+//! it, limited by its scopes:
+//! - `read_only`: GET requests
+//! - `import_only`: the domain import endpoints (`/workspace/domains/{domain}/import/...`)
+//! - `export_only`: the export endpoints (`/workspace/domains/{domain}/export/...` and
+//!   `/workspace/domains/{domain}/exports/...`)
+//! - `full`: every workspace route
+//!
+//! Personal access tokens are keys a user creates for their own scripts: they carry one or
+//! more scopes, always expire and start with `dmp_` instead of `dmk_`. Both kinds share the
+//! storage below and are checked by the same middleware.
+//!
+//! Keys only authenticate workspace routes; they can't manage keys or sessions. Only a SHA-256
+//! hash of each key is stored, so the key itself is shown once, when it is created.
+//!
+//! Keys are stored in the storage backend:
+//! - file mode: `api_keys.yaml` in the workspace data directory
+//! - PostgreSQL mode: the `api_keys` table
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use axum::http::Method;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::postgres::PgRow;
+use sqlx::{PgPool, Row};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::storage::traits::UserContext;
+
+/// File holding the API keys in file mode
+pub const API_KEYS_FILE: &str = "api_keys.yaml";
+/// Prefix of every API key, telling keys apart from JWTs
+pub const API_KEY_PREFIX: &str = "dmk_";
+/// Prefix of every personal access token
+pub const PERSONAL_ACCESS_TOKEN_PREFIX: &str = "dmp_";
+
+/// Characters of a key kept to identify it in listings
+const DISPLAY_PREFIX_LEN: usize = 12;
+
+/// What an API key may do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyScope {
+    ReadOnly,
+    ImportOnly,
+    ExportOnly,
+    Full,
+}
+
+impl ApiKeyScope {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyScope::ReadOnly => "read_only",
+            ApiKeyScope::ImportOnly => "import_only",
+            ApiKeyScope::ExportOnly => "export_only",
+            ApiKeyScope::Full => "full",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        serde_json::from_value(serde_json::Value::String(value.to_string())).ok()
+    }
+
+    /// Whether a key with this scope may call `method` on the workspace route `path`
+    /// (relative to `/api/v1`).
+    pub fn allows(&self, method: &Method, path: &str) -> bool {
+        match self {
+            ApiKeyScope::Full => true,
+            ApiKeyScope::ReadOnly => matches!(*method, Method::GET | Method::HEAD),
+            ApiKeyScope::ImportOnly => is_import_path(path),
+            ApiKeyScope::ExportOnly => {
+                matches!(*method, Method::GET | Method::HEAD) && is_export_path(path)
+            }
+        }
+    }
+}
+
+/// Whether `path` is a domain import endpoint: `/workspace/domains/{domain}/import/...`
+fn is_import_path(path: &str) -> bool {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    matches!(
+        segments.as_slice(),
+        ["workspace", "domains", domain, "import", _, ..] if !domain.is_empty()
+    )
+}
+
+/// Whether `path` is a domain export endpoint: `/workspace/domains/{domain}/export/...` or
+/// the export history under `/workspace/domains/{domain}/exports`
+fn is_export_path(path: &str) -> bool {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    matches!(
+        segments.as_slice(),
+        ["workspace", "domains", domain, "export", _, ..] if !domain.is_empty()
+    ) || matches!(
+        segments.as_slice(),
+        ["workspace", "domains", domain, "exports", ..] if !domain.is_empty()
+    )
+}
+
+/// Kind of key: a client's API key or a user's personal access token
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyKind {
+    #[default]
+    ApiKey,
+    PersonalAccessToken,
+}
+
+impl ApiKeyKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiKeyKind::ApiKey => "api_key",
+            ApiKeyKind::PersonalAccessToken => "personal_access_token",
+        }
+    }
+
+    fn prefix(&self) -> &'static str {
+        match self {
+            ApiKeyKind::ApiKey => API_KEY_PREFIX,
+            ApiKeyKind::PersonalAccessToken => PERSONAL_ACCESS_TOKEN_PREFIX,
+        }
+    }
+}
+
+/// Scopes stored as a single scope (API keys written before personal access tokens) or a list
+fn one_or_many_scopes<'de, D>(deserializer: D) -> Result<Vec<ApiKeyScope>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Scopes {
+        One(ApiKeyScope),
+        Many(Vec<ApiKeyScope>),
+    }
+    Ok(match Scopes::deserialize(deserializer)? {
+        Scopes::One(scope) => vec![scope],
+        Scopes::Many(scopes) => scopes,
+    })
+}
+
+/// An API key, without its secret
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiKey {
+    pub id: Uuid,
+    #[serde(default)]
+    pub kind: ApiKeyKind,
+    pub name: String,
+    /// What the key may do; a request is allowed if any scope allows it
+    #[serde(alias = "scope", deserialize_with = "one_or_many_scopes")]
+    pub scopes: Vec<ApiKeyScope>,
+    /// User the key acts as
+    pub owner_id: Uuid,
+    pub owner_email: String,
+    /// First characters of the key, to tell keys apart
+    pub prefix: String,
+    /// SHA-256 of the key
+    pub key_hash: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    /// A new key acting as `owner`, and its secret.
+    pub fn generate(
+        kind: ApiKeyKind,
+        name: String,
+        scopes: Vec<ApiKeyScope>,
+        owner: &UserContext,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> (Self, String) {
+        let secret = format!(
+            "{}{}{}",
+            kind.prefix(),
+            Uuid::new_v4().simple(),
+            Uuid::new_v4().simple()
+        );
+        let key = Self {
+            id: Uuid::new_v4(),
+            kind,
+            name,
+            scopes,
+            owner_id: owner.user_id,
+            owner_email: owner.email.clone(),
+            prefix: secret[..DISPLAY_PREFIX_LEN].to_string(),
+            key_hash: hash_key(&secret),
+            created_at: Utc::now(),
+            expires_at,
+        };
+        (key, secret)
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Whether one of the key's scopes allows `method` on the workspace route `path`
+    pub fn allows(&self, method: &Method, path: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.allows(method, path))
+    }
+
+    /// Scopes as stored and reported, e.g. `read_only,export_only`
+    pub fn scopes_str(&self) -> String {
+        self.scopes
+            .iter()
+            .map(ApiKeyScope::as_str)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// The user the key acts as
+    pub fn user_context(&self) -> UserContext {
+        UserContext {
+            user_id: self.owner_id,
+            email: self.owner_email.clone(),
+        }
+    }
+}
+
+/// Stored hash of a key
+pub fn hash_key(secret: &str) -> String {
+    format!("{:x}", Sha256::digest(secret.as_bytes()))
+}
+
+/// Whether a credential is an API key or personal access token rather than a JWT
+pub fn is_api_key(credential: &str) -> bool {
+    credential.starts_with(API_KEY_PREFIX) || credential.starts_with(PERSONAL_ACCESS_TOKEN_PREFIX)
+}
+
+/// Storage of API keys
+#[async_trait]
+pub trait ApiKeyStore: Send + Sync {
+    /// Keys created by `owner_email`, oldest first
+    async fn list(&self, owner_email: &str) -> Result<Vec<ApiKey>>;
+
+    async fn create(&self, key: &ApiKey) -> Result<()>;
+
+    /// Delete a key of `owner_email`. Returns false if it has no such key.
+    async fn delete(&self, owner_email: &str, id: Uuid) -> Result<bool>;
+
+    /// The key with hash `key_hash`, expired or not
+    async fn find_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>>;
+}
+
+/// Contents of `api_keys.yaml`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ApiKeysFile {
+    #[serde(default)]
+    keys: Vec<ApiKey>,
+}
+
+/// Serializes changes to `api_keys.yaml`
+static API_KEYS_FILE_LOCK: Mutex<()> = Mutex::new(());
+
+/// API keys in `api_keys.yaml` (file mode).
+pub struct FileApiKeyStore {
+    path: PathBuf,
+}
+
+impl FileApiKeyStore {
+    pub fn new(workspace_data_dir: &Path) -> Self {
+        Self {
+            path: workspace_data_dir.join(API_KEYS_FILE),
+        }
+    }
+
+    fn load(&self) -> ApiKeysFile {
+        std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, file: &ApiKeysFile) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        std::fs::write(&self.path, serde_yaml::to_string(file)?)
+            .with_context(|| format!("Failed to write {}", self.path.display()))
+    }
+}
+
+#[async_trait]
+impl ApiKeyStore for FileApiKeyStore {
+    async fn list(&self, owner_email: &str) -> Result<Vec<ApiKey>> {
+        Ok(self
+            .load()
+            .keys
+            .into_iter()
+            .filter(|k| k.owner_email.eq_ignore_ascii_case(owner_email))
+            .collect())
+    }
+
+    async fn create(&self, key: &ApiKey) -> Result<()> {
+        let _guard = API_KEYS_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut file = self.load();
+        file.keys.push(key.clone());
+        self.save(&file)
+    }
+
+    async fn delete(&self, owner_email: &str, id: Uuid) -> Result<bool> {
+        let _guard = API_KEYS_FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut file = self.load();
+        let before = file.keys.len();
+        file.keys
+            .retain(|k| k.id != id || !k.owner_email.eq_ignore_ascii_case(owner_email));
+        if file.keys.len() == before {
+            return Ok(false);
+        }
+        self.save(&file)?;
+        Ok(true)
+    }
+
+    async fn find_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>> {
+        Ok(self
+            .load()
+            .keys
+            .into_iter()
+            .find(|k| k.key_hash == key_hash))
+    }
+}
+
+/// API keys in the `api_keys` table (PostgreSQL mode).
+pub struct PostgresApiKeyStore {
+    pool: PgPool,
+}
+
+impl PostgresApiKeyStore {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn key_from_row(row: &PgRow) -> Result<Option<ApiKey>> {
+    let scopes: String = row.try_get("scope")?;
+    let Some(scopes) = scopes
+        .split(',')
+        .map(ApiKeyScope::parse)
+        .collect::<Option<Vec<_>>>()
+    else {
+        return Ok(None);
+    };
+    let kind: String = row.try_get("kind")?;
+    let kind = match kind.as_str() {
+        "personal_access_token" => ApiKeyKind::PersonalAccessToken,
+        _ => ApiKeyKind::ApiKey,
+    };
+    Ok(Some(ApiKey {
+        id: row.try_get("id")?,
+        kind,
+        name: row.try_get("name")?,
+        scopes,
+        owner_id: row.try_get("owner_id")?,
+        owner_email: row.try_get("owner_email")?,
+        prefix: row.try_get("key_prefix")?,
+        key_hash: row.try_get("key_hash")?,
+        created_at: row.try_get("created_at")?,
+        expires_at: row.try_get("expires_at")?,
+    }))
+}
+
+#[async_trait]
+impl ApiKeyStore for PostgresApiKeyStore {
+    async fn list(&self, owner_email: &str) -> Result<Vec<ApiKey>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, kind, name, scope, owner_id, owner_email, key_prefix, key_hash, created_at, expires_at
+            FROM api_keys
+            WHERE LOWER(owner_email) = LOWER($1)
+            ORDER BY created_at
+            "#,
+        )
+        .bind(owner_email)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to list API keys")?;
+
+        let mut keys = Vec::with_capacity(rows.len());
+        for row in &rows {
+            keys.extend(key_from_row(row)?);
+        }
+        Ok(keys)
+    }
+
+    async fn create(&self, key: &ApiKey) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO api_keys
+                (id, kind, name, scope, owner_id, owner_email, key_prefix, key_hash, created_at, expires_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            "#,
+        )
+        .bind(key.id)
+        .bind(key.kind.as_str())
+        .bind(&key.name)
+        .bind(key.scopes_str())
+        .bind(key.owner_id)
+        .bind(&key.owner_email)
+        .bind(&key.prefix)
+        .bind(&key.key_hash)
+        .bind(key.created_at)
+        .bind(key.expires_at)
+        .execute(&self.pool)
+        .await
+        .context("Failed to create API key")?;
+        Ok(())
+    }
+
+    async fn delete(&self, owner_email: &str, id: Uuid) -> Result<bool> {
+        let result =
+            sqlx::query("DELETE FROM api_keys WHERE id = $1 AND LOWER(owner_email) = LOWER($2)")
+                .bind(id)
+                .bind(owner_email)
+                .execute(&self.pool)
+                .await
+                .context("Failed to delete API key")?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_by_hash(&self, key_hash: &str) -> Result<Option<ApiKey>> {
+        let row = sqlx::query(
+            r#"
+            SELECT id, kind, name, scope, owner_id, owner_email, key_prefix, key_hash, created_at, expires_at
+            FROM api_keys
+            WHERE key_hash = $1
+            "#,
+        )
+        .bind(key_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .context("Failed to look up API key")?;
+        match row {
+            Some(row) => key_from_row(&row),
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_api_key_scopes_and_storage() {
+        let owner = UserContext {
+            user_id: Uuid::new_v4(),
+            email: "ci@example.com".to_string(),
+        };
+        let (key, secret) = ApiKey::generate(
+            ApiKeyKind::ApiKey,
+            "ci".to_string(),
+            vec![ApiKeyScope::ImportOnly],
+            &owner,
+            None,
+        );
+        assert!(is_api_key(&secret));
+        assert!(secret.starts_with(&key.prefix));
+        assert_ne!(key.key_hash, secret);
+
+        let import = "/workspace/domains/sales/import/sql/text";
+        assert!(key.allows(&Method::POST, import));
+        assert!(!key.allows(&Method::GET, "/workspace/domains/sales/tables"));
+        assert!(!key.allows(&Method::POST, "/workspace/domains/sales/import"));
+        assert!(ApiKeyScope::ReadOnly.allows(&Method::GET, "/workspace/domains"));
+        assert!(!ApiKeyScope::ReadOnly.allows(&Method::POST, import));
+        assert!(ApiKeyScope::Full.allows(&Method::DELETE, "/workspace/domains/sales"));
+
+        // Personal access tokens combine scopes
+        let (token, token_secret) = ApiKey::generate(
+            ApiKeyKind::PersonalAccessToken,
+            "export script".to_string(),
+            vec![ApiKeyScope::ExportOnly, ApiKeyScope::ImportOnly],
+            &owner,
+            None,
+        );
+        assert!(is_api_key(&token_secret) && token_secret.starts_with("dmp_"));
+        assert!(token.allows(&Method::GET, "/workspace/domains/sales/export/odcs"));
+        assert!(token.allows(&Method::GET, "/workspace/domains/sales/exports/abc"));
+        assert!(token.allows(&Method::POST, import));
+        assert!(!token.allows(&Method::POST, "/workspace/domains/sales/export/png/share"));
+        assert!(!token.allows(&Method::GET, "/workspace/domains/sales/tables"));
+        assert_eq!(token.scopes_str(), "export_only,import_only");
+
+        // Keys stored before personal access tokens have a single scope
+        let legacy = serde_yaml::to_string(&key)
+            .unwrap()
+            .replace("scopes:\n- import_only", "scope: import_only");
+        assert!(legacy.contains("\nscope: import_only\n"));
+        assert_eq!(serde_yaml::from_str::<ApiKey>(&legacy).unwrap(), key);
+
+        let dir = tempfile::tempdir().unwrap();
+        let store = FileApiKeyStore::new(dir.path());
+        store.create(&key).await.unwrap();
+        store.create(&token).await.unwrap();
+        let found = store.find_by_hash(&hash_key(&secret)).await.unwrap();
+        assert_eq!(found.as_ref(), Some(&key));
+        assert_eq!(store.list("CI@example.com").await.unwrap().len(), 2);
+        assert!(!store.delete("other@example.com", key.id).await.unwrap());
+        assert!(store.delete("ci@example.com", key.id).await.unwrap());
+        assert!(store.find_by_hash(&key.key_hash).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,470 @@
+//! Attachment storage for model artifacts.
+//!
+//! Small supporting files (sample payloads, mapping spreadsheets, screenshots) can be attached
+//! to a domain or one of its tables. The attachment index is stored per domain in
+//! `attachments.yaml`; file contents go to a pluggable [`AttachmentStore`]:
+//! - local files under `ATTACHMENT_STORAGE_DIR` (default `$WORKSPACE_DATA/.attachments`)
+//! - an S3 bucket when `ATTACHMENT_S3_BUCKET` is set (`ATTACHMENT_S3_REGION`, default
+//!   `us-east-1`; optional `ATTACHMENT_S3_ENDPOINT` for S3-compatible stores; credentials
+//!   from `AWS_*`)
+//!
+//! Uploads are limited to `ATTACHMENT_MAX_FILE_BYTES` (default 10 MiB) per file and
+//! `ATTACHMENT_DOMAIN_QUOTA_BYTES` (default 100 MiB) per domain. If
+//! `ATTACHMENT_SCAN_COMMAND` is set (e.g. `clamdscan --no-summary -`), every upload is piped
+//! to it on stdin and rejected unless the command exits with status 0.
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::export_scheduler_service::{AwsCredentials, s3_request};
+
+const DEFAULT_MAX_FILE_BYTES: usize = 10 * 1024 * 1024;
+const DEFAULT_DOMAIN_QUOTA_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Kind of object a file is attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentTarget {
+    Domain,
+    Table,
+}
+
+/// An attached file
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub target: AttachmentTarget,
+    /// Table id for table attachments
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_id: Option<String>,
+    pub filename: String,
+    pub content_type: String,
+    pub size: u64,
+    /// Hex SHA-256 of the content
+    pub sha256: String,
+    /// Object key in the attachment store
+    pub storage_key: String,
+    pub uploaded_by: String,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+/// All attachments of a domain (`attachments.yaml`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AttachmentIndex {
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+}
+
+impl AttachmentIndex {
+    /// Attachments of the domain itself or of one table
+    pub fn for_target(&self, target: AttachmentTarget, target_id: Option<&str>) -> Vec<Attachment> {
+        self.attachments
+            .iter()
+            .filter(|a| a.target == target && a.target_id.as_deref() == target_id)
+            .cloned()
+            .collect()
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&Attachment> {
+        self.attachments.iter().find(|a| a.id == id)
+    }
+
+    /// Total size of all attachments in bytes
+    pub fn total_size(&self) -> u64 {
+        self.attachments.iter().map(|a| a.size).sum()
+    }
+
+    /// Remove an attachment, returning it if it existed.
+    pub fn remove(&mut self, id: Uuid) -> Option<Attachment> {
+        let index = self.attachments.iter().position(|a| a.id == id)?;
+        Some(self.attachments.remove(index))
+    }
+}
+
+/// Backend holding attachment contents.
+#[async_trait::async_trait]
+pub trait AttachmentStore: Send + Sync {
+    /// Backend name used in logs.
+    fn backend(&self) -> &'static str;
+
+    async fn put(&self, key: &str, content: Vec<u8>) -> Result<()>;
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>>;
+
+    async fn delete(&self, key: &str) -> Result<()>;
+}
+
+/// Stores attachments as files below a root directory.
+pub struct FileAttachmentStore {
+    root: PathBuf,
+}
+
+impl FileAttachmentStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf> {
+        if key
+            .split('/')
+            .any(|part| part.is_empty() || part == "." || part == "..")
+        {
+            bail!("Invalid attachment key: {}", key);
+        }
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait::async_trait]
+impl AttachmentStore for FileAttachmentStore {
+    fn backend(&self) -> &'static str {
+        "file"
+    }
+
+    async fn put(&self, key: &str, content: Vec<u8>) -> Result<()> {
+        let path = self.path(key)?;
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        tokio::fs::write(&path, content)
+            .await
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        let path = self.path(key)?;
+        tokio::fs::read(&path)
+            .await
+            .with_context(|| format!("Failed to read {}", path.display()))
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let path = self.path(key)?;
+        match tokio::fs::remove_file(&path).await {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to delete {}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Stores attachments in an S3 (or S3-compatible) bucket.
+pub struct S3AttachmentStore {
+    client: reqwest::Client,
+    bucket: String,
+    region: String,
+    endpoint: Option<String>,
+}
+
+impl S3AttachmentStore {
+    /// Configure from `ATTACHMENT_S3_*`; None if no bucket is set.
+    pub fn from_env() -> Option<Self> {
+        let bucket = env::var("ATTACHMENT_S3_BUCKET").ok()?;
+        let region = env::var("ATTACHMENT_S3_REGION").unwrap_or_else(|_| "us-east-1".to_string());
+        Some(Self {
+            client: reqwest::Client::new(),
+            bucket,
+            region,
+            endpoint: env::var("ATTACHMENT_S3_ENDPOINT").ok(),
+        })
+    }
+
+    async fn request(&self, method: reqwest::Method, key: &str, body: Vec<u8>) -> Result<Vec<u8>> {
+        s3_request(
+            &self.client,
+            &AwsCredentials::from_env()?,
+            method,
+            &self.bucket,
+            &self.region,
+            self.endpoint.as_deref(),
+            key,
+            body,
+        )
+        .await
+    }
+}
+
+#[async_trait::async_trait]
+impl AttachmentStore for S3AttachmentStore {
+    fn backend(&self) -> &'static str {
+        "s3"
+    }
+
+    async fn put(&self, key: &str, content: Vec<u8>) -> Result<()> {
+        self.request(reqwest::Method::PUT, key, content).await?;
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> Result<Vec<u8>> {
+        self.request(reqwest::Method::GET, key, Vec::new()).await
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.request(reqwest::Method::DELETE, key, Vec::new())
+            .await?;
+        Ok(())
+    }
+}
+
+/// A file to attach
+pub struct AttachmentUpload<'a> {
+    pub target: AttachmentTarget,
+    pub target_id: Option<&'a str>,
+    pub filename: &'a str,
+    pub content_type: Option<&'a str>,
+    pub content: Vec<u8>,
+    pub uploaded_by: &'a str,
+}
+
+/// Why an upload failed
+#[derive(Debug)]
+pub enum UploadError {
+    Empty,
+    FileTooLarge { limit: usize },
+    QuotaExceeded { quota: u64 },
+    Infected(String),
+    Storage(anyhow::Error),
+}
+
+/// Per-file upload limit from `ATTACHMENT_MAX_FILE_BYTES`, also used to size the request body limit.
+pub fn max_file_bytes_from_env() -> usize {
+    env::var("ATTACHMENT_MAX_FILE_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_FILE_BYTES)
+}
+
+/// Attachment storage with size limits and an optional virus-scan hook.
+pub struct AttachmentService {
+    store: Arc<dyn AttachmentStore>,
+    pub max_file_bytes: usize,
+    pub max_domain_bytes: u64,
+    scan_command: Option<String>,
+}
+
+impl AttachmentService {
+    pub fn new(
+        store: Arc<dyn AttachmentStore>,
+        max_file_bytes: usize,
+        max_domain_bytes: u64,
+        scan_command: Option<String>,
+    ) -> Self {
+        Self {
+            store,
+            max_file_bytes,
+            max_domain_bytes,
+            scan_command,
+        }
+    }
+
+    /// Configure from the `ATTACHMENT_*` environment variables.
+    pub fn from_env() -> Self {
+        let store: Arc<dyn AttachmentStore> = match S3AttachmentStore::from_env() {
+            Some(s3) => Arc::new(s3),
+            None => {
+                let root = env::var("ATTACHMENT_STORAGE_DIR")
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|_| {
+                        match crate::services::local_mode::local_mode() {
+                            Some(local) => local.data_dir.clone(),
+                            None => PathBuf::from(
+                                env::var("WORKSPACE_DATA").unwrap_or_else(|_| ".".into()),
+                            ),
+                        }
+                        .join(".attachments")
+                    });
+                Arc::new(FileAttachmentStore::new(root))
+            }
+        };
+        Self::new(
+            store,
+            max_file_bytes_from_env(),
+            env::var("ATTACHMENT_DOMAIN_QUOTA_BYTES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_DOMAIN_QUOTA_BYTES),
+            env::var("ATTACHMENT_SCAN_COMMAND")
+                .ok()
+                .filter(|c| !c.trim().is_empty()),
+        )
+    }
+
+    /// Check size limits, scan and store an upload, returning its index entry.
+    ///
+    /// `key_prefix` namespaces the object key, e.g. by user and domain.
+    pub async fn upload(
+        &self,
+        index: &AttachmentIndex,
+        key_prefix: &str,
+        upload: AttachmentUpload<'_>,
+    ) -> std::result::Result<Attachment, UploadError> {
+        let content = upload.content;
+        if content.is_empty() {
+            return Err(UploadError::Empty);
+        }
+        if content.len() > self.max_file_bytes {
+            return Err(UploadError::FileTooLarge {
+                limit: self.max_file_bytes,
+            });
+        }
+        if index.total_size() + content.len() as u64 > self.max_domain_bytes {
+            return Err(UploadError::QuotaExceeded {
+                quota: self.max_domain_bytes,
+            });
+        }
+        if let Some(reason) = self.scan(&content).await.map_err(UploadError::Storage)? {
+            warn!(
+                "Attachment {} rejected by virus scan: {}",
+                upload.filename, reason
+            );
+            return Err(UploadError::Infected(reason));
+        }
+
+        let id = Uuid::new_v4();
+        let attachment = Attachment {
+            id,
+            target: upload.target,
+            target_id: upload.target_id.map(str::to_string),
+            filename: sanitize_filename(upload.filename),
+            content_type: upload
+                .content_type
+                .filter(|c| !c.is_empty())
+                .unwrap_or("application/octet-stream")
+                .to_string(),
+            size: content.len() as u64,
+            sha256: format!("{:x}", Sha256::digest(&content)),
+            storage_key: format!("{}/{}", key_prefix.trim_matches('/'), id),
+            uploaded_by: upload.uploaded_by.to_string(),
+            uploaded_at: Utc::now(),
+        };
+        self.store
+            .put(&attachment.storage_key, content)
+            .await
+            .map_err(UploadError::Storage)?;
+        info!(
+            "Stored attachment {} ({} bytes) in {} store",
+            attachment.filename,
+            attachment.size,
+            self.store.backend()
+        );
+        Ok(attachment)
+    }
+
+    pub async fn download(&self, attachment: &Attachment) -> Result<Vec<u8>> {
+        self.store.get(&attachment.storage_key).await
+    }
+
+    pub async fn delete(&self, attachment: &Attachment) -> Result<()> {
+        self.store.delete(&attachment.storage_key).await
+    }
+
+    /// Run the scan command; returns the rejection reason for infected content.
+    async fn scan(&self, content: &[u8]) -> Result<Option<String>> {
+        let Some(command) = &self.scan_command else {
+            return Ok(None);
+        };
+        let mut parts = command.split_whitespace();
+        let program = parts.next().ok_or_else(|| anyhow!("Empty scan command"))?;
+        let mut child = tokio::process::Command::new(program)
+            .args(parts)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .with_context(|| format!("Failed to start scan command {}", program))?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(content).await?;
+        }
+        let output = child.wait_with_output().await?;
+        if output.status.success() {
+            return Ok(None);
+        }
+        let report = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok(Some(if report.is_empty() {
+            format!("scan command exited with {}", output.status)
+        } else {
+            report
+        }))
+    }
+}
+
+/// Keep only the final path component and drop control characters.
+fn sanitize_filename(filename: &str) -> String {
+    let name = Path::new(filename.trim())
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let name: String = name.chars().filter(|c| !c.is_control()).collect();
+    if name.is_empty() {
+        "attachment".to_string()
+    } else {
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(content: &[u8]) -> AttachmentUpload<'static> {
+        AttachmentUpload {
+            target: AttachmentTarget::Table,
+            target_id: Some("t1"),
+            filename: "../samples/payload.json",
+            content_type: Some("application/json"),
+            content: content.to_vec(),
+            uploaded_by: "user@example.com",
+        }
+    }
+
+    #[tokio::test]
+    async fn test_upload_limits_and_file_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let service = AttachmentService::new(
+            Arc::new(FileAttachmentStore::new(dir.path().to_path_buf())),
+            8,
+            12,
+            None,
+        );
+        let mut index = AttachmentIndex::default();
+        let attachment = service
+            .upload(&index, "user/sales", sample(b"{\"a\":1}"))
+            .await
+            .unwrap();
+        assert_eq!(attachment.filename, "payload.json");
+        assert_eq!(service.download(&attachment).await.unwrap(), b"{\"a\":1}");
+        index.attachments.push(attachment.clone());
+        assert_eq!(
+            index.for_target(AttachmentTarget::Table, Some("t1")).len(),
+            1
+        );
+
+        assert!(matches!(
+            service
+                .upload(&index, "user/sales", sample(b"123456789"))
+                .await,
+            Err(UploadError::FileTooLarge { limit: 8 })
+        ));
+        assert!(matches!(
+            service
+                .upload(&index, "user/sales", sample(b"123456"))
+                .await,
+            Err(UploadError::QuotaExceeded { quota: 12 })
+        ));
+
+        service.delete(&attachment).await.unwrap();
+        assert!(service.download(&attachment).await.is_err());
+    }
+}
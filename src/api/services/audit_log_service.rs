@@ -0,0 +1,453 @@
+//! Audit log of model changes.
+//!
+//! Every create, update and delete of a table, relationship, domain or cross-domain table
+//! reference is recorded as an [`AuditRecord`]: who made the change, when, the entity's state
+//! before and after it, and the fields that changed. Governance teams read a domain's records
+//! for compliance reviews, filtered by actor and entity type.
+//!
+//! Records are stored per workspace and keyed by the domain's name at the time of the change,
+//! so the records of a deleted domain are kept:
+//! - file mode: `audit.jsonl` in the workspace directory, one JSON record per line
+//! - PostgreSQL mode: the `audit_entries` table, also read by the `/audit/.../history` routes
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use sqlx::{PgPool, Row};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::event_log_service::ChangeEventType;
+
+/// File name of a workspace's audit log in file mode
+pub const AUDIT_LOG_FILE: &str = "audit.jsonl";
+
+/// Kind of entity an audit record is about
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditEntityType {
+    Table,
+    Relationship,
+    Domain,
+    CrossDomainRef,
+}
+
+impl AuditEntityType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditEntityType::Table => "table",
+            AuditEntityType::Relationship => "relationship",
+            AuditEntityType::Domain => "domain",
+            AuditEntityType::CrossDomainRef => "cross_domain_ref",
+        }
+    }
+}
+
+/// What was done to the entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+        }
+    }
+}
+
+/// Entity type and action of a table or relationship change event
+pub fn audit_kind(event_type: ChangeEventType) -> (AuditEntityType, AuditAction) {
+    match event_type {
+        ChangeEventType::TableCreated => (AuditEntityType::Table, AuditAction::Create),
+        ChangeEventType::TableUpdated => (AuditEntityType::Table, AuditAction::Update),
+        ChangeEventType::TableDeleted => (AuditEntityType::Table, AuditAction::Delete),
+        ChangeEventType::RelationshipCreated => {
+            (AuditEntityType::Relationship, AuditAction::Create)
+        }
+        ChangeEventType::RelationshipUpdated => {
+            (AuditEntityType::Relationship, AuditAction::Update)
+        }
+        ChangeEventType::RelationshipDeleted => {
+            (AuditEntityType::Relationship, AuditAction::Delete)
+        }
+    }
+}
+
+/// One audited change.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditRecord {
+    pub id: Uuid,
+    /// Name of the domain at the time of the change
+    pub domain: String,
+    pub entity_type: AuditEntityType,
+    pub entity_id: Uuid,
+    pub action: AuditAction,
+    /// Email of the user who made the change
+    pub actor: String,
+    pub actor_id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    /// Changed fields as `{"field": {"before": ..., "after": ...}}`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changes: Option<Value>,
+    /// Entity state before the change (absent for creates)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<Value>,
+    /// Entity state after the change (absent for deletes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<Value>,
+}
+
+impl AuditRecord {
+    pub fn new(
+        domain: &str,
+        entity_type: AuditEntityType,
+        entity_id: Uuid,
+        action: AuditAction,
+        actor: (&str, Uuid),
+        before: Option<Value>,
+        after: Option<Value>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            domain: domain.to_string(),
+            entity_type,
+            entity_id,
+            action,
+            actor: actor.0.to_string(),
+            actor_id: actor.1,
+            timestamp: Utc::now(),
+            changes: diff(before.as_ref(), after.as_ref()),
+            before,
+            after,
+        }
+    }
+}
+
+/// Top-level fields that differ between two entity states, `None` if nothing changed.
+pub fn diff(before: Option<&Value>, after: Option<&Value>) -> Option<Value> {
+    let fields = |state: Option<&Value>| match state {
+        Some(Value::Object(fields)) => fields.clone(),
+        _ => Map::new(),
+    };
+    let (before, after) = (fields(before), fields(after));
+    let mut changes = Map::new();
+    for key in before.keys().chain(after.keys()) {
+        let (old, new) = (before.get(key), after.get(key));
+        if old != new && !changes.contains_key(key) {
+            changes.insert(key.clone(), json!({"before": old, "after": new}));
+        }
+    }
+    (!changes.is_empty()).then_some(Value::Object(changes))
+}
+
+/// Which records of a domain to read
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub actor: Option<String>,
+    pub entity_type: Option<AuditEntityType>,
+    pub limit: usize,
+    pub offset: usize,
+}
+
+impl AuditFilter {
+    fn matches(&self, record: &AuditRecord) -> bool {
+        self.actor
+            .as_deref()
+            .is_none_or(|actor| record.actor.eq_ignore_ascii_case(actor))
+            && self.entity_type.is_none_or(|t| record.entity_type == t)
+    }
+}
+
+/// A workspace's audit log.
+#[async_trait]
+pub trait AuditLog: Send + Sync {
+    /// Append a record.
+    async fn append(&self, record: &AuditRecord) -> Result<()>;
+
+    /// A page of a domain's records matching the filter, newest first, and the number of
+    /// matching records.
+    async fn read(&self, domain: &str, filter: &AuditFilter) -> Result<(Vec<AuditRecord>, usize)>;
+}
+
+/// Serializes appends to audit log files.
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+
+/// JSON-lines audit log in a workspace directory (file mode).
+pub struct JsonlAuditLog {
+    path: PathBuf,
+}
+
+impl JsonlAuditLog {
+    pub fn new(workspace_dir: PathBuf) -> Self {
+        Self {
+            path: workspace_dir.join(AUDIT_LOG_FILE),
+        }
+    }
+}
+
+#[async_trait]
+impl AuditLog for JsonlAuditLog {
+    async fn append(&self, record: &AuditRecord) -> Result<()> {
+        let _guard = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open {}", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+        file.flush()?;
+        Ok(())
+    }
+
+    async fn read(&self, domain: &str, filter: &AuditFilter) -> Result<(Vec<AuditRecord>, usize)> {
+        if !self.path.exists() {
+            return Ok((Vec::new(), 0));
+        }
+        let reader = BufReader::new(
+            File::open(&self.path)
+                .with_context(|| format!("Failed to open {}", self.path.display()))?,
+        );
+        let mut records: Vec<AuditRecord> = reader
+            .lines()
+            .map_while(|l| l.ok())
+            .filter_map(|l| serde_json::from_str::<AuditRecord>(&l).ok())
+            .filter(|r| r.domain == domain && filter.matches(r))
+            .collect();
+        let total = records.len();
+        records.reverse();
+        Ok((
+            records
+                .into_iter()
+                .skip(filter.offset)
+                .take(filter.limit)
+                .collect(),
+            total,
+        ))
+    }
+}
+
+/// Audit log in the `audit_entries` table (PostgreSQL mode).
+pub struct PostgresAuditLog {
+    pool: PgPool,
+    workspace_id: Uuid,
+    /// Id of the domain records are appended for; `None` once it is deleted
+    domain_id: Option<Uuid>,
+}
+
+impl PostgresAuditLog {
+    pub fn new(pool: PgPool, workspace_id: Uuid, domain_id: Option<Uuid>) -> Self {
+        Self {
+            pool,
+            workspace_id,
+            domain_id,
+        }
+    }
+}
+
+#[async_trait]
+impl AuditLog for PostgresAuditLog {
+    async fn append(&self, record: &AuditRecord) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO audit_entries
+                (id, entity_type, entity_id, workspace_id, domain_id, domain_name, action,
+                 user_id, user_email, changes, previous_data, new_data, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            "#,
+        )
+        .bind(record.id)
+        .bind(record.entity_type.as_str())
+        .bind(record.entity_id)
+        .bind(self.workspace_id)
+        .bind(self.domain_id)
+        .bind(&record.domain)
+        .bind(record.action.as_str())
+        .bind(record.actor_id)
+        .bind(&record.actor)
+        .bind(&record.changes)
+        .bind(&record.before)
+        .bind(&record.after)
+        .bind(record.timestamp)
+        .execute(&self.pool)
+        .await
+        .context("Failed to insert audit entry")?;
+        Ok(())
+    }
+
+    async fn read(&self, domain: &str, filter: &AuditFilter) -> Result<(Vec<AuditRecord>, usize)> {
+        let actor = filter.actor.as_deref();
+        let entity_type = filter.entity_type.map(|t| t.as_str());
+        let total: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*)
+            FROM audit_entries
+            WHERE workspace_id = $1 AND domain_name = $2
+              AND ($3::TEXT IS NULL OR LOWER(user_email) = LOWER($3))
+              AND ($4::TEXT IS NULL OR entity_type = $4)
+            "#,
+        )
+        .bind(self.workspace_id)
+        .bind(domain)
+        .bind(actor)
+        .bind(entity_type)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to count audit entries")?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT id, entity_type, entity_id, action, user_id, user_email, changes,
+                   previous_data, new_data, created_at
+            FROM audit_entries
+            WHERE workspace_id = $1 AND domain_name = $2
+              AND ($3::TEXT IS NULL OR LOWER(user_email) = LOWER($3))
+              AND ($4::TEXT IS NULL OR entity_type = $4)
+            ORDER BY created_at DESC
+            LIMIT $5 OFFSET $6
+            "#,
+        )
+        .bind(self.workspace_id)
+        .bind(domain)
+        .bind(actor)
+        .bind(entity_type)
+        .bind(filter.limit as i64)
+        .bind(filter.offset as i64)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to read audit entries")?;
+
+        let mut records = Vec::with_capacity(rows.len());
+        for row in rows {
+            let entity_type: String = row.try_get("entity_type")?;
+            let action: String = row.try_get("action")?;
+            let (Ok(entity_type), Ok(action)) = (
+                serde_json::from_value(Value::String(entity_type)),
+                serde_json::from_value(Value::String(action)),
+            ) else {
+                continue;
+            };
+            records.push(AuditRecord {
+                id: row.try_get("id")?,
+                domain: domain.to_string(),
+                entity_type,
+                entity_id: row.try_get("entity_id")?,
+                action,
+                actor: row.try_get("user_email")?,
+                actor_id: row.try_get("user_id")?,
+                timestamp: row.try_get("created_at")?,
+                changes: row.try_get("changes")?,
+                before: row.try_get("previous_data")?,
+                after: row.try_get("new_data")?,
+            });
+        }
+        Ok((records, total.max(0) as usize))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_jsonl_audit_log_filters_and_pages() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = JsonlAuditLog::new(dir.path().to_path_buf());
+        let alice = ("alice@example.com", Uuid::new_v4());
+        let table_id = Uuid::new_v4();
+
+        let created = AuditRecord::new(
+            "sales",
+            AuditEntityType::Table,
+            table_id,
+            AuditAction::Create,
+            alice,
+            None,
+            Some(json!({"name": "orders", "description": ""})),
+        );
+        assert_eq!(
+            created.changes,
+            Some(json!({
+                "name": {"before": null, "after": "orders"},
+                "description": {"before": null, "after": ""}
+            }))
+        );
+        log.append(&created).await.unwrap();
+        let updated = AuditRecord::new(
+            "sales",
+            AuditEntityType::Table,
+            table_id,
+            AuditAction::Update,
+            ("bob@example.com", Uuid::new_v4()),
+            Some(json!({"name": "orders", "description": ""})),
+            Some(json!({"name": "orders", "description": "All orders"})),
+        );
+        assert_eq!(
+            updated.changes,
+            Some(json!({"description": {"before": "", "after": "All orders"}}))
+        );
+        log.append(&updated).await.unwrap();
+        log.append(&AuditRecord::new(
+            "sales",
+            AuditEntityType::Domain,
+            Uuid::new_v4(),
+            AuditAction::Create,
+            alice,
+            None,
+            Some(json!({"name": "sales"})),
+        ))
+        .await
+        .unwrap();
+        log.append(&AuditRecord::new(
+            "hr",
+            AuditEntityType::Domain,
+            Uuid::new_v4(),
+            AuditAction::Create,
+            alice,
+            None,
+            Some(json!({"name": "hr"})),
+        ))
+        .await
+        .unwrap();
+
+        let filter = |actor: Option<&str>, entity_type, offset| AuditFilter {
+            actor: actor.map(str::to_string),
+            entity_type,
+            limit: 2,
+            offset,
+        };
+        let (page, total) = log.read("sales", &filter(None, None, 0)).await.unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(page[0].entity_type, AuditEntityType::Domain);
+        assert_eq!(page[1].action, AuditAction::Update);
+        let (page, _) = log.read("sales", &filter(None, None, 2)).await.unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].action, AuditAction::Create);
+
+        let (page, total) = log
+            .read(
+                "sales",
+                &filter(Some("Alice@example.com"), Some(AuditEntityType::Table), 0),
+            )
+            .await
+            .unwrap();
+        assert_eq!(total, 1);
+        assert_eq!(page[0].id, created.id);
+    }
+}
@@ -0,0 +1,500 @@
+//! Avro IDL (.avdl) parser.
+//!
+//! Converts Avro IDL protocols (and Avro 1.11 IDL schema files) into the equivalent
+//! JSON Avro schemas so they can be imported through the regular Avro schema path.
+//!
+//! Supported: `protocol`, `namespace`, `record`/`error`, `enum`, `fixed`, primitive and
+//! logical types (`date`, `time_ms`, `timestamp_ms`, `uuid`, `decimal(p,s)`), `array<T>`,
+//! `map<T>`, `union { ... }`, optional `T?`, doc comments and `@namespace` annotations.
+//! Messages and imports are skipped.
+
+use anyhow::{Result, anyhow};
+use serde_json::{Value, json};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(String),
+    Doc(String),
+    Punct(char),
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+        } else if c == '/' && chars.get(i + 1) == Some(&'*') {
+            let is_doc = chars.get(i + 2) == Some(&'*') && chars.get(i + 3) != Some(&'/');
+            let start = i + if is_doc { 3 } else { 2 };
+            let mut end = start;
+            while end + 1 < chars.len() && !(chars[end] == '*' && chars[end + 1] == '/') {
+                end += 1;
+            }
+            if end + 1 >= chars.len() {
+                return Err(anyhow!("Unterminated comment"));
+            }
+            if is_doc {
+                let text: String = chars[start..end].iter().collect();
+                let doc = text
+                    .lines()
+                    .map(|l| l.trim().trim_start_matches('*').trim())
+                    .filter(|l| !l.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                tokens.push(Token::Doc(doc));
+            }
+            i = end + 2;
+        } else if c == '"' {
+            let mut value = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != '"' {
+                if chars[i] == '\\' && i + 1 < chars.len() {
+                    i += 1;
+                }
+                value.push(chars[i]);
+                i += 1;
+            }
+            if i >= chars.len() {
+                return Err(anyhow!("Unterminated string literal"));
+            }
+            tokens.push(Token::Str(value));
+            i += 1;
+        } else if c == '`' {
+            let mut value = String::new();
+            i += 1;
+            while i < chars.len() && chars[i] != '`' {
+                value.push(chars[i]);
+                i += 1;
+            }
+            tokens.push(Token::Ident(value));
+            i += 1;
+        } else if c.is_ascii_digit()
+            || (c == '-' && chars.get(i + 1).is_some_and(|d| d.is_ascii_digit()))
+        {
+            let mut value = String::from(c);
+            i += 1;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '.') {
+                value.push(chars[i]);
+                i += 1;
+            }
+            tokens.push(Token::Number(value));
+        } else if c.is_alphabetic() || c == '_' {
+            let mut value = String::new();
+            while i < chars.len()
+                && (chars[i].is_alphanumeric()
+                    || chars[i] == '_'
+                    || chars[i] == '.'
+                    || chars[i] == '-')
+            {
+                value.push(chars[i]);
+                i += 1;
+            }
+            tokens.push(Token::Ident(value));
+        } else {
+            tokens.push(Token::Punct(c));
+            i += 1;
+        }
+    }
+    Ok(tokens)
+}
+
+struct IdlParser {
+    tokens: Vec<Token>,
+    pos: usize,
+    namespace: Option<String>,
+}
+
+impl IdlParser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn is_punct(&self, c: char) -> bool {
+        self.peek() == Some(&Token::Punct(c))
+    }
+
+    fn expect_punct(&mut self, c: char) -> Result<()> {
+        match self.next() {
+            Some(Token::Punct(p)) if p == c => Ok(()),
+            other => Err(anyhow!("Expected '{}', found {:?}", c, other)),
+        }
+    }
+
+    fn expect_ident(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::Ident(s)) => Ok(s),
+            other => Err(anyhow!("Expected identifier, found {:?}", other)),
+        }
+    }
+
+    /// Skip tokens until the given punctuation at nesting depth zero (consumed).
+    fn skip_past(&mut self, c: char) {
+        let mut depth = 0;
+        while let Some(token) = self.next() {
+            match token {
+                Token::Punct('{') | Token::Punct('(') | Token::Punct('[') => depth += 1,
+                Token::Punct('}') | Token::Punct(')') | Token::Punct(']') => depth -= 1,
+                Token::Punct(p) if p == c && depth <= 0 => return,
+                _ => {}
+            }
+            if depth < 0 {
+                return;
+            }
+        }
+    }
+
+    /// Skip a balanced parenthesised group, returning the first string literal inside.
+    fn skip_parens(&mut self) -> Result<Option<String>> {
+        self.expect_punct('(')?;
+        let mut depth = 1;
+        let mut first_string = None;
+        while depth > 0 {
+            match self.next() {
+                Some(Token::Punct('(')) => depth += 1,
+                Some(Token::Punct(')')) => depth -= 1,
+                Some(Token::Str(s)) if first_string.is_none() => first_string = Some(s),
+                Some(_) => {}
+                None => return Err(anyhow!("Unbalanced parentheses")),
+            }
+        }
+        Ok(first_string)
+    }
+
+    /// Consume doc comments and annotations, returning (doc, namespace annotation).
+    fn leading(&mut self) -> Result<(Option<String>, Option<String>)> {
+        let mut doc = None;
+        let mut namespace = None;
+        loop {
+            match self.peek() {
+                Some(Token::Doc(d)) => {
+                    doc = Some(d.clone());
+                    self.pos += 1;
+                }
+                Some(Token::Punct('@')) => {
+                    self.pos += 1;
+                    let name = self.expect_ident()?;
+                    let value = self.skip_parens()?;
+                    if name == "namespace" {
+                        namespace = value;
+                    }
+                }
+                _ => return Ok((doc, namespace)),
+            }
+        }
+    }
+
+    fn parse_document(&mut self) -> Result<Vec<Value>> {
+        let mut schemas = Vec::new();
+        let (_, namespace) = self.leading()?;
+        if namespace.is_some() {
+            self.namespace = namespace;
+        }
+
+        if matches!(self.peek(), Some(Token::Ident(s)) if s == "protocol") {
+            self.pos += 1;
+            let _name = self.expect_ident()?;
+            self.expect_punct('{')?;
+            while !self.is_punct('}') {
+                if self.peek().is_none() {
+                    return Err(anyhow!("Unexpected end of protocol"));
+                }
+                if let Some(schema) = self.parse_declaration()? {
+                    schemas.push(schema);
+                }
+            }
+            self.pos += 1;
+        } else {
+            while self.peek().is_some() {
+                if let Some(schema) = self.parse_declaration()? {
+                    schemas.push(schema);
+                }
+            }
+        }
+        Ok(schemas)
+    }
+
+    fn parse_declaration(&mut self) -> Result<Option<Value>> {
+        let (doc, namespace) = self.leading()?;
+        let keyword = match self.peek() {
+            Some(Token::Ident(s)) => s.clone(),
+            Some(_) => {
+                self.pos += 1;
+                return Ok(None);
+            }
+            None => return Ok(None),
+        };
+        let namespace = namespace.or_else(|| self.namespace.clone());
+
+        match keyword.as_str() {
+            "namespace" => {
+                self.pos += 1;
+                self.namespace = Some(self.expect_ident()?);
+                self.skip_past(';');
+                Ok(None)
+            }
+            "record" | "error" => {
+                self.pos += 1;
+                let name = self.expect_ident()?;
+                self.expect_punct('{')?;
+                let mut fields = Vec::new();
+                while !self.is_punct('}') {
+                    if self.peek().is_none() {
+                        return Err(anyhow!("Unexpected end of record {}", name));
+                    }
+                    fields.extend(self.parse_fields()?);
+                }
+                self.pos += 1;
+                let mut record = json!({ "type": "record", "name": name, "fields": fields });
+                if let Some(ns) = namespace {
+                    record["namespace"] = json!(ns);
+                }
+                if let Some(doc) = doc {
+                    record["doc"] = json!(doc);
+                }
+                Ok(Some(record))
+            }
+            "enum" => {
+                self.pos += 1;
+                let name = self.expect_ident()?;
+                self.expect_punct('{')?;
+                let mut symbols = Vec::new();
+                while !self.is_punct('}') {
+                    match self.next() {
+                        Some(Token::Ident(s)) => symbols.push(s),
+                        Some(Token::Punct(',')) => {}
+                        other => return Err(anyhow!("Unexpected token in enum: {:?}", other)),
+                    }
+                }
+                self.pos += 1;
+                if self.is_punct('=') {
+                    self.skip_past(';');
+                }
+                let mut schema = json!({ "type": "enum", "name": name, "symbols": symbols });
+                if let Some(ns) = namespace {
+                    schema["namespace"] = json!(ns);
+                }
+                Ok(Some(schema))
+            }
+            "fixed" => {
+                self.pos += 1;
+                let name = self.expect_ident()?;
+                self.expect_punct('(')?;
+                let size = match self.next() {
+                    Some(Token::Number(n)) => n.parse::<u64>().unwrap_or(0),
+                    other => return Err(anyhow!("Expected fixed size, found {:?}", other)),
+                };
+                self.expect_punct(')')?;
+                self.skip_past(';');
+                let mut schema = json!({ "type": "fixed", "name": name, "size": size });
+                if let Some(ns) = namespace {
+                    schema["namespace"] = json!(ns);
+                }
+                Ok(Some(schema))
+            }
+            // Imports, `schema X;` declarations and messages are not needed for table import
+            _ => {
+                self.skip_past(';');
+                Ok(None)
+            }
+        }
+    }
+
+    /// Parse one field declaration, which may declare several variables of the same type.
+    fn parse_fields(&mut self) -> Result<Vec<Value>> {
+        let (doc, _) = self.leading()?;
+        let field_type = self.parse_type()?;
+        let mut fields = Vec::new();
+        loop {
+            let (_, _) = self.leading()?;
+            let name = self.expect_ident()?;
+            let mut field = json!({ "name": name, "type": field_type.clone() });
+            if let Some(doc) = &doc {
+                field["doc"] = json!(doc);
+            }
+            fields.push(field);
+
+            if self.is_punct('=') {
+                // Skip default value up to the next ',' or ';' at depth zero
+                self.pos += 1;
+                let mut depth = 0;
+                while let Some(token) = self.peek() {
+                    match token {
+                        Token::Punct('{') | Token::Punct('[') => depth += 1,
+                        Token::Punct('}') | Token::Punct(']') => depth -= 1,
+                        Token::Punct(',') | Token::Punct(';') if depth == 0 => break,
+                        _ => {}
+                    }
+                    self.pos += 1;
+                }
+            }
+            match self.next() {
+                Some(Token::Punct(',')) => continue,
+                Some(Token::Punct(';')) => break,
+                other => {
+                    return Err(anyhow!(
+                        "Expected ',' or ';' after field, found {:?}",
+                        other
+                    ));
+                }
+            }
+        }
+        Ok(fields)
+    }
+
+    fn parse_type(&mut self) -> Result<Value> {
+        let (_, _) = self.leading()?;
+        let name = self.expect_ident()?;
+        let base = match name.as_str() {
+            "array" => {
+                self.expect_punct('<')?;
+                let items = self.parse_type()?;
+                self.expect_punct('>')?;
+                json!({ "type": "array", "items": items })
+            }
+            "map" => {
+                self.expect_punct('<')?;
+                let values = self.parse_type()?;
+                self.expect_punct('>')?;
+                json!({ "type": "map", "values": values })
+            }
+            "union" => {
+                self.expect_punct('{')?;
+                let mut types = Vec::new();
+                while !self.is_punct('}') {
+                    if self.is_punct(',') {
+                        self.pos += 1;
+                        continue;
+                    }
+                    types.push(self.parse_type()?);
+                }
+                self.pos += 1;
+                json!(types)
+            }
+            "decimal" => {
+                self.expect_punct('(')?;
+                let mut args = Vec::new();
+                while !self.is_punct(')') {
+                    match self.next() {
+                        Some(Token::Number(n)) => args.push(n.parse::<u64>().unwrap_or(0)),
+                        Some(Token::Punct(',')) => {}
+                        other => return Err(anyhow!("Invalid decimal argument: {:?}", other)),
+                    }
+                }
+                self.pos += 1;
+                json!({
+                    "type": "bytes",
+                    "logicalType": "decimal",
+                    "precision": args.first().copied().unwrap_or(0),
+                    "scale": args.get(1).copied().unwrap_or(0)
+                })
+            }
+            "date" => json!({ "type": "int", "logicalType": "date" }),
+            "time_ms" => json!({ "type": "int", "logicalType": "time-millis" }),
+            "timestamp_ms" => json!({ "type": "long", "logicalType": "timestamp-millis" }),
+            "local_timestamp_ms" => {
+                json!({ "type": "long", "logicalType": "local-timestamp-millis" })
+            }
+            "uuid" => json!({ "type": "string", "logicalType": "uuid" }),
+            _ => json!(name),
+        };
+        if self.is_punct('?') {
+            self.pos += 1;
+            return Ok(json!(["null", base]));
+        }
+        Ok(base)
+    }
+}
+
+/// Parse Avro IDL content into JSON Avro schemas (records, enums and fixed types).
+pub fn parse_avro_idl(content: &str) -> Result<Vec<Value>> {
+    let mut parser = IdlParser {
+        tokens: tokenize(content)?,
+        pos: 0,
+        namespace: None,
+    };
+    let schemas = parser.parse_document()?;
+    if schemas.is_empty() {
+        return Err(anyhow!("No named types found in Avro IDL"));
+    }
+    Ok(schemas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::AvroParser;
+
+    #[test]
+    fn test_parse_idl_records_and_references() {
+        let idl = r#"
+@namespace("com.example")
+protocol Orders {
+  enum Status { NEW, SHIPPED }
+
+  /** A customer */
+  record Customer {
+    long id;
+    string? email;
+  }
+
+  record Order {
+    long id;
+    Status status = "NEW";
+    Customer customer;
+    array<LineItem> items;
+    decimal(10, 2) total;
+    timestamp_ms created_at;
+  }
+
+  record LineItem { string sku; int quantity; }
+
+  void ping();
+}
+"#;
+        let schemas = parse_avro_idl(idl).unwrap();
+        assert_eq!(schemas.len(), 4);
+
+        let result = AvroParser::new().parse_schema_set(&schemas);
+        assert_eq!(result.tables.len(), 3);
+        let order = result.tables.iter().find(|t| t.name == "Order").unwrap();
+        assert_eq!(order.schema_name.as_deref(), Some("com.example"));
+        assert_eq!(order.columns[1].enum_values, vec!["NEW", "SHIPPED"]);
+        assert_eq!(order.columns[4].data_type, "DECIMAL(10,2)");
+        assert_eq!(order.columns[5].data_type, "TIMESTAMP");
+
+        let customer = result.tables.iter().find(|t| t.name == "Customer").unwrap();
+        assert!(customer.columns[1].nullable);
+
+        assert_eq!(result.relationships.len(), 2);
+        assert!(
+            result
+                .relationships
+                .iter()
+                .any(|r| r.target_table == "Customer"
+                    && r.cardinality == Some(crate::models::enums::Cardinality::OneToOne))
+        );
+        assert!(
+            result
+                .relationships
+                .iter()
+                .any(|r| r.target_table == "LineItem"
+                    && r.cardinality == Some(crate::models::enums::Cardinality::OneToMany))
+        );
+    }
+}
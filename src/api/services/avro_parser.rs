@@ -1,11 +1,35 @@
 //! AVRO schema parser for importing AVRO schemas into data models.
 
+use crate::models::enums::Cardinality;
 use crate::models::{Column, Table};
+use crate::services::avro_idl_parser::parse_avro_idl;
+use crate::services::relationship_service::ImportedRelationship;
 use anyhow::{Context, Result};
 use serde_json::{Value, json};
 use std::collections::HashMap;
+use std::io::Read;
 use tracing::info;
 
+/// Maximum total uncompressed size of a schema archive (50 MB).
+const MAX_ARCHIVE_SIZE: u64 = 50 * 1024 * 1024;
+
+/// Result of parsing a set of Avro schemas with cross-references resolved.
+#[derive(Debug, Default)]
+pub struct AvroSchemaSetResult {
+    pub tables: Vec<Table>,
+    pub relationships: Vec<ImportedRelationship>,
+    pub errors: Vec<ParserError>,
+}
+
+/// How an Avro field type maps onto a column.
+struct ResolvedType {
+    data_type: String,
+    nullable: bool,
+    enum_values: Vec<String>,
+    /// Referenced record fullname and whether it is a collection (array/map)
+    reference: Option<(String, bool)>,
+}
+
 /// Parser for AVRO schema format.
 #[derive(Default)]
 pub struct AvroParser;
@@ -123,6 +147,7 @@ impl AvroParser {
             data_vault_classification: None,
             modeling_level: None,
             tags: Vec::new(),
+            aliases: Vec::new(),
             odcl_metadata,
             position: None,
             yaml_file_path: None,
@@ -204,6 +229,10 @@ impl AvroParser {
                 quality: Vec::new(),
                 enum_values: Vec::new(),
                 errors: Vec::new(),
+                classification: None,
+                masking_policy: None,
+                glossary_links: Vec::new(),
+                custom_properties: HashMap::new(),
                 column_order: 0,
             });
         } else if let Some(type_obj) = avro_type.as_object() {
@@ -295,6 +324,10 @@ impl AvroParser {
                     quality: Vec::new(),
                     enum_values: Vec::new(),
                     errors: Vec::new(),
+                    classification: None,
+                    masking_policy: None,
+                    glossary_links: Vec::new(),
+                    custom_properties: HashMap::new(),
                     column_order: 0,
                 });
             } else {
@@ -312,6 +345,10 @@ impl AvroParser {
                     quality: Vec::new(),
                     enum_values: Vec::new(),
                     errors: Vec::new(),
+                    classification: None,
+                    masking_policy: None,
+                    glossary_links: Vec::new(),
+                    custom_properties: HashMap::new(),
                     column_order: 0,
                 });
             }
@@ -338,6 +375,402 @@ impl AvroParser {
     }
 }
 
+impl AvroParser {
+    /// Parse Avro IDL (.avdl) content, creating one table per record.
+    pub fn parse_idl(&self, idl_content: &str) -> Result<AvroSchemaSetResult> {
+        let schemas = parse_avro_idl(idl_content)?;
+        Ok(self.parse_schema_set(&schemas))
+    }
+
+    /// Parse a zip archive of `.avsc` (and `.avdl`) files, resolving cross-file references.
+    pub fn parse_archive(&self, zip_bytes: &[u8]) -> Result<AvroSchemaSetResult> {
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(zip_bytes))
+            .context("Failed to read zip archive")?;
+
+        let mut schemas = Vec::new();
+        let mut errors = Vec::new();
+        let mut total_size = 0u64;
+
+        for idx in 0..archive.len() {
+            let mut file = archive.by_index(idx).context("Failed to read zip entry")?;
+            let name = file.name().to_string();
+            let is_avsc = name.ends_with(".avsc");
+            if file.is_dir() || !(is_avsc || name.ends_with(".avdl")) {
+                continue;
+            }
+
+            // Count the bytes actually inflated; the size in the entry header can lie
+            let mut bytes = Vec::new();
+            let read = file
+                .by_ref()
+                .take(MAX_ARCHIVE_SIZE - total_size + 1)
+                .read_to_end(&mut bytes);
+            total_size += bytes.len() as u64;
+            if total_size > MAX_ARCHIVE_SIZE {
+                return Err(anyhow::anyhow!("Archive exceeds maximum uncompressed size"));
+            }
+            let content = match read
+                .map_err(|e| e.to_string())
+                .and_then(|_| String::from_utf8(bytes).map_err(|e| e.to_string()))
+            {
+                Ok(content) => content,
+                Err(e) => {
+                    errors.push(ParserError {
+                        error_type: "parse_error".to_string(),
+                        field: Some(name),
+                        message: format!("Failed to read file: {}", e),
+                    });
+                    continue;
+                }
+            };
+
+            let parsed = if is_avsc {
+                serde_json::from_str::<Value>(&content)
+                    .map(|v| match v {
+                        Value::Array(items) => items,
+                        other => vec![other],
+                    })
+                    .map_err(anyhow::Error::from)
+            } else {
+                parse_avro_idl(&content)
+            };
+            match parsed {
+                Ok(mut items) => schemas.append(&mut items),
+                Err(e) => errors.push(ParserError {
+                    error_type: "parse_error".to_string(),
+                    field: Some(name),
+                    message: format!("Failed to parse schema: {}", e),
+                }),
+            }
+        }
+
+        if schemas.is_empty() {
+            return Err(anyhow::anyhow!("No Avro schemas found in archive"));
+        }
+
+        let mut result = self.parse_schema_set(&schemas);
+        errors.append(&mut result.errors);
+        result.errors = errors;
+        Ok(result)
+    }
+
+    /// Parse a set of Avro schemas, creating one table per named record.
+    ///
+    /// Named types are resolved by fullname (or by short name when unambiguous), so records
+    /// may reference types defined in other schemas. Record-typed fields become relationships
+    /// from the containing record to the referenced record.
+    pub fn parse_schema_set(&self, schemas: &[Value]) -> AvroSchemaSetResult {
+        let mut named: Vec<(String, Value)> = Vec::new();
+        for schema in schemas {
+            Self::collect_named_types(schema, None, &mut named);
+        }
+
+        let mut by_fullname: HashMap<String, &Value> = HashMap::new();
+        let mut short_names: HashMap<String, Vec<String>> = HashMap::new();
+        for (fullname, schema) in &named {
+            by_fullname.insert(fullname.clone(), schema);
+            let short = fullname.rsplit('.').next().unwrap_or(fullname).to_string();
+            short_names.entry(short).or_default().push(fullname.clone());
+        }
+
+        let mut result = AvroSchemaSetResult::default();
+        for (fullname, schema) in &named {
+            if schema.get("type").and_then(|v| v.as_str()) != Some("record")
+                && schema.get("type").and_then(|v| v.as_str()) != Some("error")
+            {
+                continue;
+            }
+            let (namespace, name) = match fullname.rsplit_once('.') {
+                Some((ns, name)) => (Some(ns.to_string()), name.to_string()),
+                None => (None, fullname.clone()),
+            };
+
+            let mut columns = Vec::new();
+            let fields = schema
+                .get("fields")
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default();
+            for field in &fields {
+                let Some(field_name) = field.get("name").and_then(|v| v.as_str()) else {
+                    result.errors.push(ParserError {
+                        error_type: "parse_error".to_string(),
+                        field: Some(name.clone()),
+                        message: "Field missing name".to_string(),
+                    });
+                    continue;
+                };
+                let Some(field_type) = field.get("type") else {
+                    result.errors.push(ParserError {
+                        error_type: "parse_error".to_string(),
+                        field: Some(format!("{}.{}", name, field_name)),
+                        message: "Field missing type".to_string(),
+                    });
+                    continue;
+                };
+
+                let resolved = Self::resolve_type(
+                    field_type,
+                    namespace.as_deref(),
+                    &by_fullname,
+                    &short_names,
+                );
+                let mut column = Column::new(field_name.to_string(), resolved.data_type);
+                column.nullable = resolved.nullable;
+                column.enum_values = resolved.enum_values;
+                column.column_order = columns.len() as i32;
+                column.description = field
+                    .get("doc")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string();
+                columns.push(column);
+
+                if let Some((target, is_collection)) = resolved.reference {
+                    let target_name = target.rsplit('.').next().unwrap_or(&target).to_string();
+                    result.relationships.push(ImportedRelationship {
+                        source_table: name.clone(),
+                        target_table: target_name,
+                        cardinality: Some(if is_collection {
+                            Cardinality::OneToMany
+                        } else {
+                            Cardinality::OneToOne
+                        }),
+                        foreign_key_details: None,
+                        relationship_type: None,
+                        notes: Some(format!("Avro field '{}'", field_name)),
+                    });
+                }
+            }
+
+            let mut table = Table::new(name, columns);
+            table.schema_name = namespace.clone();
+            if let Some(ns) = namespace {
+                table
+                    .odcl_metadata
+                    .insert("namespace".to_string(), json!(ns));
+            }
+            if let Some(doc) = schema.get("doc").and_then(|v| v.as_str()) {
+                table
+                    .odcl_metadata
+                    .insert("description".to_string(), json!(doc));
+            }
+            result.tables.push(table);
+        }
+
+        info!(
+            "Parsed AVRO schema set: {} tables, {} relationships",
+            result.tables.len(),
+            result.relationships.len()
+        );
+        result
+    }
+
+    /// Recursively collect named types (record, error, enum, fixed) with their fullnames.
+    fn collect_named_types(
+        schema: &Value,
+        namespace: Option<&str>,
+        out: &mut Vec<(String, Value)>,
+    ) {
+        match schema {
+            Value::Array(items) => {
+                for item in items {
+                    Self::collect_named_types(item, namespace, out);
+                }
+            }
+            Value::Object(obj) => {
+                let type_name = obj.get("type").and_then(|v| v.as_str()).unwrap_or_default();
+                match type_name {
+                    "record" | "error" | "enum" | "fixed" => {
+                        let Some(name) = obj.get("name").and_then(|v| v.as_str()) else {
+                            return;
+                        };
+                        let own_namespace =
+                            obj.get("namespace").and_then(|v| v.as_str()).or(namespace);
+                        let fullname = if name.contains('.') {
+                            name.to_string()
+                        } else if let Some(ns) = own_namespace.filter(|ns| !ns.is_empty()) {
+                            format!("{}.{}", ns, name)
+                        } else {
+                            name.to_string()
+                        };
+                        let child_namespace =
+                            fullname.rsplit_once('.').map(|(ns, _)| ns.to_string());
+                        if !out.iter().any(|(existing, _)| existing == &fullname) {
+                            out.push((fullname, schema.clone()));
+                        }
+                        if let Some(fields) = obj.get("fields").and_then(|v| v.as_array()) {
+                            for field in fields {
+                                if let Some(field_type) = field.get("type") {
+                                    Self::collect_named_types(
+                                        field_type,
+                                        child_namespace.as_deref(),
+                                        out,
+                                    );
+                                }
+                            }
+                        }
+                    }
+                    "array" => {
+                        if let Some(items) = obj.get("items") {
+                            Self::collect_named_types(items, namespace, out);
+                        }
+                    }
+                    "map" => {
+                        if let Some(values) = obj.get("values") {
+                            Self::collect_named_types(values, namespace, out);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Resolve an Avro field type to a column type, following named type references.
+    fn resolve_type(
+        field_type: &Value,
+        namespace: Option<&str>,
+        by_fullname: &HashMap<String, &Value>,
+        short_names: &HashMap<String, Vec<String>>,
+    ) -> ResolvedType {
+        let scalar = |data_type: &str| ResolvedType {
+            data_type: data_type.to_string(),
+            nullable: false,
+            enum_values: Vec::new(),
+            reference: None,
+        };
+
+        match field_type {
+            Value::Array(types) => {
+                let non_null: Vec<&Value> = types
+                    .iter()
+                    .filter(|t| t.as_str() != Some("null"))
+                    .collect();
+                let nullable = non_null.len() < types.len();
+                if non_null.len() == 1 {
+                    let mut resolved =
+                        Self::resolve_type(non_null[0], namespace, by_fullname, short_names);
+                    resolved.nullable = nullable;
+                    resolved
+                } else {
+                    let mut resolved = scalar("STRING");
+                    resolved.nullable = true;
+                    resolved
+                }
+            }
+            Value::Object(obj) => {
+                if let Some(logical) = obj.get("logicalType").and_then(|v| v.as_str()) {
+                    return scalar(&match logical {
+                        "date" => "DATE".to_string(),
+                        "time-millis" | "time-micros" => "TIME".to_string(),
+                        "timestamp-millis"
+                        | "timestamp-micros"
+                        | "local-timestamp-millis"
+                        | "local-timestamp-micros" => "TIMESTAMP".to_string(),
+                        "uuid" => "UUID".to_string(),
+                        "decimal" => format!(
+                            "DECIMAL({},{})",
+                            obj.get("precision").and_then(|v| v.as_u64()).unwrap_or(38),
+                            obj.get("scale").and_then(|v| v.as_u64()).unwrap_or(0)
+                        ),
+                        _ => "STRING".to_string(),
+                    });
+                }
+                match obj.get("type").and_then(|v| v.as_str()) {
+                    Some("array") | Some("map") => {
+                        let inner_type = obj.get("items").or_else(|| obj.get("values"));
+                        let inner = inner_type
+                            .map(|t| Self::resolve_type(t, namespace, by_fullname, short_names))
+                            .unwrap_or_else(|| scalar("STRING"));
+                        let wrapper = if obj.get("items").is_some() {
+                            "ARRAY"
+                        } else {
+                            "MAP"
+                        };
+                        ResolvedType {
+                            data_type: if wrapper == "ARRAY" {
+                                format!("ARRAY<{}>", inner.data_type)
+                            } else {
+                                format!("MAP<STRING,{}>", inner.data_type)
+                            },
+                            nullable: false,
+                            enum_values: Vec::new(),
+                            reference: inner.reference.map(|(target, _)| (target, true)),
+                        }
+                    }
+                    Some("record") | Some("error") | Some("enum") | Some("fixed") => {
+                        let name = obj.get("name").and_then(|v| v.as_str()).unwrap_or_default();
+                        let own_namespace =
+                            obj.get("namespace").and_then(|v| v.as_str()).or(namespace);
+                        let reference = match own_namespace {
+                            Some(ns) if !name.contains('.') && !ns.is_empty() => {
+                                Value::String(format!("{}.{}", ns, name))
+                            }
+                            _ => Value::String(name.to_string()),
+                        };
+                        Self::resolve_type(&reference, namespace, by_fullname, short_names)
+                    }
+                    Some(primitive) => scalar(&AvroParser::new().map_avro_type_to_sql(primitive)),
+                    None => scalar("STRING"),
+                }
+            }
+            Value::String(name) => {
+                if matches!(
+                    name.as_str(),
+                    "int" | "long" | "float" | "double" | "boolean" | "bytes" | "string" | "null"
+                ) {
+                    return scalar(&AvroParser::new().map_avro_type_to_sql(name));
+                }
+
+                // Named type reference: fullname, namespace-relative, then unique short name
+                let candidates = [
+                    Some(name.clone()),
+                    namespace.map(|ns| format!("{}.{}", ns, name)),
+                    short_names
+                        .get(name.as_str())
+                        .filter(|names| names.len() == 1)
+                        .map(|names| names[0].clone()),
+                ];
+                let Some((fullname, schema)) = candidates
+                    .into_iter()
+                    .flatten()
+                    .find_map(|n| by_fullname.get(&n).map(|s| (n, *s)))
+                else {
+                    return scalar("STRING");
+                };
+
+                match schema.get("type").and_then(|v| v.as_str()) {
+                    Some("enum") => ResolvedType {
+                        data_type: "STRING".to_string(),
+                        nullable: false,
+                        enum_values: schema
+                            .get("symbols")
+                            .and_then(|v| v.as_array())
+                            .map(|symbols| {
+                                symbols
+                                    .iter()
+                                    .filter_map(|s| s.as_str().map(|s| s.to_string()))
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                        reference: None,
+                    },
+                    Some("fixed") => scalar("BYTES"),
+                    _ => ResolvedType {
+                        data_type: "STRUCT".to_string(),
+                        nullable: false,
+                        enum_values: Vec::new(),
+                        reference: Some((fullname, false)),
+                    },
+                }
+            }
+            _ => scalar("STRING"),
+        }
+    }
+}
+
 /// Parser error structure (matches ODCL parser format).
 #[derive(Debug, Clone)]
 pub struct ParserError {
@@ -462,6 +462,7 @@ impl CacheService {
                 }),
             modeling_level: None,
             tags: Vec::new(),
+            aliases: Vec::new(),
             odcl_metadata,
             position,
             yaml_file_path: row.get("yaml_file_path")?,
@@ -553,6 +554,8 @@ impl CacheService {
             cardinality,
             source_optional: None, // Not stored in cache yet
             target_optional: None, // Not stored in cache yet
+            source_role: None,     // Roles not stored in cache, loaded from YAML
+            target_role: None,
             foreign_key_details,
             etl_job_metadata,
             relationship_type,
@@ -0,0 +1,92 @@
+//! Cooperative cancellation of request work.
+//!
+//! Dropping a handler future stops its async work at the next await point, but work handed to
+//! the blocking thread pool keeps running. Such work checks a [`Cancellation`] between steps
+//! instead, which the request timeout middleware cancels when the request runs out of time or
+//! the client disconnects.
+
+use std::io::{self, Seek, SeekFrom, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Shared flag telling work started for a request to stop.
+#[derive(Debug, Clone, Default)]
+pub struct Cancellation(Arc<AtomicBool>);
+
+impl Cancellation {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Fail once cancelled, for use between steps of blocking work.
+    pub fn check(&self) -> io::Result<()> {
+        if self.is_cancelled() {
+            // Not `Interrupted`, which `write_all` retries
+            Err(io::Error::other("request cancelled"))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Writer failing once cancelled, so writing a large file stops part way.
+    pub fn writer<W>(&self, inner: W) -> CancellableWriter<W> {
+        CancellableWriter {
+            inner,
+            cancellation: self.clone(),
+        }
+    }
+
+    /// Guard that cancels when dropped, unless disarmed first.
+    pub fn cancel_on_drop(&self) -> CancelOnDrop {
+        CancelOnDrop(Some(self.clone()))
+    }
+}
+
+/// Cancels its [`Cancellation`] when dropped, e.g. with the future of an abandoned request.
+pub struct CancelOnDrop(Option<Cancellation>);
+
+impl CancelOnDrop {
+    /// The work completed: dropping the guard no longer cancels.
+    pub fn disarm(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if let Some(cancellation) = self.0.take() {
+            cancellation.cancel();
+        }
+    }
+}
+
+/// Writer that fails once its [`Cancellation`] is cancelled.
+pub struct CancellableWriter<W> {
+    inner: W,
+    cancellation: Cancellation,
+}
+
+impl<W: Write> Write for CancellableWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.cancellation.check()?;
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Seek> Seek for CancellableWriter<W> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
@@ -1,6 +1,7 @@
 //! Canvas Layout Service for managing canvas positions and routing in YAML format.
 
 use crate::models::{DataModel, Position, VisualMetadata};
+use crate::services::save_coordinator_service::save_coordinator;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -59,6 +60,7 @@ impl CanvasLayoutService {
     ///
     /// Saves all table positions and relationship visual metadata.
     pub fn save_canvas_layout(&self, model: &DataModel) -> Result<()> {
+        let _save = save_coordinator().begin();
         info!("Saving canvas layout to YAML: {:?}", self.layout_file_path);
 
         // Build layout structure
@@ -263,6 +265,7 @@ impl CanvasLayoutService {
 
     /// Helper method to save layout to file
     fn save_layout_to_file(&self, layout: &CanvasLayout) -> Result<()> {
+        let _save = save_coordinator().begin();
         // Ensure parent directory exists
         if let Some(parent) = self.layout_file_path.parent() {
             fs::create_dir_all(parent)
@@ -0,0 +1,208 @@
+//! Column-level lineage edges for the canvas.
+//!
+//! When a lineage relationship (data flow, ETL transformation or one with ETL job metadata)
+//! is expanded on the canvas, the frontend draws one line per mapped column pair. Each edge
+//! carries the anchors it needs to place those lines: the table, its canvas position, the
+//! column's row within the table and the side of the table the line leaves or enters on.
+//! Pixel geometry is left to the frontend, which knows its table sizes.
+
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::relationship::{RelationshipAnchor, TableSide};
+use crate::models::{Relationship, Table};
+use crate::services::column_reference_service::is_lineage;
+
+/// Side of a table a column line attaches to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AnchorSide {
+    Left,
+    Right,
+}
+
+/// Where a column line starts or ends
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ColumnAnchor {
+    pub table_id: Uuid,
+    pub table_name: String,
+    pub column: String,
+    /// Row of the column within the table (0-based)
+    pub column_index: usize,
+    /// Number of columns of the table, for scaling the row
+    pub column_count: usize,
+    /// Canvas position of the table, if it has been placed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub x: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub y: Option<f64>,
+    pub side: AnchorSide,
+}
+
+/// One column-to-column line of a lineage relationship
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ColumnEdge {
+    pub relationship_id: Uuid,
+    /// ETL job that maps the columns, if recorded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_name: Option<String>,
+    pub source: ColumnAnchor,
+    pub target: ColumnAnchor,
+}
+
+fn anchor(table: &Table, column: &str, side: AnchorSide) -> Option<ColumnAnchor> {
+    let (column_index, column) = table
+        .columns
+        .iter()
+        .enumerate()
+        .find(|(_, c)| c.name.eq_ignore_ascii_case(column))?;
+    Some(ColumnAnchor {
+        table_id: table.id,
+        table_name: table.name.clone(),
+        column: column.name.clone(),
+        column_index,
+        column_count: table.columns.len(),
+        x: table.position.as_ref().map(|p| p.x),
+        y: table.position.as_ref().map(|p| p.y),
+        side,
+    })
+}
+
+/// Side a relationship anchor pins its end to, if it is on the left or right of the table
+fn anchored_side(anchor: Option<&RelationshipAnchor>) -> Option<AnchorSide> {
+    match anchor?.side {
+        TableSide::West => Some(AnchorSide::Left),
+        TableSide::East => Some(AnchorSide::Right),
+        TableSide::North | TableSide::South => None,
+    }
+}
+
+/// Sides the lines of a relationship leave the source and enter the target on: the sides of
+/// its anchors, otherwise facing each other, or both on the right for a table mapping onto
+/// itself.
+fn sides(relationship: &Relationship, source: &Table, target: &Table) -> (AnchorSide, AnchorSide) {
+    let (source_side, target_side) = if source.id == target.id {
+        (AnchorSide::Right, AnchorSide::Right)
+    } else {
+        match (&source.position, &target.position) {
+            (Some(s), Some(t)) if s.x > t.x => (AnchorSide::Left, AnchorSide::Right),
+            _ => (AnchorSide::Right, AnchorSide::Left),
+        }
+    };
+    let visual = relationship.visual_metadata.as_ref();
+    (
+        anchored_side(visual.and_then(|v| v.source_anchor.as_ref())).unwrap_or(source_side),
+        anchored_side(visual.and_then(|v| v.target_anchor.as_ref())).unwrap_or(target_side),
+    )
+}
+
+/// Column edges of the lineage relationships between `tables`, optionally of one
+/// relationship only. Mapped columns that no longer exist are skipped.
+pub fn column_edges(
+    tables: &[Table],
+    relationships: &[Relationship],
+    relationship_id: Option<Uuid>,
+) -> Vec<ColumnEdge> {
+    let table = |id: Uuid| tables.iter().find(|t| t.id == id);
+    let mut edges = Vec::new();
+
+    for relationship in relationships
+        .iter()
+        .filter(|r| relationship_id.is_none_or(|id| r.id == id))
+        .filter(|r| is_lineage(r))
+    {
+        let (Some(details), Some(source), Some(target)) = (
+            relationship.foreign_key_details.as_ref(),
+            table(relationship.source_table_id),
+            table(relationship.target_table_id),
+        ) else {
+            continue;
+        };
+        let (source_side, target_side) = sides(relationship, source, target);
+        for (source_column, target_column) in details.pairs() {
+            let (Some(from), Some(to)) = (
+                anchor(source, source_column, source_side),
+                anchor(target, target_column, target_side),
+            ) else {
+                continue;
+            };
+            edges.push(ColumnEdge {
+                relationship_id: relationship.id,
+                job_name: relationship
+                    .etl_job_metadata
+                    .as_ref()
+                    .map(|job| job.job_name.clone()),
+                source: from,
+                target: to,
+            });
+        }
+    }
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::enums::RelationshipType;
+    use crate::models::relationship::{ForeignKeyColumnPair, ForeignKeyDetails};
+    use crate::models::{Column, Position};
+
+    fn table(name: &str, columns: &[&str], x: f64) -> Table {
+        let mut table = Table::new(
+            name.to_string(),
+            columns
+                .iter()
+                .map(|c| Column::new(c.to_string(), "TEXT".to_string()))
+                .collect(),
+        );
+        table.position = Some(Position { x, y: 0.0 });
+        table
+    }
+
+    #[test]
+    fn test_column_edges_follow_lineage_column_pairs() {
+        let raw = table("raw_orders", &["id", "cust", "total"], 400.0);
+        let orders = table("orders", &["order_id", "customer_id"], 0.0);
+        let mut lineage = Relationship::new(raw.id, orders.id);
+        lineage.relationship_type = Some(RelationshipType::DataFlow);
+        lineage.foreign_key_details = Some(ForeignKeyDetails {
+            source_column: "id".to_string(),
+            target_column: "order_id".to_string(),
+            column_pairs: vec![
+                ForeignKeyColumnPair {
+                    source_column: "id".to_string(),
+                    target_column: "order_id".to_string(),
+                },
+                ForeignKeyColumnPair {
+                    source_column: "CUST".to_string(),
+                    target_column: "customer_id".to_string(),
+                },
+                ForeignKeyColumnPair {
+                    source_column: "dropped".to_string(),
+                    target_column: "customer_id".to_string(),
+                },
+            ],
+        });
+        let mut foreign_key = Relationship::new(orders.id, raw.id);
+        foreign_key.foreign_key_details = Some(ForeignKeyDetails::new("order_id", "id"));
+        let tables = vec![raw, orders];
+        let relationships = vec![lineage.clone(), foreign_key];
+
+        let edges = column_edges(&tables, &relationships, None);
+        assert_eq!(edges.len(), 2);
+        assert_eq!(edges[1].source.column, "cust");
+        assert_eq!(edges[1].source.column_index, 1);
+        assert_eq!(edges[1].source.column_count, 3);
+        assert_eq!(edges[1].target.column_index, 1);
+        // The source table is right of the target, so the lines run leftwards
+        assert_eq!(edges[0].source.side, AnchorSide::Left);
+        assert_eq!(edges[0].target.side, AnchorSide::Right);
+
+        assert!(column_edges(&tables, &relationships, Some(Uuid::new_v4())).is_empty());
+        assert_eq!(
+            column_edges(&tables, &relationships, Some(lineage.id)).len(),
+            2
+        );
+    }
+}
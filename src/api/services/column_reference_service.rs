@@ -0,0 +1,281 @@
+//! Column reference analysis.
+//!
+//! Finds everything that depends on a column, so modelers can rename or drop it with
+//! confidence:
+//! - relationships whose foreign key details map the column
+//! - column-level foreign keys pointing at the column, or declared on it
+//! - lineage: data-flow and ETL relationships mapping the column
+//! - cross-domain references: other domains that import the table, or a relationship
+//!   mapping the column
+//!
+//! Column names are compared case-insensitively, like SQL identifiers.
+
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::enums::RelationshipType;
+use crate::models::{Relationship, Table};
+
+/// Kind of dependency on a column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnReferenceKind {
+    Relationship,
+    ForeignKey,
+    Lineage,
+    CrossDomain,
+}
+
+/// One dependency on a column
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct ColumnReference {
+    pub kind: ColumnReferenceKind,
+    /// Human-readable summary, e.g. `orders.customer_id -> customers.id`
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relationship_id: Option<Uuid>,
+    /// The table on the other end of the reference
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table_name: Option<String>,
+    /// The column on the other end of the reference
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    /// The referencing domain (cross-domain references)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+}
+
+/// Tables and relationships another domain imports from the analysed domain
+#[derive(Debug, Clone, Default)]
+pub struct DomainImports {
+    pub domain: String,
+    pub table_ids: Vec<Uuid>,
+    pub relationship_ids: Vec<Uuid>,
+}
+
+fn same_column(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b)
+}
+
+pub(crate) fn is_lineage(relationship: &Relationship) -> bool {
+    relationship.etl_job_metadata.is_some()
+        || matches!(
+            relationship.relationship_type,
+            Some(RelationshipType::DataFlow) | Some(RelationshipType::EtlTransformation)
+        )
+}
+
+fn table_name(tables: &[Table], id: Uuid) -> Option<String> {
+    tables.iter().find(|t| t.id == id).map(|t| t.name.clone())
+}
+
+/// References to `column` of `table` within its own domain.
+pub fn find_model_references(
+    table: &Table,
+    column: &str,
+    tables: &[Table],
+    relationships: &[Relationship],
+) -> Vec<ColumnReference> {
+    let mut references = Vec::new();
+
+    for relationship in relationships {
+        let Some(details) = relationship.foreign_key_details.as_ref() else {
+            continue;
+        };
+        for (source, target) in details.pairs() {
+            let (other_table, other_column, outgoing) =
+                if relationship.source_table_id == table.id && same_column(source, column) {
+                    (relationship.target_table_id, target, true)
+                } else if relationship.target_table_id == table.id && same_column(target, column) {
+                    (relationship.source_table_id, source, false)
+                } else {
+                    continue;
+                };
+            let other_name = table_name(tables, other_table);
+            let other = format!(
+                "{}.{}",
+                other_name.as_deref().unwrap_or("<unknown table>"),
+                other_column
+            );
+            let this = format!("{}.{}", table.name, column);
+            let (from, to) = if outgoing {
+                (this, other)
+            } else {
+                (other, this)
+            };
+            references.push(ColumnReference {
+                kind: if is_lineage(relationship) {
+                    ColumnReferenceKind::Lineage
+                } else {
+                    ColumnReferenceKind::Relationship
+                },
+                description: format!("{} -> {}", from, to),
+                relationship_id: Some(relationship.id),
+                table_id: Some(other_table),
+                table_name: other_name,
+                column: Some(other_column.to_string()),
+                domain: None,
+            });
+        }
+    }
+
+    let table_id = table.id.to_string();
+    for other in tables {
+        for other_column in &other.columns {
+            let Some(fk) = other_column.foreign_key.as_ref() else {
+                continue;
+            };
+            if other.id == table.id && same_column(&other_column.name, column) {
+                // Foreign key declared on the column itself
+                let target = Uuid::parse_str(&fk.table_id).ok();
+                let target_name = target.and_then(|id| table_name(tables, id));
+                references.push(ColumnReference {
+                    kind: ColumnReferenceKind::ForeignKey,
+                    description: format!(
+                        "{}.{} references {}.{}",
+                        table.name,
+                        other_column.name,
+                        target_name.as_deref().unwrap_or(&fk.table_id),
+                        fk.column_name
+                    ),
+                    relationship_id: None,
+                    table_id: target,
+                    table_name: target_name,
+                    column: Some(fk.column_name.clone()),
+                    domain: None,
+                });
+            } else if fk.table_id == table_id && same_column(&fk.column_name, column) {
+                references.push(ColumnReference {
+                    kind: ColumnReferenceKind::ForeignKey,
+                    description: format!(
+                        "{}.{} references {}.{}",
+                        other.name, other_column.name, table.name, column
+                    ),
+                    relationship_id: None,
+                    table_id: Some(other.id),
+                    table_name: Some(other.name.clone()),
+                    column: Some(other_column.name.clone()),
+                    domain: None,
+                });
+            }
+        }
+    }
+
+    references
+}
+
+/// References from other domains, given what each of them imports and the model
+/// references found by [`find_model_references`].
+pub fn find_cross_domain_references(
+    table: &Table,
+    model_references: &[ColumnReference],
+    imports: &[DomainImports],
+) -> Vec<ColumnReference> {
+    let mut references = Vec::new();
+    for domain in imports {
+        if domain.table_ids.contains(&table.id) {
+            references.push(ColumnReference {
+                kind: ColumnReferenceKind::CrossDomain,
+                description: format!("Domain '{}' imports table {}", domain.domain, table.name),
+                relationship_id: None,
+                table_id: None,
+                table_name: None,
+                column: None,
+                domain: Some(domain.domain.clone()),
+            });
+        }
+        for reference in model_references {
+            if let Some(relationship_id) = reference.relationship_id
+                && domain.relationship_ids.contains(&relationship_id)
+            {
+                references.push(ColumnReference {
+                    kind: ColumnReferenceKind::CrossDomain,
+                    description: format!(
+                        "Domain '{}' imports relationship {}",
+                        domain.domain, reference.description
+                    ),
+                    domain: Some(domain.domain.clone()),
+                    ..reference.clone()
+                });
+            }
+        }
+    }
+    references
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Column;
+    use crate::models::column::ForeignKey;
+    use crate::models::relationship::{ETLJobMetadata, ForeignKeyDetails};
+
+    #[test]
+    fn test_finds_relationships_foreign_keys_lineage_and_cross_domain() {
+        let customers = Table::new(
+            "customers".to_string(),
+            vec![Column::new("id".to_string(), "INT".to_string())],
+        );
+        let mut customer_id = Column::new("customer_id".to_string(), "INT".to_string());
+        customer_id.foreign_key = Some(ForeignKey {
+            table_id: customers.id.to_string(),
+            column_name: "ID".to_string(),
+            constraint_name: None,
+            key_position: None,
+        });
+        let orders = Table::new("orders".to_string(), vec![customer_id]);
+        let staging = Table::new(
+            "stg_customers".to_string(),
+            vec![Column::new("id".to_string(), "INT".to_string())],
+        );
+
+        let mut fk = Relationship::new(orders.id, customers.id);
+        fk.foreign_key_details = Some(ForeignKeyDetails::new("customer_id", "id"));
+        let mut flow = Relationship::new(staging.id, customers.id);
+        flow.foreign_key_details = Some(ForeignKeyDetails::new("id", "id"));
+        flow.etl_job_metadata = Some(ETLJobMetadata {
+            job_name: "load_customers".to_string(),
+            notes: None,
+            frequency: None,
+        });
+        let unrelated = Relationship::new(orders.id, staging.id);
+
+        let tables = vec![customers.clone(), orders.clone(), staging.clone()];
+        let references =
+            find_model_references(&customers, "id", &tables, &[fk.clone(), flow, unrelated]);
+        let kinds: Vec<_> = references.iter().map(|r| r.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ColumnReferenceKind::Relationship,
+                ColumnReferenceKind::Lineage,
+                ColumnReferenceKind::ForeignKey
+            ]
+        );
+        assert_eq!(
+            references[0].description,
+            "orders.customer_id -> customers.id"
+        );
+        assert_eq!(references[2].table_name.as_deref(), Some("orders"));
+
+        let cross = find_cross_domain_references(
+            &customers,
+            &references,
+            &[DomainImports {
+                domain: "billing".to_string(),
+                table_ids: vec![customers.id],
+                relationship_ids: vec![fk.id],
+            }],
+        );
+        assert_eq!(cross.len(), 2);
+        assert!(
+            cross
+                .iter()
+                .all(|r| r.kind == ColumnReferenceKind::CrossDomain)
+        );
+        assert_eq!(cross[1].relationship_id, Some(fk.id));
+    }
+}
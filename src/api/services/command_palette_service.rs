@@ -0,0 +1,409 @@
+//! Command palette index.
+//!
+//! Lists the objects and operations of a workspace as commands with stable action ids
+//! (`table.open:<domain>:<table_id>`, `export.run:<domain>:<format>`, ...), so a keyboard
+//! driven frontend can search them and execute the chosen one through a single endpoint.
+//! Relationship commands are only offered where a column name suggests a missing foreign
+//! key (`orders.customer_id` with a `customers` table and no relationship between them).
+
+use serde::Serialize;
+use std::fmt;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::DataModel;
+use crate::services::export_scheduler_service::BUNDLE_FORMATS;
+
+/// Kind of command, which decides how it is executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandKind {
+    OpenDomain,
+    OpenTable,
+    RunExport,
+    RunExportSchedule,
+    CreateRelationship,
+    ApplySavedFilter,
+}
+
+/// A parsed action id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandAction {
+    OpenDomain {
+        domain: String,
+    },
+    OpenTable {
+        domain: String,
+        table_id: Uuid,
+    },
+    RunExport {
+        domain: String,
+        format: String,
+    },
+    RunExportSchedule {
+        domain: String,
+        schedule_id: Uuid,
+    },
+    CreateRelationship {
+        domain: String,
+        source_table_id: Uuid,
+        target_table_id: Uuid,
+        source_column: String,
+        target_column: String,
+    },
+    ApplySavedFilter {
+        domain: String,
+        filter_id: Uuid,
+    },
+}
+
+impl CommandAction {
+    /// Parse an action id; returns None for unknown or malformed ids.
+    pub fn parse(id: &str) -> Option<Self> {
+        let (verb, rest) = id.split_once(':')?;
+        let parts: Vec<&str> = rest.split(':').collect();
+        let uuid = |s: &str| Uuid::parse_str(s).ok();
+        let action = match (verb, parts.as_slice()) {
+            ("domain.open", [domain]) => Self::OpenDomain {
+                domain: domain.to_string(),
+            },
+            ("table.open", [domain, table_id]) => Self::OpenTable {
+                domain: domain.to_string(),
+                table_id: uuid(table_id)?,
+            },
+            ("export.run", [domain, format]) if BUNDLE_FORMATS.contains(format) => {
+                Self::RunExport {
+                    domain: domain.to_string(),
+                    format: format.to_string(),
+                }
+            }
+            ("export_schedule.run", [domain, schedule_id]) => Self::RunExportSchedule {
+                domain: domain.to_string(),
+                schedule_id: uuid(schedule_id)?,
+            },
+            ("relationship.create", [domain, source, target, source_column, target_column]) => {
+                Self::CreateRelationship {
+                    domain: domain.to_string(),
+                    source_table_id: uuid(source)?,
+                    target_table_id: uuid(target)?,
+                    source_column: source_column.to_string(),
+                    target_column: target_column.to_string(),
+                }
+            }
+            ("saved_filter.apply", [domain, filter_id]) => Self::ApplySavedFilter {
+                domain: domain.to_string(),
+                filter_id: uuid(filter_id)?,
+            },
+            _ => return None,
+        };
+        (!action.domain().is_empty()).then_some(action)
+    }
+
+    pub fn domain(&self) -> &str {
+        match self {
+            Self::OpenDomain { domain }
+            | Self::OpenTable { domain, .. }
+            | Self::RunExport { domain, .. }
+            | Self::RunExportSchedule { domain, .. }
+            | Self::CreateRelationship { domain, .. }
+            | Self::ApplySavedFilter { domain, .. } => domain,
+        }
+    }
+
+    pub fn kind(&self) -> CommandKind {
+        match self {
+            Self::OpenDomain { .. } => CommandKind::OpenDomain,
+            Self::OpenTable { .. } => CommandKind::OpenTable,
+            Self::RunExport { .. } => CommandKind::RunExport,
+            Self::RunExportSchedule { .. } => CommandKind::RunExportSchedule,
+            Self::CreateRelationship { .. } => CommandKind::CreateRelationship,
+            Self::ApplySavedFilter { .. } => CommandKind::ApplySavedFilter,
+        }
+    }
+}
+
+impl fmt::Display for CommandAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OpenDomain { domain } => write!(f, "domain.open:{}", domain),
+            Self::OpenTable { domain, table_id } => write!(f, "table.open:{}:{}", domain, table_id),
+            Self::RunExport { domain, format } => write!(f, "export.run:{}:{}", domain, format),
+            Self::RunExportSchedule {
+                domain,
+                schedule_id,
+            } => write!(f, "export_schedule.run:{}:{}", domain, schedule_id),
+            Self::CreateRelationship {
+                domain,
+                source_table_id,
+                target_table_id,
+                source_column,
+                target_column,
+            } => write!(
+                f,
+                "relationship.create:{}:{}:{}:{}:{}",
+                domain, source_table_id, target_table_id, source_column, target_column
+            ),
+            Self::ApplySavedFilter { domain, filter_id } => {
+                write!(f, "saved_filter.apply:{}:{}", domain, filter_id)
+            }
+        }
+    }
+}
+
+/// An entry of the command index
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Command {
+    /// Stable action id, passed to the execute endpoint
+    pub id: String,
+    pub kind: CommandKind,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subtitle: Option<String>,
+    pub domain: String,
+    /// Extra search terms (aliases, tags, column names)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub keywords: Vec<String>,
+}
+
+impl Command {
+    fn new(action: CommandAction, title: String, subtitle: Option<String>) -> Self {
+        Self {
+            id: action.to_string(),
+            kind: action.kind(),
+            title,
+            subtitle,
+            domain: action.domain().to_string(),
+            keywords: Vec::new(),
+        }
+    }
+}
+
+/// Named objects of a domain that commands are built for, besides its model
+#[derive(Debug, Default)]
+pub struct DomainObjects {
+    /// Export schedules as (id, name)
+    pub export_schedules: Vec<(Uuid, String)>,
+    /// Saved filters as (id, name)
+    pub saved_filters: Vec<(Uuid, String)>,
+}
+
+/// Column-name foreign keys without a relationship: (source table, column, target table,
+/// target column), where the target column is the target's single primary key or `id`.
+fn suggested_relationships(model: &DataModel) -> Vec<(usize, usize, usize, usize)> {
+    let mut suggestions = Vec::new();
+    for (si, source) in model.tables.iter().enumerate() {
+        for (ci, column) in source.columns.iter().enumerate() {
+            let lower = column.name.to_lowercase();
+            let Some(stem) = lower.strip_suffix("_id").filter(|s| !s.is_empty()) else {
+                continue;
+            };
+            let candidates = [
+                stem.to_string(),
+                format!("{}s", stem),
+                format!("{}es", stem),
+            ];
+            let Some(ti) = model.tables.iter().position(|t| {
+                t.id != source.id && candidates.iter().any(|c| t.name.eq_ignore_ascii_case(c))
+            }) else {
+                continue;
+            };
+            let target = &model.tables[ti];
+            let linked = model.relationships.iter().any(|r| {
+                (r.source_table_id == source.id && r.target_table_id == target.id)
+                    || (r.source_table_id == target.id && r.target_table_id == source.id)
+            });
+            let primary_keys: Vec<usize> = target
+                .columns
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.primary_key)
+                .map(|(i, _)| i)
+                .collect();
+            let key = match primary_keys.as_slice() {
+                [key] => Some(*key),
+                [] => target
+                    .columns
+                    .iter()
+                    .position(|c| c.name.eq_ignore_ascii_case("id")),
+                _ => None,
+            };
+            if let Some(key) = key
+                && !linked
+            {
+                suggestions.push((si, ci, ti, key));
+            }
+        }
+    }
+    suggestions
+}
+
+/// Commands for one domain model.
+pub fn domain_commands(model: &DataModel, objects: &DomainObjects) -> Vec<Command> {
+    let domain = &model.name;
+    let mut commands = vec![Command::new(
+        CommandAction::OpenDomain {
+            domain: domain.clone(),
+        },
+        format!("Open domain {}", domain),
+        Some(format!("{} tables", model.tables.len())),
+    )];
+
+    for table in &model.tables {
+        let mut command = Command::new(
+            CommandAction::OpenTable {
+                domain: domain.clone(),
+                table_id: table.id,
+            },
+            format!("Open table {}", table.name),
+            Some(domain.clone()),
+        );
+        command.keywords = table
+            .aliases
+            .iter()
+            .chain(&table.tags)
+            .cloned()
+            .chain(table.columns.iter().map(|c| c.name.clone()))
+            .collect();
+        commands.push(command);
+    }
+
+    for format in BUNDLE_FORMATS {
+        commands.push(Command::new(
+            CommandAction::RunExport {
+                domain: domain.clone(),
+                format: format.to_string(),
+            },
+            format!("Export {} as {}", domain, format),
+            None,
+        ));
+    }
+
+    for (id, name) in &objects.export_schedules {
+        commands.push(Command::new(
+            CommandAction::RunExportSchedule {
+                domain: domain.clone(),
+                schedule_id: *id,
+            },
+            format!("Run export schedule {}", name),
+            Some(domain.clone()),
+        ));
+    }
+
+    for (id, name) in &objects.saved_filters {
+        commands.push(Command::new(
+            CommandAction::ApplySavedFilter {
+                domain: domain.clone(),
+                filter_id: *id,
+            },
+            format!("Show saved filter {}", name),
+            Some(domain.clone()),
+        ));
+    }
+
+    for (si, ci, ti, tci) in suggested_relationships(model) {
+        let (source, target) = (&model.tables[si], &model.tables[ti]);
+        let (source_column, target_column) = (&source.columns[ci], &target.columns[tci]);
+        commands.push(Command::new(
+            CommandAction::CreateRelationship {
+                domain: domain.clone(),
+                source_table_id: source.id,
+                target_table_id: target.id,
+                source_column: source_column.name.clone(),
+                target_column: target_column.name.clone(),
+            },
+            format!("Create relationship {} -> {}", source.name, target.name),
+            Some(format!(
+                "{}.{} references {}.{}",
+                source.name, source_column.name, target.name, target_column.name
+            )),
+        ));
+    }
+    commands
+}
+
+/// Commands matching every whitespace-separated term of `query` in their title or
+/// keywords, titles starting with the query first; all commands for an empty query.
+pub fn search_commands(commands: Vec<Command>, query: &str) -> Vec<Command> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return commands;
+    }
+    let terms: Vec<&str> = query.split_whitespace().collect();
+    let mut matches: Vec<(bool, Command)> = commands
+        .into_iter()
+        .filter_map(|command| {
+            let title = command.title.to_lowercase();
+            let haystack = std::iter::once(title.clone())
+                .chain(command.keywords.iter().map(|k| k.to_lowercase()))
+                .collect::<Vec<_>>()
+                .join(" ");
+            terms.iter().all(|t| haystack.contains(t)).then(|| {
+                // Titles read "Open table orders", so also rank by the object name
+                let name = title.split_whitespace().last().unwrap_or_default();
+                (
+                    !(title.starts_with(&query) || name.starts_with(&query)),
+                    command,
+                )
+            })
+        })
+        .collect();
+    matches.sort_by_key(|(rank, _)| *rank);
+    matches.into_iter().map(|(_, command)| command).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Column, Table};
+
+    #[test]
+    fn test_index_suggests_relationships_and_round_trips_ids() {
+        let mut model = DataModel::new("sales".to_string(), String::new(), String::new());
+        let mut id = Column::new("id".to_string(), "BIGINT".to_string());
+        id.primary_key = true;
+        let customers = Table::new("customers".to_string(), vec![id.clone()]);
+        let orders = Table::new(
+            "orders".to_string(),
+            vec![
+                id,
+                Column::new("customer_id".to_string(), "BIGINT".to_string()),
+            ],
+        );
+        model.tables = vec![customers, orders];
+
+        let commands = domain_commands(&model, &DomainObjects::default());
+        let relationship = commands
+            .iter()
+            .find(|c| c.kind == CommandKind::CreateRelationship)
+            .unwrap();
+        assert_eq!(
+            relationship.title,
+            "Create relationship orders -> customers"
+        );
+        match CommandAction::parse(&relationship.id).unwrap() {
+            CommandAction::CreateRelationship {
+                source_table_id,
+                source_column,
+                target_column,
+                ..
+            } => {
+                assert_eq!(source_table_id, model.tables[1].id);
+                assert_eq!(
+                    (source_column.as_str(), target_column.as_str()),
+                    ("customer_id", "id")
+                );
+            }
+            other => panic!("unexpected action {:?}", other),
+        }
+        for command in &commands {
+            assert_eq!(
+                CommandAction::parse(&command.id).map(|a| a.to_string()),
+                Some(command.id.clone())
+            );
+        }
+        assert!(CommandAction::parse("export.run:sales:xlsx").is_none());
+
+        let found = search_commands(commands, "orders");
+        assert_eq!(found[0].title, "Open table orders");
+    }
+}
@@ -0,0 +1,325 @@
+//! Data product metadata (data mesh support).
+//!
+//! A domain can describe itself as a data product: its owner and SLA, the output ports
+//! through which it serves its own tables, and the input ports through which it consumes
+//! other domains' tables. The definition is stored as `data-product.yaml` in the domain
+//! directory and can be exported as a Data Product Specification descriptor.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::Table;
+
+/// File holding a domain's data product definition
+pub const DATA_PRODUCT_FILE: &str = "data-product.yaml";
+
+/// Version of the Data Product Specification written by [`DataProduct::descriptor`]
+pub const DATA_PRODUCT_SPECIFICATION_VERSION: &str = "0.0.1";
+
+/// Service level objectives of a data product
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DataProductSla {
+    /// Availability target, e.g. "99.9%"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub availability: Option<String>,
+    /// Maximum data age, e.g. "24h"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub freshness: Option<String>,
+    /// Support hours or channel, e.g. "business hours, #sales-data"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub support: Option<String>,
+}
+
+/// Tables of the domain served to consumers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct OutputPort {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Ids of the domain's tables served through this port
+    pub table_ids: Vec<Uuid>,
+}
+
+/// Tables of another domain consumed by this one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct InputPort {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Domain providing the tables
+    pub source_domain: String,
+    /// Ids of the source domain's tables consumed through this port
+    pub table_ids: Vec<Uuid>,
+}
+
+/// Data product definition of a domain
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DataProduct {
+    /// Product name (defaults to the domain name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Product owner email
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sla: Option<DataProductSla>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub output_ports: Vec<OutputPort>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub input_ports: Vec<InputPort>,
+}
+
+/// Data Product Specification descriptor
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Descriptor {
+    data_product_specification: &'static str,
+    id: String,
+    info: DescriptorInfo,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    input_ports: Vec<DescriptorPort>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    output_ports: Vec<DescriptorPort>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sla: Option<DataProductSla>,
+}
+
+#[derive(Debug, Serialize)]
+struct DescriptorInfo {
+    title: String,
+    domain: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DescriptorPort {
+    id: String,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(rename = "type")]
+    port_type: &'static str,
+    /// Domain providing the tables (input ports)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source_domain: Option<String>,
+    /// Qualified table names (`domain.table`)
+    tables: Vec<String>,
+}
+
+/// Identifier derived from a name: lowercase, with runs of other characters replaced by `-`
+pub(crate) fn slug(name: &str) -> String {
+    let mut id = String::new();
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() {
+            id.push(c.to_ascii_lowercase());
+        } else if !id.ends_with('-') {
+            id.push('-');
+        }
+    }
+    id.trim_matches('-').to_string()
+}
+
+impl DataProduct {
+    /// Check the owner and port names, and that every port references known tables.
+    ///
+    /// `tables` are the domain's own tables; `source_tables` returns the tables of another
+    /// domain, or None if there is no such domain.
+    pub fn validate(
+        &self,
+        domain: &str,
+        tables: &[Table],
+        source_tables: impl Fn(&str) -> Option<Vec<Uuid>>,
+    ) -> Result<(), String> {
+        if let Some(owner) = &self.owner
+            && !owner.contains('@')
+        {
+            return Err(format!("Invalid owner email '{}'", owner));
+        }
+        let mut names = HashSet::new();
+        let port_names = (self.output_ports.iter().map(|p| &p.name))
+            .chain(self.input_ports.iter().map(|p| &p.name));
+        for name in port_names {
+            if slug(name).is_empty() {
+                return Err(format!("Invalid port name '{}'", name));
+            }
+            if !names.insert(slug(name)) {
+                return Err(format!("Duplicate port name '{}'", name));
+            }
+        }
+
+        let own: HashSet<Uuid> = tables.iter().map(|t| t.id).collect();
+        for port in &self.output_ports {
+            if let Some(id) = port.table_ids.iter().find(|id| !own.contains(id)) {
+                return Err(format!(
+                    "Output port '{}' references table {} which is not in this domain",
+                    port.name, id
+                ));
+            }
+        }
+        for port in &self.input_ports {
+            if port.source_domain == domain {
+                return Err(format!(
+                    "Input port '{}' must consume another domain",
+                    port.name
+                ));
+            }
+            let Some(provided) = source_tables(&port.source_domain) else {
+                return Err(format!(
+                    "Input port '{}' references unknown domain '{}'",
+                    port.name, port.source_domain
+                ));
+            };
+            if let Some(id) = port.table_ids.iter().find(|id| !provided.contains(id)) {
+                return Err(format!(
+                    "Input port '{}' references table {} which is not in domain '{}'",
+                    port.name, id, port.source_domain
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Load the definition from a domain directory, returning an empty one if none is stored.
+    pub fn load(domain_dir: &Path) -> Self {
+        std::fs::read_to_string(domain_dir.join(DATA_PRODUCT_FILE))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the definition to a domain directory.
+    pub fn save(&self, domain_dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(domain_dir)
+            .with_context(|| format!("Failed to create {}", domain_dir.display()))?;
+        let path = domain_dir.join(DATA_PRODUCT_FILE);
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Render the Data Product Specification descriptor YAML.
+    ///
+    /// `table_names` maps table ids of this and other domains to their names; tables that
+    /// no longer exist are listed by id.
+    pub fn descriptor(
+        &self,
+        domain: &str,
+        table_names: &BTreeMap<Uuid, String>,
+    ) -> Result<String, serde_yaml::Error> {
+        let qualified = |domain: &str, ids: &[Uuid]| -> Vec<String> {
+            ids.iter()
+                .map(|id| match table_names.get(id) {
+                    Some(name) => format!("{}.{}", domain, name),
+                    None => id.to_string(),
+                })
+                .collect()
+        };
+        let descriptor = Descriptor {
+            data_product_specification: DATA_PRODUCT_SPECIFICATION_VERSION,
+            id: slug(self.name.as_deref().unwrap_or(domain)),
+            info: DescriptorInfo {
+                title: self.name.clone().unwrap_or_else(|| domain.to_string()),
+                domain: domain.to_string(),
+                owner: self.owner.clone(),
+                description: self.description.clone(),
+            },
+            input_ports: self
+                .input_ports
+                .iter()
+                .map(|port| DescriptorPort {
+                    id: slug(&port.name),
+                    name: port.name.clone(),
+                    description: port.description.clone(),
+                    port_type: "table",
+                    source_domain: Some(port.source_domain.clone()),
+                    tables: qualified(&port.source_domain, &port.table_ids),
+                })
+                .collect(),
+            output_ports: self
+                .output_ports
+                .iter()
+                .map(|port| DescriptorPort {
+                    id: slug(&port.name),
+                    name: port.name.clone(),
+                    description: port.description.clone(),
+                    port_type: "table",
+                    source_domain: None,
+                    tables: qualified(domain, &port.table_ids),
+                })
+                .collect(),
+            sla: self.sla.clone(),
+        };
+        serde_yaml::to_string(&descriptor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Column;
+
+    #[test]
+    fn test_validate_and_render_descriptor() {
+        let table = |name: &str| {
+            Table::new(
+                name.to_string(),
+                vec![Column::new("id".to_string(), "bigint".to_string())],
+            )
+        };
+        let (orders, customers) = (table("orders"), table("customers"));
+        let product = DataProduct {
+            name: Some("Sales Orders".to_string()),
+            owner: Some("owner@example.com".to_string()),
+            sla: Some(DataProductSla {
+                freshness: Some("24h".to_string()),
+                ..Default::default()
+            }),
+            output_ports: vec![OutputPort {
+                name: "Orders".to_string(),
+                description: None,
+                table_ids: vec![orders.id],
+            }],
+            input_ports: vec![InputPort {
+                name: "CRM customers".to_string(),
+                description: None,
+                source_domain: "crm".to_string(),
+                table_ids: vec![customers.id],
+            }],
+            ..Default::default()
+        };
+        let crm = |domain: &str| (domain == "crm").then(|| vec![customers.id]);
+        assert!(
+            product
+                .validate("sales", std::slice::from_ref(&orders), crm)
+                .is_ok()
+        );
+        // Output ports serve the domain's own tables only
+        assert!(product.validate("sales", &[], crm).is_err());
+        assert!(
+            product
+                .validate("sales", std::slice::from_ref(&orders), |_| None)
+                .is_err()
+        );
+
+        let names = BTreeMap::from([
+            (orders.id, "orders".to_string()),
+            (customers.id, "customers".to_string()),
+        ]);
+        let yaml = product.descriptor("sales", &names).unwrap();
+        assert!(yaml.starts_with("dataProductSpecification: 0.0.1\nid: sales-orders\n"));
+        assert!(yaml.contains("- id: crm-customers"));
+        assert!(yaml.contains("sourceDomain: crm"));
+        assert!(yaml.contains("  - crm.customers"));
+        assert!(yaml.contains("  - sales.orders"));
+        assert!(yaml.contains("freshness: 24h"));
+    }
+}
@@ -0,0 +1,1042 @@
+//! DBML parser for dbdiagram.io imports.
+//!
+//! Supports:
+//! - `Table` definitions with column settings (`pk`, `not null`, `unique`, `default`, `note`
+//!   and inline `ref`), table notes and `indexes` (primary keys and unique columns)
+//! - `Ref` definitions in short (`Ref: a.x > b.y`) and long (`Ref { ... }`) form, including
+//!   composite keys (`a.(x, y) > b.(x, y)`)
+//! - `Enum` definitions: enum-typed columns become `STRING` columns with the enum values
+//! - `TableGroup` definitions, applied as `table_group:<name>` table tags
+//!
+//! `Project`, sticky note, `TablePartial` and `Records` blocks are skipped. Unsupported
+//! constructs are reported as parser errors rather than failing the whole import.
+
+use crate::models::enums::Cardinality;
+use crate::models::relationship::ForeignKeyDetails;
+use crate::models::{Column, Table};
+use crate::services::avro_parser::ParserError;
+use crate::services::relationship_service::ImportedRelationship;
+use anyhow::Result;
+use serde_json::json;
+use std::collections::HashMap;
+use tracing::info;
+
+/// Tag prefix marking the DBML table group a table belongs to
+pub const TABLE_GROUP_TAG_PREFIX: &str = "table_group:";
+
+/// Result of parsing a DBML document.
+#[derive(Debug, Default)]
+pub struct DbmlImportResult {
+    pub tables: Vec<Table>,
+    pub relationships: Vec<ImportedRelationship>,
+    /// Table group name -> names of its tables
+    pub table_groups: HashMap<String, Vec<String>>,
+    pub errors: Vec<ParserError>,
+}
+
+/// Parser for DBML documents.
+#[derive(Default)]
+pub struct DbmlParser;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    /// Identifier, keyword or number (double-quoted identifiers are unquoted)
+    Word,
+    /// Single- or triple-quoted string
+    Str,
+    /// Backtick expression
+    Expr,
+    Sym,
+    Newline,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    text: String,
+    /// Byte range in the source
+    start: usize,
+    end: usize,
+}
+
+/// `table.column` or `table.(column, ...)` as written in a reference
+struct Endpoint {
+    table: String,
+    columns: Vec<String>,
+}
+
+/// A reference found while parsing, before table names and aliases are resolved.
+struct PendingRef {
+    left: Endpoint,
+    /// `>` many-to-one, `<` one-to-many, `-` one-to-one or `<>` many-to-many
+    op: &'static str,
+    right: Endpoint,
+}
+
+/// `key: value` or flag inside a `[...]` settings list
+struct Setting {
+    /// Lowercased key, words joined by a space (`not null`, `default`)
+    key: String,
+    /// Token range of the value
+    value: Option<(usize, usize)>,
+}
+
+/// A table being parsed, with what is resolved once the whole document is read.
+struct ParsedTable {
+    /// Name as written, including the schema
+    qualified_name: String,
+    alias: Option<String>,
+    table: Table,
+    /// (column index, type name) of columns whose type may name an enum
+    typed_columns: Vec<(usize, String)>,
+}
+
+#[derive(Default)]
+struct Document {
+    tables: Vec<ParsedTable>,
+    refs: Vec<PendingRef>,
+    enums: HashMap<String, Vec<String>>,
+    groups: Vec<(String, Vec<String>)>,
+    errors: Vec<ParserError>,
+}
+
+fn parse_error(field: Option<String>, message: impl Into<String>) -> ParserError {
+    ParserError {
+        error_type: "parse_error".to_string(),
+        field,
+        message: message.into(),
+    }
+}
+
+impl DbmlParser {
+    /// Create a new DBML parser instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse a DBML document.
+    pub fn parse(&self, content: &str) -> Result<DbmlImportResult> {
+        let mut cursor = Cursor {
+            source: content,
+            tokens: tokenize(content),
+            pos: 0,
+        };
+        let mut doc = Document::default();
+        cursor.parse_document(&mut doc);
+        if doc.tables.is_empty() {
+            return Err(anyhow::anyhow!("No DBML tables found"));
+        }
+        let result = resolve(doc);
+        info!(
+            "Parsed DBML: {} tables, {} relationships, {} table groups",
+            result.tables.len(),
+            result.relationships.len(),
+            result.table_groups.len()
+        );
+        Ok(result)
+    }
+}
+
+/// Split DBML source into tokens, dropping comments and whitespace other than newlines.
+fn tokenize(content: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while let Some(c) = content[pos..].chars().next() {
+        let start = pos;
+        let rest = &content[pos..];
+        pos += c.len_utf8();
+        let (kind, text) = match c {
+            '\n' => (TokenKind::Newline, String::new()),
+            _ if c.is_whitespace() => continue,
+            '/' if rest.starts_with("//") => {
+                pos = start + rest.find('\n').unwrap_or(rest.len());
+                continue;
+            }
+            '/' if rest.starts_with("/*") => {
+                pos = start + rest[2..].find("*/").map_or(rest.len(), |i| i + 4);
+                continue;
+            }
+            '\'' if rest.starts_with("'''") => {
+                let end = rest[3..].find("'''").map_or(rest.len(), |i| i + 3);
+                pos = start + (end + 3).min(rest.len());
+                let text = rest[3..end]
+                    .lines()
+                    .map(str::trim)
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                (TokenKind::Str, text.trim().to_string())
+            }
+            '\'' | '"' | '`' => {
+                let (text, len) = delimited(&rest[1..], c);
+                pos = start + 1 + len;
+                let kind = match c {
+                    '"' => TokenKind::Word,
+                    '`' => TokenKind::Expr,
+                    _ => TokenKind::Str,
+                };
+                (kind, text)
+            }
+            _ if c.is_alphanumeric() || c == '_' => {
+                let len = rest
+                    .find(|ch: char| !(ch.is_alphanumeric() || ch == '_'))
+                    .unwrap_or(rest.len());
+                pos = start + len;
+                (TokenKind::Word, rest[..len].to_string())
+            }
+            _ => (TokenKind::Sym, c.to_string()),
+        };
+        tokens.push(Token {
+            kind,
+            text,
+            start,
+            end: pos,
+        });
+    }
+    tokens
+}
+
+/// Text up to the closing `quote` (backslash escapes resolved) and the bytes consumed.
+fn delimited(rest: &str, quote: char) -> (String, usize) {
+    let mut text = String::new();
+    let mut chars = rest.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, 'n')) => text.push('\n'),
+                Some((_, 't')) => text.push('\t'),
+                Some((_, escaped)) => text.push(escaped),
+                None => {}
+            },
+            _ if c == quote => return (text, i + 1),
+            _ => text.push(c),
+        }
+    }
+    (text, rest.len())
+}
+
+struct Cursor<'a> {
+    source: &'a str,
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Cursor<'_> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&Token> {
+        self.tokens.get(self.pos + offset)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        if token.is_some() {
+            self.pos += 1;
+        }
+        token
+    }
+
+    fn is_sym(&self, sym: char) -> bool {
+        is_sym(self.peek(), sym)
+    }
+
+    fn eat_sym(&mut self, sym: char) -> bool {
+        let found = self.is_sym(sym);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn is_keyword(&self, offset: usize, keyword: &str) -> bool {
+        self.peek_at(offset)
+            .is_some_and(|t| t.kind == TokenKind::Word && t.text.eq_ignore_ascii_case(keyword))
+    }
+
+    fn skip_newlines(&mut self) {
+        while self.peek().is_some_and(|t| t.kind == TokenKind::Newline) {
+            self.pos += 1;
+        }
+    }
+
+    /// Skip to the start of the next line.
+    fn skip_line(&mut self) {
+        while let Some(token) = self.next() {
+            if token.kind == TokenKind::Newline {
+                break;
+            }
+        }
+    }
+
+    /// Skip a statement: the rest of its line, or its `{ ... }` block if one opens first.
+    /// Stops before a `}` closing the enclosing block.
+    fn skip_statement(&mut self) {
+        while let Some(token) = self.peek() {
+            if token.kind == TokenKind::Newline || is_sym(Some(token), '}') {
+                return;
+            }
+            if is_sym(Some(token), '{') {
+                self.skip_block();
+                return;
+            }
+            self.pos += 1;
+        }
+    }
+
+    /// Skip a `{ ... }` block starting at the cursor, including nested blocks.
+    fn skip_block(&mut self) {
+        let mut depth = 0;
+        while let Some(token) = self.next() {
+            if is_sym(Some(&token), '{') {
+                depth += 1;
+            } else if is_sym(Some(&token), '}') {
+                depth -= 1;
+                if depth <= 0 {
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Source text of the token range `[from, to)`, or the token's own text for one token.
+    fn text(&self, (from, to): (usize, usize)) -> String {
+        if to == from + 1 {
+            return self.tokens[from].text.clone();
+        }
+        self.raw((from, to)).to_string()
+    }
+
+    /// Source text of the token range `[from, to)` as written.
+    fn raw(&self, (from, to): (usize, usize)) -> &str {
+        if to <= from {
+            return "";
+        }
+        &self.source[self.tokens[from].start..self.tokens[to - 1].end]
+    }
+
+    /// `name` or `schema.name`; empty if no name follows.
+    fn qualified_name(&mut self) -> Vec<String> {
+        let mut parts = Vec::new();
+        if self.peek().is_some_and(|t| t.kind == TokenKind::Word) {
+            parts.extend(self.next().map(|t| t.text));
+            while self.is_sym('.') && self.peek_at(1).is_some_and(|t| t.kind == TokenKind::Word) {
+                self.pos += 1;
+                parts.extend(self.next().map(|t| t.text));
+            }
+        }
+        parts
+    }
+
+    /// `table.column`, `schema.table.column` or `table.(column, ...)`.
+    fn endpoint(&mut self) -> Option<Endpoint> {
+        let mut parts = vec![self.word()?];
+        let mut columns = Vec::new();
+        while self.eat_sym('.') {
+            if self.is_sym('(') {
+                columns = self.column_list();
+                break;
+            }
+            parts.push(self.word()?);
+        }
+        if columns.is_empty() {
+            columns.push(parts.pop()?);
+        }
+        (!parts.is_empty()).then(|| Endpoint {
+            table: parts.join("."),
+            columns,
+        })
+    }
+
+    /// The next identifier or backtick expression.
+    fn word(&mut self) -> Option<String> {
+        match self.peek() {
+            Some(t) if matches!(t.kind, TokenKind::Word | TokenKind::Expr) => {
+                self.next().map(|t| t.text)
+            }
+            _ => None,
+        }
+    }
+
+    /// `(a, b, ...)` starting at the cursor.
+    fn column_list(&mut self) -> Vec<String> {
+        let mut columns = Vec::new();
+        if !self.eat_sym('(') {
+            return columns;
+        }
+        while let Some(token) = self.next() {
+            match token.kind {
+                TokenKind::Word | TokenKind::Expr => columns.push(token.text),
+                TokenKind::Sym if token.text == ")" => break,
+                _ => {}
+            }
+        }
+        columns
+    }
+
+    /// `>`, `<`, `-` or `<>`.
+    fn relation_op(&mut self) -> Option<&'static str> {
+        if self.eat_sym('<') {
+            return Some(if self.eat_sym('>') { "<>" } else { "<" });
+        }
+        if self.eat_sym('>') {
+            return Some(">");
+        }
+        self.eat_sym('-').then_some("-")
+    }
+
+    /// `[key: value, flag, ...]` starting at the cursor; empty if no list opens here.
+    fn settings(&mut self) -> Vec<Setting> {
+        let mut settings = Vec::new();
+        if !self.eat_sym('[') {
+            return settings;
+        }
+        let mut depth = 0;
+        let mut segment_start = self.pos;
+        while let Some(token) = self.peek().cloned() {
+            let end = match token.text.as_str() {
+                "[" | "(" if token.kind == TokenKind::Sym => {
+                    depth += 1;
+                    false
+                }
+                ")" if token.kind == TokenKind::Sym => {
+                    depth -= 1;
+                    false
+                }
+                "]" if token.kind == TokenKind::Sym && depth > 0 => {
+                    depth -= 1;
+                    false
+                }
+                "]" | "," if token.kind == TokenKind::Sym && depth == 0 => true,
+                _ => false,
+            };
+            if end {
+                settings.extend(self.setting(segment_start, self.pos));
+                let closed = token.text == "]";
+                self.pos += 1;
+                if closed {
+                    break;
+                }
+                segment_start = self.pos;
+            } else {
+                self.pos += 1;
+            }
+        }
+        settings
+    }
+
+    fn setting(&self, from: usize, to: usize) -> Option<Setting> {
+        let tokens: Vec<usize> = (from..to)
+            .filter(|&i| self.tokens[i].kind != TokenKind::Newline)
+            .collect();
+        let colon = tokens
+            .iter()
+            .position(|&i| is_sym(Some(&self.tokens[i]), ':'));
+        let key_tokens = &tokens[..colon.unwrap_or(tokens.len())];
+        let key = key_tokens
+            .iter()
+            .map(|&i| self.tokens[i].text.to_lowercase())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if key.is_empty() {
+            return None;
+        }
+        let value = colon
+            .and_then(|c| tokens.get(c + 1..))
+            .filter(|rest| !rest.is_empty())
+            .map(|rest| (rest[0], rest[rest.len() - 1] + 1));
+        Some(Setting { key, value })
+    }
+
+    fn parse_document(&mut self, doc: &mut Document) {
+        loop {
+            self.skip_newlines();
+            let Some(token) = self.peek() else {
+                return;
+            };
+            let keyword = token.text.to_lowercase();
+            match (token.kind, keyword.as_str()) {
+                (TokenKind::Word, "table") => self.parse_table(doc),
+                (TokenKind::Word, "ref") => self.parse_ref(doc),
+                (TokenKind::Word, "enum") => self.parse_enum(doc),
+                (TokenKind::Word, "tablegroup") => self.parse_table_group(doc),
+                (TokenKind::Word, "project" | "note" | "tablepartial" | "records") => {
+                    self.skip_statement()
+                }
+                _ => {
+                    doc.errors.push(ParserError {
+                        error_type: "unsupported".to_string(),
+                        field: Some(token.text.clone()),
+                        message: format!("Unsupported DBML element '{}' was skipped", token.text),
+                    });
+                    self.pos += 1;
+                    self.skip_statement();
+                }
+            }
+        }
+    }
+
+    fn parse_table(&mut self, doc: &mut Document) {
+        self.pos += 1;
+        let parts = self.qualified_name();
+        let Some(name) = parts.last().cloned() else {
+            doc.errors
+                .push(parse_error(None, "Table without a name was skipped"));
+            self.skip_statement();
+            return;
+        };
+        let alias = if self.is_keyword(0, "as") {
+            self.pos += 1;
+            self.word()
+        } else {
+            None
+        };
+        let mut table = Table::new(name.clone(), Vec::new());
+        if parts.len() > 1 {
+            table.schema_name = Some(parts[parts.len() - 2].clone());
+        }
+        for setting in self.settings() {
+            if setting.key == "note"
+                && let Some(value) = setting.value
+            {
+                let note = self.text(value);
+                table
+                    .odcl_metadata
+                    .insert("description".to_string(), json!(note));
+            }
+        }
+        if !self.eat_sym('{') {
+            doc.errors
+                .push(parse_error(Some(name), "Table definition has no body"));
+            self.skip_statement();
+            return;
+        }
+
+        let mut parsed = ParsedTable {
+            qualified_name: parts.join("."),
+            alias,
+            table,
+            typed_columns: Vec::new(),
+        };
+        loop {
+            self.skip_newlines();
+            let Some(token) = self.peek() else {
+                doc.errors.push(parse_error(
+                    Some(name.clone()),
+                    "Table definition is not closed",
+                ));
+                break;
+            };
+            if is_sym(Some(token), '}') {
+                self.pos += 1;
+                break;
+            }
+            if self.is_keyword(0, "note")
+                && (is_sym(self.peek_at(1), ':') || is_sym(self.peek_at(1), '{'))
+            {
+                self.pos += 1;
+                if let Some(note) = self.note() {
+                    parsed
+                        .table
+                        .odcl_metadata
+                        .insert("description".to_string(), json!(note));
+                }
+            } else if self.is_keyword(0, "indexes") && is_sym(self.peek_at(1), '{') {
+                self.pos += 1;
+                self.parse_indexes(&mut parsed.table);
+            } else if matches!(token.kind, TokenKind::Word) {
+                self.parse_column(&mut parsed, doc);
+            } else {
+                self.skip_statement();
+            }
+        }
+        if parsed.table.columns.is_empty() {
+            doc.errors.push(parse_error(Some(name), "No columns found"));
+        }
+        doc.tables.push(parsed);
+    }
+
+    /// Text of `: 'note'` or `{ 'note' }` after a `Note` keyword.
+    fn note(&mut self) -> Option<String> {
+        if self.eat_sym(':') {
+            let note = self
+                .peek()
+                .filter(|t| t.kind == TokenKind::Str)
+                .map(|t| t.text.clone());
+            self.skip_line();
+            return note;
+        }
+        let start = self.pos;
+        self.skip_block();
+        self.tokens[start..self.pos]
+            .iter()
+            .find(|t| t.kind == TokenKind::Str)
+            .map(|t| t.text.clone())
+    }
+
+    fn parse_column(&mut self, parsed: &mut ParsedTable, doc: &mut Document) {
+        let Some(name) = self.next().map(|t| t.text) else {
+            return;
+        };
+        let type_start = self.pos;
+        while let Some(token) = self.peek() {
+            let array = is_sym(Some(token), '[') && is_sym(self.peek_at(1), ']');
+            if token.kind == TokenKind::Newline
+                || is_sym(Some(token), '}')
+                || (is_sym(Some(token), '[') && !array)
+            {
+                break;
+            }
+            self.pos += if array { 2 } else { 1 };
+        }
+        let type_range = (type_start, self.pos);
+        if type_range.0 == type_range.1 {
+            doc.errors.push(parse_error(
+                Some(format!("{}.{}", parsed.table.name, name)),
+                "Column has no type",
+            ));
+            self.skip_statement();
+            return;
+        }
+        let data_type = self.text(type_range);
+        let mut column = Column::new(name.clone(), data_type.clone());
+        column.column_order = parsed.table.columns.len() as i32;
+
+        for setting in self.settings() {
+            match setting.key.as_str() {
+                "pk" | "primary key" => {
+                    column.primary_key = true;
+                    column.nullable = false;
+                }
+                "not null" => column.nullable = false,
+                "null" => column.nullable = column.primary_key,
+                "unique" => column.constraints.push("UNIQUE".to_string()),
+                "default" => {
+                    if let Some(value) = setting.value {
+                        column
+                            .constraints
+                            .push(format!("DEFAULT {}", self.raw(value)));
+                    }
+                }
+                "note" => {
+                    if let Some(value) = setting.value {
+                        column.description = self.text(value);
+                    }
+                }
+                "ref" => {
+                    let Some((from, to)) = setting.value else {
+                        continue;
+                    };
+                    let resume = self.pos;
+                    self.pos = from;
+                    let reference = self
+                        .relation_op()
+                        .and_then(|op| Some((op, self.endpoint()?)))
+                        .filter(|_| self.pos == to);
+                    self.pos = resume;
+                    match reference {
+                        Some((op, right)) => doc.refs.push(PendingRef {
+                            left: Endpoint {
+                                table: parsed.qualified_name.clone(),
+                                columns: vec![name.clone()],
+                            },
+                            op,
+                            right,
+                        }),
+                        None => doc.errors.push(parse_error(
+                            Some(format!("{}.{}", parsed.table.name, name)),
+                            format!("Invalid inline reference '{}'", self.raw((from, to))),
+                        )),
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.skip_statement();
+        parsed
+            .typed_columns
+            .push((parsed.table.columns.len(), data_type));
+        parsed.table.columns.push(column);
+    }
+
+    /// `indexes { ... }`: primary key and unique single-column indexes mark their columns.
+    fn parse_indexes(&mut self, table: &mut Table) {
+        self.pos += 1;
+        loop {
+            self.skip_newlines();
+            match self.peek() {
+                None => return,
+                Some(t) if is_sym(Some(t), '}') => {
+                    self.pos += 1;
+                    return;
+                }
+                _ => {}
+            }
+            let columns = if self.is_sym('(') {
+                self.column_list()
+            } else {
+                self.word().into_iter().collect()
+            };
+            let settings = self.settings();
+            let is = |key: &str| settings.iter().any(|s| s.key == key);
+            for column in table
+                .columns
+                .iter_mut()
+                .filter(|c| columns.contains(&c.name))
+            {
+                if is("pk") || is("primary key") {
+                    column.primary_key = true;
+                    column.nullable = false;
+                } else if is("unique")
+                    && columns.len() == 1
+                    && !column.constraints.iter().any(|c| c == "UNIQUE")
+                {
+                    column.constraints.push("UNIQUE".to_string());
+                }
+            }
+            self.skip_statement();
+        }
+    }
+
+    /// `Ref name: a.x > b.y` or `Ref name { a.x > b.y ... }`.
+    fn parse_ref(&mut self, doc: &mut Document) {
+        self.pos += 1;
+        self.qualified_name();
+        if self.eat_sym(':') {
+            self.parse_ref_line(doc);
+            return;
+        }
+        if !self.eat_sym('{') {
+            doc.errors.push(parse_error(None, "Invalid Ref definition"));
+            self.skip_statement();
+            return;
+        }
+        loop {
+            self.skip_newlines();
+            match self.peek() {
+                None => return,
+                Some(t) if is_sym(Some(t), '}') => {
+                    self.pos += 1;
+                    return;
+                }
+                _ => self.parse_ref_line(doc),
+            }
+        }
+    }
+
+    fn parse_ref_line(&mut self, doc: &mut Document) {
+        let start = self.pos;
+        let reference = self.endpoint().and_then(|left| {
+            let op = self.relation_op()?;
+            Some(PendingRef {
+                left,
+                op,
+                right: self.endpoint()?,
+            })
+        });
+        match reference {
+            Some(reference) => {
+                // Settings such as `delete: cascade` have no equivalent in the model
+                self.settings();
+                doc.refs.push(reference);
+            }
+            None => {
+                let end = (start + 1..self.tokens.len())
+                    .find(|&i| self.tokens[i].kind == TokenKind::Newline)
+                    .unwrap_or(self.tokens.len());
+                doc.errors.push(ParserError {
+                    error_type: "relationship_error".to_string(),
+                    field: None,
+                    message: format!("Invalid reference '{}'", self.raw((start, end))),
+                });
+            }
+        }
+        self.skip_statement();
+    }
+
+    fn parse_enum(&mut self, doc: &mut Document) {
+        self.pos += 1;
+        let parts = self.qualified_name();
+        if parts.is_empty() || !self.eat_sym('{') {
+            doc.errors
+                .push(parse_error(None, "Invalid Enum definition"));
+            self.skip_statement();
+            return;
+        }
+        let mut values = Vec::new();
+        loop {
+            self.skip_newlines();
+            match self.peek() {
+                None => break,
+                Some(t) if is_sym(Some(t), '}') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some(t) if matches!(t.kind, TokenKind::Word | TokenKind::Str) => {
+                    values.extend(self.next().map(|t| t.text));
+                    self.settings();
+                }
+                _ => {}
+            }
+            self.skip_statement();
+        }
+        let name = parts.join(".");
+        if parts.len() > 1 {
+            doc.enums
+                .entry(parts[parts.len() - 1].clone())
+                .or_insert_with(|| values.clone());
+        }
+        doc.enums.insert(name, values);
+    }
+
+    fn parse_table_group(&mut self, doc: &mut Document) {
+        self.pos += 1;
+        let name = self.qualified_name().join(".");
+        self.settings();
+        if name.is_empty() || !self.eat_sym('{') {
+            doc.errors
+                .push(parse_error(None, "Invalid TableGroup definition"));
+            self.skip_statement();
+            return;
+        }
+        let mut members = Vec::new();
+        loop {
+            self.skip_newlines();
+            match self.peek() {
+                None => break,
+                Some(t) if is_sym(Some(t), '}') => {
+                    self.pos += 1;
+                    break;
+                }
+                _ if self.is_keyword(0, "note")
+                    && (is_sym(self.peek_at(1), ':') || is_sym(self.peek_at(1), '{')) =>
+                {
+                    self.pos += 1;
+                    self.note();
+                    continue;
+                }
+                _ => {
+                    let member = self.qualified_name();
+                    if !member.is_empty() {
+                        members.push(member.join("."));
+                    }
+                }
+            }
+            self.skip_statement();
+        }
+        doc.groups.push((name, members));
+    }
+}
+
+fn is_sym(token: Option<&Token>, sym: char) -> bool {
+    token.is_some_and(|t| t.kind == TokenKind::Sym && t.text.starts_with(sym))
+}
+
+/// Resolve enums, references and table groups against the parsed tables.
+fn resolve(doc: Document) -> DbmlImportResult {
+    let Document {
+        mut tables,
+        refs,
+        enums,
+        groups,
+        mut errors,
+    } = doc;
+
+    // Tables are referenced by their qualified name, bare name or alias
+    let mut by_name: HashMap<String, usize> = HashMap::new();
+    for (idx, parsed) in tables.iter().enumerate() {
+        by_name.insert(parsed.qualified_name.to_lowercase(), idx);
+        if let Some(alias) = &parsed.alias {
+            by_name.insert(alias.to_lowercase(), idx);
+        }
+    }
+    for (idx, parsed) in tables.iter().enumerate() {
+        by_name
+            .entry(parsed.table.name.to_lowercase())
+            .or_insert(idx);
+    }
+    let lookup = |name: &str| {
+        let name = name.to_lowercase();
+        by_name
+            .get(&name)
+            .or_else(|| by_name.get(name.strip_prefix("public.")?))
+            .copied()
+    };
+    // Unknown tables keep their bare name, so they can match tables already in the domain
+    let table_name = |name: &str| match lookup(name) {
+        Some(idx) => tables[idx].table.name.clone(),
+        None => name.rsplit('.').next().unwrap_or(name).to_string(),
+    };
+
+    let mut relationships = Vec::new();
+    for reference in &refs {
+        let (source, target, cardinality) = match reference.op {
+            "<" => (&reference.right, &reference.left, Cardinality::ManyToOne),
+            "-" => (&reference.left, &reference.right, Cardinality::OneToOne),
+            "<>" => (&reference.left, &reference.right, Cardinality::ManyToMany),
+            _ => (&reference.left, &reference.right, Cardinality::ManyToOne),
+        };
+        let source_table = table_name(&source.table);
+        let target_table = table_name(&target.table);
+        match ForeignKeyDetails::from_columns(&source.columns, &target.columns) {
+            Ok(details) => {
+                let mut relationship =
+                    ImportedRelationship::foreign_key(source_table, target_table, Some(details));
+                relationship.cardinality = Some(cardinality);
+                relationships.push(relationship);
+            }
+            Err(message) => errors.push(ParserError {
+                error_type: "relationship_error".to_string(),
+                field: Some(format!("{} -> {}", source_table, target_table)),
+                message,
+            }),
+        }
+    }
+
+    let mut table_groups = HashMap::new();
+    for (group, members) in groups {
+        let mut names = Vec::new();
+        for member in members {
+            let Some(idx) = lookup(&member) else {
+                errors.push(parse_error(
+                    Some(group.clone()),
+                    format!("TableGroup references unknown table '{}'", member),
+                ));
+                continue;
+            };
+            let table = &mut tables[idx].table;
+            let tag = format!("{}{}", TABLE_GROUP_TAG_PREFIX, group);
+            if !table.tags.contains(&tag) {
+                table.tags.push(tag);
+            }
+            names.push(table.name.clone());
+        }
+        table_groups.insert(group, names);
+    }
+
+    let tables = tables
+        .into_iter()
+        .map(|mut parsed| {
+            for (idx, type_name) in &parsed.typed_columns {
+                let values = enums
+                    .get(type_name)
+                    .or_else(|| enums.get(type_name.strip_prefix("public.").unwrap_or(type_name)));
+                if let Some(values) = values {
+                    let column = &mut parsed.table.columns[*idx];
+                    column.data_type = "STRING".to_string();
+                    column.enum_values = values.clone();
+                }
+            }
+            parsed
+                .table
+                .odcl_metadata
+                .insert("source_format".to_string(), json!("dbml"));
+            parsed.table
+        })
+        .collect();
+
+    DbmlImportResult {
+        tables,
+        relationships,
+        table_groups,
+        errors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tables_refs_enums_and_groups() {
+        let source = r#"
+Project shop { database_type: 'PostgreSQL' }
+
+// Orders placed by customers
+Table public.users as U {
+  id integer [pk, increment]
+  email varchar(255) [not null, unique, note: 'Login \'email\'']
+  created_at timestamp [default: `now()`]
+}
+
+Table orders [note: 'Placed orders'] {
+  id int [primary key]
+  user_id int [ref: > U.id]
+  status order_status [default: 'new']
+  tags "varchar[]"
+}
+
+Table order_items {
+  order_id int
+  line int
+  indexes {
+    (order_id, line) [pk]
+  }
+}
+
+Ref: order_items.order_id > orders.id [delete: cascade]
+Ref { users.id - orders.id }
+
+Enum order_status {
+  new
+  "in progress" [note: 'Being packed']
+}
+
+TableGroup commerce {
+  orders
+  order_items
+}
+"#;
+        let result = DbmlParser::new().parse(source).unwrap();
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(result.tables.len(), 3);
+
+        let users = &result.tables[0];
+        assert_eq!(users.name, "users");
+        assert_eq!(users.schema_name.as_deref(), Some("public"));
+        assert!(users.columns[0].primary_key);
+        assert_eq!(users.columns[1].data_type, "VARCHAR(255)");
+        assert!(!users.columns[1].nullable);
+        assert_eq!(users.columns[1].constraints, ["UNIQUE"]);
+        assert_eq!(users.columns[1].description, "Login 'email'");
+        assert_eq!(users.columns[2].constraints, ["DEFAULT `now()`"]);
+
+        let orders = &result.tables[1];
+        assert_eq!(orders.odcl_metadata["description"], json!("Placed orders"));
+        assert_eq!(orders.columns[2].data_type, "STRING");
+        assert_eq!(orders.columns[2].enum_values, ["new", "in progress"]);
+        assert_eq!(orders.columns[3].data_type, "VARCHAR[]");
+        assert_eq!(orders.tags, ["table_group:commerce"]);
+
+        let items = &result.tables[2];
+        assert!(items.columns.iter().all(|c| c.primary_key && !c.nullable));
+        assert_eq!(result.table_groups["commerce"], ["orders", "order_items"]);
+
+        let rels: Vec<_> = result
+            .relationships
+            .iter()
+            .map(|r| {
+                let fk = r.foreign_key_details.as_ref().unwrap();
+                (
+                    r.source_table.as_str(),
+                    fk.source_column.as_str(),
+                    r.target_table.as_str(),
+                    r.cardinality,
+                )
+            })
+            .collect();
+        assert_eq!(
+            rels,
+            [
+                ("orders", "user_id", "users", Some(Cardinality::ManyToOne)),
+                (
+                    "order_items",
+                    "order_id",
+                    "orders",
+                    Some(Cardinality::ManyToOne)
+                ),
+                ("users", "id", "orders", Some(Cardinality::OneToOne)),
+            ]
+        );
+    }
+}
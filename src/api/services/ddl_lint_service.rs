@@ -0,0 +1,360 @@
+//! SQL DDL linting.
+//!
+//! Tables parsed from a SQL import are checked for DDL that is usually a mistake at the source:
+//! - `missing_primary_key`: the table declares no primary key
+//! - `nullable_key`: a primary key column is not declared `NOT NULL`
+//! - `unbounded_string`: an MSSQL `TEXT`/`NTEXT` column, or a `VARCHAR`/`CHAR` without a length
+//! - `reserved_word`: a table or column named with an SQL reserved word
+//!
+//! Findings are attached to the table's `errors` with the rule's severity, so they are saved
+//! with the table and shown wherever table errors are.
+//!
+//! Configuration (environment variables):
+//! - `SQL_LINT_SEVERITIES`: comma-separated `rule=severity` overrides, where severity is
+//!   `error`, `warning`, `info` or `off` (e.g. `reserved_word=error,unbounded_string=off`)
+
+use crate::models::Table;
+use crate::models::enums::DatabaseType;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use tracing::warn;
+
+/// Reserved in ANSI SQL and the common dialects; quoting is needed wherever they are used.
+const RESERVED_WORDS: &[&str] = &[
+    "ALL",
+    "ALTER",
+    "AND",
+    "AS",
+    "BETWEEN",
+    "BY",
+    "CASE",
+    "CHECK",
+    "COLUMN",
+    "CONSTRAINT",
+    "CREATE",
+    "CROSS",
+    "CURRENT_DATE",
+    "CURRENT_TIMESTAMP",
+    "CURRENT_USER",
+    "DEFAULT",
+    "DELETE",
+    "DISTINCT",
+    "DROP",
+    "ELSE",
+    "END",
+    "EXISTS",
+    "FOREIGN",
+    "FROM",
+    "FULL",
+    "GRANT",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INNER",
+    "INSERT",
+    "INTERSECT",
+    "INTO",
+    "IS",
+    "JOIN",
+    "KEY",
+    "LEFT",
+    "LIKE",
+    "NOT",
+    "NULL",
+    "ON",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "PRIMARY",
+    "REFERENCES",
+    "RIGHT",
+    "SELECT",
+    "SET",
+    "TABLE",
+    "THEN",
+    "TO",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USER",
+    "VALUES",
+    "WHEN",
+    "WHERE",
+    "WITH",
+];
+
+/// A DDL lint rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LintRule {
+    MissingPrimaryKey,
+    NullableKey,
+    UnboundedString,
+    ReservedWord,
+}
+
+impl LintRule {
+    pub const ALL: [LintRule; 4] = [
+        LintRule::MissingPrimaryKey,
+        LintRule::NullableKey,
+        LintRule::UnboundedString,
+        LintRule::ReservedWord,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::MissingPrimaryKey => "missing_primary_key",
+            Self::NullableKey => "nullable_key",
+            Self::UnboundedString => "unbounded_string",
+            Self::ReservedWord => "reserved_word",
+        }
+    }
+
+    fn default_severity(self) -> Option<LintSeverity> {
+        Some(match self {
+            Self::MissingPrimaryKey | Self::NullableKey => LintSeverity::Warning,
+            Self::UnboundedString | Self::ReservedWord => LintSeverity::Info,
+        })
+    }
+}
+
+/// How serious a lint finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LintSeverity {
+    Error,
+    Warning,
+    Info,
+}
+
+impl LintSeverity {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Info => "info",
+        }
+    }
+}
+
+/// A single lint finding
+#[derive(Debug, Clone, Serialize)]
+pub struct LintFinding {
+    pub rule: LintRule,
+    pub severity: LintSeverity,
+    pub table: String,
+    /// Column the finding is about, or `table`
+    pub field: String,
+    pub message: String,
+}
+
+impl LintFinding {
+    /// The finding as an entry of a table's `errors`
+    pub fn to_error(&self) -> HashMap<String, Value> {
+        HashMap::from([
+            ("type".to_string(), Value::from("lint")),
+            ("rule".to_string(), Value::from(self.rule.name())),
+            ("severity".to_string(), Value::from(self.severity.as_str())),
+            ("field".to_string(), Value::from(self.field.clone())),
+            ("message".to_string(), Value::from(self.message.clone())),
+        ])
+    }
+}
+
+/// Severity of each lint rule; `None` turns a rule off
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DdlLintConfig {
+    severities: HashMap<LintRule, Option<LintSeverity>>,
+}
+
+impl Default for DdlLintConfig {
+    fn default() -> Self {
+        Self {
+            severities: LintRule::ALL
+                .into_iter()
+                .map(|rule| (rule, rule.default_severity()))
+                .collect(),
+        }
+    }
+}
+
+impl DdlLintConfig {
+    /// Read severity overrides from `SQL_LINT_SEVERITIES`.
+    pub fn from_env() -> Self {
+        match std::env::var("SQL_LINT_SEVERITIES") {
+            Ok(value) => Self::parse(&value),
+            Err(_) => Self::default(),
+        }
+    }
+
+    /// Defaults overridden by `rule=severity` pairs; invalid pairs are logged and ignored.
+    pub fn parse(overrides: &str) -> Self {
+        let mut config = Self::default();
+        for pair in overrides
+            .split(',')
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+        {
+            let parsed = pair.split_once('=').and_then(|(rule, severity)| {
+                let rule = LintRule::ALL
+                    .into_iter()
+                    .find(|r| r.name().eq_ignore_ascii_case(rule.trim()))?;
+                let severity = match severity.trim().to_lowercase().as_str() {
+                    "error" => Some(LintSeverity::Error),
+                    "warning" => Some(LintSeverity::Warning),
+                    "info" => Some(LintSeverity::Info),
+                    "off" => None,
+                    _ => return None,
+                };
+                Some((rule, severity))
+            });
+            match parsed {
+                Some((rule, severity)) => {
+                    config.severities.insert(rule, severity);
+                }
+                None => warn!("Ignoring invalid SQL_LINT_SEVERITIES entry '{}'", pair),
+            }
+        }
+        config
+    }
+
+    pub fn severity(&self, rule: LintRule) -> Option<LintSeverity> {
+        self.severities.get(&rule).copied().flatten()
+    }
+
+    /// Lint the tables of an import, attaching each finding to its table's `errors`.
+    pub fn lint_tables(&self, tables: &mut [Table]) -> Vec<LintFinding> {
+        let mut findings = Vec::new();
+        for table in tables.iter_mut() {
+            let table_findings = self.lint_table(table);
+            table
+                .errors
+                .extend(table_findings.iter().map(LintFinding::to_error));
+            findings.extend(table_findings);
+        }
+        findings
+    }
+
+    fn lint_table(&self, table: &Table) -> Vec<LintFinding> {
+        let mut findings = Vec::new();
+        let mut report = |rule: LintRule, field: &str, message: String| {
+            if let Some(severity) = self.severity(rule) {
+                findings.push(LintFinding {
+                    rule,
+                    severity,
+                    table: table.name.clone(),
+                    field: field.to_string(),
+                    message,
+                });
+            }
+        };
+
+        if !table.columns.iter().any(|c| c.primary_key) {
+            report(
+                LintRule::MissingPrimaryKey,
+                "table",
+                format!("Table '{}' has no primary key", table.name),
+            );
+        }
+        if is_reserved_word(&table.name) {
+            report(
+                LintRule::ReservedWord,
+                "table",
+                format!("Table name '{}' is a reserved word", table.name),
+            );
+        }
+
+        let mssql = table.database_type == Some(DatabaseType::SqlServer);
+        for column in &table.columns {
+            if column.primary_key && column.nullable {
+                report(
+                    LintRule::NullableKey,
+                    &column.name,
+                    format!(
+                        "Primary key column '{}' is not declared NOT NULL",
+                        column.name
+                    ),
+                );
+            }
+            if let Some(problem) = unbounded_string(&column.data_type, mssql) {
+                report(
+                    LintRule::UnboundedString,
+                    &column.name,
+                    format!("Column '{}' {}", column.name, problem),
+                );
+            }
+            if is_reserved_word(&column.name) {
+                report(
+                    LintRule::ReservedWord,
+                    &column.name,
+                    format!("Column name '{}' is a reserved word", column.name),
+                );
+            }
+        }
+        findings
+    }
+}
+
+fn is_reserved_word(name: &str) -> bool {
+    RESERVED_WORDS
+        .iter()
+        .any(|word| word.eq_ignore_ascii_case(name))
+}
+
+/// Why a string column type has no usable length, if it has none.
+fn unbounded_string(data_type: &str, mssql: bool) -> Option<&'static str> {
+    let base = data_type.trim().to_uppercase();
+    match base.as_str() {
+        "TEXT" | "NTEXT" if mssql => {
+            Some("uses TEXT, which is deprecated in SQL Server; use VARCHAR(n) or VARCHAR(MAX)")
+        }
+        // The SQL parser keeps the length of these (but not of NVARCHAR)
+        "VARCHAR" | "CHAR" => Some("has no length; declare one, e.g. VARCHAR(255)"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::SQLParser;
+
+    #[test]
+    fn test_lint_sql_import() {
+        let sql = r#"
+            CREATE TABLE [order] (id INT PRIMARY KEY, notes TEXT, code VARCHAR, name VARCHAR(50));
+            CREATE TABLE audit (happened_at DATETIME, [user] NVARCHAR(100));
+        "#;
+        let (mut tables, _, _) = SQLParser::with_dialect_name("mssql")
+            .parse_with_relationships(sql)
+            .unwrap();
+
+        let config = DdlLintConfig::parse("reserved_word=error, unbounded_string=bogus");
+        let findings = config.lint_tables(&mut tables);
+        let found: Vec<(&str, &str, LintSeverity)> = findings
+            .iter()
+            .map(|f| (f.rule.name(), f.field.as_str(), f.severity))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("reserved_word", "table", LintSeverity::Error),
+                ("nullable_key", "id", LintSeverity::Warning),
+                ("unbounded_string", "notes", LintSeverity::Info),
+                ("unbounded_string", "code", LintSeverity::Info),
+                ("missing_primary_key", "table", LintSeverity::Warning),
+                ("reserved_word", "user", LintSeverity::Error),
+            ]
+        );
+        assert_eq!(tables[0].errors.len(), 4);
+        assert_eq!(tables[1].errors[0]["rule"], "missing_primary_key");
+
+        let quiet = DdlLintConfig::parse("nullable_key=off,missing_primary_key=off");
+        assert_eq!(quiet.severity(LintRule::NullableKey), None);
+        assert_eq!(
+            quiet.severity(LintRule::ReservedWord),
+            Some(LintSeverity::Info)
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! Shared diagram images.
+//!
+//! Rendered PNG diagrams can be embedded in wikis through signed, expiring URLs (see
+//! `JwtService::sign_diagram_share`). Renders are cached per share target and reused until
+//! the model changes: each entry records a fingerprint of the tables and relationships it
+//! was drawn from, and a request whose fingerprint no longer matches renders the image again.
+
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use uuid::Uuid;
+
+use crate::models::DataModel;
+
+/// Most rendered images kept in memory; the least recently used is evicted first
+const MAX_CACHED_IMAGES: usize = 128;
+
+/// Fingerprint of the part of a model drawn in a diagram: the selected tables (all of them
+/// if `table_ids` is empty) and the relationships between them.
+pub fn model_fingerprint(model: &DataModel, table_ids: &[Uuid]) -> String {
+    let selected = |id: &Uuid| table_ids.is_empty() || table_ids.contains(id);
+    let tables: Vec<serde_json::Value> = model
+        .tables
+        .iter()
+        .filter(|t| selected(&t.id))
+        .filter_map(|t| serde_json::to_value(t).ok())
+        .collect();
+    let relationships: Vec<serde_json::Value> = model
+        .relationships
+        .iter()
+        .filter(|r| selected(&r.source_table_id) && selected(&r.target_table_id))
+        .filter_map(|r| serde_json::to_value(r).ok())
+        .collect();
+    let content = serde_json::json!({
+        "tables": tables,
+        "relationships": relationships,
+    });
+    format!("{:x}", Sha256::digest(content.to_string().as_bytes()))
+}
+
+struct CachedImage {
+    fingerprint: String,
+    png: Arc<Vec<u8>>,
+    last_used: Instant,
+}
+
+/// In-memory cache of rendered diagram images
+#[derive(Default)]
+pub struct DiagramImageCache {
+    entries: Mutex<HashMap<String, CachedImage>>,
+}
+
+impl DiagramImageCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache key of a share target
+    pub fn key(email: &str, domain: &str, table_ids: &[Uuid]) -> String {
+        let mut ids: Vec<String> = table_ids.iter().map(Uuid::to_string).collect();
+        ids.sort();
+        format!("{}/{}/{}", email, domain, ids.join(","))
+    }
+
+    /// The cached image of `key`, if it was rendered from a model with `fingerprint`.
+    /// An image rendered from an older model is dropped.
+    pub fn get(&self, key: &str, fingerprint: &str) -> Option<Arc<Vec<u8>>> {
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        match entries.get_mut(key) {
+            Some(entry) if entry.fingerprint == fingerprint => {
+                entry.last_used = Instant::now();
+                Some(entry.png.clone())
+            }
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn insert(&self, key: String, fingerprint: String, png: Vec<u8>) -> Arc<Vec<u8>> {
+        let png = Arc::new(png);
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() >= MAX_CACHED_IMAGES
+            && !entries.contains_key(&key)
+            && let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+        {
+            entries.remove(&oldest);
+        }
+        entries.insert(
+            key,
+            CachedImage {
+                fingerprint,
+                png: png.clone(),
+                last_used: Instant::now(),
+            },
+        );
+        png
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Column, Table};
+
+    #[test]
+    fn test_model_change_invalidates_cached_image() {
+        let mut model = DataModel::new("sales".to_string(), String::new(), String::new());
+        model.tables.push(Table::new(
+            "orders".to_string(),
+            vec![Column::new("id".to_string(), "INT".to_string())],
+        ));
+        let cache = DiagramImageCache::new();
+        let key = DiagramImageCache::key("a@example.com", "sales", &[]);
+
+        let before = model_fingerprint(&model, &[]);
+        cache.insert(key.clone(), before.clone(), vec![1, 2, 3]);
+        assert_eq!(cache.get(&key, &before).as_deref(), Some(&vec![1, 2, 3]));
+
+        // Tables outside the selection do not change the fingerprint
+        let other = Uuid::new_v4();
+        let selected_before = model_fingerprint(&model, &[other]);
+        model.tables[0]
+            .columns
+            .push(Column::new("total".to_string(), "DECIMAL".to_string()));
+        assert_eq!(model_fingerprint(&model, &[other]), selected_before);
+
+        let after = model_fingerprint(&model, &[]);
+        assert_ne!(before, after);
+        assert!(cache.get(&key, &after).is_none());
+        // The stale render was dropped
+        assert!(cache.get(&key, &before).is_none());
+    }
+}
@@ -0,0 +1,205 @@
+//! Domain dependency graph.
+//!
+//! Builds a domain-level "context map" of a workspace: an edge from a consumer domain to a
+//! provider domain when the consumer imports the provider's tables or relationships
+//! (cross-domain references), or when one of its tables references a table owned by the
+//! provider (a relationship whose source table is in the consumer and whose target table
+//! is in the provider). Domains that depend on each other in a loop are reported as cycles.
+
+use petgraph::algo::tarjan_scc;
+use petgraph::graphmap::DiGraphMap;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::DataModel;
+
+/// Tables and relationships a domain imports from another domain
+#[derive(Debug, Clone, Default)]
+pub struct DomainImport {
+    pub consumer: String,
+    pub provider: String,
+    pub tables: usize,
+    pub relationships: usize,
+}
+
+/// A domain of the graph
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+pub struct DomainNode {
+    pub name: String,
+    pub tables: usize,
+    /// Domains this domain consumes
+    pub depends_on: usize,
+    /// Domains consuming this domain
+    pub used_by: usize,
+}
+
+/// `consumer` depends on `provider`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, ToSchema)]
+pub struct DomainDependency {
+    pub consumer: String,
+    pub provider: String,
+    /// Provider tables imported by the consumer
+    pub imported_tables: usize,
+    /// Provider relationships imported by the consumer
+    pub imported_relationships: usize,
+    /// Relationships from consumer tables to provider tables
+    pub relationships: usize,
+}
+
+/// Domain-level dependency graph of a workspace
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, ToSchema)]
+pub struct DomainGraph {
+    pub domains: Vec<DomainNode>,
+    pub dependencies: Vec<DomainDependency>,
+    /// Groups of domains that depend on each other in a loop, each sorted by name
+    pub cycles: Vec<Vec<String>>,
+}
+
+/// Build the dependency graph of the domain models (named after their domains).
+pub fn build_domain_graph(models: &[DataModel], imports: &[DomainImport]) -> DomainGraph {
+    let owner: HashMap<Uuid, &str> = models
+        .iter()
+        .flat_map(|m| m.tables.iter().map(|t| (t.id, m.name.as_str())))
+        .collect();
+
+    let mut relationship_counts: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    for relationship in models.iter().flat_map(|m| &m.relationships) {
+        if let (Some(&source), Some(&target)) = (
+            owner.get(&relationship.source_table_id),
+            owner.get(&relationship.target_table_id),
+        ) && source != target
+        {
+            *relationship_counts.entry((source, target)).or_default() += 1;
+        }
+    }
+
+    let mut dependencies: BTreeMap<(String, String), DomainDependency> = BTreeMap::new();
+    for ((consumer, provider), count) in relationship_counts {
+        dependencies
+            .entry((consumer.to_string(), provider.to_string()))
+            .or_insert_with(|| DomainDependency {
+                consumer: consumer.to_string(),
+                provider: provider.to_string(),
+                ..Default::default()
+            })
+            .relationships += count;
+    }
+    for import in imports.iter().filter(|i| i.consumer != i.provider) {
+        if import.tables == 0 && import.relationships == 0 {
+            continue;
+        }
+        let dependency = dependencies
+            .entry((import.consumer.clone(), import.provider.clone()))
+            .or_insert_with(|| DomainDependency {
+                consumer: import.consumer.clone(),
+                provider: import.provider.clone(),
+                ..Default::default()
+            });
+        dependency.imported_tables += import.tables;
+        dependency.imported_relationships += import.relationships;
+    }
+    let dependencies: Vec<DomainDependency> = dependencies.into_values().collect();
+
+    let mut graph = DiGraphMap::<&str, ()>::new();
+    for model in models {
+        graph.add_node(model.name.as_str());
+    }
+    for dependency in &dependencies {
+        graph.add_edge(&dependency.consumer, &dependency.provider, ());
+    }
+    let mut cycles: Vec<Vec<String>> = tarjan_scc(&graph)
+        .into_iter()
+        .filter(|component| component.len() > 1)
+        .map(|component| {
+            let mut names: Vec<String> = component.into_iter().map(str::to_string).collect();
+            names.sort();
+            names
+        })
+        .collect();
+    cycles.sort();
+
+    let mut domains: Vec<DomainNode> = models
+        .iter()
+        .map(|m| DomainNode {
+            name: m.name.clone(),
+            tables: m.tables.len(),
+            depends_on: dependencies.iter().filter(|d| d.consumer == m.name).count(),
+            used_by: dependencies.iter().filter(|d| d.provider == m.name).count(),
+        })
+        .collect();
+    domains.sort_by(|a, b| a.name.cmp(&b.name));
+
+    DomainGraph {
+        domains,
+        dependencies,
+        cycles,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Column, Relationship, Table};
+
+    fn domain(name: &str, tables: &[&Table]) -> DataModel {
+        let mut model = DataModel::new(name.to_string(), String::new(), String::new());
+        model.tables = tables.iter().map(|t| (*t).clone()).collect();
+        model
+    }
+
+    #[test]
+    fn test_domain_graph_dependencies_and_cycles() {
+        let table = |name: &str| {
+            Table::new(
+                name.to_string(),
+                vec![Column::new("id".to_string(), "bigint".to_string())],
+            )
+        };
+        let (customers, orders, invoices) =
+            (table("customers"), table("orders"), table("invoices"));
+        let mut sales = domain("sales", &[&orders]);
+        sales.relationships = vec![
+            Relationship::new(orders.id, customers.id),
+            Relationship::new(orders.id, orders.id),
+        ];
+        let crm = domain("crm", &[&customers]);
+        let finance = domain("finance", &[&invoices]);
+        let imports = vec![
+            DomainImport {
+                consumer: "finance".to_string(),
+                provider: "sales".to_string(),
+                tables: 1,
+                relationships: 0,
+            },
+            DomainImport {
+                consumer: "crm".to_string(),
+                provider: "finance".to_string(),
+                tables: 1,
+                relationships: 1,
+            },
+        ];
+
+        let graph = build_domain_graph(&[sales, crm, finance], &imports);
+        assert_eq!(
+            graph.dependencies[2],
+            DomainDependency {
+                consumer: "sales".to_string(),
+                provider: "crm".to_string(),
+                relationships: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(graph.dependencies.len(), 3);
+        assert_eq!(graph.domains[0].name, "crm");
+        assert_eq!(
+            (graph.domains[0].depends_on, graph.domains[0].used_by),
+            (1, 1)
+        );
+        assert_eq!(graph.cycles, vec![vec!["crm", "finance", "sales"]]);
+
+        let acyclic = build_domain_graph(&[domain("crm", &[&customers])], &imports[..1]);
+        assert!(acyclic.cycles.is_empty());
+    }
+}
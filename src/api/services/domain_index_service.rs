@@ -0,0 +1,161 @@
+//! Materialized per-domain index.
+//!
+//! Listing a domain's stats used to glob its `tables/` directory and parse
+//! `relationships.yaml` on every request. The index file caches the table names and the
+//! table, relationship and imported table counts together with the modification times of
+//! the files they were read from. Writes through the git and model services refresh it;
+//! changes made behind the API's back (git pulls, manual edits) are caught by comparing the
+//! stored modification times, so reading the index costs a few `stat` calls and one small
+//! file read regardless of domain size.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use data_modelling_sdk::models::CrossDomainConfig;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tracing::warn;
+
+use crate::services::relationships_file_service::{RELATIONSHIPS_FILE, count_relationships};
+
+/// Index file in the domain directory (hidden, so it is not listed as a domain or table)
+pub const DOMAIN_INDEX_FILE: &str = ".domain-index.yaml";
+
+const TABLES_DIR: &str = "tables";
+const CROSS_DOMAIN_FILE: &str = "cross_domain.yaml";
+
+/// Cached stats of a file-backed domain.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DomainIndex {
+    /// Table names (file stems under `tables/`), sorted
+    pub tables: Vec<String>,
+    pub relationship_count: usize,
+    pub imported_table_count: usize,
+    /// Modification times of the sources, used to detect out-of-band changes
+    #[serde(default)]
+    pub tables_modified: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub relationships_modified: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub cross_domain_modified: Option<DateTime<Utc>>,
+}
+
+fn modified(path: &Path) -> Option<DateTime<Utc>> {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .map(DateTime::<Utc>::from)
+}
+
+impl DomainIndex {
+    pub fn table_count(&self) -> usize {
+        self.tables.len()
+    }
+
+    /// Scan the domain directory.
+    pub fn build(domain_dir: &Path) -> Self {
+        let tables_dir = domain_dir.join(TABLES_DIR);
+        let mut tables: Vec<String> = std::fs::read_dir(&tables_dir)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| {
+                        p.extension()
+                            .is_some_and(|ext| ext == "yaml" || ext == "yml")
+                    })
+                    .filter_map(|p| p.file_stem()?.to_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        tables.sort();
+
+        let relationships_path = domain_dir.join(RELATIONSHIPS_FILE);
+        let relationship_count = std::fs::read_to_string(&relationships_path)
+            .map(|content| count_relationships(&content))
+            .unwrap_or(0);
+
+        let cross_domain_path = domain_dir.join(CROSS_DOMAIN_FILE);
+        let imported_table_count = std::fs::read_to_string(&cross_domain_path)
+            .ok()
+            .and_then(|content| serde_yaml::from_str::<CrossDomainConfig>(&content).ok())
+            .map(|config| config.imported_tables.len())
+            .unwrap_or(0);
+
+        Self {
+            tables,
+            relationship_count,
+            imported_table_count,
+            tables_modified: modified(&tables_dir),
+            relationships_modified: modified(&relationships_path),
+            cross_domain_modified: modified(&cross_domain_path),
+        }
+    }
+
+    /// Whether the sources are unchanged since the index was built.
+    fn is_fresh(&self, domain_dir: &Path) -> bool {
+        self.tables_modified == modified(&domain_dir.join(TABLES_DIR))
+            && self.relationships_modified == modified(&domain_dir.join(RELATIONSHIPS_FILE))
+            && self.cross_domain_modified == modified(&domain_dir.join(CROSS_DOMAIN_FILE))
+    }
+
+    fn load(domain_dir: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(domain_dir.join(DOMAIN_INDEX_FILE)).ok()?;
+        serde_yaml::from_str(&content).ok()
+    }
+
+    fn save(&self, domain_dir: &Path) -> Result<()> {
+        let path = domain_dir.join(DOMAIN_INDEX_FILE);
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Rebuild and store the index after the domain's files were written.
+    pub fn refresh(domain_dir: &Path) -> Self {
+        let index = Self::build(domain_dir);
+        if let Err(e) = index.save(domain_dir) {
+            warn!("[DomainIndex] {}", e);
+        }
+        index
+    }
+
+    /// The stored index, rebuilt first if it is missing or stale.
+    pub fn get(domain_dir: &Path) -> Self {
+        match Self::load(domain_dir) {
+            Some(index) if index.is_fresh(domain_dir) => index,
+            _ => Self::refresh(domain_dir),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_tracks_domain_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let domain = dir.path();
+        std::fs::create_dir_all(domain.join("tables")).unwrap();
+        std::fs::write(domain.join("tables/orders.yaml"), "name: orders\n").unwrap();
+        std::fs::write(domain.join("tables/notes.txt"), "ignored").unwrap();
+        std::fs::write(
+            domain.join(RELATIONSHIPS_FILE),
+            "relationships:\n  - id: 7f1c1a52-0000-4000-8000-000000000001\n    source_table_id: 7f1c1a52-0000-4000-8000-000000000002\n    target_table_id: 7f1c1a52-0000-4000-8000-000000000003\n",
+        )
+        .unwrap();
+
+        let index = DomainIndex::get(domain);
+        assert_eq!(index.tables, vec!["orders"]);
+        assert_eq!(index.relationship_count, 1);
+        assert_eq!(index.imported_table_count, 0);
+        assert_eq!(DomainIndex::load(domain), Some(index.clone()));
+
+        // A table added behind the index's back is picked up once the directory changes
+        std::fs::write(domain.join("tables/customers.yaml"), "name: customers\n").unwrap();
+        let mut stale = index;
+        stale.tables_modified = None;
+        stale.save(domain).unwrap();
+        assert_eq!(DomainIndex::get(domain).tables, vec!["customers", "orders"]);
+        assert_eq!(DomainIndex::get(domain).table_count(), 2);
+    }
+}
@@ -0,0 +1,291 @@
+//! Domain invites and members.
+//!
+//! Domain owners invite other users to a domain without an administrator editing its
+//! owners. An invite grants a role and is redeemed once with a signed token (see
+//! `JwtService::sign_domain_invite`) by any authenticated user, or only by the email it was
+//! made for; the invite record kept here decides whether the token still works, so revoking
+//! it invalidates the token. Pending invites and the members who redeemed one are stored per
+//! domain in `members.yaml`. Changes hold [`DomainMembers::lock`] from load to save, so
+//! concurrent redemptions and grants don't overwrite each other.
+
+use anyhow::{Context, Result};
+use axum::http::Method;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::sync::OwnedMutexGuard;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// File name of a domain's invites and members
+pub const MEMBERS_FILE: &str = "members.yaml";
+
+/// Role of a member on a domain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DomainRole {
+    /// Counts as a domain owner (may share, invite and change owner-only settings)
+    Owner,
+    Editor,
+    Viewer,
+}
+
+impl DomainRole {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DomainRole::Owner => "owner",
+            DomainRole::Editor => "editor",
+            DomainRole::Viewer => "viewer",
+        }
+    }
+
+    /// Whether the role may send a request with `method`: viewers only read
+    pub fn allows(&self, method: &Method) -> bool {
+        match self {
+            DomainRole::Owner | DomainRole::Editor => true,
+            DomainRole::Viewer => matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS),
+        }
+    }
+}
+
+/// A pending invite to a domain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DomainInvite {
+    pub id: Uuid,
+    pub role: DomainRole,
+    /// Only this user may redeem the invite; anyone with the token if omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl DomainInvite {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at <= now
+    }
+}
+
+/// A user who redeemed an invite or was granted a role
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DomainMember {
+    pub email: String,
+    pub role: DomainRole,
+    /// Who made the invite or granted the role
+    pub invited_by: String,
+    pub joined_at: DateTime<Utc>,
+}
+
+/// Why an invite could not be redeemed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RedeemError {
+    /// Revoked or already redeemed
+    NotFound,
+    Expired,
+    /// Made for another email
+    WrongRecipient,
+}
+
+/// Invites and members of a domain (`members.yaml`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DomainMembers {
+    #[serde(default)]
+    pub invites: Vec<DomainInvite>,
+    #[serde(default)]
+    pub members: Vec<DomainMember>,
+}
+
+impl DomainMembers {
+    fn path(domain_dir: &Path) -> PathBuf {
+        domain_dir.join(MEMBERS_FILE)
+    }
+
+    /// Serialize changes to a domain's `members.yaml`; hold the guard from load to save.
+    pub async fn lock(domain_dir: &Path) -> OwnedMutexGuard<()> {
+        type Locks = Mutex<HashMap<PathBuf, Arc<tokio::sync::Mutex<()>>>>;
+        static LOCKS: OnceLock<Locks> = OnceLock::new();
+        let lock = LOCKS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(Self::path(domain_dir))
+            .or_default()
+            .clone();
+        lock.lock_owned().await
+    }
+
+    /// Load a domain's invites and members (none if the file is missing).
+    pub fn load(domain_dir: &Path) -> Self {
+        std::fs::read_to_string(Self::path(domain_dir))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, domain_dir: &Path) -> Result<()> {
+        let path = Self::path(domain_dir);
+        std::fs::create_dir_all(domain_dir)
+            .with_context(|| format!("Failed to create {}", domain_dir.display()))?;
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Invites that can still be redeemed
+    pub fn pending(&self, now: DateTime<Utc>) -> Vec<DomainInvite> {
+        self.invites
+            .iter()
+            .filter(|invite| !invite.is_expired(now))
+            .cloned()
+            .collect()
+    }
+
+    pub fn revoke(&mut self, id: Uuid) -> bool {
+        let before = self.invites.len();
+        self.invites.retain(|i| i.id != id);
+        self.invites.len() != before
+    }
+
+    /// Role of a user on the domain, if they are a member
+    pub fn role_of(&self, email: &str) -> Option<DomainRole> {
+        self.members
+            .iter()
+            .find(|m| m.email.eq_ignore_ascii_case(email))
+            .map(|m| m.role)
+    }
+
+    /// Redeem an invite for `email`, replacing any role the user had.
+    ///
+    /// Invites are single-use: a redeemed invite is removed, as are expired ones.
+    pub fn redeem(
+        &mut self,
+        id: Uuid,
+        email: &str,
+        now: DateTime<Utc>,
+    ) -> Result<DomainMember, RedeemError> {
+        let invite = self
+            .invites
+            .iter()
+            .find(|i| i.id == id)
+            .cloned()
+            .ok_or(RedeemError::NotFound)?;
+        if invite.is_expired(now) {
+            self.revoke(id);
+            return Err(RedeemError::Expired);
+        }
+        if invite
+            .email
+            .as_deref()
+            .is_some_and(|recipient| !recipient.eq_ignore_ascii_case(email))
+        {
+            return Err(RedeemError::WrongRecipient);
+        }
+
+        self.revoke(id);
+        Ok(self.grant(email, invite.role, &invite.created_by, now))
+    }
+
+    /// Give `email` a role, replacing any role the user had
+    pub fn grant(
+        &mut self,
+        email: &str,
+        role: DomainRole,
+        granted_by: &str,
+        now: DateTime<Utc>,
+    ) -> DomainMember {
+        self.remove_member(email);
+        let member = DomainMember {
+            email: email.to_string(),
+            role,
+            invited_by: granted_by.to_string(),
+            joined_at: now,
+        };
+        self.members.push(member.clone());
+        member
+    }
+
+    pub fn remove_member(&mut self, email: &str) -> bool {
+        let before = self.members.len();
+        self.members
+            .retain(|m| !m.email.eq_ignore_ascii_case(email));
+        self.members.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_invites_are_single_use_and_respect_recipient_and_expiry() {
+        let now = Utc::now();
+        let invite = |role, email: Option<&str>, expires_at| DomainInvite {
+            id: Uuid::new_v4(),
+            role,
+            email: email.map(str::to_string),
+            created_by: "owner@example.com".to_string(),
+            created_at: now,
+            expires_at,
+        };
+        let open = invite(DomainRole::Editor, None, now + Duration::days(7));
+        let personal = invite(
+            DomainRole::Owner,
+            Some("Dev@Example.com"),
+            now + Duration::days(7),
+        );
+        let expired = invite(DomainRole::Viewer, None, now - Duration::days(1));
+        let mut members = DomainMembers {
+            invites: vec![open.clone(), personal.clone(), expired.clone()],
+            members: Vec::new(),
+        };
+        assert_eq!(members.pending(now).len(), 2);
+
+        let member = members.redeem(open.id, "dev@example.com", now).unwrap();
+        assert_eq!(member.role, DomainRole::Editor);
+        assert_eq!(
+            members.redeem(open.id, "other@example.com", now),
+            Err(RedeemError::NotFound)
+        );
+        assert_eq!(
+            members.redeem(personal.id, "other@example.com", now),
+            Err(RedeemError::WrongRecipient)
+        );
+        assert_eq!(
+            members.redeem(expired.id, "dev@example.com", now),
+            Err(RedeemError::Expired)
+        );
+
+        // Redeeming again replaces the member's role
+        members.redeem(personal.id, "dev@example.com", now).unwrap();
+        assert_eq!(members.members.len(), 1);
+        assert_eq!(members.role_of("DEV@example.com"), Some(DomainRole::Owner));
+        assert!(members.invites.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_changes_are_serialized() {
+        let dir = tempfile::tempdir().unwrap();
+        let grants = (0..8).map(|i| {
+            let dir = dir.path().to_path_buf();
+            tokio::spawn(async move {
+                let _lock = DomainMembers::lock(&dir).await;
+                let mut members = DomainMembers::load(&dir);
+                tokio::task::yield_now().await;
+                members.grant(
+                    &format!("user{}@example.com", i),
+                    DomainRole::Viewer,
+                    "owner@example.com",
+                    Utc::now(),
+                );
+                members.save(&dir).unwrap();
+            })
+        });
+        for grant in grants.collect::<Vec<_>>() {
+            grant.await.unwrap();
+        }
+        assert_eq!(DomainMembers::load(dir.path()).members.len(), 8);
+    }
+}
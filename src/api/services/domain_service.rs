@@ -0,0 +1,202 @@
+//! Domain metadata and provisioning helpers.
+//!
+//! Domain metadata (description, color, icon, owners) is stored as `domain.yaml` in the
+//! domain directory and surfaced by the domain list/get endpoints and the docs export. New domains can be provisioned from a template domain: its tables and
+//! relationships are copied with fresh ids, along with the template's domain-level
+//! configuration files.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::{DataModel, Relationship, Table};
+
+/// Longest accepted icon name or emoji
+const MAX_ICON_LEN: usize = 64;
+
+/// File holding a domain's metadata
+pub const DOMAIN_METADATA_FILE: &str = "domain.yaml";
+
+/// Domain configuration files copied when a domain is cloned from a template.
+///
+/// Work item links, export schedules and cross-domain references point at the template's
+/// own tables or targets and are not copied.
+pub const TEMPLATE_CONFIG_FILES: [&str; 3] = [
+    "environments.yaml",
+    "naming.yaml",
+    "relationship-rules.yaml",
+];
+
+/// Descriptive metadata of a domain.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct DomainMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Hex color used in the domain picker (e.g. "#1f77b4")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Icon name or emoji
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub icon: Option<String>,
+    /// Owner emails
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub owners: Vec<String>,
+}
+
+impl DomainMetadata {
+    pub fn is_empty(&self) -> bool {
+        self.description.is_none()
+            && self.color.is_none()
+            && self.icon.is_none()
+            && self.owners.is_empty()
+    }
+
+    /// Check color, icon and owner values.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(color) = &self.color {
+            let hex = color.strip_prefix('#').unwrap_or_default();
+            if !matches!(hex.len(), 3 | 6) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(format!(
+                    "Invalid color '{}': expected #RGB or #RRGGBB",
+                    color
+                ));
+            }
+        }
+        if let Some(icon) = &self.icon
+            && (icon.trim().is_empty() || icon.chars().count() > MAX_ICON_LEN)
+        {
+            return Err(format!("Icon must be 1-{} characters long", MAX_ICON_LEN));
+        }
+        if let Some(owner) = self.owners.iter().find(|o| !o.contains('@')) {
+            return Err(format!("Invalid owner email '{}'", owner));
+        }
+        Ok(())
+    }
+
+    /// Load metadata from a domain directory, returning empty metadata if none is stored.
+    pub fn load(domain_dir: &Path) -> Self {
+        std::fs::read_to_string(domain_dir.join(DOMAIN_METADATA_FILE))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save metadata to a domain directory.
+    pub fn save(&self, domain_dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(domain_dir)
+            .with_context(|| format!("Failed to create {}", domain_dir.display()))?;
+        let path = domain_dir.join(DOMAIN_METADATA_FILE);
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Set the model description shown by the docs export: the domain description
+    /// followed by its owners.
+    pub fn apply_to_model(&self, model: &DataModel) -> DataModel {
+        let mut model = model.clone();
+        let owners =
+            (!self.owners.is_empty()).then(|| format!("Owners: {}", self.owners.join(", ")));
+        let summary: Vec<String> = self
+            .description
+            .clone()
+            .or_else(|| model.description.clone())
+            .into_iter()
+            .chain(owners)
+            .collect();
+        if !summary.is_empty() {
+            model.description = Some(summary.join("\n\n"));
+        }
+        model
+    }
+}
+
+/// Copy a template model's tables and relationships with fresh ids.
+///
+/// Relationships are remapped to the copied tables; relationships whose tables are not
+/// part of the template are dropped.
+pub fn clone_model_contents(template: &DataModel) -> (Vec<Table>, Vec<Relationship>) {
+    let now = chrono::Utc::now();
+    let mut id_map = HashMap::new();
+    let tables: Vec<Table> = template
+        .tables
+        .iter()
+        .map(|table| {
+            let mut copy = table.clone();
+            copy.id = Uuid::new_v4();
+            copy.created_at = now;
+            copy.updated_at = now;
+            id_map.insert(table.id, copy.id);
+            copy
+        })
+        .collect();
+    let relationships = template
+        .relationships
+        .iter()
+        .filter_map(|relationship| {
+            let mut copy = relationship.clone();
+            copy.id = Uuid::new_v4();
+            copy.source_table_id = *id_map.get(&relationship.source_table_id)?;
+            copy.target_table_id = *id_map.get(&relationship.target_table_id)?;
+            copy.drawio_edge_id = None;
+            copy.created_at = now;
+            copy.updated_at = now;
+            Some(copy)
+        })
+        .collect();
+    (tables, relationships)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clone_model_contents_remaps_relationships() {
+        let customers = Table::new("customers".to_string(), Vec::new());
+        let orders = Table::new("orders".to_string(), Vec::new());
+        let mut template = DataModel::new("template".to_string(), String::new(), String::new());
+        template.relationships = vec![
+            Relationship::new(orders.id, customers.id),
+            // Dangling: target table isn't in the template
+            Relationship::new(orders.id, Uuid::new_v4()),
+        ];
+        template.tables = vec![customers.clone(), orders.clone()];
+
+        let (tables, relationships) = clone_model_contents(&template);
+        assert_eq!(tables.len(), 2);
+        assert!(
+            tables
+                .iter()
+                .all(|t| t.id != customers.id && t.id != orders.id)
+        );
+        assert_eq!(relationships.len(), 1);
+        assert_eq!(relationships[0].source_table_id, tables[1].id);
+        assert_eq!(relationships[0].target_table_id, tables[0].id);
+    }
+
+    #[test]
+    fn test_domain_metadata_validation() {
+        let mut metadata = DomainMetadata {
+            description: Some("Sales pipeline".to_string()),
+            color: Some("#1f77b4".to_string()),
+            icon: Some("chart".to_string()),
+            owners: vec!["lead@example.com".to_string()],
+        };
+        assert!(metadata.validate().is_ok());
+        let model = metadata.apply_to_model(&DataModel::new(
+            "sales".to_string(),
+            String::new(),
+            String::new(),
+        ));
+        assert_eq!(
+            model.description.as_deref(),
+            Some("Sales pipeline\n\nOwners: lead@example.com")
+        );
+
+        metadata.color = Some("blue".to_string());
+        assert!(metadata.validate().is_err());
+    }
+}
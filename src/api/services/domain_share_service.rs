@@ -0,0 +1,273 @@
+//! Read-only domain share links.
+//!
+//! A share lets external partners browse a domain's canvas and tables without an account.
+//! Links carry a signed token (see `JwtService::sign_domain_share`); the share record kept
+//! here decides whether the link still works, so deleting it revokes the link. Records are
+//! stored per domain in `shares.yaml` and may set an expiry and a password (stored as a
+//! salted PBKDF2-HMAC-SHA256 hash). Wrong passwords are throttled per link and per client.
+//!
+//! Administrators can turn sharing off for the whole server; the setting is stored in
+//! `sharing.yaml` at the root of the workspace data directory and defaults to
+//! `PUBLIC_SHARING_ENABLED` (enabled unless set to `false`).
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use subtle::ConstantTimeEq;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// File name of a domain's share records
+pub const SHARES_FILE: &str = "shares.yaml";
+/// File name of the server-wide sharing setting
+pub const SHARING_SETTINGS_FILE: &str = "sharing.yaml";
+
+const PASSWORD_ITERATIONS: u32 = 100_000;
+
+/// Wrong passwords a link accepts per window, from all clients
+const SHARE_FAILURE_LIMIT: u32 = 20;
+/// Wrong passwords a client may send per window, to any link
+const CLIENT_FAILURE_LIMIT: u32 = 10;
+const FAILURE_WINDOW: Duration = Duration::from_secs(15 * 60);
+/// Keys tracked before expired windows are pruned
+const MAX_TRACKED_KEYS: usize = 10_000;
+
+/// A share link of a domain
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DomainShare {
+    pub id: Uuid,
+    /// Who the link was made for (e.g. a partner name)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<DateTime<Utc>>,
+    /// `salt$hash` of the link password, if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password_hash: Option<String>,
+}
+
+impl DomainShare {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Whether `password` opens the link (always true for links without a password).
+    pub fn check_password(&self, password: Option<&str>) -> bool {
+        match (&self.password_hash, password) {
+            (None, _) => true,
+            (Some(stored), Some(password)) => verify_password(password, stored),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Share records of a domain (`shares.yaml`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DomainShares {
+    #[serde(default)]
+    pub shares: Vec<DomainShare>,
+}
+
+impl DomainShares {
+    fn path(domain_dir: &Path) -> PathBuf {
+        domain_dir.join(SHARES_FILE)
+    }
+
+    /// Load a domain's shares (none if the file is missing).
+    pub fn load(domain_dir: &Path) -> Self {
+        std::fs::read_to_string(Self::path(domain_dir))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, domain_dir: &Path) -> Result<()> {
+        let path = Self::path(domain_dir);
+        std::fs::create_dir_all(domain_dir)
+            .with_context(|| format!("Failed to create {}", domain_dir.display()))?;
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&DomainShare> {
+        self.shares.iter().find(|s| s.id == id)
+    }
+
+    pub fn remove(&mut self, id: Uuid) -> bool {
+        let before = self.shares.len();
+        self.shares.retain(|s| s.id != id);
+        self.shares.len() != before
+    }
+}
+
+/// Server-wide sharing setting (`sharing.yaml`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct SharingSettings {
+    /// Whether share links (domain and diagram image links) work
+    pub enabled: bool,
+}
+
+impl Default for SharingSettings {
+    fn default() -> Self {
+        Self {
+            enabled: std::env::var("PUBLIC_SHARING_ENABLED")
+                .map(|v| !v.eq_ignore_ascii_case("false"))
+                .unwrap_or(true),
+        }
+    }
+}
+
+impl SharingSettings {
+    pub fn load(workspace_data_dir: &Path) -> Self {
+        std::fs::read_to_string(workspace_data_dir.join(SHARING_SETTINGS_FILE))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, workspace_data_dir: &Path) -> Result<()> {
+        let path = workspace_data_dir.join(SHARING_SETTINGS_FILE);
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+/// Failed attempts per key in fixed windows.
+pub struct AttemptThrottle<K> {
+    limit: u32,
+    window: Duration,
+    failures: Mutex<HashMap<K, (u32, Instant)>>,
+}
+
+impl<K: Hash + Eq> AttemptThrottle<K> {
+    pub fn new(limit: u32, window: Duration) -> Self {
+        Self {
+            limit,
+            window,
+            failures: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Time until `key` may try again, if it used up its failures in the current window.
+    pub fn locked_for(&self, key: &K, now: Instant) -> Option<Duration> {
+        let failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        let (count, started) = failures.get(key)?;
+        let elapsed = now.saturating_duration_since(*started);
+        (*count >= self.limit && elapsed < self.window).then(|| self.window - elapsed)
+    }
+
+    pub fn record_failure(&self, key: K, now: Instant) {
+        let mut failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        if failures.len() >= MAX_TRACKED_KEYS {
+            failures
+                .retain(|_, (_, started)| now.saturating_duration_since(*started) < self.window);
+        }
+        let entry = failures.entry(key).or_insert((0, now));
+        if now.saturating_duration_since(entry.1) >= self.window {
+            *entry = (0, now);
+        }
+        entry.0 += 1;
+    }
+}
+
+/// Wrong password attempts per share link.
+pub fn share_password_failures() -> &'static AttemptThrottle<Uuid> {
+    static FAILURES: OnceLock<AttemptThrottle<Uuid>> = OnceLock::new();
+    FAILURES.get_or_init(|| AttemptThrottle::new(SHARE_FAILURE_LIMIT, FAILURE_WINDOW))
+}
+
+/// Wrong password attempts per client address.
+pub fn client_password_failures() -> &'static AttemptThrottle<IpAddr> {
+    static FAILURES: OnceLock<AttemptThrottle<IpAddr>> = OnceLock::new();
+    FAILURES.get_or_init(|| AttemptThrottle::new(CLIENT_FAILURE_LIMIT, FAILURE_WINDOW))
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// PBKDF2-HMAC-SHA256 with a 32-byte output
+fn pbkdf2(password: &str, salt: &str) -> [u8; 32] {
+    pbkdf2::pbkdf2_hmac_array::<Sha256, 32>(
+        password.as_bytes(),
+        salt.as_bytes(),
+        PASSWORD_ITERATIONS,
+    )
+}
+
+/// Hash a link password as `salt$hash`.
+pub fn hash_password(password: &str) -> String {
+    let salt = Uuid::new_v4().simple().to_string();
+    format!("{}${}", salt, to_hex(&pbkdf2(password, &salt)))
+}
+
+fn verify_password(password: &str, stored: &str) -> bool {
+    let Some((salt, hash)) = stored.split_once('$') else {
+        return false;
+    };
+    let computed = to_hex(&pbkdf2(password, salt));
+    computed.as_bytes().ct_eq(hash.as_bytes()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_share_password_and_expiry() {
+        let now = Utc::now();
+        let mut share = DomainShare {
+            id: Uuid::new_v4(),
+            label: None,
+            created_by: "owner@example.com".to_string(),
+            created_at: now,
+            expires_at: Some(now + chrono::Duration::days(1)),
+            password_hash: None,
+        };
+        assert!(share.check_password(None));
+        assert!(!share.is_expired(now));
+        assert!(share.is_expired(now + chrono::Duration::days(2)));
+
+        share.password_hash = Some(hash_password("s3cret"));
+        assert!(share.check_password(Some("s3cret")));
+        assert!(!share.check_password(Some("S3cret")));
+        assert!(!share.check_password(None));
+
+        // Known-answer vector: 100,000 rounds of PBKDF2-HMAC-SHA256 over "s3cret"
+        share.password_hash = Some(
+            "0123456789abcdef0123456789abcdef$aef3ec7fd91064696d492b06c8af026a14df95c32d3edb6e85745ffa87755330"
+                .to_string(),
+        );
+        assert!(share.check_password(Some("s3cret")));
+        assert!(!share.check_password(Some("s3cret!")));
+    }
+
+    #[test]
+    fn test_attempt_throttle() {
+        let throttle = AttemptThrottle::new(2, Duration::from_secs(60));
+        let start = Instant::now();
+        throttle.record_failure("a", start);
+        assert_eq!(throttle.locked_for(&"a", start), None);
+        throttle.record_failure("a", start);
+        assert_eq!(
+            throttle.locked_for(&"a", start + Duration::from_secs(20)),
+            Some(Duration::from_secs(40))
+        );
+        assert_eq!(throttle.locked_for(&"b", start), None);
+
+        // A new window starts once the old one has passed
+        let later = start + Duration::from_secs(60);
+        assert_eq!(throttle.locked_for(&"a", later), None);
+        throttle.record_failure("a", later);
+        assert_eq!(throttle.locked_for(&"a", later), None);
+    }
+}
@@ -0,0 +1,543 @@
+//! Session-scoped draft mode.
+//!
+//! While a session has a draft open on a domain, the domain's table and relationship
+//! endpoints work on an in-memory copy of the model instead of the domain files (or the
+//! database). The copy has no directory, so the model service never writes it. Saving
+//! compares the copy with the snapshot taken when the draft was opened and applies only the
+//! difference; discarding drops the copy. Drafts are kept in memory and do not survive a
+//! restart.
+//!
+//! A draft can autosave on an interval, either everything or only layout changes (tables
+//! that were just moved on the canvas). Saved changes are folded into the snapshot, so the
+//! remaining difference is always what has not been written yet.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::{DataModel, Relationship, Table};
+use crate::services::model_service::ModelService;
+
+/// Environment variable with the default autosave interval of new drafts, in seconds
+pub const DRAFT_AUTOSAVE_SECS_ENV: &str = "DRAFT_AUTOSAVE_SECS";
+
+/// Which changes autosave writes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AutosaveScope {
+    /// Every change in the draft
+    #[default]
+    All,
+    /// Only tables whose canvas position changed and nothing else
+    Layout,
+}
+
+/// Autosave configuration of a draft
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct AutosaveSettings {
+    /// Seconds between autosaves; 0 turns autosave off
+    pub interval_secs: u64,
+    pub scope: AutosaveScope,
+}
+
+impl AutosaveSettings {
+    /// Default settings from `DRAFT_AUTOSAVE_SECS` (unset or 0: autosave off)
+    pub fn from_env() -> Self {
+        Self::from_lookup(|k| std::env::var(k).ok())
+    }
+
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        Self {
+            interval_secs: lookup(DRAFT_AUTOSAVE_SECS_ENV)
+                .and_then(|v| v.trim().parse().ok())
+                .unwrap_or(0),
+            scope: AutosaveScope::All,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.interval_secs > 0
+    }
+}
+
+/// Save bookkeeping of a draft
+#[derive(Debug, Default)]
+struct SaveState {
+    autosave: AutosaveSettings,
+    autosave_running: bool,
+    last_saved_at: Option<DateTime<Utc>>,
+    last_autosave_at: Option<DateTime<Utc>>,
+    autosave_error: Option<String>,
+}
+
+/// An open draft: the saved model as of the last save, and the working copy.
+pub struct Draft {
+    base: Mutex<DataModel>,
+    pub working: Arc<tokio::sync::Mutex<ModelService>>,
+    pub opened_at: DateTime<Utc>,
+    saves: Mutex<SaveState>,
+    /// Held while changes are written, so an autosave and a save never write the same change
+    saving: tokio::sync::Mutex<()>,
+}
+
+impl Draft {
+    fn new(base: DataModel, autosave: AutosaveSettings) -> Self {
+        let mut copy = base.clone();
+        copy.git_directory_path.clear();
+        let mut working = ModelService::new();
+        working.set_current_model(copy);
+        Self {
+            base: Mutex::new(base),
+            working: Arc::new(tokio::sync::Mutex::new(working)),
+            opened_at: Utc::now(),
+            saves: Mutex::new(SaveState {
+                autosave,
+                ..Default::default()
+            }),
+            saving: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    /// Wait for any save in progress; changes must be read and written under this guard.
+    pub async fn lock_saves(&self) -> tokio::sync::MutexGuard<'_, ()> {
+        self.saving.lock().await
+    }
+
+    fn saves(&self) -> std::sync::MutexGuard<'_, SaveState> {
+        self.saves.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Changes made in the draft and not saved yet.
+    pub async fn changes(&self) -> DraftChanges {
+        let working = self.working.lock().await;
+        let base = self.base.lock().unwrap_or_else(|e| e.into_inner());
+        match working.get_current_model() {
+            Some(model) => DraftChanges::between(&base, model),
+            None => DraftChanges::default(),
+        }
+    }
+
+    /// Fold `changes`, which were just written, into the snapshot.
+    pub fn mark_saved(&self, changes: &DraftChanges, autosave: bool) {
+        {
+            let mut base = self.base.lock().unwrap_or_else(|e| e.into_inner());
+            let deleted: HashSet<Uuid> = changes.deleted_tables.iter().map(|t| t.id).collect();
+            base.tables.retain(|t| !deleted.contains(&t.id));
+            let saved = (changes.created_tables.iter())
+                .chain(changes.updated_tables.iter().map(|(_, t)| t));
+            upsert(&mut base.tables, saved, |t| t.id);
+
+            let deleted: HashSet<Uuid> =
+                changes.deleted_relationships.iter().map(|r| r.id).collect();
+            base.relationships.retain(|r| !deleted.contains(&r.id));
+            let saved = (changes.created_relationships.iter())
+                .chain(changes.updated_relationships.iter().map(|(_, r)| r));
+            upsert(&mut base.relationships, saved, |r| r.id);
+        }
+        let mut saves = self.saves();
+        let now = Utc::now();
+        saves.last_saved_at = Some(now);
+        if autosave {
+            saves.last_autosave_at = Some(now);
+            saves.autosave_error = None;
+        }
+    }
+
+    pub fn autosave(&self) -> AutosaveSettings {
+        self.saves().autosave
+    }
+
+    pub fn set_autosave(&self, settings: AutosaveSettings) {
+        self.saves().autosave = settings;
+    }
+
+    /// Claim the draft's autosave task; false if one is already running or autosave is off.
+    pub fn start_autosave(&self) -> bool {
+        let mut saves = self.saves();
+        if saves.autosave_running || !saves.autosave.enabled() {
+            return false;
+        }
+        saves.autosave_running = true;
+        true
+    }
+
+    /// Settings for the autosave task's next round, or None (releasing the task's claim)
+    /// once autosave was turned off.
+    pub fn next_autosave(&self) -> Option<AutosaveSettings> {
+        let mut saves = self.saves();
+        if saves.autosave.enabled() {
+            return Some(saves.autosave);
+        }
+        saves.autosave_running = false;
+        None
+    }
+
+    pub fn record_autosave_error(&self, error: String) {
+        self.saves().autosave_error = Some(error);
+    }
+
+    pub fn last_saved_at(&self) -> Option<DateTime<Utc>> {
+        self.saves().last_saved_at
+    }
+
+    pub fn last_autosave_at(&self) -> Option<DateTime<Utc>> {
+        self.saves().last_autosave_at
+    }
+
+    pub fn autosave_error(&self) -> Option<String> {
+        self.saves().autosave_error.clone()
+    }
+}
+
+/// Replace the items of `items` with the same id as an item of `saved`, appending new ones
+fn upsert<'a, T: Clone + 'a>(
+    items: &mut Vec<T>,
+    saved: impl Iterator<Item = &'a T>,
+    id: impl Fn(&T) -> Uuid,
+) {
+    for item in saved {
+        match items.iter_mut().find(|existing| id(existing) == id(item)) {
+            Some(existing) => *existing = item.clone(),
+            None => items.push(item.clone()),
+        }
+    }
+}
+
+/// Open drafts by session and domain
+#[derive(Default)]
+pub struct DraftStore {
+    drafts: Mutex<HashMap<String, Arc<Draft>>>,
+}
+
+impl DraftStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draft key of a session's domain
+    pub fn key(session_id: &str, email: &str, domain: &str) -> String {
+        format!("{}/{}/{}", session_id, email, domain)
+    }
+
+    pub fn get(&self, key: &str) -> Option<Arc<Draft>> {
+        let drafts = self.drafts.lock().unwrap_or_else(|e| e.into_inner());
+        drafts.get(key).cloned()
+    }
+
+    /// Open a draft of `base`, or return the draft that is already open.
+    pub fn open(&self, key: String, base: DataModel, autosave: AutosaveSettings) -> Arc<Draft> {
+        let mut drafts = self.drafts.lock().unwrap_or_else(|e| e.into_inner());
+        drafts
+            .entry(key)
+            .or_insert_with(|| Arc::new(Draft::new(base, autosave)))
+            .clone()
+    }
+
+    /// Whether `draft` is still the open draft under `key`
+    pub fn is_open(&self, key: &str, draft: &Arc<Draft>) -> bool {
+        self.get(key).is_some_and(|open| Arc::ptr_eq(&open, draft))
+    }
+
+    pub fn remove(&self, key: &str) -> Option<Arc<Draft>> {
+        let mut drafts = self.drafts.lock().unwrap_or_else(|e| e.into_inner());
+        drafts.remove(key)
+    }
+}
+
+/// Difference between a draft's snapshot and its working copy
+#[derive(Debug, Default)]
+pub struct DraftChanges {
+    pub created_tables: Vec<Table>,
+    /// Tables as they were in the snapshot and as they are in the draft
+    pub updated_tables: Vec<(Table, Table)>,
+    pub deleted_tables: Vec<Table>,
+    pub created_relationships: Vec<Relationship>,
+    pub updated_relationships: Vec<(Relationship, Relationship)>,
+    pub deleted_relationships: Vec<Relationship>,
+}
+
+/// Kind of change of an unsaved table or relationship
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DirtyChange {
+    Created,
+    Updated,
+    Deleted,
+}
+
+/// A table or relationship with unsaved changes
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DirtyObject {
+    /// "table" or "relationship"
+    pub kind: &'static str,
+    pub id: Uuid,
+    /// Table name (relationships have none)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub change: DirtyChange,
+    /// When the saved version was last written; None for objects that were never saved
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persisted_at: Option<DateTime<Utc>>,
+}
+
+/// Counts of the changes in a draft
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, ToSchema)]
+pub struct DraftSummary {
+    pub tables_created: usize,
+    pub tables_updated: usize,
+    pub tables_deleted: usize,
+    pub relationships_created: usize,
+    pub relationships_updated: usize,
+    pub relationships_deleted: usize,
+}
+
+/// Fields of an entity without its timestamps, which are not preserved by every backend
+fn content<T: Serialize>(item: &T) -> Option<serde_json::Value> {
+    let mut value = serde_json::to_value(item).ok()?;
+    if let Some(fields) = value.as_object_mut() {
+        fields.remove("created_at");
+        fields.remove("updated_at");
+    }
+    Some(value)
+}
+
+/// Entities present in `before` or `after`, split into created, updated and deleted
+fn diff<T: Clone + Serialize>(
+    before: &[T],
+    after: &[T],
+    id: impl Fn(&T) -> Uuid,
+) -> (Vec<T>, Vec<(T, T)>, Vec<T>) {
+    let old: HashMap<Uuid, &T> = before.iter().map(|item| (id(item), item)).collect();
+    let new_ids: HashSet<Uuid> = after.iter().map(&id).collect();
+    let mut created = Vec::new();
+    let mut updated = Vec::new();
+    for item in after {
+        match old.get(&id(item)) {
+            None => created.push(item.clone()),
+            Some(previous) if content(*previous) != content(item) => {
+                updated.push(((*previous).clone(), item.clone()))
+            }
+            Some(_) => {}
+        }
+    }
+    let deleted = before
+        .iter()
+        .filter(|item| !new_ids.contains(&id(item)))
+        .cloned()
+        .collect();
+    (created, updated, deleted)
+}
+
+/// Ids of `changed` entities whose current version differs from the snapshot
+fn modified_since<'a, T: Serialize + 'a>(
+    changed: impl Iterator<Item = &'a T>,
+    current: &[T],
+    id: impl Fn(&T) -> Uuid,
+) -> Vec<Uuid> {
+    let current: HashMap<Uuid, &T> = current.iter().map(|item| (id(item), item)).collect();
+    changed
+        .filter(|before| {
+            current
+                .get(&id(before))
+                .is_some_and(|now| content(*now) != content(*before))
+        })
+        .map(&id)
+        .collect()
+}
+
+impl DraftChanges {
+    pub fn between(base: &DataModel, working: &DataModel) -> Self {
+        let (created_tables, updated_tables, deleted_tables) =
+            diff(&base.tables, &working.tables, |t| t.id);
+        let (created_relationships, updated_relationships, deleted_relationships) =
+            diff(&base.relationships, &working.relationships, |r| r.id);
+        Self {
+            created_tables,
+            updated_tables,
+            deleted_tables,
+            created_relationships,
+            updated_relationships,
+            deleted_relationships,
+        }
+    }
+
+    pub fn summary(&self) -> DraftSummary {
+        DraftSummary {
+            tables_created: self.created_tables.len(),
+            tables_updated: self.updated_tables.len(),
+            tables_deleted: self.deleted_tables.len(),
+            relationships_created: self.created_relationships.len(),
+            relationships_updated: self.updated_relationships.len(),
+            relationships_deleted: self.deleted_relationships.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.summary() == DraftSummary::default()
+    }
+
+    /// Only the tables that were moved on the canvas and changed in no other way
+    pub fn layout_only(&self) -> Self {
+        let without_position = |t: &Table| {
+            let mut t = t.clone();
+            t.position = None;
+            content(&t)
+        };
+        Self {
+            updated_tables: (self.updated_tables.iter())
+                .filter(|(before, after)| without_position(before) == without_position(after))
+                .cloned()
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    /// The changed tables and relationships, with when their saved versions were written
+    pub fn dirty_objects(&self) -> Vec<DirtyObject> {
+        let table = |t: &Table, change, persisted: bool| DirtyObject {
+            kind: "table",
+            id: t.id,
+            name: Some(t.name.clone()),
+            change,
+            persisted_at: persisted.then_some(t.updated_at),
+        };
+        let relationship = |r: &Relationship, change, persisted: bool| DirtyObject {
+            kind: "relationship",
+            id: r.id,
+            name: None,
+            change,
+            persisted_at: persisted.then_some(r.updated_at),
+        };
+        let mut dirty: Vec<DirtyObject> = Vec::new();
+        dirty.extend((self.created_tables.iter()).map(|t| table(t, DirtyChange::Created, false)));
+        dirty.extend(
+            (self.updated_tables.iter()).map(|(t, _)| table(t, DirtyChange::Updated, true)),
+        );
+        dirty.extend((self.deleted_tables.iter()).map(|t| table(t, DirtyChange::Deleted, true)));
+        dirty.extend(
+            (self.created_relationships.iter())
+                .map(|r| relationship(r, DirtyChange::Created, false)),
+        );
+        dirty.extend(
+            (self.updated_relationships.iter())
+                .map(|(r, _)| relationship(r, DirtyChange::Updated, true)),
+        );
+        dirty.extend(
+            (self.deleted_relationships.iter())
+                .map(|r| relationship(r, DirtyChange::Deleted, true)),
+        );
+        dirty
+    }
+
+    /// Ids of the changed tables and relationships that someone else modified since the
+    /// draft was opened.
+    pub fn conflicts(&self, tables: &[Table], relationships: &[Relationship]) -> Vec<Uuid> {
+        let changed_tables = self
+            .updated_tables
+            .iter()
+            .map(|(before, _)| before)
+            .chain(&self.deleted_tables);
+        let changed_relationships = self
+            .updated_relationships
+            .iter()
+            .map(|(before, _)| before)
+            .chain(&self.deleted_relationships);
+        let mut conflicts = modified_since(changed_tables, tables, |t| t.id);
+        conflicts.extend(modified_since(changed_relationships, relationships, |r| {
+            r.id
+        }));
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Column, Position};
+
+    fn table(name: &str) -> Table {
+        Table::new(
+            name.to_string(),
+            vec![Column::new("id".to_string(), "bigint".to_string())],
+        )
+    }
+
+    #[tokio::test]
+    async fn test_draft_changes_and_conflicts() {
+        let model = || {
+            DataModel::new(
+                "sales".to_string(),
+                "/tmp/sales".to_string(),
+                "/tmp/sales/tables.yaml".to_string(),
+            )
+        };
+        let mut base = model();
+        let (orders, customers, returns) = (table("orders"), table("customers"), table("returns"));
+        base.tables = vec![orders.clone(), customers.clone(), returns.clone()];
+
+        let store = DraftStore::new();
+        let key = DraftStore::key("session", "a@example.com", "sales");
+        let draft = store.open(key.clone(), base, AutosaveSettings::default());
+        assert!(store.is_open(&key, &draft));
+        assert!(Arc::ptr_eq(
+            &draft,
+            &store.open(key.clone(), model(), AutosaveSettings::default())
+        ));
+        {
+            let mut working = draft.working.lock().await;
+            let model = working.get_current_model_mut().unwrap();
+            assert!(model.git_directory_path.is_empty());
+            model.tables[0].position = Some(Position { x: 5.0, y: 5.0 });
+            model.tables.retain(|t| t.id != customers.id);
+            model.tables.push(table("invoices"));
+        }
+
+        let changes = draft.changes().await;
+        assert_eq!(
+            changes.summary(),
+            DraftSummary {
+                tables_created: 1,
+                tables_updated: 1,
+                tables_deleted: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(changes.updated_tables[0].1.name, "orders");
+
+        // Only tables the draft changed can conflict
+        let mut current = vec![orders, customers.clone(), returns];
+        assert!(changes.conflicts(&current, &[]).is_empty());
+        current[0].updated_at += chrono::Duration::seconds(1);
+        current[1].tags.push("pii".to_string());
+        current[2].tags.push("pii".to_string());
+        assert_eq!(changes.conflicts(&current, &[]), vec![customers.id]);
+
+        // Saving only the layout leaves the other changes pending
+        let layout = changes.layout_only();
+        assert_eq!(layout.summary().tables_updated, 1);
+        assert_eq!(layout.dirty_objects()[0].change, DirtyChange::Updated);
+        draft.mark_saved(&layout, true);
+        assert!(draft.last_autosave_at().is_some());
+        let remaining = draft.changes().await.summary();
+        assert_eq!((remaining.tables_created, remaining.tables_updated), (1, 0));
+        assert_eq!(remaining.tables_deleted, 1);
+        draft.mark_saved(&draft.changes().await, false);
+        assert!(draft.changes().await.is_empty());
+
+        assert!(store.remove(&key).is_some());
+        assert!(store.get(&key).is_none());
+        assert!(!store.is_open(&key, &draft));
+    }
+
+    #[test]
+    fn test_autosave_settings_from_env() {
+        let settings = AutosaveSettings::from_lookup(|_| Some("30".to_string()));
+        assert!(settings.enabled());
+        assert_eq!(settings.interval_secs, 30);
+        assert!(!AutosaveSettings::from_lookup(|_| None).enabled());
+    }
+}
@@ -4,6 +4,8 @@ use crate::drawio::document::DrawIODocument;
 use crate::drawio::models::{DrawIOCell, DrawIOEdge, DrawIOPoint, DrawIOPoints};
 use crate::models::{DataModel, Relationship};
 use crate::models::{Position, Table};
+use crate::services::inheritance_service::hierarchies;
+use crate::services::save_coordinator_service::save_coordinator;
 use anyhow::{Context, Result};
 use std::collections::HashMap;
 use std::fs;
@@ -255,21 +257,13 @@ impl DrawIOService {
                     .map(|(x, y)| ConnectionPoint { x: *x, y: *y })
                     .collect();
 
-                relationship.visual_metadata = Some(VisualMetadata {
-                    source_connection_point: relationship
-                        .visual_metadata
-                        .as_ref()
-                        .and_then(|v| v.source_connection_point.clone()),
-                    target_connection_point: relationship
-                        .visual_metadata
-                        .as_ref()
-                        .and_then(|v| v.target_connection_point.clone()),
-                    routing_waypoints,
-                    label_position: relationship
-                        .visual_metadata
-                        .as_ref()
-                        .and_then(|v| v.label_position.clone()),
-                });
+                // Keep connection points, label position and anchors
+                let mut visual = relationship
+                    .visual_metadata
+                    .take()
+                    .unwrap_or_else(VisualMetadata::default);
+                visual.routing_waypoints = routing_waypoints;
+                relationship.visual_metadata = Some(visual);
             }
         }
 
@@ -482,6 +476,7 @@ impl DrawIOService {
 
     /// Save DrawIO document to file.
     fn save_document(&self, document: &DrawIODocument) -> Result<()> {
+        let _save = save_coordinator().begin();
         // Ensure parent directory exists
         if let Some(parent) = self.diagram_file_path.parent() {
             fs::create_dir_all(parent)
@@ -602,6 +597,16 @@ impl DrawIOService {
             builder.add_relationship(relationship, waypoints);
         }
 
+        // Supertype/subtype hierarchies are drawn as generalizations
+        for (supertype, hierarchy) in hierarchies(&model.tables) {
+            for subtype in &hierarchy.subtypes {
+                if model.get_table_by_id(subtype.table_id).is_none() {
+                    continue;
+                }
+                builder.add_subtype(supertype.id, subtype.table_id, &subtype.discriminator_value);
+            }
+        }
+
         // Build document and generate XML
         let document = builder.build();
         let xml = document
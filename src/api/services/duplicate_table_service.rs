@@ -0,0 +1,239 @@
+//! Near-duplicate table detection.
+//!
+//! Workspaces accumulate copies of the same entity (a `customer` table in every domain).
+//! Tables are compared pairwise on name similarity (normalized Levenshtein over names with
+//! layer and kind prefixes such as `stg_` or `dim_` removed) and on the Jaccard similarity
+//! of their column name sets. Pairs scoring above a threshold are reported with a
+//! suggestion: consolidate copies within a domain, or reference the most complete copy
+//! from other domains instead of keeping their own.
+
+use serde::Serialize;
+use std::collections::BTreeSet;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::Table;
+
+/// Default score at which two tables are reported as near-duplicates
+pub const DEFAULT_THRESHOLD: f64 = 0.7;
+/// Weight of name similarity in the score; column similarity makes up the rest
+const NAME_WEIGHT: f64 = 0.4;
+
+/// Name prefixes and suffixes that mark a layer or table kind rather than the entity
+const AFFIXES: &[&str] = &[
+    "raw", "stg", "staging", "bronze", "silver", "gold", "dim", "fact", "fct", "tbl", "src",
+    "hist", "snapshot", "v",
+];
+
+/// A table of the workspace
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TableRef {
+    pub domain: String,
+    pub table_id: Uuid,
+    pub table_name: String,
+    pub columns: usize,
+}
+
+/// Recommended way to remove a duplicate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateSuggestion {
+    /// Both tables are in one domain: merge them
+    Consolidate,
+    /// The tables are in different domains: reference the canonical one across domains
+    CrossDomainReference,
+}
+
+/// Two tables that look like copies of each other
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct DuplicateTablePair {
+    /// Weighted score, 0-1
+    pub score: f64,
+    pub name_similarity: f64,
+    /// Jaccard similarity of the column name sets
+    pub column_similarity: f64,
+    /// The more complete table, suggested as the one to keep
+    pub canonical: TableRef,
+    pub duplicate: TableRef,
+    pub shared_columns: Vec<String>,
+    pub suggestion: DuplicateSuggestion,
+    pub message: String,
+}
+
+/// Entity part of a table name: lowercase, alphanumeric words, without layer/kind affixes
+/// and a plural `s`.
+fn normalize_name(name: &str) -> String {
+    let lower = name.to_lowercase();
+    let words: Vec<&str> = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect();
+    let core: Vec<&str> = match words.iter().position(|w| !AFFIXES.contains(w)) {
+        Some(start) => {
+            let end = words
+                .iter()
+                .rposition(|w| !AFFIXES.contains(w))
+                .unwrap_or(start);
+            words[start..=end].to_vec()
+        }
+        None => words,
+    };
+    let joined = core.join("_");
+    match joined.strip_suffix('s') {
+        Some(singular) if singular.len() > 2 && !singular.ends_with('s') => singular.to_string(),
+        _ => joined,
+    }
+}
+
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Similarity of two table names, 0-1.
+pub fn name_similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (normalize_name(a), normalize_name(b));
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 0.0;
+    }
+    1.0 - levenshtein(&a, &b) as f64 / longest as f64
+}
+
+fn column_names(table: &Table) -> BTreeSet<String> {
+    table
+        .columns
+        .iter()
+        .map(|c| c.name.to_lowercase())
+        .collect()
+}
+
+fn round(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+/// Scored near-duplicate pairs among `tables` (domain, table), best first.
+pub fn find_duplicates(tables: &[(String, Table)], threshold: f64) -> Vec<DuplicateTablePair> {
+    let columns: Vec<BTreeSet<String>> = tables.iter().map(|(_, t)| column_names(t)).collect();
+    let table_ref = |(domain, table): &(String, Table)| TableRef {
+        domain: domain.clone(),
+        table_id: table.id,
+        table_name: table.name.clone(),
+        columns: table.columns.len(),
+    };
+
+    let mut pairs = Vec::new();
+    for i in 0..tables.len() {
+        for j in (i + 1)..tables.len() {
+            if tables[i].1.id == tables[j].1.id {
+                continue;
+            }
+            let shared: Vec<String> = columns[i].intersection(&columns[j]).cloned().collect();
+            let union = columns[i].union(&columns[j]).count();
+            let column_similarity = if union == 0 {
+                0.0
+            } else {
+                shared.len() as f64 / union as f64
+            };
+            let name_similarity = name_similarity(&tables[i].1.name, &tables[j].1.name);
+            let score = NAME_WEIGHT * name_similarity + (1.0 - NAME_WEIGHT) * column_similarity;
+            if score < threshold {
+                continue;
+            }
+
+            // Keep the table with more columns; ties go to the older one
+            let (canonical, duplicate) = {
+                let (a, b) = (&tables[i], &tables[j]);
+                if (b.1.columns.len(), a.1.created_at) > (a.1.columns.len(), b.1.created_at) {
+                    (b, a)
+                } else {
+                    (a, b)
+                }
+            };
+            let (suggestion, message) = if canonical.0 == duplicate.0 {
+                (
+                    DuplicateSuggestion::Consolidate,
+                    format!(
+                        "Consolidate '{}' into '{}' in domain '{}'",
+                        duplicate.1.name, canonical.1.name, canonical.0
+                    ),
+                )
+            } else {
+                (
+                    DuplicateSuggestion::CrossDomainReference,
+                    format!(
+                        "Reference '{}.{}' from domain '{}' instead of keeping '{}'",
+                        canonical.0, canonical.1.name, duplicate.0, duplicate.1.name
+                    ),
+                )
+            };
+            pairs.push(DuplicateTablePair {
+                score: round(score),
+                name_similarity: round(name_similarity),
+                column_similarity: round(column_similarity),
+                canonical: table_ref(canonical),
+                duplicate: table_ref(duplicate),
+                shared_columns: shared,
+                suggestion,
+                message,
+            });
+        }
+    }
+    pairs.sort_by(|a, b| b.score.total_cmp(&a.score));
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Column;
+
+    fn table(name: &str, columns: &[&str]) -> Table {
+        Table::new(
+            name.to_string(),
+            columns
+                .iter()
+                .map(|c| Column::new(c.to_string(), "STRING".to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_finds_customer_copies_across_domains() {
+        assert_eq!(normalize_name("dim_Customers"), "customer");
+        assert_eq!(normalize_name("stg_address"), "address");
+
+        let tables = vec![
+            (
+                "sales".to_string(),
+                table("customer", &["id", "name", "email", "phone"]),
+            ),
+            (
+                "marketing".to_string(),
+                table("dim_customers", &["id", "name", "email"]),
+            ),
+            (
+                "sales".to_string(),
+                table("orders", &["id", "customer_id", "total"]),
+            ),
+        ];
+        let pairs = find_duplicates(&tables, DEFAULT_THRESHOLD);
+
+        assert_eq!(pairs.len(), 1);
+        let pair = &pairs[0];
+        assert_eq!(pair.name_similarity, 1.0);
+        assert_eq!(pair.column_similarity, 0.75);
+        assert_eq!(pair.canonical.domain, "sales");
+        assert_eq!(pair.suggestion, DuplicateSuggestion::CrossDomainReference);
+        assert_eq!(pair.shared_columns, vec!["email", "id", "name"]);
+    }
+}
@@ -0,0 +1,159 @@
+//! Workspace-wide enterprise model.
+//!
+//! Merges every domain of a workspace into one model for enterprise architecture reviews.
+//! Table names are prefixed with their domain (`sales.orders`) and tagged `domain:<name>`.
+//! Relationships are matched to tables by id across all domains, so relationships to
+//! tables imported from another domain connect to the owning domain's table. Each domain
+//! is laid out to the right of the previous one so diagrams do not overlap.
+
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::{DataModel, Position};
+
+/// Horizontal space between two domains' tables in the merged layout
+const DOMAIN_GAP: f64 = 400.0;
+
+/// Horizontal space given to each table without a stored position
+const TABLE_SPACING: f64 = 300.0;
+
+/// What went into an enterprise model
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, ToSchema)]
+pub struct EnterpriseModelSummary {
+    pub domains: Vec<String>,
+    pub tables: usize,
+    pub relationships: usize,
+    /// Relationships between tables of different domains
+    pub cross_domain_relationships: usize,
+    /// Relationships left out because a table they connect is in no domain
+    pub unresolved_relationships: usize,
+}
+
+/// Merge the domain models (named after their domains) into one model named `name`.
+///
+/// A table or relationship that appears in several domains is kept once, from the first
+/// domain in `models`.
+pub fn merge_domains(name: &str, models: &[DataModel]) -> (DataModel, EnterpriseModelSummary) {
+    let mut merged = DataModel::new(name.to_string(), String::new(), String::new());
+    let mut summary = EnterpriseModelSummary::default();
+    let mut table_domain: HashMap<Uuid, &str> = HashMap::new();
+    let mut offset = 0.0;
+
+    for model in models {
+        summary.domains.push(model.name.clone());
+        let min_x = model
+            .tables
+            .iter()
+            .filter_map(|t| t.position.as_ref().map(|p| p.x))
+            .fold(f64::INFINITY, f64::min);
+        let min_x = if min_x.is_finite() { min_x } else { 0.0 };
+        let mut next_x = model
+            .tables
+            .iter()
+            .filter_map(|t| t.position.as_ref().map(|p| p.x - min_x + TABLE_SPACING))
+            .fold(0.0, f64::max);
+
+        for table in &model.tables {
+            if table_domain.contains_key(&table.id) {
+                continue;
+            }
+            table_domain.insert(table.id, &model.name);
+            let mut table = table.clone();
+            table.name = format!("{}.{}", model.name, table.name);
+            table.tags.push(format!("domain:{}", model.name));
+            table.position = Some(match &table.position {
+                Some(p) => Position {
+                    x: offset + p.x - min_x,
+                    y: p.y,
+                },
+                None => {
+                    next_x += TABLE_SPACING;
+                    Position {
+                        x: offset + next_x - TABLE_SPACING,
+                        y: 0.0,
+                    }
+                }
+            });
+            merged.tables.push(table);
+        }
+        offset += next_x + DOMAIN_GAP;
+    }
+
+    let mut seen: HashSet<Uuid> = HashSet::new();
+    for relationship in models.iter().flat_map(|m| &m.relationships) {
+        if !seen.insert(relationship.id) {
+            continue;
+        }
+        match (
+            table_domain.get(&relationship.source_table_id),
+            table_domain.get(&relationship.target_table_id),
+        ) {
+            (Some(source), Some(target)) => {
+                if source != target {
+                    summary.cross_domain_relationships += 1;
+                }
+                merged.relationships.push(relationship.clone());
+            }
+            _ => summary.unresolved_relationships += 1,
+        }
+    }
+
+    summary.tables = merged.tables.len();
+    summary.relationships = merged.relationships.len();
+    (merged, summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Column, Relationship, Table};
+
+    fn table(name: &str, x: Option<f64>) -> Table {
+        let mut table = Table::new(
+            name.to_string(),
+            vec![Column::new("id".to_string(), "bigint".to_string())],
+        );
+        table.position = x.map(|x| Position { x, y: 10.0 });
+        table
+    }
+
+    #[test]
+    fn test_merge_domains_namespaces_and_resolves_relationships() {
+        let customers = table("customers", Some(100.0));
+        let orders = table("orders", None);
+
+        let mut crm = DataModel::new("crm".to_string(), String::new(), String::new());
+        crm.tables = vec![customers.clone()];
+        let mut sales = DataModel::new("sales".to_string(), String::new(), String::new());
+        sales.tables = vec![orders.clone()];
+        // sales relates its orders to crm's customers, and to a table that no longer exists
+        let cross = Relationship::new(orders.id, customers.id);
+        let dangling = Relationship::new(orders.id, Uuid::new_v4());
+        sales.relationships = vec![cross.clone(), dangling];
+        // crm imports the same relationship
+        crm.relationships = vec![cross.clone()];
+
+        let (model, summary) = merge_domains("enterprise", &[crm, sales]);
+        assert_eq!(
+            summary,
+            EnterpriseModelSummary {
+                domains: vec!["crm".to_string(), "sales".to_string()],
+                tables: 2,
+                relationships: 1,
+                cross_domain_relationships: 1,
+                unresolved_relationships: 1,
+            }
+        );
+        assert_eq!(model.tables[0].name, "crm.customers");
+        assert_eq!(model.tables[1].name, "sales.orders");
+        assert!(model.tables[1].tags.contains(&"domain:sales".to_string()));
+        assert_eq!(model.relationships[0].id, cross.id);
+
+        // The sales domain starts to the right of crm
+        let x = |i: usize| model.tables[i].position.as_ref().unwrap().x;
+        assert_eq!(x(0), 0.0);
+        assert_eq!(x(1), TABLE_SPACING + DOMAIN_GAP);
+    }
+}
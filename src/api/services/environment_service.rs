@@ -0,0 +1,241 @@
+//! Environment profile service.
+//!
+//! Environment profiles (e.g. `dev`, `test`, `prod`) let a single domain model be exported
+//! against different deployment targets. Each profile overrides catalog/schema names and
+//! carries free-form connection metadata. Profiles are stored per domain as
+//! `environments.yaml` next to the domain's tables.
+//!
+//! Catalog and schema names may contain `${variable}` placeholders (e.g. `${catalog}`),
+//! resolved at export time from the domain's variables, overridden by the selected
+//! profile's variables. Unknown variables are left in place.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use utoipa::ToSchema;
+
+use crate::models::{DataModel, Table};
+
+/// A single environment profile.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct EnvironmentProfile {
+    /// Environment name (e.g. "dev", "prod")
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Catalog applied to every table that has no specific override
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub catalog_name: Option<String>,
+    /// Schema applied to every table that has no specific override
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_name: Option<String>,
+    /// Maps a catalog name used in the model to the name used in this environment
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub catalog_overrides: HashMap<String, String>,
+    /// Maps a schema name used in the model to the name used in this environment
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub schema_overrides: HashMap<String, String>,
+    /// Connection metadata (host, port, database, warehouse, ...)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub connection: BTreeMap<String, String>,
+    /// Values for `${variable}` placeholders, overriding the domain variables
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, String>,
+}
+
+/// All environment profiles configured for a domain.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct EnvironmentConfig {
+    /// Domain-wide values for `${variable}` placeholders
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, String>,
+    #[serde(default)]
+    pub environments: Vec<EnvironmentProfile>,
+}
+
+impl EnvironmentConfig {
+    /// Find a profile by name.
+    pub fn get(&self, name: &str) -> Option<&EnvironmentProfile> {
+        self.environments.iter().find(|e| e.name == name)
+    }
+
+    /// Insert a profile, replacing any existing profile with the same name.
+    pub fn upsert(&mut self, profile: EnvironmentProfile) {
+        match self
+            .environments
+            .iter_mut()
+            .find(|e| e.name == profile.name)
+        {
+            Some(existing) => *existing = profile,
+            None => self.environments.push(profile),
+        }
+    }
+
+    /// Remove a profile by name. Returns true if a profile was removed.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let before = self.environments.len();
+        self.environments.retain(|e| e.name != name);
+        self.environments.len() != before
+    }
+
+    /// Build the profile applied at export time.
+    ///
+    /// The named profile (if any) is combined with the domain variables; returns None
+    /// when there is nothing to substitute. Errors with the name of an unknown profile.
+    pub fn export_profile(
+        &self,
+        environment: Option<&str>,
+    ) -> Result<Option<EnvironmentProfile>, String> {
+        let mut profile = match environment {
+            Some(name) => self.get(name).cloned().ok_or_else(|| name.to_string())?,
+            None if self.variables.is_empty() => return Ok(None),
+            None => EnvironmentProfile::default(),
+        };
+        for (key, value) in &self.variables {
+            profile
+                .variables
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        Ok(Some(profile))
+    }
+}
+
+impl EnvironmentProfile {
+    /// Substitute this environment's catalog/schema names on a table.
+    ///
+    /// Specific overrides win over the profile-wide catalog/schema.
+    pub fn apply_to_table(&self, table: &mut Table) {
+        let catalog = table
+            .catalog_name
+            .as_ref()
+            .and_then(|c| self.catalog_overrides.get(c))
+            .or(self.catalog_name.as_ref());
+        if let Some(catalog) = catalog {
+            table.catalog_name = Some(catalog.clone());
+        }
+        if let Some(catalog) = table.catalog_name.as_mut() {
+            *catalog = resolve_placeholders(catalog, &self.variables);
+        }
+
+        let schema = table
+            .schema_name
+            .as_ref()
+            .and_then(|s| self.schema_overrides.get(s))
+            .or(self.schema_name.as_ref());
+        if let Some(schema) = schema {
+            table.schema_name = Some(schema.clone());
+        }
+        if let Some(schema) = table.schema_name.as_mut() {
+            *schema = resolve_placeholders(schema, &self.variables);
+        }
+    }
+
+    /// Return a copy of the model with this environment's identifiers substituted.
+    pub fn apply_to_model(&self, model: &DataModel) -> DataModel {
+        let mut model = model.clone();
+        for table in &mut model.tables {
+            self.apply_to_table(table);
+        }
+        model
+    }
+}
+
+/// Replace `${name}` placeholders with values from `variables`.
+///
+/// Placeholders without a value are kept verbatim so they stay visible in the output.
+pub fn resolve_placeholders(value: &str, variables: &BTreeMap<String, String>) -> String {
+    if !value.contains("${") {
+        return value.to_string();
+    }
+    let mut result = String::with_capacity(value.len());
+    let mut rest = value;
+    while let Some(start) = rest.find("${") {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find('}') {
+            Some(end) => {
+                let name = &after[..end];
+                match variables.get(name) {
+                    Some(resolved) => result.push_str(resolved),
+                    None => result.push_str(&rest[start..start + 2 + end + 1]),
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Validate an environment name (used as a YAML key and in URLs).
+pub fn is_valid_environment_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_overrides_before_defaults() {
+        let mut table = Table::new("orders".to_string(), Vec::new());
+        table.catalog_name = Some("sales_dev".to_string());
+        table.schema_name = Some("raw".to_string());
+
+        let profile = EnvironmentProfile {
+            name: "prod".to_string(),
+            catalog_name: Some("main".to_string()),
+            schema_overrides: HashMap::from([("raw".to_string(), "bronze".to_string())]),
+            catalog_overrides: HashMap::from([("sales_dev".to_string(), "sales".to_string())]),
+            ..Default::default()
+        };
+        profile.apply_to_table(&mut table);
+        assert_eq!(table.catalog_name.as_deref(), Some("sales"));
+        assert_eq!(table.schema_name.as_deref(), Some("bronze"));
+
+        let mut other = Table::new("customers".to_string(), Vec::new());
+        profile.apply_to_table(&mut other);
+        assert_eq!(other.catalog_name.as_deref(), Some("main"));
+        assert_eq!(other.schema_name, None);
+    }
+
+    #[test]
+    fn test_profile_variables_override_domain_variables() {
+        let config = EnvironmentConfig {
+            variables: BTreeMap::from([
+                ("catalog".to_string(), "dev_catalog".to_string()),
+                ("layer".to_string(), "bronze".to_string()),
+            ]),
+            environments: vec![EnvironmentProfile {
+                name: "prod".to_string(),
+                variables: BTreeMap::from([("catalog".to_string(), "prod_catalog".to_string())]),
+                ..Default::default()
+            }],
+        };
+
+        let mut table = Table::new("orders".to_string(), Vec::new());
+        table.catalog_name = Some("${catalog}".to_string());
+        table.schema_name = Some("${layer}_${region}".to_string());
+
+        let profile = config.export_profile(Some("prod")).unwrap().unwrap();
+        profile.apply_to_table(&mut table);
+        assert_eq!(table.catalog_name.as_deref(), Some("prod_catalog"));
+        assert_eq!(table.schema_name.as_deref(), Some("bronze_${region}"));
+
+        assert!(config.export_profile(Some("staging")).is_err());
+        assert!(
+            EnvironmentConfig::default()
+                .export_profile(None)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,480 @@
+//! Erwin / ER/Studio XML parser for importing legacy modeling tool exports.
+//!
+//! Supports the Erwin XML export format (both the classic `ERwin4` layout and the
+//! `EMX:`-prefixed r9+ layout). Element prefixes are ignored, so namespaced and
+//! non-namespaced exports are handled the same way.
+//!
+//! Mapping:
+//! - Entities become tables (physical name preferred over logical name)
+//! - Attributes become columns (data type, nullability, primary key, definition)
+//! - Relationships become foreign key relationships (child -> parent)
+//! - Subject areas become `subject_area:<name>` table tags
+
+use crate::models::relationship::ForeignKeyDetails;
+use crate::models::{Column, Table};
+use crate::services::avro_parser::ParserError;
+use crate::services::relationship_service::ImportedRelationship;
+use anyhow::{Context, Result};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use tracing::info;
+
+/// Result of parsing an Erwin XML export.
+#[derive(Debug, Default)]
+pub struct ErwinImportResult {
+    pub tables: Vec<Table>,
+    pub relationships: Vec<ImportedRelationship>,
+    /// Subject area name -> table names
+    pub subject_areas: HashMap<String, Vec<String>>,
+    pub errors: Vec<ParserError>,
+}
+
+/// Parser for Erwin XML exports.
+#[derive(Default)]
+pub struct ErwinParser;
+
+/// Minimal XML element tree used for walking Erwin exports.
+#[derive(Debug, Default)]
+struct XmlNode {
+    name: String,
+    attrs: HashMap<String, String>,
+    text: String,
+    children: Vec<XmlNode>,
+}
+
+impl XmlNode {
+    fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs.get(key).map(|s| s.as_str())
+    }
+
+    /// Find all descendants (depth-first) with the given local name, not descending into matches.
+    fn find_all<'a>(&'a self, name: &str, out: &mut Vec<&'a XmlNode>) {
+        for child in &self.children {
+            if child.name == name {
+                out.push(child);
+            } else {
+                child.find_all(name, out);
+            }
+        }
+    }
+
+    /// Find the first descendant with any of the given local names that has text.
+    fn find_text(&self, names: &[&str]) -> Option<&str> {
+        for child in &self.children {
+            if names.contains(&child.name.as_str()) && !child.text.trim().is_empty() {
+                return Some(child.text.trim());
+            }
+        }
+        for child in &self.children {
+            // Don't descend into nested entities/attributes of a different kind
+            if child.name == "Attribute" || child.name == "Entity" {
+                continue;
+            }
+            if let Some(text) = child.find_text(names) {
+                return Some(text);
+            }
+        }
+        None
+    }
+
+    /// Collect all descendant texts with any of the given local names.
+    fn collect_texts(&self, names: &[&str], out: &mut Vec<String>) {
+        for child in &self.children {
+            if names.contains(&child.name.as_str()) && !child.text.trim().is_empty() {
+                out.push(child.text.trim().to_string());
+            }
+            child.collect_texts(names, out);
+        }
+    }
+}
+
+fn local_name(start: &BytesStart) -> String {
+    String::from_utf8_lossy(start.local_name().as_ref()).to_string()
+}
+
+fn node_from_start(start: &BytesStart) -> XmlNode {
+    let mut node = XmlNode {
+        name: local_name(start),
+        ..Default::default()
+    };
+    for attr in start.attributes().flatten() {
+        let key = String::from_utf8_lossy(attr.key.local_name().as_ref()).to_string();
+        if let Ok(value) = attr.unescape_value() {
+            node.attrs.insert(key, value.to_string());
+        }
+    }
+    node
+}
+
+fn parse_tree(xml: &str) -> Result<XmlNode> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut stack: Vec<XmlNode> = vec![XmlNode::default()];
+    loop {
+        match reader.read_event().context("Invalid XML")? {
+            Event::Start(start) => stack.push(node_from_start(&start)),
+            Event::Empty(start) => {
+                let node = node_from_start(&start);
+                if let Some(parent) = stack.last_mut() {
+                    parent.children.push(node);
+                }
+            }
+            Event::Text(text) => {
+                if let Some(current) = stack.last_mut() {
+                    current.text.push_str(&text.unescape().unwrap_or_default());
+                }
+            }
+            Event::CData(data) => {
+                if let Some(current) = stack.last_mut() {
+                    current
+                        .text
+                        .push_str(&String::from_utf8_lossy(&data.into_inner()));
+                }
+            }
+            Event::End(_) => {
+                let node = stack.pop().context("Unbalanced XML")?;
+                stack
+                    .last_mut()
+                    .context("Unbalanced XML")?
+                    .children
+                    .push(node);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if stack.len() != 1 {
+        return Err(anyhow::anyhow!("Unexpected end of XML document"));
+    }
+    Ok(stack.pop().unwrap_or_default())
+}
+
+impl ErwinParser {
+    /// Create a new Erwin parser instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse Erwin XML content into tables, relationships, and subject areas.
+    pub fn parse(&self, xml_content: &str) -> Result<ErwinImportResult> {
+        let root = parse_tree(xml_content)?;
+        let mut result = ErwinImportResult::default();
+
+        let mut entities = Vec::new();
+        root.find_all("Entity", &mut entities);
+        if entities.is_empty() {
+            return Err(anyhow::anyhow!("No Erwin entities found in XML document"));
+        }
+
+        // Entity id/name -> table name, attribute id/name -> (table name, column name)
+        let mut entity_names: HashMap<String, String> = HashMap::new();
+        let mut attribute_names: HashMap<String, (String, String)> = HashMap::new();
+
+        for entity in entities {
+            match self.parse_entity(entity, &mut attribute_names) {
+                Ok(table) => {
+                    if let Some(id) = entity.attr("id") {
+                        entity_names.insert(id.to_string(), table.name.clone());
+                    }
+                    if let Some(name) = entity.attr("name") {
+                        entity_names.insert(name.to_string(), table.name.clone());
+                    }
+                    entity_names.insert(table.name.clone(), table.name.clone());
+                    result.tables.push(table);
+                }
+                Err(e) => result.errors.push(ParserError {
+                    error_type: "parse_error".to_string(),
+                    field: entity.attr("name").map(|s| s.to_string()),
+                    message: format!("Failed to parse entity: {}", e),
+                }),
+            }
+        }
+
+        let mut relationships = Vec::new();
+        root.find_all("Relationship", &mut relationships);
+        for relationship in relationships {
+            self.parse_relationship(relationship, &entity_names, &attribute_names, &mut result);
+        }
+
+        let mut subject_areas = Vec::new();
+        root.find_all("Subject_Area", &mut subject_areas);
+        for area in subject_areas {
+            let Some(area_name) = area
+                .attr("name")
+                .map(|s| s.to_string())
+                .or_else(|| area.find_text(&["Name"]).map(|s| s.to_string()))
+            else {
+                continue;
+            };
+
+            let mut refs = Vec::new();
+            area.collect_texts(
+                &[
+                    "Referenced_Entities_Ref",
+                    "Referenced_Entities",
+                    "Entity_Ref",
+                ],
+                &mut refs,
+            );
+            let mut members: Vec<String> = refs
+                .iter()
+                .flat_map(|r| r.split_whitespace())
+                .filter_map(|r| entity_names.get(r).cloned())
+                .collect::<HashSet<_>>()
+                .into_iter()
+                .collect();
+            members.sort();
+
+            let tag = format!("subject_area:{}", area_name);
+            for table in result
+                .tables
+                .iter_mut()
+                .filter(|t| members.contains(&t.name))
+            {
+                if !table.tags.contains(&tag) {
+                    table.tags.push(tag.clone());
+                }
+            }
+            result.subject_areas.insert(area_name, members);
+        }
+
+        info!(
+            "Parsed Erwin XML: {} tables, {} relationships, {} subject areas",
+            result.tables.len(),
+            result.relationships.len(),
+            result.subject_areas.len()
+        );
+        Ok(result)
+    }
+
+    /// Parse a single entity into a table.
+    fn parse_entity(
+        &self,
+        entity: &XmlNode,
+        attribute_names: &mut HashMap<String, (String, String)>,
+    ) -> Result<Table> {
+        let logical_name = entity
+            .attr("name")
+            .map(|s| s.to_string())
+            .or_else(|| entity.find_text(&["Name"]).map(|s| s.to_string()))
+            .ok_or_else(|| anyhow::anyhow!("Entity has no name"))?;
+        let table_name = entity
+            .find_text(&["Physical_Name"])
+            .map(|s| s.to_string())
+            .unwrap_or_else(|| logical_name.replace(' ', "_"));
+
+        // Primary key attribute ids from PK key groups
+        let mut pk_attribute_refs = HashSet::new();
+        let mut key_groups = Vec::new();
+        entity.find_all("Key_Group", &mut key_groups);
+        for key_group in key_groups {
+            let key_type = key_group
+                .find_text(&["Key_Group_Type"])
+                .unwrap_or_default()
+                .to_uppercase();
+            if key_type == "PK" || key_type == "PRIMARY" {
+                let mut refs = Vec::new();
+                key_group.collect_texts(&["Attribute_Ref", "Key_Group_Member_Column"], &mut refs);
+                pk_attribute_refs.extend(refs);
+            }
+        }
+
+        let mut attributes = Vec::new();
+        entity.find_all("Attribute", &mut attributes);
+
+        let mut columns = Vec::new();
+        for (idx, attribute) in attributes.iter().enumerate() {
+            let Some(attr_logical) = attribute
+                .attr("name")
+                .map(|s| s.to_string())
+                .or_else(|| attribute.find_text(&["Name"]).map(|s| s.to_string()))
+            else {
+                continue;
+            };
+            let column_name = attribute
+                .find_text(&["Physical_Name"])
+                .map(|s| s.to_string())
+                .unwrap_or_else(|| attr_logical.replace(' ', "_"));
+            let data_type = attribute
+                .find_text(&["Physical_Data_Type", "Datatype", "Logical_Data_Type"])
+                .unwrap_or("VARCHAR")
+                .to_string();
+
+            let mut column = Column::new(column_name.clone(), data_type);
+            column.column_order = idx as i32;
+
+            let attribute_id = attribute.attr("id").map(|s| s.to_string());
+            let is_pk = attribute_id
+                .as_ref()
+                .map(|id| pk_attribute_refs.contains(id))
+                .unwrap_or(false)
+                || pk_attribute_refs.contains(&attr_logical)
+                || attribute.find_text(&["Type"]) == Some("100");
+            column.primary_key = is_pk;
+
+            let null_option = attribute
+                .find_text(&["Null_Option_Type", "Null_Option"])
+                .unwrap_or_default()
+                .to_uppercase();
+            column.nullable = !(is_pk || null_option == "1" || null_option.contains("NOT"));
+
+            if let Some(definition) = attribute.find_text(&["Definition", "Comment"]) {
+                column.description = definition.to_string();
+            }
+
+            if let Some(id) = attribute_id {
+                attribute_names.insert(id, (table_name.clone(), column_name.clone()));
+            }
+            attribute_names.insert(
+                format!("{}.{}", table_name, attr_logical),
+                (table_name.clone(), column_name),
+            );
+            columns.push(column);
+        }
+
+        let mut table = Table::new(table_name, columns);
+        if let Some(definition) = entity.find_text(&["Definition", "Comment"]) {
+            table
+                .odcl_metadata
+                .insert("description".to_string(), json!(definition));
+        }
+        if logical_name != table.name {
+            table
+                .odcl_metadata
+                .insert("logicalName".to_string(), json!(logical_name));
+        }
+        table
+            .odcl_metadata
+            .insert("source_format".to_string(), json!("erwin"));
+        Ok(table)
+    }
+
+    /// Parse a relationship between two entities.
+    fn parse_relationship(
+        &self,
+        relationship: &XmlNode,
+        entity_names: &HashMap<String, String>,
+        attribute_names: &HashMap<String, (String, String)>,
+        result: &mut ErwinImportResult,
+    ) {
+        let parent_ref = relationship.find_text(&[
+            "Parent_Entity_Ref",
+            "Relationship_Parent_Entity",
+            "Parent_Entity",
+        ]);
+        let child_ref = relationship.find_text(&[
+            "Child_Entity_Ref",
+            "Relationship_Child_Entity",
+            "Child_Entity",
+        ]);
+
+        let (Some(parent), Some(child)) = (
+            parent_ref.and_then(|r| entity_names.get(r)),
+            child_ref.and_then(|r| entity_names.get(r)),
+        ) else {
+            result.errors.push(ParserError {
+                error_type: "relationship_error".to_string(),
+                field: relationship.attr("name").map(|s| s.to_string()),
+                message: "Relationship references unknown parent or child entity".to_string(),
+            });
+            return;
+        };
+
+        // Foreign key columns, if the export lists them
+        let mut fk_refs = Vec::new();
+        relationship.collect_texts(&["Child_Attribute_Ref", "Foreign_Key_Ref"], &mut fk_refs);
+        let mut parent_refs = Vec::new();
+        relationship.collect_texts(&["Parent_Attribute_Ref"], &mut parent_refs);
+        let column_names = |refs: &[String]| -> Vec<String> {
+            refs.iter()
+                .filter_map(|r| attribute_names.get(r))
+                .map(|(_, column)| column.clone())
+                .collect()
+        };
+        let foreign_key_details =
+            ForeignKeyDetails::from_columns(&column_names(&fk_refs), &column_names(&parent_refs))
+                .ok();
+
+        let mut imported =
+            ImportedRelationship::foreign_key(child.clone(), parent.clone(), foreign_key_details);
+        imported.notes = relationship
+            .attr("name")
+            .map(|s| s.to_string())
+            .or_else(|| relationship.find_text(&["Name"]).map(|s| s.to_string()));
+        result.relationships.push(imported);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"<?xml version="1.0"?>
+<EMX:Model xmlns:EMX="http://www.erwin.com/dm">
+  <EMX:Entity_Groups>
+    <EMX:Entity id="E1" name="Customer">
+      <EMX:EntityProps><EMX:Physical_Name>CUSTOMER</EMX:Physical_Name></EMX:EntityProps>
+      <EMX:Attribute_Groups>
+        <EMX:Attribute id="A1" name="Customer Id">
+          <EMX:AttributeProps>
+            <EMX:Physical_Name>CUSTOMER_ID</EMX:Physical_Name>
+            <EMX:Physical_Data_Type>INTEGER</EMX:Physical_Data_Type>
+            <EMX:Type>100</EMX:Type>
+          </EMX:AttributeProps>
+        </EMX:Attribute>
+      </EMX:Attribute_Groups>
+    </EMX:Entity>
+    <EMX:Entity id="E2" name="Order">
+      <EMX:Attribute_Groups>
+        <EMX:Attribute id="A2" name="order_id">
+          <EMX:AttributeProps><EMX:Physical_Data_Type>INTEGER</EMX:Physical_Data_Type><EMX:Null_Option_Type>1</EMX:Null_Option_Type></EMX:AttributeProps>
+        </EMX:Attribute>
+        <EMX:Attribute id="A3" name="customer_id">
+          <EMX:AttributeProps><EMX:Physical_Data_Type>INTEGER</EMX:Physical_Data_Type></EMX:AttributeProps>
+        </EMX:Attribute>
+      </EMX:Attribute_Groups>
+    </EMX:Entity>
+  </EMX:Entity_Groups>
+  <EMX:Relationship_Groups>
+    <EMX:Relationship id="R1" name="places">
+      <EMX:RelationshipProps>
+        <EMX:Parent_Entity_Ref>E1</EMX:Parent_Entity_Ref>
+        <EMX:Child_Entity_Ref>E2</EMX:Child_Entity_Ref>
+      </EMX:RelationshipProps>
+    </EMX:Relationship>
+  </EMX:Relationship_Groups>
+  <EMX:Subject_Area_Groups>
+    <EMX:Subject_Area id="S1" name="Sales">
+      <EMX:Subject_AreaProps><EMX:Referenced_Entities_Ref>E1</EMX:Referenced_Entities_Ref><EMX:Referenced_Entities_Ref>E2</EMX:Referenced_Entities_Ref></EMX:Subject_AreaProps>
+    </EMX:Subject_Area>
+  </EMX:Subject_Area_Groups>
+</EMX:Model>"#;
+
+    #[test]
+    fn test_parse_entities_relationships_and_subject_areas() {
+        let result = ErwinParser::new().parse(SAMPLE).unwrap();
+        assert_eq!(result.tables.len(), 2);
+
+        let customer = &result.tables[0];
+        assert_eq!(customer.name, "CUSTOMER");
+        assert_eq!(customer.columns[0].name, "CUSTOMER_ID");
+        assert!(customer.columns[0].primary_key);
+        assert!(!customer.columns[0].nullable);
+
+        let order = &result.tables[1];
+        assert_eq!(order.name, "Order");
+        assert!(!order.columns[0].nullable);
+        assert!(order.columns[1].nullable);
+
+        assert_eq!(result.relationships.len(), 1);
+        assert_eq!(result.relationships[0].source_table, "Order");
+        assert_eq!(result.relationships[0].target_table, "CUSTOMER");
+
+        assert_eq!(result.subject_areas["Sales"].len(), 2);
+        assert!(order.tags.contains(&"subject_area:Sales".to_string()));
+    }
+}
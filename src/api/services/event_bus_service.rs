@@ -0,0 +1,357 @@
+//! Event bus publishing of model changes.
+//!
+//! Optionally publishes every recorded [`ModelChangeEvent`] to Kafka or NATS so other systems
+//! can subscribe to model changes instead of polling `GET /workspace/domains/{domain}/events`.
+//! Messages are JSON documents whose fields follow the Avro schema returned by
+//! [`event_avro_schema`] (`before`/`after` carry the entity state as JSON strings).
+//!
+//! Configuration (environment variables):
+//! - `EVENT_BUS_NATS_URL`: NATS server, e.g. `nats://token@nats:4222`; events are published to
+//!   `<prefix>.<domain>.<event>`, e.g. `datamodel.sales.table.updated`
+//! - `EVENT_BUS_KAFKA_REST_URL`: Kafka REST proxy, e.g. `http://kafka-rest:8082`; events are
+//!   produced to `EVENT_BUS_KAFKA_TOPIC` (default `datamodel.model-changes`) keyed by domain
+//! - `EVENT_BUS_SUBJECT_PREFIX` (default `datamodel`)
+//!
+//! Publishing is disabled when neither URL is set. Failures are logged and never affect the
+//! request that made the change.
+
+use anyhow::{Context, Result, anyhow, bail};
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tracing::{debug, warn};
+
+use super::event_log_service::ModelChangeEvent;
+
+const DEFAULT_SUBJECT_PREFIX: &str = "datamodel";
+const DEFAULT_KAFKA_TOPIC: &str = "datamodel.model-changes";
+const NATS_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Avro schema of the event payloads published on the bus.
+pub fn event_avro_schema() -> Value {
+    let optional_string = |name: &str, doc: &str| {
+        json!({
+            "name": name,
+            "type": ["null", "string"],
+            "default": null,
+            "doc": doc
+        })
+    };
+    json!({
+        "type": "record",
+        "name": "ModelChangeEvent",
+        "namespace": "com.opendatamodelling.events",
+        "doc": "A table or relationship change in a data model domain",
+        "fields": [
+            {"name": "id", "type": {"type": "string", "logicalType": "uuid"}},
+            {"name": "sequence", "type": "long", "doc": "Position in the domain's event log"},
+            {"name": "domain", "type": "string"},
+            {"name": "event", "type": "string", "doc": "Event name, e.g. table.updated"},
+            {"name": "entity_type", "type": {
+                "type": "enum",
+                "name": "EntityType",
+                "symbols": ["table", "relationship"]
+            }},
+            {"name": "entity_id", "type": {"type": "string", "logicalType": "uuid"}},
+            {"name": "actor", "type": "string", "doc": "Email of the user who made the change"},
+            {"name": "timestamp", "type": {"type": "long", "logicalType": "timestamp-millis"}},
+            optional_string("before", "Entity state before the change, as JSON"),
+            optional_string("after", "Entity state after the change, as JSON"),
+        ]
+    })
+}
+
+/// Bus message, shaped after [`event_avro_schema`].
+#[derive(Debug, Serialize)]
+pub struct BusEvent {
+    pub id: String,
+    pub sequence: i64,
+    pub domain: String,
+    pub event: &'static str,
+    pub entity_type: &'static str,
+    pub entity_id: String,
+    pub actor: String,
+    pub timestamp: i64,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl From<&ModelChangeEvent> for BusEvent {
+    fn from(event: &ModelChangeEvent) -> Self {
+        let event_name = event.event_type.event_name();
+        Self {
+            id: event.id.to_string(),
+            sequence: event.sequence,
+            domain: event.domain.clone(),
+            event: event_name,
+            entity_type: event_name.split('.').next().unwrap_or_default(),
+            entity_id: event.entity_id.to_string(),
+            actor: event.actor.clone(),
+            timestamp: event.timestamp.timestamp_millis(),
+            before: event.before.as_ref().map(Value::to_string),
+            after: event.after.as_ref().map(Value::to_string),
+        }
+    }
+}
+
+/// A message broker the events are published to.
+#[async_trait]
+pub trait EventPublisher: Send + Sync {
+    /// Broker name, used in logs
+    fn broker(&self) -> &'static str;
+
+    async fn publish(&self, event: &BusEvent) -> Result<()>;
+}
+
+/// Publishes to NATS subjects over the NATS text protocol.
+pub struct NatsPublisher {
+    address: String,
+    connect_options: Value,
+    subject_prefix: String,
+}
+
+impl NatsPublisher {
+    /// Parse a `nats://[user:password@|token@]host[:port]` URL.
+    pub fn new(url: &str, subject_prefix: String) -> Result<Self> {
+        let parsed = url::Url::parse(url).context("Invalid NATS URL")?;
+        let host = parsed.host_str().context("NATS URL has no host")?;
+        let mut connect_options = json!({
+            "verbose": false,
+            "pedantic": false,
+            "name": "data-modelling-api",
+            "lang": "rust",
+            "version": env!("CARGO_PKG_VERSION"),
+        });
+        match (parsed.username(), parsed.password()) {
+            ("", _) => {}
+            (user, Some(password)) => {
+                connect_options["user"] = json!(user);
+                connect_options["pass"] = json!(password);
+            }
+            (token, None) => connect_options["auth_token"] = json!(token),
+        }
+        Ok(Self {
+            address: format!("{}:{}", host, parsed.port().unwrap_or(4222)),
+            connect_options,
+            subject_prefix,
+        })
+    }
+
+    fn subject(&self, event: &BusEvent) -> String {
+        format!("{}.{}.{}", self.subject_prefix, event.domain, event.event)
+    }
+
+    async fn send(&self, subject: &str, payload: &[u8]) -> Result<()> {
+        let stream = TcpStream::connect(&self.address)
+            .await
+            .with_context(|| format!("Failed to connect to NATS at {}", self.address))?;
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        let info = lines.next_line().await?.unwrap_or_default();
+        if !info.starts_with("INFO") {
+            bail!("Unexpected NATS greeting: {}", info);
+        }
+        let mut message = format!("CONNECT {}\r\n", self.connect_options).into_bytes();
+        message.extend_from_slice(format!("PUB {} {}\r\n", subject, payload.len()).as_bytes());
+        message.extend_from_slice(payload);
+        // PING after PUB: the PONG confirms the server processed (or rejected) the message
+        message.extend_from_slice(b"\r\nPING\r\n");
+        writer.write_all(&message).await?;
+
+        while let Some(line) = lines.next_line().await? {
+            if line.starts_with("PONG") {
+                return Ok(());
+            }
+            if let Some(error) = line.strip_prefix("-ERR") {
+                bail!("NATS rejected the message:{}", error);
+            }
+        }
+        Err(anyhow!("NATS closed the connection"))
+    }
+}
+
+#[async_trait]
+impl EventPublisher for NatsPublisher {
+    fn broker(&self) -> &'static str {
+        "nats"
+    }
+
+    async fn publish(&self, event: &BusEvent) -> Result<()> {
+        let payload = serde_json::to_vec(event)?;
+        tokio::time::timeout(NATS_TIMEOUT, self.send(&self.subject(event), &payload))
+            .await
+            .context("Timed out publishing to NATS")?
+    }
+}
+
+/// Produces to a Kafka topic through a Kafka REST proxy (v2 API).
+pub struct KafkaRestPublisher {
+    client: reqwest::Client,
+    endpoint: String,
+}
+
+impl KafkaRestPublisher {
+    pub fn new(base_url: &str, topic: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoint: format!("{}/topics/{}", base_url.trim_end_matches('/'), topic),
+        }
+    }
+}
+
+#[async_trait]
+impl EventPublisher for KafkaRestPublisher {
+    fn broker(&self) -> &'static str {
+        "kafka"
+    }
+
+    async fn publish(&self, event: &BusEvent) -> Result<()> {
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header("Content-Type", "application/vnd.kafka.json.v2+json")
+            .json(&json!({"records": [{"key": event.domain, "value": event}]}))
+            .send()
+            .await
+            .context("Failed to reach Kafka REST proxy")?;
+        if !response.status().is_success() {
+            bail!("Kafka REST proxy returned {}", response.status());
+        }
+        Ok(())
+    }
+}
+
+/// Publishes model changes to all configured brokers.
+#[derive(Clone, Default)]
+pub struct EventBus {
+    publishers: Vec<Arc<dyn EventPublisher>>,
+}
+
+impl EventBus {
+    pub fn new(publishers: Vec<Arc<dyn EventPublisher>>) -> Self {
+        Self { publishers }
+    }
+
+    /// Configure from the `EVENT_BUS_*` environment variables.
+    pub fn from_env() -> Self {
+        let mut publishers: Vec<Arc<dyn EventPublisher>> = Vec::new();
+        let prefix = env::var("EVENT_BUS_SUBJECT_PREFIX")
+            .ok()
+            .filter(|p| !p.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_SUBJECT_PREFIX.to_string());
+        if let Ok(url) = env::var("EVENT_BUS_NATS_URL") {
+            match NatsPublisher::new(&url, prefix) {
+                Ok(nats) => publishers.push(Arc::new(nats)),
+                Err(e) => warn!("Event bus: ignoring EVENT_BUS_NATS_URL: {}", e),
+            }
+        }
+        if let Ok(url) = env::var("EVENT_BUS_KAFKA_REST_URL") {
+            let topic = env::var("EVENT_BUS_KAFKA_TOPIC")
+                .unwrap_or_else(|_| DEFAULT_KAFKA_TOPIC.to_string());
+            publishers.push(Arc::new(KafkaRestPublisher::new(&url, &topic)));
+        }
+        Self::new(publishers)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.publishers.is_empty()
+    }
+
+    /// Publish an event to every broker; failures are logged only.
+    pub async fn publish(&self, event: &ModelChangeEvent) {
+        let message = BusEvent::from(event);
+        for publisher in &self.publishers {
+            match publisher.publish(&message).await {
+                Ok(()) => debug!(
+                    "Published {} for domain {} to {}",
+                    message.event,
+                    message.domain,
+                    publisher.broker()
+                ),
+                Err(e) => warn!(
+                    "Failed to publish {} for domain {} to {}: {}",
+                    message.event,
+                    message.domain,
+                    publisher.broker(),
+                    e
+                ),
+            }
+        }
+    }
+
+    /// Publish without blocking the caller.
+    pub fn publish_in_background(&self, event: ModelChangeEvent) {
+        if !self.is_enabled() {
+            return;
+        }
+        let bus = self.clone();
+        tokio::spawn(async move {
+            bus.publish(&event).await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::event_log_service::ChangeEventType;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::TcpListener;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_nats_publish_matches_avro_schema() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            socket.write_all(b"INFO {}\r\n").await.unwrap();
+            let mut received = Vec::new();
+            let mut buf = [0u8; 4096];
+            while !received.ends_with(b"PING\r\n") {
+                let n = socket.read(&mut buf).await.unwrap();
+                received.extend_from_slice(&buf[..n]);
+            }
+            socket.write_all(b"PONG\r\n").await.unwrap();
+            String::from_utf8(received).unwrap()
+        });
+
+        let event = ModelChangeEvent::new(
+            "sales",
+            ChangeEventType::TableUpdated,
+            Uuid::new_v4(),
+            "user@example.com",
+            Some(json!({"name": "orders"})),
+            Some(json!({"name": "orders_v2"})),
+        );
+        let message = BusEvent::from(&event);
+        let publisher = NatsPublisher::new(
+            &format!("nats://secret@127.0.0.1:{}", port),
+            "datamodel".to_string(),
+        )
+        .unwrap();
+        publisher.publish(&message).await.unwrap();
+
+        let received = server.await.unwrap();
+        assert!(received.contains("\"auth_token\":\"secret\""));
+        assert!(received.contains("PUB datamodel.sales.table.updated "));
+
+        let payload = serde_json::to_value(&message).unwrap();
+        let schema = event_avro_schema();
+        let fields: Vec<&str> = schema["fields"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|f| f["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(fields.len(), payload.as_object().unwrap().len());
+        assert!(fields.iter().all(|f| payload.get(*f).is_some()));
+        assert_eq!(payload["entity_type"], "table");
+    }
+}
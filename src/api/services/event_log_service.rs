@@ -0,0 +1,445 @@
+//! Append-only event log of model changes.
+//!
+//! Every table and relationship mutation is recorded as a [`ModelChangeEvent`] carrying the
+//! entity's state before and after the change, so downstream systems can rebuild a domain by
+//! replaying its events and undo, audit and webhook consumers can share one source.
+//!
+//! Events are stored per domain:
+//! - file mode: `events.jsonl` in the domain directory, one JSON event per line
+//! - PostgreSQL mode: the `model_events` table
+//!
+//! Each event has a `sequence` number that increases within a domain. Consumers read with
+//! `since=<last sequence seen>`; sequence numbers may have gaps in PostgreSQL mode.
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{PgPool, Row};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// File name of a domain's event log in file mode
+pub const EVENT_LOG_FILE: &str = "events.jsonl";
+
+/// Kind of model change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeEventType {
+    TableCreated,
+    TableUpdated,
+    TableDeleted,
+    RelationshipCreated,
+    RelationshipUpdated,
+    RelationshipDeleted,
+}
+
+impl ChangeEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeEventType::TableCreated => "table_created",
+            ChangeEventType::TableUpdated => "table_updated",
+            ChangeEventType::TableDeleted => "table_deleted",
+            ChangeEventType::RelationshipCreated => "relationship_created",
+            ChangeEventType::RelationshipUpdated => "relationship_updated",
+            ChangeEventType::RelationshipDeleted => "relationship_deleted",
+        }
+    }
+
+    /// Dotted event name used on the event bus, e.g. `table.updated`
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            ChangeEventType::TableCreated => "table.created",
+            ChangeEventType::TableUpdated => "table.updated",
+            ChangeEventType::TableDeleted => "table.deleted",
+            ChangeEventType::RelationshipCreated => "relationship.created",
+            ChangeEventType::RelationshipUpdated => "relationship.updated",
+            ChangeEventType::RelationshipDeleted => "relationship.deleted",
+        }
+    }
+
+    /// Whether the change is to a table rather than a relationship
+    pub fn is_table(&self) -> bool {
+        matches!(
+            self,
+            ChangeEventType::TableCreated
+                | ChangeEventType::TableUpdated
+                | ChangeEventType::TableDeleted
+        )
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        serde_json::from_value(Value::String(value.to_string())).ok()
+    }
+}
+
+/// One recorded model change.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ModelChangeEvent {
+    /// Position in the domain's log; pass the last one seen as `since`
+    pub sequence: i64,
+    pub id: Uuid,
+    pub domain: String,
+    pub event_type: ChangeEventType,
+    /// Table or relationship id
+    pub entity_id: Uuid,
+    /// Email of the user who made the change
+    pub actor: String,
+    pub timestamp: DateTime<Utc>,
+    /// Entity state before the change (absent for creates)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub before: Option<Value>,
+    /// Entity state after the change (absent for deletes)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after: Option<Value>,
+}
+
+impl ModelChangeEvent {
+    /// New event; the sequence number is assigned when it is appended.
+    pub fn new(
+        domain: &str,
+        event_type: ChangeEventType,
+        entity_id: Uuid,
+        actor: &str,
+        before: Option<Value>,
+        after: Option<Value>,
+    ) -> Self {
+        Self {
+            sequence: 0,
+            id: Uuid::new_v4(),
+            domain: domain.to_string(),
+            event_type,
+            entity_id,
+            actor: actor.to_string(),
+            timestamp: Utc::now(),
+            before,
+            after,
+        }
+    }
+}
+
+/// A domain's event log.
+#[async_trait]
+pub trait EventLog: Send + Sync {
+    /// Append an event, returning it with its assigned sequence number.
+    async fn append(&self, event: ModelChangeEvent) -> Result<ModelChangeEvent>;
+
+    /// Events with a sequence number greater than `since`, oldest first.
+    async fn read(&self, since: i64, limit: usize) -> Result<Vec<ModelChangeEvent>>;
+}
+
+/// Last sequence number per log file, so appends don't rescan the file.
+fn last_sequences() -> &'static Mutex<HashMap<PathBuf, i64>> {
+    static LAST: OnceLock<Mutex<HashMap<PathBuf, i64>>> = OnceLock::new();
+    LAST.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// JSON-lines event log in a domain directory (file mode).
+pub struct JsonlEventLog {
+    path: PathBuf,
+}
+
+impl JsonlEventLog {
+    pub fn new(domain_dir: PathBuf) -> Self {
+        Self {
+            path: domain_dir.join(EVENT_LOG_FILE),
+        }
+    }
+
+    fn read_all(&self) -> Result<Vec<ModelChangeEvent>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let reader = BufReader::new(
+            File::open(&self.path)
+                .with_context(|| format!("Failed to open {}", self.path.display()))?,
+        );
+        Ok(reader
+            .lines()
+            .map_while(|l| l.ok())
+            .filter_map(|l| serde_json::from_str::<ModelChangeEvent>(&l).ok())
+            .collect())
+    }
+}
+
+#[async_trait]
+impl EventLog for JsonlEventLog {
+    async fn append(&self, mut event: ModelChangeEvent) -> Result<ModelChangeEvent> {
+        let mut last = last_sequences().lock().unwrap_or_else(|e| e.into_inner());
+        let previous = match last.get(&self.path) {
+            Some(sequence) => *sequence,
+            None => self
+                .read_all()?
+                .last()
+                .map(|e| e.sequence)
+                .unwrap_or_default(),
+        };
+        event.sequence = previous + 1;
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open {}", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(&event)?)?;
+        file.flush()?;
+
+        last.insert(self.path.clone(), event.sequence);
+        Ok(event)
+    }
+
+    async fn read(&self, since: i64, limit: usize) -> Result<Vec<ModelChangeEvent>> {
+        Ok(self
+            .read_all()?
+            .into_iter()
+            .filter(|e| e.sequence > since)
+            .take(limit)
+            .collect())
+    }
+}
+
+/// Event log in the `model_events` table (PostgreSQL mode).
+pub struct PostgresEventLog {
+    pool: PgPool,
+    domain_id: Uuid,
+}
+
+impl PostgresEventLog {
+    pub fn new(pool: PgPool, domain_id: Uuid) -> Self {
+        Self { pool, domain_id }
+    }
+}
+
+#[async_trait]
+impl EventLog for PostgresEventLog {
+    async fn append(&self, mut event: ModelChangeEvent) -> Result<ModelChangeEvent> {
+        let row = sqlx::query(
+            r#"
+            INSERT INTO model_events
+                (id, domain_id, domain_name, event_type, entity_id, actor, before_data, after_data, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING sequence
+            "#,
+        )
+        .bind(event.id)
+        .bind(self.domain_id)
+        .bind(&event.domain)
+        .bind(event.event_type.as_str())
+        .bind(event.entity_id)
+        .bind(&event.actor)
+        .bind(&event.before)
+        .bind(&event.after)
+        .bind(event.timestamp)
+        .fetch_one(&self.pool)
+        .await
+        .context("Failed to insert model event")?;
+        event.sequence = row.try_get("sequence")?;
+        Ok(event)
+    }
+
+    async fn read(&self, since: i64, limit: usize) -> Result<Vec<ModelChangeEvent>> {
+        let rows = sqlx::query(
+            r#"
+            SELECT sequence, id, domain_name, event_type, entity_id, actor, before_data, after_data, created_at
+            FROM model_events
+            WHERE domain_id = $1 AND sequence > $2
+            ORDER BY sequence
+            LIMIT $3
+            "#,
+        )
+        .bind(self.domain_id)
+        .bind(since)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to read model events")?;
+
+        let mut events = Vec::with_capacity(rows.len());
+        for row in rows {
+            let event_type: String = row.try_get("event_type")?;
+            let Some(event_type) = ChangeEventType::parse(&event_type) else {
+                continue;
+            };
+            events.push(ModelChangeEvent {
+                sequence: row.try_get("sequence")?,
+                id: row.try_get("id")?,
+                domain: row.try_get("domain_name")?,
+                event_type,
+                entity_id: row.try_get("entity_id")?,
+                actor: row.try_get("actor")?,
+                timestamp: row.try_get("created_at")?,
+                before: row.try_get("before_data")?,
+                after: row.try_get("after_data")?,
+            });
+        }
+        Ok(events)
+    }
+}
+
+/// Tables and relationships of a domain as JSON, for reconstructing past states.
+#[derive(Debug, Clone, Default)]
+pub struct ModelState {
+    pub tables: Vec<Value>,
+    pub relationships: Vec<Value>,
+}
+
+impl ModelState {
+    /// Undo the events recorded after `as_of`, newest first, turning the current state into
+    /// the state at that time.
+    ///
+    /// Each undone event puts back the entity's `before` state, or removes the entity if it
+    /// was created. Entities last changed before the log started keep their current state.
+    pub fn rewind(&mut self, events: &[ModelChangeEvent], as_of: DateTime<Utc>) {
+        let mut undone: Vec<&ModelChangeEvent> =
+            events.iter().filter(|e| e.timestamp > as_of).collect();
+        undone.sort_by_key(|e| std::cmp::Reverse(e.sequence));
+
+        for event in undone {
+            let entities = if event.event_type.is_table() {
+                &mut self.tables
+            } else {
+                &mut self.relationships
+            };
+            let id = event.entity_id.to_string();
+            let position = entities
+                .iter()
+                .position(|e| e.get("id").and_then(Value::as_str) == Some(id.as_str()));
+            match (position, &event.before) {
+                (Some(index), Some(before)) => entities[index] = before.clone(),
+                (Some(index), None) => {
+                    entities.remove(index);
+                }
+                (None, Some(before)) => entities.push(before.clone()),
+                (None, None) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_jsonl_append_and_cursor_read() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = JsonlEventLog::new(dir.path().to_path_buf());
+        let table_id = Uuid::new_v4();
+
+        let created = log
+            .append(ModelChangeEvent::new(
+                "sales",
+                ChangeEventType::TableCreated,
+                table_id,
+                "user@example.com",
+                None,
+                Some(json!({"name": "orders"})),
+            ))
+            .await
+            .unwrap();
+        assert_eq!(created.sequence, 1);
+        log.append(ModelChangeEvent::new(
+            "sales",
+            ChangeEventType::TableDeleted,
+            table_id,
+            "user@example.com",
+            Some(json!({"name": "orders"})),
+            None,
+        ))
+        .await
+        .unwrap();
+
+        let all = log.read(0, 100).await.unwrap();
+        assert_eq!(all.len(), 2);
+        let after_first = log.read(1, 100).await.unwrap();
+        assert_eq!(after_first.len(), 1);
+        assert_eq!(after_first[0].event_type, ChangeEventType::TableDeleted);
+        assert_eq!(after_first[0].sequence, 2);
+        assert!(log.read(2, 100).await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rewind_restores_the_state_at_a_past_time() {
+        let orders = Uuid::new_v4();
+        let customers = Uuid::new_v4();
+        let products = Uuid::new_v4();
+        let event = |sequence, event_type, entity_id: Uuid, hours_ago, before, after| {
+            let mut event =
+                ModelChangeEvent::new("sales", event_type, entity_id, "a@b.c", before, after);
+            event.sequence = sequence;
+            event.timestamp = Utc::now() - chrono::Duration::hours(hours_ago);
+            event
+        };
+        let table = |id: Uuid, name: &str| json!({"id": id.to_string(), "name": name});
+        let events = vec![
+            event(
+                1,
+                ChangeEventType::TableCreated,
+                orders,
+                5,
+                None,
+                Some(table(orders, "orders")),
+            ),
+            event(
+                2,
+                ChangeEventType::TableCreated,
+                customers,
+                4,
+                None,
+                Some(table(customers, "customers")),
+            ),
+            event(
+                3,
+                ChangeEventType::TableUpdated,
+                orders,
+                2,
+                Some(table(orders, "orders")),
+                Some(table(orders, "sales_orders")),
+            ),
+            event(
+                4,
+                ChangeEventType::TableDeleted,
+                customers,
+                2,
+                Some(table(customers, "customers")),
+                None,
+            ),
+            event(
+                5,
+                ChangeEventType::TableCreated,
+                products,
+                1,
+                None,
+                Some(table(products, "products")),
+            ),
+        ];
+        let current = ModelState {
+            tables: vec![table(orders, "sales_orders"), table(products, "products")],
+            relationships: vec![],
+        };
+
+        let mut state = current.clone();
+        state.rewind(&events, Utc::now() - chrono::Duration::hours(3));
+        let names: Vec<&str> = state
+            .tables
+            .iter()
+            .map(|t| t["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, vec!["orders", "customers"]);
+
+        let mut state = current.clone();
+        state.rewind(&events, Utc::now());
+        assert_eq!(state.tables, current.tables);
+    }
+}
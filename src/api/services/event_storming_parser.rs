@@ -0,0 +1,616 @@
+//! Event storming board parser.
+//!
+//! Turns the output of an event storming workshop (aggregates, commands and domain events,
+//! exported as JSON or CSV) into a candidate conceptual model:
+//! - Aggregates become tables, with the commands they handle and the events they emit
+//!   recorded in `odcl_metadata.event_storming`
+//! - Domain events become tables referencing the aggregate that emits them
+//! - Aggregate references become many-to-one relationships between the aggregates
+//!
+//! Every table is at the conceptual modeling level and tagged `event_storming` plus
+//! `event_storming:aggregate` or `event_storming:event`, so the candidates are easy to find
+//! and refine. Names are turned into snake_case identifiers; the sticky-note label is kept as
+//! `logicalName`. Aggregates that are only named by a command or event are created as well.
+//!
+//! JSON boards look like
+//! `{"aggregates": [{"name", "description", "references"}], "commands": [{"name",
+//! "aggregate", "actor", "description"}], "events": [{"name", "aggregate", "triggered_by",
+//! "description"}]}`. CSV boards have one sticky note per row with the columns
+//! `type,name,aggregate,description,triggered_by,actor,references` (only `type` and `name` are
+//! required; `references` lists aggregates separated by `;`).
+
+use crate::models::enums::{Cardinality, ModelingLevel, RelationshipType};
+use crate::models::relationship::ForeignKeyDetails;
+use crate::models::{Column, Relationship, Table};
+use crate::services::avro_parser::ParserError;
+use anyhow::{Result, anyhow, bail};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+/// Key of `odcl_metadata` holding where a table came from on the board
+pub const EVENT_STORMING_KEY: &str = "event_storming";
+
+/// Tag of every table created from a board
+pub const EVENT_STORMING_TAG: &str = "event_storming";
+
+/// Maximum number of sticky notes imported from one board
+const MAX_NOTES: usize = 5_000;
+
+/// Board format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventStormingFormat {
+    Json,
+    Csv,
+}
+
+impl EventStormingFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+
+    /// Detect the format from the filename extension, or else from the content.
+    pub fn detect(filename: Option<&str>, content: &str) -> Self {
+        match filename.map(|f| f.to_lowercase()) {
+            Some(f) if f.ends_with(".json") => Self::Json,
+            Some(f) if f.ends_with(".csv") => Self::Csv,
+            _ if content.trim_start().starts_with(['{', '[']) => Self::Json,
+            _ => Self::Csv,
+        }
+    }
+}
+
+/// An aggregate on the board
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BoardAggregate {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Aggregates this one refers to
+    #[serde(default)]
+    pub references: Vec<String>,
+}
+
+/// A command on the board
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BoardCommand {
+    pub name: String,
+    #[serde(default)]
+    pub aggregate: Option<String>,
+    #[serde(default)]
+    pub actor: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// A domain event on the board
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BoardEvent {
+    pub name: String,
+    #[serde(default)]
+    pub aggregate: Option<String>,
+    /// Command that causes the event
+    #[serde(default)]
+    pub triggered_by: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Sticky notes of an event storming board
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct EventStormingBoard {
+    #[serde(default)]
+    pub aggregates: Vec<BoardAggregate>,
+    #[serde(default)]
+    pub commands: Vec<BoardCommand>,
+    #[serde(default)]
+    pub events: Vec<BoardEvent>,
+}
+
+/// A command an aggregate handles, as recorded on the aggregate's table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandledCommand {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Result of parsing a board
+#[derive(Debug, Default)]
+pub struct EventStormingImportResult {
+    pub tables: Vec<Table>,
+    pub relationships: Vec<Relationship>,
+    pub errors: Vec<ParserError>,
+}
+
+/// Parser for event storming boards.
+#[derive(Default)]
+pub struct EventStormingParser;
+
+impl EventStormingParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse a board and build its candidate conceptual model. Fails when the content can't
+    /// be read as a board at all; problems with single notes are returned as errors.
+    pub fn parse(
+        &self,
+        content: &str,
+        format: EventStormingFormat,
+    ) -> Result<EventStormingImportResult> {
+        let content = content.trim_start_matches('\u{feff}');
+        let board = match format {
+            EventStormingFormat::Json => serde_json::from_str::<EventStormingBoard>(content)
+                .map_err(|e| anyhow!("Invalid event storming JSON: {}", e))?,
+            EventStormingFormat::Csv => parse_csv_board(content)?,
+        };
+        if board.aggregates.len() + board.commands.len() + board.events.len() > MAX_NOTES {
+            bail!("Board has more than {} sticky notes", MAX_NOTES);
+        }
+        Ok(build_model(&board))
+    }
+}
+
+/// Read a CSV board, one sticky note per row.
+fn parse_csv_board(content: &str) -> Result<EventStormingBoard> {
+    let rows = crate::services::relationship_csv_parser::parse_csv(content);
+    let Some(headers) = rows.first() else {
+        bail!("CSV file is empty");
+    };
+    let find = |name: &str| {
+        headers
+            .iter()
+            .position(|h| h.trim().eq_ignore_ascii_case(name))
+    };
+    let type_idx = find("type").ok_or_else(|| anyhow!("Missing column 'type'"))?;
+    let name_idx = find("name").ok_or_else(|| anyhow!("Missing column 'name'"))?;
+    let [
+        aggregate_idx,
+        description_idx,
+        triggered_by_idx,
+        actor_idx,
+        references_idx,
+    ] = [
+        "aggregate",
+        "description",
+        "triggered_by",
+        "actor",
+        "references",
+    ]
+    .map(find);
+
+    let mut board = EventStormingBoard::default();
+    for (offset, row) in rows.iter().enumerate().skip(1) {
+        let cell = |idx: Option<usize>| {
+            idx.and_then(|i| row.get(i))
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+        if row.iter().all(|c| c.trim().is_empty()) {
+            continue;
+        }
+        let name = cell(Some(name_idx)).unwrap_or_default();
+        let note_type = cell(Some(type_idx)).unwrap_or_default().to_lowercase();
+        match note_type.as_str() {
+            "aggregate" => board.aggregates.push(BoardAggregate {
+                name,
+                description: cell(description_idx),
+                references: cell(references_idx)
+                    .map(|r| {
+                        r.split(';')
+                            .map(|s| s.trim().to_string())
+                            .filter(|s| !s.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            }),
+            "command" => board.commands.push(BoardCommand {
+                name,
+                aggregate: cell(aggregate_idx),
+                actor: cell(actor_idx),
+                description: cell(description_idx),
+            }),
+            "event" | "domain_event" | "domain event" => board.events.push(BoardEvent {
+                name,
+                aggregate: cell(aggregate_idx),
+                triggered_by: cell(triggered_by_idx),
+                description: cell(description_idx),
+            }),
+            // Other sticky notes (policies, read models, hot spots...) don't map to entities
+            _ => tracing::debug!(
+                "Skipping '{}' note on row {} of event storming board",
+                note_type,
+                offset + 1
+            ),
+        }
+    }
+    Ok(board)
+}
+
+/// Identifier for a sticky-note label: `Order Placed` and `OrderPlaced` become `order_placed`.
+pub fn identifier(label: &str) -> String {
+    let mut name = String::new();
+    let mut previous: Option<char> = None;
+    for c in label.trim().chars() {
+        if c.is_alphanumeric() {
+            let word_start = c.is_uppercase()
+                && previous.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit());
+            if word_start && !name.ends_with('_') {
+                name.push('_');
+            }
+            name.extend(c.to_lowercase());
+        } else if !name.is_empty() && !name.ends_with('_') {
+            name.push('_');
+        }
+        previous = Some(c);
+    }
+    name.trim_end_matches('_').to_string()
+}
+
+fn note_error(error_type: &str, field: String, message: String) -> ParserError {
+    ParserError {
+        error_type: error_type.to_string(),
+        field: Some(field),
+        message,
+    }
+}
+
+/// Tables of the board under construction, looked up by identifier
+struct ModelBuilder {
+    tables: Vec<Table>,
+    relationships: Vec<Relationship>,
+    errors: Vec<ParserError>,
+}
+
+impl ModelBuilder {
+    fn find(&self, name: &str) -> Option<usize> {
+        self.tables.iter().position(|t| t.name == name)
+    }
+
+    fn kind_of(table: &Table) -> Option<&str> {
+        table
+            .odcl_metadata
+            .get(EVENT_STORMING_KEY)
+            .and_then(|m| m.get("kind"))
+            .and_then(|k| k.as_str())
+    }
+
+    /// Add a conceptual table for a sticky note, or None if its name is empty or taken.
+    fn add_table(
+        &mut self,
+        kind: &str,
+        label: &str,
+        description: Option<&str>,
+        columns: Vec<Column>,
+    ) -> Option<usize> {
+        let name = identifier(label);
+        if name.is_empty() {
+            self.errors.push(note_error(
+                "missing_name",
+                kind.to_string(),
+                format!("A {} has no name", kind),
+            ));
+            return None;
+        }
+        if let Some(existing) = self.find(&name) {
+            let existing_kind = Self::kind_of(&self.tables[existing]).unwrap_or("note");
+            self.errors.push(note_error(
+                "duplicate_name",
+                format!("{} {}", kind, label.trim()),
+                format!(
+                    "'{}' is already used by the {} '{}'",
+                    name, existing_kind, self.tables[existing].name
+                ),
+            ));
+            return None;
+        }
+        let mut table = Table::new(name, columns);
+        table.modeling_level = Some(ModelingLevel::Conceptual);
+        table.tags = vec![
+            EVENT_STORMING_TAG.to_string(),
+            format!("{}:{}", EVENT_STORMING_TAG, kind),
+        ];
+        if let Some(description) = description.filter(|d| !d.trim().is_empty()) {
+            table
+                .odcl_metadata
+                .insert("description".to_string(), json!(description.trim()));
+        }
+        if label.trim() != table.name {
+            table
+                .odcl_metadata
+                .insert("logicalName".to_string(), json!(label.trim()));
+        }
+        table
+            .odcl_metadata
+            .insert(EVENT_STORMING_KEY.to_string(), json!({ "kind": kind }));
+        self.tables.push(table);
+        Some(self.tables.len() - 1)
+    }
+
+    fn add_aggregate(&mut self, label: &str, description: Option<&str>) -> Option<usize> {
+        let mut id = Column::new("id".to_string(), "UUID".to_string());
+        id.primary_key = true;
+        id.nullable = false;
+        self.add_table("aggregate", label, description, vec![id])
+    }
+
+    /// The aggregate a command or event names, creating it if the board doesn't list it.
+    fn aggregate(&mut self, label: &str, named_by: &str) -> Option<usize> {
+        let name = identifier(label);
+        match self.find(&name) {
+            Some(index) if Self::kind_of(&self.tables[index]) == Some("aggregate") => Some(index),
+            Some(_) => {
+                self.errors.push(note_error(
+                    "unknown_aggregate",
+                    named_by.to_string(),
+                    format!("'{}' is not an aggregate", label.trim()),
+                ));
+                None
+            }
+            None => {
+                let index = self.add_aggregate(label, None)?;
+                self.errors.push(note_error(
+                    "implied_aggregate",
+                    named_by.to_string(),
+                    format!(
+                        "Aggregate '{}' is not on the board; created it",
+                        label.trim()
+                    ),
+                ));
+                Some(index)
+            }
+        }
+    }
+
+    /// Append to a list in an aggregate's event storming metadata
+    fn record(&mut self, aggregate: usize, list: &str, value: serde_json::Value) {
+        if let Some(metadata) = self.tables[aggregate]
+            .odcl_metadata
+            .get_mut(EVENT_STORMING_KEY)
+        {
+            match metadata[list].as_array_mut() {
+                Some(values) => values.push(value),
+                None => metadata[list] = json!([value]),
+            }
+        }
+    }
+
+    /// Many-to-one relationship from `source` to `target`, adding `<target>_id` to `source`.
+    fn reference(&mut self, source: usize, target: usize, notes: String) {
+        let column_name = format!("{}_id", self.tables[target].name);
+        let source_table = &mut self.tables[source];
+        if !source_table.columns.iter().any(|c| c.name == column_name) {
+            let mut column = Column::new(column_name.clone(), "UUID".to_string());
+            column.nullable = false;
+            column.column_order = source_table.columns.len() as i32;
+            source_table.columns.push(column);
+        }
+        let mut relationship = Relationship::new(self.tables[source].id, self.tables[target].id);
+        relationship.cardinality = Some(Cardinality::ManyToOne);
+        relationship.relationship_type = Some(RelationshipType::ForeignKey);
+        relationship.foreign_key_details = Some(ForeignKeyDetails::new(column_name, "id"));
+        relationship.notes = Some(notes);
+        self.relationships.push(relationship);
+    }
+}
+
+/// Build the conceptual model of a board.
+fn build_model(board: &EventStormingBoard) -> EventStormingImportResult {
+    let mut builder = ModelBuilder {
+        tables: Vec::new(),
+        relationships: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    for aggregate in &board.aggregates {
+        builder.add_aggregate(&aggregate.name, aggregate.description.as_deref());
+    }
+
+    for command in &board.commands {
+        let field = format!("command {}", command.name.trim());
+        let Some(label) = command
+            .aggregate
+            .as_deref()
+            .filter(|a| !a.trim().is_empty())
+        else {
+            builder.errors.push(note_error(
+                "unassigned_command",
+                field,
+                "Command names no aggregate; skipped".to_string(),
+            ));
+            continue;
+        };
+        if let Some(aggregate) = builder.aggregate(label, &field) {
+            let handled = HandledCommand {
+                name: command.name.trim().to_string(),
+                actor: command.actor.clone(),
+                description: command.description.clone(),
+            };
+            builder.record(aggregate, "commands", json!(handled));
+        }
+    }
+
+    for event in &board.events {
+        let field = format!("event {}", event.name.trim());
+        let aggregate = match event.aggregate.as_deref().filter(|a| !a.trim().is_empty()) {
+            Some(label) => builder.aggregate(label, &field),
+            None => {
+                builder.errors.push(note_error(
+                    "unassigned_event",
+                    field.clone(),
+                    "Event names no aggregate; created it without a relationship".to_string(),
+                ));
+                None
+            }
+        };
+        let mut id = Column::new("id".to_string(), "UUID".to_string());
+        id.primary_key = true;
+        id.nullable = false;
+        let mut occurred_at = Column::new("occurred_at".to_string(), "TIMESTAMP".to_string());
+        occurred_at.nullable = false;
+        occurred_at.column_order = 1;
+        let Some(index) = builder.add_table(
+            "event",
+            &event.name,
+            event.description.as_deref(),
+            vec![id, occurred_at],
+        ) else {
+            continue;
+        };
+        if let Some(command) = event
+            .triggered_by
+            .as_deref()
+            .filter(|c| !c.trim().is_empty())
+            && let Some(metadata) = builder.tables[index]
+                .odcl_metadata
+                .get_mut(EVENT_STORMING_KEY)
+        {
+            metadata["triggered_by"] = json!(command.trim());
+        }
+        if let Some(aggregate) = aggregate {
+            let event_name = builder.tables[index].name.clone();
+            builder.record(aggregate, "events", json!(event_name));
+            let notes = format!("Emitted by {}", builder.tables[aggregate].name);
+            builder.reference(index, aggregate, notes);
+        }
+    }
+
+    for aggregate in &board.aggregates {
+        let Some(source) = builder.find(&identifier(&aggregate.name)) else {
+            continue;
+        };
+        for reference in &aggregate.references {
+            let field = format!("aggregate {}", aggregate.name.trim());
+            if let Some(target) = builder.aggregate(reference, &field)
+                && target != source
+            {
+                let notes = format!("Referenced by {}", builder.tables[source].name);
+                builder.reference(source, target, notes);
+            }
+        }
+    }
+
+    EventStormingImportResult {
+        tables: builder.tables,
+        relationships: builder.relationships,
+        errors: builder.errors,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_board_becomes_conceptual_model() {
+        let json_board = r#"{
+            "aggregates": [
+                {"name": "Order", "description": "A customer's order", "references": ["Customer"]},
+                {"name": "Customer"}
+            ],
+            "commands": [
+                {"name": "Place Order", "aggregate": "Order", "actor": "Shopper"},
+                {"name": "Ship Order", "aggregate": "Shipment"},
+                {"name": "Refund"}
+            ],
+            "events": [
+                {"name": "OrderPlaced", "aggregate": "Order", "triggered_by": "Place Order"},
+                {"name": "Order Placed", "aggregate": "Order"},
+                {"name": "Payment Failed"}
+            ]
+        }"#;
+        let csv_board = "type,name,aggregate,references,actor,triggered_by\n\
+            aggregate,Order,,Customer,,\n\
+            aggregate,Customer,,,,\n\
+            command,Place Order,Order,,Shopper,\n\
+            command,Ship Order,Shipment,,,\n\
+            command,Refund,,,,\n\
+            event,OrderPlaced,Order,,,Place Order\n\
+            event,Order Placed,Order,,,\n\
+            event,Payment Failed,,,,\n\
+            policy,Notify shopper,,,,\n";
+
+        for (content, format) in [
+            (json_board, EventStormingFormat::Json),
+            (csv_board, EventStormingFormat::Csv),
+        ] {
+            assert_eq!(EventStormingFormat::detect(None, content), format);
+            let result = EventStormingParser::new().parse(content, format).unwrap();
+            let names: Vec<&str> = result.tables.iter().map(|t| t.name.as_str()).collect();
+            assert_eq!(
+                names,
+                vec![
+                    "order",
+                    "customer",
+                    "shipment",
+                    "order_placed",
+                    "payment_failed"
+                ]
+            );
+            assert!(result.tables.iter().all(|t| {
+                t.modeling_level == Some(ModelingLevel::Conceptual)
+                    && t.tags.contains(&EVENT_STORMING_TAG.to_string())
+            }));
+
+            let order = &result.tables[0];
+            let metadata = &order.odcl_metadata[EVENT_STORMING_KEY];
+            assert_eq!(
+                metadata["commands"],
+                json!([{"name": "Place Order", "actor": "Shopper"}])
+            );
+            assert_eq!(metadata["events"], json!(["order_placed"]));
+            assert!(order.columns.iter().any(|c| c.name == "customer_id"));
+
+            let placed = &result.tables[3];
+            assert_eq!(placed.tags[1], "event_storming:event");
+            assert_eq!(placed.odcl_metadata["logicalName"], json!("OrderPlaced"));
+            assert_eq!(
+                placed.odcl_metadata[EVENT_STORMING_KEY]["triggered_by"],
+                json!("Place Order")
+            );
+
+            let links: Vec<(Uuid, Uuid)> = result
+                .relationships
+                .iter()
+                .map(|r| (r.source_table_id, r.target_table_id))
+                .collect();
+            let id = |name| result.tables.iter().find(|t| t.name == name).unwrap().id;
+            assert_eq!(
+                links,
+                vec![
+                    (id("order_placed"), id("order")),
+                    (id("order"), id("customer"))
+                ]
+            );
+
+            let errors: Vec<&str> = result
+                .errors
+                .iter()
+                .map(|e| e.error_type.as_str())
+                .collect();
+            assert_eq!(
+                errors,
+                vec![
+                    "implied_aggregate",
+                    "unassigned_command",
+                    "duplicate_name",
+                    "unassigned_event"
+                ]
+            );
+        }
+
+        assert_eq!(identifier(" Order Line-Item "), "order_line_item");
+        assert!(
+            EventStormingParser::new()
+                .parse("name\nOrder\n", EventStormingFormat::Csv)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,214 @@
+//! Schema evolution policies.
+//!
+//! Each table can declare how its schema may evolve, following schema-registry compatibility
+//! modes:
+//! - `backward`: consumers using the new schema can read data written with the old one
+//!   (columns may be dropped; added columns must be nullable)
+//! - `forward`: consumers using the old schema can read data written with the new one
+//!   (columns may be added; only nullable columns may be dropped)
+//! - `full`: both
+//! - `none`: no checks (the default)
+//!
+//! Changing a column's type or nullability in a way the policy rules out is also a
+//! violation; changes are classified by the model diff engine (`diff_columns`). Policies are
+//! stored per domain in `evolution_policies.yaml`, keyed by table id.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::Column;
+use crate::services::model_diff_service::{ColumnChangeKind, diff_columns};
+
+/// File name of a domain's evolution policies
+pub const EVOLUTION_POLICIES_FILE: &str = "evolution_policies.yaml";
+
+/// How a table's schema may evolve
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EvolutionPolicy {
+    #[default]
+    None,
+    Backward,
+    Forward,
+    Full,
+}
+
+impl EvolutionPolicy {
+    fn backward(self) -> bool {
+        matches!(self, Self::Backward | Self::Full)
+    }
+
+    fn forward(self) -> bool {
+        matches!(self, Self::Forward | Self::Full)
+    }
+}
+
+/// Declared policies of a domain's tables (`evolution_policies.yaml`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EvolutionPolicies {
+    #[serde(default)]
+    pub tables: BTreeMap<Uuid, EvolutionPolicy>,
+}
+
+impl EvolutionPolicies {
+    fn path(domain_dir: &Path) -> PathBuf {
+        domain_dir.join(EVOLUTION_POLICIES_FILE)
+    }
+
+    /// Load a domain's policies (none declared if the file is missing).
+    pub fn load(domain_dir: &Path) -> Self {
+        std::fs::read_to_string(Self::path(domain_dir))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, domain_dir: &Path) -> Result<()> {
+        let path = Self::path(domain_dir);
+        std::fs::create_dir_all(domain_dir)
+            .with_context(|| format!("Failed to create {}", domain_dir.display()))?;
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, table_id: Uuid) -> EvolutionPolicy {
+        self.tables.get(&table_id).copied().unwrap_or_default()
+    }
+
+    /// Declare a table's policy; `none` removes the declaration.
+    pub fn set(&mut self, table_id: Uuid, policy: EvolutionPolicy) {
+        if policy == EvolutionPolicy::None {
+            self.tables.remove(&table_id);
+        } else {
+            self.tables.insert(table_id, policy);
+        }
+    }
+}
+
+/// A schema change the table's policy does not allow
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct CompatibilityViolation {
+    pub column: String,
+    pub message: String,
+}
+
+fn find<'a>(columns: &'a [Column], name: &str) -> Option<&'a Column> {
+    columns.iter().find(|c| c.name.eq_ignore_ascii_case(name))
+}
+
+/// Check a change of a table's columns from `before` to `after` against `policy`.
+pub fn check_compatibility(
+    policy: EvolutionPolicy,
+    before: &[Column],
+    after: &[Column],
+) -> Vec<CompatibilityViolation> {
+    let mut violations = Vec::new();
+    let mut violation = |column: &str, message: String| {
+        violations.push(CompatibilityViolation {
+            column: column.to_string(),
+            message,
+        })
+    };
+    let diff = diff_columns("", before, after);
+
+    for old in before {
+        if diff.removed_columns.contains(&old.name) {
+            if policy.forward() && !old.nullable {
+                violation(
+                    &old.name,
+                    format!(
+                        "Removing required column '{}' breaks forward compatibility",
+                        old.name
+                    ),
+                );
+            }
+            continue;
+        }
+        let changes = diff
+            .changed_columns
+            .iter()
+            .filter(|c| c.column.eq_ignore_ascii_case(&old.name));
+        for change in changes {
+            match change.kind {
+                ColumnChangeKind::TypeChanged if policy != EvolutionPolicy::None => violation(
+                    &old.name,
+                    format!(
+                        "Changing the type of '{}' from {} to {} is not compatible",
+                        old.name, change.before, change.after
+                    ),
+                ),
+                ColumnChangeKind::NullabilityTightened if policy.backward() => violation(
+                    &old.name,
+                    format!(
+                        "Making '{}' required breaks backward compatibility",
+                        old.name
+                    ),
+                ),
+                ColumnChangeKind::NullabilityRelaxed if policy.forward() => violation(
+                    &old.name,
+                    format!(
+                        "Making '{}' nullable breaks forward compatibility",
+                        old.name
+                    ),
+                ),
+                _ => {}
+            }
+        }
+    }
+
+    if policy.backward() {
+        let added = diff
+            .added_columns
+            .iter()
+            .filter_map(|name| find(after, name));
+        for new in added.filter(|c| !c.nullable) {
+            violation(
+                &new.name,
+                format!(
+                    "Adding required column '{}' breaks backward compatibility",
+                    new.name
+                ),
+            );
+        }
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, data_type: &str, nullable: bool) -> Column {
+        let mut column = Column::new(name.to_string(), data_type.to_string());
+        column.nullable = nullable;
+        column
+    }
+
+    #[test]
+    fn test_policies_check_the_right_direction() {
+        let before = vec![column("id", "INT", false), column("email", "TEXT", true)];
+        // Drops a nullable column and adds a required one
+        let after = vec![column("id", "int", false), column("name", "TEXT", false)];
+
+        assert!(check_compatibility(EvolutionPolicy::None, &before, &after).is_empty());
+        assert!(check_compatibility(EvolutionPolicy::Forward, &before, &after).is_empty());
+        let backward = check_compatibility(EvolutionPolicy::Backward, &before, &after);
+        assert_eq!(backward.len(), 1);
+        assert_eq!(backward[0].column, "name");
+
+        // Drops a required column and changes a type
+        let after = vec![column("email", "VARCHAR", true)];
+        let full = check_compatibility(EvolutionPolicy::Full, &before, &after);
+        let columns: Vec<_> = full.iter().map(|v| v.column.as_str()).collect();
+        assert_eq!(columns, vec!["id", "email"]);
+        assert_eq!(
+            check_compatibility(EvolutionPolicy::Backward, &before, &after).len(),
+            1
+        );
+    }
+}
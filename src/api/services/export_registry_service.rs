@@ -0,0 +1,306 @@
+//! Export artifact registry.
+//!
+//! Every domain export is recorded with its format, the exported tables and options, the
+//! SHA-256 of the delivered content, who requested it and when, so teams can show exactly
+//! which contract version was delivered to a consumer. The registry is stored per domain in
+//! `exports.yaml` and the artifacts under `.exports/` in the domain directory, from where a
+//! previous export can be downloaded again byte for byte.
+//!
+//! The response body is copied to the artifact file while it is streamed to the client. An
+//! export is only registered once its whole body has been sent; failed or interrupted
+//! exports leave nothing behind.
+
+use anyhow::{Context, Result};
+use axum::body::{Body, Bytes};
+use chrono::{DateTime, Utc};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tokio::io::AsyncWriteExt;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// File holding a domain's export registry
+pub const EXPORT_REGISTRY_FILE: &str = "exports.yaml";
+
+/// Directory of the domain holding export artifacts
+const ARTIFACT_DIR: &str = ".exports";
+
+/// Serializes registry updates of concurrent exports
+static REGISTRY_LOCK: Mutex<()> = Mutex::new(());
+
+/// A recorded export
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ExportArtifact {
+    pub id: Uuid,
+    pub format: String,
+    /// Ids of the exported tables
+    pub table_ids: Vec<Uuid>,
+    /// Export options, e.g. `dialect`, `format` (ODCS variant) or `environment`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub options: BTreeMap<String, String>,
+    pub filename: String,
+    pub content_type: String,
+    pub size: u64,
+    /// Hex SHA-256 of the content
+    pub sha256: String,
+    pub exported_by: String,
+    pub exported_at: DateTime<Utc>,
+}
+
+/// All recorded exports of a domain (`exports.yaml`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportRegistry {
+    #[serde(default)]
+    pub artifacts: Vec<ExportArtifact>,
+}
+
+impl ExportRegistry {
+    /// Load the registry from a domain directory, returning an empty one if none is stored.
+    pub fn load(domain_dir: &Path) -> Self {
+        std::fs::read_to_string(domain_dir.join(EXPORT_REGISTRY_FILE))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self, domain_dir: &Path) -> Result<()> {
+        let path = domain_dir.join(EXPORT_REGISTRY_FILE);
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, id: Uuid) -> Option<&ExportArtifact> {
+        self.artifacts.iter().find(|a| a.id == id)
+    }
+
+    /// Exports newest first, optionally only those containing a table or of one format
+    pub fn history(&self, table_id: Option<Uuid>, format: Option<&str>) -> Vec<ExportArtifact> {
+        let mut artifacts: Vec<ExportArtifact> = self
+            .artifacts
+            .iter()
+            .filter(|a| table_id.is_none_or(|id| a.table_ids.contains(&id)))
+            .filter(|a| format.is_none_or(|f| a.format == f))
+            .cloned()
+            .collect();
+        artifacts.sort_by_key(|a| std::cmp::Reverse(a.exported_at));
+        artifacts
+    }
+}
+
+/// Path of an artifact's content in a domain directory
+pub fn artifact_path(domain_dir: &Path, id: Uuid) -> PathBuf {
+    domain_dir.join(ARTIFACT_DIR).join(id.to_string())
+}
+
+/// What is being exported, recorded with the artifact
+#[derive(Debug, Clone)]
+pub struct ExportRequest {
+    pub format: String,
+    pub table_ids: Vec<Uuid>,
+    pub options: BTreeMap<String, String>,
+    pub filename: String,
+    pub content_type: String,
+    pub exported_by: String,
+}
+
+/// Copies an export body to its artifact file and registers it when the body is complete.
+///
+/// Dropping it without [`ArtifactWriter::finish`] (failed or disconnected export) removes
+/// the partial file.
+struct ArtifactWriter {
+    domain_dir: PathBuf,
+    part_path: PathBuf,
+    file: Option<tokio::fs::File>,
+    hasher: Sha256,
+    size: u64,
+    artifact: ExportArtifact,
+}
+
+impl ArtifactWriter {
+    fn create(domain_dir: &Path, request: ExportRequest) -> Result<Self> {
+        let dir = domain_dir.join(ARTIFACT_DIR);
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        let id = Uuid::new_v4();
+        let part_path = dir.join(format!("{}.part", id));
+        let file = std::fs::File::create(&part_path)
+            .with_context(|| format!("Failed to create {}", part_path.display()))?;
+
+        Ok(Self {
+            domain_dir: domain_dir.to_path_buf(),
+            part_path,
+            file: Some(tokio::fs::File::from_std(file)),
+            hasher: Sha256::new(),
+            size: 0,
+            artifact: ExportArtifact {
+                id,
+                format: request.format,
+                table_ids: request.table_ids,
+                options: request.options,
+                filename: request.filename,
+                content_type: request.content_type,
+                size: 0,
+                sha256: String::new(),
+                exported_by: request.exported_by,
+                exported_at: Utc::now(),
+            },
+        })
+    }
+
+    async fn write(&mut self, chunk: &Bytes) -> std::io::Result<()> {
+        if let Some(file) = self.file.as_mut() {
+            file.write_all(chunk).await?;
+        }
+        self.hasher.update(chunk);
+        self.size += chunk.len() as u64;
+        Ok(())
+    }
+
+    async fn finish(mut self) -> Result<ExportArtifact> {
+        let mut file = self.file.take().context("Artifact file already closed")?;
+        file.flush().await?;
+        drop(file);
+
+        let mut artifact = self.artifact.clone();
+        artifact.size = self.size;
+        artifact.sha256 = format!("{:x}", std::mem::take(&mut self.hasher).finalize());
+        let path = artifact_path(&self.domain_dir, artifact.id);
+        std::fs::rename(&self.part_path, &path)
+            .with_context(|| format!("Failed to store {}", path.display()))?;
+
+        let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let mut registry = ExportRegistry::load(&self.domain_dir);
+        registry.artifacts.push(artifact.clone());
+        if let Err(e) = registry.save(&self.domain_dir) {
+            let _ = std::fs::remove_file(&path);
+            return Err(e);
+        }
+        Ok(artifact)
+    }
+}
+
+impl Drop for ArtifactWriter {
+    fn drop(&mut self) {
+        // Already renamed if the artifact was stored
+        let _ = std::fs::remove_file(&self.part_path);
+    }
+}
+
+/// Record an export of a domain while its body is streamed.
+///
+/// Returns the id the artifact will be registered under and the body to send in place of
+/// `body`. If the artifact cannot be created, `body` is returned unchanged without an id.
+pub fn record_export(
+    domain_dir: &Path,
+    request: ExportRequest,
+    body: Body,
+) -> (Option<Uuid>, Body) {
+    let writer = match ArtifactWriter::create(domain_dir, request) {
+        Ok(writer) => writer,
+        Err(e) => {
+            warn!("Failed to record export: {}", e);
+            return (None, body);
+        }
+    };
+    let id = writer.artifact.id;
+    let stream = futures_util::stream::unfold(
+        (body.into_data_stream(), Some(writer)),
+        |(mut stream, mut writer)| async move {
+            match stream.next().await {
+                Some(Ok(chunk)) => {
+                    if let Some(w) = writer.as_mut()
+                        && let Err(e) = w.write(&chunk).await
+                    {
+                        warn!("Failed to write export artifact: {}", e);
+                        writer = None;
+                    }
+                    Some((Ok(chunk), (stream, writer)))
+                }
+                // Dropping the writer discards the partial artifact
+                Some(Err(e)) => Some((Err(e), (stream, None))),
+                None => {
+                    if let Some(w) = writer {
+                        match w.finish().await {
+                            Ok(artifact) => info!(
+                                "Recorded {} export {} ({} bytes, sha256 {})",
+                                artifact.format, artifact.id, artifact.size, artifact.sha256
+                            ),
+                            Err(e) => warn!("Failed to record export artifact: {}", e),
+                        }
+                    }
+                    None
+                }
+            }
+        },
+    );
+    (Some(id), Body::from_stream(stream))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::export_stream_service::stream_body;
+    use axum::body::to_bytes;
+    use std::io::Write;
+
+    fn request(table_id: Uuid) -> ExportRequest {
+        ExportRequest {
+            format: "sql".to_string(),
+            table_ids: vec![table_id],
+            options: BTreeMap::from([("dialect".to_string(), "postgres".to_string())]),
+            filename: "sales.sql".to_string(),
+            content_type: "text/plain".to_string(),
+            exported_by: "user@example.com".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_export_registers_complete_artifacts_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let table_id = Uuid::new_v4();
+
+        let (id, body) =
+            record_export(dir.path(), request(table_id), Body::from("CREATE TABLE t;"));
+        let id = id.unwrap();
+        assert!(ExportRegistry::load(dir.path()).get(id).is_none());
+        assert_eq!(to_bytes(body, usize::MAX).await.unwrap(), "CREATE TABLE t;");
+
+        let registry = ExportRegistry::load(dir.path());
+        let artifact = registry.get(id).unwrap();
+        assert_eq!(artifact.size, 15);
+        assert_eq!(
+            artifact.sha256,
+            format!("{:x}", Sha256::digest(b"CREATE TABLE t;"))
+        );
+        assert_eq!(
+            std::fs::read(artifact_path(dir.path(), id)).unwrap(),
+            b"CREATE TABLE t;"
+        );
+        assert_eq!(registry.history(Some(table_id), Some("sql")).len(), 1);
+        assert!(registry.history(Some(Uuid::new_v4()), None).is_empty());
+
+        // A failed export is neither registered nor kept
+        let failing = stream_body(|out| {
+            out.write_all(b"partial")?;
+            Err(std::io::Error::other("renderer failed"))
+        });
+        let (failed, body) = record_export(dir.path(), request(table_id), failing);
+        assert!(to_bytes(body, usize::MAX).await.is_err());
+        assert!(
+            ExportRegistry::load(dir.path())
+                .get(failed.unwrap())
+                .is_none()
+        );
+        assert_eq!(
+            std::fs::read_dir(dir.path().join(ARTIFACT_DIR))
+                .unwrap()
+                .count(),
+            1
+        );
+    }
+}
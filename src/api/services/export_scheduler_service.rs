@@ -0,0 +1,1125 @@
+//! Scheduled export publishing.
+//!
+//! Users configure recurring jobs (cron expressions, UTC) that render an export bundle,
+//! e.g. ODCS contracts plus dbt sources, and publish it to a git repository, an S3 bucket
+//! or a webhook. Schedules and their run history are stored per domain in
+//! `export-schedules.yaml`.
+//!
+//! Publishing is diff-aware: each schedule keeps a content hash per table for its target and
+//! only regenerates per-table artifacts (ODCS contracts) of tables that changed since the last
+//! successful publish, deleting those of removed tables. Model-wide files (dbt sources, SQL, …)
+//! are only rewritten when some table changed. Runs without changes are skipped unless forced.
+//!
+//! Target credentials:
+//! - Git: the server's `EXPORT_GIT_USERNAME` + `EXPORT_GIT_TOKEN` (HTTPS remotes) or
+//!   `EXPORT_GIT_SSH_KEY_PATH` (SSH remotes) are only used for hosts listed in
+//!   `EXPORT_ALLOWED_GIT_HOSTS`; other remotes must be HTTPS and the schedule must carry its
+//!   own `credentials.username` and `credentials.token`
+//! - S3: the server's `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and optional
+//!   `AWS_SESSION_TOKEN` are only used for buckets listed in `EXPORT_ALLOWED_S3_BUCKETS`
+//!   (`bucket`, or `endpoint-host/bucket` with a custom `endpoint`); other buckets need the
+//!   schedule's own access key. Requests are SigV4 signed, so S3-compatible stores work via
+//!   `endpoint`
+//! - Webhook: the bundle is `POST`ed as a ZIP archive to an `https://` URL on a public address
+//!
+//! Secrets stored with a schedule are never returned by the API (see
+//! [`ExportSchedule::redacted`]).
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{DateTime, Utc};
+use cron::Schedule;
+use data_modelling_sdk::git::{GitCredentials, GitService as SdkGitService};
+use hmac::{Hmac, Mac};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::env;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::{DataModel, Table};
+use crate::services::export_service::ExportService;
+use crate::services::outbound_http_service::{check_public_url, public_client};
+
+/// Export formats that can be part of a scheduled bundle
+pub const BUNDLE_FORMATS: &[&str] = &[
+    "odcl",
+    "dbt_sources",
+    "json_schema",
+    "avro",
+    "protobuf",
+    "sql",
+    "prisma",
+    "docs",
+    "png",
+];
+
+/// Runs kept per schedule; older runs are dropped from the history
+const MAX_RUNS_PER_SCHEDULE: usize = 50;
+
+fn default_formats() -> Vec<String> {
+    vec!["odcl".to_string(), "dbt_sources".to_string()]
+}
+
+fn default_branch() -> String {
+    "main".to_string()
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Lowercased entries of a comma-separated allowlist environment variable
+fn allowlist(name: &str) -> Vec<String> {
+    env::var(name)
+        .unwrap_or_default()
+        .split(',')
+        .map(|entry| entry.trim().to_lowercase())
+        .filter(|entry| !entry.is_empty())
+        .collect()
+}
+
+/// Host of an HTTPS (`https://host/…`) or SSH (`ssh://user@host/…`, `git@host:…`) remote
+fn git_remote_host(remote: &str) -> Option<String> {
+    let remote = remote.trim();
+    if let Ok(url) = Url::parse(remote)
+        && let Some(host) = url.host_str()
+    {
+        return Some(host.to_lowercase());
+    }
+    // scp-like syntax: [user@]host:path
+    let (authority, _) = remote.split_once(':')?;
+    let host = authority.rsplit('@').next()?;
+    (!host.is_empty()).then(|| host.to_lowercase())
+}
+
+/// Where a bundle is published
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ExportTarget {
+    /// Commit the bundle to a git repository and push it
+    Git {
+        /// HTTPS or SSH remote URL
+        remote_url: String,
+        #[serde(default = "default_branch")]
+        branch: String,
+        /// Directory inside the repository to write the bundle to
+        #[serde(default)]
+        path: String,
+    },
+    /// Upload each bundle file to an S3 bucket
+    S3 {
+        bucket: String,
+        region: String,
+        /// Key prefix for uploaded files
+        #[serde(default)]
+        prefix: String,
+        /// Custom endpoint for S3-compatible stores (path-style addressing)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        endpoint: Option<String>,
+    },
+    /// POST the bundle as a ZIP archive
+    Webhook {
+        /// `https://` URL on a public address
+        url: String,
+    },
+}
+
+/// Credentials a schedule publishes with when its target isn't allowlisted for the server's
+/// own. Secrets (`token`, `secret_access_key`, `session_token`) are write-only.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TargetCredentials {
+    /// Git HTTPS username
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    /// Git HTTPS token or password
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// S3 access key ID
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub access_key_id: Option<String>,
+    /// S3 secret access key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret_access_key: Option<String>,
+    /// S3 session token of temporary credentials
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_token: Option<String>,
+}
+
+impl ExportTarget {
+    /// Reject targets that cannot be published to.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            ExportTarget::Git {
+                remote_url, path, ..
+            } => {
+                let remote = remote_url.trim();
+                if !(remote.starts_with("https://")
+                    || remote.starts_with("ssh://")
+                    || remote.starts_with("git@"))
+                {
+                    bail!("Git remote must be an https:// or SSH URL");
+                }
+                if Path::new(path).is_absolute() || path.split('/').any(|s| s == "..") {
+                    bail!("Git path must be relative to the repository root");
+                }
+            }
+            ExportTarget::S3 {
+                bucket,
+                region,
+                endpoint,
+                ..
+            } => {
+                if bucket.trim().is_empty() || region.trim().is_empty() {
+                    bail!("S3 bucket and region are required");
+                }
+                // Both end up in the host name of the request
+                let valid = |name: &str, extra: &[char]| {
+                    name.chars()
+                        .all(|c| c.is_ascii_alphanumeric() || c == '-' || extra.contains(&c))
+                };
+                if !valid(bucket, &['.', '_']) || !valid(region, &[]) {
+                    bail!("Invalid S3 bucket or region name");
+                }
+                if let Some(endpoint) = endpoint {
+                    Url::parse(endpoint).context("Invalid S3 endpoint")?;
+                }
+            }
+            ExportTarget::Webhook { url } => {
+                check_public_url(url).context("Invalid webhook URL")?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the operator allows publishing to this target with the server's own credentials
+    pub fn server_credentials_allowed(&self) -> bool {
+        match self {
+            ExportTarget::Git { remote_url, .. } => git_remote_host(remote_url)
+                .is_some_and(|host| allowlist("EXPORT_ALLOWED_GIT_HOSTS").contains(&host)),
+            ExportTarget::S3 {
+                bucket, endpoint, ..
+            } => {
+                let entry = match endpoint {
+                    None => bucket.clone(),
+                    Some(endpoint) => {
+                        match Url::parse(endpoint)
+                            .ok()
+                            .and_then(|u| u.host_str().map(str::to_string))
+                        {
+                            Some(host) => format!("{}/{}", host, bucket),
+                            None => return false,
+                        }
+                    }
+                };
+                allowlist("EXPORT_ALLOWED_S3_BUCKETS").contains(&entry.to_lowercase())
+            }
+            ExportTarget::Webhook { .. } => false,
+        }
+    }
+}
+
+/// Content hash of a table as last published to a schedule's target
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct PublishedTable {
+    pub name: String,
+    pub hash: String,
+}
+
+/// A recurring export job
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportSchedule {
+    pub id: Uuid,
+    pub name: String,
+    /// Cron expression in UTC: `min hour day month weekday`, or with a leading seconds field
+    pub cron: String,
+    /// Export formats in the bundle
+    #[serde(default = "default_formats")]
+    pub formats: Vec<String>,
+    /// Environment profile applied before exporting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<String>,
+    pub target: ExportTarget,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Owner; runs use their workspace and failures notify them
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_run_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_run_at: Option<DateTime<Utc>>,
+    /// Tables as of the last successful publish, keyed by table id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub published_tables: BTreeMap<Uuid, PublishedTable>,
+    /// Credentials for targets not allowlisted for the server's own
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<TargetCredentials>,
+}
+
+impl ExportSchedule {
+    /// Validate the cron expression, formats and target, and compute the next run.
+    pub fn prepare(&mut self, now: DateTime<Utc>) -> Result<()> {
+        if self.name.trim().is_empty() {
+            bail!("Schedule name is required");
+        }
+        if self.formats.is_empty() {
+            bail!("At least one export format is required");
+        }
+        if let Some(format) = self
+            .formats
+            .iter()
+            .find(|f| !BUNDLE_FORMATS.contains(&f.as_str()))
+        {
+            bail!("Unsupported export format: {}", format);
+        }
+        self.target.validate()?;
+        self.check_credentials()?;
+        self.next_run_at = parse_cron(&self.cron)?.after(&now).next();
+        Ok(())
+    }
+
+    /// Require the schedule's own credentials for targets the server's may not be used for.
+    pub fn check_credentials(&self) -> Result<()> {
+        if self.target.server_credentials_allowed() {
+            return Ok(());
+        }
+        let credentials = self.credentials.clone().unwrap_or_default();
+        match &self.target {
+            ExportTarget::Git { remote_url, .. } => {
+                if !remote_url.trim().starts_with("https://") {
+                    bail!("SSH remotes must be on a host in EXPORT_ALLOWED_GIT_HOSTS");
+                }
+                if credentials.username.is_none() || credentials.token.is_none() {
+                    bail!(
+                        "Git host is not in EXPORT_ALLOWED_GIT_HOSTS; credentials.username and credentials.token are required"
+                    );
+                }
+            }
+            ExportTarget::S3 { .. } => {
+                if credentials.access_key_id.is_none() || credentials.secret_access_key.is_none() {
+                    bail!(
+                        "S3 bucket is not in EXPORT_ALLOWED_S3_BUCKETS; credentials.access_key_id and credentials.secret_access_key are required"
+                    );
+                }
+            }
+            ExportTarget::Webhook { .. } => {}
+        }
+        Ok(())
+    }
+
+    /// The schedule without its secrets, as returned by the API
+    pub fn redacted(mut self) -> Self {
+        if let Some(credentials) = &mut self.credentials {
+            credentials.token = None;
+            credentials.secret_access_key = None;
+            credentials.session_token = None;
+        }
+        self
+    }
+
+    fn git_credentials(&self) -> GitCredentials {
+        if self.target.server_credentials_allowed() {
+            return GitCredentials {
+                ssh_key_path: env::var("EXPORT_GIT_SSH_KEY_PATH").ok().map(PathBuf::from),
+                username: env::var("EXPORT_GIT_USERNAME").ok(),
+                token: env::var("EXPORT_GIT_TOKEN").ok(),
+            };
+        }
+        let credentials = self.credentials.clone().unwrap_or_default();
+        GitCredentials {
+            ssh_key_path: None,
+            username: credentials.username,
+            token: credentials.token,
+        }
+    }
+
+    fn aws_credentials(&self) -> Result<AwsCredentials> {
+        if self.target.server_credentials_allowed() {
+            return AwsCredentials::from_env();
+        }
+        let credentials = self.credentials.clone().unwrap_or_default();
+        Ok(AwsCredentials {
+            access_key_id: credentials
+                .access_key_id
+                .ok_or_else(|| anyhow!("credentials.access_key_id not set"))?,
+            secret_access_key: credentials
+                .secret_access_key
+                .ok_or_else(|| anyhow!("credentials.secret_access_key not set"))?,
+            session_token: credentials.session_token,
+        })
+    }
+
+    /// Whether the schedule should run at `now`
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.enabled && self.next_run_at.is_some_and(|next| next <= now)
+    }
+}
+
+/// Parse a 5-field (minute resolution) or 6/7-field (seconds, optional year) cron expression.
+pub fn parse_cron(expression: &str) -> Result<Schedule> {
+    let expression = expression.trim();
+    let normalized = if expression.split_whitespace().count() == 5 {
+        format!("0 {}", expression)
+    } else {
+        expression.to_string()
+    };
+    Schedule::from_str(&normalized).map_err(|e| anyhow!("Invalid cron expression: {}", e))
+}
+
+/// What started a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RunTrigger {
+    Scheduled,
+    Manual,
+}
+
+/// Outcome of a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RunStatus {
+    Succeeded,
+    /// No table changed since the last publish
+    Skipped,
+    Failed,
+}
+
+/// One execution of a schedule
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ExportRun {
+    pub id: Uuid,
+    pub schedule_id: Uuid,
+    pub trigger: RunTrigger,
+    pub status: RunStatus,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    /// Files in the published bundle
+    #[serde(default)]
+    pub files: Vec<String>,
+    /// Files deleted from the target because their tables were removed or renamed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed_files: Vec<String>,
+    /// Tables that changed since the last publish
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub changed_tables: Vec<String>,
+    /// Target-specific result, e.g. pushed branch or uploaded keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// All export schedules of a domain with their run history (`export-schedules.yaml`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportSchedules {
+    #[serde(default)]
+    pub schedules: Vec<ExportSchedule>,
+    #[serde(default)]
+    pub runs: Vec<ExportRun>,
+}
+
+impl ExportSchedules {
+    pub fn get(&self, id: Uuid) -> Option<&ExportSchedule> {
+        self.schedules.iter().find(|s| s.id == id)
+    }
+
+    /// Remove a schedule and its history; returns whether it existed.
+    pub fn remove(&mut self, id: Uuid) -> bool {
+        let before = self.schedules.len();
+        self.schedules.retain(|s| s.id != id);
+        self.runs.retain(|r| r.schedule_id != id);
+        self.schedules.len() != before
+    }
+
+    /// Runs of a schedule, newest first
+    pub fn runs_for(&self, id: Uuid) -> Vec<ExportRun> {
+        let mut runs: Vec<ExportRun> = self
+            .runs
+            .iter()
+            .filter(|r| r.schedule_id == id)
+            .cloned()
+            .collect();
+        runs.sort_by_key(|r| std::cmp::Reverse(r.started_at));
+        runs
+    }
+
+    /// Record a finished run and advance the schedule's next run time. `published` replaces
+    /// the schedule's table hashes after a successful publish.
+    pub fn record_run(
+        &mut self,
+        run: ExportRun,
+        published: Option<BTreeMap<Uuid, PublishedTable>>,
+    ) {
+        if let Some(schedule) = self.schedules.iter_mut().find(|s| s.id == run.schedule_id) {
+            schedule.last_run_at = Some(run.started_at);
+            if let Some(published) = published {
+                schedule.published_tables = published;
+            }
+            schedule.next_run_at = parse_cron(&schedule.cron)
+                .ok()
+                .and_then(|c| c.after(&run.finished_at).next());
+        }
+        let schedule_id = run.schedule_id;
+        self.runs.push(run);
+        let mut history = self
+            .runs
+            .iter()
+            .filter(|r| r.schedule_id == schedule_id)
+            .count();
+        self.runs.retain(|r| {
+            if r.schedule_id == schedule_id && history > MAX_RUNS_PER_SCHEDULE {
+                history -= 1;
+                return false;
+            }
+            true
+        });
+    }
+}
+
+/// A rendered file of an export bundle
+#[derive(Debug, Clone)]
+pub struct BundleFile {
+    pub path: String,
+    pub content: Vec<u8>,
+}
+
+/// Files to publish for a run, computed against the last published table hashes
+#[derive(Debug, Clone, Default)]
+pub struct BundlePlan {
+    pub files: Vec<BundleFile>,
+    /// Bundle paths of per-table artifacts whose tables were removed or renamed
+    pub removed: Vec<String>,
+    /// Names of tables that are new or changed
+    pub changed_tables: Vec<String>,
+    /// Table hashes to store once the plan is published
+    pub published: BTreeMap<Uuid, PublishedTable>,
+}
+
+impl BundlePlan {
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// Plan a bundle: only tables whose content hash differs from `previous` (or all with `force`)
+/// are re-rendered. Returns an empty plan when nothing changed.
+pub fn plan_bundle(
+    model: &DataModel,
+    formats: &[String],
+    previous: &BTreeMap<Uuid, PublishedTable>,
+    force: bool,
+) -> Result<BundlePlan> {
+    let published: BTreeMap<Uuid, PublishedTable> = model
+        .tables
+        .iter()
+        .map(|table| {
+            let entry = PublishedTable {
+                name: table.name.clone(),
+                hash: table_hash(model, table),
+            };
+            (table.id, entry)
+        })
+        .collect();
+    let changed: Vec<&Table> = model
+        .tables
+        .iter()
+        .filter(|t| force || previous.get(&t.id) != published.get(&t.id))
+        .collect();
+    let removed: Vec<String> = if formats.iter().any(|f| f == "odcl") {
+        previous
+            .iter()
+            .filter(|(id, old)| published.get(id).is_none_or(|new| new.name != old.name))
+            .map(|(_, old)| odcs_path(&old.name))
+            .collect()
+    } else {
+        Vec::new()
+    };
+    if changed.is_empty() && removed.is_empty() && !force {
+        return Ok(BundlePlan {
+            published,
+            ..Default::default()
+        });
+    }
+
+    let changed_ids: Vec<Uuid> = changed.iter().map(|t| t.id).collect();
+    Ok(BundlePlan {
+        files: build_bundle(model, formats, &changed_ids)?,
+        removed,
+        changed_tables: changed.iter().map(|t| t.name.clone()).collect(),
+        published,
+    })
+}
+
+/// Stable hash of a table's exported content and the relationships it takes part in.
+/// Layout and bookkeeping fields (timestamps, canvas position, file paths) are ignored.
+fn table_hash(model: &DataModel, table: &Table) -> String {
+    const IGNORED: &[&str] = &[
+        "created_at",
+        "updated_at",
+        "position",
+        "yaml_file_path",
+        "drawio_cell_id",
+    ];
+    let strip = |mut value: serde_json::Value| {
+        if let Some(object) = value.as_object_mut() {
+            object.retain(|key, _| !IGNORED.contains(&key.as_str()));
+        }
+        value
+    };
+    let relationships: Vec<serde_json::Value> = model
+        .relationships
+        .iter()
+        .filter(|r| r.source_table_id == table.id || r.target_table_id == table.id)
+        .filter_map(|r| serde_json::to_value(r).ok())
+        .map(strip)
+        .collect();
+    let content = serde_json::json!({
+        "table": serde_json::to_value(table).map(strip).unwrap_or_default(),
+        "relationships": relationships,
+    });
+    format!("{:x}", Sha256::digest(content.to_string().as_bytes()))
+}
+
+fn odcs_path(table_name: &str) -> String {
+    format!("odcs/{}.odcs.yaml", table_name)
+}
+
+/// Render the requested formats. ODCS contracts are written one file per table, for
+/// `odcs_tables` only; the other formats cover the whole model.
+fn build_bundle(
+    model: &DataModel,
+    formats: &[String],
+    odcs_tables: &[Uuid],
+) -> Result<Vec<BundleFile>> {
+    let mut files = Vec::new();
+    for format in formats {
+        if format == "odcl" {
+            if odcs_tables.is_empty() {
+                continue;
+            }
+            let mut contracts: Vec<_> =
+                ExportService::export_odcl(model, Some(odcs_tables), "odcs_v3_1_0")
+                    .into_iter()
+                    .collect();
+            contracts.sort();
+            files.extend(contracts.into_iter().map(|(table, yaml)| BundleFile {
+                path: odcs_path(&table),
+                content: yaml.into_bytes(),
+            }));
+            continue;
+        }
+        let exported = ExportService::export_format(model, format, None, None, None)
+            .map_err(|e| anyhow!("{} export failed: {}", format, e))?
+            .ok_or_else(|| anyhow!("Unsupported export format: {}", format))?;
+        let path = match format.as_str() {
+            "dbt_sources" => format!("dbt/{}", exported.filename),
+            _ => exported.filename,
+        };
+        files.push(BundleFile {
+            path,
+            content: exported.content,
+        });
+    }
+    Ok(files)
+}
+
+/// Pack bundle files into a ZIP archive.
+pub fn bundle_zip(files: &[BundleFile]) -> Result<Vec<u8>> {
+    let mut data = Vec::new();
+    {
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(&mut data));
+        let options =
+            zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+        for file in files {
+            zip.start_file(file.path.as_str(), options)?;
+            zip.write_all(&file.content)?;
+        }
+        zip.finish()?;
+    }
+    Ok(data)
+}
+
+/// Publish a bundle to its target, deleting `removed` paths, and describe the result.
+///
+/// `work_dir` is the schedule's git working copy (only used by git targets).
+pub async fn publish(
+    schedule: &ExportSchedule,
+    domain: &str,
+    files: Vec<BundleFile>,
+    removed: Vec<String>,
+    work_dir: PathBuf,
+) -> Result<String> {
+    // Allowlists may have changed since the schedule was saved
+    schedule.check_credentials()?;
+    match &schedule.target {
+        ExportTarget::Git {
+            remote_url,
+            branch,
+            path,
+        } => {
+            let (remote_url, branch, path) = (remote_url.clone(), branch.clone(), path.clone());
+            let message = format!("Scheduled export '{}' of domain {}", schedule.name, domain);
+            let author = schedule.created_by.clone();
+            let credentials = schedule.git_credentials();
+            tokio::task::spawn_blocking(move || {
+                publish_git(
+                    credentials,
+                    &remote_url,
+                    &branch,
+                    &path,
+                    &files,
+                    &removed,
+                    &work_dir,
+                    &message,
+                    &author,
+                )
+            })
+            .await?
+        }
+        ExportTarget::S3 {
+            bucket,
+            region,
+            prefix,
+            endpoint,
+        } => {
+            let credentials = schedule.aws_credentials()?;
+            // Operator-approved endpoints may be internal; others must be public
+            let client = match endpoint {
+                Some(endpoint) if !schedule.target.server_credentials_allowed() => {
+                    public_client(endpoint).await?
+                }
+                _ => reqwest::Client::new(),
+            };
+            let s3_key = |path: &str| {
+                [prefix.trim_matches('/'), path]
+                    .iter()
+                    .filter(|s| !s.is_empty())
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join("/")
+            };
+            for file in &files {
+                let key = s3_key(&file.path);
+                s3_request(
+                    &client,
+                    &credentials,
+                    reqwest::Method::PUT,
+                    bucket,
+                    region,
+                    endpoint.as_deref(),
+                    &key,
+                    file.content.clone(),
+                )
+                .await
+                .with_context(|| format!("Upload of {} failed", key))?;
+            }
+            for path in &removed {
+                let key = s3_key(path);
+                s3_request(
+                    &client,
+                    &credentials,
+                    reqwest::Method::DELETE,
+                    bucket,
+                    region,
+                    endpoint.as_deref(),
+                    &key,
+                    Vec::new(),
+                )
+                .await
+                .with_context(|| format!("Delete of {} failed", key))?;
+            }
+            Ok(format!(
+                "Uploaded {} and deleted {} file(s) in s3://{}",
+                files.len(),
+                removed.len(),
+                bucket
+            ))
+        }
+        ExportTarget::Webhook { url } => {
+            let response = public_client(url)
+                .await?
+                .post(url)
+                .header("Content-Type", "application/zip")
+                .header("X-Export-Schedule", schedule.id.to_string())
+                .header("X-Export-Domain", domain)
+                .header("X-Export-Removed-Files", removed.join(","))
+                .body(bundle_zip(&files)?)
+                .send()
+                .await?
+                .error_for_status()?;
+            Ok(format!("Webhook responded {}", response.status()))
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn publish_git(
+    credentials: GitCredentials,
+    remote_url: &str,
+    branch: &str,
+    path: &str,
+    files: &[BundleFile],
+    removed: &[String],
+    work_dir: &Path,
+    message: &str,
+    author_email: &str,
+) -> Result<String> {
+    let mut git = SdkGitService::with_credentials(credentials);
+    if work_dir.join(".git").exists() {
+        git.open_or_init(work_dir)?;
+        if !git.pull("origin", branch)? {
+            bail!("Merge conflicts while updating {}", remote_url);
+        }
+    } else {
+        git.clone_repository(remote_url, work_dir, Some(branch))?;
+    }
+
+    let root = work_dir.join(path);
+    for file in files {
+        let target = root.join(&file.path);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&target, &file.content)?;
+    }
+    if !removed.is_empty()
+        && let Some(repo) = git.repository()
+    {
+        let mut index = repo.index()?;
+        for removed_path in removed {
+            let relative = Path::new(path).join(removed_path);
+            let absolute = work_dir.join(&relative);
+            if absolute.exists() {
+                std::fs::remove_file(&absolute)?;
+                index.remove_path(&relative)?;
+            }
+        }
+        index.write()?;
+    }
+    if !git.status()?.has_changes {
+        return Ok(format!("No changes to push to {} ({})", remote_url, branch));
+    }
+    git.commit_all(message, "Data Modelling Scheduler", author_email)?;
+    git.push("origin", branch)?;
+    Ok(format!("Pushed to {} ({})", remote_url, branch))
+}
+
+pub(crate) struct AwsCredentials {
+    access_key_id: String,
+    secret_access_key: String,
+    session_token: Option<String>,
+}
+
+impl AwsCredentials {
+    pub(crate) fn from_env() -> Result<Self> {
+        Ok(Self {
+            access_key_id: env::var("AWS_ACCESS_KEY_ID")
+                .map_err(|_| anyhow!("AWS_ACCESS_KEY_ID not set"))?,
+            secret_access_key: env::var("AWS_SECRET_ACCESS_KEY")
+                .map_err(|_| anyhow!("AWS_SECRET_ACCESS_KEY not set"))?,
+            session_token: env::var("AWS_SESSION_TOKEN").ok(),
+        })
+    }
+}
+
+/// Send a SigV4-signed object request (`PUT` upload, `GET` download or `DELETE`) and
+/// return the response body.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn s3_request(
+    client: &reqwest::Client,
+    credentials: &AwsCredentials,
+    method: reqwest::Method,
+    bucket: &str,
+    region: &str,
+    endpoint: Option<&str>,
+    key: &str,
+    body: Vec<u8>,
+) -> Result<Vec<u8>> {
+    let encoded_key: Vec<String> = key.split('/').map(uri_encode).collect();
+    let url = match endpoint {
+        Some(endpoint) => format!(
+            "{}/{}/{}",
+            endpoint.trim_end_matches('/'),
+            bucket,
+            encoded_key.join("/")
+        ),
+        None => format!(
+            "https://{}.s3.{}.amazonaws.com/{}",
+            bucket,
+            region,
+            encoded_key.join("/")
+        ),
+    };
+    let parsed = Url::parse(&url)?;
+    let host = match parsed.port() {
+        Some(port) => format!("{}:{}", parsed.host_str().unwrap_or_default(), port),
+        None => parsed.host_str().unwrap_or_default().to_string(),
+    };
+
+    let now = Utc::now();
+    let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+    let payload_hash = format!("{:x}", Sha256::digest(&body));
+
+    let mut headers = vec![
+        ("host", host),
+        ("x-amz-content-sha256", payload_hash.clone()),
+        ("x-amz-date", amz_date.clone()),
+    ];
+    if let Some(token) = &credentials.session_token {
+        headers.push(("x-amz-security-token", token.clone()));
+    }
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_headers = headers
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(";");
+    let canonical_request = format!(
+        "{}\n{}\n\n{}\n{}\n{}",
+        method,
+        parsed.path(),
+        canonical_headers,
+        signed_headers,
+        payload_hash
+    );
+    let scope = format!("{}/{}/s3/aws4_request", date, region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{:x}",
+        amz_date,
+        scope,
+        Sha256::digest(canonical_request.as_bytes())
+    );
+    let signing_key = [region, "s3", "aws4_request"].iter().fold(
+        hmac_sha256(
+            format!("AWS4{}", credentials.secret_access_key).as_bytes(),
+            &date,
+        ),
+        |key, part| hmac_sha256(&key, part),
+    );
+    let signature: String = hmac_sha256(&signing_key, &string_to_sign)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect();
+
+    let mut request = client.request(method, parsed).body(body).header(
+        "Authorization",
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+            credentials.access_key_id, scope, signed_headers, signature
+        ),
+    );
+    for (name, value) in headers.into_iter().filter(|(name, _)| *name != "host") {
+        request = request.header(name, value);
+    }
+    let response = request.send().await?.error_for_status()?;
+    Ok(response.bytes().await?.to_vec())
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+/// Percent-encode a path segment as required by SigV4 (RFC 3986 unreserved characters kept).
+fn uri_encode(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_schedule_next_run_and_history() {
+        let now = Utc.with_ymd_and_hms(2026, 3, 1, 10, 30, 0).unwrap();
+        let mut schedule = ExportSchedule {
+            id: Uuid::new_v4(),
+            name: "nightly".to_string(),
+            cron: "0 2 * * *".to_string(),
+            formats: default_formats(),
+            environment: None,
+            target: ExportTarget::Webhook {
+                url: "https://example.com/hook".to_string(),
+            },
+            enabled: true,
+            created_by: "a@b.c".to_string(),
+            created_at: now,
+            last_run_at: None,
+            next_run_at: None,
+            published_tables: BTreeMap::new(),
+            credentials: None,
+        };
+        schedule.prepare(now).unwrap();
+        let next = Utc.with_ymd_and_hms(2026, 3, 2, 2, 0, 0).unwrap();
+        assert_eq!(schedule.next_run_at, Some(next));
+        assert!(!schedule.is_due(now));
+        assert!(schedule.is_due(next));
+
+        let mut invalid = schedule.clone();
+        invalid.cron = "every night".to_string();
+        assert!(invalid.prepare(now).is_err());
+        invalid.cron = "0 2 * * *".to_string();
+        invalid.formats = vec!["xlsx".to_string()];
+        assert!(invalid.prepare(now).is_err());
+
+        let mut schedules = ExportSchedules {
+            schedules: vec![schedule.clone()],
+            runs: Vec::new(),
+        };
+        for _ in 0..MAX_RUNS_PER_SCHEDULE + 5 {
+            schedules.record_run(
+                ExportRun {
+                    id: Uuid::new_v4(),
+                    schedule_id: schedule.id,
+                    trigger: RunTrigger::Scheduled,
+                    status: RunStatus::Succeeded,
+                    started_at: next,
+                    finished_at: next,
+                    files: Vec::new(),
+                    removed_files: Vec::new(),
+                    changed_tables: Vec::new(),
+                    detail: None,
+                    error: None,
+                },
+                None,
+            );
+        }
+        assert_eq!(schedules.runs_for(schedule.id).len(), MAX_RUNS_PER_SCHEDULE);
+        assert_eq!(
+            schedules.get(schedule.id).unwrap().next_run_at,
+            Some(Utc.with_ymd_and_hms(2026, 3, 3, 2, 0, 0).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_target_credentials_and_webhook_checks() {
+        let now = Utc::now();
+        let schedule =
+            |target: ExportTarget, credentials: Option<TargetCredentials>| ExportSchedule {
+                id: Uuid::new_v4(),
+                name: "nightly".to_string(),
+                cron: "0 2 * * *".to_string(),
+                formats: default_formats(),
+                environment: None,
+                target,
+                enabled: true,
+                created_by: "a@b.c".to_string(),
+                created_at: now,
+                last_run_at: None,
+                next_run_at: None,
+                published_tables: BTreeMap::new(),
+                credentials,
+            };
+        let git = |remote: &str| ExportTarget::Git {
+            remote_url: remote.to_string(),
+            branch: default_branch(),
+            path: String::new(),
+        };
+        let token = TargetCredentials {
+            username: Some("bot".to_string()),
+            token: Some("secret".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            git_remote_host("git@GitHub.com:org/repo.git").as_deref(),
+            Some("github.com")
+        );
+        assert_eq!(
+            git_remote_host("https://gitlab.example.com/org/repo.git").as_deref(),
+            Some("gitlab.example.com")
+        );
+
+        // Hosts that aren't allowlisted never get the server's credentials
+        let remote = "https://attacker.example.com/repo.git";
+        assert!(!git(remote).server_credentials_allowed());
+        assert!(schedule(git(remote), None).prepare(now).is_err());
+        assert!(
+            schedule(
+                git("git@attacker.example.com:repo.git"),
+                Some(token.clone())
+            )
+            .prepare(now)
+            .is_err()
+        );
+        let mut own = schedule(git(remote), Some(token));
+        own.prepare(now).unwrap();
+        assert_eq!(own.git_credentials().token.as_deref(), Some("secret"));
+        assert!(own.git_credentials().ssh_key_path.is_none());
+        let redacted = own.redacted().credentials.unwrap();
+        assert_eq!(redacted.username.as_deref(), Some("bot"));
+        assert_eq!(redacted.token, None);
+
+        let s3 = |bucket: &str| ExportTarget::S3 {
+            bucket: bucket.to_string(),
+            region: "eu-west-1".to_string(),
+            prefix: String::new(),
+            endpoint: None,
+        };
+        assert!(schedule(s3("exports"), None).prepare(now).is_err());
+        assert!(s3("evil.com/#").validate().is_err());
+
+        let webhook = |url: &str| ExportTarget::Webhook {
+            url: url.to_string(),
+        };
+        assert!(webhook("https://hooks.example.com/x").validate().is_ok());
+        assert!(webhook("http://hooks.example.com/x").validate().is_err());
+        assert!(webhook("https://169.254.169.254/x").validate().is_err());
+    }
+
+    #[test]
+    fn test_plan_bundle_only_changed_tables() {
+        use crate::models::{Column, Table};
+
+        let mut model = DataModel::new("sales".to_string(), String::new(), String::new());
+        let id_column = || Column::new("id".to_string(), "INT".to_string());
+        model.tables = vec![
+            Table::new("orders".to_string(), vec![id_column()]),
+            Table::new("customers".to_string(), vec![id_column()]),
+        ];
+        let formats = vec!["odcl".to_string(), "dbt_sources".to_string()];
+
+        let first = plan_bundle(&model, &formats, &BTreeMap::new(), false).unwrap();
+        assert_eq!(first.changed_tables.len(), 2);
+        assert!(first.files.iter().any(|f| f.path == "dbt/sources.yml"));
+
+        let unchanged = plan_bundle(&model, &formats, &first.published, false).unwrap();
+        assert!(unchanged.is_empty());
+        let forced = plan_bundle(&model, &formats, &first.published, true).unwrap();
+        assert_eq!(forced.changed_tables.len(), 2);
+
+        model.tables[0].updated_at = Utc::now();
+        model.tables[1].name = "clients".to_string();
+        let renamed = plan_bundle(&model, &formats, &first.published, false).unwrap();
+        assert_eq!(renamed.changed_tables, vec!["clients".to_string()]);
+        assert_eq!(
+            renamed.removed,
+            vec!["odcs/customers.odcs.yaml".to_string()]
+        );
+        assert!(
+            renamed
+                .files
+                .iter()
+                .all(|f| f.path != "odcs/orders.odcs.yaml")
+        );
+    }
+}
@@ -3,17 +3,126 @@
 //! Provides multi-format export functionality.
 //! Uses SDK exporters to avoid code duplication.
 
-use crate::models::DataModel;
-use crate::services::table_converter::{api_datamodel_to_sdk_datamodel, api_table_to_sdk_table};
+use crate::models::{DataModel, MaskType, RowPolicyCommand};
+use crate::services::table_converter::api_table_to_sdk_table;
+use crate::services::temporal_service::history_table_name;
 use data_modelling_sdk::export::{AvroExporter, JSONSchemaExporter, ODCSExporter, SQLExporter};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Write;
 use uuid::Uuid;
 
 /// Export service wrapper around local exporters
 pub struct ExportService;
 
+/// A rendered export ready to be returned or written to disk.
+pub struct ExportedFile {
+    pub content: Vec<u8>,
+    pub content_type: &'static str,
+    pub filename: String,
+}
+
 impl ExportService {
+    /// Export model to a named format
+    /// (json_schema, avro, protobuf, sql, sql-queries, prisma, docs, dbt_sources, mermaid, dbml,
+    /// odcl, png).
+    ///
+    /// Returns `Ok(None)` for an unknown format. `odcl_format` selects the ODCS variant.
+    pub fn export_format(
+        model: &DataModel,
+        format: &str,
+        table_ids: Option<&[Uuid]>,
+        dialect: Option<&str>,
+        odcl_format: Option<&str>,
+    ) -> Result<Option<ExportedFile>, String> {
+        let to_json =
+            |value: &Value| serde_json::to_string_pretty(value).map_err(|e| e.to_string());
+        let (content, content_type, filename) = match format {
+            "json_schema" => (
+                to_json(&Self::export_json_schema(model, table_ids))?,
+                "application/json",
+                format!("{}.json", model.name),
+            ),
+            "avro" => (
+                to_json(&Self::export_avro(model, table_ids))?,
+                "application/json",
+                format!("{}.avsc", model.name),
+            ),
+            "protobuf" => (
+                Self::export_protobuf(model, table_ids),
+                "application/x-protobuf",
+                format!("{}.proto", model.name),
+            ),
+            "sql" => (
+                Self::export_sql(model, table_ids, dialect),
+                "text/plain",
+                format!("{}.sql", model.name),
+            ),
+            "sql-queries" | "sql_queries" => (
+                crate::export::sql_queries::SqlQueriesExporter::export_model(
+                    model, table_ids, dialect,
+                ),
+                "text/plain",
+                format!("{}.queries.sql", model.name),
+            ),
+            "prisma" => (
+                crate::export::prisma::PrismaExporter::export_model(model, table_ids, dialect),
+                "text/plain",
+                "schema.prisma".to_string(),
+            ),
+            "docs" => (
+                crate::export::markdown::MarkdownExporter::export_model(model, table_ids),
+                "text/markdown",
+                format!("{}.md", model.name),
+            ),
+            "dbt_sources" => (
+                crate::export::dbt::DbtExporter::export_sources(model, table_ids),
+                "application/x-yaml",
+                "sources.yml".to_string(),
+            ),
+            "mermaid" => (
+                crate::export::mermaid::MermaidExporter::export_model(model, table_ids),
+                "text/plain",
+                format!("{}.mmd", model.name),
+            ),
+            "dbml" => (
+                crate::export::dbml::DbmlExporter::export_model(model, table_ids),
+                "text/plain",
+                format!("{}.dbml", model.name),
+            ),
+            "odcl" => {
+                let exports =
+                    Self::export_odcl(model, table_ids, odcl_format.unwrap_or("odcs_v3_1_0"));
+                // For single table, return YAML directly; for multiple, return JSON with all YAMLs
+                if exports.len() == 1 {
+                    let (_, yaml) = exports.into_iter().next().unwrap();
+                    (yaml, "application/x-yaml", format!("{}.yaml", model.name))
+                } else {
+                    (
+                        to_json(&serde_json::json!(exports))?,
+                        "application/json",
+                        format!("{}.odcl.json", model.name),
+                    )
+                }
+            }
+            "png" => {
+                let content =
+                    Self::export_png(model, 1920, 1080, table_ids).map_err(|e| e.to_string())?;
+                return Ok(Some(ExportedFile {
+                    content,
+                    content_type: "image/png",
+                    filename: format!("{}.png", model.name),
+                }));
+            }
+            _ => return Ok(None),
+        };
+        Ok(Some(ExportedFile {
+            content: content.into_bytes(),
+            content_type,
+            filename,
+        }))
+    }
+
     /// Export model to JSON Schema format using SDK
     pub fn export_json_schema(model: &DataModel, table_ids: Option<&[Uuid]>) -> Value {
         use crate::services::table_converter::api_datamodel_to_sdk_datamodel;
@@ -68,8 +177,433 @@ impl ExportService {
         table_ids: Option<&[Uuid]>,
         dialect: Option<&str>,
     ) -> String {
-        let sdk_model = api_datamodel_to_sdk_datamodel(model, table_ids);
-        SQLExporter::export_model(&sdk_model, table_ids, dialect)
+        let mut sql = Vec::new();
+        Self::write_sql(model, table_ids, dialect, &mut sql).expect("writing to a Vec");
+        String::from_utf8(sql).expect("SQL export is UTF-8")
+    }
+
+    /// Write the SQL export table by table, so large models can be streamed.
+    pub fn write_sql(
+        model: &DataModel,
+        table_ids: Option<&[Uuid]>,
+        dialect: Option<&str>,
+        out: &mut dyn Write,
+    ) -> std::io::Result<()> {
+        let sqlserver = matches!(dialect, Some("sqlserver" | "mssql"));
+        for table in Self::selected_tables(model, table_ids) {
+            let sdk_table = match table.temporal() {
+                // SQL Server adds system period columns with the period (see below)
+                Ok(Some(settings)) if sqlserver && settings.kind.has_system_time() => {
+                    let mut table = table.clone();
+                    table.columns.retain(|c| {
+                        !c.name.eq_ignore_ascii_case(&settings.system_start_column)
+                            && !c.name.eq_ignore_ascii_case(&settings.system_end_column)
+                    });
+                    api_table_to_sdk_table(&table)
+                }
+                _ => api_table_to_sdk_table(table),
+            };
+            out.write_all(SQLExporter::export_table(&sdk_table, dialect).as_bytes())?;
+            out.write_all(b"\n")?;
+        }
+        out.write_all(Self::export_sql_foreign_keys(model, table_ids, dialect).as_bytes())?;
+        out.write_all(Self::export_sql_masking_policies(model, table_ids, dialect).as_bytes())?;
+        out.write_all(Self::export_sql_row_access_policies(model, table_ids, dialect).as_bytes())?;
+        out.write_all(Self::export_sql_temporal_tables(model, table_ids, dialect).as_bytes())
+    }
+
+    /// Tables of the model, or only those with the given ids.
+    fn selected_tables<'a>(
+        model: &'a DataModel,
+        table_ids: Option<&[Uuid]>,
+    ) -> impl Iterator<Item = &'a crate::models::Table> {
+        let table_ids = table_ids.map(<[Uuid]>::to_vec);
+        model
+            .tables
+            .iter()
+            .filter(move |t| table_ids.as_ref().is_none_or(|ids| ids.contains(&t.id)))
+    }
+
+    /// Render `ALTER TABLE ... ADD CONSTRAINT ... FOREIGN KEY` statements for relationships
+    /// with foreign key details whose tables are both exported. Composite keys keep their
+    /// column order.
+    fn export_sql_foreign_keys(
+        model: &DataModel,
+        table_ids: Option<&[Uuid]>,
+        dialect: Option<&str>,
+    ) -> String {
+        let dialect = dialect.unwrap_or("standard");
+        let quote = |identifier: &str| match dialect {
+            "mysql" => format!("`{}`", identifier.replace('`', "``")),
+            "sqlserver" | "mssql" => format!("[{}]", identifier.replace(']', "]]")),
+            _ => format!("\"{}\"", identifier.replace('"', "\"\"")),
+        };
+        let qualified = |table: &crate::models::Table| {
+            [
+                table.catalog_name.as_deref(),
+                table.schema_name.as_deref(),
+                Some(table.name.as_str()),
+            ]
+            .into_iter()
+            .flatten()
+            .map(&quote)
+            .collect::<Vec<_>>()
+            .join(".")
+        };
+        let exported = |id: Uuid| table_ids.is_none_or(|ids| ids.contains(&id));
+
+        let mut sql = String::new();
+        for relationship in &model.relationships {
+            let Some(details) = &relationship.foreign_key_details else {
+                continue;
+            };
+            let (Some(source), Some(target)) = (
+                model.get_table_by_id(relationship.source_table_id),
+                model.get_table_by_id(relationship.target_table_id),
+            ) else {
+                continue;
+            };
+            if !exported(source.id) || !exported(target.id) {
+                continue;
+            }
+            let columns =
+                |names: Vec<&str>| names.into_iter().map(&quote).collect::<Vec<_>>().join(", ");
+            sql.push_str(&format!(
+                "ALTER TABLE {} ADD CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {} ({});\n",
+                qualified(source),
+                quote(&format!(
+                    "fk_{}_{}_{}",
+                    source.name, target.name, details.source_column
+                )),
+                columns(details.source_columns()),
+                qualified(target),
+                columns(details.target_columns())
+            ));
+        }
+        sql
+    }
+
+    /// Render the columns' masking policies as Databricks column masks (a SQL function per
+    /// column, applied with `ALTER COLUMN ... SET MASK`) or Snowflake masking policies
+    /// (`CREATE MASKING POLICY` and `MODIFY COLUMN ... SET MASKING POLICY`). Users in a
+    /// visibility role see the value, everyone else the mask. Other dialects have no masking
+    /// DDL; nested columns are skipped, as both platforms only mask top-level columns.
+    fn export_sql_masking_policies(
+        model: &DataModel,
+        table_ids: Option<&[Uuid]>,
+        dialect: Option<&str>,
+    ) -> String {
+        let databricks = match dialect {
+            Some("databricks" | "databricks_delta") => true,
+            Some("snowflake") => false,
+            _ => return String::new(),
+        };
+        let quote = |identifier: &str| {
+            if databricks {
+                format!("`{}`", identifier.replace('`', "``"))
+            } else {
+                format!("\"{}\"", identifier.replace('"', "\"\""))
+            }
+        };
+        let literal = |value: &str| format!("'{}'", value.replace('\'', "''"));
+
+        let mut sql = String::new();
+        for table in Self::selected_tables(model, table_ids) {
+            let prefix: String = [table.catalog_name.as_deref(), table.schema_name.as_deref()]
+                .into_iter()
+                .flatten()
+                .map(|part| format!("{}.", quote(part)))
+                .collect();
+            let table_name = format!("{}{}", prefix, quote(&table.name));
+            for column in &table.columns {
+                let Some(policy) = &column.masking_policy else {
+                    continue;
+                };
+                if column.name.contains('.') {
+                    continue;
+                }
+                let policy_name = format!(
+                    "{}{}",
+                    prefix,
+                    quote(&format!("mask_{}_{}", table.name, column.name))
+                );
+                let masked = match policy.mask_type {
+                    MaskType::Redact if column.is_string() => "'****'".to_string(),
+                    MaskType::Redact | MaskType::Null => "NULL".to_string(),
+                    MaskType::Partial if databricks => "concat('****', right(value, 4))".into(),
+                    MaskType::Partial => "'****' || RIGHT(value, 4)".to_string(),
+                    MaskType::Hash if databricks => "sha2(value, 256)".to_string(),
+                    MaskType::Hash => "SHA2(value, 256)".to_string(),
+                };
+                let visible: Vec<String> = policy
+                    .visibility_roles
+                    .iter()
+                    .map(|role| {
+                        let check = if databricks {
+                            "is_account_group_member"
+                        } else {
+                            "IS_ROLE_IN_SESSION"
+                        };
+                        format!("{}({})", check, literal(role.trim()))
+                    })
+                    .collect();
+                let body = if visible.is_empty() {
+                    masked
+                } else {
+                    format!(
+                        "CASE WHEN {} THEN value ELSE {} END",
+                        visible.join(" OR "),
+                        masked
+                    )
+                };
+
+                let data_type = &column.data_type;
+                if databricks {
+                    sql.push_str(&format!(
+                        "CREATE OR REPLACE FUNCTION {}(value {}) RETURNS {} RETURN {};\n",
+                        policy_name, data_type, data_type, body
+                    ));
+                    sql.push_str(&format!(
+                        "ALTER TABLE {} ALTER COLUMN {} SET MASK {};\n",
+                        table_name,
+                        quote(&column.name),
+                        policy_name
+                    ));
+                } else {
+                    sql.push_str(&format!(
+                        "CREATE OR REPLACE MASKING POLICY {} AS (value {}) RETURNS {} -> {};\n",
+                        policy_name, data_type, data_type, body
+                    ));
+                    sql.push_str(&format!(
+                        "ALTER TABLE {} MODIFY COLUMN {} SET MASKING POLICY {};\n",
+                        table_name,
+                        quote(&column.name),
+                        policy_name
+                    ));
+                }
+            }
+        }
+        sql
+    }
+
+    /// Render the tables' row access policies as PostgreSQL row-level security (`ENABLE ROW
+    /// LEVEL SECURITY` and a `CREATE POLICY` per policy) or a Snowflake row access policy per
+    /// table. Snowflake allows one policy per table, so its policy admits a row if any of the
+    /// table's policies does; its arguments are the columns the predicates mention, and the
+    /// policies' commands are ignored. Other dialects have no row-level security DDL, and
+    /// malformed policies are skipped (model validation reports them).
+    fn export_sql_row_access_policies(
+        model: &DataModel,
+        table_ids: Option<&[Uuid]>,
+        dialect: Option<&str>,
+    ) -> String {
+        let postgres = match dialect {
+            Some("postgres" | "postgresql") => true,
+            Some("snowflake") => false,
+            _ => return String::new(),
+        };
+        let quote = |identifier: &str| format!("\"{}\"", identifier.replace('"', "\"\""));
+        let literal = |value: &str| format!("'{}'", value.replace('\'', "''"));
+
+        let mut sql = String::new();
+        for table in Self::selected_tables(model, table_ids) {
+            let policies = table.row_access_policies().unwrap_or_default();
+            if policies.is_empty() {
+                continue;
+            }
+            let prefix: String = [table.catalog_name.as_deref(), table.schema_name.as_deref()]
+                .into_iter()
+                .flatten()
+                .map(|part| format!("{}.", quote(part)))
+                .collect();
+            let table_name = format!("{}{}", prefix, quote(&table.name));
+
+            if postgres {
+                sql.push_str(&format!(
+                    "ALTER TABLE {} ENABLE ROW LEVEL SECURITY;\n",
+                    table_name
+                ));
+                for policy in &policies {
+                    let roles = if policy.roles.is_empty() {
+                        String::new()
+                    } else {
+                        let roles: Vec<String> =
+                            policy.roles.iter().map(|r| quote(r.trim())).collect();
+                        format!(" TO {}", roles.join(", "))
+                    };
+                    // INSERT policies can only check new rows
+                    let clause = if policy.command == RowPolicyCommand::Insert {
+                        "WITH CHECK"
+                    } else {
+                        "USING"
+                    };
+                    sql.push_str(&format!(
+                        "CREATE POLICY {} ON {} FOR {}{} {} ({});\n",
+                        quote(&policy.name),
+                        table_name,
+                        policy.command.as_str().to_uppercase(),
+                        roles,
+                        clause,
+                        policy.predicate.trim()
+                    ));
+                }
+                continue;
+            }
+
+            let predicates: Vec<&str> = policies.iter().map(|p| p.predicate.as_str()).collect();
+            let mut arguments: Vec<&crate::models::Column> = table
+                .columns
+                .iter()
+                .filter(|c| {
+                    !c.name.contains('.')
+                        && predicates.iter().any(|p| mentions_identifier(p, &c.name))
+                })
+                .collect();
+            // A row access policy needs at least one argument
+            if arguments.is_empty() {
+                arguments.extend(table.columns.iter().find(|c| !c.name.contains('.')));
+            }
+            if arguments.is_empty() {
+                continue;
+            }
+            let body: Vec<String> = policies
+                .iter()
+                .map(|policy| {
+                    let roles: Vec<String> = policy
+                        .roles
+                        .iter()
+                        .map(|r| format!("IS_ROLE_IN_SESSION({})", literal(r.trim())))
+                        .collect();
+                    if roles.is_empty() {
+                        format!("({})", policy.predicate.trim())
+                    } else {
+                        format!(
+                            "(({}) AND ({}))",
+                            roles.join(" OR "),
+                            policy.predicate.trim()
+                        )
+                    }
+                })
+                .collect();
+            let policy_name = format!("{}{}", prefix, quote(&format!("rap_{}", table.name)));
+            sql.push_str(&format!(
+                "CREATE OR REPLACE ROW ACCESS POLICY {} AS ({}) RETURNS BOOLEAN -> {};\n",
+                policy_name,
+                arguments
+                    .iter()
+                    .map(|c| format!("{} {}", c.name, c.data_type))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                body.join(" OR ")
+            ));
+            sql.push_str(&format!(
+                "ALTER TABLE {} ADD ROW ACCESS POLICY {} ON ({});\n",
+                table_name,
+                policy_name,
+                arguments
+                    .iter()
+                    .map(|c| quote(&c.name))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        sql
+    }
+
+    /// Render temporal tables as SQL Server system-versioned tables (period columns added with
+    /// `PERIOD FOR SYSTEM_TIME`, then `SYSTEM_VERSIONING = ON` with the history table) or with
+    /// the PostgreSQL `periods` extension (`add_system_time_period`, `add_system_versioning`
+    /// and `add_period` for application time). SQL Server has no application-time periods,
+    /// so those columns stay plain columns there. Other dialects have no temporal DDL.
+    fn export_sql_temporal_tables(
+        model: &DataModel,
+        table_ids: Option<&[Uuid]>,
+        dialect: Option<&str>,
+    ) -> String {
+        let sqlserver = match dialect {
+            Some("sqlserver" | "mssql") => true,
+            Some("postgres" | "postgresql") => false,
+            _ => return String::new(),
+        };
+        let quote = |identifier: &str| {
+            if sqlserver {
+                format!("[{}]", identifier.replace(']', "]]"))
+            } else {
+                format!("\"{}\"", identifier.replace('"', "\"\""))
+            }
+        };
+        let literal = |value: &str| format!("'{}'", value.replace('\'', "''"));
+
+        let mut sql = String::new();
+        for table in Self::selected_tables(model, table_ids) {
+            let Ok(Some(settings)) = table.temporal() else {
+                continue;
+            };
+            let prefix: String = [table.catalog_name.as_deref(), table.schema_name.as_deref()]
+                .into_iter()
+                .flatten()
+                .map(|part| format!("{}.", quote(part)))
+                .collect();
+            let table_name = format!("{}{}", prefix, quote(&table.name));
+            let history_name = history_table_name(table, &settings);
+
+            if sqlserver {
+                if !settings.kind.has_system_time() {
+                    continue;
+                }
+                let (start, end) = (
+                    quote(&settings.system_start_column),
+                    quote(&settings.system_end_column),
+                );
+                sql.push_str(&format!(
+                    "ALTER TABLE {} ADD {} DATETIME2 GENERATED ALWAYS AS ROW START NOT NULL \
+                     DEFAULT SYSUTCDATETIME(), {} DATETIME2 GENERATED ALWAYS AS ROW END NOT NULL \
+                     DEFAULT CONVERT(DATETIME2, '9999-12-31 23:59:59.9999999'), \
+                     PERIOD FOR SYSTEM_TIME ({}, {});\n",
+                    table_name, start, end, start, end
+                ));
+                // The history table must be schema-qualified
+                let history_prefix = if prefix.is_empty() {
+                    quote("dbo") + "."
+                } else {
+                    prefix.clone()
+                };
+                sql.push_str(&format!(
+                    "ALTER TABLE {} SET (SYSTEM_VERSIONING = ON (HISTORY_TABLE = {}{}));\n",
+                    table_name,
+                    history_prefix,
+                    quote(&history_name)
+                ));
+                continue;
+            }
+
+            if sql.is_empty() {
+                sql.push_str("CREATE EXTENSION IF NOT EXISTS periods CASCADE;\n");
+            }
+            let regclass = literal(&table_name);
+            if settings.kind.has_system_time() {
+                sql.push_str(&format!(
+                    "SELECT periods.add_system_time_period({}, {}, {});\n",
+                    regclass,
+                    literal(&settings.system_start_column),
+                    literal(&settings.system_end_column)
+                ));
+                sql.push_str(&format!(
+                    "SELECT periods.add_system_versioning({}, history_table_name => {});\n",
+                    regclass,
+                    literal(&history_name)
+                ));
+            }
+            if settings.kind.has_application_time() {
+                sql.push_str(&format!(
+                    "SELECT periods.add_period({}, 'valid', {}, {});\n",
+                    regclass,
+                    literal(&settings.valid_from_column),
+                    literal(&settings.valid_to_column)
+                ));
+            }
+        }
+        sql
     }
 
     /// Export model to ODCL/ODCS format using SDK
@@ -90,13 +624,165 @@ impl ExportService {
 
         let mut exports = HashMap::new();
         for table in tables_to_export {
-            let sdk_table = api_table_to_sdk_table(table);
-            let yaml = ODCSExporter::export_table(&sdk_table, format_type);
-            exports.insert(table.name.clone(), yaml);
+            exports.insert(
+                table.name.clone(),
+                Self::export_odcl_table(model, table, format_type),
+            );
         }
         exports
     }
 
+    /// Export one table of the model to ODCL/ODCS YAML.
+    pub fn export_odcl_table(
+        model: &DataModel,
+        table: &crate::models::Table,
+        format_type: &str,
+    ) -> String {
+        let sdk_table = api_table_to_sdk_table(table);
+        let yaml = ODCSExporter::export_table(&sdk_table, format_type);
+        let yaml = Self::add_odcs_composite_foreign_keys(model, table, yaml);
+        Self::add_odcs_column_custom_properties(table, yaml)
+    }
+
+    /// Write the multi-table ODCL export (a JSON object of YAML documents keyed by table
+    /// name) one table at a time, so large models can be streamed.
+    pub fn write_odcl_json(
+        model: &DataModel,
+        table_ids: Option<&[Uuid]>,
+        format_type: &str,
+        out: &mut dyn Write,
+    ) -> std::io::Result<()> {
+        // Same key order and duplicate handling as serializing the `export_odcl` map
+        let tables: BTreeMap<&str, &crate::models::Table> = Self::selected_tables(model, table_ids)
+            .map(|t| (t.name.as_str(), t))
+            .collect();
+        out.write_all(b"{")?;
+        for (idx, (name, table)) in tables.iter().enumerate() {
+            out.write_all(if idx == 0 { "\n  " } else { ",\n  " }.as_bytes())?;
+            serde_json::to_writer(&mut *out, name)?;
+            out.write_all(b": ")?;
+            serde_json::to_writer(
+                &mut *out,
+                &Self::export_odcl_table(model, table, format_type),
+            )?;
+        }
+        out.write_all(if tables.is_empty() { "}" } else { "\n}" }.as_bytes())
+    }
+
+    /// Add composite foreign keys of `table` to its ODCS schema object as `relationships`
+    /// entries (`type: foreignKey` with ordered `from`/`to` column lists). Single-column
+    /// keys are already expressed by each property's `foreignKey`.
+    fn add_odcs_composite_foreign_keys(
+        model: &DataModel,
+        table: &crate::models::Table,
+        yaml: String,
+    ) -> String {
+        let relationships: Vec<serde_yaml::Value> = model
+            .relationships
+            .iter()
+            .filter(|r| r.source_table_id == table.id)
+            .filter_map(|r| {
+                let details = r
+                    .foreign_key_details
+                    .as_ref()
+                    .filter(|d| d.is_composite())?;
+                let target = model.get_table_by_id(r.target_table_id)?;
+                let qualify = |table_name: &str, columns: Vec<&str>| {
+                    columns
+                        .into_iter()
+                        .map(|c| serde_yaml::Value::String(format!("{}.{}", table_name, c)))
+                        .collect::<Vec<_>>()
+                };
+                let mut entry = serde_yaml::Mapping::new();
+                entry.insert("type".into(), "foreignKey".into());
+                entry.insert(
+                    "from".into(),
+                    serde_yaml::Value::Sequence(qualify(&table.name, details.source_columns())),
+                );
+                entry.insert(
+                    "to".into(),
+                    serde_yaml::Value::Sequence(qualify(&target.name, details.target_columns())),
+                );
+                Some(serde_yaml::Value::Mapping(entry))
+            })
+            .collect();
+        if relationships.is_empty() {
+            return yaml;
+        }
+
+        let Ok(mut document) = serde_yaml::from_str::<serde_yaml::Value>(&yaml) else {
+            return yaml;
+        };
+        let Some(schema) = document
+            .get_mut("schema")
+            .and_then(|s| s.as_sequence_mut())
+            .and_then(|s| s.first_mut())
+            .and_then(|s| s.as_mapping_mut())
+        else {
+            return yaml;
+        };
+        schema.insert(
+            "relationships".into(),
+            serde_yaml::Value::Sequence(relationships),
+        );
+        serde_yaml::to_string(&document).unwrap_or(yaml)
+    }
+
+    /// Add the custom field values of `table`'s columns to their ODCS properties as
+    /// `customProperties` entries (`property`/`value` pairs, like table-level ones).
+    fn add_odcs_column_custom_properties(table: &crate::models::Table, yaml: String) -> String {
+        if table.columns.iter().all(|c| c.custom_properties.is_empty()) {
+            return yaml;
+        }
+        let Ok(mut document) = serde_yaml::from_str::<serde_yaml::Value>(&yaml) else {
+            return yaml;
+        };
+        let Some(schema) = document
+            .get_mut("schema")
+            .and_then(|s| s.as_sequence_mut())
+            .and_then(|s| s.first_mut())
+        else {
+            return yaml;
+        };
+
+        for column in &table.columns {
+            if column.custom_properties.is_empty() {
+                continue;
+            }
+            // Nested columns (`parent.child`) live under the parent's properties or items
+            let mut property = Some(&mut *schema);
+            for part in column.name.split('.') {
+                property = property.and_then(|p| {
+                    let p = match p.get("items") {
+                        Some(_) => p.get_mut("items")?,
+                        None => p,
+                    };
+                    p.get_mut("properties")?.get_mut(part)
+                });
+            }
+            let Some(property) = property.and_then(|p| p.as_mapping_mut()) else {
+                continue;
+            };
+            let mut names: Vec<&String> = column.custom_properties.keys().collect();
+            names.sort();
+            let entries = names
+                .into_iter()
+                .filter_map(|name| {
+                    let value = serde_yaml::to_value(&column.custom_properties[name]).ok()?;
+                    let mut entry = serde_yaml::Mapping::new();
+                    entry.insert("property".into(), name.as_str().into());
+                    entry.insert("value".into(), value);
+                    Some(serde_yaml::Value::Mapping(entry))
+                })
+                .collect();
+            property.insert(
+                "customProperties".into(),
+                serde_yaml::Value::Sequence(entries),
+            );
+        }
+        serde_yaml::to_string(&document).unwrap_or(yaml)
+    }
+
     /// Export model to PNG format (diagram)
     /// Note: PNG export requires DrawIO XML conversion via external tooling
     /// This returns DrawIO XML which can be converted to PNG using DrawIO desktop/web app
@@ -132,8 +818,196 @@ impl ExportService {
     }
 }
 
+/// Whether the SQL expression mentions `identifier` (case-insensitive) outside string literals.
+fn mentions_identifier(expression: &str, identifier: &str) -> bool {
+    let mut in_literal = false;
+    let mut word = String::new();
+    for c in expression.chars().chain(std::iter::once(' ')) {
+        if c == '\'' {
+            in_literal = !in_literal;
+            word.clear();
+        } else if !in_literal && (c.is_alphanumeric() || c == '_') {
+            word.push(c);
+        } else {
+            if word.eq_ignore_ascii_case(identifier) {
+                return true;
+            }
+            word.clear();
+        }
+    }
+    false
+}
+
 impl Default for ExportService {
     fn default() -> Self {
         Self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Column, MaskingPolicy, RowAccessPolicy, Table};
+
+    #[test]
+    fn test_streamed_writers_match_buffered_exports() {
+        let mut model = DataModel::new("sales".to_string(), String::new(), String::new());
+        for name in ["orders", "customers"] {
+            model.tables.push(Table::new(
+                name.to_string(),
+                vec![Column::new("id".to_string(), "BIGINT".to_string())],
+            ));
+        }
+
+        let mut odcl = Vec::new();
+        ExportService::write_odcl_json(&model, None, "odcs_v3_1_0", &mut odcl).unwrap();
+        let buffered = serde_json::to_string_pretty(&serde_json::json!(
+            ExportService::export_odcl(&model, None, "odcs_v3_1_0")
+        ))
+        .unwrap();
+        assert_eq!(String::from_utf8(odcl).unwrap(), buffered);
+
+        let sdk_model =
+            crate::services::table_converter::api_datamodel_to_sdk_datamodel(&model, None);
+        assert_eq!(
+            ExportService::export_sql(&model, None, Some("postgres")),
+            SQLExporter::export_model(&sdk_model, None, Some("postgres"))
+        );
+    }
+
+    #[test]
+    fn test_sql_export_renders_masking_policies() {
+        let mut email = Column::new("email".to_string(), "STRING".to_string());
+        email.masking_policy = Some(MaskingPolicy {
+            mask_type: MaskType::Partial,
+            visibility_roles: vec!["support".to_string(), "o'brien".to_string()],
+        });
+        let mut salary = Column::new("salary".to_string(), "DECIMAL(10,2)".to_string());
+        salary.masking_policy = Some(MaskingPolicy {
+            mask_type: MaskType::Redact,
+            visibility_roles: Vec::new(),
+        });
+        let mut customers = Table::new("customers".to_string(), vec![email, salary]);
+        customers.schema_name = Some("crm".to_string());
+        let mut model = DataModel::new("sales".to_string(), String::new(), String::new());
+        model.tables.push(customers);
+
+        let databricks = ExportService::export_sql(&model, None, Some("databricks"));
+        assert!(databricks.contains(
+            "CREATE OR REPLACE FUNCTION `crm`.`mask_customers_email`(value STRING) RETURNS STRING \
+             RETURN CASE WHEN is_account_group_member('support') OR is_account_group_member('o''brien') \
+             THEN value ELSE concat('****', right(value, 4)) END;"
+        ));
+        assert!(databricks.contains(
+            "ALTER TABLE `crm`.`customers` ALTER COLUMN `email` SET MASK `crm`.`mask_customers_email`;"
+        ));
+        // Non-string redaction masks to NULL, and without roles nobody sees the value
+        assert!(databricks.contains("RETURNS DECIMAL(10,2) RETURN NULL;"));
+
+        let snowflake = ExportService::export_sql(&model, None, Some("snowflake"));
+        assert!(snowflake.contains(
+            "CREATE OR REPLACE MASKING POLICY \"crm\".\"mask_customers_email\" AS (value STRING) \
+             RETURNS STRING -> CASE WHEN IS_ROLE_IN_SESSION('support')"
+        ));
+        assert!(snowflake.contains(
+            "ALTER TABLE \"crm\".\"customers\" MODIFY COLUMN \"salary\" SET MASKING POLICY \"crm\".\"mask_customers_salary\";"
+        ));
+        assert!(!ExportService::export_sql(&model, None, Some("postgres")).contains("MASK"));
+    }
+
+    #[test]
+    fn test_sql_export_renders_row_access_policies() {
+        let mut orders = Table::new(
+            "orders".to_string(),
+            vec![
+                Column::new("id".to_string(), "BIGINT".to_string()),
+                Column::new("region".to_string(), "VARCHAR(16)".to_string()),
+                Column::new("owner_id".to_string(), "BIGINT".to_string()),
+            ],
+        );
+        orders.schema_name = Some("sales".to_string());
+        orders.set_row_access_policies(&[
+            RowAccessPolicy {
+                name: "emea_analysts".to_string(),
+                predicate: "region = 'EMEA id'".to_string(),
+                roles: vec!["analyst".to_string()],
+                command: RowPolicyCommand::Select,
+            },
+            RowAccessPolicy {
+                name: "own_rows".to_string(),
+                predicate: "owner_id = current_user_id()".to_string(),
+                roles: Vec::new(),
+                command: RowPolicyCommand::Insert,
+            },
+        ]);
+        let mut model = DataModel::new("sales".to_string(), String::new(), String::new());
+        model.tables.push(orders);
+        assert_eq!(model.tables[0].row_access_policies().unwrap().len(), 2);
+
+        let postgres = ExportService::export_sql(&model, None, Some("postgres"));
+        assert!(postgres.contains("ALTER TABLE \"sales\".\"orders\" ENABLE ROW LEVEL SECURITY;"));
+        assert!(postgres.contains(
+            "CREATE POLICY \"emea_analysts\" ON \"sales\".\"orders\" FOR SELECT TO \"analyst\" \
+             USING (region = 'EMEA id');"
+        ));
+        assert!(postgres.contains(
+            "CREATE POLICY \"own_rows\" ON \"sales\".\"orders\" FOR INSERT \
+             WITH CHECK (owner_id = current_user_id());"
+        ));
+
+        // Identifiers inside string literals are not policy arguments
+        let snowflake = ExportService::export_sql(&model, None, Some("snowflake"));
+        assert!(snowflake.contains(
+            "CREATE OR REPLACE ROW ACCESS POLICY \"sales\".\"rap_orders\" \
+             AS (region VARCHAR(16), owner_id BIGINT) RETURNS BOOLEAN -> \
+             ((IS_ROLE_IN_SESSION('analyst')) AND (region = 'EMEA id')) \
+             OR (owner_id = current_user_id());"
+        ));
+        assert!(snowflake.contains(
+            "ALTER TABLE \"sales\".\"orders\" ADD ROW ACCESS POLICY \"sales\".\"rap_orders\" \
+             ON (\"region\", \"owner_id\");"
+        ));
+        assert!(!ExportService::export_sql(&model, None, Some("mysql")).contains("POLICY"));
+    }
+
+    #[test]
+    fn test_sql_export_renders_temporal_tables() {
+        let mut policies = Table::new(
+            "policies".to_string(),
+            vec![Column::new("id".to_string(), "BIGINT".to_string())],
+        );
+        policies.odcl_metadata.insert(
+            crate::models::table::TEMPORAL_KEY.to_string(),
+            serde_json::json!({ "kind": "bitemporal", "period_data_type": "DATETIME2" }),
+        );
+        let settings = policies.temporal().unwrap().unwrap();
+        crate::services::temporal_service::add_period_columns(&mut policies, &settings);
+        let mut model = DataModel::new("insurance".to_string(), String::new(), String::new());
+        model.tables.push(policies);
+
+        // SQL Server adds the system period columns itself; application time stays plain
+        let sqlserver = ExportService::export_sql(&model, None, Some("sqlserver"));
+        assert!(!sqlserver.contains("sys_start DATETIME2"));
+        assert!(sqlserver.contains("valid_from"));
+        assert!(sqlserver.contains(
+            "ALTER TABLE [policies] ADD [sys_start] DATETIME2 GENERATED ALWAYS AS ROW START NOT NULL"
+        ));
+        assert!(sqlserver.contains("PERIOD FOR SYSTEM_TIME ([sys_start], [sys_end]);"));
+        assert!(sqlserver.contains(
+            "ALTER TABLE [policies] SET (SYSTEM_VERSIONING = ON (HISTORY_TABLE = [dbo].[policies_history]));"
+        ));
+
+        let postgres = ExportService::export_sql(&model, None, Some("postgres"));
+        assert!(postgres.contains("CREATE EXTENSION IF NOT EXISTS periods CASCADE;"));
+        assert!(postgres.contains(
+            "SELECT periods.add_system_time_period('\"policies\"', 'sys_start', 'sys_end');"
+        ));
+        assert!(postgres.contains(
+            "SELECT periods.add_system_versioning('\"policies\"', history_table_name => 'policies_history');"
+        ));
+        assert!(postgres.contains(
+            "SELECT periods.add_period('\"policies\"', 'valid', 'valid_from', 'valid_to');"
+        ));
+        assert!(!ExportService::export_sql(&model, None, Some("mysql")).contains("periods"));
+    }
+}
@@ -0,0 +1,114 @@
+//! Streamed export responses.
+//!
+//! Exports are written by blocking code into a [`ChunkWriter`], which hands fixed-size chunks
+//! to the response body over a bounded channel. When the client reads slowly the channel
+//! fills up and the writer blocks, so at most a few chunks of an export are held in memory.
+//! A client that disconnects makes the next write fail, which stops the export.
+
+use axum::body::{Body, Bytes};
+use std::io::{self, Write};
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// Size of the chunks sent to the client
+pub const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Chunks buffered between the export and the client before the export blocks
+const STREAM_BUFFERED_CHUNKS: usize = 4;
+
+/// Zip archives larger than this are spooled to a temporary file while they are built
+pub const ZIP_SPOOL_LIMIT: usize = 8 * 1024 * 1024;
+
+/// Blocking writer feeding a streamed response body.
+pub struct ChunkWriter {
+    buffer: Vec<u8>,
+    sender: mpsc::Sender<io::Result<Bytes>>,
+}
+
+impl ChunkWriter {
+    fn new(sender: mpsc::Sender<io::Result<Bytes>>) -> Self {
+        Self {
+            buffer: Vec::with_capacity(STREAM_CHUNK_SIZE),
+            sender,
+        }
+    }
+
+    fn send_buffer(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::replace(&mut self.buffer, Vec::with_capacity(STREAM_CHUNK_SIZE));
+        self.sender
+            .blocking_send(Ok(Bytes::from(chunk)))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "client disconnected"))
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(STREAM_CHUNK_SIZE - self.buffer.len());
+        self.buffer.extend_from_slice(&buf[..len]);
+        if self.buffer.len() == STREAM_CHUNK_SIZE {
+            self.send_buffer()?;
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.send_buffer()
+    }
+}
+
+/// Response body produced by `write` on the blocking thread pool.
+///
+/// An error other than the client going away aborts the response, so the client sees a
+/// failed transfer rather than a truncated file.
+pub fn stream_body<F>(write: F) -> Body
+where
+    F: FnOnce(&mut ChunkWriter) -> io::Result<()> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel(STREAM_BUFFERED_CHUNKS);
+    tokio::task::spawn_blocking(move || {
+        let mut writer = ChunkWriter::new(sender.clone());
+        if let Err(e) = write(&mut writer).and_then(|_| writer.flush())
+            && e.kind() != io::ErrorKind::BrokenPipe
+        {
+            warn!("[ExportStream] Export failed mid-stream: {}", e);
+            let _ = sender.blocking_send(Err(e));
+        }
+    });
+    Body::from_stream(futures_util::stream::unfold(
+        receiver,
+        |mut receiver| async move { receiver.recv().await.map(|chunk| (chunk, receiver)) },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+
+    #[tokio::test]
+    async fn test_stream_body_chunks_large_output() {
+        let line = "CREATE TABLE t (id INT);\n";
+        let lines = 2 * STREAM_CHUNK_SIZE / line.len() + 1;
+        let body = stream_body(move |out| {
+            for _ in 0..lines {
+                out.write_all(line.as_bytes())?;
+            }
+            Ok(())
+        });
+        let bytes = to_bytes(body, usize::MAX).await.unwrap();
+        assert_eq!(bytes.len(), lines * line.len());
+        assert!(bytes.ends_with(line.as_bytes()));
+    }
+
+    #[tokio::test]
+    async fn test_stream_body_aborts_on_error() {
+        let body = stream_body(|out| {
+            out.write_all(b"partial")?;
+            Err(io::Error::other("renderer failed"))
+        });
+        assert!(to_bytes(body, usize::MAX).await.is_err());
+    }
+}
@@ -0,0 +1,421 @@
+//! Deterministic sample models for integration tests.
+//!
+//! Generates the domains of a fixture scenario, so frontend and SDK tests run against models
+//! the server itself provisioned instead of hand-written JSON that drifts from real output.
+//! Every id and timestamp is derived from the scenario and a seed (the workspace email), so
+//! the same account gets the same ids on every run and in every environment, while two
+//! accounts sharing a database never collide.
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::enums::{Cardinality, DatabaseType, RelationshipType};
+use crate::models::relationship::ForeignKeyDetails;
+use crate::models::{Column, Position, Relationship, Table};
+
+/// Prefix of every fixture domain name
+pub const FIXTURE_DOMAIN_PREFIX: &str = "fixture-";
+
+/// Tables of the `large-domain` scenario when no count is given
+pub const DEFAULT_LARGE_TABLES: usize = 200;
+
+/// Most tables the `large-domain` scenario generates
+pub const MAX_LARGE_TABLES: usize = 2000;
+
+/// Sample workspace to provision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, ToSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum FixtureScenario {
+    /// One domain with many tables, each referencing a parent table
+    LargeDomain,
+    /// Sales, customer and finance domains importing each other's tables
+    MultiDomain,
+    /// Two domains defining the same tables with conflicting columns
+    Conflicts,
+}
+
+impl FixtureScenario {
+    fn index(self) -> u128 {
+        match self {
+            Self::LargeDomain => 1,
+            Self::MultiDomain => 2,
+            Self::Conflicts => 3,
+        }
+    }
+}
+
+/// A table another domain imports (cross-domain reference)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureImport {
+    pub source_domain: String,
+    pub table_id: Uuid,
+}
+
+/// One generated domain
+#[derive(Debug, Clone)]
+pub struct FixtureDomain {
+    pub name: String,
+    pub description: String,
+    pub tables: Vec<Table>,
+    pub relationships: Vec<Relationship>,
+    pub imports: Vec<FixtureImport>,
+}
+
+/// Creation time of every fixture table and relationship
+fn fixture_time() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap()
+}
+
+/// FNV-1a, spelled out because std's hasher output may change between releases
+fn stable_hash(value: &str) -> u64 {
+    value.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Builds the tables and relationships of a scenario with stable ids.
+struct FixtureBuilder {
+    seed: u64,
+    scenario: FixtureScenario,
+    domain: u128,
+    next: u128,
+}
+
+impl FixtureBuilder {
+    fn id(&mut self) -> Uuid {
+        self.next += 1;
+        let low = (self.scenario.index() << 56) | (self.domain << 32) | self.next;
+        Uuid::from_u128((u128::from(self.seed) << 64) | low)
+    }
+
+    fn table(&mut self, name: &str, schema: &str, columns: &[(&str, &str)]) -> Table {
+        let columns = columns
+            .iter()
+            .enumerate()
+            .map(|(order, (name, data_type))| {
+                let mut column = Column::new(name.to_string(), data_type.to_string());
+                column.primary_key = *name == "id";
+                column.nullable = *name != "id";
+                column.column_order = order as i32;
+                column
+            })
+            .collect();
+        let mut table = Table::new(name.to_string(), columns);
+        table.id = self.id();
+        table.database_type = Some(DatabaseType::Postgres);
+        table.schema_name = Some(schema.to_string());
+        table.tags = vec!["fixture".to_string()];
+        table.created_at = fixture_time();
+        table.updated_at = fixture_time();
+        table
+    }
+
+    /// Many-to-one foreign key from `source.column` to `target.id`
+    fn foreign_key(&mut self, source: &Table, column: &str, target: &Table) -> Relationship {
+        let mut relationship = Relationship::new(source.id, target.id);
+        relationship.id = self.id();
+        relationship.cardinality = Some(Cardinality::ManyToOne);
+        relationship.relationship_type = Some(RelationshipType::ForeignKey);
+        relationship.foreign_key_details = Some(ForeignKeyDetails {
+            source_column: column.to_string(),
+            target_column: "id".to_string(),
+            column_pairs: Vec::new(),
+        });
+        relationship.created_at = fixture_time();
+        relationship.updated_at = fixture_time();
+        relationship
+    }
+
+    fn domain(&mut self, name: &str, description: &str) -> FixtureDomain {
+        self.domain += 1;
+        self.next = 0;
+        FixtureDomain {
+            name: format!("{}{}", FIXTURE_DOMAIN_PREFIX, name),
+            description: description.to_string(),
+            tables: Vec::new(),
+            relationships: Vec::new(),
+            imports: Vec::new(),
+        }
+    }
+}
+
+/// Lay tables out on a grid, so diagrams of the fixtures are readable
+fn lay_out(tables: &mut [Table]) {
+    const COLUMNS: usize = 10;
+    for (index, table) in tables.iter_mut().enumerate() {
+        table.position = Some(Position {
+            x: (index % COLUMNS) as f64 * 300.0,
+            y: (index / COLUMNS) as f64 * 250.0,
+        });
+    }
+}
+
+/// Generate the domains of `scenario` for the account identified by `seed`.
+///
+/// `table_count` sizes the `large-domain` scenario (default [`DEFAULT_LARGE_TABLES`], at
+/// most [`MAX_LARGE_TABLES`]); the other scenarios have a fixed shape.
+pub fn generate(
+    scenario: FixtureScenario,
+    seed: &str,
+    table_count: Option<usize>,
+) -> Vec<FixtureDomain> {
+    let mut builder = FixtureBuilder {
+        seed: stable_hash(seed),
+        scenario,
+        domain: 0,
+        next: 0,
+    };
+    let mut domains = match scenario {
+        FixtureScenario::LargeDomain => {
+            let count = table_count
+                .unwrap_or(DEFAULT_LARGE_TABLES)
+                .clamp(1, MAX_LARGE_TABLES);
+            vec![large_domain(&mut builder, count)]
+        }
+        FixtureScenario::MultiDomain => multi_domain(&mut builder),
+        FixtureScenario::Conflicts => conflicts(&mut builder),
+    };
+    for domain in &mut domains {
+        lay_out(&mut domain.tables);
+    }
+    domains
+}
+
+fn large_domain(builder: &mut FixtureBuilder, count: usize) -> FixtureDomain {
+    let mut domain = builder.domain("large", "Generated domain with many related tables");
+    for index in 0..count {
+        let name = format!("entity_{:04}", index);
+        let table = builder.table(
+            &name,
+            "public",
+            &[
+                ("id", "BIGINT"),
+                ("parent_id", "BIGINT"),
+                ("code", "VARCHAR(32)"),
+                ("name", "VARCHAR(255)"),
+                ("amount", "DECIMAL(18,2)"),
+                ("created_at", "TIMESTAMP"),
+            ],
+        );
+        // A tree: every table but the first references the table at half its index
+        if index > 0 {
+            let parent = &domain.tables[(index - 1) / 2];
+            let relationship = builder.foreign_key(&table, "parent_id", parent);
+            domain.relationships.push(relationship);
+        }
+        domain.tables.push(table);
+    }
+    domain
+}
+
+fn multi_domain(builder: &mut FixtureBuilder) -> Vec<FixtureDomain> {
+    let mut customers = builder.domain("customers", "Customer master data");
+    let customer = builder.table(
+        "customers",
+        "crm",
+        &[
+            ("id", "BIGINT"),
+            ("email", "VARCHAR(255)"),
+            ("name", "VARCHAR(255)"),
+            ("created_at", "TIMESTAMP"),
+        ],
+    );
+    let address = builder.table(
+        "addresses",
+        "crm",
+        &[
+            ("id", "BIGINT"),
+            ("customer_id", "BIGINT"),
+            ("line1", "VARCHAR(255)"),
+            ("city", "VARCHAR(100)"),
+            ("country", "CHAR(2)"),
+        ],
+    );
+    customers
+        .relationships
+        .push(builder.foreign_key(&address, "customer_id", &customer));
+    customers.tables = vec![customer.clone(), address];
+
+    let mut sales = builder.domain("sales", "Orders placed by customers");
+    let order = builder.table(
+        "orders",
+        "sales",
+        &[
+            ("id", "BIGINT"),
+            ("customer_id", "BIGINT"),
+            ("ordered_at", "TIMESTAMP"),
+            ("status", "VARCHAR(20)"),
+        ],
+    );
+    let order_line = builder.table(
+        "order_lines",
+        "sales",
+        &[
+            ("id", "BIGINT"),
+            ("order_id", "BIGINT"),
+            ("sku", "VARCHAR(64)"),
+            ("quantity", "INTEGER"),
+            ("unit_price", "DECIMAL(18,2)"),
+        ],
+    );
+    sales
+        .relationships
+        .push(builder.foreign_key(&order_line, "order_id", &order));
+    // Orders reference the customer domain's table, which sales imports
+    sales
+        .relationships
+        .push(builder.foreign_key(&order, "customer_id", &customer));
+    sales.imports.push(FixtureImport {
+        source_domain: customers.name.clone(),
+        table_id: customer.id,
+    });
+    sales.tables = vec![order.clone(), order_line];
+
+    let mut finance = builder.domain("finance", "Invoicing and payments");
+    let invoice = builder.table(
+        "invoices",
+        "finance",
+        &[
+            ("id", "BIGINT"),
+            ("order_id", "BIGINT"),
+            ("customer_id", "BIGINT"),
+            ("issued_at", "TIMESTAMP"),
+            ("total", "DECIMAL(18,2)"),
+        ],
+    );
+    let payment = builder.table(
+        "payments",
+        "finance",
+        &[
+            ("id", "BIGINT"),
+            ("invoice_id", "BIGINT"),
+            ("paid_at", "TIMESTAMP"),
+            ("amount", "DECIMAL(18,2)"),
+        ],
+    );
+    finance
+        .relationships
+        .push(builder.foreign_key(&payment, "invoice_id", &invoice));
+    finance
+        .relationships
+        .push(builder.foreign_key(&invoice, "order_id", &order));
+    finance
+        .relationships
+        .push(builder.foreign_key(&invoice, "customer_id", &customer));
+    finance.imports = vec![
+        FixtureImport {
+            source_domain: sales.name.clone(),
+            table_id: order.id,
+        },
+        FixtureImport {
+            source_domain: customers.name.clone(),
+            table_id: customer.id,
+        },
+    ];
+    finance.tables = vec![invoice, payment];
+
+    vec![customers, sales, finance]
+}
+
+fn conflicts(builder: &mut FixtureBuilder) -> Vec<FixtureDomain> {
+    let mut crm = builder.domain("conflicts-crm", "CRM copy of the customer tables");
+    let crm_customer = builder.table(
+        "customers",
+        "public",
+        &[
+            ("id", "BIGINT"),
+            ("email", "VARCHAR(255)"),
+            ("name", "VARCHAR(255)"),
+            ("phone", "VARCHAR(32)"),
+        ],
+    );
+    let crm_address = builder.table(
+        "addresses",
+        "public",
+        &[
+            ("id", "BIGINT"),
+            ("customer_id", "BIGINT"),
+            ("postcode", "VARCHAR(16)"),
+        ],
+    );
+    crm.relationships
+        .push(builder.foreign_key(&crm_address, "customer_id", &crm_customer));
+    crm.tables = vec![crm_customer, crm_address];
+
+    // Same names and unique keys, with columns missing, added or of another type
+    let mut billing = builder.domain("conflicts-billing", "Billing copy of the customer tables");
+    let billing_customer = builder.table(
+        "customers",
+        "public",
+        &[
+            ("id", "VARCHAR(36)"),
+            ("email", "VARCHAR(320)"),
+            ("name", "VARCHAR(255)"),
+            ("vat_number", "VARCHAR(20)"),
+        ],
+    );
+    let billing_address = builder.table(
+        "addresses",
+        "public",
+        &[
+            ("id", "BIGINT"),
+            ("customer_id", "VARCHAR(36)"),
+            ("postcode", "INTEGER"),
+        ],
+    );
+    billing.relationships.push(builder.foreign_key(
+        &billing_address,
+        "customer_id",
+        &billing_customer,
+    ));
+    billing.tables = vec![billing_customer, billing_address];
+
+    vec![crm, billing]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixtures_are_deterministic_per_seed() {
+        let first = generate(FixtureScenario::MultiDomain, "dev@example.com", None);
+        let again = generate(FixtureScenario::MultiDomain, "dev@example.com", None);
+        let other = generate(FixtureScenario::MultiDomain, "qa@example.com", None);
+        let ids = |domains: &[FixtureDomain]| -> Vec<Uuid> {
+            domains
+                .iter()
+                .flat_map(|d| {
+                    d.tables
+                        .iter()
+                        .map(|t| t.id)
+                        .chain(d.relationships.iter().map(|r| r.id))
+                })
+                .collect()
+        };
+        assert_eq!(ids(&first), ids(&again));
+        assert!(ids(&first).iter().all(|id| !ids(&other).contains(id)));
+
+        // Imports point at tables of the source domain
+        let finance = &first[2];
+        assert_eq!(finance.name, "fixture-finance");
+        for import in &finance.imports {
+            let source = first
+                .iter()
+                .find(|d| d.name == import.source_domain)
+                .unwrap();
+            assert!(source.tables.iter().any(|t| t.id == import.table_id));
+        }
+
+        let large = generate(FixtureScenario::LargeDomain, "dev@example.com", Some(50));
+        assert_eq!(large[0].tables.len(), 50);
+        assert_eq!(large[0].relationships.len(), 49);
+        let conflicts = generate(FixtureScenario::Conflicts, "dev@example.com", None);
+        assert_eq!(
+            conflicts[0].tables[0].get_unique_key(),
+            conflicts[1].tables[0].get_unique_key()
+        );
+    }
+}
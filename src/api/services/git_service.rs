@@ -1,7 +1,11 @@
 //! Git service for managing model storage in Git directories.
 
 use crate::models::{DataModel, Relationship, Table};
+use crate::services::domain_index_service::DomainIndex;
 use crate::services::odcs_parser::ODCSParser;
+use crate::services::relationships_file_service::validate_relationships_file;
+use crate::services::rename_service::{AliasIndex, sync_table_aliases};
+use crate::services::save_coordinator_service::save_coordinator;
 use anyhow::{Context, Result};
 use data_modelling_sdk::git::GitService as SdkGitService;
 use serde_yaml;
@@ -192,6 +196,9 @@ impl GitService {
             }
         }
 
+        // Aliases aren't part of ODCS; they live beside the tables
+        AliasIndex::load(git_dir).apply_table_aliases(&mut tables);
+
         // Build set of table IDs for validation
         let table_ids: std::collections::HashSet<Uuid> = tables.iter().map(|t| t.id).collect();
 
@@ -216,7 +223,16 @@ impl GitService {
             );
 
             // Read YAML file to extract both relationships and table names
-            if let Ok(yaml_content) = fs::read_to_string(&control_file)
+            let yaml_content = fs::read_to_string(&control_file).ok();
+            // Report hand edits that don't match the schema; invalid entries are skipped below
+            for error in yaml_content
+                .as_deref()
+                .map(|content| validate_relationships_file(content, None))
+                .unwrap_or_default()
+            {
+                warn!("[GitService] Invalid {:?}: {}", control_file, error);
+            }
+            if let Some(yaml_content) = yaml_content
                 && let Ok(data) = serde_yaml::from_str::<serde_yaml::Value>(&yaml_content)
             {
                 // Extract relationships array
@@ -503,6 +519,23 @@ impl GitService {
         Ok(relationships)
     }
 
+    /// Parse the relationships of a `relationships.yaml` document.
+    pub fn parse_relationships(&self, yaml_content: &str) -> Result<Vec<Relationship>> {
+        let data: serde_yaml::Value = serde_yaml::from_str(yaml_content)
+            .with_context(|| "Failed to parse relationships YAML")?;
+        let Some(entries) = data
+            .get("relationships")
+            .and_then(|v| v.as_sequence())
+            .or_else(|| data.as_sequence())
+        else {
+            return Ok(Vec::new());
+        };
+        entries
+            .iter()
+            .map(|entry| self.parse_relationship(entry))
+            .collect()
+    }
+
     /// Parse a relationship from YAML value.
     fn parse_relationship(&self, data: &serde_yaml::Value) -> Result<Relationship> {
         use crate::models::enums::{Cardinality, RelationshipType};
@@ -549,10 +582,15 @@ impl GitService {
             });
 
         let foreign_key_details = data.get("foreign_key_details").and_then(|v| {
-            Some(ForeignKeyDetails {
-                source_column: v.get("source_column")?.as_str()?.to_string(),
-                target_column: v.get("target_column")?.as_str()?.to_string(),
-            })
+            let mut details = ForeignKeyDetails::new(
+                v.get("source_column")?.as_str()?,
+                v.get("target_column")?.as_str()?,
+            );
+            details.column_pairs = v
+                .get("column_pairs")
+                .and_then(|pairs| serde_yaml::from_value(pairs.clone()).ok())
+                .unwrap_or_default();
+            Some(details)
         });
 
         let etl_job_metadata = data.get("etl_job_metadata").and_then(|v| {
@@ -579,6 +617,16 @@ impl GitService {
         let source_optional = data.get("source_optional").and_then(|v| v.as_bool());
         let target_optional = data.get("target_optional").and_then(|v| v.as_bool());
 
+        // Role names of each end (e.g. self-references such as employee -> manager)
+        let source_role = data
+            .get("source_role")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+        let target_role = data
+            .get("target_role")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
         let created_at = data
             .get("created_at")
             .and_then(|v| v.as_str())
@@ -600,6 +648,8 @@ impl GitService {
             cardinality,
             source_optional,
             target_optional,
+            source_role,
+            target_role,
             foreign_key_details,
             etl_job_metadata,
             relationship_type,
@@ -613,6 +663,7 @@ impl GitService {
 
     /// Save a table to ODCS YAML file.
     pub fn save_table_to_yaml(&self, table: &Table) -> Result<PathBuf> {
+        let _save = save_coordinator().begin();
         let git_dir = self
             .git_directory
             .as_ref()
@@ -632,6 +683,8 @@ impl GitService {
 
         fs::write(&yaml_file, yaml_content)
             .with_context(|| format!("Failed to write YAML file: {:?}", yaml_file))?;
+        sync_table_aliases(git_dir, table)?;
+        DomainIndex::refresh(git_dir);
 
         info!("Saved table {} to {:?}", table.name, yaml_file);
         Ok(yaml_file)
@@ -644,13 +697,32 @@ impl GitService {
         relationships: &[Relationship],
         tables: &[Table],
     ) -> Result<PathBuf> {
+        let _save = save_coordinator().begin();
         let git_dir = self
             .git_directory
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Git directory not mapped"))?;
 
         let control_file = git_dir.join("relationships.yaml");
+        let yaml_content = Self::relationships_to_yaml(relationships, tables)?;
+
+        fs::write(&control_file, yaml_content)
+            .with_context(|| format!("Failed to write relationships file: {:?}", control_file))?;
+        DomainIndex::refresh(git_dir);
+
+        info!(
+            "Saved {} relationships to {:?}",
+            relationships.len(),
+            control_file
+        );
+        Ok(control_file)
+    }
 
+    /// Render relationships in the `relationships.yaml` format.
+    pub fn relationships_to_yaml(
+        relationships: &[Relationship],
+        tables: &[Table],
+    ) -> Result<String> {
         // Create a lookup map for table names by ID
         let table_name_map: HashMap<Uuid, &str> =
             tables.iter().map(|t| (t.id, t.name.as_str())).collect();
@@ -723,6 +795,18 @@ impl GitService {
                     serde_json::Value::Bool(target_opt),
                 );
             }
+            if let Some(ref role) = rel.source_role {
+                rel_data.insert(
+                    "source_role".to_string(),
+                    serde_json::Value::String(role.clone()),
+                );
+            }
+            if let Some(ref role) = rel.target_role {
+                rel_data.insert(
+                    "target_role".to_string(),
+                    serde_json::Value::String(role.clone()),
+                );
+            }
 
             if let Some(ref fk) = rel.foreign_key_details {
                 let mut fk_data = serde_json::Map::new();
@@ -734,6 +818,12 @@ impl GitService {
                     "target_column".to_string(),
                     serde_json::Value::String(fk.target_column.clone()),
                 );
+                if !fk.column_pairs.is_empty() {
+                    fk_data.insert(
+                        "column_pairs".to_string(),
+                        serde_json::to_value(&fk.column_pairs).unwrap_or_default(),
+                    );
+                }
                 rel_data.insert(
                     "foreign_key_details".to_string(),
                     serde_json::Value::Object(fk_data),
@@ -820,23 +910,14 @@ impl GitService {
         );
 
         // Convert to YAML
-        let yaml_content = serde_yaml::to_string(&yaml_data)
-            .with_context(|| "Failed to serialize relationships to YAML")?;
-
-        fs::write(&control_file, yaml_content)
-            .with_context(|| format!("Failed to write relationships file: {:?}", control_file))?;
-
-        info!(
-            "Saved {} relationships to {:?}",
-            relationships.len(),
-            control_file
-        );
-        Ok(control_file)
+        serde_yaml::to_string(&yaml_data)
+            .with_context(|| "Failed to serialize relationships to YAML")
     }
 
     /// Save DrawIO XML file.
     #[allow(dead_code)]
     pub fn save_drawio_xml(&self, xml_content: &str) -> Result<PathBuf> {
+        let _save = save_coordinator().begin();
         let git_dir = self
             .git_directory
             .as_ref()
@@ -0,0 +1,377 @@
+//! Google Sheets data dictionary parser.
+//!
+//! Imports tables and columns from a spreadsheet where each row describes one column
+//! (table name, column name, data type, ...). Which sheet and header names hold which
+//! attribute is configured by a [`SheetsColumnMapping`], saved per workspace so analysts
+//! can keep their existing dictionary layout. Rows are read through the Sheets API v4 with
+//! the caller's OAuth access token; nothing is stored server-side except the mapping.
+
+use crate::models::{Column, Table};
+use crate::services::avro_parser::ParserError;
+use anyhow::{Context, Result, anyhow, bail};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::OnceLock;
+use utoipa::ToSchema;
+
+const SHEETS_API_URL: &str = "https://sheets.googleapis.com/v4/spreadsheets";
+
+/// Maximum number of dictionary rows imported from one sheet
+const MAX_ROWS: usize = 50_000;
+
+/// Layout of a data dictionary sheet: which header holds which attribute.
+///
+/// Header names are matched case-insensitively. Optional attributes that are unset or whose
+/// header is missing from the sheet are skipped.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct SheetsColumnMapping {
+    /// Sheet (tab) title; defaults to the tab in the URL (`gid`) or the first tab
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sheet: Option<String>,
+    /// 1-based row holding the headers
+    pub header_row: usize,
+    pub table: String,
+    pub column: String,
+    pub data_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub primary_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub catalog: Option<String>,
+    /// Comma-separated table tags
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tags: Option<String>,
+}
+
+impl Default for SheetsColumnMapping {
+    fn default() -> Self {
+        Self {
+            sheet: None,
+            header_row: 1,
+            table: "Table".to_string(),
+            column: "Column".to_string(),
+            data_type: "Data Type".to_string(),
+            description: Some("Description".to_string()),
+            nullable: Some("Nullable".to_string()),
+            primary_key: Some("Primary Key".to_string()),
+            schema: None,
+            catalog: None,
+            tags: None,
+        }
+    }
+}
+
+/// Spreadsheet id and optional tab id (`gid`) extracted from a Sheets URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SheetReference {
+    pub spreadsheet_id: String,
+    pub gid: Option<i64>,
+}
+
+impl SheetReference {
+    /// Parse a `https://docs.google.com/spreadsheets/d/<id>/edit#gid=<gid>` URL.
+    pub fn from_url(url: &str) -> Option<Self> {
+        static ID: OnceLock<Regex> = OnceLock::new();
+        static GID: OnceLock<Regex> = OnceLock::new();
+        let id = ID.get_or_init(|| {
+            Regex::new(r"^https://docs\.google\.com/spreadsheets/d/([A-Za-z0-9_-]+)").unwrap()
+        });
+        let gid = GID.get_or_init(|| Regex::new(r"[#?&]gid=(\d+)").unwrap());
+
+        let spreadsheet_id = id.captures(url)?.get(1)?.as_str().to_string();
+        Some(Self {
+            spreadsheet_id,
+            gid: gid
+                .captures(url)
+                .and_then(|c| c.get(1)?.as_str().parse().ok()),
+        })
+    }
+}
+
+/// Parser for Google Sheets data dictionaries.
+#[derive(Default)]
+pub struct GoogleSheetsParser;
+
+impl GoogleSheetsParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Fetch the dictionary rows of a sheet with the caller's OAuth access token.
+    pub async fn fetch_rows(
+        &self,
+        sheet: &SheetReference,
+        access_token: &str,
+        mapping: &SheetsColumnMapping,
+    ) -> Result<Vec<Vec<String>>> {
+        let client = reqwest::Client::new();
+        let title = match &mapping.sheet {
+            Some(title) => title.clone(),
+            None => {
+                self.resolve_sheet_title(&client, sheet, access_token)
+                    .await?
+            }
+        };
+
+        let mut url = reqwest::Url::parse(SHEETS_API_URL)?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow!("Invalid Sheets API URL"))?
+            .push(&sheet.spreadsheet_id)
+            .push("values")
+            .push(&format!("'{}'", title.replace('\'', "''")));
+        let body: Value = client
+            .get(url)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .context("Failed to reach the Google Sheets API")?
+            .error_for_status()
+            .context("Google Sheets API rejected the request")?
+            .json()
+            .await?;
+
+        let rows = body
+            .get("values")
+            .and_then(Value::as_array)
+            .map(|rows| {
+                rows.iter()
+                    .take(MAX_ROWS)
+                    .map(|row| {
+                        row.as_array()
+                            .map(|cells| cells.iter().map(cell_text).collect())
+                            .unwrap_or_default()
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(rows)
+    }
+
+    /// Find the title of the tab referenced by `gid`, or of the first tab.
+    async fn resolve_sheet_title(
+        &self,
+        client: &reqwest::Client,
+        sheet: &SheetReference,
+        access_token: &str,
+    ) -> Result<String> {
+        let mut url = reqwest::Url::parse(SHEETS_API_URL)?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow!("Invalid Sheets API URL"))?
+            .push(&sheet.spreadsheet_id);
+        url.query_pairs_mut()
+            .append_pair("fields", "sheets.properties(sheetId,title)");
+        let body: Value = client
+            .get(url)
+            .bearer_auth(access_token)
+            .send()
+            .await
+            .context("Failed to reach the Google Sheets API")?
+            .error_for_status()
+            .context("Google Sheets API rejected the request")?
+            .json()
+            .await?;
+
+        let sheets = body
+            .get("sheets")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        sheets
+            .iter()
+            .map(|s| &s["properties"])
+            .find(|p| {
+                sheet
+                    .gid
+                    .is_none_or(|gid| p["sheetId"].as_i64() == Some(gid))
+            })
+            .and_then(|p| p["title"].as_str())
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("Sheet not found in spreadsheet"))
+    }
+
+    /// Convert dictionary rows into tables. Rows without a table name continue the previous
+    /// table, so dictionaries that only name the table on its first row are supported.
+    pub fn parse(
+        &self,
+        rows: &[Vec<String>],
+        mapping: &SheetsColumnMapping,
+    ) -> Result<(Vec<Table>, Vec<ParserError>)> {
+        let header_index = mapping.header_row.max(1) - 1;
+        let Some(headers) = rows.get(header_index) else {
+            bail!("Header row {} not found", mapping.header_row);
+        };
+        let find = |name: &str| {
+            headers
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(name.trim()))
+        };
+        let required = |name: &str| find(name).ok_or_else(|| anyhow!("Missing column '{}'", name));
+        let optional = |name: &Option<String>| name.as_deref().and_then(find);
+
+        let table_idx = required(&mapping.table)?;
+        let column_idx = required(&mapping.column)?;
+        let type_idx = required(&mapping.data_type)?;
+        let description_idx = optional(&mapping.description);
+        let nullable_idx = optional(&mapping.nullable);
+        let pk_idx = optional(&mapping.primary_key);
+        let schema_idx = optional(&mapping.schema);
+        let catalog_idx = optional(&mapping.catalog);
+        let tags_idx = optional(&mapping.tags);
+
+        let mut tables: Vec<Table> = Vec::new();
+        let mut errors = Vec::new();
+        let mut current_table: Option<String> = None;
+
+        for (offset, row) in rows.iter().enumerate().skip(header_index + 1) {
+            let cell = |idx: usize| row.get(idx).map(|s| s.trim()).unwrap_or("");
+            let cell_opt = |idx: Option<usize>| idx.map(cell).filter(|s| !s.is_empty());
+            let row_number = offset + 1;
+
+            if !cell(table_idx).is_empty() {
+                current_table = Some(cell(table_idx).to_string());
+            }
+            let column_name = cell(column_idx);
+            if column_name.is_empty() {
+                continue;
+            }
+            let Some(table_name) = current_table.clone() else {
+                errors.push(ParserError {
+                    error_type: "missing_table".to_string(),
+                    field: Some(format!("row {}", row_number)),
+                    message: format!("Column '{}' has no table name", column_name),
+                });
+                continue;
+            };
+            let data_type = match cell(type_idx) {
+                "" => {
+                    errors.push(ParserError {
+                        error_type: "missing_data_type".to_string(),
+                        field: Some(format!("{}.{}", table_name, column_name)),
+                        message: format!(
+                            "Row {} has no data type; defaulted to STRING",
+                            row_number
+                        ),
+                    });
+                    "STRING"
+                }
+                data_type => data_type,
+            };
+
+            let table_pos = match tables.iter().position(|t| t.name == table_name) {
+                Some(pos) => pos,
+                None => {
+                    tables.push(Table::new(table_name.clone(), Vec::new()));
+                    tables.len() - 1
+                }
+            };
+            let table = &mut tables[table_pos];
+            if table.schema_name.is_none() {
+                table.schema_name = cell_opt(schema_idx).map(str::to_string);
+            }
+            if table.catalog_name.is_none() {
+                table.catalog_name = cell_opt(catalog_idx).map(str::to_string);
+            }
+            for tag in cell_opt(tags_idx).into_iter().flat_map(|t| t.split(',')) {
+                let tag = tag.trim().to_string();
+                if !tag.is_empty() && !table.tags.contains(&tag) {
+                    table.tags.push(tag);
+                }
+            }
+
+            if table.columns.iter().any(|c| c.name == column_name) {
+                errors.push(ParserError {
+                    error_type: "duplicate_column".to_string(),
+                    field: Some(format!("{}.{}", table_name, column_name)),
+                    message: format!("Row {} repeats an earlier column; skipped", row_number),
+                });
+                continue;
+            }
+            let mut column = Column::new(column_name.to_string(), data_type.to_string());
+            column.primary_key = cell_opt(pk_idx).is_some_and(is_truthy);
+            column.nullable = !column.primary_key && cell_opt(nullable_idx).is_none_or(is_truthy);
+            column.description = cell_opt(description_idx).unwrap_or_default().to_string();
+            column.column_order = table.columns.len() as i32;
+            table.columns.push(column);
+        }
+
+        Ok((tables, errors))
+    }
+}
+
+fn cell_text(cell: &Value) -> String {
+    match cell {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.to_lowercase().as_str(),
+        "1" | "true" | "yes" | "y" | "x" | "pk" | "✓" | "✔"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(data: &[&[&str]]) -> Vec<Vec<String>> {
+        data.iter()
+            .map(|r| r.iter().map(|c| c.to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_dictionary_with_custom_mapping() {
+        let reference =
+            SheetReference::from_url("https://docs.google.com/spreadsheets/d/1AbC_d-9/edit#gid=42")
+                .unwrap();
+        assert_eq!(reference.spreadsheet_id, "1AbC_d-9");
+        assert_eq!(reference.gid, Some(42));
+        assert!(SheetReference::from_url("https://example.com/spreadsheets/d/x").is_none());
+
+        let mapping = SheetsColumnMapping {
+            header_row: 2,
+            table: "Entity".to_string(),
+            column: "Field".to_string(),
+            data_type: "Type".to_string(),
+            primary_key: Some("Key?".to_string()),
+            tags: Some("Tags".to_string()),
+            ..Default::default()
+        };
+        let sheet = rows(&[
+            &["Sales data dictionary"],
+            &["Entity", "Field", "Type", "Key?", "Description", "Tags"],
+            &["orders", "id", "BIGINT", "Y", "Order id", "pii, core"],
+            &["", "amount", "DECIMAL(10,2)", "", "", ""],
+            &["", "amount", "DECIMAL(10,2)", "", "", ""],
+            &["customers", "name", "", "", "", ""],
+        ]);
+
+        let (tables, errors) = GoogleSheetsParser::new().parse(&sheet, &mapping).unwrap();
+        assert_eq!(tables.len(), 2);
+        let orders = &tables[0];
+        assert_eq!(orders.columns.len(), 2);
+        assert!(orders.columns[0].primary_key && !orders.columns[0].nullable);
+        assert_eq!(orders.columns[0].description, "Order id");
+        assert_eq!(orders.tags, vec!["pii", "core"]);
+        assert_eq!(tables[1].columns[0].data_type, "STRING");
+        let kinds: Vec<&str> = errors.iter().map(|e| e.error_type.as_str()).collect();
+        assert_eq!(kinds, vec!["duplicate_column", "missing_data_type"]);
+
+        assert!(
+            GoogleSheetsParser::new()
+                .parse(&sheet, &SheetsColumnMapping::default())
+                .is_err()
+        );
+    }
+}
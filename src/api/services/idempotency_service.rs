@@ -0,0 +1,286 @@
+//! Idempotency keys for retried requests.
+//!
+//! Clients on flaky networks retry uploads. When a request carries an `Idempotency-Key`
+//! header, the first request with that key runs and its successful response is kept for the
+//! dedupe window; repeats within the window get the original response instead of running
+//! again. A repeat that arrives while the first request is still running waits for it.
+//!
+//! Keys are scoped per user, method and path, and bound to a fingerprint of the request body:
+//! reusing a key with a different body is rejected. For multipart bodies the boundary is left
+//! out of the fingerprint, since clients pick a new one on every retry.
+//!
+//! Configuration (environment variables):
+//! - `IMPORT_IDEMPOTENCY_WINDOW_SECS` (default 86400): how long responses are replayed
+
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+const DEFAULT_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+/// A buffered response that can be replayed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub content_type: Option<String>,
+    pub body: Vec<u8>,
+}
+
+impl StoredResponse {
+    fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+type Completion = watch::Receiver<Option<Arc<StoredResponse>>>;
+
+enum Slot {
+    InFlight {
+        fingerprint: String,
+        completion: Completion,
+    },
+    Done {
+        fingerprint: String,
+        response: Arc<StoredResponse>,
+        stored_at: Instant,
+    },
+}
+
+/// Outcome of claiming an idempotency key.
+pub enum Claim {
+    /// First request with this key: run it and call [`ClaimGuard::complete`]
+    Run(ClaimGuard),
+    /// The key was already used within the window
+    Replay(Arc<StoredResponse>),
+    /// A request with this key is still running; wait with [`wait_for_completion`]
+    InProgress(Completion),
+    /// The key was used with a different request body
+    Mismatch,
+}
+
+/// In-memory idempotency key store with a dedupe window.
+pub struct IdempotencyCache {
+    window: Duration,
+    slots: Mutex<HashMap<String, Slot>>,
+}
+
+impl IdempotencyCache {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            slots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Configure from `IMPORT_IDEMPOTENCY_WINDOW_SECS`.
+    pub fn from_env() -> Self {
+        Self::new(Duration::from_secs(
+            env::var("IMPORT_IDEMPOTENCY_WINDOW_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_WINDOW_SECS),
+        ))
+    }
+
+    /// Claim `key` for a request whose body has the given fingerprint.
+    pub fn claim(self: &Arc<Self>, key: &str, fingerprint: &str) -> Claim {
+        let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        let window = self.window;
+        slots.retain(|_, slot| match slot {
+            Slot::Done { stored_at, .. } => stored_at.elapsed() < window,
+            Slot::InFlight { .. } => true,
+        });
+
+        match slots.get(key) {
+            Some(Slot::Done {
+                fingerprint: stored,
+                response,
+                ..
+            }) => {
+                if stored == fingerprint {
+                    Claim::Replay(response.clone())
+                } else {
+                    Claim::Mismatch
+                }
+            }
+            Some(Slot::InFlight {
+                fingerprint: stored,
+                completion,
+            }) => {
+                if stored == fingerprint {
+                    Claim::InProgress(completion.clone())
+                } else {
+                    Claim::Mismatch
+                }
+            }
+            None => {
+                let (sender, completion) = watch::channel(None);
+                slots.insert(
+                    key.to_string(),
+                    Slot::InFlight {
+                        fingerprint: fingerprint.to_string(),
+                        completion,
+                    },
+                );
+                Claim::Run(ClaimGuard {
+                    cache: self.clone(),
+                    key: key.to_string(),
+                    fingerprint: fingerprint.to_string(),
+                    sender,
+                    completed: false,
+                })
+            }
+        }
+    }
+}
+
+/// Held by the request that runs; releases the key if the request is abandoned.
+pub struct ClaimGuard {
+    cache: Arc<IdempotencyCache>,
+    key: String,
+    fingerprint: String,
+    sender: watch::Sender<Option<Arc<StoredResponse>>>,
+    completed: bool,
+}
+
+impl ClaimGuard {
+    /// Record the response. Successful responses are replayed for the window; failures
+    /// release the key so the request can be retried.
+    pub fn complete(mut self, response: StoredResponse) {
+        let response = Arc::new(response);
+        {
+            let mut slots = self.cache.slots.lock().unwrap_or_else(|e| e.into_inner());
+            if response.is_success() {
+                slots.insert(
+                    self.key.clone(),
+                    Slot::Done {
+                        fingerprint: self.fingerprint.clone(),
+                        response: response.clone(),
+                        stored_at: Instant::now(),
+                    },
+                );
+            } else {
+                slots.remove(&self.key);
+            }
+        }
+        self.completed = true;
+        self.sender.send_replace(Some(response));
+    }
+}
+
+impl Drop for ClaimGuard {
+    fn drop(&mut self) {
+        if !self.completed {
+            let mut slots = self.cache.slots.lock().unwrap_or_else(|e| e.into_inner());
+            slots.remove(&self.key);
+        }
+    }
+}
+
+/// Wait for the running request with the same key; `None` if it was abandoned.
+pub async fn wait_for_completion(mut completion: Completion) -> Option<Arc<StoredResponse>> {
+    completion
+        .wait_for(Option::is_some)
+        .await
+        .ok()
+        .and_then(|response| response.clone())
+}
+
+/// Fingerprint of a request body, ignoring the multipart boundary.
+pub fn body_fingerprint(content_type: Option<&str>, body: &[u8]) -> String {
+    let boundary = content_type
+        .filter(|ct| ct.starts_with("multipart/"))
+        .and_then(|ct| {
+            ct.split(';')
+                .find_map(|p| p.trim().strip_prefix("boundary="))
+        })
+        .map(|b| b.trim_matches('"'))
+        .filter(|b| !b.is_empty());
+
+    let mut hasher = Sha256::new();
+    match boundary {
+        Some(boundary) => {
+            let boundary = boundary.as_bytes();
+            let mut rest = body;
+            while let Some(pos) = rest
+                .windows(boundary.len())
+                .position(|window| window == boundary)
+            {
+                hasher.update(&rest[..pos]);
+                rest = &rest[pos + boundary.len()..];
+            }
+            hasher.update(rest);
+        }
+        None => hasher.update(body),
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ok_response() -> StoredResponse {
+        StoredResponse {
+            status: 200,
+            content_type: Some("application/json".to_string()),
+            body: b"{\"tables\":[]}".to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_claim_replay_and_release() {
+        let cache = Arc::new(IdempotencyCache::new(Duration::from_secs(60)));
+
+        let Claim::Run(guard) = cache.claim("k1", "fp") else {
+            panic!("first claim should run");
+        };
+        let Claim::InProgress(completion) = cache.claim("k1", "fp") else {
+            panic!("concurrent claim should wait");
+        };
+        assert!(matches!(cache.claim("k1", "other"), Claim::Mismatch));
+        guard.complete(ok_response());
+        assert_eq!(
+            wait_for_completion(completion).await.as_deref(),
+            Some(&ok_response())
+        );
+        assert!(matches!(cache.claim("k1", "fp"), Claim::Replay(r) if *r == ok_response()));
+
+        // Failed and abandoned requests release the key
+        let Claim::Run(guard) = cache.claim("k2", "fp") else {
+            panic!("first claim should run");
+        };
+        guard.complete(StoredResponse {
+            status: 500,
+            content_type: None,
+            body: Vec::new(),
+        });
+        let Claim::Run(guard) = cache.claim("k2", "fp") else {
+            panic!("failed request should be retryable");
+        };
+        drop(guard);
+        assert!(matches!(cache.claim("k2", "fp"), Claim::Run(_)));
+    }
+
+    #[test]
+    fn test_multipart_fingerprint_ignores_boundary() {
+        let body = |b: &str| {
+            format!(
+                "--{b}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"a.sql\"\r\n\r\nCREATE TABLE a (id INT);\r\n--{b}--\r\n"
+            )
+        };
+        let first = body_fingerprint(
+            Some("multipart/form-data; boundary=abc123"),
+            body("abc123").as_bytes(),
+        );
+        let retry = body_fingerprint(
+            Some("multipart/form-data; boundary=\"xyz789\""),
+            body("xyz789").as_bytes(),
+        );
+        assert_eq!(first, retry);
+        assert_ne!(first, body_fingerprint(None, body("abc123").as_bytes()));
+    }
+}
@@ -0,0 +1,456 @@
+//! Supertype/subtype (inheritance) modeling.
+//!
+//! A supertype table lists its subtype tables and the discriminator column telling them
+//! apart (`odcl_metadata.subtypes`). Diagrams draw the hierarchy as generalizations; exports
+//! map it to tables with one of the classic strategies, chosen at export time.
+
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::column::ForeignKey;
+use crate::models::enums::{Cardinality, RelationshipType};
+use crate::models::relationship::ForeignKeyDetails;
+use crate::models::table::SUBTYPES_KEY;
+use crate::models::{DataModel, Relationship, SubtypeHierarchy, Table};
+
+/// How a supertype/subtype hierarchy is mapped to tables on export
+#[allow(clippy::enum_variant_names)] // The patterns' usual names
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema, clap::ValueEnum)]
+#[serde(rename_all = "snake_case")]
+#[value(rename_all = "snake_case")]
+pub enum InheritanceStrategy {
+    /// One table: the subtypes' columns are folded into the supertype
+    SingleTable,
+    /// A table per type: subtypes share the supertype's primary key and reference it
+    ClassTable,
+    /// A table per concrete type: subtypes repeat the supertype's columns
+    ConcreteTable,
+}
+
+/// Supertypes of the tables with their hierarchies; malformed hierarchies are skipped
+pub fn hierarchies(tables: &[Table]) -> Vec<(&Table, SubtypeHierarchy)> {
+    tables
+        .iter()
+        .filter_map(|table| match table.subtypes() {
+            Ok(Some(hierarchy)) => Some((table, hierarchy)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Problems with a supertype's hierarchy: a missing discriminator column, unknown or
+/// repeated subtypes, empty or repeated discriminator values, and subtypes that already
+/// belong to another supertype.
+pub fn hierarchy_problems(
+    tables: &[Table],
+    supertype: &Table,
+    hierarchy: &SubtypeHierarchy,
+) -> Vec<String> {
+    let mut problems = Vec::new();
+    if !supertype
+        .columns
+        .iter()
+        .any(|c| c.name.eq_ignore_ascii_case(&hierarchy.discriminator_column))
+    {
+        problems.push(format!(
+            "Supertype {} has no discriminator column '{}'",
+            supertype.name, hierarchy.discriminator_column
+        ));
+    }
+    for (i, subtype) in hierarchy.subtypes.iter().enumerate() {
+        let earlier = &hierarchy.subtypes[..i];
+        if subtype.table_id == supertype.id {
+            problems.push(format!("{} cannot be its own subtype", supertype.name));
+            continue;
+        }
+        let Some(table) = tables.iter().find(|t| t.id == subtype.table_id) else {
+            problems.push(format!("Subtype table {} not found", subtype.table_id));
+            continue;
+        };
+        if earlier.iter().any(|s| s.table_id == subtype.table_id) {
+            problems.push(format!("Subtype {} is listed twice", table.name));
+        }
+        if subtype.discriminator_value.trim().is_empty() {
+            problems.push(format!(
+                "Subtype {} has an empty discriminator value",
+                table.name
+            ));
+        } else if earlier
+            .iter()
+            .any(|s| s.discriminator_value == subtype.discriminator_value)
+        {
+            problems.push(format!(
+                "Discriminator value '{}' is used twice",
+                subtype.discriminator_value
+            ));
+        }
+        for (other, other_hierarchy) in hierarchies(tables) {
+            if other.id != supertype.id
+                && other_hierarchy
+                    .subtypes
+                    .iter()
+                    .any(|s| s.table_id == subtype.table_id)
+            {
+                problems.push(format!(
+                    "{} is already a subtype of {}",
+                    table.name, other.name
+                ));
+            }
+        }
+    }
+    problems
+}
+
+/// Map the model's hierarchies to tables with the strategy
+pub fn apply_inheritance(model: &DataModel, strategy: InheritanceStrategy) -> DataModel {
+    let mut model = model.clone();
+    let supertypes: Vec<(Uuid, SubtypeHierarchy)> = hierarchies(&model.tables)
+        .into_iter()
+        .map(|(table, hierarchy)| (table.id, hierarchy))
+        .collect();
+    for (supertype_id, hierarchy) in supertypes {
+        let Some(supertype) = model.get_table_by_id(supertype_id).cloned() else {
+            continue;
+        };
+        match strategy {
+            InheritanceStrategy::SingleTable => single_table(&mut model, &supertype, &hierarchy),
+            InheritanceStrategy::ClassTable => class_table(&mut model, &supertype, &hierarchy),
+            InheritanceStrategy::ConcreteTable => {
+                concrete_table(&mut model, &supertype, &hierarchy)
+            }
+        }
+        if strategy != InheritanceStrategy::ClassTable
+            && let Some(supertype) = model.get_table_by_id_mut(supertype_id)
+        {
+            supertype.odcl_metadata.remove(SUBTYPES_KEY);
+        }
+    }
+    for table in &mut model.tables {
+        for (order, column) in table.columns.iter_mut().enumerate() {
+            column.column_order = order as i32;
+        }
+    }
+    model
+}
+
+fn has_column(table: &Table, name: &str) -> bool {
+    table
+        .columns
+        .iter()
+        .any(|c| c.name.eq_ignore_ascii_case(name))
+}
+
+fn links(relationship: &Relationship, a: Uuid, b: Uuid) -> bool {
+    (relationship.source_table_id == a && relationship.target_table_id == b)
+        || (relationship.source_table_id == b && relationship.target_table_id == a)
+}
+
+/// Fold the subtypes' columns into the supertype and drop the subtype tables
+fn single_table(model: &mut DataModel, supertype: &Table, hierarchy: &SubtypeHierarchy) {
+    let subtype_ids: HashSet<Uuid> = hierarchy.subtypes.iter().map(|s| s.table_id).collect();
+    let mut merged = supertype.clone();
+    for subtype in &hierarchy.subtypes {
+        let Some(table) = model.get_table_by_id(subtype.table_id) else {
+            continue;
+        };
+        for column in &table.columns {
+            if column.primary_key || has_column(&merged, &column.name) {
+                continue;
+            }
+            let mut column = column.clone();
+            // Rows of the other types have no value for it
+            column.nullable = true;
+            if column
+                .foreign_key
+                .as_ref()
+                .is_some_and(|fk| fk.table_id == supertype.id.to_string())
+            {
+                column.foreign_key = None;
+            }
+            merged.columns.push(column);
+        }
+    }
+    if let Some(discriminator) = merged
+        .columns
+        .iter_mut()
+        .find(|c| c.name.eq_ignore_ascii_case(&hierarchy.discriminator_column))
+    {
+        discriminator.nullable = false;
+        if discriminator.enum_values.is_empty() {
+            discriminator.enum_values = hierarchy
+                .subtypes
+                .iter()
+                .map(|s| s.discriminator_value.clone())
+                .collect();
+        }
+    }
+
+    model.tables.retain(|t| !subtype_ids.contains(&t.id));
+    if let Some(table) = model.get_table_by_id_mut(supertype.id) {
+        *table = merged;
+    }
+    model.relationships.retain(|r| {
+        !subtype_ids
+            .iter()
+            .any(|&subtype_id| links(r, subtype_id, supertype.id))
+    });
+    for relationship in &mut model.relationships {
+        if subtype_ids.contains(&relationship.source_table_id) {
+            relationship.source_table_id = supertype.id;
+        }
+        if subtype_ids.contains(&relationship.target_table_id) {
+            relationship.target_table_id = supertype.id;
+        }
+    }
+}
+
+/// Give each subtype the supertype's primary key, referencing the supertype
+fn class_table(model: &mut DataModel, supertype: &Table, hierarchy: &SubtypeHierarchy) {
+    let key: Vec<_> = supertype.columns.iter().filter(|c| c.primary_key).collect();
+    let key_names: Vec<String> = key.iter().map(|c| c.name.clone()).collect();
+    if key.is_empty() {
+        return;
+    }
+    for subtype in &hierarchy.subtypes {
+        let Some(table) = model.get_table_by_id_mut(subtype.table_id) else {
+            continue;
+        };
+        // The subtype is identified by the supertype's key
+        for column in &mut table.columns {
+            if !key_names
+                .iter()
+                .any(|k| k.eq_ignore_ascii_case(&column.name))
+            {
+                column.primary_key = false;
+            }
+        }
+        for (position, key_column) in key.iter().enumerate() {
+            let foreign_key = ForeignKey {
+                table_id: supertype.id.to_string(),
+                column_name: key_column.name.clone(),
+                constraint_name: None,
+                key_position: None,
+            };
+            match table
+                .columns
+                .iter_mut()
+                .find(|c| c.name.eq_ignore_ascii_case(&key_column.name))
+            {
+                Some(column) => {
+                    column.primary_key = true;
+                    column.nullable = false;
+                    column.foreign_key = Some(foreign_key);
+                }
+                None => {
+                    let mut column = (*key_column).clone();
+                    column.foreign_key = Some(foreign_key);
+                    table.columns.insert(position, column);
+                }
+            }
+        }
+        if !model
+            .relationships
+            .iter()
+            .any(|r| links(r, subtype.table_id, supertype.id))
+        {
+            let mut relationship = Relationship::new(subtype.table_id, supertype.id);
+            relationship.cardinality = Some(Cardinality::OneToOne);
+            relationship.relationship_type = Some(RelationshipType::ForeignKey);
+            relationship.foreign_key_details =
+                ForeignKeyDetails::from_columns(&key_names, &key_names).ok();
+            model.relationships.push(relationship);
+        }
+    }
+}
+
+/// Repeat the supertype's columns in each subtype; the supertype is dropped if every row
+/// belongs to a subtype
+fn concrete_table(model: &mut DataModel, supertype: &Table, hierarchy: &SubtypeHierarchy) {
+    let inherited: Vec<_> = supertype
+        .columns
+        .iter()
+        .filter(|c| !c.name.eq_ignore_ascii_case(&hierarchy.discriminator_column))
+        .cloned()
+        .collect();
+    let supertype_ref = supertype.id.to_string();
+    for subtype in &hierarchy.subtypes {
+        let Some(table) = model.get_table_by_id_mut(subtype.table_id) else {
+            continue;
+        };
+        let mut columns: Vec<_> = inherited
+            .iter()
+            .filter(|c| !has_column(table, &c.name))
+            .cloned()
+            .collect();
+        columns.append(&mut table.columns);
+        for column in &mut columns {
+            if column
+                .foreign_key
+                .as_ref()
+                .is_some_and(|fk| fk.table_id == supertype_ref)
+            {
+                column.foreign_key = None;
+            }
+        }
+        table.columns = columns;
+
+        // The subtype inherits the supertype's references to other tables
+        let inherited_relationships: Vec<Relationship> = model
+            .relationships
+            .iter()
+            .filter(|r| {
+                r.source_table_id == supertype.id
+                    && !hierarchy
+                        .subtypes
+                        .iter()
+                        .any(|s| s.table_id == r.target_table_id)
+            })
+            .map(|r| {
+                let mut relationship = r.clone();
+                relationship.id = Uuid::new_v4();
+                relationship.source_table_id = subtype.table_id;
+                relationship.drawio_edge_id = None;
+                relationship.visual_metadata = None;
+                relationship
+            })
+            .collect();
+        model.relationships.extend(inherited_relationships);
+    }
+    model.relationships.retain(|r| {
+        !hierarchy
+            .subtypes
+            .iter()
+            .any(|s| links(r, s.table_id, supertype.id))
+    });
+    if hierarchy.complete {
+        model.tables.retain(|t| t.id != supertype.id);
+        model
+            .relationships
+            .retain(|r| r.source_table_id != supertype.id && r.target_table_id != supertype.id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Column, Subtype};
+
+    fn column(name: &str, primary_key: bool) -> Column {
+        let mut column = Column::new(name.to_string(), "VARCHAR(64)".to_string());
+        column.primary_key = primary_key;
+        column.nullable = !primary_key;
+        column
+    }
+
+    fn model() -> DataModel {
+        let mut party = Table::new(
+            "party".to_string(),
+            vec![column("id", true), column("party_type", false)],
+        );
+        let person = Table::new("person".to_string(), vec![column("birth_date", false)]);
+        let organisation = Table::new(
+            "organisation".to_string(),
+            vec![column("id", true), column("registration_no", false)],
+        );
+        let country = Table::new("country".to_string(), vec![column("code", true)]);
+        let hierarchy = SubtypeHierarchy {
+            discriminator_column: "party_type".to_string(),
+            subtypes: vec![
+                Subtype {
+                    table_id: person.id,
+                    discriminator_value: "P".to_string(),
+                },
+                Subtype {
+                    table_id: organisation.id,
+                    discriminator_value: "O".to_string(),
+                },
+            ],
+            complete: true,
+        };
+        party
+            .odcl_metadata
+            .insert(SUBTYPES_KEY.to_string(), serde_json::json!(hierarchy));
+        let mut model = DataModel::new("m".to_string(), String::new(), String::new());
+        model.relationships = vec![
+            Relationship::new(party.id, country.id),
+            Relationship::new(organisation.id, party.id),
+        ];
+        model.tables = vec![party, person, organisation, country];
+        model
+    }
+
+    #[test]
+    fn test_hierarchy_exported_with_each_strategy() {
+        let model = model();
+        let (party, hierarchy) = hierarchies(&model.tables).pop().unwrap();
+        assert!(hierarchy_problems(&model.tables, party, &hierarchy).is_empty());
+        let mut broken = hierarchy.clone();
+        broken.discriminator_column = "kind".to_string();
+        broken.subtypes[1].discriminator_value = "P".to_string();
+        assert_eq!(
+            hierarchy_problems(&model.tables, party, &broken),
+            vec![
+                "Supertype party has no discriminator column 'kind'",
+                "Discriminator value 'P' is used twice"
+            ]
+        );
+        let (party_id, person_id, organisation_id, country_id) = (
+            model.tables[0].id,
+            model.tables[1].id,
+            model.tables[2].id,
+            model.tables[3].id,
+        );
+
+        let single = apply_inheritance(&model, InheritanceStrategy::SingleTable);
+        assert_eq!(single.tables.len(), 2);
+        let party = single.get_table_by_id(party_id).unwrap();
+        let names: Vec<_> = party.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["id", "party_type", "birth_date", "registration_no"]);
+        assert_eq!(party.columns[1].enum_values, ["P", "O"]);
+        assert_eq!(party.columns[3].column_order, 3);
+        assert!(party.subtypes().unwrap().is_none());
+        assert_eq!(single.relationships.len(), 1);
+
+        let class = apply_inheritance(&model, InheritanceStrategy::ClassTable);
+        assert_eq!(class.tables.len(), 4);
+        let person = class.get_table_by_id(person_id).unwrap();
+        assert!(person.columns[0].primary_key);
+        assert_eq!(
+            person.columns[0].foreign_key.as_ref().unwrap().table_id,
+            party_id.to_string()
+        );
+        // The existing organisation → party relationship is kept, person gets one
+        assert_eq!(class.relationships.len(), 3);
+        assert!(
+            class
+                .relationships
+                .iter()
+                .any(|r| r.source_table_id == person_id && r.target_table_id == party_id)
+        );
+
+        let concrete = apply_inheritance(&model, InheritanceStrategy::ConcreteTable);
+        assert!(concrete.get_table_by_id(party_id).is_none());
+        let organisation = concrete.get_table_by_id(organisation_id).unwrap();
+        let names: Vec<_> = organisation
+            .columns
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, ["id", "registration_no"]);
+        assert_eq!(
+            concrete.get_table_by_id(person_id).unwrap().columns.len(),
+            2
+        );
+        // Each subtype inherits the supertype's reference to country
+        assert_eq!(concrete.relationships.len(), 2);
+        assert!(
+            concrete
+                .relationships
+                .iter()
+                .all(|r| r.target_table_id == country_id)
+        );
+    }
+}
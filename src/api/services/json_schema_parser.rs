@@ -147,6 +147,7 @@ impl JSONSchemaParser {
             data_vault_classification: None,
             modeling_level: None,
             tags: Vec::new(),
+            aliases: Vec::new(),
             odcl_metadata,
             position: None,
             yaml_file_path: None,
@@ -243,6 +244,10 @@ impl JSONSchemaParser {
                         quality: Vec::new(),
                         enum_values: Vec::new(),
                         errors: Vec::new(),
+                        classification: None,
+                        masking_policy: None,
+                        glossary_links: Vec::new(),
+                        custom_properties: HashMap::new(),
                         column_order: 0,
                     });
                 }
@@ -320,6 +325,10 @@ impl JSONSchemaParser {
                     quality: Vec::new(),
                     enum_values: Vec::new(),
                     errors: Vec::new(),
+                    classification: None,
+                    masking_policy: None,
+                    glossary_links: Vec::new(),
+                    custom_properties: HashMap::new(),
                     column_order: 0,
                 });
             }
@@ -339,6 +348,10 @@ impl JSONSchemaParser {
                     quality: Vec::new(),
                     enum_values: Vec::new(),
                     errors: Vec::new(),
+                    classification: None,
+                    masking_policy: None,
+                    glossary_links: Vec::new(),
+                    custom_properties: HashMap::new(),
                     column_order: 0,
                 });
             }
@@ -2,13 +2,18 @@
 //!
 //! Provides time-scoped JWT tokens for API authentication.
 //! - Access tokens: Short-lived (15 minutes) for API requests
-//! - Refresh tokens: Longer-lived (7 days) for obtaining new access tokens
+//! - Refresh tokens: Longer-lived (7 days) for obtaining new access tokens; each carries a `jti`
+//!   so it can be rotated and revoked (see `token_revocation_service`)
+//! - Diagram share tokens: Read-only access to one domain's rendered diagram, for wiki embeds
+//! - Domain share tokens: Read-only browsing of one domain, valid while its share record exists
 
-use chrono::{Duration, Utc};
+use chrono::{DateTime, Duration, Utc};
 use jsonwebtoken::{DecodingKey, EncodingKey, Header, TokenData, Validation, decode, encode};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::{info, warn};
+use uuid::Uuid;
 
 /// JWT claims structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +32,9 @@ pub struct Claims {
     pub token_type: TokenType,
     /// Session ID (for tracking/revocation)
     pub session_id: String,
+    /// Token ID of refresh tokens (for rotation/revocation)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -34,6 +42,30 @@ pub struct Claims {
 pub enum TokenType {
     Access,
     Refresh,
+    #[serde(rename = "diagram_share")]
+    DiagramShare,
+    #[serde(rename = "domain_share")]
+    DomainShare,
+    #[serde(rename = "domain_invite")]
+    DomainInvite,
+}
+
+/// Claims of a diagram share token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagramShareClaims {
+    /// Email of the user who shared the diagram (whose workspace is rendered)
+    pub sub: String,
+    /// Domain whose diagram is rendered
+    pub domain: String,
+    /// Tables to render; empty for the whole domain
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub table_ids: Vec<Uuid>,
+    /// Expiration time (Unix timestamp)
+    pub exp: i64,
+    /// Issued at (Unix timestamp)
+    pub iat: i64,
+    /// Always `diagram_share`
+    pub token_type: TokenType,
 }
 
 /// Token pair returned after authentication
@@ -46,6 +78,40 @@ pub struct TokenPair {
     pub token_type: String,
 }
 
+/// Claims of a domain share token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainShareClaims {
+    /// Email of the user who shared the domain (whose workspace is browsed)
+    pub sub: String,
+    /// Shared domain
+    pub domain: String,
+    /// Share record that must still exist for the token to work
+    pub share_id: Uuid,
+    /// Expiration time (Unix timestamp)
+    pub exp: i64,
+    /// Issued at (Unix timestamp)
+    pub iat: i64,
+    /// Always `domain_share`
+    pub token_type: TokenType,
+}
+
+/// Claims of a domain invite token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DomainInviteClaims {
+    /// Email of the user whose workspace holds the domain
+    pub sub: String,
+    /// Domain the invite is for
+    pub domain: String,
+    /// Invite record that must still exist for the token to work
+    pub invite_id: Uuid,
+    /// Expiration time (Unix timestamp)
+    pub exp: i64,
+    /// Issued at (Unix timestamp)
+    pub iat: i64,
+    /// Always `domain_invite`
+    pub token_type: TokenType,
+}
+
 /// JWT Service configuration
 #[derive(Clone)]
 pub struct JwtService {
@@ -55,6 +121,18 @@ pub struct JwtService {
     refresh_token_duration: Duration,
 }
 
+/// Secret of the local mode data directory, generated on first use.
+fn local_secret() -> Result<String, String> {
+    let local = super::local_mode::local_mode().ok_or("Local mode is not enabled")?;
+    info!(
+        "JWT_SECRET not set; using the secret stored in {:?}",
+        local.data_dir
+    );
+    local
+        .jwt_secret()
+        .map_err(|e| format!("Failed to read or create the local JWT secret: {}", e))
+}
+
 impl JwtService {
     /// Create a new JWT service with the given secret
     ///
@@ -72,16 +150,22 @@ impl JwtService {
     /// Create a new JWT service from environment variables.
     ///
     /// In production (APP_ENV != "development"), this will panic if JWT_SECRET is not set.
-    /// In development, falls back to an insecure default secret with a warning.
+    /// In development, falls back to an insecure default secret with a warning. In local mode,
+    /// falls back to a random secret stored in the data directory.
     ///
     /// # Panics
     /// Panics in production if JWT_SECRET environment variable is not set.
     pub fn from_env() -> Self {
         let app_env = std::env::var("APP_ENV").unwrap_or_else(|_| "production".to_string());
-        let is_development = app_env.to_lowercase() == "development";
+        // Local mode never issues user tokens, so it does not need a production secret
+        let is_development =
+            app_env.to_lowercase() == "development" || super::local_mode::local_mode().is_some();
 
         let secret = match std::env::var("JWT_SECRET") {
             Ok(s) => s,
+            Err(_) if super::local_mode::local_mode().is_some() => {
+                local_secret().unwrap_or_else(|e| panic!("CRITICAL: {}", e))
+            }
             Err(_) => {
                 if is_development {
                     warn!(
@@ -114,10 +198,13 @@ impl JwtService {
     #[allow(dead_code)]
     pub fn try_from_env() -> Result<Self, String> {
         let app_env = std::env::var("APP_ENV").unwrap_or_else(|_| "production".to_string());
-        let is_development = app_env.to_lowercase() == "development";
+        // Local mode never issues user tokens, so it does not need a production secret
+        let is_development =
+            app_env.to_lowercase() == "development" || super::local_mode::local_mode().is_some();
 
         let secret = match std::env::var("JWT_SECRET") {
             Ok(s) => s,
+            Err(_) if super::local_mode::local_mode().is_some() => local_secret()?,
             Err(_) => {
                 if is_development {
                     warn!(
@@ -163,6 +250,7 @@ impl JwtService {
             iat: now.timestamp(),
             token_type: TokenType::Access,
             session_id: session_id.to_string(),
+            jti: None,
         };
 
         let access_token = encode(&Header::default(), &access_claims, &self.encoding_key)
@@ -178,6 +266,7 @@ impl JwtService {
             iat: now.timestamp(),
             token_type: TokenType::Refresh,
             session_id: session_id.to_string(),
+            jti: Some(Uuid::new_v4().to_string()),
         };
 
         let refresh_token = encode(&Header::default(), &refresh_claims, &self.encoding_key)
@@ -219,12 +308,114 @@ impl JwtService {
         Ok(token_data.claims)
     }
 
+    /// Sign a token granting read-only access to a domain's diagram image until `expires_in` passes
+    pub fn sign_diagram_share(
+        &self,
+        email: &str,
+        domain: &str,
+        table_ids: Vec<Uuid>,
+        expires_in: Duration,
+    ) -> Result<(String, i64), String> {
+        let now = Utc::now();
+        let claims = DiagramShareClaims {
+            sub: email.to_string(),
+            domain: domain.to_string(),
+            table_ids,
+            exp: (now + expires_in).timestamp(),
+            iat: now.timestamp(),
+            token_type: TokenType::DiagramShare,
+        };
+        let token = encode(&Header::default(), &claims, &self.encoding_key)
+            .map_err(|e| format!("Failed to encode diagram share token: {}", e))?;
+        Ok((token, claims.exp))
+    }
+
+    /// Validate a diagram share token and return the claims
+    pub fn validate_diagram_share(&self, token: &str) -> Result<DiagramShareClaims, String> {
+        let token_data = self.decode_claims::<DiagramShareClaims>(token)?;
+
+        if token_data.claims.token_type != TokenType::DiagramShare {
+            return Err("Invalid token type: expected diagram share token".to_string());
+        }
+
+        Ok(token_data.claims)
+    }
+
+    /// Sign a token for a domain share link; links without an expiry get a 100-year token
+    pub fn sign_domain_share(
+        &self,
+        email: &str,
+        domain: &str,
+        share_id: Uuid,
+        expires_at: Option<DateTime<Utc>>,
+    ) -> Result<String, String> {
+        let now = Utc::now();
+        let claims = DomainShareClaims {
+            sub: email.to_string(),
+            domain: domain.to_string(),
+            share_id,
+            exp: expires_at
+                .unwrap_or_else(|| now + Duration::days(36_500))
+                .timestamp(),
+            iat: now.timestamp(),
+            token_type: TokenType::DomainShare,
+        };
+        encode(&Header::default(), &claims, &self.encoding_key)
+            .map_err(|e| format!("Failed to encode domain share token: {}", e))
+    }
+
+    /// Validate a domain share token and return the claims
+    pub fn validate_domain_share(&self, token: &str) -> Result<DomainShareClaims, String> {
+        let token_data = self.decode_claims::<DomainShareClaims>(token)?;
+
+        if token_data.claims.token_type != TokenType::DomainShare {
+            return Err("Invalid token type: expected domain share token".to_string());
+        }
+
+        Ok(token_data.claims)
+    }
+
+    /// Sign a token for a domain invite
+    pub fn sign_domain_invite(
+        &self,
+        email: &str,
+        domain: &str,
+        invite_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> Result<String, String> {
+        let claims = DomainInviteClaims {
+            sub: email.to_string(),
+            domain: domain.to_string(),
+            invite_id,
+            exp: expires_at.timestamp(),
+            iat: Utc::now().timestamp(),
+            token_type: TokenType::DomainInvite,
+        };
+        encode(&Header::default(), &claims, &self.encoding_key)
+            .map_err(|e| format!("Failed to encode domain invite token: {}", e))
+    }
+
+    /// Validate a domain invite token and return the claims
+    pub fn validate_domain_invite(&self, token: &str) -> Result<DomainInviteClaims, String> {
+        let token_data = self.decode_claims::<DomainInviteClaims>(token)?;
+
+        if token_data.claims.token_type != TokenType::DomainInvite {
+            return Err("Invalid token type: expected domain invite token".to_string());
+        }
+
+        Ok(token_data.claims)
+    }
+
     /// Decode and validate a token (checks signature and expiration)
     fn decode_token(&self, token: &str) -> Result<TokenData<Claims>, String> {
+        self.decode_claims(token)
+    }
+
+    fn decode_claims<T: DeserializeOwned>(&self, token: &str) -> Result<TokenData<T>, String> {
         let mut validation = Validation::default();
         validation.validate_exp = true;
 
-        decode::<Claims>(token, &self.decoding_key, &validation).map_err(|e| match e.kind() {
+        decode::<T>(token, &self.decoding_key, &validation).map_err(|e| match e.kind() {
             jsonwebtoken::errors::ErrorKind::ExpiredSignature => "Token has expired".to_string(),
             jsonwebtoken::errors::ErrorKind::InvalidToken => "Invalid token format".to_string(),
             jsonwebtoken::errors::ErrorKind::InvalidSignature => {
@@ -306,6 +497,36 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_diagram_share_token() {
+        let service = JwtService::new("test-secret-key-at-least-32-chars");
+        let table_id = Uuid::new_v4();
+
+        let (token, _) = service
+            .sign_diagram_share(
+                "test@example.com",
+                "sales",
+                vec![table_id],
+                Duration::days(1),
+            )
+            .unwrap();
+        let claims = service.validate_diagram_share(&token).unwrap();
+        assert_eq!(claims.sub, "test@example.com");
+        assert_eq!(claims.domain, "sales");
+        assert_eq!(claims.table_ids, vec![table_id]);
+
+        // Share tokens and API tokens are not interchangeable
+        assert!(service.validate_access_token(&token).is_err());
+        let token_pair = service
+            .generate_token_pair("test@example.com", 12345, "testuser", "session-123")
+            .unwrap();
+        assert!(
+            service
+                .validate_diagram_share(&token_pair.access_token)
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_invalid_token() {
         let service = JwtService::new("test-secret-key-at-least-32-chars");
@@ -332,6 +553,16 @@ mod tests {
             .unwrap();
         assert_eq!(claims.sub, "test@example.com");
         assert_eq!(claims.session_id, "session-123");
+
+        // The refresh token is rotated
+        let old = service
+            .validate_refresh_token(&original_pair.refresh_token)
+            .unwrap();
+        let new = service
+            .validate_refresh_token(&new_pair.refresh_token)
+            .unwrap();
+        assert!(old.jti.is_some() && new.jti.is_some());
+        assert_ne!(old.jti, new.jti);
     }
 
     #[test]
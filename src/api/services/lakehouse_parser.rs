@@ -0,0 +1,689 @@
+//! Delta Lake and Apache Iceberg metadata parser.
+//!
+//! Reads table schemas from lakehouse metadata rather than DDL:
+//! - Delta Lake: `_delta_log/*.json` commit files (the latest `metaData` action wins)
+//! - Iceberg: `metadata/*.metadata.json` (the current schema and default partition spec)
+//!
+//! Nested types are preserved as `STRUCT<...>`, `ARRAY<...>` and `MAP<...>`, partition
+//! columns are recorded, and table properties become quality rules the same way as
+//! `TBLPROPERTIES` in SQL imports (a `quality` property sets the medallion layer).
+
+use crate::models::enums::{DatabaseType, MedallionLayer};
+use crate::models::{Column, Table};
+use crate::services::avro_parser::ParserError;
+use anyhow::{Context, Result};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
+use std::path::Path;
+use tracing::info;
+
+/// Maximum total uncompressed size of a metadata archive (50 MB).
+const MAX_ARCHIVE_SIZE: u64 = 50 * 1024 * 1024;
+
+/// Parser for Delta Lake and Iceberg table metadata.
+#[derive(Default)]
+pub struct LakehouseParser;
+
+impl LakehouseParser {
+    /// Create a new lakehouse metadata parser instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse a single metadata file: a Delta commit (NDJSON) or an Iceberg `metadata.json`.
+    pub fn parse(
+        &self,
+        content: &str,
+        table_name: Option<&str>,
+    ) -> Result<(Vec<Table>, Vec<ParserError>)> {
+        let mut errors = Vec::new();
+        let table = self.parse_metadata_files(&[content.to_string()], table_name, &mut errors)?;
+        Ok((vec![table], errors))
+    }
+
+    /// Parse a zip archive containing one or more `_delta_log` or Iceberg `metadata` directories.
+    pub fn parse_archive(&self, zip_bytes: &[u8]) -> Result<(Vec<Table>, Vec<ParserError>)> {
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(zip_bytes))
+            .context("Failed to read zip archive")?;
+
+        // Table directory prefix -> (file name -> content), ordered by file name
+        let mut groups: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+        let mut total_size = 0u64;
+        for idx in 0..archive.len() {
+            let mut file = archive.by_index(idx).context("Failed to read zip entry")?;
+            let name = file.name().to_string();
+            let Some(prefix) = Self::table_prefix(&name) else {
+                continue;
+            };
+            // Count the bytes actually inflated; the size in the entry header can lie
+            let mut bytes = Vec::new();
+            file.by_ref()
+                .take(MAX_ARCHIVE_SIZE - total_size + 1)
+                .read_to_end(&mut bytes)
+                .with_context(|| format!("Failed to read {}", name))?;
+            total_size += bytes.len() as u64;
+            if total_size > MAX_ARCHIVE_SIZE {
+                return Err(anyhow::anyhow!("Archive exceeds maximum uncompressed size"));
+            }
+            let content =
+                String::from_utf8(bytes).with_context(|| format!("Failed to read {}", name))?;
+            groups.entry(prefix).or_default().insert(name, content);
+        }
+
+        self.parse_groups(groups)
+    }
+
+    /// Parse table metadata from a local directory (a table root, `_delta_log` or `metadata`).
+    pub fn parse_directory(&self, dir: &Path) -> Result<(Vec<Table>, Vec<ParserError>)> {
+        let mut groups: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+        let mut total_size = 0u64;
+
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(current) = pending.pop() {
+            for entry in std::fs::read_dir(&current)
+                .with_context(|| format!("Failed to read directory {}", current.display()))?
+            {
+                let path = entry?.path();
+                if path.is_dir() {
+                    pending.push(path);
+                    continue;
+                }
+                let relative = path
+                    .strip_prefix(dir.parent().unwrap_or(dir))
+                    .unwrap_or(&path)
+                    .to_string_lossy()
+                    .replace('\\', "/");
+                let Some(prefix) = Self::table_prefix(&relative) else {
+                    continue;
+                };
+                total_size += std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                if total_size > MAX_ARCHIVE_SIZE {
+                    return Err(anyhow::anyhow!("Metadata exceeds maximum size"));
+                }
+                let content = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                groups.entry(prefix).or_default().insert(relative, content);
+            }
+        }
+
+        self.parse_groups(groups)
+    }
+
+    /// Return the table directory prefix for a metadata file path, if it is one.
+    fn table_prefix(path: &str) -> Option<String> {
+        if let Some((prefix, file)) = path.rsplit_once("_delta_log/") {
+            return (file.ends_with(".json") && !file.contains('/')).then(|| prefix.to_string());
+        }
+        if let Some((prefix, file)) = path.rsplit_once("metadata/") {
+            return (file.ends_with(".metadata.json") && !file.contains('/'))
+                .then(|| prefix.to_string());
+        }
+        None
+    }
+
+    fn parse_groups(
+        &self,
+        groups: BTreeMap<String, BTreeMap<String, String>>,
+    ) -> Result<(Vec<Table>, Vec<ParserError>)> {
+        if groups.is_empty() {
+            return Err(anyhow::anyhow!(
+                "No _delta_log or Iceberg metadata files found"
+            ));
+        }
+
+        let mut tables = Vec::new();
+        let mut errors = Vec::new();
+        for (prefix, files) in groups {
+            let dir_name = prefix
+                .trim_end_matches('/')
+                .rsplit('/')
+                .next()
+                .filter(|s| !s.is_empty())
+                .map(|s| s.to_string());
+            let contents: Vec<String> = files.into_values().collect();
+            match self.parse_metadata_files(&contents, dir_name.as_deref(), &mut errors) {
+                Ok(table) => tables.push(table),
+                Err(e) => errors.push(ParserError {
+                    error_type: "parse_error".to_string(),
+                    field: Some(prefix),
+                    message: format!("Failed to parse table metadata: {}", e),
+                }),
+            }
+        }
+        Ok((tables, errors))
+    }
+
+    /// Parse ordered metadata files belonging to one table.
+    fn parse_metadata_files(
+        &self,
+        contents: &[String],
+        table_name: Option<&str>,
+        errors: &mut Vec<ParserError>,
+    ) -> Result<Table> {
+        // Iceberg metadata: the most recently updated file is current
+        let iceberg = contents
+            .iter()
+            .filter_map(|c| serde_json::from_str::<Value>(c).ok())
+            .filter(|m| m.get("format-version").is_some() || m.get("table-uuid").is_some())
+            .max_by_key(|m| {
+                m.get("last-updated-ms")
+                    .and_then(|v| v.as_i64())
+                    .unwrap_or(0)
+            });
+        if let Some(metadata) = iceberg {
+            return self.parse_iceberg(&metadata, table_name, errors);
+        }
+
+        // Delta log: the latest metaData action across commits wins
+        let mut latest_metadata = None;
+        for content in contents {
+            for line in content.lines().filter(|l| !l.trim().is_empty()) {
+                let action: Value =
+                    serde_json::from_str(line).context("Delta log line is not valid JSON")?;
+                if let Some(metadata) = action.get("metaData") {
+                    latest_metadata = Some(metadata.clone());
+                }
+            }
+        }
+        let metadata = latest_metadata
+            .ok_or_else(|| anyhow::anyhow!("No metaData action found in Delta log"))?;
+        self.parse_delta(&metadata, table_name, errors)
+    }
+
+    /// Build a table from a Delta `metaData` action.
+    fn parse_delta(
+        &self,
+        metadata: &Value,
+        table_name: Option<&str>,
+        errors: &mut Vec<ParserError>,
+    ) -> Result<Table> {
+        let schema: Value = serde_json::from_str(
+            metadata
+                .get("schemaString")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow::anyhow!("metaData has no schemaString"))?,
+        )
+        .context("Invalid Delta schemaString")?;
+
+        let name = metadata
+            .get("name")
+            .and_then(|v| v.as_str())
+            .or(table_name)
+            .unwrap_or("delta_table")
+            .to_string();
+
+        let partition_columns: Vec<String> = metadata
+            .get("partitionColumns")
+            .and_then(|v| v.as_array())
+            .map(|cols| {
+                cols.iter()
+                    .filter_map(|c| c.as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut columns = Vec::new();
+        for field in schema
+            .get("fields")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default()
+        {
+            let Some(field_name) = field.get("name").and_then(|v| v.as_str()) else {
+                errors.push(ParserError {
+                    error_type: "parse_error".to_string(),
+                    field: Some(name.clone()),
+                    message: "Delta field missing name".to_string(),
+                });
+                continue;
+            };
+            let data_type = field
+                .get("type")
+                .map(Self::delta_type)
+                .unwrap_or_else(|| "STRING".to_string());
+            let mut column = Column::new(field_name.to_string(), data_type);
+            column.nullable = field
+                .get("nullable")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            column.description = field
+                .pointer("/metadata/comment")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+            column.column_order = columns.len() as i32;
+            columns.push(column);
+        }
+
+        let properties: HashMap<String, String> = metadata
+            .get("configuration")
+            .and_then(|v| v.as_object())
+            .map(|obj| {
+                obj.iter()
+                    .map(|(k, v)| {
+                        (
+                            k.clone(),
+                            v.as_str()
+                                .map(|s| s.to_string())
+                                .unwrap_or_else(|| v.to_string()),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut table = Table::new(name, columns);
+        table.database_type = Some(DatabaseType::DatabricksDelta);
+        if let Some(description) = metadata.get("description").and_then(|v| v.as_str()) {
+            table
+                .odcl_metadata
+                .insert("description".to_string(), json!(description));
+        }
+        Self::apply_partitions_and_properties(&mut table, &partition_columns, &properties);
+
+        info!(
+            "Parsed Delta table {} with {} columns",
+            table.name,
+            table.columns.len()
+        );
+        Ok(table)
+    }
+
+    /// Build a table from Iceberg table metadata.
+    fn parse_iceberg(
+        &self,
+        metadata: &Value,
+        table_name: Option<&str>,
+        errors: &mut Vec<ParserError>,
+    ) -> Result<Table> {
+        // Format v2 keeps a list of schemas; v1 has a single `schema`
+        let current_schema_id = metadata.get("current-schema-id").and_then(|v| v.as_i64());
+        let schema = metadata
+            .get("schemas")
+            .and_then(|v| v.as_array())
+            .and_then(|schemas| {
+                schemas
+                    .iter()
+                    .find(|s| s.get("schema-id").and_then(|v| v.as_i64()) == current_schema_id)
+                    .or_else(|| schemas.last())
+            })
+            .or_else(|| metadata.get("schema"))
+            .ok_or_else(|| anyhow::anyhow!("Iceberg metadata has no schema"))?;
+
+        let name = table_name
+            .map(|s| s.to_string())
+            .or_else(|| {
+                metadata
+                    .get("location")
+                    .and_then(|v| v.as_str())
+                    .and_then(|l| l.trim_end_matches('/').rsplit('/').next())
+                    .map(|s| s.to_string())
+            })
+            .unwrap_or_else(|| "iceberg_table".to_string());
+
+        let mut columns = Vec::new();
+        let mut names_by_id: HashMap<i64, String> = HashMap::new();
+        for field in schema
+            .get("fields")
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default()
+        {
+            let Some(field_name) = field.get("name").and_then(|v| v.as_str()) else {
+                errors.push(ParserError {
+                    error_type: "parse_error".to_string(),
+                    field: Some(name.clone()),
+                    message: "Iceberg field missing name".to_string(),
+                });
+                continue;
+            };
+            if let Some(id) = field.get("id").and_then(|v| v.as_i64()) {
+                names_by_id.insert(id, field_name.to_string());
+            }
+            let data_type = field
+                .get("type")
+                .map(Self::iceberg_type)
+                .unwrap_or_else(|| "STRING".to_string());
+            let mut column = Column::new(field_name.to_string(), data_type);
+            column.nullable = !field
+                .get("required")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            column.description = field
+                .get("doc")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string();
+            column.column_order = columns.len() as i32;
+            columns.push(column);
+        }
+
+        // Identifier fields act as the primary key
+        if let Some(ids) = schema
+            .get("identifier-field-ids")
+            .and_then(|v| v.as_array())
+        {
+            for id in ids.iter().filter_map(|v| v.as_i64()) {
+                if let Some(column) = names_by_id
+                    .get(&id)
+                    .and_then(|n| columns.iter_mut().find(|c| &c.name == n))
+                {
+                    column.primary_key = true;
+                    column.nullable = false;
+                }
+            }
+        }
+
+        // Partition columns from the default spec (v2) or `partition-spec` (v1)
+        let default_spec_id = metadata.get("default-spec-id").and_then(|v| v.as_i64());
+        let spec_fields = metadata
+            .get("partition-specs")
+            .and_then(|v| v.as_array())
+            .and_then(|specs| {
+                specs
+                    .iter()
+                    .find(|s| s.get("spec-id").and_then(|v| v.as_i64()) == default_spec_id)
+                    .or_else(|| specs.last())
+            })
+            .and_then(|s| s.get("fields"))
+            .or_else(|| metadata.get("partition-spec"))
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+        let partition_columns: Vec<String> = spec_fields
+            .iter()
+            .filter_map(|f| f.get("source-id").and_then(|v| v.as_i64()))
+            .filter_map(|id| names_by_id.get(&id).cloned())
+            .collect();
+
+        let properties: HashMap<String, String> = metadata
+            .get("properties")
+            .and_then(|v| v.as_object())
+            .map(|obj| {
+                obj.iter()
+                    .map(|(k, v)| {
+                        (
+                            k.clone(),
+                            v.as_str()
+                                .map(|s| s.to_string())
+                                .unwrap_or_else(|| v.to_string()),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut table = Table::new(name, columns);
+        table.database_type = Some(DatabaseType::DatabricksIceberg);
+        if let Some(location) = metadata.get("location").and_then(|v| v.as_str()) {
+            table
+                .odcl_metadata
+                .insert("location".to_string(), json!(location));
+        }
+        if let Some(comment) = properties.get("comment") {
+            table
+                .odcl_metadata
+                .insert("description".to_string(), json!(comment));
+        }
+        Self::apply_partitions_and_properties(&mut table, &partition_columns, &properties);
+
+        info!(
+            "Parsed Iceberg table {} with {} columns",
+            table.name,
+            table.columns.len()
+        );
+        Ok(table)
+    }
+
+    /// Record partition columns and map table properties to quality rules and medallion layers.
+    fn apply_partitions_and_properties(
+        table: &mut Table,
+        partition_columns: &[String],
+        properties: &HashMap<String, String>,
+    ) {
+        if !partition_columns.is_empty() {
+            for column in table
+                .columns
+                .iter_mut()
+                .filter(|c| partition_columns.contains(&c.name))
+            {
+                column.constraints.push("PARTITION KEY".to_string());
+            }
+            table
+                .odcl_metadata
+                .insert("partitionColumns".to_string(), json!(partition_columns));
+        }
+
+        let mut keys: Vec<&String> = properties.keys().collect();
+        keys.sort();
+        for key in keys {
+            let value = &properties[key];
+            let mut rule = HashMap::new();
+            rule.insert("property".to_string(), Value::String(key.clone()));
+            rule.insert("value".to_string(), Value::String(value.clone()));
+
+            if key.to_lowercase() == "quality" {
+                rule.insert(
+                    "type".to_string(),
+                    Value::String("medallion_layer".to_string()),
+                );
+                let layer = match value.to_lowercase().as_str() {
+                    "bronze" => Some(MedallionLayer::Bronze),
+                    "silver" => Some(MedallionLayer::Silver),
+                    "gold" => Some(MedallionLayer::Gold),
+                    "operational" => Some(MedallionLayer::Operational),
+                    _ => None,
+                };
+                if let Some(layer) = layer
+                    && !table.medallion_layers.contains(&layer)
+                {
+                    table.medallion_layers.push(layer);
+                }
+            }
+            table.quality.push(rule);
+        }
+    }
+
+    /// Map a Delta (Spark) type to a SQL type, preserving nested structure.
+    fn delta_type(data_type: &Value) -> String {
+        match data_type {
+            Value::String(name) => match name.as_str() {
+                "string" => "STRING".to_string(),
+                "long" => "BIGINT".to_string(),
+                "integer" => "INT".to_string(),
+                "short" => "SMALLINT".to_string(),
+                "byte" => "TINYINT".to_string(),
+                "float" => "FLOAT".to_string(),
+                "double" => "DOUBLE".to_string(),
+                "boolean" => "BOOLEAN".to_string(),
+                "binary" => "BINARY".to_string(),
+                "date" => "DATE".to_string(),
+                "timestamp" => "TIMESTAMP".to_string(),
+                "timestamp_ntz" => "TIMESTAMP_NTZ".to_string(),
+                other => other.to_uppercase().replace(' ', ""),
+            },
+            Value::Object(obj) => match obj.get("type").and_then(|v| v.as_str()) {
+                Some("struct") => {
+                    let fields: Vec<String> = obj
+                        .get("fields")
+                        .and_then(|v| v.as_array())
+                        .map(|fields| {
+                            fields
+                                .iter()
+                                .map(|f| {
+                                    format!(
+                                        "{}: {}",
+                                        f.get("name").and_then(|v| v.as_str()).unwrap_or_default(),
+                                        f.get("type")
+                                            .map(Self::delta_type)
+                                            .unwrap_or_else(|| "STRING".to_string())
+                                    )
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    format!("STRUCT<{}>", fields.join(", "))
+                }
+                Some("array") => format!(
+                    "ARRAY<{}>",
+                    obj.get("elementType")
+                        .map(Self::delta_type)
+                        .unwrap_or_else(|| "STRING".to_string())
+                ),
+                Some("map") => format!(
+                    "MAP<{}, {}>",
+                    obj.get("keyType")
+                        .map(Self::delta_type)
+                        .unwrap_or_else(|| "STRING".to_string()),
+                    obj.get("valueType")
+                        .map(Self::delta_type)
+                        .unwrap_or_else(|| "STRING".to_string())
+                ),
+                _ => "STRING".to_string(),
+            },
+            _ => "STRING".to_string(),
+        }
+    }
+
+    /// Map an Iceberg type to a SQL type, preserving nested structure.
+    fn iceberg_type(data_type: &Value) -> String {
+        match data_type {
+            Value::String(name) => match name.as_str() {
+                "boolean" => "BOOLEAN".to_string(),
+                "int" => "INT".to_string(),
+                "long" => "BIGINT".to_string(),
+                "float" => "FLOAT".to_string(),
+                "double" => "DOUBLE".to_string(),
+                "date" => "DATE".to_string(),
+                "time" => "TIME".to_string(),
+                "timestamp" | "timestamp_ns" => "TIMESTAMP_NTZ".to_string(),
+                "timestamptz" | "timestamptz_ns" => "TIMESTAMP".to_string(),
+                "string" => "STRING".to_string(),
+                "uuid" => "UUID".to_string(),
+                "binary" => "BINARY".to_string(),
+                other if other.starts_with("fixed") => "BINARY".to_string(),
+                other => other.to_uppercase().replace(' ', ""),
+            },
+            Value::Object(obj) => match obj.get("type").and_then(|v| v.as_str()) {
+                Some("struct") => {
+                    let fields: Vec<String> = obj
+                        .get("fields")
+                        .and_then(|v| v.as_array())
+                        .map(|fields| {
+                            fields
+                                .iter()
+                                .map(|f| {
+                                    format!(
+                                        "{}: {}",
+                                        f.get("name").and_then(|v| v.as_str()).unwrap_or_default(),
+                                        f.get("type")
+                                            .map(Self::iceberg_type)
+                                            .unwrap_or_else(|| "STRING".to_string())
+                                    )
+                                })
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    format!("STRUCT<{}>", fields.join(", "))
+                }
+                Some("list") => format!(
+                    "ARRAY<{}>",
+                    obj.get("element")
+                        .map(Self::iceberg_type)
+                        .unwrap_or_else(|| "STRING".to_string())
+                ),
+                Some("map") => format!(
+                    "MAP<{}, {}>",
+                    obj.get("key")
+                        .map(Self::iceberg_type)
+                        .unwrap_or_else(|| "STRING".to_string()),
+                    obj.get("value")
+                        .map(Self::iceberg_type)
+                        .unwrap_or_else(|| "STRING".to_string())
+                ),
+                _ => "STRING".to_string(),
+            },
+            _ => "STRING".to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_delta_log_metadata() {
+        let schema = json!({
+            "type": "struct",
+            "fields": [
+                {"name": "id", "type": "long", "nullable": false, "metadata": {"comment": "Order id"}},
+                {"name": "event_date", "type": "date", "nullable": true, "metadata": {}},
+                {"name": "address", "type": {"type": "struct", "fields": [
+                    {"name": "city", "type": "string", "nullable": true, "metadata": {}}
+                ]}, "nullable": true, "metadata": {}},
+                {"name": "tags", "type": {"type": "array", "elementType": "string", "containsNull": true}, "nullable": true, "metadata": {}}
+            ]
+        });
+        let log = format!(
+            "{}\n{}\n",
+            json!({"protocol": {"minReaderVersion": 1, "minWriterVersion": 2}}),
+            json!({"metaData": {
+                "id": "abc",
+                "name": "orders",
+                "schemaString": schema.to_string(),
+                "partitionColumns": ["event_date"],
+                "configuration": {"quality": "silver", "delta.appendOnly": "true"}
+            }})
+        );
+
+        let (tables, errors) = LakehouseParser::new().parse(&log, None).unwrap();
+        assert!(errors.is_empty());
+        let table = &tables[0];
+        assert_eq!(table.name, "orders");
+        assert!(!table.columns[0].nullable);
+        assert_eq!(table.columns[0].description, "Order id");
+        assert_eq!(table.columns[2].data_type, "STRUCT<city: STRING>");
+        assert_eq!(table.columns[3].data_type, "ARRAY<STRING>");
+        assert!(
+            table.columns[1]
+                .constraints
+                .contains(&"PARTITION KEY".to_string())
+        );
+        assert_eq!(table.medallion_layers, vec![MedallionLayer::Silver]);
+        assert_eq!(table.quality.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_iceberg_metadata() {
+        let metadata = json!({
+            "format-version": 2,
+            "table-uuid": "9c12d441-03fe-4693-9a96-a0705ddf69c1",
+            "location": "s3://bucket/warehouse/db/customers",
+            "current-schema-id": 0,
+            "schemas": [{
+                "type": "struct",
+                "schema-id": 0,
+                "identifier-field-ids": [1],
+                "fields": [
+                    {"id": 1, "name": "id", "required": true, "type": "long"},
+                    {"id": 2, "name": "country", "required": false, "type": "string", "doc": "ISO code"},
+                    {"id": 3, "name": "attrs", "required": false, "type": {"type": "map", "key-id": 4, "key": "string", "value-id": 5, "value": "string", "value-required": false}}
+                ]
+            }],
+            "default-spec-id": 0,
+            "partition-specs": [{"spec-id": 0, "fields": [{"name": "country", "transform": "identity", "source-id": 2, "field-id": 1000}]}],
+            "properties": {"owner": "data-team"}
+        });
+
+        let (tables, _) = LakehouseParser::new()
+            .parse(&metadata.to_string(), None)
+            .unwrap();
+        let table = &tables[0];
+        assert_eq!(table.name, "customers");
+        assert!(table.columns[0].primary_key);
+        assert_eq!(table.columns[2].data_type, "MAP<STRING, STRING>");
+        assert_eq!(table.odcl_metadata["partitionColumns"], json!(["country"]));
+    }
+}
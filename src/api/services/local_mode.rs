@@ -0,0 +1,244 @@
+//! Single-user local mode.
+//!
+//! With `LOCAL_MODE=true` the server runs for one modeler on their own machine: GitHub OAuth
+//! and JWT sessions are bypassed, every request acts as a fixed local user, models are stored
+//! as files in a chosen directory and the server only listens on the loopback interface.
+//! Requests must also be addressed to `127.0.0.1:<port>`, `localhost:<port>` or
+//! `[::1]:<port>`, so a web page on another host can't reach the server through DNS
+//! rebinding. Without `JWT_SECRET`, tokens such as share links are signed with a random
+//! secret kept in `.jwt-secret` in the data directory.
+//!
+//! Configuration (environment variables):
+//! - `LOCAL_MODE` (default false)
+//! - `LOCAL_USER_EMAIL` (default `local@localhost.localdomain`): identity recorded as author of changes
+//! - `LOCAL_USER_NAME` (default `local`): display name returned by `/auth/me`
+//! - `LOCAL_DATA_DIR`: workspace directory; defaults to `WORKSPACE_DATA`, then `./workspace-data`
+//!
+//! The `desktop` subcommand enables local mode in embedded form, which additionally exposes
+//! the `/desktop/files` endpoints for opening and saving model archives on the local disk.
+
+use axum::{
+    extract::Request,
+    http::{StatusCode, header::HOST},
+    middleware::Next,
+    response::Response,
+};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::storage::traits::UserContext;
+
+/// Email of the local user when `LOCAL_USER_EMAIL` is not set
+pub const DEFAULT_LOCAL_EMAIL: &str = "local@localhost.localdomain";
+
+/// File in the data directory holding the generated JWT secret
+const JWT_SECRET_FILE: &str = ".jwt-secret";
+
+/// Local mode settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalMode {
+    pub email: String,
+    pub name: String,
+    pub data_dir: PathBuf,
+    /// Running as a desktop app (`desktop` subcommand)
+    pub embedded: bool,
+}
+
+impl LocalMode {
+    /// Read local mode settings; returns None unless `LOCAL_MODE` is enabled.
+    pub fn from_env() -> Option<Self> {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Read local mode settings using the given variable lookup.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        let enabled = lookup("LOCAL_MODE")
+            .is_some_and(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes" | "on"));
+        if !enabled {
+            return None;
+        }
+        let non_empty = |key: &str| {
+            lookup(key)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        Some(Self {
+            email: non_empty("LOCAL_USER_EMAIL")
+                .map(|e| e.to_lowercase())
+                .unwrap_or_else(|| DEFAULT_LOCAL_EMAIL.to_string()),
+            name: non_empty("LOCAL_USER_NAME").unwrap_or_else(|| "local".to_string()),
+            data_dir: non_empty("LOCAL_DATA_DIR")
+                .or_else(|| non_empty("WORKSPACE_DATA"))
+                .map(PathBuf::from)
+                .unwrap_or_else(|| PathBuf::from("workspace-data")),
+            embedded: false,
+        })
+    }
+
+    /// User context of the local user, with the same deterministic id as file-based sessions.
+    pub fn user_context(&self) -> UserContext {
+        UserContext {
+            user_id: Uuid::new_v5(&Uuid::NAMESPACE_DNS, self.email.as_bytes()),
+            email: self.email.clone(),
+        }
+    }
+
+    /// Secret for signing tokens when `JWT_SECRET` is not set.
+    ///
+    /// Generated on first use and stored in the data directory, so links signed by one run
+    /// stay valid in the next.
+    pub fn jwt_secret(&self) -> std::io::Result<String> {
+        let path = self.data_dir.join(JWT_SECRET_FILE);
+        if let Ok(existing) = std::fs::read_to_string(&path) {
+            let existing = existing.trim();
+            if existing.len() >= 32 {
+                return Ok(existing.to_string());
+            }
+        }
+        std::fs::create_dir_all(&self.data_dir)?;
+        let secret = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        std::io::Write::write_all(&mut options.open(&path)?, secret.as_bytes())?;
+        Ok(secret)
+    }
+}
+
+static LOCAL_MODE: OnceLock<Option<LocalMode>> = OnceLock::new();
+
+/// Local mode settings of this process, read once from the environment.
+pub fn local_mode() -> Option<&'static LocalMode> {
+    LOCAL_MODE.get_or_init(LocalMode::from_env).as_ref()
+}
+
+/// Enable local mode with the given settings instead of the environment.
+///
+/// Must be called before the first `local_mode()` lookup; returns false otherwise.
+pub fn enable_local_mode(mode: LocalMode) -> bool {
+    LOCAL_MODE.set(Some(mode)).is_ok()
+}
+
+/// Whether the server runs as a desktop app.
+pub fn embedded_mode() -> bool {
+    local_mode().is_some_and(|local| local.embedded)
+}
+
+static LOCAL_PORT: OnceLock<u16> = OnceLock::new();
+
+/// Record the loopback port the local server is bound to.
+pub fn set_local_port(port: u16) {
+    let _ = LOCAL_PORT.set(port);
+}
+
+/// Whether a `Host` header addresses the local server: `127.0.0.1`, `localhost` or `[::1]`
+/// on the port it is bound to.
+pub fn is_local_host(host: &str) -> bool {
+    LOCAL_PORT
+        .get()
+        .is_some_and(|port| is_loopback_host(host, *port))
+}
+
+/// Reject requests whose `Host` header does not address the local server.
+///
+/// Only layered in local mode, where every request acts as the local user.
+pub async fn local_host_middleware(request: Request, next: Next) -> Result<Response, StatusCode> {
+    let host = request
+        .headers()
+        .get(HOST)
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or_default();
+    if !is_local_host(host) {
+        warn!("[LocalMode] Rejected request for host '{}'", host);
+        return Err(StatusCode::FORBIDDEN);
+    }
+    Ok(next.run(request).await)
+}
+
+fn is_loopback_host(host: &str, port: u16) -> bool {
+    let Some((name, host_port)) = host.rsplit_once(':') else {
+        return false;
+    };
+    host_port.parse() == Ok(port)
+        && (name == "127.0.0.1" || name == "[::1]" || name.eq_ignore_ascii_case("localhost"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn config(vars: &[(&str, &str)]) -> Option<LocalMode> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        LocalMode::from_lookup(|key| vars.get(key).cloned())
+    }
+
+    #[test]
+    fn test_local_mode_settings() {
+        assert_eq!(config(&[("LOCAL_USER_EMAIL", "me@example.com")]), None);
+        assert_eq!(config(&[("LOCAL_MODE", "false")]), None);
+
+        let defaults = config(&[("LOCAL_MODE", "true"), ("WORKSPACE_DATA", "/srv/data")]).unwrap();
+        assert_eq!(defaults.email, DEFAULT_LOCAL_EMAIL);
+        assert_eq!(defaults.data_dir, PathBuf::from("/srv/data"));
+
+        let custom = config(&[
+            ("LOCAL_MODE", "1"),
+            ("LOCAL_USER_EMAIL", " Ada@Example.com "),
+            ("LOCAL_DATA_DIR", "/home/ada/models"),
+            ("WORKSPACE_DATA", "/srv/data"),
+        ])
+        .unwrap();
+        assert_eq!(custom.email, "ada@example.com");
+        assert_eq!(custom.data_dir, PathBuf::from("/home/ada/models"));
+        assert_eq!(
+            custom.user_context().user_id,
+            Uuid::new_v5(&Uuid::NAMESPACE_DNS, b"ada@example.com")
+        );
+    }
+
+    #[test]
+    fn test_jwt_secret_is_persisted() {
+        let dir = tempfile::tempdir().unwrap();
+        let local = config(&[
+            ("LOCAL_MODE", "true"),
+            (
+                "LOCAL_DATA_DIR",
+                dir.path().join("models").to_str().unwrap(),
+            ),
+        ])
+        .unwrap();
+
+        let secret = local.jwt_secret().unwrap();
+        assert_eq!(secret.len(), 64);
+        assert_eq!(local.jwt_secret().unwrap(), secret);
+
+        let other = tempfile::tempdir().unwrap();
+        let elsewhere = LocalMode {
+            data_dir: other.path().to_path_buf(),
+            ..local
+        };
+        assert_ne!(elsewhere.jwt_secret().unwrap(), secret);
+    }
+
+    #[test]
+    fn test_loopback_host() {
+        assert!(is_loopback_host("127.0.0.1:8080", 8080));
+        assert!(is_loopback_host("localhost:8080", 8080));
+        assert!(is_loopback_host("LOCALHOST:8080", 8080));
+        assert!(is_loopback_host("[::1]:8080", 8080));
+        assert!(!is_loopback_host("127.0.0.1:8081", 8080));
+        assert!(!is_loopback_host("localhost", 8080));
+        assert!(!is_loopback_host("evil.example.com:8080", 8080));
+        assert!(!is_loopback_host("localhost.evil.example.com:8080", 8080));
+    }
+}
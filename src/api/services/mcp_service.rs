@@ -0,0 +1,504 @@
+//! Model Context Protocol (MCP) server.
+//!
+//! Exposes a domain model to LLM agents and IDE assistants as MCP tools over JSON-RPC 2.0:
+//! `search_tables`, `get_table_schema`, `get_lineage` and `propose_table`. The server works on
+//! a `ModelService`, so tables are read from and written to the domain directory exactly as
+//! the API does. Write access is a separate scope: with the read scope `propose_table` only
+//! validates drafts, with the write scope it can also add them to the model.
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::collections::{HashSet, VecDeque};
+use std::path::Path;
+
+use crate::models::{Column, DataModel, Table};
+use crate::services::ModelService;
+use crate::services::model_validation_service::{Severity, validate_model};
+use crate::services::table_readme_service::TableReadmes;
+
+/// MCP protocol revision implemented by this server
+pub const PROTOCOL_VERSION: &str = "2024-11-05";
+
+/// Maximum lineage traversal depth
+const MAX_LINEAGE_DEPTH: usize = 10;
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+/// Access granted to the connected client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum McpScope {
+    /// Query tools only; proposals are validated but never applied
+    Read,
+    /// Query tools plus applying proposed tables to the model
+    Write,
+}
+
+/// MCP server over a loaded domain model.
+pub struct McpServer {
+    model_service: ModelService,
+    scope: McpScope,
+}
+
+impl McpServer {
+    pub fn new(model_service: ModelService, scope: McpScope) -> Self {
+        Self {
+            model_service,
+            scope,
+        }
+    }
+
+    /// Handle one raw JSON-RPC message; returns the serialized response, if any.
+    pub fn handle_line(&mut self, line: &str) -> Option<String> {
+        let response = match serde_json::from_str::<Value>(line) {
+            Ok(message) => self.handle(&message)?,
+            Err(e) => error_response(Value::Null, PARSE_ERROR, format!("Parse error: {}", e)),
+        };
+        Some(response.to_string())
+    }
+
+    /// Handle a JSON-RPC request. Notifications (no `id`) get no response.
+    pub fn handle(&mut self, message: &Value) -> Option<Value> {
+        let id = message.get("id").cloned()?;
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": PROTOCOL_VERSION,
+                "capabilities": { "tools": {} },
+                "serverInfo": {
+                    "name": "data-modelling-api",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": self.tools() })),
+            "tools/call" => self.call_tool(&params),
+            other => Err((METHOD_NOT_FOUND, format!("Method not found: {}", other))),
+        };
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, message),
+        })
+    }
+
+    fn tools(&self) -> Vec<Value> {
+        let apply_note = match self.scope {
+            McpScope::Read => " This server is read-only: proposals are validated but not applied.",
+            McpScope::Write => " Set `apply` to add the table to the model.",
+        };
+        vec![
+            json!({
+                "name": "search_tables",
+                "description": "Search tables by name, tag, column name, column description or README.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "query": { "type": "string" },
+                        "limit": { "type": "integer", "minimum": 1, "default": 20 },
+                    },
+                    "required": ["query"],
+                },
+            }),
+            json!({
+                "name": "get_table_schema",
+                "description": "Get a table's full definition including columns, keys and metadata.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": { "table": { "type": "string", "description": "Table name or id" } },
+                    "required": ["table"],
+                },
+            }),
+            json!({
+                "name": "get_lineage",
+                "description": "List tables upstream and/or downstream of a table by following relationships.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "table": { "type": "string", "description": "Table name or id" },
+                        "direction": { "type": "string", "enum": ["upstream", "downstream", "both"], "default": "both" },
+                        "depth": { "type": "integer", "minimum": 1, "maximum": MAX_LINEAGE_DEPTH, "default": 3 },
+                    },
+                    "required": ["table"],
+                },
+            }),
+            json!({
+                "name": "propose_table",
+                "description": format!(
+                    "Draft a new table and validate it against the model.{}",
+                    apply_note
+                ),
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "name": { "type": "string" },
+                        "catalog_name": { "type": "string" },
+                        "schema_name": { "type": "string" },
+                        "tags": { "type": "array", "items": { "type": "string" } },
+                        "columns": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "name": { "type": "string" },
+                                    "data_type": { "type": "string" },
+                                    "nullable": { "type": "boolean", "default": true },
+                                    "primary_key": { "type": "boolean", "default": false },
+                                    "description": { "type": "string" },
+                                },
+                                "required": ["name", "data_type"],
+                            },
+                        },
+                        "apply": { "type": "boolean", "default": false },
+                    },
+                    "required": ["name", "columns"],
+                },
+            }),
+        ]
+    }
+
+    fn call_tool(&mut self, params: &Value) -> Result<Value, (i64, String)> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or((INVALID_PARAMS, "Missing tool name".to_string()))?;
+        let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+
+        let output = match name {
+            "search_tables" => parse(arguments).map(|args| self.search_tables(args)),
+            "get_table_schema" => parse(arguments).and_then(|args| self.get_table_schema(args)),
+            "get_lineage" => parse(arguments).and_then(|args| self.get_lineage(args)),
+            "propose_table" => parse(arguments).and_then(|args| self.propose_table(args)),
+            other => return Err((INVALID_PARAMS, format!("Unknown tool: {}", other))),
+        };
+        // Tool failures are reported in the result so the model can see and correct them
+        Ok(match output {
+            Ok(value) => json!({
+                "content": [{
+                    "type": "text",
+                    "text": serde_json::to_string_pretty(&value).unwrap_or_default(),
+                }],
+                "isError": false,
+            }),
+            Err(message) => json!({
+                "content": [{ "type": "text", "text": message }],
+                "isError": true,
+            }),
+        })
+    }
+
+    fn model(&self) -> Result<&DataModel, String> {
+        self.model_service
+            .get_current_model()
+            .ok_or_else(|| "No model loaded".to_string())
+    }
+
+    fn search_tables(&self, args: SearchArgs) -> Value {
+        let Ok(model) = self.model() else {
+            return json!([]);
+        };
+        let needle = args.query.to_lowercase();
+        let matches = |s: &str| s.to_lowercase().contains(&needle);
+        let readmes = TableReadmes::load(Path::new(&model.git_directory_path));
+
+        let hits: Vec<Value> = model
+            .tables
+            .iter()
+            .filter_map(|table| {
+                let columns: Vec<&str> = table
+                    .columns
+                    .iter()
+                    .filter(|c| matches(&c.name) || matches(&c.description))
+                    .map(|c| c.name.as_str())
+                    .collect();
+                let matched = matches(&table.name)
+                    || table.aliases.iter().any(|a| matches(a))
+                    || table.tags.iter().any(|t| matches(t))
+                    || !columns.is_empty()
+                    || readmes.matches(&table.name, &needle);
+                matched.then(|| {
+                    json!({
+                        "id": table.id,
+                        "name": table.name,
+                        "schema_name": table.schema_name,
+                        "aliases": table.aliases,
+                        "tags": table.tags,
+                        "matching_columns": columns,
+                    })
+                })
+            })
+            .take(args.limit.max(1))
+            .collect();
+        json!(hits)
+    }
+
+    fn get_table_schema(&self, args: TableArgs) -> Result<Value, String> {
+        let table = find_table(self.model()?, &args.table)?;
+        serde_json::to_value(table).map_err(|e| e.to_string())
+    }
+
+    fn get_lineage(&self, args: LineageArgs) -> Result<Value, String> {
+        let model = self.model()?;
+        let start = find_table(model, &args.table)?;
+        let depth = args.depth.clamp(1, MAX_LINEAGE_DEPTH);
+
+        let mut nodes = Vec::new();
+        let mut visited = HashSet::from([start.id]);
+        let mut queue = VecDeque::from([(start.id, 0)]);
+        while let Some((table_id, level)) = queue.pop_front() {
+            if level == depth {
+                continue;
+            }
+            for r in &model.relationships {
+                let step = if r.source_table_id == table_id && args.direction != "upstream" {
+                    Some((r.target_table_id, "downstream"))
+                } else if r.target_table_id == table_id && args.direction != "downstream" {
+                    Some((r.source_table_id, "upstream"))
+                } else {
+                    None
+                };
+                if let Some((next, direction)) = step
+                    && visited.insert(next)
+                    && let Some(table) = model.get_table_by_id(next)
+                {
+                    nodes.push(json!({
+                        "id": table.id,
+                        "name": table.name,
+                        "direction": direction,
+                        "depth": level + 1,
+                        "relationship_id": r.id,
+                    }));
+                    queue.push_back((next, level + 1));
+                }
+            }
+        }
+        Ok(json!({ "table": start.name, "lineage": nodes }))
+    }
+
+    fn propose_table(&mut self, args: ProposeTableArgs) -> Result<Value, String> {
+        if args.apply && self.scope != McpScope::Write {
+            return Err("Applying proposals requires the write scope".to_string());
+        }
+        let mut table = Table::new(
+            args.name,
+            args.columns
+                .into_iter()
+                .enumerate()
+                .map(|(i, c)| {
+                    let mut column = Column::new(c.name, c.data_type);
+                    column.nullable = c.nullable && !c.primary_key;
+                    column.primary_key = c.primary_key;
+                    column.description = c.description;
+                    column.column_order = i as i32;
+                    column
+                })
+                .collect(),
+        );
+        table.catalog_name = args.catalog_name;
+        table.schema_name = args.schema_name;
+        table.tags = args.tags;
+
+        // Validate the draft in the context of the existing model
+        let mut draft = self.model()?.clone();
+        draft.tables.push(table.clone());
+        let issues: Vec<_> = validate_model(&draft)
+            .into_iter()
+            .filter(|i| i.table.as_deref() == Some(table.name.as_str()) || i.table.is_none())
+            .collect();
+        let valid = !issues.iter().any(|i| i.severity == Severity::Error);
+
+        let applied = if args.apply && valid {
+            table = self
+                .model_service
+                .add_table(table)
+                .map_err(|e| e.to_string())?;
+            true
+        } else {
+            false
+        };
+        Ok(json!({
+            "table": table,
+            "valid": valid,
+            "issues": issues,
+            "applied": applied,
+        }))
+    }
+}
+
+fn error_response(id: Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn parse<T: serde::de::DeserializeOwned>(arguments: Value) -> Result<T, String> {
+    serde_json::from_value(arguments).map_err(|e| format!("Invalid arguments: {}", e))
+}
+
+/// Find a table by id or (case-insensitive) name.
+fn find_table<'a>(model: &'a DataModel, table: &str) -> Result<&'a Table, String> {
+    model
+        .tables
+        .iter()
+        .find(|t| t.id.to_string() == table || t.name.eq_ignore_ascii_case(table))
+        .or_else(|| model.tables.iter().find(|t| t.has_alias(table)))
+        .ok_or_else(|| format!("Table not found: {}", table))
+}
+
+#[derive(Deserialize)]
+struct SearchArgs {
+    query: String,
+    #[serde(default = "default_limit")]
+    limit: usize,
+}
+
+fn default_limit() -> usize {
+    20
+}
+
+#[derive(Deserialize)]
+struct TableArgs {
+    table: String,
+}
+
+#[derive(Deserialize)]
+struct LineageArgs {
+    table: String,
+    #[serde(default = "default_direction")]
+    direction: String,
+    #[serde(default = "default_depth")]
+    depth: usize,
+}
+
+fn default_direction() -> String {
+    "both".to_string()
+}
+
+fn default_depth() -> usize {
+    3
+}
+
+#[derive(Deserialize)]
+struct ProposeTableArgs {
+    name: String,
+    columns: Vec<ProposedColumn>,
+    #[serde(default)]
+    catalog_name: Option<String>,
+    #[serde(default)]
+    schema_name: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    apply: bool,
+}
+
+#[derive(Deserialize)]
+struct ProposedColumn {
+    name: String,
+    data_type: String,
+    #[serde(default = "default_nullable")]
+    nullable: bool,
+    #[serde(default)]
+    primary_key: bool,
+    #[serde(default)]
+    description: String,
+}
+
+fn default_nullable() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Relationship;
+
+    fn server(scope: McpScope) -> (McpServer, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let mut model_service = ModelService::new();
+        model_service
+            .load_or_create_model("sales".to_string(), dir.path().to_path_buf(), None)
+            .unwrap();
+        let orders = model_service
+            .add_table(Table::new(
+                "orders".to_string(),
+                vec![Column::new("customer_id".to_string(), "INT".to_string())],
+            ))
+            .unwrap();
+        let customers = model_service
+            .add_table(Table::new("customers".to_string(), vec![]))
+            .unwrap();
+        model_service
+            .get_current_model_mut()
+            .unwrap()
+            .relationships
+            .push(Relationship::new(customers.id, orders.id));
+        (McpServer::new(model_service, scope), dir)
+    }
+
+    fn call(server: &mut McpServer, tool: &str, arguments: Value) -> Value {
+        let response = server
+            .handle(&json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "tools/call",
+                "params": { "name": tool, "arguments": arguments },
+            }))
+            .unwrap();
+        let result = &response["result"];
+        let text = result["content"][0]["text"].as_str().unwrap();
+        if result["isError"] == true {
+            return json!({ "error": text });
+        }
+        serde_json::from_str(text).unwrap()
+    }
+
+    #[test]
+    fn test_tools_and_scopes() {
+        let (mut server, _dir) = server(McpScope::Read);
+        let tools = server
+            .handle(&json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/list" }))
+            .unwrap();
+        assert_eq!(tools["result"]["tools"].as_array().unwrap().len(), 4);
+        assert!(
+            server
+                .handle(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }))
+                .is_none()
+        );
+
+        let hits = call(&mut server, "search_tables", json!({ "query": "customer" }));
+        assert_eq!(hits.as_array().unwrap().len(), 2);
+
+        let lineage = call(
+            &mut server,
+            "get_lineage",
+            json!({ "table": "orders", "direction": "upstream" }),
+        );
+        assert_eq!(lineage["lineage"][0]["name"], "customers");
+
+        let proposal = json!({
+            "name": "payments",
+            "columns": [{ "name": "id", "data_type": "BIGINT", "primary_key": true }],
+            "apply": true,
+        });
+        let denied = call(&mut server, "propose_table", proposal.clone());
+        assert!(denied["error"].as_str().unwrap().contains("write scope"));
+
+        server.scope = McpScope::Write;
+        let applied = call(&mut server, "propose_table", proposal);
+        assert_eq!(applied["applied"], true);
+        assert_eq!(
+            call(
+                &mut server,
+                "get_table_schema",
+                json!({ "table": "payments" })
+            )["name"],
+            "payments"
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Medallion layer inference for imports.
+//!
+//! Imported tables that don't declare a medallion layer (e.g. through a `quality`
+//! `TBLPROPERTIES` entry) get one inferred from their naming: a table name prefix such as
+//! `bronze_`, or a schema named after the layer. The mapping lives in `medallion-layers.yaml`
+//! at the root of the workspace data directory and defaults to the `bronze`, `silver` and
+//! `gold` conventions, so a big import doesn't need a manual classification pass.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use utoipa::ToSchema;
+
+use crate::models::Table;
+use crate::models::enums::MedallionLayer;
+
+/// Mapping file at the root of the workspace data directory
+const MEDALLION_LAYERS_FILE: &str = "medallion-layers.yaml";
+
+/// Naming conventions of one medallion layer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MedallionLayerRule {
+    /// `bronze`, `silver`, `gold` or `operational`
+    #[schema(value_type = String)]
+    pub layer: MedallionLayer,
+    /// Table name prefixes, e.g. `bronze_` (case-insensitive)
+    #[serde(default)]
+    pub table_prefixes: Vec<String>,
+    /// Schema (or catalog) names, e.g. `raw` (case-insensitive)
+    #[serde(default)]
+    pub schema_names: Vec<String>,
+}
+
+impl MedallionLayerRule {
+    fn new(layer: MedallionLayer, table_prefixes: &[&str], schema_names: &[&str]) -> Self {
+        Self {
+            layer,
+            table_prefixes: table_prefixes.iter().map(|p| p.to_string()).collect(),
+            schema_names: schema_names.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn matches(&self, table: &Table) -> bool {
+        let name = table.name.to_lowercase();
+        self.table_prefixes
+            .iter()
+            .any(|prefix| !prefix.is_empty() && name.starts_with(&prefix.to_lowercase()))
+            || [&table.schema_name, &table.catalog_name]
+                .into_iter()
+                .flatten()
+                .any(|schema| {
+                    self.schema_names
+                        .iter()
+                        .any(|s| s.eq_ignore_ascii_case(schema))
+                })
+    }
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_rules() -> Vec<MedallionLayerRule> {
+    vec![
+        MedallionLayerRule::new(MedallionLayer::Bronze, &["bronze_"], &["bronze", "raw"]),
+        MedallionLayerRule::new(MedallionLayer::Silver, &["silver_"], &["silver"]),
+        MedallionLayerRule::new(MedallionLayer::Gold, &["gold_"], &["gold"]),
+    ]
+}
+
+/// Medallion layer naming conventions configured for the workspace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MedallionLayerMapping {
+    /// Infer layers of imported tables
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Conventions checked in order; the first matching rule sets the layer
+    #[serde(default = "default_rules")]
+    pub rules: Vec<MedallionLayerRule>,
+}
+
+impl Default for MedallionLayerMapping {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            rules: default_rules(),
+        }
+    }
+}
+
+impl MedallionLayerMapping {
+    pub fn load(workspace_data_dir: &Path) -> Self {
+        std::fs::read_to_string(workspace_data_dir.join(MEDALLION_LAYERS_FILE))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, workspace_data_dir: &Path) -> Result<()> {
+        let path = workspace_data_dir.join(MEDALLION_LAYERS_FILE);
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Check every rule matches something.
+    pub fn check_rules(&self) -> std::result::Result<(), String> {
+        for rule in &self.rules {
+            let mut patterns = rule.table_prefixes.iter().chain(&rule.schema_names);
+            if patterns.clone().next().is_none() {
+                return Err("Every rule needs a table prefix or schema name".to_string());
+            }
+            if patterns.any(|p| p.trim().is_empty()) {
+                return Err("Table prefixes and schema names must not be empty".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the layer of a table without one from the first matching rule, returning it.
+    pub fn infer(&self, table: &mut Table) -> Option<MedallionLayer> {
+        if !self.enabled || !table.medallion_layers.is_empty() {
+            return None;
+        }
+        let layer = self.rules.iter().find(|rule| rule.matches(table))?.layer;
+        table.medallion_layers.push(layer);
+        Some(layer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::SQLParser;
+
+    #[test]
+    fn test_infer_layers_from_prefixes_and_schemas() {
+        let mapping = MedallionLayerMapping::default();
+        let table = |name: &str, schema: Option<&str>| {
+            let mut table = Table::new(name.to_string(), vec![]);
+            table.schema_name = schema.map(str::to_string);
+            table
+        };
+
+        let mut raw_orders = table("Bronze_Orders", None);
+        assert_eq!(mapping.infer(&mut raw_orders), Some(MedallionLayer::Bronze));
+        let mut customers = table("customers", Some("RAW"));
+        assert_eq!(mapping.infer(&mut customers), Some(MedallionLayer::Bronze));
+        let (mut tables, _) = SQLParser::new()
+            .parse("CREATE TABLE warehouse.gold.revenue (id INT PRIMARY KEY);")
+            .unwrap();
+        assert_eq!(mapping.infer(&mut tables[0]), Some(MedallionLayer::Gold));
+        assert_eq!(tables[0].medallion_layers, vec![MedallionLayer::Gold]);
+
+        // Declared layers win, and names that match no rule are left alone
+        let mut declared = table("silver_orders", None);
+        declared.medallion_layers = vec![MedallionLayer::Gold];
+        assert_eq!(mapping.infer(&mut declared), None);
+        assert_eq!(declared.medallion_layers, vec![MedallionLayer::Gold]);
+        let mut plain = table("orders", Some("sales"));
+        assert_eq!(mapping.infer(&mut plain), None);
+        assert!(plain.medallion_layers.is_empty());
+
+        let custom: MedallionLayerMapping =
+            serde_yaml::from_str("rules:\n  - layer: operational\n    table_prefixes: [ops_]\n")
+                .unwrap();
+        assert!(custom.enabled);
+        assert!(custom.check_rules().is_ok());
+        let mut ops = table("ops_tickets", Some("gold"));
+        assert_eq!(custom.infer(&mut ops), Some(MedallionLayer::Operational));
+
+        let empty: MedallionLayerMapping =
+            serde_yaml::from_str("rules:\n  - layer: gold\n").unwrap();
+        assert!(empty.check_rules().is_err());
+    }
+}
@@ -0,0 +1,187 @@
+//! Metadata propagation along column-level lineage.
+//!
+//! Descriptions, classifications and glossary links flow from upstream columns to the
+//! downstream columns they feed, as mapped by the column pairs of lineage relationships
+//! (data-flow and ETL relationships, source upstream). Only blank downstream fields are
+//! filled, so documentation written for a silver or gold column is never overwritten.
+//! Propagation repeats until nothing changes, so a gold column inherits from bronze through
+//! silver in one run. When several upstream columns feed a blank field, the first lineage
+//! relationship wins.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::{Column, Relationship, Table};
+use crate::services::column_reference_service::is_lineage;
+
+/// Column metadata that can be propagated
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PropagatedField {
+    Description,
+    Classification,
+    GlossaryLinks,
+}
+
+impl PropagatedField {
+    pub const ALL: [Self; 3] = [Self::Description, Self::Classification, Self::GlossaryLinks];
+
+    /// The column's value, or None if the field is blank.
+    fn get(self, column: &Column) -> Option<Value> {
+        match self {
+            Self::Description => Some(column.description.trim())
+                .filter(|d| !d.is_empty())
+                .map(|d| json!(d)),
+            Self::Classification => column
+                .classification
+                .as_deref()
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .map(|c| json!(c)),
+            Self::GlossaryLinks => Some(&column.glossary_links)
+                .filter(|l| !l.is_empty())
+                .map(|l| json!(l)),
+        }
+    }
+
+    fn set(self, column: &mut Column, value: &Value) {
+        match self {
+            Self::Description => column.description = value.as_str().unwrap_or_default().into(),
+            Self::Classification => column.classification = value.as_str().map(str::to_string),
+            Self::GlossaryLinks => {
+                column.glossary_links = serde_json::from_value(value.clone()).unwrap_or_default()
+            }
+        }
+    }
+}
+
+/// A value copied to a downstream column
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PropagatedValue {
+    pub table_id: Uuid,
+    pub table_name: String,
+    pub column: String,
+    pub field: PropagatedField,
+    #[schema(value_type = Object)]
+    pub value: Value,
+    /// Upstream column the value came from, as `table.column`
+    pub source: String,
+    /// Lineage relationship that maps the columns
+    pub relationship_id: Uuid,
+}
+
+/// Position of a column: (table index, column index)
+type ColumnPosition = (usize, usize);
+
+fn locate(tables: &[Table], table_id: Uuid, column: &str) -> Option<ColumnPosition> {
+    let table = tables.iter().position(|t| t.id == table_id)?;
+    let column = tables[table]
+        .columns
+        .iter()
+        .position(|c| c.name.eq_ignore_ascii_case(column))?;
+    Some((table, column))
+}
+
+/// Fill blank `fields` of downstream columns from their upstream columns, returning every
+/// value copied (in the order applied).
+pub fn propagate_metadata(
+    tables: &mut [Table],
+    relationships: &[Relationship],
+    fields: &[PropagatedField],
+) -> Vec<PropagatedValue> {
+    let edges: Vec<(Uuid, ColumnPosition, ColumnPosition)> = relationships
+        .iter()
+        .filter(|r| is_lineage(r))
+        .filter_map(|r| Some((r, r.foreign_key_details.as_ref()?)))
+        .flat_map(|(r, details)| {
+            details
+                .pairs()
+                .into_iter()
+                .filter_map(|(source, target)| {
+                    Some((
+                        r.id,
+                        locate(tables, r.source_table_id, source)?,
+                        locate(tables, r.target_table_id, target)?,
+                    ))
+                })
+                .collect::<Vec<_>>()
+        })
+        .filter(|(_, upstream, downstream)| upstream != downstream)
+        .collect();
+
+    let mut propagated = Vec::new();
+    loop {
+        let before = propagated.len();
+        for (relationship_id, (ut, uc), (dt, dc)) in &edges {
+            for field in fields {
+                let Some(value) = field.get(&tables[*ut].columns[*uc]) else {
+                    continue;
+                };
+                if field.get(&tables[*dt].columns[*dc]).is_some() {
+                    continue;
+                }
+                field.set(&mut tables[*dt].columns[*dc], &value);
+                propagated.push(PropagatedValue {
+                    table_id: tables[*dt].id,
+                    table_name: tables[*dt].name.clone(),
+                    column: tables[*dt].columns[*dc].name.clone(),
+                    field: *field,
+                    value,
+                    source: format!("{}.{}", tables[*ut].name, tables[*ut].columns[*uc].name),
+                    relationship_id: *relationship_id,
+                });
+            }
+        }
+        // Every pass fills at least one blank field or stops, so this terminates
+        if propagated.len() == before {
+            return propagated;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::enums::RelationshipType;
+    use crate::models::relationship::ForeignKeyDetails;
+
+    fn lineage(source: &Table, target: &Table, column: &str) -> Relationship {
+        let mut relationship = Relationship::new(source.id, target.id);
+        relationship.relationship_type = Some(RelationshipType::DataFlow);
+        relationship.foreign_key_details = Some(ForeignKeyDetails::new(column, column));
+        relationship
+    }
+
+    #[test]
+    fn test_propagates_through_layers_without_overwriting() {
+        let column = |description: &str| {
+            let mut c = Column::new("email".to_string(), "STRING".to_string());
+            c.description = description.to_string();
+            c
+        };
+        let mut bronze = Table::new("bronze_users".to_string(), vec![column("Login email")]);
+        bronze.columns[0].classification = Some("pii".to_string());
+        let silver = Table::new("silver_users".to_string(), vec![column("")]);
+        let gold = Table::new("gold_users".to_string(), vec![column("Contact address")]);
+        // Listed downstream-first so gold only fills in on a later pass
+        let relationships = vec![
+            lineage(&silver, &gold, "email"),
+            lineage(&bronze, &silver, "EMAIL"),
+        ];
+        let mut tables = vec![bronze, silver, gold];
+
+        let propagated = propagate_metadata(&mut tables, &relationships, &PropagatedField::ALL);
+
+        assert_eq!(tables[1].columns[0].description, "Login email");
+        assert_eq!(tables[2].columns[0].description, "Contact address");
+        assert_eq!(tables[2].columns[0].classification.as_deref(), Some("pii"));
+        assert_eq!(propagated.len(), 3);
+        assert!(
+            propagated
+                .iter()
+                .all(|p| p.field != PropagatedField::GlossaryLinks)
+        );
+    }
+}
@@ -0,0 +1,334 @@
+//! Typed custom metadata fields.
+//!
+//! A workspace declares custom fields for tables and columns in `metadata-schema.yaml` at the
+//! root of the workspace data directory. Table field values live in the table's
+//! `odcl_metadata` and column field values in the column's `custom_properties`, keyed by
+//! field name. Declared fields are type checked when tables are created or updated, exported
+//! as ODCS `customProperties` and included in table search.
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use utoipa::ToSchema;
+
+use crate::models::{Column, Table};
+
+/// Schema file at the root of the workspace data directory
+const METADATA_SCHEMA_FILE: &str = "metadata-schema.yaml";
+
+/// What a custom field describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomFieldTarget {
+    Table,
+    Column,
+}
+
+/// Value type of a custom field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomFieldType {
+    String,
+    Number,
+    Integer,
+    Boolean,
+    /// ISO 8601 date (`YYYY-MM-DD`)
+    Date,
+    /// Array of strings
+    List,
+}
+
+/// A custom field declared by the workspace.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CustomFieldDefinition {
+    pub name: String,
+    pub target: CustomFieldTarget,
+    #[serde(rename = "type")]
+    pub field_type: CustomFieldType,
+    #[serde(default)]
+    pub required: bool,
+    /// Permitted values of `string` and `list` fields (any value if empty)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_values: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl CustomFieldDefinition {
+    /// Describe why a value does not fit this field, if it does not.
+    fn check(&self, value: &Value) -> Option<String> {
+        let type_ok = match self.field_type {
+            CustomFieldType::String => value.is_string(),
+            CustomFieldType::Number => value.is_number(),
+            CustomFieldType::Integer => value.is_i64() || value.is_u64(),
+            CustomFieldType::Boolean => value.is_boolean(),
+            CustomFieldType::Date => value
+                .as_str()
+                .is_some_and(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").is_ok()),
+            CustomFieldType::List => value
+                .as_array()
+                .is_some_and(|items| items.iter().all(Value::is_string)),
+        };
+        if !type_ok {
+            return Some(format!("must be a {:?} value", self.field_type).to_lowercase());
+        }
+        if self.allowed_values.is_empty() {
+            return None;
+        }
+        let values: Vec<&str> = match value {
+            Value::Array(items) => items.iter().filter_map(Value::as_str).collect(),
+            other => other.as_str().into_iter().collect(),
+        };
+        values
+            .iter()
+            .find(|v| !self.allowed_values.iter().any(|allowed| allowed == *v))
+            .map(|v| format!("'{}' is not one of: {}", v, self.allowed_values.join(", ")))
+    }
+}
+
+/// Custom fields declared for the workspace.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct MetadataSchema {
+    #[serde(default)]
+    pub fields: Vec<CustomFieldDefinition>,
+}
+
+/// A custom field value that is missing or does not match its declaration.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct CustomFieldViolation {
+    pub field: String,
+    /// Column the value belongs to (table fields have none)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    pub message: String,
+}
+
+impl MetadataSchema {
+    pub fn load(workspace_data_dir: &Path) -> Self {
+        std::fs::read_to_string(workspace_data_dir.join(METADATA_SCHEMA_FILE))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, workspace_data_dir: &Path) -> Result<()> {
+        let path = workspace_data_dir.join(METADATA_SCHEMA_FILE);
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn fields(&self, target: CustomFieldTarget) -> impl Iterator<Item = &CustomFieldDefinition> {
+        self.fields.iter().filter(move |f| f.target == target)
+    }
+
+    /// Check field names are set and unique per target and that allowed values are only
+    /// given for string and list fields.
+    pub fn check_definitions(&self) -> std::result::Result<(), String> {
+        let mut names = HashSet::new();
+        for field in &self.fields {
+            if field.name.trim().is_empty() {
+                return Err("Custom field name is required".to_string());
+            }
+            if !names.insert((field.target, field.name.as_str())) {
+                return Err(
+                    format!("Duplicate custom {:?} field '{}'", field.target, field.name)
+                        .to_lowercase(),
+                );
+            }
+            if !field.allowed_values.is_empty()
+                && !matches!(
+                    field.field_type,
+                    CustomFieldType::String | CustomFieldType::List
+                )
+            {
+                return Err(format!(
+                    "Custom field '{}': allowed values are only supported for string and list fields",
+                    field.name
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_values(
+        &self,
+        target: CustomFieldTarget,
+        values: &HashMap<String, Value>,
+        column: Option<&str>,
+        violations: &mut Vec<CustomFieldViolation>,
+    ) {
+        for field in self.fields(target) {
+            let message = match values.get(&field.name).filter(|v| !v.is_null()) {
+                Some(value) => field.check(value),
+                None if field.required => Some("is required".to_string()),
+                None => None,
+            };
+            if let Some(message) = message {
+                violations.push(CustomFieldViolation {
+                    field: field.name.clone(),
+                    column: column.map(str::to_string),
+                    message,
+                });
+            }
+        }
+    }
+
+    /// Validate a table's custom field values (`odcl_metadata`).
+    pub fn validate_table_metadata(
+        &self,
+        odcl_metadata: &HashMap<String, Value>,
+    ) -> Vec<CustomFieldViolation> {
+        let mut violations = Vec::new();
+        self.check_values(
+            CustomFieldTarget::Table,
+            odcl_metadata,
+            None,
+            &mut violations,
+        );
+        violations
+    }
+
+    /// Validate the custom field values of columns.
+    pub fn validate_columns(&self, columns: &[Column]) -> Vec<CustomFieldViolation> {
+        let mut violations = Vec::new();
+        for column in columns {
+            self.check_values(
+                CustomFieldTarget::Column,
+                &column.custom_properties,
+                Some(&column.name),
+                &mut violations,
+            );
+        }
+        violations
+    }
+
+    /// Whether any of the table's custom field values contains the (lowercase) search term.
+    pub fn table_matches(&self, table: &Table, needle: &str) -> bool {
+        self.fields(CustomFieldTarget::Table).any(|f| {
+            table
+                .odcl_metadata
+                .get(&f.name)
+                .is_some_and(|v| value_matches(v, needle))
+        })
+    }
+
+    /// Whether any of the column's custom field values contains the (lowercase) search term.
+    pub fn column_matches(&self, column: &Column, needle: &str) -> bool {
+        self.fields(CustomFieldTarget::Column).any(|f| {
+            column
+                .custom_properties
+                .get(&f.name)
+                .is_some_and(|v| value_matches(v, needle))
+        })
+    }
+}
+
+fn value_matches(value: &Value, needle: &str) -> bool {
+    match value {
+        Value::String(s) => s.to_lowercase().contains(needle),
+        Value::Array(items) => items.iter().any(|item| value_matches(item, needle)),
+        Value::Number(n) => n.to_string().contains(needle),
+        Value::Bool(b) => b.to_string() == needle,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> MetadataSchema {
+        serde_yaml::from_str(
+            r#"
+fields:
+  - name: owner
+    target: table
+    type: string
+    required: true
+  - name: tier
+    target: table
+    type: list
+    allowed_values: [gold, silver]
+  - name: reviewed_on
+    target: table
+    type: date
+  - name: retention_days
+    target: column
+    type: integer
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_table_metadata() {
+        let schema = schema();
+        assert!(schema.check_definitions().is_ok());
+
+        let metadata = HashMap::from([
+            ("owner".to_string(), json!("payments")),
+            ("tier".to_string(), json!(["gold"])),
+            ("reviewed_on".to_string(), json!("2026-01-31")),
+            ("unrelated".to_string(), json!(42)),
+        ]);
+        assert!(schema.validate_table_metadata(&metadata).is_empty());
+
+        let metadata = HashMap::from([
+            ("tier".to_string(), json!(["gold", "bronze"])),
+            ("reviewed_on".to_string(), json!("31/01/2026")),
+        ]);
+        let violations = schema.validate_table_metadata(&metadata);
+        let fields: Vec<_> = violations.iter().map(|v| v.field.as_str()).collect();
+        assert_eq!(fields, vec!["owner", "tier", "reviewed_on"]);
+        assert_eq!(violations[0].message, "is required");
+        assert!(violations[1].message.contains("'bronze'"));
+    }
+
+    #[test]
+    fn test_validate_columns_and_search() {
+        let schema = schema();
+        let mut column = Column::new("id".to_string(), "INT".to_string());
+        column
+            .custom_properties
+            .insert("retention_days".to_string(), json!("30"));
+        let violations = schema.validate_columns(std::slice::from_ref(&column));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].column.as_deref(), Some("id"));
+
+        column
+            .custom_properties
+            .insert("retention_days".to_string(), json!(365));
+        assert!(
+            schema
+                .validate_columns(std::slice::from_ref(&column))
+                .is_empty()
+        );
+        assert!(schema.column_matches(&column, "365"));
+        assert!(!schema.column_matches(&column, "30"));
+    }
+
+    #[test]
+    fn test_check_definitions() {
+        let mut schema = schema();
+        schema.fields.push(CustomFieldDefinition {
+            name: "owner".to_string(),
+            target: CustomFieldTarget::Column,
+            field_type: CustomFieldType::String,
+            required: false,
+            allowed_values: Vec::new(),
+            description: None,
+        });
+        assert!(schema.check_definitions().is_ok());
+
+        schema.fields[4].target = CustomFieldTarget::Table;
+        assert!(schema.check_definitions().is_err());
+
+        schema.fields.pop();
+        schema.fields[3].allowed_values = vec!["7".to_string()];
+        assert!(schema.check_definitions().is_err());
+    }
+}
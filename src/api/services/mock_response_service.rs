@@ -0,0 +1,404 @@
+//! Canned API responses generated from the OpenAPI specification.
+//!
+//! In mock mode every documented endpoint answers with an example of its documented success
+//! response instead of running its handler, so frontend teams can build against endpoints
+//! whose handlers are not ready and notice when the documented contract changes. Examples are
+//! built from the response schema (its `example` if it has one, otherwise a value of the
+//! schema's shape); a fixture file named after the operation id replaces the generated body.
+
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::path::Path;
+use tracing::{info, warn};
+
+/// A canned response
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockResponse {
+    pub status: u16,
+    /// Content type of the body, None for an empty response
+    pub content_type: Option<String>,
+    pub body: Option<Value>,
+}
+
+/// One documented operation
+#[derive(Debug, Clone)]
+struct MockOperation {
+    method: String,
+    segments: Vec<String>,
+    operation_id: String,
+    response: MockResponse,
+}
+
+/// Canned responses of every operation of a specification.
+#[derive(Debug, Clone, Default)]
+pub struct MockResponder {
+    operations: Vec<MockOperation>,
+}
+
+impl MockResponder {
+    /// Build responses for every operation of `spec` (an OpenAPI document as JSON).
+    ///
+    /// `fixtures` maps operation ids to response bodies replacing the generated ones.
+    pub fn from_spec(spec: &Value, fixtures: &HashMap<String, Value>) -> Self {
+        let schemas = spec
+            .pointer("/components/schemas")
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        let mut operations = Vec::new();
+        for (path, item) in spec
+            .get("paths")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            for (method, operation) in item.as_object().into_iter().flatten() {
+                if !matches!(
+                    method.as_str(),
+                    "get" | "put" | "post" | "delete" | "patch" | "head" | "options"
+                ) {
+                    continue;
+                }
+                let operation_id = operation
+                    .get("operationId")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string();
+                let mut response = success_response(operation, &schemas);
+                if let Some(fixture) = fixtures.get(&operation_id) {
+                    response.content_type = Some("application/json".to_string());
+                    response.body = Some(fixture.clone());
+                }
+                operations.push(MockOperation {
+                    method: method.to_uppercase(),
+                    segments: path_segments(path),
+                    operation_id,
+                    response,
+                });
+            }
+        }
+        Self { operations }
+    }
+
+    /// Number of operations with a canned response
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Canned response for `method` on `path` (relative to the API root, e.g. `/workspace/domains`).
+    ///
+    /// Paths match templates segment by segment; when several do, the one with the most
+    /// literal segments wins, so `/tables/search` is preferred over `/tables/{table_id}`.
+    pub fn respond(&self, method: &str, path: &str) -> Option<&MockResponse> {
+        self.find(method, path).map(|op| &op.response)
+    }
+
+    /// Operation id of the operation answering `method` on `path`
+    pub fn operation_id(&self, method: &str, path: &str) -> Option<&str> {
+        self.find(method, path).map(|op| op.operation_id.as_str())
+    }
+
+    fn find(&self, method: &str, path: &str) -> Option<&MockOperation> {
+        let segments = path_segments(path);
+        self.operations
+            .iter()
+            .filter(|op| op.method.eq_ignore_ascii_case(method))
+            .filter_map(|op| match_score(&op.segments, &segments).map(|score| (score, op)))
+            .max_by_key(|(score, _)| *score)
+            .map(|(_, op)| op)
+    }
+}
+
+/// Read `<operation_id>.json` fixture files from `dir`.
+pub fn load_fixtures(dir: &Path) -> HashMap<String, Value> {
+    let mut fixtures = HashMap::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        warn!("Cannot read mock fixtures directory {:?}", dir);
+        return fixtures;
+    };
+    for path in entries.flatten().map(|e| e.path()) {
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(operation_id) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        match std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))
+        {
+            Ok(body) => {
+                fixtures.insert(operation_id.to_string(), body);
+            }
+            Err(e) => warn!("Ignoring mock fixture {:?}: {}", path, e),
+        }
+    }
+    info!("Loaded {} mock fixture(s) from {:?}", fixtures.len(), dir);
+    fixtures
+}
+
+fn path_segments(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Number of literal segments if `template` matches `path`
+fn match_score(template: &[String], path: &[String]) -> Option<usize> {
+    if template.len() != path.len() {
+        return None;
+    }
+    let mut literals = 0;
+    for (expected, actual) in template.iter().zip(path) {
+        if expected.starts_with('{') && expected.ends_with('}') {
+            continue;
+        }
+        if expected != actual {
+            return None;
+        }
+        literals += 1;
+    }
+    Some(literals)
+}
+
+/// The operation's lowest documented 2xx response, or 200 without a body
+fn success_response(operation: &Value, schemas: &Map<String, Value>) -> MockResponse {
+    let documented = operation
+        .get("responses")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .filter_map(|(status, response)| Some((status.parse::<u16>().ok()?, response)))
+        .filter(|(status, _)| (200..300).contains(status))
+        .min_by_key(|(status, _)| *status);
+    let Some((status, response)) = documented else {
+        return MockResponse {
+            status: 200,
+            content_type: None,
+            body: None,
+        };
+    };
+    let content = response
+        .get("content")
+        .and_then(Value::as_object)
+        .and_then(|content| {
+            content
+                .get_key_value("application/json")
+                .or_else(|| content.iter().next())
+        });
+    match content {
+        Some((content_type, media)) => {
+            let body = media.get("example").cloned().unwrap_or_else(|| {
+                media
+                    .get("schema")
+                    .map(|schema| example(schema, schemas, &mut Vec::new()))
+                    .unwrap_or_else(|| json!({}))
+            });
+            MockResponse {
+                status,
+                content_type: Some(content_type.clone()),
+                body: Some(body),
+            }
+        }
+        None => MockResponse {
+            status,
+            content_type: None,
+            body: None,
+        },
+    }
+}
+
+/// Example value of `schema`. `seen` holds the schemas being expanded; a schema referring
+/// back to one of them (e.g. a table's parent table) is given null.
+fn example(schema: &Value, schemas: &Map<String, Value>, seen: &mut Vec<String>) -> Value {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference.rsplit('/').next().unwrap_or_default();
+        let Some(target) = schemas.get(name) else {
+            return Value::Null;
+        };
+        if seen.iter().any(|s| s == name) {
+            return Value::Null;
+        }
+        seen.push(name.to_string());
+        let value = example(target, schemas, seen);
+        seen.pop();
+        return value;
+    }
+    if let Some(value) = schema.get("example").or_else(|| schema.get("default")) {
+        return value.clone();
+    }
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .and_then(|values| values.first())
+    {
+        return first.clone();
+    }
+    for key in ["oneOf", "anyOf"] {
+        if let Some(first) = schema
+            .get(key)
+            .and_then(Value::as_array)
+            .and_then(|variants| {
+                variants
+                    .iter()
+                    .find(|v| v.get("type").and_then(Value::as_str) != Some("null"))
+            })
+        {
+            return example(first, schemas, seen);
+        }
+    }
+    if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+        let mut merged = Map::new();
+        for part in parts {
+            match example(part, schemas, seen) {
+                Value::Object(fields) => merged.extend(fields),
+                other if parts.len() == 1 => return other,
+                _ => {}
+            }
+        }
+        return Value::Object(merged);
+    }
+
+    match schema_type(schema) {
+        Some("object") => {
+            let fields = schema
+                .get("properties")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flatten()
+                .map(|(name, property)| (name.clone(), example(property, schemas, seen)))
+                .collect();
+            Value::Object(fields)
+        }
+        Some("array") => match schema.get("items") {
+            Some(items) => match example(items, schemas, seen) {
+                Value::Null => json!([]),
+                item => json!([item]),
+            },
+            None => json!([]),
+        },
+        Some("string") => json!(string_example(schema.get("format").and_then(Value::as_str))),
+        Some("integer") => json!(0),
+        Some("number") => json!(0.0),
+        Some("boolean") => json!(false),
+        Some("null") => Value::Null,
+        _ if schema.get("properties").is_some() => {
+            let mut typed = schema.clone();
+            typed["type"] = json!("object");
+            example(&typed, schemas, seen)
+        }
+        _ => json!({}),
+    }
+}
+
+/// The schema's type; the first non-null one if it lists several (`["string", "null"]`)
+fn schema_type(schema: &Value) -> Option<&str> {
+    match schema.get("type")? {
+        Value::String(t) => Some(t),
+        Value::Array(types) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|t| *t != "null")
+            .or(Some("null")),
+        _ => None,
+    }
+}
+
+fn string_example(format: Option<&str>) -> &'static str {
+    match format {
+        Some("uuid") => "00000000-0000-0000-0000-000000000000",
+        Some("date-time") => "2024-01-01T00:00:00Z",
+        Some("date") => "2024-01-01",
+        Some("email") => "user@example.com",
+        Some("uri") | Some("url") => "https://example.com",
+        Some("binary") | Some("byte") => "",
+        _ => "string",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_responses_follow_the_spec() {
+        let spec = json!({
+            "paths": {
+                "/workspace/domains/{domain}/tables/{table_id}": {
+                    "get": {
+                        "operationId": "get_table",
+                        "responses": {
+                            "200": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/Table"}}}},
+                            "404": {"description": "Not found"}
+                        }
+                    },
+                    "delete": {"operationId": "delete_table", "responses": {"204": {"description": "Deleted"}}}
+                },
+                "/workspace/domains/{domain}/tables/search": {
+                    "get": {
+                        "operationId": "search_tables",
+                        "responses": {"200": {"content": {"application/json": {"schema": {
+                            "type": "array", "items": {"$ref": "#/components/schemas/Table"}
+                        }}}}}
+                    }
+                }
+            },
+            "components": {"schemas": {
+                "Table": {
+                    "type": "object",
+                    "properties": {
+                        "id": {"type": "string", "format": "uuid"},
+                        "name": {"type": "string", "example": "orders"},
+                        "database_type": {"oneOf": [{"type": "null"}, {"type": "string", "enum": ["Postgres", "MySql"]}]},
+                        "columns": {"type": "array", "items": {"type": "object", "properties": {"nullable": {"type": "boolean"}}}},
+                        "parent": {"$ref": "#/components/schemas/Table"}
+                    }
+                }
+            }}
+        });
+        let fixtures = HashMap::from([("search_tables".to_string(), json!([{"name": "fixture"}]))]);
+        let responder = MockResponder::from_spec(&spec, &fixtures);
+        assert_eq!(responder.len(), 3);
+
+        let table = responder
+            .respond("GET", "/workspace/domains/sales/tables/42")
+            .unwrap();
+        assert_eq!(table.status, 200);
+        let body = table.body.as_ref().unwrap();
+        assert_eq!(body["id"], "00000000-0000-0000-0000-000000000000");
+        assert_eq!(body["name"], "orders");
+        assert_eq!(body["database_type"], "Postgres");
+        assert_eq!(body["columns"], json!([{"nullable": false}]));
+        assert_eq!(body["parent"], Value::Null);
+
+        // Literal segments win over templates; fixtures replace generated bodies
+        let search = responder
+            .respond("get", "/workspace/domains/sales/tables/search")
+            .unwrap();
+        assert_eq!(search.body, Some(json!([{"name": "fixture"}])));
+        assert_eq!(
+            responder.operation_id("GET", "/workspace/domains/sales/tables/search"),
+            Some("search_tables")
+        );
+
+        let deleted = responder
+            .respond("DELETE", "/workspace/domains/sales/tables/42")
+            .unwrap();
+        assert_eq!((deleted.status, deleted.body.clone()), (204, None));
+        assert!(
+            responder
+                .respond("POST", "/workspace/domains/sales/tables/42")
+                .is_none()
+        );
+        assert!(
+            responder
+                .respond("GET", "/workspace/domains/sales")
+                .is_none()
+        );
+    }
+}
@@ -1,24 +1,94 @@
 //! Services module - contains business logic services migrated from Python backend.
 
+pub mod access_control_service;
 pub mod ai_service;
+pub mod api_key_service;
+pub mod attachment_service;
+pub mod audit_log_service;
+pub mod avro_idl_parser;
 pub mod avro_parser;
 pub mod cache_service;
+pub mod cancellation;
 pub mod canvas_layout_service;
+pub mod column_edge_service;
+pub mod column_reference_service;
+pub mod command_palette_service;
+pub mod data_product_service;
+pub mod dbml_parser;
+pub mod ddl_lint_service;
+pub mod diagram_share_service;
+pub mod domain_graph_service;
+pub mod domain_index_service;
+pub mod domain_invite_service;
+pub mod domain_service;
+pub mod domain_share_service;
+pub mod draft_service;
 pub mod drawio_service;
+pub mod duplicate_table_service;
+pub mod enterprise_model_service;
+pub mod environment_service;
+pub mod erwin_parser;
+pub mod event_bus_service;
+pub mod event_log_service;
+pub mod event_storming_parser;
+pub mod evolution_policy_service;
+pub mod export_registry_service;
+pub mod export_scheduler_service;
 pub mod export_service;
+pub mod export_stream_service;
 pub mod filter_service;
+pub mod fixture_service;
 pub mod git_service;
 pub mod git_sync_service;
+pub mod google_sheets_parser;
+pub mod idempotency_service;
+pub mod inheritance_service;
 pub mod json_schema_parser;
 pub mod jwt_service;
+pub mod lakehouse_parser;
+pub mod local_mode;
+pub mod mcp_service;
+pub mod medallion_inference_service;
+pub mod metadata_propagation_service;
+pub mod metadata_schema_service;
+pub mod mock_response_service;
+pub mod model_archive_service;
+pub mod model_diff_service;
+pub mod model_limits_service;
 pub mod model_service;
+pub mod model_validation_service;
+pub mod naming_service;
+pub mod notification_service;
 pub mod oauth_service;
 pub mod odcl_converter;
 pub mod odcs_parser;
+pub mod orm_parser;
+pub mod outbound_http_service;
+pub mod payload_validation_service;
 pub mod protobuf_parser;
+pub mod quality_score_service;
+pub mod relationship_csv_parser;
+pub mod relationship_rule_service;
 pub mod relationship_service;
+pub mod relationships_file_service;
+pub mod rename_service;
+pub mod request_audit_service;
+pub mod retention_service;
+pub mod save_coordinator_service;
+pub mod saved_filter_service;
+pub mod script_hook_service;
+pub mod session_expiry_service;
+pub mod size_estimate_service;
 pub mod sql_parser;
+pub mod standard_columns_service;
 pub mod table_converter;
+pub mod table_profile_service;
+pub mod table_readme_service;
+pub mod telemetry_service;
+pub mod temporal_service;
+pub mod token_revocation_service;
+pub mod work_item_service;
+pub mod workspace_provisioning_service;
 
 // Re-export for convenience
 #[allow(unused_imports)]
@@ -31,6 +101,10 @@ pub use canvas_layout_service::CanvasLayoutService;
 #[allow(unused_imports)]
 pub use drawio_service::DrawIOService;
 #[allow(unused_imports)]
+pub use environment_service::{EnvironmentConfig, EnvironmentProfile};
+#[allow(unused_imports)]
+pub use erwin_parser::ErwinParser;
+#[allow(unused_imports)]
 pub use export_service::ExportService;
 pub use filter_service::FilterService;
 #[allow(unused_imports)]
@@ -43,10 +117,16 @@ pub use json_schema_parser::JSONSchemaParser;
 pub use jwt_service::{Claims, JwtService, SharedJwtService, TokenPair, TokenType};
 pub use model_service::ModelService;
 #[allow(unused_imports)]
-pub use oauth_service::OAuthService;
+pub use notification_service::{NotificationEvent, NotificationPreferences, NotificationService};
+#[allow(unused_imports)]
+pub use oauth_service::{OAuthProvider, OAuthProviders};
 #[allow(unused_imports)]
 pub use odcl_converter::ODCLConverter;
 pub use odcs_parser::ODCSParser;
+#[allow(unused_imports)]
+pub use orm_parser::OrmParser;
 pub use protobuf_parser::ProtobufParser;
 pub use relationship_service::RelationshipService;
+#[allow(unused_imports)]
+pub use request_audit_service::{RequestAuditEntry, RequestAuditLog};
 pub use sql_parser::SQLParser;
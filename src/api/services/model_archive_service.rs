@@ -0,0 +1,208 @@
+//! Model archives.
+//!
+//! A model archive is a zip of a domain directory (`tables/*.yaml`, `relationships.yaml` and
+//! the domain's other YAML files) with paths relative to the domain root. Hidden entries such
+//! as `.git` are left out. Desktop mode opens and saves archives on the local disk.
+
+use anyhow::{Context, Result, bail};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+/// File extension of model archives
+pub const ARCHIVE_EXTENSION: &str = "zip";
+
+/// Maximum total uncompressed size of an archive (100 MB)
+const MAX_ARCHIVE_SIZE: u64 = 100 * 1024 * 1024;
+
+fn is_hidden(name: &str) -> bool {
+    name.starts_with('.')
+}
+
+/// Files of a domain directory, keyed by their path relative to the domain root
+#[derive(Debug, Default)]
+pub struct ModelArchive {
+    files: Vec<(PathBuf, Vec<u8>)>,
+}
+
+impl ModelArchive {
+    /// Collect the non-hidden files of a domain directory.
+    pub fn from_domain(dir: &Path) -> Result<Self> {
+        fn collect(root: &Path, dir: &Path, files: &mut Vec<(PathBuf, Vec<u8>)>) -> Result<()> {
+            let mut entries: Vec<_> = std::fs::read_dir(dir)
+                .with_context(|| format!("Failed to read {}", dir.display()))?
+                .flatten()
+                .filter(|e| !is_hidden(&e.file_name().to_string_lossy()))
+                .collect();
+            entries.sort_by_key(|e| e.file_name());
+            for entry in entries {
+                let path = entry.path();
+                if path.is_dir() {
+                    collect(root, &path, files)?;
+                } else if let Ok(relative) = path.strip_prefix(root) {
+                    files.push((relative.to_path_buf(), std::fs::read(&path)?));
+                }
+            }
+            Ok(())
+        }
+
+        let mut files = Vec::new();
+        collect(dir, dir, &mut files)?;
+        Ok(Self { files })
+    }
+
+    /// Read and check a zip archive.
+    ///
+    /// Entries must stay inside the domain directory and the archive must contain a
+    /// `tables/` directory or `relationships.yaml`.
+    pub fn read(zip_bytes: &[u8]) -> Result<Self> {
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(zip_bytes))
+            .context("Failed to read zip archive")?;
+
+        let mut files = Vec::new();
+        let mut total_size = 0u64;
+        for idx in 0..archive.len() {
+            let mut file = archive.by_index(idx).context("Failed to read zip entry")?;
+            if file.is_dir() {
+                continue;
+            }
+            let Some(relative) = file.enclosed_name().map(Path::to_path_buf) else {
+                bail!(
+                    "Archive entry '{}' is outside the domain directory",
+                    file.name()
+                );
+            };
+            if relative
+                .components()
+                .any(|c| matches!(c, Component::Normal(n) if is_hidden(&n.to_string_lossy())))
+            {
+                continue;
+            }
+            // Count the bytes actually inflated; the size in the entry header can lie
+            let mut content = Vec::new();
+            let name = file.name().to_string();
+            file.by_ref()
+                .take(MAX_ARCHIVE_SIZE - total_size + 1)
+                .read_to_end(&mut content)
+                .with_context(|| format!("Failed to read {}", name))?;
+            total_size += content.len() as u64;
+            if total_size > MAX_ARCHIVE_SIZE {
+                bail!("Archive exceeds maximum uncompressed size");
+            }
+            files.push((relative, content));
+        }
+
+        if !files
+            .iter()
+            .any(|(path, _)| path.starts_with("tables") || path == Path::new("relationships.yaml"))
+        {
+            bail!("Not a model archive: no tables/ directory or relationships.yaml");
+        }
+        Ok(Self { files })
+    }
+
+    /// Paths of the archived files, `/`-separated.
+    pub fn paths(&self) -> Vec<String> {
+        self.files
+            .iter()
+            .map(|(path, _)| {
+                path.components()
+                    .map(|c| c.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .collect()
+    }
+
+    /// Zip the archived files.
+    pub fn to_zip(&self) -> Result<Vec<u8>> {
+        let mut zip_data = Vec::new();
+        {
+            let mut zip = zip::ZipWriter::new(std::io::Cursor::new(&mut zip_data));
+            let options = zip::write::FileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated);
+            for (name, (_, content)) in self.paths().into_iter().zip(&self.files) {
+                zip.start_file(name, options)?;
+                zip.write_all(content)?;
+            }
+            zip.finish()?;
+        }
+        Ok(zip_data)
+    }
+
+    /// Write the archived files into a domain directory. With `replace`, the directory's
+    /// other non-hidden contents are removed first; hidden entries such as `.git` are kept.
+    pub fn extract(&self, dir: &Path, replace: bool) -> Result<()> {
+        if replace && dir.exists() {
+            for entry in std::fs::read_dir(dir)?.flatten() {
+                if is_hidden(&entry.file_name().to_string_lossy()) {
+                    continue;
+                }
+                let path = entry.path();
+                if path.is_dir() {
+                    std::fs::remove_dir_all(&path)?;
+                } else {
+                    std::fs::remove_file(&path)?;
+                }
+            }
+        }
+        for (relative, content) in &self.files {
+            let path = dir.join(relative);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, content)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_round_trip_skips_hidden_entries() {
+        let source = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(source.path().join("tables")).unwrap();
+        std::fs::create_dir_all(source.path().join(".git")).unwrap();
+        std::fs::write(source.path().join("tables/orders.yaml"), "name: orders\n").unwrap();
+        std::fs::write(
+            source.path().join("relationships.yaml"),
+            "relationships: []\n",
+        )
+        .unwrap();
+        std::fs::write(source.path().join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+
+        let zip_data = ModelArchive::from_domain(source.path())
+            .unwrap()
+            .to_zip()
+            .unwrap();
+        let archive = ModelArchive::read(&zip_data).unwrap();
+        assert_eq!(
+            archive.paths(),
+            vec!["relationships.yaml", "tables/orders.yaml"]
+        );
+
+        let target = tempfile::tempdir().unwrap();
+        std::fs::write(target.path().join("stale.yaml"), "old").unwrap();
+        archive.extract(target.path(), true).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(target.path().join("tables/orders.yaml")).unwrap(),
+            "name: orders\n"
+        );
+        assert!(!target.path().join("stale.yaml").exists());
+
+        // Entries escaping the domain directory are rejected
+        let mut zip_data = Vec::new();
+        {
+            let mut zip = zip::ZipWriter::new(std::io::Cursor::new(&mut zip_data));
+            zip.start_file("tables/a.yaml", zip::write::FileOptions::default())
+                .unwrap();
+            zip.start_file("../escape.yaml", zip::write::FileOptions::default())
+                .unwrap();
+            zip.finish().unwrap();
+        }
+        assert!(ModelArchive::read(&zip_data).is_err());
+    }
+}
@@ -0,0 +1,395 @@
+//! Model diffing.
+//!
+//! Compares two data models by table name and reports added/removed tables, column changes
+//! and relationship changes.
+//!
+//! Column changes are classified with a [`ColumnChangeKind`] (type changed, nullability
+//! tightened or relaxed, description edited, moved, ...), a machine-readable taxonomy that
+//! the schema evolution compatibility check consumes as well. Columns are matched by name,
+//! case-insensitively like SQL identifiers.
+
+use crate::models::{Column, DataModel};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use utoipa::ToSchema;
+
+/// What changed about a column present before and after.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnChangeKind {
+    TypeChanged,
+    /// Nullable column made NOT NULL
+    NullabilityTightened,
+    /// NOT NULL column made nullable
+    NullabilityRelaxed,
+    PrimaryKeyAdded,
+    PrimaryKeyRemoved,
+    DescriptionChanged,
+    EnumValuesChanged,
+    ClassificationChanged,
+    /// Position changed relative to the other columns present in both versions
+    OrderChanged,
+}
+
+impl ColumnChangeKind {
+    /// Human-readable description, e.g. `type changed`.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::TypeChanged => "type changed",
+            Self::NullabilityTightened => "nullability tightened",
+            Self::NullabilityRelaxed => "nullability relaxed",
+            Self::PrimaryKeyAdded => "added to primary key",
+            Self::PrimaryKeyRemoved => "removed from primary key",
+            Self::DescriptionChanged => "description edited",
+            Self::EnumValuesChanged => "allowed values changed",
+            Self::ClassificationChanged => "classification changed",
+            Self::OrderChanged => "order changed",
+        }
+    }
+}
+
+/// A changed attribute of a column.
+#[derive(Debug, Clone, Serialize, PartialEq, ToSchema)]
+pub struct ColumnChange {
+    pub column: String,
+    pub kind: ColumnChangeKind,
+    /// Changed attribute: `data_type`, `nullable`, `primary_key`, `description`,
+    /// `enum_values`, `classification` or `position`
+    pub field: String,
+    pub before: String,
+    pub after: String,
+}
+
+/// Column-level differences of a table present in both models.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct TableDiff {
+    pub table: String,
+    pub added_columns: Vec<String>,
+    pub removed_columns: Vec<String>,
+    pub changed_columns: Vec<ColumnChange>,
+}
+
+impl TableDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_columns.is_empty()
+            && self.removed_columns.is_empty()
+            && self.changed_columns.is_empty()
+    }
+}
+
+/// Differences between two models.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ModelDiff {
+    pub added_tables: Vec<String>,
+    pub removed_tables: Vec<String>,
+    pub changed_tables: Vec<TableDiff>,
+    /// Relationships as `source -> target` table names
+    pub added_relationships: Vec<String>,
+    pub removed_relationships: Vec<String>,
+}
+
+impl ModelDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_tables.is_empty()
+            && self.removed_tables.is_empty()
+            && self.changed_tables.is_empty()
+            && self.added_relationships.is_empty()
+            && self.removed_relationships.is_empty()
+    }
+}
+
+/// Attribute changes of a column present in both versions (everything but its position).
+fn attribute_changes(old: &Column, new: &Column) -> Vec<ColumnChange> {
+    let change = |kind, field: &str, before: String, after: String| ColumnChange {
+        column: new.name.clone(),
+        kind,
+        field: field.to_string(),
+        before,
+        after,
+    };
+    let mut changes = Vec::new();
+    if !old
+        .data_type
+        .trim()
+        .eq_ignore_ascii_case(new.data_type.trim())
+    {
+        changes.push(change(
+            ColumnChangeKind::TypeChanged,
+            "data_type",
+            old.data_type.clone(),
+            new.data_type.clone(),
+        ));
+    }
+    if old.nullable != new.nullable {
+        changes.push(change(
+            if new.nullable {
+                ColumnChangeKind::NullabilityRelaxed
+            } else {
+                ColumnChangeKind::NullabilityTightened
+            },
+            "nullable",
+            old.nullable.to_string(),
+            new.nullable.to_string(),
+        ));
+    }
+    if old.primary_key != new.primary_key {
+        changes.push(change(
+            if new.primary_key {
+                ColumnChangeKind::PrimaryKeyAdded
+            } else {
+                ColumnChangeKind::PrimaryKeyRemoved
+            },
+            "primary_key",
+            old.primary_key.to_string(),
+            new.primary_key.to_string(),
+        ));
+    }
+    if old.description.trim() != new.description.trim() {
+        changes.push(change(
+            ColumnChangeKind::DescriptionChanged,
+            "description",
+            old.description.clone(),
+            new.description.clone(),
+        ));
+    }
+    if old.enum_values != new.enum_values {
+        changes.push(change(
+            ColumnChangeKind::EnumValuesChanged,
+            "enum_values",
+            old.enum_values.join(", "),
+            new.enum_values.join(", "),
+        ));
+    }
+    if old.classification != new.classification {
+        changes.push(change(
+            ColumnChangeKind::ClassificationChanged,
+            "classification",
+            old.classification.clone().unwrap_or_default(),
+            new.classification.clone().unwrap_or_default(),
+        ));
+    }
+    changes
+}
+
+/// Names (lowercase) of the columns that moved: those outside the longest common
+/// subsequence of the shared columns' old and new orders.
+fn moved_columns(old: &[String], new: &[String]) -> BTreeSet<String> {
+    let (n, m) = (old.len(), new.len());
+    let mut lengths = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+    let mut stayed = BTreeSet::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if old[i] == new[j] {
+            stayed.insert(old[i].clone());
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    old.iter()
+        .filter(|c| !stayed.contains(*c))
+        .cloned()
+        .collect()
+}
+
+/// Column-level differences of a table going from `before` to `after`.
+pub fn diff_columns(table: &str, before: &[Column], after: &[Column]) -> TableDiff {
+    let find = |columns: &'_ [Column], name: &str| {
+        columns
+            .iter()
+            .position(|c| c.name.eq_ignore_ascii_case(name))
+    };
+    let mut diff = TableDiff {
+        table: table.to_string(),
+        added_columns: after
+            .iter()
+            .filter(|c| find(before, &c.name).is_none())
+            .map(|c| c.name.clone())
+            .collect(),
+        removed_columns: before
+            .iter()
+            .filter(|c| find(after, &c.name).is_none())
+            .map(|c| c.name.clone())
+            .collect(),
+        ..Default::default()
+    };
+
+    let shared = |columns: &[Column], others: &[Column]| -> Vec<String> {
+        columns
+            .iter()
+            .filter(|c| find(others, &c.name).is_some())
+            .map(|c| c.name.to_lowercase())
+            .collect()
+    };
+    let (old_order, new_order) = (shared(before, after), shared(after, before));
+    let moved = moved_columns(&old_order, &new_order);
+
+    for old in before {
+        let Some(position) = find(after, &old.name) else {
+            continue;
+        };
+        let new = &after[position];
+        diff.changed_columns.extend(attribute_changes(old, new));
+        let key = old.name.to_lowercase();
+        if moved.contains(&key) {
+            let index = |order: &[String]| {
+                order
+                    .iter()
+                    .position(|c| *c == key)
+                    .map_or(0, |i| i + 1)
+                    .to_string()
+            };
+            diff.changed_columns.push(ColumnChange {
+                column: new.name.clone(),
+                kind: ColumnChangeKind::OrderChanged,
+                field: "position".to_string(),
+                before: index(&old_order),
+                after: index(&new_order),
+            });
+        }
+    }
+    diff
+}
+
+fn relationship_names(model: &DataModel) -> BTreeSet<String> {
+    let name = |id| {
+        model
+            .get_table_by_id(id)
+            .map(|t| t.name.clone())
+            .unwrap_or_else(|| id.to_string())
+    };
+    model
+        .relationships
+        .iter()
+        .map(|r| format!("{} -> {}", name(r.source_table_id), name(r.target_table_id)))
+        .collect()
+}
+
+/// Compute the differences going from `before` to `after`.
+pub fn diff_models(before: &DataModel, after: &DataModel) -> ModelDiff {
+    let old: BTreeMap<&str, _> = before.tables.iter().map(|t| (t.name.as_str(), t)).collect();
+    let new: BTreeMap<&str, _> = after.tables.iter().map(|t| (t.name.as_str(), t)).collect();
+
+    let mut diff = ModelDiff {
+        added_tables: new
+            .keys()
+            .filter(|n| !old.contains_key(*n))
+            .map(|n| n.to_string())
+            .collect(),
+        removed_tables: old
+            .keys()
+            .filter(|n| !new.contains_key(*n))
+            .map(|n| n.to_string())
+            .collect(),
+        ..Default::default()
+    };
+
+    for (name, old_table) in &old {
+        let Some(new_table) = new.get(name) else {
+            continue;
+        };
+        let table_diff = diff_columns(name, &old_table.columns, &new_table.columns);
+        if !table_diff.is_empty() {
+            diff.changed_tables.push(table_diff);
+        }
+    }
+
+    let old_rels = relationship_names(before);
+    let new_rels = relationship_names(after);
+    diff.added_relationships = new_rels.difference(&old_rels).cloned().collect();
+    diff.removed_relationships = old_rels.difference(&new_rels).cloned().collect();
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Relationship, Table};
+
+    fn model(tables: Vec<Table>, relationships: Vec<Relationship>) -> DataModel {
+        DataModel {
+            tables,
+            relationships,
+            ..DataModel::new(
+                "m".to_string(),
+                "/tmp/m".to_string(),
+                "/tmp/m/relationships.yaml".to_string(),
+            )
+        }
+    }
+
+    #[test]
+    fn test_diff_tables_columns_and_relationships() {
+        let id = Column::new("id".to_string(), "INT".to_string());
+        let customers = Table::new("customers".to_string(), vec![id.clone()]);
+        let orders = Table::new("orders".to_string(), vec![id.clone()]);
+        let mut customer = customers.clone();
+        customer.name = "customer".to_string();
+        customer.columns = vec![
+            Column::new("email".to_string(), "TEXT".to_string()),
+            Column::new("name".to_string(), "TEXT".to_string()),
+            id.clone(),
+        ];
+        let before = model(
+            vec![customers.clone(), orders.clone(), customer.clone()],
+            Vec::new(),
+        );
+
+        let mut changed_orders = orders.clone();
+        changed_orders.columns[0].data_type = "BIGINT".to_string();
+        changed_orders
+            .columns
+            .push(Column::new("customer_id".to_string(), "BIGINT".to_string()));
+        let mut changed_customer = customer.clone();
+        changed_customer.columns.rotate_left(1);
+        changed_customer.columns[0].nullable = false;
+        changed_customer.columns[2].description = "Primary email".to_string();
+        let relationship = Relationship::new(changed_orders.id, customers.id);
+        let after = model(
+            vec![
+                changed_orders,
+                Table::new("products".to_string(), vec![id]),
+                changed_customer,
+            ],
+            vec![relationship],
+        );
+
+        let diff = diff_models(&before, &after);
+        assert_eq!(diff.added_tables, vec!["products"]);
+        assert_eq!(diff.removed_tables, vec!["customers"]);
+        assert_eq!(diff.changed_tables.len(), 2);
+        // Tables are reported in name order: customer, orders
+        let kinds: Vec<_> = diff.changed_tables[0]
+            .changed_columns
+            .iter()
+            .map(|c| (c.column.as_str(), c.kind))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("email", ColumnChangeKind::DescriptionChanged),
+                ("email", ColumnChangeKind::OrderChanged),
+                ("name", ColumnChangeKind::NullabilityTightened),
+            ]
+        );
+        assert_eq!(diff.changed_tables[1].added_columns, vec!["customer_id"]);
+        assert_eq!(
+            diff.changed_tables[1].changed_columns[0].kind,
+            ColumnChangeKind::TypeChanged
+        );
+        assert_eq!(diff.changed_tables[1].changed_columns[0].after, "BIGINT");
+        assert_eq!(diff.added_relationships.len(), 1);
+    }
+}
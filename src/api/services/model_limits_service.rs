@@ -0,0 +1,165 @@
+//! Model size guardrails.
+//!
+//! Runaway imports can produce domains far larger than the canvas can load. These limits are
+//! checked whenever tables are created, updated or imported, and reported by
+//! `GET /workspace/usage`.
+//!
+//! Configuration (environment variables):
+//! - `MODEL_MAX_TABLES_PER_DOMAIN` (default 1000)
+//! - `MODEL_MAX_COLUMNS_PER_TABLE` (default 500)
+//! - `IMPORT_MAX_FILE_BYTES` (default 10 MiB): largest file or text accepted by an import
+
+use serde::Serialize;
+use std::env;
+use std::fmt;
+use utoipa::ToSchema;
+
+const DEFAULT_MAX_TABLES_PER_DOMAIN: usize = 1000;
+const DEFAULT_MAX_COLUMNS_PER_TABLE: usize = 500;
+const DEFAULT_MAX_IMPORT_FILE_BYTES: usize = 10 * 1024 * 1024;
+
+/// Configured model size limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+pub struct ModelLimits {
+    pub max_tables_per_domain: usize,
+    pub max_columns_per_table: usize,
+    pub max_import_file_bytes: usize,
+}
+
+/// A limit that a change would exceed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LimitExceeded {
+    Tables {
+        limit: usize,
+        requested: usize,
+    },
+    Columns {
+        table: String,
+        limit: usize,
+        requested: usize,
+    },
+    ImportFileSize {
+        limit: usize,
+        size: usize,
+    },
+}
+
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitExceeded::Tables { limit, requested } => write!(
+                f,
+                "Domain would have {} tables; the limit is {} tables per domain",
+                requested, limit
+            ),
+            LimitExceeded::Columns {
+                table,
+                limit,
+                requested,
+            } => write!(
+                f,
+                "Table '{}' has {} columns; the limit is {} columns per table",
+                table, requested, limit
+            ),
+            LimitExceeded::ImportFileSize { limit, size } => write!(
+                f,
+                "Import is {} bytes; the limit is {} bytes per import",
+                size, limit
+            ),
+        }
+    }
+}
+
+impl ModelLimits {
+    /// Configure from `MODEL_MAX_TABLES_PER_DOMAIN`, `MODEL_MAX_COLUMNS_PER_TABLE` and
+    /// `IMPORT_MAX_FILE_BYTES`.
+    pub fn from_env() -> Self {
+        fn var(name: &str, default: usize) -> usize {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(default)
+        }
+        Self {
+            max_tables_per_domain: var(
+                "MODEL_MAX_TABLES_PER_DOMAIN",
+                DEFAULT_MAX_TABLES_PER_DOMAIN,
+            ),
+            max_columns_per_table: var(
+                "MODEL_MAX_COLUMNS_PER_TABLE",
+                DEFAULT_MAX_COLUMNS_PER_TABLE,
+            ),
+            max_import_file_bytes: var("IMPORT_MAX_FILE_BYTES", DEFAULT_MAX_IMPORT_FILE_BYTES),
+        }
+    }
+
+    /// Check that a domain with `existing` tables can take `added` more.
+    pub fn check_table_count(&self, existing: usize, added: usize) -> Result<(), LimitExceeded> {
+        let requested = existing + added;
+        if added > 0 && requested > self.max_tables_per_domain {
+            return Err(LimitExceeded::Tables {
+                limit: self.max_tables_per_domain,
+                requested,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check the column count of a table.
+    pub fn check_columns(&self, table: &str, columns: usize) -> Result<(), LimitExceeded> {
+        if columns > self.max_columns_per_table {
+            return Err(LimitExceeded::Columns {
+                table: table.to_string(),
+                limit: self.max_columns_per_table,
+                requested: columns,
+            });
+        }
+        Ok(())
+    }
+
+    /// Check the size of an uploaded file or pasted text.
+    pub fn check_import_size(&self, size: usize) -> Result<(), LimitExceeded> {
+        if size > self.max_import_file_bytes {
+            return Err(LimitExceeded::ImportFileSize {
+                limit: self.max_import_file_bytes,
+                size,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_checks() {
+        let limits = ModelLimits {
+            max_tables_per_domain: 3,
+            max_columns_per_table: 2,
+            max_import_file_bytes: 10,
+        };
+
+        assert!(limits.check_table_count(1, 2).is_ok());
+        assert_eq!(
+            limits.check_table_count(2, 2),
+            Err(LimitExceeded::Tables {
+                limit: 3,
+                requested: 4
+            })
+        );
+        // Domains already over the limit can still be edited
+        assert!(limits.check_table_count(5, 0).is_ok());
+
+        assert!(limits.check_columns("orders", 2).is_ok());
+        let err = limits.check_columns("orders", 3).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Table 'orders' has 3 columns; the limit is 2 columns per table"
+        );
+
+        assert!(limits.check_import_size(10).is_ok());
+        assert!(limits.check_import_size(11).is_err());
+    }
+}
@@ -1,8 +1,13 @@
 //! Model service for managing data models and table operations.
 
 use crate::models::{DataModel, Table};
+use crate::services::domain_index_service::DomainIndex;
+use crate::services::draft_service::DraftChanges;
+use crate::services::git_service::GitService;
+use crate::services::rename_service::sync_table_aliases;
+use crate::services::save_coordinator_service::save_coordinator;
 use anyhow::{Context, Result};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use tracing::{info, warn};
 use uuid::Uuid;
@@ -126,8 +131,6 @@ impl ModelService {
             }
         }
 
-        use crate::services::git_service::GitService;
-
         // Use GitService to load model from YAML (handles all YAML I/O)
         let mut git_service = GitService::new();
         let model = match git_service.map_git_directory(&git_directory_path) {
@@ -269,18 +272,21 @@ impl ModelService {
 
         model.tables.push(table_with_position.clone());
 
-        // Auto-save canvas layout when table is added (includes position)
-        let git_path = PathBuf::from(&model.git_directory_path);
-        if let Err(e) = Self::save_canvas_layout(model, &git_path) {
-            warn!("Failed to auto-save canvas layout: {}", e);
-        }
+        // Models without a directory (draft working copies) are kept in memory only
+        if !model.git_directory_path.is_empty() {
+            // Auto-save canvas layout when table is added (includes position)
+            let git_path = PathBuf::from(&model.git_directory_path);
+            if let Err(e) = Self::save_canvas_layout(model, &git_path) {
+                warn!("Failed to auto-save canvas layout: {}", e);
+            }
 
-        // Auto-save table to YAML file
-        if let Err(e) = Self::save_table_to_yaml(&table_with_position, &git_path) {
-            warn!(
-                "Failed to auto-save table {} to YAML: {}",
-                table_with_position.name, e
-            );
+            // Auto-save table to YAML file
+            if let Err(e) = Self::save_table_to_yaml(&table_with_position, &git_path) {
+                warn!(
+                    "Failed to auto-save table {} to YAML: {}",
+                    table_with_position.name, e
+                );
+            }
         }
 
         info!("Added table: {}", table_with_position.name);
@@ -288,6 +294,41 @@ impl ModelService {
         Ok(table_with_position)
     }
 
+    /// Add an imported table, or map it onto the existing table it is an alias of.
+    ///
+    /// A source table that was physically renamed keeps updating the same modeled entity:
+    /// the existing table takes the imported columns and keeps its id, name and layout.
+    pub fn import_table(&mut self, table: Table) -> Result<Table> {
+        let git_path = match self.current_model.as_mut() {
+            Some(model) => PathBuf::from(&model.git_directory_path),
+            None => return self.add_table(table),
+        };
+        let Some(existing) = self
+            .current_model
+            .as_mut()
+            .and_then(|m| m.get_table_by_alias_mut(&table.name))
+        else {
+            return self.add_table(table);
+        };
+
+        info!(
+            "[ModelService] Imported table '{}' is an alias of '{}'",
+            table.name, existing.name
+        );
+        existing.columns = table.columns;
+        existing.odcl_metadata.extend(table.odcl_metadata);
+        if !table.quality.is_empty() {
+            existing.quality = table.quality;
+        }
+        existing.updated_at = chrono::Utc::now();
+        let existing = existing.clone();
+
+        if let Err(e) = Self::save_table_to_yaml(&existing, &git_path) {
+            warn!("Failed to auto-save table {} to YAML: {}", existing.name, e);
+        }
+        Ok(existing)
+    }
+
     /// Get a table by ID.
     pub fn get_table(&self, table_id: Uuid) -> Option<&Table> {
         self.current_model.as_ref()?.get_table_by_id(table_id)
@@ -481,6 +522,15 @@ impl ModelService {
                                 .collect();
                         }
                     }
+                    "aliases" => {
+                        if let Some(arr) = value.as_array() {
+                            table.aliases = arr
+                                .iter()
+                                .filter_map(|v| v.as_str().map(|s| s.trim().to_string()))
+                                .filter(|s| !s.is_empty())
+                                .collect();
+                        }
+                    }
                     "columns" => {
                         if let Some(arr) = value.as_array() {
                             // Deserialize columns
@@ -550,9 +600,13 @@ impl ModelService {
                     }
                     "odcl_metadata" => {
                         if let Some(obj) = value.as_object() {
-                            // Merge with existing metadata
+                            // Merge with existing metadata; null removes a key
                             for (k, v) in obj {
-                                table.odcl_metadata.insert(k.clone(), v.clone());
+                                if v.is_null() {
+                                    table.odcl_metadata.remove(k);
+                                } else {
+                                    table.odcl_metadata.insert(k.clone(), v.clone());
+                                }
                             }
                         }
                     }
@@ -648,6 +702,90 @@ impl ModelService {
         Ok(true)
     }
 
+    /// Apply the changes of a saved draft and write them to the model's directory at once.
+    pub fn apply_draft(&mut self, changes: &DraftChanges) -> Result<()> {
+        // The draft's files are written as one save, so shutdown waits for all of them
+        let _save = save_coordinator().begin();
+        let model = self
+            .current_model
+            .as_mut()
+            .ok_or_else(|| anyhow::anyhow!("No model available"))?;
+        let git_path = PathBuf::from(&model.git_directory_path);
+        let tables_dir = git_path.join("tables");
+        let remove_yaml = |name: &str| {
+            let path = tables_dir.join(format!("{}.yaml", name));
+            if path.exists()
+                && let Err(e) = std::fs::remove_file(&path)
+            {
+                warn!("Failed to remove {:?}: {}", path, e);
+            }
+        };
+
+        for table in &changes.deleted_tables {
+            model.tables.retain(|t| t.id != table.id);
+            remove_yaml(&table.name);
+        }
+        for (before, table) in &changes.updated_tables {
+            if before.name != table.name {
+                remove_yaml(&before.name);
+            }
+        }
+        // Upserts, so saving a draft again after a failed write does not duplicate tables
+        for table in changes
+            .created_tables
+            .iter()
+            .chain(changes.updated_tables.iter().map(|(_, t)| t))
+        {
+            match model.tables.iter_mut().find(|t| t.id == table.id) {
+                Some(existing) => *existing = table.clone(),
+                None => model.tables.push(table.clone()),
+            }
+            Self::save_table_to_yaml(table, &git_path)?;
+        }
+
+        let deleted_table_ids: HashSet<Uuid> =
+            changes.deleted_tables.iter().map(|t| t.id).collect();
+        let deleted_relationship_ids: HashSet<Uuid> =
+            changes.deleted_relationships.iter().map(|r| r.id).collect();
+        model.relationships.retain(|r| {
+            !deleted_relationship_ids.contains(&r.id)
+                && !deleted_table_ids.contains(&r.source_table_id)
+                && !deleted_table_ids.contains(&r.target_table_id)
+        });
+        for relationship in changes
+            .created_relationships
+            .iter()
+            .chain(changes.updated_relationships.iter().map(|(_, r)| r))
+        {
+            match model
+                .relationships
+                .iter_mut()
+                .find(|r| r.id == relationship.id)
+            {
+                Some(existing) => *existing = relationship.clone(),
+                None => model.relationships.push(relationship.clone()),
+            }
+        }
+        if !deleted_table_ids.is_empty()
+            || !deleted_relationship_ids.is_empty()
+            || !changes.updated_relationships.is_empty()
+            || !changes.created_relationships.is_empty()
+        {
+            let mut git_service = GitService::new();
+            git_service.set_git_directory_path(&git_path)?;
+            git_service.save_relationships_to_yaml(&model.relationships, &model.tables)?;
+        }
+        DomainIndex::refresh(&git_path);
+        Self::save_canvas_layout(model, &git_path)?;
+
+        info!(
+            "Saved draft of {}: {:?}",
+            model.git_directory_path,
+            changes.summary()
+        );
+        Ok(())
+    }
+
     /// Detect naming conflicts between new tables and existing tables using unique keys.
     pub fn detect_naming_conflicts(&self, new_tables: &[Table]) -> Vec<(Table, Table)> {
         let model = match &self.current_model {
@@ -778,6 +916,7 @@ impl ModelService {
 
     /// Save a table to YAML file in the git directory.
     fn save_table_to_yaml(table: &Table, git_directory_path: &Path) -> Result<()> {
+        let _save = save_coordinator().begin();
         use crate::services::table_converter::api_table_to_sdk_table;
         use data_modelling_sdk::export::ODCSExporter;
         use std::fs;
@@ -794,6 +933,8 @@ impl ModelService {
 
         fs::write(&yaml_file, yaml_content)
             .with_context(|| format!("Failed to write YAML file: {:?}", yaml_file))?;
+        sync_table_aliases(git_directory_path, table)?;
+        DomainIndex::refresh(git_directory_path);
 
         info!("Saved table {} to {:?}", table.name, yaml_file);
         Ok(())
@@ -888,3 +1029,59 @@ impl Default for ModelService {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Column;
+    use crate::services::rename_service::{ALIASES_FILE, AliasIndex};
+
+    #[test]
+    fn test_import_table_updates_aliased_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut service = ModelService::new();
+        service
+            .create_model("sales".to_string(), dir.path().to_path_buf(), None)
+            .unwrap();
+
+        // Tables without aliases don't create an alias index
+        service
+            .add_table(Table::new(
+                "customers".to_string(),
+                vec![Column::new("id".to_string(), "INT".to_string())],
+            ))
+            .unwrap();
+        assert!(!dir.path().join(ALIASES_FILE).exists());
+
+        let mut orders = Table::new(
+            "orders".to_string(),
+            vec![Column::new("id".to_string(), "INT".to_string())],
+        );
+        orders.aliases = vec!["legacy_orders".to_string()];
+        let orders = service.add_table(orders).unwrap();
+        assert_eq!(
+            AliasIndex::load(dir.path()).tables.get(&orders.id),
+            Some(&vec!["legacy_orders".to_string()])
+        );
+
+        let imported = service
+            .import_table(Table::new(
+                "legacy_orders".to_string(),
+                vec![
+                    Column::new("id".to_string(), "BIGINT".to_string()),
+                    Column::new("amount".to_string(), "DECIMAL".to_string()),
+                ],
+            ))
+            .unwrap();
+        assert_eq!(imported.id, orders.id);
+        assert_eq!(imported.name, "orders");
+        let columns: Vec<_> = imported.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(columns, vec!["id", "amount"]);
+
+        let model = service.get_current_model().unwrap();
+        assert_eq!(model.tables.len(), 2);
+        assert_eq!(model.get_table_by_id(orders.id).unwrap().columns.len(), 2);
+        assert!(dir.path().join("tables/orders.yaml").exists());
+        assert!(!dir.path().join("tables/legacy_orders.yaml").exists());
+    }
+}
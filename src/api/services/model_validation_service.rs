@@ -0,0 +1,371 @@
+//! Model validation.
+//!
+//! Structural checks over a loaded data model, shared by the CLI `validate` command and
+//! anything else that needs to gate on model consistency (e.g. CI).
+
+use crate::models::{DataModel, RowAccessPolicy};
+use crate::services::inheritance_service::hierarchy_problems;
+use crate::services::relationship_rule_service::RelationshipRules;
+use crate::services::table_profile_service::TableProfiles;
+use crate::services::temporal_service::temporal_problems;
+use serde::Serialize;
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// How serious a validation issue is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single validation finding.
+#[derive(Debug, Clone, Serialize)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub table: Option<String>,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    fn error(table: Option<&str>, message: String) -> Self {
+        Self {
+            severity: Severity::Error,
+            table: table.map(str::to_string),
+            message,
+        }
+    }
+
+    fn warning(table: Option<&str>, message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            table: table.map(str::to_string),
+            message,
+        }
+    }
+}
+
+/// Validate tables, columns and relationships of a model.
+pub fn validate_model(model: &DataModel) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let table_ids: HashSet<Uuid> = model.tables.iter().map(|t| t.id).collect();
+    let mut unique_keys = HashSet::new();
+
+    for table in &model.tables {
+        let name = Some(table.name.as_str());
+        if table.name.trim().is_empty() {
+            issues.push(ValidationIssue::error(
+                None,
+                "Table has an empty name".into(),
+            ));
+        }
+        if !unique_keys.insert(table.get_unique_key()) {
+            issues.push(ValidationIssue::error(
+                name,
+                "Duplicate table (same name, catalog and schema)".into(),
+            ));
+        }
+        if table.columns.is_empty() {
+            issues.push(ValidationIssue::warning(
+                name,
+                "Table has no columns".into(),
+            ));
+        }
+        if let Err(e) = table.validate_pattern_exclusivity() {
+            issues.push(ValidationIssue::error(name, e));
+        }
+
+        let mut column_names = HashSet::new();
+        for column in &table.columns {
+            if column.name.trim().is_empty() {
+                issues.push(ValidationIssue::error(
+                    name,
+                    "Column has an empty name".into(),
+                ));
+            } else if !column_names.insert(column.name.to_lowercase()) {
+                issues.push(ValidationIssue::error(
+                    name,
+                    format!("Duplicate column '{}'", column.name),
+                ));
+            }
+            if column.data_type.trim().is_empty() {
+                issues.push(ValidationIssue::error(
+                    name,
+                    format!("Column '{}' has no data type", column.name),
+                ));
+            }
+            match &column.masking_policy {
+                None if column.is_pii() => issues.push(ValidationIssue::error(
+                    name,
+                    format!("PII column '{}' has no masking policy", column.name),
+                )),
+                Some(policy) if policy.mask_type.requires_string() && !column.is_string() => issues
+                    .push(ValidationIssue::error(
+                        name,
+                        format!(
+                            "Column '{}' of type {} cannot use a {} mask",
+                            column.name,
+                            column.data_type,
+                            policy.mask_type.as_str()
+                        ),
+                    )),
+                Some(policy) if policy.visibility_roles.iter().any(|r| r.trim().is_empty()) => {
+                    issues.push(ValidationIssue::error(
+                        name,
+                        format!("Column '{}' has an empty visibility role", column.name),
+                    ))
+                }
+                _ => {}
+            }
+            if let Some(fk) = &column.foreign_key
+                && !Uuid::parse_str(&fk.table_id).is_ok_and(|id| table_ids.contains(&id))
+            {
+                issues.push(ValidationIssue::error(
+                    name,
+                    format!(
+                        "Column '{}' references unknown table {}",
+                        column.name, fk.table_id
+                    ),
+                ));
+            }
+        }
+
+        match table.row_access_policies() {
+            Ok(policies) => issues.extend(
+                row_access_policy_problems(&policies)
+                    .into_iter()
+                    .map(|problem| ValidationIssue::error(name, problem)),
+            ),
+            Err(e) => issues.push(ValidationIssue::error(
+                name,
+                format!("Malformed row access policies: {}", e),
+            )),
+        }
+        match table.temporal() {
+            Ok(Some(settings)) => issues.extend(
+                temporal_problems(table, &settings)
+                    .into_iter()
+                    .map(|problem| ValidationIssue::error(name, problem)),
+            ),
+            Ok(None) => {}
+            Err(e) => issues.push(ValidationIssue::error(
+                name,
+                format!("Malformed temporal settings: {}", e),
+            )),
+        }
+        match table.subtypes() {
+            Ok(Some(hierarchy)) => issues.extend(
+                hierarchy_problems(&model.tables, table, &hierarchy)
+                    .into_iter()
+                    .map(|problem| ValidationIssue::error(name, problem)),
+            ),
+            Ok(None) => {}
+            Err(e) => issues.push(ValidationIssue::error(
+                name,
+                format!("Malformed subtypes: {}", e),
+            )),
+        }
+    }
+
+    for relationship in &model.relationships {
+        for (end, id) in [
+            ("source", relationship.source_table_id),
+            ("target", relationship.target_table_id),
+        ] {
+            if !table_ids.contains(&id) {
+                issues.push(ValidationIssue::error(
+                    None,
+                    format!(
+                        "Relationship {} references unknown {} table {}",
+                        relationship.id, end, id
+                    ),
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+/// Problems with a table's row access policies: empty or duplicate names, empty predicates
+/// and empty roles.
+pub fn row_access_policy_problems(policies: &[RowAccessPolicy]) -> Vec<String> {
+    let mut problems = Vec::new();
+    let mut names = HashSet::new();
+    for policy in policies {
+        if policy.name.trim().is_empty() {
+            problems.push("Row access policy has an empty name".to_string());
+        } else if !names.insert(policy.name.to_lowercase()) {
+            problems.push(format!("Duplicate row access policy '{}'", policy.name));
+        }
+        if policy.predicate.trim().is_empty() {
+            problems.push(format!(
+                "Row access policy '{}' has an empty predicate",
+                policy.name
+            ));
+        }
+        if policy.roles.iter().any(|r| r.trim().is_empty()) {
+            problems.push(format!(
+                "Row access policy '{}' has an empty role",
+                policy.name
+            ));
+        }
+    }
+    problems
+}
+
+/// Report relationships that break the domain's layer transition rules as errors.
+pub fn validate_relationship_rules(
+    model: &DataModel,
+    rules: &RelationshipRules,
+) -> Vec<ValidationIssue> {
+    rules
+        .check_model(model)
+        .into_iter()
+        .map(|violation| {
+            ValidationIssue::error(
+                Some(&violation.reading_table),
+                format!("Rule '{}': {}", violation.rule, violation.message),
+            )
+        })
+        .collect()
+}
+
+/// Compare declared column constraints with the latest observed profile of each table:
+/// NOT NULL columns with nulls and primary key or UNIQUE columns with duplicates are
+/// errors; enum columns with more distinct values than allowed and profiled columns
+/// missing from the table are warnings.
+pub fn validate_profiles(model: &DataModel, profiles: &TableProfiles) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    for table in &model.tables {
+        let Some(profile) = profiles.latest(table.id) else {
+            continue;
+        };
+        let name = Some(table.name.as_str());
+        let single_primary_key = table.columns.iter().filter(|c| c.primary_key).count() == 1;
+        for (column_name, observed) in &profile.columns {
+            let Some(column) = table.columns.iter().find(|c| &c.name == column_name) else {
+                issues.push(ValidationIssue::warning(
+                    name,
+                    format!("Profiled column '{}' is not in the table", column_name),
+                ));
+                continue;
+            };
+            if !column.nullable
+                && let Some(percent) = observed.null_percent.filter(|p| *p > 0.0)
+            {
+                issues.push(ValidationIssue::error(
+                    name,
+                    format!(
+                        "Column '{}' is NOT NULL but {}% of profiled values are null",
+                        column.name, percent
+                    ),
+                ));
+            }
+            let unique = (column.primary_key && single_primary_key)
+                || column
+                    .constraints
+                    .iter()
+                    .any(|c| c.eq_ignore_ascii_case("unique"));
+            if unique
+                && let (Some(distinct), Some(rows)) = (observed.distinct_count, profile.row_count)
+                && distinct < rows
+            {
+                issues.push(ValidationIssue::error(
+                    name,
+                    format!(
+                        "Column '{}' is declared unique but has {} distinct values in {} rows",
+                        column.name, distinct, rows
+                    ),
+                ));
+            }
+            if !column.enum_values.is_empty()
+                && let Some(distinct) = observed.distinct_count
+                && distinct > column.enum_values.len() as u64
+            {
+                issues.push(ValidationIssue::warning(
+                    name,
+                    format!(
+                        "Column '{}' allows {} values but {} distinct values were profiled",
+                        column.name,
+                        column.enum_values.len(),
+                        distinct
+                    ),
+                ));
+            }
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Column, MaskType, MaskingPolicy, Relationship, Table};
+
+    #[test]
+    fn test_reports_duplicates_and_dangling_relationships() {
+        let mut orders = Table::new(
+            "orders".to_string(),
+            vec![
+                Column::new("id".to_string(), "BIGINT".to_string()),
+                Column::new("ID".to_string(), "BIGINT".to_string()),
+            ],
+        );
+        orders.columns[1].data_type = String::new();
+        let empty = Table::new("empty".to_string(), Vec::new());
+        let model = DataModel {
+            relationships: vec![Relationship::new(orders.id, Uuid::new_v4())],
+            tables: vec![orders, empty],
+            ..DataModel::new(
+                "m".to_string(),
+                "/tmp/m".to_string(),
+                "/tmp/m/relationships.yaml".to_string(),
+            )
+        };
+
+        let issues = validate_model(&model);
+        let errors: Vec<_> = issues
+            .iter()
+            .filter(|i| i.severity == Severity::Error)
+            .map(|i| i.message.as_str())
+            .collect();
+        assert!(errors.contains(&"Duplicate column 'ID'"));
+        assert!(errors.contains(&"Column 'ID' has no data type"));
+        assert!(errors.iter().any(|m| m.contains("unknown target table")));
+        assert!(
+            issues
+                .iter()
+                .any(|i| i.severity == Severity::Warning && i.table.as_deref() == Some("empty"))
+        );
+    }
+
+    #[test]
+    fn test_pii_columns_need_a_masking_policy() {
+        let mut email = Column::new("email".to_string(), "VARCHAR(255)".to_string());
+        email.classification = Some("PII".to_string());
+        let mut age = Column::new("age".to_string(), "INT".to_string());
+        age.masking_policy = Some(MaskingPolicy {
+            mask_type: MaskType::Hash,
+            visibility_roles: vec!["hr".to_string()],
+        });
+        let model = DataModel {
+            tables: vec![Table::new("people".to_string(), vec![email, age])],
+            ..DataModel::new("m".to_string(), String::new(), String::new())
+        };
+
+        let messages: Vec<String> = validate_model(&model)
+            .into_iter()
+            .map(|i| i.message)
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "PII column 'email' has no masking policy",
+                "Column 'age' of type INT cannot use a hash mask"
+            ]
+        );
+    }
+}
@@ -0,0 +1,159 @@
+//! Physical naming templates applied at export time.
+//!
+//! Templates add prefixes/suffixes to table names (e.g. `stg_` for bronze, `dim_` for tables
+//! tagged `dimension`) so physical naming standards are enforced without renaming the logical
+//! model. Templates are stored per domain as `naming.yaml`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::enums::MedallionLayer;
+use crate::models::{DataModel, Table};
+
+/// Prefix/suffix added to a table name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct NamingRule {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub prefix: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub suffix: String,
+}
+
+impl NamingRule {
+    /// Apply the rule, skipping a prefix/suffix the name already carries.
+    pub fn apply(&self, name: &str) -> String {
+        let mut result = name.to_string();
+        if !self.prefix.is_empty() && !result.starts_with(&self.prefix) {
+            result = format!("{}{}", self.prefix, result);
+        }
+        if !self.suffix.is_empty() && !result.ends_with(&self.suffix) {
+            result.push_str(&self.suffix);
+        }
+        result
+    }
+}
+
+/// Naming templates configured for a domain.
+///
+/// Tag rules take precedence over medallion layer rules, which take precedence over the default.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct NamingTemplates {
+    /// Rules keyed by medallion layer (bronze, silver, gold, operational)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub layers: BTreeMap<String, NamingRule>,
+    /// Rules keyed by table tag (e.g. "dimension", "fact")
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, NamingRule>,
+    /// Rule for tables matching no tag or layer rule
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<NamingRule>,
+}
+
+/// Logical vs. physical name of a table, returned by the preview endpoint.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct NamePreview {
+    pub table_id: Uuid,
+    pub logical_name: String,
+    pub physical_name: String,
+}
+
+impl NamingTemplates {
+    /// True if no rules are configured.
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty() && self.tags.is_empty() && self.default.is_none()
+    }
+
+    /// Find the rule that applies to a table.
+    pub fn rule_for(&self, table: &Table) -> Option<&NamingRule> {
+        table
+            .tags
+            .iter()
+            .find_map(|tag| self.tags.get(tag))
+            .or_else(|| {
+                table
+                    .medallion_layers
+                    .iter()
+                    .find_map(|layer| self.layers.get(layer_key(layer)))
+            })
+            .or(self.default.as_ref())
+    }
+
+    /// Physical name of a table under these templates.
+    pub fn physical_name(&self, table: &Table) -> String {
+        match self.rule_for(table) {
+            Some(rule) => rule.apply(&table.name),
+            None => table.name.clone(),
+        }
+    }
+
+    /// Return a copy of the model with physical table names.
+    pub fn apply_to_model(&self, model: &DataModel) -> DataModel {
+        let mut model = model.clone();
+        for table in &mut model.tables {
+            table.name = self.physical_name(table);
+        }
+        model
+    }
+
+    /// Preview logical and physical names for every table in the model.
+    pub fn preview(&self, model: &DataModel) -> Vec<NamePreview> {
+        model
+            .tables
+            .iter()
+            .map(|table| NamePreview {
+                table_id: table.id,
+                logical_name: table.name.clone(),
+                physical_name: self.physical_name(table),
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn layer_key(layer: &MedallionLayer) -> &'static str {
+    match layer {
+        MedallionLayer::Bronze => "bronze",
+        MedallionLayer::Silver => "silver",
+        MedallionLayer::Gold => "gold",
+        MedallionLayer::Operational => "operational",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_rule_wins_over_layer_rule() {
+        let templates = NamingTemplates {
+            layers: BTreeMap::from([(
+                "bronze".to_string(),
+                NamingRule {
+                    prefix: "stg_".to_string(),
+                    ..Default::default()
+                },
+            )]),
+            tags: BTreeMap::from([(
+                "dimension".to_string(),
+                NamingRule {
+                    prefix: "dim_".to_string(),
+                    ..Default::default()
+                },
+            )]),
+            default: None,
+        };
+
+        let mut staged = Table::new("orders".to_string(), Vec::new());
+        staged.medallion_layers = vec![MedallionLayer::Bronze];
+        assert_eq!(templates.physical_name(&staged), "stg_orders");
+
+        let mut dimension = Table::new("dim_customer".to_string(), Vec::new());
+        dimension.medallion_layers = vec![MedallionLayer::Bronze];
+        dimension.tags = vec!["dimension".to_string()];
+        assert_eq!(templates.physical_name(&dimension), "dim_customer");
+
+        let plain = Table::new("events".to_string(), Vec::new());
+        assert_eq!(templates.physical_name(&plain), "events");
+    }
+}
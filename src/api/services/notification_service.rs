@@ -0,0 +1,558 @@
+//! Notification service for model events.
+//!
+//! Dispatches model events (proposal opened, table published, breaking change detected,
+//! scheduled export failed) to pluggable notifiers. Slack incoming webhooks and SMTP email are supported out of the box.
+//!
+//! Configuration (environment variables):
+//! - `SLACK_WEBHOOK_URL`: default Slack webhook used when a user has not configured their own
+//! - `NOTIFICATION_WEBHOOK_HOSTS`: comma-separated hosts, besides `hooks.slack.com`, that users
+//!   may point their webhook at (e.g. a self-hosted Mattermost)
+//! - `SMTP_HOST`, `SMTP_PORT` (default 587), `SMTP_USERNAME`, `SMTP_PASSWORD`, `SMTP_FROM`
+
+use anyhow::{Context, Result, bail};
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::env;
+use std::sync::Arc;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::services::evolution_policy_service::CompatibilityViolation;
+use crate::services::outbound_http_service::check_public_url;
+
+/// Host of Slack incoming webhooks
+const SLACK_WEBHOOK_HOST: &str = "hooks.slack.com";
+
+/// Model events that can trigger notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    /// A change proposal was opened for review
+    ProposalOpened,
+    /// Tables were published (e.g. pushed to the git remote)
+    TablePublished,
+    /// A breaking schema change was detected
+    BreakingChangeDetected,
+    /// A scheduled export failed to build or publish
+    ScheduledExportFailed,
+}
+
+impl NotificationEvent {
+    /// Human readable label used in message subjects.
+    pub fn label(&self) -> &'static str {
+        match self {
+            NotificationEvent::ProposalOpened => "Proposal opened",
+            NotificationEvent::TablePublished => "Table published",
+            NotificationEvent::BreakingChangeDetected => "Breaking change detected",
+            NotificationEvent::ScheduledExportFailed => "Scheduled export failed",
+        }
+    }
+}
+
+/// A model event to be delivered to notifiers.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelEvent {
+    pub event: NotificationEvent,
+    pub domain: String,
+    pub actor_email: String,
+    pub summary: String,
+    #[serde(default)]
+    pub table_names: Vec<String>,
+}
+
+impl ModelEvent {
+    /// Event for a proposal the server has just learned of.
+    pub fn proposal_opened(domain: &str, actor_email: &str, proposal_id: &str, url: &str) -> Self {
+        Self {
+            event: NotificationEvent::ProposalOpened,
+            domain: domain.to_string(),
+            actor_email: actor_email.to_string(),
+            summary: format!("Proposal {} opened: {}", proposal_id, url),
+            table_names: Vec::new(),
+        }
+    }
+
+    /// Event for a table change that breaks compatibility, listing what broke.
+    pub fn breaking_change(
+        domain: &str,
+        actor_email: &str,
+        table_name: &str,
+        violations: &[CompatibilityViolation],
+    ) -> Self {
+        let messages: Vec<&str> = violations.iter().map(|v| v.message.as_str()).collect();
+        Self {
+            event: NotificationEvent::BreakingChangeDetected,
+            domain: domain.to_string(),
+            actor_email: actor_email.to_string(),
+            summary: messages.join("; "),
+            table_names: vec![table_name.to_string()],
+        }
+    }
+
+    /// Plain-text rendering shared by the Slack and email notifiers.
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "[{}] domain '{}' by {}: {}",
+            self.event.label(),
+            self.domain,
+            self.actor_email,
+            self.summary
+        );
+        if !self.table_names.is_empty() {
+            text.push_str(&format!("\nTables: {}", self.table_names.join(", ")));
+        }
+        text
+    }
+}
+
+/// Per-user notification preferences.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NotificationPreferences {
+    /// Send email notifications
+    #[serde(default)]
+    pub email_enabled: bool,
+    /// Address to send email to (defaults to the user's login email)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub email_address: Option<String>,
+    /// Send Slack notifications
+    #[serde(default)]
+    pub slack_enabled: bool,
+    /// Slack incoming webhook URL (defaults to SLACK_WEBHOOK_URL)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slack_webhook_url: Option<String>,
+    /// Events the user is subscribed to
+    #[serde(default = "default_events")]
+    pub events: Vec<NotificationEvent>,
+}
+
+fn default_events() -> Vec<NotificationEvent> {
+    vec![
+        NotificationEvent::ProposalOpened,
+        NotificationEvent::TablePublished,
+        NotificationEvent::BreakingChangeDetected,
+        NotificationEvent::ScheduledExportFailed,
+    ]
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            email_enabled: false,
+            email_address: None,
+            slack_enabled: false,
+            slack_webhook_url: None,
+            events: default_events(),
+        }
+    }
+}
+
+impl NotificationPreferences {
+    /// Check whether the user is subscribed to an event.
+    pub fn is_subscribed(&self, event: NotificationEvent) -> bool {
+        self.events.contains(&event)
+    }
+}
+
+/// Check that a user-supplied webhook URL is an https URL on `hooks.slack.com` or on a host
+/// in `NOTIFICATION_WEBHOOK_HOSTS`.
+pub fn check_slack_webhook_url(url: &str) -> Result<()> {
+    let parsed = check_public_url(url)?;
+    let host = parsed.host_str().unwrap_or_default().to_lowercase();
+    let allowed = env::var("NOTIFICATION_WEBHOOK_HOSTS").unwrap_or_default();
+    if host == SLACK_WEBHOOK_HOST
+        || allowed
+            .split(',')
+            .any(|h| h.trim().eq_ignore_ascii_case(&host))
+    {
+        return Ok(());
+    }
+    bail!("Webhook host {} is not allowed", host)
+}
+
+/// A delivery channel for model events.
+#[async_trait::async_trait]
+pub trait Notifier: Send + Sync {
+    /// Channel name used in logs.
+    fn channel(&self) -> &'static str;
+
+    /// Whether this notifier should deliver to the given recipient.
+    fn is_enabled_for(&self, preferences: &NotificationPreferences) -> bool;
+
+    /// Deliver an event to a single recipient.
+    async fn send(
+        &self,
+        recipient_email: &str,
+        preferences: &NotificationPreferences,
+        event: &ModelEvent,
+    ) -> Result<()>;
+}
+
+/// Slack incoming-webhook notifier.
+pub struct SlackNotifier {
+    http_client: reqwest::Client,
+    default_webhook_url: Option<String>,
+}
+
+impl SlackNotifier {
+    pub fn new(default_webhook_url: Option<String>) -> Self {
+        Self {
+            // Webhooks must not redirect elsewhere
+            http_client: reqwest::Client::builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .unwrap_or_default(),
+            default_webhook_url,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Notifier for SlackNotifier {
+    fn channel(&self) -> &'static str {
+        "slack"
+    }
+
+    fn is_enabled_for(&self, preferences: &NotificationPreferences) -> bool {
+        preferences.slack_enabled
+            && (preferences.slack_webhook_url.is_some() || self.default_webhook_url.is_some())
+    }
+
+    async fn send(
+        &self,
+        _recipient_email: &str,
+        preferences: &NotificationPreferences,
+        event: &ModelEvent,
+    ) -> Result<()> {
+        // The server's webhook is trusted; users' webhooks are checked again on every send
+        if let Some(url) = preferences.slack_webhook_url.as_deref() {
+            check_slack_webhook_url(url)?;
+        }
+        let url = preferences
+            .slack_webhook_url
+            .as_ref()
+            .or(self.default_webhook_url.as_ref())
+            .context("No Slack webhook URL configured")?;
+
+        let response = self
+            .http_client
+            .post(url)
+            .json(&json!({ "text": event.to_text() }))
+            .send()
+            .await
+            .context("Failed to send Slack webhook")?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!(
+                "Slack webhook returned status {}",
+                response.status()
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// SMTP email notifier.
+pub struct EmailNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl EmailNotifier {
+    /// Build an email notifier from `SMTP_*` environment variables.
+    ///
+    /// Returns `None` if `SMTP_HOST` or `SMTP_FROM` are not set or invalid.
+    pub fn from_env() -> Option<Self> {
+        let host = env::var("SMTP_HOST").ok()?;
+        let from: Mailbox = match env::var("SMTP_FROM").ok()?.parse() {
+            Ok(mailbox) => mailbox,
+            Err(e) => {
+                warn!("Invalid SMTP_FROM address: {}", e);
+                return None;
+            }
+        };
+        let port = env::var("SMTP_PORT")
+            .ok()
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(587);
+
+        let mut builder = match AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host) {
+            Ok(builder) => builder.port(port),
+            Err(e) => {
+                warn!("Failed to configure SMTP relay {}: {}", host, e);
+                return None;
+            }
+        };
+        if let (Ok(username), Ok(password)) = (env::var("SMTP_USERNAME"), env::var("SMTP_PASSWORD"))
+        {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        Some(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Notifier for EmailNotifier {
+    fn channel(&self) -> &'static str {
+        "email"
+    }
+
+    fn is_enabled_for(&self, preferences: &NotificationPreferences) -> bool {
+        preferences.email_enabled
+    }
+
+    async fn send(
+        &self,
+        recipient_email: &str,
+        preferences: &NotificationPreferences,
+        event: &ModelEvent,
+    ) -> Result<()> {
+        let to: Mailbox = preferences
+            .email_address
+            .as_deref()
+            .unwrap_or(recipient_email)
+            .parse()
+            .context("Invalid recipient email address")?;
+
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(format!(
+                "[Data Modelling] {} - {}",
+                event.event.label(),
+                event.domain
+            ))
+            .body(event.to_text())
+            .context("Failed to build email message")?;
+
+        self.transport
+            .send(message)
+            .await
+            .context("Failed to send email")?;
+        Ok(())
+    }
+}
+
+/// Service dispatching model events to all configured notifiers.
+#[derive(Clone, Default)]
+pub struct NotificationService {
+    notifiers: Vec<Arc<dyn Notifier>>,
+}
+
+impl NotificationService {
+    /// Create a notification service with an explicit set of notifiers.
+    pub fn new(notifiers: Vec<Arc<dyn Notifier>>) -> Self {
+        Self { notifiers }
+    }
+
+    /// Create a notification service from environment configuration.
+    ///
+    /// Slack is always available (users may supply their own webhook URL);
+    /// email is only available when SMTP is configured.
+    pub fn from_env() -> Self {
+        let mut notifiers: Vec<Arc<dyn Notifier>> = vec![Arc::new(SlackNotifier::new(
+            env::var("SLACK_WEBHOOK_URL").ok(),
+        ))];
+        if let Some(email) = EmailNotifier::from_env() {
+            notifiers.push(Arc::new(email));
+        }
+        Self::new(notifiers)
+    }
+
+    /// Names of the channels available on this server.
+    pub fn available_channels(&self) -> Vec<&'static str> {
+        self.notifiers.iter().map(|n| n.channel()).collect()
+    }
+
+    /// Deliver an event to a recipient according to their preferences.
+    ///
+    /// Delivery failures are logged and do not propagate; returns the channels that succeeded.
+    pub async fn dispatch(
+        &self,
+        recipient_email: &str,
+        preferences: &NotificationPreferences,
+        event: &ModelEvent,
+    ) -> Vec<&'static str> {
+        let mut delivered = Vec::new();
+        if !preferences.is_subscribed(event.event) {
+            return delivered;
+        }
+
+        for notifier in &self.notifiers {
+            if !notifier.is_enabled_for(preferences) {
+                continue;
+            }
+            match notifier.send(recipient_email, preferences, event).await {
+                Ok(()) => {
+                    info!(
+                        "Sent {:?} notification via {} to {}",
+                        event.event,
+                        notifier.channel(),
+                        recipient_email
+                    );
+                    delivered.push(notifier.channel());
+                }
+                Err(e) => warn!(
+                    "Failed to send {:?} notification via {}: {}",
+                    event.event,
+                    notifier.channel(),
+                    e
+                ),
+            }
+        }
+        delivered
+    }
+
+    /// Deliver an event in the background so callers are not blocked on network I/O.
+    pub fn dispatch_in_background(
+        &self,
+        recipient_email: String,
+        preferences: NotificationPreferences,
+        event: ModelEvent,
+    ) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            service
+                .dispatch(&recipient_email, &preferences, &event)
+                .await;
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Notifier recording the events it was asked to send
+    #[derive(Default)]
+    struct RecordingNotifier {
+        sent: Mutex<Vec<(String, NotificationEvent)>>,
+    }
+
+    #[async_trait::async_trait]
+    impl Notifier for RecordingNotifier {
+        fn channel(&self) -> &'static str {
+            "recording"
+        }
+
+        fn is_enabled_for(&self, preferences: &NotificationPreferences) -> bool {
+            preferences.email_enabled
+        }
+
+        async fn send(
+            &self,
+            recipient_email: &str,
+            _preferences: &NotificationPreferences,
+            event: &ModelEvent,
+        ) -> Result<()> {
+            let mut sent = self.sent.lock().unwrap();
+            sent.push((recipient_email.to_string(), event.event));
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_preferences_round_trip() {
+        let preferences: NotificationPreferences =
+            serde_yaml::from_str("slack_enabled: true\n").unwrap();
+        assert!(preferences.slack_enabled);
+        assert_eq!(preferences.events, default_events());
+
+        let preferences = NotificationPreferences {
+            email_enabled: true,
+            email_address: Some("alice@example.com".to_string()),
+            events: vec![NotificationEvent::BreakingChangeDetected],
+            ..Default::default()
+        };
+        let yaml = serde_yaml::to_string(&preferences).unwrap();
+        assert!(!yaml.contains("slack_webhook_url"));
+        let loaded: NotificationPreferences = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(loaded.email_address.as_deref(), Some("alice@example.com"));
+        assert!(loaded.is_subscribed(NotificationEvent::BreakingChangeDetected));
+        assert!(!loaded.is_subscribed(NotificationEvent::TablePublished));
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_filters_events_and_channels() {
+        let notifier = Arc::new(RecordingNotifier::default());
+        let service = NotificationService::new(vec![notifier.clone()]);
+        let event = ModelEvent::proposal_opened("sales", "bob@example.com", "42", "https://x/42");
+        let mut preferences = NotificationPreferences {
+            email_enabled: true,
+            events: vec![NotificationEvent::TablePublished],
+            ..Default::default()
+        };
+
+        // Not subscribed to the event
+        assert!(
+            service
+                .dispatch("alice@example.com", &preferences, &event)
+                .await
+                .is_empty()
+        );
+        // Subscribed, but the channel is disabled
+        preferences.events.push(NotificationEvent::ProposalOpened);
+        preferences.email_enabled = false;
+        assert!(
+            service
+                .dispatch("alice@example.com", &preferences, &event)
+                .await
+                .is_empty()
+        );
+
+        preferences.email_enabled = true;
+        let delivered = service
+            .dispatch("alice@example.com", &preferences, &event)
+            .await;
+        assert_eq!(delivered, vec!["recording"]);
+        assert_eq!(
+            *notifier.sent.lock().unwrap(),
+            vec![(
+                "alice@example.com".to_string(),
+                NotificationEvent::ProposalOpened
+            )]
+        );
+    }
+
+    #[test]
+    fn test_message_building() {
+        let violations = vec![
+            CompatibilityViolation {
+                column: "id".to_string(),
+                message: "Column 'id' was removed".to_string(),
+            },
+            CompatibilityViolation {
+                column: "total".to_string(),
+                message: "Column 'total' changed type".to_string(),
+            },
+        ];
+        let event = ModelEvent::breaking_change("sales", "bob@example.com", "orders", &violations);
+        assert_eq!(event.event, NotificationEvent::BreakingChangeDetected);
+        assert_eq!(
+            event.to_text(),
+            "[Breaking change detected] domain 'sales' by bob@example.com: \
+             Column 'id' was removed; Column 'total' changed type\nTables: orders"
+        );
+
+        let event = ModelEvent::proposal_opened("sales", "bob@example.com", "42", "https://x/42");
+        assert_eq!(
+            event.to_text(),
+            "[Proposal opened] domain 'sales' by bob@example.com: Proposal 42 opened: https://x/42"
+        );
+    }
+
+    #[test]
+    fn test_slack_webhook_hosts() {
+        assert!(check_slack_webhook_url("https://hooks.slack.com/services/T0/B0/x").is_ok());
+        assert!(check_slack_webhook_url("http://hooks.slack.com/services/T0/B0/x").is_err());
+        assert!(check_slack_webhook_url("https://example.com/hook").is_err());
+        assert!(check_slack_webhook_url("https://169.254.169.254/latest").is_err());
+    }
+}
@@ -211,6 +211,7 @@ impl ODCSParser {
             data_vault_classification,
             modeling_level: None,
             tags: Vec::new(),
+            aliases: Vec::new(),
             odcl_metadata,
             position: None,
             yaml_file_path: None,
@@ -331,6 +332,10 @@ impl ODCSParser {
             errors: Vec::new(),
             quality: column_quality_rules,
             enum_values: Vec::new(),
+            classification: None,
+            masking_policy: None,
+            glossary_links: Vec::new(),
+            custom_properties: HashMap::new(),
             column_order: 0,
         })
     }
@@ -351,6 +356,14 @@ impl ODCSParser {
                 .and_then(|v| v.as_str())
                 .unwrap_or("")
                 .to_string(),
+            constraint_name: obj
+                .get("constraint_name")
+                .and_then(|v| v.as_str())
+                .map(|s| s.to_string()),
+            key_position: obj
+                .get("key_position")
+                .and_then(|v| v.as_u64())
+                .map(|p| p as u32),
         })
     }
 
@@ -567,6 +580,7 @@ impl ODCSParser {
                     data_vault_classification: None,
                     modeling_level: None,
                     tags: Vec::new(),
+                    aliases: Vec::new(),
                     odcl_metadata: HashMap::new(),
                     position: None,
                     yaml_file_path: None,
@@ -594,6 +608,7 @@ impl ODCSParser {
                     data_vault_classification: None,
                     modeling_level: None,
                     tags: Vec::new(),
+                    aliases: Vec::new(),
                     odcl_metadata: HashMap::new(),
                     position: None,
                     yaml_file_path: None,
@@ -795,6 +810,7 @@ impl ODCSParser {
             data_vault_classification,
             modeling_level: None,
             tags,
+            aliases: Vec::new(),
             odcl_metadata,
             position: None,
             yaml_file_path: None,
@@ -1051,6 +1067,7 @@ impl ODCSParser {
                     data_vault_classification: None,
                     modeling_level: None,
                     tags: Vec::new(),
+                    aliases: Vec::new(),
                     odcl_metadata: HashMap::new(),
                     position: None,
                     yaml_file_path: None,
@@ -1225,6 +1242,7 @@ impl ODCSParser {
             data_vault_classification: None,
             modeling_level: None,
             tags,
+            aliases: Vec::new(),
             odcl_metadata,
             position: None,
             yaml_file_path: None,
@@ -1357,6 +1375,10 @@ impl ODCSParser {
                         errors: Vec::new(),
                         quality: quality_rules.clone(),
                         enum_values: Vec::new(),
+                        classification: None,
+                        masking_policy: None,
+                        glossary_links: Vec::new(),
+                        custom_properties: HashMap::new(),
                         column_order: 0,
                     });
                 } else {
@@ -1395,6 +1417,10 @@ impl ODCSParser {
                         errors: Vec::new(),
                         quality: quality_rules,
                         enum_values,
+                        classification: None,
+                        masking_policy: None,
+                        glossary_links: Vec::new(),
+                        custom_properties: HashMap::new(),
                         column_order: 0,
                     });
                 }
@@ -1430,6 +1456,10 @@ impl ODCSParser {
                     errors: col_errors,
                     quality: Vec::new(),
                     enum_values: Vec::new(),
+                    classification: None,
+                    masking_policy: None,
+                    glossary_links: Vec::new(),
+                    custom_properties: HashMap::new(),
                     column_order: 0,
                 });
                 return Ok(columns);
@@ -1474,6 +1504,10 @@ impl ODCSParser {
                         errors: Vec::new(),
                         quality: Vec::new(),
                         enum_values: Vec::new(),
+                        classification: None,
+                        masking_policy: None,
+                        glossary_links: Vec::new(),
+                        custom_properties: HashMap::new(),
                         column_order: 0,
                     });
 
@@ -1519,6 +1553,10 @@ impl ODCSParser {
                             errors: Vec::new(),
                             quality: Vec::new(),
                             enum_values: Vec::new(),
+                            classification: None,
+                            masking_policy: None,
+                            glossary_links: Vec::new(),
+                            custom_properties: HashMap::new(),
                             column_order: 0,
                         });
 
@@ -1572,6 +1610,10 @@ impl ODCSParser {
                                                 errors: Vec::new(),
                                                 quality: Vec::new(),
                                                 enum_values: Vec::new(),
+                                                classification: None,
+                                                masking_policy: None,
+                                                glossary_links: Vec::new(),
+                                                custom_properties: HashMap::new(),
                                                 column_order: 0,
                                             });
                                         }
@@ -1603,6 +1645,10 @@ impl ODCSParser {
                             errors: Vec::new(),
                             quality: Vec::new(),
                             enum_values: Vec::new(),
+                            classification: None,
+                            masking_policy: None,
+                            glossary_links: Vec::new(),
+                            custom_properties: HashMap::new(),
                             column_order: 0,
                         });
                         return Ok(columns);
@@ -1629,6 +1675,10 @@ impl ODCSParser {
                         errors: Vec::new(),
                         quality: Vec::new(),
                         enum_values: Vec::new(),
+                        classification: None,
+                        masking_policy: None,
+                        glossary_links: Vec::new(),
+                        custom_properties: HashMap::new(),
                         column_order: 0,
                     });
                     return Ok(columns);
@@ -1655,6 +1705,10 @@ impl ODCSParser {
                 errors: Vec::new(),
                 quality: Vec::new(),
                 enum_values: Vec::new(),
+                classification: None,
+                masking_policy: None,
+                glossary_links: Vec::new(),
+                custom_properties: HashMap::new(),
                 column_order: 0,
             });
             return Ok(columns);
@@ -1693,6 +1747,10 @@ impl ODCSParser {
                 errors: Vec::new(),
                 quality: Vec::new(),
                 enum_values: Vec::new(),
+                classification: None,
+                masking_policy: None,
+                glossary_links: Vec::new(),
+                custom_properties: HashMap::new(),
                 column_order: 0,
             });
 
@@ -1734,6 +1792,10 @@ impl ODCSParser {
                                 errors: Vec::new(),
                                 quality: Vec::new(),
                                 enum_values: Vec::new(),
+                                classification: None,
+                                masking_policy: None,
+                                glossary_links: Vec::new(),
+                                custom_properties: HashMap::new(),
                                 column_order: 0,
                             });
                         }
@@ -1817,6 +1879,10 @@ impl ODCSParser {
             errors: Vec::new(),
             quality: column_quality_rules,
             enum_values: Vec::new(),
+            classification: None,
+            masking_policy: None,
+            glossary_links: Vec::new(),
+            custom_properties: HashMap::new(),
             column_order: 0,
         });
 
@@ -1844,6 +1910,8 @@ impl ODCSParser {
                     .and_then(|v| v.as_str())
                     .unwrap_or("")
                     .to_string(),
+                constraint_name: None,
+                key_position: None,
             })
     }
 
@@ -1988,6 +2056,10 @@ impl ODCSParser {
                                 errors: Vec::new(),
                                 quality: Vec::new(),
                                 enum_values: Vec::new(),
+                                classification: None,
+                                masking_policy: None,
+                                glossary_links: Vec::new(),
+                                custom_properties: HashMap::new(),
                                 column_order: 0,
                             });
                         }
@@ -2014,6 +2086,10 @@ impl ODCSParser {
                         errors: Vec::new(),
                         quality: Vec::new(),
                         enum_values: Vec::new(),
+                        classification: None,
+                        masking_policy: None,
+                        glossary_links: Vec::new(),
+                        custom_properties: HashMap::new(),
                         column_order: 0,
                     });
                 }
@@ -0,0 +1,722 @@
+//! ORM model parser for code-first imports.
+//!
+//! Supports:
+//! - SQLAlchemy `Table()` definitions and declarative classes (`Column` / `mapped_column`)
+//! - Django `models.py` model classes
+//! - Prisma `schema.prisma` models
+//!
+//! Parsing is line/regex based and intentionally lenient: unsupported constructs are
+//! skipped and reported as parser errors rather than failing the whole import.
+
+use crate::models::relationship::ForeignKeyDetails;
+use crate::models::{Column, Table};
+use crate::services::avro_parser::ParserError;
+use crate::services::relationship_service::ImportedRelationship;
+use anyhow::Result;
+use regex::Regex;
+use serde_json::json;
+use std::collections::HashMap;
+use tracing::info;
+
+/// Supported ORM source formats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrmFormat {
+    SqlAlchemy,
+    Django,
+    Prisma,
+}
+
+impl OrmFormat {
+    /// Parse a format name (`sqlalchemy`, `django`, `prisma`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "sqlalchemy" => Some(OrmFormat::SqlAlchemy),
+            "django" => Some(OrmFormat::Django),
+            "prisma" => Some(OrmFormat::Prisma),
+            _ => None,
+        }
+    }
+
+    /// Detect the format from a filename and/or file content.
+    pub fn detect(filename: Option<&str>, content: &str) -> Option<Self> {
+        if filename.is_some_and(|f| f.ends_with(".prisma")) || content.contains("datasource ") {
+            return Some(OrmFormat::Prisma);
+        }
+        if content.contains("models.Model") {
+            return Some(OrmFormat::Django);
+        }
+        if content.contains("sqlalchemy")
+            || content.contains("__tablename__")
+            || content.contains("Table(")
+        {
+            return Some(OrmFormat::SqlAlchemy);
+        }
+        if Regex::new(r"(?m)^\s*model\s+\w+\s*\{")
+            .map(|re| re.is_match(content))
+            .unwrap_or(false)
+        {
+            return Some(OrmFormat::Prisma);
+        }
+        None
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            OrmFormat::SqlAlchemy => "sqlalchemy",
+            OrmFormat::Django => "django",
+            OrmFormat::Prisma => "prisma",
+        }
+    }
+}
+
+/// Result of parsing ORM model source.
+#[derive(Debug, Default)]
+pub struct OrmImportResult {
+    pub tables: Vec<Table>,
+    pub relationships: Vec<ImportedRelationship>,
+    pub errors: Vec<ParserError>,
+}
+
+/// Parser for ORM model definitions.
+#[derive(Default)]
+pub struct OrmParser;
+
+/// A foreign key found while parsing, before model/class names are resolved to tables.
+struct PendingForeignKey {
+    source_table: String,
+    source_column: String,
+    /// Referenced model/class or table name
+    target: String,
+    target_column: Option<String>,
+}
+
+/// Python class being parsed: (class name, table name, attribute name/args pairs).
+type PythonClass = (String, Option<String>, Vec<(String, String)>);
+
+/// Django model being parsed: (class name, db_table, columns, foreign keys as (column, target class)).
+type DjangoModel = (String, Option<String>, Vec<Column>, Vec<(String, String)>);
+
+impl OrmParser {
+    /// Create a new ORM parser instance.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse ORM source in the given format.
+    pub fn parse(&self, content: &str, format: OrmFormat) -> Result<OrmImportResult> {
+        let mut result = OrmImportResult::default();
+        let mut pending = Vec::new();
+        // Model/class name -> table name
+        let mut class_tables: HashMap<String, String> = HashMap::new();
+
+        match format {
+            OrmFormat::SqlAlchemy => {
+                self.parse_sqlalchemy(content, &mut result, &mut pending, &mut class_tables)?
+            }
+            OrmFormat::Django => {
+                self.parse_django(content, &mut result, &mut pending, &mut class_tables)?
+            }
+            OrmFormat::Prisma => {
+                self.parse_prisma(content, &mut result, &mut pending, &mut class_tables)?
+            }
+        }
+
+        for table in &mut result.tables {
+            table
+                .odcl_metadata
+                .insert("source_format".to_string(), json!(format.name()));
+        }
+
+        // Resolve foreign keys against parsed tables
+        for fk in pending {
+            let target_table = class_tables
+                .get(&fk.target)
+                .cloned()
+                .unwrap_or_else(|| fk.target.clone());
+            let target_column = fk.target_column.unwrap_or_else(|| {
+                result
+                    .tables
+                    .iter()
+                    .find(|t| t.name == target_table)
+                    .and_then(|t| t.columns.iter().find(|c| c.primary_key))
+                    .map(|c| c.name.clone())
+                    .unwrap_or_else(|| "id".to_string())
+            });
+            result.relationships.push(ImportedRelationship::foreign_key(
+                fk.source_table,
+                target_table,
+                Some(ForeignKeyDetails::new(fk.source_column, target_column)),
+            ));
+        }
+
+        info!(
+            "Parsed {} models: {} tables, {} relationships",
+            format.name(),
+            result.tables.len(),
+            result.relationships.len()
+        );
+        Ok(result)
+    }
+
+    /// Parse SQLAlchemy `Table()` definitions and declarative classes.
+    fn parse_sqlalchemy(
+        &self,
+        content: &str,
+        result: &mut OrmImportResult,
+        pending: &mut Vec<PendingForeignKey>,
+        class_tables: &mut HashMap<String, String>,
+    ) -> Result<()> {
+        let lines = logical_lines(content);
+        let class_re = Regex::new(r"^class\s+(\w+)\s*\(([^)]*)\)\s*:")?;
+        let tablename_re = Regex::new(r#"^\s+__tablename__\s*=\s*["'](\w+)["']"#)?;
+        let attr_re = Regex::new(
+            r"^\s+(\w+)\s*(?::\s*[^=]+)?=\s*(?:\w+\.)?(?:Column|mapped_column)\((.*)\)\s*$",
+        )?;
+        let table_re = Regex::new(r#"(?s)Table\(\s*["'](\w+)["']\s*,(.*)\)\s*$"#)?;
+        let table_column_re = Regex::new(r#"Column\(\s*["'](\w+)["']\s*,?"#)?;
+
+        let mut current: Option<PythonClass> = None;
+        let mut classes = Vec::new();
+
+        for line in &lines {
+            if let Some(caps) = class_re.captures(line) {
+                if let Some(class) = current.take() {
+                    classes.push(class);
+                }
+                current = Some((caps[1].to_string(), None, Vec::new()));
+                continue;
+            }
+            if let Some((_, tablename, attrs)) = current.as_mut() {
+                if let Some(caps) = tablename_re.captures(line) {
+                    *tablename = Some(caps[1].to_string());
+                    continue;
+                }
+                if let Some(caps) = attr_re.captures(line) {
+                    attrs.push((caps[1].to_string(), caps[2].to_string()));
+                    continue;
+                }
+                if !line.starts_with(char::is_whitespace) && !line.trim().is_empty() {
+                    classes.push(current.take().unwrap_or_default());
+                }
+            }
+
+            // Imperative `Table("name", metadata, Column("col", Type, ...), ...)`
+            if let Some(caps) = table_re.captures(line) {
+                let table_name = caps[1].to_string();
+                let body = &caps[2];
+                let mut columns = Vec::new();
+                let starts: Vec<_> = table_column_re.captures_iter(body).collect();
+                for (idx, column_caps) in starts.iter().enumerate() {
+                    let whole = column_caps.get(0).map(|m| m.end()).unwrap_or(0);
+                    let end = starts
+                        .get(idx + 1)
+                        .and_then(|c| c.get(0))
+                        .map(|m| m.start())
+                        .unwrap_or(body.len());
+                    let args = body[whole..end].trim_end_matches([',', ' ', '\n']);
+                    let args = args.strip_suffix(')').unwrap_or(args).trim();
+                    columns.push((column_caps[1].to_string(), args.to_string()));
+                }
+                let table =
+                    self.build_sqlalchemy_table(&table_name, &columns, pending, &mut result.errors);
+                result.tables.push(table);
+            }
+        }
+        if let Some(class) = current.take() {
+            classes.push(class);
+        }
+
+        for (class_name, tablename, attrs) in classes {
+            if attrs.is_empty() {
+                continue;
+            }
+            let Some(table_name) = tablename else {
+                result.errors.push(ParserError {
+                    error_type: "parse_error".to_string(),
+                    field: Some(class_name),
+                    message: "Declarative class has no __tablename__".to_string(),
+                });
+                continue;
+            };
+            class_tables.insert(class_name, table_name.clone());
+            let table =
+                self.build_sqlalchemy_table(&table_name, &attrs, pending, &mut result.errors);
+            result.tables.push(table);
+        }
+        Ok(())
+    }
+
+    fn build_sqlalchemy_table(
+        &self,
+        table_name: &str,
+        columns: &[(String, String)],
+        pending: &mut Vec<PendingForeignKey>,
+        errors: &mut Vec<ParserError>,
+    ) -> Table {
+        let fk_re = Regex::new(r#"ForeignKey\(\s*["'](\w+)\.(\w+)["']"#).ok();
+        let type_re = Regex::new(r"^\s*(?:sa\.|db\.)?([A-Z][A-Za-z_]*)(\(([^)]*)\))?").ok();
+
+        let mut table_columns = Vec::new();
+        for (idx, (name, args)) in columns.iter().enumerate() {
+            // The first positional argument may be a column name string in mapped_column
+            let type_args = args
+                .trim_start()
+                .strip_prefix(['"', '\''])
+                .and_then(|rest| rest.split_once(',').map(|(_, tail)| tail))
+                .unwrap_or(args);
+            let data_type = type_re
+                .as_ref()
+                .and_then(|re| re.captures(type_args))
+                .filter(|caps| &caps[1] != "ForeignKey")
+                .map(|caps| {
+                    sqlalchemy_type(&caps[1], caps.get(3).map(|m| m.as_str()).unwrap_or(""))
+                })
+                .unwrap_or_else(|| "INTEGER".to_string());
+
+            let mut column = Column::new(name.clone(), data_type);
+            column.column_order = idx as i32;
+            column.primary_key = args.contains("primary_key=True");
+            column.nullable = !column.primary_key && !args.contains("nullable=False");
+
+            if let Some(caps) = fk_re.as_ref().and_then(|re| re.captures(args)) {
+                pending.push(PendingForeignKey {
+                    source_table: table_name.to_string(),
+                    source_column: name.clone(),
+                    target: caps[1].to_string(),
+                    target_column: Some(caps[2].to_string()),
+                });
+            }
+            table_columns.push(column);
+        }
+
+        if table_columns.is_empty() {
+            errors.push(ParserError {
+                error_type: "parse_error".to_string(),
+                field: Some(table_name.to_string()),
+                message: "No columns found".to_string(),
+            });
+        }
+        Table::new(table_name.to_string(), table_columns)
+    }
+
+    /// Parse Django `models.Model` classes.
+    fn parse_django(
+        &self,
+        content: &str,
+        result: &mut OrmImportResult,
+        pending: &mut Vec<PendingForeignKey>,
+        class_tables: &mut HashMap<String, String>,
+    ) -> Result<()> {
+        let lines = logical_lines(content);
+        let class_re = Regex::new(r"^class\s+(\w+)\s*\(([^)]*)\)\s*:")?;
+        let field_re =
+            Regex::new(r"^\s+(\w+)\s*=\s*(?:models\.)?(\w+Field|ForeignKey)\((.*)\)\s*$")?;
+        let db_table_re = Regex::new(r#"^\s+db_table\s*=\s*["'](\w+)["']"#)?;
+        let max_length_re = Regex::new(r"max_length\s*=\s*(\d+)")?;
+        let digits_re = Regex::new(r"max_digits\s*=\s*(\d+).*decimal_places\s*=\s*(\d+)")?;
+        let target_re = Regex::new(r#"^\s*["']?([\w.]+)["']?"#)?;
+
+        let mut models: Vec<DjangoModel> = Vec::new();
+        let mut in_model = false;
+
+        for line in &lines {
+            if let Some(caps) = class_re.captures(line) {
+                in_model = caps[2].contains("Model");
+                if in_model {
+                    models.push((caps[1].to_string(), None, Vec::new(), Vec::new()));
+                }
+                continue;
+            }
+            if !in_model {
+                continue;
+            }
+            if !line.starts_with(char::is_whitespace) && !line.trim().is_empty() {
+                in_model = false;
+                continue;
+            }
+            let Some((class_name, db_table, columns, fks)) = models.last_mut() else {
+                continue;
+            };
+            if let Some(caps) = db_table_re.captures(line) {
+                *db_table = Some(caps[1].to_string());
+                continue;
+            }
+            let Some(caps) = field_re.captures(line) else {
+                continue;
+            };
+            let (name, field_type, args) = (&caps[1], &caps[2], &caps[3]);
+
+            if field_type == "ManyToManyField" {
+                result.errors.push(ParserError {
+                    error_type: "unsupported".to_string(),
+                    field: Some(format!("{}.{}", class_name, name)),
+                    message: "ManyToManyField is not imported; model the join table explicitly"
+                        .to_string(),
+                });
+                continue;
+            }
+
+            let is_fk = field_type == "ForeignKey" || field_type == "OneToOneField";
+            let column_name = if is_fk {
+                format!("{}_id", name)
+            } else {
+                name.to_string()
+            };
+            let data_type = match field_type {
+                "CharField" | "SlugField" | "EmailField" | "URLField" => format!(
+                    "VARCHAR({})",
+                    max_length_re
+                        .captures(args)
+                        .map(|c| c[1].to_string())
+                        .unwrap_or_else(|| "255".to_string())
+                ),
+                "DecimalField" => digits_re
+                    .captures(args)
+                    .map(|c| format!("DECIMAL({},{})", &c[1], &c[2]))
+                    .unwrap_or_else(|| "DECIMAL".to_string()),
+                other => django_type(other).to_string(),
+            };
+
+            let mut column = Column::new(column_name.clone(), data_type);
+            column.column_order = columns.len() as i32;
+            column.primary_key = args.contains("primary_key=True")
+                || field_type == "AutoField"
+                || field_type == "BigAutoField";
+            column.nullable = !column.primary_key && args.contains("null=True");
+            columns.push(column);
+
+            if is_fk && let Some(target) = target_re.captures(args) {
+                let target = target[1].rsplit('.').next().unwrap_or_default().to_string();
+                let target = if target == "self" {
+                    class_name.clone()
+                } else {
+                    target
+                };
+                fks.push((column_name, target));
+            }
+        }
+
+        for (class_name, db_table, mut columns, fks) in models {
+            let table_name = db_table.unwrap_or_else(|| class_name.to_lowercase());
+            // Django adds an implicit auto primary key unless one is declared
+            if !columns.iter().any(|c| c.primary_key) {
+                let mut id = Column::new("id".to_string(), "BIGINT".to_string());
+                id.primary_key = true;
+                id.nullable = false;
+                columns.insert(0, id);
+                for (idx, column) in columns.iter_mut().enumerate() {
+                    column.column_order = idx as i32;
+                }
+            }
+            for (column, target) in fks {
+                pending.push(PendingForeignKey {
+                    source_table: table_name.clone(),
+                    source_column: column,
+                    target,
+                    target_column: None,
+                });
+            }
+            class_tables.insert(class_name, table_name.clone());
+            result.tables.push(Table::new(table_name, columns));
+        }
+        Ok(())
+    }
+
+    /// Parse Prisma `model` blocks.
+    fn parse_prisma(
+        &self,
+        content: &str,
+        result: &mut OrmImportResult,
+        pending: &mut Vec<PendingForeignKey>,
+        class_tables: &mut HashMap<String, String>,
+    ) -> Result<()> {
+        let model_re = Regex::new(r"(?ms)^\s*model\s+(\w+)\s*\{(.*?)^\s*\}")?;
+        let field_re = Regex::new(r"^\s*(\w+)\s+(\w+)(\[\])?(\?)?\s*(.*)$")?;
+        let map_re = Regex::new(r#"@map\(\s*"(\w+)"\s*\)"#)?;
+        let block_map_re = Regex::new(r#"@@map\(\s*"(\w+)"\s*\)"#)?;
+        let block_id_re = Regex::new(r"@@id\(\s*(?:fields:\s*)?\[([^\]]*)\]")?;
+        let relation_re =
+            Regex::new(r"@relation\([^)]*fields:\s*\[([^\]]*)\][^)]*references:\s*\[([^\]]*)\]")?;
+        let db_type_re = Regex::new(r"@db\.(\w+(?:\([^)]*\))?)")?;
+
+        let model_names: Vec<String> = model_re
+            .captures_iter(content)
+            .map(|c| c[1].to_string())
+            .collect();
+
+        for caps in model_re.captures_iter(content) {
+            let model_name = caps[1].to_string();
+            let body = &caps[2];
+            let table_name = block_map_re
+                .captures(body)
+                .map(|c| c[1].to_string())
+                .unwrap_or_else(|| model_name.clone());
+            class_tables.insert(model_name.clone(), table_name.clone());
+
+            let composite_pk: Vec<String> = block_id_re
+                .captures(body)
+                .map(|c| c[1].split(',').map(|s| s.trim().to_string()).collect())
+                .unwrap_or_default();
+
+            // Field name -> column name (for @map and relation field resolution)
+            let mut column_names: HashMap<String, String> = HashMap::new();
+            let mut columns = Vec::new();
+            let mut relations = Vec::new();
+
+            for line in body.lines() {
+                let line = line.split("//").next().unwrap_or_default();
+                if line.trim().is_empty() || line.trim_start().starts_with("@@") {
+                    continue;
+                }
+                let Some(field) = field_re.captures(line) else {
+                    continue;
+                };
+                let (name, field_type, is_list, is_optional, attrs) = (
+                    &field[1],
+                    &field[2],
+                    field.get(3).is_some(),
+                    field.get(4).is_some(),
+                    field.get(5).map(|m| m.as_str()).unwrap_or(""),
+                );
+
+                if model_names.iter().any(|m| m == field_type) {
+                    // Relation field: only the side holding `fields:` owns the foreign key
+                    if !is_list && let Some(rel) = relation_re.captures(attrs) {
+                        relations.push((
+                            rel[1]
+                                .split(',')
+                                .next()
+                                .unwrap_or_default()
+                                .trim()
+                                .to_string(),
+                            field_type.to_string(),
+                            rel[2]
+                                .split(',')
+                                .next()
+                                .unwrap_or_default()
+                                .trim()
+                                .to_string(),
+                        ));
+                    }
+                    continue;
+                }
+
+                let column_name = map_re
+                    .captures(attrs)
+                    .map(|c| c[1].to_string())
+                    .unwrap_or_else(|| name.to_string());
+                let data_type = db_type_re
+                    .captures(attrs)
+                    .map(|c| c[1].to_string())
+                    .unwrap_or_else(|| prisma_type(field_type).to_string());
+                let data_type = if is_list {
+                    format!("ARRAY<{}>", data_type)
+                } else {
+                    data_type
+                };
+
+                let mut column = Column::new(column_name.clone(), data_type);
+                column.column_order = columns.len() as i32;
+                column.primary_key =
+                    attrs.contains("@id") || composite_pk.iter().any(|pk| pk == name);
+                column.nullable = is_optional && !column.primary_key;
+                column_names.insert(name.to_string(), column_name);
+                columns.push(column);
+            }
+
+            for (field, target_model, reference) in relations {
+                pending.push(PendingForeignKey {
+                    source_table: table_name.clone(),
+                    source_column: column_names.get(&field).cloned().unwrap_or(field),
+                    target: target_model,
+                    target_column: Some(reference),
+                });
+            }
+            result.tables.push(Table::new(table_name, columns));
+        }
+
+        if result.tables.is_empty() {
+            return Err(anyhow::anyhow!("No Prisma models found"));
+        }
+        Ok(())
+    }
+}
+
+/// Join Python lines that continue inside open brackets into single logical lines.
+fn logical_lines(content: &str) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut depth: i32 = 0;
+
+    for raw in content.lines() {
+        let line = raw.split('#').next().unwrap_or_default();
+        if depth > 0 {
+            current.push(' ');
+            current.push_str(line.trim());
+        } else {
+            current = line.trim_end().to_string();
+        }
+        depth += line.matches(['(', '[', '{']).count() as i32;
+        depth -= line.matches([')', ']', '}']).count() as i32;
+        if depth <= 0 {
+            depth = 0;
+            lines.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+fn sqlalchemy_type(name: &str, args: &str) -> String {
+    let base = match name {
+        "Integer" | "SmallInteger" => "INTEGER",
+        "BigInteger" => "BIGINT",
+        "String" | "Unicode" => "VARCHAR",
+        "Text" | "UnicodeText" => "TEXT",
+        "Boolean" => "BOOLEAN",
+        "DateTime" | "TIMESTAMP" => "TIMESTAMP",
+        "Date" => "DATE",
+        "Time" => "TIME",
+        "Float" => "FLOAT",
+        "Numeric" | "DECIMAL" => "DECIMAL",
+        "JSON" | "JSONB" => "JSON",
+        "LargeBinary" => "BINARY",
+        "Uuid" | "UUID" => "UUID",
+        "Enum" => return "VARCHAR".to_string(),
+        other => other,
+    };
+    let args = args.trim();
+    if args.is_empty() || !args.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+        base.to_string()
+    } else {
+        format!("{}({})", base, args.replace(' ', ""))
+    }
+}
+
+fn django_type(field_type: &str) -> &'static str {
+    match field_type {
+        "AutoField"
+        | "IntegerField"
+        | "SmallIntegerField"
+        | "PositiveIntegerField"
+        | "PositiveSmallIntegerField" => "INTEGER",
+        "BigAutoField" | "BigIntegerField" | "ForeignKey" | "OneToOneField" => "BIGINT",
+        "TextField" => "TEXT",
+        "BooleanField" => "BOOLEAN",
+        "DateTimeField" => "TIMESTAMP",
+        "DateField" => "DATE",
+        "TimeField" => "TIME",
+        "FloatField" => "FLOAT",
+        "JSONField" => "JSON",
+        "UUIDField" => "UUID",
+        "BinaryField" => "BINARY",
+        _ => "VARCHAR(255)",
+    }
+}
+
+fn prisma_type(field_type: &str) -> &'static str {
+    match field_type {
+        "String" => "VARCHAR",
+        "Int" => "INTEGER",
+        "BigInt" => "BIGINT",
+        "Float" => "DOUBLE",
+        "Decimal" => "DECIMAL",
+        "Boolean" => "BOOLEAN",
+        "DateTime" => "TIMESTAMP",
+        "Json" => "JSON",
+        "Bytes" => "BINARY",
+        // Enums and unsupported types
+        _ => "VARCHAR",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sqlalchemy_declarative() {
+        let source = r#"
+from sqlalchemy import Column, ForeignKey, Integer, String
+
+class User(Base):
+    __tablename__ = "users"
+    id = Column(Integer, primary_key=True)
+    name = Column(String(100), nullable=False)
+
+class Post(Base):
+    __tablename__ = "posts"
+    id: Mapped[int] = mapped_column(primary_key=True)
+    user_id = Column(
+        Integer,
+        ForeignKey("users.id"),
+    )
+"#;
+        let result = OrmParser::new()
+            .parse(source, OrmFormat::SqlAlchemy)
+            .unwrap();
+        assert_eq!(result.tables.len(), 2);
+        assert_eq!(result.tables[0].columns[1].data_type, "VARCHAR(100)");
+        assert!(!result.tables[0].columns[1].nullable);
+        assert_eq!(result.relationships.len(), 1);
+        assert_eq!(result.relationships[0].source_table, "posts");
+        assert_eq!(result.relationships[0].target_table, "users");
+    }
+
+    #[test]
+    fn test_parse_django_models() {
+        let source = r#"
+class Author(models.Model):
+    name = models.CharField(max_length=50)
+
+class Book(models.Model):
+    title = models.CharField(max_length=200)
+    author = models.ForeignKey(Author, on_delete=models.CASCADE, null=True)
+
+    class Meta:
+        db_table = "library_book"
+"#;
+        let result = OrmParser::new().parse(source, OrmFormat::Django).unwrap();
+        assert_eq!(result.tables[0].name, "author");
+        assert_eq!(result.tables[0].columns[0].name, "id");
+        assert_eq!(result.tables[1].name, "library_book");
+        assert!(
+            result.tables[1]
+                .columns
+                .iter()
+                .any(|c| c.name == "author_id")
+        );
+        assert_eq!(result.relationships[0].target_table, "author");
+    }
+
+    #[test]
+    fn test_parse_prisma_schema() {
+        let source = r#"
+model User {
+  id    Int    @id @default(autoincrement())
+  email String @unique
+  posts Post[]
+}
+
+model Post {
+  id       Int   @id
+  authorId Int?  @map("author_id")
+  author   User? @relation(fields: [authorId], references: [id])
+  @@map("posts")
+}
+"#;
+        let result = OrmParser::new().parse(source, OrmFormat::Prisma).unwrap();
+        assert_eq!(result.tables.len(), 2);
+        assert_eq!(result.tables[1].name, "posts");
+        assert!(result.tables[1].columns[1].nullable);
+        let fk = &result.relationships[0];
+        assert_eq!(fk.source_table, "posts");
+        assert_eq!(fk.target_table, "User");
+        assert_eq!(
+            fk.foreign_key_details.as_ref().unwrap().source_column,
+            "author_id"
+        );
+    }
+}
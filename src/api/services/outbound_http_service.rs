@@ -0,0 +1,145 @@
+//! Outbound HTTP requests to user-supplied URLs.
+//!
+//! Webhooks and other URLs taken from requests are only called over HTTPS on public
+//! addresses. The host is resolved up front and every address it resolves to must be public
+//! (not loopback, private, link-local, …). The client is pinned to the checked address and
+//! does not follow redirects, so neither DNS rebinding nor a redirect reaches an internal host.
+//! Downloads are read with [`read_limited`] so an oversized body is never buffered whole.
+
+use anyhow::{Context, Result, anyhow, bail};
+use reqwest::Url;
+use std::net::{IpAddr, SocketAddr};
+
+/// Whether an address is reachable on the public internet.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => {
+            let [a, b, ..] = v4.octets();
+            !(v4.is_private()
+                || v4.is_loopback()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+                || v4.is_documentation()
+                || v4.is_multicast()
+                // "This network" (0.0.0.0/8) and carrier-grade NAT (100.64.0.0/10)
+                || a == 0
+                || (a == 100 && b & 0xc0 == 64))
+        }
+        IpAddr::V6(v6) => {
+            if let Some(v4) = v6.to_ipv4_mapped() {
+                return is_public_ip(IpAddr::V4(v4));
+            }
+            let first = v6.segments()[0];
+            !(v6.is_loopback()
+                || v6.is_unspecified()
+                || v6.is_multicast()
+                // Unique local (fc00::/7) and link-local (fe80::/10)
+                || first & 0xfe00 == 0xfc00
+                || first & 0xffc0 == 0xfe80)
+        }
+    }
+}
+
+/// Reject URLs that aren't HTTPS or whose host is a non-public IP address literal.
+///
+/// Host names are only checked once resolved, by [`public_client`].
+pub fn check_public_url(url: &str) -> Result<Url> {
+    let parsed = Url::parse(url).context("Invalid URL")?;
+    if parsed.scheme() != "https" {
+        bail!("URL must use https");
+    }
+    match parsed.host() {
+        None => bail!("URL has no host"),
+        Some(url::Host::Ipv4(ip)) if !is_public_ip(IpAddr::V4(ip)) => {
+            bail!("URL points to a non-public address")
+        }
+        Some(url::Host::Ipv6(ip)) if !is_public_ip(IpAddr::V6(ip)) => {
+            bail!("URL points to a non-public address")
+        }
+        _ => Ok(parsed),
+    }
+}
+
+/// A client for `url` that only connects to the public address its host resolves to.
+pub async fn public_client(url: &str) -> Result<reqwest::Client> {
+    let parsed = check_public_url(url)?;
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| anyhow!("URL has no host"))?
+        .trim_matches(|c| c == '[' || c == ']')
+        .to_string();
+    let port = parsed.port_or_known_default().unwrap_or(443);
+    let addrs: Vec<SocketAddr> = tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .with_context(|| format!("Failed to resolve {}", host))?
+        .collect();
+    if addrs.is_empty() {
+        bail!("{} did not resolve to any address", host);
+    }
+    if let Some(addr) = addrs.iter().find(|addr| !is_public_ip(addr.ip())) {
+        bail!("{} resolves to non-public address {}", host, addr.ip());
+    }
+    Ok(reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .resolve(&host, addrs[0])
+        .build()?)
+}
+
+/// Read a response body, stopping once it exceeds `limit` bytes. A body over the limit is
+/// returned truncated to `limit + 1` bytes, so the caller's size check fails without the
+/// rest being buffered.
+pub async fn read_limited(mut response: reqwest::Response, limit: usize) -> Result<Vec<u8>> {
+    if let Some(length) = response.content_length()
+        && length > limit as u64
+    {
+        bail!(
+            "Response of {} bytes exceeds the limit of {} bytes",
+            length,
+            limit
+        );
+    }
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        let remaining = limit + 1 - body.len();
+        body.extend_from_slice(&chunk[..chunk.len().min(remaining)]);
+        if body.len() > limit {
+            break;
+        }
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_only_public_https_urls() {
+        for ip in ["8.8.8.8", "2606:4700::1111", "::ffff:1.1.1.1"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:169.254.169.254",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{}", ip);
+        }
+
+        assert!(check_public_url("https://hooks.example.com/x").is_ok());
+        assert!(check_public_url("http://hooks.example.com/x").is_err());
+        assert!(check_public_url("https://169.254.169.254/latest").is_err());
+        assert!(check_public_url("https://[::1]:8443/").is_err());
+        assert!(public_client("https://localhost/hook").await.is_err());
+        assert!(public_client("https://127.0.0.1/hook").await.is_err());
+    }
+}
@@ -0,0 +1,206 @@
+//! Sample payload validation against a table's JSON Schema.
+//!
+//! Producers send a JSON document, a JSON array of documents or an NDJSON batch; each
+//! document is checked against the schema generated for the table by the JSON Schema
+//! export. The validator covers the keywords that export emits: `type`, `format`,
+//! `properties`, `required`, plus `enum` and `additionalProperties` when present. `null` is
+//! accepted for properties that are not required, since those are nullable columns.
+
+use chrono::{DateTime, NaiveDate, NaiveTime};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Most errors reported for one request
+pub const MAX_REPORTED_ERRORS: usize = 1000;
+
+/// A problem with one document of a payload
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct PayloadError {
+    /// 1-based position of the document in the payload (line number for NDJSON)
+    pub row: usize,
+    /// Offending field, or None for a problem with the whole document
+    pub field: Option<String>,
+    pub message: String,
+}
+
+/// Split a payload into documents: a JSON array is a batch, any other JSON value a single
+/// document, and anything else is read as NDJSON. Lines that do not parse become errors.
+pub fn parse_payload(body: &str) -> Vec<(usize, Result<Value, String>)> {
+    match serde_json::from_str::<Value>(body) {
+        Ok(Value::Array(documents)) => documents
+            .into_iter()
+            .enumerate()
+            .map(|(i, document)| (i + 1, Ok(document)))
+            .collect(),
+        Ok(document) => vec![(1, Ok(document))],
+        Err(_) => body
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                (
+                    i + 1,
+                    serde_json::from_str(line).map_err(|e| format!("Invalid JSON: {}", e)),
+                )
+            })
+            .collect(),
+    }
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match expected {
+        "string" => value.is_string(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|f| f.fract() == 0.0)
+        }
+        "number" => value.is_number(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "null" => value.is_null(),
+        _ => true,
+    }
+}
+
+fn format_matches(format: &str, value: &str) -> bool {
+    match format {
+        "date" => NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+        "time" => NaiveTime::parse_from_str(value, "%H:%M:%S%.f").is_ok(),
+        "date-time" => DateTime::parse_from_rfc3339(value).is_ok(),
+        "uuid" => Uuid::parse_str(value).is_ok(),
+        "uri" => url::Url::parse(value).is_ok(),
+        "email" => value
+            .split_once('@')
+            .is_some_and(|(local, domain)| !local.is_empty() && domain.contains('.')),
+        _ => true,
+    }
+}
+
+fn describe(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// Check one field value against its property schema.
+fn validate_property(schema: &Map<String, Value>, value: &Value) -> Option<String> {
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(ts)) => ts.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if !types.is_empty() && !types.iter().any(|t| type_matches(t, value)) {
+        return Some(format!(
+            "Expected {}, got {}",
+            types.join(" or "),
+            describe(value)
+        ));
+    }
+    if let (Some(format), Some(text)) =
+        (schema.get("format").and_then(Value::as_str), value.as_str())
+        && !format_matches(format, text)
+    {
+        return Some(format!("'{}' is not a valid {}", text, format));
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array)
+        && !allowed.contains(value)
+    {
+        return Some(format!("{} is not one of the allowed values", value));
+    }
+    None
+}
+
+/// Validate one document against an object schema.
+pub fn validate_document(schema: &Value, row: usize, document: &Value) -> Vec<PayloadError> {
+    let error = |field: Option<&str>, message: String| PayloadError {
+        row,
+        field: field.map(str::to_string),
+        message,
+    };
+    let Some(object) = document.as_object() else {
+        return vec![error(
+            None,
+            format!("Expected a JSON object, got {}", describe(document)),
+        )];
+    };
+    let empty = Map::new();
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|r| r.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    let mut errors: Vec<PayloadError> = required
+        .iter()
+        .filter(|field| object.get(**field).is_none_or(Value::is_null))
+        .map(|field| error(Some(field), "Required field is missing".to_string()))
+        .collect();
+    for (field, value) in object {
+        match properties.get(field).and_then(Value::as_object) {
+            Some(_) if value.is_null() => {}
+            Some(property) => {
+                if let Some(message) = validate_property(property, value) {
+                    errors.push(error(Some(field), message));
+                }
+            }
+            None if schema.get("additionalProperties") == Some(&Value::Bool(false)) => {
+                errors.push(error(Some(field), "Field is not in the schema".to_string()));
+            }
+            None => {}
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_validates_ndjson_rows_against_schema() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "integer"},
+                "email": {"type": "string", "format": "email"},
+                "created_at": {"type": "string", "format": "date-time"},
+                "note": {"type": "string"}
+            },
+            "required": ["id", "email"]
+        });
+        let body = "{\"id\": 1, \"email\": \"a@example.com\", \"note\": null}\n\n\
+                    {\"id\": \"2\", \"created_at\": \"yesterday\"}\n\
+                    not json\n";
+        let documents = parse_payload(body);
+        assert_eq!(documents.len(), 3);
+        assert_eq!(documents[2].0, 4);
+        assert!(documents[2].1.is_err());
+
+        let row = |i: usize| {
+            let (row, document) = &documents[i];
+            validate_document(&schema, *row, document.as_ref().unwrap())
+        };
+        assert!(row(0).is_empty());
+        let errors = row(1);
+        let mut fields: Vec<_> = errors.iter().filter_map(|e| e.field.as_deref()).collect();
+        fields.sort();
+        assert_eq!(fields, vec!["created_at", "email", "id"]);
+        assert!(errors.iter().all(|e| e.row == 3));
+
+        // A JSON array is a batch
+        assert_eq!(parse_payload("[{\"id\": 1}, {\"id\": 2}]").len(), 2);
+    }
+}
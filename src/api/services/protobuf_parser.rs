@@ -59,6 +59,10 @@ impl ProtobufParser {
                     errors: Vec::new(),
                     quality: Vec::new(),
                     enum_values: Vec::new(),
+                    classification: None,
+                    masking_policy: None,
+                    glossary_links: Vec::new(),
+                    custom_properties: HashMap::new(),
                     column_order: 0,
                 }
             })
@@ -78,6 +82,7 @@ impl ProtobufParser {
             data_vault_classification: None,
             modeling_level: None,
             tags: Vec::new(),
+            aliases: Vec::new(),
             odcl_metadata: HashMap::new(),
             position: None,
             yaml_file_path: None,
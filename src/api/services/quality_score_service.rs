@@ -0,0 +1,305 @@
+//! Data model quality score.
+//!
+//! A domain's score (0-100) is the weighted mean of four components, each a percentage:
+//! - description coverage (30%): tables and columns with a description
+//! - key coverage (25%): tables with a primary key
+//! - validation (25%): 100 less the validation violations per table and relationship
+//!   (errors count 1, warnings 0.5)
+//! - lineage completeness (20%): tables connected by at least one lineage relationship
+//!   (data flow, ETL transformation or ETL job metadata)
+//!
+//! Scores are recomputed on every save and appended to `quality_scores.jsonl` in the domain
+//! directory when they changed, so the history is one point per change.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::{Relationship, Table};
+use crate::services::column_reference_service::is_lineage;
+use crate::services::model_validation_service::{Severity, ValidationIssue};
+
+/// File holding a domain's score history
+pub const QUALITY_HISTORY_FILE: &str = "quality_scores.jsonl";
+
+const DESCRIPTION_WEIGHT: f64 = 0.30;
+const KEY_WEIGHT: f64 = 0.25;
+const VALIDATION_WEIGHT: f64 = 0.25;
+const LINEAGE_WEIGHT: f64 = 0.20;
+
+/// Quality score of a domain and its components
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct QualityScore {
+    /// Weighted composite score, 0-100
+    pub score: f64,
+    /// Tables and columns with a description, %
+    pub description_coverage: f64,
+    /// Tables with a primary key, %
+    pub key_coverage: f64,
+    /// 100 less the violations per table and relationship, %
+    pub validation: f64,
+    /// Tables with lineage, %
+    pub lineage_completeness: f64,
+    pub tables: usize,
+    pub columns: usize,
+    pub relationships: usize,
+    /// Validation errors and warnings
+    pub violations: usize,
+}
+
+/// A recorded score
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct QualityScorePoint {
+    pub recorded_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub score: QualityScore,
+}
+
+/// Percentage of `part` in `whole`; nothing to cover counts as complete
+fn percent(part: usize, whole: usize) -> f64 {
+    if whole == 0 {
+        100.0
+    } else {
+        round(part as f64 * 100.0 / whole as f64)
+    }
+}
+
+fn round(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Whether a table has a description: a string, or an ODCS description object with any
+/// non-empty field (`purpose`, `usage`, ...)
+fn has_table_description(table: &Table) -> bool {
+    match table.odcl_metadata.get("description") {
+        Some(serde_json::Value::String(s)) => !s.trim().is_empty(),
+        Some(serde_json::Value::Object(fields)) => fields
+            .values()
+            .any(|v| v.as_str().is_some_and(|s| !s.trim().is_empty())),
+        _ => false,
+    }
+}
+
+/// Score a domain from its model and validation issues.
+pub fn compute_score(
+    tables: &[Table],
+    relationships: &[Relationship],
+    issues: &[ValidationIssue],
+) -> QualityScore {
+    let columns: usize = tables.iter().map(|t| t.columns.len()).sum();
+    let described = tables.iter().filter(|t| has_table_description(t)).count()
+        + tables
+            .iter()
+            .flat_map(|t| &t.columns)
+            .filter(|c| !c.description.trim().is_empty())
+            .count();
+    let keyed = tables
+        .iter()
+        .filter(|t| t.columns.iter().any(|c| c.primary_key))
+        .count();
+
+    let lineage_tables: HashSet<Uuid> = relationships
+        .iter()
+        .filter(|r| is_lineage(r))
+        .flat_map(|r| [r.source_table_id, r.target_table_id])
+        .collect();
+    let with_lineage = tables
+        .iter()
+        .filter(|t| lineage_tables.contains(&t.id))
+        .count();
+
+    let penalty: f64 = issues
+        .iter()
+        .map(|i| match i.severity {
+            Severity::Error => 1.0,
+            Severity::Warning => 0.5,
+        })
+        .sum();
+    let items = tables.len() + relationships.len();
+    let validation = if items == 0 {
+        100.0
+    } else {
+        round((100.0 * (1.0 - penalty / items as f64)).max(0.0))
+    };
+
+    let description_coverage = percent(described, tables.len() + columns);
+    let key_coverage = percent(keyed, tables.len());
+    let lineage_completeness = percent(with_lineage, tables.len());
+    QualityScore {
+        score: round(
+            description_coverage * DESCRIPTION_WEIGHT
+                + key_coverage * KEY_WEIGHT
+                + validation * VALIDATION_WEIGHT
+                + lineage_completeness * LINEAGE_WEIGHT,
+        ),
+        description_coverage,
+        key_coverage,
+        validation,
+        lineage_completeness,
+        tables: tables.len(),
+        columns,
+        relationships: relationships.len(),
+        violations: issues.len(),
+    }
+}
+
+/// Parse a history window such as `90d` (days) or `12w` (weeks).
+pub fn parse_window(value: &str) -> std::result::Result<Duration, String> {
+    let value = value.trim();
+    let invalid = || format!("Invalid history window '{}': use e.g. 30d or 12w", value);
+    let (count, unit) = value.split_at(value.len().saturating_sub(1));
+    let count: i64 = count.parse().map_err(|_| invalid())?;
+    if count <= 0 {
+        return Err(invalid());
+    }
+    match unit {
+        "d" => Ok(Duration::days(count)),
+        "w" => Ok(Duration::weeks(count)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Last recorded score per history file, so saves don't re-read the history
+fn last_scores() -> &'static Mutex<HashMap<PathBuf, QualityScore>> {
+    static LAST: OnceLock<Mutex<HashMap<PathBuf, QualityScore>>> = OnceLock::new();
+    LAST.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Score history of a domain (`quality_scores.jsonl`).
+pub struct QualityHistory {
+    path: PathBuf,
+}
+
+impl QualityHistory {
+    pub fn new(domain_dir: &Path) -> Self {
+        Self {
+            path: domain_dir.join(QUALITY_HISTORY_FILE),
+        }
+    }
+
+    /// All recorded points, oldest first.
+    pub fn read(&self) -> Result<Vec<QualityScorePoint>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let reader = BufReader::new(
+            File::open(&self.path)
+                .with_context(|| format!("Failed to open {}", self.path.display()))?,
+        );
+        Ok(reader
+            .lines()
+            .map_while(|l| l.ok())
+            .filter_map(|l| serde_json::from_str::<QualityScorePoint>(&l).ok())
+            .collect())
+    }
+
+    /// Points recorded at or after `since`, oldest first.
+    pub fn since(&self, since: DateTime<Utc>) -> Result<Vec<QualityScorePoint>> {
+        Ok(self
+            .read()?
+            .into_iter()
+            .filter(|p| p.recorded_at >= since)
+            .collect())
+    }
+
+    /// Append `score` unless it equals the last recorded one. Returns whether it was recorded.
+    pub fn record(&self, score: &QualityScore) -> Result<bool> {
+        let mut last = last_scores().lock().unwrap_or_else(|e| e.into_inner());
+        let previous = match last.get(&self.path) {
+            Some(previous) => Some(previous.clone()),
+            None => self.read()?.pop().map(|p| p.score),
+        };
+        if previous.as_ref() == Some(score) {
+            return Ok(false);
+        }
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let point = QualityScorePoint {
+            recorded_at: Utc::now(),
+            score: score.clone(),
+        };
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open {}", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(&point)?)?;
+        file.flush()?;
+
+        last.insert(self.path.clone(), score.clone());
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Column;
+    use crate::models::enums::RelationshipType;
+
+    #[test]
+    fn test_score_components_and_history() {
+        let mut orders = Table::new(
+            "orders".to_string(),
+            vec![
+                Column::new("id".to_string(), "INT".to_string()),
+                Column::new("total".to_string(), "DECIMAL".to_string()),
+            ],
+        );
+        orders.columns[0].primary_key = true;
+        orders.columns[0].description = "Order id".to_string();
+        orders.odcl_metadata.insert(
+            "description".to_string(),
+            serde_json::json!({"purpose": "Customer orders"}),
+        );
+        let raw = Table::new(
+            "raw_orders".to_string(),
+            vec![Column::new("payload".to_string(), "STRING".to_string())],
+        );
+        let mut flow = Relationship::new(raw.id, orders.id);
+        flow.relationship_type = Some(RelationshipType::DataFlow);
+        let tables = vec![orders, raw];
+        let issues = vec![ValidationIssue {
+            severity: Severity::Warning,
+            table: Some("raw_orders".to_string()),
+            message: "No owner".to_string(),
+        }];
+
+        let score = compute_score(&tables, std::slice::from_ref(&flow), &issues);
+        // 2 of 5 tables and columns described, 1 of 2 keyed, 0.5 penalty over 3 items
+        assert_eq!(score.description_coverage, 40.0);
+        assert_eq!(score.key_coverage, 50.0);
+        assert_eq!(score.validation, 83.3);
+        assert_eq!(score.lineage_completeness, 100.0);
+        assert_eq!(score.score, 65.3);
+        assert_eq!(compute_score(&[], &[], &[]).score, 100.0);
+
+        assert_eq!(parse_window("90d").unwrap(), Duration::days(90));
+        assert_eq!(parse_window("2w").unwrap(), Duration::days(14));
+        assert!(parse_window("0d").is_err() && parse_window("90").is_err());
+
+        // Unchanged scores are not recorded again
+        let dir = tempfile::tempdir().unwrap();
+        let history = QualityHistory::new(dir.path());
+        assert!(history.record(&score).unwrap());
+        assert!(!history.record(&score).unwrap());
+        assert!(
+            history
+                .record(&compute_score(&tables, &[], &issues))
+                .unwrap()
+        );
+        let points = history.since(Utc::now() - Duration::days(1)).unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].score, score);
+    }
+}
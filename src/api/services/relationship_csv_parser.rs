@@ -0,0 +1,299 @@
+//! Relationship CSV mapping parser.
+//!
+//! Imports foreign key mappings kept in spreadsheets, one column pair per row:
+//! `source_table,source_column,target_table,target_column,cardinality`. Headers are matched
+//! case-insensitively and `cardinality` is optional (many-to-one by default). Rows that map
+//! the same source and target table are combined into one composite foreign key. Every row
+//! is checked against the current model, and rows naming unknown tables or columns are
+//! reported with their row number instead of failing the whole file.
+
+use crate::models::DataModel;
+use crate::models::enums::{Cardinality, RelationshipType};
+use crate::models::relationship::ForeignKeyDetails;
+use crate::services::avro_parser::ParserError;
+use crate::services::relationship_service::ImportedRelationship;
+use anyhow::{Result, anyhow, bail};
+
+/// Maximum number of mapping rows imported from one file
+const MAX_ROWS: usize = 50_000;
+
+/// Parser for relationship CSV mapping files.
+#[derive(Default)]
+pub struct RelationshipCsvParser;
+
+/// Column pairs collected for one source/target table pair
+struct PendingRelationship {
+    source_table: String,
+    target_table: String,
+    cardinality: Option<Cardinality>,
+    source_columns: Vec<String>,
+    target_columns: Vec<String>,
+}
+
+impl RelationshipCsvParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Resolve mapping rows against `model`, returning the relationships to create and an
+    /// error for every rejected row. Fails only when the header is missing a required column.
+    pub fn parse(
+        &self,
+        content: &str,
+        model: &DataModel,
+    ) -> Result<(Vec<ImportedRelationship>, Vec<ParserError>)> {
+        let rows = parse_csv(content.trim_start_matches('\u{feff}'));
+        let Some(headers) = rows.first() else {
+            bail!("CSV file is empty");
+        };
+        let find = |name: &str| {
+            headers
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(name))
+        };
+        let required = |name: &str| find(name).ok_or_else(|| anyhow!("Missing column '{}'", name));
+        let source_table_idx = required("source_table")?;
+        let source_column_idx = required("source_column")?;
+        let target_table_idx = required("target_table")?;
+        let target_column_idx = required("target_column")?;
+        let cardinality_idx = find("cardinality");
+
+        let mut pending: Vec<PendingRelationship> = Vec::new();
+        let mut errors = Vec::new();
+
+        for (offset, row) in rows.iter().enumerate().skip(1).take(MAX_ROWS) {
+            let cell = |idx: usize| row.get(idx).map(|s| s.trim()).unwrap_or("");
+            let row_number = offset + 1;
+            if row.iter().all(|c| c.trim().is_empty()) {
+                continue;
+            }
+            let mut reject = |error_type: &str, message: String| {
+                errors.push(ParserError {
+                    error_type: error_type.to_string(),
+                    field: Some(format!("row {}", row_number)),
+                    message,
+                });
+            };
+
+            let values = [
+                cell(source_table_idx),
+                cell(source_column_idx),
+                cell(target_table_idx),
+                cell(target_column_idx),
+            ];
+            if values.iter().any(|v| v.is_empty()) {
+                reject(
+                    "missing_value",
+                    "source_table, source_column, target_table and target_column are required"
+                        .to_string(),
+                );
+                continue;
+            }
+            let [source_table, source_column, target_table, target_column] = values;
+
+            let cardinality = match cardinality_idx.map(cell).filter(|c| !c.is_empty()) {
+                None => None,
+                Some(value) => match parse_cardinality(value) {
+                    Some(cardinality) => Some(cardinality),
+                    None => {
+                        reject(
+                            "invalid_cardinality",
+                            format!("Unknown cardinality '{}'", value),
+                        );
+                        continue;
+                    }
+                },
+            };
+
+            let resolve = |table_name: &str, column_name: &str| {
+                let table = model
+                    .tables
+                    .iter()
+                    .find(|t| t.name.eq_ignore_ascii_case(table_name))
+                    .ok_or_else(|| {
+                        ("unknown_table", format!("Table '{}' not found", table_name))
+                    })?;
+                let column = table
+                    .columns
+                    .iter()
+                    .find(|c| c.name.eq_ignore_ascii_case(column_name))
+                    .ok_or_else(|| {
+                        (
+                            "unknown_column",
+                            format!("Column '{}.{}' not found", table.name, column_name),
+                        )
+                    })?;
+                Ok((table.name.clone(), column.name.clone()))
+            };
+            let resolved = resolve(source_table, source_column)
+                .and_then(|source| Ok((source, resolve(target_table, target_column)?)));
+            let ((source_table, source_column), (target_table, target_column)) = match resolved {
+                Ok(resolved) => resolved,
+                Err((error_type, message)) => {
+                    reject(error_type, message);
+                    continue;
+                }
+            };
+
+            match pending
+                .iter_mut()
+                .find(|p| p.source_table == source_table && p.target_table == target_table)
+            {
+                Some(existing) => {
+                    if existing.source_columns.contains(&source_column) {
+                        reject(
+                            "duplicate_mapping",
+                            format!(
+                                "Column '{}.{}' is already mapped to '{}'",
+                                source_table, source_column, target_table
+                            ),
+                        );
+                        continue;
+                    }
+                    existing.cardinality = existing.cardinality.or(cardinality);
+                    existing.source_columns.push(source_column);
+                    existing.target_columns.push(target_column);
+                }
+                None => pending.push(PendingRelationship {
+                    source_table,
+                    target_table,
+                    cardinality,
+                    source_columns: vec![source_column],
+                    target_columns: vec![target_column],
+                }),
+            }
+        }
+
+        let relationships = pending
+            .into_iter()
+            .map(|p| ImportedRelationship {
+                foreign_key_details: ForeignKeyDetails::from_columns(
+                    &p.source_columns,
+                    &p.target_columns,
+                )
+                .ok(),
+                cardinality: Some(p.cardinality.unwrap_or(Cardinality::ManyToOne)),
+                relationship_type: Some(RelationshipType::ForeignKey),
+                notes: None,
+                source_table: p.source_table,
+                target_table: p.target_table,
+            })
+            .collect();
+        Ok((relationships, errors))
+    }
+}
+
+/// Parse a cardinality such as `ManyToOne`, `many-to-one`, `N:1` or `1:n`.
+fn parse_cardinality(value: &str) -> Option<Cardinality> {
+    let normalized: String = value
+        .chars()
+        .filter(|c| !matches!(c, '_' | '-' | ' '))
+        .collect::<String>()
+        .to_lowercase();
+    match normalized.as_str() {
+        "onetoone" | "1:1" => Some(Cardinality::OneToOne),
+        "onetomany" | "1:n" | "1:m" | "1:*" => Some(Cardinality::OneToMany),
+        "manytoone" | "n:1" | "m:1" | "*:1" => Some(Cardinality::ManyToOne),
+        "manytomany" | "n:n" | "m:n" | "n:m" | "*:*" => Some(Cardinality::ManyToMany),
+        _ => None,
+    }
+}
+
+/// Split CSV content into rows of fields. Supports quoted fields with embedded commas,
+/// newlines and doubled quotes, and both LF and CRLF line endings.
+pub(crate) fn parse_csv(content: &str) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = content.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => row.push(std::mem::take(&mut field)),
+            '\r' if !in_quotes && chars.peek() == Some(&'\n') => {}
+            '\n' if !in_quotes => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Column, Table};
+
+    fn table(name: &str, columns: &[&str]) -> Table {
+        Table::new(
+            name.to_string(),
+            columns
+                .iter()
+                .map(|c| Column::new(c.to_string(), "BIGINT".to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_parse_mapping_with_row_errors() {
+        let mut model = DataModel::new(
+            "m".to_string(),
+            "/tmp/m".to_string(),
+            "/tmp/m/relationships.yaml".to_string(),
+        );
+        model.tables = vec![
+            table("orders", &["id", "customer_id", "region", "country"]),
+            table("customers", &["id"]),
+            table("regions", &["code", "country"]),
+        ];
+        let csv = "\u{feff}Source_Table,source_column,target_table,target_column,cardinality\r\n\
+            orders,customer_id,CUSTOMERS,id,N:1\r\n\
+            orders,region,regions,code,\r\n\
+            orders,country,regions,country,many-to-one\r\n\
+            orders,total,customers,id,\r\n\
+            \"orders\",\"id\",\"shipments\",\"id\",\r\n\
+            orders,id,customers,id,sideways\r\n\
+            ,,,,\r\n";
+
+        let (relationships, errors) = RelationshipCsvParser::new().parse(csv, &model).unwrap();
+
+        assert_eq!(relationships.len(), 2);
+        assert_eq!(relationships[0].target_table, "customers");
+        assert_eq!(relationships[0].cardinality, Some(Cardinality::ManyToOne));
+        let composite = relationships[1].foreign_key_details.as_ref().unwrap();
+        assert_eq!(
+            composite.pairs(),
+            vec![("region", "code"), ("country", "country")]
+        );
+        let rejected: Vec<(&str, Option<&str>)> = errors
+            .iter()
+            .map(|e| (e.error_type.as_str(), e.field.as_deref()))
+            .collect();
+        assert_eq!(
+            rejected,
+            vec![
+                ("unknown_column", Some("row 5")),
+                ("unknown_table", Some("row 6")),
+                ("invalid_cardinality", Some("row 7")),
+            ]
+        );
+
+        assert!(
+            RelationshipCsvParser::new()
+                .parse("from,to\n", &model)
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,183 @@
+//! Architectural relationship rules (allowed medallion layer transitions).
+//!
+//! A rule such as "gold tables may only read from silver" restricts which layers a table
+//! may depend on. The reading side of a relationship depends on its type: data flow and
+//! ETL relationships move data from source to target, so the target reads; foreign key,
+//! dependency and untyped relationships point from the dependent source to the target,
+//! so the source reads. Rules are stored per domain as `relationship-rules.yaml`.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::naming_service::layer_key;
+use crate::models::enums::RelationshipType;
+use crate::models::{DataModel, Table};
+
+/// A layer and the layers its tables may read from.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct LayerTransitionRule {
+    /// Rule name shown in violations (e.g. "gold-reads-silver")
+    pub name: String,
+    /// Layer of the reading table (bronze, silver, gold, operational)
+    pub layer: String,
+    /// Layers the reading table may read from
+    pub may_read_from: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// Relationship rules configured for a domain.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct RelationshipRules {
+    #[serde(default)]
+    pub rules: Vec<LayerTransitionRule>,
+}
+
+/// A relationship that breaks a rule.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RuleViolation {
+    pub rule: String,
+    /// None when checking a relationship that doesn't exist yet
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relationship_id: Option<Uuid>,
+    /// Table that reads (depends on) the other one
+    pub reading_table: String,
+    /// Table being read from
+    pub read_table: String,
+    pub message: String,
+}
+
+impl RelationshipRules {
+    /// Check a (proposed) relationship between two tables of the model.
+    ///
+    /// Self-references and tables without a medallion layer are not constrained.
+    pub fn check(
+        &self,
+        model: &DataModel,
+        source_table_id: Uuid,
+        target_table_id: Uuid,
+        relationship_type: Option<RelationshipType>,
+    ) -> Vec<RuleViolation> {
+        if self.rules.is_empty() || source_table_id == target_table_id {
+            return Vec::new();
+        }
+        let (Some(source), Some(target)) = (
+            model.get_table_by_id(source_table_id),
+            model.get_table_by_id(target_table_id),
+        ) else {
+            return Vec::new();
+        };
+        let (reading, read) = match relationship_type {
+            Some(RelationshipType::DataFlow) | Some(RelationshipType::EtlTransformation) => {
+                (target, source)
+            }
+            _ => (source, target),
+        };
+        self.check_tables(reading, read)
+    }
+
+    /// Check every relationship of the model.
+    pub fn check_model(&self, model: &DataModel) -> Vec<RuleViolation> {
+        model
+            .relationships
+            .iter()
+            .flat_map(|relationship| {
+                self.check(
+                    model,
+                    relationship.source_table_id,
+                    relationship.target_table_id,
+                    relationship.relationship_type,
+                )
+                .into_iter()
+                .map(|mut violation| {
+                    violation.relationship_id = Some(relationship.id);
+                    violation
+                })
+            })
+            .collect()
+    }
+
+    fn check_tables(&self, reading: &Table, read: &Table) -> Vec<RuleViolation> {
+        if read.medallion_layers.is_empty() {
+            return Vec::new();
+        }
+        let read_layers: Vec<&str> = read.medallion_layers.iter().map(layer_key).collect();
+        reading
+            .medallion_layers
+            .iter()
+            .map(layer_key)
+            .flat_map(|layer| {
+                self.rules
+                    .iter()
+                    .filter(move |rule| rule.layer.eq_ignore_ascii_case(layer))
+            })
+            .filter(|rule| {
+                !read_layers.iter().any(|read_layer| {
+                    rule.may_read_from
+                        .iter()
+                        .any(|allowed| allowed.eq_ignore_ascii_case(read_layer))
+                })
+            })
+            .map(|rule| RuleViolation {
+                rule: rule.name.clone(),
+                relationship_id: None,
+                reading_table: reading.name.clone(),
+                read_table: read.name.clone(),
+                message: format!(
+                    "{} tables may only read from {}, but '{}' reads from '{}' ({})",
+                    rule.layer,
+                    rule.may_read_from.join(", "),
+                    reading.name,
+                    read.name,
+                    read_layers.join(", ")
+                ),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Relationship;
+    use crate::models::enums::MedallionLayer;
+
+    #[test]
+    fn test_gold_may_only_read_from_silver() {
+        let layered = |name: &str, layer| {
+            let mut table = Table::new(name.to_string(), Vec::new());
+            table.medallion_layers = vec![layer];
+            table
+        };
+        let raw = layered("raw_orders", MedallionLayer::Bronze);
+        let orders = layered("orders", MedallionLayer::Silver);
+        let revenue = layered("revenue", MedallionLayer::Gold);
+
+        let mut from_bronze = Relationship::new(raw.id, revenue.id);
+        from_bronze.relationship_type = Some(RelationshipType::DataFlow);
+        let mut from_silver = Relationship::new(orders.id, revenue.id);
+        from_silver.relationship_type = Some(RelationshipType::DataFlow);
+        // Foreign key: revenue (source) depends on raw_orders (target)
+        let fk = Relationship::new(revenue.id, raw.id);
+
+        let mut model = DataModel::new("m".to_string(), String::new(), String::new());
+        model.tables = vec![raw, orders, revenue];
+        model.relationships = vec![from_bronze.clone(), from_silver, fk.clone()];
+
+        let rules = RelationshipRules {
+            rules: vec![LayerTransitionRule {
+                name: "gold-reads-silver".to_string(),
+                layer: "gold".to_string(),
+                may_read_from: vec!["silver".to_string()],
+                description: None,
+            }],
+        };
+        let violations = rules.check_model(&model);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].relationship_id, Some(from_bronze.id));
+        assert_eq!(violations[0].reading_table, "revenue");
+        assert_eq!(violations[0].read_table, "raw_orders");
+        assert_eq!(violations[1].relationship_id, Some(fk.id));
+    }
+}
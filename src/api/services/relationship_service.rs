@@ -6,9 +6,43 @@ use crate::models::{DataModel, Relationship};
 use anyhow::Result;
 use petgraph::algo::is_cyclic_directed;
 use petgraph::graphmap::DiGraphMap;
+use std::collections::HashMap;
 use tracing::info;
 use uuid::Uuid;
 
+/// A relationship discovered by an importer, referencing tables by name.
+///
+/// Importers produce these before table ids are known; they are resolved against
+/// the model with [`RelationshipService::link_imported_relationships`].
+/// By convention `source_table` holds the foreign key and `target_table` is referenced.
+#[derive(Debug, Clone)]
+pub struct ImportedRelationship {
+    pub source_table: String,
+    pub target_table: String,
+    pub cardinality: Option<Cardinality>,
+    pub foreign_key_details: Option<ForeignKeyDetails>,
+    pub relationship_type: Option<RelationshipType>,
+    pub notes: Option<String>,
+}
+
+impl ImportedRelationship {
+    /// Create a foreign key relationship (many source rows reference one target row).
+    pub fn foreign_key(
+        source_table: impl Into<String>,
+        target_table: impl Into<String>,
+        foreign_key_details: Option<ForeignKeyDetails>,
+    ) -> Self {
+        Self {
+            source_table: source_table.into(),
+            target_table: target_table.into(),
+            cardinality: Some(Cardinality::ManyToOne),
+            foreign_key_details,
+            relationship_type: Some(RelationshipType::ForeignKey),
+            notes: None,
+        }
+    }
+}
+
 /// Service for managing relationships between tables.
 pub struct RelationshipService {
     /// Data model containing tables and relationships
@@ -60,14 +94,18 @@ impl RelationshipService {
             .map(|t| t.name.clone())
             .ok_or_else(|| anyhow::anyhow!("Target table {} not found", target_table_id))?;
 
-        // Check for self-reference
-        if source_table_id == target_table_id {
-            return Err(anyhow::anyhow!(
-                "Cannot create relationship from table to itself"
-            ));
+        if let Some(ref details) = foreign_key_details
+            && let (Some(source), Some(target)) = (
+                model.get_table_by_id(source_table_id),
+                model.get_table_by_id(target_table_id),
+            )
+        {
+            details
+                .validate(source, target)
+                .map_err(|e| anyhow::anyhow!(e))?;
         }
 
-        // Check for circular dependency (clone model to avoid borrow conflict)
+        // Check for circular dependency (self-references are intentional and allowed) (clone model to avoid borrow conflict)
         let model_clone = model.clone();
         let temp_service = RelationshipService::new(Some(model_clone));
         let (is_circular, cycle_path) =
@@ -95,6 +133,8 @@ impl RelationshipService {
             cardinality,
             source_optional: None,
             target_optional: None,
+            source_role: None,
+            target_role: None,
             foreign_key_details,
             etl_job_metadata,
             relationship_type,
@@ -143,6 +183,8 @@ impl RelationshipService {
     /// - None = field not provided, don't update
     /// - Some(None) = clear the cardinality
     /// - Some(Some(c)) = set the cardinality
+    ///
+    /// For role names, `None` leaves the role unchanged and an empty string clears it.
     #[allow(clippy::too_many_arguments)]
     pub fn update_relationship(
         &mut self,
@@ -150,6 +192,8 @@ impl RelationshipService {
         cardinality: Option<Option<Cardinality>>,
         source_optional: Option<bool>,
         target_optional: Option<bool>,
+        source_role: Option<String>,
+        target_role: Option<String>,
         foreign_key_details: Option<ForeignKeyDetails>,
         etl_job_metadata: Option<ETLJobMetadata>,
         relationship_type: Option<RelationshipType>,
@@ -160,6 +204,18 @@ impl RelationshipService {
             .as_mut()
             .ok_or_else(|| anyhow::anyhow!("No model loaded"))?;
 
+        if let Some(ref details) = foreign_key_details
+            && let Some(existing) = model.relationships.iter().find(|r| r.id == relationship_id)
+            && let (Some(source), Some(target)) = (
+                model.get_table_by_id(existing.source_table_id),
+                model.get_table_by_id(existing.target_table_id),
+            )
+        {
+            details
+                .validate(source, target)
+                .map_err(|e| anyhow::anyhow!(e))?;
+        }
+
         let relationship = model
             .relationships
             .iter_mut()
@@ -186,6 +242,12 @@ impl RelationshipService {
         if target_optional.is_some() {
             relationship.target_optional = target_optional;
         }
+        if let Some(role) = source_role {
+            relationship.source_role = (!role.is_empty()).then_some(role);
+        }
+        if let Some(role) = target_role {
+            relationship.target_role = (!role.is_empty()).then_some(role);
+        }
         if let Some(fk) = foreign_key_details {
             relationship.foreign_key_details = Some(fk);
         }
@@ -222,7 +284,92 @@ impl RelationshipService {
         }
     }
 
+    /// Resolve imported relationships by table name and add them to the model.
+    ///
+    /// Table names are matched case-insensitively. Relationships whose tables cannot be
+    /// found, that duplicate an existing relationship, or that would create a cycle are
+    /// skipped and reported as messages.
+    pub fn link_imported_relationships(
+        &mut self,
+        imported: &[ImportedRelationship],
+    ) -> Result<(Vec<Relationship>, Vec<String>)> {
+        let model = self
+            .model
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("No model loaded"))?;
+
+        let ids_by_name: HashMap<String, Uuid> = model
+            .tables
+            .iter()
+            .map(|t| (t.name.to_lowercase(), t.id))
+            .collect();
+
+        let mut created = Vec::new();
+        let mut skipped = Vec::new();
+
+        for rel in imported {
+            let (Some(&source_id), Some(&target_id)) = (
+                ids_by_name.get(&rel.source_table.to_lowercase()),
+                ids_by_name.get(&rel.target_table.to_lowercase()),
+            ) else {
+                skipped.push(format!(
+                    "Relationship {} -> {} references an unknown table",
+                    rel.source_table, rel.target_table
+                ));
+                continue;
+            };
+
+            let model = self
+                .model
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("No model loaded"))?;
+            if model
+                .relationships
+                .iter()
+                .any(|r| r.source_table_id == source_id && r.target_table_id == target_id)
+            {
+                skipped.push(format!(
+                    "Relationship {} -> {} already exists",
+                    rel.source_table, rel.target_table
+                ));
+                continue;
+            }
+
+            match self.create_relationship(
+                source_id,
+                target_id,
+                rel.cardinality,
+                rel.foreign_key_details.clone(),
+                None,
+                rel.relationship_type,
+            ) {
+                Ok(mut relationship) => {
+                    if rel.notes.is_some()
+                        && let Some(model) = self.model.as_mut()
+                        && let Some(stored) = model
+                            .relationships
+                            .iter_mut()
+                            .find(|r| r.id == relationship.id)
+                    {
+                        stored.notes = rel.notes.clone();
+                        relationship.notes = rel.notes.clone();
+                    }
+                    created.push(relationship);
+                }
+                Err(e) => skipped.push(format!(
+                    "Relationship {} -> {} skipped: {}",
+                    rel.source_table, rel.target_table, e
+                )),
+            }
+        }
+
+        Ok((created, skipped))
+    }
+
     /// Check if adding a relationship would create a circular dependency.
+    ///
+    /// Self-references (a table referencing itself, e.g. `employee.manager_id`) are
+    /// intentional hierarchies rather than cycles, so loop edges are ignored.
     pub fn check_circular_dependency(
         &self,
         source_table_id: Uuid,
@@ -233,11 +380,19 @@ impl RelationshipService {
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("No model loaded"))?;
 
+        if source_table_id == target_table_id {
+            return Ok((false, None));
+        }
+
         // Build graph from existing relationships
         let mut graph = DiGraphMap::<Uuid, ()>::new();
 
         // Add all existing relationships
-        for rel in &model.relationships {
+        for rel in model
+            .relationships
+            .iter()
+            .filter(|r| !r.is_self_reference())
+        {
             graph.add_edge(rel.source_table_id, rel.target_table_id, ());
         }
 
@@ -306,3 +461,95 @@ impl RelationshipService {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Column, Table};
+
+    fn composite_model() -> (DataModel, Uuid, Uuid) {
+        let orders = Table::new(
+            "orders".to_string(),
+            vec![
+                Column::new("id".to_string(), "BIGINT".to_string()),
+                Column::new("region".to_string(), "VARCHAR(8)".to_string()),
+            ],
+        );
+        let lines = Table::new(
+            "order_lines".to_string(),
+            vec![
+                Column::new("order_id".to_string(), "INTEGER".to_string()),
+                Column::new("order_region".to_string(), "TEXT".to_string()),
+            ],
+        );
+        let (orders_id, lines_id) = (orders.id, lines.id);
+        let mut model = DataModel::new("test".to_string(), String::new(), String::new());
+        model.tables = vec![orders, lines];
+        (model, lines_id, orders_id)
+    }
+
+    #[test]
+    fn test_composite_foreign_key_validation() {
+        let (model, lines_id, orders_id) = composite_model();
+        let mut service = RelationshipService::new(Some(model));
+        let columns = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+
+        assert!(
+            ForeignKeyDetails::from_columns(&columns(&["order_id"]), &columns(&["id", "region"]))
+                .is_err()
+        );
+
+        let swapped = ForeignKeyDetails::from_columns(
+            &columns(&["order_id", "order_region"]),
+            &columns(&["region", "id"]),
+        )
+        .unwrap();
+        let err = service
+            .create_relationship(lines_id, orders_id, None, Some(swapped), None, None)
+            .unwrap_err();
+        assert!(err.to_string().contains("type mismatch"));
+
+        let details = ForeignKeyDetails::from_columns(
+            &columns(&["order_id", "order_region"]),
+            &columns(&["id", "region"]),
+        )
+        .unwrap();
+        let relationship = service
+            .create_relationship(lines_id, orders_id, None, Some(details), None, None)
+            .unwrap();
+        let stored = relationship.foreign_key_details.unwrap();
+        assert_eq!(stored.label(), "(order_id, order_region) -> (id, region)");
+    }
+
+    #[test]
+    fn test_self_reference_is_not_a_cycle() {
+        let employees = Table::new(
+            "employees".to_string(),
+            vec![
+                Column::new("id".to_string(), "BIGINT".to_string()),
+                Column::new("manager_id".to_string(), "BIGINT".to_string()),
+            ],
+        );
+        let employees_id = employees.id;
+        let mut model = DataModel::new("test".to_string(), String::new(), String::new());
+        model.tables = vec![employees];
+        let mut service = RelationshipService::new(Some(model));
+
+        let relationship = service
+            .create_relationship(
+                employees_id,
+                employees_id,
+                Some(Cardinality::ManyToOne),
+                Some(ForeignKeyDetails::new("manager_id", "id")),
+                None,
+                Some(RelationshipType::ForeignKey),
+            )
+            .unwrap();
+        assert!(relationship.is_self_reference());
+
+        let (is_circular, _) = service
+            .check_circular_dependency(employees_id, employees_id)
+            .unwrap();
+        assert!(!is_circular);
+    }
+}
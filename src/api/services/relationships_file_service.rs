@@ -0,0 +1,416 @@
+//! The on-disk relationships file of a domain (`relationships.yaml`).
+//!
+//! The file holds every relationship of the domain under a top-level `relationships` key
+//! (a bare array is still accepted when loading). Its format is described by the JSON
+//! Schema returned by [`relationships_schema`]; [`validate_relationships_file`] checks a
+//! file against it and reports each problem with its path (e.g.
+//! `relationships[2].cardinality`) and the line it was found on, so hand edits that would
+//! otherwise be skipped on load can be fixed.
+
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::HashSet;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::services::payload_validation_service::validate_document;
+
+/// File name of a domain's relationships
+pub const RELATIONSHIPS_FILE: &str = "relationships.yaml";
+
+/// A problem found in a relationships file
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct RelationshipsFileError {
+    /// Location in the document, e.g. `relationships[2].cardinality` (empty for the whole file)
+    pub path: String,
+    /// 1-based line of the problem, when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    /// 1-based column, reported for YAML syntax errors
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+/// JSON Schema of one relationship entry
+fn relationship_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "id": {"type": "string", "format": "uuid"},
+            "source_table_id": {"type": "string", "format": "uuid"},
+            "target_table_id": {"type": "string", "format": "uuid"},
+            "source_table_name": {"type": "string", "description": "For readability; used to repair stale table ids"},
+            "target_table_name": {"type": "string", "description": "For readability; used to repair stale table ids"},
+            "cardinality": {"type": "string", "enum": ["OneToOne", "OneToMany", "ManyToOne", "ManyToMany"]},
+            "source_optional": {"type": "boolean"},
+            "target_optional": {"type": "boolean"},
+            "source_role": {"type": "string"},
+            "target_role": {"type": "string"},
+            "foreign_key_details": {
+                "type": "object",
+                "properties": {
+                    "source_column": {"type": "string"},
+                    "target_column": {"type": "string"},
+                    "column_pairs": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "source_column": {"type": "string"},
+                                "target_column": {"type": "string"}
+                            },
+                            "required": ["source_column", "target_column"],
+                            "additionalProperties": false
+                        }
+                    }
+                },
+                "required": ["source_column", "target_column"],
+                "additionalProperties": false
+            },
+            "etl_job_metadata": {
+                "type": "object",
+                "properties": {
+                    "job_name": {"type": "string"},
+                    "notes": {"type": "string"},
+                    "frequency": {"type": "string"}
+                },
+                "required": ["job_name"],
+                "additionalProperties": false
+            },
+            "relationship_type": {"type": "string", "enum": ["DataFlow", "Dependency", "ForeignKey", "EtlTransformation"]},
+            "notes": {"type": "string"},
+            "created_at": {"type": "string", "format": "date-time"},
+            "updated_at": {"type": "string", "format": "date-time"}
+        },
+        "required": ["id", "source_table_id", "target_table_id"],
+        "additionalProperties": false
+    })
+}
+
+/// JSON Schema (draft 2020-12) of `relationships.yaml`.
+pub fn relationships_schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "relationships.yaml",
+        "description": "Relationships between the tables of a domain",
+        "type": "object",
+        "properties": {
+            "relationships": {
+                "type": "array",
+                "items": relationship_schema()
+            }
+        },
+        "required": ["relationships"],
+        "additionalProperties": false
+    })
+}
+
+/// Number of relationships in a relationships file (0 if it cannot be read).
+pub fn count_relationships(content: &str) -> usize {
+    serde_yaml::from_str::<serde_yaml::Value>(content)
+        .ok()
+        .and_then(|data| {
+            data.get("relationships")
+                .and_then(|v| v.as_sequence())
+                .or_else(|| data.as_sequence())
+                .map(Vec::len)
+        })
+        .unwrap_or(0)
+}
+
+/// 0-based line index of each entry of the relationships array.
+fn entry_lines(lines: &[&str]) -> Vec<usize> {
+    let start = lines
+        .iter()
+        .position(|l| l.starts_with("relationships:"))
+        .map_or(0, |i| i + 1);
+    let mut indent = None;
+    let mut entries = Vec::new();
+    for (i, line) in lines.iter().enumerate().skip(start) {
+        let trimmed = line.trim_start();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let line_indent = line.len() - trimmed.len();
+        let is_item = trimmed == "-" || trimmed.starts_with("- ");
+        match indent {
+            None if is_item => indent = Some(line_indent),
+            None => break,
+            Some(n) if line_indent < n || (line_indent == n && !is_item) => break,
+            Some(_) => {}
+        }
+        if is_item && indent == Some(line_indent) {
+            entries.push(i);
+        }
+    }
+    entries
+}
+
+/// 1-based line of the innermost key of `path` found within lines `[from, to)`, or of the
+/// entry itself when none is (e.g. for missing fields).
+fn path_line(lines: &[&str], from: usize, to: usize, path: &str) -> usize {
+    let keys: Vec<&str> = path.split('.').skip(1).collect();
+    keys.iter()
+        .rev()
+        .map(|segment| format!("{}:", segment.split('[').next().unwrap_or(segment)))
+        .find_map(|prefix| {
+            (from..to.min(lines.len())).find(|&i| {
+                lines[i]
+                    .trim_start()
+                    .trim_start_matches("- ")
+                    .starts_with(&prefix)
+            })
+        })
+        .unwrap_or(from)
+        + 1
+}
+
+/// Validate `schema` against `value`, recursing into nested objects and arrays of objects.
+fn validate_value(schema: &Value, value: &Value, path: &str, errors: &mut Vec<(String, String)>) {
+    for error in validate_document(schema, 0, value) {
+        let field_path = match &error.field {
+            Some(field) => format!("{}.{}", path, field),
+            None => path.to_string(),
+        };
+        errors.push((field_path, error.message));
+    }
+    let Some(object) = value.as_object() else {
+        return;
+    };
+    for (field, value) in object {
+        let Some(property) = schema.get("properties").and_then(|p| p.get(field)) else {
+            continue;
+        };
+        let field_path = format!("{}.{}", path, field);
+        match (property.get("type").and_then(Value::as_str), value) {
+            (Some("object"), Value::Object(_)) => {
+                validate_value(property, value, &field_path, errors)
+            }
+            (Some("array"), Value::Array(items)) => {
+                if let Some(item_schema) = property.get("items") {
+                    for (i, item) in items.iter().enumerate() {
+                        let item_path = format!("{}[{}]", field_path, i);
+                        validate_value(item_schema, item, &item_path, errors);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl RelationshipsFileError {
+    fn new(path: impl Into<String>, line: Option<usize>, message: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            line,
+            column: None,
+            message: message.into(),
+        }
+    }
+}
+
+impl std::fmt::Display for RelationshipsFileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(line) = self.line {
+            write!(f, "line {}: ", line)?;
+        }
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+/// Check a relationships file against [`relationships_schema`].
+///
+/// With `table_ids`, table ids that are not among them are reported as well.
+pub fn validate_relationships_file(
+    content: &str,
+    table_ids: Option<&HashSet<Uuid>>,
+) -> Vec<RelationshipsFileError> {
+    let data = match serde_yaml::from_str::<serde_yaml::Value>(content) {
+        Ok(data) => data,
+        Err(e) => {
+            let location = e.location();
+            return vec![RelationshipsFileError {
+                path: String::new(),
+                line: location.as_ref().map(|l| l.line()),
+                column: location.as_ref().map(|l| l.column()),
+                message: format!("Invalid YAML: {}", e),
+            }];
+        }
+    };
+    let data = match serde_json::to_value(&data) {
+        Ok(data) => data,
+        Err(e) => {
+            return vec![RelationshipsFileError::new(
+                "",
+                None,
+                format!("Unsupported YAML: {}", e),
+            )];
+        }
+    };
+
+    match &data {
+        // An empty file has no relationships
+        Value::Null => Vec::new(),
+        // Bare array of relationships (older files)
+        Value::Array(entries) => validate_entries(content, entries, table_ids),
+        Value::Object(object) => {
+            let mut errors: Vec<_> = object
+                .keys()
+                .filter(|key| *key != "relationships")
+                .map(|key| {
+                    let line = content
+                        .lines()
+                        .position(|l| l.starts_with(&format!("{}:", key)))
+                        .map(|i| i + 1);
+                    RelationshipsFileError::new(key.as_str(), line, "Field is not in the schema")
+                })
+                .collect();
+            match object.get("relationships") {
+                Some(Value::Array(entries)) => {
+                    errors.extend(validate_entries(content, entries, table_ids))
+                }
+                Some(Value::Null) => {}
+                Some(_) => errors.push(RelationshipsFileError::new(
+                    "relationships",
+                    None,
+                    "Expected an array of relationships",
+                )),
+                None => errors.push(RelationshipsFileError::new(
+                    "relationships",
+                    None,
+                    "Required field is missing",
+                )),
+            }
+            errors
+        }
+        _ => vec![RelationshipsFileError::new(
+            "",
+            None,
+            "Expected a mapping with a 'relationships' array",
+        )],
+    }
+}
+
+fn validate_entries(
+    content: &str,
+    entries: &[Value],
+    table_ids: Option<&HashSet<Uuid>>,
+) -> Vec<RelationshipsFileError> {
+    let lines: Vec<&str> = content.lines().collect();
+    let starts = entry_lines(&lines);
+    let schema = relationship_schema();
+    let mut ids = HashSet::new();
+    let mut errors = Vec::new();
+
+    for (i, entry) in entries.iter().enumerate() {
+        let path = format!("relationships[{}]", i);
+        let mut entry_errors = Vec::new();
+        validate_value(&schema, entry, &path, &mut entry_errors);
+        if let Some(id) = entry.get("id").and_then(Value::as_str)
+            && !ids.insert(id)
+        {
+            entry_errors.push((
+                format!("{}.id", path),
+                format!("Duplicate relationship id '{}'", id),
+            ));
+        }
+        if let Some(table_ids) = table_ids {
+            for field in ["source_table_id", "target_table_id"] {
+                if let Some(table_id) = entry
+                    .get(field)
+                    .and_then(Value::as_str)
+                    .and_then(|id| Uuid::parse_str(id).ok())
+                    && !table_ids.contains(&table_id)
+                {
+                    entry_errors.push((
+                        format!("{}.{}", path, field),
+                        format!("Table {} does not exist in the domain", table_id),
+                    ));
+                }
+            }
+        }
+
+        let from = starts.get(i).copied();
+        let to = starts.get(i + 1).copied().unwrap_or(lines.len());
+        for (error_path, message) in entry_errors {
+            let line = from.map(|from| path_line(&lines, from, to, &error_path));
+            errors.push(RelationshipsFileError::new(error_path, line, message));
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VALID: &str = "relationships:
+- id: 6a1b5c1e-1111-4a4a-9a9a-000000000001
+  source_table_id: 6a1b5c1e-1111-4a4a-9a9a-000000000002
+  target_table_id: 6a1b5c1e-1111-4a4a-9a9a-000000000003
+  source_table_name: orders
+  cardinality: ManyToOne
+  foreign_key_details:
+    source_column: customer_id
+    target_column: id
+  created_at: 2025-01-01T00:00:00+00:00
+";
+
+    #[test]
+    fn test_valid_file_and_count() {
+        assert!(validate_relationships_file(VALID, None).is_empty());
+        assert!(validate_relationships_file("", None).is_empty());
+        assert_eq!(count_relationships(VALID), 1);
+        assert_eq!(count_relationships("not: [valid"), 0);
+    }
+
+    #[test]
+    fn test_unknown_tables() {
+        let known: HashSet<Uuid> =
+            HashSet::from([Uuid::parse_str("6a1b5c1e-1111-4a4a-9a9a-000000000002").unwrap()]);
+        let errors = validate_relationships_file(VALID, Some(&known));
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path, "relationships[0].target_table_id");
+        assert_eq!(errors[0].line, Some(4));
+    }
+
+    #[test]
+    fn test_errors_have_paths_and_lines() {
+        let content = format!(
+            "{}- id: 6a1b5c1e-1111-4a4a-9a9a-000000000001
+  source_table_id: not-a-uuid
+  cardinality: SomeToMany
+  foreign_key_details:
+    source_column: customer_id
+  colour: red
+",
+            VALID
+        );
+        let errors = validate_relationships_file(&content, None);
+        let found: Vec<(&str, Option<usize>)> =
+            errors.iter().map(|e| (e.path.as_str(), e.line)).collect();
+        assert!(found.contains(&("relationships[1].target_table_id", Some(11))));
+        assert!(found.contains(&("relationships[1].source_table_id", Some(12))));
+        assert!(found.contains(&("relationships[1].cardinality", Some(13))));
+        assert!(found.contains(&(
+            "relationships[1].foreign_key_details.target_column",
+            Some(14)
+        )));
+        assert!(found.contains(&("relationships[1].colour", Some(16))));
+        assert!(found.contains(&("relationships[1].id", Some(11))));
+        assert_eq!(errors.len(), 6);
+    }
+
+    #[test]
+    fn test_yaml_syntax_error_location() {
+        let errors = validate_relationships_file("relationships:\n- id: [unclosed\n", None);
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].line.is_some());
+        assert!(errors[0].message.starts_with("Invalid YAML"));
+    }
+}
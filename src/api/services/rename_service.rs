@@ -0,0 +1,289 @@
+//! Table and column rename refactoring.
+//!
+//! Renaming a column rewrites everything that refers to it by name: relationship foreign key
+//! details (including lineage relationships) and column-level foreign keys, in the table's own
+//! domain and in other domains that import the table. Tables are referenced by id, so a table
+//! rename only moves its files (`tables/<name>.yaml`, `tables/<name>.md`).
+//!
+//! Old names are kept as aliases (`Table::aliases` for tables), so imports that still use them
+//! can be mapped onto the renamed table or column. ODCS YAML has no place for them, so in file
+//! mode they are stored in the domain's `aliases.yaml`.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use super::table_readme_service::readme_path;
+use crate::models::{Relationship, Table};
+
+/// File name of a domain's alias index
+pub const ALIASES_FILE: &str = "aliases.yaml";
+
+/// Previous names of a domain's tables and columns (`aliases.yaml`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AliasIndex {
+    /// Old table names by table id
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tables: BTreeMap<Uuid, Vec<String>>,
+    /// Old column names by table id and current column name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub columns: BTreeMap<Uuid, BTreeMap<String, Vec<String>>>,
+}
+
+fn push_alias(aliases: &mut Vec<String>, name: &str, current: &str) {
+    aliases.retain(|a| !a.eq_ignore_ascii_case(current));
+    if !name.eq_ignore_ascii_case(current) && !aliases.iter().any(|a| a == name) {
+        aliases.push(name.to_string());
+    }
+}
+
+impl AliasIndex {
+    fn path(domain_dir: &Path) -> PathBuf {
+        domain_dir.join(ALIASES_FILE)
+    }
+
+    /// Load a domain's alias index (empty if it has none).
+    pub fn load(domain_dir: &Path) -> Self {
+        std::fs::read_to_string(Self::path(domain_dir))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, domain_dir: &Path) -> Result<()> {
+        let path = Self::path(domain_dir);
+        std::fs::create_dir_all(domain_dir)
+            .with_context(|| format!("Failed to create {}", domain_dir.display()))?;
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Make the entry of `table` match its aliases; returns true if the index changed.
+    pub fn set_table_aliases(&mut self, table: &Table) -> bool {
+        if table.aliases.is_empty() {
+            return self.tables.remove(&table.id).is_some();
+        }
+        if self.tables.get(&table.id) == Some(&table.aliases) {
+            return false;
+        }
+        self.tables.insert(table.id, table.aliases.clone());
+        true
+    }
+
+    /// Fill in the aliases of tables loaded from YAML.
+    pub fn apply_table_aliases(&self, tables: &mut [Table]) {
+        for table in tables {
+            if let Some(aliases) = self.tables.get(&table.id) {
+                table.aliases = aliases.clone();
+            }
+        }
+    }
+
+    /// Record that a column was renamed; aliases of the old name carry over.
+    pub fn record_column_rename(&mut self, table_id: Uuid, old_name: &str, new_name: &str) {
+        let columns = self.columns.entry(table_id).or_default();
+        let mut aliases = columns.remove(old_name).unwrap_or_default();
+        aliases.extend(columns.remove(new_name).unwrap_or_default());
+        push_alias(&mut aliases, old_name, new_name);
+        columns.insert(new_name.to_string(), aliases);
+    }
+}
+
+/// Store a saved table's aliases in its domain's `aliases.yaml`.
+pub fn sync_table_aliases(domain_dir: &Path, table: &Table) -> Result<()> {
+    // Without aliases or an index there is no entry to add or remove
+    if table.aliases.is_empty() && !AliasIndex::path(domain_dir).exists() {
+        return Ok(());
+    }
+    let mut index = AliasIndex::load(domain_dir);
+    if index.set_table_aliases(table) {
+        index.save(domain_dir)?;
+    }
+    Ok(())
+}
+
+/// What a column rename rewrote
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ColumnRenameChanges {
+    /// Tables whose columns changed (the renamed table and tables with foreign keys to it)
+    pub tables: Vec<Uuid>,
+    /// Relationships whose foreign key details changed
+    pub relationships: Vec<Uuid>,
+}
+
+impl ColumnRenameChanges {
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty() && self.relationships.is_empty()
+    }
+}
+
+fn rename(name: &mut String, old_name: &str, new_name: &str) -> bool {
+    if name.eq_ignore_ascii_case(old_name) {
+        *name = new_name.to_string();
+        true
+    } else {
+        false
+    }
+}
+
+/// Rename column `old_name` of table `table_id` and rewrite every reference to it in
+/// `tables` and `relationships`. The renamed table itself may be absent (other domains).
+pub fn apply_column_rename(
+    tables: &mut [Table],
+    relationships: &mut [Relationship],
+    table_id: Uuid,
+    old_name: &str,
+    new_name: &str,
+) -> ColumnRenameChanges {
+    let mut changes = ColumnRenameChanges::default();
+    let table_id_str = table_id.to_string();
+
+    for table in tables.iter_mut() {
+        let mut changed = false;
+        for column in &mut table.columns {
+            if table.id == table_id {
+                changed |= rename(&mut column.name, old_name, new_name);
+            }
+            if let Some(fk) = column.foreign_key.as_mut()
+                && fk.table_id == table_id_str
+            {
+                changed |= rename(&mut fk.column_name, old_name, new_name);
+            }
+        }
+        if changed {
+            table.updated_at = chrono::Utc::now();
+            changes.tables.push(table.id);
+        }
+    }
+
+    for relationship in relationships.iter_mut() {
+        let is_source = relationship.source_table_id == table_id;
+        let is_target = relationship.target_table_id == table_id;
+        let Some(details) = relationship.foreign_key_details.as_mut() else {
+            continue;
+        };
+        let mut changed = false;
+        if is_source {
+            changed |= rename(&mut details.source_column, old_name, new_name);
+            for pair in &mut details.column_pairs {
+                changed |= rename(&mut pair.source_column, old_name, new_name);
+            }
+        }
+        if is_target {
+            changed |= rename(&mut details.target_column, old_name, new_name);
+            for pair in &mut details.column_pairs {
+                changed |= rename(&mut pair.target_column, old_name, new_name);
+            }
+        }
+        if changed {
+            relationship.updated_at = chrono::Utc::now();
+            changes.relationships.push(relationship.id);
+        }
+    }
+
+    changes
+}
+
+/// Move a renamed table's README and remove its YAML under the old name
+/// (the YAML under the new name is written when the table is saved).
+pub fn move_table_files(domain_dir: &Path, old_name: &str, new_name: &str) -> Result<()> {
+    let old_readme = readme_path(domain_dir, old_name);
+    if old_readme.exists() {
+        let new_readme = readme_path(domain_dir, new_name);
+        std::fs::rename(&old_readme, &new_readme)
+            .with_context(|| format!("Failed to move {}", old_readme.display()))?;
+    }
+    for ext in ["yaml", "yml"] {
+        let old_yaml = domain_dir
+            .join("tables")
+            .join(format!("{}.{}", old_name, ext));
+        if old_yaml.exists() {
+            std::fs::remove_file(&old_yaml)
+                .with_context(|| format!("Failed to remove {}", old_yaml.display()))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Column;
+    use crate::models::column::ForeignKey;
+    use crate::models::relationship::ForeignKeyDetails;
+
+    #[test]
+    fn test_column_rename_rewrites_references() {
+        let customers = Table::new(
+            "customers".to_string(),
+            vec![Column::new("id".to_string(), "INT".to_string())],
+        );
+        let mut customer_id = Column::new("customer_id".to_string(), "INT".to_string());
+        customer_id.foreign_key = Some(ForeignKey {
+            table_id: customers.id.to_string(),
+            column_name: "id".to_string(),
+            constraint_name: None,
+            key_position: None,
+        });
+        let orders = Table::new("orders".to_string(), vec![customer_id]);
+        let mut fk = Relationship::new(orders.id, customers.id);
+        fk.foreign_key_details = Some(ForeignKeyDetails::new("customer_id", "id"));
+        let unrelated = Relationship::new(customers.id, orders.id);
+
+        let mut tables = vec![customers.clone(), orders.clone()];
+        let mut relationships = vec![fk.clone(), unrelated];
+        let changes = apply_column_rename(
+            &mut tables,
+            &mut relationships,
+            customers.id,
+            "id",
+            "customer_key",
+        );
+
+        assert_eq!(changes.tables, vec![customers.id, orders.id]);
+        assert_eq!(changes.relationships, vec![fk.id]);
+        assert_eq!(tables[0].columns[0].name, "customer_key");
+        assert_eq!(
+            tables[1].columns[0]
+                .foreign_key
+                .as_ref()
+                .unwrap()
+                .column_name,
+            "customer_key"
+        );
+        let details = relationships[0].foreign_key_details.as_ref().unwrap();
+        assert_eq!(details.source_column, "customer_id");
+        assert_eq!(details.target_column, "customer_key");
+    }
+
+    #[test]
+    fn test_alias_index_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut table = Table::new("cust".to_string(), Vec::new());
+        let table_id = table.id;
+        table.rename("customers");
+        // Renaming back to an old name drops it from the aliases
+        table.rename("cust");
+        sync_table_aliases(dir.path(), &table).unwrap();
+        let mut index = AliasIndex::load(dir.path());
+        index.record_column_rename(table_id, "cust_id", "customer_id");
+        index.record_column_rename(table_id, "customer_id", "id");
+        index.save(dir.path()).unwrap();
+
+        let loaded = AliasIndex::load(dir.path());
+        assert_eq!(loaded.tables[&table_id], vec!["customers".to_string()]);
+        let mut reloaded = vec![Table {
+            aliases: Vec::new(),
+            ..table.clone()
+        }];
+        loaded.apply_table_aliases(&mut reloaded);
+        assert_eq!(reloaded[0].aliases, table.aliases);
+        assert_eq!(
+            loaded.columns[&table_id]["id"],
+            vec!["cust_id".to_string(), "customer_id".to_string()]
+        );
+        assert_eq!(loaded, index);
+    }
+}
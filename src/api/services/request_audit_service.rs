@@ -0,0 +1,409 @@
+//! Audit-grade request log.
+//!
+//! Records mutating API calls (POST/PUT/PATCH/DELETE) as JSON lines in a dedicated sink,
+//! separate from tracing output. One file is written per UTC day
+//! (`requests-YYYY-MM-DD.jsonl`). Request bodies are redacted before they are written:
+//! credentials (passwords, tokens, one-time codes, secrets, API keys, webhook URLs) and
+//! configured fields are replaced and email addresses are pseudonymized with a SHA-256 digest,
+//! so entries stay correlatable without storing secrets or PII in plaintext.
+//!
+//! Configuration (environment variables):
+//! - `REQUEST_AUDIT_LOG_DIR`: directory for the log files; the sink is disabled when unset
+//! - `REQUEST_AUDIT_REDACT_FIELDS`: comma-separated JSON keys to redact in addition to
+//!   credentials (default: description,notes,comment,email,emails,user_email)
+//! - `REQUEST_AUDIT_REDACT_EMAILS` (default true): pseudonymize email addresses, including the user
+//! - `REQUEST_AUDIT_RETENTION_DAYS`: delete daily files older than this many days
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+use tracing::warn;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Keys always redacted as credentials, e.g. `code` of `/auth/exchange`
+const CREDENTIAL_KEYS: &[&str] = &[
+    "code",
+    "api_key",
+    "apikey",
+    "access_key_id",
+    "private_key",
+    "authorization",
+];
+
+/// Key fragments always redacted as credentials, e.g. `refresh_token`, `password`,
+/// `secret_access_key` or `slack_webhook_url`
+const CREDENTIAL_KEY_PARTS: &[&str] = &["password", "passphrase", "secret", "token", "webhook"];
+
+/// Whether a JSON key holds a credential
+fn is_credential_key(key: &str) -> bool {
+    CREDENTIAL_KEYS.contains(&key) || CREDENTIAL_KEY_PARTS.iter().any(|part| key.contains(part))
+}
+
+/// One audited API call.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RequestAuditEntry {
+    pub id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub duration_ms: u64,
+    /// Authenticated user (pseudonymized when email redaction is on)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    /// Domain name from the request path
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub domain: Option<String>,
+    /// UUIDs found in the request path (tables, relationships, ...)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub object_ids: Vec<String>,
+    /// Redacted JSON request body
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    /// Why the body was not recorded (non-JSON, too large)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body_omitted: Option<String>,
+    /// Policy event recorded by a handler rather than the middleware
+    /// (e.g. `relationship_rule_bypass`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+}
+
+/// Filter for querying the log.
+#[derive(Debug, Clone, Default)]
+pub struct RequestAuditFilter {
+    pub date: Option<NaiveDate>,
+    pub user: Option<String>,
+    pub domain: Option<String>,
+    pub method: Option<String>,
+    pub limit: usize,
+}
+
+/// Redaction rules applied to users and request bodies.
+#[derive(Debug, Clone)]
+pub struct RedactionConfig {
+    /// Keys redacted in addition to credentials
+    pub fields: HashSet<String>,
+    pub redact_emails: bool,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            fields: [
+                "description",
+                "notes",
+                "comment",
+                "email",
+                "emails",
+                "user_email",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+            redact_emails: true,
+        }
+    }
+}
+
+fn email_regex() -> &'static Regex {
+    static EMAIL_RE: OnceLock<Regex> = OnceLock::new();
+    EMAIL_RE.get_or_init(|| Regex::new(r"[A-Za-z0-9._%+-]+@[A-Za-z0-9.-]+\.[A-Za-z]{2,}").unwrap())
+}
+
+impl RedactionConfig {
+    /// Stable pseudonym for an email address.
+    pub fn pseudonymize(&self, email: &str) -> String {
+        if !self.redact_emails {
+            return email.to_string();
+        }
+        let digest = Sha256::digest(email.trim().to_lowercase().as_bytes());
+        format!("sha256:{:x}", digest)[..23].to_string()
+    }
+
+    /// Redact a JSON value in place.
+    pub fn redact(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, v) in map.iter_mut() {
+                    let key = key.to_lowercase();
+                    if is_credential_key(&key) || self.fields.contains(&key) {
+                        *v = Value::String(REDACTED.to_string());
+                    } else {
+                        self.redact(v);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|v| self.redact(v)),
+            Value::String(s) if self.redact_emails && email_regex().is_match(s) => {
+                *s = email_regex()
+                    .replace_all(s, |caps: &regex::Captures| self.pseudonymize(&caps[0]))
+                    .to_string();
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Daily-rotated JSON-lines audit sink.
+pub struct RequestAuditLog {
+    dir: Option<PathBuf>,
+    retention_days: Option<i64>,
+    pub redaction: RedactionConfig,
+    current: Mutex<Option<(NaiveDate, File)>>,
+}
+
+impl RequestAuditLog {
+    /// Create a sink writing to `dir` (None disables it).
+    pub fn new(
+        dir: Option<PathBuf>,
+        redaction: RedactionConfig,
+        retention_days: Option<i64>,
+    ) -> Self {
+        Self {
+            dir,
+            retention_days,
+            redaction,
+            current: Mutex::new(None),
+        }
+    }
+
+    /// Create the sink from environment variables.
+    pub fn from_env() -> Self {
+        let mut redaction = RedactionConfig::default();
+        if let Ok(fields) = std::env::var("REQUEST_AUDIT_REDACT_FIELDS") {
+            redaction.fields = fields
+                .split(',')
+                .map(|f| f.trim().to_lowercase())
+                .filter(|f| !f.is_empty())
+                .collect();
+        }
+        if let Ok(v) = std::env::var("REQUEST_AUDIT_REDACT_EMAILS") {
+            redaction.redact_emails =
+                !matches!(v.to_lowercase().as_str(), "0" | "false" | "no" | "off");
+        }
+        Self::new(
+            std::env::var("REQUEST_AUDIT_LOG_DIR")
+                .ok()
+                .map(PathBuf::from),
+            redaction,
+            std::env::var("REQUEST_AUDIT_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok()),
+        )
+    }
+
+    /// True if entries are being recorded.
+    pub fn is_enabled(&self) -> bool {
+        self.dir.is_some()
+    }
+
+    fn file_path(&self, date: NaiveDate) -> Option<PathBuf> {
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(format!("requests-{}.jsonl", date.format("%Y-%m-%d"))))
+    }
+
+    /// Append an entry, rotating to a new file when the UTC day changes.
+    pub fn record(&self, entry: &RequestAuditEntry) -> Result<()> {
+        let Some(dir) = self.dir.as_ref() else {
+            return Ok(());
+        };
+        let date = entry.timestamp.date_naive();
+        let line = serde_json::to_string(entry)?;
+
+        let mut current = self.current.lock().unwrap_or_else(|e| e.into_inner());
+        if current.as_ref().map(|(d, _)| *d) != Some(date) {
+            std::fs::create_dir_all(dir).context("Failed to create audit log directory")?;
+            let path = self.file_path(date).context("Audit log disabled")?;
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .with_context(|| format!("Failed to open audit log {}", path.display()))?;
+            *current = Some((date, file));
+            self.prune(date);
+        }
+        if let Some((_, file)) = current.as_mut() {
+            writeln!(file, "{}", line)?;
+            file.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Delete daily files older than the retention period.
+    fn prune(&self, today: NaiveDate) {
+        let (Some(dir), Some(days)) = (self.dir.as_ref(), self.retention_days) else {
+            return;
+        };
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if let Some(date) = name
+                .strip_prefix("requests-")
+                .and_then(|n| n.strip_suffix(".jsonl"))
+                .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+                && (today - date).num_days() > days
+                && let Err(e) = std::fs::remove_file(entry.path())
+            {
+                warn!("Failed to delete expired audit log {}: {}", name, e);
+            }
+        }
+    }
+
+    /// Read entries for one day, newest first.
+    pub fn query(&self, filter: &RequestAuditFilter) -> Result<Vec<RequestAuditEntry>> {
+        let date = filter.date.unwrap_or_else(|| Utc::now().date_naive());
+        let Some(path) = self.file_path(date) else {
+            return Ok(Vec::new());
+        };
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let user = filter
+            .user
+            .as_deref()
+            .map(|u| self.redaction.pseudonymize(u));
+
+        let reader = BufReader::new(File::open(&path)?);
+        let mut entries: Vec<RequestAuditEntry> = reader
+            .lines()
+            .map_while(|l| l.ok())
+            .filter_map(|l| serde_json::from_str::<RequestAuditEntry>(&l).ok())
+            .filter(|e| user.is_none() || e.user == user)
+            .filter(|e| filter.domain.is_none() || e.domain == filter.domain)
+            .filter(|e| {
+                filter
+                    .method
+                    .as_deref()
+                    .is_none_or(|m| e.method.eq_ignore_ascii_case(m))
+            })
+            .collect();
+        entries.reverse();
+        entries.truncate(filter.limit.max(1));
+        Ok(entries)
+    }
+}
+
+/// Extract the domain name and object UUIDs from a request path.
+pub fn path_context(path: &str) -> (Option<String>, Vec<String>) {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let domain = segments
+        .iter()
+        .position(|s| *s == "domains")
+        .and_then(|i| segments.get(i + 1))
+        .map(|s| s.to_string());
+    let object_ids = segments
+        .iter()
+        .filter(|s| Uuid::parse_str(s).is_ok())
+        .map(|s| s.to_string())
+        .collect();
+    (domain, object_ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_redacts_fields_and_emails() {
+        let redaction = RedactionConfig::default();
+        let mut body = json!({
+            "name": "orders",
+            "description": "Customer orders",
+            "owner": "Contact jane@example.com",
+            "columns": [{"name": "id", "notes": "pk"}]
+        });
+        redaction.redact(&mut body);
+        assert_eq!(body["name"], "orders");
+        assert_eq!(body["description"], REDACTED);
+        assert_eq!(body["columns"][0]["notes"], REDACTED);
+        let owner = body["owner"].as_str().unwrap();
+        assert!(!owner.contains("jane@example.com"));
+        assert!(owner.contains(&redaction.pseudonymize("jane@example.com")));
+    }
+
+    #[test]
+    fn test_always_redacts_credentials() {
+        // Credentials are redacted even with no configured fields
+        let redaction = RedactionConfig {
+            fields: HashSet::new(),
+            redact_emails: false,
+        };
+        let mut bodies = [
+            json!({"refresh_token": "rt-123"}),
+            json!({"code": "one-time", "email": "a@b.c"}),
+            json!({"password": "hunter2", "expires_in_days": 7}),
+            json!({"slack_webhook_url": "https://hooks.slack.com/services/T/B/x"}),
+            json!({"target": {"type": "git"}, "credentials": {"username": "bot", "token": "t", "secret_access_key": "k"}}),
+        ];
+        for body in &mut bodies {
+            redaction.redact(body);
+        }
+        assert_eq!(bodies[0]["refresh_token"], REDACTED);
+        assert_eq!(bodies[1]["code"], REDACTED);
+        assert_eq!(bodies[1]["email"], "a@b.c");
+        assert_eq!(bodies[2]["password"], REDACTED);
+        assert_eq!(bodies[2]["expires_in_days"], 7);
+        assert_eq!(bodies[3]["slack_webhook_url"], REDACTED);
+        assert_eq!(bodies[4]["credentials"]["username"], "bot");
+        assert_eq!(bodies[4]["credentials"]["token"], REDACTED);
+        assert_eq!(bodies[4]["credentials"]["secret_access_key"], REDACTED);
+        assert_eq!(bodies[4]["target"]["type"], "git");
+    }
+
+    #[test]
+    fn test_record_and_query_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = RequestAuditLog::new(
+            Some(dir.path().to_path_buf()),
+            RedactionConfig::default(),
+            None,
+        );
+        let table_id = Uuid::new_v4();
+        let (domain, object_ids) = path_context(&format!(
+            "/api/v1/workspace/domains/sales/tables/{}",
+            table_id
+        ));
+        let entry = RequestAuditEntry {
+            id: Uuid::new_v4(),
+            timestamp: Utc::now(),
+            method: "PUT".to_string(),
+            path: "/api/v1/workspace/domains/sales/tables".to_string(),
+            status: 200,
+            duration_ms: 3,
+            user: Some(log.redaction.pseudonymize("jane@example.com")),
+            domain,
+            object_ids,
+            body: None,
+            body_omitted: None,
+            event: None,
+        };
+        log.record(&entry).unwrap();
+
+        let found = log
+            .query(&RequestAuditFilter {
+                user: Some("jane@example.com".to_string()),
+                domain: Some("sales".to_string()),
+                limit: 10,
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].object_ids, vec![table_id.to_string()]);
+    }
+}
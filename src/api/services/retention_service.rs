@@ -0,0 +1,279 @@
+//! Retention and archival metadata.
+//!
+//! Each table can declare how long its data is kept, where it is archived when the period
+//! ends and whether it is under legal hold. Policies are stored per domain in
+//! `retention.yaml`, keyed by table id. The purge plan groups a domain's tables by retention
+//! class and says what happens to each at the end of its period, for records management.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::Table;
+
+/// File name of a domain's retention policies
+pub const RETENTION_FILE: &str = "retention.yaml";
+
+/// Class of tables without a declared retention period
+const UNCLASSIFIED: &str = "unclassified";
+
+/// How long a table's data is kept and what happens to it afterwards
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct RetentionPolicy {
+    /// Retention period in days; kept indefinitely if omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention_days: Option<u32>,
+    /// Records-management class the table belongs to (e.g. "financial-7y"); the period is
+    /// used as the class if omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retention_class: Option<String>,
+    /// Where expired data is archived (e.g. "s3://archive/orders"); purged if omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archival_target: Option<String>,
+    /// Data under legal hold must not be purged or archived
+    #[serde(default)]
+    pub legal_hold: bool,
+}
+
+impl RetentionPolicy {
+    /// Check the period and names are usable.
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        if self.retention_days == Some(0) {
+            return Err("Retention period must be at least one day".to_string());
+        }
+        if self
+            .retention_class
+            .as_deref()
+            .is_some_and(|c| c.trim().is_empty())
+        {
+            return Err("Retention class must not be empty".to_string());
+        }
+        if self
+            .archival_target
+            .as_deref()
+            .is_some_and(|t| t.trim().is_empty())
+        {
+            return Err("Archival target must not be empty".to_string());
+        }
+        Ok(())
+    }
+
+    /// Class the table is grouped under in the purge plan
+    fn class(&self) -> String {
+        match (&self.retention_class, self.retention_days) {
+            (Some(class), _) => class.clone(),
+            (None, Some(days)) => format!("{} days", days),
+            (None, None) => UNCLASSIFIED.to_string(),
+        }
+    }
+
+    /// What happens to the table's data at the end of its retention period
+    fn action(&self) -> PurgeAction {
+        if self.legal_hold {
+            PurgeAction::Hold
+        } else if self.retention_days.is_none() {
+            PurgeAction::Retain
+        } else if self.archival_target.is_some() {
+            PurgeAction::Archive
+        } else {
+            PurgeAction::Purge
+        }
+    }
+}
+
+/// Declared retention policies of a domain's tables (`retention.yaml`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RetentionPolicies {
+    #[serde(default)]
+    pub tables: BTreeMap<Uuid, RetentionPolicy>,
+}
+
+impl RetentionPolicies {
+    fn path(domain_dir: &Path) -> PathBuf {
+        domain_dir.join(RETENTION_FILE)
+    }
+
+    /// Load a domain's policies (none declared if the file is missing).
+    pub fn load(domain_dir: &Path) -> Self {
+        std::fs::read_to_string(Self::path(domain_dir))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, domain_dir: &Path) -> Result<()> {
+        let path = Self::path(domain_dir);
+        std::fs::create_dir_all(domain_dir)
+            .with_context(|| format!("Failed to create {}", domain_dir.display()))?;
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn get(&self, table_id: Uuid) -> RetentionPolicy {
+        self.tables.get(&table_id).cloned().unwrap_or_default()
+    }
+
+    /// Declare a table's policy; an empty policy removes the declaration.
+    pub fn set(&mut self, table_id: Uuid, policy: RetentionPolicy) {
+        if policy == RetentionPolicy::default() {
+            self.tables.remove(&table_id);
+        } else {
+            self.tables.insert(table_id, policy);
+        }
+    }
+}
+
+/// What happens to a table's data at the end of its retention period
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PurgeAction {
+    /// Moved to the archival target
+    Archive,
+    /// Deleted
+    Purge,
+    /// Kept: under legal hold
+    Hold,
+    /// Kept: no retention period declared
+    Retain,
+}
+
+/// A table in the purge plan
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PurgePlanTable {
+    pub table_id: Uuid,
+    pub table_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retention_days: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archival_target: Option<String>,
+    pub legal_hold: bool,
+    pub action: PurgeAction,
+}
+
+/// Tables sharing a retention class
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct RetentionClassPlan {
+    pub retention_class: String,
+    pub tables: Vec<PurgePlanTable>,
+}
+
+/// Purge and archival plan of a domain
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct PurgePlan {
+    pub domain: String,
+    pub generated_at: DateTime<Utc>,
+    /// Retention classes by name, with unclassified tables last
+    pub classes: Vec<RetentionClassPlan>,
+}
+
+/// Group a domain's tables by retention class, each with its end-of-period action.
+pub fn purge_plan(
+    domain: &str,
+    tables: &[Table],
+    policies: &RetentionPolicies,
+    generated_at: DateTime<Utc>,
+) -> PurgePlan {
+    let mut classes: BTreeMap<String, Vec<PurgePlanTable>> = BTreeMap::new();
+    for table in tables {
+        let policy = policies.get(table.id);
+        classes
+            .entry(policy.class())
+            .or_default()
+            .push(PurgePlanTable {
+                table_id: table.id,
+                table_name: table.name.clone(),
+                retention_days: policy.retention_days,
+                action: policy.action(),
+                archival_target: policy.archival_target,
+                legal_hold: policy.legal_hold,
+            });
+    }
+    let unclassified = classes.remove(UNCLASSIFIED);
+
+    let plans = classes
+        .into_iter()
+        .chain(unclassified.map(|tables| (UNCLASSIFIED.to_string(), tables)))
+        .map(|(retention_class, mut tables)| {
+            tables.sort_by(|a, b| a.table_name.cmp(&b.table_name));
+            RetentionClassPlan {
+                retention_class,
+                tables,
+            }
+        })
+        .collect();
+
+    PurgePlan {
+        domain: domain.to_string(),
+        generated_at,
+        classes: plans,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_purge_plan_groups_tables_by_retention_class() {
+        let invoices = Table::new("invoices".to_string(), vec![]);
+        let ledger = Table::new("ledger".to_string(), vec![]);
+        let sessions = Table::new("sessions".to_string(), vec![]);
+        let lookup = Table::new("lookup".to_string(), vec![]);
+        let mut policies = RetentionPolicies::default();
+        let financial = RetentionPolicy {
+            retention_days: Some(2555),
+            retention_class: Some("financial-7y".to_string()),
+            archival_target: Some("s3://archive/finance".to_string()),
+            legal_hold: false,
+        };
+        policies.set(invoices.id, financial.clone());
+        policies.set(
+            ledger.id,
+            RetentionPolicy {
+                legal_hold: true,
+                ..financial
+            },
+        );
+        policies.set(
+            sessions.id,
+            RetentionPolicy {
+                retention_days: Some(90),
+                ..Default::default()
+            },
+        );
+        policies.set(lookup.id, RetentionPolicy::default());
+        assert_eq!(policies.tables.len(), 3);
+
+        let plan = purge_plan(
+            "sales",
+            &[sessions, ledger, lookup, invoices],
+            &policies,
+            Utc::now(),
+        );
+        let classes: Vec<&str> = plan
+            .classes
+            .iter()
+            .map(|c| c.retention_class.as_str())
+            .collect();
+        assert_eq!(classes, vec!["90 days", "financial-7y", "unclassified"]);
+        let financial = &plan.classes[1].tables;
+        assert_eq!(financial[0].table_name, "invoices");
+        assert_eq!(financial[0].action, PurgeAction::Archive);
+        assert_eq!(financial[1].action, PurgeAction::Hold);
+        assert_eq!(plan.classes[0].tables[0].action, PurgeAction::Purge);
+        assert_eq!(plan.classes[2].tables[0].action, PurgeAction::Retain);
+
+        assert!(
+            RetentionPolicy {
+                retention_days: Some(0),
+                ..Default::default()
+            }
+            .validate()
+            .is_err()
+        );
+    }
+}
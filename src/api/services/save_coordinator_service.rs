@@ -0,0 +1,103 @@
+//! Coordination of model file writes with shutdown.
+//!
+//! Writes of model files (table YAML, relationships, canvas layout, diagrams) hold a
+//! [`SaveGuard`] while they run. On SIGTERM the server stops reporting ready and waits for the
+//! writes in flight to finish, up to a timeout, before exiting, so a deploy doesn't leave a
+//! half-written file behind.
+
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// Tracks model file writes in flight.
+#[derive(Debug, Default)]
+pub struct SaveCoordinator {
+    in_flight: AtomicUsize,
+    draining: AtomicBool,
+    idle: Notify,
+}
+
+/// Coordinator shared by every model service of the process
+pub fn save_coordinator() -> &'static SaveCoordinator {
+    static COORDINATOR: OnceLock<SaveCoordinator> = OnceLock::new();
+    COORDINATOR.get_or_init(SaveCoordinator::default)
+}
+
+impl SaveCoordinator {
+    /// Record a write starting; it counts as in flight until the guard is dropped.
+    pub fn begin(&self) -> SaveGuard<'_> {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        SaveGuard(self)
+    }
+
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.load(Ordering::SeqCst)
+    }
+
+    /// Whether shutdown has started
+    pub fn is_draining(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Start shutting down and wait for the writes in flight, up to `timeout`.
+    ///
+    /// Returns false if writes were still running when the timeout elapsed.
+    pub async fn drain(&self, timeout: Duration) -> bool {
+        self.draining.store(true, Ordering::SeqCst);
+        tokio::time::timeout(timeout, async {
+            loop {
+                // Registered before the check, so a write finishing in between still wakes us
+                let idle = self.idle.notified();
+                if self.in_flight() == 0 {
+                    return;
+                }
+                idle.await;
+            }
+        })
+        .await
+        .is_ok()
+    }
+}
+
+/// A model file write in flight.
+pub struct SaveGuard<'a>(&'a SaveCoordinator);
+
+impl Drop for SaveGuard<'_> {
+    fn drop(&mut self) {
+        if self.0.in_flight.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_drain_waits_for_writes_in_flight() {
+        let coordinator = Arc::new(SaveCoordinator::default());
+        assert!(coordinator.drain(Duration::from_millis(10)).await);
+        assert!(coordinator.is_draining());
+
+        let guard = coordinator.begin();
+        assert_eq!(coordinator.in_flight(), 1);
+        assert!(!coordinator.drain(Duration::from_millis(10)).await);
+
+        let writer = coordinator.clone();
+        let (started, writing) = tokio::sync::oneshot::channel();
+        let write = tokio::task::spawn_blocking(move || {
+            let _guard = writer.begin();
+            let _ = started.send(());
+            std::thread::sleep(Duration::from_millis(50));
+        });
+        writing.await.unwrap();
+        drop(guard);
+        assert_eq!(coordinator.in_flight(), 1);
+        assert!(coordinator.drain(Duration::from_secs(5)).await);
+        assert_eq!(coordinator.in_flight(), 0);
+        write.await.unwrap();
+    }
+}
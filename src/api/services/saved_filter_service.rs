@@ -0,0 +1,195 @@
+//! Saved filters ("smart collections").
+//!
+//! A saved filter is a named set of table criteria such as "gold layer, finance tag, missing
+//! descriptions". Filters are stored per domain and can be applied to the table list and
+//! canvas endpoints by id, so recurring governance reviews reuse the same query. Criteria
+//! are combined with AND; list criteria match if the table has any of the listed layers or
+//! database types, and all of the listed tags.
+
+use anyhow::{Result, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::Table;
+
+/// File holding a domain's saved filters
+pub const SAVED_FILTERS_FILE: &str = "saved-filters.yaml";
+
+const MEDALLION_LAYERS: &[&str] = &["bronze", "silver", "gold", "operational"];
+const MODELING_LEVELS: &[&str] = &["conceptual", "logical", "physical"];
+
+/// Table criteria of a saved filter; unset criteria match every table
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct FilterCriteria {
+    /// Case-insensitive substring of the table name or one of its aliases
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name_contains: Option<String>,
+    /// Medallion layers, any of which must be present (e.g. `gold`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub medallion_layers: Vec<String>,
+    /// Tags that must all be present
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// `conceptual`, `logical` or `physical`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modeling_level: Option<String>,
+    /// Database types, one of which must match (e.g. `Postgres`)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub database_types: Vec<String>,
+    /// Only tables with at least one column lacking a description
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub missing_descriptions: bool,
+}
+
+impl FilterCriteria {
+    /// Reject unknown medallion layers and modeling levels.
+    pub fn validate(&self) -> Result<()> {
+        for layer in &self.medallion_layers {
+            if !MEDALLION_LAYERS.contains(&layer.to_lowercase().as_str()) {
+                bail!("Unknown medallion layer '{}'", layer);
+            }
+        }
+        if let Some(level) = &self.modeling_level
+            && !MODELING_LEVELS.contains(&level.to_lowercase().as_str())
+        {
+            bail!("Unknown modeling level '{}'", level);
+        }
+        Ok(())
+    }
+
+    /// Whether `table` meets every criterion.
+    pub fn matches(&self, table: &Table) -> bool {
+        if let Some(needle) = self.name_contains.as_deref().map(str::to_lowercase)
+            && !std::iter::once(&table.name)
+                .chain(&table.aliases)
+                .any(|name| name.to_lowercase().contains(&needle))
+        {
+            return false;
+        }
+        if !self.medallion_layers.is_empty()
+            && !table.medallion_layers.iter().any(|layer| {
+                let layer = format!("{:?}", layer);
+                self.medallion_layers
+                    .iter()
+                    .any(|l| l.eq_ignore_ascii_case(&layer))
+            })
+        {
+            return false;
+        }
+        if !self
+            .tags
+            .iter()
+            .all(|tag| table.tags.iter().any(|t| t.eq_ignore_ascii_case(tag)))
+        {
+            return false;
+        }
+        if let Some(level) = &self.modeling_level
+            && !table
+                .modeling_level
+                .is_some_and(|l| format!("{:?}", l).eq_ignore_ascii_case(level))
+        {
+            return false;
+        }
+        if !self.database_types.is_empty()
+            && !table.database_type.is_some_and(|dt| {
+                let dt = format!("{:?}", dt);
+                self.database_types
+                    .iter()
+                    .any(|d| d.eq_ignore_ascii_case(&dt))
+            })
+        {
+            return false;
+        }
+        if self.missing_descriptions
+            && table
+                .columns
+                .iter()
+                .all(|c| !c.description.trim().is_empty())
+        {
+            return false;
+        }
+        true
+    }
+}
+
+/// A named, reusable table filter
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SavedFilter {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub criteria: FilterCriteria,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// All saved filters of a domain (`saved-filters.yaml`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SavedFilters {
+    #[serde(default)]
+    pub filters: Vec<SavedFilter>,
+}
+
+impl SavedFilters {
+    pub fn get(&self, id: Uuid) -> Option<&SavedFilter> {
+        self.filters.iter().find(|f| f.id == id)
+    }
+
+    /// Whether another filter (other than `except`) already uses `name`.
+    pub fn name_taken(&self, name: &str, except: Option<Uuid>) -> bool {
+        self.filters
+            .iter()
+            .any(|f| Some(f.id) != except && f.name.trim().eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Remove a filter; returns whether it existed.
+    pub fn remove(&mut self, id: Uuid) -> bool {
+        let before = self.filters.len();
+        self.filters.retain(|f| f.id != id);
+        self.filters.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Column;
+    use crate::models::enums::MedallionLayer;
+
+    #[test]
+    fn test_governance_review_filter() {
+        let criteria: FilterCriteria = serde_json::from_value(serde_json::json!({
+            "medallion_layers": ["gold"],
+            "tags": ["Finance"],
+            "missing_descriptions": true
+        }))
+        .unwrap();
+        assert!(criteria.validate().is_ok());
+
+        let mut documented = Column::new("id".to_string(), "BIGINT".to_string());
+        documented.description = "Revenue row id".to_string();
+        let blank = Column::new("amount".to_string(), "DECIMAL".to_string());
+        let mut table = Table::new("revenue".to_string(), vec![documented.clone(), blank]);
+        table.medallion_layers = vec![MedallionLayer::Gold];
+        table.tags = vec!["finance".to_string(), "kpi".to_string()];
+        assert!(criteria.matches(&table));
+
+        let mut silver = table.clone();
+        silver.medallion_layers = vec![MedallionLayer::Silver];
+        assert!(!criteria.matches(&silver));
+        let mut described = table.clone();
+        described.columns = vec![documented];
+        assert!(!criteria.matches(&described));
+
+        let invalid = FilterCriteria {
+            medallion_layers: vec!["platinum".to_string()],
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+}
@@ -0,0 +1,375 @@
+//! Scripting hooks for custom validation and import transforms.
+//!
+//! Workspace admins store [Rhai](https://rhai.rs) scripts in `script-hooks.yaml` at the root
+//! of the workspace data directory. Every script runs once per imported table with the table
+//! (as serialized by the API) in the variable `table`:
+//!
+//! - `import_transform` hooks run first, in order, and may modify `table`, e.g.
+//!   `table.name = table.name.to_lower();`
+//! - `validation` hooks run on the transformed tables and return `()` or `true` when the
+//!   table passes, or a message (or array of messages) describing what is wrong, e.g.
+//!   `if table.tags.len() == 0 { "every table needs an owner tag" }`
+//!
+//! Scripts run in a sandbox: there is no file, network or module access, `eval` is disabled
+//! and runs are bounded by an operation count, a wall-clock timeout per import and limits on
+//! string, array and map sizes and nesting.
+//!
+//! Limits (environment variables):
+//! - `SCRIPT_MAX_OPERATIONS` (default 1000000): operations per script run
+//! - `SCRIPT_TIMEOUT_MS` (default 1000): wall-clock time for all hooks of one import
+
+use anyhow::{Context, Result};
+use rhai::{Dynamic, Engine, Scope};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tracing::debug;
+use utoipa::ToSchema;
+
+use crate::models::Table;
+
+/// Hooks file at the root of the workspace data directory
+const SCRIPT_HOOKS_FILE: &str = "script-hooks.yaml";
+
+const DEFAULT_MAX_OPERATIONS: u64 = 1_000_000;
+const DEFAULT_TIMEOUT: Duration = Duration::from_millis(1000);
+const MAX_CALL_LEVELS: usize = 32;
+const MAX_EXPR_DEPTH: usize = 64;
+const MAX_STRING_SIZE: usize = 64 * 1024;
+const MAX_COLLECTION_SIZE: usize = 10_000;
+
+/// When a hook runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum HookKind {
+    /// Checks each imported table; failing tables reject the import
+    Validation,
+    /// Modifies each imported table before it is validated and stored
+    ImportTransform,
+}
+
+/// A script run for every imported table.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ScriptHook {
+    /// Hook name shown in validation errors
+    pub name: String,
+    pub kind: HookKind,
+    /// Rhai source; the table is available as `table`
+    pub script: String,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Script hooks configured for the workspace.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ScriptHooks {
+    #[serde(default)]
+    pub hooks: Vec<ScriptHook>,
+}
+
+impl ScriptHooks {
+    pub fn load(workspace_data_dir: &Path) -> Self {
+        std::fs::read_to_string(workspace_data_dir.join(SCRIPT_HOOKS_FILE))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, workspace_data_dir: &Path) -> Result<()> {
+        let path = workspace_data_dir.join(SCRIPT_HOOKS_FILE);
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn enabled(&self, kind: HookKind) -> impl Iterator<Item = &ScriptHook> {
+        self.hooks
+            .iter()
+            .filter(move |hook| hook.enabled && hook.kind == kind)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.hooks.iter().any(|hook| hook.enabled)
+    }
+}
+
+/// A table rejected by a validation hook, or a hook that failed to run.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct HookFailure {
+    pub hook: String,
+    pub table: String,
+    pub message: String,
+}
+
+/// Resource limits for script runs.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScriptLimits {
+    pub max_operations: u64,
+    pub timeout: Duration,
+}
+
+impl Default for ScriptLimits {
+    fn default() -> Self {
+        Self {
+            max_operations: DEFAULT_MAX_OPERATIONS,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+impl ScriptLimits {
+    /// Read script limits from environment variables.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Read script limits using the given variable lookup.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let number = |key: &str| {
+            lookup(key)
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|n| *n > 0)
+        };
+        let defaults = Self::default();
+        Self {
+            max_operations: number("SCRIPT_MAX_OPERATIONS").unwrap_or(defaults.max_operations),
+            timeout: number("SCRIPT_TIMEOUT_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.timeout),
+        }
+    }
+
+    /// A sandboxed engine that stops at `deadline`.
+    fn engine(&self, deadline: Instant) -> Engine {
+        let mut engine = Engine::new();
+        engine
+            .set_max_operations(self.max_operations)
+            .set_max_call_levels(MAX_CALL_LEVELS)
+            .set_max_expr_depths(MAX_EXPR_DEPTH, MAX_EXPR_DEPTH)
+            .set_max_string_size(MAX_STRING_SIZE)
+            .set_max_array_size(MAX_COLLECTION_SIZE)
+            .set_max_map_size(MAX_COLLECTION_SIZE)
+            .set_module_resolver(rhai::module_resolvers::DummyModuleResolver::new())
+            .disable_symbol("eval")
+            .on_print(|text| debug!("[Script] {}", text))
+            .on_debug(|text, _, _| debug!("[Script] {}", text))
+            .on_progress(move |_| {
+                (Instant::now() > deadline).then(|| Dynamic::from("script timeout exceeded"))
+            });
+        engine
+    }
+
+    /// Check that a script compiles.
+    pub fn compile(&self, script: &str) -> Result<(), String> {
+        self.engine(Instant::now() + self.timeout)
+            .compile(script)
+            .map(|_| ())
+            .map_err(|e| e.to_string())
+    }
+
+    /// Run the workspace's hooks over imported tables: transforms first, then validations.
+    ///
+    /// Tables are modified in place. A hook that fails to run (script error, limit exceeded,
+    /// or a transform that leaves `table` invalid) is reported like a failed validation.
+    pub fn run_import_hooks(&self, hooks: &ScriptHooks, tables: &mut [Table]) -> Vec<HookFailure> {
+        let mut failures = Vec::new();
+        if hooks.is_empty() || tables.is_empty() {
+            return failures;
+        }
+        let engine = self.engine(Instant::now() + self.timeout);
+        let compiled = |kind| {
+            hooks
+                .enabled(kind)
+                .map(|hook| (hook, engine.compile(&hook.script)))
+                .collect::<Vec<_>>()
+        };
+        let transforms = compiled(HookKind::ImportTransform);
+        let validations = compiled(HookKind::Validation);
+
+        for table in tables.iter_mut() {
+            for (hook, ast) in &transforms {
+                let result = ast.as_ref().map_err(|e| e.to_string()).and_then(|ast| {
+                    let mut scope = Scope::new();
+                    scope.push(
+                        "table",
+                        rhai::serde::to_dynamic(&*table).map_err(|e| e.to_string())?,
+                    );
+                    engine
+                        .run_ast_with_scope(&mut scope, ast)
+                        .map_err(|e| e.to_string())?;
+                    let value = scope
+                        .get_value::<Dynamic>("table")
+                        .ok_or("`table` was removed")?;
+                    rhai::serde::from_dynamic::<Table>(&value)
+                        .map_err(|e| format!("`table` is no longer a valid table: {}", e))
+                });
+                match result {
+                    Ok(transformed) => *table = transformed,
+                    Err(message) => failures.push(HookFailure {
+                        hook: hook.name.clone(),
+                        table: table.name.clone(),
+                        message,
+                    }),
+                }
+            }
+        }
+
+        for table in tables.iter() {
+            for (hook, ast) in &validations {
+                let result = ast.as_ref().map_err(|e| e.to_string()).and_then(|ast| {
+                    let mut scope = Scope::new();
+                    scope.push(
+                        "table",
+                        rhai::serde::to_dynamic(table).map_err(|e| e.to_string())?,
+                    );
+                    engine
+                        .eval_ast_with_scope::<Dynamic>(&mut scope, ast)
+                        .map_err(|e| e.to_string())
+                });
+                let messages = match result {
+                    Ok(value) => validation_messages(value),
+                    Err(message) => vec![message],
+                };
+                failures.extend(messages.into_iter().map(|message| HookFailure {
+                    hook: hook.name.clone(),
+                    table: table.name.clone(),
+                    message,
+                }));
+            }
+        }
+        failures
+    }
+
+    /// [`run_import_hooks`](Self::run_import_hooks) on the blocking thread pool, so scripts
+    /// running up to their timeout don't stall the async runtime. Returns the tables.
+    pub async fn run_import_hooks_blocking(
+        self,
+        hooks: ScriptHooks,
+        mut tables: Vec<Table>,
+    ) -> (Vec<Table>, Vec<HookFailure>) {
+        if hooks.is_empty() || tables.is_empty() {
+            return (tables, Vec::new());
+        }
+        let task = tokio::task::spawn_blocking(move || {
+            let failures = self.run_import_hooks(&hooks, &mut tables);
+            (tables, failures)
+        });
+        match task.await {
+            Ok(result) => result,
+            Err(e) => std::panic::resume_unwind(e.into_panic()),
+        }
+    }
+}
+
+/// Messages returned by a validation script; `()` and `true` mean the table passes.
+fn validation_messages(value: Dynamic) -> Vec<String> {
+    if value.is_unit() || value.as_bool() == Ok(true) {
+        Vec::new()
+    } else if value.is_array() {
+        value
+            .into_array()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|item| item.to_string())
+            .collect()
+    } else if value.as_bool() == Ok(false) {
+        vec!["validation failed".to_string()]
+    } else {
+        vec![value.to_string()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Column;
+
+    fn hook(name: &str, kind: HookKind, script: &str) -> ScriptHook {
+        ScriptHook {
+            name: name.to_string(),
+            kind,
+            script: script.to_string(),
+            enabled: true,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn test_transforms_then_validates_imported_tables() {
+        let hooks = ScriptHooks {
+            hooks: vec![
+                hook(
+                    "require-id",
+                    HookKind::Validation,
+                    r#"if !table.columns.some(|c| c.name == "id") { "missing id column" }"#,
+                ),
+                hook(
+                    "lowercase",
+                    HookKind::ImportTransform,
+                    "table.name = table.name.to_lower();",
+                ),
+            ],
+        };
+        let mut tables = vec![
+            Table::new(
+                "Orders".to_string(),
+                vec![Column::new("id".to_string(), "BIGINT".to_string())],
+            ),
+            Table::new("Customers".to_string(), Vec::new()),
+        ];
+
+        let failures = ScriptLimits::default().run_import_hooks(&hooks, &mut tables);
+        assert_eq!(tables[0].name, "orders");
+        assert_eq!(tables[0].columns[0].name, "id");
+        assert_eq!(
+            failures,
+            vec![HookFailure {
+                hook: "require-id".to_string(),
+                table: "customers".to_string(),
+                message: "missing id column".to_string(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_hooks_run_off_the_async_runtime() {
+        let hooks = ScriptHooks {
+            hooks: vec![hook(
+                "lowercase",
+                HookKind::ImportTransform,
+                "table.name = table.name.to_lower();",
+            )],
+        };
+        let tables = vec![Table::new("Orders".to_string(), Vec::new())];
+
+        let (tables, failures) = ScriptLimits::default()
+            .run_import_hooks_blocking(hooks, tables)
+            .await;
+        assert_eq!(tables[0].name, "orders");
+        assert!(failures.is_empty());
+    }
+
+    #[test]
+    fn test_scripts_are_sandboxed() {
+        let limits = ScriptLimits {
+            max_operations: 10_000,
+            timeout: Duration::from_secs(5),
+        };
+        let hooks = ScriptHooks {
+            hooks: vec![
+                hook("loop", HookKind::Validation, "loop {}"),
+                hook("import", HookKind::Validation, r#"import "fs" as fs;"#),
+            ],
+        };
+        let mut tables = vec![Table::new("orders".to_string(), Vec::new())];
+        let failures = limits.run_import_hooks(&hooks, &mut tables);
+        assert_eq!(failures.len(), 2);
+        assert!(limits.compile(r#"eval("1")"#).is_err());
+        assert!(limits.compile("let x = ").is_err());
+    }
+}
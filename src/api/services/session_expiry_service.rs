@@ -0,0 +1,143 @@
+//! Server-side session expiry.
+//!
+//! Sessions expire when they have been idle for the TTL; activity on a session (an
+//! authenticated request in file mode, a token refresh or status check with PostgreSQL)
+//! slides the expiry forward, up to the session's maximum lifetime. Expired sessions are
+//! pruned by a background task.
+//!
+//! Configuration (environment variables):
+//! - `SESSION_TTL_HOURS` (default 168): idle time after which a session expires
+//! - `SESSION_MAX_LIFETIME_HOURS` (default 720): lifetime after which a session expires
+//!   regardless of activity
+//! - `SESSION_PRUNE_INTERVAL_SECS` (default 3600): how often expired sessions are pruned
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::env;
+
+use crate::routes::auth::SessionMetadata;
+
+const DEFAULT_TTL_HOURS: i64 = 7 * 24;
+const DEFAULT_MAX_LIFETIME_HOURS: i64 = 30 * 24;
+const DEFAULT_PRUNE_INTERVAL_SECS: u64 = 3600;
+
+/// Configured session expiry
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionExpiry {
+    pub ttl: Duration,
+    pub max_lifetime: Duration,
+    pub prune_interval: std::time::Duration,
+}
+
+impl Default for SessionExpiry {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::hours(DEFAULT_TTL_HOURS),
+            max_lifetime: Duration::hours(DEFAULT_MAX_LIFETIME_HOURS),
+            prune_interval: std::time::Duration::from_secs(DEFAULT_PRUNE_INTERVAL_SECS),
+        }
+    }
+}
+
+impl SessionExpiry {
+    /// Configure from `SESSION_TTL_HOURS`, `SESSION_MAX_LIFETIME_HOURS` and
+    /// `SESSION_PRUNE_INTERVAL_SECS`; zero or invalid values keep the defaults.
+    pub fn from_env() -> Self {
+        fn var(name: &str) -> Option<u64> {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+        }
+        let defaults = Self::default();
+        Self {
+            ttl: var("SESSION_TTL_HOURS")
+                .map(|h| Duration::hours(h as i64))
+                .unwrap_or(defaults.ttl),
+            max_lifetime: var("SESSION_MAX_LIFETIME_HOURS")
+                .map(|h| Duration::hours(h as i64))
+                .unwrap_or(defaults.max_lifetime),
+            prune_interval: var("SESSION_PRUNE_INTERVAL_SECS")
+                .map(std::time::Duration::from_secs)
+                .unwrap_or(defaults.prune_interval),
+        }
+    }
+
+    /// Expiry of a session created at `created_at` that was last active at `now`
+    pub fn expires_at(&self, created_at: DateTime<Utc>, now: DateTime<Utc>) -> DateTime<Utc> {
+        (now + self.ttl).min(created_at + self.max_lifetime)
+    }
+
+    /// Record activity on a session, sliding its expiry forward. False if it had expired.
+    pub fn touch(&self, session: &mut SessionMetadata, now: DateTime<Utc>) -> bool {
+        if session.expires_at <= now || session.revoked_at.is_some() {
+            return false;
+        }
+        session.last_activity = now;
+        session.expires_at = self.expires_at(session.created_at, now);
+        true
+    }
+
+    /// Remove expired and revoked sessions, returning how many were removed.
+    pub fn prune(
+        &self,
+        sessions: &mut HashMap<String, SessionMetadata>,
+        now: DateTime<Utc>,
+    ) -> usize {
+        let before = sessions.len();
+        sessions.retain(|_, s| s.expires_at > now && s.revoked_at.is_none());
+        before - sessions.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_activity_slides_expiry_up_to_max_lifetime() {
+        let expiry = SessionExpiry {
+            ttl: Duration::hours(1),
+            max_lifetime: Duration::hours(3),
+            ..Default::default()
+        };
+        let created = Utc::now();
+        let mut session = SessionMetadata {
+            user_id: Uuid::new_v4(),
+            github_id: 1,
+            github_username: "dev".to_string(),
+            github_access_token: String::new(),
+            emails: Vec::new(),
+            selected_email: None,
+            created_at: created,
+            last_activity: created,
+            revoked_at: None,
+            expires_at: expiry.expires_at(created, created),
+        };
+        assert_eq!(session.expires_at, created + Duration::hours(1));
+
+        let later = created + Duration::minutes(50);
+        assert!(expiry.touch(&mut session, later));
+        assert_eq!(session.expires_at, later + Duration::hours(1));
+        assert_eq!(session.last_activity, later);
+
+        // Capped by the maximum lifetime however active the session is
+        let late = created + Duration::minutes(170);
+        let mut sessions = HashMap::from([("a".to_string(), session.clone())]);
+        for minutes in [100, 150, 170] {
+            assert!(expiry.touch(
+                sessions.get_mut("a").unwrap(),
+                created + Duration::minutes(minutes)
+            ));
+        }
+        assert_eq!(sessions["a"].expires_at, created + Duration::hours(3));
+        assert!(!expiry.touch(&mut session, later + Duration::hours(2)));
+
+        sessions.insert("b".to_string(), session);
+        assert_eq!(expiry.prune(&mut sessions, late), 1);
+        assert!(sessions.contains_key("a"));
+        assert_eq!(expiry.prune(&mut sessions, created + Duration::hours(3)), 1);
+        assert!(sessions.is_empty());
+    }
+}
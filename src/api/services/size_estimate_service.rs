@@ -0,0 +1,323 @@
+//! Storage size estimates for capacity planning.
+//!
+//! Tables can be annotated with an estimated row count and a monthly growth rate, stored
+//! per domain in `size_estimates.yaml` keyed by table id. Row sizes are estimated from the
+//! column types with per-dialect heuristics (fixed widths for numeric and temporal types,
+//! half the declared length for strings, row header overhead of the storage engine), so the
+//! estimates are rough figures for planning discussions, not exact sizes.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::{Column, Table};
+
+/// File name of a domain's size annotations
+pub const SIZE_ESTIMATES_FILE: &str = "size_estimates.yaml";
+
+/// Assumed width of strings and binaries without a declared length
+const DEFAULT_VARIABLE_BYTES: u64 = 32;
+/// Assumed width of nested and semi-structured values
+const NESTED_BYTES: u64 = 256;
+/// Months the growth projection covers
+const PROJECTION_MONTHS: i32 = 12;
+
+/// Estimated volume of a table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SizeAnnotation {
+    pub estimated_rows: u64,
+    /// Expected growth per month as a fraction (0.05 = 5% more rows each month)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub monthly_growth_rate: Option<f64>,
+}
+
+impl SizeAnnotation {
+    pub fn validate(&self) -> std::result::Result<(), String> {
+        match self.monthly_growth_rate {
+            Some(rate) if !rate.is_finite() || rate <= -1.0 => {
+                Err("Monthly growth rate must be a number above -1".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Size annotations of a domain's tables (`size_estimates.yaml`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SizeAnnotations {
+    #[serde(default)]
+    pub tables: BTreeMap<Uuid, SizeAnnotation>,
+}
+
+impl SizeAnnotations {
+    fn path(domain_dir: &Path) -> PathBuf {
+        domain_dir.join(SIZE_ESTIMATES_FILE)
+    }
+
+    /// Load a domain's annotations (none if the file is missing).
+    pub fn load(domain_dir: &Path) -> Self {
+        std::fs::read_to_string(Self::path(domain_dir))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, domain_dir: &Path) -> Result<()> {
+        let path = Self::path(domain_dir);
+        std::fs::create_dir_all(domain_dir)
+            .with_context(|| format!("Failed to create {}", domain_dir.display()))?;
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Annotate a table; `None` removes its annotation.
+    pub fn set(&mut self, table_id: Uuid, annotation: Option<SizeAnnotation>) {
+        match annotation {
+            Some(annotation) => self.tables.insert(table_id, annotation),
+            None => self.tables.remove(&table_id),
+        };
+    }
+}
+
+/// Storage engine whose row layout the estimate follows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageDialect {
+    #[default]
+    Generic,
+    Postgres,
+    Mysql,
+    Sqlserver,
+    /// Columnar (Databricks, Delta Lake and Parquet based engines)
+    Databricks,
+}
+
+impl StorageDialect {
+    /// Dialect by name as used for exports (unknown names use generic heuristics)
+    pub fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "postgres" | "postgresql" => Self::Postgres,
+            "mysql" | "mariadb" => Self::Mysql,
+            "sqlserver" | "mssql" => Self::Sqlserver,
+            "databricks" | "delta" | "spark" => Self::Databricks,
+            _ => Self::Generic,
+        }
+    }
+
+    /// Bytes per row the engine adds (tuple header, item pointer, null bitmap)
+    fn row_overhead(self) -> u64 {
+        match self {
+            Self::Postgres => 28,
+            Self::Mysql => 20,
+            Self::Sqlserver => 11,
+            Self::Generic | Self::Databricks => 0,
+        }
+    }
+
+    /// Length prefix of variable-width values
+    fn length_prefix(self) -> u64 {
+        match self {
+            Self::Postgres | Self::Sqlserver => 2,
+            Self::Mysql => 1,
+            Self::Generic | Self::Databricks => 0,
+        }
+    }
+
+    /// Share of the raw size kept after compression (columnar formats compress well)
+    fn compression_ratio(self) -> f64 {
+        match self {
+            Self::Databricks => 0.3,
+            _ => 1.0,
+        }
+    }
+}
+
+/// Parameters of a type such as `VARCHAR(100)` or `DECIMAL(18, 2)`
+fn type_params(data_type: &str) -> Vec<u64> {
+    data_type
+        .split_once('(')
+        .and_then(|(_, rest)| rest.split_once(')'))
+        .map(|(params, _)| {
+            params
+                .split(',')
+                .filter_map(|p| p.trim().parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Estimated stored width of one value of a column type
+pub fn column_bytes(data_type: &str, dialect: StorageDialect) -> u64 {
+    let upper = data_type.trim().to_uppercase();
+    let base = upper
+        .split(['(', '<', ' '])
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let params = type_params(&upper);
+    let variable = |length: Option<u64>| {
+        length.map_or(DEFAULT_VARIABLE_BYTES, |n| n.div_ceil(2)) + dialect.length_prefix()
+    };
+
+    match base.as_str() {
+        "BOOLEAN" | "BOOL" | "BIT" | "TINYINT" => 1,
+        "SMALLINT" | "INT2" => 2,
+        "INT" | "INTEGER" | "INT4" | "MEDIUMINT" | "REAL" | "FLOAT4" | "DATE" => 4,
+        "BIGINT" | "INT8" | "LONG" | "DOUBLE" | "FLOAT" | "FLOAT8" | "TIMESTAMP"
+        | "TIMESTAMPTZ" | "TIMESTAMP_NTZ" | "DATETIME" | "DATETIME2" | "TIME" | "MONEY" => 8,
+        "UUID" | "UNIQUEIDENTIFIER" | "INTERVAL" => 16,
+        "DECIMAL" | "NUMERIC" | "NUMBER" => {
+            let precision = params.first().copied().unwrap_or(18);
+            precision.div_ceil(2) + 1
+        }
+        "CHAR" | "NCHAR" | "CHARACTER" => params.first().copied().unwrap_or(1),
+        "VARCHAR" | "NVARCHAR" | "VARCHAR2" | "STRING" | "TEXT" | "CLOB" | "VARBINARY"
+        | "BINARY" | "BYTEA" | "BLOB" | "BYTES" => variable(params.first().copied()),
+        "JSON" | "JSONB" | "VARIANT" | "STRUCT" | "ARRAY" | "MAP" | "OBJECT" | "XML" => {
+            NESTED_BYTES
+        }
+        _ => variable(None),
+    }
+}
+
+/// Estimated stored width of a row
+pub fn row_bytes(columns: &[Column], dialect: StorageDialect) -> u64 {
+    // Nested fields ("customer.name") are stored inside their parent column
+    let raw: u64 = columns
+        .iter()
+        .filter(|c| !c.name.contains('.'))
+        .map(|c| column_bytes(&c.data_type, dialect))
+        .sum::<u64>()
+        + dialect.row_overhead();
+    (raw as f64 * dialect.compression_ratio()).ceil() as u64
+}
+
+/// Estimated size of one table
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TableSizeEstimate {
+    pub table_id: Uuid,
+    pub table_name: String,
+    pub row_bytes: u64,
+    /// Only set for annotated tables
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_rows: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monthly_growth_rate: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_bytes: Option<u64>,
+    /// Size after twelve months of growth
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projected_bytes_12_months: Option<u64>,
+}
+
+/// Estimated storage footprint of a domain
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct StorageEstimate {
+    pub dialect: StorageDialect,
+    /// Sum over the annotated tables
+    pub total_bytes: u64,
+    pub projected_total_bytes_12_months: u64,
+    pub tables: Vec<TableSizeEstimate>,
+}
+
+/// Estimate the storage footprint of `tables` in `dialect` from their annotations.
+pub fn estimate_storage(
+    tables: &[Table],
+    annotations: &SizeAnnotations,
+    dialect: StorageDialect,
+) -> StorageEstimate {
+    let mut estimates: Vec<TableSizeEstimate> = tables
+        .iter()
+        .map(|table| {
+            let row_bytes = row_bytes(&table.columns, dialect);
+            let annotation = annotations.tables.get(&table.id);
+            let estimated_bytes = annotation.map(|a| a.estimated_rows.saturating_mul(row_bytes));
+            let projected = annotation.zip(estimated_bytes).map(|(a, bytes)| {
+                let growth = (1.0 + a.monthly_growth_rate.unwrap_or(0.0)).powi(PROJECTION_MONTHS);
+                (bytes as f64 * growth).round() as u64
+            });
+            TableSizeEstimate {
+                table_id: table.id,
+                table_name: table.name.clone(),
+                row_bytes,
+                estimated_rows: annotation.map(|a| a.estimated_rows),
+                monthly_growth_rate: annotation.and_then(|a| a.monthly_growth_rate),
+                estimated_bytes,
+                projected_bytes_12_months: projected,
+            }
+        })
+        .collect();
+    estimates.sort_by(|a, b| a.table_name.cmp(&b.table_name));
+
+    StorageEstimate {
+        dialect,
+        total_bytes: estimates.iter().filter_map(|t| t.estimated_bytes).sum(),
+        projected_total_bytes_12_months: estimates
+            .iter()
+            .filter_map(|t| t.projected_bytes_12_months)
+            .sum(),
+        tables: estimates,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_estimate_uses_column_types_and_annotations() {
+        assert_eq!(column_bytes("BIGINT", StorageDialect::Generic), 8);
+        assert_eq!(column_bytes("varchar(100)", StorageDialect::Postgres), 52);
+        assert_eq!(column_bytes("DECIMAL(18,2)", StorageDialect::Mysql), 10);
+        assert_eq!(column_bytes("STRUCT<a:INT>", StorageDialect::Generic), 256);
+
+        let mut orders = Table::new(
+            "orders".to_string(),
+            vec![
+                Column::new("id".to_string(), "BIGINT".to_string()),
+                Column::new("placed_at".to_string(), "TIMESTAMP".to_string()),
+                Column::new("status".to_string(), "VARCHAR(20)".to_string()),
+            ],
+        );
+        orders
+            .columns
+            .push(Column::new("status.code".to_string(), "INT".to_string()));
+        let lookup = Table::new("lookup".to_string(), vec![]);
+        let mut annotations = SizeAnnotations::default();
+        annotations.set(
+            orders.id,
+            Some(SizeAnnotation {
+                estimated_rows: 1_000_000,
+                monthly_growth_rate: Some(0.1),
+            }),
+        );
+
+        let postgres = estimate_storage(
+            &[orders.clone(), lookup.clone()],
+            &annotations,
+            StorageDialect::Postgres,
+        );
+        // 8 + 8 + (10 + 2) + 28 row overhead
+        assert_eq!(postgres.tables[1].row_bytes, 56);
+        assert_eq!(postgres.total_bytes, 56_000_000);
+        // 10% a month compounds to about 3.14 times the size in a year
+        assert_eq!(postgres.projected_total_bytes_12_months / 1_000_000, 175);
+        assert!(postgres.tables[0].estimated_bytes.is_none());
+
+        let databricks =
+            estimate_storage(&[orders, lookup], &annotations, StorageDialect::Databricks);
+        assert!(databricks.total_bytes < postgres.total_bytes);
+
+        assert!(
+            SizeAnnotation {
+                estimated_rows: 1,
+                monthly_growth_rate: Some(-1.0),
+            }
+            .validate()
+            .is_err()
+        );
+    }
+}
@@ -1,18 +1,29 @@
 //! SQL parser service for extracting table definitions from SQL CREATE statements.
 //!
 //! This service parses SQL CREATE TABLE statements and extracts table and column definitions.
-//! Supports standard SQL and Liquibase formats.
+//! Supports standard SQL and Liquibase formats. Inline `REFERENCES` clauses and table-level
+//! `FOREIGN KEY` constraints are turned into relationships by [`SQLParser::parse_with_relationships`].
+//!
+//! Large scripts are split into statements that are parsed on the rayon thread pool; results
+//! keep the order of the script, so table indexes and column order are unchanged.
 
 use crate::models::column::ForeignKey;
+use crate::models::relationship::ForeignKeyDetails;
 use crate::models::{Column, Table};
+use crate::services::relationship_service::ImportedRelationship;
 use anyhow::Result;
+use rayon::prelude::*;
 use regex::Regex;
-use sqlparser::ast::{ColumnOption, DataType, Statement};
-use sqlparser::dialect::{GenericDialect, dialect_from_str};
+use sqlparser::ast::{ColumnOption, DataType, Statement, TableConstraint};
+use sqlparser::dialect::{Dialect, GenericDialect, dialect_from_str};
 use sqlparser::parser::Parser;
 use std::collections::HashMap;
+use std::sync::OnceLock;
 use tracing::{debug, info, warn};
 
+/// Minimum number of statements before parsing is spread across threads
+const PARALLEL_PARSE_MIN_STATEMENTS: usize = 16;
+
 /// SQL parser service for extracting table definitions from SQL CREATE statements.
 pub struct SQLParser {
     /// Dialect to use for parsing (default: Generic)
@@ -49,21 +60,27 @@ impl SQLParser {
     /// For "databricks", uses datafusion's DatabricksDialect for proper Databricks SQL support.
     pub fn with_dialect_name(dialect_name: &str) -> Self {
         let dialect_name_lower = dialect_name.to_lowercase();
-
-        // Handle Databricks dialect specially using datafusion's DatabricksDialect
         if dialect_name_lower == "databricks" || dialect_name_lower == "databricks_delta" {
             info!(
                 "[SQLParser] Using datafusion's DatabricksDialect for '{}'",
                 dialect_name
             );
-            return Self {
-                dialect: Box::new(datafusion::sql::sqlparser::dialect::DatabricksDialect {}),
-                dialect_name: dialect_name_lower.clone(),
-            };
+        }
+        Self {
+            dialect: Self::dialect_for(&dialect_name_lower),
+            dialect_name: dialect_name_lower,
+        }
+    }
+
+    /// Build the sqlparser dialect for a (lowercase) dialect name.
+    fn dialect_for(dialect_name_lower: &str) -> Box<dyn Dialect> {
+        // Handle Databricks dialect specially using datafusion's DatabricksDialect
+        if dialect_name_lower == "databricks" || dialect_name_lower == "databricks_delta" {
+            return Box::new(datafusion::sql::sqlparser::dialect::DatabricksDialect {});
         }
 
         // Map user-friendly names to sqlparser dialect names
-        let sqlparser_dialect_name = match dialect_name_lower.as_str() {
+        let sqlparser_dialect_name = match dialect_name_lower {
             "oracle" => {
                 // Oracle syntax is complex, use GenericDialect for now
                 "generic"
@@ -71,17 +88,11 @@ impl SQLParser {
             "mssql" | "sqlserver" => "mssql",
             "postgres" => "postgresql",
             "other" => "generic",
-            _ => &dialect_name_lower,
+            _ => dialect_name_lower,
         };
 
         // Use sqlparser's built-in dialect_from_str function
-        let dialect: Box<dyn sqlparser::dialect::Dialect> =
-            dialect_from_str(sqlparser_dialect_name).unwrap_or_else(|| Box::new(GenericDialect {}));
-
-        Self {
-            dialect,
-            dialect_name: dialect_name_lower.clone(),
-        }
+        dialect_from_str(sqlparser_dialect_name).unwrap_or_else(|| Box::new(GenericDialect {}))
     }
 
     /// Map dialect name to DatabaseType enum
@@ -142,7 +153,7 @@ impl SQLParser {
         let preprocessed_sql = self.preprocess_sql_for_ast(sql);
 
         // Standard SQL parsing - try sqlparser first, fallback to string parsing if needed
-        match self.parse_statements(&preprocessed_sql) {
+        match self.parse_statements_parallel(&preprocessed_sql) {
             Ok(statements) => {
                 for (idx, statement) in statements.iter().enumerate() {
                     if let Statement::CreateTable(create_table) = statement {
@@ -185,6 +196,224 @@ impl SQLParser {
         Ok((tables, tables_requiring_name))
     }
 
+    /// Parse SQL like [`parse`](Self::parse) and also discover relationships from inline
+    /// `REFERENCES` clauses and table-level `FOREIGN KEY` constraints (including composite
+    /// keys). Source columns of table-level constraints get their `foreign_key` set too.
+    ///
+    /// Constraints whose source and referenced column counts differ are not turned into
+    /// relationships; an error is recorded on the source table instead.
+    pub fn parse_with_relationships(
+        &self,
+        sql: &str,
+    ) -> Result<(Vec<Table>, Vec<TableNameInput>, Vec<ImportedRelationship>)> {
+        let (mut tables, tables_requiring_name) = self.parse(sql)?;
+
+        let foreign_keys = match self.parse_statements_parallel(&self.preprocess_sql_for_ast(sql)) {
+            Ok(statements) => self.extract_foreign_keys_from_ast(&statements),
+            Err(_) => Self::extract_foreign_keys_from_string(sql),
+        };
+
+        let mut relationships = Vec::new();
+        for fk in foreign_keys {
+            let table = tables
+                .iter_mut()
+                .find(|t| t.name.eq_ignore_ascii_case(&fk.table));
+            if !fk.referred_columns.is_empty() && fk.referred_columns.len() != fk.columns.len() {
+                let message = format!(
+                    "Foreign key ({}) references {} with {} column(s) ({})",
+                    fk.columns.join(", "),
+                    fk.foreign_table,
+                    fk.referred_columns.len(),
+                    fk.referred_columns.join(", ")
+                );
+                warn!("Skipping foreign key on {}: {}", fk.table, message);
+                if let Some(table) = table {
+                    let mut error = HashMap::new();
+                    error.insert(
+                        "type".to_string(),
+                        serde_json::Value::String("validation_error".to_string()),
+                    );
+                    error.insert(
+                        "field".to_string(),
+                        serde_json::Value::String("foreign_key".to_string()),
+                    );
+                    error.insert("message".to_string(), serde_json::Value::String(message));
+                    table.errors.push(error);
+                }
+                continue;
+            }
+
+            if let Some(table) = table {
+                let composite = fk.columns.len() > 1;
+                let constraint_name = composite.then(|| {
+                    fk.name
+                        .clone()
+                        .unwrap_or_else(|| format!("fk_{}_{}", fk.table, fk.foreign_table))
+                });
+                for (position, (column_name, referred)) in
+                    fk.columns.iter().zip(&fk.referred_columns).enumerate()
+                {
+                    if let Some(column) = table
+                        .columns
+                        .iter_mut()
+                        .find(|c| c.name.eq_ignore_ascii_case(column_name))
+                        && column.foreign_key.is_none()
+                    {
+                        column.foreign_key = Some(ForeignKey {
+                            table_id: fk.foreign_table.clone(),
+                            column_name: referred.clone(),
+                            constraint_name: constraint_name.clone(),
+                            key_position: composite.then_some(position as u32 + 1),
+                        });
+                    }
+                }
+            }
+            relationships.push(fk.into_relationship());
+        }
+        if !relationships.is_empty() {
+            info!(
+                "Discovered {} foreign key relationship(s) from SQL",
+                relationships.len()
+            );
+        }
+        Ok((tables, tables_requiring_name, relationships))
+    }
+
+    /// Collect inline and table-level foreign keys from parsed CREATE TABLE statements.
+    fn extract_foreign_keys_from_ast(&self, statements: &[Statement]) -> Vec<ParsedForeignKey> {
+        let mut foreign_keys = Vec::new();
+        for statement in statements {
+            let Statement::CreateTable(create_table) = statement else {
+                continue;
+            };
+            let Ok(table) = self.extract_table_name_from_ast(&create_table.name) else {
+                continue;
+            };
+
+            for col_def in &create_table.columns {
+                for opt in &col_def.options {
+                    if let ColumnOption::ForeignKey {
+                        foreign_table,
+                        referred_columns,
+                        ..
+                    } = &opt.option
+                        && let Ok(foreign_table) = self.extract_table_name_from_ast(foreign_table)
+                    {
+                        foreign_keys.push(ParsedForeignKey {
+                            name: None,
+                            table: table.clone(),
+                            columns: vec![col_def.name.value.clone()],
+                            foreign_table,
+                            referred_columns: referred_columns
+                                .iter()
+                                .map(|c| c.value.clone())
+                                .collect(),
+                        });
+                    }
+                }
+            }
+
+            for constraint in &create_table.constraints {
+                if let TableConstraint::ForeignKey {
+                    name,
+                    columns,
+                    foreign_table,
+                    referred_columns,
+                    ..
+                } = constraint
+                    && let Ok(foreign_table) = self.extract_table_name_from_ast(foreign_table)
+                {
+                    foreign_keys.push(ParsedForeignKey {
+                        name: name.as_ref().map(|n| n.value.clone()),
+                        table: table.clone(),
+                        columns: columns.iter().map(|c| c.value.clone()).collect(),
+                        foreign_table,
+                        referred_columns: referred_columns
+                            .iter()
+                            .map(|c| c.value.clone())
+                            .collect(),
+                    });
+                }
+            }
+        }
+        foreign_keys
+    }
+
+    /// Collect foreign keys with regular expressions when the SQL cannot be parsed into an AST.
+    fn extract_foreign_keys_from_string(sql: &str) -> Vec<ParsedForeignKey> {
+        let create_re = Regex::new(
+            r#"(?is)CREATE\s+(?:OR\s+REPLACE\s+)?TABLE\s+(?:IF\s+NOT\s+EXISTS\s+)?([\w.`"\[\]]+)\s*\("#,
+        )
+        .unwrap();
+        let fk_re = Regex::new(
+            r#"(?is)(?:CONSTRAINT\s+([\w`"\[\]]+)\s+)?FOREIGN\s+KEY\s*\(([^)]*)\)\s*REFERENCES\s+([\w.`"\[\]]+)\s*(?:\(([^)]*)\))?"#,
+        )
+        .unwrap();
+        let inline_re = Regex::new(
+            r#"(?im)^\s*([\w`"\[\]]+)\s+[^,]*?\bREFERENCES\s+([\w.`"\[\]]+)\s*(?:\(([^)]*)\))?"#,
+        )
+        .unwrap();
+        let unquote = |name: &str| {
+            name.rsplit('.')
+                .next()
+                .unwrap_or(name)
+                .trim_matches(|c| matches!(c, '`' | '"' | '[' | ']'))
+                .to_string()
+        };
+        let identifiers = |list: Option<regex::Match>| -> Vec<String> {
+            list.map(|m| {
+                m.as_str()
+                    .split(',')
+                    .map(|c| unquote(c.trim()))
+                    .filter(|c| !c.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+        };
+
+        let headers: Vec<(usize, usize, String)> = create_re
+            .captures_iter(sql)
+            .filter_map(|c| {
+                let header = c.get(0)?;
+                Some((header.start(), header.end(), unquote(c.get(1)?.as_str())))
+            })
+            .collect();
+        let mut foreign_keys = Vec::new();
+        for (idx, (_, body_start, table)) in headers.iter().enumerate() {
+            let body_end = headers
+                .get(idx + 1)
+                .map(|(start, _, _)| *start)
+                .unwrap_or(sql.len());
+            let body = &sql[*body_start..body_end];
+            for caps in fk_re.captures_iter(body) {
+                foreign_keys.push(ParsedForeignKey {
+                    name: caps.get(1).map(|n| unquote(n.as_str())),
+                    table: table.clone(),
+                    columns: identifiers(caps.get(2)),
+                    foreign_table: unquote(&caps[3]),
+                    referred_columns: identifiers(caps.get(4)),
+                });
+            }
+            for caps in inline_re.captures_iter(body) {
+                let column = unquote(&caps[1]);
+                if matches!(
+                    column.to_uppercase().as_str(),
+                    "FOREIGN" | "CONSTRAINT" | "PRIMARY" | "UNIQUE" | "CHECK"
+                ) {
+                    continue;
+                }
+                foreign_keys.push(ParsedForeignKey {
+                    name: None,
+                    table: table.clone(),
+                    columns: vec![column],
+                    foreign_table: unquote(&caps[2]),
+                    referred_columns: identifiers(caps.get(3)),
+                });
+            }
+        }
+        foreign_keys
+    }
+
     /// Check if SQL is in Liquibase format.
     fn is_liquibase_format(&self, sql: &str) -> bool {
         let sql_upper = sql.to_uppercase();
@@ -307,6 +536,43 @@ impl SQLParser {
         }
     }
 
+    /// Parse SQL statements, spreading large scripts across the rayon thread pool.
+    ///
+    /// The script is split at top-level semicolons and each statement is parsed with a
+    /// per-thread dialect; statements are returned in script order. Short scripts, parsers
+    /// built from a custom dialect, and scripts a statement of which does not parse on its own
+    /// (e.g. procedure bodies containing semicolons) are parsed in one pass instead.
+    fn parse_statements_parallel(&self, sql: &str) -> Result<Vec<Statement>> {
+        let chunks = split_statements(sql);
+        let dialect_name = self.dialect_name.as_str();
+        if chunks.len() < PARALLEL_PARSE_MIN_STATEMENTS
+            || self.dialect.dialect() != Self::dialect_for(dialect_name).dialect()
+        {
+            return self.parse_statements(sql);
+        }
+
+        let parsed: Result<Vec<Vec<Statement>>, _> = chunks
+            .par_iter()
+            .map_init(
+                || Self::dialect_for(dialect_name),
+                |dialect, chunk| Parser::parse_sql(&**dialect, chunk),
+            )
+            .collect();
+        match parsed {
+            Ok(parsed) => {
+                debug!("[SQLParser] Parsed {} statements in parallel", parsed.len());
+                Ok(parsed.into_iter().flatten().collect())
+            }
+            Err(e) => {
+                debug!(
+                    "[SQLParser] Statement did not parse on its own ({}), parsing the whole script",
+                    e
+                );
+                self.parse_statements(sql)
+            }
+        }
+    }
+
     /// Parse SQL from string (fallback method for complex cases).
     fn parse_from_string(&self, sql: &str) -> Result<(Vec<Table>, Vec<TableNameInput>)> {
         let mut tables = Vec::new();
@@ -526,18 +792,26 @@ impl SQLParser {
                 // Set database_type from dialect if available
                 let database_type = Self::dialect_to_database_type(&self.dialect_name);
 
+                // Keep dynamic catalog/schema parts as ${variable} placeholders
+                let (catalog_name, schema_name) = if requires_input {
+                    Self::extract_dynamic_qualifiers(table_name_expr)
+                } else {
+                    (None, None)
+                };
+
                 let table = Table {
                     id: uuid::Uuid::new_v4(),
                     name: name.clone(),
                     columns,
                     database_type,
-                    catalog_name: None,
-                    schema_name: None,
+                    catalog_name,
+                    schema_name,
                     medallion_layers,
                     scd_pattern: None,
                     data_vault_classification: None,
                     modeling_level: None,
                     tags: Vec::new(),
+                    aliases: Vec::new(),
                     odcl_metadata: HashMap::new(),
                     position: None,
                     yaml_file_path: None,
@@ -681,6 +955,52 @@ impl SQLParser {
         }
     }
 
+    /// Extract catalog/schema qualifiers from a dynamic table name expression.
+    ///
+    /// Variables (`:catalog`) become `${catalog}` placeholders so the name can be
+    /// resolved per domain/environment at export time, e.g.
+    /// `IDENTIFIER(:catalog || '.bronze.orders')` yields (`${catalog}`, `bronze`).
+    fn extract_dynamic_qualifiers(expr: &str) -> (Option<String>, Option<String>) {
+        static VAR_RE: OnceLock<Regex> = OnceLock::new();
+        let var_re = VAR_RE.get_or_init(|| Regex::new(r":(\w+)").unwrap());
+        let trimmed = expr.trim();
+
+        let qualified = if trimmed.to_uppercase().starts_with("IDENTIFIER") {
+            let (Some(start), Some(end)) = (trimmed.find('('), trimmed.rfind(')')) else {
+                return (None, None);
+            };
+            trimmed[start + 1..end]
+                .split("||")
+                .map(|part| {
+                    let part = part.trim();
+                    let unquoted = part.trim_matches(|c| c == '\'' || c == '"');
+                    if unquoted.len() < part.len() {
+                        unquoted.to_string()
+                    } else {
+                        var_re.replace_all(part, "$${$1}").to_string()
+                    }
+                })
+                .collect::<String>()
+        } else {
+            let name = trimmed.trim_matches(|c| c == '`' || c == '"' || c == '[' || c == ']');
+            var_re.replace_all(name, "$${$1}").to_string()
+        };
+
+        let parts: Vec<&str> = qualified
+            .split('.')
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .collect();
+        match parts.len() {
+            0 | 1 => (None, None),
+            2 => (None, Some(parts[0].to_string())),
+            n => (
+                Some(parts[n - 3].to_string()),
+                Some(parts[n - 2].to_string()),
+            ),
+        }
+    }
+
     /// Extract table definition from CREATE TABLE statement (AST-based).
     fn extract_table_from_ast(
         &self,
@@ -716,18 +1036,25 @@ impl SQLParser {
         // Set database_type from dialect if available
         let database_type = Self::dialect_to_database_type(&self.dialect_name);
 
+        let (catalog_name, schema_name) = if requires_input {
+            (None, None)
+        } else {
+            Self::extract_qualifiers_from_ast(name)
+        };
+
         let table = Table {
             id: uuid::Uuid::new_v4(),
             name: table_name.clone(),
             columns: parsed_columns,
             database_type,
-            catalog_name: None,
-            schema_name: None,
+            catalog_name,
+            schema_name,
             medallion_layers,
             scd_pattern: None,
             data_vault_classification: None,
             modeling_level: None,
             tags: Vec::new(),
+            aliases: Vec::new(),
             odcl_metadata,
             position: None,
             yaml_file_path: None,
@@ -845,6 +1172,22 @@ impl SQLParser {
         Ok(table_name.clone())
     }
 
+    /// Extract catalog/schema qualifiers of a static table name, e.g. `sales.raw.orders`
+    /// yields (`sales`, `raw`).
+    fn extract_qualifiers_from_ast(
+        name: &sqlparser::ast::ObjectName,
+    ) -> (Option<String>, Option<String>) {
+        let parts: Vec<&str> = name.0.iter().map(|ident| ident.value.as_str()).collect();
+        match parts.len() {
+            0 | 1 => (None, None),
+            2 => (None, Some(parts[0].to_string())),
+            n => (
+                Some(parts[n - 3].to_string()),
+                Some(parts[n - 2].to_string()),
+            ),
+        }
+    }
+
     /// Check if table name is dynamic (requires user input).
     fn is_dynamic_table_name(&self, name: &sqlparser::ast::ObjectName) -> bool {
         // Check if any identifier contains IDENTIFIER() or variable patterns
@@ -929,6 +1272,8 @@ impl SQLParser {
                 Some(ForeignKey {
                     table_id: ref_table_name,
                     column_name: ref_column_name,
+                    constraint_name: None,
+                    key_position: None,
                 })
             } else {
                 None
@@ -965,6 +1310,10 @@ impl SQLParser {
             errors: Vec::new(),
             quality: Vec::new(),
             enum_values: Vec::new(),
+            classification: None,
+            masking_policy: None,
+            glossary_links: Vec::new(),
+            custom_properties: HashMap::new(),
             column_order: 0, // Will be set by extract_columns_from_ast
         });
 
@@ -1023,6 +1372,10 @@ impl SQLParser {
                         errors: Vec::new(),
                         quality: Vec::new(),
                         enum_values: Vec::new(),
+                        classification: None,
+                        masking_policy: None,
+                        glossary_links: Vec::new(),
+                        custom_properties: HashMap::new(),
                         column_order: 0,
                     });
 
@@ -1141,6 +1494,10 @@ impl SQLParser {
                             errors: Vec::new(),
                             quality: Vec::new(),
                             enum_values: Vec::new(),
+                            classification: None,
+                            masking_policy: None,
+                            glossary_links: Vec::new(),
+                            custom_properties: HashMap::new(),
                             column_order: 0,
                         });
                         field_defs.push(format!("{}: STRING", field_name.as_str()));
@@ -1570,6 +1927,10 @@ impl SQLParser {
                 errors: Vec::new(),
                 quality: Vec::new(),
                 enum_values: Vec::new(),
+                classification: None,
+                masking_policy: None,
+                glossary_links: Vec::new(),
+                custom_properties: HashMap::new(),
                 column_order: 0,
             });
 
@@ -1774,6 +2135,10 @@ impl SQLParser {
             errors: Vec::new(),
             quality: Vec::new(),
             enum_values: Vec::new(),
+            classification: None,
+            masking_policy: None,
+            glossary_links: Vec::new(),
+            custom_properties: HashMap::new(),
             column_order: 0,
         }))
     }
@@ -2348,6 +2713,10 @@ impl SQLParser {
                 errors: Vec::new(),
                 quality: Vec::new(),
                 enum_values: Vec::new(),
+                classification: None,
+                masking_policy: None,
+                glossary_links: Vec::new(),
+                custom_properties: HashMap::new(),
                 column_order: 0,
             });
 
@@ -2534,6 +2903,10 @@ impl SQLParser {
                 errors: Vec::new(),
                 quality: Vec::new(),
                 enum_values: Vec::new(),
+                classification: None,
+                masking_policy: None,
+                glossary_links: Vec::new(),
+                custom_properties: HashMap::new(),
                 column_order: 0,
             });
         }
@@ -2688,6 +3061,10 @@ impl SQLParser {
                     errors: Vec::new(),
                     quality: Vec::new(),
                     enum_values: Vec::new(),
+                    classification: None,
+                    masking_policy: None,
+                    glossary_links: Vec::new(),
+                    custom_properties: HashMap::new(),
                     column_order: 0,
                 });
 
@@ -2758,6 +3135,10 @@ impl SQLParser {
                     errors: Vec::new(),
                     quality: Vec::new(),
                     enum_values: Vec::new(),
+                    classification: None,
+                    masking_policy: None,
+                    glossary_links: Vec::new(),
+                    custom_properties: HashMap::new(),
                     column_order: 0,
                 });
             }
@@ -2840,6 +3221,27 @@ impl Default for SQLParser {
     }
 }
 
+/// A foreign key found in a CREATE TABLE statement, referencing tables by name.
+#[derive(Debug, Clone)]
+struct ParsedForeignKey {
+    name: Option<String>,
+    table: String,
+    columns: Vec<String>,
+    foreign_table: String,
+    /// Empty when the constraint references the target's primary key implicitly
+    referred_columns: Vec<String>,
+}
+
+impl ParsedForeignKey {
+    fn into_relationship(self) -> ImportedRelationship {
+        let details = ForeignKeyDetails::from_columns(&self.columns, &self.referred_columns).ok();
+        let mut relationship =
+            ImportedRelationship::foreign_key(self.table, self.foreign_table, details);
+        relationship.notes = self.name.map(|name| format!("Constraint {}", name));
+        relationship
+    }
+}
+
 /// Information about a table that requires name input.
 #[derive(Debug, Clone)]
 pub struct TableNameInput {
@@ -2851,10 +3253,101 @@ pub struct TableNameInput {
     pub original_expression: String,
 }
 
+/// Split SQL into statements at top-level semicolons.
+///
+/// Semicolons inside quoted strings and identifiers, comments and dollar-quoted bodies do not
+/// end a statement. Blank statements are dropped.
+fn split_statements(sql: &str) -> Vec<&str> {
+    let bytes = sql.as_bytes();
+    let find = |from: usize, pattern: &[u8]| {
+        bytes[from.min(bytes.len())..]
+            .windows(pattern.len())
+            .position(|w| w == pattern)
+            .map(|p| from + p)
+    };
+
+    let mut statements = Vec::new();
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        i = match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`') => find(i + 1, &[quote]).map_or(bytes.len(), |e| e + 1),
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                find(i, b"\n").map_or(bytes.len(), |e| e + 1)
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                find(i + 2, b"*/").map_or(bytes.len(), |e| e + 2)
+            }
+            b'$' => {
+                // Dollar quoting: $$ ... $$ or $tag$ ... $tag$
+                let tag_len = bytes[i + 1..]
+                    .iter()
+                    .position(|b| !(b.is_ascii_alphanumeric() || *b == b'_'))
+                    .filter(|&n| bytes.get(i + 1 + n) == Some(&b'$'));
+                match tag_len {
+                    Some(n) => {
+                        let tag = &bytes[i..i + n + 2];
+                        find(i + tag.len(), tag).map_or(bytes.len(), |e| e + tag.len())
+                    }
+                    None => i + 1,
+                }
+            }
+            b';' => {
+                statements.push(&sql[start..i]);
+                start = i + 1;
+                i + 1
+            }
+            _ => i + 1,
+        };
+    }
+    statements.push(&sql[start..]);
+    statements.retain(|s| !s.trim().is_empty());
+    statements
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_split_statements() {
+        let sql = "CREATE TABLE a (x TEXT DEFAULT ';');\n-- a comment; still a comment\n\
+                   CREATE TABLE \"b;c\" (y INT); /* ; */\n\
+                   CREATE FUNCTION f() RETURNS INT AS $body$ SELECT 1; $body$ LANGUAGE sql;\n;";
+        let statements = split_statements(sql);
+        assert_eq!(statements.len(), 3);
+        assert!(statements[0].ends_with("DEFAULT ';')"));
+        assert!(statements[1].contains("\"b;c\" (y INT)"));
+        assert!(statements[2].contains("SELECT 1; $body$"));
+    }
+
+    #[test]
+    fn test_parallel_parse_keeps_script_order() {
+        let sql: String = (0..PARALLEL_PARSE_MIN_STATEMENTS * 2)
+            .map(|i| {
+                format!(
+                    "CREATE TABLE t{i} (id INT PRIMARY KEY, note TEXT DEFAULT 'a;b', parent_id INT REFERENCES t0(id));\n"
+                )
+            })
+            .collect();
+        let parser = SQLParser::with_dialect_name("postgres");
+        let (tables, _, relationships) = parser.parse_with_relationships(&sql).unwrap();
+        let names: Vec<String> = tables.iter().map(|t| t.name.clone()).collect();
+        let expected: Vec<String> = (0..PARALLEL_PARSE_MIN_STATEMENTS * 2)
+            .map(|i| format!("t{i}"))
+            .collect();
+        assert_eq!(names, expected);
+        assert_eq!(relationships.len(), PARALLEL_PARSE_MIN_STATEMENTS * 2);
+        assert_eq!(
+            tables[5]
+                .columns
+                .iter()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["id", "note", "parent_id"]
+        );
+    }
+
     #[test]
     fn test_parse_simple_create_table() {
         let parser = SQLParser::new();
@@ -2908,10 +3401,56 @@ mod tests {
             );
         "#;
 
-        let (tables, _) = parser.parse(sql).unwrap();
+        let (tables, _, relationships) = parser.parse_with_relationships(sql).unwrap();
         assert_eq!(tables.len(), 1);
-        // Note: Foreign key extraction from AST may need adjustment
-        // This test verifies the parser doesn't crash
+        let user_id = tables[0]
+            .columns
+            .iter()
+            .find(|c| c.name == "user_id")
+            .unwrap();
+        assert_eq!(user_id.foreign_key.as_ref().unwrap().table_id, "users");
+        assert_eq!(relationships.len(), 1);
+        assert_eq!(relationships[0].source_table, "orders");
+        assert_eq!(relationships[0].target_table, "users");
+        let details = relationships[0].foreign_key_details.as_ref().unwrap();
+        assert_eq!(details.source_column, "user_id");
+        assert_eq!(details.target_column, "id");
+    }
+
+    #[test]
+    fn test_parse_composite_foreign_key_constraint() {
+        let parser = SQLParser::new();
+        let sql = r#"
+            CREATE TABLE order_lines (
+                order_id INTEGER,
+                line_no INTEGER,
+                CONSTRAINT fk_lines FOREIGN KEY (order_id, line_no) REFERENCES orders (id, line_no)
+            );
+        "#;
+
+        let (tables, _, relationships) = parser.parse_with_relationships(sql).unwrap();
+        assert_eq!(relationships.len(), 1);
+        let details = relationships[0].foreign_key_details.as_ref().unwrap();
+        assert!(details.is_composite());
+        assert_eq!(
+            details.pairs(),
+            vec![("order_id", "id"), ("line_no", "line_no")]
+        );
+        let line_no = tables[0]
+            .columns
+            .iter()
+            .find(|c| c.name == "line_no")
+            .unwrap();
+        let fk = line_no.foreign_key.as_ref().unwrap();
+        assert_eq!(fk.constraint_name.as_deref(), Some("fk_lines"));
+        assert_eq!(fk.key_position, Some(2));
+
+        // String fallback (unparseable SQL) discovers the same constraint
+        let fks = SQLParser::extract_foreign_keys_from_string(sql);
+        assert_eq!(fks.len(), 1);
+        assert_eq!(fks[0].name.as_deref(), Some("fk_lines"));
+        assert_eq!(fks[0].columns, vec!["order_id", "line_no"]);
+        assert_eq!(fks[0].referred_columns, vec!["id", "line_no"]);
     }
 
     #[test]
@@ -3197,6 +3736,8 @@ mod tests {
         assert_eq!(tables.len(), 1);
         assert_eq!(name_inputs.len(), 1); // Should require name input due to variable
         assert_eq!(tables[0].name, "dummy_table_name"); // Should extract from quoted string
+        assert_eq!(tables[0].catalog_name.as_deref(), Some("${dummy_catalog}"));
+        assert_eq!(tables[0].schema_name.as_deref(), Some("bronze"));
         assert_eq!(tables[0].columns.len(), 3);
         assert_eq!(tables[0].columns[0].name, "id");
         assert_eq!(tables[0].columns[0].data_type, "STRING");
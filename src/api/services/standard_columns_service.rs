@@ -0,0 +1,254 @@
+//! Standard columns policy.
+//!
+//! When a workspace enables standard columns in `standard-columns.yaml` at the root of the
+//! workspace data directory, tables created through the API or by an import get a surrogate
+//! key (if they have no primary key) and the configured audit columns appended, so the
+//! conventions hold without reviewers checking for them. A table opts out by setting
+//! `standard_columns: false` in its `odcl_metadata`. Columns the table already has are left
+//! as they are.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use utoipa::ToSchema;
+
+use crate::models::{Column, Table};
+
+/// Policy file at the root of the workspace data directory
+const STANDARD_COLUMNS_FILE: &str = "standard-columns.yaml";
+
+/// `odcl_metadata` key a table sets to `false` to opt out of the policy
+pub const OPT_OUT_KEY: &str = "standard_columns";
+
+/// A column every table gets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StandardColumn {
+    pub name: String,
+    pub data_type: String,
+    #[serde(default)]
+    pub nullable: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl StandardColumn {
+    fn new(name: &str, data_type: &str, nullable: bool, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            nullable,
+            description: Some(description.to_string()),
+        }
+    }
+
+    fn to_column(&self) -> Column {
+        let mut column = Column::new(self.name.clone(), self.data_type.clone());
+        column.nullable = self.nullable;
+        column.description = self.description.clone().unwrap_or_default();
+        column
+    }
+}
+
+/// Surrogate key added to tables without a primary key.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SurrogateKeyConvention {
+    /// Column name, where `{table}` is replaced by the table name (e.g. `{table}_id`)
+    pub name_template: String,
+    pub data_type: String,
+}
+
+impl Default for SurrogateKeyConvention {
+    fn default() -> Self {
+        Self {
+            name_template: "{table}_id".to_string(),
+            data_type: "BIGINT".to_string(),
+        }
+    }
+}
+
+impl SurrogateKeyConvention {
+    pub fn column_name(&self, table_name: &str) -> String {
+        self.name_template.replace("{table}", table_name)
+    }
+}
+
+fn default_audit_columns() -> Vec<StandardColumn> {
+    vec![
+        StandardColumn::new("created_at", "TIMESTAMP", false, "When the row was created"),
+        StandardColumn::new(
+            "updated_at",
+            "TIMESTAMP",
+            false,
+            "When the row was last updated",
+        ),
+        StandardColumn::new("created_by", "VARCHAR(255)", true, "Who created the row"),
+    ]
+}
+
+/// Standard columns configured for the workspace.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct StandardColumnsPolicy {
+    /// Apply the policy to new tables
+    #[serde(default)]
+    pub enabled: bool,
+    /// Surrogate key for tables without a primary key; none are added if omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub surrogate_key: Option<SurrogateKeyConvention>,
+    /// Columns appended to every table
+    #[serde(default = "default_audit_columns")]
+    pub audit_columns: Vec<StandardColumn>,
+}
+
+impl Default for StandardColumnsPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            surrogate_key: Some(SurrogateKeyConvention::default()),
+            audit_columns: default_audit_columns(),
+        }
+    }
+}
+
+impl StandardColumnsPolicy {
+    pub fn load(workspace_data_dir: &Path) -> Self {
+        std::fs::read_to_string(workspace_data_dir.join(STANDARD_COLUMNS_FILE))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, workspace_data_dir: &Path) -> Result<()> {
+        let path = workspace_data_dir.join(STANDARD_COLUMNS_FILE);
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Check column names and types are set and unique.
+    pub fn check_definitions(&self) -> std::result::Result<(), String> {
+        if let Some(key) = &self.surrogate_key
+            && (key.name_template.trim().is_empty() || key.data_type.trim().is_empty())
+        {
+            return Err("Surrogate key name template and data type are required".to_string());
+        }
+        let mut names: Vec<String> = Vec::new();
+        for column in &self.audit_columns {
+            if column.name.trim().is_empty() || column.data_type.trim().is_empty() {
+                return Err("Audit column name and data type are required".to_string());
+            }
+            if names.iter().any(|n| n.eq_ignore_ascii_case(&column.name)) {
+                return Err(format!("Duplicate audit column '{}'", column.name));
+            }
+            names.push(column.name.clone());
+        }
+        Ok(())
+    }
+
+    /// Whether a table opted out of the policy
+    pub fn opted_out(table: &Table) -> bool {
+        table
+            .odcl_metadata
+            .get(OPT_OUT_KEY)
+            .and_then(|v| v.as_bool())
+            == Some(false)
+    }
+
+    /// Add the standard columns a new table is missing, returning the names added.
+    pub fn apply(&self, table: &mut Table) -> Vec<String> {
+        if !self.enabled || Self::opted_out(table) {
+            return Vec::new();
+        }
+        let has = |table: &Table, name: &str| {
+            table
+                .columns
+                .iter()
+                .any(|c| c.name.eq_ignore_ascii_case(name))
+        };
+        let mut added = Vec::new();
+
+        if let Some(key) = &self.surrogate_key
+            && !table.columns.iter().any(|c| c.primary_key)
+        {
+            let name = key.column_name(&table.name);
+            if !has(table, &name) {
+                let mut column = Column::new(name.clone(), key.data_type.clone());
+                column.nullable = false;
+                column.primary_key = true;
+                column.description = "Surrogate key".to_string();
+                table.columns.insert(0, column);
+                added.push(name);
+            }
+        }
+        for standard in &self.audit_columns {
+            if !has(table, &standard.name) {
+                table.columns.push(standard.to_column());
+                added.push(standard.name.clone());
+            }
+        }
+
+        if !added.is_empty() {
+            for (index, column) in table.columns.iter_mut().enumerate() {
+                column.column_order = index as i32;
+            }
+        }
+        added
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_adds_missing_standard_columns_unless_opted_out() {
+        let policy = StandardColumnsPolicy {
+            enabled: true,
+            ..Default::default()
+        };
+        let mut orders = Table::new(
+            "orders".to_string(),
+            vec![
+                Column::new("total".to_string(), "DECIMAL".to_string()),
+                Column::new("Created_At".to_string(), "TIMESTAMP".to_string()),
+            ],
+        );
+        assert_eq!(
+            policy.apply(&mut orders),
+            vec!["orders_id", "updated_at", "created_by"]
+        );
+        let names: Vec<&str> = orders.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "orders_id",
+                "total",
+                "Created_At",
+                "updated_at",
+                "created_by"
+            ]
+        );
+        assert!(orders.columns[0].primary_key && !orders.columns[0].nullable);
+        assert_eq!(orders.columns[4].column_order, 4);
+        // Applying again changes nothing
+        assert!(policy.apply(&mut orders).is_empty());
+
+        // Tables with a primary key keep it; opted-out tables are left alone
+        let mut keyed = Table::new("keyed".to_string(), vec![]);
+        keyed
+            .columns
+            .push(Column::new("code".to_string(), "TEXT".to_string()));
+        keyed.columns[0].primary_key = true;
+        assert_eq!(policy.apply(&mut keyed).len(), 3);
+        assert!(!keyed.columns.iter().any(|c| c.name == "keyed_id"));
+
+        let mut legacy = Table::new("legacy".to_string(), vec![]);
+        legacy
+            .odcl_metadata
+            .insert(OPT_OUT_KEY.to_string(), serde_json::Value::Bool(false));
+        assert!(policy.apply(&mut legacy).is_empty());
+        assert!(
+            StandardColumnsPolicy::default()
+                .apply(&mut orders)
+                .is_empty()
+        );
+    }
+}
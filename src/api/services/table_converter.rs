@@ -157,6 +157,10 @@ pub fn sdk_table_to_api_table(sdk_table: data_modelling_sdk::import::TableData)
             errors: Vec::new(),
             quality: Vec::new(),
             enum_values: Vec::new(),
+            classification: None,
+            masking_policy: None,
+            glossary_links: Vec::new(),
+            custom_properties: HashMap::new(),
             column_order: 0,
         })
         .collect();
@@ -175,6 +179,7 @@ pub fn sdk_table_to_api_table(sdk_table: data_modelling_sdk::import::TableData)
         data_vault_classification: None,
         modeling_level: None,
         tags: Vec::new(),
+        aliases: Vec::new(),
         odcl_metadata: HashMap::new(),
         position: None,
         yaml_file_path: None,
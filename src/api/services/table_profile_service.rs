@@ -0,0 +1,167 @@
+//! Observed data profiles of tables.
+//!
+//! Profiling jobs upload statistics (row count and per-column null %, distinct count,
+//! min/max) for a table. Uploads are kept per table in the domain's `profiles.yaml`, newest
+//! last, so the model can be enriched with what the data actually looks like: the latest
+//! profile is shown in table payloads and the docs export, and model validation compares it
+//! with the declared constraints.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, HashMap};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::DataModel;
+
+/// File name of a domain's profiles
+pub const PROFILES_FILE: &str = "profiles.yaml";
+/// Profiles kept per table; older uploads are dropped
+pub const MAX_PROFILE_HISTORY: usize = 20;
+
+/// Observed statistics of one column
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ColumnProfile {
+    /// Share of null values, 0-100
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub null_percent: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distinct_count: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub min: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schema(value_type = Option<Object>)]
+    pub max: Option<Value>,
+}
+
+/// One profiling run of a table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct TableProfile {
+    pub profiled_at: DateTime<Utc>,
+    pub uploaded_by: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub row_count: Option<u64>,
+    /// Statistics keyed by column name
+    #[serde(default)]
+    pub columns: BTreeMap<String, ColumnProfile>,
+}
+
+impl TableProfile {
+    /// Check the statistics are in range.
+    pub fn validate(&self) -> Result<(), String> {
+        for (name, column) in &self.columns {
+            if column
+                .null_percent
+                .is_some_and(|p| !(0.0..=100.0).contains(&p))
+            {
+                return Err(format!(
+                    "null_percent of '{}' must be between 0 and 100",
+                    name
+                ));
+            }
+            if let (Some(distinct), Some(rows)) = (column.distinct_count, self.row_count)
+                && distinct > rows
+            {
+                return Err(format!(
+                    "distinct_count of '{}' exceeds the row count",
+                    name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Profiles of a domain's tables (`profiles.yaml`)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TableProfiles {
+    /// Profiling runs keyed by table ID, oldest first
+    #[serde(default)]
+    pub tables: HashMap<Uuid, Vec<TableProfile>>,
+}
+
+impl TableProfiles {
+    pub fn is_empty(&self) -> bool {
+        self.tables.is_empty()
+    }
+
+    /// Most recent profile of a table.
+    pub fn latest(&self, table_id: Uuid) -> Option<&TableProfile> {
+        self.tables
+            .get(&table_id)
+            .and_then(|history| history.last())
+    }
+
+    pub fn history(&self, table_id: Uuid) -> &[TableProfile] {
+        self.tables.get(&table_id).map_or(&[], Vec::as_slice)
+    }
+
+    /// Record a profiling run, keeping at most `MAX_PROFILE_HISTORY` per table.
+    pub fn record(&mut self, table_id: Uuid, profile: TableProfile) {
+        let history = self.tables.entry(table_id).or_default();
+        history.push(profile);
+        history.sort_by_key(|p| p.profiled_at);
+        if history.len() > MAX_PROFILE_HISTORY {
+            history.drain(..history.len() - MAX_PROFILE_HISTORY);
+        }
+    }
+
+    /// Attach the latest profile as `odcl_metadata.profile` for the docs export.
+    pub fn apply_to_model(&self, model: &DataModel) -> DataModel {
+        let mut model = model.clone();
+        for table in &mut model.tables {
+            if let Some(profile) = self.latest(table.id) {
+                table
+                    .odcl_metadata
+                    .insert("profile".to_string(), json!(profile));
+            }
+        }
+        model
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn profile(profiled_at: DateTime<Utc>, rows: u64) -> TableProfile {
+        TableProfile {
+            profiled_at,
+            uploaded_by: "etl@example.com".to_string(),
+            row_count: Some(rows),
+            columns: BTreeMap::from([(
+                "id".to_string(),
+                ColumnProfile {
+                    null_percent: Some(0.0),
+                    distinct_count: Some(rows),
+                    ..Default::default()
+                },
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_history_is_capped_and_latest_wins() {
+        let table_id = Uuid::new_v4();
+        let start = Utc::now();
+        let mut profiles = TableProfiles::default();
+        for i in 0..(MAX_PROFILE_HISTORY as i64 + 5) {
+            profiles.record(table_id, profile(start + Duration::hours(i), i as u64));
+        }
+        // A late upload of an older run does not replace the latest profile
+        profiles.record(table_id, profile(start, 1));
+
+        assert_eq!(profiles.history(table_id).len(), MAX_PROFILE_HISTORY);
+        assert_eq!(
+            profiles.latest(table_id).unwrap().row_count,
+            Some(MAX_PROFILE_HISTORY as u64 + 4)
+        );
+
+        let mut invalid = profile(start, 10);
+        invalid.columns.get_mut("id").unwrap().distinct_count = Some(11);
+        assert!(invalid.validate().is_err());
+    }
+}
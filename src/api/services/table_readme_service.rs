@@ -0,0 +1,121 @@
+//! Table README documentation.
+//!
+//! Long-form Markdown documentation for a table is stored beside its YAML as
+//! `tables/<table name>.md`. READMEs are rendered in the docs export and included in
+//! table search.
+
+use anyhow::{Context, Result};
+use serde_json::json;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::models::DataModel;
+
+/// Largest README accepted (bytes)
+pub const MAX_README_BYTES: usize = 512 * 1024;
+
+/// Path of a table's README in a domain directory
+pub fn readme_path(domain_dir: &Path, table_name: &str) -> PathBuf {
+    domain_dir.join("tables").join(format!("{}.md", table_name))
+}
+
+/// Load a table's README, if it has one.
+pub fn load_readme(domain_dir: &Path, table_name: &str) -> Option<String> {
+    std::fs::read_to_string(readme_path(domain_dir, table_name))
+        .ok()
+        .filter(|content| !content.trim().is_empty())
+}
+
+/// Save a table's README. Blank content removes it.
+pub fn save_readme(domain_dir: &Path, table_name: &str, content: &str) -> Result<()> {
+    let path = readme_path(domain_dir, table_name);
+    if content.trim().is_empty() {
+        if path.exists() {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// All table READMEs of a domain, keyed by table name.
+#[derive(Debug, Clone, Default)]
+pub struct TableReadmes {
+    by_table: HashMap<String, String>,
+}
+
+impl TableReadmes {
+    /// Load every `tables/*.md` file of a domain directory.
+    pub fn load(domain_dir: &Path) -> Self {
+        let mut by_table = HashMap::new();
+        if let Ok(entries) = std::fs::read_dir(domain_dir.join("tables")) {
+            for path in entries.flatten().map(|e| e.path()) {
+                if path.extension().is_some_and(|ext| ext == "md")
+                    && let Some(name) = path.file_stem().and_then(|s| s.to_str())
+                    && let Ok(content) = std::fs::read_to_string(&path)
+                    && !content.trim().is_empty()
+                {
+                    by_table.insert(name.to_string(), content);
+                }
+            }
+        }
+        Self { by_table }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_table.is_empty()
+    }
+
+    pub fn get(&self, table_name: &str) -> Option<&str> {
+        self.by_table.get(table_name).map(String::as_str)
+    }
+
+    /// Whether a table's README contains the (lowercase) search term.
+    pub fn matches(&self, table_name: &str, needle: &str) -> bool {
+        self.get(table_name)
+            .is_some_and(|readme| readme.to_lowercase().contains(needle))
+    }
+
+    /// Attach READMEs as `odcl_metadata.readme` for the docs export.
+    pub fn apply_to_model(&self, model: &DataModel) -> DataModel {
+        let mut model = model.clone();
+        for table in &mut model.tables {
+            if let Some(readme) = self.by_table.get(&table.name) {
+                table
+                    .odcl_metadata
+                    .insert("readme".to_string(), json!(readme));
+            }
+        }
+        model
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readme_roundtrip_and_search() {
+        let dir = tempfile::tempdir().unwrap();
+        save_readme(
+            dir.path(),
+            "orders",
+            "# Orders\n\nOne row per **checkout**.",
+        )
+        .unwrap();
+        assert!(readme_path(dir.path(), "orders").exists());
+
+        let readmes = TableReadmes::load(dir.path());
+        assert!(readmes.matches("orders", "checkout"));
+        assert!(!readmes.matches("customers", "checkout"));
+
+        save_readme(dir.path(), "orders", "  ").unwrap();
+        assert!(load_readme(dir.path(), "orders").is_none());
+        assert!(TableReadmes::load(dir.path()).is_empty());
+    }
+}
@@ -0,0 +1,393 @@
+//! Opt-in anonymous usage telemetry.
+//!
+//! When enabled, the server counts which API features are used, which import formats are
+//! used and how large the loaded models are (as size buckets), and periodically reports the
+//! counts to a configurable endpoint so maintainers can prioritize format support. Reports
+//! never contain emails, domain, table or column names, or ids; installations are identified
+//! by a random id stored in the buffer directory. Every report is first appended to a local
+//! buffer (`pending.jsonl`) and removed once the endpoint accepted it, so reports survive
+//! restarts and endpoint outages. `GET /api/v1/telemetry` shows exactly what is sent.
+//!
+//! Configuration (environment variables):
+//! - `TELEMETRY_ENABLED` (default false): the opt-in switch; `DO_NOT_TRACK=1` always disables
+//! - `TELEMETRY_ENDPOINT`: URL reports are POSTed to; without it reports are only buffered
+//! - `TELEMETRY_BUFFER_DIR`: local buffer (default `<workspace data>/.telemetry`)
+//! - `TELEMETRY_INTERVAL_SECS` (default 86400): length of a reporting period
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::local_mode::local_mode;
+
+/// Buffered reports not yet accepted by the endpoint
+const PENDING_FILE: &str = "pending.jsonl";
+/// Random id of this installation
+const INSTALLATION_ID_FILE: &str = "installation-id";
+/// Oldest buffered reports are dropped beyond this many
+const MAX_PENDING_REPORTS: usize = 100;
+const DEFAULT_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Telemetry settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TelemetryConfig {
+    pub enabled: bool,
+    pub endpoint: Option<String>,
+    pub buffer_dir: PathBuf,
+    pub interval: Duration,
+}
+
+impl TelemetryConfig {
+    /// Read telemetry settings from environment variables.
+    pub fn from_env() -> Self {
+        Self::from_lookup(|key| std::env::var(key).ok())
+    }
+
+    /// Read telemetry settings using the given variable lookup.
+    pub fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let flag = |key: &str| {
+            lookup(key)
+                .map(|v| matches!(v.to_lowercase().as_str(), "1" | "true" | "yes" | "on"))
+                .unwrap_or(false)
+        };
+        let non_empty = |key: &str| {
+            lookup(key)
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let buffer_dir = non_empty("TELEMETRY_BUFFER_DIR")
+            .map(PathBuf::from)
+            .or_else(|| local_mode().map(|local| local.data_dir.join(".telemetry")))
+            .or_else(|| non_empty("WORKSPACE_DATA").map(|d| PathBuf::from(d).join(".telemetry")))
+            .unwrap_or_else(|| PathBuf::from(".telemetry"));
+        Self {
+            enabled: flag("TELEMETRY_ENABLED") && !flag("DO_NOT_TRACK"),
+            endpoint: non_empty("TELEMETRY_ENDPOINT"),
+            buffer_dir,
+            interval: non_empty("TELEMETRY_INTERVAL_SECS")
+                .and_then(|v| v.parse::<u64>().ok())
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_INTERVAL),
+        }
+    }
+}
+
+/// Anonymous usage counts of one reporting period
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct UsageReport {
+    pub installation_id: Uuid,
+    /// Server version
+    pub version: String,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// Requests per API feature, e.g. `tables`, `saved-filters`, `export`
+    pub features: BTreeMap<String, u64>,
+    /// Import requests per format, e.g. `sql`, `avro`
+    pub import_formats: BTreeMap<String, u64>,
+    /// Loaded domains per table-count bucket, e.g. `11-50`
+    pub model_sizes: BTreeMap<String, u64>,
+}
+
+impl UsageReport {
+    fn is_empty(&self) -> bool {
+        self.features.is_empty() && self.import_formats.is_empty() && self.model_sizes.is_empty()
+    }
+}
+
+/// Counters of the current period
+#[derive(Debug)]
+struct UsageCounters {
+    period_start: DateTime<Utc>,
+    features: BTreeMap<String, u64>,
+    import_formats: BTreeMap<String, u64>,
+    /// Latest size bucket per domain; domain ids are never reported
+    model_sizes: HashMap<Uuid, &'static str>,
+}
+
+impl UsageCounters {
+    fn new() -> Self {
+        Self {
+            period_start: Utc::now(),
+            features: BTreeMap::new(),
+            import_formats: BTreeMap::new(),
+            model_sizes: HashMap::new(),
+        }
+    }
+
+    fn report(&self, installation_id: Uuid) -> UsageReport {
+        let mut model_sizes = BTreeMap::new();
+        for bucket in self.model_sizes.values() {
+            *model_sizes.entry(bucket.to_string()).or_insert(0) += 1;
+        }
+        UsageReport {
+            installation_id,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            period_start: self.period_start,
+            period_end: Utc::now(),
+            features: self.features.clone(),
+            import_formats: self.import_formats.clone(),
+            model_sizes,
+        }
+    }
+}
+
+/// Feature and import format of an API request path, e.g.
+/// `/api/v1/workspace/domains/sales/import/sql` is feature `import` with format `sql`.
+/// Domain names and ids are skipped; health and documentation requests are not counted.
+pub fn classify_path(path: &str) -> Option<(String, Option<String>)> {
+    let (_, rest) = path.split_once("/api/v1/")?;
+    let segments: Vec<&str> = rest.split('/').filter(|s| !s.is_empty()).collect();
+    let (feature, next) = match segments.as_slice() {
+        ["workspace", "domains", _, feature, rest @ ..] => (*feature, rest.first()),
+        ["workspace", "domains", ..] => ("domains", None),
+        ["workspace", feature, rest @ ..] => (*feature, rest.first()),
+        [feature, ..] => (*feature, None),
+        [] => return None,
+    };
+    let is_name = |s: &str| {
+        !s.is_empty()
+            && s.len() <= 40
+            && Uuid::parse_str(s).is_err()
+            && s.chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+    };
+    if !is_name(feature) || matches!(feature, "health" | "openapi.json" | "swagger") {
+        return None;
+    }
+    let format = (feature == "import")
+        .then(|| next.copied().filter(|f| is_name(f)).map(str::to_string))
+        .flatten();
+    Some((feature.to_string(), format))
+}
+
+/// Size bucket of a model with `tables` tables.
+pub fn size_bucket(tables: usize) -> &'static str {
+    match tables {
+        0 => "0",
+        1..=10 => "1-10",
+        11..=50 => "11-50",
+        51..=200 => "51-200",
+        201..=1000 => "201-1000",
+        _ => "1000+",
+    }
+}
+
+/// Usage telemetry collector and reporter.
+pub struct Telemetry {
+    pub config: TelemetryConfig,
+    counters: Mutex<UsageCounters>,
+    http_client: reqwest::Client,
+}
+
+impl Telemetry {
+    pub fn new(config: TelemetryConfig) -> Self {
+        Self {
+            config,
+            counters: Mutex::new(UsageCounters::new()),
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    /// Create the collector from environment variables.
+    pub fn from_env() -> Self {
+        Self::new(TelemetryConfig::from_env())
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Count an API request by its path.
+    pub fn record_request(&self, path: &str) {
+        if !self.is_enabled() {
+            return;
+        }
+        let Some((feature, format)) = classify_path(path) else {
+            return;
+        };
+        let mut counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+        *counters.features.entry(feature).or_insert(0) += 1;
+        if let Some(format) = format {
+            *counters.import_formats.entry(format).or_insert(0) += 1;
+        }
+    }
+
+    /// Record the current size of a loaded domain model.
+    pub fn record_model_size(&self, domain_id: Uuid, tables: usize) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+        counters.model_sizes.insert(domain_id, size_bucket(tables));
+    }
+
+    /// Random id of this installation, created on first use.
+    fn installation_id(&self) -> Result<Uuid> {
+        let path = self.config.buffer_dir.join(INSTALLATION_ID_FILE);
+        if let Ok(content) = std::fs::read_to_string(&path)
+            && let Ok(id) = Uuid::parse_str(content.trim())
+        {
+            return Ok(id);
+        }
+        let id = Uuid::new_v4();
+        std::fs::create_dir_all(&self.config.buffer_dir)?;
+        std::fs::write(&path, id.to_string())?;
+        Ok(id)
+    }
+
+    /// Report of the current period so far, without resetting it.
+    pub fn current_report(&self) -> UsageReport {
+        let installation_id = self.installation_id().unwrap_or_default();
+        self.counters
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .report(installation_id)
+    }
+
+    /// Reports buffered locally and not yet accepted by the endpoint.
+    pub fn pending_reports(&self) -> Vec<UsageReport> {
+        std::fs::read_to_string(self.config.buffer_dir.join(PENDING_FILE))
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+
+    fn write_pending(&self, reports: &[UsageReport]) -> Result<()> {
+        std::fs::create_dir_all(&self.config.buffer_dir)?;
+        let start = reports.len().saturating_sub(MAX_PENDING_REPORTS);
+        let mut content = String::new();
+        for report in &reports[start..] {
+            content.push_str(&serde_json::to_string(report)?);
+            content.push('\n');
+        }
+        std::fs::write(self.config.buffer_dir.join(PENDING_FILE), content)?;
+        Ok(())
+    }
+
+    /// Close the current period, buffer its report and send all buffered reports.
+    pub async fn flush(&self) -> Result<()> {
+        let installation_id = self.installation_id()?;
+        let report = {
+            let mut counters = self.counters.lock().unwrap_or_else(|e| e.into_inner());
+            let report = counters.report(installation_id);
+            *counters = UsageCounters::new();
+            report
+        };
+        let mut pending = self.pending_reports();
+        if !report.is_empty() {
+            pending.push(report);
+            self.write_pending(&pending)?;
+        }
+
+        let Some(endpoint) = &self.config.endpoint else {
+            return Ok(());
+        };
+        if pending.is_empty() {
+            return Ok(());
+        }
+        let response = self
+            .http_client
+            .post(endpoint)
+            .timeout(SEND_TIMEOUT)
+            .json(&serde_json::json!({ "reports": pending }))
+            .send()
+            .await
+            .context("Failed to send usage reports")?;
+        if !response.status().is_success() {
+            bail!("Telemetry endpoint returned {}", response.status());
+        }
+        self.write_pending(&[])?;
+        info!("[Telemetry] Sent {} usage report(s)", pending.len());
+        Ok(())
+    }
+}
+
+/// Report usage once per period until the process exits.
+pub async fn start_telemetry_reporter(telemetry: std::sync::Arc<Telemetry>) {
+    if !telemetry.is_enabled() {
+        return;
+    }
+    info!(
+        "[Telemetry] Anonymous usage reporting enabled (buffer: {:?}, endpoint: {})",
+        telemetry.config.buffer_dir,
+        telemetry.config.endpoint.as_deref().unwrap_or("none")
+    );
+    let mut interval = tokio::time::interval(telemetry.config.interval);
+    // The first tick completes immediately; skip it so a period has passed before reporting
+    interval.tick().await;
+    loop {
+        interval.tick().await;
+        if let Err(e) = telemetry.flush().await {
+            warn!("[Telemetry] Usage report kept in local buffer: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_path_skips_names_and_ids() {
+        assert_eq!(
+            classify_path("/api/v1/workspace/domains/sales/import/sql/text"),
+            Some(("import".to_string(), Some("sql".to_string())))
+        );
+        assert_eq!(
+            classify_path(&format!(
+                "/modelling/api/v1/workspace/domains/sales/tables/{}",
+                Uuid::new_v4()
+            )),
+            Some(("tables".to_string(), None))
+        );
+        assert_eq!(
+            classify_path("/api/v1/workspace/command-index"),
+            Some(("command-index".to_string(), None))
+        );
+        assert_eq!(classify_path("/api/v1/health"), None);
+        assert_eq!(classify_path("/assets/app.js"), None);
+    }
+
+    #[tokio::test]
+    async fn test_flush_buffers_reports_without_endpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let telemetry = Telemetry::new(TelemetryConfig {
+            enabled: true,
+            endpoint: None,
+            buffer_dir: dir.path().to_path_buf(),
+            interval: DEFAULT_INTERVAL,
+        });
+        telemetry.record_request("/api/v1/workspace/domains/sales/import/avro");
+        telemetry.record_request("/api/v1/workspace/domains/sales/import/avro");
+        let domain_id = Uuid::new_v4();
+        telemetry.record_model_size(domain_id, 5);
+        telemetry.record_model_size(domain_id, 12);
+
+        telemetry.flush().await.unwrap();
+        let pending = telemetry.pending_reports();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].import_formats["avro"], 2);
+        assert_eq!(
+            pending[0].model_sizes,
+            BTreeMap::from([("11-50".to_string(), 1)])
+        );
+
+        // Empty periods are not buffered
+        telemetry.flush().await.unwrap();
+        assert_eq!(telemetry.pending_reports().len(), 1);
+        assert_eq!(
+            pending[0].installation_id,
+            telemetry.current_report().installation_id
+        );
+    }
+}
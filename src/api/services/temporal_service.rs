@@ -0,0 +1,179 @@
+//! Temporal table modeling.
+//!
+//! A table marked temporal (`odcl_metadata.temporal`) tracks system time, application time
+//! or both. Marking it adds the period columns it is missing; system-versioned tables also
+//! get a history table definition, and the SQL export renders the dialect's temporal DDL.
+
+use crate::models::{Column, Table, TemporalSettings};
+
+/// Period columns the settings call for: system time first, then application time
+pub fn period_columns(settings: &TemporalSettings) -> Vec<Column> {
+    let mut columns = Vec::new();
+    let mut push = |name: &str, description: &str| {
+        let mut column = Column::new(name.to_string(), settings.period_data_type.clone());
+        column.nullable = false;
+        column.description = description.to_string();
+        columns.push(column);
+    };
+    if settings.kind.has_system_time() {
+        push(
+            &settings.system_start_column,
+            "When this row version became current (system time)",
+        );
+        push(
+            &settings.system_end_column,
+            "When this row version was superseded (system time)",
+        );
+    }
+    if settings.kind.has_application_time() {
+        push(
+            &settings.valid_from_column,
+            "Start of the period the row is valid in (application time)",
+        );
+        push(
+            &settings.valid_to_column,
+            "End of the period the row is valid in (application time)",
+        );
+    }
+    columns
+}
+
+/// Append the period columns the table is missing (matched case-insensitively) and return
+/// their names.
+pub fn add_period_columns(table: &mut Table, settings: &TemporalSettings) -> Vec<String> {
+    let mut added = Vec::new();
+    for mut column in period_columns(settings) {
+        if table
+            .columns
+            .iter()
+            .any(|c| c.name.eq_ignore_ascii_case(&column.name))
+        {
+            continue;
+        }
+        column.column_order = table.columns.len() as i32;
+        added.push(column.name.clone());
+        table.columns.push(column);
+    }
+    added
+}
+
+/// Name of a system-versioned table's history table
+pub fn history_table_name(table: &Table, settings: &TemporalSettings) -> String {
+    settings
+        .history_table
+        .clone()
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| format!("{}_history", table.name))
+}
+
+/// Definition of a system-versioned table's history table: the table's columns without
+/// keys or constraints, as every version of a row is kept. None if the table has no
+/// system time.
+pub fn history_table(table: &Table, settings: &TemporalSettings) -> Option<Table> {
+    if !settings.kind.has_system_time() {
+        return None;
+    }
+    let columns = table
+        .columns
+        .iter()
+        .map(|column| {
+            let mut column = column.clone();
+            column.primary_key = false;
+            column.secondary_key = false;
+            column.composite_key = None;
+            column.foreign_key = None;
+            column.constraints = Vec::new();
+            column
+        })
+        .collect();
+    let mut history = Table::new(history_table_name(table, settings), columns);
+    history.catalog_name = table.catalog_name.clone();
+    history.schema_name = table.schema_name.clone();
+    history.database_type = table.database_type;
+    history.medallion_layers = table.medallion_layers.clone();
+    history.odcl_metadata.insert(
+        "description".to_string(),
+        serde_json::json!(format!("Row versions of {}", table.name)),
+    );
+    Some(history)
+}
+
+/// Problems with a table's temporal settings: period columns that are missing or shared.
+pub fn temporal_problems(table: &Table, settings: &TemporalSettings) -> Vec<String> {
+    let mut problems = Vec::new();
+    let names: Vec<String> = period_columns(settings)
+        .into_iter()
+        .map(|c| c.name)
+        .collect();
+    for (i, name) in names.iter().enumerate() {
+        if name.trim().is_empty() {
+            problems.push("Temporal period column has an empty name".to_string());
+        } else if names[..i].iter().any(|n| n.eq_ignore_ascii_case(name)) {
+            problems.push(format!("Period column '{}' is used twice", name));
+        } else if !table
+            .columns
+            .iter()
+            .any(|c| c.name.eq_ignore_ascii_case(name))
+        {
+            problems.push(format!(
+                "{} table has no period column '{}'",
+                settings.kind.as_str(),
+                name
+            ));
+        }
+    }
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::TemporalKind;
+
+    #[test]
+    fn test_bitemporal_table_gets_period_columns_and_history_table() {
+        let mut id = Column::new("id".to_string(), "BIGINT".to_string());
+        id.primary_key = true;
+        let mut table = Table::new(
+            "policies".to_string(),
+            vec![
+                id,
+                Column::new("VALID_FROM".to_string(), "DATE".to_string()),
+            ],
+        );
+        let settings: TemporalSettings =
+            serde_json::from_value(serde_json::json!({ "kind": "bitemporal" })).unwrap();
+        assert_eq!(settings.kind, TemporalKind::Bitemporal);
+        assert_eq!(
+            temporal_problems(&table, &settings),
+            vec![
+                "bitemporal table has no period column 'sys_start'",
+                "bitemporal table has no period column 'sys_end'",
+                "bitemporal table has no period column 'valid_to'"
+            ]
+        );
+
+        // Existing period columns are kept as they are
+        assert_eq!(
+            add_period_columns(&mut table, &settings),
+            vec!["sys_start", "sys_end", "valid_to"]
+        );
+        assert_eq!(table.columns[1].data_type, "DATE");
+        assert_eq!(table.columns[4].column_order, 4);
+        assert!(!table.columns[4].nullable);
+        assert!(temporal_problems(&table, &settings).is_empty());
+        assert!(add_period_columns(&mut table, &settings).is_empty());
+
+        let history = history_table(&table, &settings).unwrap();
+        assert_eq!(history.name, "policies_history");
+        assert_eq!(history.columns.len(), 5);
+        assert!(!history.columns[0].primary_key);
+
+        let application_time = TemporalSettings {
+            kind: TemporalKind::ApplicationTime,
+            ..settings
+        };
+        assert!(history_table(&table, &application_time).is_none());
+        assert_eq!(period_columns(&application_time).len(), 2);
+    }
+}
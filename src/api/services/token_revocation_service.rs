@@ -0,0 +1,247 @@
+//! Revocation list of refresh tokens.
+//!
+//! Refreshing rotates the refresh token: the presented token is added to the revocation list
+//! and a new pair is issued. A refresh token presented again after it was rotated means it was
+//! copied, so the whole session is revoked. Entries are kept until the token would have expired.
+//!
+//! The list is stored in the storage backend:
+//! - file mode: `revoked_refresh_tokens.jsonl` in the workspace data directory
+//! - PostgreSQL mode: the `revoked_refresh_tokens` table
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use tokio::sync::Mutex;
+
+use crate::services::jwt_service::Claims;
+
+/// File holding the revocation list in file mode
+pub const REVOCATION_LIST_FILE: &str = "revoked_refresh_tokens.jsonl";
+
+/// A revoked refresh token
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RevokedRefreshToken {
+    /// Token id: the `jti` claim, or a hash of the token for tokens issued without one
+    pub token_id: String,
+    pub session_id: String,
+    pub revoked_at: DateTime<Utc>,
+    /// Expiry of the token; the entry can be dropped after it
+    pub expires_at: DateTime<Utc>,
+}
+
+impl RevokedRefreshToken {
+    /// Entry revoking the refresh token `token` with `claims`, as of now
+    pub fn new(token: &str, claims: &Claims) -> Self {
+        Self {
+            token_id: token_id(token, claims),
+            session_id: claims.session_id.clone(),
+            revoked_at: Utc::now(),
+            expires_at: DateTime::from_timestamp(claims.exp, 0).unwrap_or_else(Utc::now),
+        }
+    }
+}
+
+/// Id of a refresh token on the revocation list
+pub fn token_id(token: &str, claims: &Claims) -> String {
+    match &claims.jti {
+        Some(jti) => jti.clone(),
+        None => format!("{:x}", Sha256::digest(token.as_bytes())),
+    }
+}
+
+/// Storage of revoked refresh tokens
+#[async_trait]
+pub trait RevocationList: Send + Sync {
+    /// Add `entry` to the list. Returns false if the token was already on it, i.e. it is
+    /// being reused.
+    async fn revoke(&self, entry: RevokedRefreshToken) -> Result<bool>;
+}
+
+/// Revoked token ids and their expiry
+type RevokedIds = HashMap<String, DateTime<Utc>>;
+
+/// Revoked token ids per list file, loaded on first use
+fn loaded_lists() -> &'static Mutex<HashMap<PathBuf, RevokedIds>> {
+    static LOADED: OnceLock<Mutex<HashMap<PathBuf, RevokedIds>>> = OnceLock::new();
+    LOADED.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Revocation list in a JSON Lines file (file mode).
+///
+/// The file is loaded on first use, dropping expired entries, and appended to afterwards.
+pub struct JsonlRevocationList {
+    path: PathBuf,
+}
+
+impl JsonlRevocationList {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            path: dir.join(REVOCATION_LIST_FILE),
+        }
+    }
+}
+
+/// Unexpired entries of a list file, rewriting it without the expired ones
+fn load_list(path: &Path) -> Result<RevokedIds> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let reader = BufReader::new(
+        File::open(path).with_context(|| format!("Failed to open {}", path.display()))?,
+    );
+    let now = Utc::now();
+    let mut expired = 0;
+    let mut live = Vec::new();
+    for entry in reader
+        .lines()
+        .map_while(|l| l.ok())
+        .filter_map(|l| serde_json::from_str::<RevokedRefreshToken>(&l).ok())
+    {
+        if entry.expires_at > now {
+            live.push(entry);
+        } else {
+            expired += 1;
+        }
+    }
+
+    if expired > 0 {
+        let mut content = String::new();
+        for entry in &live {
+            content.push_str(&serde_json::to_string(entry)?);
+            content.push('\n');
+        }
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+    }
+    Ok(live
+        .into_iter()
+        .map(|e| (e.token_id, e.expires_at))
+        .collect())
+}
+
+/// Append a JSON line to a list file
+fn append_to_list(path: &Path, line: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", line)?;
+    file.flush()?;
+    Ok(())
+}
+
+#[async_trait]
+impl RevocationList for JsonlRevocationList {
+    async fn revoke(&self, entry: RevokedRefreshToken) -> Result<bool> {
+        // The async lock is held across the file I/O, which runs on the blocking pool
+        let mut lists = loaded_lists().lock().await;
+        let entries = match lists.entry(self.path.clone()) {
+            Entry::Occupied(entries) => entries.into_mut(),
+            Entry::Vacant(vacant) => {
+                let path = self.path.clone();
+                vacant.insert(tokio::task::spawn_blocking(move || load_list(&path)).await??)
+            }
+        };
+        if entries.contains_key(&entry.token_id) {
+            return Ok(false);
+        }
+
+        let path = self.path.clone();
+        let line = serde_json::to_string(&entry)?;
+        tokio::task::spawn_blocking(move || append_to_list(&path, &line)).await??;
+
+        entries.insert(entry.token_id, entry.expires_at);
+        Ok(true)
+    }
+}
+
+/// Revocation list in the `revoked_refresh_tokens` table (PostgreSQL mode).
+pub struct PostgresRevocationList {
+    pool: PgPool,
+}
+
+impl PostgresRevocationList {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RevocationList for PostgresRevocationList {
+    async fn revoke(&self, entry: RevokedRefreshToken) -> Result<bool> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO revoked_refresh_tokens (token_id, session_id, revoked_at, expires_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (token_id) DO NOTHING
+            "#,
+        )
+        .bind(&entry.token_id)
+        .bind(&entry.session_id)
+        .bind(entry.revoked_at)
+        .bind(entry.expires_at)
+        .execute(&self.pool)
+        .await
+        .context("Failed to revoke refresh token")?;
+        Ok(result.rows_affected() == 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::jwt_service::JwtService;
+
+    #[tokio::test]
+    async fn test_rotated_tokens_are_rejected_on_reuse() {
+        let service = JwtService::new("test-secret-key-at-least-32-chars");
+        let pair = service
+            .generate_token_pair("test@example.com", 12345, "testuser", "session-123")
+            .unwrap();
+        let claims = service.validate_refresh_token(&pair.refresh_token).unwrap();
+        assert!(claims.jti.is_some());
+
+        let dir = tempfile::tempdir().unwrap();
+        let list = JsonlRevocationList::new(dir.path());
+        let entry = RevokedRefreshToken::new(&pair.refresh_token, &claims);
+        assert!(list.revoke(entry.clone()).await.unwrap());
+        assert!(!list.revoke(entry.clone()).await.unwrap());
+
+        // Tokens issued without a jti are identified by their hash
+        let mut legacy = claims.clone();
+        legacy.jti = None;
+        assert_eq!(token_id("a.b.c", &legacy).len(), 64);
+
+        // The list survives a restart; expired entries are dropped
+        let mut expired = entry.clone();
+        expired.token_id = "expired".to_string();
+        expired.expires_at = Utc::now() - chrono::Duration::hours(1);
+        assert!(list.revoke(expired.clone()).await.unwrap());
+        // A copy of the file is loaded afresh, like after a restart
+        let restarted = tempfile::tempdir().unwrap();
+        std::fs::copy(
+            dir.path().join(REVOCATION_LIST_FILE),
+            restarted.path().join(REVOCATION_LIST_FILE),
+        )
+        .unwrap();
+        let reloaded = JsonlRevocationList::new(restarted.path());
+        assert!(!reloaded.revoke(entry).await.unwrap());
+        let content = std::fs::read_to_string(restarted.path().join(REVOCATION_LIST_FILE)).unwrap();
+        assert_eq!(content.lines().count(), 1);
+        assert!(reloaded.revoke(expired).await.unwrap());
+    }
+}
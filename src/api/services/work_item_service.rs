@@ -0,0 +1,329 @@
+//! External work item links.
+//!
+//! Tables and change proposals can reference Jira issues or Azure DevOps work items so schema
+//! changes are traceable to tickets. Links are stored per domain in `work-items.yaml`; their
+//! title and status are refreshed from the tracker's REST API on request:
+//! - Jira: `JIRA_BASE_URL` plus `JIRA_API_TOKEN` (and `JIRA_USER_EMAIL` for Jira Cloud basic
+//!   auth). Credentials are only sent to links under `JIRA_BASE_URL`.
+//! - Azure DevOps: `AZURE_DEVOPS_PAT`, used for `dev.azure.com` / `*.visualstudio.com` links.
+
+use anyhow::{Result, anyhow, bail};
+use chrono::{DateTime, Utc};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::env;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::models::DataModel;
+
+/// Work item tracker a link points to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkItemProvider {
+    Jira,
+    AzureDevOps,
+}
+
+/// Kind of object a work item is linked to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LinkTarget {
+    Table,
+    Proposal,
+}
+
+/// A work item reference attached to a table or proposal
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WorkItemLink {
+    pub id: Uuid,
+    pub target: LinkTarget,
+    pub target_id: String,
+    pub url: String,
+    pub provider: WorkItemProvider,
+    /// Issue key (`PROJ-123`) or work item id (`4711`)
+    pub key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub synced_at: Option<DateTime<Utc>>,
+    /// Last sync failure, cleared on the next successful sync
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync_error: Option<String>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// All work item links of a domain (`work-items.yaml`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WorkItemLinks {
+    #[serde(default)]
+    pub links: Vec<WorkItemLink>,
+}
+
+impl WorkItemLinks {
+    /// Links attached to one table or proposal
+    pub fn for_target(&self, target: LinkTarget, target_id: &str) -> Vec<WorkItemLink> {
+        self.links
+            .iter()
+            .filter(|l| l.target == target && l.target_id == target_id)
+            .cloned()
+            .collect()
+    }
+
+    /// Add a link for a work item URL. Returns an error for unrecognised URLs and
+    /// the existing link if the URL is already attached to the target.
+    pub fn add(
+        &mut self,
+        target: LinkTarget,
+        target_id: &str,
+        url: &str,
+        created_by: &str,
+    ) -> Result<WorkItemLink> {
+        let reference = WorkItemRef::from_url(url)
+            .ok_or_else(|| anyhow!("Not a Jira issue or Azure DevOps work item URL"))?;
+        if let Some(existing) = self
+            .links
+            .iter()
+            .find(|l| l.target == target && l.target_id == target_id && l.key == reference.key)
+        {
+            return Ok(existing.clone());
+        }
+        let link = WorkItemLink {
+            id: Uuid::new_v4(),
+            target,
+            target_id: target_id.to_string(),
+            url: url.trim().to_string(),
+            provider: reference.provider,
+            key: reference.key,
+            title: None,
+            status: None,
+            synced_at: None,
+            sync_error: None,
+            created_by: created_by.to_string(),
+            created_at: Utc::now(),
+        };
+        self.links.push(link.clone());
+        Ok(link)
+    }
+
+    /// Remove a link from a target; returns whether it existed.
+    pub fn remove(&mut self, target: LinkTarget, target_id: &str, link_id: Uuid) -> bool {
+        let before = self.links.len();
+        self.links
+            .retain(|l| !(l.id == link_id && l.target == target && l.target_id == target_id));
+        self.links.len() != before
+    }
+
+    /// Attach table links to `odcl_metadata.workItems` so exports can show them.
+    pub fn apply_to_model(&self, model: &DataModel) -> DataModel {
+        let mut model = model.clone();
+        for table in &mut model.tables {
+            let links = self.for_target(LinkTarget::Table, &table.id.to_string());
+            if links.is_empty() {
+                continue;
+            }
+            let items: Vec<Value> = links
+                .iter()
+                .map(|l| {
+                    json!({
+                        "key": l.key,
+                        "url": l.url,
+                        "provider": l.provider,
+                        "title": l.title,
+                        "status": l.status,
+                    })
+                })
+                .collect();
+            table
+                .odcl_metadata
+                .insert("workItems".to_string(), json!(items));
+        }
+        model
+    }
+}
+
+/// Refresh title and status of links from their trackers. Failures are recorded per link.
+pub async fn sync_links(links: &mut [WorkItemLink]) {
+    let client = reqwest::Client::new();
+    for link in links {
+        match fetch_work_item(&client, &link.url).await {
+            Ok((title, status)) => {
+                link.title = title.or(link.title.take());
+                link.status = status;
+                link.synced_at = Some(Utc::now());
+                link.sync_error = None;
+            }
+            Err(e) => link.sync_error = Some(e.to_string()),
+        }
+    }
+}
+
+/// A parsed work item URL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorkItemRef {
+    pub provider: WorkItemProvider,
+    pub key: String,
+    /// REST endpoint returning the work item's fields
+    pub api_url: String,
+}
+
+impl WorkItemRef {
+    /// Recognise Jira (`https://<host>/browse/PROJ-123`) and Azure DevOps
+    /// (`https://dev.azure.com/<org>/<project>/_workitems/edit/<id>`) URLs.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let parsed = Url::parse(url.trim()).ok()?;
+        if parsed.scheme() != "https" {
+            return None;
+        }
+        let host = parsed.host_str()?.to_lowercase();
+        let segments: Vec<&str> = parsed.path_segments()?.filter(|s| !s.is_empty()).collect();
+
+        // Azure DevOps: [org]/project/_workitems/edit/id
+        if let Some(pos) = segments.iter().position(|s| *s == "_workitems")
+            && segments.get(pos + 1) == Some(&"edit")
+            && let Some(id) = segments.get(pos + 2).filter(|id| id.parse::<u64>().is_ok())
+        {
+            let base = if host == "dev.azure.com" && pos == 2 {
+                format!("https://dev.azure.com/{}/{}", segments[0], segments[1])
+            } else if host.ends_with(".visualstudio.com") && pos == 1 {
+                format!("https://{}/{}", host, segments[0])
+            } else {
+                return None;
+            };
+            return Some(Self {
+                provider: WorkItemProvider::AzureDevOps,
+                key: id.to_string(),
+                api_url: format!("{}/_apis/wit/workitems/{}?api-version=7.0", base, id),
+            });
+        }
+
+        // Jira: /browse/KEY-1 (optionally under a context path)
+        if let Some(pos) = segments.iter().position(|s| *s == "browse")
+            && let Some(key) = segments.get(pos + 1).filter(|k| is_jira_key(k))
+        {
+            let context: String = segments[..pos].iter().map(|s| format!("/{}", s)).collect();
+            return Some(Self {
+                provider: WorkItemProvider::Jira,
+                key: key.to_string(),
+                api_url: format!(
+                    "https://{}{}/rest/api/2/issue/{}?fields=summary,status",
+                    parsed.host_str()?,
+                    context,
+                    key
+                ),
+            });
+        }
+        None
+    }
+}
+
+fn is_jira_key(key: &str) -> bool {
+    let Some((project, number)) = key.rsplit_once('-') else {
+        return false;
+    };
+    project.starts_with(|c: char| c.is_ascii_uppercase())
+        && project
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        && !number.is_empty()
+        && number.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Fetch (title, status) of a work item.
+async fn fetch_work_item(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<(Option<String>, Option<String>)> {
+    let reference =
+        WorkItemRef::from_url(url).ok_or_else(|| anyhow!("Unrecognised work item URL"))?;
+    let request = client.get(&reference.api_url);
+    let request = match reference.provider {
+        WorkItemProvider::Jira => {
+            let base = env::var("JIRA_BASE_URL").map_err(|_| anyhow!("JIRA_BASE_URL not set"))?;
+            if !reference
+                .api_url
+                .starts_with(&format!("{}/", base.trim_end_matches('/')))
+            {
+                bail!("Link is not under JIRA_BASE_URL");
+            }
+            let token =
+                env::var("JIRA_API_TOKEN").map_err(|_| anyhow!("JIRA_API_TOKEN not set"))?;
+            match env::var("JIRA_USER_EMAIL") {
+                Ok(email) => request.basic_auth(email, Some(token)),
+                Err(_) => request.bearer_auth(token),
+            }
+        }
+        WorkItemProvider::AzureDevOps => {
+            let pat =
+                env::var("AZURE_DEVOPS_PAT").map_err(|_| anyhow!("AZURE_DEVOPS_PAT not set"))?;
+            request.basic_auth("", Some(pat))
+        }
+    };
+
+    let body: Value = request.send().await?.error_for_status()?.json().await?;
+    let fields = &body["fields"];
+    let text = |v: &Value| v.as_str().map(str::to_string);
+    Ok(match reference.provider {
+        WorkItemProvider::Jira => (text(&fields["summary"]), text(&fields["status"]["name"])),
+        WorkItemProvider::AzureDevOps => {
+            (text(&fields["System.Title"]), text(&fields["System.State"]))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_work_item_urls() {
+        let jira = WorkItemRef::from_url("https://acme.atlassian.net/browse/DATA-42").unwrap();
+        assert_eq!(jira.provider, WorkItemProvider::Jira);
+        assert_eq!(jira.key, "DATA-42");
+        assert_eq!(
+            jira.api_url,
+            "https://acme.atlassian.net/rest/api/2/issue/DATA-42?fields=summary,status"
+        );
+
+        let ado =
+            WorkItemRef::from_url("https://dev.azure.com/acme/Data/_workitems/edit/4711").unwrap();
+        assert_eq!(ado.provider, WorkItemProvider::AzureDevOps);
+        assert_eq!(
+            ado.api_url,
+            "https://dev.azure.com/acme/Data/_apis/wit/workitems/4711?api-version=7.0"
+        );
+        assert!(
+            WorkItemRef::from_url("https://acme.visualstudio.com/Data/_workitems/edit/7").is_some()
+        );
+
+        assert!(WorkItemRef::from_url("http://acme.atlassian.net/browse/DATA-42").is_none());
+        assert!(WorkItemRef::from_url("https://example.com/browse/not-a-key").is_none());
+
+        let mut links = WorkItemLinks::default();
+        let table_id = Uuid::new_v4().to_string();
+        let first = links
+            .add(
+                LinkTarget::Table,
+                &table_id,
+                "https://acme.atlassian.net/browse/DATA-42",
+                "a@b.c",
+            )
+            .unwrap();
+        let again = links
+            .add(
+                LinkTarget::Table,
+                &table_id,
+                "https://acme.atlassian.net/browse/DATA-42",
+                "a@b.c",
+            )
+            .unwrap();
+        assert_eq!(first.id, again.id);
+        assert_eq!(links.for_target(LinkTarget::Table, &table_id).len(), 1);
+        assert!(links.for_target(LinkTarget::Proposal, &table_id).is_empty());
+        assert!(links.remove(LinkTarget::Table, &table_id, first.id));
+    }
+}
@@ -0,0 +1,299 @@
+//! Workspace auto-provisioning rules.
+//!
+//! By default every user works in an isolated personal workspace keyed by their email.
+//! Administrators can configure rules that instead place new users in a shared team
+//! workspace on first login, matched by email domain or by group membership (the user's
+//! GitHub organizations). A rule names the team workspace by the email it is keyed by (e.g.
+//! a team mailbox) and may name a template domain that is cloned into the team workspace
+//! when it is first provisioned.
+//!
+//! Rules are stored in `provisioning-rules.yaml` in the workspace data directory, and the
+//! resulting user-to-workspace assignments in `workspace-assignments.yaml`. Users who
+//! already have a workspace are never moved. Requests look assignments up through an
+//! in-memory copy that saving refreshes, so changes must go through
+//! [`WorkspaceAssignments::save`].
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+use utoipa::ToSchema;
+
+/// File holding the provisioning rules
+pub const PROVISIONING_RULES_FILE: &str = "provisioning-rules.yaml";
+
+/// File holding the users provisioned into team workspaces
+pub const WORKSPACE_ASSIGNMENTS_FILE: &str = "workspace-assignments.yaml";
+
+/// Domain cloned into a team workspace when it is first provisioned
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct TemplateDomain {
+    /// Email of the workspace holding the template domain
+    pub workspace: String,
+    pub domain: String,
+}
+
+/// Places matching users in a shared team workspace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ProvisioningRule {
+    pub name: String,
+    /// Email domains that match, e.g. `example.com`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub email_domains: Vec<String>,
+    /// Groups (GitHub organization logins) that match
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<String>,
+    /// Email the team workspace is keyed by
+    pub workspace: String,
+    /// Display name of the team workspace
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub workspace_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<TemplateDomain>,
+}
+
+impl ProvisioningRule {
+    /// Whether the rule matches a user's email domain or one of their groups
+    pub fn matches(&self, email: &str, groups: &[String]) -> bool {
+        let email_domain = email.rsplit_once('@').map(|(_, domain)| domain);
+        let domain_match = email_domain.is_some_and(|domain| {
+            self.email_domains
+                .iter()
+                .any(|d| d.trim_start_matches('@').eq_ignore_ascii_case(domain))
+        });
+        domain_match
+            || self
+                .groups
+                .iter()
+                .any(|g| groups.iter().any(|group| group.eq_ignore_ascii_case(g)))
+    }
+}
+
+/// Provisioning rules, applied in order (`provisioning-rules.yaml`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ProvisioningRules {
+    #[serde(default)]
+    pub rules: Vec<ProvisioningRule>,
+}
+
+impl ProvisioningRules {
+    /// Load the rules from the workspace data directory (none if no file exists).
+    pub fn load(workspace_data_dir: &Path) -> Self {
+        std::fs::read_to_string(workspace_data_dir.join(PROVISIONING_RULES_FILE))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the rules to the workspace data directory.
+    pub fn save(&self, workspace_data_dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(workspace_data_dir)?;
+        let path = workspace_data_dir.join(PROVISIONING_RULES_FILE);
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Check that rule names are unique, every rule matches on something and workspace
+    /// emails are valid.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut names = HashSet::new();
+        for rule in &self.rules {
+            if rule.name.trim().is_empty() {
+                return Err("Rule name cannot be empty".to_string());
+            }
+            if !names.insert(rule.name.trim()) {
+                return Err(format!("Duplicate rule name '{}'", rule.name));
+            }
+            if rule.email_domains.is_empty() && rule.groups.is_empty() {
+                return Err(format!(
+                    "Rule '{}' must match email domains or groups",
+                    rule.name
+                ));
+            }
+            let emails = std::iter::once(&rule.workspace)
+                .chain(rule.template.as_ref().map(|t| &t.workspace));
+            for email in emails {
+                if !email.contains('@') || !email.contains('.') {
+                    return Err(format!(
+                        "Rule '{}' has invalid workspace email '{}'",
+                        rule.name, email
+                    ));
+                }
+            }
+            if let Some(template) = &rule.template
+                && template.domain.trim().is_empty()
+            {
+                return Err(format!("Rule '{}' has an empty template domain", rule.name));
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether any rule matches on groups, so group memberships need to be looked up
+    pub fn uses_groups(&self) -> bool {
+        self.rules.iter().any(|rule| !rule.groups.is_empty())
+    }
+
+    /// First rule matching a user
+    pub fn first_match(&self, email: &str, groups: &[String]) -> Option<&ProvisioningRule> {
+        self.rules.iter().find(|rule| rule.matches(email, groups))
+    }
+}
+
+/// A user provisioned into a team workspace
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceAssignment {
+    /// Email the team workspace is keyed by
+    pub workspace: String,
+    /// Rule that matched
+    pub rule: String,
+    pub assigned_at: DateTime<Utc>,
+}
+
+/// Users provisioned into team workspaces, by lowercase email (`workspace-assignments.yaml`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceAssignments {
+    #[serde(default)]
+    pub users: BTreeMap<String, WorkspaceAssignment>,
+}
+
+/// Assignments per workspace data directory, as last loaded or saved
+fn assignments_cache() -> &'static RwLock<HashMap<PathBuf, Arc<WorkspaceAssignments>>> {
+    static CACHE: OnceLock<RwLock<HashMap<PathBuf, Arc<WorkspaceAssignments>>>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+impl WorkspaceAssignments {
+    /// Load the assignments from the workspace data directory (none if no file exists).
+    pub fn load(workspace_data_dir: &Path) -> Self {
+        std::fs::read_to_string(workspace_data_dir.join(WORKSPACE_ASSIGNMENTS_FILE))
+            .ok()
+            .and_then(|content| serde_yaml::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the assignments to the workspace data directory.
+    pub fn save(&self, workspace_data_dir: &Path) -> Result<()> {
+        std::fs::create_dir_all(workspace_data_dir)?;
+        let path = workspace_data_dir.join(WORKSPACE_ASSIGNMENTS_FILE);
+        std::fs::write(&path, serde_yaml::to_string(self)?)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        assignments_cache()
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(workspace_data_dir.to_path_buf(), Arc::new(self.clone()));
+        Ok(())
+    }
+
+    /// The assignments of the workspace data directory, read from disk on first use.
+    pub fn cached(workspace_data_dir: &Path) -> Arc<Self> {
+        if let Some(assignments) = assignments_cache()
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(workspace_data_dir)
+        {
+            return assignments.clone();
+        }
+        let loaded = Arc::new(Self::load(workspace_data_dir));
+        assignments_cache()
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(workspace_data_dir.to_path_buf())
+            .or_insert(loaded)
+            .clone()
+    }
+
+    /// Team workspace a user was provisioned into
+    pub fn workspace_for(&self, email: &str) -> Option<&str> {
+        self.users
+            .get(&email.to_lowercase())
+            .map(|a| a.workspace.as_str())
+    }
+
+    /// Provision a user into the workspace of `rule`
+    pub fn assign(&mut self, email: &str, rule: &ProvisioningRule) {
+        self.users.insert(
+            email.to_lowercase(),
+            WorkspaceAssignment {
+                workspace: rule.workspace.to_lowercase(),
+                rule: rule.name.clone(),
+                assigned_at: Utc::now(),
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_match_email_domain_or_group_in_order() {
+        let rule = |name: &str, domains: &[&str], groups: &[&str]| ProvisioningRule {
+            name: name.to_string(),
+            email_domains: domains.iter().map(|d| d.to_string()).collect(),
+            groups: groups.iter().map(|g| g.to_string()).collect(),
+            workspace: format!("{}@example.com", name),
+            workspace_name: None,
+            template: None,
+        };
+        let rules = ProvisioningRules {
+            rules: vec![
+                rule("platform", &[], &["acme-platform"]),
+                rule("acme", &["@Example.com"], &[]),
+            ],
+        };
+        assert!(rules.validate().is_ok());
+        assert!(rules.uses_groups());
+
+        let platform = vec!["ACME-Platform".to_string()];
+        assert_eq!(
+            rules
+                .first_match("dev@example.com", &platform)
+                .unwrap()
+                .name,
+            "platform"
+        );
+        assert_eq!(
+            rules.first_match("dev@EXAMPLE.com", &[]).unwrap().name,
+            "acme"
+        );
+        assert!(rules.first_match("dev@other.org", &[]).is_none());
+
+        let mut assignments = WorkspaceAssignments::default();
+        assignments.assign("Dev@Example.com", &rules.rules[1]);
+        assert_eq!(
+            assignments.workspace_for("dev@example.com"),
+            Some("acme@example.com")
+        );
+
+        let invalid = ProvisioningRules {
+            rules: vec![rule("empty", &[], &[])],
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_cached_assignments_follow_saves() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(WorkspaceAssignments::cached(dir.path()).users.is_empty());
+
+        let rule = ProvisioningRule {
+            name: "acme".to_string(),
+            email_domains: vec!["example.com".to_string()],
+            groups: Vec::new(),
+            workspace: "team@example.com".to_string(),
+            workspace_name: None,
+            template: None,
+        };
+        let mut assignments = WorkspaceAssignments::load(dir.path());
+        assignments.assign("dev@example.com", &rule);
+        assignments.save(dir.path()).unwrap();
+        assert_eq!(
+            WorkspaceAssignments::cached(dir.path()).workspace_for("dev@example.com"),
+            Some("team@example.com")
+        );
+    }
+}
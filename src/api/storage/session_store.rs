@@ -1,11 +1,13 @@
 //! Session store for PostgreSQL.
 //!
-//! Provides database-backed session storage.
+//! Provides database-backed session storage. Sessions expire as configured by
+//! `SessionExpiry`; recorded activity slides the expiry forward.
 
 use crate::routes::auth::SessionMetadata;
+use crate::services::session_expiry_service::SessionExpiry;
 use crate::storage::traits::EmailInfo;
 use chrono::Utc;
-use sqlx::PgPool;
+use sqlx::{PgPool, Row};
 use uuid::Uuid;
 
 /// Session creation parameters
@@ -84,7 +86,8 @@ impl DbSessionStore {
     /// Create a new session
     pub async fn create_session(&self, params: CreateSessionParams) -> Result<(), sqlx::Error> {
         let emails_json = serde_json::to_value(params.emails).unwrap_or(serde_json::json!([]));
-        let expires_at = Utc::now() + chrono::Duration::days(7); // 7 days expiry
+        let now = Utc::now();
+        let expires_at = SessionExpiry::from_env().expires_at(now, now);
 
         sqlx::query!(
             r#"
@@ -109,22 +112,72 @@ impl DbSessionStore {
         Ok(())
     }
 
-    /// Update session activity timestamp
+    /// Update session activity timestamp, sliding the session's expiry forward
     pub async fn update_session_activity(&self, session_id: Uuid) -> Result<(), sqlx::Error> {
-        sqlx::query!(
+        let expiry = SessionExpiry::from_env();
+        sqlx::query(
             r#"
             UPDATE sessions
-            SET last_activity = NOW()
+            SET last_activity = NOW(),
+                expires_at = LEAST(NOW() + $2, created_at + $3)
             WHERE id = $1 AND expires_at > NOW()
             "#,
-            session_id
         )
+        .bind(session_id)
+        .bind(expiry.ttl)
+        .bind(expiry.max_lifetime)
         .execute(&self.pool)
         .await?;
 
         Ok(())
     }
 
+    /// Active sessions of a user, most recently active first
+    pub async fn list_user_sessions(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<(Uuid, SessionMetadata)>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, user_id, github_id, github_username, emails, selected_email, created_at, last_activity, expires_at
+            FROM sessions
+            WHERE user_id = $1 AND expires_at > NOW()
+            ORDER BY last_activity DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.into_iter()
+            .map(|r| {
+                let emails: Vec<EmailInfo> =
+                    serde_json::from_value(r.try_get("emails")?).unwrap_or_default();
+                let session = SessionMetadata {
+                    user_id: r.try_get("user_id")?,
+                    github_id: r.try_get::<i64, _>("github_id")? as u64,
+                    github_username: r.try_get("github_username")?,
+                    // Listing never hands out the provider token
+                    github_access_token: String::new(),
+                    emails: emails
+                        .into_iter()
+                        .map(|e| crate::services::oauth_service::GitHubEmail {
+                            email: e.email,
+                            verified: e.verified,
+                            primary: e.primary,
+                        })
+                        .collect(),
+                    selected_email: r.try_get("selected_email")?,
+                    created_at: r.try_get("created_at")?,
+                    last_activity: r.try_get("last_activity")?,
+                    revoked_at: None,
+                    expires_at: r.try_get("expires_at")?,
+                };
+                Ok((r.try_get("id")?, session))
+            })
+            .collect()
+    }
+
     /// Update selected email for a session
     pub async fn update_selected_email(
         &self,
@@ -180,7 +233,7 @@ impl DbSessionStore {
 
 /// Start background task to clean up expired sessions
 pub async fn start_session_cleanup_task(pool: PgPool) {
-    let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600)); // Run every hour
+    let mut interval = tokio::time::interval(SessionExpiry::from_env().prune_interval);
 
     loop {
         interval.tick().await;
@@ -197,5 +250,13 @@ pub async fn start_session_cleanup_task(pool: PgPool) {
         {
             tracing::error!("Failed to cleanup expired sessions: {}", e);
         }
+
+        // Delete revoked refresh tokens that have expired anyway
+        if let Err(e) = sqlx::query("DELETE FROM revoked_refresh_tokens WHERE expires_at < NOW()")
+            .execute(&pool)
+            .await
+        {
+            tracing::error!("Failed to cleanup revoked refresh tokens: {}", e);
+        }
     }
 }
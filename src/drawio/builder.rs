@@ -2,15 +2,34 @@
 
 // Note: When building the binary, models are at crate::models
 // The library build uses a different structure (handled via lib.rs)
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
 use crate::models::enums::{MedallionLayer, RelationshipType};
+use crate::models::relationship::RelationshipAnchor;
 use crate::models::{Relationship, Table};
 
 use super::document::DrawIODocument;
 use super::models::{DrawIOCell, DrawIOEdge};
 
+// Vertical layout of a table box's HTML (12px font): top padding, header row (line height
+// 1.3 plus 2px padding above and below) and column rows (line height 1.15)
+const BOX_TOP_PADDING: f64 = 8.0;
+const HEADER_ROW_HEIGHT: f64 = 12.0 * 1.3 + 4.0;
+const COLUMN_ROW_HEIGHT: f64 = 12.0 * 1.15;
+
+/// Columns a table box lists, for placing relationship anchors on their rows
+struct TableRows {
+    /// Model indices of the listed columns, in display order
+    columns: Vec<usize>,
+    height: f64,
+}
+
 /// Builder for constructing DrawIO XML documents from data models.
 pub struct DrawIOBuilder {
     document: DrawIODocument,
+    table_rows: HashMap<Uuid, TableRows>,
 }
 
 impl DrawIOBuilder {
@@ -18,6 +37,7 @@ impl DrawIOBuilder {
     pub fn new(diagram_name: String) -> Self {
         Self {
             document: DrawIODocument::new(diagram_name),
+            table_rows: HashMap::new(),
         }
     }
 
@@ -85,6 +105,66 @@ impl DrawIOBuilder {
         );
 
         self.document.add_table_cell(cell);
+        self.table_rows.insert(
+            table.id,
+            TableRows {
+                columns: Self::listed_columns(table, modeling_level),
+                height,
+            },
+        );
+    }
+
+    /// Model indices of the columns a table box lists, in the order `generate_table_value`
+    /// lists them.
+    fn listed_columns(
+        table: &Table,
+        modeling_level: Option<crate::models::enums::ModelingLevel>,
+    ) -> Vec<usize> {
+        let mut columns: Vec<usize> = match modeling_level {
+            Some(crate::models::enums::ModelingLevel::Logical) => table
+                .columns
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| c.primary_key || c.secondary_key || c.foreign_key.is_some())
+                .map(|(i, _)| i)
+                .collect(),
+            Some(crate::models::enums::ModelingLevel::Physical) => {
+                (0..table.columns.len()).collect()
+            }
+            _ => Vec::new(),
+        };
+        if modeling_level == Some(crate::models::enums::ModelingLevel::Physical) {
+            columns.sort_by_key(|&i| {
+                let name = &table.columns[i].name;
+                (name.matches('.').count(), name.clone())
+            });
+        }
+        columns
+    }
+
+    /// Relative position of an anchor on a table box.
+    fn anchor_point(&self, table_id: Uuid, anchor: &RelationshipAnchor) -> (f64, f64) {
+        let rows = self.table_rows.get(&table_id);
+        anchor.relative_point(|index| {
+            let rows = rows?;
+            let row = rows.columns.iter().position(|&c| c == index)?;
+            let y = BOX_TOP_PADDING + HEADER_ROW_HEIGHT + (row as f64 + 0.5) * COLUMN_ROW_HEIGHT;
+            Some((y / rows.height).clamp(0.0, 1.0))
+        })
+    }
+
+    /// Replace the `exit` or `entry` connection point of an edge style.
+    fn with_connection_point(style: &str, prefix: &str, (x, y): (f64, f64)) -> String {
+        let keys = ["X", "Y", "Dx", "Dy"].map(|k| format!("{}{}=", prefix, k));
+        let mut parts: Vec<String> = style
+            .split(';')
+            .filter(|p| !p.is_empty() && !keys.iter().any(|k| p.starts_with(k.as_str())))
+            .map(str::to_string)
+            .collect();
+        parts.push(format!(
+            "{prefix}X={x:.3};{prefix}Y={y:.3};{prefix}Dx=0;{prefix}Dy=0"
+        ));
+        parts.join(";")
     }
 
     /// Generate table value (HTML-formatted) with column details based on modeling level.
@@ -278,17 +358,53 @@ impl DrawIOBuilder {
         let target_cell_id = format!("table-{}", relationship.target_table_id);
 
         // Generate style based on relationship type and cardinality (includes Crow's Foot markers)
-        let style = Self::generate_edge_style(
+        let mut style = Self::generate_edge_style(
             relationship.relationship_type,
             relationship.cardinality.as_ref(),
             relationship.source_optional,
             relationship.target_optional,
         );
+        if relationship.is_self_reference() {
+            // Self-references loop out of and back into the table's right side; further
+            // loops on the same table are stacked so they don't overlap
+            let existing_loops = self
+                .document
+                .diagram
+                .graph_model
+                .root
+                .relationship_edges
+                .iter()
+                .filter(|e| e.source == source_cell_id && e.target == source_cell_id)
+                .count();
+            let offset = (existing_loops % 4) as f64 * 0.1;
+            style = format!(
+                "{};exitX=1;exitY={:.1};exitDx=0;exitDy=0;entryX=1;entryY={:.1};entryDx=0;entryDy=0",
+                style.replacen(
+                    "edgeStyle=orthogonalEdgeStyle",
+                    "edgeStyle=loopEdgeStyle",
+                    1
+                ),
+                0.2 + offset,
+                0.8 - offset
+            );
+        }
+
+        // Anchored ends attach where they were placed on the canvas
+        if let Some(visual) = relationship.visual_metadata.as_ref() {
+            if let Some(anchor) = visual.source_anchor.as_ref() {
+                let point = self.anchor_point(relationship.source_table_id, anchor);
+                style = Self::with_connection_point(&style, "exit", point);
+            }
+            if let Some(anchor) = visual.target_anchor.as_ref() {
+                let point = self.anchor_point(relationship.target_table_id, anchor);
+                style = Self::with_connection_point(&style, "entry", point);
+            }
+        }
 
         // Get cardinality as string
         let cardinality = relationship.cardinality.map(|c| format!("{:?}", c));
 
-        let edge = DrawIOEdge::new_relationship(
+        let mut edge = DrawIOEdge::new_relationship(
             relationship.id,
             source_cell_id,
             target_cell_id,
@@ -296,10 +412,45 @@ impl DrawIOBuilder {
             cardinality,
             waypoints,
         );
+        // Role names and composite keys can't be inferred from the connected tables,
+        // so show them on the edge
+        let labels: Vec<String> = [
+            relationship.role_label(),
+            relationship
+                .foreign_key_details
+                .as_ref()
+                .filter(|fk| fk.is_composite())
+                .map(|fk| fk.label()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        edge.value = (!labels.is_empty()).then(|| labels.join(" : "));
 
         self.document.add_relationship_edge(edge);
     }
 
+    /// Add a generalization edge from a subtype to its supertype.
+    ///
+    /// Drawn with a hollow UML inheritance arrow and labelled with the subtype's
+    /// discriminator value. The edge carries no relationship ID, so it is ignored when the
+    /// diagram is imported back.
+    pub fn add_subtype(&mut self, supertype_id: Uuid, subtype_id: Uuid, discriminator_value: &str) {
+        let mut edge = DrawIOEdge::new_relationship(
+            subtype_id,
+            format!("table-{}", subtype_id),
+            format!("table-{}", supertype_id),
+            "edgeStyle=orthogonalEdgeStyle;rounded=0;html=1;endArrow=block;endFill=0;endSize=14;"
+                .to_string(),
+            None,
+            None,
+        );
+        edge.id = format!("edge-subtype-{}", subtype_id);
+        edge.relationship_id = None;
+        edge.value = Some(discriminator_value.to_string());
+        self.document.add_relationship_edge(edge);
+    }
+
     /// Build the DrawIO XML document.
     pub fn build(self) -> DrawIODocument {
         self.document
@@ -458,6 +609,7 @@ mod tests {
             data_vault_classification: None,
             modeling_level: None,
             tags: Vec::new(),
+            aliases: Vec::new(),
             odcl_metadata: Default::default(),
             position: None,
             yaml_file_path: None,
@@ -504,6 +656,8 @@ mod tests {
             cardinality: Some(crate::models::enums::Cardinality::OneToMany),
             source_optional: None,
             target_optional: None,
+            source_role: None,
+            target_role: None,
             foreign_key_details: None,
             etl_job_metadata: None,
             relationship_type: Some(RelationshipType::DataFlow),
@@ -528,4 +682,71 @@ mod tests {
         assert_eq!(edge.target, format!("table-{}", target_id));
         assert!(edge.style.contains("#0066CC")); // DataFlow blue color
     }
+
+    #[test]
+    fn test_self_reference_renders_as_loop_edge() {
+        let mut builder = DrawIOBuilder::new("Test".to_string());
+        let employees = Uuid::new_v4();
+
+        let mut relationship = Relationship::new(employees, employees);
+        relationship.cardinality = Some(crate::models::enums::Cardinality::ManyToOne);
+        relationship.source_role = Some("report".to_string());
+        relationship.target_role = Some("manager".to_string());
+        builder.add_relationship(&relationship, None);
+
+        let document = builder.build();
+        let edge = &document.diagram.graph_model.root.relationship_edges[0];
+        assert_eq!(edge.source, edge.target);
+        assert!(edge.style.contains("edgeStyle=loopEdgeStyle"));
+        assert!(edge.style.contains("exitX=1"));
+        assert_eq!(edge.value.as_deref(), Some("report / manager"));
+    }
+
+    #[test]
+    fn test_anchored_relationship_attaches_at_its_anchors() {
+        use crate::models::relationship::{RelationshipAnchor, TableSide, VisualMetadata};
+
+        let mut builder = DrawIOBuilder::new("Test".to_string());
+        let orders = Table::new(
+            "orders".to_string(),
+            vec![
+                Column::new("id".to_string(), "INT".to_string()),
+                Column::new("customer_id".to_string(), "INT".to_string()),
+            ],
+        );
+        builder.add_table_with_level(
+            &orders,
+            0.0,
+            0.0,
+            Some(200.0),
+            Some(100.0),
+            Some(crate::models::enums::ModelingLevel::Physical),
+        );
+
+        let mut relationship = Relationship::new(orders.id, Uuid::new_v4());
+        relationship.visual_metadata = Some(VisualMetadata {
+            source_anchor: Some(RelationshipAnchor {
+                side: TableSide::East,
+                column_index: Some(0),
+                fraction: None,
+            }),
+            target_anchor: Some(RelationshipAnchor {
+                side: TableSide::North,
+                column_index: None,
+                fraction: Some(0.25),
+            }),
+            ..Default::default()
+        });
+        builder.add_relationship(&relationship, None);
+
+        let document = builder.build();
+        let edge = &document.diagram.graph_model.root.relationship_edges[0];
+        // Physical boxes list columns by name, so "id" is the second row:
+        // 8 + 19.6 + 1.5 * 13.8 = 48.3 of 100
+        assert!(
+            edge.style
+                .contains("exitX=1.000;exitY=0.483;exitDx=0;exitDy=0")
+        );
+        assert!(edge.style.contains("entryX=0.250;entryY=0.000"));
+    }
 }
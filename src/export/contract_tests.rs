@@ -0,0 +1,224 @@
+//! Consumer contract test exporter.
+//!
+//! Renders a test module skeleton for one table, listing the column names, types and
+//! nullability the model promises. Consumer teams fill in `load_schema` (their warehouse or
+//! schema registry lookup) and run the tests to catch contract drift. Supports pytest and
+//! Rust test modules.
+
+use crate::models::Table;
+use serde_json::Value;
+
+/// Test framework to render for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContractTestFramework {
+    Pytest,
+    Rust,
+}
+
+impl ContractTestFramework {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "pytest" | "python" => Some(Self::Pytest),
+            "rust" => Some(Self::Rust),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            Self::Pytest => "text/x-python",
+            Self::Rust => "text/x-rust",
+        }
+    }
+
+    pub fn filename(self, table: &Table) -> String {
+        let stem = table
+            .name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>()
+            .to_ascii_lowercase();
+        match self {
+            Self::Pytest => format!("test_{}_contract.py", stem),
+            Self::Rust => format!("{}_contract.rs", stem),
+        }
+    }
+}
+
+/// Exporter for consumer contract test skeletons.
+pub struct ContractTestExporter;
+
+impl ContractTestExporter {
+    /// Contract version of a table: its ODCS `version`, if it has one.
+    pub fn contract_version(table: &Table) -> Option<&str> {
+        table.odcl_metadata.get("version").and_then(Value::as_str)
+    }
+
+    /// Render the test module for `table`, pinned to contract `version`.
+    pub fn export_table(table: &Table, framework: ContractTestFramework, version: &str) -> String {
+        let qualified_name = [
+            table.catalog_name.as_deref(),
+            table.schema_name.as_deref(),
+            Some(table.name.as_str()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(".");
+        match framework {
+            ContractTestFramework::Pytest => Self::pytest(table, &qualified_name, version),
+            ContractTestFramework::Rust => Self::rust(table, &qualified_name, version),
+        }
+    }
+
+    fn pytest(table: &Table, qualified_name: &str, version: &str) -> String {
+        let mut out = format!(
+            r#""""Consumer contract tests for {name} (contract version {version}).
+
+Generated from the data model. Implement `load_schema` to return the live table's columns
+as {{column name: {{"type": str, "nullable": bool}}}}.
+"""
+import pytest
+
+CONTRACT_VERSION = {version:?}
+TABLE = {name:?}
+
+EXPECTED_COLUMNS = {{
+"#,
+            name = qualified_name,
+            version = version,
+        );
+        for column in &table.columns {
+            out.push_str(&format!(
+                "    {:?}: {{\"type\": {:?}, \"nullable\": {}}},\n",
+                column.name,
+                column.data_type,
+                if column.nullable { "True" } else { "False" }
+            ));
+        }
+        out.push_str(
+            r#"}
+
+
+def load_schema():
+    """Return the live schema of TABLE."""
+    raise NotImplementedError("Look up TABLE in your warehouse or schema registry")
+
+
+@pytest.fixture(scope="module")
+def schema():
+    return load_schema()
+
+
+@pytest.mark.parametrize("column", sorted(EXPECTED_COLUMNS))
+def test_column_exists(schema, column):
+    assert column in schema, f"{TABLE} is missing column {column}"
+
+
+@pytest.mark.parametrize("column", sorted(EXPECTED_COLUMNS))
+def test_column_type(schema, column):
+    expected = EXPECTED_COLUMNS[column]["type"]
+    assert schema[column]["type"].upper() == expected.upper(), (
+        f"{TABLE}.{column} should be {expected}"
+    )
+
+
+@pytest.mark.parametrize(
+    "column", sorted(c for c, spec in EXPECTED_COLUMNS.items() if not spec["nullable"])
+)
+def test_column_not_nullable(schema, column):
+    assert not schema[column]["nullable"], f"{TABLE}.{column} should not be nullable"
+"#,
+        );
+        out
+    }
+
+    fn rust(table: &Table, qualified_name: &str, version: &str) -> String {
+        let mut out = format!(
+            r#"//! Consumer contract tests for `{name}` (contract version {version}).
+//!
+//! Generated from the data model. Implement `load_schema` to return the live table's
+//! columns.
+
+use std::collections::HashMap;
+
+pub const CONTRACT_VERSION: &str = {version:?};
+pub const TABLE: &str = {name:?};
+
+/// Column name, type and nullability
+pub const EXPECTED_COLUMNS: &[(&str, &str, bool)] = &[
+"#,
+            name = qualified_name,
+            version = version,
+        );
+        for column in &table.columns {
+            out.push_str(&format!(
+                "    ({:?}, {:?}, {}),\n",
+                column.name, column.data_type, column.nullable
+            ));
+        }
+        out.push_str(
+            r#"];
+
+/// Live schema of `TABLE` as column name -> (type, nullable).
+fn load_schema() -> HashMap<String, (String, bool)> {
+    unimplemented!("look up TABLE in your warehouse or schema registry")
+}
+
+#[test]
+fn columns_match_contract() {
+    let schema = load_schema();
+    for (name, data_type, nullable) in EXPECTED_COLUMNS {
+        let (actual_type, actual_nullable) = schema
+            .get(*name)
+            .unwrap_or_else(|| panic!("{} is missing column {}", TABLE, name));
+        assert!(
+            actual_type.eq_ignore_ascii_case(data_type),
+            "{}.{} should be {}",
+            TABLE,
+            name,
+            data_type
+        );
+        assert!(
+            *nullable || !actual_nullable,
+            "{}.{} should not be nullable",
+            TABLE,
+            name
+        );
+    }
+}
+"#,
+        );
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Column;
+
+    #[test]
+    fn test_renders_expected_columns() {
+        let mut id = Column::new("id".to_string(), "BIGINT".to_string());
+        id.nullable = false;
+        let mut table = Table::new(
+            "Customer Orders".to_string(),
+            vec![id, Column::new("note".to_string(), "TEXT".to_string())],
+        );
+        table.schema_name = Some("sales".to_string());
+
+        let python =
+            ContractTestExporter::export_table(&table, ContractTestFramework::Pytest, "2.1.0");
+        assert!(python.contains("CONTRACT_VERSION = \"2.1.0\""));
+        assert!(python.contains("TABLE = \"sales.Customer Orders\""));
+        assert!(python.contains("\"id\": {\"type\": \"BIGINT\", \"nullable\": False},"));
+
+        let rust = ContractTestExporter::export_table(&table, ContractTestFramework::Rust, "2.1.0");
+        assert!(rust.contains("(\"id\", \"BIGINT\", false),"));
+        assert_eq!(
+            ContractTestFramework::Rust.filename(&table),
+            "customer_orders_contract.rs"
+        );
+    }
+}
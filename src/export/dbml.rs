@@ -0,0 +1,285 @@
+//! DBML exporter for dbdiagram.io.
+//!
+//! Tables become `Table` definitions with their column settings (`pk`, `not null`,
+//! `unique`, `default`, `note`) and composite primary keys as `indexes`. Columns with
+//! enum values get an `Enum` named `<table>_<column>`, foreign key relationships become
+//! `Ref` definitions (composite keys as `a.(x, y) > b.(x, y)`), and `table_group:<name>`
+//! tags become `TableGroup` definitions, so a DBML import of the export restores them.
+
+use crate::models::enums::Cardinality;
+use crate::models::{Column, DataModel, Table};
+use crate::services::dbml_parser::TABLE_GROUP_TAG_PREFIX;
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// Exporter for DBML documents.
+pub struct DbmlExporter;
+
+impl DbmlExporter {
+    /// Export a data model to DBML.
+    pub fn export_model(model: &DataModel, table_ids: Option<&[Uuid]>) -> String {
+        let tables: Vec<&Table> = model
+            .tables
+            .iter()
+            .filter(|t| table_ids.is_none_or(|ids| ids.contains(&t.id)))
+            .collect();
+        let names: HashMap<Uuid, String> =
+            tables.iter().map(|t| (t.id, Self::table_name(t))).collect();
+
+        let mut out = String::new();
+        let mut groups: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for table in &tables {
+            for column in table.columns.iter().filter(|c| !c.enum_values.is_empty()) {
+                out.push_str(&format!(
+                    "Enum {} {{\n",
+                    Self::identifier(&Self::enum_name(table, column))
+                ));
+                for value in &column.enum_values {
+                    out.push_str(&format!("  {}\n", Self::quoted_identifier(value)));
+                }
+                out.push_str("}\n\n");
+            }
+            for tag in &table.tags {
+                if let Some(group) = tag.strip_prefix(TABLE_GROUP_TAG_PREFIX) {
+                    groups.entry(group).or_default().push(&names[&table.id]);
+                }
+            }
+        }
+
+        for table in &tables {
+            Self::write_table(&mut out, table, &names[&table.id]);
+        }
+
+        for relationship in &model.relationships {
+            let (Some(source), Some(target), Some(fk)) = (
+                names.get(&relationship.source_table_id),
+                names.get(&relationship.target_table_id),
+                relationship.foreign_key_details.as_ref(),
+            ) else {
+                continue;
+            };
+            let op = match relationship.cardinality {
+                Some(Cardinality::OneToOne) => "-",
+                Some(Cardinality::OneToMany) => "<",
+                Some(Cardinality::ManyToMany) => "<>",
+                Some(Cardinality::ManyToOne) | None => ">",
+            };
+            let key = |columns: Vec<&str>| {
+                let columns: Vec<String> =
+                    columns.into_iter().map(Self::quoted_identifier).collect();
+                if columns.len() == 1 {
+                    columns[0].clone()
+                } else {
+                    format!("({})", columns.join(", "))
+                }
+            };
+            let (source_columns, target_columns) = fk.pairs().into_iter().unzip();
+            out.push_str(&format!(
+                "Ref: {}.{} {} {}.{}\n",
+                source,
+                key(source_columns),
+                op,
+                target,
+                key(target_columns)
+            ));
+        }
+
+        for (group, members) in groups {
+            out.push_str(&format!("\nTableGroup {} {{\n", Self::identifier(group)));
+            for member in members {
+                out.push_str(&format!("  {}\n", member));
+            }
+            out.push_str("}\n");
+        }
+        out
+    }
+
+    fn write_table(out: &mut String, table: &Table, name: &str) {
+        let composite_pk: Vec<&Column> = table.columns.iter().filter(|c| c.primary_key).collect();
+        let composite_pk = if composite_pk.len() > 1 {
+            composite_pk
+        } else {
+            Vec::new()
+        };
+
+        out.push_str(&format!("Table {} {{\n", name));
+        for column in &table.columns {
+            let data_type = if column.enum_values.is_empty() {
+                Self::data_type(&column.data_type)
+            } else {
+                Self::identifier(&Self::enum_name(table, column))
+            };
+            let mut settings = Vec::new();
+            if column.primary_key && composite_pk.is_empty() {
+                settings.push("pk".to_string());
+            } else if !column.nullable || column.primary_key {
+                settings.push("not null".to_string());
+            }
+            for constraint in &column.constraints {
+                if constraint.eq_ignore_ascii_case("unique") {
+                    settings.push("unique".to_string());
+                } else if let Some(default) = constraint
+                    .get(..8)
+                    .filter(|p| p.eq_ignore_ascii_case("default "))
+                    .map(|_| constraint[8..].trim())
+                {
+                    settings.push(format!("default: {}", default));
+                }
+            }
+            if !column.description.is_empty() {
+                settings.push(format!("note: {}", Self::string(&column.description)));
+            }
+            out.push_str(&format!(
+                "  {} {}",
+                Self::quoted_identifier(&column.name),
+                data_type
+            ));
+            if !settings.is_empty() {
+                out.push_str(&format!(" [{}]", settings.join(", ")));
+            }
+            out.push('\n');
+        }
+        if !composite_pk.is_empty() {
+            let columns: Vec<String> = composite_pk
+                .iter()
+                .map(|c| Self::quoted_identifier(&c.name))
+                .collect();
+            out.push_str(&format!(
+                "\n  indexes {{\n    ({}) [pk]\n  }}\n",
+                columns.join(", ")
+            ));
+        }
+        if let Some(note) = table
+            .odcl_metadata
+            .get("description")
+            .and_then(|v| v.as_str())
+            .filter(|n| !n.is_empty())
+        {
+            out.push_str(&format!("\n  Note: {}\n", Self::string(note)));
+        }
+        out.push_str("}\n\n");
+    }
+
+    fn table_name(table: &Table) -> String {
+        match &table.schema_name {
+            Some(schema) => format!(
+                "{}.{}",
+                Self::quoted_identifier(schema),
+                Self::quoted_identifier(&table.name)
+            ),
+            None => Self::quoted_identifier(&table.name),
+        }
+    }
+
+    fn enum_name(table: &Table, column: &Column) -> String {
+        format!("{}_{}", table.name, column.name)
+    }
+
+    /// A name usable as a bare DBML identifier, other characters replaced with `_`.
+    fn identifier(name: &str) -> String {
+        let identifier: String = name
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+        match identifier.chars().next() {
+            None => "unnamed".to_string(),
+            Some(c) if c.is_ascii_digit() => format!("_{}", identifier),
+            Some(_) => identifier,
+        }
+    }
+
+    /// The name as written, double-quoted unless it is a bare identifier.
+    fn quoted_identifier(name: &str) -> String {
+        let bare = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if bare {
+            name.to_string()
+        } else {
+            format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+    }
+
+    /// Column type, double-quoted when DBML could not read it as written (e.g. `ARRAY<INT>`).
+    fn data_type(data_type: &str) -> String {
+        if data_type
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '_' | '(' | ')' | ',' | '[' | ']'))
+            && !data_type.is_empty()
+        {
+            data_type.to_string()
+        } else {
+            Self::quoted_identifier(data_type)
+        }
+    }
+
+    fn string(text: &str) -> String {
+        format!(
+            "'{}'",
+            text.replace('\\', "\\\\")
+                .replace('\'', "\\'")
+                .replace('\n', "\\n")
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::relationship::ForeignKeyDetails;
+    use crate::models::{Column, Relationship};
+    use crate::services::dbml_parser::DbmlParser;
+
+    #[test]
+    fn test_export_dbml_round_trips_through_the_parser() {
+        let mut id = Column::new("id".to_string(), "BIGINT".to_string());
+        id.primary_key = true;
+        id.nullable = false;
+        let mut customers = Table::new("customers".to_string(), vec![id.clone()]);
+        customers.schema_name = Some("crm".to_string());
+        customers.tags = vec!["table_group:sales".to_string()];
+
+        let mut customer_id = Column::new("customer_id".to_string(), "BIGINT".to_string());
+        customer_id.description = "Buyer's id".to_string();
+        let mut status = Column::new("status".to_string(), "STRING".to_string());
+        status.enum_values = vec!["new".to_string(), "shipped".to_string()];
+        status.constraints = vec!["DEFAULT 'new'".to_string()];
+        let mut line = Column::new("line no".to_string(), "INT".to_string());
+        line.primary_key = true;
+        let mut orders = Table::new("orders".to_string(), vec![id, line, customer_id, status]);
+        orders.tags = vec!["table_group:sales".to_string()];
+        orders
+            .odcl_metadata
+            .insert("description".to_string(), serde_json::json!("Orders"));
+
+        let mut relationship = Relationship::new(orders.id, customers.id);
+        relationship.cardinality = Some(Cardinality::ManyToOne);
+        relationship.foreign_key_details = Some(ForeignKeyDetails::new("customer_id", "id"));
+
+        let mut model = DataModel::new("sales".to_string(), String::new(), String::new());
+        model.tables = vec![customers, orders];
+        model.relationships = vec![relationship];
+
+        let dbml = DbmlExporter::export_model(&model, None);
+        assert!(dbml.contains("Enum orders_status {\n  new\n  shipped\n}"));
+        assert!(dbml.contains("Table crm.customers {\n  id BIGINT [pk]\n}"));
+        assert!(dbml.contains("  \"line no\" INT [not null]\n"));
+        assert!(dbml.contains("  customer_id BIGINT [note: 'Buyer\\'s id']\n"));
+        assert!(dbml.contains("  status orders_status [default: 'new']\n"));
+        assert!(dbml.contains("    (id, \"line no\") [pk]\n"));
+        assert!(dbml.contains("Ref: orders.customer_id > crm.customers.id\n"));
+        assert!(dbml.contains("TableGroup sales {\n  crm.customers\n  orders\n}"));
+
+        let parsed = DbmlParser::new().parse(&dbml).unwrap();
+        assert!(parsed.errors.is_empty(), "{:?}", parsed.errors);
+        let orders = &parsed.tables[1];
+        assert_eq!(orders.columns[2].description, "Buyer's id");
+        assert_eq!(orders.columns[3].enum_values, ["new", "shipped"]);
+        assert!(orders.columns[..2].iter().all(|c| c.primary_key));
+        assert_eq!(orders.tags, ["table_group:sales"]);
+        assert_eq!(parsed.relationships.len(), 1);
+        assert_eq!(parsed.relationships[0].target_table, "customers");
+    }
+}
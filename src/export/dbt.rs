@@ -0,0 +1,110 @@
+//! dbt sources exporter.
+//!
+//! Renders a `sources.yml` declaring every table as a dbt source, grouped by
+//! catalog/schema. Primary keys get `unique` + `not_null` tests and non-nullable
+//! columns get `not_null`.
+
+use crate::models::{DataModel, Table};
+use serde::Serialize;
+use serde_json::Value;
+use uuid::Uuid;
+
+/// Exporter for dbt `sources.yml` files.
+pub struct DbtExporter;
+
+#[derive(Serialize)]
+struct SourcesFile<'a> {
+    version: u8,
+    sources: Vec<Source<'a>>,
+}
+
+#[derive(Serialize)]
+struct Source<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    database: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema: Option<&'a str>,
+    tables: Vec<SourceTable<'a>>,
+}
+
+#[derive(Serialize)]
+struct SourceTable<'a> {
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'a str>,
+    columns: Vec<SourceColumn<'a>>,
+}
+
+#[derive(Serialize)]
+struct SourceColumn<'a> {
+    name: &'a str,
+    data_type: &'a str,
+    #[serde(skip_serializing_if = "str::is_empty")]
+    description: &'a str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tests: Vec<&'static str>,
+}
+
+impl DbtExporter {
+    /// Export a data model to a dbt `sources.yml` document.
+    pub fn export_sources(model: &DataModel, table_ids: Option<&[Uuid]>) -> String {
+        let mut sources: Vec<Source> = Vec::new();
+        for table in model
+            .tables
+            .iter()
+            .filter(|t| table_ids.is_none_or(|ids| ids.contains(&t.id)))
+        {
+            let database = table.catalog_name.as_deref();
+            let schema = table.schema_name.as_deref();
+            let source = match sources
+                .iter_mut()
+                .position(|s| s.database == database && s.schema == schema)
+            {
+                Some(pos) => &mut sources[pos],
+                None => {
+                    sources.push(Source {
+                        name: schema.unwrap_or(&model.name),
+                        database,
+                        schema,
+                        tables: Vec::new(),
+                    });
+                    sources.last_mut().unwrap()
+                }
+            };
+            source.tables.push(Self::source_table(table));
+        }
+
+        serde_yaml::to_string(&SourcesFile {
+            version: 2,
+            sources,
+        })
+        .unwrap_or_default()
+    }
+
+    fn source_table(table: &Table) -> SourceTable<'_> {
+        SourceTable {
+            name: &table.name,
+            description: table
+                .odcl_metadata
+                .get("description")
+                .and_then(Value::as_str),
+            columns: table
+                .columns
+                .iter()
+                .map(|column| SourceColumn {
+                    name: &column.name,
+                    data_type: &column.data_type,
+                    description: &column.description,
+                    tests: if column.primary_key {
+                        vec!["unique", "not_null"]
+                    } else if !column.nullable {
+                        vec!["not_null"]
+                    } else {
+                        Vec::new()
+                    },
+                })
+                .collect(),
+        }
+    }
+}
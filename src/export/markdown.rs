@@ -0,0 +1,206 @@
+//! Markdown documentation exporter.
+//!
+//! Renders a human-readable data dictionary: one section per table with its metadata,
+//! columns, README (`odcl_metadata.readme`), observed statistics (`odcl_metadata.profile`)
+//! and linked work items (`odcl_metadata.workItems`), followed by relationships.
+
+use crate::models::{DataModel, Table};
+use serde_json::Value;
+use std::fmt::Write;
+use uuid::Uuid;
+
+/// Exporter for Markdown documentation.
+pub struct MarkdownExporter;
+
+impl MarkdownExporter {
+    /// Export a data model to a Markdown document.
+    pub fn export_model(model: &DataModel, table_ids: Option<&[Uuid]>) -> String {
+        let tables: Vec<&Table> = model
+            .tables
+            .iter()
+            .filter(|t| table_ids.is_none_or(|ids| ids.contains(&t.id)))
+            .collect();
+
+        let mut out = String::new();
+        let _ = writeln!(out, "# {}\n", model.name);
+        if let Some(description) = model.description.as_deref().filter(|d| !d.is_empty()) {
+            let _ = writeln!(out, "{}\n", description);
+        }
+        for table in &tables {
+            Self::write_table(&mut out, table);
+        }
+
+        let table_name = |id: Uuid| tables.iter().find(|t| t.id == id).map(|t| t.name.as_str());
+        let relationships: Vec<_> = model
+            .relationships
+            .iter()
+            .filter_map(|r| {
+                Some((
+                    table_name(r.source_table_id)?,
+                    table_name(r.target_table_id)?,
+                    r,
+                ))
+            })
+            .collect();
+        if !relationships.is_empty() {
+            let _ = writeln!(out, "## Relationships\n");
+            for (source, target, relationship) in relationships {
+                let cardinality = relationship
+                    .cardinality
+                    .as_ref()
+                    .map(|c| format!(" ({:?})", c))
+                    .unwrap_or_default();
+                let _ = writeln!(out, "- `{}` → `{}`{}", source, target, cardinality);
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    fn write_table(out: &mut String, table: &Table) {
+        let qualified: Vec<&str> = [table.catalog_name.as_deref(), table.schema_name.as_deref()]
+            .into_iter()
+            .flatten()
+            .chain([table.name.as_str()])
+            .collect();
+        let _ = writeln!(out, "## {}\n", table.name);
+        if qualified.len() > 1 {
+            let _ = writeln!(out, "Physical name: `{}`\n", qualified.join("."));
+        }
+        if let Some(description) = table
+            .odcl_metadata
+            .get("description")
+            .and_then(Value::as_str)
+        {
+            let _ = writeln!(out, "{}\n", description);
+        }
+        if !table.medallion_layers.is_empty() {
+            let layers: Vec<String> = table
+                .medallion_layers
+                .iter()
+                .map(|l| format!("{:?}", l))
+                .collect();
+            let _ = writeln!(out, "Layers: {}\n", layers.join(", "));
+        }
+        if !table.tags.is_empty() {
+            let _ = writeln!(out, "Tags: {}\n", table.tags.join(", "));
+        }
+
+        let _ = writeln!(out, "| Column | Type | Nullable | Key | Description |");
+        let _ = writeln!(out, "|---|---|---|---|---|");
+        for column in &table.columns {
+            let key = if column.primary_key {
+                "PK"
+            } else if column.foreign_key.is_some() {
+                "FK"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} |",
+                escape(&column.name),
+                escape(&column.data_type),
+                if column.nullable { "yes" } else { "no" },
+                key,
+                escape(&column.description)
+            );
+        }
+        out.push('\n');
+
+        if let Some(readme) = table
+            .odcl_metadata
+            .get("readme")
+            .and_then(Value::as_str)
+            .filter(|readme| !readme.trim().is_empty())
+        {
+            let _ = writeln!(out, "### Documentation\n");
+            let _ = writeln!(out, "{}\n", demote_headings(readme.trim()));
+        }
+
+        if let Some(profile) = table.odcl_metadata.get("profile") {
+            Self::write_profile(out, profile);
+        }
+
+        if let Some(items) = table
+            .odcl_metadata
+            .get("workItems")
+            .and_then(Value::as_array)
+            .filter(|items| !items.is_empty())
+        {
+            let _ = writeln!(out, "### Work items\n");
+            for item in items {
+                let text = |field: &str| item[field].as_str().unwrap_or_default();
+                let _ = write!(out, "- [{}]({})", text("key"), text("url"));
+                if !text("title").is_empty() {
+                    let _ = write!(out, " {}", text("title"));
+                }
+                if !text("status").is_empty() {
+                    let _ = write!(out, " — {}", text("status"));
+                }
+                out.push('\n');
+            }
+            out.push('\n');
+        }
+    }
+
+    fn write_profile(out: &mut String, profile: &Value) {
+        let _ = writeln!(out, "### Observed statistics\n");
+        let profiled_at = profile["profiled_at"].as_str().unwrap_or_default();
+        match profile["row_count"].as_u64() {
+            Some(rows) => {
+                let _ = writeln!(out, "Profiled {}: {} rows\n", profiled_at, rows);
+            }
+            None => {
+                let _ = writeln!(out, "Profiled {}\n", profiled_at);
+            }
+        }
+        let Some(columns) = profile["columns"].as_object().filter(|c| !c.is_empty()) else {
+            return;
+        };
+        let _ = writeln!(out, "| Column | Null % | Distinct | Min | Max |");
+        let _ = writeln!(out, "|---|---|---|---|---|");
+        let cell = |value: &Value| match value {
+            Value::Null => String::new(),
+            Value::String(s) => escape(s),
+            other => escape(&other.to_string()),
+        };
+        for (name, stats) in columns {
+            let _ = writeln!(
+                out,
+                "| {} | {} | {} | {} | {} |",
+                escape(name),
+                cell(&stats["null_percent"]),
+                cell(&stats["distinct_count"]),
+                cell(&stats["min"]),
+                cell(&stats["max"])
+            );
+        }
+        out.push('\n');
+    }
+}
+
+/// Nest a README's headings below the table's section (`#` becomes `####`), leaving
+/// fenced code blocks untouched.
+fn demote_headings(markdown: &str) -> String {
+    let mut in_fence = false;
+    markdown
+        .lines()
+        .map(|line| {
+            if line.trim_start().starts_with("```") {
+                in_fence = !in_fence;
+            }
+            if !in_fence && line.starts_with('#') {
+                format!("###{}", line)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Escape characters that would break a Markdown table cell.
+fn escape(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
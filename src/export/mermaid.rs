@@ -0,0 +1,205 @@
+//! Mermaid exporter for entity-relationship diagrams.
+//!
+//! Tables become entities with their columns as attributes (primary and foreign keys
+//! marked), and relationships become crow's-foot edges labelled with the foreign key
+//! column. Subtypes are joined to their supertype by `is a` edges labelled with their
+//! discriminator value. Mermaid identifiers only allow letters, digits, `_` and `-`, so other
+//! characters in table names, column names and types are replaced with `_`.
+
+use crate::models::enums::Cardinality;
+use crate::models::{DataModel, Relationship, Table};
+use crate::services::inheritance_service::hierarchies;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Exporter for Mermaid `erDiagram` documents.
+pub struct MermaidExporter;
+
+impl MermaidExporter {
+    /// Export a data model to a Mermaid ER diagram.
+    pub fn export_model(model: &DataModel, table_ids: Option<&[Uuid]>) -> String {
+        let tables: Vec<&Table> = model
+            .tables
+            .iter()
+            .filter(|t| table_ids.is_none_or(|ids| ids.contains(&t.id)))
+            .collect();
+        let entities: HashMap<Uuid, String> = tables
+            .iter()
+            .map(|t| (t.id, Self::identifier(&t.name)))
+            .collect();
+
+        let mut out = String::from("erDiagram\n");
+        for table in &tables {
+            out.push_str(&format!("    {} {{\n", entities[&table.id]));
+            for column in &table.columns {
+                let mut keys = Vec::new();
+                if column.primary_key {
+                    keys.push("PK");
+                }
+                if column.foreign_key.is_some() {
+                    keys.push("FK");
+                }
+                out.push_str(&format!(
+                    "        {} {}",
+                    Self::identifier(&column.data_type),
+                    Self::identifier(&column.name)
+                ));
+                if !keys.is_empty() {
+                    out.push_str(&format!(" {}", keys.join(", ")));
+                }
+                if !column.description.is_empty() {
+                    out.push_str(&format!(" \"{}\"", Self::quoted(&column.description)));
+                }
+                out.push('\n');
+            }
+            out.push_str("    }\n");
+        }
+
+        for relationship in &model.relationships {
+            let (Some(source), Some(target)) = (
+                entities.get(&relationship.source_table_id),
+                entities.get(&relationship.target_table_id),
+            ) else {
+                continue;
+            };
+            out.push_str(&format!(
+                "    {} {} {} : \"{}\"\n",
+                source,
+                Self::edge(relationship),
+                target,
+                Self::quoted(&Self::label(relationship))
+            ));
+        }
+
+        // Mermaid ER diagrams have no generalization, so a subtype is drawn as an optional
+        // one-to-one extension of its supertype
+        for (supertype, hierarchy) in hierarchies(&model.tables) {
+            let Some(target) = entities.get(&supertype.id) else {
+                continue;
+            };
+            for subtype in &hierarchy.subtypes {
+                if let Some(source) = entities.get(&subtype.table_id) {
+                    out.push_str(&format!(
+                        "    {} |o--|| {} : \"is a ({})\"\n",
+                        source,
+                        target,
+                        Self::quoted(&subtype.discriminator_value)
+                    ));
+                }
+            }
+        }
+        out
+    }
+
+    /// Crow's-foot notation for the relationship, one-to-many when the cardinality is unknown.
+    fn edge(relationship: &Relationship) -> String {
+        let (source_many, target_many) = match relationship.cardinality {
+            Some(Cardinality::OneToOne) => (false, false),
+            Some(Cardinality::ManyToOne) => (true, false),
+            Some(Cardinality::ManyToMany) => (true, true),
+            Some(Cardinality::OneToMany) | None => (false, true),
+        };
+        // "Many" ends default to optional and "one" ends to mandatory
+        let source_optional = relationship.source_optional.unwrap_or(source_many);
+        let target_optional = relationship.target_optional.unwrap_or(target_many);
+        let source = match (source_many, source_optional) {
+            (false, false) => "||",
+            (false, true) => "|o",
+            (true, false) => "}|",
+            (true, true) => "}o",
+        };
+        let target = match (target_many, target_optional) {
+            (false, false) => "||",
+            (false, true) => "o|",
+            (true, false) => "|{",
+            (true, true) => "o{",
+        };
+        format!("{}--{}", source, target)
+    }
+
+    fn label(relationship: &Relationship) -> String {
+        if let Some(fk) = &relationship.foreign_key_details {
+            return format!("{} -> {}", fk.source_column, fk.target_column);
+        }
+        match relationship.relationship_type {
+            Some(relationship_type) => format!("{:?}", relationship_type),
+            None => "relates to".to_string(),
+        }
+    }
+
+    fn identifier(name: &str) -> String {
+        let identifier: String = name
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        let identifier = identifier.trim_matches('_');
+        match identifier.chars().next() {
+            None => "unnamed".to_string(),
+            Some(c) if c.is_ascii_digit() || c == '-' => format!("_{}", identifier),
+            Some(_) => identifier.to_string(),
+        }
+    }
+
+    fn quoted(text: &str) -> String {
+        text.replace('"', "'").replace('\n', " ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::column::ForeignKey;
+    use crate::models::relationship::ForeignKeyDetails;
+    use crate::models::table::SUBTYPES_KEY;
+    use crate::models::{Column, Subtype, SubtypeHierarchy};
+
+    #[test]
+    fn test_export_mermaid_entities_and_edges() {
+        let mut id = Column::new("id".to_string(), "BIGINT".to_string());
+        id.primary_key = true;
+        let mut customers = Table::new("crm.customers".to_string(), vec![id.clone()]);
+        let vip = Table::new("vip".to_string(), vec![id.clone()]);
+        let hierarchy = SubtypeHierarchy {
+            discriminator_column: "tier".to_string(),
+            subtypes: vec![Subtype {
+                table_id: vip.id,
+                discriminator_value: "gold".to_string(),
+            }],
+            complete: false,
+        };
+        customers
+            .odcl_metadata
+            .insert(SUBTYPES_KEY.to_string(), serde_json::json!(hierarchy));
+
+        let mut customer_id = Column::new("customer_id".to_string(), "VARCHAR(36)".to_string());
+        customer_id.foreign_key = Some(ForeignKey {
+            table_id: customers.id.to_string(),
+            column_name: "id".to_string(),
+            constraint_name: None,
+            key_position: None,
+        });
+        customer_id.description = "Buyer \"id\"".to_string();
+        let orders = Table::new("sales.orders".to_string(), vec![id, customer_id]);
+
+        let mut relationship = Relationship::new(orders.id, customers.id);
+        relationship.cardinality = Some(Cardinality::ManyToOne);
+        relationship.foreign_key_details = Some(ForeignKeyDetails::new("customer_id", "id"));
+
+        let mut model = DataModel::new("enterprise".to_string(), String::new(), String::new());
+        model.tables = vec![customers, orders, vip];
+        model.relationships = vec![relationship];
+
+        let diagram = MermaidExporter::export_model(&model, None);
+        assert!(diagram.starts_with("erDiagram\n"));
+        assert!(diagram.contains("    crm_customers {\n        BIGINT id PK\n    }"));
+        assert!(diagram.contains("        VARCHAR_36 customer_id FK \"Buyer 'id'\""));
+        assert!(diagram.contains("    sales_orders }o--|| crm_customers : \"customer_id -> id\""));
+        assert!(diagram.contains("    vip |o--|| crm_customers : \"is a (gold)\""));
+    }
+}
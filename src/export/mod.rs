@@ -3,6 +3,14 @@
 //! Uses SDK exporters to avoid code duplication.
 
 pub mod avro;
+pub mod contract_tests;
+pub mod dbml;
+pub mod dbt;
 pub mod json_schema;
+pub mod markdown;
+pub mod mermaid;
 pub mod odcs;
+pub mod odps;
+pub mod prisma;
 pub mod protobuf;
+pub mod sql_queries;
@@ -868,6 +868,10 @@ mod tests {
                 errors: Vec::new(),
                 quality: Vec::new(),
                 enum_values: Vec::new(),
+                classification: None,
+                masking_policy: None,
+                glossary_links: Vec::new(),
+                custom_properties: HashMap::new(),
                 column_order: 0,
             }],
             database_type: None,
@@ -878,6 +882,7 @@ mod tests {
             data_vault_classification: None,
             modeling_level: None,
             tags: vec!["test".to_string()],
+            aliases: Vec::new(),
             odcl_metadata: HashMap::new(),
             position: None,
             yaml_file_path: None,
@@ -0,0 +1,277 @@
+//! ODPS exporter for generating Open Data Product Standard v1.0.0 YAML.
+//!
+//! The document describes a domain as a data product: its owner as the team, its data
+//! product ports as input and output ports, and its SLA as a custom property. ODPS ports
+//! reference one data contract each, so every table of a port becomes its own ODPS port
+//! whose `contractId` is the table's ODCS contract id (the table id). The table schema and
+//! quality rules are attached to each output port as custom properties. A domain without a
+//! data product definition exports every table as an output port.
+
+use crate::models::{DataModel, Table};
+use crate::services::data_product_service::{DataProduct, slug};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// ODPS version written by this exporter
+const ODPS_API_VERSION: &str = "v1.0.0";
+
+/// Exporter for ODPS (Open Data Product Standard) YAML documents.
+pub struct OdpsExporter;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Document {
+    api_version: &'static str,
+    kind: &'static str,
+    id: String,
+    name: String,
+    status: &'static str,
+    domain: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<Description>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    input_ports: Vec<Port>,
+    output_ports: Vec<Port>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    team: Option<Team>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    custom_properties: Vec<CustomProperty>,
+}
+
+#[derive(Serialize)]
+struct Description {
+    purpose: String,
+}
+
+#[derive(Serialize)]
+struct Team {
+    members: Vec<TeamMember>,
+}
+
+#[derive(Serialize)]
+struct TeamMember {
+    username: String,
+    role: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Port {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    port_type: Option<&'static str>,
+    version: String,
+    contract_id: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    custom_properties: Vec<CustomProperty>,
+}
+
+#[derive(Serialize)]
+struct CustomProperty {
+    property: &'static str,
+    value: Value,
+}
+
+/// Contract version of a table, as written by the ODCS export
+fn contract_version(table: &Table) -> String {
+    table
+        .odcl_metadata
+        .get("version")
+        .and_then(Value::as_str)
+        .unwrap_or("1.0.0")
+        .to_string()
+}
+
+impl OdpsExporter {
+    /// Export the domain `model` and its data product definition to ODPS YAML.
+    ///
+    /// `workspace_tables` resolves the tables of other domains consumed by input ports;
+    /// tables that cannot be resolved are listed by id.
+    pub fn export_product(
+        model: &DataModel,
+        product: &DataProduct,
+        workspace_tables: &HashMap<Uuid, &Table>,
+    ) -> Result<String, serde_yaml::Error> {
+        let domain = model.name.as_str();
+        let own: HashMap<Uuid, &Table> = model.tables.iter().map(|t| (t.id, t)).collect();
+
+        let mut output_ports = Vec::new();
+        if product.output_ports.is_empty() {
+            for table in &model.tables {
+                output_ports.push(Self::output_port(table, None));
+            }
+        }
+        for port in &product.output_ports {
+            for table in port.table_ids.iter().filter_map(|id| own.get(id)) {
+                let mut output = Self::output_port(table, port.description.clone());
+                if port.table_ids.len() > 1 {
+                    output.name = format!("{}-{}", slug(&port.name), table.name);
+                } else {
+                    output.name = port.name.clone();
+                }
+                output_ports.push(output);
+            }
+        }
+
+        let mut input_ports = Vec::new();
+        for port in &product.input_ports {
+            for id in &port.table_ids {
+                let table = workspace_tables.get(id);
+                input_ports.push(Port {
+                    name: match table {
+                        Some(table) => format!("{}.{}", port.source_domain, table.name),
+                        None => format!("{}.{}", port.source_domain, id),
+                    },
+                    description: port.description.clone(),
+                    port_type: None,
+                    version: table
+                        .map(|t| contract_version(t))
+                        .unwrap_or_else(|| "1.0.0".to_string()),
+                    contract_id: id.to_string(),
+                    custom_properties: Vec::new(),
+                });
+            }
+        }
+
+        let mut custom_properties = Vec::new();
+        if let Some(sla) = &product.sla {
+            custom_properties.push(CustomProperty {
+                property: "sla",
+                value: serde_json::to_value(sla).unwrap_or_default(),
+            });
+        }
+
+        let name = product.name.clone().unwrap_or_else(|| domain.to_string());
+        let document = Document {
+            api_version: ODPS_API_VERSION,
+            kind: "DataProduct",
+            id: slug(&name),
+            name,
+            status: "active",
+            domain: domain.to_string(),
+            description: product
+                .description
+                .clone()
+                .or_else(|| model.description.clone())
+                .map(|purpose| Description { purpose }),
+            input_ports,
+            output_ports,
+            team: product.owner.clone().map(|owner| Team {
+                members: vec![TeamMember {
+                    username: owner,
+                    role: "owner",
+                }],
+            }),
+            custom_properties,
+        };
+        serde_yaml::to_string(&document)
+    }
+
+    /// Output port serving one table, with its schema and quality rules
+    fn output_port(table: &Table, description: Option<String>) -> Port {
+        let schema: Vec<Value> = table
+            .columns
+            .iter()
+            .map(|column| {
+                let mut property = json!({
+                    "name": column.name,
+                    "physicalType": column.data_type,
+                    "required": !column.nullable,
+                    "primaryKey": column.primary_key,
+                });
+                if !column.description.is_empty() {
+                    property["description"] = json!(column.description);
+                }
+                property
+            })
+            .collect();
+        let mut quality: Vec<Value> = table.quality.iter().map(|rule| json!(rule)).collect();
+        for column in &table.columns {
+            quality.extend(column.quality.iter().map(|rule| {
+                let mut rule = json!(rule);
+                rule["column"] = json!(column.name);
+                rule
+            }));
+        }
+
+        let mut custom_properties = vec![CustomProperty {
+            property: "schema",
+            value: json!({ "name": table.name, "properties": schema }),
+        }];
+        if !quality.is_empty() {
+            custom_properties.push(CustomProperty {
+                property: "quality",
+                value: Value::Array(quality),
+            });
+        }
+        Port {
+            name: table.name.clone(),
+            description,
+            port_type: Some("tables"),
+            version: contract_version(table),
+            contract_id: table.id.to_string(),
+            custom_properties,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Column;
+    use crate::services::data_product_service::{DataProductSla, InputPort, OutputPort};
+
+    #[test]
+    fn test_export_odps_ports_schema_quality_and_sla() {
+        let mut id = Column::new("id".to_string(), "BIGINT".to_string());
+        id.primary_key = true;
+        id.nullable = false;
+        let mut orders = Table::new("orders".to_string(), vec![id]);
+        orders
+            .quality
+            .push(HashMap::from([("rule".to_string(), json!("rowCount"))]));
+        let customers = Table::new("customers".to_string(), Vec::new());
+
+        let mut model = DataModel::new("sales".to_string(), String::new(), String::new());
+        model.tables = vec![orders.clone()];
+        let product = DataProduct {
+            owner: Some("owner@example.com".to_string()),
+            sla: Some(DataProductSla {
+                availability: Some("99.9%".to_string()),
+                ..Default::default()
+            }),
+            output_ports: vec![OutputPort {
+                name: "Orders".to_string(),
+                description: Some("Confirmed orders".to_string()),
+                table_ids: vec![orders.id],
+            }],
+            input_ports: vec![InputPort {
+                name: "Customers".to_string(),
+                description: None,
+                source_domain: "crm".to_string(),
+                table_ids: vec![customers.id],
+            }],
+            ..Default::default()
+        };
+        let workspace = HashMap::from([(customers.id, &customers)]);
+
+        let yaml = OdpsExporter::export_product(&model, &product, &workspace).unwrap();
+        assert!(yaml.starts_with("apiVersion: v1.0.0\nkind: DataProduct\nid: sales\n"));
+        assert!(yaml.contains(&format!("contractId: {}", orders.id)));
+        assert!(yaml.contains("name: crm.customers"));
+        assert!(yaml.contains("physicalType: BIGINT"));
+        assert!(yaml.contains("rule: rowCount"));
+        assert!(yaml.contains("availability: 99.9%"));
+        assert!(yaml.contains("username: owner@example.com"));
+
+        // Without a definition every table is an output port
+        let yaml =
+            OdpsExporter::export_product(&model, &DataProduct::default(), &HashMap::new()).unwrap();
+        assert!(yaml.contains("- name: orders\n"));
+        assert!(!yaml.contains("inputPorts"));
+    }
+}
@@ -0,0 +1,410 @@
+//! Prisma schema exporter for generating `schema.prisma` files from data models.
+//!
+//! Tables become models and columns become fields. Physical names are preserved
+//! with `@@map` / `@map` whenever the Prisma-style identifier differs, and
+//! foreign key relationships become `@relation` fields with back-relations.
+
+use crate::models::enums::{Cardinality, RelationshipType};
+use crate::models::{DataModel, Table};
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+/// Exporter for Prisma schema format.
+pub struct PrismaExporter;
+
+/// A relation field to render on a model.
+struct RelationField {
+    name: String,
+    rendered: String,
+}
+
+impl PrismaExporter {
+    /// Export a data model to a Prisma schema.
+    ///
+    /// `dialect` selects the datasource provider (postgres by default).
+    pub fn export_model(
+        model: &DataModel,
+        table_ids: Option<&[Uuid]>,
+        dialect: Option<&str>,
+    ) -> String {
+        let tables: Vec<&Table> = if let Some(ids) = table_ids {
+            model
+                .tables
+                .iter()
+                .filter(|t| ids.contains(&t.id))
+                .collect()
+        } else {
+            model.tables.iter().collect()
+        };
+
+        let model_names: HashMap<Uuid, String> = tables
+            .iter()
+            .map(|t| (t.id, Self::to_model_name(&t.name)))
+            .collect();
+        let tables_by_id: HashMap<Uuid, &Table> = tables.iter().map(|t| (t.id, *t)).collect();
+
+        // Field names already used per model, so relation fields don't collide
+        let mut used_names: HashMap<Uuid, HashSet<String>> = tables
+            .iter()
+            .map(|t| {
+                (
+                    t.id,
+                    t.columns
+                        .iter()
+                        .map(|c| Self::to_field_name(&c.name))
+                        .collect(),
+                )
+            })
+            .collect();
+        let mut relation_fields: HashMap<Uuid, Vec<RelationField>> = HashMap::new();
+
+        // Count relationships per table pair to decide whether relation names are needed
+        let mut pair_counts: HashMap<(Uuid, Uuid), usize> = HashMap::new();
+        for rel in &model.relationships {
+            let key = if rel.source_table_id < rel.target_table_id {
+                (rel.source_table_id, rel.target_table_id)
+            } else {
+                (rel.target_table_id, rel.source_table_id)
+            };
+            *pair_counts.entry(key).or_default() += 1;
+        }
+
+        for rel in &model.relationships {
+            if !matches!(
+                rel.relationship_type,
+                None | Some(RelationshipType::ForeignKey)
+            ) || rel.cardinality == Some(Cardinality::ManyToMany)
+            {
+                continue;
+            }
+            // The child table holds the foreign key; OneToMany is stored parent -> child
+            let (child_id, parent_id) = if rel.cardinality == Some(Cardinality::OneToMany) {
+                (rel.target_table_id, rel.source_table_id)
+            } else {
+                (rel.source_table_id, rel.target_table_id)
+            };
+            let (Some(child), Some(parent)) =
+                (tables_by_id.get(&child_id), tables_by_id.get(&parent_id))
+            else {
+                continue;
+            };
+            let Some((fk_column, ref_column)) = Self::resolve_columns(rel, child, parent) else {
+                continue;
+            };
+            let Some(fk) = child.columns.iter().find(|c| c.name == fk_column) else {
+                continue;
+            };
+
+            let child_model = &model_names[&child_id];
+            let parent_model = &model_names[&parent_id];
+            let pair = if child_id < parent_id {
+                (child_id, parent_id)
+            } else {
+                (parent_id, child_id)
+            };
+            let relation_name =
+                if pair_counts.get(&pair).copied().unwrap_or(0) > 1 || child_id == parent_id {
+                    Some(format!(
+                        "{}_{}",
+                        child_model,
+                        Self::to_field_name(&fk_column)
+                    ))
+                } else {
+                    None
+                };
+            let name_arg = relation_name
+                .as_ref()
+                .map(|n| format!("\"{}\", ", n))
+                .unwrap_or_default();
+
+            // Forward relation on the child model
+            let base = fk_column
+                .strip_suffix("_id")
+                .or_else(|| fk_column.strip_suffix("Id"))
+                .filter(|b| !b.is_empty())
+                .map(Self::to_field_name)
+                .unwrap_or_else(|| Self::lower_first(parent_model));
+            let forward = Self::unique_name(used_names.entry(child_id).or_default(), &base);
+            relation_fields
+                .entry(child_id)
+                .or_default()
+                .push(RelationField {
+                    rendered: format!(
+                        "{} {}{} @relation({}fields: [{}], references: [{}])",
+                        forward,
+                        parent_model,
+                        if fk.nullable { "?" } else { "" },
+                        name_arg,
+                        Self::to_field_name(&fk_column),
+                        Self::to_field_name(&ref_column)
+                    ),
+                    name: forward,
+                });
+
+            // Back relation on the parent model
+            let back_base = Self::lower_first(child_model);
+            let back_base =
+                if rel.cardinality == Some(Cardinality::OneToOne) || back_base.ends_with('s') {
+                    back_base
+                } else {
+                    format!("{}s", back_base)
+                };
+            let back = Self::unique_name(used_names.entry(parent_id).or_default(), &back_base);
+            let back_type = if rel.cardinality == Some(Cardinality::OneToOne) {
+                format!("{}?", child_model)
+            } else {
+                format!("{}[]", child_model)
+            };
+            let back_rendered = match &relation_name {
+                Some(name) => format!("{} {} @relation(\"{}\")", back, back_type, name),
+                None => format!("{} {}", back, back_type),
+            };
+            relation_fields
+                .entry(parent_id)
+                .or_default()
+                .push(RelationField {
+                    name: back,
+                    rendered: back_rendered,
+                });
+        }
+
+        let provider = match dialect.map(|d| d.to_lowercase()) {
+            Some(d) if d == "mysql" => "mysql",
+            Some(d) if d == "sqlite" => "sqlite",
+            Some(d) if d == "sqlserver" || d == "mssql" => "sqlserver",
+            _ => "postgresql",
+        };
+
+        let mut out = String::new();
+        out.push_str("generator client {\n  provider = \"prisma-client-js\"\n}\n\n");
+        out.push_str(&format!(
+            "datasource db {{\n  provider = \"{}\"\n  url      = env(\"DATABASE_URL\")\n}}\n",
+            provider
+        ));
+
+        for table in &tables {
+            out.push('\n');
+            out.push_str(&Self::export_table(
+                table,
+                &model_names[&table.id],
+                relation_fields
+                    .get(&table.id)
+                    .map(|v| v.as_slice())
+                    .unwrap_or(&[]),
+            ));
+        }
+        out
+    }
+
+    /// Render a single model block.
+    fn export_table(table: &Table, model_name: &str, relations: &[RelationField]) -> String {
+        let primary_keys: Vec<&str> = table
+            .columns
+            .iter()
+            .filter(|c| c.primary_key)
+            .map(|c| c.name.as_str())
+            .collect();
+
+        let mut lines: Vec<(String, String, String)> = Vec::new();
+        for column in &table.columns {
+            let field_name = Self::to_field_name(&column.name);
+            let mut field_type = Self::map_data_type(&column.data_type).to_string();
+            if column.nullable && !column.primary_key {
+                field_type.push('?');
+            }
+            let mut attrs = Vec::new();
+            if column.primary_key && primary_keys.len() == 1 {
+                attrs.push("@id".to_string());
+            }
+            if field_name != column.name {
+                attrs.push(format!("@map(\"{}\")", column.name));
+            }
+            lines.push((field_name, field_type, attrs.join(" ")));
+        }
+
+        let name_width = lines
+            .iter()
+            .map(|l| l.0.len())
+            .chain(relations.iter().map(|r| r.name.len()))
+            .max()
+            .unwrap_or(0);
+        let type_width = lines.iter().map(|l| l.1.len()).max().unwrap_or(0);
+
+        let mut out = String::new();
+        if let Some(description) = table
+            .odcl_metadata
+            .get("description")
+            .and_then(|d| d.as_str())
+        {
+            out.push_str(&format!("/// {}\n", description.replace('\n', " ")));
+        }
+        out.push_str(&format!("model {} {{\n", model_name));
+        for (column, (name, field_type, attrs)) in table.columns.iter().zip(&lines) {
+            if !column.description.is_empty() {
+                out.push_str(&format!(
+                    "  /// {}\n",
+                    column.description.replace('\n', " ")
+                ));
+            }
+            let line = format!(
+                "  {:name_width$} {:type_width$} {}",
+                name,
+                field_type,
+                attrs,
+                name_width = name_width,
+                type_width = type_width
+            );
+            out.push_str(line.trim_end());
+            out.push('\n');
+        }
+        for relation in relations {
+            let (name, rest) = relation
+                .rendered
+                .split_once(' ')
+                .unwrap_or((relation.rendered.as_str(), ""));
+            out.push_str(&format!(
+                "  {:name_width$} {}\n",
+                name,
+                rest,
+                name_width = name_width
+            ));
+        }
+
+        if primary_keys.len() > 1 {
+            let fields: Vec<String> = primary_keys
+                .iter()
+                .map(|c| Self::to_field_name(c))
+                .collect();
+            out.push_str(&format!("\n  @@id([{}])\n", fields.join(", ")));
+        }
+        if model_name != table.name {
+            if primary_keys.len() <= 1 {
+                out.push('\n');
+            }
+            out.push_str(&format!("  @@map(\"{}\")\n", table.name));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    /// Determine the (foreign key column, referenced column) pair for a relationship.
+    fn resolve_columns(
+        rel: &crate::models::Relationship,
+        child: &Table,
+        parent: &Table,
+    ) -> Option<(String, String)> {
+        if let Some(details) = &rel.foreign_key_details {
+            return Some((details.source_column.clone(), details.target_column.clone()));
+        }
+        // Fall back to the `<parent>_id` naming convention
+        let ref_column = parent.columns.iter().find(|c| c.primary_key)?.name.clone();
+        let fk_name = format!("{}_id", parent.name.to_lowercase());
+        let fk_column = child
+            .columns
+            .iter()
+            .find(|c| c.name.to_lowercase() == fk_name)?
+            .name
+            .clone();
+        Some((fk_column, ref_column))
+    }
+
+    /// Map SQL/ODCL data types to Prisma scalar types.
+    fn map_data_type(data_type: &str) -> &'static str {
+        let upper = data_type.to_uppercase();
+        let base = upper.split('(').next().unwrap_or_default().trim();
+        match base {
+            "INT" | "INTEGER" | "SMALLINT" | "TINYINT" | "SERIAL" => "Int",
+            "BIGINT" | "BIGSERIAL" | "LONG" => "BigInt",
+            "FLOAT" | "REAL" | "DOUBLE" | "DOUBLE PRECISION" => "Float",
+            "DECIMAL" | "NUMERIC" | "NUMBER" => "Decimal",
+            "BOOLEAN" | "BOOL" => "Boolean",
+            "DATE" | "DATETIME" | "TIME" | "TIMESTAMP" | "TIMESTAMP_NTZ" | "TIMESTAMPTZ" => {
+                "DateTime"
+            }
+            "JSON" | "JSONB" | "VARIANT" => "Json",
+            "BYTES" | "BINARY" | "VARBINARY" | "BLOB" | "BYTEA" => "Bytes",
+            _ => "String",
+        }
+    }
+
+    /// Convert a table name to a PascalCase model name.
+    fn to_model_name(name: &str) -> String {
+        let pascal: String = name
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|p| !p.is_empty())
+            .map(|part| {
+                let mut chars = part.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+                    None => String::new(),
+                }
+            })
+            .collect();
+        if pascal.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+            format!("T{}", pascal)
+        } else {
+            pascal
+        }
+    }
+
+    /// Convert a column name to a camelCase field name.
+    fn to_field_name(name: &str) -> String {
+        let model = Self::to_model_name(name);
+        Self::lower_first(&model)
+    }
+
+    fn lower_first(name: &str) -> String {
+        let mut chars = name.chars();
+        match chars.next() {
+            Some(first) => first.to_lowercase().chain(chars).collect(),
+            None => String::new(),
+        }
+    }
+
+    /// Return a field name not yet used on the model, and reserve it.
+    fn unique_name(used: &mut HashSet<String>, base: &str) -> String {
+        let mut name = base.to_string();
+        let mut suffix = 2;
+        while used.contains(&name) {
+            name = format!("{}{}", base, suffix);
+            suffix += 1;
+        }
+        used.insert(name.clone());
+        name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::relationship::ForeignKeyDetails;
+    use crate::models::{Column, Relationship};
+
+    #[test]
+    fn test_export_prisma_with_relation_and_maps() {
+        let mut id = Column::new("id".to_string(), "BIGINT".to_string());
+        id.primary_key = true;
+        id.nullable = false;
+        let users = Table::new("users".to_string(), vec![id.clone()]);
+
+        let mut author_id = Column::new("author_id".to_string(), "BIGINT".to_string());
+        author_id.nullable = false;
+        let posts = Table::new("blog_posts".to_string(), vec![id, author_id]);
+
+        let mut relationship = Relationship::new(posts.id, users.id);
+        relationship.cardinality = Some(Cardinality::ManyToOne);
+        relationship.foreign_key_details = Some(ForeignKeyDetails::new("author_id", "id"));
+
+        let mut model = DataModel::new("test".to_string(), String::new(), String::new());
+        model.tables = vec![users, posts];
+        model.relationships = vec![relationship];
+
+        let schema = PrismaExporter::export_model(&model, None, None);
+        assert!(schema.contains("provider = \"postgresql\""));
+        assert!(schema.contains("model BlogPosts {"));
+        assert!(schema.contains("@@map(\"blog_posts\")"));
+        assert!(schema.contains("authorId BigInt @map(\"author_id\")"));
+        assert!(schema.contains("author   Users @relation(fields: [authorId], references: [id])"));
+        assert!(schema.contains("blogPosts BlogPosts[]"));
+    }
+}
@@ -0,0 +1,577 @@
+//! SQL query template exporter.
+//!
+//! Renders parameterized statement templates for each table: INSERT, UPSERT and SELECT by
+//! primary key, plus an SCD Type 2 MERGE for tables with that pattern. Statements are named
+//! with `-- name:` comments (the yesql/sqlc/aiosql convention) and list their bind
+//! parameters with the modeled types in a `-- params:` comment, in placeholder order.
+//!
+//! Placeholders follow the dialect:
+//! - postgres: `$1`, `$2`, ... (a repeated parameter reuses its number)
+//! - mysql: `?`
+//! - sqlserver: `@name`
+//! - databricks and standard SQL: `:name`
+
+use crate::models::enums::SCDPattern;
+use crate::models::{Column, DataModel, Table};
+use uuid::Uuid;
+
+/// Exporter for SQL query templates.
+pub struct SqlQueriesExporter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dialect {
+    Standard,
+    Postgres,
+    MySql,
+    SqlServer,
+    Databricks,
+}
+
+impl Dialect {
+    fn parse(name: Option<&str>) -> Self {
+        match name.map(str::to_ascii_lowercase).as_deref() {
+            Some("postgres" | "postgresql") => Self::Postgres,
+            Some("mysql") => Self::MySql,
+            Some("sqlserver" | "mssql") => Self::SqlServer,
+            Some("databricks" | "databricks_delta") => Self::Databricks,
+            _ => Self::Standard,
+        }
+    }
+
+    fn quote(self, identifier: &str) -> String {
+        match self {
+            Self::MySql | Self::Databricks => format!("`{}`", identifier.replace('`', "``")),
+            Self::SqlServer => format!("[{}]", identifier.replace(']', "]]")),
+            _ => format!("\"{}\"", identifier.replace('"', "\"\"")),
+        }
+    }
+
+    fn bool_literal(self, value: bool) -> &'static str {
+        match (self, value) {
+            (Self::SqlServer, true) => "1",
+            (Self::SqlServer, false) => "0",
+            (_, true) => "TRUE",
+            (_, false) => "FALSE",
+        }
+    }
+
+    /// `a` differs from `b`, treating NULLs as values
+    fn distinct(self, a: &str, b: &str) -> String {
+        match self {
+            Self::MySql => format!("NOT ({} <=> {})", a, b),
+            _ => format!("{} IS DISTINCT FROM {}", a, b),
+        }
+    }
+}
+
+/// Bind parameters of one statement, in placeholder order
+struct Params {
+    dialect: Dialect,
+    bound: Vec<(String, String)>,
+}
+
+impl Params {
+    fn new(dialect: Dialect) -> Self {
+        Self {
+            dialect,
+            bound: Vec::new(),
+        }
+    }
+
+    /// Placeholder for a parameter, recording its type.
+    fn bind(&mut self, name: &str, data_type: &str) -> String {
+        let name: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let position = self.bound.iter().position(|(n, _)| *n == name);
+        // `?` placeholders are positional, so every use needs its own value
+        if position.is_none() || self.dialect == Dialect::MySql {
+            self.bound.push((name.clone(), data_type.to_string()));
+        }
+        match self.dialect {
+            Dialect::Postgres => format!("${}", position.unwrap_or(self.bound.len() - 1) + 1),
+            Dialect::MySql => "?".to_string(),
+            Dialect::SqlServer => format!("@{}", name),
+            _ => format!(":{}", name),
+        }
+    }
+
+    fn column(&mut self, column: &Column) -> String {
+        self.bind(&column.name, &column.data_type)
+    }
+
+    /// Render a named statement with its parameter list.
+    fn statement(self, name: &str, sql: &str) -> String {
+        let params: Vec<String> = self
+            .bound
+            .iter()
+            .map(|(name, data_type)| format!("{} {}", name, data_type))
+            .collect();
+        format!(
+            "-- name: {}\n-- params: {}\n{}",
+            name,
+            params.join(", "),
+            sql
+        )
+    }
+}
+
+/// Validity columns of an SCD Type 2 table
+struct Scd2Columns<'a> {
+    valid_from: &'a Column,
+    valid_to: &'a Column,
+    is_current: Option<&'a Column>,
+}
+
+fn find_column<'a>(columns: &[&'a Column], names: &[&str]) -> Option<&'a Column> {
+    columns
+        .iter()
+        .copied()
+        .find(|c| names.iter().any(|n| c.name.eq_ignore_ascii_case(n)))
+}
+
+fn scd2_columns<'a>(columns: &[&'a Column]) -> Option<Scd2Columns<'a>> {
+    Some(Scd2Columns {
+        valid_from: find_column(
+            columns,
+            &[
+                "valid_from",
+                "effective_from",
+                "effective_date",
+                "start_date",
+                "dbt_valid_from",
+            ],
+        )?,
+        valid_to: find_column(
+            columns,
+            &[
+                "valid_to",
+                "effective_to",
+                "end_date",
+                "expiry_date",
+                "dbt_valid_to",
+            ],
+        )?,
+        is_current: find_column(columns, &["is_current", "current_flag", "is_active"]),
+    })
+}
+
+impl SqlQueriesExporter {
+    /// Export query templates for the tables of a model.
+    ///
+    /// `dialect` selects quoting, placeholders and the UPSERT form (standard SQL by default).
+    pub fn export_model(
+        model: &DataModel,
+        table_ids: Option<&[Uuid]>,
+        dialect: Option<&str>,
+    ) -> String {
+        let dialect = Dialect::parse(dialect);
+        model
+            .tables
+            .iter()
+            .filter(|t| table_ids.is_none_or(|ids| ids.contains(&t.id)))
+            .map(|table| Self::export_table(table, dialect))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn export_table(table: &Table, dialect: Dialect) -> String {
+        let name = [
+            table.catalog_name.as_deref(),
+            table.schema_name.as_deref(),
+            Some(table.name.as_str()),
+        ]
+        .into_iter()
+        .flatten()
+        .map(|part| dialect.quote(part))
+        .collect::<Vec<_>>()
+        .join(".");
+        let slug = table
+            .name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect::<String>()
+            .to_ascii_lowercase();
+        let mut columns: Vec<&Column> = table.columns.iter().collect();
+        columns.sort_by_key(|c| c.column_order);
+        let keys: Vec<&Column> = columns.iter().copied().filter(|c| c.primary_key).collect();
+
+        let mut out = format!("-- Table: {}\n\n", table.name);
+        out.push_str(&Self::insert(&name, &slug, &columns, dialect));
+        if keys.is_empty() {
+            out.push_str(&format!(
+                "\n-- {} has no primary key; UPSERT and SELECT by key are not generated\n",
+                table.name
+            ));
+        } else {
+            out.push('\n');
+            out.push_str(&Self::upsert(&name, &slug, &columns, &keys, dialect));
+            out.push('\n');
+            out.push_str(&Self::select_by_key(&name, &slug, &columns, &keys, dialect));
+        }
+        if table.scd_pattern == Some(SCDPattern::Type2) {
+            out.push('\n');
+            out.push_str(&Self::scd2(table, &name, &slug, &columns, dialect));
+        }
+        out
+    }
+
+    fn column_list(columns: &[&Column], dialect: Dialect) -> String {
+        columns
+            .iter()
+            .map(|c| dialect.quote(&c.name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn insert(name: &str, slug: &str, columns: &[&Column], dialect: Dialect) -> String {
+        let mut params = Params::new(dialect);
+        let values: Vec<String> = columns.iter().map(|c| params.column(c)).collect();
+        let sql = format!(
+            "INSERT INTO {} ({})\nVALUES ({});\n",
+            name,
+            Self::column_list(columns, dialect),
+            values.join(", ")
+        );
+        params.statement(&format!("insert_{}", slug), &sql)
+    }
+
+    fn upsert(
+        name: &str,
+        slug: &str,
+        columns: &[&Column],
+        keys: &[&Column],
+        dialect: Dialect,
+    ) -> String {
+        let mut params = Params::new(dialect);
+        let quote = |c: &Column| dialect.quote(&c.name);
+        let non_keys: Vec<&Column> = columns.iter().copied().filter(|c| !c.primary_key).collect();
+        let sql = match dialect {
+            Dialect::Postgres | Dialect::MySql => {
+                let values: Vec<String> = columns.iter().map(|c| params.column(c)).collect();
+                let conflict = if dialect == Dialect::Postgres {
+                    let keys: Vec<String> = keys.iter().map(|c| quote(c)).collect();
+                    if non_keys.is_empty() {
+                        format!("ON CONFLICT ({}) DO NOTHING", keys.join(", "))
+                    } else {
+                        let updates: Vec<String> = non_keys
+                            .iter()
+                            .map(|c| format!("{} = EXCLUDED.{}", quote(c), quote(c)))
+                            .collect();
+                        format!(
+                            "ON CONFLICT ({}) DO UPDATE SET\n    {}",
+                            keys.join(", "),
+                            updates.join(",\n    ")
+                        )
+                    }
+                } else {
+                    // Setting a key to itself makes the upsert of a key-only row a no-op
+                    let updated = if non_keys.is_empty() { keys } else { &non_keys };
+                    let updates: Vec<String> = updated
+                        .iter()
+                        .map(|c| format!("{} = VALUES({})", quote(c), quote(c)))
+                        .collect();
+                    format!("ON DUPLICATE KEY UPDATE\n    {}", updates.join(",\n    "))
+                };
+                format!(
+                    "INSERT INTO {} ({})\nVALUES ({})\n{};\n",
+                    name,
+                    Self::column_list(columns, dialect),
+                    values.join(", "),
+                    conflict
+                )
+            }
+            _ => {
+                let source: Vec<String> = columns
+                    .iter()
+                    .map(|c| format!("{} AS {}", params.column(c), quote(c)))
+                    .collect();
+                let on: Vec<String> = keys
+                    .iter()
+                    .map(|c| format!("target.{} = source.{}", quote(c), quote(c)))
+                    .collect();
+                let mut merge = format!(
+                    "MERGE INTO {} AS target\nUSING (SELECT {}) AS source\nON {}\n",
+                    name,
+                    source.join(", "),
+                    on.join(" AND ")
+                );
+                if !non_keys.is_empty() {
+                    let updates: Vec<String> = non_keys
+                        .iter()
+                        .map(|c| format!("{} = source.{}", quote(c), quote(c)))
+                        .collect();
+                    merge.push_str(&format!(
+                        "WHEN MATCHED THEN UPDATE SET\n    {}\n",
+                        updates.join(",\n    ")
+                    ));
+                }
+                let values: Vec<String> = columns
+                    .iter()
+                    .map(|c| format!("source.{}", quote(c)))
+                    .collect();
+                merge.push_str(&format!(
+                    "WHEN NOT MATCHED THEN INSERT ({})\n    VALUES ({});\n",
+                    Self::column_list(columns, dialect),
+                    values.join(", ")
+                ));
+                merge
+            }
+        };
+        params.statement(&format!("upsert_{}", slug), &sql)
+    }
+
+    fn select_by_key(
+        name: &str,
+        slug: &str,
+        columns: &[&Column],
+        keys: &[&Column],
+        dialect: Dialect,
+    ) -> String {
+        let mut params = Params::new(dialect);
+        let conditions: Vec<String> = keys
+            .iter()
+            .map(|c| format!("{} = {}", dialect.quote(&c.name), params.column(c)))
+            .collect();
+        let sql = format!(
+            "SELECT {}\nFROM {}\nWHERE {};\n",
+            Self::column_list(columns, dialect),
+            name,
+            conditions.join(" AND ")
+        );
+        params.statement(&format!("select_{}_by_pk", slug), &sql)
+    }
+
+    /// SCD Type 2 load in two statements: the first closes the current version of changed
+    /// rows (and inserts new business keys), the second inserts the new version of rows
+    /// that no longer have a current version.
+    fn scd2(
+        table: &Table,
+        name: &str,
+        slug: &str,
+        columns: &[&Column],
+        dialect: Dialect,
+    ) -> String {
+        let Some(validity) = scd2_columns(columns) else {
+            return format!(
+                "-- {} is SCD Type 2 but has no valid_from/valid_to columns; SCD2 templates are not generated\n",
+                table.name
+            );
+        };
+        let is_validity = |c: &Column| {
+            c.name == validity.valid_from.name
+                || c.name == validity.valid_to.name
+                || validity.is_current.is_some_and(|v| v.name == c.name)
+        };
+        // Business key: the secondary key, or the primary key if there is none
+        let mut business_key: Vec<&Column> = columns
+            .iter()
+            .copied()
+            .filter(|c| c.secondary_key && !is_validity(c))
+            .collect();
+        if business_key.is_empty() {
+            business_key = columns
+                .iter()
+                .copied()
+                .filter(|c| c.primary_key && !is_validity(c))
+                .collect();
+        }
+        if business_key.is_empty() {
+            return format!(
+                "-- {} is SCD Type 2 but has no business key; SCD2 templates are not generated\n",
+                table.name
+            );
+        }
+        // A surrogate primary key is generated by the table, not bound
+        let data: Vec<&Column> = columns
+            .iter()
+            .copied()
+            .filter(|c| {
+                !is_validity(c)
+                    && (!c.primary_key || business_key.iter().any(|k| std::ptr::eq(*k, *c)))
+            })
+            .collect();
+        let tracked: Vec<&Column> = data
+            .iter()
+            .copied()
+            .filter(|c| !business_key.iter().any(|k| std::ptr::eq(*k, *c)))
+            .collect();
+
+        let quote = |c: &Column| dialect.quote(&c.name);
+        let current = |alias: &str| match validity.is_current {
+            Some(flag) => format!("{}.{} = {}", alias, quote(flag), dialect.bool_literal(true)),
+            None => format!("{}.{} IS NULL", alias, quote(validity.valid_to)),
+        };
+        let mut insert_columns: Vec<String> = data.iter().map(|c| quote(c)).collect();
+        insert_columns.push(quote(validity.valid_from));
+        insert_columns.push(quote(validity.valid_to));
+        insert_columns.extend(validity.is_current.map(quote));
+        let as_of = validity.valid_from.data_type.as_str();
+        let close = |as_of: &str| {
+            let mut sets = vec![format!("{} = {}", quote(validity.valid_to), as_of)];
+            sets.extend(
+                validity
+                    .is_current
+                    .map(|flag| format!("{} = {}", quote(flag), dialect.bool_literal(false))),
+            );
+            sets.join(", ")
+        };
+        let new_version = |as_of: String| {
+            [as_of, "NULL".to_string()]
+                .into_iter()
+                .chain(
+                    validity
+                        .is_current
+                        .map(|_| dialect.bool_literal(true).to_string()),
+                )
+                .collect::<Vec<_>>()
+        };
+
+        let mut params = Params::new(dialect);
+        let first = if dialect == Dialect::MySql {
+            // MySQL has no MERGE, so close changed rows with an UPDATE
+            let sets = close(&params.bind("as_of", as_of));
+            let keys: Vec<String> = business_key
+                .iter()
+                .map(|c| format!("target.{} = {}", quote(c), params.column(c)))
+                .collect();
+            let changed: Vec<String> = tracked
+                .iter()
+                .map(|c| dialect.distinct(&format!("target.{}", quote(c)), &params.column(c)))
+                .collect();
+            let changed = if changed.is_empty() {
+                dialect.bool_literal(false).to_string()
+            } else {
+                changed.join("\n    OR ")
+            };
+            let sql = format!(
+                "UPDATE {} AS target\nSET {}\nWHERE {} AND {}\n  AND ({});\n",
+                name,
+                sets,
+                keys.join(" AND "),
+                current("target"),
+                changed
+            );
+            params.statement(&format!("scd2_close_{}", slug), &sql)
+        } else {
+            let source: Vec<String> = data
+                .iter()
+                .map(|c| format!("{} AS {}", params.column(c), quote(c)))
+                .collect();
+            let on: Vec<String> = business_key
+                .iter()
+                .map(|c| format!("target.{} = source.{}", quote(c), quote(c)))
+                .collect();
+            let changed: Vec<String> = tracked
+                .iter()
+                .map(|c| {
+                    dialect.distinct(
+                        &format!("target.{}", quote(c)),
+                        &format!("source.{}", quote(c)),
+                    )
+                })
+                .collect();
+            let changed = if changed.is_empty() {
+                dialect.bool_literal(false).to_string()
+            } else {
+                changed.join("\n    OR ")
+            };
+            let as_of = params.bind("as_of", as_of);
+            let values: Vec<String> = data
+                .iter()
+                .map(|c| format!("source.{}", quote(c)))
+                .chain(new_version(as_of.clone()))
+                .collect();
+            let sql = format!(
+                "MERGE INTO {} AS target\nUSING (SELECT {}) AS source\nON {} AND {}\nWHEN MATCHED AND (\n    {}\n) THEN UPDATE SET {}\nWHEN NOT MATCHED THEN INSERT ({})\n    VALUES ({});\n",
+                name,
+                source.join(", "),
+                on.join(" AND "),
+                current("target"),
+                changed,
+                close(&as_of),
+                insert_columns.join(", "),
+                values.join(", ")
+            );
+            params.statement(&format!("scd2_merge_{}", slug), &sql)
+        };
+
+        let mut params = Params::new(dialect);
+        let mut values: Vec<String> = data.iter().map(|c| params.column(c)).collect();
+        values.extend(new_version(params.bind("as_of", as_of)));
+        let keys: Vec<String> = business_key
+            .iter()
+            .map(|c| format!("current_row.{} = {}", quote(c), params.column(c)))
+            .collect();
+        let sql = format!(
+            "INSERT INTO {} ({})\nSELECT {}\nWHERE NOT EXISTS (\n    SELECT 1 FROM {} AS current_row\n    WHERE {} AND {}\n);\n",
+            name,
+            insert_columns.join(", "),
+            values.join(", "),
+            name,
+            keys.join(" AND "),
+            current("current_row")
+        );
+        let second = params.statement(&format!("scd2_insert_{}", slug), &sql);
+
+        format!("{}\n{}", first, second)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, data_type: &str, primary_key: bool) -> Column {
+        let mut column = Column::new(name.to_string(), data_type.to_string());
+        column.primary_key = primary_key;
+        column
+    }
+
+    #[test]
+    fn test_renders_crud_and_scd2_templates() {
+        let mut customers = Table::new(
+            "customers".to_string(),
+            vec![column("id", "INT", true), column("email", "TEXT", false)],
+        );
+        customers.schema_name = Some("crm".to_string());
+        let mut customer_id = column("customer_id", "INT", false);
+        customer_id.secondary_key = true;
+        let mut history = Table::new(
+            "customer_history".to_string(),
+            vec![
+                column("sk", "BIGINT", true),
+                customer_id,
+                column("email", "TEXT", false),
+                column("valid_from", "TIMESTAMP", false),
+                column("valid_to", "TIMESTAMP", false),
+            ],
+        );
+        history.scd_pattern = Some(SCDPattern::Type2);
+        let mut model = DataModel::new("crm".to_string(), String::new(), String::new());
+        model.tables = vec![customers, history];
+
+        let postgres = SqlQueriesExporter::export_model(&model, None, Some("postgres"));
+        assert!(postgres.contains(
+            "-- name: upsert_customers\n-- params: id INT, email TEXT\nINSERT INTO \"crm\".\"customers\" (\"id\", \"email\")\nVALUES ($1, $2)\nON CONFLICT (\"id\") DO UPDATE SET\n    \"email\" = EXCLUDED.\"email\";"
+        ));
+        assert!(postgres.contains("WHERE \"id\" = $1;"));
+        // The surrogate key is not bound; the business key matches the current version
+        assert!(postgres.contains(
+            "-- name: scd2_merge_customer_history\n-- params: customer_id INT, email TEXT, as_of TIMESTAMP\n"
+        ));
+        assert!(postgres.contains(
+            "ON target.\"customer_id\" = source.\"customer_id\" AND target.\"valid_to\" IS NULL"
+        ));
+        assert!(postgres.contains("SELECT $1, $2, $3, NULL\nWHERE NOT EXISTS"));
+
+        let sqlserver = SqlQueriesExporter::export_model(&model, None, Some("sqlserver"));
+        assert!(sqlserver.contains("USING (SELECT @id AS [id], @email AS [email]) AS source"));
+
+        let mysql = SqlQueriesExporter::export_model(&model, None, Some("mysql"));
+        assert!(mysql.contains(
+            "-- name: scd2_close_customer_history\n-- params: as_of TIMESTAMP, customer_id INT, email TEXT\n"
+        ));
+    }
+}
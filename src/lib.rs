@@ -13,6 +13,9 @@ pub use api::routes;
 pub use api::services;
 pub use api::storage;
 
+// Request/response DTOs shared with the SDK
+pub use data_modelling_api_types as api_types;
+
 // DrawIO module for DrawIO XML handling
 pub mod drawio;
 